@@ -0,0 +1,132 @@
+"""Python side of the Wavelength subprocess plugin protocol.
+
+Plugins exchange JSON-RPC 2.0 messages with the decoder over stdin/stdout, one message per line.
+Subclass ``Plugin``, implement ``analyze`` and call ``serve``:
+
+    from wavelength_plugin import Plugin, serve
+
+    class MyPlugin(Plugin):
+        name = "my-plugin"
+        version = "0.1.0"
+
+        def analyze(self, repo_path, progress):
+            progress("Scanning manifests", 10)
+            return {"elements": [], "relationships": [], "services": [], "findings": []}
+
+    if __name__ == "__main__":
+        serve(MyPlugin())
+
+Plugins declaring the ``post_analysis`` hook also get ``enrich`` with the stored analysis and may
+return ``{"nodes": [], "edges": [], "annotations": []}`` to add to its graph. Plugins declaring
+``report_section`` get ``report_sections`` and return sections for the HTML report.
+
+Running this file directly wraps a detector class written for the original bridge:
+
+    python3 wavelength_plugin.py --legacy webm_asset_plugin.detector:WebMethodsAssetDetector
+"""
+
+import argparse
+import importlib
+import json
+import sys
+import traceback
+
+PROTOCOL_VERSION = "1.0"
+
+
+class Plugin:
+    name = "python-plugin"
+    version = None
+    capabilities = ["analyze", "enrich", "report", "progress", "log"]
+
+    def analyze(self, repo_path, progress):
+        return {}
+
+    def enrich(self, summary, progress):
+        return {}
+
+    def report_sections(self, summary, progress):
+        """A list of ``{"title", "data", "template", "format"}``; see the README"""
+        return []
+
+
+def serve(plugin):
+    out = sys.stdout
+    # Anything the plugin prints must not end up in the protocol stream
+    sys.stdout = sys.stderr
+
+    def send(message):
+        message["jsonrpc"] = "2.0"
+        out.write(json.dumps(message) + "\n")
+        out.flush()
+
+    def progress(message, percent=None):
+        send({"method": "progress", "params": {"message": message, "percent": percent}})
+
+    for line in sys.stdin:
+        line = line.strip()
+        if not line:
+            continue
+        try:
+            request = json.loads(line)
+        except ValueError:
+            send({"id": None, "error": {"code": -32700, "message": "Parse error"}})
+            continue
+
+        method = request.get("method")
+        request_id = request.get("id")
+        params = request.get("params") or {}
+        if method == "exit":
+            return
+
+        try:
+            if method == "initialize":
+                offered = set(params.get("capabilities", []))
+                result = {
+                    "name": plugin.name,
+                    "version": plugin.version,
+                    "protocol_version": PROTOCOL_VERSION,
+                    "capabilities": [c for c in plugin.capabilities if c in offered],
+                }
+            elif method == "analyze":
+                result = plugin.analyze(params["repo_path"], progress)
+            elif method == "enrich":
+                result = plugin.enrich(params["summary"], progress)
+            elif method == "report_sections":
+                result = {"sections": plugin.report_sections(params["summary"], progress)}
+            elif method == "shutdown":
+                result = None
+            else:
+                send({"id": request_id, "error": {"code": -32601, "message": "Method not found: %s" % method}})
+                continue
+        except Exception as e:
+            traceback.print_exc(file=sys.stderr)
+            send({"id": request_id, "error": {"code": -32000, "message": str(e)}})
+            continue
+
+        if request_id is not None:
+            send({"id": request_id, "result": result})
+
+
+class LegacyDetectorPlugin(Plugin):
+    """Adapts a detector whose ``detect_assets(path).to_dict()`` carries a ``decoder_format`` section"""
+
+    def __init__(self, target):
+        module_name, class_name = target.split(":")
+        self.name = module_name.split(".")[0]
+        self.detector = getattr(importlib.import_module(module_name), class_name)()
+
+    def analyze(self, repo_path, progress):
+        progress("Detecting assets")
+        decoder_format = self.detector.detect_assets(repo_path).to_dict().get("decoder_format", {})
+        return {
+            "elements": decoder_format.get("code_elements", []),
+            "relationships": decoder_format.get("code_relationships", []),
+        }
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--legacy", metavar="MODULE:CLASS", required=True, help="detector class to wrap")
+    args = parser.parse_args()
+    serve(LegacyDetectorPlugin(args.legacy))
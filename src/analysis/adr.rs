@@ -0,0 +1,252 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use crate::analysis::documentation::{DocumentationFile, DocumentationType};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AdrFormat {
+    Nygard, // "## Status" / "## Context" / "## Decision" / "## Consequences" sections
+    Madr,   // Markdown ADR: "* Status:" bullets or front matter, "## Decision Outcome"
+}
+
+/// A component an ADR mentions by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionLink {
+    pub component_type: String, // "code_element" or "service"
+    pub component_id: String,
+    pub name: String,
+}
+
+/// Something an ADR can be linked to
+#[derive(Debug, Clone)]
+pub struct DecisionComponent {
+    pub component_type: String,
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureDecision {
+    pub number: Option<u32>,
+    pub title: String,
+    pub status: Option<String>,
+    pub superseded_by: Option<String>,
+    pub date: Option<String>,
+    pub decision: Option<String>,
+    pub file_path: String,
+    pub format: AdrFormat,
+    pub mentions: Vec<DecisionLink>,
+}
+
+/// Directory names that conventionally hold decision records
+const ADR_DIRS: &[&str] = &["adr", "adrs", "decisions", "architecture-decisions", "decision-records", "architecture_decisions"];
+
+/// True for files under an ADR directory such as `docs/adr/0001-use-postgres.md`
+pub fn is_adr_path(path: &Path) -> bool {
+    let path_str = path.to_string_lossy().to_lowercase().replace('\\', "/");
+    path_str.split('/').rev().skip(1).any(|dir| ADR_DIRS.contains(&dir))
+}
+
+/// Content check for ADRs kept outside an ADR directory
+pub fn looks_like_adr(content: &str) -> bool {
+    let lower = content.to_lowercase();
+    (has_section(&lower, "status") && (has_section(&lower, "decision") || has_section(&lower, "decision outcome")))
+        || (lower.contains("## decision outcome") && lower.contains("## considered options"))
+}
+
+fn has_section(lower: &str, name: &str) -> bool {
+    lower.lines().any(|l| {
+        let trimmed = l.trim_start_matches('#').trim();
+        l.starts_with("##") && trimmed == name
+    })
+}
+
+/// Parse status, date, decision and number out of an ADR in either Nygard or MADR style
+pub fn parse_adr(content: &str, file_path: &str) -> Option<ArchitectureDecision> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (front_matter, body_start) = front_matter(&lines);
+
+    let heading = lines[body_start..].iter().find(|l| l.starts_with("# "))?;
+    let number_prefix = Regex::new(r"^(?:ADR[-\s]?)?(\d+)[.:\s-]+\s*").unwrap();
+    let raw_title = heading.trim_start_matches("# ").trim();
+    let title = number_prefix.replace(raw_title, "").trim().to_string();
+
+    let file_name = Path::new(file_path).file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let number = Regex::new(r"^(\d+)").unwrap()
+        .captures(file_name)
+        .or_else(|| number_prefix.captures(raw_title))
+        .and_then(|c| c[1].parse().ok());
+
+    // "* Status: accepted" / "Status: Accepted" / front matter "status: accepted"
+    let field = |name: &str| -> Option<String> {
+        let pattern = Regex::new(&format!(r"(?i)^\s*(?:[*-]\s*)?\**{}\**\s*:\s*(.+)$", name)).unwrap();
+        front_matter.iter()
+            .chain(lines[body_start..].iter().take(30))
+            .find_map(|l| pattern.captures(l).map(|c| c[1].trim().trim_matches('"').to_string()))
+            .filter(|v| !v.is_empty())
+    };
+
+    let status_section = section(&lines[body_start..], &["status"]);
+    let raw_status = field("status").or_else(|| status_section.clone());
+    let format = if field("status").is_some() || has_section(&content.to_lowercase(), "decision outcome") {
+        AdrFormat::Madr
+    } else {
+        AdrFormat::Nygard
+    };
+
+    let (status, superseded_by) = match raw_status {
+        Some(raw) => {
+            let lower = raw.to_lowercase();
+            let status = ["proposed", "accepted", "deprecated", "superseded", "rejected", "draft"].iter()
+                .find(|s| lower.contains(*s))
+                .map(|s| s.to_string())
+                .or_else(|| lower.split_whitespace().next().map(|s| s.trim_matches(|c: char| !c.is_alphanumeric()).to_string()));
+            let superseded_by = Regex::new(r"(?i)superseded\s+by\s+\[?([^\]\n(]+)").unwrap()
+                .captures(&raw)
+                .map(|c| c[1].trim().to_string());
+            (status, superseded_by)
+        }
+        None => (None, None),
+    };
+
+    let date = field("date")
+        .or_else(|| section(&lines[body_start..], &["date"]))
+        .or_else(|| {
+            Regex::new(r"\b(\d{4}-\d{2}-\d{2})\b").unwrap()
+                .captures(&lines[body_start..].iter().take(15).cloned().collect::<Vec<_>>().join("\n"))
+                .map(|c| c[1].to_string())
+        });
+
+    let decision = section(&lines[body_start..], &["decision", "decision outcome"])
+        .map(|d| if d.chars().count() > 500 { format!("{}…", d.chars().take(500).collect::<String>()) } else { d });
+
+    Some(ArchitectureDecision {
+        number,
+        title,
+        status,
+        superseded_by,
+        date,
+        decision,
+        file_path: file_path.to_string(),
+        format,
+        mentions: Vec::new(),
+    })
+}
+
+/// Reads the ADRs found during documentation indexing and links each to the components it mentions
+pub struct AdrExtractor;
+
+impl AdrExtractor {
+    pub fn new() -> Self {
+        AdrExtractor
+    }
+
+    pub fn extract(&self, repo_path: &Path, docs: &[DocumentationFile], components: &[DecisionComponent]) -> Vec<ArchitectureDecision> {
+        let mut decisions = Vec::new();
+        for doc in docs.iter().filter(|d| d.doc_type == DocumentationType::Adr) {
+            let content = match std::fs::read_to_string(repo_path.join(&doc.file_path)) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            if let Some(mut decision) = parse_adr(&content, &doc.file_path) {
+                decision.mentions = link_components(&content, components);
+                decisions.push(decision);
+            }
+        }
+        decisions.sort_by(|a, b| a.number.cmp(&b.number).then_with(|| a.file_path.cmp(&b.file_path)));
+        decisions
+    }
+}
+
+/// Components named in the text. Code names match case-sensitively as whole words (or in backticks);
+/// service names match case-insensitively. Very short or generic names are ignored.
+fn link_components(content: &str, components: &[DecisionComponent]) -> Vec<DecisionLink> {
+    const GENERIC: &[&str] = &["main", "index", "app", "utils", "config", "test", "tests", "init", "__init__", "lib", "mod", "api", "server", "client"];
+    let lower = content.to_lowercase();
+    let words: HashSet<&str> = content.split(|c: char| !(c.is_alphanumeric() || c == '_')).filter(|w| !w.is_empty()).collect();
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for component in components {
+        let name = component.name.trim();
+        if name.len() < 4 || GENERIC.contains(&name.to_lowercase().as_str()) {
+            continue;
+        }
+        let mentioned = if component.component_type == "service" {
+            let pattern = format!(r"\b{}\b", regex::escape(&name.to_lowercase()));
+            Regex::new(&pattern).map(|re| re.is_match(&lower)).unwrap_or(false)
+        } else {
+            words.contains(name) || content.contains(&format!("`{}`", name))
+        };
+        if mentioned && seen.insert((component.component_type.clone(), component.id.clone())) {
+            links.push(DecisionLink {
+                component_type: component.component_type.clone(),
+                component_id: component.id.clone(),
+                name: name.to_string(),
+            });
+        }
+    }
+    links
+}
+
+/// YAML front matter lines and the index where the body starts
+fn front_matter<'a>(lines: &[&'a str]) -> (Vec<&'a str>, usize) {
+    if lines.first().map(|l| l.trim()) == Some("---") {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim() == "---") {
+            return (lines[1..end + 1].to_vec(), end + 2);
+        }
+    }
+    (Vec::new(), 0)
+}
+
+/// First paragraph of the first `##` section whose heading is one of `names`
+fn section(lines: &[&str], names: &[&str]) -> Option<String> {
+    let start = lines.iter().position(|l| {
+        l.starts_with("##") && names.contains(&l.trim_start_matches('#').trim().to_lowercase().as_str())
+    })?;
+    let paragraph: Vec<&str> = lines[start + 1..].iter()
+        .take_while(|l| !l.starts_with('#'))
+        .skip_while(|l| l.trim().is_empty())
+        .take_while(|l| !l.trim().is_empty())
+        .map(|l| l.trim())
+        .collect();
+    if paragraph.is_empty() {
+        None
+    } else {
+        Some(paragraph.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nygard_and_madr_records() {
+        let nygard = "# 3. Use PostgreSQL for orders\n\nDate: 2023-04-01\n\n## Status\n\nSuperseded by [ADR-0007](0007-move-to-aurora.md)\n\n## Context\n\nWe need durable storage.\n\n## Decision\n\nThe `OrderRepository` will use PostgreSQL.\n\n## Consequences\n\nMigrations are needed.\n";
+        let adr = parse_adr(nygard, "docs/adr/0003-use-postgresql.md").unwrap();
+        assert_eq!(adr.number, Some(3));
+        assert_eq!(adr.title, "Use PostgreSQL for orders");
+        assert_eq!(adr.status.as_deref(), Some("superseded"));
+        assert_eq!(adr.superseded_by.as_deref(), Some("ADR-0007"));
+        assert_eq!(adr.date.as_deref(), Some("2023-04-01"));
+        assert_eq!(adr.decision.as_deref(), Some("The `OrderRepository` will use PostgreSQL."));
+        assert_eq!(adr.format, AdrFormat::Nygard);
+
+        let madr = "---\nstatus: accepted\ndate: 2024-01-15\n---\n# Use Redis for sessions\n\n## Considered Options\n\n* Redis\n* Memcached\n\n## Decision Outcome\n\nChosen option: Redis, because SessionStore needs TTLs.\n";
+        let adr = parse_adr(madr, "docs/decisions/use-redis.md").unwrap();
+        assert_eq!(adr.status.as_deref(), Some("accepted"));
+        assert_eq!(adr.date.as_deref(), Some("2024-01-15"));
+        assert_eq!(adr.format, AdrFormat::Madr);
+        assert!(looks_like_adr(madr));
+
+        let components = vec![
+            DecisionComponent { component_type: "code_element".to_string(), id: "e1".to_string(), name: "SessionStore".to_string() },
+            DecisionComponent { component_type: "service".to_string(), id: "s1".to_string(), name: "Redis".to_string() },
+            DecisionComponent { component_type: "code_element".to_string(), id: "e2".to_string(), name: "Session".to_string() },
+        ];
+        let links = link_components(madr, &components);
+        assert_eq!(links.iter().map(|l| l.component_id.as_str()).collect::<Vec<_>>(), vec!["e1", "s1"]);
+    }
+}
@@ -0,0 +1,387 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::analysis::layering::LayerAssignment;
+use crate::analysis::utils::{glob_to_regex, resolve_local_imports};
+use crate::analysis::{ArchitectureFinding, FindingSeverity, Layer};
+
+/// Rule files looked up in the analyzed repository, first match wins
+pub const RULE_FILES: &[&str] = &[
+    ".wavelength/architecture.yml",
+    ".wavelength/architecture.yaml",
+    "architecture-rules.yml",
+    "architecture-rules.yaml",
+    "architecture-rules.json",
+];
+
+/// User-declared dependency constraints, e.g.
+///
+/// ```yaml
+/// modules:
+///   web: "src/web/**"
+///   domain: ["src/domain/**", "src/model/**"]
+/// rules:
+///   - name: domain-is-pure
+///     from: domain
+///     forbid: [web, adapter]
+///   - name: web-goes-through-services
+///     from: web
+///     allow: [service, domain, "src/shared/**"]
+///     severity: medium
+/// hexagonal:
+///   domain: "src/domain/**"
+///   application: "src/app/**"
+///   ports: "src/ports/**"
+///   adapters: ["src/adapters/**", "src/web/**"]
+/// ```
+///
+/// Selectors in `from`/`allow`/`forbid` are declared module names, built-in layer names
+/// (controller, service, repository, domain, port, adapter) or path globs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchitectureRuleSet {
+    #[serde(default)]
+    pub modules: BTreeMap<String, ModulePatterns>,
+    #[serde(default)]
+    pub rules: Vec<ArchitectureRule>,
+    #[serde(default)]
+    pub hexagonal: Option<HexagonalLayers>,
+}
+
+/// Rings of a ports-and-adapters project, innermost first. Dependencies may only point inward:
+/// adapters may use ports, the application and the domain; the application and its ports may
+/// use the domain; the domain uses nothing outside itself. A ring left out falls back to the
+/// built-in layer of the same name (`service` for the application).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HexagonalLayers {
+    #[serde(default)]
+    pub domain: Option<ModulePatterns>,
+    #[serde(default)]
+    pub application: Option<ModulePatterns>,
+    #[serde(default)]
+    pub ports: Option<ModulePatterns>,
+    #[serde(default)]
+    pub adapters: Option<ModulePatterns>,
+    #[serde(default)]
+    pub severity: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ModulePatterns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ModulePatterns {
+    pub fn globs(&self) -> Vec<String> {
+        match self {
+            ModulePatterns::One(p) => vec![p.clone()],
+            ModulePatterns::Many(p) => p.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureRule {
+    pub name: String,
+    pub from: String,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub forbid: Vec<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ArchitectureRuleSet {
+    /// Load the repository's rule file, if it has one
+    pub fn load(repo_path: &Path) -> Result<Option<(String, ArchitectureRuleSet)>> {
+        for file in RULE_FILES {
+            let path = repo_path.join(file);
+            if !path.is_file() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)?;
+            let rule_set = Self::parse(&content, file).with_context(|| format!("Invalid architecture rules in {}", file))?;
+            return Ok(Some((file.to_string(), rule_set)));
+        }
+        Ok(None)
+    }
+
+    pub fn parse(content: &str, file_name: &str) -> Result<ArchitectureRuleSet> {
+        let rule_set: ArchitectureRuleSet = if file_name.ends_with(".json") {
+            serde_json::from_str(content)?
+        } else {
+            serde_yaml::from_str(content)?
+        };
+        rule_set.validate()?;
+        Ok(rule_set)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for rule in &self.rules {
+            if rule.allow.is_empty() && rule.forbid.is_empty() {
+                anyhow::bail!("Rule '{}' needs an 'allow' or 'forbid' list", rule.name);
+            }
+            if let Some(severity) = &rule.severity {
+                parse_severity(severity).ok_or_else(|| anyhow::anyhow!("Rule '{}' has unknown severity '{}'", rule.name, severity))?;
+            }
+            for selector in std::iter::once(&rule.from).chain(&rule.allow).chain(&rule.forbid) {
+                if !is_glob(selector) && !self.modules.contains_key(selector) && Layer::parse(selector).is_none() {
+                    anyhow::bail!("Rule '{}' references unknown module '{}'", rule.name, selector);
+                }
+            }
+        }
+        if let Some(hexagonal) = &self.hexagonal {
+            if let Some(severity) = &hexagonal.severity {
+                parse_severity(severity).ok_or_else(|| anyhow::anyhow!("Hexagonal rings have unknown severity '{}'", severity))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// (ring, distance from the core, built-in layers that stand in for it when it has no globs)
+const HEXAGONAL_RINGS: &[(&str, usize, &[Layer])] = &[
+    ("domain", 0, &[Layer::Domain]),
+    ("ports", 1, &[Layer::Port]),
+    ("application", 1, &[Layer::Service]),
+    ("adapters", 2, &[Layer::Adapter, Layer::Controller, Layer::Repository]),
+];
+
+/// Evaluates a rule set against the imports between repository files
+pub struct ArchitectureRuleEngine {
+    rule_set: ArchitectureRuleSet,
+    module_patterns: BTreeMap<String, Vec<Regex>>,
+    /// (ring name, depth, globs) in the order globs are tried
+    ring_patterns: Vec<(&'static str, usize, Vec<Regex>)>,
+}
+
+impl ArchitectureRuleEngine {
+    pub fn new(rule_set: ArchitectureRuleSet) -> Self {
+        let module_patterns = rule_set.modules.iter()
+            .map(|(name, patterns)| (name.clone(), patterns.globs().iter().map(|g| glob_to_regex(g)).collect()))
+            .collect();
+        // Ports sit on the application ring but are listed first so a `src/app/ports/**` glob wins
+        let ring_patterns = rule_set.hexagonal.iter()
+            .flat_map(|h| [("ports", 1, &h.ports), ("domain", 0, &h.domain), ("application", 1, &h.application), ("adapters", 2, &h.adapters)])
+            .filter_map(|(name, depth, patterns)| {
+                patterns.as_ref().map(|p| (name, depth, p.globs().iter().map(|g| glob_to_regex(g)).collect()))
+            })
+            .collect();
+        ArchitectureRuleEngine { rule_set, module_patterns, ring_patterns }
+    }
+
+    pub fn evaluate(&self, repo_path: &Path, files: &[&str], layers: &[LayerAssignment]) -> Vec<ArchitectureFinding> {
+        let file_layers: BTreeMap<&str, Layer> = layers.iter().map(|a| (a.file_path.as_str(), a.layer)).collect();
+        let mut findings = Vec::new();
+        let mut imports_cache: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+
+        for rule in &self.rule_set.rules {
+            let severity = rule.severity.as_deref().and_then(parse_severity).unwrap_or(FindingSeverity::High);
+            for file in files.iter().filter(|f| self.matches(&rule.from, f, &file_layers)) {
+                let imports = imports_cache.entry(file).or_insert_with(|| {
+                    std::fs::read_to_string(repo_path.join(file))
+                        .map(|content| resolve_local_imports(&content, file, files))
+                        .unwrap_or_default()
+                });
+                for (line_number, target) in imports.iter() {
+                    if self.matches(&rule.from, target, &file_layers) {
+                        continue;
+                    }
+                    let forbidden = rule.forbid.iter().find(|s| self.matches(s, target, &file_layers));
+                    let not_allowed = !rule.allow.is_empty() && !rule.allow.iter().any(|s| self.matches(s, target, &file_layers));
+                    if forbidden.is_none() && !not_allowed {
+                        continue;
+                    }
+
+                    let target_module = forbidden.cloned().or_else(|| self.module_of(target, &file_layers));
+                    let message = match (&rule.description, forbidden) {
+                        (Some(description), _) => description.clone(),
+                        (None, Some(selector)) => format!("'{}' must not depend on '{}'", rule.from, selector),
+                        (None, None) => format!("'{}' may only depend on {}", rule.from, rule.allow.join(", ")),
+                    };
+                    findings.push(ArchitectureFinding {
+                        rule: rule.name.clone(),
+                        severity: severity.clone(),
+                        source_file: file.to_string(),
+                        source_layer: Some(rule.from.clone()),
+                        target_file: target.to_string(),
+                        target_layer: target_module,
+                        line_number: *line_number,
+                        message,
+                    });
+                }
+            }
+        }
+
+        if let Some(hexagonal) = &self.rule_set.hexagonal {
+            let severity = hexagonal.severity.as_deref().and_then(parse_severity).unwrap_or(FindingSeverity::High);
+            for file in files {
+                let (source_ring, source_depth) = match self.ring_of(file, &file_layers) {
+                    Some(r) => r,
+                    None => continue,
+                };
+                if source_depth == 2 {
+                    continue;
+                }
+                let imports = imports_cache.entry(file).or_insert_with(|| {
+                    std::fs::read_to_string(repo_path.join(file))
+                        .map(|content| resolve_local_imports(&content, file, files))
+                        .unwrap_or_default()
+                });
+                for (line_number, target) in imports.iter() {
+                    let (target_ring, target_depth) = match self.ring_of(target, &file_layers) {
+                        Some(r) => r,
+                        None => continue,
+                    };
+                    if target_depth <= source_depth {
+                        continue;
+                    }
+                    findings.push(ArchitectureFinding {
+                        rule: "hexagonal-outward-dependency".to_string(),
+                        severity: severity.clone(),
+                        source_file: file.to_string(),
+                        source_layer: Some(source_ring.to_string()),
+                        target_file: target.to_string(),
+                        target_layer: Some(target_ring.to_string()),
+                        line_number: *line_number,
+                        message: format!(
+                            "{}:{} imports {} from the {} ring; {} code may only depend inward",
+                            file, line_number, target, target_ring, source_ring
+                        ),
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// Hexagonal ring of a file and its distance from the core, from the configured globs or,
+    /// for rings without globs, the file's built-in layer
+    fn ring_of(&self, file: &str, file_layers: &BTreeMap<&str, Layer>) -> Option<(&'static str, usize)> {
+        if let Some((name, depth, _)) = self.ring_patterns.iter().find(|(_, _, patterns)| patterns.iter().any(|p| p.is_match(file))) {
+            return Some((name, *depth));
+        }
+        let layer = file_layers.get(file)?;
+        HEXAGONAL_RINGS.iter()
+            .find(|(name, _, layers)| layers.contains(layer) && !self.ring_patterns.iter().any(|(configured, ..)| configured == name))
+            .map(|(name, depth, _)| (*name, *depth))
+    }
+
+    fn matches(&self, selector: &str, file: &str, file_layers: &BTreeMap<&str, Layer>) -> bool {
+        if let Some(patterns) = self.module_patterns.get(selector) {
+            patterns.iter().any(|p| p.is_match(file))
+        } else if let Some(layer) = Layer::parse(selector) {
+            file_layers.get(file) == Some(&layer)
+        } else {
+            glob_to_regex(selector).is_match(file)
+        }
+    }
+
+    fn module_of(&self, file: &str, file_layers: &BTreeMap<&str, Layer>) -> Option<String> {
+        self.module_patterns.iter()
+            .find(|(_, patterns)| patterns.iter().any(|p| p.is_match(file)))
+            .map(|(name, _)| name.clone())
+            .or_else(|| file_layers.get(file).map(|l| l.as_str().to_string()))
+    }
+}
+
+fn parse_severity(value: &str) -> Option<FindingSeverity> {
+    match value.to_lowercase().as_str() {
+        "low" => Some(FindingSeverity::Low),
+        "medium" => Some(FindingSeverity::Medium),
+        "high" => Some(FindingSeverity::High),
+        _ => None,
+    }
+}
+
+fn is_glob(selector: &str) -> bool {
+    selector.contains('/') || selector.contains('*') || selector.contains('?')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_reports_forbidden_and_unlisted_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("src/domain/order.ts", "import { db } from \"../infra/db\";\n"),
+            ("src/web/orders.ts", "import { Order } from \"../domain/order\";\nimport { log } from \"../shared/log\";\nimport { db } from \"../infra/db\";\n"),
+            ("src/infra/db.ts", ""),
+            ("src/shared/log.ts", ""),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let rules = ArchitectureRuleSet::parse(r#"
+modules:
+  domain: "src/domain/**"
+  web: src/web
+rules:
+  - name: domain-is-pure
+    from: domain
+    forbid: ["src/infra/**"]
+  - name: web-layering
+    from: web
+    allow: [domain, "src/shared/*.ts"]
+    severity: medium
+"#, "architecture-rules.yml").unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|(p, _)| *p).collect();
+        let findings = ArchitectureRuleEngine::new(rules).evaluate(temp_dir.path(), &paths, &[]);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule, "domain-is-pure");
+        assert_eq!(findings[0].source_file, "src/domain/order.ts");
+        assert_eq!(findings[0].line_number, 1);
+        assert_eq!(findings[1].rule, "web-layering");
+        assert_eq!(findings[1].target_file, "src/infra/db.ts");
+        assert_eq!(findings[1].line_number, 3);
+        assert_eq!(findings[1].severity, FindingSeverity::Medium);
+
+        assert!(ArchitectureRuleSet::parse("rules:\n  - name: x\n    from: nowhere\n    forbid: [web]\n", "a.yml").is_err());
+    }
+
+    #[test]
+    fn test_reports_outward_hexagonal_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("src/core/order.ts", "import { save } from \"../app/checkout\";\n"),
+            ("src/app/checkout.ts", "import { Order } from \"../core/order\";\nimport { pg } from \"../infra/pg\";\n"),
+            ("src/app/ports/store.ts", "import { Order } from \"../../core/order\";\n"),
+            ("src/infra/pg.ts", "import { Store } from \"../app/ports/store\";\n"),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let rules = ArchitectureRuleSet::parse(r#"
+hexagonal:
+  domain: "src/core/**"
+  application: "src/app/**"
+  ports: "src/app/ports/**"
+  adapters: "src/infra/**"
+"#, "architecture-rules.yml").unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|(p, _)| *p).collect();
+        let findings = ArchitectureRuleEngine::new(rules).evaluate(temp_dir.path(), &paths, &[]);
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.rule == "hexagonal-outward-dependency"));
+        let edges: Vec<(&str, &str, usize)> = findings.iter()
+            .map(|f| (f.source_file.as_str(), f.target_file.as_str(), f.line_number))
+            .collect();
+        assert!(edges.contains(&("src/core/order.ts", "src/app/checkout.ts", 1)));
+        assert!(edges.contains(&("src/app/checkout.ts", "src/infra/pg.ts", 2)));
+        let to_adapter = findings.iter().find(|f| f.target_file == "src/infra/pg.ts").unwrap();
+        assert_eq!(to_adapter.source_layer.as_deref(), Some("application"));
+        assert_eq!(to_adapter.target_layer.as_deref(), Some("adapters"));
+    }
+}
@@ -0,0 +1,381 @@
+use anyhow::Result;
+use git2::Repository;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::FindingSeverity;
+use crate::analysis::repo_path::RepoPath;
+
+/// Files at least this large are reported even when they are not a recognized artifact
+pub const LARGE_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// GitHub warns above 50 MB and rejects pushes above 100 MB
+pub const HUGE_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Binary,         // Native executables, shared/static libraries, object files and WebAssembly
+    Bytecode,       // `.class`, `.pyc`
+    JavaArchive,    // `.jar`, `.war`, `.ear`, `.aar`
+    PythonPackage,  // `.whl`, `.egg`
+    Installer,      // `.deb`, `.rpm`, `.msi`, `.dmg`, `.apk`, `.ipa`, `.nupkg`, `.gem`
+    Archive,        // `.zip`, `.tar.gz`, `.7z`, ...
+    ContainerImage, // Image referenced by a Dockerfile, compose file or manifest
+    Vendored,       // Directory of third-party code copied into the repository
+    LargeFile,      // Any other file over the size limit
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedArtifact {
+    pub kind: ArtifactKind,
+    pub name: String, // File name, image reference or vendored directory
+    pub file_path: String, // The file, the vendored directory, or the file referencing the image
+    pub size_bytes: Option<u64>, // Total size for vendored directories; None for images
+    pub sha256: Option<String>, // Content hash; digest of pinned images; hash over every file of a vendored directory
+    pub file_count: Option<usize>, // Vendored directories only
+    pub line_number: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HygieneFinding {
+    pub rule: String,
+    pub severity: FindingSeverity,
+    pub file_path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactInventory {
+    pub artifacts: Vec<DetectedArtifact>,
+    pub findings: Vec<HygieneFinding>,
+}
+
+/// Extension (longest first where they overlap) and the kind of artifact it marks
+const ARTIFACT_EXTENSIONS: &[(&str, ArtifactKind)] = &[
+    (".exe", ArtifactKind::Binary), (".dll", ArtifactKind::Binary), (".so", ArtifactKind::Binary),
+    (".dylib", ArtifactKind::Binary), (".o", ArtifactKind::Binary),
+    (".a", ArtifactKind::Binary), (".lib", ArtifactKind::Binary), (".wasm", ArtifactKind::Binary),
+    (".class", ArtifactKind::Bytecode), (".pyc", ArtifactKind::Bytecode), (".pyo", ArtifactKind::Bytecode),
+    (".jar", ArtifactKind::JavaArchive), (".war", ArtifactKind::JavaArchive), (".ear", ArtifactKind::JavaArchive), (".aar", ArtifactKind::JavaArchive),
+    (".whl", ArtifactKind::PythonPackage), (".egg", ArtifactKind::PythonPackage),
+    (".deb", ArtifactKind::Installer), (".rpm", ArtifactKind::Installer), (".msi", ArtifactKind::Installer),
+    (".dmg", ArtifactKind::Installer), (".apk", ArtifactKind::Installer), (".ipa", ArtifactKind::Installer),
+    (".nupkg", ArtifactKind::Installer), (".gem", ArtifactKind::Installer),
+    (".tar.gz", ArtifactKind::Archive), (".tgz", ArtifactKind::Archive), (".tar.bz2", ArtifactKind::Archive),
+    (".tar.xz", ArtifactKind::Archive), (".tar", ArtifactKind::Archive), (".zip", ArtifactKind::Archive),
+    (".7z", ArtifactKind::Archive), (".rar", ArtifactKind::Archive),
+];
+
+/// ELF, Mach-O (32/64-bit, both byte orders) and PE headers of extensionless executables
+const EXECUTABLE_MAGIC: &[&[u8]] = &[
+    b"\x7fELF", b"\xfe\xed\xfa\xce", b"\xfe\xed\xfa\xcf", b"\xce\xfa\xed\xfe", b"\xcf\xfa\xed\xfe", b"MZ",
+];
+
+/// Directories of copied third-party code
+pub(crate) const VENDORED_DIRS: &[&str] = &["vendor", "vendored", "third_party", "third-party", "thirdparty", "node_modules", "bower_components", "site-packages"];
+/// Vendored directories that hold installed dependencies rather than deliberately copied code
+const INSTALLED_DEPENDENCY_DIRS: &[&str] = &["node_modules", "bower_components", "site-packages"];
+/// Artifacts under these directories are test inputs
+const FIXTURE_DIRS: &[&str] = &["fixtures", "__fixtures__", "testdata", "test-data", "test_data", "resources"];
+/// Wrapper jars that build tools expect to be committed
+const EXPECTED_ARTIFACTS: &[&str] = &["gradle-wrapper.jar", "maven-wrapper.jar"];
+
+static DOCKERFILE_FROM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^\s*FROM\s+(?:--platform=\S+\s+)?(\S+)(?:\s+AS\s+(\S+))?").unwrap());
+static YAML_IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*-?\s*image:\s*["']?([\w./:@\-]+)["']?\s*$"#).unwrap());
+
+pub struct ArtifactInventoryScanner;
+
+impl ArtifactInventoryScanner {
+    pub fn new() -> Self {
+        ArtifactInventoryScanner
+    }
+
+    /// Catalog committed binaries, packages and archives, vendored directories and container image
+    /// references, with sizes and hashes, and report the ones that do not belong in a repository.
+    /// In a git checkout only tracked files count; untracked build output is ignored.
+    pub fn scan(&self, repo_path: &Path) -> Result<ArtifactInventory> {
+        let tracked = tracked_files(repo_path);
+        let mut inventory = ArtifactInventory::default();
+        let mut vendored: BTreeMap<String, (usize, u64, Sha256)> = BTreeMap::new();
+
+        for entry in WalkDir::new(repo_path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+            if let Some(tracked) = &tracked {
+                if !tracked.contains(&normalized_path) {
+                    continue;
+                }
+            }
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let segments: Vec<&str> = normalized_path.split('/').collect();
+
+            // Vendored directories are summarized as a whole instead of file by file
+            if let Some(index) = segments.iter().position(|s| VENDORED_DIRS.contains(&s.to_lowercase().as_str())) {
+                let root = segments[..=index].join("/");
+                let (count, bytes, hasher) = vendored.entry(root).or_insert_with(|| (0, 0, Sha256::new()));
+                *count += 1;
+                *bytes += size;
+                hasher.update(normalized_path.as_bytes());
+                if let Some(hash) = file_sha256(path) {
+                    hasher.update(hash.as_bytes());
+                }
+                continue;
+            }
+
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let file_name_lower = file_name.to_lowercase();
+
+            let is_yaml = file_name_lower.ends_with(".yml") || file_name_lower.ends_with(".yaml");
+            let is_dockerfile = file_name_lower == "dockerfile" || file_name_lower.starts_with("dockerfile.") || file_name_lower.ends_with(".dockerfile");
+            if is_yaml || is_dockerfile {
+                if let Ok(content) = std::fs::read_to_string(path) {
+                    inventory.artifacts.extend(image_references(&content, &normalized_path, is_yaml));
+                }
+            }
+
+            let kind = match artifact_kind(path, &file_name_lower) {
+                Some(kind) => kind,
+                None if size >= LARGE_FILE_BYTES => ArtifactKind::LargeFile,
+                None => continue,
+            };
+
+            inventory.findings.extend(hygiene_findings(kind, &normalized_path, &file_name_lower, &segments, size));
+            inventory.artifacts.push(DetectedArtifact {
+                kind,
+                name: file_name.to_string(),
+                file_path: normalized_path,
+                size_bytes: Some(size),
+                sha256: file_sha256(path),
+                file_count: None,
+                line_number: None,
+            });
+        }
+
+        for (root, (count, bytes, hasher)) in vendored {
+            let name = root.rsplit('/').next().unwrap_or(&root).to_string();
+            if INSTALLED_DEPENDENCY_DIRS.contains(&name.to_lowercase().as_str()) {
+                inventory.findings.push(HygieneFinding {
+                    rule: "committed-dependencies".to_string(),
+                    severity: FindingSeverity::High,
+                    file_path: root.clone(),
+                    message: format!("Installed dependencies ({} files, {}) are committed; install them from the lockfile instead", count, format_size(bytes)),
+                });
+            }
+            inventory.artifacts.push(DetectedArtifact {
+                kind: ArtifactKind::Vendored,
+                name,
+                file_path: root,
+                size_bytes: Some(bytes),
+                sha256: Some(hex(&hasher.finalize())),
+                file_count: Some(count),
+                line_number: None,
+            });
+        }
+
+        Ok(inventory)
+    }
+}
+
+/// Repository-relative paths in the git index; None when the path is not a git checkout
+fn tracked_files(repo_path: &Path) -> Option<HashSet<String>> {
+    let repo = Repository::open(repo_path).ok()?;
+    let index = repo.index().ok()?;
+    Some(index.iter()
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .collect())
+}
+
+fn artifact_kind(path: &Path, file_name: &str) -> Option<ArtifactKind> {
+    if let Some((_, kind)) = ARTIFACT_EXTENSIONS.iter().find(|(ext, _)| file_name.ends_with(ext)) {
+        return Some(*kind);
+    }
+    if path.extension().is_some() {
+        return None;
+    }
+    let mut header = [0u8; 4];
+    let read = std::fs::File::open(path).and_then(|mut f| f.read(&mut header)).ok()?;
+    EXECUTABLE_MAGIC.iter()
+        .any(|magic| read >= magic.len() && header.starts_with(magic))
+        .then_some(ArtifactKind::Binary)
+}
+
+fn hygiene_findings(kind: ArtifactKind, file_path: &str, file_name: &str, segments: &[&str], size: u64) -> Vec<HygieneFinding> {
+    let mut findings = Vec::new();
+    let finding = |rule: &str, severity: FindingSeverity, message: String| HygieneFinding {
+        rule: rule.to_string(),
+        severity,
+        file_path: file_path.to_string(),
+        message,
+    };
+
+    if size >= LARGE_FILE_BYTES {
+        let severity = if size >= HUGE_FILE_BYTES { FindingSeverity::High } else { FindingSeverity::Medium };
+        findings.push(finding("large-file", severity, format!("{} is committed to the repository; keep large files in Git LFS or an artifact store", format_size(size))));
+    }
+
+    let expected = EXPECTED_ARTIFACTS.contains(&file_name)
+        || segments.iter().any(|s| FIXTURE_DIRS.contains(&s.to_lowercase().as_str()));
+    if !expected {
+        match kind {
+            ArtifactKind::Binary | ArtifactKind::Bytecode => findings.push(finding("committed-binary", FindingSeverity::Medium,
+                "Compiled binary is committed to the repository; build it from source in CI instead".to_string())),
+            ArtifactKind::JavaArchive | ArtifactKind::PythonPackage | ArtifactKind::Installer | ArtifactKind::Archive => findings.push(finding("committed-package", FindingSeverity::Low,
+                "Package or archive is committed to the repository; fetch it from a registry or artifact store instead".to_string())),
+            _ => {}
+        }
+    }
+
+    findings
+}
+
+/// `FROM` lines of a Dockerfile, or `image:` keys of compose files and manifests
+pub(crate) fn image_references(content: &str, file_path: &str, is_yaml: bool) -> Vec<DetectedArtifact> {
+    let mut stages = HashSet::new();
+    let mut images = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let (image, stage) = if is_yaml {
+            match YAML_IMAGE.captures(line) {
+                Some(cap) => (cap[1].to_string(), None),
+                None => continue,
+            }
+        } else {
+            match DOCKERFILE_FROM.captures(line) {
+                Some(cap) => (cap[1].to_string(), cap.get(2).map(|s| s.as_str().to_lowercase())),
+                None => continue,
+            }
+        };
+        // Earlier build stages, `scratch` and unresolved build args are not images
+        let is_image = !stages.contains(&image.to_lowercase()) && image != "scratch" && !image.contains('$') && !image.contains("{{");
+        if let Some(stage) = stage {
+            stages.insert(stage);
+        }
+        if !is_image {
+            continue;
+        }
+        images.push(DetectedArtifact {
+            kind: ArtifactKind::ContainerImage,
+            sha256: image.split_once("@sha256:").map(|(_, digest)| digest.to_string()),
+            name: image,
+            file_path: file_path.to_string(),
+            size_bytes: None,
+            file_count: None,
+            line_number: Some(index + 1),
+        });
+    }
+    images
+}
+
+fn file_sha256(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hex(&hasher.finalize()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_inventories_artifacts_and_reports_hygiene_findings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "bin/tool", b"\x7fELF\x02\x01\x01\x00");
+        write(root, "libs/client.jar", b"PK\x03\x04");
+        write(root, "gradle/wrapper/gradle-wrapper.jar", b"PK\x03\x04");
+        write(root, "tests/fixtures/sample.zip", b"PK\x03\x04");
+        write(root, "data/dump.csv", &vec![b'x'; LARGE_FILE_BYTES as usize]);
+        write(root, "vendor/github.com/acme/lib/lib.go", b"package lib\n");
+        write(root, "vendor/github.com/acme/lib/util.go", b"package lib\n");
+        write(root, "web/node_modules/left-pad/index.js", b"module.exports = 1;\n");
+        write(root, "Dockerfile", b"FROM golang:1.22 AS build\nRUN go build\nFROM build AS test\nFROM gcr.io/distroless/static@sha256:abc123\n");
+        write(root, "docker-compose.yml", b"services:\n  db:\n    image: postgres:16\n");
+        write(root, "src/main.go", b"package main\n");
+
+        let inventory = ArtifactInventoryScanner::new().scan(temp_dir.path()).unwrap();
+        let find = |name: &str| inventory.artifacts.iter().find(|a| a.name == name).unwrap();
+
+        assert_eq!(find("tool").kind, ArtifactKind::Binary);
+        assert_eq!(find("client.jar").sha256.as_ref().map(|h| h.len()), Some(64));
+        assert_eq!(find("client.jar").sha256, find("gradle-wrapper.jar").sha256);
+        assert_eq!(find("client.jar").size_bytes, Some(4));
+        assert_eq!(find("dump.csv").kind, ArtifactKind::LargeFile);
+        let vendor = find("vendor");
+        assert_eq!((vendor.file_count, vendor.size_bytes), (Some(2), Some(24)));
+        assert_eq!(find("node_modules").file_path, "web/node_modules");
+        assert!(inventory.artifacts.iter().all(|a| a.name != "main.go" && a.name != "lib.go"));
+
+        let images: Vec<&str> = inventory.artifacts.iter()
+            .filter(|a| a.kind == ArtifactKind::ContainerImage)
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(images, vec!["golang:1.22", "gcr.io/distroless/static@sha256:abc123", "postgres:16"]);
+        assert_eq!(find("gcr.io/distroless/static@sha256:abc123").sha256.as_deref(), Some("abc123"));
+
+        let mut findings: Vec<(&str, &str)> = inventory.findings.iter()
+            .map(|f| (f.rule.as_str(), f.file_path.as_str()))
+            .collect();
+        findings.sort();
+        assert_eq!(findings, vec![
+            ("committed-binary", "bin/tool"),
+            ("committed-dependencies", "web/node_modules"),
+            ("committed-package", "libs/client.jar"),
+            ("large-file", "data/dump.csv"),
+        ]);
+    }
+
+    #[test]
+    fn test_skips_untracked_output_scripts_and_unresolved_images() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "Makefile", "build:\n\tgo build\n");
+        write(root, "scripts/deploy", "#!/bin/sh\necho deploy\n");
+        write(root, "testdata/sample.tar.gz", b"\x1f\x8b");
+        write(root, "Dockerfile", "ARG BASE=alpine:3.19\nFROM $BASE AS build\nFROM scratch\nCOPY --from=build /app /app\n");
+        write(root, "chart/templates/deployment.yaml", "containers:\n  - image: \"{{ .Values.image }}\"\n");
+        write(root, "vendor/acme/lib.go", "package acme\n");
+        write(root, "build/output.jar", b"PK\x03\x04");
+
+        let repo = Repository::init(root).unwrap();
+        let mut index = repo.index().unwrap();
+        for path in ["Makefile", "scripts/deploy", "testdata/sample.tar.gz", "Dockerfile", "chart/templates/deployment.yaml", "vendor/acme/lib.go"] {
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+
+        let inventory = ArtifactInventoryScanner::new().scan(root).unwrap();
+        let artifacts: Vec<(&str, ArtifactKind)> = inventory.artifacts.iter()
+            .map(|a| (a.file_path.as_str(), a.kind))
+            .collect();
+        // Untracked build output is ignored, and neither `$BASE`, `scratch` nor a template is an image
+        assert_eq!(artifacts, vec![
+            ("testdata/sample.tar.gz", ArtifactKind::Archive),
+            ("vendor", ArtifactKind::Vendored),
+        ]);
+        // Fixtures are expected, and copied vendor code is not an installed dependency tree
+        assert!(inventory.findings.is_empty(), "{:?}", inventory.findings);
+    }
+}
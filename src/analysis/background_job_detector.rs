@@ -0,0 +1,627 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+/// Lines searched after a job declaration for its handler, options or schedule
+const LOOKAHEAD_LINES: usize = 12;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum JobTrigger {
+    Queue,      // Runs when a message is enqueued: Celery tasks, Sidekiq/ActiveJob workers, BullMQ workers
+    Schedule,   // Runs on a cron expression or fixed interval: Celery beat, @Scheduled, Quartz, CronJobs
+    Background, // Spawned by the application itself and runs alongside it: tokio::spawn
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedBackgroundJob {
+    pub name: String,
+    pub framework: String, // e.g. "celery", "sidekiq", "bullmq", "spring", "quartz", "kubernetes", "github_actions", "cron", "tokio"
+    pub trigger: JobTrigger,
+    pub schedule: Option<String>, // Cron expression or interval, as written
+    pub queue: Option<String>,
+    pub handler: Option<String>, // Function, method or class that does the work
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+}
+
+static CELERY_TASK: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*@(?:\w+\.task|shared_task)\b").unwrap());
+static PYTHON_DEF: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:async\s+)?def\s+(\w+)").unwrap());
+static QUEUE_ARG: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bqueue\s*[=:]\s*:?['"]?([\w.\-]+)"#).unwrap());
+/// `'cleanup-every-hour': {` in a Celery beat schedule
+static BEAT_ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*['"]([^'"]+)['"]\s*:\s*(?:\{|dict\()"#).unwrap());
+static BEAT_TASK: Lazy<Regex> = Lazy::new(|| Regex::new(r#"['"]?task['"]?\s*[:=]\s*['"]([\w.]+)['"]"#).unwrap());
+static BEAT_SCHEDULE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"['"]?schedule['"]?\s*[:=]\s*(.+?),?\s*$"#).unwrap());
+static RUBY_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*class\s+([\w:]+)(?:\s*<\s*([\w:]+))?").unwrap());
+static SIDEKIQ_INCLUDE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*include\s+Sidekiq::(?:Worker|Job)\b").unwrap());
+static ACTIVE_JOB_QUEUE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*queue_as\s+:?['"]?(\w+)"#).unwrap());
+/// `new Worker('emails', sendEmail, { connection })`
+static BULL_WORKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"new\s+Worker\s*(?:<[^>]*>)?\s*\(\s*['"`]([^'"`]+)['"`]\s*,\s*(\w+)?"#).unwrap()
+});
+/// `const emailQueue = new Queue('emails')`; producers only, so they name queues rather than jobs
+static BULL_QUEUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:(\w+)\s*=\s*)?new\s+(?:Queue|Bull)\s*(?:<[^>]*>)?\s*\(\s*['"`]([^'"`]+)['"`]"#).unwrap()
+});
+/// Bull's `emailQueue.process(handler)` / `emailQueue.process('welcome', 5, handler)`
+static BULL_PROCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(\w+)\.process\s*\(\s*(?:['"`][^'"`]+['"`]\s*,\s*)?(?:\d+\s*,\s*)?(\w+)?"#).unwrap()
+});
+static BULL_ADD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(\w+)\.add\s*\(\s*['"`]([^'"`]+)['"`]"#).unwrap());
+static BULL_REPEAT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"repeat\s*:\s*\{[^}]*?\b(?:(?:cron|pattern)\s*:\s*['"`]([^'"`]+)['"`]|every\s*:\s*([\w.*\s]+))"#).unwrap()
+});
+static SPRING_SCHEDULED: Lazy<Regex> = Lazy::new(|| Regex::new(r"@Scheduled\s*\(([^)]*)\)").unwrap());
+static SPRING_SCHEDULE_ARG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(cron|fixedRate|fixedDelay|fixedRateString|fixedDelayString)\s*=\s*"?([^",)]+)"?"#).unwrap()
+});
+static JAVA_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bclass\s+(\w+)").unwrap());
+static JAVA_METHOD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:public|protected|private|static|final|synchronized)\s+)*[\w<>\[\],\s]+?\s+(\w+)\s*\(").unwrap()
+});
+static QUARTZ_JOB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bclass\s+(\w+)\s+(?:extends\s+QuartzJobBean\b|implements\s+[^{]*\b(?:Job|StatefulJob|InterruptableJob)\b)").unwrap()
+});
+static QUARTZ_NEW_JOB: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bnewJob\s*\(\s*(\w+)\.class").unwrap());
+static QUARTZ_CRON: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bcronSchedule\s*\(\s*"([^"]+)""#).unwrap());
+static TOKIO_SPAWN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\btokio::(?:task::)?spawn\s*\(\s*(?:async\s+(?:move\s+)?\{\s*)?(?:([\w:]+)\s*\()?").unwrap()
+});
+static TOKIO_INTERVAL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\binterval\s*\(\s*(?:std::time::|tokio::time::)?Duration::from_(secs|millis|mins)\s*\(\s*(\d+)").unwrap()
+});
+static RUST_FN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(\w+)").unwrap());
+/// `- cron: '0 3 * * *'` in a GitHub Actions `on.schedule`
+static WORKFLOW_CRON: Lazy<Regex> = Lazy::new(|| Regex::new(r##"^\s*-?\s*cron\s*:\s*['"]?([^'"#]+?)['"]?\s*(?:#.*)?$"##).unwrap());
+static WORKFLOW_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r##"^name\s*:\s*['"]?([^'"#]+?)['"]?\s*$"##).unwrap());
+/// A crontab line: five schedule fields (or an `@daily`-style macro) and the command
+static CRONTAB_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(@\w+|(?:[\d*/,\-]+\s+){4}[\d*/,\-A-Za-z]+)\s+(?:(?:root|www-data)\s+)?(\S.*)$").unwrap()
+});
+
+/// Nested dicts inside a beat entry that are not entries themselves
+const BEAT_ENTRY_OPTIONS: &[&str] = &["options", "kwargs", "args"];
+
+pub struct BackgroundJobDetector;
+
+impl BackgroundJobDetector {
+    pub fn new() -> Self {
+        BackgroundJobDetector
+    }
+
+    /// Detect background jobs, queue workers and scheduled tasks in a repository
+    pub fn detect_jobs(&self, repo_path: &Path) -> Result<Vec<DetectedBackgroundJob>> {
+        let mut jobs = Vec::new();
+        let mut quartz_schedules: HashMap<String, String> = HashMap::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            // Workflows live under .github, which the usual skip rules leave out
+            let is_workflow = normalized_path.starts_with(".github/workflows/")
+                && (file_name.ends_with(".yml") || file_name.ends_with(".yaml"));
+            if !is_workflow && utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if is_workflow {
+                jobs.extend(self.detect_workflow_schedules(&content, &normalized_path, &file_name));
+                continue;
+            }
+            if file_name == "crontab" || file_name.ends_with(".crontab") || file_name.ends_with(".cron") {
+                jobs.extend(self.detect_crontab(&content, &normalized_path));
+                continue;
+            }
+            if file_name.ends_with(".yml") || file_name.ends_with(".yaml") {
+                if content.contains("kind: CronJob") {
+                    jobs.extend(self.detect_kubernetes_cronjobs(&content, &normalized_path));
+                } else if file_name.contains("schedule") || file_name.contains("sidekiq") {
+                    jobs.extend(self.detect_sidekiq_schedule(&content, &normalized_path));
+                }
+                continue;
+            }
+            if file_name.ends_with(".rb") {
+                jobs.extend(self.detect_ruby(&content, &normalized_path));
+                continue;
+            }
+
+            if utils::is_minified_or_compiled(&content, &normalized_path) {
+                continue;
+            }
+            match utils::detect_language(path).as_deref() {
+                Some("python") => jobs.extend(self.detect_python(&content, &normalized_path)),
+                Some(language @ ("javascript" | "typescript")) => jobs.extend(self.detect_js(&content, &normalized_path, language)),
+                Some("java") => {
+                    for (class, cron) in self.quartz_schedules(&content) {
+                        quartz_schedules.insert(class, cron);
+                    }
+                    jobs.extend(self.detect_java(&content, &normalized_path));
+                }
+                Some("rust") => jobs.extend(self.detect_rust(&content, &normalized_path)),
+                _ => {}
+            }
+        }
+
+        // Quartz jobs are scheduled where they are registered, usually in a config class
+        for job in jobs.iter_mut().filter(|j| j.framework == "quartz" && j.schedule.is_none()) {
+            if let Some(cron) = job.handler.as_ref().and_then(|h| quartz_schedules.get(h)) {
+                job.schedule = Some(cron.clone());
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    fn detect_python(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+
+        // Celery tasks: @app.task(queue="emails") / @shared_task above a def
+        for (idx, line) in lines.iter().enumerate() {
+            if !CELERY_TASK.is_match(line) {
+                continue;
+            }
+            let mut decorator = String::new();
+            let mut handler = None;
+            for next in lines.iter().skip(idx).take(LOOKAHEAD_LINES) {
+                if let Some(cap) = PYTHON_DEF.captures(next) {
+                    handler = Some(cap[1].to_string());
+                    break;
+                }
+                decorator.push_str(next);
+            }
+            let Some(handler) = handler else { continue };
+            jobs.push(DetectedBackgroundJob {
+                name: handler.clone(),
+                framework: "celery".to_string(),
+                trigger: JobTrigger::Queue,
+                schedule: None,
+                queue: QUEUE_ARG.captures(&decorator).map(|cap| cap[1].to_string()),
+                handler: Some(handler),
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                language: Some("python".to_string()),
+            });
+        }
+
+        // Celery beat: entries of beat_schedule naming a task and when it runs
+        if content.contains("beat_schedule") || content.contains("CELERYBEAT_SCHEDULE") {
+            let mut entry: Option<(String, usize, Option<String>, Option<String>)> = None;
+            let flush = |entry: Option<(String, usize, Option<String>, Option<String>)>, jobs: &mut Vec<DetectedBackgroundJob>| {
+                if let Some((name, line_number, Some(task), schedule)) = entry {
+                    jobs.push(DetectedBackgroundJob {
+                        name,
+                        framework: "celery".to_string(),
+                        trigger: JobTrigger::Schedule,
+                        schedule,
+                        queue: None,
+                        handler: Some(task.rsplit('.').next().unwrap_or(&task).to_string()),
+                        file_path: file_path.to_string(),
+                        line_number: Some(line_number),
+                        language: Some("python".to_string()),
+                    });
+                }
+            };
+            for (idx, line) in lines.iter().enumerate() {
+                if let Some(cap) = BEAT_ENTRY.captures(line).filter(|cap| !BEAT_ENTRY_OPTIONS.contains(&&cap[1])) {
+                    flush(entry.take(), &mut jobs);
+                    entry = Some((cap[1].to_string(), idx + 1, None, None));
+                } else if let Some((_, _, task, schedule)) = entry.as_mut() {
+                    if let Some(cap) = BEAT_TASK.captures(line) {
+                        *task = Some(cap[1].to_string());
+                    } else if let Some(cap) = BEAT_SCHEDULE.captures(line) {
+                        *schedule = Some(cap[1].trim().to_string());
+                    }
+                }
+            }
+            flush(entry, &mut jobs);
+        }
+
+        jobs
+    }
+
+    fn detect_ruby(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        let mut class: Option<(String, usize)> = None;
+        for (idx, line) in content.lines().enumerate() {
+            if let Some(cap) = RUBY_CLASS.captures(line) {
+                let name = cap[1].to_string();
+                // ActiveJob subclasses are jobs by inheritance
+                if matches!(cap.get(2).map(|m| m.as_str()), Some("ApplicationJob" | "ActiveJob::Base")) {
+                    jobs.push(self.ruby_job(&name, "activejob", file_path, idx + 1));
+                }
+                class = Some((name, idx + 1));
+            } else if SIDEKIQ_INCLUDE.is_match(line) {
+                if let Some((name, line_number)) = &class {
+                    jobs.push(self.ruby_job(name, "sidekiq", file_path, *line_number));
+                }
+            } else if line.trim_start().starts_with("sidekiq_options") || ACTIVE_JOB_QUEUE.is_match(line) {
+                let queue = QUEUE_ARG.captures(line).or_else(|| ACTIVE_JOB_QUEUE.captures(line));
+                if let (Some(job), Some(cap)) = (jobs.last_mut(), queue) {
+                    job.queue = Some(cap[1].to_string());
+                }
+            }
+        }
+        jobs
+    }
+
+    fn ruby_job(&self, class: &str, framework: &str, file_path: &str, line_number: usize) -> DetectedBackgroundJob {
+        DetectedBackgroundJob {
+            name: class.to_string(),
+            framework: framework.to_string(),
+            trigger: JobTrigger::Queue,
+            schedule: None,
+            queue: Some("default".to_string()),
+            handler: Some(class.to_string()),
+            file_path: file_path.to_string(),
+            line_number: Some(line_number),
+            language: Some("ruby".to_string()),
+        }
+    }
+
+    /// sidekiq-cron / sidekiq-scheduler: `name: { cron: "...", class: "Worker" }`, at the top level
+    /// or under `:schedule:`
+    fn detect_sidekiq_schedule(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let yaml: Value = match serde_yaml::from_str(content) {
+            Ok(v) => v,
+            Err(_) => return Vec::new(),
+        };
+        let entries = yaml.get(":schedule:").or_else(|| yaml.get("schedule")).unwrap_or(&yaml);
+        let Some(entries) = entries.as_mapping() else { return Vec::new() };
+        entries.iter()
+            .filter_map(|(name, entry)| {
+                let name = name.as_str()?;
+                let class = entry.get("class")?.as_str()?;
+                let schedule = ["cron", "every", "at", "in"].iter().find_map(|key| entry.get(*key)).and_then(|v| v.as_str())?;
+                Some(DetectedBackgroundJob {
+                    name: name.to_string(),
+                    framework: "sidekiq".to_string(),
+                    trigger: JobTrigger::Schedule,
+                    schedule: Some(schedule.to_string()),
+                    queue: entry.get("queue").and_then(|q| q.as_str()).map(|q| q.to_string()),
+                    handler: Some(class.to_string()),
+                    file_path: file_path.to_string(),
+                    line_number: line_of(content, &format!("{}:", name)),
+                    language: Some("ruby".to_string()),
+                })
+            })
+            .collect()
+    }
+
+    fn detect_js(&self, content: &str, file_path: &str, language: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        if !content.contains("bullmq") && !content.contains("'bull'") && !content.contains("\"bull\"") {
+            return jobs;
+        }
+        let lines: Vec<&str> = content.lines().collect();
+        let queues: HashMap<String, String> = BULL_QUEUE.captures_iter(content)
+            .filter_map(|cap| cap.get(1).map(|var| (var.as_str().to_string(), cap[2].to_string())))
+            .collect();
+        let job = |name: &str, trigger: JobTrigger, schedule: Option<String>, queue: &str, handler: Option<String>, line: usize| DetectedBackgroundJob {
+            name: name.to_string(),
+            framework: "bullmq".to_string(),
+            trigger,
+            schedule,
+            queue: Some(queue.to_string()),
+            handler,
+            file_path: file_path.to_string(),
+            line_number: Some(line),
+            language: Some(language.to_string()),
+        };
+
+        for (idx, line) in lines.iter().enumerate() {
+            // Inline `async (job) => {}` processors have no name to link to
+            if let Some(cap) = BULL_WORKER.captures(line) {
+                let handler = cap.get(2).map(|m| m.as_str()).filter(|h| !["async", "function"].contains(h));
+                jobs.push(job(&cap[1], JobTrigger::Queue, None, &cap[1], handler.map(|h| h.to_string()), idx + 1));
+            }
+            if let Some(cap) = BULL_PROCESS.captures(line) {
+                if let Some(queue) = queues.get(&cap[1]) {
+                    let handler = cap.get(2).map(|m| m.as_str()).filter(|h| !["async", "function"].contains(h));
+                    jobs.push(job(queue, JobTrigger::Queue, None, queue, handler.map(|h| h.to_string()), idx + 1));
+                }
+            }
+            // Repeatable jobs: queue.add('report', data, { repeat: { pattern: '0 * * * *' } })
+            if let Some(cap) = BULL_ADD.captures(line) {
+                let call = lines[idx..(idx + LOOKAHEAD_LINES).min(lines.len())].join(" ");
+                let call = call.split(");").next().unwrap_or(&call);
+                if let Some(repeat) = BULL_REPEAT.captures(call) {
+                    let schedule = repeat.get(1).or_else(|| repeat.get(2)).map(|m| m.as_str().trim().to_string());
+                    let queue = queues.get(&cap[1]).cloned().unwrap_or_else(|| cap[1].to_string());
+                    jobs.push(job(&cap[2], JobTrigger::Schedule, schedule, &queue, None, idx + 1));
+                }
+            }
+        }
+        jobs
+    }
+
+    fn detect_java(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut class = String::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(cap) = QUARTZ_JOB.captures(line) {
+                jobs.push(DetectedBackgroundJob {
+                    name: cap[1].to_string(),
+                    framework: "quartz".to_string(),
+                    trigger: JobTrigger::Schedule,
+                    schedule: None,
+                    queue: None,
+                    handler: Some(cap[1].to_string()),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                    language: Some("java".to_string()),
+                });
+            }
+            if let Some(cap) = JAVA_CLASS.captures(line) {
+                class = cap[1].to_string();
+            }
+
+            // Spring: @Scheduled(cron = "0 0 * * * *") / @Scheduled(fixedRate = 5000) above a method
+            if let Some(cap) = SPRING_SCHEDULED.captures(line) {
+                let schedule = SPRING_SCHEDULE_ARG.captures(&cap[1]).map(|arg| match &arg[1] {
+                    "cron" => arg[2].trim().to_string(),
+                    kind => format!("{}={}", kind.trim_end_matches("String"), arg[2].trim()),
+                });
+                let handler = lines.iter().skip(idx + 1).take(LOOKAHEAD_LINES)
+                    .filter(|l| !l.trim_start().starts_with('@'))
+                    .find_map(|l| JAVA_METHOD.captures(l).map(|m| m[1].to_string()));
+                let Some(handler) = handler else { continue };
+                jobs.push(DetectedBackgroundJob {
+                    name: if class.is_empty() { handler.clone() } else { format!("{}.{}", class, handler) },
+                    framework: "spring".to_string(),
+                    trigger: JobTrigger::Schedule,
+                    schedule,
+                    queue: None,
+                    handler: Some(handler),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                    language: Some("java".to_string()),
+                });
+            }
+        }
+        jobs
+    }
+
+    /// Quartz job classes mapped to the cron expression they are registered with:
+    /// `JobBuilder.newJob(ReportJob.class)` followed by `cronSchedule("0 0 3 * * ?")`
+    fn quartz_schedules(&self, content: &str) -> Vec<(String, String)> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut schedules = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(cap) = QUARTZ_NEW_JOB.captures(line) {
+                let cron = lines.iter().skip(idx).take(LOOKAHEAD_LINES * 2).find_map(|l| QUARTZ_CRON.captures(l));
+                if let Some(cron) = cron {
+                    schedules.push((cap[1].to_string(), cron[1].to_string()));
+                }
+            }
+        }
+        schedules
+    }
+
+    fn detect_rust(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut function = String::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            if let Some(cap) = RUST_FN.captures(line) {
+                function = cap[1].to_string();
+            }
+            let Some(cap) = TOKIO_SPAWN.captures(line) else { continue };
+            let handler = cap.get(1).map(|m| m.as_str().to_string());
+            // A spawned loop around tokio::time::interval is a periodic task
+            let interval = lines.iter().skip(idx).take(LOOKAHEAD_LINES).find_map(|l| TOKIO_INTERVAL.captures(l));
+            let (trigger, schedule) = match interval {
+                Some(interval) => {
+                    let unit = match &interval[1] {
+                        "millis" => "ms",
+                        "mins" => "m",
+                        _ => "s",
+                    };
+                    (JobTrigger::Schedule, Some(format!("every {}{}", &interval[2], unit)))
+                }
+                None => (JobTrigger::Background, None),
+            };
+            let name = match (&handler, function.is_empty()) {
+                (Some(handler), _) => handler.clone(),
+                (None, false) => format!("{} task", function),
+                (None, true) => format!("task at line {}", idx + 1),
+            };
+            jobs.push(DetectedBackgroundJob {
+                name,
+                framework: "tokio".to_string(),
+                trigger,
+                schedule,
+                queue: None,
+                handler,
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                language: Some("rust".to_string()),
+            });
+        }
+        jobs
+    }
+
+    fn detect_workflow_schedules(&self, content: &str, file_path: &str, file_name: &str) -> Vec<DetectedBackgroundJob> {
+        if !content.contains("schedule") {
+            return Vec::new();
+        }
+        let workflow = content.lines()
+            .find_map(|l| WORKFLOW_NAME.captures(l).map(|cap| cap[1].to_string()))
+            .unwrap_or_else(|| file_name.trim_end_matches(".yml").trim_end_matches(".yaml").to_string());
+        content.lines()
+            .enumerate()
+            .filter_map(|(idx, line)| WORKFLOW_CRON.captures(line).map(|cap| DetectedBackgroundJob {
+                name: workflow.clone(),
+                framework: "github_actions".to_string(),
+                trigger: JobTrigger::Schedule,
+                schedule: Some(cap[1].to_string()),
+                queue: None,
+                handler: None,
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                language: None,
+            }))
+            .collect()
+    }
+
+    fn detect_crontab(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        content.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim_start().starts_with('#'))
+            .filter_map(|(idx, line)| {
+                let cap = CRONTAB_LINE.captures(line)?;
+                let command = cap[2].trim().to_string();
+                Some(DetectedBackgroundJob {
+                    name: command.split_whitespace().take(3).collect::<Vec<_>>().join(" "),
+                    framework: "cron".to_string(),
+                    trigger: JobTrigger::Schedule,
+                    schedule: Some(cap[1].trim().to_string()),
+                    queue: None,
+                    handler: Some(command),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                    language: None,
+                })
+            })
+            .collect()
+    }
+
+    fn detect_kubernetes_cronjobs(&self, content: &str, file_path: &str) -> Vec<DetectedBackgroundJob> {
+        let mut jobs = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let Ok(value) = Value::deserialize(document) else { continue };
+            if value.get("kind").and_then(|k| k.as_str()) != Some("CronJob") {
+                continue;
+            }
+            let Some(name) = value.get("metadata").and_then(|m| m.get("name")).and_then(|n| n.as_str()) else { continue };
+            let schedule = value.get("spec").and_then(|s| s.get("schedule")).and_then(|s| s.as_str());
+            // What the job runs: the first container's command, or its image
+            let container = value.get("spec")
+                .and_then(|s| s.get("jobTemplate"))
+                .and_then(|j| j.get("spec"))
+                .and_then(|s| s.get("template"))
+                .and_then(|t| t.get("spec"))
+                .and_then(|s| s.get("containers"))
+                .and_then(|c| c.as_sequence())
+                .and_then(|c| c.first());
+            let command = container.and_then(|c| {
+                let parts: Vec<&str> = ["command", "args"].iter()
+                    .filter_map(|key| c.get(*key).and_then(|v| v.as_sequence()))
+                    .flatten()
+                    .filter_map(|v| v.as_str())
+                    .collect();
+                (!parts.is_empty()).then(|| parts.join(" "))
+            });
+            let image = container.and_then(|c| c.get("image")).and_then(|i| i.as_str()).map(|i| i.to_string());
+            jobs.push(DetectedBackgroundJob {
+                name: name.to_string(),
+                framework: "kubernetes".to_string(),
+                trigger: JobTrigger::Schedule,
+                schedule: schedule.map(|s| s.to_string()),
+                queue: None,
+                handler: command.or(image),
+                file_path: file_path.to_string(),
+                line_number: line_of(content, &format!("name: {}", name)),
+                language: None,
+            });
+        }
+        jobs
+    }
+}
+
+/// 1-based line of the first line containing `needle`
+fn line_of(content: &str, needle: &str) -> Option<usize> {
+    content.lines().position(|l| l.contains(needle)).map(|idx| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_queue_workers_and_scheduled_tasks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "app/tasks.py", "@app.task(queue=\"emails\")\ndef send_welcome(user_id):\n    pass\n\napp.conf.beat_schedule = {\n    'nightly-cleanup': {\n        'task': 'app.tasks.cleanup',\n        'schedule': crontab(hour=3, minute=0),\n    },\n}\n");
+        write(root, "workers/hard_worker.rb", "class HardWorker\n  include Sidekiq::Worker\n  sidekiq_options queue: :critical\nend\n");
+        write(root, "jobs/queue.ts", "import { Queue, Worker } from 'bullmq';\nconst reports = new Queue('reports');\nnew Worker('reports', buildReport);\nreports.add('daily', {}, {\n  repeat: { pattern: '0 6 * * *' },\n});\n");
+        write(root, "src/main/java/ReportJobs.java", "public class ReportJobs {\n    @Scheduled(cron = \"0 0 * * * *\")\n    public void hourly() {}\n}\n");
+        write(root, "src/worker.rs", "async fn start() {\n    tokio::spawn(async move {\n        let mut ticker = tokio::time::interval(Duration::from_secs(30));\n    });\n}\n");
+        write(root, "deploy/cron.yaml", "apiVersion: batch/v1\nkind: CronJob\nmetadata:\n  name: backup\nspec:\n  schedule: \"0 2 * * *\"\n  jobTemplate:\n    spec:\n      template:\n        spec:\n          containers:\n            - name: backup\n              image: backup:1.0\n              command: [\"/bin/backup\", \"--all\"]\n");
+        write(root, ".github/workflows/nightly.yml", "name: Nightly\non:\n  schedule:\n    - cron: '0 4 * * *'\n");
+
+        let mut found: Vec<(String, String, JobTrigger, Option<String>, Option<String>, Option<String>)> = BackgroundJobDetector::new()
+            .detect_jobs(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|j| (j.framework, j.name, j.trigger, j.schedule, j.queue, j.handler))
+            .collect();
+        found.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(found, vec![
+            ("bullmq".to_string(), "daily".to_string(), JobTrigger::Schedule, s("0 6 * * *"), s("reports"), None),
+            ("bullmq".to_string(), "reports".to_string(), JobTrigger::Queue, None, s("reports"), s("buildReport")),
+            ("celery".to_string(), "nightly-cleanup".to_string(), JobTrigger::Schedule, s("crontab(hour=3, minute=0)"), None, s("cleanup")),
+            ("celery".to_string(), "send_welcome".to_string(), JobTrigger::Queue, None, s("emails"), s("send_welcome")),
+            ("github_actions".to_string(), "Nightly".to_string(), JobTrigger::Schedule, s("0 4 * * *"), None, None),
+            ("kubernetes".to_string(), "backup".to_string(), JobTrigger::Schedule, s("0 2 * * *"), None, s("/bin/backup --all")),
+            ("sidekiq".to_string(), "HardWorker".to_string(), JobTrigger::Queue, None, s("critical"), s("HardWorker")),
+            ("spring".to_string(), "ReportJobs.hourly".to_string(), JobTrigger::Schedule, s("0 0 * * * *"), None, s("hourly")),
+            ("tokio".to_string(), "start task".to_string(), JobTrigger::Schedule, s("every 30s"), None, None),
+        ]);
+    }
+
+    #[test]
+    fn test_skips_non_job_lookalikes_and_falls_back_to_images_and_spawned_calls() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        // A browser Web Worker and a push-triggered workflow are not background jobs
+        write(root, "web/worker.js", "const worker = new Worker('/static/sw.js');\n");
+        write(root, ".github/workflows/ci.yml", "name: CI\non:\n  push:\n    branches: [main]\n");
+        write(root, "deploy/crontab", "SHELL=/bin/bash\nMAILTO=ops@acme.io\n# 0 1 * * * /usr/bin/old\n@daily /usr/bin/backup --full\n");
+        write(root, "app/celery.py", "app.conf.beat_schedule = {\n    'sync-orders': {\n        'task': 'orders.tasks.sync',\n        'schedule': 300.0,\n        'options': {'queue': 'sync'},\n    },\n    'disabled': {\n        'schedule': 60.0,\n    },\n}\n");
+        write(root, "deploy/k8s.yaml", "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web\n---\napiVersion: batch/v1\nkind: CronJob\nmetadata:\n  name: report\nspec:\n  schedule: \"*/15 * * * *\"\n  jobTemplate:\n    spec:\n      template:\n        spec:\n          containers:\n            - name: report\n              image: acme/report:2.1\n");
+        write(root, "src/main.rs", "async fn main() {\n    tokio::spawn(run_metrics_server(config));\n}\n");
+
+        let mut found: Vec<(String, String, JobTrigger, Option<String>, Option<String>)> = BackgroundJobDetector::new()
+            .detect_jobs(root)
+            .unwrap()
+            .into_iter()
+            .map(|j| (j.framework, j.name, j.trigger, j.schedule, j.handler))
+            .collect();
+        found.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(found, vec![
+            ("celery".to_string(), "sync-orders".to_string(), JobTrigger::Schedule, s("300.0"), s("sync")),
+            ("cron".to_string(), "/usr/bin/backup --full".to_string(), JobTrigger::Schedule, s("@daily"), s("/usr/bin/backup --full")),
+            ("kubernetes".to_string(), "report".to_string(), JobTrigger::Schedule, s("*/15 * * * *"), s("acme/report:2.1")),
+            ("tokio".to_string(), "run_metrics_server".to_string(), JobTrigger::Background, None, s("run_metrics_server")),
+        ]);
+    }
+}
@@ -0,0 +1,704 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::{DetectedTool, ToolCategory, ToolDetector, ToolType};
+use crate::parsers::CiPipeline;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BuildRunner {
+    Make,
+    Just,
+    Task,
+    Npm,
+    Gradle,
+}
+
+impl BuildRunner {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildRunner::Make => "make",
+            BuildRunner::Just => "just",
+            BuildRunner::Task => "task",
+            BuildRunner::Npm => "npm",
+            BuildRunner::Gradle => "gradle",
+        }
+    }
+
+    fn for_category(category: &ToolCategory) -> Option<BuildRunner> {
+        match category {
+            ToolCategory::Make => Some(BuildRunner::Make),
+            ToolCategory::Just => Some(BuildRunner::Just),
+            ToolCategory::Task => Some(BuildRunner::Task),
+            ToolCategory::NpmScripts => Some(BuildRunner::Npm),
+            ToolCategory::Gradle => Some(BuildRunner::Gradle),
+            _ => None,
+        }
+    }
+
+    fn owns_file(&self, file_name: &str) -> bool {
+        match self {
+            BuildRunner::Make => matches!(file_name, "makefile" | "gnumakefile") || file_name.ends_with(".mk"),
+            BuildRunner::Just => file_name == "justfile",
+            BuildRunner::Task => matches!(file_name, "taskfile.yml" | "taskfile.yaml"),
+            BuildRunner::Npm => file_name == "package.json",
+            BuildRunner::Gradle => matches!(file_name, "build.gradle" | "build.gradle.kts"),
+        }
+    }
+}
+
+/// A Make/Just/Task target, npm script or Gradle task and what running it pulls in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildTarget {
+    pub runner: BuildRunner,
+    pub name: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub commands: Vec<String>,
+    pub depends_on: Vec<String>, // Keys (`make:build`, `npm:lint`) of the targets this one runs
+    pub tools: Vec<String>,      // Detected tools its commands invoke
+    pub ci_jobs: Vec<String>,    // `pipeline / job` of the CI jobs that invoke it directly
+    pub dependents: usize,       // Targets that run this one, directly or through others
+    pub ci_reach: usize,         // CI jobs that run this one, directly or through other targets
+    pub unused: bool,            // Neither CI nor another target runs it, and it is not a conventional entry point
+}
+
+impl BuildTarget {
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.runner.as_str(), self.name)
+    }
+
+    fn new(runner: BuildRunner, name: &str, file_path: &str, line_number: Option<usize>) -> Self {
+        BuildTarget {
+            runner,
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+            line_number,
+            commands: Vec::new(),
+            depends_on: Vec::new(),
+            tools: Vec::new(),
+            ci_jobs: Vec::new(),
+            dependents: 0,
+            ci_reach: 0,
+            unused: false,
+        }
+    }
+}
+
+/// Targets people run by hand or that package managers run on their own; never reported as unused
+const ENTRY_TARGETS: &[&str] = &[
+    "all", "default", "help", "build", "test", "check", "start", "dev", "serve", "run", "install", "clean",
+    "lint", "fmt", "format", "release", "deploy", "preinstall", "postinstall", "prepare", "prepublishOnly",
+    "prepack", "postpack",
+];
+
+/// `yarn`/`pnpm`/`bun` subcommands that are not script names
+const PACKAGE_MANAGER_COMMANDS: &[&str] = &[
+    "add", "audit", "bin", "cache", "ci", "config", "create", "dedupe", "dlx", "env", "exec", "global", "help",
+    "i", "import", "info", "init", "install", "link", "list", "login", "logout", "ls", "node", "outdated", "pack",
+    "patch", "plugin", "prune", "publish", "rebuild", "remove", "rm", "set", "store", "tag", "unlink", "up",
+    "update", "upgrade", "version", "why", "workspace", "workspaces", "x",
+];
+
+/// `dist/app.js: src/app.ts | build-dir` (not `VAR := value`)
+static MAKE_RULE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([^\s:#=][^:#=]*?)\s*::?(?:\s+([^=]*))?$").unwrap()
+});
+/// `build target="debug": lint (gen "x")` (not `version := "1"`)
+static JUST_RECIPE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^@?([A-Za-z_][\w-]*)[^:]*?:(?:\s+(.*))?$").unwrap()
+});
+static QUOTED: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).unwrap());
+/// `tasks.register("dist")`, `tasks.register<Copy>("dist")`, `tasks.named("build")`, `task dist(type: Zip)`
+static GRADLE_TASK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"tasks\.(?:register|create|named)\s*(?:<[^>]*>)?\s*\(\s*["']([\w-]+)["']|^\s*task\s*\(?\s*["']?([\w-]+)"#).unwrap()
+});
+/// `dist.dependsOn assemble`, `tasks.build.dependsOn("dist")`
+static GRADLE_DEPENDS_OWNER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:tasks\.)?([\w-]+)\.(?:configure\s*\{\s*)?dependsOn\b").unwrap()
+});
+static GRADLE_DEPENDS_ON: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bdependsOn\b\s*(?:\(|=|:|\+=)?\s*(.*)$").unwrap());
+static GRADLE_COMMAND: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:commandLine|executable|args)\b\s*\(?\s*(.*)$").unwrap());
+static IDENTIFIER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z_][\w-]*").unwrap());
+
+/// Parses the targets of the task runners `ToolDetector` found into a graph of target → target and
+/// target → tool edges, and marks which targets CI runs, which many CI jobs funnel through and which
+/// nothing runs at all
+pub struct BuildGraphExtractor;
+
+impl BuildGraphExtractor {
+    pub fn new() -> Self {
+        BuildGraphExtractor
+    }
+
+    pub fn extract(&self, repo_path: &Path, tools: &[DetectedTool], pipelines: &[CiPipeline]) -> Result<Vec<BuildTarget>> {
+        let runners: BTreeSet<BuildRunner> = tools.iter().filter_map(|t| BuildRunner::for_category(&t.category)).collect();
+        if runners.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut targets = Vec::new();
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                name != "node_modules" && name != "target" && name != ".git" && name != "vendor"
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let runner = match runners.iter().find(|r| r.owns_file(&file_name)) {
+                Some(r) => *r,
+                None => continue,
+            };
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let file_path = RepoPath::new(repo_path, path).into_string();
+            targets.extend(match runner {
+                BuildRunner::Make => parse_makefile(&content, &file_path),
+                BuildRunner::Just => parse_justfile(&content, &file_path),
+                BuildRunner::Task => parse_taskfile(&content, &file_path),
+                BuildRunner::Npm => parse_package_scripts(&content, &file_path),
+                BuildRunner::Gradle => parse_gradle(&content, &file_path),
+            });
+        }
+
+        // Root files first, so an ambiguous `make build` resolves to the top-level Makefile
+        targets.sort_by_key(|t: &BuildTarget| (t.file_path.matches('/').count(), t.file_path.clone(), t.line_number));
+        link_targets(&mut targets, tools, pipelines);
+        Ok(targets)
+    }
+}
+
+/// Resolves dependencies to known targets, attaches tools and CI jobs and computes reach
+fn link_targets(targets: &mut [BuildTarget], tools: &[DetectedTool], pipelines: &[CiPipeline]) {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (i, target) in targets.iter().enumerate() {
+        index.entry(target.key()).or_insert(i);
+    }
+    let resolve = |key: &str, file_path: &str, targets: &[BuildTarget]| -> Option<usize> {
+        // Prefer a target declared in the same file over one of the same name elsewhere
+        targets.iter().position(|t| t.file_path == file_path && t.key() == key).or_else(|| index.get(key).copied())
+    };
+
+    let tool_detector = ToolDetector::new();
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); targets.len()];
+    for i in 0..targets.len() {
+        let mut keys: Vec<String> = targets[i].depends_on.clone();
+        for command in &targets[i].commands {
+            keys.extend(invocations(command).into_iter().map(|(runner, name)| format!("{}:{}", runner.as_str(), name)));
+        }
+        let mut resolved = Vec::new();
+        for key in keys {
+            if let Some(j) = resolve(&key, &targets[i].file_path, targets) {
+                if j != i && !edges[i].contains(&j) {
+                    edges[i].push(j);
+                    resolved.push(key);
+                }
+            }
+        }
+        targets[i].depends_on = resolved;
+
+        let mut target_tools: BTreeSet<String> = BTreeSet::new();
+        for command in &targets[i].commands {
+            let words: BTreeSet<String> = command.split(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '@')))
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_lowercase())
+                .collect();
+            for tool in tools {
+                if matches!(tool.tool_type, ToolType::TaskRunner | ToolType::ShellScript | ToolType::DevEnvironment) {
+                    continue;
+                }
+                if words.contains(&tool.name.to_lowercase()) {
+                    target_tools.insert(tool.name.clone());
+                }
+            }
+            if let Some(tool) = tool_detector.detect_tool_from_command(command, Path::new(&targets[i].file_path)) {
+                target_tools.insert(tool.name);
+            }
+        }
+        targets[i].tools = target_tools.into_iter().collect();
+    }
+
+    let reach = |start: usize| -> BTreeSet<usize> {
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in &edges[node] {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    };
+
+    let mut dependents = vec![0usize; targets.len()];
+    for i in 0..targets.len() {
+        for j in reach(i) {
+            if j != i {
+                dependents[j] += 1;
+            }
+        }
+    }
+
+    let mut ci_reach = vec![0usize; targets.len()];
+    for pipeline in pipelines {
+        for job in &pipeline.jobs {
+            let label = format!("{} / {}", pipeline.name, job.name);
+            let mut reached = BTreeSet::new();
+            for command in &job.commands {
+                for (runner, name) in invocations(command) {
+                    if let Some(&i) = index.get(&format!("{}:{}", runner.as_str(), name)) {
+                        if !targets[i].ci_jobs.contains(&label) {
+                            targets[i].ci_jobs.push(label.clone());
+                        }
+                        reached.insert(i);
+                        reached.extend(reach(i));
+                    }
+                }
+            }
+            for i in reached {
+                ci_reach[i] += 1;
+            }
+        }
+    }
+
+    let default_goals: BTreeSet<usize> = {
+        // The first target of a Makefile or justfile runs on a bare `make`/`just`
+        let mut seen_files = BTreeSet::new();
+        let mut firsts: Vec<(usize, &BuildTarget)> = targets.iter().enumerate()
+            .filter(|(_, t)| matches!(t.runner, BuildRunner::Make | BuildRunner::Just))
+            .collect();
+        firsts.sort_by_key(|(_, t)| t.line_number);
+        firsts.into_iter().filter(|(_, t)| seen_files.insert(t.file_path.clone())).map(|(i, _)| i).collect()
+    };
+    for (i, target) in targets.iter_mut().enumerate() {
+        target.dependents = dependents[i];
+        target.ci_reach = ci_reach[i];
+        target.unused = dependents[i] == 0 && ci_reach[i] == 0
+            && !default_goals.contains(&i)
+            && !ENTRY_TARGETS.contains(&target.name.as_str());
+    }
+}
+
+/// Build targets a shell command runs: `make -C web build test`, `$(MAKE) lint`, `just deploy prod`,
+/// `task ci`, `npm run build`, `yarn lint`, `npm test`, `./gradlew :app:assemble`
+pub fn invocations(command: &str) -> Vec<(BuildRunner, String)> {
+    let command = command.replace("$(MAKE)", "make").replace("${MAKE}", "make");
+    let mut found = Vec::new();
+    for segment in command.split([';', '|', '&', '\n', '(', ')', '`']) {
+        let mut words = segment.split_whitespace()
+            .skip_while(|w| is_assignment(w) || matches!(*w, "sudo" | "exec" | "time" | "env" | "then" | "do"));
+        let program = match words.next() {
+            Some(p) => p.rsplit('/').next().unwrap_or(p),
+            None => continue,
+        };
+        let args: Vec<&str> = words.collect();
+        let (runner, names): (BuildRunner, Vec<&str>) = match program {
+            "make" | "gmake" => (BuildRunner::Make, positional(&args, &["-C", "-f", "-o", "-W", "-I", "--directory", "--file"])
+                .into_iter()
+                .filter(|a| !is_assignment(a) && a.parse::<u32>().is_err())
+                .collect()),
+            "just" => (BuildRunner::Just, positional(&args, &["-f", "-d", "--justfile", "--working-directory", "--set"])
+                .into_iter()
+                .take(1)
+                .collect()),
+            "task" | "go-task" => (BuildRunner::Task, positional(&args, &["-t", "-d", "--taskfile", "--dir"])
+                .into_iter()
+                .take_while(|a| *a != "--")
+                .filter(|a| !is_assignment(a))
+                .collect()),
+            "npm" | "pnpm" | "yarn" | "bun" => {
+                let args = positional(&args, &["--prefix", "-C", "--cwd", "--dir", "--filter", "-F", "-w", "--workspace"]);
+                let name = match args.first().copied() {
+                    Some("run") | Some("run-script") => args.get(1).copied(),
+                    Some("t") | Some("tst") if program == "npm" => Some("test"),
+                    Some(p @ ("test" | "start" | "stop" | "restart")) => Some(p),
+                    Some(p) if program != "npm" && !PACKAGE_MANAGER_COMMANDS.contains(&p) => Some(p),
+                    _ => None,
+                };
+                let name = name.filter(|n| n.chars().all(|c| c.is_alphanumeric() || matches!(c, ':' | '-' | '_')));
+                (BuildRunner::Npm, name.into_iter().collect())
+            }
+            "gradle" | "gradlew" | "gradlew.bat" => (BuildRunner::Gradle, positional(&args, &["-p", "-x", "-b", "-c", "--project-dir", "--exclude-task", "--build-file"])
+                .into_iter()
+                .map(|a| a.rsplit(':').next().unwrap_or(a))
+                .collect()),
+            _ => continue,
+        };
+        for name in names {
+            if !name.is_empty() && !name.contains(['$', '"', '\'', '=']) {
+                found.push((runner, name.to_string()));
+            }
+        }
+    }
+    found
+}
+
+/// Arguments that are neither flags nor the values of the given flags
+fn positional<'a>(args: &[&'a str], flags_with_value: &[&str]) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut skip = false;
+    for arg in args {
+        if skip {
+            skip = false;
+        } else if flags_with_value.contains(arg) {
+            skip = true;
+        } else if !arg.starts_with('-') {
+            result.push(*arg);
+        }
+    }
+    result
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').map(|(name, _)| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')).unwrap_or(false)
+}
+
+fn parse_makefile(content: &str, file_path: &str) -> Vec<BuildTarget> {
+    let mut targets: Vec<BuildTarget> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+    let mut in_define = false;
+    let mut logical = String::new();
+    let mut start_line = 0;
+
+    for (i, line) in content.lines().enumerate() {
+        if logical.is_empty() {
+            start_line = i + 1;
+        }
+        if let Some(continued) = line.strip_suffix('\\') {
+            logical.push_str(continued);
+            logical.push(' ');
+            continue;
+        }
+        logical.push_str(line);
+        let line = std::mem::take(&mut logical);
+
+        let trimmed = line.trim();
+        if trimmed.starts_with("define ") {
+            in_define = true;
+            continue;
+        }
+        if in_define {
+            in_define = trimmed != "endef";
+            continue;
+        }
+        if line.starts_with('\t') {
+            let command = trimmed.trim_start_matches(['@', '-', '+']).trim();
+            if !command.is_empty() && !command.starts_with('#') {
+                for &t in &current {
+                    targets[t].commands.push(command.to_string());
+                }
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        current.clear();
+        let (rule, inline_command) = match line.split_once(';') {
+            Some((rule, command)) => (rule.to_string(), Some(command.trim().to_string())),
+            None => (line.clone(), None),
+        };
+        let caps = match MAKE_RULE.captures(rule.trim_end()) {
+            Some(c) => c,
+            None => continue,
+        };
+        let deps: Vec<String> = caps.get(2)
+            .map(|d| d.as_str().split_whitespace()
+                .filter(|d| *d != "|" && !d.contains(['$', '%']))
+                .map(|d| format!("make:{}", d))
+                .collect())
+            .unwrap_or_default();
+        for name in caps[1].split_whitespace() {
+            if name.starts_with('.') || name.contains(['$', '%']) {
+                continue;
+            }
+            let index = match targets.iter().position(|t| t.name == name) {
+                Some(index) => index,
+                None => {
+                    targets.push(BuildTarget::new(BuildRunner::Make, name, file_path, Some(start_line)));
+                    targets.len() - 1
+                }
+            };
+            targets[index].depends_on.extend(deps.iter().cloned());
+            if let Some(command) = &inline_command {
+                targets[index].commands.push(command.clone());
+            }
+            current.push(index);
+        }
+    }
+    targets
+}
+
+fn parse_justfile(content: &str, file_path: &str) -> Vec<BuildTarget> {
+    let mut targets: Vec<BuildTarget> = Vec::new();
+    let mut current: Option<usize> = None;
+
+    for (i, line) in content.lines().enumerate() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(t) = current {
+                let command = line.trim().trim_start_matches(['@', '-']).trim();
+                if !command.is_empty() && !command.starts_with('#') {
+                    targets[t].commands.push(command.to_string());
+                }
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('[') {
+            continue;
+        }
+        current = None;
+        if trimmed.contains(":=") || ["set ", "alias ", "export ", "import ", "mod "].iter().any(|k| trimmed.starts_with(k)) {
+            continue;
+        }
+        let caps = match JUST_RECIPE.captures(trimmed) {
+            Some(c) => c,
+            None => continue,
+        };
+        let mut target = BuildTarget::new(BuildRunner::Just, &caps[1], file_path, Some(i + 1));
+        if let Some(deps) = caps.get(2) {
+            // `a (b "arg") && c`: the first word of a parenthesized dependency is the recipe
+            let deps = QUOTED.replace_all(deps.as_str(), "").replace("&&", " ");
+            let mut depth = 0;
+            for word in deps.split_whitespace() {
+                let opens = word.starts_with('(');
+                if depth == 0 || opens {
+                    if let Some(name) = IDENTIFIER.find(word) {
+                        target.depends_on.push(format!("just:{}", name.as_str()));
+                    }
+                }
+                depth += word.matches('(').count() as i32 - word.matches(')').count() as i32;
+            }
+        }
+        targets.push(target);
+        current = Some(targets.len() - 1);
+    }
+    targets
+}
+
+fn parse_taskfile(content: &str, file_path: &str) -> Vec<BuildTarget> {
+    let doc: Value = match serde_yaml::from_str(content) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let tasks = match doc.get("tasks").and_then(|t| t.as_mapping()) {
+        Some(t) => t,
+        None => return Vec::new(),
+    };
+
+    let mut targets = Vec::new();
+    for (name, definition) in tasks {
+        let name = match name.as_str() {
+            Some(n) => n,
+            None => continue,
+        };
+        let key = Regex::new(&format!(r#"^\s+["']?{}["']?\s*:"#, regex::escape(name))).unwrap();
+        let line_number = content.lines().position(|l| key.is_match(l)).map(|i| i + 1);
+        let mut target = BuildTarget::new(BuildRunner::Task, name, file_path, line_number);
+
+        let task_ref = |item: &Value| -> Option<String> {
+            item.as_str().map(str::to_string)
+                .or_else(|| item.get("task").and_then(|t| t.as_str()).map(str::to_string))
+        };
+        let (cmds, deps) = match definition {
+            Value::Sequence(cmds) => (Some(cmds.clone()), None),
+            Value::String(cmd) => (Some(vec![Value::String(cmd.clone())]), None),
+            Value::Mapping(_) => (
+                definition.get("cmds").and_then(|c| c.as_sequence()).cloned(),
+                definition.get("deps").and_then(|d| d.as_sequence()).cloned(),
+            ),
+            _ => (None, None),
+        };
+        for dep in deps.unwrap_or_default() {
+            if let Some(task) = task_ref(&dep) {
+                target.depends_on.push(format!("task:{}", task));
+            }
+        }
+        for cmd in cmds.unwrap_or_default() {
+            if let Some(command) = cmd.as_str().or_else(|| cmd.get("cmd").and_then(|c| c.as_str())) {
+                target.commands.push(command.trim().to_string());
+            } else if let Some(task) = cmd.get("task").and_then(|t| t.as_str()) {
+                target.depends_on.push(format!("task:{}", task));
+            }
+        }
+        targets.push(target);
+    }
+    targets
+}
+
+fn parse_package_scripts(content: &str, file_path: &str) -> Vec<BuildTarget> {
+    let json: serde_json::Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let scripts = match json.get("scripts").and_then(|s| s.as_object()) {
+        Some(s) => s,
+        None => return Vec::new(),
+    };
+
+    let mut targets = Vec::new();
+    for (name, command) in scripts {
+        let quoted = format!("\"{}\"", name);
+        let line_number = content.lines().position(|l| l.trim_start().starts_with(&quoted)).map(|i| i + 1);
+        let mut target = BuildTarget::new(BuildRunner::Npm, name, file_path, line_number);
+        if let Some(command) = command.as_str() {
+            target.commands.push(command.to_string());
+        }
+        // `npm run build` also runs `prebuild` and `postbuild`
+        for hook in ["pre", "post"] {
+            let hook_name = format!("{}{}", hook, name);
+            if scripts.contains_key(&hook_name) {
+                target.depends_on.push(format!("npm:{}", hook_name));
+            }
+        }
+        targets.push(target);
+    }
+    targets
+}
+
+fn parse_gradle(content: &str, file_path: &str) -> Vec<BuildTarget> {
+    let mut targets: Vec<BuildTarget> = Vec::new();
+    let mut current: Option<usize> = None;
+    let find_or_add = |targets: &mut Vec<BuildTarget>, name: &str, line: usize| -> usize {
+        match targets.iter().position(|t| t.name == name) {
+            Some(i) => i,
+            None => {
+                targets.push(BuildTarget::new(BuildRunner::Gradle, name, file_path, Some(line)));
+                targets.len() - 1
+            }
+        }
+    };
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") {
+            continue;
+        }
+        if let Some(caps) = GRADLE_TASK.captures(line) {
+            let name = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+            current = Some(find_or_add(&mut targets, name, i + 1));
+        }
+        let owner = GRADLE_DEPENDS_OWNER.captures(line)
+            .map(|caps| find_or_add(&mut targets, &caps[1], i + 1))
+            .or(current);
+        if let (Some(owner), Some(caps)) = (owner, GRADLE_DEPENDS_ON.captures(line)) {
+            let args = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+            let quoted: Vec<String> = QUOTED.captures_iter(args)
+                .filter_map(|c| c.get(1).or_else(|| c.get(2)).map(|m| m.as_str().to_string()))
+                .collect();
+            let names: Vec<String> = if quoted.is_empty() {
+                IDENTIFIER.find_iter(args).map(|m| m.as_str().to_string()).filter(|n| n != "tasks" && n != "named").collect()
+            } else {
+                quoted
+            };
+            for name in names {
+                let name = name.rsplit(':').next().unwrap_or(&name).to_string();
+                targets[owner].depends_on.push(format!("gradle:{}", name));
+            }
+        }
+        if let (Some(owner), Some(caps)) = (current, GRADLE_COMMAND.captures(line)) {
+            let words: Vec<&str> = QUOTED.captures_iter(&caps[1])
+                .filter_map(|c| c.get(1).or_else(|| c.get(2)).map(|m| m.as_str()))
+                .collect();
+            if !words.is_empty() {
+                targets[owner].commands.push(words.join(" "));
+            }
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn extract(repo_path: &Path) -> Vec<BuildTarget> {
+        let tools = ToolDetector::new().detect_tools(repo_path).unwrap();
+        let pipelines = crate::parsers::FileParser.detect_pipelines(repo_path).unwrap();
+        BuildGraphExtractor::new().extract(repo_path, &tools, &pipelines).unwrap()
+    }
+
+    #[test]
+    fn test_extracts_targets_edges_and_ci_usage() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Makefile"), "\
+.PHONY: all build test
+all: build test
+
+build: gen
+\tnpm run bundle
+
+gen:
+\tprotoc --go_out=. api.proto
+
+test: build
+\tgo test ./...
+
+stale:
+\techo never
+").unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{
+  "scripts": {
+    "prebundle": "eslint src",
+    "bundle": "webpack --mode production"
+  },
+  "devDependencies": { "webpack": "5.0.0" }
+}"#).unwrap();
+        fs::create_dir_all(temp_dir.path().join(".github/workflows")).unwrap();
+        fs::write(temp_dir.path().join(".github/workflows/ci.yml"), "\
+name: CI
+on: push
+jobs:
+  unit:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make test
+  package:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make build
+").unwrap();
+
+        let targets = extract(temp_dir.path());
+        let get = |key: &str| targets.iter().find(|t| t.key() == key).unwrap_or_else(|| panic!("missing {}", key));
+
+        assert_eq!(get("make:all").depends_on, vec!["make:build", "make:test"]);
+        assert_eq!(get("make:build").depends_on, vec!["make:gen", "npm:bundle"]);
+        assert_eq!(get("npm:bundle").depends_on, vec!["npm:prebundle"]);
+        assert!(get("npm:bundle").tools.contains(&"webpack".to_string()));
+
+        // Both CI jobs go through `build`, so everything under it sits on every CI path
+        assert_eq!(get("make:build").ci_reach, 2);
+        assert_eq!(get("npm:prebundle").ci_reach, 2);
+        assert_eq!(get("make:test").ci_jobs, vec!["CI / unit"]);
+        assert_eq!(get("make:gen").dependents, 3);
+
+        assert!(get("make:stale").unused);
+        assert!(!get("make:all").unused);
+        assert!(!targets.iter().any(|t| t.name.starts_with('.')));
+
+        assert_eq!(invocations("cd web && yarn lint && $(MAKE) -C api -j 4 build VERSION=1"), vec![
+            (BuildRunner::Npm, "lint".to_string()),
+            (BuildRunner::Make, "build".to_string()),
+        ]);
+        assert_eq!(invocations("./gradlew :app:assemble -x test"), vec![(BuildRunner::Gradle, "assemble".to_string())]);
+    }
+}
@@ -0,0 +1,266 @@
+//! Calls between the functions of a repository. The line-heuristic analyzers find call sites in
+//! each function's body (found as in [`code_scope`](super::code_scope)), the tree-sitter backend
+//! reads them from the syntax tree; either way a call is written as the source spells it
+//! (`self.save`, `OrderService.create`, `Cart::new`) and resolved against the repository's symbol
+//! table once every file is read, so calls across files resolve by name. Calls to anything not
+//! declared in the repository (library functions, builtins) are dropped.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use crate::analysis::code_scope::{braced_body_end, indented_body_end};
+use crate::analysis::code_structure::{CodeCall, CodeElement, CodeElementType};
+use crate::analysis::symbols::SymbolTable;
+
+/// A name, optionally behind a receiver chain (`a.b`, `a?.b`, `a::b`, `a->b`), followed by `(`
+static CALL_SITE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([A-Za-z_$][\w$]*(?:\s*(?:\?\.|\.|::|->)\s*[A-Za-z_$][\w$]*)*)\s*(?:::<[^()]*>)?\s*\(").unwrap()
+});
+
+/// Words followed by `(` that are not calls
+const NOT_CALLS: &[&str] = &[
+    "if", "elif", "for", "foreach", "while", "switch", "catch", "except", "return", "match", "when", "with",
+    "and", "or", "not", "in", "is", "typeof", "sizeof", "instanceof", "function", "fn", "func", "def", "lambda",
+    "assert", "await", "yield", "throw", "case", "guard", "synchronized", "using", "super", "this", "self",
+];
+
+/// Words that make the name after them a declaration rather than a call
+const DECLARATIONS: &[&str] = &["function", "def", "fn", "func", "class", "struct", "interface", "enum", "trait", "type", "void"];
+
+/// A call from a function, before the callee is resolved
+pub struct CallReference {
+    pub caller_id: String,
+    /// The callee as written, receiver included when it names a type or module
+    pub reference: String,
+    pub call_type: String,
+    pub line_number: usize,
+}
+
+/// The reference a call to `name` on `receiver` resolves by. A receiver that is the object itself
+/// resolves by name alone; other receivers are kept so `OrderService.create` only resolves to that
+/// class's method. Receivers that are themselves calls or expressions cannot be resolved.
+pub fn call_reference(receiver: Option<&str>, name: &str) -> Option<(String, &'static str)> {
+    if name.is_empty() {
+        return None;
+    }
+    match receiver {
+        None => Some((name.to_string(), "function_call")),
+        Some("this" | "self" | "super" | "cls") => Some((name.to_string(), "method_call")),
+        Some(receiver) if receiver.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | ':')) => {
+            Some((format!("{}.{}", receiver, name), "method_call"))
+        }
+        Some(_) => None,
+    }
+}
+
+/// The code on one line with string contents and comments blanked out; `block` carries an open
+/// `/* */` comment or Python triple-quoted string to the next line
+fn code_of(line: &str, language: &str, block: &mut Option<&'static str>) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let at = |i: usize, pattern: &str| pattern.chars().enumerate().all(|(k, p)| chars.get(i + k) == Some(&p));
+    let mut code = String::with_capacity(line.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(close) = *block {
+            if at(i, close) {
+                i += close.len();
+                *block = None;
+            } else {
+                i += 1;
+            }
+            continue;
+        }
+        let c = chars[i];
+        if language == "python" {
+            if c == '#' {
+                break;
+            }
+            if let Some(quotes) = ["\"\"\"", "'''"].into_iter().find(|q| at(i, q)) {
+                *block = Some(quotes);
+                i += 3;
+                continue;
+            }
+        } else if at(i, "//") {
+            break;
+        } else if at(i, "/*") {
+            *block = Some("*/");
+            i += 2;
+            continue;
+        }
+        // Rust uses `'` for lifetimes as well as characters
+        if c == '"' || c == '`' || c == '\'' && language != "rust" {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            code.push(' ');
+            i += 1;
+            continue;
+        }
+        code.push(c);
+        i += 1;
+    }
+    code
+}
+
+/// Call sites in the bodies of one file's functions, each attributed to the innermost function
+/// whose body contains it. `Self::` is read as the enclosing type.
+pub fn extract_calls(elements: &[CodeElement], content: &str, language: &str) -> Vec<CallReference> {
+    let lines: Vec<&str> = content.lines().collect();
+    let names: HashMap<&str, &str> = elements.iter().map(|e| (e.id.as_str(), e.name.as_str())).collect();
+    let bodies: Vec<(usize, usize, &CodeElement)> = elements.iter()
+        .filter(|e| matches!(e.element_type, CodeElementType::Function | CodeElementType::Method))
+        .filter_map(|e| {
+            let end = match language {
+                "python" => indented_body_end(&lines, e.line_number),
+                _ => braced_body_end(&lines, e.line_number),
+            }?;
+            Some((e.line_number, end, e))
+        })
+        .collect();
+    if bodies.is_empty() {
+        return Vec::new();
+    }
+
+    let mut calls = Vec::new();
+    let mut block = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let line_number = idx + 1;
+        let code = code_of(line, language, &mut block);
+        let Some(&(start, _, caller)) = bodies.iter()
+            .filter(|(start, end, _)| *start <= line_number && line_number <= *end)
+            .max_by_key(|(start, _, _)| *start)
+        else {
+            continue;
+        };
+        // On the declaration line only what follows the opening brace is body
+        let body = if start != line_number {
+            code.as_str()
+        } else if language == "python" {
+            continue;
+        } else {
+            match code.find('{') {
+                Some(open) => &code[open + 1..],
+                None => continue,
+            }
+        };
+
+        for caps in CALL_SITE.captures_iter(body) {
+            let site = caps.get(1).unwrap();
+            let before = body[..site.start()].trim_end();
+            // A receiver that is itself an expression (`a().b(`, `x[0]->y(`) or an annotation
+            if before.ends_with(['.', '@']) || before.ends_with("::") || before.ends_with("->") {
+                continue;
+            }
+            let previous_word = before.rsplit(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or("");
+            if DECLARATIONS.contains(&previous_word) {
+                continue;
+            }
+
+            let chain: String = site.as_str().split_whitespace().collect::<String>().replace("?.", ".").replace("->", ".");
+            let name_start = chain.rfind(['.', ':']).map(|i| i + 1).unwrap_or(0);
+            let name = &chain[name_start..];
+            let receiver = Some(chain[..name_start].trim_end_matches(['.', ':'])).filter(|r| !r.is_empty());
+            if receiver.is_none() && NOT_CALLS.contains(&name) {
+                continue;
+            }
+            let receiver = match receiver {
+                Some("Self") => caller.parent_id.as_deref().and_then(|p| names.get(p).copied()).or(receiver),
+                _ => receiver,
+            };
+            if let Some((reference, call_type)) = call_reference(receiver, name) {
+                calls.push(CallReference {
+                    caller_id: caller.id.clone(),
+                    reference,
+                    call_type: call_type.to_string(),
+                    line_number,
+                });
+            }
+        }
+    }
+    calls
+}
+
+/// Resolves the call references of every file against the repository's elements. Calls that
+/// construct a class or struct become `instantiation`s; references to anything else that cannot
+/// be called are dropped.
+pub fn resolve_calls(elements: &[CodeElement], references: Vec<CallReference>) -> Vec<CodeCall> {
+    let table = SymbolTable::new(elements);
+    let by_id: HashMap<&str, &CodeElement> = elements.iter().map(|e| (e.id.as_str(), e)).collect();
+    let mut seen = HashSet::new();
+    let mut calls = Vec::new();
+    for reference in references {
+        let Some(caller) = by_id.get(reference.caller_id.as_str()) else { continue };
+        let Some(callee) = table.resolve(&reference.reference, &caller.file_path).and_then(|id| by_id.get(id)) else { continue };
+        let call_type = match callee.element_type {
+            CodeElementType::Function | CodeElementType::Method => reference.call_type,
+            CodeElementType::Class | CodeElementType::Struct => "instantiation".to_string(),
+            _ => continue,
+        };
+        if !seen.insert((reference.caller_id.clone(), callee.id.clone(), reference.line_number)) {
+            continue;
+        }
+        calls.push(CodeCall {
+            caller_id: reference.caller_id,
+            callee_id: callee.id.clone(),
+            call_type,
+            line_number: reference.line_number,
+        });
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::CodeAnalyzer;
+
+    #[test]
+    fn test_calls_resolve_within_and_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("orders")).unwrap();
+        std::fs::write(dir.path().join("orders/service.py"), r#"from orders.models import Order
+
+class OrderService:
+    def create(self, customer_id):
+        # validate(customer_id) is only mentioned here
+        self.validate(customer_id)
+        log("creating order")
+        return Order(customer_id)
+
+    def validate(self, customer_id):
+        pass
+"#).unwrap();
+        std::fs::write(dir.path().join("orders/models.py"), "class Order:\n    pass\n").unwrap();
+        std::fs::write(dir.path().join("orders/cart.rs"), r#"pub struct Cart { items: Vec<u32> }
+
+impl Cart {
+    pub fn new() -> Self { Self::empty() }
+
+    fn empty() -> Self {
+        let total = compute_total(&[1, 2]).max(0);
+        Cart { items: Vec::new() }
+    }
+}
+
+fn compute_total(items: &[u32]) -> u32 {
+    items.iter().sum()
+}
+"#).unwrap();
+
+        let structure = CodeAnalyzer::with_settings(crate::analysis::CodeStructureSettings {
+            parser: crate::analysis::CodeParser::Heuristic,
+        }).analyze_repository(dir.path()).unwrap();
+        let name_of = |id: &str| structure.elements.iter().find(|e| e.id == id).map(|e| e.name.as_str()).unwrap();
+        let mut calls: Vec<(&str, &str, &str, usize)> = structure.calls.iter()
+            .filter(|c| c.call_type != "import")
+            .map(|c| (name_of(&c.caller_id), name_of(&c.callee_id), c.call_type.as_str(), c.line_number))
+            .collect();
+        calls.sort();
+        assert_eq!(calls, vec![
+            ("create", "Order", "instantiation", 8),
+            ("create", "validate", "method_call", 6),
+            ("empty", "compute_total", "function_call", 7),
+            ("new", "empty", "method_call", 4),
+        ]);
+    }
+}
@@ -0,0 +1,272 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Changelog file names, checked at the repository root and under `docs/`
+const CHANGELOG_FILES: &[&str] = &["CHANGELOG.md", "CHANGES.md", "HISTORY.md", "RELEASES.md", "NEWS.md", "CHANGELOG", "CHANGELOG.txt"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseSource {
+    Changelog,
+    GitHub,
+}
+
+impl ReleaseSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReleaseSource::Changelog => "changelog",
+            ReleaseSource::GitHub => "github",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Release {
+    pub version: String,
+    pub date: Option<String>, // YYYY-MM-DD
+    pub source: ReleaseSource,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub sections: BTreeMap<String, Vec<String>>, // "Added", "Fixed", ... -> entries
+}
+
+/// What analysis saw at one point in time, kept per analysis so changes can be lined up with releases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureSnapshot {
+    pub taken_at: String,
+    pub dependencies: BTreeMap<String, String>, // package name -> version
+    pub services: BTreeSet<String>,
+    pub endpoint_count: usize,
+    pub code_element_count: usize,
+}
+
+/// Dependency and architecture changes between two consecutive snapshots, with the releases cut in between
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotChange {
+    pub from: String,
+    pub to: String,
+    pub releases: Vec<String>,
+    pub dependencies_added: Vec<String>,
+    pub dependencies_removed: Vec<String>,
+    pub dependencies_changed: Vec<String>, // "name 1.0.0 -> 2.0.0"
+    pub services_added: Vec<String>,
+    pub services_removed: Vec<String>,
+    pub endpoint_delta: i64,
+    pub code_element_delta: i64,
+}
+
+impl SnapshotChange {
+    pub fn between(before: &ArchitectureSnapshot, after: &ArchitectureSnapshot, releases: Vec<String>) -> SnapshotChange {
+        SnapshotChange {
+            releases,
+            dependencies_added: after.dependencies.keys().filter(|k| !before.dependencies.contains_key(*k)).cloned().collect(),
+            dependencies_removed: before.dependencies.keys().filter(|k| !after.dependencies.contains_key(*k)).cloned().collect(),
+            dependencies_changed: after.dependencies.iter()
+                .filter_map(|(name, version)| before.dependencies.get(name)
+                    .filter(|old| *old != version)
+                    .map(|old| format!("{} {} -> {}", name, old, version)))
+                .collect(),
+            services_added: after.services.difference(&before.services).cloned().collect(),
+            services_removed: before.services.difference(&after.services).cloned().collect(),
+            endpoint_delta: after.endpoint_count as i64 - before.endpoint_count as i64,
+            code_element_delta: after.code_element_count as i64 - before.code_element_count as i64,
+            from: before.taken_at.clone(),
+            to: after.taken_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseTimeline {
+    pub releases: Vec<Release>, // Newest first
+    pub changes: Vec<SnapshotChange>, // Oldest first
+}
+
+static VERSION_HEADING: Lazy<Regex> = Lazy::new(|| {
+    // "## [1.2.0] - 2024-01-31", "## v1.2.0 (2024-01-31)", "# Version 1.2.0 — January 31, 2024", "## [Unreleased]"
+    Regex::new(r"(?i)^#{1,3}\s+(?:version\s+|release\s+)?\[?(v?\d+(?:\.\d+){1,3}(?:[-+][0-9A-Za-z.\-]+)?|unreleased)\]?\s*(?:[-–—:]\s*|\(\s*)?(.*?)\)?\s*$").unwrap()
+});
+static SECTION_HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^#{1,4}\s+(.+?)\s*$").unwrap());
+static ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s{0,3}[-*+]\s+(.+)$").unwrap());
+
+/// Parses changelogs and release notes into releases
+pub struct ChangelogParser;
+
+impl ChangelogParser {
+    pub fn new() -> Self {
+        ChangelogParser
+    }
+
+    /// Releases from the first changelog found in the repository, newest first as written
+    pub fn parse_repository(&self, repo_path: &Path) -> Result<Vec<Release>> {
+        for dir in ["", "docs/"] {
+            for name in CHANGELOG_FILES {
+                let path = repo_path.join(format!("{}{}", dir, name));
+                if path.is_file() {
+                    let content = std::fs::read_to_string(&path)?;
+                    return Ok(self.parse(&content));
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
+    pub fn parse(&self, content: &str) -> Vec<Release> {
+        let mut releases: Vec<Release> = Vec::new();
+        let mut section = String::from("Changes");
+        let mut in_code_block = false;
+
+        for line in content.lines() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+            if let Some(cap) = VERSION_HEADING.captures(line) {
+                let version = cap[1].to_string();
+                let rest = cap[2].trim();
+                let date = parse_date(rest);
+                releases.push(Release {
+                    version: if version.eq_ignore_ascii_case("unreleased") { "Unreleased".to_string() } else { version.trim_start_matches(['v', 'V']).to_string() },
+                    title: (!rest.is_empty() && date.is_none()).then(|| rest.to_string()),
+                    date,
+                    source: ReleaseSource::Changelog,
+                    url: None,
+                    sections: BTreeMap::new(),
+                });
+                section = String::from("Changes");
+                continue;
+            }
+            let release = match releases.last_mut() {
+                Some(r) => r,
+                None => continue,
+            };
+            if let Some(cap) = SECTION_HEADING.captures(line) {
+                section = cap[1].trim_matches(['*', ':', ' ']).to_string();
+            } else if let Some(cap) = ENTRY.captures(line) {
+                release.sections.entry(section.clone()).or_default().push(cap[1].trim().to_string());
+            }
+        }
+
+        releases
+    }
+
+    /// Release notes body (markdown) of a hosted release, sectioned like a changelog entry
+    pub fn parse_notes(&self, body: &str) -> BTreeMap<String, Vec<String>> {
+        let mut sections: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut section = String::from("Changes");
+        for line in body.lines() {
+            if let Some(cap) = SECTION_HEADING.captures(line) {
+                section = cap[1].trim_matches(['*', ':', ' ']).to_string();
+            } else if let Some(cap) = ENTRY.captures(line) {
+                sections.entry(section.clone()).or_default().push(cap[1].trim().to_string());
+            }
+        }
+        sections
+    }
+}
+
+/// ISO dates, or "January 31, 2024" / "31 January 2024" anywhere in the heading remainder
+fn parse_date(text: &str) -> Option<String> {
+    static ISO_DATE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap());
+    if let Some(m) = ISO_DATE.find(text) {
+        return NaiveDate::parse_from_str(m.as_str(), "%Y-%m-%d").ok().map(|d| d.to_string());
+    }
+    let cleaned = text.trim_matches(['(', ')', ' ']).replace(',', "");
+    ["%B %d %Y", "%b %d %Y", "%d %B %Y", "%d %b %Y"].iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(&cleaned, fmt).ok())
+        .map(|d| d.to_string())
+}
+
+fn normalize_version(version: &str) -> String {
+    version.trim_start_matches(['v', 'V']).to_lowercase()
+}
+
+/// Merge changelog and hosted releases (one entry per version) and line them up with snapshot changes
+pub fn build_timeline(changelog: Vec<Release>, hosted: Vec<Release>, snapshots: &[ArchitectureSnapshot]) -> ReleaseTimeline {
+    let mut releases = changelog;
+    for release in hosted {
+        match releases.iter_mut().find(|r| normalize_version(&r.version) == normalize_version(&release.version)) {
+            // The changelog is the better-curated source; hosted releases fill in dates and links
+            Some(existing) => {
+                existing.date = existing.date.take().or(release.date);
+                existing.url = existing.url.take().or(release.url);
+                if existing.sections.is_empty() {
+                    existing.sections = release.sections;
+                }
+            }
+            None => releases.push(release),
+        }
+    }
+    releases.sort_by(|a, b| match (&a.date, &b.date) {
+        (Some(a), Some(b)) => b.cmp(a),
+        (None, Some(_)) => std::cmp::Ordering::Less, // Unreleased and undated entries stay on top
+        (Some(_), None) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    let day = |ts: &str| DateTime::parse_from_rfc3339(ts).map(|t| t.with_timezone(&Utc).date_naive().to_string()).unwrap_or_default();
+    let changes = snapshots.windows(2).map(|pair| {
+        let (from, to) = (day(&pair[0].taken_at), day(&pair[1].taken_at));
+        let released = releases.iter()
+            .filter(|r| r.date.as_ref().map(|d| d > &from && d <= &to).unwrap_or(false))
+            .map(|r| r.version.clone())
+            .collect();
+        SnapshotChange::between(&pair[0], &pair[1], released)
+    }).collect();
+
+    ReleaseTimeline { releases, changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_changelog_and_correlates_snapshots() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n- Work in progress\n\n## [1.2.0] - 2024-03-10\n### Added\n- Redis cache\n### Fixed\n* Crash on empty input\n\n## v1.1.0 (February 2, 2024)\n- Initial API\n";
+        let releases = ChangelogParser::new().parse(changelog);
+        let summary: Vec<(&str, Option<&str>)> = releases.iter().map(|r| (r.version.as_str(), r.date.as_deref())).collect();
+        assert_eq!(summary, vec![("Unreleased", None), ("1.2.0", Some("2024-03-10")), ("1.1.0", Some("2024-02-02"))]);
+        assert_eq!(releases[1].sections["Added"], vec!["Redis cache"]);
+        assert_eq!(releases[1].sections["Fixed"], vec!["Crash on empty input"]);
+
+        let hosted = vec![Release {
+            version: "v1.2.0".to_string(),
+            date: Some("2024-03-11".to_string()),
+            source: ReleaseSource::GitHub,
+            title: None,
+            url: Some("https://github.com/acme/shop/releases/tag/v1.2.0".to_string()),
+            sections: BTreeMap::new(),
+        }];
+        let snapshot = |taken_at: &str, deps: &[(&str, &str)], services: &[&str]| ArchitectureSnapshot {
+            taken_at: taken_at.to_string(),
+            dependencies: deps.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+            services: services.iter().map(|s| s.to_string()).collect(),
+            endpoint_count: services.len(),
+            code_element_count: 0,
+        };
+        let snapshots = vec![
+            snapshot("2024-03-01T00:00:00Z", &[("express", "4.18.0"), ("lodash", "4.17.0")], &["Postgres"]),
+            snapshot("2024-03-15T00:00:00Z", &[("express", "4.19.0"), ("ioredis", "5.3.0")], &["Postgres", "Redis"]),
+        ];
+
+        let timeline = build_timeline(releases, hosted, &snapshots);
+        assert_eq!(timeline.releases.len(), 3);
+        assert_eq!(timeline.releases[1].url.as_deref(), Some("https://github.com/acme/shop/releases/tag/v1.2.0"));
+        let change = &timeline.changes[0];
+        assert_eq!(change.releases, vec!["1.2.0"]);
+        assert_eq!(change.dependencies_added, vec!["ioredis"]);
+        assert_eq!(change.dependencies_removed, vec!["lodash"]);
+        assert_eq!(change.dependencies_changed, vec!["express 4.18.0 -> 4.19.0"]);
+        assert_eq!(change.services_added, vec!["Redis"]);
+        assert_eq!(change.endpoint_delta, 1);
+    }
+}
@@ -145,6 +145,13 @@ impl CodeRelationshipDetector {
                         confidence += 0.3;
                     }
                 }
+                // Apex callouts name the Salesforce named credential instead of the URL
+                if let Some(credential) = config.get("named_credential").and_then(|v| v.as_str()) {
+                    if element_body.to_lowercase().contains(&format!("callout:{}", credential.to_lowercase())) {
+                        evidence.push(format!("Named credential '{}' called out to", credential));
+                        confidence += 0.6;
+                    }
+                }
             }
 
             // Only create relationship if we have sufficient evidence
@@ -0,0 +1,331 @@
+//! Which class, struct, interface or impl block each function is declared in. Bodies are found by
+//! brace matching, by indentation for Python and between `@interface`/`@implementation` and `@end`
+//! for Objective-C; Go methods belong to their receiver type. Functions declared directly in a
+//! container become methods with a `parent_id`, functions nested in other functions are left alone.
+
+use crate::analysis::{CodeElement, CodeElementType};
+
+/// Lines a signature may span before its body opens
+const MAX_HEADER_LINES: usize = 5;
+
+/// The lines of a declaration's body, 1-based and inclusive of the declaration line
+struct Extent {
+    start: usize,
+    end: usize,
+    owner: Owner,
+}
+
+enum Owner {
+    /// `None` for impl blocks, traits and extensions whose target is declared in another file
+    Container(Option<String>),
+    Function,
+}
+
+fn is_container(element_type: &CodeElementType) -> bool {
+    matches!(element_type, CodeElementType::Class | CodeElementType::Struct | CodeElementType::Interface | CodeElementType::Enum)
+}
+
+fn is_function(element_type: &CodeElementType) -> bool {
+    matches!(element_type, CodeElementType::Function | CodeElementType::Method)
+}
+
+/// Marks the functions of one file that are declared in a container as methods of it
+pub fn assign_parents(elements: &mut [CodeElement], content: &str, language: &str) {
+    if language == "go" {
+        assign_go_receivers(elements);
+        return;
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut extents = Vec::new();
+    for element in elements.iter() {
+        let owner = if is_container(&element.element_type) {
+            Owner::Container(Some(element.id.clone()))
+        } else if is_function(&element.element_type) {
+            Owner::Function
+        } else {
+            continue;
+        };
+        let end = match language {
+            "python" => indented_body_end(&lines, element.line_number),
+            "objective-c" => None,
+            _ => braced_body_end(&lines, element.line_number),
+        };
+        if let Some(end) = end {
+            extents.push(Extent { start: element.line_number, end, owner });
+        }
+    }
+    match language {
+        "rust" | "swift" => extents.extend(impl_blocks(&lines, elements, language)),
+        "objective-c" => extents.extend(objc_blocks(&lines, elements)),
+        _ => {}
+    }
+
+    for element in elements.iter_mut().filter(|e| is_function(&e.element_type)) {
+        let innermost = extents.iter()
+            .filter(|e| e.start < element.line_number && element.line_number <= e.end)
+            .max_by_key(|e| e.start);
+        if let Some(Extent { owner: Owner::Container(parent), .. }) = innermost {
+            element.element_type = CodeElementType::Method;
+            element.parent_id = parent.clone();
+        }
+    }
+}
+
+/// Last line of the `{ ... }` body opened by the declaration on `line_number`, or `None` when
+/// the declaration has no body (`struct Unit;`, an abstract method, a one-line arrow function)
+pub fn braced_body_end(lines: &[&str], line_number: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut opened = false;
+    for (idx, line) in lines.iter().enumerate().skip(line_number.saturating_sub(1)) {
+        let mut quote: Option<char> = None;
+        let mut prev = ' ';
+        for c in line.chars() {
+            match quote {
+                Some(q) => {
+                    if c == q && prev != '\\' {
+                        quote = None;
+                    }
+                }
+                None => match c {
+                    '"' | '`' => quote = Some(c),
+                    '/' if prev == '/' => break,
+                    '{' => {
+                        depth += 1;
+                        opened = true;
+                    }
+                    '}' if opened => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(idx + 1);
+                        }
+                    }
+                    _ => {}
+                },
+            }
+            prev = c;
+        }
+        if !opened && (line.trim_end().ends_with(';') || idx + 1 >= line_number + MAX_HEADER_LINES) {
+            return None;
+        }
+    }
+    if opened { Some(lines.len()) } else { None }
+}
+
+/// Last line indented deeper than the Python `def`/`class` on `line_number`
+pub fn indented_body_end(lines: &[&str], line_number: usize) -> Option<usize> {
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let indent = indent_of(lines.get(line_number.checked_sub(1)?)?);
+
+    // The header may span lines (`def f(\n    a,\n):`), the body starts after its closing `:`
+    let header_end = (line_number - 1..lines.len().min(line_number - 1 + MAX_HEADER_LINES))
+        .find(|&idx| lines[idx].trim_end().ends_with(':'))
+        .unwrap_or(line_number - 1);
+
+    let mut end = header_end + 1;
+    for (idx, line) in lines.iter().enumerate().skip(header_end + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent_of(line) <= indent {
+            break;
+        }
+        end = idx + 1;
+    }
+    Some(end)
+}
+
+/// The container declared in this file under `name`, if any
+fn container_named(elements: &[CodeElement], name: &str) -> Option<String> {
+    elements.iter()
+        .find(|e| e.name == name && is_container(&e.element_type))
+        .map(|e| e.id.clone())
+}
+
+/// Rust `impl`/`trait` blocks and Swift `extension`s, which add methods to a type without being
+/// an element themselves
+fn impl_blocks(lines: &[&str], elements: &[CodeElement], language: &str) -> Vec<Extent> {
+    let mut extents = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let target = match language {
+            "rust" => rust_block_target(line.trim()),
+            _ => swift_extension_target(line.trim()),
+        };
+        if let Some(target) = target {
+            if let Some(end) = braced_body_end(lines, idx + 1) {
+                extents.push(Extent {
+                    start: idx + 1,
+                    end,
+                    owner: Owner::Container(container_named(elements, &target)),
+                });
+            }
+        }
+    }
+    extents
+}
+
+/// `Foo` for `impl<T> Display for Foo<T> {`, `impl Foo {` or `pub trait Foo {`
+fn rust_block_target(line: &str) -> Option<String> {
+    let line = line.strip_prefix("pub(crate) ").or_else(|| line.strip_prefix("pub ")).unwrap_or(line);
+    let line = line.strip_prefix("unsafe ").unwrap_or(line);
+    let rest = if let Some(rest) = line.strip_prefix("trait ") {
+        rest
+    } else {
+        let rest = line.strip_prefix("impl")?;
+        if !rest.starts_with(|c: char| c == '<' || c.is_whitespace()) {
+            return None;
+        }
+        let rest = skip_generics(rest.trim_start())?;
+        match rest.find(" for ") {
+            Some(pos) => &rest[pos + 5..],
+            None => rest,
+        }
+    };
+    let path: String = rest.trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+        .collect();
+    let name = path.rsplit("::").next().unwrap_or("");
+    if name.is_empty() { None } else { Some(name.to_string()) }
+}
+
+/// The text after a leading `<...>` generic list, or all of it when there is none
+fn skip_generics(text: &str) -> Option<&str> {
+    if !text.starts_with('<') {
+        return Some(text);
+    }
+    let mut depth = 0;
+    for (pos, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[pos + 1..]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `Foo` for `extension Foo: Codable {` or `public extension Foo {`
+fn swift_extension_target(line: &str) -> Option<String> {
+    let start = line.find("extension ")?;
+    if !line[..start].split_whitespace().all(|m| matches!(m, "public" | "private" | "internal" | "fileprivate")) {
+        return None;
+    }
+    let name: String = line[start + 10..].trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+        .collect();
+    let name = name.rsplit('.').next().unwrap_or("");
+    if name.is_empty() { None } else { Some(name.to_string()) }
+}
+
+/// `@interface Foo ... @end` and `@implementation Foo ... @end`
+fn objc_blocks(lines: &[&str], elements: &[CodeElement]) -> Vec<Extent> {
+    let mut extents = Vec::new();
+    let mut open: Option<(usize, String)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        let declared = line.strip_prefix("@interface ").or_else(|| line.strip_prefix("@implementation "));
+        if let Some(rest) = declared {
+            let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            open = Some((idx + 1, name));
+        } else if line.starts_with("@end") {
+            if let Some((start, name)) = open.take() {
+                extents.push(Extent {
+                    start,
+                    end: idx + 1,
+                    owner: Owner::Container(container_named(elements, &name)),
+                });
+            }
+        }
+    }
+    extents
+}
+
+/// Go methods name their type in the receiver: `func (s *Server) Start() error`
+fn assign_go_receivers(elements: &mut [CodeElement]) {
+    let receivers: Vec<(usize, String)> = elements.iter()
+        .enumerate()
+        .filter(|(_, e)| is_function(&e.element_type))
+        .filter_map(|(idx, e)| {
+            let signature = e.signature.as_deref()?.trim_start();
+            let receiver = signature.strip_prefix("func")?.trim_start().strip_prefix('(')?;
+            let receiver = &receiver[..receiver.find(')')?];
+            let type_name = receiver.split_whitespace().last()?.trim_start_matches('*');
+            let type_name = type_name.split('[').next().unwrap_or(type_name);
+            Some((idx, type_name.to_string()))
+        })
+        .collect();
+    for (idx, type_name) in receivers {
+        let parent = container_named(elements, &type_name);
+        elements[idx].element_type = CodeElementType::Method;
+        elements[idx].parent_id = parent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(id: &str, element_type: CodeElementType, line_number: usize, signature: &str) -> CodeElement {
+        CodeElement {
+            id: id.to_string(),
+            name: id.to_string(),
+            element_type,
+            file_path: "src/lib".to_string(),
+            line_number,
+            language: String::new(),
+            signature: Some(signature.to_string()),
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_methods_link_to_their_container() {
+        let rust = "struct Server;\n\nimpl Server {\n    fn start(&self) {\n        fn helper() {}\n    }\n}\n\nfn main() {}\n";
+        let mut elements = vec![
+            element("Server", CodeElementType::Struct, 1, "struct Server;"),
+            element("start", CodeElementType::Function, 4, "fn start(&self) {"),
+            element("helper", CodeElementType::Function, 5, "fn helper() {}"),
+            element("main", CodeElementType::Function, 9, "fn main() {}"),
+        ];
+        assign_parents(&mut elements, rust, "rust");
+        assert_eq!(elements[1].element_type, CodeElementType::Method);
+        assert_eq!(elements[1].parent_id.as_deref(), Some("Server"));
+        assert_eq!(elements[2].element_type, CodeElementType::Function);
+        assert_eq!(elements[3].parent_id, None);
+
+        let python = "class Repo(\n    Base,\n):\n    def save(self):\n        pass\n\ndef load():\n    pass\n";
+        let mut elements = vec![
+            element("Repo", CodeElementType::Class, 1, "class Repo("),
+            element("save", CodeElementType::Function, 4, "def save(self):"),
+            element("load", CodeElementType::Function, 7, "def load():"),
+        ];
+        assign_parents(&mut elements, python, "python");
+        assert_eq!(elements[1].parent_id.as_deref(), Some("Repo"));
+        assert_eq!(elements[2].element_type, CodeElementType::Function);
+
+        let mut elements = vec![
+            element("Server", CodeElementType::Struct, 1, "type Server struct {"),
+            element("Start", CodeElementType::Function, 5, "func (s *Server) Start() error {"),
+        ];
+        assign_parents(&mut elements, "", "go");
+        assert_eq!(elements[1].element_type, CodeElementType::Method);
+        assert_eq!(elements[1].parent_id.as_deref(), Some("Server"));
+
+        assert_eq!(rust_block_target("impl<T: Into<String>> fmt::Display for Wrapper<T> {").as_deref(), Some("Wrapper"));
+        assert_eq!(rust_block_target("important()"), None);
+    }
+}
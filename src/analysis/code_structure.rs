@@ -1,10 +1,20 @@
 use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use walkdir::WalkDir;
 use uuid::Uuid;
 use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::provenance::{CodeOrigin, ProvenanceClassifier};
+
+/// A method in a JS/TS class body; the name is checked against `JS_KEYWORDS` so `if (x) {` is skipped
+static JS_CLASS_METHOD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?:static|async|get|set|public|private|protected|readonly|override)\s+)*\*?#?([A-Za-z_$][\w$]*)\s*(?:<[^>]*>)?\s*\(.*\)\s*(?::\s*[^{]+)?\{\s*$").unwrap()
+});
+const JS_KEYWORDS: &[&str] = &["if", "for", "while", "switch", "catch", "with", "return", "function"];
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum CodeElementType {
@@ -32,6 +42,18 @@ pub struct CodeElement {
     pub visibility: Option<String>, // public, private, protected
     pub parameters: Vec<String>,
     pub return_type: Option<String>,
+    /// The class, struct, interface or impl target a method is declared in
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// `module::Class::method`, derived from the file path and the chain of containers
+    #[serde(default)]
+    pub qualified_name: String,
+    /// Decorators, annotations and attributes above the declaration, as written
+    #[serde(default)]
+    pub decorators: Vec<String>,
+    /// Whether the file is authored here, vendored or generated; non-authored code is left out of metrics by default
+    #[serde(default)]
+    pub origin: CodeOrigin,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,17 +70,43 @@ pub struct CodeStructure {
     pub calls: Vec<CodeCall>,
 }
 
-pub struct CodeAnalyzer;
+/// Which backend reads source files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CodeParser {
+    /// Tree-sitter where the build has it and a grammar exists, line heuristics otherwise
+    #[default]
+    Auto,
+    Heuristic,
+    TreeSitter,
+}
+
+/// `[analyzers.code_structure]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeStructureSettings {
+    pub parser: CodeParser,
+}
+
+pub struct CodeAnalyzer {
+    parser: CodeParser,
+}
 
 impl CodeAnalyzer {
     pub fn new() -> Self {
-        CodeAnalyzer
+        Self::with_settings(CodeStructureSettings::default())
+    }
+
+    pub fn with_settings(settings: CodeStructureSettings) -> Self {
+        CodeAnalyzer { parser: settings.parser }
     }
 
     /// Analyze code structure in a repository
     pub fn analyze_repository(&self, repo_path: &Path) -> Result<CodeStructure> {
         let mut elements = Vec::new();
         let mut calls = Vec::new();
+        let mut call_references = Vec::new();
+        let provenance = ProvenanceClassifier::new(repo_path);
 
         // Walk through code files
         for entry in WalkDir::new(repo_path)
@@ -79,12 +127,13 @@ impl CodeAnalyzer {
             }
 
             // Normalize path: make it relative to repo_path
-            let normalized_path = path.strip_prefix(repo_path)
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| path.to_string_lossy().to_string());
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
 
-            // Determine language from extension
-            let language = utils::detect_language(path);
+            // Determine language from extension; Salesforce markup and metadata need the name and directory,
+            // game engine assembly definitions the full name
+            let language = utils::detect_language(path)
+                .or_else(|| super::salesforce::language_of(&file_name, &path_str))
+                .or_else(|| super::game_engine::language_of(&file_name));
             if language.is_none() {
                 continue;
             }
@@ -95,48 +144,51 @@ impl CodeAnalyzer {
                 if utils::is_minified_or_compiled(&content, &normalized_path) {
                     continue;
                 }
-                
-                match language.as_deref() {
-                    Some("javascript") | Some("typescript") => {
-                        let (file_elements, file_calls) = self.analyze_js_ts(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("python") => {
-                        let (file_elements, file_calls) = self.analyze_python(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("rust") => {
-                        let (file_elements, file_calls) = self.analyze_rust(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("go") => {
-                        let (file_elements, file_calls) = self.analyze_go(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("swift") => {
-                        let (file_elements, file_calls) = self.analyze_swift(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("objective-c") => {
-                        let (file_elements, file_calls) = self.analyze_objective_c(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    Some("java") => {
-                        let (file_elements, file_calls) = self.analyze_java(&content, &normalized_path)?;
-                        elements.extend(file_elements);
-                        calls.extend(file_calls);
-                    }
-                    _ => {}
+                let origin = provenance.classify_content(&normalized_path, &content);
+
+                let parsed = match self.parser {
+                    CodeParser::Heuristic => None,
+                    _ => super::syntax_tree::parse(language.as_deref().unwrap_or(""), &normalized_path, &content),
+                };
+                if let Some(parsed) = parsed {
+                    elements.extend(parsed.elements.into_iter().map(|element| CodeElement { origin, ..element }));
+                    call_references.extend(parsed.calls);
+                    continue;
                 }
+
+                let (mut file_elements, file_calls) = match language.as_deref() {
+                    Some("javascript") | Some("typescript") => self.analyze_js_ts(&content, &normalized_path)?,
+                    Some("python") => self.analyze_python(&content, &normalized_path)?,
+                    Some("rust") => self.analyze_rust(&content, &normalized_path)?,
+                    Some("go") => self.analyze_go(&content, &normalized_path)?,
+                    Some("swift") => self.analyze_swift(&content, &normalized_path)?,
+                    Some("objective-c") => self.analyze_objective_c(&content, &normalized_path)?,
+                    Some("java") => self.analyze_java(&content, &normalized_path)?,
+                    Some("apex") => (super::salesforce::analyze_apex(&content, &normalized_path), Vec::new()),
+                    Some("salesforce") => (super::salesforce::analyze_metadata(&content, &normalized_path), Vec::new()),
+                    Some("unity") => (super::game_engine::analyze_assembly_definition(&content, &normalized_path), Vec::new()),
+                    Some("unreal") => (super::game_engine::analyze_unreal(&content, &normalized_path), Vec::new()),
+                    _ => continue,
+                };
+                // Metadata XML and assembly definitions have no signatures or bodies to follow
+                if !matches!(language.as_deref(), Some("salesforce" | "unity" | "unreal")) {
+                    self.complete_declarations(&mut file_elements, &content, language.as_deref().unwrap_or(""));
+                    super::code_scope::assign_parents(&mut file_elements, &content, language.as_deref().unwrap_or(""));
+                    call_references.extend(super::call_graph::extract_calls(&file_elements, &content, language.as_deref().unwrap_or("")));
+                }
+                for element in &mut file_elements {
+                    element.origin = origin;
+                }
+                elements.extend(file_elements);
+                calls.extend(file_calls);
             }
         }
 
+        super::game_engine::resolve_assembly_guids(repo_path, &mut elements);
+        super::symbols::assign_qualified_names(&mut elements);
+        let elements = super::symbols::dedupe_elements(elements);
+        let elements = link_imports(elements, &mut calls);
+        calls.extend(super::call_graph::resolve_calls(&elements, call_references));
         Ok(CodeStructure { elements, calls })
     }
 
@@ -146,6 +198,7 @@ impl CodeAnalyzer {
         let mut elements = Vec::new();
         let calls = Vec::new();
         let mut element_map: HashMap<String, String> = HashMap::new(); // name -> id
+        let mut class_body_end: Option<usize> = None;
 
         let lines: Vec<&str> = content.lines().collect();
         
@@ -153,6 +206,36 @@ impl CodeAnalyzer {
             let line = line.trim();
             let line_idx = line_num + 1;
 
+            // Detect class methods: name(...) {, async name(...) {, static get name() {
+            if class_body_end.map(|end| line_idx <= end).unwrap_or(false) && !line.contains("function ") {
+                if let Some(caps) = JS_CLASS_METHOD.captures(line) {
+                    let name = caps[1].to_string();
+                    if !JS_KEYWORDS.contains(&name.as_str()) {
+                        let id = uuid::Uuid::new_v4().to_string();
+                        element_map.insert(name.clone(), id.clone());
+
+                        elements.push(CodeElement {
+                            id: id.clone(),
+                            name,
+                            element_type: CodeElementType::Method,
+                            file_path: normalized_path.to_string(),
+                            line_number: line_idx,
+                            language: "javascript".to_string(),
+                            signature: Some(line.to_string()),
+                            doc_comment: self.extract_doc_comment(&lines, line_num),
+                            visibility: None,
+                            parameters: self.extract_parameters_js(line),
+                            return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
+                        });
+                        continue;
+                    }
+                }
+            }
+
             // Detect function declarations
             // Match: function name(...), const name = () =>, var name = function(), let name = function()
             if line.contains("function ") 
@@ -180,6 +263,10 @@ impl CodeAnalyzer {
                         visibility: None,
                         parameters: self.extract_parameters_js(line),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -189,6 +276,7 @@ impl CodeAnalyzer {
                 if let Some(name) = self.extract_class_name_js(line) {
                     let id = uuid::Uuid::new_v4().to_string();
                     element_map.insert(name.clone(), id.clone());
+                    class_body_end = super::code_scope::braced_body_end(&lines, line_idx);
                     
                     elements.push(CodeElement {
                         id: id.clone(),
@@ -202,6 +290,10 @@ impl CodeAnalyzer {
                         visibility: None,
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -224,6 +316,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -264,6 +360,10 @@ impl CodeAnalyzer {
                         visibility: None,
                         parameters: self.extract_parameters_python(line),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -286,6 +386,10 @@ impl CodeAnalyzer {
                         visibility: None,
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -307,6 +411,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -347,6 +455,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_rust(line),
                         parameters: self.extract_parameters_rust(line),
                         return_type: self.extract_return_type_rust(line),
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -369,6 +481,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_rust(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -391,6 +507,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_rust(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -412,6 +532,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -452,6 +576,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_go(line),
                         parameters: self.extract_parameters_go(line),
                         return_type: self.extract_return_type_go(line),
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -474,6 +602,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_go(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -495,6 +627,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -831,6 +967,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_swift(line),
                         parameters: self.extract_parameters_swift(line),
                         return_type: self.extract_return_type_swift(line),
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -853,6 +993,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_swift(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -875,6 +1019,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_swift(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -897,6 +1045,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_swift(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -918,6 +1070,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -958,6 +1114,10 @@ impl CodeAnalyzer {
                         visibility: if line.starts_with("+") { Some("public".to_string()) } else { Some("private".to_string()) },
                         parameters: Vec::new(),
                         return_type: self.extract_return_type_objc(line),
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -980,6 +1140,10 @@ impl CodeAnalyzer {
                         visibility: Some("public".to_string()),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -1004,6 +1168,10 @@ impl CodeAnalyzer {
                             visibility: Some("public".to_string()),
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                     }
                 }
@@ -1026,6 +1194,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -1255,6 +1427,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_java(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -1278,6 +1454,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_java(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -1301,6 +1481,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_java(line),
                         parameters: Vec::new(),
                         return_type: None,
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -1326,6 +1510,10 @@ impl CodeAnalyzer {
                         visibility: self.extract_visibility_java(line),
                         parameters: self.extract_parameters_java(line),
                         return_type: self.extract_return_type_java(line),
+                        parent_id: None,
+                        qualified_name: String::new(),
+                        decorators: Vec::new(),
+                        origin: Default::default(),
                     });
                 }
             }
@@ -1347,6 +1535,10 @@ impl CodeAnalyzer {
                             visibility: None,
                             parameters: Vec::new(),
                             return_type: None,
+                            parent_id: None,
+                            qualified_name: String::new(),
+                            decorators: Vec::new(),
+                            origin: Default::default(),
                         });
                         element_map.insert(module_id.clone(), module_id.clone());
                     }
@@ -1524,28 +1716,87 @@ impl CodeAnalyzer {
     }
 
     fn extract_doc_comment(&self, lines: &[&str], line_num: usize) -> Option<String> {
-        // Look for doc comments above the function/class
-        let mut doc_lines = Vec::new();
-        let mut check_line = line_num;
-        
-        while check_line > 0 {
-            check_line -= 1;
-            let line = lines[check_line].trim();
-            if line.starts_with("//") || line.starts_with("#") || line.starts_with("///") {
-                doc_lines.insert(0, line.to_string());
-            } else if line.is_empty() {
-                continue;
-            } else {
-                break;
+        super::doc_comments::extract_doc_comment(lines, line_num)
+    }
+
+    /// The analyzers read one line per declaration; this picks up the rest of signatures that
+    /// continue past it, re-deriving parameters and return types from the whole signature, and the
+    /// decorators stacked above each declaration
+    fn complete_declarations(&self, elements: &mut [CodeElement], content: &str, language: &str) {
+        let lines: Vec<&str> = content.lines().collect();
+        for element in elements.iter_mut().filter(|e| e.element_type != CodeElementType::Module) {
+            element.decorators = super::signatures::decorators(&lines, element.line_number, language);
+            let signature = match super::signatures::full_signature(&lines, element.line_number, language) {
+                Some(signature) => signature,
+                None => continue,
+            };
+            if matches!(element.element_type, CodeElementType::Function | CodeElementType::Method) {
+                match language {
+                    "javascript" | "typescript" => element.parameters = self.extract_parameters_js(&signature),
+                    "python" => element.parameters = self.extract_parameters_python(&signature),
+                    "rust" => {
+                        element.parameters = self.extract_parameters_rust(&signature);
+                        element.return_type = self.extract_return_type_rust(&signature);
+                    }
+                    "go" => {
+                        element.parameters = self.extract_parameters_go(&signature);
+                        element.return_type = self.extract_return_type_go(&signature);
+                    }
+                    "swift" => {
+                        element.parameters = self.extract_parameters_swift(&signature);
+                        element.return_type = self.extract_return_type_swift(&signature);
+                    }
+                    "java" => {
+                        element.parameters = self.extract_parameters_java(&signature);
+                        element.return_type = self.extract_return_type_java(&signature);
+                    }
+                    "apex" => super::salesforce::complete_apex(element, &signature),
+                    _ => {}
+                }
             }
+            element.signature = Some(signature);
         }
-        
-        if !doc_lines.is_empty() {
-            Some(doc_lines.join("\n"))
-        } else {
-            None
+    }
+}
+
+/// Collapses the Module element each import produced into one element per module name, and
+/// records every import as an `import` call from the importing file to that module. Files have no
+/// element of their own, so the first non-module element declared in the file stands in for it;
+/// imports in files that declare nothing else still share the module but get no call.
+fn link_imports(elements: Vec<CodeElement>, calls: &mut Vec<CodeCall>) -> Vec<CodeElement> {
+    let mut importers: HashMap<String, (usize, String)> = HashMap::new(); // file_path -> (line, element id)
+    for element in elements.iter().filter(|e| e.element_type != CodeElementType::Module) {
+        let first = importers.entry(element.file_path.clone())
+            .or_insert_with(|| (element.line_number, element.id.clone()));
+        if element.line_number < first.0 {
+            *first = (element.line_number, element.id.clone());
+        }
+    }
+
+    let mut modules: HashMap<String, String> = HashMap::new(); // module name -> id
+    let mut linked = HashSet::new();
+    let mut kept = Vec::with_capacity(elements.len());
+    for element in elements {
+        if element.element_type != CodeElementType::Module {
+            kept.push(element);
+            continue;
+        }
+        let module_id = modules.entry(element.name.clone()).or_insert_with(|| element.id.clone()).clone();
+        if let Some((_, importer)) = importers.get(&element.file_path) {
+            if linked.insert((importer.clone(), module_id.clone())) {
+                calls.push(CodeCall {
+                    caller_id: importer.clone(),
+                    callee_id: module_id.clone(),
+                    call_type: "import".to_string(),
+                    line_number: element.line_number,
+                });
+            }
+        }
+        if module_id == element.id {
+            kept.push(element);
         }
     }
+    kept
 }
 
 #[cfg(test)]
@@ -1569,5 +1820,41 @@ mod tests {
         let analyzer = CodeAnalyzer::new();
         assert_eq!(analyzer.extract_function_name_python("def test_function():"), Some("test_function".to_string()));
     }
-}
 
+    #[test]
+    fn test_imports_share_one_module_element() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "import requests\nimport os\n\ndef fetch():\n    pass\n").unwrap();
+        std::fs::write(dir.path().join("b.py"), "import requests\n\nclass Client:\n    pass\n").unwrap();
+
+        let structure = CodeAnalyzer::new().analyze_repository(dir.path()).unwrap();
+        let modules: Vec<_> = structure.elements.iter()
+            .filter(|e| e.element_type == CodeElementType::Module)
+            .collect();
+        assert_eq!(modules.len(), 2);
+
+        let requests = modules.iter().find(|m| m.name == "requests").unwrap();
+        let importers: Vec<&str> = structure.calls.iter()
+            .filter(|c| c.call_type == "import" && c.callee_id == requests.id)
+            .filter_map(|c| structure.elements.iter().find(|e| e.id == c.caller_id))
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(importers.len(), 2);
+        assert!(importers.contains(&"fetch") && importers.contains(&"Client"));
+    }
+
+    #[test]
+    fn test_js_class_methods_link_to_class() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("cart.ts"), "export class Cart {\n  async add(item: Item): Promise<void> {\n    if (item) {\n      this.items.push(item);\n    }\n  }\n}\n\nfunction total() {}\n").unwrap();
+
+        let structure = CodeAnalyzer::new().analyze_repository(dir.path()).unwrap();
+        let cart = structure.elements.iter().find(|e| e.name == "Cart").unwrap();
+        let add = structure.elements.iter().find(|e| e.name == "add").unwrap();
+        assert_eq!(add.element_type, CodeElementType::Method);
+        assert_eq!(add.parent_id.as_ref(), Some(&cart.id));
+        assert!(!structure.elements.iter().any(|e| e.name == "if"));
+        let total = structure.elements.iter().find(|e| e.name == "total").unwrap();
+        assert_eq!((&total.element_type, &total.parent_id), (&CodeElementType::Function, &None));
+    }
+}
@@ -0,0 +1,525 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Stated with every estimate so nobody mistakes it for a bill
+pub const COST_ASSUMPTIONS: &[&str] = &[
+    "On-demand us-east-1 list prices in USD, 730 hours per month, no reserved or savings plan discounts",
+    "Lambda functions are priced at 1M invocations of 200 ms per month",
+    "Data transfer, request charges, backups and unrecognized resources are not included",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum CostCategory {
+    Compute,
+    Database,
+    Cache,
+    Serverless,
+    Network,
+    Kubernetes,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceCost {
+    pub resource_type: String, // As declared: `aws_db_instance`, `AWS::EC2::Instance`, `serverless:function`
+    pub name: String,
+    pub category: CostCategory,
+    pub size: Option<String>, // Instance type/class, or Lambda memory such as `1024MB`
+    pub count: u32,
+    pub monthly_cost: Option<f64>, // None when the size is not in the price table
+    pub oversized: Option<String>, // Why the resource looks larger than it needs to be
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub monthly_total: f64,
+    pub resources: Vec<ResourceCost>,
+}
+
+/// A priced resource before its cost is worked out
+struct Resource {
+    resource_type: String,
+    name: String,
+    category: CostCategory,
+    size: Option<String>,
+    count: u32,
+    multi_az: bool,
+    storage_gb: Option<f64>,
+    file_path: String,
+    line_number: Option<usize>,
+}
+
+/// Hourly on-demand price of the `large` size of an EC2 family; other sizes scale from it
+const EC2_LARGE_HOURLY: &[(&str, f64)] = &[
+    ("t2", 0.0928), ("t3", 0.0832), ("t3a", 0.0752), ("t4g", 0.0672),
+    ("m4", 0.10), ("m5", 0.096), ("m5a", 0.086), ("m6i", 0.096), ("m6a", 0.0864), ("m6g", 0.077), ("m7i", 0.1008), ("m7g", 0.0816),
+    ("c4", 0.10), ("c5", 0.085), ("c5a", 0.077), ("c6i", 0.085), ("c6a", 0.0765), ("c6g", 0.068), ("c7i", 0.0893), ("c7g", 0.0725),
+    ("r4", 0.133), ("r5", 0.126), ("r5a", 0.113), ("r6i", 0.126), ("r6a", 0.1134), ("r6g", 0.1008), ("r7i", 0.1323), ("r7g", 0.1071),
+    ("i3", 0.156), ("i4i", 0.172), ("x1e", 0.834), ("x2idn", 0.834),
+    ("p3", 0.765), ("g4dn", 0.263), ("g5", 0.503),
+];
+/// Managed services charge a premium over the same EC2 size
+const RDS_MARKUP: f64 = 1.75;
+const ELASTICACHE_MARKUP: f64 = 1.6;
+const RDS_STORAGE_GB_MONTH: f64 = 0.115;
+const LAMBDA_GB_SECOND: f64 = 0.0000166667;
+const LAMBDA_MONTHLY_REQUESTS: f64 = 0.20;
+const LAMBDA_MONTHLY_GB_SECONDS_PER_GB: f64 = 1_000_000.0 * 0.2;
+const NAT_GATEWAY_HOURLY: f64 = 0.045;
+const LOAD_BALANCER_HOURLY: f64 = 0.0225;
+const EKS_CLUSTER_HOURLY: f64 = 0.10;
+/// GCE per-vCPU hourly price of a `standard` machine (memory included)
+const GCE_VCPU_HOURLY: &[(&str, f64)] = &[("e2", 0.0335), ("n1", 0.0475), ("n2", 0.0486), ("n2d", 0.0423), ("c2", 0.0522), ("t2d", 0.0422)];
+const GCE_SHARED_CORE_HOURLY: &[(&str, f64)] = &[("e2-micro", 0.0084), ("e2-small", 0.0168), ("e2-medium", 0.0335), ("f1-micro", 0.0076), ("g1-small", 0.0257)];
+
+/// `dev`, `staging`, `qa`, ... as a path segment or name part
+static NON_PRODUCTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[/_\-.])(?:dev|develop|development|test|testing|staging|stage|sandbox|qa)(?:$|[/_\-.])").unwrap()
+});
+static TF_RESOURCE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*resource\s+"(\w+)"\s+"([\w\-]+)"\s*\{"#).unwrap());
+static TF_VARIABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*variable\s+"([\w\-]+)"\s*\{"#).unwrap());
+static TF_ATTRIBUTE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*(\w+)\s*=\s*(.+?)\s*$"#).unwrap());
+static TF_STRING: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]*)""#).unwrap());
+
+pub struct CostEstimator;
+
+impl CostEstimator {
+    pub fn new() -> Self {
+        CostEstimator
+    }
+
+    /// Estimate the monthly cost of the compute, database, cache, Lambda and network resources
+    /// declared in Terraform, CloudFormation/SAM and Serverless Framework files
+    pub fn estimate(&self, repo_path: &Path) -> Result<CostEstimate> {
+        let mut terraform_files = Vec::new();
+        let mut resources = Vec::new();
+        let mut variables: HashMap<String, String> = HashMap::new();
+        let mut tfvars: HashMap<String, String> = HashMap::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name, &path_str) || path_str.contains("/.terraform/") {
+                continue;
+            }
+
+            let is_tf = file_name.ends_with(".tf");
+            let is_tfvars = file_name.ends_with(".tfvars");
+            let is_yaml = file_name.ends_with(".yml") || file_name.ends_with(".yaml");
+            let is_json = file_name.ends_with(".json") || file_name.ends_with(".template");
+            if !(is_tf || is_tfvars || is_yaml || is_json) {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            if is_tf {
+                collect_tf_variable_defaults(&content, &mut variables);
+                terraform_files.push((normalized_path, content));
+            } else if is_tfvars {
+                for line in content.lines() {
+                    if let Some(cap) = TF_ATTRIBUTE.captures(line) {
+                        let value = TF_STRING.captures(&cap[2]).map(|s| s[1].to_string()).unwrap_or_else(|| cap[2].to_string());
+                        tfvars.insert(cap[1].to_string(), value);
+                    }
+                }
+            } else if content.contains("AWSTemplateFormatVersion") || content.contains("AWS::") {
+                resources.extend(cloudformation_resources(&content, &normalized_path));
+            } else if file_name.starts_with("serverless.") {
+                resources.extend(serverless_resources(&content, &normalized_path));
+            }
+        }
+
+        // tfvars override variable defaults
+        variables.extend(tfvars);
+        for (file_path, content) in &terraform_files {
+            resources.extend(terraform_resources(content, file_path, &variables));
+        }
+
+        let mut estimate = CostEstimate::default();
+        for resource in resources {
+            let monthly_cost = monthly_cost(&resource);
+            estimate.monthly_total += monthly_cost.unwrap_or(0.0);
+            estimate.resources.push(ResourceCost {
+                oversized: oversized(&resource),
+                resource_type: resource.resource_type,
+                name: resource.name,
+                category: resource.category,
+                size: resource.size,
+                count: resource.count,
+                monthly_cost: monthly_cost.map(round_cents),
+                file_path: resource.file_path,
+                line_number: resource.line_number,
+            });
+        }
+        estimate.monthly_total = round_cents(estimate.monthly_total);
+        estimate.resources.sort_by(|a, b| b.monthly_cost.unwrap_or(0.0).total_cmp(&a.monthly_cost.unwrap_or(0.0)));
+
+        Ok(estimate)
+    }
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn collect_tf_variable_defaults(content: &str, variables: &mut HashMap<String, String>) {
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        if let Some(cap) = TF_VARIABLE.captures(line) {
+            current = Some(cap[1].to_string());
+            continue;
+        }
+        let Some(name) = &current else { continue };
+        if let Some(cap) = TF_ATTRIBUTE.captures(line) {
+            if &cap[1] == "default" {
+                let value = TF_STRING.captures(&cap[2]).map(|s| s[1].to_string()).unwrap_or_else(|| cap[2].to_string());
+                variables.insert(name.clone(), value);
+            }
+        }
+        if line.starts_with('}') {
+            current = None;
+        }
+    }
+}
+
+/// Attributes of each top-level `resource` block, nested blocks flattened into it
+fn terraform_resources(content: &str, file_path: &str, variables: &HashMap<String, String>) -> Vec<Resource> {
+    let mut resources = Vec::new();
+    let mut current: Option<(String, String, usize, HashMap<String, String>)> = None;
+    let mut depth = 0i32;
+
+    for (idx, line) in content.lines().enumerate() {
+        let code = line.split('#').next().unwrap_or("");
+        if current.is_none() {
+            if let Some(cap) = TF_RESOURCE.captures(code) {
+                current = Some((cap[1].to_string(), cap[2].to_string(), idx + 1, HashMap::new()));
+                depth = 0;
+            }
+        }
+        let Some((_, _, _, attributes)) = current.as_mut() else { continue };
+
+        if let Some(cap) = TF_ATTRIBUTE.captures(code) {
+            let raw = cap[2].trim();
+            let value = if let Some(s) = TF_STRING.captures(raw) {
+                s[1].to_string()
+            } else if let Some(name) = raw.strip_prefix("var.") {
+                variables.get(name).cloned().unwrap_or_else(|| raw.to_string())
+            } else {
+                raw.to_string()
+            };
+            attributes.entry(cap[1].to_string()).or_insert(value);
+        }
+
+        depth += code.matches('{').count() as i32 - code.matches('}').count() as i32;
+        if depth <= 0 {
+            let (resource_type, name, line_number, attributes) = current.take().unwrap();
+            if let Some(resource) = terraform_resource(resource_type, name, line_number, &attributes, file_path) {
+                resources.push(resource);
+            }
+        }
+    }
+
+    resources
+}
+
+fn terraform_resource(resource_type: String, name: String, line_number: usize, attributes: &HashMap<String, String>, file_path: &str) -> Option<Resource> {
+    let number = |key: &str| attributes.get(key).and_then(|v| v.parse::<f64>().ok());
+    let count = number("count").map(|c| c as u32).unwrap_or(1);
+    let text = |key: &str| attributes.get(key).cloned();
+
+    let (category, size, count) = match resource_type.as_str() {
+        "aws_instance" => (CostCategory::Compute, text("instance_type"), count),
+        "aws_db_instance" | "aws_rds_cluster_instance" => (CostCategory::Database, text("instance_class"), count),
+        "aws_elasticache_cluster" => (CostCategory::Cache, text("node_type"), count * number("num_cache_nodes").unwrap_or(1.0) as u32),
+        "aws_elasticache_replication_group" => (CostCategory::Cache, text("node_type"), count * number("num_cache_clusters").unwrap_or(1.0) as u32),
+        "aws_eks_node_group" => (CostCategory::Compute, text("instance_types"), count * number("desired_size").unwrap_or(1.0) as u32),
+        "aws_lambda_function" => (CostCategory::Serverless, Some(format!("{}MB", number("memory_size").unwrap_or(128.0) as u32)), count),
+        "aws_nat_gateway" => (CostCategory::Network, None, count),
+        "aws_lb" | "aws_alb" | "aws_elb" => (CostCategory::Network, None, count),
+        "aws_eks_cluster" => (CostCategory::Kubernetes, None, count),
+        "google_compute_instance" => (CostCategory::Compute, text("machine_type"), count),
+        _ => return None,
+    };
+
+    Some(Resource {
+        resource_type,
+        name,
+        category,
+        size,
+        count,
+        multi_az: attributes.get("multi_az").is_some_and(|v| v == "true"),
+        storage_gb: number("allocated_storage"),
+        file_path: file_path.to_string(),
+        line_number: Some(line_number),
+    })
+}
+
+/// `Resources` of a CloudFormation or SAM template, with `Ref`s to parameters resolved to their defaults
+fn cloudformation_resources(content: &str, file_path: &str) -> Vec<Resource> {
+    let template: Value = match serde_yaml::from_str(content) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let Some(definitions) = template.get("Resources").and_then(|r| r.as_object()) else { return Vec::new() };
+    let parameters = template.get("Parameters");
+    let global_memory = template.pointer("/Globals/Function/MemorySize").and_then(|m| m.as_f64());
+
+    let resolve = |value: Option<&Value>| -> Option<Value> {
+        let value = value?;
+        match value.get("Ref").and_then(|r| r.as_str()) {
+            Some(name) => parameters.and_then(|p| p.get(name)).and_then(|p| p.get("Default")).cloned(),
+            None => Some(value.clone()),
+        }
+    };
+
+    let mut resources = Vec::new();
+    for (name, definition) in definitions {
+        let Some(resource_type) = definition.get("Type").and_then(|t| t.as_str()) else { continue };
+        let properties = definition.get("Properties");
+        let property = |key: &str| resolve(properties.and_then(|p| p.get(key)));
+        let text = |key: &str| property(key).and_then(|v| v.as_str().map(|s| s.to_string()));
+        let number = |key: &str| property(key).and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())));
+
+        let (category, size, count) = match resource_type {
+            "AWS::EC2::Instance" => (CostCategory::Compute, text("InstanceType"), 1),
+            "AWS::RDS::DBInstance" => (CostCategory::Database, text("DBInstanceClass"), 1),
+            "AWS::ElastiCache::CacheCluster" => (CostCategory::Cache, text("CacheNodeType"), number("NumCacheNodes").unwrap_or(1.0) as u32),
+            "AWS::Lambda::Function" => (CostCategory::Serverless, Some(format!("{}MB", number("MemorySize").unwrap_or(128.0) as u32)), 1),
+            "AWS::Serverless::Function" => (CostCategory::Serverless, Some(format!("{}MB", number("MemorySize").or(global_memory).unwrap_or(128.0) as u32)), 1),
+            "AWS::EC2::NatGateway" | "AWS::ElasticLoadBalancingV2::LoadBalancer" | "AWS::ElasticLoadBalancing::LoadBalancer" => (CostCategory::Network, None, 1),
+            "AWS::EKS::Cluster" => (CostCategory::Kubernetes, None, 1),
+            _ => continue,
+        };
+        let multi_az = property("MultiAZ").is_some_and(|v| v.as_bool() == Some(true) || v.as_str() == Some("true"));
+
+        resources.push(Resource {
+            resource_type: resource_type.to_string(),
+            name: name.clone(),
+            category,
+            size,
+            count,
+            multi_az,
+            storage_gb: number("AllocatedStorage"),
+            file_path: file_path.to_string(),
+            line_number: line_of(content, name),
+        });
+    }
+    resources
+}
+
+/// Functions of a Serverless Framework service; the framework defaults memory to 1024 MB
+fn serverless_resources(content: &str, file_path: &str) -> Vec<Resource> {
+    let config: Value = match serde_yaml::from_str(content) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let Some(functions) = config.get("functions").and_then(|f| f.as_object()) else { return Vec::new() };
+    let provider_memory = config.pointer("/provider/memorySize").and_then(|m| m.as_f64()).unwrap_or(1024.0);
+
+    functions.iter().map(|(name, function)| {
+        let memory = function.get("memorySize").and_then(|m| m.as_f64()).unwrap_or(provider_memory);
+        Resource {
+            resource_type: "serverless:function".to_string(),
+            name: name.clone(),
+            category: CostCategory::Serverless,
+            size: Some(format!("{}MB", memory as u32)),
+            count: 1,
+            multi_az: false,
+            storage_gb: None,
+            file_path: file_path.to_string(),
+            line_number: line_of(content, name),
+        }
+    }).collect()
+}
+
+/// The line declaring a YAML/JSON key
+fn line_of(content: &str, key: &str) -> Option<usize> {
+    content.lines()
+        .position(|l| {
+            let l = l.trim_start().trim_start_matches('"');
+            l.starts_with(key) && l[key.len()..].trim_start_matches('"').trim_start().starts_with(':')
+        })
+        .map(|i| i + 1)
+}
+
+/// `m5.large` → (`m5`, 1.0), `db.r6g.2xlarge` → (`r6g`, 4.0), `cache.t3.micro` → (`t3`, 0.125)
+fn instance_size(size: &str) -> Option<(&str, f64)> {
+    let size = size.trim_start_matches("db.").trim_start_matches("cache.");
+    let (family, rest) = size.split_once('.')?;
+    let rest = rest.trim_end_matches(".search");
+    let multiplier = match rest {
+        "nano" => 0.0625,
+        "micro" => 0.125,
+        "small" => 0.25,
+        "medium" => 0.5,
+        "large" => 1.0,
+        "xlarge" => 2.0,
+        "metal" => 48.0,
+        _ => 2.0 * rest.strip_suffix("xlarge")?.parse::<f64>().ok()?,
+    };
+    Some((family, multiplier))
+}
+
+fn ec2_hourly(size: &str) -> Option<f64> {
+    let (family, multiplier) = instance_size(size)?;
+    EC2_LARGE_HOURLY.iter().find(|(f, _)| *f == family).map(|(_, price)| price * multiplier)
+}
+
+/// `e2-standard-4` → 4 vCPUs at the e2 rate; shared-core types have a flat price
+fn gce_hourly(machine_type: &str) -> Option<f64> {
+    if let Some((_, price)) = GCE_SHARED_CORE_HOURLY.iter().find(|(t, _)| *t == machine_type) {
+        return Some(*price);
+    }
+    let mut parts = machine_type.split('-');
+    let (series, class, vcpus) = (parts.next()?, parts.next()?, parts.next()?.parse::<f64>().ok()?);
+    let rate = GCE_VCPU_HOURLY.iter().find(|(s, _)| *s == series)?.1;
+    let class_factor = match class {
+        "standard" => 1.0,
+        "highmem" => 1.35,
+        "highcpu" => 0.75,
+        _ => return None,
+    };
+    Some(rate * class_factor * vcpus)
+}
+
+fn lambda_memory_mb(size: &str) -> Option<f64> {
+    size.strip_suffix("MB")?.parse().ok()
+}
+
+fn monthly_cost(resource: &Resource) -> Option<f64> {
+    let size = resource.size.as_deref();
+    let unit = match (resource.resource_type.as_str(), resource.category) {
+        ("google_compute_instance", _) => gce_hourly(size?)? * HOURS_PER_MONTH,
+        (_, CostCategory::Compute) => ec2_hourly(size?)? * HOURS_PER_MONTH,
+        (_, CostCategory::Database) => {
+            let instance = ec2_hourly(size?)? * RDS_MARKUP * HOURS_PER_MONTH * if resource.multi_az { 2.0 } else { 1.0 };
+            instance + resource.storage_gb.unwrap_or(0.0) * RDS_STORAGE_GB_MONTH
+        }
+        (_, CostCategory::Cache) => ec2_hourly(size?)? * ELASTICACHE_MARKUP * HOURS_PER_MONTH,
+        (_, CostCategory::Serverless) => lambda_memory_mb(size?)? / 1024.0 * LAMBDA_MONTHLY_GB_SECONDS_PER_GB * LAMBDA_GB_SECOND + LAMBDA_MONTHLY_REQUESTS,
+        ("aws_nat_gateway" | "AWS::EC2::NatGateway", _) => NAT_GATEWAY_HOURLY * HOURS_PER_MONTH,
+        (_, CostCategory::Network) => LOAD_BALANCER_HOURLY * HOURS_PER_MONTH,
+        (_, CostCategory::Kubernetes) => EKS_CLUSTER_HOURLY * HOURS_PER_MONTH,
+    };
+    Some(unit * resource.count as f64)
+}
+
+/// Sizes that rarely make sense: 8xlarge and up anywhere, 2xlarge and up or Multi-AZ outside
+/// production, Lambdas above 3 GB and GCE machines with 32+ vCPUs
+fn oversized(resource: &Resource) -> Option<String> {
+    let size = resource.size.as_deref()?;
+    let non_production = NON_PRODUCTION.is_match(&resource.file_path) || NON_PRODUCTION.is_match(&resource.name);
+
+    if resource.category == CostCategory::Serverless {
+        let memory = lambda_memory_mb(size)?;
+        return (memory > 3008.0).then(|| format!("{} of memory for a Lambda function", size));
+    }
+    if resource.resource_type == "google_compute_instance" {
+        let vcpus: f64 = size.rsplit('-').next()?.parse().ok()?;
+        return (vcpus >= 32.0).then(|| format!("{} has {} vCPUs", size, vcpus));
+    }
+
+    let (_, multiplier) = instance_size(size)?;
+    if multiplier >= 16.0 {
+        Some(format!("{} is 8xlarge or larger", size))
+    } else if non_production && multiplier >= 4.0 {
+        Some(format!("{} in a non-production environment", size))
+    } else if non_production && resource.multi_az {
+        Some("Multi-AZ in a non-production environment".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_estimates_iac_costs_and_flags_oversized() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "infra/main.tf", "variable \"web_type\" {\n  default = \"t3.micro\"\n}\n\nresource \"aws_instance\" \"web\" {\n  count         = 2\n  instance_type = var.web_type\n  tags = {\n    Name = \"web\"\n  }\n}\n\nresource \"aws_db_instance\" \"main\" {\n  instance_class    = \"db.m5.large\"\n  allocated_storage = 100\n  multi_az          = true\n}\n\nresource \"aws_s3_bucket\" \"assets\" {\n  bucket = \"assets\"\n}\n");
+        write(root, "infra/prod.tfvars", "web_type = \"m5.large\"\n");
+        write(root, "envs/staging/cache.tf", "resource \"aws_elasticache_cluster\" \"sessions\" {\n  node_type       = \"cache.r6g.4xlarge\"\n  num_cache_nodes = 1\n}\n");
+        write(root, "template.yaml", "AWSTemplateFormatVersion: '2010-09-09'\nTransform: AWS::Serverless-2016-10-31\nResources:\n  ResizeFunction:\n    Type: AWS::Serverless::Function\n    Properties:\n      MemorySize: 4096\n  Nat:\n    Type: AWS::EC2::NatGateway\n");
+
+        let estimate = CostEstimator::new().estimate(temp_dir.path()).unwrap();
+        let find = |name: &str| estimate.resources.iter().find(|r| r.name == name).unwrap();
+
+        let web = find("web");
+        assert_eq!((web.size.as_deref(), web.count), (Some("m5.large"), 2));
+        assert_eq!(web.monthly_cost, Some(140.16));
+        // 0.096 * 1.75 * 730 * 2 for Multi-AZ, plus 100 GB of storage
+        assert_eq!(find("main").monthly_cost, Some(256.78));
+        assert_eq!(find("Nat").monthly_cost, Some(32.85));
+        assert_eq!(find("Nat").line_number, Some(8));
+        assert_eq!(find("ResizeFunction").size.as_deref(), Some("4096MB"));
+        assert!(estimate.resources.iter().all(|r| r.name != "assets"));
+
+        let mut oversized: Vec<&str> = estimate.resources.iter()
+            .filter(|r| r.oversized.is_some())
+            .map(|r| r.name.as_str())
+            .collect();
+        oversized.sort();
+        assert_eq!(oversized, vec!["ResizeFunction", "sessions"]);
+
+        let total: f64 = estimate.resources.iter().filter_map(|r| r.monthly_cost).sum();
+        assert!((estimate.monthly_total - total).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_unpriced_commented_and_vendored_resources_stay_out_of_the_total() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "infra/main.tf", "# resource \"aws_instance\" \"old\" {\n#   instance_type = \"m5.24xlarge\"\n# }\n\nresource \"aws_instance\" \"mystery\" {\n  instance_type = var.undeclared\n}\n\nresource \"aws_instance\" \"batch\" {\n  instance_type = \"z9.large\"\n}\n\nresource \"aws_db_instance\" \"orders\" {\n  instance_class = \"db.r5.2xlarge\"\n  multi_az       = true\n}\n");
+        write(root, "infra/.terraform/modules/vpc/main.tf", "resource \"aws_instance\" \"vendored\" {\n  instance_type = \"m5.large\"\n}\n");
+        write(root, "api/serverless.yml", "service: api\nprovider:\n  name: aws\nfunctions:\n  hello:\n    handler: handler.hello\n");
+        write(root, "broken/template.yaml", "AWSTemplateFormatVersion: '2010-09-09'\nResources: [unclosed\n");
+
+        let estimate = CostEstimator::new().estimate(root).unwrap();
+        let mut resources: Vec<(&str, Option<&str>, Option<f64>)> = estimate.resources.iter()
+            .map(|r| (r.name.as_str(), r.size.as_deref(), r.monthly_cost))
+            .collect();
+        resources.sort_by(|a, b| a.0.cmp(b.0));
+        assert_eq!(resources, vec![
+            ("batch", Some("z9.large"), None),
+            ("hello", Some("1024MB"), Some(3.53)),
+            ("mystery", Some("var.undeclared"), None),
+            ("orders", Some("db.r5.2xlarge"), Some(1287.72)),
+        ]);
+        // 2xlarge and Multi-AZ are expected in production
+        assert!(estimate.resources.iter().all(|r| r.oversized.is_none()));
+        assert_eq!(estimate.monthly_total, 1291.25);
+
+        let empty = tempfile::tempdir().unwrap();
+        let estimate = CostEstimator::new().estimate(empty.path()).unwrap();
+        assert!(estimate.resources.is_empty());
+        assert_eq!(estimate.monthly_total, 0.0);
+    }
+}
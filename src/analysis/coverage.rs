@@ -0,0 +1,438 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::{CodeElement, CodeElementType};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+    Jacoco,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCoverage {
+    pub file_path: String, // Repository-relative when the reported path could be resolved
+    pub format: CoverageFormat,
+    pub report_path: String, // Coverage artifact the data came from
+    pub lines: BTreeMap<usize, u64>, // Line number -> hit count, instrumented lines only
+    pub branches: BTreeMap<usize, (usize, usize)>, // Line number -> (branches found, branches hit)
+}
+
+impl FileCoverage {
+    pub fn lines_found(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    pub fn branches_found(&self) -> usize {
+        self.branches.values().map(|(found, _)| found).sum()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches.values().map(|(_, hit)| hit).sum()
+    }
+
+    pub fn line_percentage(&self) -> f64 {
+        percentage(self.lines_hit(), self.lines_found())
+    }
+}
+
+/// Coverage joined onto a function or method. The element's span runs until the next
+/// code element in the same file, which is close enough for ranking untested code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementCoverage {
+    pub element_id: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_number: usize,
+    pub end_line: Option<usize>,
+    pub lines_found: usize,
+    pub lines_hit: usize,
+    pub line_percentage: f64,
+    pub complexity: usize, // 1 + decision points reported by the coverage tool
+}
+
+/// Parses lcov, Cobertura and JaCoCo coverage artifacts
+pub struct CoverageParser;
+
+impl CoverageParser {
+    pub fn new() -> Self {
+        CoverageParser
+    }
+
+    /// Find coverage artifacts committed to the repository and resolve them against its files
+    pub fn detect_reports(&self, repo_path: &Path) -> Result<Vec<FileCoverage>> {
+        let mut coverage = Vec::new();
+        let mut known_files = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            // Coverage output usually lives in coverage/ or target/, which the shared skip list excludes
+            if path_str.contains("node_modules") || path_str.contains("/.git/") {
+                continue;
+            }
+            if is_coverage_artifact(&file_name) {
+                let content = match std::fs::read_to_string(path) {
+                    Ok(c) => c,
+                    Err(_) => continue,
+                };
+                if let Some(format) = detect_format(&content) {
+                    coverage.extend(self.parse(&content, format, &normalized_path));
+                }
+                continue;
+            }
+
+            if !utils::should_skip_file(&file_name, &path_str) {
+                known_files.push(normalized_path);
+            }
+        }
+
+        Ok(resolve_paths(coverage, &known_files))
+    }
+
+    pub fn parse(&self, content: &str, format: CoverageFormat, report_path: &str) -> Vec<FileCoverage> {
+        match format {
+            CoverageFormat::Lcov => self.parse_lcov(content, report_path),
+            CoverageFormat::Cobertura => self.parse_cobertura(content, report_path),
+            CoverageFormat::Jacoco => self.parse_jacoco(content, report_path),
+        }
+    }
+
+    fn parse_lcov(&self, content: &str, report_path: &str) -> Vec<FileCoverage> {
+        let mut files = Vec::new();
+        let mut current: Option<FileCoverage> = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(source) = line.strip_prefix("SF:") {
+                current = Some(new_file(source, CoverageFormat::Lcov, report_path));
+            } else if line == "end_of_record" {
+                if let Some(file) = current.take() {
+                    files.push(file);
+                }
+            } else if let Some(file) = current.as_mut() {
+                if let Some(data) = line.strip_prefix("DA:") {
+                    let mut parts = data.split(',');
+                    if let (Some(Ok(number)), Some(Ok(hits))) = (
+                        parts.next().map(|p| p.parse::<usize>()),
+                        parts.next().map(|p| p.parse::<u64>()),
+                    ) {
+                        *file.lines.entry(number).or_insert(0) += hits;
+                    }
+                } else if let Some(data) = line.strip_prefix("BRDA:") {
+                    // BRDA:<line>,<block>,<branch>,<taken>, where taken is "-" for never evaluated
+                    let parts: Vec<&str> = data.split(',').collect();
+                    if let (Some(Ok(number)), Some(taken)) = (parts.first().map(|p| p.parse::<usize>()), parts.get(3)) {
+                        let hit = taken.parse::<u64>().map(|t| t > 0).unwrap_or(false);
+                        let branch = file.branches.entry(number).or_insert((0, 0));
+                        branch.0 += 1;
+                        if hit {
+                            branch.1 += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Tolerate a truncated final record
+        if let Some(file) = current {
+            files.push(file);
+        }
+        files
+    }
+
+    fn parse_cobertura(&self, content: &str, report_path: &str) -> Vec<FileCoverage> {
+        let mut files: Vec<FileCoverage> = Vec::new();
+        let mut current: Option<usize> = None;
+
+        for tag in xml_tags(content) {
+            if tag.starts_with("<class") {
+                current = attr(tag, "filename").map(|filename| {
+                    match files.iter().position(|f| f.file_path == filename) {
+                        Some(idx) => idx,
+                        None => {
+                            files.push(new_file(filename, CoverageFormat::Cobertura, report_path));
+                            files.len() - 1
+                        }
+                    }
+                });
+            } else if tag.starts_with("<line ") || tag.starts_with("<line\t") {
+                let idx = match current {
+                    Some(idx) => idx,
+                    None => continue,
+                };
+                let number = attr(tag, "number").and_then(|n| n.parse::<usize>().ok());
+                let hits = attr(tag, "hits").and_then(|h| h.parse::<u64>().ok());
+                if let (Some(number), Some(hits)) = (number, hits) {
+                    let file = &mut files[idx];
+                    // Method-level <lines> repeat the class-level ones; keep the highest count
+                    let entry = file.lines.entry(number).or_insert(0);
+                    *entry = (*entry).max(hits);
+                    if attr(tag, "branch") == Some("true") {
+                        // condition-coverage="50% (1/2)"
+                        if let Some((hit, found)) = attr(tag, "condition-coverage")
+                            .and_then(|c| c.split_once('('))
+                            .and_then(|(_, rest)| rest.trim_end_matches(')').split_once('/'))
+                            .and_then(|(hit, found)| Some((hit.trim().parse::<usize>().ok()?, found.trim().parse::<usize>().ok()?)))
+                        {
+                            file.branches.insert(number, (found, hit));
+                        }
+                    }
+                }
+            } else if tag.starts_with("</class") {
+                current = None;
+            }
+        }
+
+        files
+    }
+
+    fn parse_jacoco(&self, content: &str, report_path: &str) -> Vec<FileCoverage> {
+        let mut files = Vec::new();
+        let mut package = String::new();
+        let mut current: Option<FileCoverage> = None;
+
+        for tag in xml_tags(content) {
+            if tag.starts_with("<package") {
+                package = attr(tag, "name").unwrap_or("").to_string();
+            } else if tag.starts_with("<sourcefile") {
+                if let Some(name) = attr(tag, "name") {
+                    let source = if package.is_empty() { name.to_string() } else { format!("{}/{}", package, name) };
+                    current = Some(new_file(&source, CoverageFormat::Jacoco, report_path));
+                }
+            } else if tag.starts_with("</sourcefile") {
+                if let Some(file) = current.take() {
+                    files.push(file);
+                }
+            } else if tag.starts_with("<line ") {
+                if let Some(file) = current.as_mut() {
+                    let count = |name: &str| attr(tag, name).and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+                    let number = count("nr");
+                    if number == 0 {
+                        continue;
+                    }
+                    // JaCoCo counts instructions rather than hits; a line with covered instructions was executed
+                    file.lines.insert(number, if count("ci") > 0 { 1 } else { 0 });
+                    let (missed_branches, covered_branches) = (count("mb"), count("cb"));
+                    if missed_branches + covered_branches > 0 {
+                        file.branches.insert(number, (missed_branches + covered_branches, covered_branches));
+                    }
+                }
+            }
+        }
+
+        files
+    }
+}
+
+/// Detect the coverage format from the artifact contents
+pub fn detect_format(content: &str) -> Option<CoverageFormat> {
+    if content.contains("SF:") && content.contains("end_of_record") {
+        Some(CoverageFormat::Lcov)
+    } else if content.contains("<sourcefile") && (content.contains("JACOCO") || content.contains("<report")) {
+        Some(CoverageFormat::Jacoco)
+    } else if content.contains("<coverage") && content.contains("line-rate") {
+        Some(CoverageFormat::Cobertura)
+    } else {
+        None
+    }
+}
+
+/// Map reported paths (often absolute CI paths or package-relative) onto repository files,
+/// merging entries that resolve to the same file
+pub fn resolve_paths(coverage: Vec<FileCoverage>, known_files: &[String]) -> Vec<FileCoverage> {
+    let mut merged: Vec<FileCoverage> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for mut file in coverage {
+        let reported = file.file_path.replace('\\', "/");
+        let reported = reported.trim_start_matches("./").to_string();
+        let resolved = if known_files.contains(&reported) {
+            Some(reported.clone())
+        } else {
+            known_files.iter()
+                .filter(|known| {
+                    reported.ends_with(&format!("/{}", known)) || known.ends_with(&format!("/{}", reported))
+                })
+                .max_by_key(|known| known.len())
+                .cloned()
+        };
+        file.file_path = resolved.unwrap_or(reported);
+
+        match index.get(&file.file_path) {
+            Some(&idx) => {
+                let existing = &mut merged[idx];
+                for (line, hits) in file.lines {
+                    *existing.lines.entry(line).or_insert(0) += hits;
+                }
+                for (line, (found, hit)) in file.branches {
+                    let entry = existing.branches.entry(line).or_insert((0, 0));
+                    *entry = (entry.0.max(found), entry.1.max(hit));
+                }
+            }
+            None => {
+                index.insert(file.file_path.clone(), merged.len());
+                merged.push(file);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Join file coverage onto functions and methods
+pub fn element_coverage(elements: &[CodeElement], coverage: &[FileCoverage]) -> Vec<ElementCoverage> {
+    let by_file: HashMap<&str, &FileCoverage> = coverage.iter()
+        .map(|f| (f.file_path.as_str(), f))
+        .collect();
+    let mut elements_by_file: HashMap<&str, Vec<&CodeElement>> = HashMap::new();
+    for element in elements {
+        elements_by_file.entry(element.file_path.as_str()).or_default().push(element);
+    }
+
+    let mut results = Vec::new();
+    for (file_path, mut file_elements) in elements_by_file {
+        let file = match by_file.get(file_path) {
+            Some(f) => f,
+            None => continue,
+        };
+        file_elements.sort_by_key(|e| e.line_number);
+
+        for (idx, element) in file_elements.iter().enumerate() {
+            if !matches!(element.element_type, CodeElementType::Function | CodeElementType::Method) {
+                continue;
+            }
+            let end_line = file_elements[idx + 1..].iter()
+                .find(|next| next.line_number > element.line_number)
+                .map(|next| next.line_number - 1);
+            let in_span = |line: &usize| *line >= element.line_number && end_line.map(|end| *line <= end).unwrap_or(true);
+
+            let span_lines: Vec<u64> = file.lines.range(element.line_number..)
+                .filter(|(line, _)| in_span(line))
+                .map(|(_, hits)| *hits)
+                .collect();
+            if span_lines.is_empty() {
+                continue;
+            }
+            let lines_hit = span_lines.iter().filter(|hits| **hits > 0).count();
+            let decision_points: usize = file.branches.range(element.line_number..)
+                .filter(|(line, _)| in_span(line))
+                // A two-way branch is one decision
+                .map(|(_, (found, _))| found.saturating_sub(1).max(1))
+                .sum();
+
+            results.push(ElementCoverage {
+                element_id: element.id.clone(),
+                name: element.name.clone(),
+                file_path: element.file_path.clone(),
+                line_number: element.line_number,
+                end_line,
+                lines_found: span_lines.len(),
+                lines_hit,
+                line_percentage: percentage(lines_hit, span_lines.len()),
+                complexity: 1 + decision_points,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| a.file_path.cmp(&b.file_path).then_with(|| a.line_number.cmp(&b.line_number)));
+    results
+}
+
+fn is_coverage_artifact(file_name: &str) -> bool {
+    file_name == "lcov.info"
+        || file_name.ends_with(".lcov")
+        || (file_name.ends_with(".xml")
+            && (file_name.contains("coverage") || file_name.contains("cobertura") || file_name.contains("jacoco")))
+}
+
+fn new_file(source: &str, format: CoverageFormat, report_path: &str) -> FileCoverage {
+    FileCoverage {
+        file_path: source.to_string(),
+        format,
+        report_path: report_path.to_string(),
+        lines: BTreeMap::new(),
+        branches: BTreeMap::new(),
+    }
+}
+
+fn percentage(hit: usize, found: usize) -> f64 {
+    if found == 0 {
+        0.0
+    } else {
+        (hit as f64 / found as f64 * 10000.0).round() / 100.0
+    }
+}
+
+/// Iterate over XML tags without a full parser; coverage reports are flat enough for this
+fn xml_tags(content: &str) -> impl Iterator<Item = &str> {
+    content.match_indices('<')
+        .filter_map(move |(start, _)| content[start..].find('>').map(|end| &content[start..start + end]))
+}
+
+fn attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lcov_and_resolve_absolute_paths() {
+        let lcov = "TN:\nSF:/home/runner/work/app/app/src/math.js\nDA:1,1\nDA:2,0\nDA:3,4\nBRDA:2,0,0,1\nBRDA:2,0,1,-\nend_of_record\n";
+        let parsed = CoverageParser::new().parse(lcov, CoverageFormat::Lcov, "coverage/lcov.info");
+        let resolved = resolve_paths(parsed, &["src/math.js".to_string()]);
+
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].file_path, "src/math.js");
+        assert_eq!(resolved[0].lines_found(), 3);
+        assert_eq!(resolved[0].lines_hit(), 2);
+        assert_eq!(resolved[0].branches.get(&2), Some(&(2, 1)));
+    }
+
+    #[test]
+    fn test_parse_jacoco_and_cobertura() {
+        let jacoco = r#"<?xml version="1.0"?><!DOCTYPE report PUBLIC "-//JACOCO//DTD Report 1.1//EN" "report.dtd">
+<report name="app"><package name="com/acme"><sourcefile name="Billing.java">
+<line nr="10" mi="0" ci="3" mb="1" cb="1"/><line nr="11" mi="2" ci="0" mb="0" cb="0"/>
+</sourcefile></package></report>"#;
+        assert_eq!(detect_format(jacoco), Some(CoverageFormat::Jacoco));
+        let parsed = CoverageParser::new().parse(jacoco, CoverageFormat::Jacoco, "jacoco.xml");
+        let resolved = resolve_paths(parsed, &["src/main/java/com/acme/Billing.java".to_string()]);
+        assert_eq!(resolved[0].file_path, "src/main/java/com/acme/Billing.java");
+        assert_eq!(resolved[0].line_percentage(), 50.0);
+
+        let cobertura = r#"<coverage line-rate="0.5"><packages><package><classes>
+<class name="app" filename="app/views.py"><lines>
+<line number="1" hits="1"/><line number="4" hits="0" branch="true" condition-coverage="0% (0/2)"/>
+</lines></class></classes></package></packages></coverage>"#;
+        assert_eq!(detect_format(cobertura), Some(CoverageFormat::Cobertura));
+        let parsed = CoverageParser::new().parse(cobertura, CoverageFormat::Cobertura, "coverage.xml");
+        assert_eq!(parsed[0].file_path, "app/views.py");
+        assert_eq!(parsed[0].branches_found(), 2);
+        assert_eq!(parsed[0].lines_hit(), 1);
+    }
+}
@@ -0,0 +1,781 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DataOrchestrator {
+    Airflow,
+    Dbt,
+    Dagster,
+    Prefect,
+}
+
+impl DataOrchestrator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataOrchestrator::Airflow => "airflow",
+            DataOrchestrator::Dbt => "dbt",
+            DataOrchestrator::Dagster => "dagster",
+            DataOrchestrator::Prefect => "prefect",
+        }
+    }
+}
+
+/// A step of a data pipeline: an Airflow task, dbt model/seed/snapshot, Dagster asset/op or Prefect task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataTask {
+    pub name: String,
+    pub kind: String, // Operator class, `@task`, dbt `model`/`seed`/`snapshot`, Dagster `asset`/`op`
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub upstream: Vec<String>, // Names of the tasks in the same pipeline that run before this one
+    pub inputs: Vec<String>,   // Datasets read: dbt sources and refs, Airflow inlets, Dagster upstream assets
+    pub outputs: Vec<String>,  // Datasets written: dbt models and seeds, Dagster assets, Airflow outlets
+    pub notebook: Option<String>, // Notebook a Papermill/Databricks task executes
+}
+
+/// An Airflow DAG, dbt project, Dagster job or asset group, or Prefect flow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataPipeline {
+    pub orchestrator: DataOrchestrator,
+    pub name: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub schedule: Option<String>,
+    pub triggered_by: Vec<String>, // Datasets whose updates schedule the pipeline (Airflow data-aware scheduling)
+    pub tasks: Vec<DataTask>,
+    #[serde(default)]
+    pub warehouse: Option<String>, // dbt adapter type: snowflake, bigquery, postgres, ...
+    #[serde(default)]
+    pub sources: Vec<DataSource>,
+    #[serde(default)]
+    pub exposures: Vec<DataExposure>,
+}
+
+/// A dbt source: a table loaded outside the project and read with `source('name', 'table')`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSource {
+    pub name: String, // `name.table`, as task inputs refer to it
+    pub relation: Option<String>, // `database.schema.identifier` in the warehouse, as far as declared
+    pub loader: Option<String>, // Fivetran, Airbyte, ...
+    pub file_path: String,
+}
+
+/// A dashboard, notebook, application or ML model that a dbt project declares as depending on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExposure {
+    pub name: String,
+    pub kind: String, // dashboard, notebook, analysis, ml or application
+    pub owner: Option<String>,
+    pub url: Option<String>,
+    pub depends_on: Vec<String>, // Datasets: models and `name.table` sources
+    pub file_path: String,
+}
+
+static PY_DEF: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\s*)(?:async\s+)?def\s+(\w+)\s*\(").unwrap());
+static PY_DECORATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*@([\w.]+)").unwrap());
+static PY_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\w+)(?:\.(?:submit|map|expand|partial|override)\([^()]*\))*(?:\.(?:submit|map|expand))?\s*\(").unwrap());
+static PY_IDENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b[A-Za-z_]\w*\b").unwrap());
+static ASSIGNED_TO: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(\w+)\s*=\s*$").unwrap());
+static AIRFLOW_DAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bDAG\s*\(").unwrap());
+static AIRFLOW_OPERATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*(\w+)\s*=\s*(\w+(?:Operator|Sensor))\s*\(").unwrap());
+static AIRFLOW_DATASET: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(?:Dataset|Asset)\(\s*(?:uri\s*=\s*)?["']([^"']+)["']"#).unwrap());
+static DATASET_VARIABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?m)^\s*(\w+)\s*=\s*(?:Dataset|Asset)\(\s*(?:uri\s*=\s*)?["']([^"']+)["']"#).unwrap());
+static NOTEBOOK_PATH: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(?:input_nb|notebook_path|notebook)\s*=\s*["']([^"']+)["']"#).unwrap());
+static DEPENDENCY_OPERATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r">>|<<").unwrap());
+static SET_STREAM: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\w+)\.set_(downstream|upstream)\s*\(").unwrap());
+static LIST_KWARG: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(inlets|outlets|deps)\s*=\s*\[").unwrap());
+static CHAIN_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bchain\s*\(").unwrap());
+
+/// A Python function with the decorators stacked above it
+struct PyFunction {
+    name: String,
+    line_number: usize,
+    end_line: usize, // Last line of the body
+    decorators: Vec<(String, String)>, // (dotted name, argument text)
+    params: String,
+    body: String,
+}
+
+impl PyFunction {
+    /// Arguments of the first decorator named `name`, also matching `module.name` and `name.variant`
+    fn decorator(&self, name: &str) -> Option<&str> {
+        self.decorators.iter()
+            .find(|(d, _)| d == name || d.ends_with(&format!(".{}", name)) || d.starts_with(&format!("{}.", name)))
+            .map(|(_, args)| args.as_str())
+    }
+}
+
+pub struct DataPipelineDetector;
+
+impl DataPipelineDetector {
+    pub fn new() -> Self {
+        DataPipelineDetector
+    }
+
+    /// Find Airflow DAGs, dbt projects, Dagster jobs/assets and Prefect flows with their task
+    /// dependencies and the datasets the tasks read and write
+    pub fn detect(&self, repo_path: &Path) -> Result<Vec<DataPipeline>> {
+        let mut pipelines = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            if file_name == "dbt_project.yml" {
+                if let Some(pipeline) = crate::analysis::dbt::detect_project(repo_path, path, &normalized_path) {
+                    pipelines.push(pipeline);
+                }
+                continue;
+            }
+            if !file_name.ends_with(".py") {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else { continue };
+            let orchestrator = if imports(&content, "airflow") {
+                DataOrchestrator::Airflow
+            } else if imports(&content, "dagster") {
+                DataOrchestrator::Dagster
+            } else if imports(&content, "prefect") {
+                DataOrchestrator::Prefect
+            } else {
+                continue;
+            };
+            let functions = parse_functions(&content);
+            let found = match orchestrator {
+                DataOrchestrator::Airflow => detect_airflow(&content, &functions, &normalized_path),
+                DataOrchestrator::Dagster => detect_dagster(&functions, &normalized_path),
+                _ => detect_prefect(&functions, &normalized_path),
+            };
+            pipelines.extend(found);
+        }
+
+        for pipeline in &mut pipelines {
+            // Upstream names that are not tasks of the pipeline stay visible as inputs only
+            let names: Vec<String> = pipeline.tasks.iter().map(|t| t.name.clone()).collect();
+            for task in &mut pipeline.tasks {
+                task.upstream.retain(|u| u != &task.name && names.contains(u));
+                task.upstream.sort();
+                task.upstream.dedup();
+                task.inputs.sort();
+                task.inputs.dedup();
+                task.outputs.sort();
+                task.outputs.dedup();
+            }
+        }
+        pipelines.sort_by(|a, b| (a.orchestrator, &a.file_path, &a.name).cmp(&(b.orchestrator, &b.file_path, &b.name)));
+        Ok(pipelines)
+    }
+}
+
+fn imports(content: &str, module: &str) -> bool {
+    content.lines().map(str::trim_start).any(|l| {
+        l.strip_prefix("from ").or_else(|| l.strip_prefix("import "))
+            .is_some_and(|rest| rest == module || rest.starts_with(&format!("{} ", module)) || rest.starts_with(&format!("{}.", module)))
+    })
+}
+
+fn detect_airflow(content: &str, functions: &[PyFunction], file_path: &str) -> Vec<DataPipeline> {
+    let dataset_variables: HashMap<String, String> = DATASET_VARIABLE.captures_iter(content)
+        .map(|c| (c[1].to_string(), c[2].to_string()))
+        .collect();
+    // TaskFlow tasks by function name
+    let task_functions: HashMap<String, String> = functions.iter()
+        .filter_map(|f| f.decorator("task").map(|args| (f.name.clone(), kwarg(args, "task_id").unwrap_or_else(|| f.name.clone()))))
+        .collect();
+
+    // (pipeline, start offset, end offset) of each DAG's extent in the file
+    let mut dags: Vec<(DataPipeline, usize, usize)> = Vec::new();
+    for m in AIRFLOW_DAG.find_iter(content) {
+        let args = enclosed(content, m.end() - 1);
+        let Some(name) = first_string(args).or_else(|| kwarg(args, "dag_id")) else { continue };
+        let (schedule, triggered_by) = schedule_of(args, &dataset_variables);
+        dags.push((DataPipeline {
+            orchestrator: DataOrchestrator::Airflow,
+            name,
+            file_path: file_path.to_string(),
+            line_number: Some(line_of(content, m.start())),
+            schedule,
+            triggered_by,
+            tasks: Vec::new(),
+            warehouse: None,
+            sources: Vec::new(),
+            exposures: Vec::new(),
+        }, m.start(), content.len()));
+    }
+    for i in 1..dags.len() {
+        dags[i - 1].2 = dags[i].1;
+    }
+    for function in functions.iter().filter(|f| f.decorator("dag").is_some()) {
+        let args = function.decorator("dag").unwrap_or_default();
+        let (schedule, triggered_by) = schedule_of(args, &dataset_variables);
+        let start = offset_of_line(content, function.line_number);
+        let end = offset_of_line(content, function.end_line + 1);
+        dags.push((DataPipeline {
+            orchestrator: DataOrchestrator::Airflow,
+            name: kwarg(args, "dag_id").unwrap_or_else(|| function.name.clone()),
+            file_path: file_path.to_string(),
+            line_number: Some(function.line_number),
+            schedule,
+            triggered_by,
+            tasks: Vec::new(),
+            warehouse: None,
+            sources: Vec::new(),
+            exposures: Vec::new(),
+        }, start, end));
+    }
+    if dags.is_empty() {
+        return Vec::new();
+    }
+
+    // Classic operators assigned to a variable
+    let mut variables: HashMap<String, String> = HashMap::new(); // variable -> task id
+    for c in AIRFLOW_OPERATOR.captures_iter(content) {
+        let whole = c.get(0).unwrap();
+        let args = enclosed(content, whole.end() - 1);
+        let task_id = kwarg(args, "task_id").unwrap_or_else(|| c[1].to_string());
+        variables.insert(c[1].to_string(), task_id.clone());
+        let datasets = |key: &str| -> Vec<String> {
+            let Some(list) = list_kwarg(args, key) else { return Vec::new() };
+            let mut found: Vec<String> = AIRFLOW_DATASET.captures_iter(list).map(|c| c[1].to_string()).collect();
+            found.extend(PY_IDENT.find_iter(list).filter_map(|i| dataset_variables.get(i.as_str()).cloned()));
+            found
+        };
+        let index = dag_at(&dags, whole.start());
+        dags[index].0.tasks.push(DataTask {
+            name: task_id,
+            kind: c[2].to_string(),
+            file_path: file_path.to_string(),
+            line_number: Some(line_of(content, whole.start())),
+            upstream: Vec::new(),
+            inputs: datasets("inlets"),
+            outputs: datasets("outlets"),
+            notebook: NOTEBOOK_PATH.captures(args).map(|n| n[1].to_string()),
+        });
+    }
+
+    // TaskFlow calls inside each DAG wire tasks by passing outputs along
+    let lines: HashMap<&str, usize> = functions.iter().map(|f| (f.name.as_str(), f.line_number)).collect();
+    for (dag, start, end) in dags.iter_mut() {
+        let extent = &content[*start..(*end).min(content.len())];
+        let (calls, call_variables) = wire_calls(extent, &task_functions);
+        variables.extend(call_variables);
+        for (function, task_id, upstream) in calls {
+            let task = task_entry(dag, &task_id, "@task", file_path, lines.get(function.as_str()).copied());
+            task.upstream.extend(upstream);
+        }
+    }
+
+    // Explicit ordering: `a >> b`, `[a, b] >> c`, `a.set_downstream(b)` and `chain(a, b, c)`
+    let resolve = |operand: &str| -> Vec<String> {
+        let operand = operand.trim().trim_start_matches('[').trim_end_matches(']');
+        split_top_level(operand).iter().filter_map(|item| {
+            let name = item.split(['(', '.']).next().unwrap_or("").trim();
+            variables.get(name).or_else(|| task_functions.get(name)).cloned()
+        }).collect()
+    };
+    let mut orderings: Vec<(usize, Vec<String>, Vec<String>)> = Vec::new(); // (offset, before, after)
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let code = line.split('#').next().unwrap_or("");
+        if DEPENDENCY_OPERATOR.is_match(code) {
+            let operands: Vec<&str> = DEPENDENCY_OPERATOR.split(code).collect();
+            let operators: Vec<&str> = DEPENDENCY_OPERATOR.find_iter(code).map(|m| m.as_str()).collect();
+            for (i, operator) in operators.iter().enumerate() {
+                let (left, right) = (resolve(operands[i]), resolve(operands[i + 1]));
+                if *operator == ">>" {
+                    orderings.push((offset, left, right));
+                } else {
+                    orderings.push((offset, right, left));
+                }
+            }
+        }
+        offset += line.len();
+    }
+    for c in SET_STREAM.captures_iter(content) {
+        let whole = c.get(0).unwrap();
+        let source = resolve(&c[1]);
+        let other = resolve(enclosed(content, whole.end() - 1));
+        if &c[2] == "downstream" {
+            orderings.push((whole.start(), source, other));
+        } else {
+            orderings.push((whole.start(), other, source));
+        }
+    }
+    for m in CHAIN_CALL.find_iter(content) {
+        let steps: Vec<Vec<String>> = split_top_level(enclosed(content, m.end() - 1)).iter().map(|s| resolve(s)).collect();
+        for pair in steps.windows(2) {
+            orderings.push((m.start(), pair[0].clone(), pair[1].clone()));
+        }
+    }
+    for (offset, before, after) in orderings {
+        let index = dag_at(&dags, offset);
+        let dag = &mut dags[index].0;
+        for task in dag.tasks.iter_mut().filter(|t| after.contains(&t.name)) {
+            task.upstream.extend(before.iter().cloned());
+        }
+    }
+
+    dags.into_iter().map(|(dag, _, _)| dag).collect()
+}
+
+/// The innermost DAG whose extent contains `offset`, so a `@dag` function wins over a `with DAG` block around it
+fn dag_at(dags: &[(DataPipeline, usize, usize)], offset: usize) -> usize {
+    dags.iter()
+        .enumerate()
+        .filter(|(_, (_, start, end))| offset >= *start && offset < *end)
+        .min_by_key(|(_, (_, start, end))| end - start)
+        .map_or(0, |(i, _)| i)
+}
+
+fn detect_dagster(functions: &[PyFunction], file_path: &str) -> Vec<DataPipeline> {
+    let mut pipelines = Vec::new();
+
+    // Software-defined assets, grouped by `group_name` or else by module
+    let module = Path::new(file_path).file_stem().and_then(|s| s.to_str()).unwrap_or("assets").to_string();
+    let mut groups: BTreeMap<String, Vec<DataTask>> = BTreeMap::new();
+    for function in functions {
+        let Some(args) = function.decorator("asset") else { continue };
+        let name = kwarg(args, "name").or_else(|| kwarg(args, "key")).unwrap_or_else(|| function.name.clone());
+        let mut inputs: Vec<String> = split_top_level(&function.params).iter()
+            .map(|p| p.split([':', '=']).next().unwrap_or("").trim().to_string())
+            .filter(|p| !p.is_empty() && !p.starts_with('*') && p != "context" && p != "self")
+            .collect();
+        if let Some(deps) = list_kwarg(args, "deps") {
+            inputs.extend(split_top_level(deps).iter()
+                .map(|d| d.trim().trim_matches(['"', '\'']).to_string())
+                .filter(|d| !d.is_empty()));
+        }
+        groups.entry(kwarg(args, "group_name").unwrap_or_else(|| module.clone())).or_default().push(DataTask {
+            name: name.clone(),
+            kind: "asset".to_string(),
+            file_path: file_path.to_string(),
+            line_number: Some(function.line_number),
+            upstream: inputs.clone(),
+            inputs,
+            outputs: vec![name],
+            notebook: None,
+        });
+    }
+    for (name, tasks) in groups {
+        pipelines.push(DataPipeline {
+            orchestrator: DataOrchestrator::Dagster,
+            name,
+            file_path: file_path.to_string(),
+            line_number: tasks.first().and_then(|t| t.line_number),
+            schedule: None,
+            triggered_by: Vec::new(),
+            tasks,
+            warehouse: None,
+            sources: Vec::new(),
+            exposures: Vec::new(),
+        });
+    }
+
+    // Jobs and graphs composed of ops
+    let ops: HashMap<String, String> = functions.iter()
+        .filter_map(|f| f.decorator("op").map(|args| (f.name.clone(), kwarg(args, "name").unwrap_or_else(|| f.name.clone()))))
+        .collect();
+    pipelines.extend(composed_pipelines(functions, &["job", "graph"], &ops, "op", DataOrchestrator::Dagster, file_path));
+    pipelines
+}
+
+fn detect_prefect(functions: &[PyFunction], file_path: &str) -> Vec<DataPipeline> {
+    let tasks: HashMap<String, String> = functions.iter()
+        .filter_map(|f| f.decorator("task").map(|args| (f.name.clone(), kwarg(args, "name").unwrap_or_else(|| f.name.clone()))))
+        .collect();
+    composed_pipelines(functions, &["flow"], &tasks, "task", DataOrchestrator::Prefect, file_path)
+}
+
+/// Pipelines declared as a decorated function whose body calls the task functions
+fn composed_pipelines(
+    functions: &[PyFunction],
+    decorators: &[&str],
+    task_functions: &HashMap<String, String>,
+    kind: &str,
+    orchestrator: DataOrchestrator,
+    file_path: &str,
+) -> Vec<DataPipeline> {
+    let lines: HashMap<&str, usize> = functions.iter().map(|f| (f.name.as_str(), f.line_number)).collect();
+    let mut pipelines = Vec::new();
+    for function in functions {
+        let Some(args) = decorators.iter().find_map(|d| function.decorator(d)) else { continue };
+        let mut pipeline = DataPipeline {
+            orchestrator,
+            name: kwarg(args, "name").unwrap_or_else(|| function.name.clone()),
+            file_path: file_path.to_string(),
+            line_number: Some(function.line_number),
+            schedule: kwarg(args, "cron"),
+            triggered_by: Vec::new(),
+            tasks: Vec::new(),
+            warehouse: None,
+            sources: Vec::new(),
+            exposures: Vec::new(),
+        };
+        for (called, task_name, upstream) in wire_calls(&function.body, task_functions).0 {
+            let task = task_entry(&mut pipeline, &task_name, kind, file_path, lines.get(called.as_str()).copied());
+            task.upstream.extend(upstream);
+        }
+        pipelines.push(pipeline);
+    }
+    pipelines
+}
+
+/// The pipeline's task named `name`, added on first use
+fn task_entry<'a>(pipeline: &'a mut DataPipeline, name: &str, kind: &str, file_path: &str, line_number: Option<usize>) -> &'a mut DataTask {
+    let index = match pipeline.tasks.iter().position(|t| t.name == name) {
+        Some(index) => index,
+        None => {
+            pipeline.tasks.push(DataTask {
+                name: name.to_string(),
+                kind: kind.to_string(),
+                file_path: file_path.to_string(),
+                line_number,
+                upstream: Vec::new(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                notebook: None,
+            });
+            pipeline.tasks.len() - 1
+        }
+    };
+    &mut pipeline.tasks[index]
+}
+
+/// `(function, task name, upstream task names)` of a task function call
+type WiredCall = (String, String, Vec<String>);
+
+/// Calls of task functions in `code`, in order. A task is upstream of another when its call or the
+/// variable holding its result is an argument, as in `load(transform(extract()))` or
+/// `data = extract()` followed by `load(data)`. Also returns the variables holding task results.
+fn wire_calls(code: &str, task_functions: &HashMap<String, String>) -> (Vec<WiredCall>, HashMap<String, String>) {
+    let calls: Vec<(usize, usize, &str)> = PY_CALL.captures_iter(code)
+        .filter(|c| task_functions.contains_key(&c[1]))
+        // A task defined inside a DAG block is not a call of it
+        .filter(|c| !code[..c.get(0).unwrap().start()].trim_end().ends_with("def"))
+        .map(|c| {
+            let whole = c.get(0).unwrap();
+            (whole.start(), whole.end() - 1, c.get(1).unwrap().as_str())
+        })
+        .collect();
+    let mut variables: HashMap<String, String> = HashMap::new();
+    let mut wired: Vec<WiredCall> = Vec::new();
+    for &(start, open, function) in &calls {
+        let task = task_functions[function].clone();
+        let args = enclosed(code, open);
+        let close = open + args.len() + 1;
+        let mut upstream: Vec<String> = calls.iter()
+            .filter(|(other, _, _)| *other > open && *other < close)
+            .map(|(_, _, f)| task_functions[*f].clone())
+            .collect();
+        upstream.extend(PY_IDENT.find_iter(args).filter_map(|i| variables.get(i.as_str()).cloned()));
+
+        let nested = calls.iter().any(|&(other, other_open, _)| {
+            other < start && start < other_open + enclosed(code, other_open).len() + 1
+        });
+        let line_start = code[..start].rfind('\n').map_or(0, |i| i + 1);
+        if !nested {
+            if let Some(c) = ASSIGNED_TO.captures(&code[line_start..start]) {
+                variables.insert(c[1].to_string(), task.clone());
+            }
+        }
+        wired.push((function.to_string(), task, upstream));
+    }
+    (wired, variables)
+}
+
+/// `schedule=`/`schedule_interval=` of a DAG: a cron preset or expression, or the datasets that trigger it
+fn schedule_of(args: &str, dataset_variables: &HashMap<String, String>) -> (Option<String>, Vec<String>) {
+    static SCHEDULE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bschedule(?:_interval)?\s*=\s*(?:["']([^"']*)["']|(\[)|([^,\n]+))"#).unwrap());
+    let Some(c) = SCHEDULE.captures(args) else { return (None, Vec::new()) };
+    if let Some(value) = c.get(1) {
+        return (Some(value.as_str().to_string()), Vec::new());
+    }
+    if let Some(bracket) = c.get(2) {
+        let list = enclosed(args, bracket.start());
+        let mut datasets: Vec<String> = AIRFLOW_DATASET.captures_iter(list).map(|d| d[1].to_string()).collect();
+        datasets.extend(PY_IDENT.find_iter(list).filter_map(|i| dataset_variables.get(i.as_str()).cloned()));
+        return (Some("datasets".to_string()), datasets);
+    }
+    let expression = c.get(3).map(|v| v.as_str().trim().trim_end_matches(')').trim()).unwrap_or("");
+    if expression.is_empty() || expression == "None" {
+        (None, Vec::new())
+    } else {
+        (Some(expression.to_string()), Vec::new())
+    }
+}
+
+fn parse_functions(content: &str) -> Vec<PyFunction> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut functions = Vec::new();
+    let mut decorators: Vec<(String, String)> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some(c) = PY_DECORATOR.captures(line) {
+            let (text, end) = joined_until_balanced(&lines, i);
+            let args = text.find('(').map(|p| enclosed(&text, p).to_string()).unwrap_or_default();
+            decorators.push((c[1].to_string(), args));
+            i = end + 1;
+            continue;
+        }
+        if let Some(c) = PY_DEF.captures(line) {
+            let indent = c[1].len();
+            let (header, end) = joined_until_balanced(&lines, i);
+            let params = header.find('(').map(|p| enclosed(&header, p).to_string()).unwrap_or_default();
+            let mut body_end = end + 1;
+            while body_end < lines.len() && (lines[body_end].trim().is_empty() || indent_of(lines[body_end]) > indent) {
+                body_end += 1;
+            }
+            functions.push(PyFunction {
+                name: c[2].to_string(),
+                line_number: i + 1,
+                end_line: body_end,
+                decorators: std::mem::take(&mut decorators),
+                params,
+                body: lines[end + 1..body_end].join("\n"),
+            });
+            // Nested functions are picked up too, which is how TaskFlow tasks inside a @dag are found
+            i = end + 1;
+            continue;
+        }
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            decorators.clear();
+        }
+        i += 1;
+    }
+    functions
+}
+
+/// Lines from `start` joined until their parentheses balance, with the index of the last line used
+fn joined_until_balanced(lines: &[&str], start: usize) -> (String, usize) {
+    let mut text = lines[start].to_string();
+    let mut end = start;
+    while text.matches('(').count() > text.matches(')').count() && end + 1 < lines.len() {
+        end += 1;
+        text.push('\n');
+        text.push_str(lines[end]);
+    }
+    (text, end)
+}
+
+/// Text between the bracket at `open` and its matching close, skipping brackets inside strings
+fn enclosed(text: &str, open: usize) -> &str {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut quote: Option<u8> = None;
+    for (i, &b) in bytes.iter().enumerate().skip(open) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None => match b {
+                b'"' | b'\'' => quote = Some(b),
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return &text[open + 1..i];
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    &text[(open + 1).min(text.len())..]
+}
+
+/// Comma-separated items outside nested brackets and strings
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for c in text.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    items.push(std::mem::take(&mut current).trim().to_string());
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+    items.retain(|i| !i.is_empty());
+    items
+}
+
+fn kwarg(args: &str, key: &str) -> Option<String> {
+    Regex::new(&format!(r#"\b{}\s*=\s*["']([^"']+)["']"#, key)).ok()?
+        .captures(args)
+        .map(|c| c[1].to_string())
+}
+
+/// Items of an `inlets=[...]`, `outlets=[...]` or `deps=[...]` argument
+fn list_kwarg<'a>(args: &'a str, key: &str) -> Option<&'a str> {
+    LIST_KWARG.captures_iter(args)
+        .find(|c| &c[1] == key)
+        .map(|c| enclosed(args, c.get(0).unwrap().end() - 1))
+}
+
+/// A leading positional string argument
+fn first_string(args: &str) -> Option<String> {
+    static FIRST: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*["']([^"']+)["']"#).unwrap());
+    FIRST.captures(args).map(|c| c[1].to_string())
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+fn offset_of_line(content: &str, line_number: usize) -> usize {
+    content.split_inclusive('\n').take(line_number - 1).map(str::len).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_airflow_dbt_dagster_and_prefect_lineage() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "dags/orders.py", r#"
+from airflow import DAG
+from airflow.datasets import Dataset
+from airflow.operators.bash import BashOperator
+from airflow.providers.papermill.operators.papermill import PapermillOperator
+
+raw_orders = Dataset("s3://lake/raw/orders")
+
+with DAG("orders_daily", schedule="@daily") as dag:
+    extract = BashOperator(
+        task_id="extract_orders",
+        bash_command="extract.sh",
+        outlets=[raw_orders],
+    )
+    report = PapermillOperator(task_id="report", input_nb="notebooks/report.ipynb")
+    transform = BashOperator(task_id="transform", bash_command="dbt run")
+    extract >> transform >> report
+
+with DAG(dag_id="orders_refresh", schedule=[raw_orders]) as refresh:
+    refresh_cache = BashOperator(task_id="refresh_cache", bash_command="refresh.sh", inlets=[raw_orders])
+"#);
+        write(root, "warehouse/dbt_project.yml", "name: shop\nmodel-paths: [\"models\"]\n");
+        write(root, "warehouse/models/stg_orders.sql", "select * from {{ source('shop', 'orders') }}\n");
+        write(root, "warehouse/models/orders.sql", "{{ config(materialized='table') }}\nselect * from {{ ref('stg_orders') }}\n");
+        write(root, "assets/customers.py", r#"
+from dagster import asset
+
+@asset(group_name="crm")
+def raw_customers():
+    return fetch()
+
+@asset(group_name="crm")
+def customers(context, raw_customers):
+    return clean(raw_customers)
+"#);
+        write(root, "flows/etl.py", r#"
+from prefect import flow, task
+
+@task
+def extract():
+    return 1
+
+@task(name="load-warehouse")
+def load(data):
+    pass
+
+@flow(name="nightly-etl")
+def etl():
+    data = extract()
+    load(data)
+"#);
+
+        let pipelines = DataPipelineDetector::new().detect(temp_dir.path()).unwrap();
+        let find = |name: &str| pipelines.iter().find(|p| p.name == name).unwrap_or_else(|| panic!("missing pipeline {}", name));
+        let task = |pipeline: &DataPipeline, name: &str| pipeline.tasks.iter().find(|t| t.name == name).unwrap().clone();
+
+        let daily = find("orders_daily");
+        assert_eq!(daily.schedule.as_deref(), Some("@daily"));
+        assert_eq!(daily.tasks.len(), 3);
+        assert_eq!(task(daily, "transform").upstream, vec!["extract_orders"]);
+        assert_eq!(task(daily, "report").upstream, vec!["transform"]);
+        assert_eq!(task(daily, "report").notebook.as_deref(), Some("notebooks/report.ipynb"));
+        assert_eq!(task(daily, "extract_orders").outputs, vec!["s3://lake/raw/orders"]);
+        let refresh = find("orders_refresh");
+        assert_eq!(refresh.triggered_by, vec!["s3://lake/raw/orders"]);
+        assert_eq!(task(refresh, "refresh_cache").inputs, vec!["s3://lake/raw/orders"]);
+
+        let dbt = find("shop");
+        assert_eq!(dbt.orchestrator, DataOrchestrator::Dbt);
+        let orders = task(dbt, "orders");
+        assert_eq!(orders.kind, "model (table)");
+        assert_eq!(orders.upstream, vec!["stg_orders"]);
+        assert_eq!(task(dbt, "stg_orders").inputs, vec!["shop.orders"]);
+
+        let crm = find("crm");
+        assert_eq!(task(crm, "customers").upstream, vec!["raw_customers"]);
+        assert_eq!(task(crm, "customers").outputs, vec!["customers"]);
+
+        let etl = find("nightly-etl");
+        assert_eq!(etl.orchestrator, DataOrchestrator::Prefect);
+        assert_eq!(task(etl, "load-warehouse").upstream, vec!["extract"]);
+    }
+
+    #[test]
+    fn test_ignores_lookalike_imports_and_keeps_external_dependencies_as_inputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "scripts/notes.py", "import airflow_utils\nprint('airflow DAG(')\n");
+        write(root, "assets/events.py", "from dagster import asset\n\n@asset(deps=[\"raw_events\"])\ndef sessions(context):\n    return build()\n");
+        write(root, "flows/cleanup.py", "from prefect import flow, task\n\n@task\ndef purge():\n    pass\n\ndef helper():\n    pass\n\n@flow\ndef cleanup():\n    helper()\n    purge()\n");
+
+        let pipelines = DataPipelineDetector::new().detect(root).unwrap();
+        let mut names: Vec<(&str, Vec<&str>)> = pipelines.iter()
+            .map(|p| (p.name.as_str(), p.tasks.iter().map(|t| t.name.as_str()).collect()))
+            .collect();
+        names.sort();
+        // Assets without a group are grouped by module, and plain helpers are not tasks
+        assert_eq!(names, vec![("cleanup", vec!["purge"]), ("events", vec!["sessions"])]);
+
+        let sessions = &pipelines.iter().find(|p| p.name == "events").unwrap().tasks[0];
+        assert!(sessions.upstream.is_empty());
+        assert_eq!(sessions.inputs, vec!["raw_events"]);
+        assert_eq!(sessions.outputs, vec!["sessions"]);
+    }
+}
@@ -0,0 +1,389 @@
+//! dbt projects: models, seeds and snapshots with their `ref()` lineage, the sources they read,
+//! the exposures (dashboards, notebooks, applications) built on them and the warehouse adapter.
+//! A compiled `target/manifest.json` is used when the project has one, since it has every node
+//! resolved; otherwise the SQL files and the `.yml` property files are read.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::data_pipeline::{DataExposure, DataOrchestrator, DataPipeline, DataSource, DataTask};
+use crate::analysis::repo_path::RepoPath;
+
+static DBT_REF: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bref\(\s*["']([^"']+)["'](?:\s*,\s*["']([^"']+)["'])?"#).unwrap());
+static DBT_SOURCE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bsource\(\s*["']([^"']+)["']\s*,\s*["']([^"']+)["']"#).unwrap());
+static DBT_MATERIALIZED: Lazy<Regex> = Lazy::new(|| Regex::new(r#"materialized\s*=\s*["'](\w+)["']"#).unwrap());
+static DBT_SNAPSHOT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{%-?\s*snapshot\s+(\w+)").unwrap());
+static ADAPTER_PACKAGE: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"\bdbt-(snowflake|bigquery|redshift|postgres|databricks|spark|duckdb|trino|sqlserver|synapse|fabric|clickhouse|athena|glue)\b"
+).unwrap());
+
+/// Where the adapter package is installed from, next to the project or at the repository root
+const REQUIREMENT_FILES: &[&str] = &["requirements.txt", "pyproject.toml", "Pipfile", "setup.py"];
+
+/// The project declared by `dbt_project.yml` at `project_file`
+pub(crate) fn detect_project(repo_path: &Path, project_file: &Path, file_path: &str) -> Option<DataPipeline> {
+    let project: serde_yaml::Value = serde_yaml::from_str(&std::fs::read_to_string(project_file).ok()?).ok()?;
+    let project_dir = project_file.parent()?;
+    let name = project.get("name").and_then(|n| n.as_str()).unwrap_or("dbt").to_string();
+    let target_path = project.get("target-path").and_then(|p| p.as_str()).unwrap_or("target");
+    let manifest: Option<Value> = std::fs::read_to_string(project_dir.join(target_path).join("manifest.json")).ok()
+        .and_then(|m| serde_json::from_str(&m).ok());
+
+    let mut pipeline = DataPipeline {
+        orchestrator: DataOrchestrator::Dbt,
+        name,
+        file_path: file_path.to_string(),
+        line_number: None,
+        schedule: None,
+        triggered_by: Vec::new(),
+        tasks: Vec::new(),
+        warehouse: None,
+        sources: Vec::new(),
+        exposures: Vec::new(),
+    };
+    match &manifest {
+        Some(manifest) => read_manifest(repo_path, project_dir, manifest, &mut pipeline),
+        None => read_project_files(repo_path, project_dir, &project, &mut pipeline),
+    }
+    pipeline.warehouse = manifest.as_ref()
+        .and_then(|m| m["metadata"]["adapter_type"].as_str().map(String::from))
+        .or_else(|| profile_adapter(project_dir, &project))
+        .or_else(|| installed_adapter(repo_path, project_dir));
+    Some(pipeline)
+}
+
+/// Nodes, sources and exposures of the project itself; those of installed packages stay visible
+/// as the datasets the project reads
+fn read_manifest(repo_path: &Path, project_dir: &Path, manifest: &Value, pipeline: &mut DataPipeline) {
+    let project_name = manifest["metadata"]["project_name"].as_str().unwrap_or(&pipeline.name).to_string();
+    let own = |node: &Value| node["package_name"].as_str().is_none_or(|p| p == project_name);
+    let file_of = |node: &Value| {
+        let original = node["original_file_path"].as_str().unwrap_or("");
+        RepoPath::new(repo_path, &project_dir.join(original)).into_string()
+    };
+    // `model.shop.orders` -> `orders`, `source.shop.raw.orders` -> `raw.orders`
+    let dataset_of = |unique_id: &str| -> Option<(String, bool)> {
+        if let Some(source) = manifest["sources"].get(unique_id) {
+            return Some((format!("{}.{}", source["source_name"].as_str()?, source["name"].as_str()?), false));
+        }
+        let node = manifest["nodes"].get(unique_id)?;
+        matches!(node["resource_type"].as_str(), Some("model" | "seed" | "snapshot")).then(|| (node["name"].as_str().unwrap_or_default().to_string(), true))
+    };
+    let depends_on = |node: &Value| -> Vec<(String, bool)> {
+        node["depends_on"]["nodes"].as_array().into_iter().flatten()
+            .filter_map(|id| id.as_str().and_then(dataset_of))
+            .collect()
+    };
+
+    let mut nodes: Vec<(&String, &Value)> = manifest["nodes"].as_object().into_iter().flatten().collect();
+    nodes.sort_by_key(|(id, _)| id.as_str());
+    for (_, node) in nodes {
+        let Some(resource_type @ ("model" | "seed" | "snapshot")) = node["resource_type"].as_str() else { continue };
+        if !own(node) {
+            continue;
+        }
+        let name = node["name"].as_str().unwrap_or_default().to_string();
+        let kind = match node["config"]["materialized"].as_str() {
+            Some(materialized) if resource_type == "model" => format!("model ({})", materialized),
+            _ => resource_type.to_string(),
+        };
+        let inputs = depends_on(node);
+        pipeline.tasks.push(DataTask {
+            name: name.clone(),
+            kind,
+            file_path: file_of(node),
+            line_number: None,
+            upstream: inputs.iter().filter(|(_, is_node)| *is_node).map(|(d, _)| d.clone()).collect(),
+            inputs: inputs.into_iter().map(|(d, _)| d).collect(),
+            outputs: vec![name],
+            notebook: None,
+        });
+    }
+
+    for source in manifest["sources"].as_object().into_iter().flatten().map(|(_, s)| s).filter(|s| own(s)) {
+        let (Some(source_name), Some(table)) = (source["source_name"].as_str(), source["name"].as_str()) else { continue };
+        let relation = source["relation_name"].as_str()
+            .map(|r| r.replace(['"', '`'], ""))
+            .or_else(|| relation(&[source["database"].as_str(), source["schema"].as_str(), source["identifier"].as_str()]));
+        pipeline.sources.push(DataSource {
+            name: format!("{}.{}", source_name, table),
+            relation,
+            loader: source["loader"].as_str().filter(|l| !l.is_empty()).map(String::from),
+            file_path: file_of(source),
+        });
+    }
+
+    for exposure in manifest["exposures"].as_object().into_iter().flatten().map(|(_, e)| e).filter(|e| own(e)) {
+        let Some(name) = exposure["name"].as_str() else { continue };
+        pipeline.exposures.push(DataExposure {
+            name: name.to_string(),
+            kind: exposure["type"].as_str().unwrap_or("dashboard").to_string(),
+            owner: exposure["owner"]["name"].as_str().or(exposure["owner"]["email"].as_str()).map(String::from),
+            url: exposure["url"].as_str().map(String::from),
+            depends_on: depends_on(exposure).into_iter().map(|(d, _)| d).collect(),
+            file_path: file_of(exposure),
+        });
+    }
+    pipeline.sources.sort_by(|a, b| a.name.cmp(&b.name));
+    pipeline.exposures.sort_by(|a, b| a.name.cmp(&b.name));
+}
+
+/// Models, seeds and snapshots from their files, sources and exposures from the property files
+/// next to the models
+fn read_project_files(repo_path: &Path, project_dir: &Path, project: &serde_yaml::Value, pipeline: &mut DataPipeline) {
+    let paths = |key: &str, default: &str| -> Vec<String> {
+        project.get(key).and_then(|v| v.as_sequence())
+            .map(|s| s.iter().filter_map(|p| p.as_str().map(String::from)).collect())
+            .unwrap_or_else(|| vec![default.to_string()])
+    };
+
+    for (kind, dirs) in [("model", paths("model-paths", "models")), ("seed", paths("seed-paths", "seeds")), ("snapshot", paths("snapshot-paths", "snapshots"))] {
+        for dir in dirs {
+            for entry in WalkDir::new(project_dir.join(dir)).sort_by_file_name().into_iter().filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+                let path = entry.path();
+                let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+                let task_path = RepoPath::new(repo_path, path).into_string();
+                if extension == "yml" || extension == "yaml" {
+                    if kind == "model" {
+                        read_properties(path, &task_path, pipeline);
+                    }
+                    continue;
+                }
+                if kind == "seed" {
+                    if extension == "csv" {
+                        pipeline.tasks.push(dbt_task(&stem, "seed", &task_path, ""));
+                    }
+                    continue;
+                }
+                if extension != "sql" {
+                    continue;
+                }
+                let Ok(sql) = std::fs::read_to_string(path) else { continue };
+                if kind == "snapshot" {
+                    for c in DBT_SNAPSHOT.captures_iter(&sql) {
+                        pipeline.tasks.push(dbt_task(&c[1], "snapshot", &task_path, &sql));
+                    }
+                } else {
+                    pipeline.tasks.push(dbt_task(&stem, kind, &task_path, &sql));
+                }
+            }
+        }
+    }
+}
+
+/// `sources:` and `exposures:` of a property file
+fn read_properties(path: &Path, file_path: &str, pipeline: &mut DataPipeline) {
+    let Some(properties) = std::fs::read_to_string(path).ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok()) else { return };
+    let text = |value: &serde_yaml::Value, key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    for source in properties.get("sources").and_then(|s| s.as_sequence()).into_iter().flatten() {
+        let Some(source_name) = text(source, "name") else { continue };
+        // The schema defaults to the source's name
+        let schema = text(source, "schema").unwrap_or_else(|| source_name.clone());
+        for table in source.get("tables").and_then(|t| t.as_sequence()).into_iter().flatten() {
+            let Some(table_name) = text(table, "name") else { continue };
+            let identifier = text(table, "identifier").unwrap_or_else(|| table_name.clone());
+            pipeline.sources.push(DataSource {
+                name: format!("{}.{}", source_name, table_name),
+                relation: relation(&[text(source, "database").as_deref(), Some(&schema), Some(&identifier)]),
+                loader: text(source, "loader"),
+                file_path: file_path.to_string(),
+            });
+        }
+    }
+
+    for exposure in properties.get("exposures").and_then(|e| e.as_sequence()).into_iter().flatten() {
+        let Some(name) = text(exposure, "name") else { continue };
+        let owner = exposure.get("owner").and_then(|o| text(o, "name").or_else(|| text(o, "email")));
+        let depends_on = exposure.get("depends_on").and_then(|d| d.as_sequence()).into_iter().flatten()
+            .filter_map(|d| d.as_str())
+            .flat_map(|d| {
+                let (refs, sources) = references(d);
+                sources.into_iter().chain(refs)
+            })
+            .collect();
+        pipeline.exposures.push(DataExposure {
+            name,
+            kind: text(exposure, "type").unwrap_or_else(|| "dashboard".to_string()),
+            owner,
+            url: text(exposure, "url"),
+            depends_on,
+            file_path: file_path.to_string(),
+        });
+    }
+}
+
+/// Models named by `ref()` and `name.table` of sources named by `source()`
+fn references(text: &str) -> (Vec<String>, Vec<String>) {
+    // `ref('package', 'model')` names the model last
+    let refs = DBT_REF.captures_iter(text)
+        .map(|c| c.get(2).unwrap_or_else(|| c.get(1).unwrap()).as_str().to_string())
+        .collect();
+    let sources = DBT_SOURCE.captures_iter(text).map(|c| format!("{}.{}", &c[1], &c[2])).collect();
+    (refs, sources)
+}
+
+fn dbt_task(name: &str, kind: &str, file_path: &str, sql: &str) -> DataTask {
+    let (refs, mut inputs) = references(sql);
+    inputs.extend(refs.iter().cloned());
+    let kind = match DBT_MATERIALIZED.captures(sql) {
+        Some(c) if kind == "model" => format!("model ({})", &c[1]),
+        _ => kind.to_string(),
+    };
+    DataTask {
+        name: name.to_string(),
+        kind,
+        file_path: file_path.to_string(),
+        line_number: None,
+        upstream: refs,
+        inputs,
+        outputs: vec![name.to_string()],
+        notebook: None,
+    }
+}
+
+/// `database.schema.identifier`, leaving out the parts that are not declared
+fn relation(parts: &[Option<&str>]) -> Option<String> {
+    let parts: Vec<&str> = parts.iter().flatten().copied().filter(|p| !p.is_empty()).collect();
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
+/// The adapter `type` of the project's profile in a `profiles.yml` kept with the project
+fn profile_adapter(project_dir: &Path, project: &serde_yaml::Value) -> Option<String> {
+    let profiles: serde_yaml::Value = serde_yaml::from_str(&std::fs::read_to_string(project_dir.join("profiles.yml")).ok()?).ok()?;
+    let profile_name = project.get("profile").and_then(|p| p.as_str())
+        .or_else(|| project.get("name").and_then(|n| n.as_str()))?;
+    let profile = profiles.get(profile_name)?;
+    let outputs = profile.get("outputs")?;
+    let target = profile.get("target").and_then(|t| t.as_str());
+    let output = target.and_then(|t| outputs.get(t))
+        .or_else(|| outputs.as_mapping().and_then(|m| m.values().next()))?;
+    output.get("type").and_then(|t| t.as_str()).map(String::from)
+}
+
+/// The adapter from a `dbt-<adapter>` requirement
+fn installed_adapter(repo_path: &Path, project_dir: &Path) -> Option<String> {
+    let mut dirs = vec![project_dir];
+    if project_dir != repo_path {
+        dirs.push(repo_path);
+    }
+    dirs.into_iter()
+        .flat_map(|dir| REQUIREMENT_FILES.iter().map(move |f| dir.join(f)))
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find_map(|content| ADAPTER_PACKAGE.captures(&content).map(|c| c[1].to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_reads_sources_exposures_and_warehouse_from_files_and_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "shop/dbt_project.yml", "name: shop\nprofile: warehouse\n");
+        write(root, "shop/profiles.yml", "warehouse:\n  target: prod\n  outputs:\n    dev: {type: duckdb}\n    prod: {type: snowflake, account: acme}\n");
+        write(root, "shop/models/orders.sql", "select * from {{ source('stripe', 'charges') }}\n");
+        write(root, "shop/models/schema.yml", r#"
+sources:
+  - name: stripe
+    database: raw
+    loader: fivetran
+    tables:
+      - name: charges
+        identifier: stripe_charges
+exposures:
+  - name: revenue_dashboard
+    type: dashboard
+    url: https://bi.acme.com/revenue
+    owner: {name: Finance, email: finance@acme.com}
+    depends_on:
+      - ref('orders')
+"#);
+        let project = temp_dir.path().join("shop/dbt_project.yml");
+        let shop = detect_project(temp_dir.path(), &project, "shop/dbt_project.yml").unwrap();
+        assert_eq!(shop.warehouse.as_deref(), Some("snowflake"));
+        assert_eq!(shop.sources.len(), 1);
+        assert_eq!(shop.sources[0].name, "stripe.charges");
+        assert_eq!(shop.sources[0].relation.as_deref(), Some("raw.stripe.stripe_charges"));
+        assert_eq!(shop.sources[0].loader.as_deref(), Some("fivetran"));
+        assert_eq!(shop.exposures[0].depends_on, vec!["orders"]);
+        assert_eq!(shop.exposures[0].owner.as_deref(), Some("Finance"));
+
+        write(root, "shop/target/manifest.json", r#"{
+  "metadata": {"project_name": "shop", "adapter_type": "bigquery"},
+  "nodes": {
+    "model.shop.stg_charges": {"resource_type": "model", "name": "stg_charges", "package_name": "shop",
+      "original_file_path": "models/staging/stg_charges.sql", "config": {"materialized": "view"},
+      "depends_on": {"nodes": ["source.shop.stripe.charges"]}},
+    "model.shop.revenue": {"resource_type": "model", "name": "revenue", "package_name": "shop",
+      "original_file_path": "models/revenue.sql", "config": {"materialized": "table"},
+      "depends_on": {"nodes": ["model.shop.stg_charges", "model.utils.calendar"]}},
+    "model.utils.calendar": {"resource_type": "model", "name": "calendar", "package_name": "utils",
+      "original_file_path": "models/calendar.sql", "config": {"materialized": "table"}, "depends_on": {"nodes": []}},
+    "test.shop.not_null_revenue_id": {"resource_type": "test", "name": "not_null_revenue_id", "package_name": "shop",
+      "depends_on": {"nodes": ["model.shop.revenue"]}}
+  },
+  "sources": {
+    "source.shop.stripe.charges": {"source_name": "stripe", "name": "charges", "package_name": "shop",
+      "relation_name": "`raw`.`stripe`.`charges`", "original_file_path": "models/schema.yml", "loader": ""}
+  },
+  "exposures": {
+    "exposure.shop.revenue_dashboard": {"name": "revenue_dashboard", "type": "dashboard", "package_name": "shop",
+      "owner": {"email": "finance@acme.com"}, "original_file_path": "models/schema.yml",
+      "depends_on": {"nodes": ["model.shop.revenue"]}}
+  }
+}"#);
+        let shop = detect_project(temp_dir.path(), &project, "shop/dbt_project.yml").unwrap();
+        assert_eq!(shop.warehouse.as_deref(), Some("bigquery"));
+        assert_eq!(shop.tasks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["revenue", "stg_charges"]);
+        let revenue = &shop.tasks[0];
+        assert_eq!(revenue.kind, "model (table)");
+        assert_eq!(revenue.file_path, "shop/models/revenue.sql");
+        assert_eq!(revenue.upstream, vec!["stg_charges", "calendar"]);
+        assert_eq!(shop.tasks[1].inputs, vec!["stripe.charges"]);
+        assert_eq!(shop.sources[0].relation.as_deref(), Some("raw.stripe.charges"));
+        assert_eq!(shop.sources[0].loader, None);
+        assert_eq!(shop.exposures[0].owner.as_deref(), Some("finance@acme.com"));
+        assert_eq!(shop.exposures[0].depends_on, vec!["revenue"]);
+    }
+
+    #[test]
+    fn test_falls_back_to_files_when_the_manifest_is_unreadable() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "requirements.txt", "dbt-postgres==1.7.0\n");
+        write(root, "analytics/dbt_project.yml", "model-paths: [\"transform\"]\n");
+        write(root, "analytics/target/manifest.json", "{ not json");
+        write(root, "analytics/transform/daily.sql", "select * from {{ ref('utils', 'calendar') }} join {{ source('app', 'users') }}\n");
+        write(root, "analytics/transform/props.yml", "sources:\n  - name: app\n    tables:\n      - name: users\n");
+        write(root, "analytics/seeds/countries.csv", "code\nUS\n");
+        write(root, "analytics/seeds/README.md", "Reference data\n");
+        write(root, "analytics/snapshots/history.sql", "{% snapshot users_history %}\nselect * from {{ source('app', 'users') }}\n{% endsnapshot %}\n");
+        write(root, "broken/dbt_project.yml", "name: [unclosed\n");
+
+        assert!(detect_project(root, &root.join("broken/dbt_project.yml"), "broken/dbt_project.yml").is_none());
+
+        let project = detect_project(root, &root.join("analytics/dbt_project.yml"), "analytics/dbt_project.yml").unwrap();
+        assert_eq!(project.name, "dbt");
+        assert_eq!(project.warehouse.as_deref(), Some("postgres"));
+        let tasks: Vec<(&str, &str, Vec<&str>, Vec<&str>)> = project.tasks.iter()
+            .map(|t| (t.name.as_str(), t.kind.as_str(), t.upstream.iter().map(String::as_str).collect(), t.inputs.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(tasks, vec![
+            ("daily", "model", vec!["calendar"], vec!["app.users", "calendar"]),
+            ("countries", "seed", vec![], vec![]),
+            ("users_history", "snapshot", vec![], vec!["app.users"]),
+        ]);
+        // Without a schema the source's name is the schema
+        assert_eq!(project.sources.len(), 1);
+        assert_eq!(project.sources[0].relation.as_deref(), Some("app.users"));
+        assert!(project.exposures.is_empty());
+    }
+}
@@ -0,0 +1,434 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use crate::analysis::{CodeCall, CodeElement, DataStoreUse};
+use crate::analysis::service_boundaries::directory_unit;
+use crate::analysis::utils::resolve_local_imports;
+
+/// An endpoint and the file whose handler serves it
+#[derive(Debug, Clone)]
+pub struct EndpointUse {
+    pub name: String, // `GET /orders`
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionCandidate {
+    pub name: String,
+    pub paths: Vec<String>, // Module directories the community spans
+    pub file_count: usize,
+    pub element_count: usize,
+    pub endpoints: Vec<String>,
+    pub tables: Vec<String>,        // Only this candidate touches them
+    pub shared_tables: Vec<String>, // Also read or written elsewhere
+    pub data_stores: Vec<String>,
+    pub internal_references: usize,
+    pub afferent_coupling: usize, // References into the candidate from the rest of the code
+    pub efferent_coupling: usize, // References from the candidate out to the rest
+    pub instability: f64,         // Efferent / (afferent + efferent)
+    pub cohesion: f64,            // Internal / (internal + efferent)
+    pub depends_on: Vec<String>,
+    pub used_by: Vec<String>,
+    pub score: f64,
+    pub steps: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedTable {
+    pub table: String,
+    pub writers: Vec<String>,
+    pub readers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecompositionReport {
+    /// Newman modularity of the detected communities; above ~0.3 the code already splits cleanly
+    pub modularity: f64,
+    pub candidates: Vec<ExtractionCandidate>,
+    pub shared_tables: Vec<SharedTable>,
+}
+
+static SQL_WRITE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:insert\s+into|delete\s+from|merge\s+into|update)\s+[`"\[]?(?:\w+[`"\]]?\.[`"\[]?)?([a-z_]\w*)[`"\]]?(?:\s|\(|$)"#).unwrap()
+});
+static SQL_UPDATE_SET: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bupdate\s+\S+\s+set\b").unwrap());
+static SQL_READ: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)\bselect\b.{1,300}?\bfrom\s+[`"\[]?(?:\w+[`"\]]?\.[`"\[]?)?([a-z_]\w*)|(?i)\bjoin\s+[`"\[]?([a-z_]\w*)[`"\]]?\s+(?:as\s+)?\w*\s*on\b"#).unwrap()
+});
+/// ORM mappings, counted as writes since the model owns its table: SQLAlchemy, JPA, Django, Rails, TypeORM, Sequelize
+static ORM_TABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"__tablename__\s*=\s*['"](\w+)|@Table\s*\(\s*name\s*=\s*"(\w+)"|db_table\s*=\s*['"](\w+)|self\.table_name\s*=\s*['"](\w+)|@Entity\s*\(\s*['"](\w+)|tableName\s*:\s*['"](\w+)"#).unwrap()
+});
+const NOT_TABLES: &[&str] = &["select", "where", "set", "values", "the", "a", "an", "dual", "information_schema", "pg_catalog", "sqlite_master", "lateral", "unnest", "json_each"];
+
+/// Groups files into communities by how they reference each other (label propagation seeded with
+/// the module directories), then proposes each community as a service extraction with its
+/// endpoints, database tables and coupling to the rest of the code
+pub struct DecompositionAdvisor;
+
+impl DecompositionAdvisor {
+    pub fn new() -> Self {
+        DecompositionAdvisor
+    }
+
+    pub fn analyze(
+        &self,
+        repo_path: &Path,
+        elements: &[CodeElement],
+        calls: &[CodeCall],
+        endpoints: &[EndpointUse],
+        data_stores: &[DataStoreUse],
+    ) -> DecompositionReport {
+        let files: Vec<&str> = elements.iter()
+            .map(|e| e.file_path.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if files.len() < 2 {
+            return DecompositionReport::default();
+        }
+        let index: HashMap<&str, usize> = files.iter().enumerate().map(|(i, f)| (*f, i)).collect();
+
+        // Undirected file graph weighted by calls and imports; directed counts kept for coupling
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+        let element_files: HashMap<&str, usize> = elements.iter()
+            .map(|e| (e.id.as_str(), index[e.file_path.as_str()]))
+            .collect();
+        for call in calls {
+            if let (Some(&from), Some(&to)) = (element_files.get(call.caller_id.as_str()), element_files.get(call.callee_id.as_str())) {
+                *directed.entry((from, to)).or_default() += 1;
+            }
+        }
+        let mut tables: BTreeMap<String, (BTreeSet<usize>, BTreeSet<usize>)> = BTreeMap::new(); // table -> (writers, readers)
+        for (i, file) in files.iter().enumerate() {
+            let Ok(content) = std::fs::read_to_string(repo_path.join(file)) else { continue };
+            for (_, target) in resolve_local_imports(&content, file, &files) {
+                *directed.entry((i, index[target])).or_default() += 1;
+            }
+            if !file.to_lowercase().contains("migration") {
+                for (table, write) in table_accesses(&content) {
+                    let entry = tables.entry(table).or_default();
+                    if write { entry.0.insert(i) } else { entry.1.insert(i) };
+                }
+            }
+        }
+        let mut neighbors: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); files.len()];
+        for (&(from, to), &count) in &directed {
+            if from != to {
+                *neighbors[from].entry(to).or_default() += count as f64;
+                *neighbors[to].entry(from).or_default() += count as f64;
+            }
+        }
+
+        let seeds: Vec<String> = files.iter().map(|f| directory_unit(f)).collect();
+        let labels = propagate_labels(&neighbors, &seeds);
+        let modularity = modularity(&neighbors, &labels);
+
+        // Each community is named after the module most of its files come from
+        let mut communities: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, label) in labels.iter().enumerate() {
+            communities.entry(label.as_str()).or_default().push(i);
+        }
+        let community_of: Vec<&str> = labels.iter().map(|l| l.as_str()).collect();
+        let name_of = |label: &str| if label.is_empty() { "(root)".to_string() } else { label.to_string() };
+
+        let mut table_owners: BTreeMap<&str, (BTreeSet<&str>, BTreeSet<&str>)> = BTreeMap::new();
+        for (table, (writers, readers)) in &tables {
+            let entry = table_owners.entry(table.as_str()).or_default();
+            entry.0.extend(writers.iter().map(|f| community_of[*f]));
+            entry.1.extend(readers.iter().map(|f| community_of[*f]));
+        }
+        let shared_tables: Vec<SharedTable> = table_owners.iter()
+            .filter(|(_, (w, r))| w.union(r).count() > 1)
+            .map(|(table, (w, r))| SharedTable {
+                table: table.to_string(),
+                writers: w.iter().map(|c| name_of(c)).collect(),
+                readers: r.difference(w).map(|c| name_of(c)).collect(),
+            })
+            .collect();
+
+        let mut candidates = Vec::new();
+        for (label, members) in &communities {
+            if label.is_empty() || members.len() < 2 {
+                continue;
+            }
+            let member_set: BTreeSet<usize> = members.iter().copied().collect();
+            let mut internal = 0;
+            let mut afferent = 0;
+            let mut efferent = 0;
+            let mut depends_on: BTreeMap<String, usize> = BTreeMap::new();
+            let mut used_by: BTreeMap<String, usize> = BTreeMap::new();
+            for (&(from, to), &count) in &directed {
+                match (member_set.contains(&from), member_set.contains(&to)) {
+                    (true, true) => internal += count,
+                    (true, false) => {
+                        efferent += count;
+                        *depends_on.entry(name_of(community_of[to])).or_default() += count;
+                    }
+                    (false, true) => {
+                        afferent += count;
+                        *used_by.entry(name_of(community_of[from])).or_default() += count;
+                    }
+                    _ => {}
+                }
+            }
+
+            let touched: Vec<&str> = table_owners.iter()
+                .filter(|(_, (w, r))| w.contains(label) || r.contains(label))
+                .map(|(t, _)| *t)
+                .collect();
+            let (shared, owned): (Vec<&str>, Vec<&str>) = touched.into_iter()
+                .partition(|t| shared_tables.iter().any(|s| s.table == *t));
+            let candidate_endpoints: Vec<String> = endpoints.iter()
+                .filter(|e| index.get(e.file_path.as_str()).map(|i| member_set.contains(i)).unwrap_or(false))
+                .map(|e| e.name.clone())
+                .collect();
+            let stores: BTreeSet<String> = data_stores.iter()
+                .filter(|s| index.get(s.file_path.as_str()).map(|i| member_set.contains(i)).unwrap_or(false))
+                .map(|s| s.name.clone())
+                .collect();
+            let paths: BTreeSet<String> = members.iter()
+                .map(|i| if seeds[*i].is_empty() { ".".to_string() } else { seeds[*i].clone() })
+                .collect();
+
+            let ratio = |a: usize, b: usize| if a + b == 0 { 0.0 } else { a as f64 / (a + b) as f64 };
+            let cohesion = if internal + efferent == 0 { 1.0 } else { ratio(internal, efferent) };
+            let instability = ratio(efferent, afferent);
+            let exclusivity = if owned.is_empty() && shared.is_empty() { 1.0 } else { ratio(owned.len(), shared.len()) };
+            let mut score = 0.5 * cohesion + 0.25 * exclusivity + 0.1 * (1.0 - ratio(afferent, internal));
+            if !candidate_endpoints.is_empty() {
+                score += 0.15;
+            }
+
+            let name = name_of(label);
+            let mut steps = Vec::new();
+            if !candidate_endpoints.is_empty() {
+                steps.push(format!("Serve its {} endpoint(s) from the new service and route them there", candidate_endpoints.len()));
+            }
+            for table in &shared {
+                let others: Vec<String> = shared_tables.iter()
+                    .find(|s| s.table == *table)
+                    .map(|s| s.writers.iter().chain(&s.readers).filter(|c| **c != name).cloned().collect())
+                    .unwrap_or_default();
+                steps.push(format!("Give table `{}` a single owner; {} also use it and would go through an API or events", table, others.join(", ")));
+            }
+            if !owned.is_empty() {
+                steps.push(format!("Move table(s) {} into the service's own database", owned.join(", ")));
+            } else if shared.is_empty() {
+                steps.push("No database tables found, so it can move without a data migration".to_string());
+            }
+            let mut outgoing: Vec<(String, usize)> = depends_on.clone().into_iter().collect();
+            outgoing.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            for (target, count) in outgoing.iter().take(3) {
+                steps.push(format!("Replace {} reference(s) into {} with calls to its API, or copy what is shared", count, target));
+            }
+            if afferent > 0 {
+                steps.push(format!("Publish an API or events for the {} reference(s) from {}", afferent, used_by.keys().cloned().collect::<Vec<_>>().join(", ")));
+            }
+
+            candidates.push(ExtractionCandidate {
+                name,
+                paths: paths.into_iter().collect(),
+                file_count: members.len(),
+                element_count: elements.iter().filter(|e| member_set.contains(&index[e.file_path.as_str()])).count(),
+                endpoints: candidate_endpoints,
+                tables: owned.iter().map(|t| t.to_string()).collect(),
+                shared_tables: shared.iter().map(|t| t.to_string()).collect(),
+                data_stores: stores.into_iter().collect(),
+                internal_references: internal,
+                afferent_coupling: afferent,
+                efferent_coupling: efferent,
+                instability: round2(instability),
+                cohesion: round2(cohesion),
+                depends_on: outgoing.into_iter().map(|(n, _)| n).collect(),
+                used_by: used_by.into_keys().collect(),
+                score: round2(score.clamp(0.0, 1.0)),
+                steps,
+            });
+        }
+
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name)));
+        DecompositionReport {
+            modularity: round2(modularity),
+            candidates,
+            shared_tables,
+        }
+    }
+}
+
+/// `(table, is_write)` for SQL statements in string literals and ORM table mappings
+fn table_accesses(content: &str) -> Vec<(String, bool)> {
+    let mut found = Vec::new();
+    for cap in ORM_TABLE.captures_iter(content) {
+        if let Some(table) = cap.iter().skip(1).flatten().next() {
+            found.push((table.as_str().to_lowercase(), true));
+        }
+    }
+    for line in content.lines().filter(|l| l.contains(['"', '\'', '`'])) {
+        for cap in SQL_WRITE.captures_iter(line) {
+            // A bare `update x` is only SQL when followed by `set`
+            if cap[0].trim_start().to_lowercase().starts_with("update") && !SQL_UPDATE_SET.is_match(line) {
+                continue;
+            }
+            found.push((cap[1].to_lowercase(), true));
+        }
+    }
+    for cap in SQL_READ.captures_iter(content) {
+        if let Some(table) = cap.get(1).or(cap.get(2)) {
+            found.push((table.as_str().to_lowercase(), false));
+        }
+    }
+    found.retain(|(t, _)| !NOT_TABLES.contains(&t.as_str()));
+    found
+}
+
+/// Each file takes the label with the most reference weight among its neighbours, counting its
+/// own module with weight one so loosely connected files stay with their directory
+fn propagate_labels(neighbors: &[BTreeMap<usize, f64>], seeds: &[String]) -> Vec<String> {
+    let mut labels = seeds.to_vec();
+    for _ in 0..20 {
+        let mut changed = false;
+        for node in 0..labels.len() {
+            let mut weights: BTreeMap<&str, f64> = BTreeMap::new();
+            *weights.entry(seeds[node].as_str()).or_default() += 1.0;
+            for (other, weight) in &neighbors[node] {
+                *weights.entry(labels[*other].as_str()).or_default() += weight;
+            }
+            let best = weights.iter()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal)
+                    // Ties keep the current label, then the alphabetically first
+                    .then_with(|| (*a.0 == labels[node]).cmp(&(*b.0 == labels[node])))
+                    .then_with(|| b.0.cmp(a.0)))
+                .map(|(label, _)| label.to_string())
+                .unwrap_or_default();
+            if best != labels[node] {
+                labels[node] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    labels
+}
+
+fn modularity(neighbors: &[BTreeMap<usize, f64>], labels: &[String]) -> f64 {
+    let degree: Vec<f64> = neighbors.iter().map(|n| n.values().sum()).collect();
+    let two_m: f64 = degree.iter().sum();
+    if two_m == 0.0 {
+        return 0.0;
+    }
+    let mut inside: HashMap<&str, f64> = HashMap::new();
+    let mut total: HashMap<&str, f64> = HashMap::new();
+    for (node, edges) in neighbors.iter().enumerate() {
+        *total.entry(labels[node].as_str()).or_default() += degree[node];
+        for (other, weight) in edges {
+            if labels[*other] == labels[node] {
+                *inside.entry(labels[node].as_str()).or_default() += weight;
+            }
+        }
+    }
+    total.iter()
+        .map(|(label, degree_sum)| inside.get(label).copied().unwrap_or(0.0) / two_m - (degree_sum / two_m).powi(2))
+        .sum()
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::CodeElementType;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    fn element(id: &str, file_path: &str) -> CodeElement {
+        CodeElement {
+            id: id.to_string(),
+            name: id.to_string(),
+            element_type: CodeElementType::Function,
+            file_path: file_path.to_string(),
+            line_number: 1,
+            language: "typescript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_proposes_candidates_with_tables_endpoints_and_coupling() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("src/orders/routes.ts", "import { save } from \"./repo\";\nimport { charge } from \"../billing/charge\";\n"),
+            ("src/orders/repo.ts", "const q = \"INSERT INTO orders (id) VALUES ($1)\";\nconst r = \"SELECT id FROM customers WHERE id = $1\";\n"),
+            ("src/billing/charge.ts", "import { record } from \"./ledger\";\n"),
+            ("src/billing/ledger.ts", "const q = `INSERT INTO invoices (id) VALUES (?)`;\nconst c = `UPDATE customers SET balance = 0`;\n"),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let elements: Vec<CodeElement> = files.iter().enumerate().map(|(i, (p, _))| element(&format!("e{}", i), p)).collect();
+        let endpoints = vec![EndpointUse { name: "POST /orders".to_string(), file_path: "src/orders/routes.ts".to_string() }];
+
+        let report = DecompositionAdvisor::new().analyze(temp_dir.path(), &elements, &[], &endpoints, &[]);
+        assert_eq!(report.candidates.len(), 2);
+        let orders = report.candidates.iter().find(|c| c.name == "src/orders").unwrap();
+        assert_eq!(orders.endpoints, vec!["POST /orders".to_string()]);
+        assert_eq!(orders.tables, vec!["orders".to_string()]);
+        assert_eq!(orders.shared_tables, vec!["customers".to_string()]);
+        assert_eq!(orders.efferent_coupling, 1);
+        assert_eq!(orders.depends_on, vec!["src/billing".to_string()]);
+        let billing = report.candidates.iter().find(|c| c.name == "src/billing").unwrap();
+        assert_eq!(billing.afferent_coupling, 1);
+        assert_eq!(billing.instability, 0.0);
+
+        let customers = report.shared_tables.iter().find(|t| t.table == "customers").unwrap();
+        assert_eq!(customers.writers, vec!["src/billing".to_string()]);
+        assert_eq!(customers.readers, vec!["src/orders".to_string()]);
+        assert!(orders.steps.iter().any(|s| s.contains("`customers`")));
+    }
+
+    #[test]
+    fn test_skips_migrations_lookalike_sql_and_single_file_communities() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("src/users/model.py", "class User(Base):\n    __tablename__ = \"users\"\n"),
+            ("src/users/views.py", "store.update(\"profile\")\ncursor.execute(\"SELECT 1 FROM dual\")\n"),
+            ("db/migrations/001_init.py", "op.execute(\"INSERT INTO audit (id) VALUES (1)\")\n"),
+            ("main.py", "cursor.execute(\"SELECT id FROM users\")\n"),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let elements: Vec<CodeElement> = files.iter().enumerate().map(|(i, (p, _))| element(&format!("e{}", i), p)).collect();
+
+        let report = DecompositionAdvisor::new().analyze(temp_dir.path(), &elements, &[], &[], &[]);
+        // Root files and lone migration files are never proposed on their own
+        let names: Vec<&str> = report.candidates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["src/users"]);
+        let users = &report.candidates[0];
+        assert!(users.tables.is_empty());
+        assert_eq!(users.shared_tables, vec!["users".to_string()]);
+        // Migrations, `dual` and method calls named `update` are not table accesses
+        let tables: Vec<&str> = report.shared_tables.iter().map(|t| t.table.as_str()).collect();
+        assert_eq!(tables, vec!["users"]);
+        assert_eq!(report.shared_tables[0].readers, vec!["(root)".to_string()]);
+
+        // Fewer than two files cannot be split
+        let report = DecompositionAdvisor::new().analyze(temp_dir.path(), &elements[..1], &[], &[], &[]);
+        assert!(report.candidates.is_empty() && report.shared_tables.is_empty());
+    }
+}
@@ -1,7 +1,14 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::time::Duration;
 use walkdir::WalkDir;
+use crate::analysis::repo_path::RepoPath;
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+use crate::storage::StoredDependency;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum PackageManager {
@@ -16,6 +23,8 @@ pub enum PackageManager {
     SwiftPackageManager,
     CocoaPods,
     WebMethodsIS,
+    Unity,
+    Unreal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +94,16 @@ impl DependencyExtractor {
             manifests.push(manifest);
         }
 
+        // Look for Packages/manifest.json (Unity Package Manager)
+        if let Some(manifest) = self.extract_unity(repo_path)? {
+            manifests.push(manifest);
+        }
+
+        // Look for *.uproject (Unreal Engine plugins)
+        if let Some(manifest) = self.extract_unreal(repo_path)? {
+            manifests.push(manifest);
+        }
+
         // Extract dependencies from plugins (generic)
         if let Some(plugin_manifests) = self.extract_plugin_dependencies(repo_path)? {
             manifests.extend(plugin_manifests);
@@ -350,10 +369,7 @@ impl DependencyExtractor {
             if path.file_name().and_then(|n| n.to_str()) == Some("pom.xml") {
                 if let Ok(deps) = self.extract_maven_from_file(path) {
                     if !deps.is_empty() {
-                        found_files.push(path.strip_prefix(repo_path)
-                            .unwrap_or(path)
-                            .to_string_lossy()
-                            .to_string());
+                        found_files.push(RepoPath::new(repo_path, path).into_string());
                         all_dependencies.extend(deps);
                     }
                 }
@@ -887,6 +903,82 @@ impl DependencyExtractor {
         }))
     }
 
+    /// Extract Unity Package Manager dependencies from Packages/manifest.json. Unity projects often
+    /// sit below the repository root, so the first manifest within a few levels is read. Built-in
+    /// `com.unity.modules.*` entries ship with the editor and are left out.
+    fn extract_unity(&self, repo_path: &Path) -> Result<Option<DependencyManifest>> {
+        let Some(manifest_path) = find_engine_file(repo_path, |path, name| {
+            name == "manifest.json" && path.parent().and_then(|p| p.file_name()).is_some_and(|p| p == "Packages")
+        }) else {
+            return Ok(None);
+        };
+
+        let content = std::fs::read_to_string(&manifest_path)?;
+        let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))?;
+
+        let mut dependencies = Vec::new();
+        if let Some(deps) = json.get("dependencies").and_then(|v| v.as_object()) {
+            for (name, version) in deps {
+                if name.starts_with("com.unity.modules.") {
+                    continue;
+                }
+                dependencies.push(PackageDependency {
+                    name: name.clone(),
+                    version: version.as_str().unwrap_or("unknown").to_string(),
+                    package_manager: PackageManager::Unity,
+                    is_dev: false,
+                    is_optional: false,
+                });
+            }
+        }
+
+        if dependencies.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(DependencyManifest {
+            package_manager: PackageManager::Unity,
+            dependencies,
+            file_path: RepoPath::new(repo_path, &manifest_path).into_string(),
+        }))
+    }
+
+    /// Extract the enabled plugins of an Unreal Engine project from its .uproject file. Engine and
+    /// marketplace plugins carry no version of their own.
+    fn extract_unreal(&self, repo_path: &Path) -> Result<Option<DependencyManifest>> {
+        let Some(project_path) = find_engine_file(repo_path, |_, name| name.ends_with(".uproject")) else {
+            return Ok(None);
+        };
+
+        let content = std::fs::read_to_string(&project_path)?;
+        let json: serde_json::Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))?;
+
+        let mut dependencies = Vec::new();
+        for plugin in json.get("Plugins").and_then(|v| v.as_array()).into_iter().flatten() {
+            let Some(name) = plugin.get("Name").and_then(|v| v.as_str()) else { continue };
+            if plugin.get("Enabled").and_then(|v| v.as_bool()) == Some(false) {
+                continue;
+            }
+            dependencies.push(PackageDependency {
+                name: name.to_string(),
+                version: "unknown".to_string(),
+                package_manager: PackageManager::Unreal,
+                is_dev: false,
+                is_optional: plugin.get("Optional").and_then(|v| v.as_bool()).unwrap_or(false),
+            });
+        }
+
+        if dependencies.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(DependencyManifest {
+            package_manager: PackageManager::Unreal,
+            dependencies,
+            file_path: RepoPath::new(repo_path, &project_path).into_string(),
+        }))
+    }
+
     /// Extract dependencies from plugins (generic)
     /// Discovers Python plugins and calls them to extract domain-specific dependencies
     fn extract_plugin_dependencies(&self, repo_path: &Path) -> Result<Option<Vec<DependencyManifest>>> {
@@ -1070,6 +1162,353 @@ except Exception as e:
     }
 }
 
+/// The version when it names one release rather than a range (`^1.2`, `>=2`, `*`)
+pub fn exact_version(version: &str) -> Option<&str> {
+    let version = version.trim().trim_start_matches('=').trim_start_matches('v');
+    let exact = version.starts_with(|c: char| c.is_ascii_digit())
+        && !version.contains(|c: char| c.is_whitespace() || matches!(c, '^' | '~' | '>' | '<' | '*' | ',' | '|'));
+    exact.then_some(version)
+}
+
+/// The lowest release a declared version admits: the version itself when exact, else the lower
+/// bound of a caret, tilde or `>=` range. Ranges without a lower bound (`*`, `<2`) have none.
+fn lowest_admitted_version(version: &str) -> Option<&str> {
+    if let Some(exact) = exact_version(version) {
+        return Some(exact);
+    }
+    let version = version.trim();
+    let bound = version.strip_prefix(">=")
+        .or_else(|| version.strip_prefix("~="))
+        .or_else(|| version.strip_prefix('^'))
+        .or_else(|| version.strip_prefix('~'))?;
+    let bound = bound.trim().split(|c: char| c.is_whitespace() || c == ',').next()?;
+    exact_version(bound)
+}
+
+/// The OSV ecosystem of a package manager; Swift packages, CocoaPods and engine packages have none
+pub fn osv_ecosystem(package_manager: &str) -> Option<&'static str> {
+    match package_manager {
+        "npm" => Some("npm"),
+        "pip" => Some("PyPI"),
+        "cargo" => Some("crates.io"),
+        "maven" | "gradle" => Some("Maven"),
+        "go" => Some("Go"),
+        "composer" => Some("Packagist"),
+        "nuget" => Some("NuGet"),
+        _ => None,
+    }
+}
+
+/// Orders dotted versions component by component, numerically where both components are numbers
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| v.trim_start_matches('v').split(['.', '-', '+']).map(str::to_string).collect::<Vec<_>>();
+    let (a, b) = (parts(a), parts(b));
+    for i in 0..a.len().max(b.len()) {
+        let (x, y) = (a.get(i).map(String::as_str).unwrap_or("0"), b.get(i).map(String::as_str).unwrap_or("0"));
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            // A pre-release sorts before the release it precedes
+            (Ok(_), Err(_)) => Ordering::Greater,
+            (Err(_), Ok(_)) => Ordering::Less,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// CVSS v3 base score of a vector string such as `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+fn cvss3_base_score(vector: &str) -> Option<f64> {
+    let metrics: HashMap<&str, &str> = vector.split('/').filter_map(|m| m.split_once(':')).collect();
+    let changed = *metrics.get("S")? == "C";
+    let attack_vector = match *metrics.get("AV")? { "N" => 0.85, "A" => 0.62, "L" => 0.55, "P" => 0.2, _ => return None };
+    let complexity = match *metrics.get("AC")? { "L" => 0.77, "H" => 0.44, _ => return None };
+    let privileges = match (*metrics.get("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let interaction = match *metrics.get("UI")? { "N" => 0.85, "R" => 0.62, _ => return None };
+    let impact_of = |metric: &str| match metrics.get(metric).copied() { Some("H") => Some(0.56), Some("L") => Some(0.22), Some("N") => Some(0.0), _ => None };
+    let base = 1.0 - (1.0 - impact_of("C")?) * (1.0 - impact_of("I")?) * (1.0 - impact_of("A")?);
+    let impact = if changed { 7.52 * (base - 0.029) - 3.25 * (base - 0.02f64).powi(15) } else { 6.42 * base };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * attack_vector * complexity * privileges * interaction;
+    let score = if changed { (1.08 * (impact + exploitability)).min(10.0) } else { (impact + exploitability).min(10.0) };
+    // Round up to one decimal, as the specification does, without floating point noise
+    let scaled = (score * 100_000.0).round() as i64;
+    Some(if scaled % 10_000 == 0 { scaled as f64 / 100_000.0 } else { ((scaled / 10_000) + 1) as f64 / 10.0 })
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OsvAdvisory {
+    pub id: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    pub affected: Vec<OsvAffected>,
+    #[serde(default)]
+    pub database_specific: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvSeverity {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub score: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvAffected {
+    #[serde(default)]
+    pub package: Option<OsvPackage>,
+    #[serde(default)]
+    pub ranges: Vec<OsvRange>,
+    #[serde(default)]
+    pub database_specific: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvPackage {
+    pub name: String,
+    pub ecosystem: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvRange {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub events: Vec<BTreeMap<String, String>>,
+}
+
+/// Vulnerability type of the findings the OSV lookup stores
+pub const VULNERABLE_DEPENDENCY: &str = "VulnerableDependency";
+
+impl OsvAdvisory {
+    /// The advisory's entry for the package, or its first entry
+    fn affected_entry(&self, name: &str, ecosystem: &str) -> Option<&OsvAffected> {
+        self.affected.iter()
+            .find(|a| a.package.as_ref().is_some_and(|p| p.ecosystem == ecosystem && p.name.eq_ignore_ascii_case(name)))
+            .or_else(|| self.affected.first())
+    }
+
+    /// The severity GitHub's review assigned, else the CVSS v3 score's band, else medium
+    fn severity(&self, affected: Option<&OsvAffected>) -> VulnerabilitySeverity {
+        let label = [self.database_specific.as_ref(), affected.and_then(|a| a.database_specific.as_ref())]
+            .into_iter()
+            .flatten()
+            .find_map(|d| d.get("severity").and_then(Value::as_str));
+        match label.map(|l| l.to_uppercase()).as_deref() {
+            Some("CRITICAL") => return VulnerabilitySeverity::Critical,
+            Some("HIGH") => return VulnerabilitySeverity::High,
+            Some("MODERATE") | Some("MEDIUM") => return VulnerabilitySeverity::Medium,
+            Some("LOW") => return VulnerabilitySeverity::Low,
+            _ => {}
+        }
+        let score = self.severity.iter()
+            .filter(|s| s.kind.starts_with("CVSS_V3"))
+            .find_map(|s| cvss3_base_score(&s.score));
+        match score {
+            Some(score) if score >= 9.0 => VulnerabilitySeverity::Critical,
+            Some(score) if score >= 7.0 => VulnerabilitySeverity::High,
+            Some(score) if score >= 4.0 => VulnerabilitySeverity::Medium,
+            Some(score) if score > 0.0 => VulnerabilitySeverity::Low,
+            Some(_) => VulnerabilitySeverity::Info,
+            None => VulnerabilitySeverity::Medium,
+        }
+    }
+}
+
+/// The affected versions of an advisory entry as `>=a, <b` intervals joined by `||`, and the
+/// release that fixes the interval `version` falls in
+fn affected_versions(affected: &OsvAffected, version: &str) -> (Option<String>, Option<String>) {
+    let mut intervals = Vec::new();
+    let mut fixed_version = None;
+    for range in affected.ranges.iter().filter(|r| r.kind != "GIT") {
+        let mut introduced: Option<&str> = None;
+        for event in &range.events {
+            if let Some(start) = event.get("introduced") {
+                introduced = Some(start.as_str());
+                continue;
+            }
+            let (end, inclusive) = match (event.get("fixed"), event.get("last_affected")) {
+                (Some(fixed), _) => (fixed.as_str(), false),
+                (None, Some(last)) => (last.as_str(), true),
+                _ => continue,
+            };
+            let start = introduced.take().filter(|s| *s != "0");
+            let mut interval = start.map(|s| format!(">={}, ", s)).unwrap_or_default();
+            interval.push_str(&format!("{}{}", if inclusive { "<=" } else { "<" }, end));
+            intervals.push(interval);
+            let contains = start.is_none_or(|s| compare_versions(version, s) != Ordering::Less)
+                && compare_versions(version, end) == Ordering::Less;
+            if !inclusive && contains && fixed_version.is_none() {
+                fixed_version = Some(end.to_string());
+            }
+        }
+        if let Some(start) = introduced {
+            intervals.push(if start == "0" { "*".to_string() } else { format!(">={}", start) });
+        }
+    }
+    let range = (!intervals.is_empty()).then(|| intervals.join(" || "));
+    (range, fixed_version)
+}
+
+/// The finding for a dependency an advisory affects. `queried` is the version looked up: the
+/// declared one, or the lowest release a declared range admits.
+pub fn advisory_vulnerability(dependency: &StoredDependency, queried: &str, advisory: &OsvAdvisory) -> SecurityVulnerability {
+    let ecosystem = osv_ecosystem(&dependency.package_manager).unwrap_or_default();
+    let affected = advisory.affected_entry(&dependency.name, ecosystem);
+    let (affected_range, fixed_version) = affected.map(|a| affected_versions(a, queried)).unwrap_or_default();
+
+    let cves: Vec<&str> = advisory.aliases.iter().map(String::as_str).filter(|a| a.starts_with("CVE-")).collect();
+    let title = if cves.is_empty() { advisory.id.clone() } else { format!("{} ({})", advisory.id, cves.join(", ")) };
+    let subject = if exact_version(&dependency.version).is_some() {
+        format!("{} {} is affected by {}", dependency.name, queried, title)
+    } else {
+        format!("{} {} admits {}, which is affected by {}", dependency.name, dependency.version, queried, title)
+    };
+    let description = match advisory.summary.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(summary) => format!("{}: {}", subject, summary),
+        None => subject,
+    };
+    let recommendation = match &fixed_version {
+        Some(fixed) => format!("Upgrade {} to {} or later (https://osv.dev/vulnerability/{})", dependency.name, fixed, advisory.id),
+        None => format!("No fixed release of {} is published; replace it or apply the mitigations in https://osv.dev/vulnerability/{}", dependency.name, advisory.id),
+    };
+
+    SecurityVulnerability {
+        id: format!("{}:{}", dependency.id, advisory.id),
+        entity_id: dependency.id.clone(),
+        vulnerability_type: VULNERABLE_DEPENDENCY.to_string(),
+        severity: advisory.severity(affected),
+        description,
+        recommendation,
+        file_path: dependency.file_path.clone(),
+        line_number: None,
+        affected_range,
+        fixed_version,
+    }
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvBatchResult>,
+}
+
+#[derive(Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvAdvisoryRef>,
+}
+
+#[derive(Deserialize)]
+struct OsvAdvisoryRef {
+    id: String,
+}
+
+/// Looks dependencies up in an OSV database (https://osv.dev, or a mirror serving its API)
+pub struct OsvClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+/// The most queries OSV accepts in one batch
+const OSV_BATCH_SIZE: usize = 1000;
+
+impl OsvClient {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(OsvClient {
+            client: crate::network::http_client(Duration::from_secs(30))?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Known advisories for every dependency in an ecosystem OSV covers, one finding per
+    /// declaring manifest. Ranges are checked at the lowest release they admit.
+    pub async fn scan(&self, dependencies: &[StoredDependency]) -> Result<Vec<SecurityVulnerability>> {
+        // Each package version is queried once, however many manifests declare it
+        let mut packages: BTreeMap<(&str, &str, &str), Vec<&StoredDependency>> = BTreeMap::new();
+        for dependency in dependencies {
+            let (Some(ecosystem), Some(version)) = (osv_ecosystem(&dependency.package_manager), lowest_admitted_version(&dependency.version)) else {
+                continue;
+            };
+            packages.entry((ecosystem, dependency.name.as_str(), version)).or_default().push(dependency);
+        }
+        let packages: Vec<_> = packages.into_iter().collect();
+
+        let mut matches: Vec<(usize, String)> = Vec::new();
+        for (offset, chunk) in packages.chunks(OSV_BATCH_SIZE).enumerate() {
+            let queries: Vec<Value> = chunk.iter()
+                .map(|((ecosystem, name, version), _)| json!({"package": {"name": name, "ecosystem": ecosystem}, "version": version}))
+                .collect();
+            let response: OsvBatchResponse = self.client.post(format!("{}/v1/querybatch", self.base_url))
+                .json(&json!({"queries": queries}))
+                .send().await?
+                .error_for_status()?
+                .json().await?;
+            for (i, result) in response.results.into_iter().enumerate() {
+                matches.extend(result.vulns.into_iter().map(|v| (offset * OSV_BATCH_SIZE + i, v.id)));
+            }
+        }
+
+        // The batch answer only names the advisories; their ranges and severity are fetched once each
+        let mut advisories: HashMap<String, OsvAdvisory> = HashMap::new();
+        let mut vulnerabilities = Vec::new();
+        for (index, id) in matches {
+            if !advisories.contains_key(&id) {
+                match self.advisory(&id).await {
+                    Ok(advisory) => { advisories.insert(id.clone(), advisory); }
+                    Err(e) => {
+                        log::debug!("Fetching OSV advisory {} failed: {}", id, e);
+                        continue;
+                    }
+                }
+            }
+            let ((_, _, version), declared) = &packages[index];
+            for dependency in declared {
+                vulnerabilities.push(advisory_vulnerability(dependency, version, &advisories[&id]));
+            }
+        }
+        Ok(vulnerabilities)
+    }
+
+    async fn advisory(&self, id: &str) -> Result<OsvAdvisory> {
+        Ok(self.client.get(format!("{}/v1/vulns/{}", self.base_url, id))
+            .send().await?
+            .error_for_status()?
+            .json().await?)
+    }
+}
+
+/// The first file within three levels of the repository root that `matches` a game engine project
+/// file, skipping the engine's generated and cached directories
+fn find_engine_file(repo_path: &Path, matches: impl Fn(&Path, &str) -> bool) -> Option<std::path::PathBuf> {
+    let skip_dirs = [".git", "node_modules", "Library", "Temp", "Intermediate", "Saved", "Binaries", "DerivedDataCache"];
+    WalkDir::new(repo_path)
+        .max_depth(3)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !skip_dirs.iter().any(|skip| e.file_name() == *skip))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .find(|e| matches(e.path(), &e.file_name().to_string_lossy()))
+        .map(|e| e.into_path())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1122,5 +1561,79 @@ mockito = "1.0"
         assert_eq!(manifest.package_manager, PackageManager::Cargo);
         assert!(manifest.dependencies.len() >= 2);
     }
-}
 
+    #[test]
+    fn test_extract_unity_and_unreal() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("Game/Packages")).unwrap();
+        fs::write(temp_dir.path().join("Game/Packages/manifest.json"), r#"{
+            "dependencies": {
+                "com.unity.inputsystem": "1.7.0",
+                "com.example.tools": "https://github.com/example/tools.git#v2",
+                "com.unity.modules.physics": "1.0.0"
+            }
+        }"#).unwrap();
+        fs::write(temp_dir.path().join("Shooter.uproject"), r#"{
+            "EngineAssociation": "5.3",
+            "Plugins": [
+                { "Name": "EnhancedInput", "Enabled": true },
+                { "Name": "OnlineSubsystemSteam", "Enabled": true, "Optional": true },
+                { "Name": "Paper2D", "Enabled": false }
+            ]
+        }"#).unwrap();
+
+        let extractor = DependencyExtractor::new();
+        let unity = extractor.extract_unity(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(unity.file_path, "Game/Packages/manifest.json");
+        let packages: Vec<(&str, &str)> = unity.dependencies.iter().map(|d| (d.name.as_str(), d.version.as_str())).collect();
+        assert_eq!(packages, vec![
+            ("com.example.tools", "https://github.com/example/tools.git#v2"),
+            ("com.unity.inputsystem", "1.7.0"),
+        ]);
+
+        let unreal = extractor.extract_unreal(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(unreal.file_path, "Shooter.uproject");
+        let plugins: Vec<(&str, bool)> = unreal.dependencies.iter().map(|d| (d.name.as_str(), d.is_optional)).collect();
+        assert_eq!(plugins, vec![("EnhancedInput", false), ("OnlineSubsystemSteam", true)]);
+    }
+
+    #[test]
+    fn test_osv_advisory_vulnerability() {
+        let advisory: OsvAdvisory = serde_json::from_value(serde_json::json!({
+            "id": "GHSA-35jh-r3h4-6jhm",
+            "summary": "Command Injection in lodash",
+            "aliases": ["CVE-2021-23337"],
+            "severity": [{"type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"}],
+            "affected": [{
+                "package": {"name": "lodash", "ecosystem": "npm"},
+                "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]
+            }]
+        })).unwrap();
+        let dependency = StoredDependency {
+            id: "dep-1".to_string(),
+            repository_id: "repo".to_string(),
+            name: "lodash".to_string(),
+            version: "^4.17.15".to_string(),
+            package_manager: "npm".to_string(),
+            is_dev: false,
+            is_optional: false,
+            file_path: "web/package.json".to_string(),
+            created_at: chrono::Utc::now(),
+        };
+
+        let queried = lowest_admitted_version(&dependency.version).unwrap();
+        assert_eq!(queried, "4.17.15");
+        let vulnerability = advisory_vulnerability(&dependency, queried, &advisory);
+        assert_eq!(vulnerability.vulnerability_type, VULNERABLE_DEPENDENCY);
+        assert!(matches!(vulnerability.severity, VulnerabilitySeverity::Critical));
+        assert_eq!(vulnerability.affected_range.as_deref(), Some("<4.17.21"));
+        assert_eq!(vulnerability.fixed_version.as_deref(), Some("4.17.21"));
+        assert_eq!(vulnerability.file_path, "web/package.json");
+        assert!(vulnerability.description.starts_with("lodash ^4.17.15 admits 4.17.15, which is affected by GHSA-35jh-r3h4-6jhm (CVE-2021-23337)"));
+
+        assert_eq!(cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), Some(6.1));
+        assert_eq!(lowest_admitted_version("*"), None);
+        assert_eq!(osv_ecosystem("pip"), Some("PyPI"));
+    }
+}
@@ -0,0 +1,344 @@
+//! Documentation attached to a code element: line comments (`//`, `///`, `#`) and block comments
+//! (`/** ... */`) above it, a Python docstring or Rust `//!` inner docs below it. Javadoc/JSDoc
+//! `@param`/`@return` tags and reST `:param:`/`:returns:` fields are parsed out, so every language
+//! stores the same plain-text layout.
+
+/// Lines searched upwards for the start of a block comment
+const MAX_BLOCK_LINES: usize = 200;
+/// Lines a Python signature may span before its closing `:`
+const MAX_HEADER_LINES: usize = 10;
+
+/// C preprocessor and C# directives, which start with `#` but are not comments
+const DIRECTIVES: &[&str] = &[
+    "include", "import", "define", "undef", "pragma", "if", "ifdef", "ifndef", "elif", "else",
+    "endif", "error", "warning", "region", "endregion",
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocComment {
+    pub description: String,
+    /// `(name, description)`, with the type folded into the name when a tag gives one
+    pub params: Vec<(String, String)>,
+    pub returns: Option<String>,
+    /// Other tags (`@throws`, `@see`, `@deprecated`, ...) as written
+    pub tags: Vec<String>,
+}
+
+/// The documentation for the element declared on `lines[line_num]`, rendered as plain text
+pub fn extract_doc_comment(lines: &[&str], line_num: usize) -> Option<String> {
+    let text = docstring_below(lines, line_num)
+        .or_else(|| inner_doc_below(lines, line_num))
+        .or_else(|| comment_above(lines, line_num))?;
+    Some(DocComment::parse(&text).render()).filter(|doc| !doc.is_empty())
+}
+
+/// Text of a line comment, without its marker. `None` for code, directives and `//!`, which
+/// documents the enclosing module rather than what follows.
+fn line_comment_text(line: &str) -> Option<&str> {
+    let text = if line.starts_with("//!") {
+        return None;
+    } else if let Some(text) = line.strip_prefix("///").or_else(|| line.strip_prefix("//")) {
+        text
+    } else if let Some(text) = line.strip_prefix('#') {
+        let word: String = text.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+        if text.starts_with(['!', '[']) || DIRECTIVES.contains(&word.as_str()) {
+            return None;
+        }
+        text
+    } else {
+        return None;
+    };
+    Some(text.strip_prefix(' ').unwrap_or(text).trim_end())
+}
+
+/// Decorators, annotations and attributes, which sit between an element and its comment
+fn is_annotation(line: &str) -> bool {
+    line.starts_with('@') || line.starts_with("#[")
+}
+
+/// The inside of a `/* ... */` block, without the leading `*` of each line
+fn block_comment_text(block: &[&str]) -> String {
+    let joined = block.iter().map(|l| l.trim()).collect::<Vec<_>>().join("\n");
+    let inner = joined
+        .trim_start_matches("/**").trim_start_matches("/*!").trim_start_matches("/*")
+        .trim_end_matches("*/");
+    inner.lines()
+        .map(|l| {
+            let l = l.trim();
+            let l = l.strip_prefix('*').unwrap_or(l);
+            l.strip_prefix(' ').unwrap_or(l).trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn comment_above(lines: &[&str], line_num: usize) -> Option<String> {
+    let mut doc: Vec<&str> = Vec::new();
+    let mut i = line_num.min(lines.len());
+    while i > 0 {
+        i -= 1;
+        let line = lines[i].trim();
+        if line.is_empty() || is_annotation(line) {
+            if doc.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if line.ends_with("*/") && doc.is_empty() {
+            let start = (i.saturating_sub(MAX_BLOCK_LINES)..=i).rev().find(|&j| lines[j].contains("/*"))?;
+            if !lines[start].trim().starts_with("/*") {
+                return None;
+            }
+            return Some(block_comment_text(&lines[start..=i]));
+        }
+        match line_comment_text(line) {
+            Some(text) => doc.insert(0, text),
+            None => break,
+        }
+    }
+    Some(doc.join("\n")).filter(|doc| !doc.trim().is_empty())
+}
+
+/// A Python docstring: the first statement after a `def`/`class` header that ends with `:`
+fn docstring_below(lines: &[&str], line_num: usize) -> Option<String> {
+    let header = lines.get(line_num)?.trim();
+    if !["def ", "async def ", "class "].iter().any(|k| header.starts_with(k)) {
+        return None;
+    }
+    let header_end = (line_num..lines.len().min(line_num + MAX_HEADER_LINES))
+        .find(|&k| lines[k].split('#').next().unwrap_or("").trim_end().ends_with(':'))?;
+    let (first, rest) = lines[header_end + 1..].split_first()?;
+    let first = first.trim();
+    let opened = first.trim_start_matches(['r', 'u', 'R', 'U']);
+    let quote = ["\"\"\"", "'''"].into_iter().find(|q| opened.starts_with(q))?;
+    let body = &opened[quote.len()..];
+    if let Some(end) = body.find(quote) {
+        return Some(body[..end].trim().to_string());
+    }
+
+    let mut doc = vec![body.trim()];
+    for line in rest {
+        let line = line.trim();
+        if let Some(end) = line.find(quote) {
+            doc.push(&line[..end]);
+            return Some(doc.join("\n"));
+        }
+        doc.push(line);
+    }
+    None
+}
+
+/// Rust inner docs (`//!` or `/*! */`) opening the body of the element
+fn inner_doc_below(lines: &[&str], line_num: usize) -> Option<String> {
+    if !lines.get(line_num)?.trim_end().ends_with('{') {
+        return None;
+    }
+    let body = &lines[line_num + 1..];
+    let first = body.iter().position(|l| !l.trim().is_empty())?;
+    if body[first].trim().starts_with("/*!") {
+        let end = body[first..].iter().position(|l| l.contains("*/"))?;
+        return Some(block_comment_text(&body[first..=first + end]));
+    }
+    let doc: Vec<&str> = body[first..].iter()
+        .map_while(|l| l.trim().strip_prefix("//!"))
+        .map(|l| l.strip_prefix(' ').unwrap_or(l).trim_end())
+        .collect();
+    Some(doc.join("\n")).filter(|doc| !doc.trim().is_empty())
+}
+
+impl DocComment {
+    /// Splits comment text into description and tags; tag text continues onto following lines
+    /// until the next tag
+    pub fn parse(text: &str) -> DocComment {
+        enum Target { Description, Param, Returns, Tag }
+        let mut doc = DocComment::default();
+        let mut description = Vec::new();
+        let mut target = Target::Description;
+
+        for line in text.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("@param").filter(|r| r.starts_with(char::is_whitespace)) {
+                doc.params.push(parse_param(rest.trim()));
+                target = Target::Param;
+            } else if let Some(rest) = strip_tag(line, &["@returns", "@return"]) {
+                doc.returns = Some(parse_returns(rest));
+                target = Target::Returns;
+            } else if let Some(field) = line.strip_prefix(":param ") {
+                let (name, description) = field.split_once(':').unwrap_or((field, ""));
+                // `:param int count:` names its type before the parameter
+                let name = match name.trim().rsplit_once(' ') {
+                    Some((kind, name)) => format!("{} ({})", name, kind),
+                    None => name.trim().to_string(),
+                };
+                doc.params.push((name, description.trim().to_string()));
+                target = Target::Param;
+            } else if let Some(rest) = strip_tag(line, &[":returns:", ":return:"]) {
+                doc.returns = Some(rest.to_string());
+                target = Target::Returns;
+            } else if line.starts_with('@') || (line.starts_with(':') && line[1..].contains(':')) {
+                doc.tags.push(line.to_string());
+                target = Target::Tag;
+            } else {
+                let continued = match target {
+                    Target::Description => None,
+                    Target::Param => doc.params.last_mut().map(|(_, d)| d),
+                    Target::Returns => doc.returns.as_mut(),
+                    Target::Tag => doc.tags.last_mut(),
+                };
+                match continued {
+                    Some(text) if !line.is_empty() => {
+                        if !text.is_empty() {
+                            text.push(' ');
+                        }
+                        text.push_str(line);
+                    }
+                    Some(_) => target = Target::Description,
+                    None => description.push(line),
+                }
+            }
+        }
+
+        doc.description = description.join("\n").trim().to_string();
+        doc
+    }
+
+    pub fn render(&self) -> String {
+        let mut sections = Vec::new();
+        if !self.description.is_empty() {
+            sections.push(self.description.clone());
+        }
+        let mut fields = Vec::new();
+        if !self.params.is_empty() {
+            fields.push("Parameters:".to_string());
+            for (name, description) in &self.params {
+                fields.push(if description.is_empty() { format!("  {}", name) } else { format!("  {}: {}", name, description) });
+            }
+        }
+        if let Some(returns) = &self.returns {
+            fields.push(format!("Returns: {}", returns));
+        }
+        fields.extend(self.tags.iter().cloned());
+        if !fields.is_empty() {
+            sections.push(fields.join("\n"));
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// What follows the first of `tags` that `line` starts with, as a whole word
+fn strip_tag<'a>(line: &'a str, tags: &[&str]) -> Option<&'a str> {
+    tags.iter().find_map(|tag| line.strip_prefix(tag))
+        .filter(|rest| !rest.starts_with(char::is_alphanumeric))
+        .map(str::trim)
+}
+
+/// A leading JSDoc `{type}` and the text after it
+fn split_type(text: &str) -> (Option<&str>, &str) {
+    match text.strip_prefix('{').and_then(|t| t.split_once('}')) {
+        Some((kind, rest)) => (Some(kind.trim()), rest.trim()),
+        None => (None, text),
+    }
+}
+
+/// `name description` or JSDoc `{type} name description`
+fn parse_param(text: &str) -> (String, String) {
+    let (kind, rest) = split_type(text);
+    let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let description = description.trim().trim_start_matches("- ").to_string();
+    match kind {
+        Some(kind) => (format!("{} ({})", name, kind), description),
+        None => (name.to_string(), description),
+    }
+}
+
+/// `description` or JSDoc `{type} description`
+fn parse_returns(text: &str) -> String {
+    match split_type(text) {
+        (Some(kind), "") => kind.to_string(),
+        (Some(kind), description) => format!("{} ({})", description, kind),
+        (None, description) => description.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(source: &str, marker: &str) -> Option<String> {
+        let lines: Vec<&str> = source.lines().collect();
+        let line_num = lines.iter().position(|l| l.contains(marker)).unwrap();
+        extract_doc_comment(&lines, line_num)
+    }
+
+    #[test]
+    fn test_javadoc_block_with_tags() {
+        let source = r#"
+    /**
+     * Loads an order.
+     *
+     * @param id the order id,
+     *     as issued by checkout
+     * @return the order, or null
+     * @throws IOException when the store is down
+     */
+    @Override
+    public Order load(String id) throws IOException {"#;
+        assert_eq!(doc(source, "public Order load").unwrap(), "Loads an order.\n\nParameters:\n  id: the order id, as issued by checkout\nReturns: the order, or null\n@throws IOException when the store is down");
+    }
+
+    #[test]
+    fn test_jsdoc_types_and_line_comments() {
+        let source = "/** @param {string} name - who to greet\n * @returns {string} */\nfunction greet(name) {";
+        assert_eq!(doc(source, "function greet").unwrap(), "Parameters:\n  name (string): who to greet\nReturns: string");
+
+        let source = "// Shared helper\n// for retries\n\nconst retry = function() {";
+        assert_eq!(doc(source, "const retry").unwrap(), "Shared helper\nfor retries");
+
+        // A trailing comment on a line of code is not documentation
+        assert_eq!(doc("call(); /* done */\nfunction next() {", "function next"), None);
+    }
+
+    #[test]
+    fn test_python_docstrings() {
+        let source = r#"
+# Not the docstring
+@app.route("/orders")
+def list_orders(
+    status,
+    limit=10,
+):
+    """List orders.
+
+    :param str status: only orders in this state
+    :returns: a list of orders
+    """
+    return []
+
+class Cart:
+    '''A shopping cart.'''
+"#;
+        assert_eq!(doc(source, "def list_orders").unwrap(), "List orders.\n\nParameters:\n  status (str): only orders in this state\nReturns: a list of orders");
+        assert_eq!(doc(source, "class Cart").unwrap(), "A shopping cart.");
+    }
+
+    #[test]
+    fn test_rust_doc_comments() {
+        let source = r#"//! Crate docs, not the function's
+
+/// Parses a manifest.
+#[inline]
+pub fn parse() {
+}
+
+mod storage {
+    //! Where results are kept.
+}
+
+#[derive(Debug)]
+#[cfg(feature = "x")]
+pub struct Bare {"#;
+        assert_eq!(doc(source, "pub fn parse").unwrap(), "Parses a manifest.");
+        assert_eq!(doc(source, "mod storage").unwrap(), "Where results are kept.");
+        assert_eq!(doc(source, "pub struct Bare"), None);
+        assert_eq!(doc("//! Module docs\nfn first() {", "fn first"), None);
+    }
+}
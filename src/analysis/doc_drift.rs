@@ -0,0 +1,432 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use crate::analysis::documentation::DocumentationFile;
+use crate::analysis::endpoint_detector::DetectedEndpoint;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    Endpoint,
+    Service,
+    EnvVar,
+}
+
+impl DriftKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftKind::Endpoint => "endpoint",
+            DriftKind::Service => "service",
+            DriftKind::EnvVar => "env_var",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftDirection {
+    Undetected,   // Documented, but not found in the code
+    Undocumented, // Found in the code, but no doc mentions it
+}
+
+impl DriftDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriftDirection::Undetected => "undetected",
+            DriftDirection::Undocumented => "undocumented",
+        }
+    }
+}
+
+/// A statement in the docs that no longer matches what analysis found, or the reverse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocDrift {
+    pub kind: DriftKind,
+    pub direction: DriftDirection,
+    pub subject: String,
+    pub file_path: Option<String>,
+    pub line_number: Option<usize>,
+    pub message: String,
+}
+
+/// Services the service detector can find, with the words docs use for them. Services outside
+/// this list are never reported as undetected since analysis could not have found them anyway.
+const KNOWN_SERVICES: &[(&str, &[&str])] = &[
+    ("Redis", &["redis"]),
+    ("PostgreSQL", &["postgres", "postgresql"]),
+    ("MySQL", &["mysql"]),
+    ("MongoDB", &["mongodb", "mongo"]),
+    ("DynamoDB", &["dynamodb"]),
+    ("Stripe", &["stripe"]),
+    ("Twilio", &["twilio"]),
+    ("SendGrid", &["sendgrid"]),
+    ("Mailgun", &["mailgun"]),
+    ("Auth0", &["auth0"]),
+    ("Sentry", &["sentry"]),
+    ("Datadog", &["datadog"]),
+    ("New Relic", &["newrelic", "new relic"]),
+    ("OpenAI", &["openai"]),
+    ("Anthropic", &["anthropic"]),
+    ("Cloudflare", &["cloudflare"]),
+    ("SQS", &["sqs"]),
+    ("SNS", &["sns"]),
+];
+
+/// Docs naming this many known services are catalogs or comparisons, not claims about this project
+const CATALOG_THRESHOLD: usize = 6;
+
+/// Env templates that document variables alongside the docs, relative to the repository root
+const ENV_TEMPLATES: &[&str] = &[".env.example", ".env.sample", ".env.template", "env.example", ".env.dist", "config/.env.example"];
+
+/// Variables set by the platform or toolchain rather than by whoever deploys the project
+const AMBIENT_ENV_VARS: &[&str] = &["PATH", "HOME", "USER", "PWD", "SHELL", "TMPDIR", "TERM", "CI", "NODE_ENV", "RUST_LOG", "RUST_BACKTRACE", "DEBUG", "HOSTNAME", "LANG"];
+
+static DOC_ENDPOINT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(GET|POST|PUT|PATCH|DELETE)\s+(/[A-Za-z0-9_\-./:{}<>\[\]*]*)").unwrap()
+});
+static CODE_SPAN: Lazy<Regex> = Lazy::new(|| Regex::new(r"`\$?\{?([A-Z][A-Z0-9_]{2,})\}?`").unwrap());
+static ENV_ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|\s)(?:export\s+|-e\s+)?([A-Z][A-Z0-9]*(?:_[A-Z0-9]+)+)=").unwrap()
+});
+static ENV_READ: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r#"(?:env::var(?:_os)?\(\s*"|env!\(\s*"|option_env!\(\s*"|env\s*=\s*"|process\.env\.|process\.env\[\s*['"]|import\.meta\.env\.|"#,
+        r#"os\.environ\[\s*['"]|os\.environ\.get\(\s*['"]|os\.getenv\(\s*['"]|os\.Getenv\(\s*"|os\.LookupEnv\(\s*"|"#,
+        r#"System\.getenv\(\s*"|ENV\[\s*['"]|ENV\.fetch\(\s*['"]|GetEnvironmentVariable\(\s*")([A-Z_][A-Z0-9_]*)"#,
+    )).unwrap()
+});
+static UPPER_SNAKE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b[A-Z][A-Z0-9]*(?:_[A-Z0-9]+)+\b").unwrap());
+
+/// Files that set environment variables for the app rather than document them
+const ENV_DEFINITION_FILES: &[&str] = &[".env", "Dockerfile", "docker-compose.yml", "docker-compose.yaml", "compose.yml", "compose.yaml"];
+
+/// Where an environment variable is set (env files, templates, Dockerfiles, compose files) or read
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnvVarReference {
+    pub file_path: String,
+    pub line_number: usize,
+    /// "definition" or "read"
+    pub kind: String,
+}
+
+/// Every place `name` is set or read; `code_files` are repository-relative
+pub fn find_env_var(repo_path: &Path, name: &str, code_files: &[&str]) -> Vec<EnvVarReference> {
+    let mut references = Vec::new();
+    let mut scan = |file: &str, pattern: &Regex, kind: &str| {
+        let content = match std::fs::read_to_string(repo_path.join(file)) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim_start().trim_start_matches('#');
+            if pattern.captures_iter(line).any(|c| &c[1] == name) {
+                references.push(EnvVarReference { file_path: file.to_string(), line_number: idx + 1, kind: kind.to_string() });
+            }
+        }
+    };
+    for file in ENV_DEFINITION_FILES.iter().chain(ENV_TEMPLATES) {
+        scan(file, &ENV_ASSIGNMENT, "definition");
+    }
+    for file in code_files {
+        scan(file, &ENV_READ, "read");
+    }
+    references
+}
+
+/// Finds drift between what the documentation claims and what analysis detected
+pub struct DocDriftDetector;
+
+impl DocDriftDetector {
+    pub fn new() -> Self {
+        DocDriftDetector
+    }
+
+    /// `services` are the names and providers of detected services; `code_files` are repository-relative
+    pub fn detect(&self, repo_path: &Path, docs: &[DocumentationFile], endpoints: &[DetectedEndpoint], services: &[&str], code_files: &[&str]) -> Vec<DocDrift> {
+        let doc_contents: Vec<(&str, String)> = docs.iter()
+            .filter_map(|d| std::fs::read_to_string(repo_path.join(&d.file_path)).ok().map(|c| (d.file_path.as_str(), c)))
+            .collect();
+
+        let mut drift = Vec::new();
+        drift.extend(service_drift(&doc_contents, services));
+        drift.extend(endpoint_drift(repo_path, &doc_contents, endpoints));
+        drift.extend(env_var_drift(repo_path, &doc_contents, code_files));
+        drift
+    }
+}
+
+fn service_drift(docs: &[(&str, String)], services: &[&str]) -> Vec<DocDrift> {
+    let detected: Vec<String> = services.iter().map(|s| s.to_lowercase()).collect();
+    let patterns: Vec<Vec<Regex>> = KNOWN_SERVICES.iter()
+        .map(|(_, aliases)| aliases.iter().filter_map(|a| Regex::new(&format!(r"(?i)\b{}\b", regex::escape(a))).ok()).collect())
+        .collect();
+    let first_mention = |content: &str, service: usize| {
+        content.lines().position(|line| patterns[service].iter().any(|p| p.is_match(line))).map(|idx| idx + 1)
+    };
+    let claims: Vec<&(&str, String)> = docs.iter()
+        .filter(|(_, content)| (0..KNOWN_SERVICES.len()).filter(|i| first_mention(content, *i).is_some()).count() < CATALOG_THRESHOLD)
+        .collect();
+
+    let mut drift = Vec::new();
+    for (i, (service, aliases)) in KNOWN_SERVICES.iter().enumerate() {
+        let mention = claims.iter().find_map(|(path, content)| first_mention(content, i).map(|line| (*path, line)));
+        let is_detected = detected.iter().any(|d| aliases.iter().any(|a| d.contains(&a.replace(' ', ""))));
+
+        match (mention, is_detected) {
+            (Some((path, line)), false) => drift.push(DocDrift {
+                kind: DriftKind::Service,
+                direction: DriftDirection::Undetected,
+                subject: service.to_string(),
+                file_path: Some(path.to_string()),
+                line_number: Some(line),
+                message: format!("{} mentions {} but no {} usage was detected", path, service, service),
+            }),
+            (None, true) => drift.push(DocDrift {
+                kind: DriftKind::Service,
+                direction: DriftDirection::Undocumented,
+                subject: service.to_string(),
+                file_path: None,
+                line_number: None,
+                message: format!("{} is used but not mentioned in any documentation", service),
+            }),
+            _ => {}
+        }
+    }
+    drift
+}
+
+/// Lowercased path with parameters (`:id`, `{id}`, `<id>`, `[id]`) collapsed to `{}`
+fn normalize_endpoint(path: &str) -> String {
+    let segments: Vec<String> = path.split(['?', '#']).next().unwrap_or("")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let is_param = s.starts_with(':') || (s.starts_with('{') && s.ends_with('}'))
+                || (s.starts_with('<') && s.ends_with('>')) || (s.starts_with('[') && s.ends_with(']'));
+            if is_param { "{}".to_string() } else { s.to_lowercase() }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+fn endpoint_drift(repo_path: &Path, docs: &[(&str, String)], endpoints: &[DetectedEndpoint]) -> Vec<DocDrift> {
+    let detected: Vec<(String, String, &DetectedEndpoint)> = endpoints.iter()
+        .map(|e| (format!("{:?}", e.method).to_uppercase(), normalize_endpoint(&e.path), e))
+        .collect();
+    // Router prefixes are not always resolved, so a documented path may end with the detected one
+    let matches = |method: &str, documented: &str, (detected_method, path, _): &(String, String, &DetectedEndpoint)| {
+        (detected_method == method || detected_method == "ANY")
+            && (documented == path || (path != "/" && documented.ends_with(path.as_str())))
+    };
+
+    let mut drift = Vec::new();
+    let mut documented = Vec::new();
+    let mut seen = HashSet::new();
+    for (doc_path, content) in docs {
+        for (idx, line) in content.lines().enumerate() {
+            for cap in DOC_ENDPOINT.captures_iter(line) {
+                let method = cap[1].to_string();
+                let path = normalize_endpoint(cap[2].trim_end_matches(['.', ',', ')']));
+                if !seen.insert((method.clone(), path.clone())) {
+                    continue;
+                }
+                if !detected.iter().any(|d| matches(&method, &path, d)) {
+                    drift.push(DocDrift {
+                        kind: DriftKind::Endpoint,
+                        direction: DriftDirection::Undetected,
+                        subject: format!("{} {}", method, &cap[2]),
+                        file_path: Some(doc_path.to_string()),
+                        line_number: Some(idx + 1),
+                        message: format!("{} documents {} {} but no such endpoint was detected", doc_path, method, &cap[2]),
+                    });
+                }
+                documented.push((method, path));
+            }
+        }
+    }
+
+    // Only hold the docs to the full route list once they document endpoints at all
+    if !documented.is_empty() {
+        let mut reported = HashSet::new();
+        for entry in &detected {
+            let (method, path, endpoint) = entry;
+            if method == "ANY" || !reported.insert((method.clone(), path.clone())) {
+                continue;
+            }
+            if !documented.iter().any(|(m, p)| matches(m, p, entry)) {
+                let file_path = RepoPath::new(repo_path, Path::new(&endpoint.file_path)).into_string();
+                drift.push(DocDrift {
+                    kind: DriftKind::Endpoint,
+                    direction: DriftDirection::Undocumented,
+                    subject: format!("{} {}", method, endpoint.path),
+                    message: format!("{} {} ({}) is not documented", method, endpoint.path, file_path),
+                    file_path: Some(file_path),
+                    line_number: endpoint.line_number,
+                });
+            }
+        }
+    }
+    drift
+}
+
+fn env_var_drift(repo_path: &Path, docs: &[(&str, String)], code_files: &[&str]) -> Vec<DocDrift> {
+    // First place each variable is documented: backticked names, `NAME=` lines in code blocks and env templates
+    let mut documented: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for (doc_path, content) in docs {
+        let mut in_code_block = false;
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            let names = CODE_SPAN.captures_iter(line).map(|c| c[1].to_string())
+                .chain(in_code_block.then(|| ENV_ASSIGNMENT.captures_iter(line).map(|c| c[1].to_string()).collect::<Vec<_>>()).into_iter().flatten());
+            for name in names {
+                documented.entry(name).or_insert_with(|| (doc_path.to_string(), idx + 1));
+            }
+        }
+    }
+    for template in ENV_TEMPLATES {
+        if let Ok(content) = std::fs::read_to_string(repo_path.join(template)) {
+            for (idx, line) in content.lines().enumerate() {
+                let line = line.trim_start().trim_start_matches('#').trim_start();
+                if let Some(cap) = ENV_ASSIGNMENT.captures(line) {
+                    documented.entry(cap[1].to_string()).or_insert_with(|| (template.to_string(), idx + 1));
+                }
+            }
+        }
+    }
+
+    // Variables read in code, and every upper-snake token so dynamically built names are not flagged
+    let mut reads: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    let mut mentioned: HashSet<String> = HashSet::new();
+    for file in code_files {
+        let content = match std::fs::read_to_string(repo_path.join(file)) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        for (idx, line) in content.lines().enumerate() {
+            for cap in ENV_READ.captures_iter(line) {
+                reads.entry(cap[1].to_string()).or_insert_with(|| (file.to_string(), idx + 1));
+            }
+            mentioned.extend(UPPER_SNAKE.find_iter(line).map(|m| m.as_str().to_string()));
+        }
+    }
+
+    let is_ambient = |name: &str| AMBIENT_ENV_VARS.contains(&name) || name.starts_with("CARGO_") || name.starts_with("npm_");
+    let mut drift = Vec::new();
+    for (name, (doc_path, line)) in &documented {
+        // Single words in backticks are as likely to be keywords as variables
+        if !name.contains('_') || is_ambient(name) || reads.contains_key(name) || mentioned.contains(name) {
+            continue;
+        }
+        drift.push(DocDrift {
+            kind: DriftKind::EnvVar,
+            direction: DriftDirection::Undetected,
+            subject: name.clone(),
+            file_path: Some(doc_path.clone()),
+            line_number: Some(*line),
+            message: format!("{} documents {} but the code never reads it", doc_path, name),
+        });
+    }
+    for (name, (file, line)) in &reads {
+        if is_ambient(name) || documented.contains_key(name) {
+            continue;
+        }
+        drift.push(DocDrift {
+            kind: DriftKind::EnvVar,
+            direction: DriftDirection::Undocumented,
+            subject: name.clone(),
+            file_path: Some(file.clone()),
+            line_number: Some(*line),
+            message: format!("{} is read in {} but not documented", name, file),
+        });
+    }
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::documentation::DocumentationType;
+    use crate::analysis::endpoint_detector::{EndpointProtocol, HttpMethod};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_drift_in_both_directions() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("README.md"),
+            "# Orders\nOrders are cached in Redis.\n\n## API\n- `GET /api/orders/{id}`\n- `DELETE /api/orders/{id}`\n\n```\nexport DATABASE_URL=postgres://localhost\nexport LEGACY_TOKEN=abc\n```\n",
+        ).unwrap();
+        fs::write(
+            temp_dir.path().join("src/server.js"),
+            "const db = process.env.DATABASE_URL;\nconst key = process.env.STRIPE_KEY;\n",
+        ).unwrap();
+
+        let doc = DocumentationFile {
+            id: "d1".to_string(),
+            repository_id: "r".to_string(),
+            file_path: "README.md".to_string(),
+            file_name: "README.md".to_string(),
+            doc_type: DocumentationType::Readme,
+            title: None,
+            description: None,
+            content_preview: String::new(),
+            word_count: 0,
+            line_count: 0,
+            has_code_examples: false,
+            has_api_references: false,
+            has_diagrams: false,
+            metadata: serde_json::json!({}),
+        };
+        let endpoint = |method: HttpMethod, path: &str| DetectedEndpoint {
+            path: path.to_string(),
+            method,
+            handler: None,
+            file_path: "src/server.js".to_string(),
+            line_number: Some(3),
+            framework: Some("express".to_string()),
+            middleware: Vec::new(),
+            parameters: Vec::new(),
+            protocol: EndpointProtocol::Http,
+        };
+        let endpoints = vec![endpoint(HttpMethod::Get, "/orders/:id"), endpoint(HttpMethod::Post, "/orders")];
+
+        let drift = DocDriftDetector::new().detect(temp_dir.path(), &[doc], &endpoints, &["Postgres", "Stripe"], &["src/server.js"]);
+        let summary: Vec<(DriftKind, DriftDirection, &str)> = drift.iter()
+            .map(|d| (d.kind, d.direction, d.subject.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (DriftKind::Service, DriftDirection::Undetected, "Redis"),
+            (DriftKind::Service, DriftDirection::Undocumented, "Stripe"),
+            (DriftKind::Endpoint, DriftDirection::Undetected, "DELETE /api/orders/{id}"),
+            (DriftKind::Endpoint, DriftDirection::Undocumented, "POST /orders"),
+            (DriftKind::EnvVar, DriftDirection::Undetected, "LEGACY_TOKEN"),
+            (DriftKind::EnvVar, DriftDirection::Undocumented, "STRIPE_KEY"),
+        ]);
+        assert_eq!(drift[0].message, "README.md mentions Redis but no Redis usage was detected");
+    }
+
+    #[test]
+    fn test_find_env_var() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".env.example"), "# DATABASE_URL=postgres://localhost\nPORT=8080\n").unwrap();
+        fs::write(temp_dir.path().join("Dockerfile"), "FROM node\nENV DATABASE_URL=postgres://db\n").unwrap();
+        fs::write(temp_dir.path().join("app.py"), "import os\nurl = os.getenv('DATABASE_URL')\n").unwrap();
+
+        let found: Vec<(String, usize, String)> = find_env_var(temp_dir.path(), "DATABASE_URL", &["app.py"]).into_iter()
+            .map(|r| (r.file_path, r.line_number, r.kind))
+            .collect();
+        assert_eq!(found, vec![
+            ("Dockerfile".to_string(), 2, "definition".to_string()),
+            (".env.example".to_string(), 1, "definition".to_string()),
+            ("app.py".to_string(), 2, "read".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,242 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use crate::analysis::doc_quality::MARKDOWN_LINK;
+use crate::analysis::symbols::SymbolTable;
+use crate::analysis::documentation::DocumentationFile;
+use crate::analysis::{CodeElement, CodeElementType};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DocLinkKind {
+    Internal, // Link to another file in the repository
+    External, // http(s) URL
+    Code,     // Code element or source file named in the text
+    Service,  // Detected service mentioned by name
+}
+
+impl DocLinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocLinkKind::Internal => "internal",
+            DocLinkKind::External => "external",
+            DocLinkKind::Code => "code",
+            DocLinkKind::Service => "service",
+        }
+    }
+}
+
+/// One reference from a documentation file. `target_file` is the repository path the reference
+/// resolves to and `target_name` the component name, so dependents can be found after re-analysis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocLink {
+    pub doc_path: String,
+    pub line_number: usize,
+    pub kind: DocLinkKind,
+    pub target: String,
+    pub target_file: Option<String>,
+    pub target_name: Option<String>,
+}
+
+static URL: Lazy<Regex> = Lazy::new(|| Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#).unwrap());
+static CODE_SPAN: Lazy<Regex> = Lazy::new(|| Regex::new(r"`([^`\n]{3,120})`").unwrap());
+
+/// Names too common to count as a reference to one particular element
+const GENERIC_NAMES: &[&str] = &["main", "index", "init", "new", "test", "setup", "config", "default", "run", "start", "get", "set", "data", "app", "utils", "helpers"];
+
+/// Extracts intra-repo links, external URLs and code/service references from documentation
+pub struct DocLinkExtractor;
+
+impl DocLinkExtractor {
+    pub fn new() -> Self {
+        DocLinkExtractor
+    }
+
+    pub fn extract(&self, repo_path: &Path, docs: &[DocumentationFile], elements: &[CodeElement], service_names: &[&str]) -> Vec<DocLink> {
+        // Element names that identify a single declaration worth linking to
+        let mut by_name: HashMap<&str, Vec<&CodeElement>> = HashMap::new();
+        for element in elements.iter().filter(|e| !matches!(e.element_type, CodeElementType::Variable | CodeElementType::Constant)) {
+            if element.name.len() >= 4 && !GENERIC_NAMES.contains(&element.name.to_lowercase().as_str()) {
+                by_name.entry(element.name.as_str()).or_default().push(element);
+            }
+        }
+        let code_files: HashSet<&str> = elements.iter().map(|e| e.file_path.as_str()).collect();
+        let symbols = SymbolTable::new(elements);
+        let elements_by_id: HashMap<&str, &CodeElement> = elements.iter().map(|e| (e.id.as_str(), e)).collect();
+        let service_patterns: Vec<(&str, Regex)> = service_names.iter()
+            .filter(|n| n.len() >= 3)
+            .filter_map(|n| Regex::new(&format!(r"(?i)\b{}\b", regex::escape(n))).ok().map(|re| (*n, re)))
+            .collect();
+
+        let mut links = Vec::new();
+        for doc in docs {
+            let content = match std::fs::read_to_string(repo_path.join(&doc.file_path)) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let doc_dir = Path::new(&doc.file_path).parent().unwrap_or(Path::new(""));
+            let mut seen: HashSet<(DocLinkKind, String, Option<String>)> = HashSet::new();
+            let mut push = |links: &mut Vec<DocLink>, line_number: usize, kind: DocLinkKind, target: &str, target_file: Option<String>, target_name: Option<String>| {
+                let key = target_file.clone().unwrap_or_else(|| target.to_string());
+                if seen.insert((kind, key, target_name.clone())) {
+                    links.push(DocLink {
+                        doc_path: doc.file_path.clone(),
+                        line_number,
+                        kind,
+                        target: target.to_string(),
+                        target_file,
+                        target_name,
+                    });
+                }
+            };
+
+            for (idx, line) in content.lines().enumerate() {
+                let line_number = idx + 1;
+
+                for cap in MARKDOWN_LINK.captures_iter(line) {
+                    let target = &cap[1];
+                    if target.contains("://") || target.starts_with('#') || target.starts_with("mailto:") {
+                        continue;
+                    }
+                    let file = target.split(['#', '?']).next().unwrap_or("").replace("%20", " ");
+                    let resolved = match file.strip_prefix('/') {
+                        Some(rooted) => normalize(Path::new(rooted)),
+                        None => normalize(&doc_dir.join(&file)),
+                    };
+                    if let Some(resolved) = resolved.filter(|r| repo_path.join(r).exists()) {
+                        let kind = if code_files.contains(resolved.as_str()) { DocLinkKind::Code } else { DocLinkKind::Internal };
+                        push(&mut links, line_number, kind, target, Some(resolved), None);
+                    }
+                }
+
+                for url in URL.find_iter(line) {
+                    let url = url.as_str().trim_end_matches(['.', ',', ';', ':']);
+                    push(&mut links, line_number, DocLinkKind::External, url, None, None);
+                }
+
+                for cap in CODE_SPAN.captures_iter(line) {
+                    let span = cap[1].trim();
+                    if code_files.contains(span.trim_start_matches("./")) {
+                        let file = span.trim_start_matches("./").to_string();
+                        push(&mut links, line_number, DocLinkKind::Code, span, Some(file), None);
+                        continue;
+                    }
+                    // A qualified reference that names one declaration links only to it
+                    let reference = span.split('(').next().unwrap_or("").trim();
+                    if reference.contains(['.', ':', '#']) {
+                        if let Some(element) = symbols.resolve(reference, &doc.file_path).and_then(|id| elements_by_id.get(id)) {
+                            push(&mut links, line_number, DocLinkKind::Code, span, Some(element.file_path.clone()), Some(element.name.clone()));
+                            continue;
+                        }
+                    }
+                    // `OrderService.create()` / `orders::OrderService` / `createOrder(id)`: the innermost known segment
+                    let known = reference
+                        .rsplit(['.', ':', '#'])
+                        .find_map(|segment| by_name.get(segment.trim()));
+                    if let Some(candidates) = known {
+                        // Ambiguous names link to every declaration so all owners see the change
+                        for element in candidates {
+                            push(&mut links, line_number, DocLinkKind::Code, span, Some(element.file_path.clone()), Some(element.name.clone()));
+                        }
+                    }
+                }
+
+                for (name, pattern) in &service_patterns {
+                    if pattern.is_match(line) {
+                        push(&mut links, line_number, DocLinkKind::Service, name, None, Some(name.to_string()));
+                    }
+                }
+            }
+        }
+
+        links
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem; `None` when the path escapes the repository
+fn normalize(path: &Path) -> Option<String> {
+    let mut parts = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::ParentDir => {
+                if !parts.pop() {
+                    return None;
+                }
+            }
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    let normalized = parts.to_string_lossy().replace('\\', "/");
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::documentation::DocumentationType;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extracts_links_code_references_and_services() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/orders.ts"), "export class OrderService {}\n").unwrap();
+        fs::write(temp_dir.path().join("docs/setup.md"), "# Setup\n").unwrap();
+        fs::write(
+            temp_dir.path().join("docs/guide.md"),
+            "See [setup](./setup.md) and [missing](nope.md).\nCall `OrderService.create()` in [orders](../src/orders.ts).\nOrders are cached in Redis, see https://redis.io/docs.\nStart with `main`.\n",
+        ).unwrap();
+
+        let doc = DocumentationFile {
+            id: "d1".to_string(),
+            repository_id: "r".to_string(),
+            file_path: "docs/guide.md".to_string(),
+            file_name: "guide.md".to_string(),
+            doc_type: DocumentationType::Tutorial,
+            title: None,
+            description: None,
+            content_preview: String::new(),
+            word_count: 0,
+            line_count: 0,
+            has_code_examples: false,
+            has_api_references: false,
+            has_diagrams: false,
+            metadata: serde_json::json!({}),
+        };
+        let element = CodeElement {
+            id: "e1".to_string(),
+            name: "OrderService".to_string(),
+            element_type: CodeElementType::Class,
+            file_path: "src/orders.ts".to_string(),
+            line_number: 1,
+            language: "typescript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        };
+
+        let links = DocLinkExtractor::new().extract(temp_dir.path(), &[doc], &[element], &["Redis"]);
+        let summary: Vec<(DocLinkKind, Option<&str>, Option<&str>)> = links.iter()
+            .map(|l| (l.kind, l.target_file.as_deref(), l.target_name.as_deref()))
+            .collect();
+        assert_eq!(summary, vec![
+            (DocLinkKind::Internal, Some("docs/setup.md"), None),
+            (DocLinkKind::Code, Some("src/orders.ts"), None),
+            (DocLinkKind::Code, Some("src/orders.ts"), Some("OrderService")),
+            (DocLinkKind::External, None, None),
+            (DocLinkKind::Service, None, Some("Redis")),
+        ]);
+        assert_eq!(links[3].target, "https://redis.io/docs");
+    }
+}
@@ -0,0 +1,305 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::analysis::doc_drift::DocDrift;
+use crate::analysis::documentation::{DocumentationFile, DocumentationType};
+use crate::analysis::git_blame::GitBlame;
+
+/// Docs further behind the latest code change than this are reported as stale
+const STALE_AFTER_DAYS: i64 = 180;
+/// Commits walked to find when docs and code last changed
+const HISTORY_DEPTH: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocCheck {
+    pub name: String,
+    pub passed: bool,
+    pub weight: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrokenLink {
+    pub file_path: String,
+    pub line_number: usize,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleDoc {
+    pub file_path: String,
+    pub doc_updated_at: String,
+    pub code_updated_at: String,
+    pub days_behind: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocQualityReport {
+    pub score: f64, // 0-100, higher is better
+    pub grade: String,
+    pub checks: Vec<DocCheck>,
+    pub broken_links: Vec<BrokenLink>,
+    pub stale_docs: Vec<StaleDoc>,
+    pub gaps: Vec<String>, // Actionable suggestions for failed checks
+    #[serde(default)]
+    pub drift: Vec<DocDrift>, // Endpoints, services and env vars the docs and the code disagree on
+}
+
+impl DocQualityReport {
+    /// Attach drift findings; they are listed with the gaps but do not change the score
+    pub fn with_drift(mut self, drift: Vec<DocDrift>) -> Self {
+        if let Some(first) = drift.first() {
+            self.gaps.push(format!("Reconcile {} places where the docs and the code disagree (first: {})", drift.len(), first.message));
+        }
+        self.drift = drift;
+        self
+    }
+}
+
+/// Markdown links and images: `[text](target)` / `![alt](target "title")`
+pub static MARKDOWN_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap()
+});
+
+/// A topic onboarding docs should cover, found by heading keyword or by a dedicated doc file
+struct SectionCheck {
+    name: &'static str,
+    weight: f64,
+    keywords: &'static [&'static str],
+    doc_types: &'static [DocumentationType],
+    gap: &'static str,
+}
+
+const SECTION_CHECKS: &[SectionCheck] = &[
+    SectionCheck {
+        name: "setup",
+        weight: 15.0,
+        keywords: &["install", "setup", "set up", "getting started", "prerequisites", "requirements"],
+        doc_types: &[DocumentationType::Setup],
+        gap: "Add an installation/setup section listing prerequisites and how to install dependencies",
+    },
+    SectionCheck {
+        name: "run",
+        weight: 15.0,
+        keywords: &["usage", "running", "run", "quick start", "quickstart", "start", "development", "local"],
+        doc_types: &[DocumentationType::Tutorial],
+        gap: "Add a usage section showing how to run the project locally",
+    },
+    SectionCheck {
+        name: "deploy",
+        weight: 10.0,
+        keywords: &["deploy", "deployment", "release", "production", "hosting"],
+        doc_types: &[],
+        gap: "Document how the project is deployed or released",
+    },
+    SectionCheck {
+        name: "architecture",
+        weight: 15.0,
+        keywords: &["architecture", "design", "overview", "how it works", "components", "structure"],
+        doc_types: &[DocumentationType::Architecture, DocumentationType::Adr],
+        gap: "Describe the architecture: main components, how they interact and where data lives",
+    },
+    SectionCheck {
+        name: "configuration",
+        weight: 10.0,
+        keywords: &["configuration", "config", "environment", "settings", "env"],
+        doc_types: &[],
+        gap: "Document configuration and required environment variables",
+    },
+];
+
+/// Scores how well a repository's documentation onboards a new contributor
+pub struct DocQualityScorer {
+    history_depth: usize,
+}
+
+impl DocQualityScorer {
+    pub fn new() -> Self {
+        DocQualityScorer { history_depth: HISTORY_DEPTH }
+    }
+
+    /// Commits walked to find stale docs; 0 skips the check
+    pub fn with_history_depth(mut self, history_depth: usize) -> Self {
+        self.history_depth = history_depth;
+        self
+    }
+
+    pub fn score(&self, repo_path: &Path, docs: &[DocumentationFile], code_files: &[&str]) -> DocQualityReport {
+        let mut checks = Vec::new();
+        let mut gaps = Vec::new();
+
+        let readme = docs.iter()
+            .filter(|d| d.doc_type == DocumentationType::Readme)
+            .min_by_key(|d| d.file_path.matches('/').count());
+        let readme_content = readme
+            .and_then(|r| std::fs::read_to_string(repo_path.join(&r.file_path)).ok())
+            .unwrap_or_default();
+        checks.push(DocCheck {
+            name: "readme".to_string(),
+            passed: readme.is_some() && readme_content.split_whitespace().count() >= 50,
+            weight: 20.0,
+            detail: match readme {
+                Some(r) => format!("{} ({} words)", r.file_path, r.word_count),
+                None => "No README found".to_string(),
+            },
+        });
+        if readme.is_none() {
+            gaps.push("Add a README.md at the repository root describing what the project does".to_string());
+        } else if readme_content.split_whitespace().count() < 50 {
+            gaps.push("Expand the README beyond a one-line description".to_string());
+        }
+
+        let headings: Vec<String> = readme_content.lines()
+            .filter(|l| l.trim_start().starts_with('#'))
+            .map(|l| l.trim_start_matches('#').trim().to_lowercase())
+            .chain(docs.iter().filter_map(|d| d.title.as_ref().map(|t| t.to_lowercase())))
+            .collect();
+        for check in SECTION_CHECKS {
+            let by_heading = headings.iter().find(|h| check.keywords.iter().any(|k| contains_word(h, k)));
+            let by_doc = docs.iter().find(|d| check.doc_types.contains(&d.doc_type));
+            let passed = by_heading.is_some() || by_doc.is_some();
+            checks.push(DocCheck {
+                name: check.name.to_string(),
+                passed,
+                weight: check.weight,
+                detail: match (by_heading, by_doc) {
+                    (Some(h), _) => format!("Section \"{}\"", h),
+                    (None, Some(d)) => d.file_path.clone(),
+                    (None, None) => "Not documented".to_string(),
+                },
+            });
+            if !passed {
+                gaps.push(check.gap.to_string());
+            }
+        }
+
+        let contributing = docs.iter().find(|d| d.doc_type == DocumentationType::Contributing);
+        checks.push(DocCheck {
+            name: "contributing".to_string(),
+            passed: contributing.is_some(),
+            weight: 5.0,
+            detail: contributing.map(|d| d.file_path.clone()).unwrap_or_else(|| "No CONTRIBUTING guide".to_string()),
+        });
+        if contributing.is_none() {
+            gaps.push("Add a CONTRIBUTING.md explaining how to build, test and submit changes".to_string());
+        }
+
+        let broken_links = find_broken_links(repo_path, docs);
+        checks.push(DocCheck {
+            name: "links".to_string(),
+            passed: broken_links.is_empty(),
+            weight: 10.0,
+            detail: format!("{} broken relative links", broken_links.len()),
+        });
+        if !broken_links.is_empty() {
+            gaps.push(format!("Fix {} broken links (first: {} in {})", broken_links.len(), broken_links[0].target, broken_links[0].file_path));
+        }
+
+        let stale_docs = find_stale_docs(repo_path, docs, code_files, self.history_depth);
+        checks.push(DocCheck {
+            name: "freshness".to_string(),
+            passed: stale_docs.is_empty(),
+            weight: 10.0,
+            detail: format!("{} docs more than {} days behind the code", stale_docs.len(), STALE_AFTER_DAYS),
+        });
+        for stale in stale_docs.iter().take(3) {
+            gaps.push(format!("Review {}: last updated {} days before the latest code change", stale.file_path, stale.days_behind));
+        }
+
+        let total: f64 = checks.iter().map(|c| c.weight).sum();
+        let earned: f64 = checks.iter().filter(|c| c.passed).map(|c| c.weight).sum();
+        let score = if total == 0.0 { 0.0 } else { (earned / total * 1000.0).round() / 10.0 };
+        let grade = match score {
+            s if s >= 90.0 => "A",
+            s if s >= 75.0 => "B",
+            s if s >= 60.0 => "C",
+            s if s >= 40.0 => "D",
+            _ => "E",
+        };
+
+        DocQualityReport {
+            score,
+            grade: grade.to_string(),
+            checks,
+            broken_links,
+            stale_docs,
+            gaps,
+            drift: Vec::new(),
+        }
+    }
+}
+
+fn contains_word(haystack: &str, word: &str) -> bool {
+    haystack.match_indices(word).any(|(idx, _)| {
+        let before = haystack[..idx].chars().next_back();
+        let after = haystack[idx + word.len()..].chars().next();
+        !before.map(|c| c.is_alphanumeric()).unwrap_or(false) && !after.map(|c| c.is_alphanumeric()).unwrap_or(false)
+    })
+}
+
+/// Relative links in markdown docs whose target file does not exist
+fn find_broken_links(repo_path: &Path, docs: &[DocumentationFile]) -> Vec<BrokenLink> {
+    let mut broken = Vec::new();
+    for doc in docs.iter().filter(|d| d.file_path.to_lowercase().ends_with(".md")) {
+        let content = match std::fs::read_to_string(repo_path.join(&doc.file_path)) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let doc_dir = Path::new(&doc.file_path).parent().unwrap_or(Path::new(""));
+        let mut in_code_block = false;
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if in_code_block {
+                continue;
+            }
+            for cap in MARKDOWN_LINK.captures_iter(line) {
+                let target = &cap[1];
+                let file = target.split(['#', '?']).next().unwrap_or("").replace("%20", " ");
+                if file.is_empty() || target.contains("://") || target.starts_with("mailto:") || target.starts_with("tel:") {
+                    continue;
+                }
+                let resolved = match file.strip_prefix('/') {
+                    Some(rooted) => repo_path.join(rooted),
+                    None => repo_path.join(doc_dir).join(&file),
+                };
+                if !resolved.exists() {
+                    broken.push(BrokenLink {
+                        file_path: doc.file_path.clone(),
+                        line_number: idx + 1,
+                        target: target.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    broken
+}
+
+/// Onboarding docs last changed long before the most recent code change
+fn find_stale_docs(repo_path: &Path, docs: &[DocumentationFile], code_files: &[&str], history_depth: usize) -> Vec<StaleDoc> {
+    let changes = GitBlame::open(repo_path).recent_changes(history_depth);
+    let code_updated = match code_files.iter().filter_map(|f| changes.get(*f)).max() {
+        Some(t) => *t,
+        None => return Vec::new(),
+    };
+    let to_rfc3339 = |ts: i64| chrono::DateTime::from_timestamp(ts, 0).map(|t| t.to_rfc3339()).unwrap_or_default();
+
+    let mut stale: Vec<StaleDoc> = docs.iter()
+        .filter(|d| matches!(d.doc_type, DocumentationType::Readme | DocumentationType::Setup | DocumentationType::Architecture | DocumentationType::ApiDocs))
+        .filter_map(|d| {
+            let doc_updated = *changes.get(&d.file_path)?;
+            let days_behind = (code_updated - doc_updated) / 86_400;
+            (days_behind > STALE_AFTER_DAYS).then(|| StaleDoc {
+                file_path: d.file_path.clone(),
+                doc_updated_at: to_rfc3339(doc_updated),
+                code_updated_at: to_rfc3339(code_updated),
+                days_behind,
+            })
+        })
+        .collect();
+    stale.sort_by_key(|s| std::cmp::Reverse(s.days_behind));
+    stale
+}
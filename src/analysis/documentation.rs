@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use walkdir::WalkDir;
 use uuid::Uuid;
+use crate::analysis::adr;
+use crate::analysis::repo_path::RepoPath;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentationFile {
@@ -33,6 +35,7 @@ pub enum DocumentationType {
     Setup,
     Tutorial,
     Reference,
+    Adr, // Architecture Decision Record (docs/adr/*, MADR)
     Other,
 }
 
@@ -64,6 +67,9 @@ impl DocumentationType {
             && (file_name.ends_with(".md") || file_name.ends_with(".txt") || file_name.ends_with(".rst") || !file_name.contains(".")) {
             return DocumentationType::ApiDocs;
         }
+        if adr::is_adr_path(path) || file_name.contains("architecture-decision") {
+            return DocumentationType::Adr;
+        }
         if file_name.contains("architecture") || file_name.contains("arch") {
             return DocumentationType::Architecture;
         }
@@ -203,7 +209,13 @@ impl DocumentationIndexer {
             .unwrap_or("unknown")
             .to_string();
         
-        let doc_type = DocumentationType::from_path(path);
+        let mut doc_type = DocumentationType::from_path(path);
+        // ADRs kept outside an adr/ directory are recognized by their sections
+        if matches!(doc_type, DocumentationType::Reference | DocumentationType::Other | DocumentationType::Architecture)
+            && adr::looks_like_adr(content)
+        {
+            doc_type = DocumentationType::Adr;
+        }
         
         // Extract title (first heading or first line)
         let title = extract_title(content);
@@ -224,7 +236,7 @@ impl DocumentationIndexer {
         let has_diagrams = detect_diagrams(content);
         
         // Extract metadata
-        let metadata = extract_metadata(content, &doc_type);
+        let metadata = extract_metadata(content, normalized_path, &doc_type);
         
         Ok(DocumentationFile {
             id: Uuid::new_v4().to_string(),
@@ -246,10 +258,7 @@ impl DocumentationIndexer {
 }
 
 fn normalize_path(path: &Path, repo_path: &Path) -> String {
-    path.strip_prefix(repo_path)
-        .unwrap_or(path)
-        .to_string_lossy()
-        .replace('\\', "/")
+    RepoPath::new(repo_path, path).into_string()
 }
 
 fn extract_title(content: &str) -> Option<String> {
@@ -335,7 +344,7 @@ fn detect_diagrams(content: &str) -> bool {
     content.contains("![") && (content.contains("diagram") || content.contains("arch"))
 }
 
-fn extract_metadata(content: &str, doc_type: &DocumentationType) -> serde_json::Value {
+fn extract_metadata(content: &str, file_path: &str, doc_type: &DocumentationType) -> serde_json::Value {
     let mut metadata = serde_json::json!({});
     
     // Extract frontmatter if present (YAML frontmatter)
@@ -369,6 +378,15 @@ fn extract_metadata(content: &str, doc_type: &DocumentationType) -> serde_json::
         }
     }
     
+    if *doc_type == DocumentationType::Adr {
+        if let Some(decision) = adr::parse_adr(content, file_path) {
+            metadata["adr_number"] = serde_json::json!(decision.number);
+            metadata["adr_status"] = serde_json::json!(decision.status);
+            metadata["adr_date"] = serde_json::json!(decision.date);
+            metadata["adr_decision"] = serde_json::json!(decision.decision);
+        }
+    }
+    
     metadata
 }
 
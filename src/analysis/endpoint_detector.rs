@@ -1,8 +1,45 @@
 use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use walkdir::WalkDir;
+use crate::analysis::{endpoint_middleware, endpoint_mounts, realtime_endpoints};
+use crate::analysis::repo_path::RepoPath;
+use once_cell::sync::Lazy;
+
+/// Lines a multi-line route registration may span
+const MAX_CALL_LINES: usize = 8;
+
+/// `get(list_users)` and `.post(create_user)` in an axum or poem method router
+static METHOD_ROUTER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(get|post|put|delete|patch|options|head|any)\s*\(\s*([\w:]*)").unwrap()
+});
+/// The import that marks a JS file as a Hono, Koa or hapi app rather than Express
+static JS_FRAMEWORK_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:from\s+|require\s*\(\s*)['"](hono|koa|@koa/router|koa-router|@hapi/hapi)['"/]"#).unwrap()
+});
+static JS_APP_BINDING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:const|let|var)\s+(\w+)\s*=\s*new\s+(?:Hono|OpenAPIHono|Router|KoaRouter)\b").unwrap()
+});
+static HAPI_PATH: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bpath\s*:\s*['"]([^'"]+)['"]"#).unwrap());
+static HAPI_METHOD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bmethod\s*:\s*(\[[^\]]*\]|['"][\w*]+['"])"#).unwrap());
+static HAPI_HANDLER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bhandler\s*:\s*([\w.]*)").unwrap());
+static HAPI_METHOD_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r#"['"]([\w*]+)['"]"#).unwrap());
+/// The last plain identifier passed after the path: `app.get('/posts/:id', auth, getPost)`
+static JS_ROUTE_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\.(?:get|post|put|delete|patch|all)\s*\(\s*['"][^'"]*['"]\s*,\s*(?:[\w.]+\s*,\s*)*([\w.]+)\s*\)"#).unwrap()
+});
+static SVELTEKIT_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*export\s+(?:const\s+|(?:async\s+)?function\s+)(GET|POST|PUT|PATCH|DELETE|OPTIONS|HEAD|fallback)\b").unwrap()
+});
+/// `[id]`, `[[lang]]`, `[...rest]` and `[id=integer]` in a SvelteKit route directory
+static SVELTEKIT_PARAM: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[\[?(?:\.\.\.)?(\w+)(?:=\w+)?\]\]?").unwrap());
+static REMIX_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*export\s+(?:const\s+|(?:async\s+)?function\s+)(loader|action)\b").unwrap()
+});
+static WARP_METHOD: Lazy<Regex> = Lazy::new(|| Regex::new(r"warp::(get|post|put|delete|patch|head|options)\s*\(\)").unwrap());
+static WARP_HANDLER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.(?:and_then|map)\s*\(\s*([\w:]+)\s*\)").unwrap());
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum HttpMethod {
@@ -16,6 +53,14 @@ pub enum HttpMethod {
     Any, // Catch-all routes
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum EndpointProtocol {
+    #[default]
+    Http,
+    WebSocket,
+    Sse, // Server-sent events (text/event-stream)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedEndpoint {
     pub path: String, // e.g., "/api/users", "/users/:id"
@@ -24,8 +69,10 @@ pub struct DetectedEndpoint {
     pub file_path: String,
     pub line_number: Option<usize>,
     pub framework: Option<String>, // e.g., "express", "flask", "actix"
-    pub middleware: Vec<String>, // Middleware/guards applied to this endpoint
+    pub middleware: Vec<String>, // Middleware, guards and security annotations applied to this endpoint, as written
     pub parameters: Vec<String>, // Route parameters like :id, {id}
+    #[serde(default)]
+    pub protocol: EndpointProtocol,
 }
 
 pub struct EndpointDetector;
@@ -38,6 +85,9 @@ impl EndpointDetector {
     /// Detect API endpoints in a repository
     pub fn detect_endpoints(&self, repo_path: &Path) -> Result<Vec<DetectedEndpoint>> {
         let mut endpoints = Vec::new();
+        let mut service_wraps = HashMap::new();
+        let mut mounts = endpoint_mounts::MountTable::new();
+        let mut realtime_handlers = HashMap::new();
 
         for entry in WalkDir::new(repo_path)
             .into_iter()
@@ -64,24 +114,26 @@ impl EndpointDetector {
             if let Ok(content) = std::fs::read_to_string(path) {
                 // Detect endpoints in code files
                 if let Some(lang) = &ext {
-                    match lang.as_str() {
+                    let (mut file_endpoints, language) = match lang.as_str() {
                         "js" | "jsx" | "ts" | "tsx" => {
-                            endpoints.extend(self.detect_endpoints_js(&content, path)?);
-                        }
-                        "py" => {
-                            endpoints.extend(self.detect_endpoints_python(&content, path)?);
+                            mounts.scan_js(&content, path);
+                            (self.detect_endpoints_js(&content, path, repo_path)?, "javascript")
                         }
+                        "py" => (self.detect_endpoints_python(&content, path)?, "python"),
                         "rs" => {
-                            endpoints.extend(self.detect_endpoints_rust(&content, path)?);
-                        }
-                        "go" => {
-                            endpoints.extend(self.detect_endpoints_go(&content, path)?);
+                            endpoint_middleware::actix_service_wraps(&content, &mut service_wraps);
+                            (self.detect_endpoints_rust(&content, path)?, "rust")
                         }
-                        "java" => {
-                            endpoints.extend(self.detect_endpoints_java(&content, path)?);
-                        }
-                        _ => {}
-                    }
+                        "go" => (self.detect_endpoints_go(&content, path)?, "go"),
+                        "java" => (self.detect_endpoints_java(&content, path)?, "java"),
+                        _ => (Vec::new(), ""),
+                    };
+                    file_endpoints.extend(realtime_endpoints::detect(&content, path, language));
+                    realtime_endpoints::classify(&mut file_endpoints, &content, language);
+                    realtime_endpoints::collect_handlers(&content, language, &mut realtime_handlers);
+                    endpoint_middleware::attach(&mut file_endpoints, &content, language);
+                    endpoint_mounts::apply_file_prefixes(&mut file_endpoints, &content, language);
+                    endpoints.extend(file_endpoints);
                 }
 
                 // Detect endpoints in config files (OpenAPI, API Gateway, etc.)
@@ -96,12 +148,69 @@ impl EndpointDetector {
             }
         }
 
+        endpoint_middleware::apply_service_wraps(&mut endpoints, &service_wraps);
+        realtime_endpoints::apply_handlers(&mut endpoints, &realtime_handlers);
+        mounts.apply(&mut endpoints);
+        for endpoint in &mut endpoints {
+            RepoPath::normalize(repo_path, &mut endpoint.file_path);
+            // Mount prefixes can carry parameters of their own (`/users/:userId/posts`)
+            if endpoint.path.contains([':', '{']) {
+                endpoint.parameters = self.extract_route_params(&endpoint.path);
+            }
+        }
+
         Ok(endpoints)
     }
 
-    fn detect_endpoints_js(&self, content: &str, file_path: &Path) -> Result<Vec<DetectedEndpoint>> {
+    /// Uses the repository's detected frameworks as hints for detected endpoints.
+    /// When several patterns match the same route (e.g. the Express and Next.js regexes on one line),
+    /// the match from a detected framework wins, and detected-framework endpoints are listed first.
+    pub fn prefer_frameworks(endpoints: Vec<DetectedEndpoint>, frameworks: &[String]) -> Vec<DetectedEndpoint> {
+        if frameworks.is_empty() {
+            return endpoints;
+        }
+
+        let is_known = |e: &DetectedEndpoint| {
+            e.framework.as_ref().map(|f| frameworks.contains(f)).unwrap_or(false)
+        };
+
+        let mut prioritized: Vec<DetectedEndpoint> = Vec::with_capacity(endpoints.len());
+        for endpoint in endpoints {
+            let duplicate = prioritized.iter().position(|e| {
+                e.path == endpoint.path
+                    && e.method == endpoint.method
+                    && e.file_path == endpoint.file_path
+                    && e.line_number == endpoint.line_number
+            });
+            match duplicate {
+                Some(idx) => {
+                    if !is_known(&prioritized[idx]) && is_known(&endpoint) {
+                        prioritized[idx] = endpoint;
+                    }
+                }
+                None => prioritized.push(endpoint),
+            }
+        }
+
+        // Stable sort keeps file order within each group
+        prioritized.sort_by_key(|e| !is_known(e));
+        prioritized
+    }
+
+    fn detect_endpoints_js(&self, content: &str, file_path: &Path, repo_path: &Path) -> Result<Vec<DetectedEndpoint>> {
+        if let Some(endpoints) = self.detect_file_routes_js(content, file_path, repo_path) {
+            return Ok(endpoints);
+        }
         let mut endpoints = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
+        // Hono and Koa share Express's app.get('/path', handler) shape; the file's imports tell them apart
+        let framework = JS_FRAMEWORK_IMPORT.captures(content)
+            .map(|cap| match &cap[1] {
+                "hono" => "hono",
+                "@hapi/hapi" => "hapi",
+                _ => "koa",
+            })
+            .unwrap_or("express");
 
         // Express: app.get('/api/users', handler)
         let express_get = Regex::new(r#"(?:app|router|express)\.(get|post|put|delete|patch|all)\s*\(\s*['"]([^'"]+)['"]"#)?;
@@ -109,10 +218,20 @@ impl EndpointDetector {
         let router_pattern = Regex::new(r#"router\.(get|post|put|delete|patch|all)\s*\(\s*['"]([^'"]+)['"]"#)?;
         // Fastify: fastify.get('/api/users', handler)
         let fastify_pattern = Regex::new(r#"fastify\.(get|post|put|delete|patch)\s*\(\s*['"]([^'"]+)['"]"#)?;
-        // NestJS: @Get('/users'), @Post('/users')
-        let nestjs_decorator = Regex::new(r#"@(Get|Post|Put|Delete|Patch|All)\s*\(\s*['"]([^'"]+)['"]"#)?;
+        // NestJS: @Get('/users'), @Post('/users'), @Get() for the controller's own path
+        let nestjs_decorator = Regex::new(r#"@(Get|Post|Put|Delete|Patch|All)\s*\(\s*(?:['"]([^'"]*)['"]|\))"#)?;
         // Next.js API routes: export default function handler(req, res) in /api/*.js
         let nextjs_api = Regex::new(r"export\s+(?:default\s+)?(?:async\s+)?function\s+(\w+)")?;
+        // Hono apps and Koa routers bound to other names: const api = new Hono()
+        let receivers: Vec<String> = JS_APP_BINDING.captures_iter(content)
+            .map(|cap| cap[1].to_string())
+            .filter(|name| !["app", "router", "express"].contains(&name.as_str()))
+            .collect();
+        let bound_route = if receivers.is_empty() {
+            None
+        } else {
+            Some(Regex::new(&format!(r#"\b(?:{})\.(get|post|put|delete|patch|all)\s*\(\s*['"]([^'"]+)['"]"#, receivers.join("|")))?)
+        };
 
         for (line_num, line) in lines.iter().enumerate() {
             // Express routes
@@ -123,12 +242,13 @@ impl EndpointDetector {
                         endpoints.push(DetectedEndpoint {
                             path: path_str.as_str().to_string(),
                             method,
-                            handler: self.extract_handler_name(line, line_num, &lines),
+                            handler: self.js_route_handler(line, line_num, &lines),
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: Some(line_num + 1),
-                            framework: Some("express".to_string()),
+                            framework: Some(framework.to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
@@ -142,57 +262,97 @@ impl EndpointDetector {
                         endpoints.push(DetectedEndpoint {
                             path: path_str.as_str().to_string(),
                             method,
-                            handler: self.extract_handler_name(line, line_num, &lines),
+                            handler: self.js_route_handler(line, line_num, &lines),
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: Some(line_num + 1),
-                            framework: Some("express".to_string()),
+                            framework: Some(framework.to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
             }
 
-            // Fastify routes
-            for cap in fastify_pattern.captures_iter(line) {
-                if let Some(method_str) = cap.get(1) {
-                    if let Some(path_str) = cap.get(2) {
-                        let method = self.parse_method(method_str.as_str());
+            // Routes on Hono apps and Koa routers with other names
+            if let Some(bound_route) = &bound_route {
+                for cap in bound_route.captures_iter(line) {
+                    let path_str = &cap[2];
+                    endpoints.push(DetectedEndpoint {
+                        path: path_str.to_string(),
+                        method: self.parse_method(&cap[1]),
+                        handler: self.js_route_handler(line, line_num, &lines),
+                        file_path: file_path.to_string_lossy().to_string(),
+                        line_number: Some(line_num + 1),
+                        framework: Some(framework.to_string()),
+                        middleware: Vec::new(),
+                        parameters: self.extract_route_params(path_str),
+                        protocol: EndpointProtocol::Http,
+                    });
+                }
+            }
+
+            // Hapi: server.route({ method: 'GET', path: '/users', handler }) or an array of them
+            if framework == "hapi" {
+                if let Some(call) = line.find(".route(") {
+                    let arguments = self.call_arguments(&lines, line_num, call + 7);
+                    for (method, path, handler) in self.hapi_routes(&arguments) {
                         endpoints.push(DetectedEndpoint {
-                            path: path_str.as_str().to_string(),
+                            parameters: self.extract_route_params(&path),
+                            protocol: EndpointProtocol::Http,
+                            path,
                             method,
-                            handler: self.extract_handler_name(line, line_num, &lines),
+                            handler,
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: Some(line_num + 1),
-                            framework: Some("fastify".to_string()),
+                            framework: Some("hapi".to_string()),
                             middleware: Vec::new(),
-                            parameters: self.extract_route_params(path_str.as_str()),
                         });
                     }
                 }
             }
 
-            // NestJS decorators
-            for cap in nestjs_decorator.captures_iter(line) {
+            // Fastify routes
+            for cap in fastify_pattern.captures_iter(line) {
                 if let Some(method_str) = cap.get(1) {
                     if let Some(path_str) = cap.get(2) {
                         let method = self.parse_method(method_str.as_str());
-                        // Find the method name on the next few lines
-                        let handler = self.find_nestjs_handler(line_num, &lines);
                         endpoints.push(DetectedEndpoint {
                             path: path_str.as_str().to_string(),
                             method,
-                            handler,
+                            handler: self.js_route_handler(line, line_num, &lines),
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: Some(line_num + 1),
-                            framework: Some("nestjs".to_string()),
+                            framework: Some("fastify".to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
             }
 
+            // NestJS decorators
+            for cap in nestjs_decorator.captures_iter(line) {
+                if let Some(method_str) = cap.get(1) {
+                    let path_str = cap.get(2).map(|m| m.as_str()).unwrap_or("");
+                    let method = self.parse_method(method_str.as_str());
+                    // Find the method name on the next few lines
+                    let handler = self.find_nestjs_handler(line_num, &lines);
+                    endpoints.push(DetectedEndpoint {
+                        path: path_str.to_string(),
+                        method,
+                        handler,
+                        file_path: file_path.to_string_lossy().to_string(),
+                        line_number: Some(line_num + 1),
+                        framework: Some("nestjs".to_string()),
+                        middleware: Vec::new(),
+                        parameters: self.extract_route_params(path_str),
+                        protocol: EndpointProtocol::Http,
+                    });
+                }
+            }
+
             // Next.js API routes
             if file_path.to_string_lossy().contains("/api/") {
                 if let Some(cap) = nextjs_api.captures(line) {
@@ -207,6 +367,7 @@ impl EndpointDetector {
                             framework: Some("nextjs".to_string()),
                             middleware: Vec::new(),
                             parameters: Vec::new(),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
@@ -216,6 +377,57 @@ impl EndpointDetector {
         Ok(endpoints)
     }
 
+    /// SvelteKit `+server` modules and Remix route modules, whose URL comes from where the file sits
+    /// under `src/routes/` or `app/routes/`. Returns `None` for any other file.
+    /// SvelteKit `+server` files and Remix route modules; `None` for anything else, including
+    /// files under a `routes` directory that export no route handlers
+    fn detect_file_routes_js(&self, content: &str, file_path: &Path, repo_path: &Path) -> Option<Vec<DetectedEndpoint>> {
+        // Only directories inside the repository say what the file is; a checkout under /srv/app must not
+        let relative = file_path.strip_prefix(repo_path).unwrap_or(file_path);
+        let path_str = format!("/{}", relative.to_string_lossy().replace('\\', "/").trim_start_matches('/'));
+        let file_name = file_path.file_name()?.to_str()?;
+        let (framework, route, handlers) = if let Some(pos) = path_str.find("/src/routes/") {
+            if !file_name.starts_with("+server.") {
+                return None;
+            }
+            let dir = &path_str[pos + 12..path_str.len() - file_name.len()];
+            ("sveltekit", self.sveltekit_path(dir), &*SVELTEKIT_HANDLER)
+        } else if let Some(pos) = path_str.find("/app/routes/") {
+            ("remix", self.remix_path(&path_str[pos + 12..]), &*REMIX_HANDLER)
+        } else {
+            return None;
+        };
+
+        let mut endpoints = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            if let Some(cap) = handlers.captures(line) {
+                let method = match &cap[1] {
+                    "loader" => HttpMethod::Get,
+                    // Remix sends every non-GET submission to the action
+                    "action" => HttpMethod::Post,
+                    "fallback" => HttpMethod::Any,
+                    name => self.parse_method(name),
+                };
+                endpoints.push(DetectedEndpoint {
+                    path: route.clone(),
+                    method,
+                    handler: Some(cap[1].to_string()),
+                    file_path: file_path.to_string_lossy().to_string(),
+                    line_number: Some(line_num + 1),
+                    framework: Some(framework.to_string()),
+                    middleware: Vec::new(),
+                    parameters: self.extract_route_params(&route),
+                    protocol: EndpointProtocol::Http,
+                });
+            }
+        }
+        // A module under app/routes without loader or action may be anything, e.g. Express routers
+        if framework == "remix" && endpoints.is_empty() {
+            return None;
+        }
+        Some(endpoints)
+    }
+
     fn detect_endpoints_python(&self, content: &str, file_path: &Path) -> Result<Vec<DetectedEndpoint>> {
         let mut endpoints = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
@@ -251,6 +463,7 @@ impl EndpointDetector {
                         framework: Some("flask".to_string()),
                         middleware: Vec::new(),
                         parameters: self.extract_route_params(path_str.as_str()),
+                        protocol: EndpointProtocol::Http,
                     });
                 }
             }
@@ -270,6 +483,7 @@ impl EndpointDetector {
                             framework: Some("fastapi".to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
@@ -289,6 +503,7 @@ impl EndpointDetector {
                             framework: Some("django".to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
@@ -306,8 +521,14 @@ impl EndpointDetector {
         let actix_get = Regex::new(r#"#\[(get|post|put|delete|patch)\s*\(\s*"([^"]+)""#)?;
         // Actix: .route("/users", web::get().to(handler))
         let actix_route = Regex::new(r#"\.route\s*\(\s*"([^"]+)""#)?;
-        // Axum: Router::new().route("/users", get(handler))
-        let _axum_route = Regex::new(r#"\.route\s*\(\s*"([^"]+)""#)?;
+        // Axum: Router::new().route("/users", get(list).post(create)); poem: Route::new().at("/users", get(list))
+        let poem_at = Regex::new(r#"\.at\s*\(\s*"([^"]+)""#)?;
+        // Poem OpenAPI: #[oai(path = "/users", method = "get")]
+        let oai_path = Regex::new(r#"#\[oai\s*\([^\]]*\bpath\s*=\s*"([^"]+)""#)?;
+        let oai_method = Regex::new(r#"\bmethod\s*=\s*"(\w+)""#)?;
+        // Warp: warp::path!("users" / u32) or warp::path("users").and(warp::path::param())
+        let warp_macro = Regex::new(r"warp::path!\s*\(([^)]*)\)")?;
+        let warp_segment = Regex::new(r#"warp::path\s*\(\s*"([^"]+)"\s*\)|warp::path::param\s*(?:::<\s*(\w+)\s*>)?\s*\(\)"#)?;
         // Rocket: #[get("/users")]
         let rocket_get = Regex::new(r#"#\[(get|post|put|delete|patch)\s*\(\s*"([^"]+)""#)?;
 
@@ -327,29 +548,121 @@ impl EndpointDetector {
                             framework: Some("actix".to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
             }
 
-            // Actix route method
+            // Actix route method, or an axum method router: .route("/users", get(list).post(create))
             if let Some(cap) = actix_route.captures(line) {
                 if let Some(path_str) = cap.get(1) {
-                    let method = self.extract_actix_method(line);
-                    let handler = self.extract_actix_handler(line);
+                    let arguments = self.call_arguments(&lines, line_num, cap.get(0).unwrap().end());
+                    let routers = self.method_routers(&arguments);
+                    if arguments.contains("web::") || arguments.contains(".to(") || routers.is_empty() {
+                        let method = self.extract_actix_method(line);
+                        let handler = self.extract_actix_handler(line);
+                        endpoints.push(DetectedEndpoint {
+                            path: path_str.as_str().to_string(),
+                            method,
+                            handler,
+                            file_path: file_path.to_string_lossy().to_string(),
+                            line_number: Some(line_num + 1),
+                            framework: Some("actix".to_string()),
+                            middleware: Vec::new(),
+                            parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
+                        });
+                    } else {
+                        for (method, handler) in routers {
+                            endpoints.push(DetectedEndpoint {
+                                path: path_str.as_str().to_string(),
+                                method,
+                                handler,
+                                file_path: file_path.to_string_lossy().to_string(),
+                                line_number: Some(line_num + 1),
+                                framework: Some("axum".to_string()),
+                                middleware: Vec::new(),
+                                parameters: self.extract_route_params(path_str.as_str()),
+                                protocol: EndpointProtocol::Http,
+                            });
+                        }
+                    }
+                }
+            }
+
+            // Poem routes
+            if let Some(cap) = poem_at.captures(line) {
+                if let Some(path_str) = cap.get(1) {
+                    let arguments = self.call_arguments(&lines, line_num, cap.get(0).unwrap().end());
+                    for (method, handler) in self.method_routers(&arguments) {
+                        endpoints.push(DetectedEndpoint {
+                            path: path_str.as_str().to_string(),
+                            method,
+                            handler,
+                            file_path: file_path.to_string_lossy().to_string(),
+                            line_number: Some(line_num + 1),
+                            framework: Some("poem".to_string()),
+                            middleware: Vec::new(),
+                            parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
+                        });
+                    }
+                }
+            }
+
+            // Poem OpenAPI operations
+            if let Some(cap) = oai_path.captures(line) {
+                if let Some(path_str) = cap.get(1) {
+                    let method = oai_method.captures(line)
+                        .map(|m| self.parse_method(&m[1]))
+                        .unwrap_or(HttpMethod::Any);
                     endpoints.push(DetectedEndpoint {
                         path: path_str.as_str().to_string(),
                         method,
-                        handler,
+                        handler: self.find_rust_handler(line_num, &lines),
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: Some(line_num + 1),
-                        framework: Some("actix".to_string()),
+                        framework: Some("poem".to_string()),
                         middleware: Vec::new(),
                         parameters: self.extract_route_params(path_str.as_str()),
+                        protocol: EndpointProtocol::Http,
                     });
                 }
             }
 
+            // Warp filters
+            let warp_path = match warp_macro.captures(line) {
+                Some(cap) => Some(self.warp_macro_path(&cap[1])),
+                None => {
+                    let segments: Vec<String> = warp_segment.captures_iter(line)
+                        .map(|cap| match cap.get(1) {
+                            Some(segment) => segment.as_str().to_string(),
+                            None => format!("{{{}}}", cap.get(2).map(|t| t.as_str()).unwrap_or("param")),
+                        })
+                        .collect();
+                    if segments.is_empty() { None } else { Some(format!("/{}", segments.join("/"))) }
+                }
+            };
+            if let Some(path) = warp_path {
+                let statement = self.statement_text(&lines, line_num);
+                let method = WARP_METHOD.captures(&statement)
+                    .map(|m| self.parse_method(&m[1]))
+                    .unwrap_or(HttpMethod::Any);
+                let handler = WARP_HANDLER.captures(&statement).map(|h| h[1].to_string());
+                endpoints.push(DetectedEndpoint {
+                    parameters: self.extract_route_params(&path),
+                    protocol: EndpointProtocol::Http,
+                    path,
+                    method,
+                    handler,
+                    file_path: file_path.to_string_lossy().to_string(),
+                    line_number: Some(line_num + 1),
+                    framework: Some("warp".to_string()),
+                    middleware: Vec::new(),
+                });
+            }
+
             // Rocket attributes
             for cap in rocket_get.captures_iter(line) {
                 if let Some(method_str) = cap.get(1) {
@@ -365,6 +678,7 @@ impl EndpointDetector {
                             framework: Some("rocket".to_string()),
                             middleware: Vec::new(),
                             parameters: self.extract_route_params(path_str.as_str()),
+                            protocol: EndpointProtocol::Http,
                         });
                     }
                 }
@@ -411,6 +725,7 @@ impl EndpointDetector {
                                 framework,
                                 middleware: Vec::new(),
                                 parameters: self.extract_route_params(path_str.as_str()),
+                                protocol: EndpointProtocol::Http,
                             });
                         }
                     }
@@ -425,31 +740,33 @@ impl EndpointDetector {
         let mut endpoints = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
 
-        // Spring: @GetMapping("/users"), @PostMapping("/users")
-        let spring_mapping = Regex::new(r#"@(Get|Post|Put|Delete|Patch)Mapping\s*\(\s*"([^"]+)""#)?;
-        // Spring: @RequestMapping(value = "/users", method = RequestMethod.GET)
-        let spring_request = Regex::new(r#"@RequestMapping\s*\([^)]*value\s*=\s*"([^"]+)""#)?;
+        // Spring: @GetMapping("/users"), @PostMapping(path = "/users"), bare @GetMapping for the class's own path
+        let spring_mapping = Regex::new(r#"@(Get|Post|Put|Delete|Patch)Mapping\b(?:\s*\(\s*(?:(?:value|path)\s*=\s*)?\{?\s*"([^"]*)")?"#)?;
+        // Spring: @RequestMapping("/users"), @RequestMapping(value = "/users", method = RequestMethod.GET)
+        let spring_request = Regex::new(r#"@RequestMapping\s*\(\s*(?:[^)]*\b(?:value|path)\s*=\s*)?\{?\s*"([^"]+)""#)?;
         // JAX-RS: @GET @Path("/users")
         let jaxrs_path = Regex::new(r#"@Path\s*\(\s*"([^"]+)""#)?;
+        // JAX-RS: a bare @GET serves the resource class's own path
+        let jaxrs_method = Regex::new(r"^\s*@(GET|POST|PUT|DELETE|PATCH|HEAD|OPTIONS)\s*$")?;
 
         for (line_num, line) in lines.iter().enumerate() {
             // Spring mappings
             for cap in spring_mapping.captures_iter(line) {
                 if let Some(method_str) = cap.get(1) {
-                    if let Some(path_str) = cap.get(2) {
-                        let method = self.parse_method(method_str.as_str());
-                        let handler = self.find_java_handler(line_num, &lines);
-                        endpoints.push(DetectedEndpoint {
-                            path: path_str.as_str().to_string(),
-                            method,
-                            handler,
-                            file_path: file_path.to_string_lossy().to_string(),
-                            line_number: Some(line_num + 1),
-                            framework: Some("spring".to_string()),
-                            middleware: Vec::new(),
-                            parameters: self.extract_route_params(path_str.as_str()),
-                        });
-                    }
+                    let path_str = cap.get(2).map(|m| m.as_str()).unwrap_or("");
+                    let method = self.parse_method(method_str.as_str());
+                    let handler = self.find_java_handler(line_num, &lines);
+                    endpoints.push(DetectedEndpoint {
+                        path: path_str.to_string(),
+                        method,
+                        handler,
+                        file_path: file_path.to_string_lossy().to_string(),
+                        line_number: Some(line_num + 1),
+                        framework: Some("spring".to_string()),
+                        middleware: Vec::new(),
+                        parameters: self.extract_route_params(path_str),
+                        protocol: EndpointProtocol::Http,
+                    });
                 }
             }
 
@@ -467,6 +784,7 @@ impl EndpointDetector {
                         framework: Some("spring".to_string()),
                         middleware: Vec::new(),
                         parameters: self.extract_route_params(path_str.as_str()),
+                        protocol: EndpointProtocol::Http,
                     });
                 }
             }
@@ -485,6 +803,28 @@ impl EndpointDetector {
                         framework: Some("jaxrs".to_string()),
                         middleware: Vec::new(),
                         parameters: self.extract_route_params(path_str.as_str()),
+                        protocol: EndpointProtocol::Http,
+                    });
+                }
+            }
+
+            // JAX-RS methods without a @Path of their own
+            if let Some(cap) = jaxrs_method.captures(line) {
+                // The annotations stacked with it, up to the method declaration
+                let is_annotation = |idx: &usize| lines[*idx].trim_start().starts_with('@');
+                let first = (0..line_num).rev().take_while(is_annotation).last().unwrap_or(line_num);
+                let last = (line_num..lines.len()).take_while(is_annotation).last().unwrap_or(line_num);
+                if !lines[first..=last].iter().any(|l| l.contains("@Path")) {
+                    endpoints.push(DetectedEndpoint {
+                        path: String::new(),
+                        method: self.parse_method(&cap[1]),
+                        handler: self.find_java_handler(line_num, &lines),
+                        file_path: file_path.to_string_lossy().to_string(),
+                        line_number: Some(line_num + 1),
+                        framework: Some("jaxrs".to_string()),
+                        middleware: Vec::new(),
+                        parameters: Vec::new(),
+                        protocol: EndpointProtocol::Http,
                     });
                 }
             }
@@ -503,7 +843,7 @@ impl EndpointDetector {
             "PATCH" => HttpMethod::Patch,
             "OPTIONS" => HttpMethod::Options,
             "HEAD" => HttpMethod::Head,
-            "ALL" => HttpMethod::Any,
+            "ALL" | "ANY" => HttpMethod::Any,
             _ => HttpMethod::Get,
         }
     }
@@ -551,6 +891,13 @@ impl EndpointDetector {
         None
     }
 
+    /// The handler named in a JS route registration, else a function defined on or after the line
+    fn js_route_handler(&self, line: &str, line_num: usize, lines: &[&str]) -> Option<String> {
+        JS_ROUTE_HANDLER.captures(line)
+            .map(|cap| cap[1].to_string())
+            .or_else(|| self.extract_handler_name(line, line_num, lines))
+    }
+
     fn extract_python_handler(&self, line_num: usize, lines: &[&str]) -> Option<String> {
         // Look for function definition on next few lines
         let func_pattern = Regex::new(r"def\s+(\w+)\s*\(").unwrap();
@@ -613,6 +960,140 @@ impl EndpointDetector {
             .map(|m| m.as_str().to_string())
     }
 
+    /// The arguments of the call whose opening parenthesis ends before `start` on `lines[line_num]`,
+    /// read across lines until the parenthesis closes
+    fn call_arguments(&self, lines: &[&str], line_num: usize, start: usize) -> String {
+        let mut depth = 1;
+        let mut text = String::new();
+        let open = lines[line_num][..start].rfind('(').unwrap_or(start);
+        let rest = std::iter::once(&lines[line_num][open + 1..])
+            .chain(lines.iter().skip(line_num + 1).take(MAX_CALL_LINES).copied());
+        for part in rest {
+            for c in part.chars() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return text;
+                        }
+                    }
+                    _ => {}
+                }
+                text.push(c);
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The statement starting on `lines[line_num]`, up to its `;` or a few lines
+    fn statement_text(&self, lines: &[&str], line_num: usize) -> String {
+        let mut text = String::new();
+        for line in lines.iter().skip(line_num).take(MAX_CALL_LINES) {
+            text.push_str(line);
+            text.push('\n');
+            if line.trim_end().ends_with(';') {
+                break;
+            }
+        }
+        text
+    }
+
+    /// The methods and handlers of an axum or poem method router: `get(list).post(create)`
+    fn method_routers(&self, arguments: &str) -> Vec<(HttpMethod, Option<String>)> {
+        METHOD_ROUTER.captures_iter(arguments)
+            .map(|cap| {
+                let method = self.parse_method(&cap[1]);
+                let handler = cap.get(2).map(|h| h.as_str().to_string()).filter(|h| !h.is_empty());
+                (method, handler)
+            })
+            .collect()
+    }
+
+    /// The routes in the arguments of a hapi `server.route(...)` call. Each `path` takes the `method`
+    /// and `handler` written closest to it, which keeps arrays of route objects apart.
+    fn hapi_routes(&self, arguments: &str) -> Vec<(HttpMethod, String, Option<String>)> {
+        let nearest = |matches: &[(usize, String)], pos: usize| {
+            matches.iter().min_by_key(|(p, _)| p.abs_diff(pos)).map(|(_, v)| v.clone())
+        };
+        let methods: Vec<(usize, String)> = HAPI_METHOD.captures_iter(arguments)
+            .map(|cap| (cap.get(0).unwrap().start(), cap[1].to_string()))
+            .collect();
+        let handlers: Vec<(usize, String)> = HAPI_HANDLER.captures_iter(arguments)
+            .map(|cap| (cap.get(0).unwrap().start(), cap[1].to_string()))
+            .collect();
+        HAPI_PATH.captures_iter(arguments)
+            .flat_map(|cap| {
+                let pos = cap.get(0).unwrap().start();
+                // `method: ['PUT', 'PATCH']` is one route per method; `'*'` or no method is any
+                let listed: Vec<HttpMethod> = nearest(&methods, pos)
+                    .map(|m| HAPI_METHOD_NAME.captures_iter(&m)
+                        .map(|name| if &name[1] == "*" { HttpMethod::Any } else { self.parse_method(&name[1]) })
+                        .collect())
+                    .unwrap_or_default();
+                let listed = if listed.is_empty() { vec![HttpMethod::Any] } else { listed };
+                // An inline handler function has no name to report
+                let (path, handler) = (cap[1].to_string(), nearest(&handlers, pos).filter(|h| !h.is_empty()));
+                listed.into_iter().map(move |method| (method, path.clone(), handler.clone()))
+            })
+            .collect()
+    }
+
+    /// `api/users/[id]/` -> `/api/users/:id`; `(group)` directories add nothing to the URL
+    fn sveltekit_path(&self, dir: &str) -> String {
+        let is_group = |s: &str| s.starts_with('(') && s.ends_with(')');
+        let segments: Vec<String> = dir.split('/')
+            .filter(|s| !s.is_empty() && !is_group(s))
+            .map(|s| SVELTEKIT_PARAM.replace_all(s, ":$1").to_string())
+            .collect();
+        format!("/{}", segments.join("/"))
+    }
+
+    /// `users.$id.tsx` or `users/$id/route.tsx` -> `/users/:id`. Segments starting with `_` are
+    /// pathless layouts, a trailing `_` opts out of nesting and `[.]` escapes a literal dot.
+    fn remix_path(&self, relative: &str) -> String {
+        let without_ext = match relative.rsplit_once('.') {
+            Some((stem, ext)) if !ext.contains('/') => stem,
+            _ => relative,
+        };
+        let components: Vec<&str> = without_ext.split('/').collect();
+        let mut segments = Vec::new();
+        for (idx, component) in components.iter().enumerate() {
+            if idx + 1 == components.len() && components.len() > 1 && matches!(*component, "route" | "index") {
+                continue;
+            }
+            let component = component.replace("[.]", "\u{0}");
+            for segment in component.split('.') {
+                let segment = segment.replace('\u{0}', ".").replace(['[', ']'], "");
+                let segment = segment.trim_start_matches('(').trim_end_matches(')');
+                if segment.is_empty() || segment.starts_with('_') {
+                    continue;
+                }
+                let segment = segment.trim_end_matches('_');
+                segments.push(match segment.strip_prefix('$') {
+                    Some("") => "*".to_string(),
+                    Some(param) => format!(":{}", param),
+                    None => segment.to_string(),
+                });
+            }
+        }
+        format!("/{}", segments.join("/"))
+    }
+
+    /// `"users" / u32 / "orders"` -> `/users/{u32}/orders`
+    fn warp_macro_path(&self, segments: &str) -> String {
+        let path: Vec<String> = segments.split('/')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty() && *s != "..")
+            .map(|s| match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(literal) => literal.to_string(),
+                None => format!("{{{}}}", s),
+            })
+            .collect();
+        format!("/{}", path.join("/"))
+    }
+
     fn extract_go_handler(&self, line: &str) -> Option<String> {
         // Extract handler function name
         let handler_pattern = Regex::new(r",\s*(\w+)\)").unwrap();
@@ -706,6 +1187,7 @@ impl EndpointDetector {
                                     framework: Some("openapi".to_string()),
                                     middleware: Vec::new(),
                                     parameters: self.extract_route_params(path),
+                                    protocol: EndpointProtocol::Http,
                                 });
                             }
                         }
@@ -737,6 +1219,7 @@ impl EndpointDetector {
                                     framework: Some("api-gateway".to_string()),
                                     middleware: Vec::new(),
                                     parameters: self.extract_route_params(path),
+                                    protocol: EndpointProtocol::Http,
                                 });
                             }
                         }
@@ -773,6 +1256,7 @@ impl EndpointDetector {
                                 framework: Some("openapi".to_string()),
                                 middleware: Vec::new(),
                                 parameters: self.extract_route_params(&current_path),
+                                protocol: EndpointProtocol::Http,
                             });
                         }
                     }
@@ -815,3 +1299,69 @@ impl EndpointDetector {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_axum_warp_poem_and_js_framework_routes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "server/src/main.rs", "let app = Router::new()\n    .route(\"/users\", get(list_users).post(create_user))\n    .route(\"/users/:id\", delete(handlers::remove));\nlet hello = warp::path!(\"hello\" / String).and(warp::get()).map(greet);\nlet poem = Route::new().at(\"/items\", put(update_item));\n");
+        write(root, "web/api.ts", "import { Hono } from 'hono'\nconst api = new Hono()\napi.get('/posts/:id', getPost)\napi.delete('/posts/:id', auth, posts.remove)\napi.post('/posts', async (c) => c.json({}))\n");
+        write(root, "web/hapi.js", "const Hapi = require('@hapi/hapi');\nserver.route([\n  { method: 'GET', path: '/health', handler: health },\n  { method: ['PUT', 'PATCH'], path: '/things/{id}', handler: (req, h) => null },\n  { method: '*', path: '/{any*}', handler: notFound },\n]);\n");
+        write(root, "kit/src/routes/api/users/[id]/+server.ts", "export async function GET({ params }) {}\nexport const DELETE = async () => {};\n");
+        write(root, "remix/app/routes/users.$userId_.edit.tsx", "export async function loader() {}\nexport async function action() {}\nexport default function Edit() {}\n");
+
+        let mut found: Vec<(String, HttpMethod, String, Option<String>)> = EndpointDetector::new()
+            .detect_endpoints(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.framework.unwrap_or_default(), e.method, e.path, e.handler))
+            .collect();
+        found.sort_by(|a, b| (&a.0, &a.2, format!("{:?}", a.1)).cmp(&(&b.0, &b.2, format!("{:?}", b.1))));
+        let handler = |h: &str| Some(h.to_string());
+        assert_eq!(found, vec![
+            ("axum".to_string(), HttpMethod::Get, "/users".to_string(), handler("list_users")),
+            ("axum".to_string(), HttpMethod::Post, "/users".to_string(), handler("create_user")),
+            ("axum".to_string(), HttpMethod::Delete, "/users/:id".to_string(), handler("handlers::remove")),
+            ("hapi".to_string(), HttpMethod::Get, "/health".to_string(), handler("health")),
+            ("hapi".to_string(), HttpMethod::Patch, "/things/{id}".to_string(), None),
+            ("hapi".to_string(), HttpMethod::Put, "/things/{id}".to_string(), None),
+            ("hapi".to_string(), HttpMethod::Any, "/{any*}".to_string(), handler("notFound")),
+            ("hono".to_string(), HttpMethod::Post, "/posts".to_string(), None),
+            ("hono".to_string(), HttpMethod::Delete, "/posts/:id".to_string(), handler("posts.remove")),
+            ("hono".to_string(), HttpMethod::Get, "/posts/:id".to_string(), handler("getPost")),
+            ("poem".to_string(), HttpMethod::Put, "/items".to_string(), handler("update_item")),
+            ("remix".to_string(), HttpMethod::Get, "/users/:userId/edit".to_string(), handler("loader")),
+            ("remix".to_string(), HttpMethod::Post, "/users/:userId/edit".to_string(), handler("action")),
+            ("sveltekit".to_string(), HttpMethod::Delete, "/api/users/:id".to_string(), handler("DELETE")),
+            ("sveltekit".to_string(), HttpMethod::Get, "/api/users/:id".to_string(), handler("GET")),
+            ("warp".to_string(), HttpMethod::Get, "/hello/{String}".to_string(), handler("greet")),
+        ]);
+    }
+
+    #[test]
+    fn test_express_routes_under_app_routes_are_not_remix() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // The checkout itself sits in a directory called app
+        let repo = temp_dir.path().join("srv/app");
+        std::fs::create_dir_all(repo.join("routes")).unwrap();
+        std::fs::create_dir_all(repo.join("app/routes")).unwrap();
+        std::fs::write(repo.join("routes/users.js"), "const app = express();\napp.get('/users', listUsers);\n").unwrap();
+        std::fs::write(repo.join("app/routes/admin.js"), "const app = express();\napp.post('/admin', save);\n").unwrap();
+
+        let mut found: Vec<(String, HttpMethod, String)> = EndpointDetector::new()
+            .detect_endpoints(&repo)
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.framework.unwrap_or_default(), e.method, e.path))
+            .collect();
+        found.sort_by(|a, b| a.2.cmp(&b.2));
+        assert_eq!(found, vec![
+            ("express".to_string(), HttpMethod::Post, "/admin".to_string()),
+            ("express".to_string(), HttpMethod::Get, "/users".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,392 @@
+//! Middleware, guards and security annotations that run before an endpoint's handler: Express,
+//! Koa, Hono and Fastify middleware chains and `use()` calls, NestJS `@UseGuards`, Spring Security
+//! and JAX-RS annotations, Flask/FastAPI decorators, and actix `wrap()`, axum `layer()` and poem
+//! `with()` around routes. Each entry is the middleware as written, whitespace collapsed.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use crate::analysis::endpoint_detector::DetectedEndpoint;
+use crate::analysis::signatures;
+
+/// Lines searched around a route for the statement or decorator block it belongs to
+const MAX_STATEMENT_LINES: usize = 30;
+
+/// The route registration call on an Express-style line: `router.post(`
+static JS_ROUTE_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.(?:get|post|put|delete|patch|all)\s*\(").unwrap());
+/// `app.use(auth)`, `router.use('/admin', requireAdmin)`, `app.use('*', jwt({ secret }))`
+static JS_USE_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\w+\.use\s*\(").unwrap());
+/// A sub-router mounted with `use()` rather than middleware: `apiRouter`, `users.routes()`, `require('./admin')`
+static MOUNTED_ROUTER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^require\(|(?i)(?:router|routes)(?:\(\))?$").unwrap());
+/// Fastify hooks given in a route's options object
+static FASTIFY_HOOK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:preHandler|onRequest|preValidation)\s*:\s*(\[[^\]]*\]|[\w.]+)").unwrap()
+});
+/// `#[get("/users", wrap = "Auth")]`
+static ACTIX_WRAP_ARG: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bwrap\s*=\s*"([^"]+)""#).unwrap());
+/// Middleware applied to a whole actix scope/app, an axum router or a poem route
+static RUST_LAYER_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.(wrap|layer|route_layer|with)\s*\(").unwrap());
+/// `.service(list_users)` registering an attribute-routed actix handler
+static ACTIX_SERVICE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.service\s*\(\s*([\w:]+)\s*\)").unwrap());
+static ROUTE_DECORATOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^@(?:app|bp|blueprint|router|api)\.(?:route|get|post|put|delete|patch)\b").unwrap()
+});
+
+/// NestJS decorators that guard or expose a handler
+const NEST_GUARDS: &[&str] = &["UseGuards", "Public", "Roles", "AllowAnonymous"];
+/// Spring Security and JAX-RS annotations
+const JAVA_SECURITY: &[&str] = &["PreAuthorize", "PostAuthorize", "Secured", "RolesAllowed", "PermitAll", "DenyAll"];
+
+/// Fills `middleware` for the endpoints detected in one file
+pub fn attach(endpoints: &mut [DetectedEndpoint], content: &str, language: &str) {
+    if endpoints.is_empty() {
+        return;
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    match language {
+        "javascript" => attach_js(endpoints, &lines),
+        "python" => attach_python(endpoints, &lines),
+        "rust" => attach_rust(endpoints, &lines),
+        "java" => attach_java(endpoints, &lines),
+        _ => {}
+    }
+}
+
+/// Actix handlers registered with `.service(handler)` in a statement that also `wrap()`s, mapped
+/// to those wraps, since `#[get(...)]` handlers are usually registered in another file
+pub fn actix_service_wraps(content: &str, wraps: &mut HashMap<String, Vec<String>>) {
+    let lines: Vec<&str> = content.lines().collect();
+    for (idx, line) in lines.iter().enumerate() {
+        for cap in ACTIX_SERVICE.captures_iter(line) {
+            let (start, end) = statement_range(&lines, idx);
+            let layers: Vec<String> = layer_calls(&lines, start, end).into_iter()
+                .filter(|(kind, _, _)| kind == "wrap")
+                .map(|(_, _, text)| text)
+                .collect();
+            if !layers.is_empty() {
+                let handler = cap[1].rsplit("::").next().unwrap_or(&cap[1]).to_string();
+                wraps.entry(handler).or_default().extend(layers);
+            }
+        }
+    }
+}
+
+/// Gives attribute-routed actix endpoints without middleware the wraps of the scope their handler
+/// is registered in
+pub fn apply_service_wraps(endpoints: &mut [DetectedEndpoint], wraps: &HashMap<String, Vec<String>>) {
+    for endpoint in endpoints.iter_mut().filter(|e| e.framework.as_deref() == Some("actix") && e.middleware.is_empty()) {
+        if let Some(layers) = endpoint.handler.as_ref().and_then(|h| wraps.get(h)) {
+            endpoint.middleware = layers.clone();
+        }
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Top-level comma-separated arguments, ignoring commas nested in brackets or strings
+fn split_arguments(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut current = String::new();
+    let mut prev = ' ';
+    for c in text.chars() {
+        match quote {
+            Some(q) => {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    args.push(collapse(&current));
+                    current.clear();
+                    prev = c;
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+        prev = c;
+    }
+    if !current.trim().is_empty() {
+        args.push(collapse(&current));
+    }
+    args
+}
+
+/// The text inside the parentheses opened just before `start` on `lines[line]`, across lines
+fn call_arguments(lines: &[&str], line: usize, start: usize) -> String {
+    let mut depth = 1;
+    let mut text = String::new();
+    let first = lines[line].get(start..).unwrap_or("");
+    let rest = std::iter::once(first).chain(lines.iter().skip(line + 1).take(MAX_STATEMENT_LINES).copied());
+    for part in rest {
+        for c in part.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return text;
+                    }
+                }
+                _ => {}
+            }
+            text.push(c);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Whether a middleware argument is the handler itself rather than something run before it
+fn is_inline_handler(arg: &str) -> bool {
+    arg.is_empty() || arg.contains("=>") || arg.starts_with("function") || arg.starts_with("async")
+}
+
+fn attach_js(endpoints: &mut [DetectedEndpoint], lines: &[&str]) {
+    // `use()` calls apply to every route registered after them under their path prefix
+    let mut uses: Vec<(usize, String, Vec<String>)> = Vec::new(); // (line, prefix, middleware)
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(m) = JS_USE_CALL.find(line) {
+            let mut args = split_arguments(&call_arguments(lines, idx, m.end()));
+            let prefix = match args.first() {
+                Some(first) if first.starts_with(['\'', '"', '`']) => {
+                    let prefix = args.remove(0);
+                    prefix.trim_matches(['\'', '"', '`']).trim_end_matches('*').trim_end_matches('/').to_string()
+                }
+                _ => String::new(),
+            };
+            let middleware: Vec<String> = args.into_iter()
+                .filter(|a| !is_inline_handler(a) && !MOUNTED_ROUTER.is_match(a))
+                .collect();
+            if !middleware.is_empty() {
+                uses.push((idx + 1, prefix, middleware));
+            }
+        }
+    }
+
+    for endpoint in endpoints.iter_mut() {
+        let framework = endpoint.framework.as_deref().unwrap_or("");
+        let Some(line_number) = endpoint.line_number else { continue };
+        match framework {
+            "express" | "koa" | "hono" | "fastify" => {
+                let mut middleware: Vec<String> = uses.iter()
+                    .filter(|(line, prefix, _)| *line < line_number && endpoint.path.starts_with(prefix.as_str()))
+                    .flat_map(|(_, _, m)| m.iter().cloned())
+                    .collect();
+                let line = lines[line_number - 1];
+                if let Some(m) = JS_ROUTE_CALL.find(line) {
+                    let args = split_arguments(&call_arguments(lines, line_number - 1, m.end()));
+                    // Path first, handler last: everything between runs before the handler
+                    if args.len() > 2 {
+                        for arg in &args[1..args.len() - 1] {
+                            if arg.starts_with('{') {
+                                for hook in FASTIFY_HOOK.captures_iter(arg) {
+                                    middleware.extend(split_arguments(hook[1].trim_start_matches('[').trim_end_matches(']')));
+                                }
+                            } else if arg.starts_with('[') {
+                                middleware.extend(split_arguments(&arg[1..arg.len().saturating_sub(1)]));
+                            } else if !is_inline_handler(arg) {
+                                middleware.push(arg.clone());
+                            }
+                        }
+                    }
+                }
+                endpoint.middleware = middleware;
+            }
+            "nestjs" => {
+                let mut middleware = class_decorators(lines, "typescript", NEST_GUARDS);
+                if let Some(handler_line) = handler_line(lines, line_number) {
+                    middleware.extend(filtered_decorators(lines, handler_line, "typescript", NEST_GUARDS));
+                }
+                endpoint.middleware = middleware;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn attach_python(endpoints: &mut [DetectedEndpoint], lines: &[&str]) {
+    for endpoint in endpoints.iter_mut().filter(|e| matches!(e.framework.as_deref(), Some("flask") | Some("fastapi"))) {
+        let Some(handler_line) = endpoint.line_number.and_then(|line| handler_line(lines, line)) else { continue };
+        endpoint.middleware = signatures::decorators(lines, handler_line, "python").into_iter()
+            .filter(|d| !ROUTE_DECORATOR.is_match(d))
+            .map(|d| d.trim_start_matches('@').to_string())
+            .collect();
+    }
+}
+
+fn attach_java(endpoints: &mut [DetectedEndpoint], lines: &[&str]) {
+    let class_level = class_decorators(lines, "java", JAVA_SECURITY);
+    for endpoint in endpoints.iter_mut() {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let mut middleware = class_level.clone();
+        if let Some(handler_line) = handler_line(lines, line_number) {
+            middleware.extend(filtered_decorators(lines, handler_line, "java", JAVA_SECURITY));
+        }
+        endpoint.middleware = middleware;
+    }
+}
+
+fn attach_rust(endpoints: &mut [DetectedEndpoint], lines: &[&str]) {
+    for endpoint in endpoints.iter_mut() {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let idx = line_number - 1;
+        if let Some(cap) = ACTIX_WRAP_ARG.captures(lines[idx]) {
+            endpoint.middleware = vec![cap[1].to_string()];
+            continue;
+        }
+        if lines[idx].trim_start().starts_with("#[") {
+            continue;
+        }
+        let (start, end) = statement_range(lines, idx);
+        let framework = endpoint.framework.as_deref().unwrap_or("");
+        endpoint.middleware = layer_calls(lines, start, end).into_iter()
+            .filter(|(kind, line, _)| match (framework, kind.as_str()) {
+                ("actix", "wrap") => true,
+                // Axum layers only wrap the routes added before them
+                ("axum", "layer") | ("axum", "route_layer") => *line >= idx,
+                ("poem", "with") => true,
+                _ => false,
+            })
+            .map(|(_, _, text)| text)
+            .collect();
+    }
+}
+
+/// The lines (0-based, inclusive) of the builder chain around `lines[idx]`: back to the line after
+/// the previous `;` or `{` and forward to the next `;`
+fn statement_range(lines: &[&str], idx: usize) -> (usize, usize) {
+    let mut start = idx;
+    while start > 0 && idx - start < MAX_STATEMENT_LINES {
+        let previous = lines[start - 1].trim_end();
+        if previous.ends_with(';') || previous.ends_with('{') || previous.ends_with('}') || previous.is_empty() {
+            break;
+        }
+        start -= 1;
+    }
+    let mut end = idx;
+    while end + 1 < lines.len() && end - idx < MAX_STATEMENT_LINES && !lines[end].trim_end().ends_with(';') {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// `(method, line, argument)` for every `wrap`/`layer`/`route_layer`/`with` call in the range
+fn layer_calls(lines: &[&str], start: usize, end: usize) -> Vec<(String, usize, String)> {
+    let mut calls = Vec::new();
+    for idx in start..=end.min(lines.len().saturating_sub(1)) {
+        for cap in RUST_LAYER_CALL.captures_iter(lines[idx]) {
+            let argument = collapse(&call_arguments(lines, idx, cap.get(0).unwrap().end()));
+            if !argument.is_empty() {
+                calls.push((cap[1].to_string(), idx, argument));
+            }
+        }
+    }
+    calls
+}
+
+/// The 1-based line of the declaration a route decorator on `line_number` belongs to: the first
+/// line after the decorator block
+fn handler_line(lines: &[&str], line_number: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (idx, line) in lines.iter().enumerate().skip(line_number - 1).take(MAX_STATEMENT_LINES) {
+        let trimmed = line.trim();
+        let starts_decorator = trimmed.starts_with('@');
+        if depth == 0 && !starts_decorator && !trimmed.is_empty() && idx + 1 > line_number {
+            return Some(idx + 1);
+        }
+        depth += trimmed.matches(['(', '[', '{']).count() as i32 - trimmed.matches([')', ']', '}']).count() as i32;
+        depth = depth.max(0);
+    }
+    None
+}
+
+/// The decorators above the declaration on `line_number` named in `names`, without their `@`.
+/// `@UseGuards(A, B)` yields one entry per guard.
+fn filtered_decorators(lines: &[&str], line_number: usize, language: &str, names: &[&str]) -> Vec<String> {
+    let mut found = Vec::new();
+    for decorator in signatures::decorators(lines, line_number, language) {
+        let text = decorator.trim_start_matches('@');
+        let name = text.split('(').next().unwrap_or(text).trim();
+        if !names.contains(&name) {
+            continue;
+        }
+        match text.strip_prefix("UseGuards(").and_then(|args| args.strip_suffix(')')) {
+            Some(guards) => found.extend(split_arguments(guards)),
+            None => found.push(text.to_string()),
+        }
+    }
+    found
+}
+
+/// Guards and security annotations on the first class declared in the file
+fn class_decorators(lines: &[&str], language: &str, names: &[&str]) -> Vec<String> {
+    lines.iter()
+        .position(|l| {
+            let l = l.trim_start();
+            l.starts_with("class ") || l.starts_with("export class ") || l.starts_with("public class ") || l.starts_with("export default class ")
+        })
+        .map(|idx| filtered_decorators(lines, idx + 1, language, names))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::endpoint_detector::{EndpointProtocol, HttpMethod};
+
+    fn endpoint(framework: &str, path: &str, line_number: usize) -> DetectedEndpoint {
+        DetectedEndpoint {
+            path: path.to_string(),
+            method: HttpMethod::Get,
+            handler: Some("list".to_string()),
+            file_path: String::new(),
+            line_number: Some(line_number),
+            framework: Some(framework.to_string()),
+            middleware: Vec::new(),
+            parameters: Vec::new(),
+            protocol: EndpointProtocol::Http,
+        }
+    }
+
+    #[test]
+    fn test_middleware_and_guards_attach_to_endpoints() {
+        let express = "router.use(requestId);\nrouter.use('/admin', requireAdmin);\nrouter.get('/admin/users', authenticate, [rateLimit, audit], listUsers);\nrouter.get('/health', (req, res) => res.send('ok'));\n";
+        let mut endpoints = vec![endpoint("express", "/admin/users", 3), endpoint("express", "/health", 4)];
+        attach(&mut endpoints, express, "javascript");
+        assert_eq!(endpoints[0].middleware, vec!["requestId", "requireAdmin", "authenticate", "rateLimit", "audit"]);
+        assert_eq!(endpoints[1].middleware, vec!["requestId"]);
+
+        let nest = "@Controller('users')\n@UseGuards(AuthGuard('jwt'))\nexport class UsersController {\n  @Get(':id')\n  @UseGuards(RolesGuard)\n  findOne(@Param('id') id: string) {}\n}\n";
+        let mut endpoints = vec![endpoint("nestjs", ":id", 4)];
+        attach(&mut endpoints, nest, "javascript");
+        assert_eq!(endpoints[0].middleware, vec!["AuthGuard('jwt')", "RolesGuard"]);
+
+        let spring = "@RestController\n@PreAuthorize(\"isAuthenticated()\")\npublic class OrderController {\n    @Secured(\"ROLE_ADMIN\")\n    @DeleteMapping(\"/orders/{id}\")\n    public void delete(@PathVariable Long id) {}\n}\n";
+        let mut endpoints = vec![endpoint("spring", "/orders/{id}", 5)];
+        attach(&mut endpoints, spring, "java");
+        assert_eq!(endpoints[0].middleware, vec!["PreAuthorize(\"isAuthenticated()\")", "Secured(\"ROLE_ADMIN\")"]);
+
+        let actix = "App::new()\n    .wrap(Logger::default())\n    .route(\"/users\", web::get().to(list))\n    .wrap(HttpAuthentication::bearer(validator));\nlet app = Router::new()\n    .route(\"/public\", get(list))\n    .route_layer(middleware::from_fn(require_auth))\n    .route(\"/open\", get(list));\n";
+        let mut endpoints = vec![endpoint("actix", "/users", 3), endpoint("axum", "/public", 6), endpoint("axum", "/open", 8)];
+        attach(&mut endpoints, actix, "rust");
+        assert_eq!(endpoints[0].middleware, vec!["Logger::default()", "HttpAuthentication::bearer(validator)"]);
+        assert_eq!(endpoints[1].middleware, vec!["middleware::from_fn(require_auth)"]);
+        assert!(endpoints[2].middleware.is_empty());
+
+        let mut wraps = HashMap::new();
+        actix_service_wraps("web::scope(\"/api\")\n    .wrap(JwtAuth)\n    .service(handlers::list)\n", &mut wraps);
+        let mut endpoints = vec![endpoint("actix", "/users", 1)];
+        apply_service_wraps(&mut endpoints, &wraps);
+        assert_eq!(endpoints[0].middleware, vec!["JwtAuth"]);
+    }
+}
@@ -0,0 +1,532 @@
+//! Route prefixes declared away from the route itself, so endpoints are stored under the path a
+//! client actually calls: routers mounted from another file (`app.use('/api/v1', router)`,
+//! `app.route('/users', users)`, `fastify.register(routes, { prefix })`), routers created with a
+//! prefix (`new Router({ prefix })`, `new Hono().basePath()`, `APIRouter(prefix=)`,
+//! `Blueprint(url_prefix=)`), NestJS `@Controller('users')` and `setGlobalPrefix()`,
+//! class-level Spring `@RequestMapping` and JAX-RS `@Path`, and actix `web::scope()` and axum or
+//! poem `.nest()`.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use crate::analysis::endpoint_detector::DetectedEndpoint;
+
+/// Mounts followed outwards from a router before giving up (guards against import cycles)
+const MAX_MOUNT_DEPTH: usize = 8;
+
+/// Extensions tried, in order, for an import written without one
+const JS_EXTENSIONS: &[&str] = &["js", "ts", "jsx", "tsx", "mjs", "cjs"];
+
+/// Frameworks whose routers are mounted with `use()`/`route()`/`register()`
+const JS_ROUTER_FRAMEWORKS: &[&str] = &["express", "koa", "hono", "fastify"];
+
+/// `app.use('/api', router)`, `router.use('/users', users.routes())`, `app.route('/posts', posts)`,
+/// `app.use('/admin', require('./admin'))`
+static JS_MOUNT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(\w+)\.(?:use|route)\s*\(\s*['"`]([^'"`]+)['"`]\s*,\s*(?:require\(\s*['"]([^'"]+)['"]\s*\)|(\w+))"#).unwrap()
+});
+/// `fastify.register(userRoutes, { prefix: '/users' })`
+static FASTIFY_REGISTER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\.register\s*\(\s*(?:(?:require|import)\(\s*['"]([^'"]+)['"]\s*\)|(\w+))\s*,\s*\{[^}]*\bprefix\s*:\s*['"]([^'"]+)['"]"#).unwrap()
+});
+static JS_DEFAULT_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"import\s+(\w+)\s*(?:,\s*\{[^}]*\}\s*)?from\s+['"]([^'"]+)['"]"#).unwrap()
+});
+static JS_NAMED_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"import\s+(?:\w+\s*,\s*)?\{([^}]*)\}\s*from\s+['"]([^'"]+)['"]"#).unwrap()
+});
+static JS_REQUIRE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:const|let|var)\s+(\w+)\s*=\s*require\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap()
+});
+/// `const router = new Router({ prefix: '/users' })`
+static JS_ROUTER_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:const|let|var)\s+(\w+)\s*=\s*new\s+(?:Router|KoaRouter)\s*\(\s*\{[^}]*\bprefix\s*:\s*['"]([^'"]+)['"]"#).unwrap()
+});
+/// `const app = new Hono().basePath('/api')`
+static HONO_BASE_PATH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:const|let|var)\s+(\w+)\s*=\s*new\s+(?:Hono|OpenAPIHono)\s*\([^)]*\)\s*\.basePath\(\s*['"]([^'"]+)['"]"#).unwrap()
+});
+static JS_ROUTE_RECEIVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\w+)\.(?:get|post|put|delete|patch|all|options|head|ws)\s*\(").unwrap()
+});
+static NEST_GLOBAL_PREFIX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\.setGlobalPrefix\(\s*['"]([^'"]*)['"]"#).unwrap());
+/// `@Controller('users')`, `@Controller({ path: 'users' })`, `@Controller()`
+static NEST_CONTROLLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"@Controller\s*\(\s*(?:['"]([^'"]*)['"]|\{[^}]*\bpath\s*:\s*['"]([^'"]*)['"])?"#).unwrap()
+});
+/// `router = APIRouter(prefix="/items")`, `bp = Blueprint("admin", __name__, url_prefix="/admin")`
+static PY_ROUTER_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(\w+)\s*=\s*(?:APIRouter|Blueprint)\s*\([^)]*\b(?:url_)?prefix\s*=\s*['"]([^'"]+)['"]"#).unwrap()
+});
+static PY_ROUTE_RECEIVER: Lazy<Regex> = Lazy::new(|| Regex::new(r"@(\w+)\.(?:route|get|post|put|delete|patch)\b").unwrap());
+/// `web::scope("/api/v1")`
+static ACTIX_SCOPE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bweb::scope\s*\(\s*"([^"]*)"\s*\)"#).unwrap());
+/// `.nest("/api", api_routes())`, `.nest("/users", users::router())`, `.nest("/admin", admin)`
+static RUST_NEST: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\.nest\s*\(\s*"([^"]*)"\s*,"#).unwrap());
+/// A nested router named by a function call or a variable: `users::router()`, `admin`
+static RUST_ROUTER_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:[\w:]+::)?(\w+)\s*(\(\s*\))?$").unwrap());
+/// Frameworks whose routers are scoped with `web::scope()` or `.nest()`
+const RUST_ROUTER_FRAMEWORKS: &[&str] = &["actix", "axum", "poem"];
+static JAVA_CLASS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:public|protected|private|abstract|final|static)\s+)*class\s+\w+").unwrap()
+});
+
+/// Joins a mount prefix and a route path with exactly one `/` between them
+pub fn join_path(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    let joined = if path.is_empty() { prefix.to_string() } else { format!("{}/{}", prefix, path) };
+    if joined.starts_with('/') { joined } else { format!("/{}", joined) }
+}
+
+/// Applies the prefixes declared in the same file as the endpoints: router prefixes, routers
+/// mounted in that file, NestJS controllers and Spring/JAX-RS resource classes. Class-level Spring
+/// and JAX-RS mappings are prefixes, not endpoints, and are dropped.
+pub fn apply_file_prefixes(endpoints: &mut Vec<DetectedEndpoint>, content: &str, language: &str) {
+    if endpoints.is_empty() {
+        return;
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    match language {
+        "javascript" => apply_js(endpoints, content, &lines),
+        "python" => apply_python(endpoints, content, &lines),
+        "java" => apply_java(endpoints, &lines),
+        "rust" => apply_rust(endpoints, content, &lines),
+        _ => {}
+    }
+}
+
+fn apply_js(endpoints: &mut [DetectedEndpoint], content: &str, lines: &[&str]) {
+    let mut own: HashMap<String, String> = HashMap::new();
+    for cap in JS_ROUTER_PREFIX.captures_iter(content).chain(HONO_BASE_PATH.captures_iter(content)) {
+        own.insert(cap[1].to_string(), cap[2].to_string());
+    }
+    // Routers mounted by another router of the same file
+    let imported = imports(content);
+    let mut prefixes = own.clone();
+    for cap in JS_MOUNT.captures_iter(content) {
+        let Some(target) = cap.get(4).map(|m| m.as_str()) else { continue };
+        if imported.contains_key(target) {
+            continue;
+        }
+        let outer = own.get(&cap[1]).map(String::as_str).unwrap_or("");
+        let inner = own.get(target).map(String::as_str).unwrap_or("");
+        prefixes.insert(target.to_string(), join_path(&join_path(outer, &cap[2]), inner));
+    }
+
+    let controllers: Vec<(usize, String)> = lines.iter().enumerate()
+        .filter_map(|(idx, line)| NEST_CONTROLLER.captures(line).map(|cap| {
+            let prefix = cap.get(1).or_else(|| cap.get(2)).map(|m| m.as_str()).unwrap_or("");
+            (idx + 1, prefix.to_string())
+        }))
+        .collect();
+
+    for endpoint in endpoints.iter_mut() {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let framework = endpoint.framework.as_deref().unwrap_or("");
+        if framework == "nestjs" {
+            if let Some((_, prefix)) = controllers.iter().rev().find(|(line, _)| *line < line_number) {
+                endpoint.path = join_path(prefix, &endpoint.path);
+            }
+        } else if JS_ROUTER_FRAMEWORKS.contains(&framework) {
+            let receiver = lines.get(line_number - 1).and_then(|line| JS_ROUTE_RECEIVER.captures(line));
+            if let Some(prefix) = receiver.and_then(|cap| prefixes.get(&cap[1])) {
+                endpoint.path = join_path(prefix, &endpoint.path);
+            }
+        }
+    }
+}
+
+fn apply_python(endpoints: &mut [DetectedEndpoint], content: &str, lines: &[&str]) {
+    let prefixes: HashMap<&str, &str> = PY_ROUTER_PREFIX.captures_iter(content)
+        .map(|cap| (cap.get(1).unwrap().as_str(), cap.get(2).unwrap().as_str()))
+        .collect();
+    if prefixes.is_empty() {
+        return;
+    }
+    for endpoint in endpoints.iter_mut().filter(|e| matches!(e.framework.as_deref(), Some("flask" | "fastapi"))) {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let receiver = lines.get(line_number - 1).and_then(|line| PY_ROUTE_RECEIVER.captures(line));
+        if let Some(prefix) = receiver.and_then(|cap| prefixes.get(cap.get(1).unwrap().as_str())) {
+            endpoint.path = join_path(prefix, &endpoint.path);
+        }
+    }
+}
+
+fn apply_java(endpoints: &mut Vec<DetectedEndpoint>, lines: &[&str]) {
+    // (first annotation line, class line) of each class declaration, both 1-based
+    let classes: Vec<(usize, usize)> = lines.iter().enumerate()
+        .filter(|(_, line)| JAVA_CLASS.is_match(line))
+        .map(|(idx, _)| (annotation_block_start(lines, idx) + 1, idx + 1))
+        .collect();
+    if classes.is_empty() {
+        return;
+    }
+
+    let mut class_prefixes: HashMap<usize, String> = HashMap::new();
+    endpoints.retain(|endpoint| {
+        let Some(line_number) = endpoint.line_number else { return true };
+        match classes.iter().find(|(start, class_line)| (*start..*class_line).contains(&line_number)) {
+            Some((_, class_line)) if matches!(endpoint.framework.as_deref(), Some("spring" | "jaxrs")) => {
+                class_prefixes.entry(*class_line).or_insert_with(|| endpoint.path.clone());
+                false
+            }
+            _ => true,
+        }
+    });
+
+    for endpoint in endpoints.iter_mut().filter(|e| matches!(e.framework.as_deref(), Some("spring" | "jaxrs"))) {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let enclosing = classes.iter().rev().find(|(_, class_line)| *class_line < line_number);
+        if let Some(prefix) = enclosing.and_then(|(_, class_line)| class_prefixes.get(class_line)) {
+            endpoint.path = join_path(prefix, &endpoint.path);
+        }
+    }
+}
+
+fn apply_rust(endpoints: &mut [DetectedEndpoint], content: &str, lines: &[&str]) {
+    let scopes = rust_scopes(content);
+    if scopes.is_empty() {
+        return;
+    }
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect();
+    for endpoint in endpoints.iter_mut().filter(|e| RUST_ROUTER_FRAMEWORKS.contains(&e.framework.as_deref().unwrap_or(""))) {
+        let Some(line_number) = endpoint.line_number else { continue };
+        let (Some(line), Some(start)) = (lines.get(line_number - 1), line_starts.get(line_number - 1)) else { continue };
+        // The route's own path literal places it when a scope opens on the same line
+        let column = line.find(&format!("\"{}\"", endpoint.path))
+            .unwrap_or_else(|| line.len() - line.trim_start().len());
+        let prefix = prefix_at(&scopes, start + column, 0);
+        if !prefix.is_empty() {
+            endpoint.path = join_path(&prefix, &endpoint.path);
+        }
+    }
+}
+
+/// Where a Rust scope or nested router applies: `range` is in bytes of the file, and `at` is
+/// where it is declared, which scopes around it prefix further
+#[derive(Debug)]
+struct RustScope {
+    prefix: String,
+    at: usize,
+    range: std::ops::Range<usize>,
+}
+
+/// Actix scopes run to the end of the call or statement they open in. A nested router is the
+/// nest's own argument, or the function or `let` it names elsewhere in the file.
+fn rust_scopes(content: &str) -> Vec<RustScope> {
+    let mut scopes = Vec::new();
+    for cap in ACTIX_SCOPE.captures_iter(content) {
+        let whole = cap.get(0).unwrap();
+        scopes.push(RustScope { prefix: cap[1].to_string(), at: whole.start(), range: whole.end()..enclosing_end(content, whole.end()) });
+    }
+    for cap in RUST_NEST.captures_iter(content) {
+        let whole = cap.get(0).unwrap();
+        let end = enclosing_end(content, whole.end());
+        let prefix = cap[1].to_string();
+        let argument = content[whole.end()..end].trim();
+        let named = RUST_ROUTER_NAME.captures(argument).and_then(|name| {
+            let pattern = match name.get(2) {
+                Some(_) => format!(r"\bfn\s+{}\s*[(<]", regex::escape(&name[1])),
+                None => format!(r"\blet\s+(?:mut\s+)?{}\b[^=;]*=", regex::escape(&name[1])),
+            };
+            let declaration = Regex::new(&pattern).ok()?.find(content)?;
+            let body = match name.get(2) {
+                Some(_) => declaration.end() + content[declaration.end()..].find('{')? + 1,
+                None => declaration.end(),
+            };
+            Some(body..enclosing_end(content, body))
+        });
+        scopes.push(RustScope { prefix, at: whole.start(), range: named.unwrap_or(whole.end()..end) });
+    }
+    scopes
+}
+
+/// The joined prefixes of every scope around `position`, outermost first
+fn prefix_at(scopes: &[RustScope], position: usize, depth: usize) -> String {
+    if depth >= MAX_MOUNT_DEPTH {
+        return String::new();
+    }
+    let mut around: Vec<&RustScope> = scopes.iter().filter(|s| s.range.contains(&position)).collect();
+    around.sort_by_key(|s| s.range.start);
+    let Some(innermost) = around.last() else { return String::new() };
+    // Whatever scopes the declaration also scopes its routes, including a nest that names a
+    // function declared elsewhere in the file
+    join_path(&prefix_at(scopes, innermost.at, depth + 1), &innermost.prefix)
+}
+
+/// Where the call or statement open at `start` ends: its unmatched `)` or `}`, or a `;` outside
+/// any brackets. String literals and line comments are skipped.
+fn enclosing_end(content: &str, start: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut idx = start;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'"' => {
+                idx += 1;
+                while idx < bytes.len() && bytes[idx] != b'"' {
+                    idx += if bytes[idx] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => {
+                while idx < bytes.len() && bytes[idx] != b'\n' {
+                    idx += 1;
+                }
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return idx,
+            b')' | b']' | b'}' => depth -= 1,
+            b';' if depth == 0 => return idx,
+            _ => {}
+        }
+        idx += 1;
+    }
+    bytes.len()
+}
+
+/// Index of the first line of the annotations directly above the declaration at `idx` (0-based)
+fn annotation_block_start(lines: &[&str], idx: usize) -> usize {
+    let mut start = idx;
+    while start > 0 {
+        let line = lines[start - 1].trim();
+        let ends_statement = line.ends_with(';') || line.ends_with('{') || line.ends_with('}');
+        let comment = line.starts_with("//") || line.starts_with("/*") || line.starts_with('*');
+        if line.is_empty() || ends_statement || comment {
+            break;
+        }
+        start -= 1;
+    }
+    start
+}
+
+/// Local names bound to relative imports, mapped to the import specifier
+fn imports(content: &str) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for cap in JS_DEFAULT_IMPORT.captures_iter(content).chain(JS_REQUIRE.captures_iter(content)) {
+        names.insert(cap[1].to_string(), cap[2].to_string());
+    }
+    for cap in JS_NAMED_IMPORT.captures_iter(content) {
+        for name in cap[1].split(',') {
+            // `{ router as usersRouter }` binds the alias
+            if let Some(local) = name.split_whitespace().last() {
+                names.insert(local.to_string(), cap[2].to_string());
+            }
+        }
+    }
+    names.retain(|_, specifier| specifier.starts_with('.'));
+    names
+}
+
+/// One router file mounted under a path by another file
+#[derive(Debug, Clone)]
+struct Mount {
+    target: PathBuf,
+    prefix: String,
+    mounted_in: PathBuf,
+}
+
+/// Routers mounted across files, collected while the repository is walked and applied once every
+/// file's endpoints are known
+#[derive(Debug, Default)]
+pub struct MountTable {
+    mounts: Vec<Mount>,
+    nest_global_prefix: Option<String>,
+}
+
+impl MountTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the routers `file_path` mounts from other files, and a NestJS global prefix
+    pub fn scan_js(&mut self, content: &str, file_path: &Path) {
+        if let Some(cap) = NEST_GLOBAL_PREFIX.captures(content) {
+            self.nest_global_prefix = Some(cap[1].to_string());
+        }
+        let imported = imports(content);
+        let dir = file_path.parent().unwrap_or(Path::new(""));
+        let mut mount = |specifier: Option<&str>, variable: Option<&str>, prefix: &str| {
+            let specifier = specifier.or_else(|| variable.and_then(|v| imported.get(v)).map(String::as_str));
+            if let Some(target) = specifier.and_then(|s| resolve_import(dir, s)) {
+                self.mounts.push(Mount { target, prefix: prefix.to_string(), mounted_in: normalize(file_path) });
+            }
+        };
+        for cap in JS_MOUNT.captures_iter(content) {
+            mount(cap.get(3).map(|m| m.as_str()), cap.get(4).map(|m| m.as_str()), &cap[2]);
+        }
+        for cap in FASTIFY_REGISTER.captures_iter(content) {
+            mount(cap.get(1).map(|m| m.as_str()), cap.get(2).map(|m| m.as_str()), &cap[3]);
+        }
+    }
+
+    /// The full prefix a router file is served under, following mounts outwards
+    fn prefix_of(&self, file: &Path) -> Option<String> {
+        let mut prefix = String::new();
+        let mut current = file.to_path_buf();
+        let mut found = false;
+        for _ in 0..MAX_MOUNT_DEPTH {
+            let Some(mount) = self.mounts.iter().find(|m| m.target == current) else { break };
+            prefix = join_path(&mount.prefix, &prefix);
+            current = mount.mounted_in.clone();
+            found = true;
+        }
+        found.then_some(prefix)
+    }
+
+    /// Prefixes endpoints with the path their router file is mounted under, and NestJS endpoints
+    /// with the application's global prefix
+    pub fn apply(&self, endpoints: &mut [DetectedEndpoint]) {
+        let mut prefixes: HashMap<String, Option<String>> = HashMap::new();
+        for endpoint in endpoints.iter_mut() {
+            let framework = endpoint.framework.as_deref().unwrap_or("");
+            if framework == "nestjs" {
+                if let Some(global) = &self.nest_global_prefix {
+                    endpoint.path = join_path(global, &endpoint.path);
+                }
+            } else if JS_ROUTER_FRAMEWORKS.contains(&framework) {
+                let prefix = prefixes.entry(endpoint.file_path.clone())
+                    .or_insert_with(|| self.prefix_of(&normalize(Path::new(&endpoint.file_path))));
+                if let Some(prefix) = prefix {
+                    endpoint.path = join_path(prefix, &endpoint.path);
+                }
+            }
+        }
+    }
+}
+
+/// The file a relative import specifier points at, trying the usual extensions and `index` files
+fn resolve_import(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = normalize(&dir.join(specifier));
+    if base.is_file() {
+        return Some(base);
+    }
+    // TypeScript ESM imports name the compiled `.js` file
+    let stem = match base.extension().and_then(|e| e.to_str()) {
+        Some("js" | "mjs" | "cjs") => base.with_extension(""),
+        _ => base.clone(),
+    };
+    JS_EXTENSIONS.iter()
+        .map(|ext| PathBuf::from(format!("{}.{}", stem.to_string_lossy(), ext)))
+        .chain(JS_EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Resolves `.` and `..` without touching the filesystem, so walked and imported paths compare equal
+fn normalize(path: &Path) -> PathBuf {
+    let mut parts = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop();
+            }
+            other => parts.push(other.as_os_str()),
+        }
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::endpoint_detector::EndpointDetector;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_resolves_router_mounts_and_class_prefixes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "web/app.js", "const express = require('express');\nconst api = require('./routes');\nconst app = express();\napp.use('/api/v1', api);\n");
+        write(root, "web/routes/index.js", "import users from './users.js'\nconst router = express.Router();\nrouter.use('/users', users);\nrouter.get('/status', status);\n");
+        write(root, "web/routes/users.ts", "const router = express.Router();\nrouter.get('/', listUsers);\nrouter.get('/:id', getUser);\n");
+        write(root, "nest/main.ts", "const app = await NestFactory.create(AppModule);\napp.setGlobalPrefix('api');\n");
+        write(root, "nest/orders.controller.ts", "@Controller('orders')\nexport class OrdersController {\n  @Get()\n  findAll() {}\n\n  @Get(':id')\n  findOne() {}\n}\n");
+        write(root, "java/OrderController.java", "@RestController\n@RequestMapping(\"/api/orders\")\npublic class OrderController {\n    @GetMapping\n    public List<Order> list() {}\n\n    @PostMapping(\"/{id}/cancel\")\n    public Order cancel() {}\n}\n");
+        write(root, "java/UserResource.java", "@Path(\"/users\")\npublic class UserResource {\n    @GET\n    public List<User> list() {}\n\n    @GET\n    @Path(\"/{id}\")\n    public User get() {}\n}\n");
+        write(root, "py/items.py", "router = APIRouter(prefix=\"/items\")\n\n@router.get(\"/{item_id}\")\nasync def read_item(item_id: int):\n    pass\n");
+
+        let mut found: Vec<(String, String, Vec<String>)> = EndpointDetector::new()
+            .detect_endpoints(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.framework.unwrap_or_default(), e.path, e.parameters))
+            .collect();
+        found.sort();
+        // Express `router.get` lines match both the app and the router pattern
+        found.dedup();
+        let params = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(found, vec![
+            ("express".to_string(), "/api/v1/status".to_string(), params(&[])),
+            ("express".to_string(), "/api/v1/users".to_string(), params(&[])),
+            ("express".to_string(), "/api/v1/users/:id".to_string(), params(&["id"])),
+            ("fastapi".to_string(), "/items/{item_id}".to_string(), params(&["item_id"])),
+            ("jaxrs".to_string(), "/users".to_string(), params(&[])),
+            ("jaxrs".to_string(), "/users/{id}".to_string(), params(&["id"])),
+            ("nestjs".to_string(), "/api/orders".to_string(), params(&[])),
+            ("nestjs".to_string(), "/api/orders/:id".to_string(), params(&["id"])),
+            ("spring".to_string(), "/api/orders".to_string(), params(&[])),
+            ("spring".to_string(), "/api/orders/{id}/cancel".to_string(), params(&["id"])),
+        ]);
+    }
+
+    #[test]
+    fn test_resolves_actix_scopes_and_axum_nests() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "server.rs", r#"
+HttpServer::new(move || {
+    App::new()
+        .route("/health", web::get().to(health))
+        .service(
+            web::scope("/api/v1")
+                .app_data(state.clone())
+                .route("/repositories", web::get().to(list_repositories))
+                .service(web::scope("/admin").route("/jobs", web::post().to(run_job)))
+                .route("/version", web::get().to(version)),
+        )
+})
+"#);
+        write(root, "router.rs", r#"
+fn app() -> Router {
+    Router::new()
+        .route("/", get(index))
+        .nest("/api", api_routes())
+        .nest("/admin", admin)
+        .nest("/inline", Router::new().route("/ping", get(ping)))
+}
+
+fn api_routes() -> Router {
+    Router::new()
+        .route("/users", get(list_users))
+        .nest("/v2", Router::new().route("/orders", get(list_orders)))
+}
+
+let admin = Router::new()
+    .route("/stats", get(stats));
+"#);
+
+        let mut found: Vec<(String, String)> = EndpointDetector::new()
+            .detect_endpoints(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.framework.unwrap_or_default(), e.path))
+            .collect();
+        found.sort();
+        found.dedup();
+        let expected: Vec<(String, String)> = [
+            ("actix", "/api/v1/admin/jobs"),
+            ("actix", "/api/v1/repositories"),
+            ("actix", "/api/v1/version"),
+            ("actix", "/health"),
+            ("axum", "/"),
+            ("axum", "/admin/stats"),
+            ("axum", "/api/users"),
+            ("axum", "/api/v2/orders"),
+            ("axum", "/inline/ping"),
+        ].iter().map(|(f, p)| (f.to_string(), p.to_string())).collect();
+        assert_eq!(found, expected);
+    }
+}
@@ -0,0 +1,388 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EntrypointType {
+    MainFunction,      // fn main, func main, if __name__ == "__main__", static void main
+    ContainerCommand,  // Dockerfile CMD / ENTRYPOINT
+    PackageScript,     // package.json start script / main / bin, Procfile processes
+    ServerlessHandler, // Lambda / serverless function handlers
+    CliScript,         // Console scripts declared in pyproject.toml / setup.py
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedEntrypoint {
+    pub name: String,
+    pub entrypoint_type: EntrypointType,
+    pub file_path: String, // File declaring the entrypoint
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub command: Option<String>, // Raw command for container/script entrypoints
+    pub target_file: Option<String>, // Source file that is executed, when it can be resolved
+    pub handler: Option<String>, // Function invoked, e.g. "main" or "lambda_handler"
+}
+
+pub struct EntrypointDetector;
+
+impl EntrypointDetector {
+    pub fn new() -> Self {
+        EntrypointDetector
+    }
+
+    /// Detect application entrypoints in a repository
+    pub fn detect_entrypoints(&self, repo_path: &Path) -> Result<Vec<DetectedEntrypoint>> {
+        let mut entrypoints = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_string();
+            let file_name_lower = file_name.to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            if utils::should_skip_file(&file_name_lower, &path_str) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if file_name_lower == "dockerfile" || file_name_lower.starts_with("dockerfile.") || file_name_lower.ends_with(".dockerfile") {
+                entrypoints.extend(self.detect_dockerfile(&content, &normalized_path));
+                continue;
+            }
+
+            match file_name_lower.as_str() {
+                "package.json" => {
+                    entrypoints.extend(self.detect_package_json(&content, &normalized_path));
+                    continue;
+                }
+                "procfile" => {
+                    entrypoints.extend(self.detect_procfile(&content, &normalized_path));
+                    continue;
+                }
+                "serverless.yml" | "serverless.yaml" => {
+                    entrypoints.extend(self.detect_serverless_config(&content, &normalized_path));
+                    continue;
+                }
+                "pyproject.toml" => {
+                    entrypoints.extend(self.detect_pyproject(&content, &normalized_path));
+                    continue;
+                }
+                _ => {}
+            }
+
+            if utils::is_minified_or_compiled(&content, &normalized_path) {
+                continue;
+            }
+
+            if let Some(language) = utils::detect_language(path) {
+                entrypoints.extend(self.detect_in_source(&content, &normalized_path, &language)?);
+            }
+        }
+
+        Ok(entrypoints)
+    }
+
+    fn detect_in_source(&self, content: &str, file_path: &str, language: &str) -> Result<Vec<DetectedEntrypoint>> {
+        let mut entrypoints = Vec::new();
+
+        let patterns: Vec<(Regex, EntrypointType, &str)> = match language {
+            "rust" => vec![
+                (Regex::new(r"^\s*(?:pub\s+)?(?:async\s+)?fn\s+main\s*\(")?, EntrypointType::MainFunction, "main"),
+            ],
+            "go" => vec![
+                (Regex::new(r"^func\s+main\s*\(\s*\)")?, EntrypointType::MainFunction, "main"),
+                (Regex::new(r"lambda\.Start\(\s*(\w+)")?, EntrypointType::ServerlessHandler, ""),
+            ],
+            "python" => vec![
+                (Regex::new(r#"^if\s+__name__\s*==\s*['"]__main__['"]"#)?, EntrypointType::MainFunction, "__main__"),
+                (Regex::new(r"^(?:async\s+)?def\s+(\w*handler\w*)\s*\(\s*event\s*,\s*context")?, EntrypointType::ServerlessHandler, ""),
+            ],
+            "java" => vec![
+                (Regex::new(r"public\s+static\s+void\s+main\s*\(\s*(?:final\s+)?String")?, EntrypointType::MainFunction, "main"),
+                (Regex::new(r"implements\s+RequestHandler<")?, EntrypointType::ServerlessHandler, "handleRequest"),
+            ],
+            "javascript" | "typescript" => vec![
+                (Regex::new(r"^(?:module\.)?exports\.(\w*handler\w*)\s*=")?, EntrypointType::ServerlessHandler, ""),
+                (Regex::new(r"^export\s+(?:const|let)\s+(\w*handler\w*)\s*(?::\s*[\w<>, ]+)?=\s*(?:async\s*)?\(?\s*event")?, EntrypointType::ServerlessHandler, ""),
+                (Regex::new(r"^export\s+(?:async\s+)?function\s+(\w*handler\w*)\s*\(\s*event")?, EntrypointType::ServerlessHandler, ""),
+            ],
+            _ => Vec::new(),
+        };
+
+        // Go only treats func main as an entrypoint in package main
+        if language == "go" && !content.lines().any(|l| l.trim() == "package main") {
+            return Ok(entrypoints);
+        }
+
+        for (line_num, line) in content.lines().enumerate() {
+            for (pattern, entrypoint_type, fixed_handler) in &patterns {
+                if let Some(cap) = pattern.captures(line) {
+                    let handler = cap.get(1)
+                        .map(|m| m.as_str().to_string())
+                        .unwrap_or_else(|| fixed_handler.to_string());
+                    entrypoints.push(DetectedEntrypoint {
+                        name: format!("{}:{}", file_path, handler),
+                        entrypoint_type: entrypoint_type.clone(),
+                        file_path: file_path.to_string(),
+                        line_number: Some(line_num + 1),
+                        language: Some(language.to_string()),
+                        command: None,
+                        target_file: Some(file_path.to_string()),
+                        handler: Some(handler),
+                    });
+                }
+            }
+        }
+
+        Ok(entrypoints)
+    }
+
+    fn detect_dockerfile(&self, content: &str, file_path: &str) -> Vec<DetectedEntrypoint> {
+        let mut entrypoints = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            let upper = trimmed.to_uppercase();
+            let (keyword, rest) = if upper.starts_with("ENTRYPOINT ") {
+                ("ENTRYPOINT", &trimmed[11..])
+            } else if upper.starts_with("CMD ") {
+                ("CMD", &trimmed[4..])
+            } else {
+                continue;
+            };
+
+            let command = parse_docker_command(rest.trim());
+            if command.is_empty() {
+                continue;
+            }
+            entrypoints.push(DetectedEntrypoint {
+                name: format!("{} {}", keyword, command),
+                entrypoint_type: EntrypointType::ContainerCommand,
+                file_path: file_path.to_string(),
+                line_number: Some(line_num + 1),
+                language: None,
+                target_file: script_from_command(&command),
+                command: Some(command),
+                handler: None,
+            });
+        }
+
+        entrypoints
+    }
+
+    fn detect_package_json(&self, content: &str, file_path: &str) -> Vec<DetectedEntrypoint> {
+        let mut entrypoints = Vec::new();
+        let json: serde_json::Value = match serde_json::from_str(content) {
+            Ok(j) => j,
+            Err(_) => return entrypoints,
+        };
+        let base_dir = parent_dir(file_path);
+
+        if let Some(start) = json.get("scripts").and_then(|s| s.get("start")).and_then(|v| v.as_str()) {
+            entrypoints.push(DetectedEntrypoint {
+                name: "npm start".to_string(),
+                entrypoint_type: EntrypointType::PackageScript,
+                file_path: file_path.to_string(),
+                line_number: None,
+                language: Some("javascript".to_string()),
+                command: Some(start.to_string()),
+                target_file: script_from_command(start).map(|s| join_path(&base_dir, &s)),
+                handler: None,
+            });
+        }
+
+        if let Some(main) = json.get("main").and_then(|v| v.as_str()) {
+            entrypoints.push(DetectedEntrypoint {
+                name: format!("main: {}", main),
+                entrypoint_type: EntrypointType::PackageScript,
+                file_path: file_path.to_string(),
+                line_number: None,
+                language: Some("javascript".to_string()),
+                command: None,
+                target_file: Some(join_path(&base_dir, main)),
+                handler: None,
+            });
+        }
+
+        // "bin" can be a single path or a map of command name -> path
+        let bins: Vec<(String, String)> = match json.get("bin") {
+            Some(serde_json::Value::String(bin)) => {
+                let name = json.get("name").and_then(|v| v.as_str()).unwrap_or("bin").to_string();
+                vec![(name, bin.clone())]
+            }
+            Some(serde_json::Value::Object(map)) => map.iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (name, bin) in bins {
+            entrypoints.push(DetectedEntrypoint {
+                name: format!("bin: {}", name),
+                entrypoint_type: EntrypointType::CliScript,
+                file_path: file_path.to_string(),
+                line_number: None,
+                language: Some("javascript".to_string()),
+                command: Some(name),
+                target_file: Some(join_path(&base_dir, &bin)),
+                handler: None,
+            });
+        }
+
+        entrypoints
+    }
+
+    fn detect_procfile(&self, content: &str, file_path: &str) -> Vec<DetectedEntrypoint> {
+        content.lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| {
+                let (process, command) = line.split_once(':')?;
+                let process = process.trim();
+                let command = command.trim();
+                if process.is_empty() || command.is_empty() || process.starts_with('#') {
+                    return None;
+                }
+                Some(DetectedEntrypoint {
+                    name: format!("Procfile {}", process),
+                    entrypoint_type: EntrypointType::PackageScript,
+                    file_path: file_path.to_string(),
+                    line_number: Some(line_num + 1),
+                    language: None,
+                    command: Some(command.to_string()),
+                    target_file: script_from_command(command),
+                    handler: None,
+                })
+            })
+            .collect()
+    }
+
+    fn detect_serverless_config(&self, content: &str, file_path: &str) -> Vec<DetectedEntrypoint> {
+        let mut entrypoints = Vec::new();
+        let yaml: serde_yaml::Value = match serde_yaml::from_str(content) {
+            Ok(y) => y,
+            Err(_) => return entrypoints,
+        };
+        let base_dir = parent_dir(file_path);
+
+        if let Some(functions) = yaml.get("functions").and_then(|f| f.as_mapping()) {
+            for (name, function) in functions {
+                let name = name.as_str().unwrap_or("function");
+                let handler = match function.get("handler").and_then(|h| h.as_str()) {
+                    Some(h) => h,
+                    None => continue,
+                };
+                // "src/handler.hello" -> file "src/handler", function "hello"
+                let (module, function_name) = handler.rsplit_once('.').unwrap_or((handler, "handler"));
+                entrypoints.push(DetectedEntrypoint {
+                    name: format!("serverless: {}", name),
+                    entrypoint_type: EntrypointType::ServerlessHandler,
+                    file_path: file_path.to_string(),
+                    line_number: None,
+                    language: None,
+                    command: Some(handler.to_string()),
+                    target_file: Some(join_path(&base_dir, module)),
+                    handler: Some(function_name.to_string()),
+                });
+            }
+        }
+
+        entrypoints
+    }
+
+    fn detect_pyproject(&self, content: &str, file_path: &str) -> Vec<DetectedEntrypoint> {
+        let mut entrypoints = Vec::new();
+        let toml_value: toml::Value = match toml::from_str(content) {
+            Ok(v) => v,
+            Err(_) => return entrypoints,
+        };
+
+        // PEP 621 [project.scripts] and Poetry [tool.poetry.scripts]
+        let script_tables = [
+            toml_value.get("project").and_then(|p| p.get("scripts")),
+            toml_value.get("tool").and_then(|t| t.get("poetry")).and_then(|p| p.get("scripts")),
+        ];
+        for table in script_tables.iter().flatten() {
+            if let Some(scripts) = table.as_table() {
+                for (name, target) in scripts {
+                    let target = match target.as_str() {
+                        Some(t) => t,
+                        None => continue,
+                    };
+                    // "package.module:function"
+                    let (module, function_name) = target.split_once(':').unwrap_or((target, "main"));
+                    entrypoints.push(DetectedEntrypoint {
+                        name: format!("script: {}", name),
+                        entrypoint_type: EntrypointType::CliScript,
+                        file_path: file_path.to_string(),
+                        line_number: None,
+                        language: Some("python".to_string()),
+                        command: Some(name.clone()),
+                        target_file: Some(format!("{}.py", module.replace('.', "/"))),
+                        handler: Some(function_name.to_string()),
+                    });
+                }
+            }
+        }
+
+        entrypoints
+    }
+}
+
+/// Parse a Dockerfile CMD/ENTRYPOINT in exec (JSON array) or shell form into a single command string
+fn parse_docker_command(raw: &str) -> String {
+    if raw.starts_with('[') {
+        if let Ok(parts) = serde_json::from_str::<Vec<String>>(raw) {
+            return parts.join(" ");
+        }
+    }
+    raw.to_string()
+}
+
+/// Extract the script file run by a command like "node dist/server.js" or "python -m app.main"
+fn script_from_command(command: &str) -> Option<String> {
+    let parts: Vec<&str> = command.split_whitespace().collect();
+    for (i, part) in parts.iter().enumerate() {
+        if *part == "-m" {
+            return parts.get(i + 1).map(|m| format!("{}.py", m.replace('.', "/")));
+        }
+        let is_script = [".js", ".mjs", ".cjs", ".ts", ".py", ".rb", ".sh", ".php", ".jar"]
+            .iter()
+            .any(|ext| part.ends_with(ext));
+        if is_script {
+            return Some(part.trim_start_matches("./").to_string());
+        }
+    }
+    None
+}
+
+fn parent_dir(file_path: &str) -> String {
+    Path::new(file_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn join_path(base: &str, relative: &str) -> String {
+    let relative = relative.trim_start_matches("./");
+    if base.is_empty() {
+        relative.to_string()
+    } else {
+        format!("{}/{}", base, relative)
+    }
+}
@@ -0,0 +1,356 @@
+//! Explains what an analysis would do with a checkout without running or storing it: which
+//! analyzers read each file, why the others pass it over, what the service patterns match and
+//! which patterns appear in a file without matching, to answer "why wasn't my service detected"
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::repo_config::{LowConfidence, ANALYZERS};
+use crate::analysis::{utils, AnalysisProfile, DependencyExtractor, RepoConfig, RepoPath};
+use crate::config::AnalysisConfig;
+use crate::plugins::PluginRegistry;
+use crate::security::pattern_config::{PatternConfig, PatternLoader, PatternRule};
+use crate::security::{DetectedService, ServiceDetector, ServiceProvider};
+
+/// Left out of the walk altogether; every analyzer skips what is under them
+const PRUNED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// Names the file-walking analyzers skip anywhere in a file's absolute path, the checkout's own
+/// location included
+const SKIPPED_PATH_PARTS: &[&str] = &["node_modules", "target", ".git", "site-packages"];
+
+/// Manifests the dependency step only reads at the repository root
+const ROOT_MANIFESTS: &[&str] = &["package.json", "requirements.txt", "cargo.toml", "go.mod", "podfile"];
+
+/// Files larger than this are not searched for near misses
+const MAX_NEAR_MISS_FILE_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainReport {
+    pub repository: String,
+    pub profile: AnalysisProfile,
+    /// The repository's `.wavelength.yml`, when it has a valid one
+    pub config_file: Option<String>,
+    pub disabled_analyzers: Vec<String>,
+    pub warnings: Vec<String>,
+    pub skipped_directories: Vec<String>,
+    /// How many files each analyzer reads
+    pub analyzers: BTreeMap<String, usize>,
+    /// How many files are skipped for each `analyzer: reason`
+    pub skip_reasons: BTreeMap<String, usize>,
+    pub files: Vec<FileExplanation>,
+    pub services: Vec<ServiceOutcome>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileExplanation {
+    /// Relative to the repository root
+    pub path: String,
+    /// Analyzers that read the file, e.g. `services (config)` or `code_structure (typescript)`
+    pub analyzed_by: Vec<String>,
+    /// Why the other analyzers pass it over, as `analyzer: reason`
+    pub skipped: Vec<String>,
+    /// Why the repository configuration leaves results from the file out
+    pub excluded: Option<String>,
+    /// Service detections in the file, before they are aggregated
+    pub matches: Vec<String>,
+    /// Patterns whose text appears in the file without producing a detection
+    pub near_misses: Vec<String>,
+}
+
+/// An aggregated service and whether the analysis would store it
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceOutcome {
+    pub name: String,
+    pub provider: ServiceProvider,
+    pub confidence: f64,
+    pub file_path: String,
+    pub kept: bool,
+    pub reason: Option<String>,
+}
+
+pub struct Explainer {
+    detector: ServiceDetector,
+}
+
+impl Explainer {
+    pub fn new(pattern_config: PatternConfig) -> Self {
+        Explainer {
+            detector: ServiceDetector::with_config(pattern_config),
+        }
+    }
+
+    /// The pattern file merged with the pattern plugins of the plugin directory, as at startup.
+    /// A repository's plugin selection is stored with the server and not applied here
+    pub fn load(patterns: &Path, plugin_dir: &Path) -> Result<Self> {
+        let registry = PluginRegistry::load(plugin_dir);
+        let (pattern_config, _) = PatternLoader::load_with_registry(patterns, &registry)
+            .with_context(|| format!("loading patterns from {}", patterns.display()))?;
+        Ok(Self::new(pattern_config))
+    }
+
+    /// Explain an analysis of the checkout at `repo_path` with the server-wide `analysis` settings.
+    /// Verdicts on false positives are stored with the server and not applied here
+    pub fn explain(&self, repo_path: &Path, profile: Option<AnalysisProfile>, analysis: &AnalysisConfig) -> Result<ExplainReport> {
+        let repo_path = repo_path.canonicalize()
+            .with_context(|| format!("{} is not a readable directory", repo_path.display()))?;
+        let mut warnings = Vec::new();
+
+        let (config_file, mut repo_config) = match RepoConfig::load(&repo_path) {
+            Ok(Some((file, config))) => (Some(file), config),
+            Ok(None) => (None, RepoConfig::default_for(&repo_path)),
+            Err(e) => {
+                warnings.push(format!("{:#}; the analysis ignores it", e));
+                (None, RepoConfig::default_for(&repo_path))
+            }
+        };
+        repo_config.inherit(analysis);
+        let profile = AnalysisProfile::resolve(profile, None, analysis.profile);
+        let profile_settings = profile.settings();
+        repo_config.apply_profile(&profile_settings);
+        let disabled_analyzers = ANALYZERS.iter()
+            .filter(|name| !repo_config.analyzer_enabled(name))
+            .map(|name| name.to_string())
+            .collect();
+
+        let root = repo_path.to_string_lossy().to_lowercase();
+        for part in SKIPPED_PATH_PARTS.iter().filter(|part| root.contains(*part)) {
+            warnings.push(format!("The checkout's path contains '{}', so the service and code analyzers skip every file in it", part));
+        }
+
+        let manifests: HashSet<String> = match DependencyExtractor::new().extract_from_repository(&repo_path) {
+            Ok(manifests) => manifests.into_iter().map(|m| RepoPath::from_relative(&m.file_path).into_string()).collect(),
+            Err(e) => {
+                warnings.push(format!("Dependency manifests could not be read: {}", e));
+                HashSet::new()
+            }
+        };
+
+        let mut skipped_directories = Vec::new();
+        let mut files = Vec::new();
+        let walker = WalkDir::new(&repo_path).sort_by_file_name().into_iter().filter_entry(|entry| {
+            let pruned = entry.depth() > 0 && entry.file_type().is_dir()
+                && PRUNED_DIRS.iter().any(|dir| entry.file_name() == *dir);
+            if pruned {
+                skipped_directories.push(RepoPath::new(&repo_path, entry.path()).into_string());
+            }
+            !pruned
+        });
+        for entry in walker.filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+            files.push(self.explain_file(&repo_path, entry.path(), &repo_config, &manifests, profile_settings.max_file_size));
+        }
+
+        let mut analyzers = BTreeMap::new();
+        let mut skip_reasons = BTreeMap::new();
+        for file in &files {
+            for analyzer in &file.analyzed_by {
+                let name = analyzer.split(" (").next().unwrap_or(analyzer);
+                *analyzers.entry(name.to_string()).or_insert(0) += 1;
+            }
+            for reason in &file.skipped {
+                *skip_reasons.entry(reason.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let services = self.detector.detect_services(&repo_path)?
+            .into_iter()
+            .map(|service| service_outcome(&repo_config, service))
+            .collect();
+
+        Ok(ExplainReport {
+            repository: repo_path.display().to_string(),
+            profile,
+            config_file,
+            disabled_analyzers,
+            warnings,
+            skipped_directories,
+            analyzers,
+            skip_reasons,
+            files,
+            services,
+        })
+    }
+
+    fn explain_file(&self, repo_path: &Path, path: &Path, repo_config: &RepoConfig, manifests: &HashSet<String>, max_file_size: Option<u64>) -> FileExplanation {
+        let relative = RepoPath::new(repo_path, path).into_string();
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+        let mut file = FileExplanation {
+            path: relative.clone(),
+            analyzed_by: Vec::new(),
+            skipped: Vec::new(),
+            excluded: None,
+            matches: Vec::new(),
+            near_misses: Vec::new(),
+        };
+
+        let (scanned_as, detected) = match self.detector.scan_file(path) {
+            Ok(scan) => {
+                file.analyzed_by.push(format!("services ({})", scan.scanned_as));
+                (Some(scan.scanned_as), scan.services)
+            }
+            Err(reason) => {
+                file.skipped.push(format!("services: {}", reason));
+                (None, Vec::new())
+            }
+        };
+        file.matches = detected.iter().map(describe).collect();
+
+        match code_language(path, &relative, &file_name) {
+            Ok(language) => file.analyzed_by.push(format!("code_structure ({})", language)),
+            Err(reason) => file.skipped.push(format!("code_structure: {}", reason)),
+        }
+
+        if manifests.contains(&relative) {
+            file.analyzed_by.push("dependencies".to_string());
+        } else if ROOT_MANIFESTS.contains(&file_name.as_str()) {
+            file.skipped.push(if relative.contains('/') {
+                "dependencies: this manifest is only read at the repository root".to_string()
+            } else {
+                "dependencies: the manifest could not be parsed".to_string()
+            });
+        }
+
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if repo_config.is_ignored(&relative) {
+            file.excluded = Some(match max_file_size {
+                Some(max) if size > max => format!("larger than the profile's {} KB limit", max / 1024),
+                _ => "matches an ignore glob of the repository configuration".to_string(),
+            });
+        } else if repo_config.is_excluded(&relative) {
+            file.excluded = Some(format!("{:?} code, which the repository configuration does not include", repo_config.origin_of(&relative)).to_lowercase());
+        }
+
+        if size <= MAX_NEAR_MISS_FILE_SIZE {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                file.near_misses = self.near_misses(&content, scanned_as, &detected);
+            }
+        }
+        file
+    }
+
+    /// Patterns of the rule sets that ran on the file (all of them when none did) whose text
+    /// appears in it, without a detection of their service
+    fn near_misses(&self, content: &str, scanned_as: Option<&str>, detected: &[DetectedService]) -> Vec<String> {
+        let patterns = &self.detector.pattern_config().patterns;
+        let rule_sets: Vec<(&str, &Vec<PatternRule>)> = match scanned_as {
+            Some("config") => vec![("environment variable", &patterns.environment_variables), ("database", &patterns.database_patterns)],
+            Some("code") => vec![("SDK", &patterns.sdk_patterns), ("API endpoint", &patterns.api_endpoints)],
+            Some(_) => Vec::new(),
+            None => vec![
+                ("environment variable", &patterns.environment_variables),
+                ("database", &patterns.database_patterns),
+                ("SDK", &patterns.sdk_patterns),
+                ("API endpoint", &patterns.api_endpoints),
+            ],
+        };
+
+        let content = content.to_lowercase();
+        let mut seen = HashSet::new();
+        let mut misses = Vec::new();
+        for (kind, rules) in rule_sets {
+            for rule in rules {
+                let pattern = rule.pattern.to_lowercase();
+                if pattern.len() < 3 || !content.contains(&pattern) {
+                    continue;
+                }
+                let provider = self.detector.parse_provider(&rule.provider).unwrap_or(ServiceProvider::Unknown);
+                let service = rule.service_name.as_deref().unwrap_or(&rule.provider);
+                let matched = detected.iter().any(|s| {
+                    s.provider == provider && (provider != ServiceProvider::Unknown || s.name.eq_ignore_ascii_case(service))
+                });
+                if !matched && seen.insert((pattern, service.to_lowercase())) {
+                    misses.push(format!("'{}' appears, but no {} pattern for {} matched", rule.pattern, kind, service));
+                }
+            }
+        }
+        misses
+    }
+}
+
+/// The language the code structure step parses the file as, or why it does not
+fn code_language(path: &Path, relative: &str, file_name: &str) -> Result<String, &'static str> {
+    let path_str = path.to_string_lossy().to_lowercase();
+    if utils::should_skip_file(file_name, &path_str) {
+        return Err("hidden file, build output or compiled artifact");
+    }
+    let language = utils::detect_language(path)
+        .or_else(|| super::salesforce::language_of(file_name, &path_str))
+        .or_else(|| super::game_engine::language_of(file_name))
+        .ok_or("not a supported source language")?;
+    let content = std::fs::read_to_string(path).map_err(|_| "not readable as UTF-8 text")?;
+    if utils::is_minified_or_compiled(&content, relative) {
+        return Err("minified or compiled code");
+    }
+    Ok(language)
+}
+
+fn describe(service: &DetectedService) -> String {
+    let evidence = service.evidence.first().map(|e| format!(", {:?}", e.kind)).unwrap_or_default();
+    let line = service.line_number.map(|l| format!(" at line {}", l)).unwrap_or_default();
+    format!("{} ({:?}, {:.2}{}){}", service.name, service.provider, service.confidence, evidence, line)
+}
+
+/// Applies the same repository configuration as the analysis: files it excludes, then the services threshold
+fn service_outcome(repo_config: &RepoConfig, service: DetectedService) -> ServiceOutcome {
+    let threshold = repo_config.thresholds.services.filter(|t| service.confidence < *t);
+    let (kept, reason) = if repo_config.is_excluded(&service.file_path) {
+        (false, Some(format!("{} is excluded by the repository configuration", service.file_path)))
+    } else if let Some(threshold) = threshold {
+        match repo_config.thresholds.below.unwrap_or_default() {
+            LowConfidence::Drop => (false, Some(format!("below the services threshold of {:.2}", threshold))),
+            LowConfidence::Flag => (true, Some(format!("flagged as below the services threshold of {:.2}", threshold))),
+        }
+    } else {
+        (true, None)
+    };
+    ServiceOutcome {
+        name: service.name,
+        provider: service.provider,
+        confidence: service.confidence,
+        file_path: service.file_path,
+        kept,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_explain_reports_skips_and_near_misses() {
+        let repo = TempDir::new().unwrap();
+        std::fs::create_dir_all(repo.path().join("config")).unwrap();
+        std::fs::create_dir_all(repo.path().join("legacy")).unwrap();
+        std::fs::write(repo.path().join(".env"), "ACME_API_KEY=xyz\n").unwrap();
+        std::fs::write(repo.path().join("config/billing.env"), "ACME_API_KEY=xyz\n").unwrap();
+        std::fs::write(repo.path().join("legacy/old.env"), "LEGACY_MODE=1\n").unwrap();
+        std::fs::write(repo.path().join(".wavelength.yml"), "ignore: [legacy]\n").unwrap();
+
+        let patterns: PatternConfig = serde_json::from_value(serde_json::json!({
+            "version": "1.0",
+            "patterns": {
+                "environment_variables": [
+                    { "pattern": "ACME", "provider": "Unknown", "service_type": "Payment", "service_name": "Acme", "confidence": 0.8 }
+                ]
+            }
+        })).unwrap();
+        let report = Explainer::new(patterns).explain(repo.path(), None, &AnalysisConfig::default()).unwrap();
+        let file = |path: &str| report.files.iter().find(|f| f.path == path).unwrap();
+
+        let hidden = file(".env");
+        assert_eq!(hidden.skipped[0], "services: hidden file");
+        assert_eq!(hidden.near_misses, vec!["'ACME' appears, but no environment variable pattern for Acme matched".to_string()]);
+
+        let billing = file("config/billing.env");
+        assert_eq!(billing.analyzed_by, vec!["services (config)".to_string()]);
+        assert!(billing.matches[0].starts_with("Acme (Unknown, 0.80, EnvVar)"), "{:?}", billing.matches);
+        assert!(billing.near_misses.is_empty());
+
+        assert!(file("legacy/old.env").excluded.is_some());
+        assert!(report.services.iter().any(|s| s.name == "Acme" && s.kept));
+    }
+}
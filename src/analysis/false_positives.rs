@@ -0,0 +1,273 @@
+//! False-positive verdicts. Users mark a detected service, vulnerability or code relationship as
+//! wrong; later analyses of the repository leave out whatever matches the verdict. Detections get
+//! new ids on every analysis, so a verdict is matched by a fingerprint of what was detected and
+//! where. Verdicts that share a pattern are turned into suggested `.wavelength.yml` changes.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use crate::analysis::{CodeElement, CodeRelationship, RelationshipTargetType};
+use crate::security::SecurityVulnerability;
+use crate::storage::{FalsePositive, StoredDependency, StoredService};
+
+/// How many verdicts it takes before they are generalized into a suggested fix
+const MIN_VERDICTS_TO_GENERALIZE: usize = 2;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum VerdictKind {
+    Service,
+    Vulnerability,
+    Relationship,
+}
+
+impl VerdictKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerdictKind::Service => "service",
+            VerdictKind::Vulnerability => "vulnerability",
+            VerdictKind::Relationship => "relationship",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<VerdictKind> {
+        match kind {
+            "service" => Some(VerdictKind::Service),
+            "vulnerability" => Some(VerdictKind::Vulnerability),
+            "relationship" => Some(VerdictKind::Relationship),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of a detection that stay the same from one analysis to the next
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Detection {
+    pub kind: VerdictKind,
+    /// The service's name, the vulnerability type, or the service or dependency a relationship points at
+    pub subject: String,
+    /// The service's provider, the vulnerability description, or the code element and relationship type
+    pub detail: String,
+    pub file_path: Option<String>,
+    pub confidence: Option<f64>,
+}
+
+impl Detection {
+    pub fn service(provider: &str, name: &str, file_path: &str, confidence: f64) -> Detection {
+        Detection {
+            kind: VerdictKind::Service,
+            subject: name.to_string(),
+            detail: provider.to_string(),
+            file_path: Some(file_path.to_string()),
+            confidence: Some(confidence),
+        }
+    }
+
+    pub fn vulnerability(vulnerability: &SecurityVulnerability) -> Detection {
+        Detection {
+            kind: VerdictKind::Vulnerability,
+            subject: vulnerability.vulnerability_type.clone(),
+            detail: vulnerability.description.clone(),
+            file_path: Some(vulnerability.file_path.clone()),
+            confidence: None,
+        }
+    }
+
+    /// `None` when the relationship's code element or target is not among those given
+    pub fn relationship(
+        relationship: &CodeRelationship,
+        element: Option<&CodeElement>,
+        services: &[StoredService],
+        dependencies: &[StoredDependency],
+    ) -> Option<Detection> {
+        let element = element?;
+        let target = match relationship.target_type {
+            RelationshipTargetType::Service => services.iter().find(|s| s.id == relationship.target_id).map(|s| &s.name),
+            RelationshipTargetType::Dependency => dependencies.iter().find(|d| d.id == relationship.target_id).map(|d| &d.name),
+        }?;
+        Some(Detection {
+            kind: VerdictKind::Relationship,
+            subject: target.clone(),
+            detail: format!("{} {}", element.name, relationship.relationship_type),
+            file_path: Some(element.file_path.clone()),
+            confidence: Some(relationship.confidence),
+        })
+    }
+
+    pub fn fingerprint(&self) -> String {
+        // A service is one record per repository, wherever its strongest evidence moves to
+        let file_path = match self.kind {
+            VerdictKind::Service => "",
+            _ => self.file_path.as_deref().unwrap_or(""),
+        };
+        format!("{}|{}|{}|{}", self.kind.as_str(), self.subject.to_lowercase(), self.detail.to_lowercase(), file_path)
+    }
+}
+
+/// The fingerprints of a repository's verdicts, checked by the analysis before storing detections
+#[derive(Debug, Default)]
+pub struct FalsePositiveFilter {
+    fingerprints: HashSet<String>,
+}
+
+impl FalsePositiveFilter {
+    pub fn new(verdicts: &[FalsePositive]) -> Self {
+        FalsePositiveFilter {
+            fingerprints: verdicts.iter().map(|v| v.fingerprint.clone()).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    pub fn excludes(&self, detection: &Detection) -> bool {
+        self.fingerprints.contains(&detection.fingerprint())
+    }
+}
+
+/// A repository configuration change that would cover several verdicts at once, and whatever
+/// else matches the same pattern
+#[derive(Debug, Clone, Serialize)]
+pub struct SuggestedFix {
+    pub kind: VerdictKind,
+    pub summary: String,
+    /// The verdicts the fix generalizes
+    pub verdict_ids: Vec<String>,
+    /// YAML to merge into the repository's `.wavelength.yml`
+    pub config: String,
+}
+
+/// Suggests suppressions for vulnerabilities marked in several files of one directory or with the
+/// same description in several directories, and confidence thresholds that would drop every
+/// marked service or relationship while keeping all the `unmarked` ones
+pub fn suggest_fixes(verdicts: &[FalsePositive], unmarked: &[Detection]) -> Vec<SuggestedFix> {
+    let mut fixes = Vec::new();
+
+    let mut by_directory: BTreeMap<(&str, String), Vec<&FalsePositive>> = BTreeMap::new();
+    let mut by_description: BTreeMap<(&str, &str), Vec<&FalsePositive>> = BTreeMap::new();
+    for verdict in verdicts.iter().filter(|v| v.detection.kind == VerdictKind::Vulnerability) {
+        let rule = verdict.detection.subject.as_str();
+        by_directory.entry((rule, directory(&verdict.detection))).or_default().push(verdict);
+        by_description.entry((rule, verdict.detection.detail.as_str())).or_default().push(verdict);
+    }
+    for ((rule, directory), group) in by_directory {
+        if group.len() < MIN_VERDICTS_TO_GENERALIZE {
+            continue;
+        }
+        let path = if directory.is_empty() { "**".to_string() } else { format!("{}/**", directory) };
+        fixes.push(suppression(&group, format!("Suppress {} under {}", rule, path), json!({ "rule": rule, "path": path })));
+    }
+    for ((rule, description), group) in by_description {
+        let directories: BTreeSet<String> = group.iter().map(|v| directory(&v.detection)).collect();
+        // Within one directory the suppression above already covers them
+        if group.len() < MIN_VERDICTS_TO_GENERALIZE || directories.len() < 2 {
+            continue;
+        }
+        fixes.push(suppression(&group, format!("Suppress {} reporting \"{}\" anywhere", rule, description), json!({ "rule": rule, "message": description })));
+    }
+
+    for (kind, key) in [(VerdictKind::Service, "services"), (VerdictKind::Relationship, "relationships")] {
+        let marked: Vec<&FalsePositive> = verdicts.iter()
+            .filter(|v| v.detection.kind == kind && v.detection.confidence.is_some())
+            .collect();
+        if marked.len() < MIN_VERDICTS_TO_GENERALIZE {
+            continue;
+        }
+        let highest_marked = marked.iter().filter_map(|v| v.detection.confidence).fold(0.0, f64::max);
+        // The lowest two-decimal threshold above every marked detection
+        let threshold = ((highest_marked * 100.0).floor() + 1.0) / 100.0;
+        let lowest_unmarked = unmarked.iter()
+            .filter(|d| d.kind == kind)
+            .filter_map(|d| d.confidence)
+            .fold(f64::INFINITY, f64::min);
+        if threshold > 1.0 || threshold > lowest_unmarked {
+            continue;
+        }
+        fixes.push(SuggestedFix {
+            kind,
+            summary: format!("Raise the {} confidence threshold to {}; every {} marked false positive is below it and none of the others are", key, threshold, kind.as_str()),
+            verdict_ids: marked.iter().map(|v| v.id.clone()).collect(),
+            config: yaml(json!({ "thresholds": { key: threshold } })),
+        });
+    }
+
+    fixes
+}
+
+fn directory(detection: &Detection) -> String {
+    detection.file_path.as_deref()
+        .and_then(|f| Path::new(f).parent())
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default()
+}
+
+fn suppression(group: &[&FalsePositive], summary: String, mut entry: serde_json::Value) -> SuggestedFix {
+    entry["reason"] = json!(format!("Marked as a false positive {} times", group.len()));
+    SuggestedFix {
+        kind: VerdictKind::Vulnerability,
+        summary,
+        verdict_ids: group.iter().map(|v| v.id.clone()).collect(),
+        config: yaml(json!({ "suppressions": [entry] })),
+    }
+}
+
+fn yaml(value: serde_json::Value) -> String {
+    serde_yaml::to_string(&value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::VulnerabilitySeverity;
+
+    fn verdict(id: &str, detection: Detection) -> FalsePositive {
+        FalsePositive {
+            id: id.to_string(),
+            repository_id: "repo-1".to_string(),
+            fingerprint: detection.fingerprint(),
+            detection,
+            reason: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verdicts_filter_and_generalize() {
+        let vulnerability = |file_path: &str| Detection::vulnerability(&SecurityVulnerability {
+            id: "v".to_string(),
+            entity_id: "e".to_string(),
+            vulnerability_type: "HardcodedSecret".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "Hardcoded password".to_string(),
+            recommendation: String::new(),
+            file_path: file_path.to_string(),
+            line_number: None,
+            affected_range: None,
+            fixed_version: None,
+        });
+        let verdicts = vec![
+            verdict("1", vulnerability("tests/fixtures/a.yml")),
+            verdict("2", vulnerability("tests/fixtures/b.yml")),
+            verdict("3", Detection::service("aws", "Amazon S3", "package.json", 0.42)),
+            verdict("4", Detection::service("stripe", "Stripe", "src/pay.ts", 0.5)),
+        ];
+
+        let filter = FalsePositiveFilter::new(&verdicts);
+        // The service moved to another file but is the same service
+        assert!(filter.excludes(&Detection::service("aws", "amazon s3", "infra/main.tf", 0.9)));
+        assert!(filter.excludes(&vulnerability("tests/fixtures/a.yml")));
+        assert!(!filter.excludes(&vulnerability("src/config.yml")));
+
+        let fixes = suggest_fixes(&verdicts, &[Detection::service("gcp", "Cloud Storage", "main.tf", 0.8)]);
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].verdict_ids, vec!["1", "2"]);
+        assert!(fixes[0].config.contains("path: tests/fixtures/**"));
+        assert!(fixes[1].config.contains("services: 0.51"));
+
+        // A threshold that would also drop a service nobody marked is not suggested
+        let fixes = suggest_fixes(&verdicts, &[Detection::service("gcp", "Cloud Storage", "main.tf", 0.45)]);
+        assert!(fixes.iter().all(|f| f.kind == VerdictKind::Vulnerability));
+    }
+}
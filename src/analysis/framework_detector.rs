@@ -0,0 +1,294 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::PackageManager;
+use crate::analysis::dependencies::DependencyManifest;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FrameworkCategory {
+    Web,      // Server-side web/API frameworks
+    Frontend, // UI frameworks and meta-frameworks
+    Orm,      // ORMs and database toolkits
+    Runtime,  // Language runtimes (node, python, rust toolchain, ...)
+    GameEngine, // Unity and Unreal Engine projects
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedFramework {
+    pub name: String, // Matches the framework names used by EndpointDetector (e.g. "express", "actix")
+    pub category: FrameworkCategory,
+    pub language: String,
+    pub version: Option<String>,
+    pub file_path: String, // Manifest or marker file that revealed the framework
+    pub confidence: f64,
+}
+
+/// How a dependency name is matched against a known framework package
+enum Match {
+    Exact(&'static str),
+    Contains(&'static str),
+}
+
+/// (package manager, dependency match, framework name, category, language)
+const KNOWN_FRAMEWORKS: &[(PackageManager, Match, &str, FrameworkCategory, &str)] = &[
+    // JavaScript / TypeScript
+    (PackageManager::Npm, Match::Exact("express"), "express", FrameworkCategory::Web, "javascript"),
+    (PackageManager::Npm, Match::Exact("fastify"), "fastify", FrameworkCategory::Web, "javascript"),
+    (PackageManager::Npm, Match::Exact("@nestjs/core"), "nestjs", FrameworkCategory::Web, "typescript"),
+    (PackageManager::Npm, Match::Exact("koa"), "koa", FrameworkCategory::Web, "javascript"),
+    (PackageManager::Npm, Match::Exact("@hapi/hapi"), "hapi", FrameworkCategory::Web, "javascript"),
+    (PackageManager::Npm, Match::Exact("hono"), "hono", FrameworkCategory::Web, "typescript"),
+    (PackageManager::Npm, Match::Exact("next"), "nextjs", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("nuxt"), "nuxt", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("@sveltejs/kit"), "sveltekit", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Contains("@remix-run/"), "remix", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("react"), "react", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("vue"), "vue", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("@angular/core"), "angular", FrameworkCategory::Frontend, "typescript"),
+    (PackageManager::Npm, Match::Exact("svelte"), "svelte", FrameworkCategory::Frontend, "javascript"),
+    (PackageManager::Npm, Match::Exact("@prisma/client"), "prisma", FrameworkCategory::Orm, "javascript"),
+    (PackageManager::Npm, Match::Exact("typeorm"), "typeorm", FrameworkCategory::Orm, "typescript"),
+    (PackageManager::Npm, Match::Exact("sequelize"), "sequelize", FrameworkCategory::Orm, "javascript"),
+    (PackageManager::Npm, Match::Exact("mongoose"), "mongoose", FrameworkCategory::Orm, "javascript"),
+    (PackageManager::Npm, Match::Exact("drizzle-orm"), "drizzle", FrameworkCategory::Orm, "typescript"),
+    // Python
+    (PackageManager::Pip, Match::Exact("django"), "django", FrameworkCategory::Web, "python"),
+    (PackageManager::Pip, Match::Exact("flask"), "flask", FrameworkCategory::Web, "python"),
+    (PackageManager::Pip, Match::Exact("fastapi"), "fastapi", FrameworkCategory::Web, "python"),
+    (PackageManager::Pip, Match::Exact("starlette"), "starlette", FrameworkCategory::Web, "python"),
+    (PackageManager::Pip, Match::Exact("sqlalchemy"), "sqlalchemy", FrameworkCategory::Orm, "python"),
+    (PackageManager::Pip, Match::Exact("peewee"), "peewee", FrameworkCategory::Orm, "python"),
+    // Rust
+    (PackageManager::Cargo, Match::Exact("actix-web"), "actix", FrameworkCategory::Web, "rust"),
+    (PackageManager::Cargo, Match::Exact("rocket"), "rocket", FrameworkCategory::Web, "rust"),
+    (PackageManager::Cargo, Match::Exact("axum"), "axum", FrameworkCategory::Web, "rust"),
+    (PackageManager::Cargo, Match::Exact("warp"), "warp", FrameworkCategory::Web, "rust"),
+    (PackageManager::Cargo, Match::Exact("poem"), "poem", FrameworkCategory::Web, "rust"),
+    (PackageManager::Cargo, Match::Exact("diesel"), "diesel", FrameworkCategory::Orm, "rust"),
+    (PackageManager::Cargo, Match::Exact("sea-orm"), "sea-orm", FrameworkCategory::Orm, "rust"),
+    (PackageManager::Cargo, Match::Exact("sqlx"), "sqlx", FrameworkCategory::Orm, "rust"),
+    // Go
+    (PackageManager::Go, Match::Exact("github.com/gin-gonic/gin"), "gin", FrameworkCategory::Web, "go"),
+    (PackageManager::Go, Match::Contains("github.com/labstack/echo"), "echo", FrameworkCategory::Web, "go"),
+    (PackageManager::Go, Match::Contains("github.com/gofiber/fiber"), "fiber", FrameworkCategory::Web, "go"),
+    (PackageManager::Go, Match::Contains("github.com/go-chi/chi"), "chi", FrameworkCategory::Web, "go"),
+    (PackageManager::Go, Match::Exact("gorm.io/gorm"), "gorm", FrameworkCategory::Orm, "go"),
+    // JVM
+    (PackageManager::Maven, Match::Contains("spring-boot"), "spring", FrameworkCategory::Web, "java"),
+    (PackageManager::Gradle, Match::Contains("spring-boot"), "spring", FrameworkCategory::Web, "java"),
+    (PackageManager::Maven, Match::Contains("jakarta.ws.rs"), "jaxrs", FrameworkCategory::Web, "java"),
+    (PackageManager::Maven, Match::Contains("hibernate-core"), "hibernate", FrameworkCategory::Orm, "java"),
+    (PackageManager::Gradle, Match::Contains("hibernate-core"), "hibernate", FrameworkCategory::Orm, "java"),
+    // PHP
+    (PackageManager::Composer, Match::Exact("laravel/framework"), "laravel", FrameworkCategory::Web, "php"),
+    (PackageManager::Composer, Match::Contains("symfony/framework-bundle"), "symfony", FrameworkCategory::Web, "php"),
+    (PackageManager::Composer, Match::Contains("doctrine/orm"), "doctrine", FrameworkCategory::Orm, "php"),
+    // .NET
+    (PackageManager::NuGet, Match::Contains("Microsoft.AspNetCore"), "aspnetcore", FrameworkCategory::Web, "csharp"),
+    (PackageManager::NuGet, Match::Contains("Microsoft.EntityFrameworkCore"), "entity-framework", FrameworkCategory::Orm, "csharp"),
+];
+
+pub struct FrameworkDetector;
+
+impl FrameworkDetector {
+    pub fn new() -> Self {
+        FrameworkDetector
+    }
+
+    /// Detect frameworks and runtimes from extracted dependencies plus marker files in the repository
+    pub fn detect_frameworks(&self, repo_path: &Path, manifests: &[DependencyManifest]) -> Result<Vec<DetectedFramework>> {
+        let mut frameworks: Vec<DetectedFramework> = Vec::new();
+
+        for manifest in manifests {
+            for dep in &manifest.dependencies {
+                for (manager, matcher, name, category, language) in KNOWN_FRAMEWORKS {
+                    if *manager != dep.package_manager {
+                        continue;
+                    }
+                    let matched = match matcher {
+                        Match::Exact(pkg) => dep.name.eq_ignore_ascii_case(pkg),
+                        Match::Contains(pkg) => dep.name.contains(pkg),
+                    };
+                    if matched {
+                        self.add_framework(&mut frameworks, DetectedFramework {
+                            name: name.to_string(),
+                            category: category.clone(),
+                            language: language.to_string(),
+                            version: clean_version(&dep.version),
+                            file_path: manifest.file_path.clone(),
+                            confidence: 0.95,
+                        });
+                    }
+                }
+            }
+        }
+
+        for entry in WalkDir::new(repo_path)
+            .max_depth(4)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_string();
+            let path_str = path.to_string_lossy().to_lowercase();
+            // Runtime version files are dotfiles, so only apply the shared skip list to other files
+            if !file_name.starts_with('.') && utils::should_skip_file(&file_name.to_lowercase(), &path_str) {
+                continue;
+            }
+            if path_str.contains("/node_modules/") || path_str.contains("/vendor/") || path_str.contains("/target/") {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            for framework in self.detect_from_marker_file(path, &file_name, &normalized_path) {
+                self.add_framework(&mut frameworks, framework);
+            }
+        }
+
+        Ok(frameworks)
+    }
+
+    /// Keep one entry per framework, preferring entries that carry a version
+    fn add_framework(&self, frameworks: &mut Vec<DetectedFramework>, framework: DetectedFramework) {
+        if let Some(existing) = frameworks.iter_mut().find(|f| f.name == framework.name) {
+            if existing.version.is_none() && framework.version.is_some() {
+                existing.version = framework.version;
+                existing.file_path = framework.file_path;
+            }
+            existing.confidence = existing.confidence.max(framework.confidence);
+        } else {
+            frameworks.push(framework);
+        }
+    }
+
+    /// Marker files: framework entry files not visible through dependencies, and runtime version pins
+    fn detect_from_marker_file(&self, path: &Path, file_name: &str, normalized_path: &str) -> Vec<DetectedFramework> {
+        let mut found = Vec::new();
+        let read = || std::fs::read_to_string(path).unwrap_or_default();
+        let framework = |name: &str, category: FrameworkCategory, language: &str, version: Option<String>, confidence: f64| DetectedFramework {
+            name: name.to_string(),
+            category,
+            language: language.to_string(),
+            version,
+            file_path: normalized_path.to_string(),
+            confidence,
+        };
+
+        match file_name {
+            "manage.py" if read().contains("django") => {
+                found.push(framework("django", FrameworkCategory::Web, "python", None, 0.9));
+            }
+            "Gemfile" => {
+                // Bundler isn't a supported package manager, so read the Gemfile directly
+                let content = read();
+                for (gem, name, category) in [
+                    ("rails", "rails", FrameworkCategory::Web),
+                    ("sinatra", "sinatra", FrameworkCategory::Web),
+                    ("activerecord", "activerecord", FrameworkCategory::Orm),
+                ] {
+                    if let Some(version) = gemfile_version(&content, gem) {
+                        found.push(framework(name, category, "ruby", version, 0.95));
+                    }
+                }
+            }
+            ".ruby-version" => {
+                found.push(framework("ruby", FrameworkCategory::Runtime, "ruby", first_line(&read()), 0.9));
+            }
+            ".nvmrc" | ".node-version" => {
+                let version = first_line(&read()).map(|v| v.trim_start_matches('v').to_string());
+                found.push(framework("node", FrameworkCategory::Runtime, "javascript", version, 0.9));
+            }
+            "package.json" => {
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&read()) {
+                    if let Some(node) = json.get("engines").and_then(|e| e.get("node")).and_then(|v| v.as_str()) {
+                        found.push(framework("node", FrameworkCategory::Runtime, "javascript", clean_version(node), 0.85));
+                    }
+                }
+            }
+            "bun.lockb" | "bunfig.toml" => {
+                found.push(framework("bun", FrameworkCategory::Runtime, "javascript", None, 0.9));
+            }
+            "deno.json" | "deno.jsonc" => {
+                found.push(framework("deno", FrameworkCategory::Runtime, "typescript", None, 0.9));
+            }
+            ".python-version" => {
+                found.push(framework("python", FrameworkCategory::Runtime, "python", first_line(&read()), 0.9));
+            }
+            "rust-toolchain" | "rust-toolchain.toml" => {
+                let content = read();
+                let version = if file_name.ends_with(".toml") {
+                    toml::from_str::<toml::Value>(&content).ok()
+                        .and_then(|v| v.get("toolchain").and_then(|t| t.get("channel")).and_then(|c| c.as_str()).map(|s| s.to_string()))
+                } else {
+                    first_line(&content)
+                };
+                found.push(framework("rust", FrameworkCategory::Runtime, "rust", version, 0.9));
+            }
+            "go.mod" => {
+                let version = read().lines()
+                    .find_map(|l| l.trim().strip_prefix("go ").map(|v| v.trim().to_string()));
+                found.push(framework("go", FrameworkCategory::Runtime, "go", version, 0.9));
+            }
+            "ProjectVersion.txt" if normalized_path.ends_with("ProjectSettings/ProjectVersion.txt") => {
+                let version = read().lines()
+                    .find_map(|l| l.trim().strip_prefix("m_EditorVersion:").map(|v| v.trim().to_string()));
+                found.push(framework("unity", FrameworkCategory::GameEngine, "csharp", version, 0.95));
+            }
+            name if name.ends_with(".uproject") => {
+                // EngineAssociation is a version for launcher installs and a GUID for source builds
+                let version = serde_json::from_str::<serde_json::Value>(read().trim_start_matches('\u{feff}')).ok()
+                    .and_then(|json| json.get("EngineAssociation").and_then(|v| v.as_str()).map(|s| s.to_string()))
+                    .filter(|v| !v.is_empty() && !v.starts_with('{'));
+                found.push(framework("unreal", FrameworkCategory::GameEngine, "cpp", version, 0.95));
+            }
+            _ => {}
+        }
+
+        found
+    }
+}
+
+/// Strip range operators from a dependency version ("^4.18.2" -> "4.18.2")
+fn clean_version(version: &str) -> Option<String> {
+    let cleaned = version.trim()
+        .trim_start_matches(['^', '~', '=', '>', '<', 'v', ' '])
+        .to_string();
+    if cleaned.is_empty() || cleaned == "*" || cleaned == "unknown" || cleaned == "latest" {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+fn first_line(content: &str) -> Option<String> {
+    content.lines()
+        .map(|l| l.trim())
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.to_string())
+}
+
+/// Find `gem "name", "version"` in a Gemfile. Returns Some(None) when the gem has no version.
+fn gemfile_version(content: &str, gem: &str) -> Option<Option<String>> {
+    for line in content.lines() {
+        let line = line.trim();
+        let rest = match line.strip_prefix("gem ") {
+            Some(r) => r,
+            None => continue,
+        };
+        let parts: Vec<&str> = rest.split(',')
+            .map(|p| p.trim().trim_matches(['\'', '"']))
+            .collect();
+        if parts.first() == Some(&gem) {
+            return Some(parts.get(1).and_then(|v| clean_version(v)));
+        }
+    }
+    None
+}
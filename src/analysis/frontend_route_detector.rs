@@ -0,0 +1,389 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FrontendRouter {
+    NextPages,   // Next.js pages/ directory
+    NextApp,     // Next.js app/ directory (page.tsx files)
+    ReactRouter, // <Route> elements or createBrowserRouter route objects
+    VueRouter,   // createRouter / new VueRouter route records
+    Angular,     // Routes arrays passed to RouterModule / provideRouter
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrontendRoute {
+    pub path: String, // Dynamic segments use the ":param" form for every router
+    pub router: FrontendRouter,
+    pub component: Option<String>, // Component rendered for the route
+    pub component_file: Option<String>, // File defining the component, when it can be resolved
+    pub file_path: String, // Page file or router configuration declaring the route
+    pub line_number: Option<usize>,
+    pub lazy: bool, // Component is loaded with a dynamic import
+}
+
+const SCRIPT_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mjs", "vue"];
+
+pub struct FrontendRouteDetector;
+
+impl FrontendRouteDetector {
+    pub fn new() -> Self {
+        FrontendRouteDetector
+    }
+
+    /// Detect client-side routes. File-system routing is only considered when Next.js was detected,
+    /// since plain `pages/` directories are common in other projects.
+    pub fn detect_routes(&self, repo_path: &Path, frameworks: &[String]) -> Result<Vec<FrontendRoute>> {
+        let uses_next = frameworks.iter().any(|f| f == "nextjs");
+        let mut routes = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+            let extension = file_name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+            if !SCRIPT_EXTENSIONS.contains(&extension) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            if utils::is_minified_or_compiled(&content, &normalized_path) {
+                continue;
+            }
+
+            if uses_next {
+                if let Some(route) = self.detect_next_route(&content, &normalized_path) {
+                    routes.push(route);
+                    continue;
+                }
+            }
+
+            routes.extend(self.detect_router_config(repo_path, &content, &normalized_path)?);
+        }
+
+        routes.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.file_path.cmp(&b.file_path)));
+        Ok(routes)
+    }
+
+    /// Map a Next.js page file to its route
+    fn detect_next_route(&self, content: &str, file_path: &str) -> Option<FrontendRoute> {
+        let segments: Vec<&str> = file_path.split('/').collect();
+        let stem = |name: &str| name.rsplit_once('.').map(|(s, _)| s.to_string()).unwrap_or_default();
+
+        // Pages router: the route directory is pages/ or src/pages/
+        if let Some(idx) = segments.iter().position(|s| *s == "pages") {
+            if idx == 0 || (idx == 1 && segments[0] == "src") {
+                let rest = &segments[idx + 1..];
+                let page = stem(rest.last()?);
+                // API routes are picked up as endpoints; _app/_document are not pages
+                if rest.first() == Some(&"api") || page.starts_with('_') {
+                    return None;
+                }
+                let mut route_segments: Vec<String> = rest[..rest.len() - 1].iter().map(|s| s.to_string()).collect();
+                if page != "index" {
+                    route_segments.push(page);
+                }
+                return Some(self.next_route(route_segments, FrontendRouter::NextPages, content, file_path));
+            }
+        }
+
+        // App router: app/**/page.tsx, where (group) folders don't affect the path
+        if let Some(idx) = segments.iter().position(|s| *s == "app") {
+            if (idx == 0 || (idx == 1 && segments[0] == "src")) && stem(segments.last()?) == "page" {
+                let dirs = &segments[idx + 1..segments.len() - 1];
+                if dirs.iter().any(|d| d.starts_with('_')) {
+                    return None;
+                }
+                let is_group = |d: &str| d.starts_with('(') && d.ends_with(')');
+                let route_segments: Vec<String> = dirs.iter()
+                    .filter(|d| !is_group(d) && !d.starts_with('@'))
+                    .map(|d| d.to_string())
+                    .collect();
+                return Some(self.next_route(route_segments, FrontendRouter::NextApp, content, file_path));
+            }
+        }
+
+        None
+    }
+
+    fn next_route(&self, segments: Vec<String>, router: FrontendRouter, content: &str, file_path: &str) -> FrontendRoute {
+        let path = format!("/{}", segments.iter()
+            .map(|s| normalize_dynamic_segment(s))
+            .collect::<Vec<_>>()
+            .join("/"));
+        let default_export = Regex::new(r"export\s+default\s+(?:async\s+)?(?:function|class)\s+(\w+)").unwrap();
+        let default_ident = Regex::new(r"export\s+default\s+(\w+)\s*;?\s*$").unwrap();
+
+        let mut component = None;
+        let mut line_number = None;
+        for (idx, line) in content.lines().enumerate() {
+            let cap = default_export.captures(line).or_else(|| default_ident.captures(line));
+            if let Some(cap) = cap {
+                component = Some(cap[1].to_string());
+                line_number = Some(idx + 1);
+                break;
+            }
+        }
+
+        FrontendRoute {
+            path,
+            router,
+            component,
+            component_file: Some(file_path.to_string()),
+            file_path: file_path.to_string(),
+            line_number,
+            lazy: false,
+        }
+    }
+
+    /// Routes declared in code: JSX <Route> elements and `{ path: ..., component: ... }` route records
+    fn detect_router_config(&self, repo_path: &Path, content: &str, file_path: &str) -> Result<Vec<FrontendRoute>> {
+        let router = if content.contains("@angular/router") {
+            FrontendRouter::Angular
+        } else if content.contains("vue-router") {
+            FrontendRouter::VueRouter
+        } else if content.contains("react-router") {
+            FrontendRouter::ReactRouter
+        } else {
+            return Ok(Vec::new());
+        };
+
+        let imports = self.resolve_imports(repo_path, content, file_path)?;
+        let jsx_route = Regex::new(r"<Route\b")?;
+        let jsx_path = Regex::new(r#"\bpath\s*=\s*\{?\s*["'`]([^"'`]*)["'`]"#)?;
+        let object_path = Regex::new(r#"\bpath\s*:\s*["'`]([^"'`]*)["'`]"#)?;
+        let component_patterns = [
+            Regex::new(r"\belement\s*[=:]\s*\{?\s*<\s*(\w+)")?,
+            Regex::new(r"\b(?:component|Component)\s*[=:]\s*\{?\s*(\w+)")?,
+        ];
+        let lazy_import = Regex::new(r#"import\(\s*["'`]([^"'`]+)["'`]\s*\)(?:\s*\.then\(\s*\(?\s*\w+\s*\)?\s*=>\s*\w+\.(\w+))?"#)?;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut routes = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let (raw_path, window) = if jsx_route.is_match(line) {
+                // Attributes may span a few lines; stop at the next route element
+                let window = route_window(&lines, idx, |l| jsx_route.is_match(l));
+                match jsx_path.captures(&window) {
+                    Some(cap) => (cap[1].to_string(), window),
+                    None => continue,
+                }
+            } else if let Some(cap) = object_path.captures(line) {
+                let window = route_window(&lines, idx, |l| object_path.is_match(l));
+                (cap[1].to_string(), window)
+            } else {
+                continue;
+            };
+
+            let mut component = None;
+            let mut component_file = None;
+            let mut lazy = false;
+            if let Some(cap) = lazy_import.captures(&window) {
+                lazy = true;
+                let target = cap[1].to_string();
+                component = cap.get(2).map(|m| m.as_str().to_string())
+                    .or_else(|| target.rsplit('/').next().map(|s| s.split('.').next().unwrap_or(s).to_string()));
+                component_file = resolve_module(repo_path, file_path, &target);
+            } else {
+                for pattern in &component_patterns {
+                    if let Some(cap) = pattern.captures(&window) {
+                        let name = cap[1].to_string();
+                        // `component: () => import(...)` is handled above; skip keywords caught here
+                        if name == "async" || name == "function" {
+                            continue;
+                        }
+                        component_file = imports.get(&name).cloned();
+                        component = Some(name);
+                        break;
+                    }
+                }
+            }
+
+            // Redirect-only records and catch-all wildcards with nothing rendered are noise
+            if component.is_none() && (raw_path == "**" || window.contains("redirect")) {
+                continue;
+            }
+
+            routes.push(FrontendRoute {
+                path: normalize_declared_path(&raw_path),
+                router: router.clone(),
+                component,
+                component_file,
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                lazy,
+            });
+        }
+
+        Ok(routes)
+    }
+
+    /// Map imported identifiers to the repository files they come from (relative imports only)
+    fn resolve_imports(&self, repo_path: &Path, content: &str, file_path: &str) -> Result<HashMap<String, String>> {
+        let default_import = Regex::new(r#"^\s*import\s+(\w+)\s*(?:,\s*\{[^}]*\})?\s*from\s+["'](\.[^"']+)["']"#)?;
+        let named_import = Regex::new(r#"^\s*import\s+(?:\w+\s*,\s*)?\{([^}]+)\}\s*from\s+["'](\.[^"']+)["']"#)?;
+
+        let mut imports = HashMap::new();
+        for line in content.lines() {
+            if let Some(cap) = default_import.captures(line) {
+                if let Some(resolved) = resolve_module(repo_path, file_path, &cap[2]) {
+                    imports.insert(cap[1].to_string(), resolved);
+                }
+            }
+            if let Some(cap) = named_import.captures(line) {
+                if let Some(resolved) = resolve_module(repo_path, file_path, &cap[2]) {
+                    for name in cap[1].split(',') {
+                        // `Foo as Bar` binds Bar locally
+                        let local = name.split(" as ").last().unwrap_or("").trim();
+                        if !local.is_empty() {
+                            imports.insert(local.to_string(), resolved.clone());
+                        }
+                    }
+                }
+            }
+        }
+        Ok(imports)
+    }
+}
+
+/// Join a route line with its continuation lines, up to the next route declaration
+fn route_window<F: Fn(&str) -> bool>(lines: &[&str], start: usize, is_next_route: F) -> String {
+    let mut window = lines[start].to_string();
+    for line in lines.iter().skip(start + 1).take(6) {
+        if is_next_route(line) {
+            break;
+        }
+        window.push(' ');
+        window.push_str(line);
+    }
+    window
+}
+
+/// Resolve a relative import to a repository file, trying the usual extensions and index files
+fn resolve_module(repo_path: &Path, importer: &str, specifier: &str) -> Option<String> {
+    if !specifier.starts_with('.') {
+        return None;
+    }
+    let base_dir = Path::new(importer).parent().unwrap_or_else(|| Path::new(""));
+    let mut joined = PathBuf::new();
+    for component in base_dir.join(specifier).components() {
+        match component {
+            Component::ParentDir => { joined.pop(); }
+            Component::CurDir => {}
+            other => joined.push(other.as_os_str()),
+        }
+    }
+
+    let mut candidates = vec![joined.clone()];
+    for ext in SCRIPT_EXTENSIONS {
+        candidates.push(joined.with_extension(ext));
+    }
+    for ext in SCRIPT_EXTENSIONS {
+        candidates.push(joined.join(format!("index.{}", ext)));
+    }
+    candidates.into_iter()
+        .find(|c| repo_path.join(c).is_file())
+        .map(|c| c.to_string_lossy().replace('\\', "/"))
+}
+
+/// "[id]" -> ":id", "[...slug]" / "[[...slug]]" -> ":slug*"
+fn normalize_dynamic_segment(segment: &str) -> String {
+    let inner = segment.trim_start_matches('[').trim_end_matches(']');
+    if inner.len() == segment.len() {
+        return segment.to_string();
+    }
+    match inner.strip_prefix("...") {
+        Some(name) => format!(":{}*", name),
+        None => format!(":{}", inner),
+    }
+}
+
+/// Route records in router configs are often relative to a parent route; keep them as declared
+/// apart from mapping the empty path to "/" and "*"/"**" wildcards to a single form
+fn normalize_declared_path(path: &str) -> String {
+    match path {
+        "" => "/".to_string(),
+        "*" | "**" => "*".to_string(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_next_pages_and_app_routes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("pages/users")).unwrap();
+        fs::create_dir_all(root.join("pages/api")).unwrap();
+        fs::create_dir_all(root.join("app/(marketing)/blog/[slug]")).unwrap();
+        fs::write(root.join("pages/index.tsx"), "export default function Home() { return null }\n").unwrap();
+        fs::write(root.join("pages/users/[id].tsx"), "const UserPage = () => null;\nexport default UserPage;\n").unwrap();
+        fs::write(root.join("pages/_app.tsx"), "export default function App() {}\n").unwrap();
+        fs::write(root.join("pages/api/hello.ts"), "export default function handler() {}\n").unwrap();
+        fs::write(root.join("app/(marketing)/blog/[slug]/page.tsx"), "export default async function BlogPost() {}\n").unwrap();
+
+        let routes = FrontendRouteDetector::new().detect_routes(root, &["nextjs".to_string()]).unwrap();
+        let paths: Vec<&str> = routes.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, vec!["/", "/blog/:slug", "/users/:id"]);
+        assert_eq!(routes[1].router, FrontendRouter::NextApp);
+        assert_eq!(routes[2].component.as_deref(), Some("UserPage"));
+    }
+
+    #[test]
+    fn test_detect_react_router_routes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/pages")).unwrap();
+        fs::write(root.join("src/pages/Dashboard.tsx"), "export function Dashboard() {}\n").unwrap();
+        fs::write(root.join("src/App.tsx"), r#"import { Routes, Route } from "react-router-dom";
+import { Dashboard } from "./pages/Dashboard";
+const Settings = lazy(() => import("./pages/Settings"));
+
+export function App() {
+  return (
+    <Routes>
+      <Route path="/dashboard" element={<Dashboard />} />
+      <Route
+        path="/settings/:tab"
+        element={<Settings />}
+      />
+    </Routes>
+  );
+}
+"#).unwrap();
+
+        let routes = FrontendRouteDetector::new().detect_routes(root, &[]).unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].path, "/dashboard");
+        assert_eq!(routes[0].component_file.as_deref(), Some("src/pages/Dashboard.tsx"));
+        assert_eq!(routes[1].path, "/settings/:tab");
+        assert_eq!(routes[1].component.as_deref(), Some("Settings"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Scripting assemblies of game engine projects: Unity assembly definitions (`*.asmdef`) and the
+//! modules of an Unreal Engine project, declared in its `.uproject`/`.uplugin` files and built by
+//! `*.Build.cs` rules. Each assembly or module becomes an element, and the assemblies and modules
+//! it references become modules of its file, so references between them show up as imports.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use crate::analysis::code_structure::{CodeElement, CodeElementType};
+
+/// `PublicDependencyModuleNames.AddRange(new string[] { "Core", ... })` and `.Add("Core")`
+static MODULE_DEPENDENCIES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(Public|Private)DependencyModuleNames\s*\.\s*(?:AddRange|Add)\s*\(([^;]*)\)\s*;").unwrap()
+});
+static QUOTED: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]+)""#).unwrap());
+static META_GUID: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^guid:\s*([0-9a-fA-F]+)").unwrap());
+
+/// The language the assembly analyzer reads `file_name` as; the name is lowercase
+pub fn language_of(file_name: &str) -> Option<String> {
+    if file_name.ends_with(".asmdef") {
+        Some("unity".to_string())
+    } else if file_name.ends_with(".build.cs") || file_name.ends_with(".uproject") || file_name.ends_with(".uplugin") {
+        Some("unreal".to_string())
+    } else {
+        None
+    }
+}
+
+fn element(name: &str, element_type: CodeElementType, path: &str, line_number: usize, language: &str) -> CodeElement {
+    CodeElement {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        element_type,
+        file_path: path.to_string(),
+        line_number,
+        language: language.to_string(),
+        signature: None,
+        doc_comment: None,
+        visibility: None,
+        parameters: Vec::new(),
+        return_type: None,
+        parent_id: None,
+        qualified_name: String::new(),
+        decorators: Vec::new(),
+        origin: Default::default(),
+    }
+}
+
+fn line_of(content: &str, needle: &str) -> usize {
+    content.find(needle).map(|pos| content[..pos].matches('\n').count() + 1).unwrap_or(1)
+}
+
+fn strings(json: &serde_json::Value, key: &str) -> Vec<String> {
+    json.get(key).and_then(|v| v.as_array()).into_iter().flatten()
+        .filter_map(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// A Unity assembly definition: the assembly, whether it is a runtime, editor-only or test assembly,
+/// and the assemblies it references by name or by `GUID:` (see [`resolve_assembly_guids`])
+pub fn analyze_assembly_definition(content: &str, path: &str) -> Vec<CodeElement> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{feff}')) else {
+        return Vec::new();
+    };
+    let Some(name) = json.get("name").and_then(|v| v.as_str()).filter(|n| !n.is_empty()) else {
+        return Vec::new();
+    };
+    let platforms = strings(&json, "includePlatforms");
+    let defines = strings(&json, "defineConstraints");
+    let kind = if strings(&json, "optionalUnityReferences").iter().any(|r| r == "TestAssemblies")
+        || defines.iter().any(|d| d == "UNITY_INCLUDE_TESTS")
+    {
+        "test"
+    } else if platforms == ["Editor"] {
+        "editor"
+    } else {
+        "runtime"
+    };
+
+    let mut assembly = element(name, CodeElementType::Class, path, line_of(content, "\"name\""), "unity");
+    assembly.signature = Some(format!("{} assembly {}", kind, name));
+    assembly.return_type = json.get("rootNamespace").and_then(|v| v.as_str()).filter(|n| !n.is_empty()).map(|n| n.to_string());
+    assembly.parameters = platforms;
+    assembly.decorators = defines;
+    let mut elements = vec![assembly];
+    for reference in strings(&json, "references") {
+        let line_number = line_of(content, &format!("\"{}\"", reference));
+        elements.push(element(&reference, CodeElementType::Module, path, line_number, "unity"));
+    }
+    elements
+}
+
+/// An Unreal project or plugin descriptor, with the modules it declares, or a module's build rules,
+/// with the modules it depends on
+pub fn analyze_unreal(content: &str, path: &str) -> Vec<CodeElement> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let name = file_name.split('.').next().unwrap_or(file_name);
+    let mut elements = Vec::new();
+
+    if file_name.to_lowercase().ends_with(".build.cs") {
+        let mut module = element(name, CodeElementType::Class, path, line_of(content, "ModuleRules"), "unreal");
+        module.signature = Some(format!("module {}", name));
+        elements.push(module);
+        for dependencies in MODULE_DEPENDENCIES.captures_iter(content) {
+            let visibility = dependencies[1].to_lowercase();
+            let line_number = line_of(content, &dependencies[0]);
+            for dependency in QUOTED.captures_iter(&dependencies[2]) {
+                let mut dependency = element(&dependency[1], CodeElementType::Module, path, line_number, "unreal");
+                dependency.visibility = Some(visibility.clone());
+                elements.push(dependency);
+            }
+        }
+        return elements;
+    }
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{feff}')) else {
+        return elements;
+    };
+    let plugin = file_name.to_lowercase().ends_with(".uplugin");
+    let mut descriptor = element(name, CodeElementType::Class, path, 1, "unreal");
+    descriptor.signature = Some(format!("{} {}", if plugin { "plugin" } else { "project" }, name));
+    descriptor.doc_comment = json.get("Description").and_then(|v| v.as_str()).filter(|d| !d.is_empty()).map(|d| d.to_string());
+    descriptor.return_type = json.get(if plugin { "VersionName" } else { "EngineAssociation" })
+        .and_then(|v| v.as_str()).filter(|v| !v.is_empty()).map(|v| v.to_string());
+    elements.push(descriptor);
+    for module in json.get("Modules").and_then(|v| v.as_array()).into_iter().flatten() {
+        let Some(module_name) = module.get("Name").and_then(|v| v.as_str()) else { continue };
+        let mut declared = element(module_name, CodeElementType::Module, path, line_of(content, &format!("\"{}\"", module_name)), "unreal");
+        declared.signature = Some([module.get("Type"), module.get("LoadingPhase")].into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(" "))
+            .filter(|s| !s.is_empty());
+        elements.push(declared);
+    }
+    elements
+}
+
+/// Unity assemblies usually reference each other by the GUID in the referenced assembly
+/// definition's `.meta` file; rename those references to the assembly they point at
+pub fn resolve_assembly_guids(repo_path: &Path, elements: &mut [CodeElement]) {
+    let guids: HashMap<String, String> = elements.iter()
+        .filter(|e| e.language == "unity" && e.element_type == CodeElementType::Class)
+        .filter_map(|e| {
+            let meta = std::fs::read_to_string(repo_path.join(format!("{}.meta", e.file_path))).ok()?;
+            let guid = META_GUID.captures(&meta)?[1].to_lowercase();
+            Some((guid, e.name.clone()))
+        })
+        .collect();
+    for reference in elements.iter_mut().filter(|e| e.language == "unity" && e.element_type == CodeElementType::Module) {
+        let Some(guid) = reference.name.strip_prefix("GUID:") else { continue };
+        if let Some(name) = guids.get(&guid.to_lowercase()) {
+            reference.name = name.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{CodeAnalyzer, CodeElementType};
+
+    #[test]
+    fn test_reads_unity_assemblies_and_unreal_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("Assets/Scripts/Core")).unwrap();
+        std::fs::create_dir_all(root.join("Assets/Scripts/Editor")).unwrap();
+        std::fs::write(root.join("Assets/Scripts/Core/Game.Core.asmdef"), r#"{
+    "name": "Game.Core",
+    "rootNamespace": "Game",
+    "references": []
+}"#).unwrap();
+        std::fs::write(root.join("Assets/Scripts/Core/Game.Core.asmdef.meta"), "fileFormatVersion: 2\nguid: 4A1B2c3d\n").unwrap();
+        std::fs::write(root.join("Assets/Scripts/Editor/Game.Editor.asmdef"), r#"{
+    "name": "Game.Editor",
+    "references": ["GUID:4a1b2c3d", "Unity.InputSystem"],
+    "includePlatforms": ["Editor"]
+}"#).unwrap();
+        std::fs::create_dir_all(root.join("Source/Shooter")).unwrap();
+        std::fs::write(root.join("Shooter.uproject"), r#"{
+    "EngineAssociation": "5.3",
+    "Modules": [{ "Name": "Shooter", "Type": "Runtime", "LoadingPhase": "Default" }]
+}"#).unwrap();
+        std::fs::write(root.join("Source/Shooter/Shooter.Build.cs"), r#"public class Shooter : ModuleRules
+{
+    public Shooter(ReadOnlyTargetRules Target) : base(Target)
+    {
+        PublicDependencyModuleNames.AddRange(new string[] { "Core", "Engine" });
+        PrivateDependencyModuleNames.Add("EnhancedInput");
+    }
+}"#).unwrap();
+
+        let structure = CodeAnalyzer::new().analyze_repository(root).unwrap();
+        let editor = structure.elements.iter().find(|e| e.name == "Game.Editor").unwrap();
+        assert_eq!(editor.signature.as_deref(), Some("editor assembly Game.Editor"));
+        let shooter = structure.elements.iter()
+            .find(|e| e.name == "Shooter" && e.file_path == "Source/Shooter/Shooter.Build.cs")
+            .unwrap();
+        assert_eq!(shooter.element_type, CodeElementType::Class);
+
+        let name_of = |id: &str| structure.elements.iter().find(|e| e.id == id).map(|e| e.name.as_str()).unwrap();
+        let mut imports: Vec<(&str, &str)> = structure.calls.iter()
+            .filter(|c| c.call_type == "import")
+            .map(|c| (name_of(&c.caller_id), name_of(&c.callee_id)))
+            .collect();
+        imports.sort();
+        assert_eq!(imports, vec![
+            ("Game.Editor", "Game.Core"),
+            ("Game.Editor", "Unity.InputSystem"),
+            ("Shooter", "Core"),
+            ("Shooter", "Engine"),
+            ("Shooter", "EnhancedInput"),
+            ("Shooter", "Shooter"),
+        ]);
+    }
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use crate::analysis::ArchitectureSnapshot;
+use crate::config::GateConfig;
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+
+/// Findings listed in a failing check's detail before it is cut short
+const LISTED: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Passes only when every check passes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateResult {
+    pub passed: bool,
+    pub checks: Vec<GateCheck>,
+}
+
+/// What the gate looks at from one analysis
+pub struct GateInput<'a> {
+    pub vulnerabilities: &'a [SecurityVulnerability],
+    pub current: Option<&'a ArchitectureSnapshot>,
+    /// `None` when there is nothing to compare with, e.g. the first analysis
+    pub baseline: Option<&'a ArchitectureSnapshot>,
+    /// Days since a manifest last changed, from the technical debt score
+    pub dependency_staleness_days: Option<f64>,
+}
+
+fn listed(items: impl Iterator<Item = String>) -> String {
+    let items: Vec<String> = items.collect();
+    let mut text = items.iter().take(LISTED).cloned().collect::<Vec<_>>().join(", ");
+    if items.len() > LISTED {
+        text.push_str(&format!(" and {} more", items.len() - LISTED));
+    }
+    text
+}
+
+fn count_check(name: &str, vulnerabilities: &[SecurityVulnerability], severity: VulnerabilitySeverity, max: usize) -> GateCheck {
+    let found: Vec<&SecurityVulnerability> = vulnerabilities.iter().filter(|v| std::mem::discriminant(&v.severity) == std::mem::discriminant(&severity)).collect();
+    let label = format!("{:?}", severity).to_lowercase();
+    let passed = found.len() <= max;
+    let detail = if passed {
+        format!("{} {} vulnerabilities (at most {} allowed)", found.len(), label, max)
+    } else {
+        format!(
+            "{} {} vulnerabilities, at most {} allowed: {}",
+            found.len(), label, max,
+            listed(found.iter().map(|v| format!("{} in {}", v.vulnerability_type, v.file_path)))
+        )
+    };
+    GateCheck { name: name.to_string(), passed, detail }
+}
+
+/// Checks an analysis against the gate's thresholds; thresholds that are off are left out
+pub fn evaluate_gate(policy: &GateConfig, input: &GateInput) -> GateResult {
+    let mut checks = vec![count_check("critical_vulnerabilities", input.vulnerabilities, VulnerabilitySeverity::Critical, policy.max_critical)];
+    if let Some(max) = policy.max_high {
+        checks.push(count_check("high_vulnerabilities", input.vulnerabilities, VulnerabilitySeverity::High, max));
+    }
+
+    if !policy.allow_new_services {
+        checks.push(match (input.baseline, input.current) {
+            (Some(baseline), Some(current)) => {
+                let added: Vec<&String> = current.services.difference(&baseline.services).collect();
+                GateCheck {
+                    name: "new_services".to_string(),
+                    passed: added.is_empty(),
+                    detail: if added.is_empty() {
+                        format!("No external services added since {}", baseline.taken_at)
+                    } else {
+                        format!("{} external services added since {}: {}", added.len(), baseline.taken_at, listed(added.into_iter().cloned()))
+                    },
+                }
+            }
+            _ => GateCheck {
+                name: "new_services".to_string(),
+                passed: true,
+                detail: "No earlier analysis to compare with".to_string(),
+            },
+        });
+    }
+
+    if let Some(max) = policy.max_dependency_staleness_days {
+        checks.push(match input.dependency_staleness_days {
+            Some(days) => GateCheck {
+                name: "dependency_staleness".to_string(),
+                passed: days <= max as f64,
+                detail: format!("Dependency manifests last updated {:.0} days ago (at most {} allowed)", days, max),
+            },
+            None => GateCheck {
+                name: "dependency_staleness".to_string(),
+                passed: true,
+                detail: "No git history for the dependency manifests".to_string(),
+            },
+        });
+    }
+
+    GateResult {
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(services: &[&str]) -> ArchitectureSnapshot {
+        ArchitectureSnapshot {
+            taken_at: "2024-05-01T00:00:00Z".to_string(),
+            dependencies: Default::default(),
+            services: services.iter().map(|s| s.to_string()).collect(),
+            endpoint_count: 0,
+            code_element_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_gate_thresholds() {
+        let critical = SecurityVulnerability {
+            id: "v1".to_string(),
+            entity_id: "e".to_string(),
+            vulnerability_type: "hardcoded_secret".to_string(),
+            severity: VulnerabilitySeverity::Critical,
+            description: "Secret in source".to_string(),
+            recommendation: "Use a secret store".to_string(),
+            file_path: "src/config.js".to_string(),
+            line_number: Some(3),
+            affected_range: None,
+            fixed_version: None,
+        };
+        let baseline = snapshot(&["Stripe"]);
+        let current = snapshot(&["Stripe", "Twilio"]);
+        let input = GateInput {
+            vulnerabilities: std::slice::from_ref(&critical),
+            current: Some(&current),
+            baseline: Some(&baseline),
+            dependency_staleness_days: Some(400.0),
+        };
+
+        let result = evaluate_gate(&GateConfig::default(), &input);
+        assert!(!result.passed);
+        let failed: Vec<&str> = result.checks.iter().filter(|c| !c.passed).map(|c| c.name.as_str()).collect();
+        assert_eq!(failed, ["critical_vulnerabilities", "new_services", "dependency_staleness"]);
+        assert!(result.checks[1].detail.contains("Twilio"));
+
+        let lenient = GateConfig {
+            max_critical: 1,
+            max_high: Some(0),
+            allow_new_services: true,
+            max_dependency_staleness_days: None,
+        };
+        let result = evaluate_gate(&lenient, &input);
+        assert!(result.passed);
+        assert_eq!(result.checks.len(), 2);
+    }
+}
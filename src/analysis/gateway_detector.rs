@@ -0,0 +1,382 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum GatewayKind {
+    Kong,             // Declarative `kong.yml` (decK) configuration
+    ApolloFederation, // Apollo Gateway `serviceList`/`subgraphs`, Rover supergraph configs, Router overrides
+    AwsApiGateway,    // OpenAPI documents with `x-amazon-apigateway-integration`
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayRoute {
+    pub kind: GatewayKind,
+    pub gateway: String,     // Kong workspace or file, supergraph file, or the OpenAPI `info.title`
+    pub route: String,       // Path prefix, or the subgraph name for federated gateways
+    pub methods: Vec<String>,
+    pub upstream: String,    // Kong service, subgraph, Lambda function or backend host
+    pub upstream_url: Option<String>,
+    pub protocol: String,    // "http", "graphql" or "lambda"
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+/// `serviceList: [{ name: 'accounts', url: 'http://accounts:4001/graphql' }]` and `IntrospectAndCompose` subgraph lists
+static SUBGRAPH_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\{\s*name\s*:\s*['"`]([\w\-.]+)['"`]\s*,\s*url\s*:\s*(?:['"`]([^'"`]+)['"`]|([\w.]+))"#).unwrap()
+});
+static APOLLO_GATEWAY_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bApolloGateway\b|\bIntrospectAndCompose\b|\bRemoteGraphQLDataSource\b").unwrap()
+});
+/// Lambda ARN inside an integration URI: `...:function:orders-api/invocations` or `${OrdersFunction.Arn}`
+static LAMBDA_FUNCTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r":function:([\w\-]+)|\$\{(\w+)\.Arn\}").unwrap()
+});
+
+const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "x-amazon-apigateway-any-method"];
+
+pub struct GatewayDetector;
+
+impl GatewayDetector {
+    pub fn new() -> Self {
+        GatewayDetector
+    }
+
+    pub fn detect_routes(&self, repo_path: &Path) -> Result<Vec<GatewayRoute>> {
+        let mut routes = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let is_yaml = file_name.ends_with(".yaml") || file_name.ends_with(".yml");
+            let is_json = file_name.ends_with(".json");
+            let is_script = [".js", ".ts", ".mjs", ".cjs"].iter().any(|ext| file_name.ends_with(ext));
+            if !(is_yaml || is_json || is_script) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else { continue };
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            if is_script {
+                if APOLLO_GATEWAY_MARKER.is_match(&content) {
+                    routes.extend(self.parse_apollo_gateway(&content, &normalized_path));
+                }
+                continue;
+            }
+
+            // Cheap pre-check before parsing every YAML and JSON file in the repo
+            let kong = content.contains("_format_version") || (content.contains("services") && content.contains("routes") && file_name.contains("kong"));
+            let federation = content.contains("subgraphs") || content.contains("override_subgraph_url");
+            let aws = content.contains("x-amazon-apigateway-integration");
+            if !(kong || federation || aws) {
+                continue;
+            }
+            let document: Value = if is_json {
+                match serde_json::from_str(&content) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                }
+            } else {
+                match serde_yaml::from_str(&content) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                }
+            };
+
+            if kong {
+                routes.extend(self.parse_kong(&document, &content, &normalized_path));
+            }
+            if federation {
+                routes.extend(self.parse_supergraph(&document, &content, &normalized_path));
+            }
+            if aws {
+                routes.extend(self.parse_aws_openapi(&document, &content, &normalized_path));
+            }
+        }
+
+        Ok(routes)
+    }
+
+    /// decK services with their routes; a service without routes is still an upstream the gateway exposes
+    fn parse_kong(&self, document: &Value, content: &str, file_path: &str) -> Vec<GatewayRoute> {
+        let mut routes = Vec::new();
+        let gateway = document["_workspace"].as_str()
+            .map(|w| w.to_string())
+            .unwrap_or_else(|| gateway_name_from_path(file_path));
+        let Some(services) = document["services"].as_array() else { return routes };
+
+        for service in services {
+            let Some(name) = service["name"].as_str().or_else(|| service["host"].as_str()) else { continue };
+            let upstream_url = service["url"].as_str().map(|u| u.to_string()).or_else(|| {
+                let host = service["host"].as_str()?;
+                let protocol = service["protocol"].as_str().unwrap_or("http");
+                let port = service["port"].as_u64().map(|p| format!(":{}", p)).unwrap_or_default();
+                let path = service["path"].as_str().unwrap_or("");
+                Some(format!("{}://{}{}{}", protocol, host, port, path))
+            });
+            let line_number = line_of(content, name);
+            let service_routes = service["routes"].as_array().cloned().unwrap_or_default();
+            if service_routes.is_empty() {
+                routes.push(GatewayRoute {
+                    kind: GatewayKind::Kong,
+                    gateway: gateway.clone(),
+                    route: "/".to_string(),
+                    methods: Vec::new(),
+                    upstream: name.to_string(),
+                    upstream_url: upstream_url.clone(),
+                    protocol: "http".to_string(),
+                    file_path: file_path.to_string(),
+                    line_number,
+                });
+            }
+            for route in &service_routes {
+                let methods = string_list(&route["methods"]);
+                let paths = string_list(&route["paths"]);
+                let paths = if paths.is_empty() { vec!["/".to_string()] } else { paths };
+                for route_path in paths {
+                    routes.push(GatewayRoute {
+                        kind: GatewayKind::Kong,
+                        gateway: gateway.clone(),
+                        // Kong paths starting with `~` are regexes
+                        route: route_path.trim_start_matches('~').to_string(),
+                        methods: methods.clone(),
+                        upstream: name.to_string(),
+                        upstream_url: upstream_url.clone(),
+                        protocol: "http".to_string(),
+                        file_path: file_path.to_string(),
+                        line_number: route["name"].as_str().and_then(|n| line_of(content, n)).or(line_number),
+                    });
+                }
+            }
+        }
+        routes
+    }
+
+    /// Rover `supergraph.yaml` (`subgraphs: {name: {routing_url}}`) and Router `override_subgraph_url`
+    fn parse_supergraph(&self, document: &Value, content: &str, file_path: &str) -> Vec<GatewayRoute> {
+        let subgraphs: Vec<(String, Option<String>)> = match (document["subgraphs"].as_object(), document["override_subgraph_url"].as_object()) {
+            (Some(subgraphs), _) => subgraphs.iter()
+                .map(|(name, config)| (name.clone(), config["routing_url"].as_str().map(|u| u.to_string())))
+                .collect(),
+            (None, Some(overrides)) => overrides.iter()
+                .map(|(name, url)| (name.clone(), url.as_str().map(|u| u.to_string())))
+                .collect(),
+            _ => return Vec::new(),
+        };
+        let gateway = gateway_name_from_path(file_path);
+        subgraphs.into_iter()
+            .map(|(name, url)| GatewayRoute {
+                kind: GatewayKind::ApolloFederation,
+                gateway: gateway.clone(),
+                route: name.clone(),
+                methods: Vec::new(),
+                line_number: line_of(content, &name),
+                upstream: name,
+                upstream_url: url,
+                protocol: "graphql".to_string(),
+                file_path: file_path.to_string(),
+            })
+            .collect()
+    }
+
+    fn parse_apollo_gateway(&self, content: &str, file_path: &str) -> Vec<GatewayRoute> {
+        let gateway = gateway_name_from_path(file_path);
+        SUBGRAPH_ENTRY.captures_iter(content)
+            .map(|caps| {
+                let name = caps[1].to_string();
+                let start = caps.get(0).unwrap().start();
+                GatewayRoute {
+                    kind: GatewayKind::ApolloFederation,
+                    gateway: gateway.clone(),
+                    route: name.clone(),
+                    methods: Vec::new(),
+                    upstream: name,
+                    // An identifier (`process.env.ACCOUNTS_URL`) is kept as written
+                    upstream_url: caps.get(2).or(caps.get(3)).map(|u| u.as_str().to_string()),
+                    protocol: "graphql".to_string(),
+                    file_path: file_path.to_string(),
+                    line_number: Some(content[..start].lines().count().max(1)),
+                }
+            })
+            .collect()
+    }
+
+    /// One route per path and method with an integration: HTTP proxies name the backend host,
+    /// Lambda integrations the function
+    fn parse_aws_openapi(&self, document: &Value, content: &str, file_path: &str) -> Vec<GatewayRoute> {
+        let mut routes = Vec::new();
+        let gateway = document["info"]["title"].as_str()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| gateway_name_from_path(file_path));
+        let Some(paths) = document["paths"].as_object() else { return routes };
+
+        for (route_path, operations) in paths {
+            let Some(operations) = operations.as_object() else { continue };
+            for (method, operation) in operations {
+                if !HTTP_METHODS.contains(&method.as_str()) {
+                    continue;
+                }
+                let integration = &operation["x-amazon-apigateway-integration"];
+                if integration.is_null() {
+                    continue;
+                }
+                let integration_type = integration["type"].as_str().unwrap_or("").to_lowercase();
+                if integration_type == "mock" {
+                    continue;
+                }
+                // CloudFormation templates wrap the URI in `Fn::Sub`
+                let uri = integration["uri"].as_str()
+                    .or_else(|| integration["uri"]["Fn::Sub"].as_str())
+                    .map(|u| u.to_string());
+                let (upstream, protocol) = match uri.as_deref() {
+                    Some(uri) if uri.contains(":lambda:") || (integration_type.starts_with("aws") && uri.contains("function")) => {
+                        let function = LAMBDA_FUNCTION.captures(uri)
+                            .and_then(|c| c.get(1).or(c.get(2)))
+                            .map(|f| f.as_str().to_string())
+                            .unwrap_or_else(|| uri.to_string());
+                        (function, "lambda")
+                    }
+                    Some(uri) => (url_host(uri).unwrap_or(uri).to_string(), "http"),
+                    None => continue,
+                };
+                let methods = match method.as_str() {
+                    "x-amazon-apigateway-any-method" => Vec::new(),
+                    m => vec![m.to_uppercase()],
+                };
+                routes.push(GatewayRoute {
+                    kind: GatewayKind::AwsApiGateway,
+                    gateway: gateway.clone(),
+                    route: route_path.clone(),
+                    methods,
+                    upstream,
+                    upstream_url: uri,
+                    protocol: protocol.to_string(),
+                    file_path: file_path.to_string(),
+                    line_number: line_of(content, route_path),
+                });
+            }
+        }
+        routes
+    }
+}
+
+/// `gateway/kong.yml` → `gateway`; top-level files are named after themselves
+fn gateway_name_from_path(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    path.parent()
+        .and_then(|p| p.file_name())
+        .or_else(|| path.file_stem())
+        .and_then(|n| n.to_str())
+        .unwrap_or(file_path)
+        .to_string()
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value.as_array()
+        .map(|items| items.iter().filter_map(|i| i.as_str()).map(|s| s.to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://")?.1;
+    let host = rest.split(['/', ':', '?']).next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+fn line_of(content: &str, needle: &str) -> Option<usize> {
+    content.lines().position(|l| l.contains(needle)).map(|idx| idx + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_kong_federation_and_aws_gateway_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("gateway")).unwrap();
+        std::fs::write(dir.path().join("gateway/kong.yml"), r#"_format_version: "3.0"
+services:
+  - name: orders
+    url: http://orders.internal:8080
+    routes:
+      - name: orders-route
+        paths: [/api/orders]
+        methods: [GET, POST]
+"#).unwrap();
+        std::fs::write(dir.path().join("supergraph.yaml"), r#"federation_version: 2
+subgraphs:
+  accounts:
+    routing_url: http://accounts:4001/graphql
+    schema:
+      file: ./accounts.graphql
+"#).unwrap();
+        std::fs::write(dir.path().join("gateway.ts"), r#"
+const gateway = new ApolloGateway({
+  supergraphSdl: new IntrospectAndCompose({
+    subgraphs: [
+      { name: 'reviews', url: 'http://reviews:4002/graphql' },
+    ],
+  }),
+});
+"#).unwrap();
+        std::fs::write(dir.path().join("api.yaml"), r#"openapi: 3.0.1
+info:
+  title: public-api
+paths:
+  /users/{id}:
+    get:
+      x-amazon-apigateway-integration:
+        type: http_proxy
+        httpMethod: GET
+        uri: https://users.acme.io/users/{id}
+  /reports:
+    post:
+      x-amazon-apigateway-integration:
+        type: aws_proxy
+        httpMethod: POST
+        uri: arn:aws:apigateway:us-east-1:lambda:path/2015-03-31/functions/arn:aws:lambda:us-east-1:123456789012:function:report-builder/invocations
+"#).unwrap();
+
+        let routes = GatewayDetector::new().detect_routes(dir.path()).unwrap();
+        let kong = routes.iter().find(|r| r.kind == GatewayKind::Kong).unwrap();
+        assert_eq!(kong.gateway, "gateway");
+        assert_eq!(kong.route, "/api/orders");
+        assert_eq!(kong.methods, vec!["GET", "POST"]);
+        assert_eq!(kong.upstream_url.as_deref(), Some("http://orders.internal:8080"));
+
+        let subgraphs: Vec<&str> = routes.iter()
+            .filter(|r| r.kind == GatewayKind::ApolloFederation)
+            .map(|r| r.upstream.as_str())
+            .collect();
+        assert!(subgraphs.contains(&"accounts"));
+        assert!(subgraphs.contains(&"reviews"));
+
+        let users = routes.iter().find(|r| r.route == "/users/{id}").unwrap();
+        assert_eq!(users.gateway, "public-api");
+        assert_eq!(users.upstream, "users.acme.io");
+        assert_eq!(users.protocol, "http");
+        let reports = routes.iter().find(|r| r.route == "/reports").unwrap();
+        assert_eq!(reports.upstream, "report-builder");
+        assert_eq!(reports.protocol, "lambda");
+    }
+}
@@ -0,0 +1,137 @@
+use git2::{BlameOptions, Oid, Repository};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Author and commit time of one line, as attributed by `git blame`
+#[derive(Debug, Clone)]
+pub struct BlameLine {
+    pub author: Option<String>,
+    pub timestamp: i64, // Unix seconds of the commit that last touched the line
+}
+
+/// Thin wrapper around git2 blame for a cloned repository; every lookup degrades to `None`
+/// when the path is not a git checkout or the file is untracked
+pub struct GitBlame {
+    repo: Option<Repository>,
+    cache: HashMap<String, Option<Vec<BlameLine>>>,
+    max_commits: Option<usize>,
+    /// Blame stops here; older lines are attributed to this commit
+    oldest_commit: Option<Oid>,
+}
+
+impl GitBlame {
+    pub fn open(repo_path: &Path) -> Self {
+        GitBlame {
+            repo: Repository::open(repo_path).ok(),
+            cache: HashMap::new(),
+            max_commits: None,
+            oldest_commit: None,
+        }
+    }
+
+    /// Only look at the last `max_commits` commits reachable from HEAD; 0 behaves like a
+    /// repository without history
+    pub fn open_with_depth(repo_path: &Path, max_commits: Option<usize>) -> Self {
+        let mut blame = Self::open(repo_path);
+        match max_commits {
+            Some(0) => blame.repo = None,
+            Some(depth) => {
+                blame.max_commits = Some(depth);
+                blame.oldest_commit = blame.repo.as_ref().and_then(|repo| {
+                    let mut revwalk = repo.revwalk().ok()?;
+                    revwalk.push_head().ok()?;
+                    revwalk.filter_map(|o| o.ok()).nth(depth - 1)
+                });
+            }
+            None => {}
+        }
+        blame
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.repo.is_some()
+    }
+
+    /// Blame for every line of a repository-relative file, indexed from line 1 at position 0
+    pub fn lines(&mut self, file_path: &str) -> Option<&[BlameLine]> {
+        if !self.cache.contains_key(file_path) {
+            let lines = self.blame(file_path);
+            self.cache.insert(file_path.to_string(), lines);
+        }
+        self.cache.get(file_path).and_then(|l| l.as_deref())
+    }
+
+    pub fn line(&mut self, file_path: &str, line_number: usize) -> Option<&BlameLine> {
+        self.lines(file_path)?.get(line_number.checked_sub(1)?)
+    }
+
+    /// Time of the most recent commit touching any line of the file
+    pub fn last_modified(&mut self, file_path: &str) -> Option<i64> {
+        self.lines(file_path)?.iter().map(|l| l.timestamp).max()
+    }
+
+    /// Most recent commit time per repository-relative path, from a walk over the last `max_commits`
+    /// commits reachable from HEAD. Much cheaper than blaming every file when only change times matter.
+    pub fn recent_changes(&self, max_commits: usize) -> HashMap<String, i64> {
+        let mut changes = HashMap::new();
+        let repo = match self.repo.as_ref() {
+            Some(r) => r,
+            None => return changes,
+        };
+        let mut revwalk = match repo.revwalk() {
+            Ok(w) => w,
+            Err(_) => return changes,
+        };
+        if revwalk.push_head().is_err() {
+            return changes;
+        }
+
+        for oid in revwalk.filter_map(|o| o.ok()).take(self.max_commits.map_or(max_commits, |m| m.min(max_commits))) {
+            let commit = match repo.find_commit(oid) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let tree = commit.tree().ok();
+            let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+            let diff = match repo.diff_tree_to_tree(parent_tree.as_ref(), tree.as_ref(), None) {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+            // The walk runs newest first, so the first time a path is seen is its latest change
+            for delta in diff.deltas() {
+                if let Some(path) = delta.new_file().path().and_then(|p| p.to_str()) {
+                    changes.entry(path.to_string()).or_insert_with(|| commit.time().seconds());
+                }
+            }
+        }
+        changes
+    }
+
+    fn blame(&self, file_path: &str) -> Option<Vec<BlameLine>> {
+        let repo = self.repo.as_ref()?;
+        let mut options = BlameOptions::new();
+        if let Some(oldest) = self.oldest_commit {
+            options.oldest_commit(oldest);
+        }
+        let blame = repo.blame_file(Path::new(file_path), Some(&mut options)).ok()?;
+
+        let mut lines = Vec::new();
+        for hunk in blame.iter() {
+            let signature = hunk.final_signature();
+            // Uncommitted lines have a zero commit id; treat them as written now
+            let timestamp = if hunk.final_commit_id().is_zero() {
+                chrono::Utc::now().timestamp()
+            } else {
+                signature.when().seconds()
+            };
+            let line = BlameLine {
+                author: signature.name().map(|n| n.to_string()),
+                timestamp,
+            };
+            for _ in 0..hunk.lines_in_hunk() {
+                lines.push(line.clone());
+            }
+        }
+        Some(lines)
+    }
+}
@@ -0,0 +1,632 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GraphQLOperationType {
+    Query,
+    Mutation,
+    Subscription,
+    Fragment,
+}
+
+/// A type declared in SDL found in the repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLSchemaType {
+    pub name: String,
+    pub kind: String, // type, interface, input, enum, union, scalar
+    pub file_path: String,
+    pub fields: HashMap<String, String>, // Field name -> named return type (list/non-null wrappers removed)
+}
+
+/// A query, mutation, subscription or fragment embedded in client code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLOperation {
+    pub name: Option<String>,
+    pub operation_type: GraphQLOperationType,
+    pub file_path: String,
+    pub line_number: usize,
+    pub root_fields: Vec<String>, // Top-level fields selected (e.g. "user", "createOrder")
+    pub fields_used: Vec<String>, // "Type.field" pairs, only when the schema is known
+    pub types_used: Vec<String>, // Schema types the selection touches, only when the schema is known
+    pub fragment_spreads: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphQLUsage {
+    pub schema_types: Vec<GraphQLSchemaType>,
+    pub operations: Vec<GraphQLOperation>,
+}
+
+pub struct GraphQLClientDetector;
+
+impl GraphQLClientDetector {
+    pub fn new() -> Self {
+        GraphQLClientDetector
+    }
+
+    /// Find SDL and client operations, then resolve each operation's selections against the schema
+    pub fn detect(&self, repo_path: &Path) -> Result<GraphQLUsage> {
+        let template = Regex::new(r"(?s)\b(?:gql|graphql)\s*(?:\(\s*)?`([^`]*)`")?;
+        let python_string = Regex::new(r#"(?s)\bgql\(\s*(?:r|f)?"""(.*?)""""#)?;
+
+        let mut schema_types: Vec<GraphQLSchemaType> = Vec::new();
+        // (document, file_path, line offset)
+        let mut documents: Vec<(String, String, usize)> = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            let is_graphql_file = file_name.ends_with(".graphql") || file_name.ends_with(".gql") || file_name.ends_with(".graphqls");
+            let language = utils::detect_language(path);
+            if !is_graphql_file && !matches!(language.as_deref(), Some("javascript") | Some("typescript") | Some("python")) {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if is_graphql_file {
+                schema_types.extend(parse_sdl(&content, &normalized_path));
+                documents.push((content, normalized_path, 0));
+                continue;
+            }
+
+            if utils::is_minified_or_compiled(&content, &normalized_path) {
+                continue;
+            }
+            for pattern in [&template, &python_string] {
+                for cap in pattern.captures_iter(&content) {
+                    let body = cap.get(1).unwrap();
+                    let line_offset = content[..body.start()].matches('\n').count();
+                    documents.push((body.as_str().to_string(), normalized_path.clone(), line_offset));
+                }
+            }
+        }
+
+        let schema = Schema::new(&schema_types);
+        let mut operations = Vec::new();
+        for (document, file_path, line_offset) in &documents {
+            operations.extend(parse_operations(document, file_path, *line_offset, &schema));
+        }
+
+        Ok(GraphQLUsage { schema_types, operations })
+    }
+}
+
+/// Merged view of the SDL used to resolve selections
+struct Schema {
+    types: HashMap<String, HashMap<String, String>>,
+    query_root: String,
+    mutation_root: String,
+    subscription_root: String,
+}
+
+impl Schema {
+    fn new(schema_types: &[GraphQLSchemaType]) -> Self {
+        let mut types: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut roots: HashMap<String, String> = HashMap::new();
+        for schema_type in schema_types {
+            if schema_type.kind == "schema" {
+                roots.extend(schema_type.fields.clone());
+                continue;
+            }
+            types.entry(schema_type.name.clone())
+                .or_default()
+                .extend(schema_type.fields.clone());
+        }
+        Schema {
+            types,
+            query_root: roots.get("query").cloned().unwrap_or_else(|| "Query".to_string()),
+            mutation_root: roots.get("mutation").cloned().unwrap_or_else(|| "Mutation".to_string()),
+            subscription_root: roots.get("subscription").cloned().unwrap_or_else(|| "Subscription".to_string()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    fn field_type(&self, type_name: &str, field: &str) -> Option<&String> {
+        self.types.get(type_name).and_then(|fields| fields.get(field))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Punct(char),
+    Spread,
+}
+
+/// Tokenize GraphQL, dropping comments, strings and `${...}` template interpolations
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '"' {
+            // Block strings (descriptions) and regular strings
+            let block = chars.get(i + 1) == Some(&'"') && chars.get(i + 2) == Some(&'"');
+            i += if block { 3 } else { 1 };
+            while i < chars.len() {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                if block && chars[i] == '"' && chars.get(i + 1) == Some(&'"') && chars.get(i + 2) == Some(&'"') {
+                    i += 3;
+                    break;
+                }
+                if !block && chars[i] == '\\' {
+                    i += 2;
+                    continue;
+                }
+                if !block && chars[i] == '"' {
+                    i += 1;
+                    break;
+                }
+                i += 1;
+            }
+        } else if c == '$' && chars.get(i + 1) == Some(&'{') {
+            while i < chars.len() && chars[i] != '}' {
+                i += 1;
+            }
+            i += 1;
+        } else if c == '.' && chars.get(i + 1) == Some(&'.') && chars.get(i + 2) == Some(&'.') {
+            tokens.push((Token::Spread, line));
+            i += 3;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Name(chars[start..i].iter().collect()), line));
+        } else {
+            tokens.push((Token::Punct(c), line));
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+fn parse_sdl(source: &str, file_path: &str) -> Vec<GraphQLSchemaType> {
+    let tokens = tokenize(source);
+    let mut types = Vec::new();
+    let mut i = 0;
+
+    let name_at = |idx: usize| match tokens.get(idx) {
+        Some((Token::Name(n), _)) => Some(n.clone()),
+        _ => None,
+    };
+
+    while i < tokens.len() {
+        let keyword = match name_at(i) {
+            Some(k) => k,
+            None => {
+                i += 1;
+                continue;
+            }
+        };
+        // `extend type X` adds fields to X; Schema::new merges them
+        let kind_idx = if keyword == "extend" { i + 1 } else { i };
+        let kind = name_at(kind_idx).unwrap_or_default();
+
+        match kind.as_str() {
+            "type" | "interface" | "input" | "enum" => {
+                let name = match name_at(kind_idx + 1) {
+                    Some(n) => n,
+                    None => {
+                        i += 1;
+                        continue;
+                    }
+                };
+                // Skip implements/directives up to the body
+                let mut j = kind_idx + 2;
+                while j < tokens.len() && tokens[j].0 != Token::Punct('{') {
+                    // A following definition means this one has no body
+                    if tokens[j].0 == Token::Punct('}') {
+                        break;
+                    }
+                    j += 1;
+                }
+                let (fields, end) = if tokens.get(j).map(|t| &t.0) == Some(&Token::Punct('{')) {
+                    parse_fields(&tokens, j + 1, kind == "enum")
+                } else {
+                    (HashMap::new(), j)
+                };
+                types.push(GraphQLSchemaType {
+                    name,
+                    kind,
+                    file_path: file_path.to_string(),
+                    fields,
+                });
+                i = end;
+            }
+            "union" | "scalar" => {
+                if let Some(name) = name_at(kind_idx + 1) {
+                    types.push(GraphQLSchemaType {
+                        name,
+                        kind,
+                        file_path: file_path.to_string(),
+                        fields: HashMap::new(),
+                    });
+                }
+                i = kind_idx + 2;
+            }
+            "schema" if tokens.get(kind_idx + 1).map(|t| &t.0) == Some(&Token::Punct('{')) => {
+                let (fields, end) = parse_fields(&tokens, kind_idx + 2, false);
+                types.push(GraphQLSchemaType {
+                    name: "schema".to_string(),
+                    kind: "schema".to_string(),
+                    file_path: file_path.to_string(),
+                    fields,
+                });
+                i = end;
+            }
+            // Executable definitions are handled by parse_operations
+            "query" | "mutation" | "subscription" | "fragment" => {
+                i = skip_block(&tokens, kind_idx);
+            }
+            _ => i += 1,
+        }
+    }
+
+    types
+}
+
+/// Parse `name(args): Type` field definitions up to the closing brace
+fn parse_fields(tokens: &[(Token, usize)], start: usize, is_enum: bool) -> (HashMap<String, String>, usize) {
+    let mut fields = HashMap::new();
+    let mut i = start;
+    while i < tokens.len() {
+        match &tokens[i].0 {
+            Token::Punct('}') => return (fields, i + 1),
+            Token::Punct('@') => i += 2, // directive name
+            Token::Punct('(') => {
+                let mut depth = 0;
+                while i < tokens.len() {
+                    match tokens[i].0 {
+                        Token::Punct('(') => depth += 1,
+                        Token::Punct(')') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            Token::Name(name) if is_enum => {
+                fields.insert(name.clone(), String::new());
+                i += 1;
+            }
+            Token::Name(name) => {
+                // Find the colon after optional arguments, then the first name is the named type
+                let mut j = i + 1;
+                if tokens.get(j).map(|t| &t.0) == Some(&Token::Punct('(')) {
+                    let mut depth = 0;
+                    while j < tokens.len() {
+                        match tokens[j].0 {
+                            Token::Punct('(') => depth += 1,
+                            Token::Punct(')') => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                        j += 1;
+                    }
+                    j += 1;
+                }
+                if tokens.get(j).map(|t| &t.0) == Some(&Token::Punct(':')) {
+                    let mut k = j + 1;
+                    while let Some((Token::Punct('[') | Token::Punct('!'), _)) = tokens.get(k) {
+                        k += 1;
+                    }
+                    if let Some((Token::Name(type_name), _)) = tokens.get(k) {
+                        fields.insert(name.clone(), type_name.clone());
+                    }
+                    i = k + 1;
+                } else {
+                    i = j;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    (fields, i)
+}
+
+/// Skip a definition header and its selection set
+fn skip_block(tokens: &[(Token, usize)], start: usize) -> usize {
+    let mut i = start;
+    while i < tokens.len() && tokens[i].0 != Token::Punct('{') {
+        i += 1;
+    }
+    let mut depth = 0;
+    while i < tokens.len() {
+        match tokens[i].0 {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    i
+}
+
+fn parse_operations(source: &str, file_path: &str, line_offset: usize, schema: &Schema) -> Vec<GraphQLOperation> {
+    let tokens = tokenize(source);
+    let mut operations = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let (operation_type, header_start) = match &tokens[i].0 {
+            Token::Name(k) if k == "query" => (GraphQLOperationType::Query, i + 1),
+            Token::Name(k) if k == "mutation" => (GraphQLOperationType::Mutation, i + 1),
+            Token::Name(k) if k == "subscription" => (GraphQLOperationType::Subscription, i + 1),
+            Token::Name(k) if k == "fragment" => (GraphQLOperationType::Fragment, i + 1),
+            // Anonymous query shorthand, only at the top level of a document
+            Token::Punct('{') if i == 0 => (GraphQLOperationType::Query, i),
+            Token::Name(k) if matches!(k.as_str(), "type" | "interface" | "input" | "enum" | "schema" | "extend") => {
+                // SDL definitions are not client operations
+                i = skip_block(&tokens, i);
+                continue;
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let line_number = line_offset + tokens[i].1 + 1;
+
+        let name = match tokens.get(header_start) {
+            Some((Token::Name(n), _)) if header_start > i => Some(n.clone()),
+            _ => None,
+        };
+        let root_type = match operation_type {
+            GraphQLOperationType::Query => schema.query_root.clone(),
+            GraphQLOperationType::Mutation => schema.mutation_root.clone(),
+            GraphQLOperationType::Subscription => schema.subscription_root.clone(),
+            GraphQLOperationType::Fragment => {
+                // fragment Name on Type
+                match tokens.get(header_start + 2) {
+                    Some((Token::Name(t), _)) => t.clone(),
+                    _ => String::new(),
+                }
+            }
+        };
+
+        // Find the selection set, skipping variable definitions and directives
+        let mut j = header_start;
+        let mut paren_depth = 0;
+        while j < tokens.len() {
+            match tokens[j].0 {
+                Token::Punct('(') => paren_depth += 1,
+                Token::Punct(')') => paren_depth -= 1,
+                Token::Punct('{') if paren_depth == 0 => break,
+                _ => {}
+            }
+            j += 1;
+        }
+        if j >= tokens.len() {
+            break;
+        }
+
+        let mut operation = GraphQLOperation {
+            name,
+            operation_type,
+            file_path: file_path.to_string(),
+            line_number,
+            root_fields: Vec::new(),
+            fields_used: Vec::new(),
+            types_used: Vec::new(),
+            fragment_spreads: Vec::new(),
+        };
+        i = walk_selection_set(&tokens, j, &root_type, schema, &mut operation);
+        operations.push(operation);
+    }
+
+    operations
+}
+
+/// Walk a selection set, tracking the schema type at each depth
+fn walk_selection_set(tokens: &[(Token, usize)], start: usize, root_type: &str, schema: &Schema, operation: &mut GraphQLOperation) -> usize {
+    // Stack of current parent types; None when the type can't be resolved
+    let mut stack: Vec<Option<String>> = Vec::new();
+    let mut pending: Option<String> = Some(root_type.to_string()).filter(|t| !t.is_empty());
+    let mut i = start;
+
+    let add_type = |operation: &mut GraphQLOperation, type_name: &str| {
+        if !schema.is_empty() && schema.types.contains_key(type_name) && !operation.types_used.iter().any(|t| t == type_name) {
+            operation.types_used.push(type_name.to_string());
+        }
+    };
+
+    while i < tokens.len() {
+        match &tokens[i].0 {
+            Token::Punct('{') => {
+                if let Some(type_name) = &pending {
+                    add_type(operation, type_name);
+                }
+                stack.push(pending.take());
+                i += 1;
+            }
+            Token::Punct('}') => {
+                stack.pop();
+                i += 1;
+                if stack.is_empty() {
+                    return i;
+                }
+            }
+            Token::Punct('(') => {
+                let mut depth = 0;
+                while i < tokens.len() {
+                    match tokens[i].0 {
+                        Token::Punct('(') => depth += 1,
+                        Token::Punct(')') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            Token::Punct('@') => i += 2,
+            Token::Spread => {
+                match (tokens.get(i + 1).map(|t| &t.0), tokens.get(i + 2).map(|t| &t.0)) {
+                    // Inline fragment: ... on Type { }
+                    (Some(Token::Name(on)), Some(Token::Name(type_name))) if on == "on" => {
+                        pending = Some(type_name.clone());
+                        i += 3;
+                    }
+                    (Some(Token::Name(fragment)), _) => {
+                        if !operation.fragment_spreads.contains(fragment) {
+                            operation.fragment_spreads.push(fragment.clone());
+                        }
+                        i += 2;
+                    }
+                    _ => i += 1,
+                }
+            }
+            Token::Name(name) => {
+                // alias: field
+                let field = if tokens.get(i + 1).map(|t| &t.0) == Some(&Token::Punct(':')) {
+                    match tokens.get(i + 2) {
+                        Some((Token::Name(f), _)) => {
+                            i += 2;
+                            f.clone()
+                        }
+                        _ => name.clone(),
+                    }
+                } else {
+                    name.clone()
+                };
+                i += 1;
+
+                if stack.len() == 1 && !operation.root_fields.contains(&field) && field != "__typename" {
+                    operation.root_fields.push(field.clone());
+                }
+                let parent = stack.last().cloned().flatten();
+                pending = parent.as_ref().and_then(|p| {
+                    let field_type = schema.field_type(p, &field)?;
+                    let entry = format!("{}.{}", p, field);
+                    if !operation.fields_used.contains(&entry) {
+                        operation.fields_used.push(entry);
+                    }
+                    Some(field_type.clone())
+                });
+                if let Some(type_name) = &pending {
+                    // Leaf fields still reference their type (enums, custom scalars)
+                    add_type(operation, type_name);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolves_client_operations_against_sdl() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("schema.graphql"), r#"
+"""Root query"""
+type Query {
+  user(id: ID!): User
+  orders(first: Int = 10): [Order!]!
+}
+type Mutation { createOrder(input: OrderInput!): Order }
+type User { id: ID! email: String orders: [Order!]! }
+type Order { id: ID! status: OrderStatus total: Float }
+enum OrderStatus { PENDING SHIPPED }
+input OrderInput { total: Float }
+"#).unwrap();
+        fs::write(temp_dir.path().join("api.ts"), r#"import { gql } from "@apollo/client";
+
+export const GET_USER = gql`
+  query GetUser($id: ID!) {
+    user(id: $id) {
+      email
+      orders { ...OrderFields }
+    }
+  }
+  ${ORDER_FIELDS}
+`;
+
+export const ORDER_FIELDS = gql`
+  fragment OrderFields on Order { id status }
+`;
+"#).unwrap();
+
+        let usage = GraphQLClientDetector::new().detect(temp_dir.path()).unwrap();
+        assert_eq!(usage.schema_types.len(), 6);
+
+        let get_user = usage.operations.iter().find(|o| o.name.as_deref() == Some("GetUser")).unwrap();
+        assert_eq!(get_user.operation_type, GraphQLOperationType::Query);
+        assert_eq!(get_user.root_fields, vec!["user".to_string()]);
+        assert!(get_user.fields_used.contains(&"User.email".to_string()));
+        assert!(get_user.types_used.contains(&"Order".to_string()));
+        assert_eq!(get_user.fragment_spreads, vec!["OrderFields".to_string()]);
+        assert_eq!(get_user.line_number, 4);
+
+        let fragment = usage.operations.iter().find(|o| o.name.as_deref() == Some("OrderFields")).unwrap();
+        assert!(fragment.types_used.contains(&"OrderStatus".to_string()));
+    }
+}
@@ -0,0 +1,668 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+/// Workload kinds that run containers
+const WORKLOAD_KINDS: &[&str] = &["Deployment", "StatefulSet", "DaemonSet", "ReplicaSet", "Job", "CronJob", "Pod"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubernetesResource {
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub labels: HashMap<String, String>, // Pod template labels for workloads, metadata labels otherwise
+    pub selector: HashMap<String, String>, // Service selector
+    pub images: Vec<String>, // Container images run by a workload
+    pub ports: Vec<u16>, // Container ports (workloads) or service ports (services)
+    pub hosts: Vec<String>, // Ingress hosts
+    pub backend_services: Vec<String>, // Services an ingress routes to
+    pub config_refs: Vec<String>, // ConfigMaps/Secrets referenced by a workload, e.g. "ConfigMap/app-config"
+    pub built_images: Vec<String>, // Subset of `images` that are built by this repository
+}
+
+impl KubernetesResource {
+    pub fn is_workload(&self) -> bool {
+        WORKLOAD_KINDS.contains(&self.kind.as_str())
+    }
+
+    /// Namespace used for matching ("default" when not set)
+    pub fn effective_namespace(&self) -> &str {
+        self.namespace.as_deref().unwrap_or("default")
+    }
+}
+
+/// A container image built from this repository (Dockerfile, compose build, or build command)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuiltImage {
+    pub image: String, // Image repository without tag, e.g. "ghcr.io/acme/api"
+    pub dockerfile: Option<String>,
+    pub file_path: String, // Where the build was declared
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyLink {
+    pub source: String, // "Kind/name"
+    pub target: String, // "Kind/name" or image reference
+    pub link_type: String, // routes_to, selects, uses_config, runs_image
+}
+
+/// A single ingress host path resolved down to the workloads and images serving it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyRoute {
+    pub host: Option<String>,
+    pub ingress: String,
+    pub service: String,
+    pub workloads: Vec<String>,
+    pub images: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubernetesTopology {
+    pub resources: Vec<KubernetesResource>,
+    pub links: Vec<TopologyLink>,
+    pub routes: Vec<TopologyRoute>,
+}
+
+impl KubernetesTopology {
+    /// Build runtime topology (ingress host -> service -> workload -> image) from resources
+    pub fn from_resources(resources: Vec<KubernetesResource>) -> Self {
+        let mut links = Vec::new();
+        let mut routes = Vec::new();
+
+        let workloads: Vec<&KubernetesResource> = resources.iter().filter(|r| r.is_workload()).collect();
+        let services: Vec<&KubernetesResource> = resources.iter().filter(|r| r.kind == "Service").collect();
+
+        // Service -> workload via label selector
+        let mut service_workloads: HashMap<(String, String), Vec<&KubernetesResource>> = HashMap::new();
+        for service in &services {
+            if service.selector.is_empty() {
+                continue;
+            }
+            for workload in &workloads {
+                if workload.effective_namespace() != service.effective_namespace() {
+                    continue;
+                }
+                let matches = service.selector.iter()
+                    .all(|(k, v)| workload.labels.get(k) == Some(v));
+                if matches {
+                    links.push(TopologyLink {
+                        source: format!("Service/{}", service.name),
+                        target: format!("{}/{}", workload.kind, workload.name),
+                        link_type: "selects".to_string(),
+                    });
+                    service_workloads
+                        .entry((service.effective_namespace().to_string(), service.name.clone()))
+                        .or_default()
+                        .push(workload);
+                }
+            }
+        }
+
+        // Workload -> image and workload -> config
+        for workload in &workloads {
+            for image in &workload.images {
+                links.push(TopologyLink {
+                    source: format!("{}/{}", workload.kind, workload.name),
+                    target: image.clone(),
+                    link_type: "runs_image".to_string(),
+                });
+            }
+            for config in &workload.config_refs {
+                links.push(TopologyLink {
+                    source: format!("{}/{}", workload.kind, workload.name),
+                    target: config.clone(),
+                    link_type: "uses_config".to_string(),
+                });
+            }
+        }
+
+        // Ingress -> service, and resolved routes
+        for ingress in resources.iter().filter(|r| r.kind == "Ingress") {
+            for backend in &ingress.backend_services {
+                links.push(TopologyLink {
+                    source: format!("Ingress/{}", ingress.name),
+                    target: format!("Service/{}", backend),
+                    link_type: "routes_to".to_string(),
+                });
+
+                let key = (ingress.effective_namespace().to_string(), backend.clone());
+                let backing: Vec<&KubernetesResource> = service_workloads.get(&key).cloned().unwrap_or_default();
+                let mut images: Vec<String> = backing.iter().flat_map(|w| w.images.clone()).collect();
+                images.sort();
+                images.dedup();
+                let workload_names: Vec<String> = backing.iter()
+                    .map(|w| format!("{}/{}", w.kind, w.name))
+                    .collect();
+
+                let hosts: Vec<Option<String>> = if ingress.hosts.is_empty() {
+                    vec![None]
+                } else {
+                    ingress.hosts.iter().cloned().map(Some).collect()
+                };
+                for host in hosts {
+                    routes.push(TopologyRoute {
+                        host,
+                        ingress: ingress.name.clone(),
+                        service: backend.clone(),
+                        workloads: workload_names.clone(),
+                        images: images.clone(),
+                    });
+                }
+            }
+        }
+
+        KubernetesTopology { resources, links, routes }
+    }
+}
+
+pub struct KubernetesDetector;
+
+impl KubernetesDetector {
+    pub fn new() -> Self {
+        KubernetesDetector
+    }
+
+    /// Detect Kubernetes resources in a repository and mark images built by the repository
+    pub fn detect_resources(&self, repo_path: &Path) -> Result<Vec<KubernetesResource>> {
+        let mut resources = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+
+            if !(file_name.ends_with(".yaml") || file_name.ends_with(".yml")) {
+                continue;
+            }
+
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            if let Ok(content) = std::fs::read_to_string(path) {
+                // Cheap pre-check before parsing every YAML file in the repo
+                if !content.contains("apiVersion") || !content.contains("kind") {
+                    continue;
+                }
+                resources.extend(self.parse_manifest(&content, &normalized_path));
+            }
+        }
+
+        let built_images = self.detect_built_images(repo_path)?;
+        if !built_images.is_empty() {
+            for resource in resources.iter_mut().filter(|r| WORKLOAD_KINDS.contains(&r.kind.as_str())) {
+                resource.built_images = resource.images.iter()
+                    .filter(|image| built_images.iter().any(|b| image_matches(image, &b.image)))
+                    .cloned()
+                    .collect();
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// Parse a (possibly multi-document) manifest file
+    pub fn parse_manifest(&self, content: &str, file_path: &str) -> Vec<KubernetesResource> {
+        let mut resources = Vec::new();
+
+        // Split on document separators manually so we can keep line numbers
+        let mut documents: Vec<(usize, String)> = Vec::new();
+        let mut current = String::new();
+        let mut start_line = 0;
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim_end() == "---" || line.starts_with("--- ") {
+                if !current.trim().is_empty() {
+                    documents.push((start_line, std::mem::take(&mut current)));
+                }
+                current.clear();
+                start_line = idx + 1;
+                continue;
+            }
+            current.push_str(line);
+            current.push('\n');
+        }
+        if !current.trim().is_empty() {
+            documents.push((start_line, current));
+        }
+
+        for (doc_start, doc) in documents {
+            // Helm templates and other templated YAML won't parse - skip them
+            let value: Value = match serde_yaml::from_str(&doc) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let kind_line = doc.lines()
+                .position(|l| l.starts_with("kind:"))
+                .map(|offset| doc_start + offset + 1);
+
+            if str_at(&value, &["kind"]).as_deref() == Some("List") {
+                if let Some(items) = value.get("items").and_then(|v| v.as_sequence()) {
+                    for item in items {
+                        if let Some(resource) = self.parse_resource(item, file_path, kind_line) {
+                            resources.push(resource);
+                        }
+                    }
+                }
+            } else if let Some(resource) = self.parse_resource(&value, file_path, kind_line) {
+                resources.push(resource);
+            }
+        }
+
+        resources
+    }
+
+    fn parse_resource(&self, value: &Value, file_path: &str, line_number: Option<usize>) -> Option<KubernetesResource> {
+        value.get("apiVersion")?;
+        let kind = str_at(value, &["kind"])?;
+        let name = str_at(value, &["metadata", "name"])?;
+
+        let mut resource = KubernetesResource {
+            kind: kind.clone(),
+            name,
+            namespace: str_at(value, &["metadata", "namespace"]),
+            file_path: file_path.to_string(),
+            line_number,
+            labels: string_map(value_at(value, &["metadata", "labels"])),
+            selector: HashMap::new(),
+            images: Vec::new(),
+            ports: Vec::new(),
+            hosts: Vec::new(),
+            backend_services: Vec::new(),
+            config_refs: Vec::new(),
+            built_images: Vec::new(),
+        };
+
+        match kind.as_str() {
+            k if WORKLOAD_KINDS.contains(&k) => {
+                let (template_path, pod_spec_path): (&[&str], &[&str]) = match k {
+                    "Pod" => (&["metadata"], &["spec"]),
+                    "CronJob" => (
+                        &["spec", "jobTemplate", "spec", "template", "metadata"],
+                        &["spec", "jobTemplate", "spec", "template", "spec"],
+                    ),
+                    _ => (&["spec", "template", "metadata"], &["spec", "template", "spec"]),
+                };
+
+                let template_labels = string_map(value_at(value, template_path).and_then(|m| m.get("labels")));
+                if !template_labels.is_empty() {
+                    resource.labels = template_labels;
+                }
+
+                if let Some(pod_spec) = value_at(value, pod_spec_path) {
+                    self.parse_pod_spec(pod_spec, &mut resource);
+                }
+            }
+            "Service" => {
+                resource.selector = string_map(value_at(value, &["spec", "selector"]));
+                if let Some(ports) = value_at(value, &["spec", "ports"]).and_then(|v| v.as_sequence()) {
+                    for port in ports {
+                        if let Some(p) = port.get("port").and_then(|v| v.as_u64()) {
+                            resource.ports.push(p as u16);
+                        }
+                    }
+                }
+            }
+            "Ingress" => {
+                let mut backends = HashSet::new();
+                if let Some(rules) = value_at(value, &["spec", "rules"]).and_then(|v| v.as_sequence()) {
+                    for rule in rules {
+                        if let Some(host) = rule.get("host").and_then(|v| v.as_str()) {
+                            if !resource.hosts.contains(&host.to_string()) {
+                                resource.hosts.push(host.to_string());
+                            }
+                        }
+                        if let Some(paths) = value_at(rule, &["http", "paths"]).and_then(|v| v.as_sequence()) {
+                            for path in paths {
+                                if let Some(service) = path.get("backend").and_then(ingress_backend_service) {
+                                    backends.insert(service);
+                                }
+                            }
+                        }
+                    }
+                }
+                // networking.k8s.io/v1 uses defaultBackend, older APIs use backend
+                for key in ["defaultBackend", "backend"] {
+                    if let Some(service) = value_at(value, &["spec", key]).and_then(ingress_backend_service) {
+                        backends.insert(service);
+                    }
+                }
+                let mut backends: Vec<String> = backends.into_iter().collect();
+                backends.sort();
+                resource.backend_services = backends;
+            }
+            _ => {}
+        }
+
+        Some(resource)
+    }
+
+    fn parse_pod_spec(&self, pod_spec: &Value, resource: &mut KubernetesResource) {
+        let mut config_refs = HashSet::new();
+
+        for containers_key in ["initContainers", "containers"] {
+            let containers = match pod_spec.get(containers_key).and_then(|v| v.as_sequence()) {
+                Some(c) => c,
+                None => continue,
+            };
+            for container in containers {
+                if let Some(image) = container.get("image").and_then(|v| v.as_str()) {
+                    if !resource.images.contains(&image.to_string()) {
+                        resource.images.push(image.to_string());
+                    }
+                }
+                if let Some(ports) = container.get("ports").and_then(|v| v.as_sequence()) {
+                    for port in ports {
+                        if let Some(p) = port.get("containerPort").and_then(|v| v.as_u64()) {
+                            resource.ports.push(p as u16);
+                        }
+                    }
+                }
+                if let Some(env_from) = container.get("envFrom").and_then(|v| v.as_sequence()) {
+                    for source in env_from {
+                        if let Some(name) = str_at(source, &["configMapRef", "name"]) {
+                            config_refs.insert(format!("ConfigMap/{}", name));
+                        }
+                        if let Some(name) = str_at(source, &["secretRef", "name"]) {
+                            config_refs.insert(format!("Secret/{}", name));
+                        }
+                    }
+                }
+                if let Some(env) = container.get("env").and_then(|v| v.as_sequence()) {
+                    for var in env {
+                        if let Some(name) = str_at(var, &["valueFrom", "configMapKeyRef", "name"]) {
+                            config_refs.insert(format!("ConfigMap/{}", name));
+                        }
+                        if let Some(name) = str_at(var, &["valueFrom", "secretKeyRef", "name"]) {
+                            config_refs.insert(format!("Secret/{}", name));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(volumes) = pod_spec.get("volumes").and_then(|v| v.as_sequence()) {
+            for volume in volumes {
+                if let Some(name) = str_at(volume, &["configMap", "name"]) {
+                    config_refs.insert(format!("ConfigMap/{}", name));
+                }
+                if let Some(name) = str_at(volume, &["secret", "secretName"]) {
+                    config_refs.insert(format!("Secret/{}", name));
+                }
+            }
+        }
+
+        let mut config_refs: Vec<String> = config_refs.into_iter().collect();
+        config_refs.sort();
+        resource.config_refs = config_refs;
+    }
+
+    /// Detect container images built by this repository
+    /// Sources: docker-compose `build` + `image`, `docker build -t` in scripts/CI, and Dockerfiles
+    pub fn detect_built_images(&self, repo_path: &Path) -> Result<Vec<BuiltImage>> {
+        let mut images = Vec::new();
+        let tag_pattern = Regex::new(r"docker\s+(?:buildx\s+)?build\b[^\n]*?(?:-t|--tag)[\s=]+([A-Za-z0-9._/:${}-]+)")?;
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_string();
+            let file_name_lower = file_name.to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            // Allow CI directories like .github/.gitlab-ci.yml through the hidden-file filter
+            let is_ci = path_str.contains(".github/workflows") || file_name_lower == ".gitlab-ci.yml";
+            if !is_ci && utils::should_skip_file(&file_name_lower, &path_str) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            // Dockerfile: image named after the containing directory (or the repository root)
+            if file_name_lower == "dockerfile" || file_name_lower.starts_with("dockerfile.") || file_name_lower.ends_with(".dockerfile") {
+                let dir_name = path.parent()
+                    .filter(|p| *p != repo_path)
+                    .or(Some(repo_path))
+                    .and_then(|p| p.file_name())
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("")
+                    .to_lowercase();
+                if !dir_name.is_empty() {
+                    images.push(BuiltImage {
+                        image: dir_name,
+                        dockerfile: Some(normalized_path.clone()),
+                        file_path: normalized_path.clone(),
+                        evidence: "Dockerfile directory name".to_string(),
+                    });
+                }
+                continue;
+            }
+
+            let is_compose = file_name_lower.starts_with("docker-compose") || file_name_lower.starts_with("compose.");
+            let is_build_script = is_ci
+                || file_name_lower == "makefile"
+                || file_name_lower == "justfile"
+                || file_name_lower == "jenkinsfile"
+                || file_name_lower.ends_with(".sh");
+
+            if !is_compose && !is_build_script {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if is_compose {
+                if let Ok(compose) = serde_yaml::from_str::<Value>(&content) {
+                    if let Some(services) = compose.get("services").and_then(|v| v.as_mapping()) {
+                        for (service_name, service) in services {
+                            let build = match service.get("build") {
+                                Some(b) => b,
+                                None => continue,
+                            };
+                            let image = service.get("image").and_then(|v| v.as_str())
+                                .map(strip_tag)
+                                .or_else(|| service_name.as_str().map(|s| s.to_string()));
+                            let dockerfile = build.as_str().map(|ctx| format!("{}/Dockerfile", ctx.trim_end_matches('/')))
+                                .or_else(|| str_at(build, &["dockerfile"]));
+                            if let Some(image) = image {
+                                images.push(BuiltImage {
+                                    image,
+                                    dockerfile,
+                                    file_path: normalized_path.clone(),
+                                    evidence: "docker-compose build".to_string(),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+
+            if is_build_script {
+                for cap in tag_pattern.captures_iter(&content) {
+                    let tag = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+                    if tag.is_empty() || tag.starts_with('$') {
+                        continue;
+                    }
+                    images.push(BuiltImage {
+                        image: strip_tag(tag),
+                        dockerfile: None,
+                        file_path: normalized_path.clone(),
+                        evidence: cap.get(0).map(|m| m.as_str().trim().to_string()).unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        Ok(images)
+    }
+}
+
+/// Resolve the service name from an Ingress backend (v1 and v1beta1 formats)
+fn ingress_backend_service(backend: &Value) -> Option<String> {
+    str_at(backend, &["service", "name"]).or_else(|| str_at(backend, &["serviceName"]))
+}
+
+/// Strip tag or digest from an image reference, keeping registry ports intact
+fn strip_tag(image: &str) -> String {
+    let without_digest = image.split('@').next().unwrap_or(image);
+    match without_digest.rfind(':') {
+        Some(idx) if !without_digest[idx..].contains('/') => without_digest[..idx].to_string(),
+        _ => without_digest.to_string(),
+    }
+}
+
+/// Check whether a workload image refers to an image built by the repository.
+/// Full repository paths must match exactly; bare names match the last path segment.
+fn image_matches(image: &str, built: &str) -> bool {
+    let image = strip_tag(image).to_lowercase();
+    let built = built.to_lowercase();
+    if image == built {
+        return true;
+    }
+    if built.contains('/') {
+        return false;
+    }
+    image.rsplit('/').next() == Some(built.as_str())
+}
+
+fn value_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    let mut current = value;
+    for key in path {
+        current = current.get(*key)?;
+    }
+    Some(current)
+}
+
+fn str_at(value: &Value, path: &[&str]) -> Option<String> {
+    value_at(value, path).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn string_map(value: Option<&Value>) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    if let Some(mapping) = value.and_then(|v| v.as_mapping()) {
+        for (k, v) in mapping {
+            let key = k.as_str().map(|s| s.to_string());
+            let val = match v {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                Value::Bool(b) => Some(b.to_string()),
+                _ => None,
+            };
+            if let (Some(key), Some(val)) = (key, val) {
+                map.insert(key, val);
+            }
+        }
+    }
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use std::fs;
+
+    #[test]
+    fn test_topology_resolves_ingress_to_built_image() {
+        let temp_dir = TempDir::new().unwrap();
+        let k8s_dir = temp_dir.path().join("deploy");
+        fs::create_dir_all(&k8s_dir).unwrap();
+        fs::write(k8s_dir.join("app.yaml"), r#"
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: api
+spec:
+  selector:
+    matchLabels:
+      app: api
+  template:
+    metadata:
+      labels:
+        app: api
+    spec:
+      containers:
+        - name: api
+          image: ghcr.io/acme/api:1.2.3
+          envFrom:
+            - configMapRef:
+                name: api-config
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: api-svc
+spec:
+  selector:
+    app: api
+  ports:
+    - port: 80
+---
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: public
+spec:
+  rules:
+    - host: api.example.com
+      http:
+        paths:
+          - path: /
+            backend:
+              service:
+                name: api-svc
+                port:
+                  number: 80
+"#).unwrap();
+        fs::write(temp_dir.path().join("Makefile"), "image:\n\tdocker build -t ghcr.io/acme/api:latest .\n").unwrap();
+
+        let detector = KubernetesDetector::new();
+        let resources = detector.detect_resources(temp_dir.path()).unwrap();
+        assert_eq!(resources.len(), 3);
+
+        let deployment = resources.iter().find(|r| r.kind == "Deployment").unwrap();
+        assert_eq!(deployment.built_images, vec!["ghcr.io/acme/api:1.2.3".to_string()]);
+        assert_eq!(deployment.config_refs, vec!["ConfigMap/api-config".to_string()]);
+
+        let topology = KubernetesTopology::from_resources(resources);
+        assert_eq!(topology.routes.len(), 1);
+        let route = &topology.routes[0];
+        assert_eq!(route.host.as_deref(), Some("api.example.com"));
+        assert_eq!(route.workloads, vec!["Deployment/api".to_string()]);
+        assert_eq!(route.images, vec!["ghcr.io/acme/api:1.2.3".to_string()]);
+    }
+
+    #[test]
+    fn test_strip_tag_keeps_registry_port() {
+        assert_eq!(strip_tag("localhost:5000/app:dev"), "localhost:5000/app");
+        assert_eq!(strip_tag("nginx@sha256:abc"), "nginx");
+        assert_eq!(strip_tag("redis"), "redis");
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageStat {
+    pub language: String,
+    pub bytes: u64,
+    pub files: usize,
+    pub percentage: f64, // Share of total bytes, 0-100
+}
+
+/// Linguist-style language breakdown. Only programming and markup languages are counted;
+/// data formats (JSON, YAML) and prose (Markdown) are ignored like linguist does by default.
+pub struct LanguageStatsCalculator;
+
+impl LanguageStatsCalculator {
+    pub fn new() -> Self {
+        LanguageStatsCalculator
+    }
+
+    pub fn calculate(&self, repo_path: &Path) -> Result<Vec<LanguageStat>> {
+        let mut totals: HashMap<&'static str, (u64, usize)> = HashMap::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            if utils::should_skip_file(&file_name, &path_str) || path_str.contains("/vendor/") {
+                continue;
+            }
+
+            let language = match language_for_file(&file_name) {
+                Some(l) => l,
+                None => continue,
+            };
+
+            let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            let stat = totals.entry(language).or_insert((0, 0));
+            stat.0 += bytes;
+            stat.1 += 1;
+        }
+
+        let total_bytes: u64 = totals.values().map(|(bytes, _)| bytes).sum();
+        let mut stats: Vec<LanguageStat> = totals.into_iter()
+            .map(|(language, (bytes, files))| LanguageStat {
+                language: language.to_string(),
+                bytes,
+                files,
+                percentage: if total_bytes > 0 {
+                    // Round to two decimals
+                    (bytes as f64 / total_bytes as f64 * 10000.0).round() / 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        stats.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.language.cmp(&b.language)));
+        Ok(stats)
+    }
+}
+
+fn language_for_file(file_name: &str) -> Option<&'static str> {
+    match file_name {
+        "dockerfile" => return Some("Dockerfile"),
+        "makefile" | "gnumakefile" => return Some("Makefile"),
+        _ => {}
+    }
+    if file_name.starts_with("dockerfile.") {
+        return Some("Dockerfile");
+    }
+
+    let ext = file_name.rsplit_once('.').map(|(_, ext)| ext)?;
+    let language = match ext {
+        "rs" => "Rust",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "scala" => "Scala",
+        "groovy" | "gradle" => "Groovy",
+        "swift" => "Swift",
+        "m" | "mm" => "Objective-C",
+        "c" => "C",
+        "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" => "C#",
+        "fs" | "fsx" => "F#",
+        "rb" | "rake" => "Ruby",
+        "php" => "PHP",
+        "dart" => "Dart",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "hs" => "Haskell",
+        "clj" | "cljs" | "cljc" => "Clojure",
+        "lua" => "Lua",
+        "pl" | "pm" => "Perl",
+        "r" => "R",
+        "jl" => "Julia",
+        "sh" | "bash" | "zsh" => "Shell",
+        "ps1" | "psm1" => "PowerShell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" => "CSS",
+        "scss" | "sass" => "SCSS",
+        "less" => "Less",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "tf" | "hcl" => "HCL",
+        "proto" => "Protocol Buffer",
+        "graphql" | "gql" => "GraphQL",
+        "apex" | "cls" | "trigger" => "Apex",
+        _ => return None,
+    };
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_language_breakdown_ignores_data_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "fn main() {}\n".repeat(3)).unwrap();
+        fs::write(temp_dir.path().join("index.js"), "console.log(1);\n").unwrap();
+        fs::write(temp_dir.path().join("config.json"), "{\"a\": 1}").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Title").unwrap();
+
+        let stats = LanguageStatsCalculator::new().calculate(temp_dir.path()).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].language, "Rust");
+        assert_eq!(stats[0].files, 1);
+        let total: f64 = stats.iter().map(|s| s.percentage).sum();
+        assert!((total - 100.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use crate::analysis::utils::resolve_local_imports;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Layer {
+    Controller,
+    Service,
+    Repository,
+    Domain,
+    Port,
+    Adapter,
+}
+
+impl Layer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Layer::Controller => "controller",
+            Layer::Service => "service",
+            Layer::Repository => "repository",
+            Layer::Domain => "domain",
+            Layer::Port => "port",
+            Layer::Adapter => "adapter",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Layer> {
+        match value {
+            "controller" => Some(Layer::Controller),
+            "service" => Some(Layer::Service),
+            "repository" => Some(Layer::Repository),
+            "domain" => Some(Layer::Domain),
+            "port" => Some(Layer::Port),
+            "adapter" => Some(Layer::Adapter),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ArchitecturePattern {
+    Layered,   // controllers -> services -> repositories
+    Hexagonal, // domain core with ports, adapters at the edge
+    #[default]
+    Unknown,
+}
+
+impl ArchitecturePattern {
+    /// Pattern suggested by the set of layers present in a codebase
+    pub fn from_layers(layers: &HashSet<Layer>) -> ArchitecturePattern {
+        let layered = [Layer::Controller, Layer::Service, Layer::Repository].iter()
+            .filter(|l| layers.contains(l))
+            .count();
+        if layers.contains(&Layer::Port) || (layers.contains(&Layer::Adapter) && layers.contains(&Layer::Domain)) {
+            ArchitecturePattern::Hexagonal
+        } else if layered >= 2 {
+            ArchitecturePattern::Layered
+        } else {
+            ArchitecturePattern::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FindingSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+/// A dependency that breaks an architecture rule, with the import that introduces it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureFinding {
+    pub rule: String,
+    pub severity: FindingSeverity,
+    pub source_file: String,
+    pub source_layer: Option<String>,
+    pub target_file: String,
+    pub target_layer: Option<String>,
+    pub line_number: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerAssignment {
+    pub file_path: String,
+    pub layer: Layer,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayeringAnalysis {
+    pub pattern: ArchitecturePattern,
+    pub assignments: Vec<LayerAssignment>,
+    pub findings: Vec<ArchitectureFinding>,
+}
+
+/// (source layer, target layer, rule id, severity, message)
+const LAYER_RULES: &[(Layer, Layer, &str, FindingSeverity, &str)] = &[
+    (Layer::Controller, Layer::Repository, "controller-bypasses-service", FindingSeverity::Medium, "Controller imports a repository directly instead of going through a service"),
+    (Layer::Repository, Layer::Service, "repository-depends-on-service", FindingSeverity::High, "Repository depends on the service layer above it"),
+    (Layer::Repository, Layer::Controller, "repository-depends-on-controller", FindingSeverity::High, "Repository depends on the controller layer"),
+    (Layer::Service, Layer::Controller, "service-depends-on-controller", FindingSeverity::High, "Service depends on the controller layer above it"),
+    (Layer::Domain, Layer::Adapter, "domain-depends-on-adapter", FindingSeverity::High, "Domain code depends on an adapter; it should depend on a port instead"),
+    (Layer::Domain, Layer::Controller, "domain-depends-on-controller", FindingSeverity::High, "Domain code depends on the delivery layer"),
+    (Layer::Domain, Layer::Repository, "domain-depends-on-repository", FindingSeverity::Medium, "Domain code depends on a concrete repository"),
+    (Layer::Port, Layer::Adapter, "port-depends-on-adapter", FindingSeverity::High, "Port depends on one of its adapters"),
+];
+
+/// Detects layering conventions from directory and file names and checks imports against them
+pub struct LayeringAnalyzer;
+
+impl LayeringAnalyzer {
+    pub fn new() -> Self {
+        LayeringAnalyzer
+    }
+
+    pub fn analyze(&self, repo_path: &Path, files: &[&str]) -> LayeringAnalysis {
+        let mut layers: BTreeMap<&str, Layer> = BTreeMap::new();
+        for file in files {
+            if let Some(layer) = classify(file) {
+                layers.insert(file, layer);
+            }
+        }
+        let present: HashSet<Layer> = layers.values().copied().collect();
+        let pattern = ArchitecturePattern::from_layers(&present);
+
+        let mut findings = Vec::new();
+        if pattern != ArchitecturePattern::Unknown {
+            for (file, source_layer) in &layers {
+                let content = match std::fs::read_to_string(repo_path.join(file)) {
+                    Ok(c) => c,
+                    Err(_) => continue,
+                };
+                for (line_number, target) in resolve_local_imports(&content, file, files) {
+                    let target_layer = match layers.get(target) {
+                        Some(l) => *l,
+                        None => continue,
+                    };
+                    if let Some((_, _, rule, severity, message)) = LAYER_RULES.iter()
+                        .find(|(from, to, ..)| from == source_layer && *to == target_layer)
+                    {
+                        findings.push(ArchitectureFinding {
+                            rule: rule.to_string(),
+                            severity: severity.clone(),
+                            source_file: file.to_string(),
+                            source_layer: Some(source_layer.as_str().to_string()),
+                            target_file: target.to_string(),
+                            target_layer: Some(target_layer.as_str().to_string()),
+                            line_number,
+                            message: message.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        LayeringAnalysis {
+            pattern,
+            assignments: layers.into_iter()
+                .map(|(file_path, layer)| LayerAssignment { file_path: file_path.to_string(), layer })
+                .collect(),
+            findings,
+        }
+    }
+}
+
+/// Layer of a file from its file name (`UserController.java`, `user.service.ts`, `order_repository.py`)
+/// or, failing that, the nearest directory that names a layer
+fn classify(file_path: &str) -> Option<Layer> {
+    let lower = file_path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or("");
+    let stem = file_name.split('.').next().unwrap_or("");
+    let dotted = file_name.split('.').nth(1).unwrap_or("");
+
+    let by_name = |name: &str| -> Option<Layer> {
+        if name.ends_with("controller") || name.ends_with("handler") || name.ends_with("resource") {
+            Some(Layer::Controller)
+        } else if name.ends_with("repository") || name.ends_with("_repo") || name.ends_with("dao") {
+            Some(Layer::Repository)
+        } else if name.ends_with("service") || name.ends_with("usecase") {
+            Some(Layer::Service)
+        } else if name.ends_with("adapter") {
+            Some(Layer::Adapter)
+        } else {
+            None
+        }
+    };
+    if let Some(layer) = by_name(dotted).or_else(|| by_name(stem)) {
+        return Some(layer);
+    }
+
+    lower.split('/').rev().skip(1).find_map(|dir| match dir {
+        "controllers" | "controller" | "handlers" | "routes" | "resources" | "endpoints" | "views" => Some(Layer::Controller),
+        "services" | "service" | "usecases" | "use_cases" | "application" => Some(Layer::Service),
+        "repositories" | "repository" | "repos" | "dao" | "daos" | "persistence" => Some(Layer::Repository),
+        "domain" | "entities" | "core" => Some(Layer::Domain),
+        "ports" | "port" => Some(Layer::Port),
+        "adapters" | "adapter" | "infrastructure" | "infra" => Some(Layer::Adapter),
+        _ => None,
+    })
+}
@@ -1,19 +1,113 @@
 pub mod dependencies;
 pub mod graph;
 pub mod code_structure;
+pub mod doc_comments;
+pub mod code_scope;
+pub mod call_graph;
+pub mod symbols;
+pub mod signatures;
 pub mod code_relationships;
 pub mod tool_detector;
+pub mod build_graph;
+pub mod data_pipeline;
+pub mod dbt;
 pub mod documentation;
 pub mod test_detector;
 pub mod port_detector;
 pub mod endpoint_detector;
+pub mod endpoint_middleware;
+pub mod endpoint_mounts;
+pub mod realtime_endpoints;
+pub mod kubernetes_detector;
+pub mod gateway_detector;
+pub mod framework_detector;
+pub mod entrypoint_detector;
+pub mod background_job_detector;
+pub mod notification_detector;
+pub mod payment_detector;
+pub mod pii_detector;
+pub mod observability_detector;
+pub mod cost_estimator;
+pub mod environment_matrix;
+pub mod artifact_inventory;
+pub mod pinning_audit;
+pub mod language_stats;
+pub mod frontend_route_detector;
+pub mod coverage;
+pub mod test_mapping;
+pub mod graphql_client_detector;
+pub mod service_boundaries;
+pub mod decomposition;
+pub mod layering;
+pub mod architecture_rules;
+pub mod target_architecture;
+pub mod git_blame;
+pub mod tech_debt;
+pub mod update_bots;
+pub mod todo_scanner;
+pub mod adr;
+pub mod doc_quality;
+pub mod doc_links;
+pub mod doc_drift;
+pub mod changelog;
+pub mod repo_config;
+pub mod gate;
+pub mod profile;
+pub mod salesforce;
+pub mod game_engine;
+pub mod syntax_tree;
+pub mod repo_path;
+pub mod provenance;
+pub mod system_links;
+pub mod false_positives;
+pub mod rule_tests;
+pub mod explain;
 pub mod utils;
 
 pub use dependencies::{DependencyExtractor, PackageDependency, PackageManager};
-pub use code_structure::{CodeAnalyzer, CodeStructure, CodeElement, CodeElementType, CodeCall};
+pub use code_structure::{CodeAnalyzer, CodeStructure, CodeElement, CodeElementType, CodeCall, CodeParser, CodeStructureSettings};
 pub use code_relationships::{CodeRelationshipDetector, CodeRelationship, RelationshipTargetType};
 pub use tool_detector::{ToolDetector, DetectedTool, ToolType, ToolCategory};
+pub use build_graph::{BuildGraphExtractor, BuildTarget};
+pub use data_pipeline::{DataPipelineDetector, DataPipeline, DataTask, DataSource, DataExposure};
 pub use documentation::DocumentationIndexer;
 pub use test_detector::{TestDetector, DetectedTest, TestFramework};
 pub use port_detector::{PortDetector, DetectedPort, PortType};
-pub use endpoint_detector::{EndpointDetector, DetectedEndpoint, HttpMethod};
+pub use endpoint_detector::{EndpointDetector, DetectedEndpoint, EndpointProtocol, HttpMethod};
+pub use kubernetes_detector::{KubernetesDetector, KubernetesResource, KubernetesTopology};
+pub use gateway_detector::{GatewayDetector, GatewayRoute, GatewayKind};
+pub use framework_detector::{FrameworkDetector, DetectedFramework, FrameworkCategory};
+pub use entrypoint_detector::{EntrypointDetector, DetectedEntrypoint, EntrypointType};
+pub use background_job_detector::{BackgroundJobDetector, DetectedBackgroundJob, JobTrigger};
+pub use notification_detector::{NotificationDetector, DetectedNotificationConfig, NotificationChannel, NotificationSetting};
+pub use payment_detector::{PaymentDetector, PaymentAnalysis, DetectedPaymentFlow, PaymentFlowKind, PciFinding, PciFindingKind};
+pub use pii_detector::{PiiDetector, PiiAnalysis, DetectedPiiField, DetectedPiiFlow, PiiCategory, PiiSink};
+pub use observability_detector::{ObservabilityDetector, ObservabilityAnalysis, DetectedObservabilityLibrary, DetectedObservabilityExporter, ObservabilityCoverage, ObservabilitySignal, ObservabilityGap};
+pub use cost_estimator::{CostEstimator, CostEstimate, ResourceCost, CostCategory};
+pub use environment_matrix::{EnvironmentMatrixBuilder, EnvironmentMatrix, EnvironmentValue, EnvironmentSource, SettingKind};
+pub use artifact_inventory::{ArtifactInventoryScanner, ArtifactInventory, DetectedArtifact, ArtifactKind, HygieneFinding};
+pub use pinning_audit::{PinningAuditor, PinningAudit};
+pub use language_stats::{LanguageStatsCalculator, LanguageStat};
+pub use frontend_route_detector::{FrontendRouteDetector, FrontendRoute, FrontendRouter};
+pub use coverage::{CoverageParser, CoverageFormat, FileCoverage};
+pub use test_mapping::{TestMapper, TestCodeLink, TestLinkStrategy};
+pub use graphql_client_detector::{GraphQLClientDetector, GraphQLUsage, GraphQLOperation, GraphQLOperationType, GraphQLSchemaType};
+pub use service_boundaries::{ServiceBoundaryInferrer, ServiceBoundary, BoundaryKind, DataStoreUse};
+pub use decomposition::{DecompositionAdvisor, DecompositionReport, ExtractionCandidate, SharedTable, EndpointUse};
+pub use layering::{LayeringAnalyzer, LayeringAnalysis, Layer, ArchitecturePattern, ArchitectureFinding, FindingSeverity};
+pub use architecture_rules::{ArchitectureRuleSet, ArchitectureRuleEngine, ArchitectureRule};
+pub use target_architecture::{TargetArchitecture, ConformanceDelta, ConformanceInput, DeltaKind};
+pub use tech_debt::{TechDebtCalculator, TechDebtScore, DebtSubscore};
+pub use todo_scanner::{TodoScanner, TodoMarker, TodoKind};
+pub use adr::{AdrExtractor, ArchitectureDecision, DecisionLink, DecisionComponent, AdrFormat};
+pub use doc_quality::{DocQualityScorer, DocQualityReport, DocCheck, BrokenLink, StaleDoc};
+pub use doc_links::{DocLinkExtractor, DocLink, DocLinkKind};
+pub use doc_drift::{DocDriftDetector, DocDrift, DriftKind, DriftDirection, EnvVarReference, find_env_var};
+pub use changelog::{ChangelogParser, Release, ReleaseSource, ArchitectureSnapshot, SnapshotChange, ReleaseTimeline, build_timeline};
+pub use repo_config::{RepoConfig, REPO_CONFIG_FILES};
+pub use gate::{GateInput, GateResult, evaluate_gate};
+pub use profile::{AnalysisProfile, ProfileSettings};
+pub use repo_path::RepoPath;
+pub use provenance::{CodeOrigin, ProvenanceClassifier};
+pub use system_links::{SystemLinker, SystemMember, CrossRepoLink, CrossRepoRelation};
+pub use false_positives::{Detection, FalsePositiveFilter, VerdictKind};
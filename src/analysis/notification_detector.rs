@@ -0,0 +1,360 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum NotificationChannel {
+    Email,
+    Sms, // Including WhatsApp senders and Twilio messaging services
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum NotificationSetting {
+    Sender,   // From address, phone number or messaging service
+    Template, // Provider-side template ID or alias
+    Webhook,  // Status/event callback URL, or the route that receives it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedNotificationConfig {
+    pub provider: String, // "sendgrid", "twilio", "ses" or "postmark"
+    pub channel: NotificationChannel,
+    pub setting: NotificationSetting,
+    pub value: String, // As written; `env:NAME` when read from the environment
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+}
+
+struct Provider {
+    key: &'static str,
+    channel: NotificationChannel,
+    marker: &'static Lazy<Regex>,
+}
+
+static SENDGRID_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)sendgrid").unwrap());
+static TWILIO_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)twilio").unwrap());
+static POSTMARK_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)postmark").unwrap());
+static SES_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"@aws-sdk/client-sesv?2|\bSES(?:v2)?Client\b|\bSendEmailCommand\b|\bSendTemplatedEmailCommand\b|\bAWS\.SES\b|client\(\s*['"]sesv?2?['"]|AmazonSimpleEmailService|\bSesV?2?Client\b|aws_sdk_sesv?2|Aws::SES"#).unwrap()
+});
+
+static PROVIDERS: &[Provider] = &[
+    Provider { key: "sendgrid", channel: NotificationChannel::Email, marker: &SENDGRID_MARKER },
+    Provider { key: "twilio", channel: NotificationChannel::Sms, marker: &TWILIO_MARKER },
+    Provider { key: "postmark", channel: NotificationChannel::Email, marker: &POSTMARK_MARKER },
+    Provider { key: "ses", channel: NotificationChannel::Email, marker: &SES_MARKER },
+];
+
+/// `from: 'no-reply@acme.io'`, `from_email=...`, `Source: ...`, `messagingServiceSid: 'MG...'`, `setFrom(...)`
+static SENDER_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[\s{(,.])(?:from_?|from_?email|from_?address|sender|source|FromEmailAddress|messaging_?service_?sid|setFrom)\s*(?:[:=]|\()").unwrap()
+});
+/// `templateId: 'd-...'`, `TemplateAlias: 'welcome'`, `Template='welcome'`, `contentSid: 'HX...'`
+static TEMPLATE_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(template_?id|template_?alias|template_?name|template|content_?sid)\s*[:=]").unwrap()
+});
+static CALLBACK_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:sms_?)?(?:status_?callback|callback_?url|event_?webhook_?url|webhook_?url)\s*[:=]").unwrap()
+});
+/// The string, number or `{ email: '...' }` right after a key
+static LITERAL_VALUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(?:\{\s*(?:email\s*:\s*)?)?(?:['"`]([^'"`]+)['"`]|(\d+)\b)"#).unwrap()
+});
+static ENV_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"process\.env\.(\w+)|environ(?:\.get)?\s*[\[(]\s*['"](\w+)|getenv\s*\(\s*['"](\w+)|ENV(?:\.fetch)?\s*[\[(]\s*['"](\w+)"#).unwrap()
+});
+static SENDGRID_TEMPLATE_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bd-[0-9a-f]{32}\b").unwrap());
+static PHONE_NUMBER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:whatsapp:)?\+?\d[\d\s\-()]{6,}$").unwrap());
+static MESSAGING_SERVICE_SID: Lazy<Regex> = Lazy::new(|| Regex::new(r"^MG[0-9a-fA-F]{32}$").unwrap());
+static TEMPLATE_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[\w\-.]+$").unwrap());
+/// A route registered in a provider file: `app.post('/webhooks/twilio'`, `@app.route("/sms/status"`, `@PostMapping("/events")`
+static ROUTE_PATH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:\.(?:post|get|all|route)|@(?:Post|Request)Mapping|@Post)\s*\(\s*(?:(?:value|path)\s*=\s*)?['"`](/[^'"`]*)['"`]"#).unwrap()
+});
+/// Words in a variable or config key that name a sender
+const SENDER_WORDS: &[&str] = &["from", "sender", "email", "phone", "number", "messaging"];
+/// Route segments that mark a route as a provider callback
+const CALLBACK_ROUTE_WORDS: &[&str] = &["webhook", "callback", "inbound", "sendgrid", "twilio", "postmark", "ses"];
+/// `SENDGRID_FROM_EMAIL=no-reply@acme.io`, `twilio.phone-number: +15550100` in env templates and config files
+static CONFIG_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r##"^\s*(?:export\s+)?([A-Za-z][\w.\-]*)\s*[=:]\s*['"]?([^'"#\s]*)"##).unwrap()
+});
+
+pub struct NotificationDetector;
+
+impl NotificationDetector {
+    pub fn new() -> Self {
+        NotificationDetector
+    }
+
+    /// Detect email/SMS provider senders, templates and webhook callbacks in a repository
+    pub fn detect_configs(&self, repo_path: &Path) -> Result<Vec<DetectedNotificationConfig>> {
+        let mut configs = Vec::new();
+        let mut seen = HashSet::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            // Env templates are dotfiles, which the usual skip rules leave out
+            let is_env_template = file_name.starts_with(".env.")
+                && [".example", ".sample", ".template", ".dist"].iter().any(|ext| file_name.ends_with(ext));
+            if !is_env_template && utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let is_config = is_env_template
+                || [".yml", ".yaml", ".properties", ".toml", ".ini", ".env"].iter().any(|ext| file_name.ends_with(ext));
+            let language = utils::detect_language(path);
+            if !is_config && language.is_none() {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+            let file_configs = if is_config {
+                self.detect_in_config(&content, &normalized_path)
+            } else {
+                self.detect_in_code(&content, &normalized_path, language.as_deref())
+            };
+
+            // The same sender or template is usually repeated across call sites; keep the first
+            for config in file_configs {
+                if seen.insert((config.provider.clone(), config.setting, config.value.clone())) {
+                    configs.push(config);
+                }
+            }
+        }
+
+        Ok(configs)
+    }
+
+    fn detect_in_code(&self, content: &str, file_path: &str, language: Option<&str>) -> Vec<DetectedNotificationConfig> {
+        let lines: Vec<&str> = content.lines().collect();
+        // (line index, provider) of every line mentioning a provider
+        let mentions: Vec<(usize, &Provider)> = lines.iter().enumerate()
+            .flat_map(|(idx, line)| PROVIDERS.iter().filter(|p| p.marker.is_match(line)).map(move |p| (idx, p)))
+            .collect();
+        if mentions.is_empty() {
+            return Vec::new();
+        }
+
+        let mut configs = Vec::new();
+        let mut push = |idx: usize, provider: &Provider, channel: NotificationChannel, setting: NotificationSetting, value: String| {
+            configs.push(DetectedNotificationConfig {
+                provider: provider.key.to_string(),
+                channel,
+                setting,
+                value,
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                language: language.map(|l| l.to_string()),
+            });
+        };
+
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*') {
+                continue;
+            }
+
+            if let Some(value) = SENDER_KEY.find(line).and_then(|m| value_after(&line[m.end()..])) {
+                let channel = if value.contains('@') {
+                    Some(NotificationChannel::Email)
+                } else if PHONE_NUMBER.is_match(&value) || MESSAGING_SERVICE_SID.is_match(&value) {
+                    Some(NotificationChannel::Sms)
+                } else {
+                    None
+                };
+                // `source: 'web'` or `from: process.env.NODE_ENV` are not senders
+                let sender_variable = value.strip_prefix("env:")
+                    .map(|name| name.to_lowercase().split('_').any(|w| SENDER_WORDS.contains(&w)))
+                    .unwrap_or(false);
+                if channel.is_some() || sender_variable {
+                    if let Some(provider) = nearest_provider(&mentions, idx, channel) {
+                        push(idx, provider, channel.unwrap_or(provider.channel), NotificationSetting::Sender, value);
+                    }
+                }
+            }
+
+            if let Some(cap) = TEMPLATE_KEY.captures(line) {
+                let key = cap[1].to_lowercase().replace('_', "");
+                let m = cap.get(0).unwrap();
+                if let Some(value) = value_after(&line[m.end()..]).filter(|v| v.starts_with("env:") || TEMPLATE_NAME.is_match(v)) {
+                    let provider = if key == "contentsid" {
+                        PROVIDERS.iter().find(|p| p.key == "twilio")
+                    } else if SENDGRID_TEMPLATE_ID.is_match(&value) {
+                        PROVIDERS.iter().find(|p| p.key == "sendgrid")
+                    } else if key == "templatealias" {
+                        PROVIDERS.iter().find(|p| p.key == "postmark")
+                    } else {
+                        nearest_provider(&mentions, idx, Some(NotificationChannel::Email))
+                    };
+                    if let Some(provider) = provider.filter(|p| mentions.iter().any(|(_, m)| m.key == p.key)) {
+                        push(idx, provider, provider.channel, NotificationSetting::Template, value);
+                    }
+                }
+            } else if let Some(m) = SENDGRID_TEMPLATE_ID.find(line) {
+                // Template IDs kept in constants: `WELCOME_TEMPLATE = 'd-...'`
+                if let Some(provider) = PROVIDERS.iter().find(|p| p.key == "sendgrid") {
+                    push(idx, provider, NotificationChannel::Email, NotificationSetting::Template, m.as_str().to_string());
+                }
+            }
+
+            if let Some(m) = CALLBACK_KEY.find(line) {
+                if let Some(value) = value_after(&line[m.end()..]) {
+                    if let Some(provider) = nearest_provider(&mentions, idx, None) {
+                        push(idx, provider, provider.channel, NotificationSetting::Webhook, value);
+                    }
+                }
+            }
+
+            for cap in ROUTE_PATH.captures_iter(line) {
+                let route = cap[1].to_lowercase();
+                let is_callback = route.split(|c: char| !c.is_ascii_alphanumeric())
+                    .any(|segment| CALLBACK_ROUTE_WORDS.contains(&segment));
+                if !is_callback {
+                    continue;
+                }
+                let provider = PROVIDERS.iter()
+                    .find(|p| route.contains(p.key) && mentions.iter().any(|(_, m)| m.key == p.key))
+                    .or_else(|| nearest_provider(&mentions, idx, None));
+                if let Some(provider) = provider {
+                    push(idx, provider, provider.channel, NotificationSetting::Webhook, cap[1].to_string());
+                }
+            }
+        }
+
+        configs
+    }
+
+    fn detect_in_config(&self, content: &str, file_path: &str) -> Vec<DetectedNotificationConfig> {
+        let mut configs = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            let Some(cap) = CONFIG_ENTRY.captures(line) else { continue };
+            let key = cap[1].to_lowercase();
+            let words: Vec<&str> = key.split(['_', '.', '-']).collect();
+            let Some(provider) = PROVIDERS.iter().find(|p| words.contains(&p.key) || (p.key == "ses" && words.contains(&"sesv2"))) else { continue };
+            let has = |names: &[&str]| words.iter().any(|w| names.contains(w));
+            let setting = if has(&["template"]) {
+                NotificationSetting::Template
+            } else if has(&["webhook", "callback"]) {
+                NotificationSetting::Webhook
+            } else if has(SENDER_WORDS) {
+                NotificationSetting::Sender
+            } else {
+                // API keys, account SIDs and regions are not part of the communication surface
+                continue;
+            };
+            let value = match &cap[2] {
+                "" => format!("env:{}", &cap[1]),
+                literal => literal.to_string(),
+            };
+            let channel = if value.contains('@') { NotificationChannel::Email } else { provider.channel };
+            configs.push(DetectedNotificationConfig {
+                provider: provider.key.to_string(),
+                channel,
+                setting,
+                value,
+                file_path: file_path.to_string(),
+                line_number: Some(idx + 1),
+                language: None,
+            });
+        }
+        configs
+    }
+}
+
+/// The literal or environment variable a key is set to
+fn value_after(rest: &str) -> Option<String> {
+    if let Some(cap) = LITERAL_VALUE.captures(rest) {
+        let value = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str().trim();
+        return (!value.is_empty()).then(|| value.to_string());
+    }
+    ENV_REFERENCE.captures(rest)
+        .and_then(|cap| cap.iter().skip(1).flatten().next().map(|m| format!("env:{}", m.as_str())))
+}
+
+/// The provider for a setting on line `idx`: among those mentioned in the file serving `channel`,
+/// the one mentioned last before the line, else the first one after it
+fn nearest_provider<'a>(mentions: &[(usize, &'a Provider)], idx: usize, channel: Option<NotificationChannel>) -> Option<&'a Provider> {
+    let serves = |p: &&Provider| channel.is_none_or(|c| p.channel == c);
+    mentions.iter().rev().find(|(line, p)| *line <= idx && serves(p))
+        .or_else(|| mentions.iter().find(|(line, p)| *line > idx && serves(p)))
+        .map(|(_, p)| *p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_senders_templates_and_callbacks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "src/mail.js", "const sgMail = require('@sendgrid/mail');\n\nawait sgMail.send({\n  to: user.email,\n  from: 'Acme <no-reply@acme.io>',\n  templateId: 'd-0123456789abcdef0123456789abcdef',\n});\n");
+        write(root, "src/sms.ts", "import twilio from 'twilio';\n\nclient.messages.create({\n  from: '+15550100',\n  source: 'signup',\n  statusCallback: `${BASE_URL}/webhooks/twilio/status`,\n});\n\napp.post('/webhooks/twilio/status', handleStatus);\napp.post('/users', createUser);\n");
+        write(root, "app/emails.py", "import boto3\nses = boto3.client('ses')\n\nses.send_templated_email(\n    Source=os.environ['SES_SENDER'],\n    Template='welcome-email',\n)\n");
+        write(root, ".env.example", "SENDGRID_API_KEY=\nSENDGRID_FROM_EMAIL=\nTWILIO_PHONE_NUMBER=+15550199\n");
+
+        let configs = NotificationDetector::new().detect_configs(temp_dir.path()).unwrap();
+        let find = |provider: &str, setting: NotificationSetting| -> Vec<&str> {
+            configs.iter()
+                .filter(|c| c.provider == provider && c.setting == setting)
+                .map(|c| c.value.as_str())
+                .collect()
+        };
+
+        assert_eq!(find("sendgrid", NotificationSetting::Sender), vec!["Acme <no-reply@acme.io>", "env:SENDGRID_FROM_EMAIL"]);
+        assert_eq!(find("sendgrid", NotificationSetting::Template), vec!["d-0123456789abcdef0123456789abcdef"]);
+        assert_eq!(find("twilio", NotificationSetting::Sender), vec!["+15550100", "+15550199"]);
+        assert_eq!(find("twilio", NotificationSetting::Webhook), vec!["${BASE_URL}/webhooks/twilio/status", "/webhooks/twilio/status"]);
+        assert_eq!(find("ses", NotificationSetting::Sender), vec!["env:SES_SENDER"]);
+        assert_eq!(find("ses", NotificationSetting::Template), vec!["welcome-email"]);
+
+        let sms_sender = configs.iter().find(|c| c.value == "+15550100").unwrap();
+        assert_eq!(sms_sender.channel, NotificationChannel::Sms);
+        assert_eq!((sms_sender.file_path.as_str(), sms_sender.line_number), ("src/sms.ts", Some(4)));
+    }
+
+    #[test]
+    fn test_ignores_files_without_a_provider_and_settings_that_are_not_senders() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "src/other.js", "await mailer.send({ from: 'no-reply@acme.io' });\n");
+        write(root, "src/postmark.js", "const postmark = require('postmark');\n// from: 'old@acme.io',\nclient.sendEmailWithTemplate({\n  From: process.env.NODE_ENV,\n  template: 'Hello {{name}}',\n  TemplateAlias: 'receipt',\n});\n");
+        write(root, "src/billing/invoice.js", "const sgMail = require('@sendgrid/mail');\nsgMail.send({ from: 'billing@acme.io' });\n");
+        write(root, "src/billing/reminder.js", "const sgMail = require('@sendgrid/mail');\nsgMail.send({ from: 'billing@acme.io' });\n");
+        write(root, ".env.sample", "TWILIO_ACCOUNT_SID=AC123\nPOSTMARK_API_TOKEN=\nPOSTMARK_TEMPLATE_ALIAS=welcome\n");
+
+        let configs = NotificationDetector::new().detect_configs(root).unwrap();
+        let mut found: Vec<(&str, String, &str)> = configs.iter()
+            .map(|c| (c.provider.as_str(), format!("{:?}", c.setting), c.value.as_str()))
+            .collect();
+        found.sort();
+        assert_eq!(found, vec![
+            ("postmark", "Template".to_string(), "receipt"),
+            ("postmark", "Template".to_string(), "welcome"),
+            ("sendgrid", "Sender".to_string(), "billing@acme.io"),
+        ]);
+    }
+}
@@ -0,0 +1,417 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::{BoundaryKind, ServiceBoundary};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObservabilitySignal {
+    Logging,
+    Metrics,
+    Tracing,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ObservabilityGap {
+    NoStructuredLogging, // Only plain-text loggers or print calls, or no logging at all
+    NoMetrics,
+    NoTracing,
+}
+
+/// A logging, metrics or tracing library imported by a service's code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedObservabilityLibrary {
+    pub name: String,
+    pub signal: ObservabilitySignal,
+    pub structured: bool, // Emits key/value or JSON records rather than plain text (logging only)
+    pub service: Option<String>,
+    pub file_count: usize,
+    pub file_path: String, // First file importing it
+    pub line_number: Option<usize>,
+}
+
+/// Where telemetry is shipped: an OTLP collector, a Datadog agent, a Prometheus scrape, ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedObservabilityExporter {
+    pub backend: String, // "otlp", "datadog", "prometheus", "jaeger", "zipkin", "statsd", "newrelic", "honeycomb" or "elastic_apm"
+    pub signal: ObservabilitySignal,
+    pub endpoint: Option<String>, // As written; `env:NAME` when read from the environment
+    pub service: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+/// What a service instruments, and which signal it is missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservabilityCoverage {
+    pub service: String,
+    pub paths: Vec<String>,
+    pub logging: Vec<String>,
+    pub metrics: Vec<String>,
+    pub tracing: Vec<String>,
+    pub structured_logging: bool,
+    pub gaps: Vec<ObservabilityGap>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObservabilityAnalysis {
+    pub libraries: Vec<DetectedObservabilityLibrary>,
+    pub exporters: Vec<DetectedObservabilityExporter>,
+    pub coverage: Vec<ObservabilityCoverage>,
+}
+
+/// Name given to the coverage entry when the repository has no separate services
+pub const REPOSITORY_SERVICE: &str = "(repository)";
+
+struct Library {
+    name: &'static str,
+    signal: ObservabilitySignal,
+    structured: bool,
+    pattern: Regex,
+}
+
+fn library(name: &'static str, signal: ObservabilitySignal, structured: bool, pattern: &str) -> Library {
+    Library { name, signal, structured, pattern: Regex::new(pattern).unwrap() }
+}
+
+/// Matched against import, require and `use` lines only
+static LIBRARIES: Lazy<Vec<Library>> = Lazy::new(|| {
+    use ObservabilitySignal::*;
+    vec![
+        // Structured logging
+        library("winston", Logging, true, r#"['"]winston['"]"#),
+        library("pino", Logging, true, r#"['"]pino(?:-http)?['"]"#),
+        library("bunyan", Logging, true, r#"['"]bunyan['"]"#),
+        library("structlog", Logging, true, r"\bstructlog\b"),
+        library("python-json-logger", Logging, true, r"\bpythonjsonlogger\b"),
+        library("logrus", Logging, true, r"github\.com/sirupsen/logrus"),
+        library("zap", Logging, true, r"go\.uber\.org/zap"),
+        library("zerolog", Logging, true, r"github\.com/rs/zerolog"),
+        library("slog", Logging, true, r#""log/slog""#),
+        library("tracing", Logging, true, r"^\s*use\s+tracing(?:::|;|\s)|\btracing_subscriber\b"),
+        library("serilog", Logging, true, r"\bSerilog\b"),
+        library("logstash-logback-encoder", Logging, true, r"net\.logstash\.logback"),
+        // Plain-text logging
+        library("log4js", Logging, false, r#"['"]log4js['"]"#),
+        library("logging", Logging, false, r"^\s*import\s+logging\b|^\s*from\s+logging\s+import\b"),
+        library("loguru", Logging, false, r"\bloguru\b"),
+        library("log", Logging, false, r#"^\s*use\s+log(?:::|;|\s)|^\s*"log"\s*$|\bimport\s+"log"$"#),
+        library("slf4j", Logging, false, r"\borg\.slf4j\b"),
+        library("log4j", Logging, false, r"\borg\.apache\.(?:log4j|logging\.log4j)\b"),
+        library("java.util.logging", Logging, false, r"\bjava\.util\.logging\b"),
+        // Metrics
+        library("prom-client", Metrics, false, r#"['"]prom-client['"]"#),
+        library("prometheus_client", Metrics, false, r"\bprometheus_client\b"),
+        library("prometheus/client_golang", Metrics, false, r"github\.com/prometheus/client_golang"),
+        library("prometheus", Metrics, false, r"^\s*use\s+prometheus(?:::|;|\s)"),
+        library("metrics", Metrics, false, r"^\s*use\s+metrics(?:_exporter_prometheus)?(?:::|;|\s)"),
+        library("micrometer", Metrics, false, r"\bio\.micrometer\b"),
+        library("statsd", Metrics, false, r#"['"](?:hot-shots|node-statsd|statsd-client)['"]|^\s*(?:import|from)\s+statsd\b|github\.com/(?:cactus/go-statsd-client|DataDog/datadog-go)"#),
+        library("datadog", Metrics, false, r"^\s*from\s+datadog\s+import\b"),
+        library("opentelemetry-metrics", Metrics, false, r#"['"]@opentelemetry/(?:sdk-metrics|api-metrics)['"]|\bopentelemetry\.(?:sdk\.)?metrics\b|go\.opentelemetry\.io/otel/metric"#),
+        // Tracing
+        library("opentelemetry", Tracing, false, r#"['"]@opentelemetry/(?:api|sdk-node|sdk-trace-\w+|auto-instrumentations-node)['"]|\bopentelemetry(?:\.sdk)?\.trace\b|^\s*from\s+opentelemetry\s+import\s+trace\b|go\.opentelemetry\.io/otel(?:/trace|/sdk/trace)?"|\bio\.opentelemetry\b|^\s*use\s+(?:opentelemetry|tracing_opentelemetry)(?:::|;|\s)"#),
+        library("dd-trace", Tracing, false, r#"['"]dd-trace['"]|\bddtrace\b|dd-trace-go|\bdatadog\.trace\b"#),
+        library("jaeger-client", Tracing, false, r#"['"]jaeger-client['"]|\bjaeger_client\b|github\.com/uber/jaeger-client-go"#),
+        library("zipkin", Tracing, false, r#"['"]zipkin['"]|\bbrave\.\w+|github\.com/openzipkin"#),
+        library("aws-xray-sdk", Tracing, false, r#"aws-xray-sdk|\baws_xray_sdk\b|\bcom\.amazonaws\.xray\b"#),
+        library("elastic-apm", Tracing, false, r#"['"]elastic-apm-node['"]|\belasticapm\b|\bco\.elastic\.apm\b"#),
+        library("newrelic", Tracing, false, r#"['"]newrelic['"]|^\s*import\s+newrelic\b|github\.com/newrelic/go-agent"#),
+    ]
+});
+
+/// `import ...`, `from x import`, `require('x')`, `use x::`, a Go import block entry
+static IMPORT_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(?:import\b|from\s+\S+\s+import\b|use\s|extern\s+crate\b|using\s)|\brequire\s*\(|\bimport\s*\(|^\s*(?:\w+\s+)?"[\w./\-]+"\s*$"#).unwrap()
+});
+
+struct Exporter {
+    backend: &'static str,
+    signal: ObservabilitySignal,
+    pattern: Regex,
+}
+
+fn exporter(backend: &'static str, signal: ObservabilitySignal, pattern: &str) -> Exporter {
+    Exporter { backend, signal, pattern: Regex::new(pattern).unwrap() }
+}
+
+/// The first capture group, when present, is the endpoint
+static EXPORTERS: Lazy<Vec<Exporter>> = Lazy::new(|| {
+    use ObservabilitySignal::*;
+    vec![
+        exporter("otlp", Tracing, r#"\bOTEL_EXPORTER_OTLP(?:_TRACES)?_ENDPOINT\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|\bOTLPTraceExporter\b|\botlptrace(?:grpc|http)\b|\bOtlpGrpcSpanExporter\b|\bopentelemetry_otlp\b|otel/opentelemetry-collector"#),
+        exporter("otlp", Metrics, r#"\bOTEL_EXPORTER_OTLP_METRICS_ENDPOINT\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|\bOTLPMetricExporter\b|\botlpmetric(?:grpc|http)\b"#),
+        exporter("otlp", Logging, r#"\bOTEL_EXPORTER_OTLP_LOGS_ENDPOINT\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|\bOTLPLogExporter\b"#),
+        exporter("datadog", Tracing, r#"\bDD_(?:AGENT_HOST|TRACE_AGENT_URL)\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|(?:gcr\.io/datadoghq|datadog)/agent\b"#),
+        exporter("datadog", Metrics, r#"\bDD_DOGSTATSD_URL\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?"#),
+        exporter("prometheus", Metrics, r#"^\s*scrape_configs\s*:|prometheus\.io/scrape['"]?\s*:\s*['"]?true|\bcollectDefaultMetrics\s*\(|\bstart_http_server\s*\(\s*(\d+)|\bpromhttp\.Handler\s*\(|\bPrometheusMeterRegistry\b|\bPrometheusBuilder\b|['"](/metrics)['"]"#),
+        exporter("jaeger", Tracing, r#"\bJAEGER_(?:AGENT_HOST|ENDPOINT)\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|\bJaegerExporter\b|jaegertracing/all-in-one"#),
+        exporter("zipkin", Tracing, r#"\bZIPKIN_(?:URL|ENDPOINT)\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?|\bZipkinExporter\b|openzipkin/zipkin"#),
+        exporter("statsd", Metrics, r#"\bSTATSD_HOST\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?"#),
+        exporter("newrelic", Tracing, r#"\bNEW_RELIC_LICENSE_KEY\b"#),
+        exporter("honeycomb", Tracing, r#"\bHONEYCOMB_API_KEY\b|(api\.honeycomb\.io)"#),
+        exporter("elastic_apm", Tracing, r#"\bELASTIC_APM_SERVER_URL\b(?:['"]?\s*[:=]\s*['"]?([^\s'",}]+))?"#),
+    ]
+});
+static ENV_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(?:process\.env\.(\w+)|\$\{?(\w+)\}?)$"#).unwrap()
+});
+
+pub struct ObservabilityDetector;
+
+impl ObservabilityDetector {
+    pub fn new() -> Self {
+        ObservabilityDetector
+    }
+
+    /// Detect logging, metrics and tracing libraries and their exporters, and report per service
+    /// which signals it lacks. Boundaries of kind `Service` are the services; without any the
+    /// repository is reported as a single service.
+    pub fn detect_stack(&self, repo_path: &Path, boundaries: &[ServiceBoundary]) -> Result<ObservabilityAnalysis> {
+        let services: Vec<(&str, &[String])> = boundaries.iter()
+            .filter(|b| b.kind == BoundaryKind::Service)
+            .map(|b| (b.name.as_str(), b.paths.as_slice()))
+            .collect();
+        let service_of = |file: &str| -> Option<String> {
+            services.iter()
+                .find(|(_, paths)| paths.iter().any(|p| p.is_empty() || file.starts_with(&format!("{}/", p))))
+                .map(|(name, _)| name.to_string())
+        };
+
+        let mut imports: BTreeMap<(&'static str, Option<String>), (usize, String, usize)> = BTreeMap::new();
+        let mut exporters = Vec::new();
+        let mut seen_exporters = HashSet::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            // Env templates are dotfiles, which the usual skip rules leave out
+            let is_env_file = file_name == ".env" || file_name.starts_with(".env.");
+            if !is_env_file && utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let language = utils::detect_language(path);
+            let is_config = is_env_file
+                || file_name == "dockerfile"
+                || [".yml", ".yaml", ".properties", ".toml", ".ini", ".json", ".conf"].iter().any(|ext| file_name.ends_with(ext));
+            if !is_config && language.is_none() {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+            let service = service_of(&normalized_path);
+
+            let mut file_libraries = HashSet::new();
+            for (idx, line) in content.lines().enumerate() {
+                let trimmed = line.trim_start();
+                if trimmed.starts_with("//") || (trimmed.starts_with('#') && !is_config) {
+                    continue;
+                }
+
+                if language.is_some() && IMPORT_LINE.is_match(line) {
+                    for library in LIBRARIES.iter().filter(|l| l.pattern.is_match(line)) {
+                        if file_libraries.insert(library.name) {
+                            imports.entry((library.name, service.clone()))
+                                .and_modify(|(count, _, _)| *count += 1)
+                                .or_insert((1, normalized_path.clone(), idx + 1));
+                        }
+                    }
+                }
+
+                if trimmed.starts_with('#') {
+                    continue;
+                }
+                for exporter in EXPORTERS.iter() {
+                    let Some(cap) = exporter.pattern.captures(line) else { continue };
+                    let endpoint = cap.iter().skip(1).flatten().next().map(|m| endpoint_value(m.as_str()));
+                    if seen_exporters.insert((exporter.backend, exporter.signal, endpoint.clone(), normalized_path.clone())) {
+                        exporters.push(DetectedObservabilityExporter {
+                            backend: exporter.backend.to_string(),
+                            signal: exporter.signal,
+                            endpoint,
+                            service: service.clone(),
+                            file_path: normalized_path.clone(),
+                            line_number: Some(idx + 1),
+                        });
+                    }
+                }
+            }
+        }
+
+        let libraries: Vec<DetectedObservabilityLibrary> = imports.into_iter()
+            .map(|((name, service), (file_count, file_path, line))| {
+                let library = LIBRARIES.iter().find(|l| l.name == name).unwrap();
+                DetectedObservabilityLibrary {
+                    name: name.to_string(),
+                    signal: library.signal,
+                    structured: library.structured,
+                    service,
+                    file_count,
+                    file_path,
+                    line_number: Some(line),
+                }
+            })
+            .collect();
+
+        let coverage = if services.is_empty() {
+            vec![coverage_for(REPOSITORY_SERVICE, &[], libraries.iter())]
+        } else {
+            services.iter()
+                .map(|(name, paths)| coverage_for(name, paths, libraries.iter().filter(|l| l.service.as_deref() == Some(*name))))
+                .collect()
+        };
+
+        Ok(ObservabilityAnalysis { libraries, exporters, coverage })
+    }
+}
+
+/// `process.env.OTEL_ENDPOINT` and `${DD_HOST}` become `env:NAME`, anything else is kept as written
+fn endpoint_value(raw: &str) -> String {
+    match ENV_REFERENCE.captures(raw) {
+        Some(cap) => format!("env:{}", cap.iter().skip(1).flatten().next().unwrap().as_str()),
+        None => raw.to_string(),
+    }
+}
+
+fn coverage_for<'a>(service: &str, paths: &[String], libraries: impl Iterator<Item = &'a DetectedObservabilityLibrary>) -> ObservabilityCoverage {
+    let mut by_signal: BTreeMap<ObservabilitySignal, BTreeSet<String>> = BTreeMap::new();
+    let mut structured_logging = false;
+    for library in libraries {
+        by_signal.entry(library.signal).or_default().insert(library.name.clone());
+        structured_logging |= library.signal == ObservabilitySignal::Logging && library.structured;
+    }
+    let mut names = |signal: ObservabilitySignal| by_signal.remove(&signal).map(|s| s.into_iter().collect::<Vec<_>>()).unwrap_or_default();
+    let (logging, metrics, tracing) = (names(ObservabilitySignal::Logging), names(ObservabilitySignal::Metrics), names(ObservabilitySignal::Tracing));
+
+    let mut gaps = Vec::new();
+    if !structured_logging {
+        gaps.push(ObservabilityGap::NoStructuredLogging);
+    }
+    if metrics.is_empty() {
+        gaps.push(ObservabilityGap::NoMetrics);
+    }
+    if tracing.is_empty() {
+        gaps.push(ObservabilityGap::NoTracing);
+    }
+
+    ObservabilityCoverage {
+        service: service.to_string(),
+        paths: paths.to_vec(),
+        logging,
+        metrics,
+        tracing,
+        structured_logging,
+        gaps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    fn service(name: &str) -> ServiceBoundary {
+        ServiceBoundary {
+            name: name.to_string(),
+            kind: BoundaryKind::Service,
+            paths: vec![format!("services/{}", name)],
+            file_count: 0,
+            element_count: 0,
+            internal_references: 0,
+            outbound_references: 0,
+            cohesion: 0.0,
+            data_stores: Vec::new(),
+            shared_data_stores: Vec::new(),
+            depends_on: Vec::new(),
+            confidence: 0.0,
+            evidence: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_detects_stack_and_coverage_gaps() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "services/orders/src/index.js", "const pino = require('pino');\nconst client = require('prom-client');\nconst { NodeSDK } = require('@opentelemetry/sdk-node');\nclient.collectDefaultMetrics();\n");
+        write(root, "services/orders/src/db.js", "const logger = require('pino')();\n");
+        write(root, "services/billing/app.py", "import logging\n\nlogger = logging.getLogger(__name__)\n");
+        write(root, "deploy/docker-compose.yml", "services:\n  orders:\n    environment:\n      OTEL_EXPORTER_OTLP_ENDPOINT: http://otel-collector:4317\n      DD_AGENT_HOST: ${DD_HOST}\n");
+
+        let analysis = ObservabilityDetector::new()
+            .detect_stack(temp_dir.path(), &[service("orders"), service("billing")])
+            .unwrap();
+
+        let mut libraries: Vec<(&str, Option<&str>, usize)> = analysis.libraries.iter()
+            .map(|l| (l.name.as_str(), l.service.as_deref(), l.file_count))
+            .collect();
+        libraries.sort();
+        assert_eq!(libraries, vec![
+            ("logging", Some("billing"), 1),
+            ("opentelemetry", Some("orders"), 1),
+            ("pino", Some("orders"), 2),
+            ("prom-client", Some("orders"), 1),
+        ]);
+
+        let mut exporters: Vec<(&str, Option<&str>)> = analysis.exporters.iter()
+            .map(|e| (e.backend.as_str(), e.endpoint.as_deref()))
+            .collect();
+        exporters.sort();
+        assert_eq!(exporters, vec![
+            ("datadog", Some("env:DD_HOST")),
+            ("otlp", Some("http://otel-collector:4317")),
+            ("prometheus", None),
+        ]);
+
+        let gaps: Vec<(&str, &[ObservabilityGap])> = analysis.coverage.iter()
+            .map(|c| (c.service.as_str(), c.gaps.as_slice()))
+            .collect();
+        assert_eq!(gaps, vec![
+            ("orders", &[][..]),
+            ("billing", &[ObservabilityGap::NoStructuredLogging, ObservabilityGap::NoMetrics, ObservabilityGap::NoTracing][..]),
+        ]);
+    }
+
+    #[test]
+    fn test_only_imports_count_and_the_repository_is_one_service_without_boundaries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "src/app.js", "// const pino = require('pino');\nconst label = 'winston';\nconst tracer = require('dd-trace').init();\n");
+        write(root, "config/otel.yml", "# OTEL_EXPORTER_OTLP_ENDPOINT: http://old-collector:4317\nservice_name: app\n");
+        write(root, "node_modules/pino/index.js", "const pino = require('pino');\n");
+
+        let analysis = ObservabilityDetector::new().detect_stack(root, &[]).unwrap();
+        let libraries: Vec<(&str, Option<&str>)> = analysis.libraries.iter()
+            .map(|l| (l.name.as_str(), l.service.as_deref()))
+            .collect();
+        assert_eq!(libraries, vec![("dd-trace", None)]);
+        assert!(analysis.exporters.is_empty(), "{:?}", analysis.exporters);
+        assert_eq!(analysis.coverage.len(), 1);
+        assert_eq!(analysis.coverage[0].service, REPOSITORY_SERVICE);
+        assert_eq!(analysis.coverage[0].gaps, vec![ObservabilityGap::NoStructuredLogging, ObservabilityGap::NoMetrics]);
+
+        // A service with no code at all is missing every signal
+        let empty = tempfile::tempdir().unwrap();
+        let analysis = ObservabilityDetector::new().detect_stack(empty.path(), &[service("orders")]).unwrap();
+        assert_eq!(analysis.coverage[0].service, "orders");
+        assert_eq!(analysis.coverage[0].gaps, vec![ObservabilityGap::NoStructuredLogging, ObservabilityGap::NoMetrics, ObservabilityGap::NoTracing]);
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::{DetectedEndpoint, EndpointProtocol};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PaymentFlowKind {
+    Webhook,  // Signature check or parse of an incoming provider event
+    Event,    // Event type a webhook handler reacts to
+    Checkout, // Checkout session, order, payment intent or sale creation
+    Price,    // Price or plan ID
+    Product,  // Product ID
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedPaymentFlow {
+    pub provider: String, // "stripe", "paypal" or "braintree"
+    pub kind: PaymentFlowKind,
+    pub value: String, // The call, event type or ID as written
+    pub endpoint: Option<String>, // `POST /webhooks/stripe` when the flow runs in a route handler
+    pub handler: Option<String>, // Enclosing function
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PciFindingKind {
+    CardDataLogged,     // Card number, CVV or track data passed to a logger
+    RawCardData,        // Card number or CVV read from a request instead of a provider token
+    UnverifiedWebhook,  // Provider events read from a request body without a signature check
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PciFinding {
+    pub kind: PciFindingKind,
+    pub provider: Option<String>,
+    pub handler: Option<String>,
+    pub evidence: String, // The offending line, trimmed
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaymentAnalysis {
+    pub flows: Vec<DetectedPaymentFlow>,
+    pub pci_findings: Vec<PciFinding>,
+}
+
+struct Provider {
+    key: &'static str,
+    marker: &'static Lazy<Regex>,
+    verify: &'static Lazy<Regex>,
+    event: &'static Lazy<Regex>,
+    checkout: &'static Lazy<Regex>,
+}
+
+static STRIPE_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bstripe\b").unwrap());
+static PAYPAL_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)paypal").unwrap());
+static BRAINTREE_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)braintree").unwrap());
+
+static STRIPE_VERIFY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bwebhooks\.constructEvent(?:Async)?\b|\bWebhook(?:\.|::)construct_?[eE]vent\b").unwrap()
+});
+static PAYPAL_VERIFY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bverify_?webhook_?signature\b|verify-webhook-signature|\bWebhookEvent\.verify\b").unwrap()
+});
+/// Braintree's parse checks the signature itself
+static BRAINTREE_VERIFY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bwebhook_?notification(?:\(\))?\.parse\b").unwrap()
+});
+
+/// `'checkout.session.completed'`, `"invoice.payment_failed"`, `'customer.subscription.deleted'`
+static STRIPE_EVENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"['"`]((?:checkout\.session|payment_intent|charge|invoice|customer\.subscription|setup_intent|payment_method|subscription_schedule)\.[a-z_]+(?:\.[a-z_]+)?)['"`]"#).unwrap()
+});
+/// `'PAYMENT.CAPTURE.COMPLETED'`, `"CHECKOUT.ORDER.APPROVED"`, `'BILLING.SUBSCRIPTION.CANCELLED'`
+static PAYPAL_EVENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"['"`]((?:PAYMENT|CHECKOUT|BILLING|CUSTOMER\.DISPUTE)\.[A-Z_]+(?:\.[A-Z_]+)*)['"`]"#).unwrap()
+});
+/// `WebhookNotification.Kind.SubscriptionChargedSuccessfully`, `Braintree::WebhookNotification::Kind::Disbursement`
+static BRAINTREE_EVENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"WebhookNotification(?:\.|::)Kind(?:\.|::)(\w+)").unwrap()
+});
+
+static STRIPE_CHECKOUT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bcheckout\.sessions\.create\b|\bcheckout\.Session\.create\b|\bCheckout::Session\.create\b|\bpaymentIntents\.create\b|\bPaymentIntent(?:\.|::)create\b|\bSessionCreateParams\b|\bPaymentIntentCreateParams\b").unwrap()
+});
+static PAYPAL_CHECKOUT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bOrdersCreateRequest\b|\borders\.create\b|\bcreateOrder\b|/v2/checkout/orders\b|\bPayment(?:\.|::)create\b").unwrap()
+});
+static BRAINTREE_CHECKOUT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b[tT]ransaction(?:\(\))?(?:\.|::)sale\b|\b(?:clientToken|client_token|ClientToken)(?:\(\))?(?:\.|::)generate\b").unwrap()
+});
+
+static PROVIDERS: &[Provider] = &[
+    Provider { key: "stripe", marker: &STRIPE_MARKER, verify: &STRIPE_VERIFY, event: &STRIPE_EVENT, checkout: &STRIPE_CHECKOUT },
+    Provider { key: "paypal", marker: &PAYPAL_MARKER, verify: &PAYPAL_VERIFY, event: &PAYPAL_EVENT, checkout: &PAYPAL_CHECKOUT },
+    Provider { key: "braintree", marker: &BRAINTREE_MARKER, verify: &BRAINTREE_VERIFY, event: &BRAINTREE_EVENT, checkout: &BRAINTREE_CHECKOUT },
+];
+
+/// Stripe IDs start with a digit after the prefix: `price_1MoBy5LkdIwHu7ixZhnattbh`
+static STRIPE_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(price|prod)_[0-9][A-Za-z0-9]{13,}\b").unwrap());
+/// `planId: 'gold-monthly'`, `plan_id="P-5ML4271244454362WXNWU5NQ"` for PayPal and Braintree subscriptions
+static PLAN_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\bplan_?id['"]?\s*[:=]>?\s*['"]([\w\-]+)['"]"#).unwrap()
+});
+
+/// Identifiers holding a card number, CVV or track data
+static CARD_DATA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:card_?(?:number|num|no)|credit_?card(?:_?number)?|cc_?(?:number|num)|primary_?account_?number|cvv2?|cvc2?|card_?cvc|security_?code|track_?(?:1|2|data))\b").unwrap()
+});
+static LOG_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:console|logger|log|logging|slog|tracing|Rails\.logger)\s*(?:\.|::)\s*(?:log|info|debug|warn|warning|error|trace|fatal|critical|exception)\s*!?\s*\(|\b(?:print|println!|eprintln!|printf|puts)\s*[(\s]|System\.(?:out|err)\.print").unwrap()
+});
+/// Masked values (`last4`, `****`) are fine to log
+static MASKED: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)mask|last_?4|last_?four|redact|\*\*\*\*").unwrap());
+/// `req.body.cardNumber`, `request.json['cvv']`, `request.POST.get('card_number')`, `params[:cvc]`
+static REQUEST_CARD_DATA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:req|request|params|body|form)(?:\.\w+|\(\))*(?:\.|\[\s*:?['"]?|\.get\(\s*['"])(?:card_?number|credit_?card_?number|cc_?number|cvv2?|cvc2?)\b"#).unwrap()
+});
+/// The handler reads the raw request body, where an unsigned event would arrive
+static REQUEST_BODY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\breq\.body\b|\brequest\.(?:body|data|json|get_json|get_data|raw_post)\b|@RequestBody\b|\bparams\b|\bc\.(?:Bind|ShouldBind)\w*\(").unwrap()
+});
+/// Function, method and arrow function definitions across languages
+static FUNCTION_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:export\s+(?:default\s+)?)?(?:async\s+)?function\s*\*?\s*(\w+)|^\s*(?:export\s+)?(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]+)?=>|\w+\s*=>)|^\s*(?:async\s+)?def\s+(?:self\.)?(\w+)|^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?fn\s+(\w+)|^\s*(?:public|private|protected|internal)\s+(?:static\s+)?(?:async\s+)?[\w<>\[\],.?]+\s+(\w+)\s*\(|^\s*(?:public\s+|private\s+|protected\s+)?(?:async\s+)?(\w+)\s*\([^)]*\)\s*(?::\s*[^{]+)?\{\s*$").unwrap()
+});
+const NOT_FUNCTIONS: &[&str] = &["if", "for", "while", "switch", "catch", "with", "return", "function"];
+
+pub struct PaymentDetector;
+
+impl PaymentDetector {
+    pub fn new() -> Self {
+        PaymentDetector
+    }
+
+    /// Detect payment provider webhooks, checkouts and price/product references, linking each to
+    /// the endpoint whose handler runs it, plus PCI findings on card data handling
+    pub fn detect_flows(&self, repo_path: &Path, endpoints: &[DetectedEndpoint]) -> Result<PaymentAnalysis> {
+        let mut analysis = PaymentAnalysis::default();
+        let mut seen = HashSet::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+
+            // Env templates are dotfiles, which the usual skip rules leave out
+            let is_env_template = file_name.starts_with(".env.")
+                && [".example", ".sample", ".template", ".dist"].iter().any(|ext| file_name.ends_with(ext));
+            if !is_env_template && utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let is_config = is_env_template
+                || [".yml", ".yaml", ".properties", ".toml", ".ini", ".env", ".json"].iter().any(|ext| file_name.ends_with(ext));
+            let language = utils::detect_language(path);
+            if !is_config && language.is_none() {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+            let mut file_flows = self.detect_ids(&content, &normalized_path, language.as_deref());
+            if !is_config {
+                file_flows.extend(self.detect_in_code(&content, &normalized_path, language.as_deref(), &mut analysis.pci_findings));
+            }
+
+            // Price IDs and event types repeat across call sites; keep the first per handler
+            for mut flow in file_flows {
+                if seen.insert((flow.provider.clone(), flow.kind, flow.value.clone(), flow.file_path.clone(), flow.handler.clone())) {
+                    flow.endpoint = link_endpoint(&flow, &content, endpoints);
+                    analysis.flows.push(flow);
+                }
+            }
+        }
+
+        Ok(analysis)
+    }
+
+    fn detect_in_code(&self, content: &str, file_path: &str, language: Option<&str>, findings: &mut Vec<PciFinding>) -> Vec<DetectedPaymentFlow> {
+        let lines: Vec<&str> = content.lines().collect();
+        let providers: Vec<&Provider> = PROVIDERS.iter().filter(|p| p.marker.is_match(content)).collect();
+        let mut flows = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*') {
+                continue;
+            }
+            let handler = enclosing_function(&lines, idx);
+
+            for provider in &providers {
+                let mut push = |kind: PaymentFlowKind, value: &str| {
+                    flows.push(DetectedPaymentFlow {
+                        provider: provider.key.to_string(),
+                        kind,
+                        value: value.to_string(),
+                        endpoint: None,
+                        handler: handler.clone(),
+                        file_path: file_path.to_string(),
+                        line_number: Some(idx + 1),
+                        language: language.map(|l| l.to_string()),
+                    });
+                };
+                if let Some(m) = provider.verify.find(line) {
+                    push(PaymentFlowKind::Webhook, m.as_str());
+                }
+                for cap in provider.event.captures_iter(line) {
+                    push(PaymentFlowKind::Event, &cap[1]);
+                }
+                if let Some(m) = provider.checkout.find(line) {
+                    push(PaymentFlowKind::Checkout, m.as_str());
+                }
+            }
+
+            let evidence = || trimmed.chars().take(160).collect::<String>().trim_end().to_string();
+            let provider = providers.first().map(|p| p.key.to_string());
+            if LOG_CALL.is_match(line) && CARD_DATA.is_match(line) && !MASKED.is_match(line) {
+                findings.push(PciFinding {
+                    kind: PciFindingKind::CardDataLogged,
+                    provider: provider.clone(),
+                    handler: handler.clone(),
+                    evidence: evidence(),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                });
+            } else if REQUEST_CARD_DATA.is_match(line) {
+                findings.push(PciFinding {
+                    kind: PciFindingKind::RawCardData,
+                    provider,
+                    handler: handler.clone(),
+                    evidence: evidence(),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                });
+            }
+        }
+
+        // Event handling with no signature check trusts whoever can reach the route
+        if REQUEST_BODY.is_match(content) {
+            for provider in &providers {
+                let handles_events = flows.iter().any(|f| f.provider == provider.key && f.kind == PaymentFlowKind::Event);
+                let verifies = flows.iter().any(|f| f.provider == provider.key && f.kind == PaymentFlowKind::Webhook);
+                if !handles_events || verifies {
+                    continue;
+                }
+                let first = flows.iter().find(|f| f.provider == provider.key && f.kind == PaymentFlowKind::Event).unwrap();
+                let line = first.line_number.and_then(|n| lines.get(n - 1)).map(|l| l.trim()).unwrap_or("");
+                findings.push(PciFinding {
+                    kind: PciFindingKind::UnverifiedWebhook,
+                    provider: Some(provider.key.to_string()),
+                    handler: first.handler.clone(),
+                    evidence: line.chars().take(160).collect(),
+                    file_path: file_path.to_string(),
+                    line_number: first.line_number,
+                });
+            }
+        }
+
+        flows
+    }
+
+    /// Stripe price/product IDs and subscription plan IDs, in code or config
+    fn detect_ids(&self, content: &str, file_path: &str, language: Option<&str>) -> Vec<DetectedPaymentFlow> {
+        let lines: Vec<&str> = content.lines().collect();
+        let plan_provider = PROVIDERS.iter().find(|p| p.key != "stripe" && p.marker.is_match(content));
+        let mut flows = Vec::new();
+        for (idx, line) in lines.iter().enumerate() {
+            let mut push = |provider: &str, kind: PaymentFlowKind, value: &str| {
+                flows.push(DetectedPaymentFlow {
+                    provider: provider.to_string(),
+                    kind,
+                    value: value.to_string(),
+                    endpoint: None,
+                    handler: language.and_then(|_| enclosing_function(&lines, idx)),
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                    language: language.map(|l| l.to_string()),
+                });
+            };
+            for cap in STRIPE_ID.captures_iter(line) {
+                let kind = if &cap[1] == "price" { PaymentFlowKind::Price } else { PaymentFlowKind::Product };
+                push("stripe", kind, &cap[0]);
+            }
+            if let (Some(provider), Some(cap)) = (plan_provider, PLAN_ID.captures(line)) {
+                push(provider.key, PaymentFlowKind::Price, &cap[1]);
+            }
+        }
+        flows
+    }
+}
+
+/// The name of the function, method or arrow function the line sits in
+fn enclosing_function(lines: &[&str], idx: usize) -> Option<String> {
+    enclosing_function_at(lines, idx).map(|(_, name)| name)
+}
+
+fn enclosing_function_at(lines: &[&str], idx: usize) -> Option<(usize, String)> {
+    (0..=idx).rev().find_map(|i| {
+        FUNCTION_DEF.captures(lines[i])
+            .and_then(|cap| cap.iter().skip(1).flatten().next().map(|m| m.as_str().to_string()))
+            .filter(|name| !NOT_FUNCTIONS.contains(&name.as_str()))
+            .map(|name| (i, name))
+    })
+}
+
+/// The endpoint whose handler runs a flow: a route naming the enclosing function, else the
+/// closest route above it in the same file when the flow sits in an inline handler
+fn link_endpoint(flow: &DetectedPaymentFlow, content: &str, endpoints: &[DetectedEndpoint]) -> Option<String> {
+    let http = || endpoints.iter().filter(|e| e.protocol == EndpointProtocol::Http);
+    let name = |e: &DetectedEndpoint| format!("{} {}", format!("{:?}", e.method).to_uppercase(), e.path);
+    let handler_name = |e: &&DetectedEndpoint| e.handler.as_deref()
+        .and_then(|h| h.rsplit(['.', ':', '#']).next())
+        .map(|h| h.to_string());
+
+    if let Some(handler) = &flow.handler {
+        let named: Vec<&DetectedEndpoint> = http().filter(|e| handler_name(e).as_ref() == Some(handler)).collect();
+        let endpoint = named.iter().find(|e| e.file_path == flow.file_path)
+            .or_else(|| (named.len() == 1).then(|| &named[0]));
+        if let Some(endpoint) = endpoint {
+            return Some(name(endpoint));
+        }
+    }
+
+    let line = flow.line_number?;
+    let lines: Vec<&str> = content.lines().collect();
+    let function_line = enclosing_function_at(&lines, line - 1).map_or(0, |(i, _)| i + 1);
+    http()
+        .filter(|e| e.file_path == flow.file_path)
+        .filter(|e| e.line_number.is_some_and(|l| l <= line && l >= function_line))
+        .max_by_key(|e| e.line_number)
+        .map(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::HttpMethod;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_payment_flows_and_pci_findings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "src/billing.js", "const stripe = require('stripe')(process.env.STRIPE_SECRET_KEY);\n\napp.post('/webhooks/stripe', express.raw({ type: 'application/json' }), (req, res) => {\n  const event = stripe.webhooks.constructEvent(req.body, req.headers['stripe-signature'], secret);\n  if (event.type === 'checkout.session.completed') {\n    fulfil(event.data.object);\n  }\n});\n\nasync function createCheckout(req, res) {\n  console.log('paying with', req.body.cardNumber);\n  const session = await stripe.checkout.sessions.create({\n    line_items: [{ price: 'price_1MoBy5LkdIwHu7ixZhnattbh', quantity: 1 }],\n  });\n  logger.info(`card ending ${maskCardNumber(card)}`);\n}\n");
+        write(root, "app/paypal.py", "import paypalrestsdk\n\n@app.route('/paypal/events', methods=['POST'])\ndef paypal_events():\n    event = request.get_json()\n    if event['event_type'] == 'PAYMENT.CAPTURE.COMPLETED':\n        mark_paid(event)\n");
+        write(root, ".env.example", "STRIPE_PRICE_PRO=price_1NarVqLkdIwHu7ixAbCdEfGh\n");
+
+        let endpoint = |method: HttpMethod, path: &str, handler: Option<&str>, file_path: &str, line: usize| DetectedEndpoint {
+            path: path.to_string(),
+            method,
+            handler: handler.map(|h| h.to_string()),
+            file_path: file_path.to_string(),
+            line_number: Some(line),
+            framework: None,
+            middleware: Vec::new(),
+            parameters: Vec::new(),
+            protocol: EndpointProtocol::Http,
+        };
+        let endpoints = vec![
+            endpoint(HttpMethod::Post, "/webhooks/stripe", None, "src/billing.js", 3),
+            endpoint(HttpMethod::Post, "/checkout", Some("billing.createCheckout"), "src/routes.js", 1),
+            endpoint(HttpMethod::Post, "/paypal/events", Some("paypal_events"), "app/paypal.py", 3),
+        ];
+
+        let analysis = PaymentDetector::new().detect_flows(temp_dir.path(), &endpoints).unwrap();
+        let find = |provider: &str, kind: PaymentFlowKind| -> Vec<(&str, Option<&str>)> {
+            let mut flows: Vec<_> = analysis.flows.iter()
+                .filter(|f| f.provider == provider && f.kind == kind)
+                .map(|f| (f.value.as_str(), f.endpoint.as_deref()))
+                .collect();
+            flows.sort();
+            flows
+        };
+
+        assert_eq!(find("stripe", PaymentFlowKind::Webhook), vec![("webhooks.constructEvent", Some("POST /webhooks/stripe"))]);
+        assert_eq!(find("stripe", PaymentFlowKind::Event), vec![("checkout.session.completed", Some("POST /webhooks/stripe"))]);
+        assert_eq!(find("stripe", PaymentFlowKind::Checkout), vec![("checkout.sessions.create", Some("POST /checkout"))]);
+        assert_eq!(find("stripe", PaymentFlowKind::Price), vec![("price_1MoBy5LkdIwHu7ixZhnattbh", Some("POST /checkout")), ("price_1NarVqLkdIwHu7ixAbCdEfGh", None)]);
+        assert_eq!(find("paypal", PaymentFlowKind::Event), vec![("PAYMENT.CAPTURE.COMPLETED", Some("POST /paypal/events"))]);
+
+        let mut findings: Vec<(&str, Option<usize>, PciFindingKind)> = analysis.pci_findings.iter()
+            .map(|f| (f.file_path.as_str(), f.line_number, f.kind))
+            .collect();
+        findings.sort_by_key(|(file_path, line, _)| (*file_path, *line));
+        assert_eq!(findings, vec![
+            ("app/paypal.py", Some(6), PciFindingKind::UnverifiedWebhook),
+            ("src/billing.js", Some(11), PciFindingKind::CardDataLogged),
+        ]);
+    }
+
+    #[test]
+    fn test_ignores_unmarked_commented_and_verified_payment_code() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        // Event names and order calls without a provider import are someone else's orders
+        write(root, "src/orders.js", "const event = req.body;\nif (event.type === 'invoice.paid') {\n  orders.create(event);\n}\n");
+        write(root, "src/legacy.js", "const stripe = require('stripe')(key);\n// stripe.checkout.sessions.create({ mode: 'payment' })\nconst plan = 'price_basic';\nconst short = 'price_12345';\nlogger.info('card ending', last4(cardNumber));\n");
+        write(root, "app/braintree_hooks.py", "def braintree_webhook():\n    payload = request.data\n    notification = gateway.webhook_notification.parse(payload['bt_signature'], payload['bt_payload'])\n    if notification.kind == braintree.WebhookNotification.Kind.SubscriptionCanceled:\n        cancel(notification)\n");
+        write(root, "node_modules/stripe/lib/webhooks.js", "const stripe = require('stripe');\nstripe.webhooks.constructEvent(body, sig, secret);\n");
+
+        let analysis = PaymentDetector::new().detect_flows(root, &[]).unwrap();
+        let mut flows: Vec<(&str, &str, Option<&str>)> = analysis.flows.iter()
+            .map(|f| (f.provider.as_str(), f.value.as_str(), f.handler.as_deref()))
+            .collect();
+        flows.sort();
+        assert_eq!(flows, vec![
+            ("braintree", "SubscriptionCanceled", Some("braintree_webhook")),
+            ("braintree", "webhook_notification.parse", Some("braintree_webhook")),
+        ]);
+        assert!(analysis.flows.iter().all(|f| f.endpoint.is_none()));
+        // Braintree's parse verifies the payload, and the logged card number is masked
+        assert!(analysis.pci_findings.is_empty(), "{:?}", analysis.pci_findings);
+    }
+}
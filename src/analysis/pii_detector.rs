@@ -0,0 +1,361 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PiiCategory {
+    Email,
+    Phone,
+    NationalId, // SSN, national ID, tax ID or passport number
+    DateOfBirth,
+    PostalAddress,
+    IpAddress,
+    Name,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PiiSink {
+    Logging,    // Logger or print call
+    Analytics,  // Analytics, product telemetry or error tracking SDK
+    ThirdParty, // HTTP request to an external host
+}
+
+/// A model, schema or table field holding personal data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedPiiField {
+    pub category: PiiCategory,
+    pub model: String, // Enclosing class, struct, interface, schema or table
+    pub field: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+}
+
+/// Personal data passed to a logger, an analytics SDK or an external HTTP call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedPiiFlow {
+    pub category: PiiCategory,
+    pub field: String, // The identifier or key as written
+    pub sink: PiiSink,
+    pub destination: String, // "log", the analytics provider, or the external host
+    pub evidence: String, // The call's first line, trimmed
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PiiAnalysis {
+    pub fields: Vec<DetectedPiiField>,
+    pub flows: Vec<DetectedPiiFlow>,
+}
+
+/// Matched against identifiers normalized to snake_case, so `userEmail`, `user_email` and `$email` all hit
+static CATEGORIES: Lazy<Vec<(PiiCategory, Regex)>> = Lazy::new(|| vec![
+    (PiiCategory::Email, Regex::new(r"^(?:\w+_)?e_?mail(?:_address|_addr)?$").unwrap()),
+    (PiiCategory::Phone, Regex::new(r"^(?:\w+_)?(?:phone(?:_number|_no)?|mobile(?:_number|_phone)?|cell_?phone|msisdn|telephone)$").unwrap()),
+    (PiiCategory::NationalId, Regex::new(r"^(?:\w+_)?(?:ssn|social_security(?:_number|_no)?|national_id(?:_number)?|tax_id|tin|passport(?:_number|_no))$").unwrap()),
+    (PiiCategory::DateOfBirth, Regex::new(r"^(?:\w+_)?(?:dob|date_of_birth|birth_?date|birthday)$").unwrap()),
+    (PiiCategory::PostalAddress, Regex::new(r"^(?:\w+_)?(?:street(?:_address)?|home_address|mailing_address|postal_address|address_line_?\d?|postal_code|zip_?code|postcode)$").unwrap()),
+    (PiiCategory::IpAddress, Regex::new(r"^(?:\w+_)?(?:ip_?address|ip_addr|remote_addr|client_ip)$").unwrap()),
+    (PiiCategory::Name, Regex::new(r"^(?:first_name|last_name|full_name|given_name|family_name|surname|legal_name)$").unwrap()),
+]);
+
+static IDENTIFIER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$?[A-Za-z_][A-Za-z0-9_]*").unwrap());
+static STRING_LITERAL: Lazy<Regex> = Lazy::new(|| Regex::new(r#"'[^'\n]*'|"[^"\n]*"|`[^`]*`"#).unwrap());
+/// `${user.email}`, `{user.email}` in f-strings, `#{user.email}` in Ruby
+static INTERPOLATION: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{([^{}]+)\}").unwrap());
+static CAMEL_BOUNDARY: Lazy<Regex> = Lazy::new(|| Regex::new(r"([a-z0-9])([A-Z])").unwrap());
+
+/// `email: string`, `email = models.EmailField()`, `private String email;`, `Email string \`json:"email"\``, `email VARCHAR(255)`, `email String @unique`
+static FIELD_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(?:pub(?:\([^)]*\))?\s+|readonly\s+)?(\w+)\??\s*:\s*[\w\[{'"]|^\s*(\w+)\s*(?::\s*[\w\[\].]+\s*)?=\s*(?:models|db|sa|fields|serializers|mongoose)?\.?(?:\w*Field|Column|mapped_column|column|String|Email)\b|^\s*(?:@\w+(?:\([^)]*\))?\s+)*(?:private|public|protected|internal)\s+(?:final\s+)?[\w<>?\[\]]+\s+(\w+)\s*[;={]|^\s*([A-Z]\w*)\s+\*?[\w.\[\]]+\s+`|^\s*(\w+)\s+(?i:varchar|char|text|citext|date|timestamp|inet|integer|bigint|numeric|string|datetime)\b"#).unwrap()
+});
+/// `class User`, `struct Customer`, `interface Profile`, `type Account struct`, `model User {`, `CREATE TABLE users`, `const UserSchema = new Schema(`
+static CONTAINER_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(?:export\s+)?(?:pub(?:\([^)]*\))?\s+)?(?:(?:public|private|protected|internal|abstract|final|data|sealed|open)\s+)*(?:class|struct|interface|record|model|type)\s+(\w+)|^\s*(?i:create\s+table)\s+(?:if\s+not\s+exists\s+)?[`"\[]?(?:\w+[`"\]]?\.[`"\[]?)?(\w+)|^\s*(?:export\s+)?(?:const|let|var)\s+(\w+)\s*=\s*(?:new\s+)?(?:mongoose\.)?Schema\s*\("#).unwrap()
+});
+
+static LOG_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:console|logger|log|logging|slog|tracing|Rails\.logger|LOG|LOGGER)\s*(?:\.|::)\s*(?:log|info|debug|warn|warning|error|trace|fatal|critical|exception)\s*!?\s*\(|\b(?:print|println!|eprintln!|printf|puts)\s*[(\s]|System\.(?:out|err)\.print\w*\s*\(").unwrap()
+});
+static ANALYTICS_CALLS: Lazy<Vec<(&'static str, Regex)>> = Lazy::new(|| vec![
+    ("segment", Regex::new(r"\b(?:analytics|Analytics)\.(?:identify|track|page|group|alias)\s*\(").unwrap()),
+    ("mixpanel", Regex::new(r"\bmixpanel\.(?:track|identify|register(?:_once)?|people\.set(?:_once)?)\s*\(").unwrap()),
+    ("amplitude", Regex::new(r"\bamplitude(?:\.getInstance\(\))?\.(?:track|logEvent|identify|setUserId|setUserProperties)\s*\(").unwrap()),
+    ("posthog", Regex::new(r"\bposthog\.(?:capture|identify|people\.set|group)\s*\(").unwrap()),
+    ("google_analytics", Regex::new(r#"\bgtag\s*\(|\bga\s*\(\s*['"](?:send|set)|\bReactGA\.\w+\s*\("#).unwrap()),
+    ("heap", Regex::new(r"\bheap\.(?:identify|track|addUserProperties|addEventProperties)\s*\(").unwrap()),
+    ("fullstory", Regex::new(r"\bFS\.(?:identify|setUserVars|event)\s*\(").unwrap()),
+    ("intercom", Regex::new(r#"\bIntercom\s*\(\s*['"](?:boot|update|trackEvent)"#).unwrap()),
+    ("sentry", Regex::new(r"\bSentry\.(?:setUser|setContext|setExtra|setTag|configureScope)\s*\(|\bsentry_sdk\.(?:set_user|set_context|set_extra|set_tag)\s*\(").unwrap()),
+    ("datadog", Regex::new(r"\bdatadogRum\.(?:setUser|setUserProperty|addAction|setGlobalContextProperty)\s*\(").unwrap()),
+]);
+/// `axios.post('https://crm.example.com/...'`, `requests.post("https://...`, `fetch('https://...`
+static EXTERNAL_REQUEST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:axios|requests|httpx|got|superagent|http|client|session)\.(?:post|put|patch|request)\s*\(\s*['"`]https?://([\w.\-]+)|\bfetch\s*\(\s*['"`]https?://([\w.\-]+)"#).unwrap()
+});
+/// Hashed, masked or redacted values are not personal data anymore
+static PROTECTED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)hash|sha\d|md5|mask|redact|anonymi[sz]|pseudonymi[sz]|encrypt|obfuscat|scrub|last_?4|\*\*\*\*").unwrap()
+});
+const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "0.0.0.0"];
+/// How far a multi-line call is followed looking for its closing parenthesis
+const MAX_CALL_LINES: usize = 10;
+
+pub struct PiiDetector;
+
+impl PiiDetector {
+    pub fn new() -> Self {
+        PiiDetector
+    }
+
+    /// Detect model fields holding personal data and calls that pass it to loggers, analytics
+    /// SDKs or external hosts
+    pub fn detect_pii(&self, repo_path: &Path) -> Result<PiiAnalysis> {
+        let mut analysis = PiiAnalysis::default();
+        let mut seen_fields = HashSet::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name, &path_str) {
+                continue;
+            }
+
+            let is_schema = file_name.ends_with(".sql") || file_name.ends_with(".prisma");
+            let language = utils::detect_language(path);
+            if !is_schema && language.is_none() {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            if utils::is_minified_or_compiled(&content, &path_str) {
+                continue;
+            }
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            for field in self.detect_fields(&content, &normalized_path, language.as_deref()) {
+                if seen_fields.insert((field.model.clone(), field.field.clone(), field.file_path.clone())) {
+                    analysis.fields.push(field);
+                }
+            }
+            if !is_schema {
+                analysis.flows.extend(self.detect_flows(&content, &normalized_path, language.as_deref()));
+            }
+        }
+
+        Ok(analysis)
+    }
+
+    fn detect_fields(&self, content: &str, file_path: &str, language: Option<&str>) -> Vec<DetectedPiiField> {
+        let mut fields = Vec::new();
+        let mut model: Option<String> = None;
+
+        for (idx, line) in content.lines().enumerate() {
+            if is_comment(line) {
+                continue;
+            }
+            if let Some(cap) = CONTAINER_DEF.captures(line) {
+                model = cap.iter().skip(1).flatten().next().map(|m| m.as_str().to_string());
+                continue;
+            }
+            // A top-level line other than a decorator ends the previous container
+            if line.starts_with(|c: char| !c.is_whitespace()) && !line.starts_with('@') {
+                model = None;
+                continue;
+            }
+            let Some(model) = &model else { continue };
+            let Some(name) = FIELD_DEF.captures(line).and_then(|cap| cap.iter().skip(1).flatten().next().map(|m| m.as_str().to_string())) else {
+                continue;
+            };
+            if let Some(category) = categorize(&name) {
+                fields.push(DetectedPiiField {
+                    category,
+                    model: model.clone(),
+                    field: name,
+                    file_path: file_path.to_string(),
+                    line_number: Some(idx + 1),
+                    language: language.map(|l| l.to_string()),
+                });
+            }
+        }
+
+        fields
+    }
+
+    fn detect_flows(&self, content: &str, file_path: &str, language: Option<&str>) -> Vec<DetectedPiiFlow> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut flows = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            if is_comment(line) {
+                continue;
+            }
+
+            let sink = if let Some((provider, m)) = ANALYTICS_CALLS.iter().find_map(|(p, re)| re.find(line).map(|m| (*p, m))) {
+                Some((PiiSink::Analytics, provider.to_string(), m.start()))
+            } else if let Some(cap) = EXTERNAL_REQUEST.captures(line) {
+                let host = cap.iter().skip(1).flatten().next().map(|m| m.as_str().to_lowercase()).unwrap_or_default();
+                (!LOCAL_HOSTS.contains(&host.as_str())).then(|| (PiiSink::ThirdParty, host, cap.get(0).unwrap().start()))
+            } else {
+                LOG_CALL.find(line).map(|m| (PiiSink::Logging, "log".to_string(), m.start()))
+            };
+            let Some((sink, destination, start)) = sink else { continue };
+
+            let call = without_messages(&call_text(&lines, idx, start));
+            if PROTECTED.is_match(&call) {
+                continue;
+            }
+            let mut categories = HashSet::new();
+            for m in IDENTIFIER.find_iter(&call) {
+                // Function calls such as `sendEmail(` name an action, not a value
+                if call[m.end()..].trim_start().starts_with('(') {
+                    continue;
+                }
+                let Some(category) = categorize(m.as_str()) else { continue };
+                if categories.insert(category) {
+                    flows.push(DetectedPiiFlow {
+                        category,
+                        field: m.as_str().trim_start_matches('$').to_string(),
+                        sink,
+                        destination: destination.clone(),
+                        evidence: line.trim().chars().take(160).collect(),
+                        file_path: file_path.to_string(),
+                        line_number: Some(idx + 1),
+                        language: language.map(|l| l.to_string()),
+                    });
+                }
+            }
+        }
+
+        flows
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("//") || trimmed.starts_with('#') || trimmed.starts_with('*') || trimmed.starts_with("--")
+}
+
+/// The PII category an identifier names, if any
+fn categorize(identifier: &str) -> Option<PiiCategory> {
+    let snake = CAMEL_BOUNDARY.replace_all(identifier.trim_start_matches('$'), "${1}_${2}").to_lowercase();
+    CATEGORIES.iter().find(|(_, re)| re.is_match(&snake)).map(|(category, _)| *category)
+}
+
+/// Drops the text of log messages and other prose strings, keeping single-word keys such as
+/// `'email'` and the expressions interpolated into templates
+fn without_messages(call: &str) -> String {
+    STRING_LITERAL.replace_all(call, |cap: &regex::Captures| {
+        let literal = &cap[0];
+        if !literal.contains(char::is_whitespace) {
+            return literal.to_string();
+        }
+        INTERPOLATION.captures_iter(literal).map(|c| format!(" {} ", &c[1])).collect::<String>()
+    }).into_owned()
+}
+
+/// The call starting at `start` on line `idx`, followed across lines until its parentheses balance
+fn call_text(lines: &[&str], idx: usize, start: usize) -> String {
+    let mut text = String::new();
+    let mut depth = 0i32;
+    let mut opened = false;
+    for (offset, line) in lines[idx..].iter().take(MAX_CALL_LINES).enumerate() {
+        let line = if offset == 0 { &line[start..] } else { line };
+        for c in line.chars() {
+            text.push(c);
+            match c {
+                '(' => { depth += 1; opened = true; }
+                ')' => depth -= 1,
+                _ => {}
+            }
+            if opened && depth <= 0 {
+                return text;
+            }
+        }
+        // `puts user.email` and `print user.email` have no parentheses
+        if !opened {
+            return text;
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_pii_fields_and_flows() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "app/models.py", "from django.db import models\n\nclass Customer(models.Model):\n    email = models.EmailField(unique=True)\n    ssn = models.CharField(max_length=11)\n    plan = models.CharField(max_length=20)\n");
+        write(root, "src/signup.ts", "export interface SignupForm {\n  phoneNumber: string;\n  password: string;\n}\n\nexport async function signup(form: SignupForm, user: User) {\n  console.log('signing up', user.email);\n  analytics.identify(user.id, {\n    email: user.email,\n    plan: user.plan,\n  });\n  logger.info(`user ${hashEmail(user.email)} signed up`);\n  await sendEmail(user.id);\n  await axios.post('https://crm.example.com/contacts', { phone: form.phoneNumber });\n  await fetch('http://localhost:3000/internal', { body: user.email });\n}\n");
+        write(root, "db/schema.sql", "CREATE TABLE users (\n  id SERIAL PRIMARY KEY,\n  date_of_birth DATE,\n  created_at TIMESTAMP\n);\n");
+
+        let analysis = PiiDetector::new().detect_pii(temp_dir.path()).unwrap();
+
+        let mut fields: Vec<(&str, &str, PiiCategory)> = analysis.fields.iter()
+            .map(|f| (f.model.as_str(), f.field.as_str(), f.category))
+            .collect();
+        fields.sort();
+        assert_eq!(fields, vec![
+            ("Customer", "email", PiiCategory::Email),
+            ("Customer", "ssn", PiiCategory::NationalId),
+            ("SignupForm", "phoneNumber", PiiCategory::Phone),
+            ("users", "date_of_birth", PiiCategory::DateOfBirth),
+        ]);
+
+        let mut flows: Vec<(Option<usize>, PiiSink, &str, PiiCategory)> = analysis.flows.iter()
+            .map(|f| (f.line_number, f.sink, f.destination.as_str(), f.category))
+            .collect();
+        flows.sort_by_key(|(line, _, _, _)| *line);
+        assert_eq!(flows, vec![
+            (Some(7), PiiSink::Logging, "log", PiiCategory::Email),
+            (Some(8), PiiSink::Analytics, "segment", PiiCategory::Email),
+            (Some(14), PiiSink::ThirdParty, "crm.example.com", PiiCategory::Phone),
+        ]);
+    }
+
+    #[test]
+    fn test_skips_lookalike_fields_prose_and_masked_values() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        write(root, "app/models.py", "class Account(models.Model):\n    email_verified = models.BooleanField(default=False)\n    username = models.CharField(max_length=30)\n\nemail = models.EmailField()\n");
+        write(root, "app/legacy.py", "print user.ssn\n");
+        write(root, "src/audit.js", "// console.log(user.email);\nconsole.log('email sent to customer');\nlogger.info(maskEmail(user.email));\nlogger.warn(\n  'retrying delivery',\n  user.phone,\n);\n");
+
+        let analysis = PiiDetector::new().detect_pii(root).unwrap();
+        // Lookalike names are not personal data, and a top-level line is outside the class
+        assert!(analysis.fields.is_empty(), "{:?}", analysis.fields);
+
+        let mut flows: Vec<(&str, Option<usize>, PiiSink, PiiCategory)> = analysis.flows.iter()
+            .map(|f| (f.file_path.as_str(), f.line_number, f.sink, f.category))
+            .collect();
+        flows.sort_by_key(|(file_path, line, _, _)| (*file_path, *line));
+        assert_eq!(flows, vec![
+            ("app/legacy.py", Some(1), PiiSink::Logging, PiiCategory::NationalId),
+            ("src/audit.js", Some(4), PiiSink::Logging, PiiCategory::Phone),
+        ]);
+    }
+}
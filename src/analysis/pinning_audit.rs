@@ -0,0 +1,427 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::artifact_inventory::image_references;
+use crate::analysis::repo_path::RepoPath;
+use crate::analysis::dependencies::DependencyManifest;
+use crate::analysis::{FindingSeverity, PackageManager};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PinnedItemKind {
+    Dependency, // Manifest dependency
+    Image,      // Dockerfile `FROM`, compose/manifest/CI `image:`
+    Action,     // GitHub Actions `uses:`
+    Install,    // `curl | sh`, `pip install`, `npm install -g`, `go install`, `cargo install` in Dockerfiles, CI and scripts
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinningFinding {
+    pub rule: String,
+    pub severity: FindingSeverity,
+    pub kind: PinnedItemKind,
+    pub subject: String,         // Package, image, action or install command
+    pub version: Option<String>, // The floating version, tag or ref
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PinningAudit {
+    /// 0-100: share of checked references that resolve to the same thing on every build,
+    /// with partially pinned references (tags without digests, ranges without a lockfile) counting partly
+    pub score: f64,
+    pub checked: usize,
+    pub pinned: usize,
+    pub findings: Vec<PinningFinding>,
+}
+
+/// Lock files that pin every version a manifest's ranges resolve to
+const LOCKFILES: &[(PackageManager, &[&str])] = &[
+    (PackageManager::Npm, &["package-lock.json", "npm-shrinkwrap.json", "yarn.lock", "pnpm-lock.yaml", "bun.lockb", "bun.lock"]),
+    (PackageManager::Pip, &["poetry.lock", "Pipfile.lock", "uv.lock", "pdm.lock"]),
+    (PackageManager::Cargo, &["Cargo.lock"]),
+    (PackageManager::Go, &["go.sum"]),
+    (PackageManager::Composer, &["composer.lock"]),
+    (PackageManager::Gradle, &["gradle.lockfile"]),
+    (PackageManager::NuGet, &["packages.lock.json"]),
+    (PackageManager::SwiftPackageManager, &["Package.resolved"]),
+    (PackageManager::CocoaPods, &["Podfile.lock"]),
+    (PackageManager::Unity, &["packages-lock.json"]),
+];
+
+/// Branch names an action ref can float on
+const BRANCH_REFS: &[&str] = &["main", "master", "develop", "dev", "trunk", "HEAD", "latest", "stable", "nightly"];
+
+static ACTION_USES: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*-?\s*uses:\s*["']?([^\s"'#]+)["']?"#).unwrap()
+});
+/// `curl ... | sh`, `wget -qO- ... | sudo bash`, `sh -c "$(curl ...)"`, `bash <(curl ...)`
+static PIPE_TO_SHELL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:curl|wget)\b[^|\n]*\|\s*(?:sudo\s+(?:-\S+\s+)*)?(?:ba|z|k|da)?sh\b|\b(?:ba|z)?sh\s+(?:-c\s+)?["']?(?:\$\(|<\()\s*(?:curl|wget)\b"#).unwrap()
+});
+static TOOL_INSTALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(pip3?|python3?\s+-m\s+pip|npm|go|cargo)\s+(install|i)\b([^;&|\n]*)").unwrap()
+});
+static COMMIT_SHA: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[0-9a-f]{40}$").unwrap());
+
+/// Finds versions that float between builds (`latest` and untagged images, `*` and unlocked ranges,
+/// actions on branches or tags, `curl | sh` and unversioned tool installs) across manifests,
+/// Dockerfiles, compose/Kubernetes files, CI configs and scripts
+pub struct PinningAuditor;
+
+impl PinningAuditor {
+    pub fn new() -> Self {
+        PinningAuditor
+    }
+
+    pub fn audit(&self, repo_path: &Path, manifests: &[DependencyManifest]) -> Result<PinningAudit> {
+        let mut audit = PinningAudit::default();
+        let mut penalty = 0.0;
+        let mut record = |audit: &mut PinningAudit, finding: Option<PinningFinding>| {
+            audit.checked += 1;
+            match finding {
+                Some(finding) => {
+                    penalty += match finding.severity {
+                        FindingSeverity::High => 1.0,
+                        FindingSeverity::Medium => 0.5,
+                        FindingSeverity::Low => 0.25,
+                    };
+                    audit.findings.push(finding);
+                }
+                None => audit.pinned += 1,
+            }
+        };
+
+        for manifest in manifests {
+            let dir = Path::new(&manifest.file_path).parent().unwrap_or(Path::new(""));
+            let locked = LOCKFILES.iter()
+                .find(|(manager, _)| *manager == manifest.package_manager)
+                .map(|(_, files)| files.iter().any(|f| repo_path.join(dir).join(f).is_file()))
+                .unwrap_or(false);
+            let content = std::fs::read_to_string(repo_path.join(&manifest.file_path)).unwrap_or_default();
+            let mut unlocked_ranges = 0;
+            for dependency in &manifest.dependencies {
+                if dependency.version == "unknown" {
+                    continue;
+                }
+                let finding = match version_pinning(&manifest.package_manager, &dependency.version) {
+                    None | Some(Pinning::Exact) => None,
+                    Some(Pinning::Floating) => Some(("floating-dependency-version",
+                        if locked { FindingSeverity::Medium } else { FindingSeverity::High },
+                        format!("{} accepts any version ('{}'){}", dependency.name, dependency.version,
+                            if locked { "; only the lock file keeps builds repeatable" } else { " and nothing locks it" }))),
+                    Some(Pinning::Range) if !locked => {
+                        unlocked_ranges += 1;
+                        Some(("dependency-range-without-lockfile", FindingSeverity::Low,
+                            format!("{} is a range ('{}') and no lock file pins what it resolves to", dependency.name, dependency.version)))
+                    }
+                    Some(Pinning::Range) => None,
+                };
+                record(&mut audit, finding.map(|(rule, severity, message)| PinningFinding {
+                    rule: rule.to_string(),
+                    severity,
+                    kind: PinnedItemKind::Dependency,
+                    subject: dependency.name.clone(),
+                    version: Some(dependency.version.clone()),
+                    file_path: manifest.file_path.clone(),
+                    line_number: line_of(&content, &dependency.name),
+                    message,
+                }));
+            }
+            if unlocked_ranges > 0 {
+                audit.findings.push(PinningFinding {
+                    rule: "missing-lockfile".to_string(),
+                    severity: FindingSeverity::Medium,
+                    kind: PinnedItemKind::Dependency,
+                    subject: manifest.file_path.clone(),
+                    version: None,
+                    file_path: manifest.file_path.clone(),
+                    line_number: None,
+                    message: format!("{} version range(s) resolve to whatever is newest at install time; commit a lock file", unlocked_ranges),
+                });
+            }
+        }
+
+        for entry in WalkDir::new(repo_path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                name != "node_modules" && name != "target" && name != ".git" && name != "vendor"
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+            let file_path = RepoPath::new(repo_path, path).into_string();
+            let is_yaml = file_name.ends_with(".yml") || file_name.ends_with(".yaml");
+            let is_dockerfile = file_name == "dockerfile" || file_name.starts_with("dockerfile.") || file_name.ends_with(".dockerfile");
+            let is_workflow = is_yaml && (file_path.starts_with(".github/workflows/") || file_name == "action.yml" || file_name == "action.yaml");
+            let is_ci = is_workflow || file_name == ".gitlab-ci.yml" || file_path.starts_with(".circleci/")
+                || file_name == "azure-pipelines.yml" || file_name == "bitbucket-pipelines.yml" || file_name == "jenkinsfile";
+            let is_script = file_name.ends_with(".sh") || file_name == "makefile" || file_name == "justfile";
+            if !(is_yaml || is_dockerfile || is_ci || is_script) {
+                continue;
+            }
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            if is_yaml || is_dockerfile {
+                for image in image_references(&content, &file_path, is_yaml) {
+                    record(&mut audit, image_finding(&image.name, &file_path, image.line_number));
+                }
+            }
+            if is_workflow {
+                for (index, line) in content.lines().enumerate() {
+                    if let Some(caps) = ACTION_USES.captures(line) {
+                        if let Some(checked) = action_finding(&caps[1], &file_path, index + 1) {
+                            record(&mut audit, checked);
+                        }
+                    }
+                }
+            }
+            if is_dockerfile || is_ci || is_script {
+                for (index, line) in content.lines().enumerate() {
+                    if line.trim_start().starts_with('#') {
+                        continue;
+                    }
+                    for finding in install_findings(line, &file_path, index + 1) {
+                        record(&mut audit, finding);
+                    }
+                }
+            }
+        }
+
+        audit.score = if audit.checked == 0 {
+            100.0
+        } else {
+            ((1.0 - penalty / audit.checked as f64).max(0.0) * 1000.0).round() / 10.0
+        };
+        Ok(audit)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Pinning {
+    Exact,
+    Range,
+    Floating,
+}
+
+/// How tightly a manifest version pins its dependency; `None` when it cannot be told
+fn version_pinning(manager: &PackageManager, version: &str) -> Option<Pinning> {
+    let version = version.trim();
+    let lower = version.to_lowercase();
+    if lower == "unknown" {
+        return None;
+    }
+    if matches!(lower.as_str(), "" | "*" | "x" | "latest" | "next" | "release" | "master" | "main")
+        || lower.starts_with("git") || lower.starts_with("http") || lower.contains("github:")
+    {
+        // Git dependencies pinned to a commit are exact
+        return Some(match lower.rsplit_once('#') {
+            Some((_, reference)) if COMMIT_SHA.is_match(reference) => Pinning::Exact,
+            _ => Pinning::Floating,
+        });
+    }
+    if lower.starts_with("file:") || lower.starts_with("link:") || lower.starts_with("workspace:") || lower.starts_with("path:") {
+        return Some(Pinning::Exact);
+    }
+    let ranged = lower.starts_with(['^', '~', '>', '<', '[', '('])
+        || lower.contains(".x") || lower.contains(".*") || lower.contains("||") || lower.contains(" - ");
+    // Cargo reads a bare `1.2.3` as `^1.2.3`
+    let cargo_caret = *manager == PackageManager::Cargo && !lower.starts_with('=');
+    Some(if ranged || cargo_caret { Pinning::Range } else { Pinning::Exact })
+}
+
+fn image_finding(image: &str, file_path: &str, line_number: Option<usize>) -> Option<PinningFinding> {
+    if image.contains("@sha256:") {
+        return None;
+    }
+    let name = image.rsplit('/').next().unwrap_or(image);
+    let tag = name.split_once(':').map(|(_, tag)| tag);
+    let (rule, severity, message) = match tag {
+        None | Some("latest") => ("floating-image-tag", FindingSeverity::High,
+            format!("{} follows the latest image; pin a version tag and digest", image)),
+        Some(_) => ("image-tag-without-digest", FindingSeverity::Low,
+            format!("{} is pinned by tag only; tags can be re-pushed, add an @sha256 digest", image)),
+    };
+    Some(PinningFinding {
+        rule: rule.to_string(),
+        severity,
+        kind: PinnedItemKind::Image,
+        subject: image.to_string(),
+        version: tag.map(str::to_string),
+        file_path: file_path.to_string(),
+        line_number,
+        message,
+    })
+}
+
+/// `None` when the `uses:` value is not a remote action (local actions, Docker images are
+/// checked with the other images); `Some(None)` when it is pinned to a commit
+fn action_finding(uses: &str, file_path: &str, line_number: usize) -> Option<Option<PinningFinding>> {
+    if uses.starts_with("./") || uses.starts_with("docker://") || uses.contains("${{") {
+        return None;
+    }
+    let (action, reference) = match uses.split_once('@') {
+        Some((action, reference)) => (action, Some(reference)),
+        None => (uses, None),
+    };
+    let (rule, severity, message) = match reference {
+        Some(r) if COMMIT_SHA.is_match(r) => return Some(None),
+        Some(r) if !BRANCH_REFS.contains(&r) => ("action-tag-not-sha", FindingSeverity::Low,
+            format!("{} is pinned to tag '{}'; tags can be moved, pin the full commit SHA", action, r)),
+        _ => ("unpinned-action", FindingSeverity::High,
+            format!("{} runs whatever is on {}; pin the full commit SHA", action, reference.map(|r| format!("branch '{}'", r)).unwrap_or_else(|| "its default branch".to_string()))),
+    };
+    Some(Some(PinningFinding {
+        rule: rule.to_string(),
+        severity,
+        kind: PinnedItemKind::Action,
+        subject: action.to_string(),
+        version: reference.map(str::to_string),
+        file_path: file_path.to_string(),
+        line_number: Some(line_number),
+        message,
+    }))
+}
+
+/// Each install in a shell line, `None` for the ones that pin a version
+fn install_findings(line: &str, file_path: &str, line_number: usize) -> Vec<Option<PinningFinding>> {
+    let mut results = Vec::new();
+    let finding = |rule: &str, severity: FindingSeverity, subject: &str, message: String| Some(PinningFinding {
+        rule: rule.to_string(),
+        severity,
+        kind: PinnedItemKind::Install,
+        subject: subject.to_string(),
+        version: None,
+        file_path: file_path.to_string(),
+        line_number: Some(line_number),
+        message,
+    });
+
+    if let Some(m) = PIPE_TO_SHELL.find(line) {
+        results.push(finding("curl-pipe-shell", FindingSeverity::High, m.as_str().trim(),
+            "Downloads a script and runs it unverified; whatever the URL serves today is what the build installs".to_string()));
+    }
+
+    for caps in TOOL_INSTALL.captures_iter(line) {
+        let tool = caps[1].split_whitespace().last().unwrap_or("");
+        let args: Vec<&str> = caps[3].split_whitespace().collect();
+        let global = args.iter().any(|a| *a == "-g" || *a == "--global");
+        let packages: Vec<&str> = skip_flag_values(&args, &["-r", "-c", "-i", "--index-url", "--extra-index-url", "--requirement", "--constraint", "--target", "--prefix", "--root", "--path", "--git", "--version"])
+            .into_iter()
+            .filter(|a| !a.starts_with(['.', '/', '$', '"', '\'']) && !a.ends_with(".whl") && !a.ends_with(".txt") && !a.contains("=="))
+            .collect();
+        let unpinned: Vec<&str> = match tool {
+            // Upgrading the installer itself is routine and not part of the build's output
+            "pip" | "pip3" => packages.into_iter()
+                .filter(|p| !matches!(*p, "pip" | "setuptools" | "wheel") && (!p.starts_with("git+") || !p.contains('@')))
+                .collect(),
+            // Project installs (`npm install`, `npm i`) follow the lock file; only global tools float
+            "npm" if global => packages.into_iter().filter(|p| !p.trim_start_matches('@').contains('@')).collect(),
+            "go" => packages.into_iter().filter(|p| !p.contains('@') || p.ends_with("@latest")).collect(),
+            "cargo" if args.contains(&"--version") => Vec::new(),
+            "cargo" => packages.into_iter().filter(|p| !p.contains('@')).collect(),
+            _ => continue,
+        };
+        if unpinned.is_empty() {
+            results.push(None);
+        }
+        for package in unpinned {
+            results.push(finding("unpinned-tool-install", FindingSeverity::Medium, package,
+                format!("`{} install {}` installs whatever version is newest; pin one", tool, package)));
+        }
+    }
+    results
+}
+
+fn skip_flag_values<'a>(args: &[&'a str], flags_with_value: &[&str]) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut skip = false;
+    for arg in args {
+        if skip {
+            skip = false;
+        } else if flags_with_value.contains(arg) {
+            skip = true;
+        } else if !arg.starts_with('-') {
+            result.push(*arg);
+        }
+    }
+    result
+}
+
+fn line_of(content: &str, name: &str) -> Option<usize> {
+    content.lines().position(|l| l.contains(name)).map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn audit_repository(repo_path: &Path) -> PinningAudit {
+        let manifests = crate::analysis::DependencyExtractor::new().extract_from_repository(repo_path).unwrap();
+        PinningAuditor::new().audit(repo_path, &manifests).unwrap()
+    }
+
+    #[test]
+    fn test_audits_floating_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{
+  "dependencies": { "express": "^4.18.0", "lodash": "*", "left-pad": "1.3.0" }
+}"#).unwrap();
+        fs::write(temp_dir.path().join("Dockerfile"), "\
+FROM node:20 AS build
+RUN curl -fsSL https://get.example.sh | sh && pip install requests flask==3.0.0
+FROM node@sha256:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+").unwrap();
+        fs::create_dir_all(temp_dir.path().join(".github/workflows")).unwrap();
+        fs::write(temp_dir.path().join(".github/workflows/ci.yml"), "\
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@8f152de45cc393bb48ce5d89d36b731f54556e65
+      - uses: some/action@main
+      - uses: ./.github/actions/local
+").unwrap();
+
+        let audit = audit_repository(temp_dir.path());
+        let rules = |rule: &str| audit.findings.iter().filter(|f| f.rule == rule).map(|f| f.subject.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(rules("floating-dependency-version"), vec!["lodash"]);
+        assert_eq!(rules("dependency-range-without-lockfile"), vec!["express"]);
+        assert_eq!(rules("missing-lockfile"), vec!["package.json"]);
+        assert_eq!(rules("image-tag-without-digest"), vec!["node:20"]);
+        assert_eq!(rules("action-tag-not-sha"), vec!["actions/checkout"]);
+        assert_eq!(rules("unpinned-action"), vec!["some/action"]);
+        assert_eq!(rules("curl-pipe-shell").len(), 1);
+        assert_eq!(rules("unpinned-tool-install"), vec!["requests"]);
+
+        let lodash = audit.findings.iter().find(|f| f.subject == "lodash").unwrap();
+        assert_eq!(lodash.severity, FindingSeverity::High);
+        assert_eq!(lodash.line_number, Some(2));
+
+        // 3 dependencies, 2 images, 3 remote actions, 2 installs
+        assert_eq!(audit.checked, 10);
+        assert_eq!(audit.pinned, 3);
+        assert!(audit.score > 0.0 && audit.score < 100.0);
+
+        fs::write(temp_dir.path().join("package-lock.json"), "{}").unwrap();
+        let locked = audit_repository(temp_dir.path());
+        assert!(!locked.findings.iter().any(|f| f.rule == "missing-lockfile"));
+        assert_eq!(locked.findings.iter().find(|f| f.subject == "lodash").unwrap().severity, FindingSeverity::Medium);
+    }
+}
@@ -3,6 +3,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use walkdir::WalkDir;
+use crate::analysis::repo_path::RepoPath;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PortType {
@@ -98,7 +99,8 @@ impl PortDetector {
         let mut deduplicated = Vec::new();
         let mut seen = std::collections::HashSet::new();
 
-        for port in ports {
+        for mut port in ports {
+            RepoPath::normalize(repo_path, &mut port.file_path);
             let key = (port.port, port.file_path.clone(), port.line_number);
             if !seen.contains(&key) {
                 seen.insert(key);
@@ -503,4 +505,3 @@ impl PortDetector {
         path_str.contains(".venv\\")
     }
 }
-
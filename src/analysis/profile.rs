@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// How thorough an analysis is. Chosen per request, falling back to the repository's default and
+/// then the server-wide `[analysis] profile`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisProfile {
+    /// Structure and services only: no history, no network, small files
+    Fast,
+    #[default]
+    Standard,
+    /// Everything, with full history and no file size limit
+    Deep,
+}
+
+/// What a profile bundles; see [`AnalysisProfile::settings`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProfileSettings {
+    /// Optional analyzers skipped on top of any configured ones (see [`crate::analysis::repo_config::ANALYZERS`])
+    pub disabled: &'static [&'static str],
+    /// Files larger than this many bytes are left out of the results
+    pub max_file_size: Option<u64>,
+    /// Commits looked at for blame and change history; 0 skips git history entirely
+    pub history_depth: usize,
+    /// Network lookups such as GitHub releases and vulnerability or package registries
+    pub online_lookups: bool,
+}
+
+impl AnalysisProfile {
+    pub fn settings(self) -> ProfileSettings {
+        match self {
+            AnalysisProfile::Fast => ProfileSettings {
+                disabled: &["documentation", "todos", "coverage", "layering", "rules"],
+                max_file_size: Some(256 * 1024),
+                history_depth: 0,
+                online_lookups: false,
+            },
+            AnalysisProfile::Standard => ProfileSettings {
+                disabled: &[],
+                max_file_size: Some(1024 * 1024),
+                history_depth: 1000,
+                online_lookups: true,
+            },
+            AnalysisProfile::Deep => ProfileSettings {
+                disabled: &[],
+                max_file_size: None,
+                history_depth: 10_000,
+                online_lookups: true,
+            },
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnalysisProfile::Fast => "fast",
+            AnalysisProfile::Standard => "standard",
+            AnalysisProfile::Deep => "deep",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<AnalysisProfile> {
+        match name {
+            "fast" => Some(AnalysisProfile::Fast),
+            "standard" => Some(AnalysisProfile::Standard),
+            "deep" => Some(AnalysisProfile::Deep),
+            _ => None,
+        }
+    }
+
+    /// The request's profile, else the repository's default, else the server's
+    pub fn resolve(requested: Option<AnalysisProfile>, repository: Option<AnalysisProfile>, global: Option<AnalysisProfile>) -> AnalysisProfile {
+        requested.or(repository).or(global).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_resolution_and_names() {
+        assert_eq!(AnalysisProfile::resolve(None, None, None), AnalysisProfile::Standard);
+        assert_eq!(AnalysisProfile::resolve(None, Some(AnalysisProfile::Deep), Some(AnalysisProfile::Fast)), AnalysisProfile::Deep);
+        assert_eq!(AnalysisProfile::resolve(Some(AnalysisProfile::Fast), Some(AnalysisProfile::Deep), None), AnalysisProfile::Fast);
+
+        for profile in [AnalysisProfile::Fast, AnalysisProfile::Standard, AnalysisProfile::Deep] {
+            assert_eq!(AnalysisProfile::parse(profile.as_str()), Some(profile));
+        }
+        assert!(!AnalysisProfile::Fast.settings().online_lookups);
+        assert_eq!(AnalysisProfile::Deep.settings().max_file_size, None);
+    }
+}
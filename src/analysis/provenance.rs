@@ -0,0 +1,258 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use walkdir::WalkDir;
+use crate::analysis::artifact_inventory::VENDORED_DIRS;
+use crate::analysis::utils::glob_to_regex;
+
+/// Whether code was written in this repository or copied/emitted into it
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeOrigin {
+    #[default]
+    Authored,
+    Vendored,  // Under vendor/, third_party/ and similar, or marked `linguist-vendored`
+    Generated, // Protobuf/gRPC output, OpenAPI clients, `DO NOT EDIT` headers, or marked `linguist-generated`
+}
+
+impl CodeOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeOrigin::Authored => "authored",
+            CodeOrigin::Vendored => "vendored",
+            CodeOrigin::Generated => "generated",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "authored" => Some(CodeOrigin::Authored),
+            "vendored" => Some(CodeOrigin::Vendored),
+            "generated" => Some(CodeOrigin::Generated),
+            _ => None,
+        }
+    }
+}
+
+/// File name endings that code generators use
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".pb.go", "_grpc.pb.go", ".pb.gw.go", "_pb2.py", "_pb2_grpc.py", "_pb2.pyi", ".pb.cc", ".pb.h",
+    "_pb.js", "_pb.d.ts", "_grpc_pb.js", "_grpc_pb.d.ts", ".pb.ts", ".pb.swift", ".grpc.swift",
+    ".g.dart", ".freezed.dart", ".designer.cs", ".g.cs", ".g.i.cs", "_generated.go", ".gen.go", ".gen.ts",
+    ".generated.ts", ".generated.js", ".generated.cs", ".generated.swift",
+];
+const GENERATED_PREFIXES: &[&str] = &["zz_generated"];
+/// Directories whose whole content is generator output
+const GENERATED_DIRS: &[&str] = &["generated", "__generated__", "gen-src", "generated-sources", "openapi-client", "openapi_client", "swagger-client"];
+/// Comments generators put at the top of their output
+const GENERATED_MARKERS: &[&str] = &[
+    "code generated", "do not edit", "@generated", "auto-generated", "autogenerated", "automatically generated",
+    "generated by the protocol buffer compiler", "openapi generator", "openapi-generator", "swagger codegen", "swagger-codegen",
+];
+/// Lines read from the top of a file when looking for a generator header
+const HEADER_LINES: usize = 15;
+
+/// Classifies files by where their code came from, using directory conventions, file name suffixes,
+/// generator headers, `.gitattributes` linguist attributes and the file lists OpenAPI Generator writes
+#[derive(Debug, Default)]
+pub struct ProvenanceClassifier {
+    root: PathBuf,
+    /// `(pattern, origin, set)` in file order, so later lines win like git's own attribute lookup
+    attributes: Vec<(Regex, CodeOrigin, bool)>,
+    openapi_files: HashSet<String>,
+    cache: Mutex<HashMap<String, CodeOrigin>>,
+}
+
+impl ProvenanceClassifier {
+    pub fn new(root: &Path) -> Self {
+        let attributes = std::fs::read_to_string(root.join(".gitattributes"))
+            .map(|content| parse_gitattributes(&content))
+            .unwrap_or_default();
+
+        // OpenAPI Generator lists every file it wrote in `.openapi-generator/FILES`, relative to the output root
+        let mut openapi_files = HashSet::new();
+        for entry in WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                name != ".git" && name != "node_modules" && name != "target"
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name() == "FILES" && e.path().parent().is_some_and(|p| p.ends_with(".openapi-generator")))
+        {
+            let Some(output_root) = entry.path().parent().and_then(|p| p.parent()) else { continue };
+            let prefix = output_root.strip_prefix(root).map(|p| p.to_string_lossy().replace('\\', "/")).unwrap_or_default();
+            for line in std::fs::read_to_string(entry.path()).unwrap_or_default().lines().map(str::trim).filter(|l| !l.is_empty()) {
+                openapi_files.insert(if prefix.is_empty() { line.to_string() } else { format!("{}/{}", prefix, line) });
+            }
+        }
+
+        ProvenanceClassifier {
+            root: root.to_path_buf(),
+            attributes,
+            openapi_files,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `file_path` is repository-relative; the file's header is read when its path decides nothing
+    pub fn classify(&self, file_path: &str) -> CodeOrigin {
+        if let Some(origin) = self.cache.lock().unwrap().get(file_path) {
+            return *origin;
+        }
+        let origin = self.by_path(file_path).unwrap_or_else(|| {
+            match read_header(&self.root.join(file_path)) {
+                Some(header) if marks_generated(&header) => CodeOrigin::Generated,
+                _ => CodeOrigin::Authored,
+            }
+        });
+        self.cache.lock().unwrap().insert(file_path.to_string(), origin);
+        origin
+    }
+
+    /// Same as [`classify`](Self::classify) for a file whose content is already loaded
+    pub fn classify_content(&self, file_path: &str, content: &str) -> CodeOrigin {
+        let origin = self.by_path(file_path).unwrap_or_else(|| {
+            let header: String = content.lines().take(HEADER_LINES).collect::<Vec<_>>().join("\n");
+            if marks_generated(&header) { CodeOrigin::Generated } else { CodeOrigin::Authored }
+        });
+        self.cache.lock().unwrap().insert(file_path.to_string(), origin);
+        origin
+    }
+
+    fn by_path(&self, file_path: &str) -> Option<CodeOrigin> {
+        // Explicit attributes override the conventions, including `-linguist-vendored` on a vendor/ dir
+        let mut vendored = None;
+        let mut generated = None;
+        for (pattern, origin, set) in &self.attributes {
+            if pattern.is_match(file_path) {
+                match origin {
+                    CodeOrigin::Vendored => vendored = Some(*set),
+                    _ => generated = Some(*set),
+                }
+            }
+        }
+        if generated == Some(true) {
+            return Some(CodeOrigin::Generated);
+        }
+        if vendored == Some(true) {
+            return Some(CodeOrigin::Vendored);
+        }
+
+        let segments: Vec<String> = file_path.split('/').map(|s| s.to_lowercase()).collect();
+        let (file_name, dirs) = segments.split_last()?;
+        if vendored.is_none() && dirs.iter().any(|d| VENDORED_DIRS.contains(&d.as_str())) {
+            return Some(CodeOrigin::Vendored);
+        }
+        if generated == Some(false) {
+            return Some(CodeOrigin::Authored);
+        }
+        let generated_name = GENERATED_SUFFIXES.iter().any(|s| file_name.ends_with(s))
+            || GENERATED_PREFIXES.iter().any(|p| file_name.starts_with(p) && file_name.ends_with(".go"));
+        if generated_name || dirs.iter().any(|d| GENERATED_DIRS.contains(&d.as_str())) || self.openapi_files.contains(file_path) {
+            return Some(CodeOrigin::Generated);
+        }
+        if vendored == Some(false) {
+            return Some(CodeOrigin::Authored);
+        }
+        None
+    }
+}
+
+fn parse_gitattributes(content: &str) -> Vec<(Regex, CodeOrigin, bool)> {
+    let mut attributes = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next() else { continue };
+        // Patterns without a slash match at any depth, like in .gitignore
+        let glob = match pattern.strip_prefix('/') {
+            Some(anchored) => anchored.to_string(),
+            None if !pattern.trim_end_matches('/').contains('/') => format!("**/{}", pattern),
+            None => pattern.to_string(),
+        };
+        for attribute in parts {
+            let (name, set) = match attribute.strip_prefix('-') {
+                Some(name) => (name, false),
+                None => match attribute.split_once('=') {
+                    Some((name, value)) => (name, value != "false"),
+                    None => (attribute, true),
+                },
+            };
+            let origin = match name {
+                "linguist-vendored" => CodeOrigin::Vendored,
+                "linguist-generated" => CodeOrigin::Generated,
+                _ => continue,
+            };
+            attributes.push((glob_to_regex(&glob), origin, set));
+        }
+    }
+    attributes
+}
+
+fn read_header(path: &Path) -> Option<String> {
+    use std::io::{BufRead, BufReader};
+    let file = std::fs::File::open(path).ok()?;
+    let lines: Vec<String> = BufReader::new(file).lines().take(HEADER_LINES).map_while(|l| l.ok()).collect();
+    Some(lines.join("\n"))
+}
+
+fn marks_generated(header: &str) -> bool {
+    let header = header.to_lowercase();
+    GENERATED_MARKERS.iter().any(|marker| header.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_classifies_vendored_and_generated_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, ".gitattributes", "third_party/patched/** -linguist-vendored\nschema/*.ts linguist-generated\n");
+        write(root, "src/app.ts", "export const app = 1;\n");
+        write(root, "src/client.ts", "// Code generated by graphql-codegen. DO NOT EDIT.\nexport {};\n");
+        write(root, "clients/billing/.openapi-generator/FILES", "api.ts\nREADME.md\n");
+        write(root, "clients/billing/api.ts", "export class BillingApi {}\n");
+        write(root, "clients/billing/extra.ts", "export const extra = 1;\n");
+
+        let classifier = ProvenanceClassifier::new(temp_dir.path());
+        assert_eq!(classifier.classify("src/app.ts"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("src/client.ts"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("vendor/github.com/acme/lib/lib.go"), CodeOrigin::Vendored);
+        assert_eq!(classifier.classify("third_party/patched/fix.c"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("third_party/zlib/zlib.c"), CodeOrigin::Vendored);
+        assert_eq!(classifier.classify("api/v1/orders.pb.go"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("protos/orders_pb2.py"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("schema/types.ts"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("clients/billing/api.ts"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("clients/billing/extra.ts"), CodeOrigin::Authored);
+    }
+
+    #[test]
+    fn test_headers_past_the_top_and_unset_attributes_leave_code_authored() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, ".gitattributes", "# api/** linguist-vendored\napi/legacy.pb.go -linguist-generated\nreports/** linguist-generated\nreports/summary.ts linguist-generated=false\n");
+        write(root, "src/late.ts", format!("{}// Code generated by hand-rolled script. DO NOT EDIT.\n", "export const x = 1;\n".repeat(HEADER_LINES)));
+        write(root, "src/generated_report.ts", "export const report = 1;\n");
+
+        let classifier = ProvenanceClassifier::new(root);
+        assert_eq!(classifier.classify("src/late.ts"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("src/generated_report.ts"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("src/missing.ts"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("api/legacy.pb.go"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("api/orders.go"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("reports/daily.ts"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify("reports/summary.ts"), CodeOrigin::Authored);
+        assert_eq!(classifier.classify("Vendor/Acme/lib.go"), CodeOrigin::Vendored);
+        assert_eq!(classifier.classify("pkg/apis/zz_generated.deepcopy.go"), CodeOrigin::Generated);
+        assert_eq!(classifier.classify_content("src/inline.ts", "/* @generated */\nexport {};\n"), CodeOrigin::Generated);
+        // Cached per path, so the file need not exist on disk
+        assert_eq!(classifier.classify("src/inline.ts"), CodeOrigin::Generated);
+    }
+}
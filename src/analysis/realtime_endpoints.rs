@@ -0,0 +1,469 @@
+//! Realtime endpoints: WebSocket servers and handlers (socket.io, `ws`, express-ws, NestJS
+//! gateways, FastAPI/Starlette, Django Channels, Spring STOMP and `WebSocketHandler`s, Jakarta
+//! `@ServerEndpoint`) and server-sent events (NestJS `@Sse`). Plain HTTP routes whose handler
+//! upgrades the connection (actix-ws, axum `WebSocketUpgrade`, warp, gorilla) or streams
+//! `text/event-stream` (`SseEmitter`, `EventSourceResponse`, axum `Sse`) are reclassified in place.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use crate::analysis::endpoint_detector::{DetectedEndpoint, EndpointProtocol, HttpMethod};
+
+/// Lines searched after a server constructor or decorator for its options and declaration
+const MAX_OPTION_LINES: usize = 8;
+/// Lines read from a handler before giving up on finding the end of its body
+const MAX_BODY_LINES: usize = 300;
+
+/// Calls that upgrade the request to a WebSocket inside a handler
+static WEBSOCKET_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"actix_ws::handle|\bws::start\s*\(|\bWebSocketUpgrade\b|\.on_upgrade\s*\(|warp::ws\s*\(\)|[uU]pgrader\.Upgrade\s*\(|websocket\.(?:Accept|accept)\s*\(|\bupgradeWebSocket\s*\(").unwrap()
+});
+/// Responses that stream server-sent events
+static SSE_MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"text/event-stream|TEXT_EVENT_STREAM|\bSseEmitter\b|\bServerSentEvent\b|\bEventSourceResponse\b|\bSse\s*(?:<|::new)|\bstreamSSE\s*\(|actix_web_lab::sse").unwrap()
+});
+
+static SOCKET_IO_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)(?:from\s+|require\s*\(\s*)['"]socket\.io['"]|^\s*(?:import\s+socketio|from\s+(?:flask_socketio|socketio)\s+import)"#).unwrap()
+});
+static WS_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?:from\s+|require\s*\(\s*)['"]ws['"]"#).unwrap());
+/// `new Server(httpServer, {...})` or `require('socket.io')(server, {...})`
+static SOCKET_IO_SERVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"new\s+(?:Server|SocketIOServer)\s*\(|['"]socket\.io['"]\s*\)\s*\("#).unwrap()
+});
+/// `const chat = io.of('/chat')`
+static NAMESPACE_BINDING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:const|let|var)\s+(\w+)\s*=\s*\w+\.of\(\s*['"]([^'"]+)['"]"#).unwrap()
+});
+/// `io.on('connection', onConnection)`, `io.of('/chat').on('connection', ...)`
+static CONNECTION_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(\w+)(?:\.of\(\s*['"]([^'"]+)['"]\s*\))?\.on\(\s*['"]connect(?:ion)?['"]\s*,\s*(?:async\s+)?(?:function\s+)?(\w*)"#).unwrap()
+});
+/// `new WebSocketServer({ port })`, `new WebSocket.Server({ server, path: '/ws' })`
+static WS_SERVER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"new\s+(?:WebSocketServer|\w+\.Server|Server)\s*\(").unwrap()
+});
+/// express-ws: `app.ws('/echo', handler)`
+static EXPRESS_WS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b\w+\.ws\(\s*['"]([^'"]+)['"]\s*(?:,\s*(\w+)\s*\))?"#).unwrap());
+static OPTION_PATH: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bpath\s*:\s*['"]([^'"]+)['"]"#).unwrap());
+static OPTION_NAMESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bnamespace\s*[:=]\s*['"]([^'"]+)['"]"#).unwrap());
+static NEST_GATEWAY: Lazy<Regex> = Lazy::new(|| Regex::new(r"@WebSocketGateway\s*\(").unwrap());
+static NEST_SSE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"@Sse\s*\(\s*(?:['"]([^'"]*)['"])?"#).unwrap());
+static NEST_METHOD: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:async\s+)?(\w+)\s*\(").unwrap());
+/// `@app.websocket("/ws")` (FastAPI), `@app.websocket_route("/ws")` (Starlette)
+static PY_WEBSOCKET_ROUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"@\w+\.websocket(?:_route)?\s*\(\s*['"]([^'"]+)['"]"#).unwrap()
+});
+static STARLETTE_WS_ROUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"WebSocketRoute\(\s*['"]([^'"]+)['"]\s*,\s*([\w.]+)"#).unwrap()
+});
+/// Flask-SocketIO / python-socketio: `@socketio.on('connect', namespace='/chat')`
+static PY_SOCKETIO_CONNECT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"@\w+\.on\(\s*['"]connect['"]"#).unwrap());
+/// `registry.addEndpoint("/ws")` in a STOMP broker configuration
+static STOMP_ENDPOINT: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\.addEndpoint\(\s*"([^"]+)""#).unwrap());
+static MESSAGE_MAPPING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"@MessageMapping\s*\(\s*(?:value\s*=\s*)?\{?\s*"([^"]+)""#).unwrap()
+});
+/// `registry.addHandler(chatHandler(), "/chat")`
+static SPRING_WS_HANDLER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\.addHandler\(\s*(?:new\s+)?(\w+)[^,]*,\s*"([^"]+)""#).unwrap()
+});
+static SERVER_ENDPOINT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"@ServerEndpoint\s*\(\s*(?:value\s*=\s*)?"([^"]+)""#).unwrap()
+});
+
+static FUNCTION_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\bfn|\bdef|\bfunction|\bfunc(?:\s*\([^)]*\))?)\s+(\w+)\s*[(<]").unwrap()
+});
+static JS_ARROW_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s*)?(?:\([^)]*\)|\w+)\s*=>").unwrap()
+});
+static JAVA_METHOD_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:public|private|protected|static|final|synchronized)\s+)+[\w<>\[\],.? ]+\s+(\w+)\s*\(").unwrap()
+});
+static CLASS_DEF: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bclass\s+(\w+)").unwrap());
+/// The handler passed last to a route registration: `app.get('/events', streamEvents)`
+static TRAILING_HANDLER: Lazy<Regex> = Lazy::new(|| Regex::new(r",\s*([\w.]+)\s*\)\s*;?\s*$").unwrap());
+
+/// WebSocket and SSE endpoints declared in one file that the HTTP route patterns don't see
+pub fn detect(content: &str, file_path: &Path, language: &str) -> Vec<DetectedEndpoint> {
+    let lines: Vec<&str> = content.lines().collect();
+    let file_path = file_path.to_string_lossy().to_string();
+    let mut found = Found::new();
+    match language {
+        "javascript" => detect_js(content, &lines, &mut found),
+        "python" => detect_python(content, &lines, &mut found),
+        "java" => detect_java(&lines, &mut found),
+        _ => {}
+    }
+
+    found.into_iter()
+        .map(|(idx, path, handler, framework, protocol)| DetectedEndpoint {
+            path,
+            method: if framework == "stomp" { HttpMethod::Any } else { HttpMethod::Get },
+            handler,
+            file_path: file_path.clone(),
+            line_number: Some(idx + 1),
+            framework: Some(framework.to_string()),
+            middleware: Vec::new(),
+            // Extracted for every endpoint once mount prefixes are applied
+            parameters: Vec::new(),
+            protocol,
+        })
+        .collect()
+}
+
+/// (line index, path, handler, framework, protocol) of each endpoint found
+type Found = Vec<(usize, String, Option<String>, &'static str, EndpointProtocol)>;
+
+fn detect_js(content: &str, lines: &[&str], found: &mut Found) {
+    if SOCKET_IO_IMPORT.is_match(content) {
+        // Connections to the default namespace arrive on the server's path; others are named
+        let server_path = lines.iter().position(|line| SOCKET_IO_SERVER.is_match(line))
+            .and_then(|idx| option_value(lines, idx, &OPTION_PATH))
+            .unwrap_or_else(|| "/socket.io".to_string());
+        let namespaces: HashMap<&str, &str> = NAMESPACE_BINDING.captures_iter(content)
+            .map(|cap| (cap.get(1).unwrap().as_str(), cap.get(2).unwrap().as_str()))
+            .collect();
+        for (idx, line) in lines.iter().enumerate() {
+            for cap in CONNECTION_HANDLER.captures_iter(line) {
+                let namespace = cap.get(2).map(|m| m.as_str())
+                    .or_else(|| namespaces.get(&cap[1]).copied())
+                    .filter(|ns| *ns != "/");
+                let path = namespace.map(str::to_string).unwrap_or_else(|| server_path.clone());
+                let handler = cap.get(3).map(|m| m.as_str().to_string()).filter(|h| !h.is_empty());
+                found.push((idx, path, handler, "socket.io", EndpointProtocol::WebSocket));
+            }
+        }
+    } else if WS_IMPORT.is_match(content) {
+        for (idx, line) in lines.iter().enumerate() {
+            if WS_SERVER.is_match(line) {
+                let path = option_value(lines, idx, &OPTION_PATH).unwrap_or_else(|| "/".to_string());
+                found.push((idx, path, None, "ws", EndpointProtocol::WebSocket));
+            }
+        }
+    }
+
+    for (idx, line) in lines.iter().enumerate() {
+        for cap in EXPRESS_WS.captures_iter(line) {
+            let handler = cap.get(2).map(|m| m.as_str().to_string());
+            found.push((idx, cap[1].to_string(), handler, "express", EndpointProtocol::WebSocket));
+        }
+        if NEST_GATEWAY.is_match(line) {
+            let path = option_value(lines, idx, &OPTION_NAMESPACE)
+                .or_else(|| option_value(lines, idx, &OPTION_PATH))
+                .unwrap_or_else(|| "/socket.io".to_string());
+            let handler = following_name(lines, idx, &CLASS_DEF);
+            found.push((idx, path, handler, "nestjs", EndpointProtocol::WebSocket));
+        }
+        if let Some(cap) = NEST_SSE.captures(line) {
+            let path = cap.get(1).map(|m| m.as_str()).unwrap_or("").to_string();
+            let handler = following_name(lines, idx, &NEST_METHOD);
+            found.push((idx, path, handler, "nestjs", EndpointProtocol::Sse));
+        }
+    }
+}
+
+fn detect_python(content: &str, lines: &[&str], found: &mut Found) {
+    let socketio = SOCKET_IO_IMPORT.is_match(content);
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(cap) = PY_WEBSOCKET_ROUTE.captures(line) {
+            let handler = following_name(lines, idx, &FUNCTION_DEF);
+            found.push((idx, cap[1].to_string(), handler, "fastapi", EndpointProtocol::WebSocket));
+        }
+        for cap in STARLETTE_WS_ROUTE.captures_iter(line) {
+            found.push((idx, cap[1].to_string(), Some(cap[2].to_string()), "starlette", EndpointProtocol::WebSocket));
+        }
+        if socketio && PY_SOCKETIO_CONNECT.is_match(line) {
+            let path = OPTION_NAMESPACE.captures(line)
+                .map(|cap| cap[1].to_string())
+                .unwrap_or_else(|| "/socket.io".to_string());
+            let handler = following_name(lines, idx, &FUNCTION_DEF);
+            found.push((idx, path, handler, "socket.io", EndpointProtocol::WebSocket));
+        }
+    }
+}
+
+fn detect_java(lines: &[&str], found: &mut Found) {
+    for (idx, line) in lines.iter().enumerate() {
+        for cap in STOMP_ENDPOINT.captures_iter(line) {
+            found.push((idx, cap[1].to_string(), None, "stomp", EndpointProtocol::WebSocket));
+        }
+        if let Some(cap) = MESSAGE_MAPPING.captures(line) {
+            let handler = following_name(lines, idx, &JAVA_METHOD_DEF);
+            found.push((idx, cap[1].to_string(), handler, "stomp", EndpointProtocol::WebSocket));
+        }
+        for cap in SPRING_WS_HANDLER.captures_iter(line) {
+            found.push((idx, cap[2].to_string(), Some(cap[1].to_string()), "spring-websocket", EndpointProtocol::WebSocket));
+        }
+        if let Some(cap) = SERVER_ENDPOINT.captures(line) {
+            let handler = following_name(lines, idx, &CLASS_DEF);
+            found.push((idx, cap[1].to_string(), handler, "jakarta-websocket", EndpointProtocol::WebSocket));
+        }
+    }
+}
+
+/// Records the protocol of every function defined in one file, keyed by name. A name defined
+/// more than once with different protocols (`handler` in every Next.js route) maps to `None`.
+pub fn collect_handlers(content: &str, language: &str, handlers: &mut HashMap<String, Option<EndpointProtocol>>) {
+    if language.is_empty() {
+        return;
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    for (idx, line) in lines.iter().enumerate() {
+        let name = FUNCTION_DEF.captures(line)
+            .or_else(|| JS_ARROW_DEF.captures(line))
+            .or_else(|| if language == "java" { JAVA_METHOD_DEF.captures(line) } else { None })
+            .map(|cap| cap[1].to_string());
+        let Some(name) = name else { continue };
+        let protocol = block_protocol(&lines, idx, language);
+        handlers.entry(name)
+            .and_modify(|known| if *known != protocol { *known = None })
+            .or_insert(protocol);
+    }
+}
+
+/// Reclassifies the HTTP endpoints of one file whose inline or same-file handler is realtime,
+/// and Django Channels routes listed in `websocket_urlpatterns`
+pub fn classify(endpoints: &mut [DetectedEndpoint], content: &str, language: &str) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut local = HashMap::new();
+    collect_handlers(content, language, &mut local);
+    let channels = content.contains("websocket_urlpatterns");
+
+    for endpoint in endpoints.iter_mut().filter(|e| e.protocol == EndpointProtocol::Http) {
+        let Some(idx) = endpoint.line_number.map(|n| n - 1).filter(|idx| *idx < lines.len()) else { continue };
+        if channels && endpoint.framework.as_deref() == Some("django") && lines[idx].contains("as_asgi()") {
+            endpoint.protocol = EndpointProtocol::WebSocket;
+            endpoint.framework = Some("channels".to_string());
+            continue;
+        }
+        let referenced = TRAILING_HANDLER.captures(lines[idx])
+            .and_then(|cap| local.get(last_segment(&cap[1])).cloned().flatten());
+        if let Some(protocol) = block_protocol(&lines, idx, language).or(referenced) {
+            endpoint.protocol = protocol;
+        }
+    }
+}
+
+/// Reclassifies HTTP endpoints whose named handler, defined in any file, is realtime
+pub fn apply_handlers(endpoints: &mut [DetectedEndpoint], handlers: &HashMap<String, Option<EndpointProtocol>>) {
+    for endpoint in endpoints.iter_mut().filter(|e| e.protocol == EndpointProtocol::Http) {
+        let protocol = endpoint.handler.as_deref()
+            .and_then(|h| handlers.get(last_segment(h)))
+            .cloned()
+            .flatten();
+        if let Some(protocol) = protocol {
+            endpoint.protocol = protocol;
+        }
+    }
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit(['.', ':']).next().unwrap_or(name)
+}
+
+/// The protocol served by the declaration starting at `idx`, judged from its annotations,
+/// signature and body
+fn block_protocol(lines: &[&str], idx: usize, language: &str) -> Option<EndpointProtocol> {
+    let block = block(lines, idx, language);
+    if WEBSOCKET_MARKER.is_match(&block) {
+        Some(EndpointProtocol::WebSocket)
+    } else if SSE_MARKER.is_match(&block) {
+        Some(EndpointProtocol::Sse)
+    } else {
+        None
+    }
+}
+
+/// The text of the declaration starting at `idx`: stacked decorators and annotations, then the
+/// indented body (Python) or everything up to the bracket that closes the declaration's first line
+fn block(lines: &[&str], idx: usize, language: &str) -> String {
+    let end_limit = (idx + MAX_BODY_LINES).min(lines.len());
+    let mut decl = idx;
+    while decl + 1 < end_limit && is_annotation(lines[decl]) {
+        decl += 1;
+    }
+
+    let mut end = decl + 1;
+    if language == "python" {
+        let indent = indentation(lines[decl]);
+        while end < end_limit && (lines[end].trim().is_empty() || indentation(lines[end]) > indent) {
+            end += 1;
+        }
+    } else {
+        let mut depth = bracket_delta(lines[decl]);
+        while depth > 0 && end < end_limit {
+            depth += bracket_delta(lines[end]);
+            end += 1;
+        }
+    }
+    lines[idx..end].join("\n")
+}
+
+fn is_annotation(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    (trimmed.starts_with('@') || trimmed.starts_with("#[")) && bracket_delta(trimmed) <= 0
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn bracket_delta(line: &str) -> i32 {
+    line.chars().map(|c| match c {
+        '(' | '[' | '{' => 1,
+        ')' | ']' | '}' => -1,
+        _ => 0,
+    }).sum()
+}
+
+/// The first value `option` captures in the arguments of the call starting on line `idx`
+fn option_value(lines: &[&str], idx: usize, option: &Regex) -> Option<String> {
+    let mut depth = 0;
+    for line in lines[idx..].iter().take(MAX_OPTION_LINES) {
+        if let Some(cap) = option.captures(line) {
+            return Some(cap[1].to_string());
+        }
+        depth += bracket_delta(line);
+        if depth <= 0 {
+            break;
+        }
+    }
+    None
+}
+
+/// The name `definition` captures on the first line after `idx` that it matches
+fn following_name(lines: &[&str], idx: usize, definition: &Regex) -> Option<String> {
+    lines[idx + 1..].iter().take(MAX_OPTION_LINES)
+        .find_map(|line| definition.captures(line))
+        .map(|cap| cap[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{EndpointDetector, EndpointProtocol};
+    use crate::test_support::write;
+
+    #[test]
+    fn test_detects_websocket_and_sse_endpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "server.js", r#"
+const { Server } = require('socket.io');
+const io = new Server(httpServer, { cors: { origin: '*' } });
+const chat = io.of('/chat');
+chat.on('connection', onChatConnection);
+
+app.get('/events', (req, res) => {
+  res.setHeader('Content-Type', 'text/event-stream');
+});
+app.get('/health', (req, res) => res.send('ok'));
+"#);
+        write(root, "ws.py", r#"
+@app.websocket("/ws/{client_id}")
+async def websocket_endpoint(websocket: WebSocket, client_id: int):
+    await websocket.accept()
+"#);
+        write(root, "src/main.rs", r#"
+App::new()
+    .route("/ws", web::get().to(ws_index))
+"#);
+        write(root, "src/ws.rs", r#"
+async fn ws_index(req: HttpRequest, body: web::Payload) -> Result<HttpResponse, Error> {
+    let (response, session, stream) = actix_ws::handle(&req, body)?;
+    Ok(response)
+}
+"#);
+        write(root, "WebSocketConfig.java", r#"
+public class WebSocketConfig implements WebSocketMessageBrokerConfigurer {
+    public void registerStompEndpoints(StompEndpointRegistry registry) {
+        registry.addEndpoint("/stomp").withSockJS();
+    }
+}
+"#);
+        write(root, "ChatController.java", r#"
+@Controller
+public class ChatController {
+    @MessageMapping("/chat.send")
+    public ChatMessage send(ChatMessage message) {
+        return message;
+    }
+
+    @GetMapping("/stream")
+    public SseEmitter stream() {
+        return new SseEmitter();
+    }
+}
+"#);
+
+        let endpoints = EndpointDetector::new().detect_endpoints(dir.path()).unwrap();
+        let protocol = |path: &str| endpoints.iter()
+            .find(|e| e.path == path)
+            .map(|e| (e.protocol.clone(), e.framework.clone().unwrap_or_default()))
+            .unwrap_or_else(|| panic!("no endpoint at {}", path));
+
+        assert_eq!(protocol("/chat"), (EndpointProtocol::WebSocket, "socket.io".to_string()));
+        assert_eq!(protocol("/events").0, EndpointProtocol::Sse);
+        assert_eq!(protocol("/health").0, EndpointProtocol::Http);
+        assert_eq!(protocol("/ws/{client_id}"), (EndpointProtocol::WebSocket, "fastapi".to_string()));
+        assert_eq!(endpoints.iter().find(|e| e.path == "/ws/{client_id}").unwrap().parameters, vec!["client_id"]);
+        assert_eq!(protocol("/ws").0, EndpointProtocol::WebSocket);
+        assert_eq!(protocol("/stomp"), (EndpointProtocol::WebSocket, "stomp".to_string()));
+        assert_eq!(protocol("/chat.send"), (EndpointProtocol::WebSocket, "stomp".to_string()));
+        assert_eq!(protocol("/stream"), (EndpointProtocol::Sse, "spring".to_string()));
+    }
+
+    #[test]
+    fn test_defaults_lookalike_listeners_and_ambiguous_handlers() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // The default namespace is served on the server's configured path
+        write(root, "realtime.js", r#"
+const { Server } = require('socket.io');
+const io = new Server(httpServer, {
+  path: '/rt',
+});
+io.on('connection', (socket) => {});
+"#);
+        write(root, "feed.js", r#"
+const WebSocket = require('ws');
+const wss = new WebSocket.Server({ server });
+"#);
+        // Connection events on other clients are not endpoints without a socket.io import
+        write(root, "db.js", r#"
+pool.on('connect', onPoolConnect);
+redis.on('connection', () => {});
+"#);
+        // `handler` streams events in one file and not in the other, so its routes stay HTTP
+        write(root, "routes.js", r#"
+app.get('/reports', handler);
+app.get('/ticker', (req, res) => res.json(prices));
+"#);
+        write(root, "stream.js", r#"
+function handler(req, res) {
+  res.setHeader('Content-Type', 'text/event-stream');
+}
+"#);
+        write(root, "report.js", r#"
+function handler(req, res) {
+  res.json(report);
+}
+"#);
+
+        let endpoints = EndpointDetector::new().detect_endpoints(dir.path()).unwrap();
+        let realtime: Vec<(&str, &str)> = endpoints.iter()
+            .filter(|e| e.protocol != EndpointProtocol::Http)
+            .map(|e| (e.path.as_str(), e.framework.as_deref().unwrap_or("")))
+            .collect();
+        assert_eq!(realtime.len(), 2, "{:?}", realtime);
+        assert!(realtime.contains(&("/rt", "socket.io")));
+        assert!(realtime.contains(&("/", "ws")));
+        for path in ["/reports", "/ticker"] {
+            let endpoint = endpoints.iter().find(|e| e.path == path)
+                .unwrap_or_else(|| panic!("no endpoint at {}", path));
+            assert_eq!(endpoint.protocol, EndpointProtocol::Http);
+        }
+    }
+}
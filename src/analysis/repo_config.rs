@@ -0,0 +1,418 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::analysis::utils::glob_to_regex;
+use crate::analysis::{CodeOrigin, CodeRelationship, ProfileSettings, ProvenanceClassifier};
+use crate::config::AnalysisConfig;
+use crate::security::DetectedService;
+
+/// Configuration files looked up in the analyzed repository, first match wins
+pub const REPO_CONFIG_FILES: &[&str] = &[".wavelength.yml", ".wavelength.yaml"];
+
+/// Analysis steps a repository can switch off. Dependencies, services and code structure always run
+/// because everything else is built on them.
+pub const ANALYZERS: &[&str] = &[
+    "ports",
+    "endpoints",
+    "kubernetes",
+    "gateways",
+    "pipelines",
+    "environments",
+    "build_graph",
+    "data_pipelines",
+    "entrypoints",
+    "background_jobs",
+    "notifications",
+    "payments",
+    "privacy",
+    "frontend_routes",
+    "graphql",
+    "boundaries",
+    "decomposition",
+    "observability",
+    "layering",
+    "rules",
+    "tests",
+    "coverage",
+    "todos",
+    "cost",
+    "artifacts",
+    "pinning",
+    "security",
+    "documentation",
+];
+
+/// Settings a repository commits for its own analysis, e.g.
+///
+/// ```yaml
+/// ignore: ["legacy/"]
+/// include:
+///   generated: true
+/// analyzers:
+///   disabled: [documentation, todos]
+/// thresholds:
+///   services: 0.6
+/// projects: [services/billing, services/orders]
+/// suppressions:
+///   - rule: domain-is-pure
+///     path: "src/domain/legacy/**"
+///     reason: Scheduled for removal in Q3
+/// ```
+///
+/// Paths and globs are relative to the repository root.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoConfig {
+    /// Files under these globs are left out of every result
+    pub ignore: Vec<String>,
+    /// Non-authored code to keep in findings and metrics; vendored and generated files are left out otherwise
+    pub include: IncludedOrigins,
+    pub analyzers: AnalyzerToggles,
+    pub thresholds: Thresholds,
+    /// Sub-project roots for boundary inference, instead of guessing them from manifest locations
+    pub projects: Vec<String>,
+    /// Accepted findings and vulnerabilities that should no longer be reported
+    pub suppressions: Vec<Suppression>,
+    #[serde(skip)]
+    root: PathBuf,
+    #[serde(skip)]
+    ignore_patterns: Vec<Regex>,
+    /// Set by the analysis profile, not the file
+    #[serde(skip)]
+    max_file_size: Option<u64>,
+    #[serde(skip)]
+    provenance: Option<Arc<ProvenanceClassifier>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IncludedOrigins {
+    pub vendored: bool,
+    pub generated: bool,
+}
+
+/// `enabled`, when given, is the complete list of optional analyzers to run; `disabled` wins over it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalyzerToggles {
+    pub enabled: Option<Vec<String>>,
+    pub disabled: Vec<String>,
+}
+
+/// Minimum confidence (0.0 to 1.0) for a detection, and what happens to those below it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    pub services: Option<f64>,
+    pub relationships: Option<f64>,
+    pub below: Option<LowConfidence>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LowConfidence {
+    /// Leave the detection out of the analysis
+    #[default]
+    Drop,
+    /// Keep it, marked as low confidence
+    Flag,
+}
+
+impl Thresholds {
+    pub fn validate(&self) -> Result<()> {
+        for (name, threshold) in [("services", self.services), ("relationships", self.relationships)] {
+            if let Some(value) = threshold {
+                if !(0.0..=1.0).contains(&value) {
+                    anyhow::bail!("Threshold for {} must be between 0.0 and 1.0, got {}", name, value);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fails on the first name that is not one of [`ANALYZERS`]
+pub fn validate_analyzers<'a>(names: impl IntoIterator<Item = &'a String>) -> Result<()> {
+    for name in names {
+        if !ANALYZERS.contains(&name.as_str()) {
+            anyhow::bail!("Unknown analyzer '{}', expected one of: {}", name, ANALYZERS.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Matches when every given criterion matches: `rule` against the finding's rule or vulnerability
+/// type, `path` as a glob against its file, `message` as a substring of its message
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Suppression {
+    pub rule: Option<String>,
+    pub path: Option<String>,
+    pub message: Option<String>,
+    pub reason: Option<String>,
+    #[serde(skip)]
+    path_pattern: Option<Regex>,
+}
+
+impl RepoConfig {
+    /// Load the repository's configuration file, if it has one
+    pub fn load(repo_path: &Path) -> Result<Option<(String, RepoConfig)>> {
+        for file in REPO_CONFIG_FILES {
+            let path = repo_path.join(file);
+            if !path.is_file() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)?;
+            let mut config = Self::parse(&content).with_context(|| format!("Invalid repository configuration in {}", file))?;
+            config.root = repo_path.to_path_buf();
+            config.provenance = Some(Arc::new(ProvenanceClassifier::new(repo_path)));
+            return Ok(Some((file.to_string(), config)));
+        }
+        Ok(None)
+    }
+
+    /// Settings for a repository without a configuration file
+    pub fn default_for(repo_path: &Path) -> RepoConfig {
+        RepoConfig {
+            root: repo_path.to_path_buf(),
+            provenance: Some(Arc::new(ProvenanceClassifier::new(repo_path))),
+            ..RepoConfig::default()
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<RepoConfig> {
+        // An empty file is valid and changes nothing
+        let mut config: RepoConfig = serde_yaml::from_str::<Option<RepoConfig>>(content)?.unwrap_or_default();
+        config.validate()?;
+        config.ignore_patterns = config.ignore.iter().map(|g| glob_to_regex(g)).collect();
+        for suppression in &mut config.suppressions {
+            suppression.path_pattern = suppression.path.as_deref().map(glob_to_regex);
+        }
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_analyzers(self.analyzers.enabled.iter().flatten().chain(&self.analyzers.disabled))?;
+        self.thresholds.validate()?;
+        for (i, suppression) in self.suppressions.iter().enumerate() {
+            if suppression.rule.is_none() && suppression.path.is_none() && suppression.message.is_none() {
+                anyhow::bail!("Suppression #{} needs a 'rule', 'path' or 'message'", i + 1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Layer the server-wide settings underneath: globally disabled analyzers stay off, and the
+    /// repository's thresholds take precedence over the global ones
+    pub fn inherit(&mut self, global: &AnalysisConfig) {
+        for name in &global.disabled {
+            if !self.analyzers.disabled.contains(name) {
+                self.analyzers.disabled.push(name.clone());
+            }
+        }
+        self.thresholds.services = self.thresholds.services.or(global.thresholds.services);
+        self.thresholds.relationships = self.thresholds.relationships.or(global.thresholds.relationships);
+        self.thresholds.below = self.thresholds.below.or(global.thresholds.below);
+    }
+
+    /// Narrow the analysis to what the profile runs; analyzers it skips stay off even if enabled
+    pub fn apply_profile(&mut self, profile: &ProfileSettings) {
+        for name in profile.disabled {
+            if !self.analyzers.disabled.iter().any(|n| n == name) {
+                self.analyzers.disabled.push(name.to_string());
+            }
+        }
+        self.max_file_size = profile.max_file_size;
+    }
+
+    pub fn analyzer_enabled(&self, name: &str) -> bool {
+        !self.analyzers.disabled.iter().any(|n| n == name)
+            && self.analyzers.enabled.as_ref().is_none_or(|enabled| enabled.iter().any(|n| n == name))
+    }
+
+    /// Run `detect` unless the analyzer is disabled, in which case the result is empty so the
+    /// previous analysis' data is cleared when it is stored
+    pub fn run<T: Default, E>(&self, name: &str, detect: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        if self.analyzer_enabled(name) {
+            detect()
+        } else {
+            log::info!("Skipping {} (disabled by repository configuration)", name);
+            Ok(T::default())
+        }
+    }
+
+    /// `file_path` may be absolute under the repository or already repository-relative
+    pub fn is_ignored(&self, file_path: &str) -> bool {
+        let ignored = !self.ignore_patterns.is_empty() && {
+            let relative = self.relative(file_path);
+            self.ignore_patterns.iter().any(|p| p.is_match(&relative))
+        };
+        ignored || self.max_file_size.is_some_and(|max| {
+            std::fs::metadata(self.root.join(self.relative(file_path))).is_ok_and(|m| m.len() > max)
+        })
+    }
+
+    /// Whether the file was written in this repository, or vendored or generated into it
+    pub fn origin_of(&self, file_path: &str) -> CodeOrigin {
+        self.provenance.as_ref().map_or(CodeOrigin::Authored, |p| p.classify(&self.relative(file_path)))
+    }
+
+    pub fn includes_origin(&self, origin: CodeOrigin) -> bool {
+        match origin {
+            CodeOrigin::Authored => true,
+            CodeOrigin::Vendored => self.include.vendored,
+            CodeOrigin::Generated => self.include.generated,
+        }
+    }
+
+    /// Ignored, or vendored/generated code the configuration does not include
+    pub fn is_excluded(&self, file_path: &str) -> bool {
+        self.is_ignored(file_path) || !self.includes_origin(self.origin_of(file_path))
+    }
+
+    /// Drop the items in excluded files, returning how many were removed
+    pub fn retain_files<T>(&self, items: &mut Vec<T>, file_path: impl Fn(&T) -> &str) -> usize {
+        let before = items.len();
+        items.retain(|item| !self.is_excluded(file_path(item)));
+        before - items.len()
+    }
+
+    /// Apply the service threshold, returning how many services fell below it
+    pub fn apply_service_threshold(&self, services: &mut Vec<DetectedService>) -> usize {
+        let threshold = match self.thresholds.services {
+            Some(threshold) => threshold,
+            None => return 0,
+        };
+        match self.thresholds.below.unwrap_or_default() {
+            LowConfidence::Drop => {
+                let before = services.len();
+                services.retain(|s| s.confidence >= threshold);
+                before - services.len()
+            }
+            LowConfidence::Flag => {
+                let mut flagged = 0;
+                for service in services.iter_mut().filter(|s| s.confidence < threshold) {
+                    service.configuration.insert("low_confidence".to_string(), "true".to_string());
+                    flagged += 1;
+                }
+                flagged
+            }
+        }
+    }
+
+    /// Apply the relationship threshold, returning how many relationships fell below it
+    pub fn apply_relationship_threshold(&self, relationships: &mut Vec<CodeRelationship>) -> usize {
+        let threshold = match self.thresholds.relationships {
+            Some(threshold) => threshold,
+            None => return 0,
+        };
+        match self.thresholds.below.unwrap_or_default() {
+            LowConfidence::Drop => {
+                let before = relationships.len();
+                relationships.retain(|r| r.confidence >= threshold);
+                before - relationships.len()
+            }
+            LowConfidence::Flag => {
+                let mut flagged = 0;
+                for relationship in relationships.iter_mut().filter(|r| r.confidence < threshold) {
+                    relationship.evidence = format!("{} (low confidence)", relationship.evidence);
+                    flagged += 1;
+                }
+                flagged
+            }
+        }
+    }
+
+    pub fn suppresses(&self, rule: &str, file_path: &str, message: &str) -> bool {
+        let relative = self.relative(file_path);
+        self.suppressions.iter().any(|s| {
+            s.rule.as_deref().is_none_or(|r| r == rule)
+                && s.path_pattern.as_ref().is_none_or(|p| p.is_match(&relative))
+                && s.message.as_deref().is_none_or(|m| message.contains(m))
+        })
+    }
+
+    fn relative(&self, file_path: &str) -> String {
+        let path = Path::new(file_path);
+        let relative = path.strip_prefix(&self.root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        relative.trim_start_matches("./").trim_start_matches('/').to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_apply_repository_config() {
+        let config = RepoConfig::parse(r#"
+ignore: ["vendor/**", "legacy"]
+analyzers:
+  enabled: [tests, todos, documentation]
+  disabled: [documentation]
+thresholds:
+  services: 0.5
+suppressions:
+  - rule: domain-is-pure
+    path: "src/domain/old/**"
+  - message: "hardcoded"
+"#).unwrap();
+
+        assert!(config.is_ignored("vendor/lib/a.js"));
+        assert!(config.is_ignored("./legacy/main.py"));
+        assert!(!config.is_ignored("src/legacy.py"));
+
+        assert!(config.analyzer_enabled("tests"));
+        assert!(!config.analyzer_enabled("documentation"));
+        assert!(!config.analyzer_enabled("security"));
+        let skipped: Result<Vec<u8>, ()> = config.run("security", || panic!("disabled analyzer ran"));
+        assert!(skipped.unwrap().is_empty());
+
+        assert!(config.suppresses("domain-is-pure", "src/domain/old/user.rs", "imports web"));
+        assert!(!config.suppresses("domain-is-pure", "src/domain/user.rs", "imports web"));
+        assert!(config.suppresses("secret", "config/app.yml", "Possible hardcoded secret"));
+        assert_eq!(config.thresholds.services, Some(0.5));
+    }
+
+    #[test]
+    fn test_inherits_global_settings() {
+        let mut config = RepoConfig::parse("thresholds:\n  services: 0.8\n").unwrap();
+        let global: AnalysisConfig = serde_json::from_value(serde_json::json!({
+            "disabled": ["documentation"],
+            "thresholds": {"services": 0.3, "relationships": 0.4, "below": "flag"}
+        })).unwrap();
+        config.inherit(&global);
+
+        assert!(!config.analyzer_enabled("documentation"));
+        assert_eq!(config.thresholds.services, Some(0.8));
+        assert_eq!(config.thresholds.relationships, Some(0.4));
+        assert_eq!(config.thresholds.below, Some(LowConfidence::Flag));
+    }
+
+    #[test]
+    fn test_profile_skips_analyzers_and_large_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("small.js"), "x").unwrap();
+        std::fs::write(temp_dir.path().join("large.js"), "x".repeat(300 * 1024)).unwrap();
+        std::fs::write(temp_dir.path().join(".wavelength.yml"), "analyzers:\n  enabled: [todos, ports]\n").unwrap();
+        let (_, mut config) = RepoConfig::load(temp_dir.path()).unwrap().unwrap();
+        config.apply_profile(&crate::analysis::AnalysisProfile::Fast.settings());
+
+        assert!(!config.analyzer_enabled("todos"));
+        assert!(config.analyzer_enabled("ports"));
+        assert!(!config.is_ignored("small.js"));
+        assert!(config.is_ignored("large.js"));
+        assert!(config.is_ignored(&temp_dir.path().join("large.js").to_string_lossy()));
+        assert!(config.is_excluded("vendor/github.com/acme/lib.go"));
+        assert!(!config.is_excluded("small.js"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_analyzer() {
+        let err = RepoConfig::parse("analyzers:\n  disabled: [dependencies]\n").unwrap_err();
+        assert!(err.to_string().contains("Unknown analyzer 'dependencies'"));
+        assert!(RepoConfig::parse("").is_ok());
+    }
+}
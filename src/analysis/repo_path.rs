@@ -0,0 +1,89 @@
+//! The one form every stored `file_path` takes: relative to the repository checkout, with `/`
+//! separators and no leading `./`. Analyzers walk absolute cache paths, so results are converted
+//! with [`RepoPath::new`] before they leave the analyzer; that keeps joins between analyzers,
+//! editor links and SARIF locations working the same for every kind of result.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RepoPath(String);
+
+impl RepoPath {
+    /// `path` relative to the checkout at `root`. Paths outside the checkout keep their full
+    /// form, with separators normalized.
+    pub fn new(root: &Path, path: &Path) -> Self {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        RepoPath::from_relative(&relative.to_string_lossy())
+    }
+
+    /// A path that is already relative, e.g. from a manifest or a Windows checkout
+    pub fn from_relative(path: &str) -> Self {
+        let mut path = path.replace('\\', "/");
+        while let Some(rest) = path.strip_prefix("./") {
+            path = rest.to_string();
+        }
+        RepoPath(path)
+    }
+
+    /// Normalizes a `file_path` that may still be absolute, in place
+    pub fn normalize(root: &Path, file_path: &mut String) {
+        *file_path = RepoPath::new(root, Path::new(file_path.as_str())).into_string();
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Where the file is in the checkout at `root`
+    pub fn in_checkout(&self, root: &Path) -> PathBuf {
+        root.join(&self.0)
+    }
+}
+
+impl fmt::Display for RepoPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<RepoPath> for String {
+    fn from(path: RepoPath) -> String {
+        path.0
+    }
+}
+
+impl AsRef<str> for RepoPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_paths_are_relative_with_forward_slashes() {
+        let root = Path::new("./cache/repos/shop");
+        assert_eq!(RepoPath::new(root, &root.join("src/server.js")).as_str(), "src/server.js");
+        assert_eq!(RepoPath::new(Path::new("/srv/shop"), Path::new("/srv/shop/package.json")).as_str(), "package.json");
+        assert_eq!(RepoPath::from_relative(".\\src\\main.rs").as_str(), "src/main.rs");
+        assert_eq!(RepoPath::from_relative("././lib/a.py").as_str(), "lib/a.py");
+
+        // Already relative paths are left alone, outside paths keep their full form
+        assert_eq!(RepoPath::new(root, Path::new("src/server.js")).as_str(), "src/server.js");
+        assert_eq!(RepoPath::new(root, Path::new("/etc/hosts")).as_str(), "/etc/hosts");
+
+        let mut stored = "/srv/shop/src/app.py".to_string();
+        RepoPath::normalize(Path::new("/srv/shop/"), &mut stored);
+        assert_eq!(stored, "src/app.py");
+        assert_eq!(RepoPath::from_relative("src/app.py").in_checkout(Path::new("/srv/shop")), Path::new("/srv/shop/src/app.py"));
+    }
+}
@@ -0,0 +1,271 @@
+//! Fixture tests for detection patterns and plugins. A fixture is a directory holding a small
+//! repository under `repo/` and an `expected.yml` that says what should (and should not) be
+//! detected in it:
+//!
+//! ```yaml
+//! services:
+//!   - name: Acme Billing
+//!     provider: Unknown
+//!     type: Payment
+//!     min_confidence: 0.6
+//!     file: config/settings.env
+//! absent_services: [Stripe]
+//! only_expected_services: false
+//! findings:
+//!   - rule: no-raw-sql
+//!     file: src/db.ts
+//!     count: 2
+//! absent_findings: [no-eval]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use crate::plugins::{self, PluginFinding, PluginRegistry};
+use crate::security::pattern_config::{PatternConfig, PatternLoader};
+use crate::security::{DetectedService, ServiceDetector};
+
+pub const EXPECTATIONS_FILE: &str = "expected.yml";
+pub const FIXTURE_REPO_DIR: &str = "repo";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Expectations {
+    pub services: Vec<ServiceExpectation>,
+    pub absent_services: Vec<String>,
+    /// Fail on detected services that are not listed under `services`
+    pub only_expected_services: bool,
+    pub findings: Vec<FindingExpectation>,
+    pub absent_findings: Vec<String>,
+}
+
+/// Names, providers and types compare case-insensitively; providers and types use the names of
+/// the pattern config (`Stripe`, `SaaS`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceExpectation {
+    pub name: String,
+    pub provider: Option<String>,
+    #[serde(rename = "type")]
+    pub service_type: Option<String>,
+    pub min_confidence: Option<f64>,
+    /// A file, relative to `repo/`, among the service's evidence
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FindingExpectation {
+    pub rule: String,
+    pub file: Option<String>,
+    /// Exactly this many matching findings; at least one when not given
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureResult {
+    pub fixture: String,
+    pub passed: bool,
+    pub failures: Vec<String>,
+    /// What was detected, as `name (provider, confidence)`
+    pub services: Vec<String>,
+    pub findings: Vec<String>,
+}
+
+/// Runs a pattern config and a plugin directory's analyzers the way an analysis does, without a
+/// database or network
+pub struct RuleTestHarness {
+    detector: ServiceDetector,
+    registry: PluginRegistry,
+}
+
+impl RuleTestHarness {
+    pub fn new(pattern_config: PatternConfig, registry: PluginRegistry) -> Self {
+        RuleTestHarness {
+            detector: ServiceDetector::with_config(pattern_config),
+            registry,
+        }
+    }
+
+    /// The pattern file merged with the pattern plugins of the plugin directory, as at startup
+    pub fn load(patterns: &Path, plugin_dir: &Path) -> Result<Self> {
+        let registry = PluginRegistry::load(plugin_dir);
+        let (pattern_config, _) = PatternLoader::load_with_registry(patterns, &registry)
+            .with_context(|| format!("loading patterns from {}", patterns.display()))?;
+        Ok(Self::new(pattern_config, registry))
+    }
+
+    pub fn run(&self, fixture: &Path) -> Result<FixtureResult> {
+        let expectations_path = fixture.join(EXPECTATIONS_FILE);
+        let content = std::fs::read_to_string(&expectations_path)
+            .with_context(|| format!("reading {}", expectations_path.display()))?;
+        let expectations: Expectations = serde_yaml::from_str::<Option<Expectations>>(&content)
+            .with_context(|| format!("parsing {}", expectations_path.display()))?
+            .unwrap_or_default();
+        let repo_path = fixture.join(FIXTURE_REPO_DIR);
+        if !repo_path.is_dir() {
+            anyhow::bail!("{} has no {}/ directory", fixture.display(), FIXTURE_REPO_DIR);
+        }
+
+        let mut output = plugins::run_native_analyzers(&self.registry, &repo_path);
+        output.merge(plugins::run_wasm_analyzers(&self.registry, &repo_path));
+        output.merge(plugins::run_subprocess_plugins(&self.registry, &repo_path, &mut |_, _| {}));
+        let mut services = self.detector.detect_services(&repo_path)?;
+        services.extend(output.services.iter().map(|s| s.to_detected(&self.detector, &repo_path)));
+        let services = ServiceDetector::aggregate(&repo_path, services);
+
+        let failures = check(&expectations, &repo_path, &services, &output.findings);
+        Ok(FixtureResult {
+            fixture: fixture.display().to_string(),
+            passed: failures.is_empty(),
+            failures,
+            services: services.iter()
+                .map(|s| format!("{} ({:?}, {:.2})", s.name, s.provider, s.confidence))
+                .collect(),
+            findings: output.findings.iter()
+                .map(|f| format!("{} at {}", f.rule, f.file_path))
+                .collect(),
+        })
+    }
+}
+
+/// Every directory under `paths` with an `expected.yml`, in path order
+pub fn find_fixtures(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut fixtures = Vec::new();
+    for path in paths {
+        let mut walker = WalkDir::new(path).sort_by_file_name().into_iter();
+        while let Some(Ok(entry)) = walker.next() {
+            if entry.file_type().is_dir() && entry.path().join(EXPECTATIONS_FILE).is_file() {
+                fixtures.push(entry.path().to_path_buf());
+                // A fixture's repository may contain anything, including other expected.yml files
+                walker.skip_current_dir();
+            }
+        }
+    }
+    fixtures
+}
+
+fn same(a: &str, b: &str) -> bool {
+    let key = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+    key(a) == key(b)
+}
+
+fn relative(repo_path: &Path, file_path: &str) -> String {
+    let path = Path::new(file_path);
+    path.strip_prefix(repo_path).unwrap_or(path).to_string_lossy().replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+fn check(expectations: &Expectations, repo_path: &Path, services: &[DetectedService], findings: &[PluginFinding]) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    for expected in &expectations.services {
+        let candidates: Vec<&DetectedService> = services.iter().filter(|s| same(&s.name, &expected.name)).collect();
+        if candidates.is_empty() {
+            failures.push(format!("expected service '{}' was not detected", expected.name));
+            continue;
+        }
+        let mismatches: Vec<String> = candidates.iter()
+            .map(|s| {
+                let mut problems = Vec::new();
+                if let Some(provider) = &expected.provider {
+                    if !same(&format!("{:?}", s.provider), provider) {
+                        problems.push(format!("provider {:?}, expected {}", s.provider, provider));
+                    }
+                }
+                if let Some(service_type) = &expected.service_type {
+                    if !same(&format!("{:?}", s.service_type), service_type) {
+                        problems.push(format!("type {:?}, expected {}", s.service_type, service_type));
+                    }
+                }
+                if let Some(min) = expected.min_confidence {
+                    if s.confidence < min {
+                        problems.push(format!("confidence {:.2}, expected at least {:.2}", s.confidence, min));
+                    }
+                }
+                if let Some(file) = &expected.file {
+                    let found = std::iter::once(s.file_path.as_str())
+                        .chain(s.evidence.iter().map(|e| e.file_path.as_str()))
+                        .any(|f| relative(repo_path, f) == *file);
+                    if !found {
+                        problems.push(format!("no evidence in {}", file));
+                    }
+                }
+                problems.join(", ")
+            })
+            .collect();
+        if mismatches.iter().all(|m| !m.is_empty()) {
+            failures.push(format!("service '{}' was detected with {}", expected.name, mismatches.join("; ")));
+        }
+    }
+    for absent in &expectations.absent_services {
+        if services.iter().any(|s| same(&s.name, absent)) {
+            failures.push(format!("service '{}' was detected but should not be", absent));
+        }
+    }
+    if expectations.only_expected_services {
+        for service in services.iter().filter(|s| !expectations.services.iter().any(|e| same(&s.name, &e.name))) {
+            failures.push(format!("unexpected service '{}' ({:?}, {:.2})", service.name, service.provider, service.confidence));
+        }
+    }
+
+    for expected in &expectations.findings {
+        let count = findings.iter()
+            .filter(|f| f.rule == expected.rule)
+            .filter(|f| expected.file.as_ref().is_none_or(|file| relative(repo_path, &f.file_path) == *file))
+            .count();
+        let location = expected.file.as_ref().map(|f| format!(" in {}", f)).unwrap_or_default();
+        match expected.count {
+            Some(wanted) if wanted != count => {
+                failures.push(format!("expected {} '{}' finding(s){}, got {}", wanted, expected.rule, location, count));
+            }
+            None if count == 0 => failures.push(format!("expected a '{}' finding{}", expected.rule, location)),
+            _ => {}
+        }
+    }
+    for absent in &expectations.absent_findings {
+        let count = findings.iter().filter(|f| f.rule == *absent).count();
+        if count > 0 {
+            failures.push(format!("{} '{}' finding(s) reported but none expected", count, absent));
+        }
+    }
+
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fixture_expectations() {
+        let root = TempDir::new().unwrap();
+        let fixture = root.path().join("acme-billing");
+        std::fs::create_dir_all(fixture.join("repo/config")).unwrap();
+        std::fs::write(fixture.join("repo/config/settings.env"), "ACME_BILLING_API_KEY=xyz\n").unwrap();
+        std::fs::write(fixture.join(EXPECTATIONS_FILE), "\
+services:
+  - name: Acme Billing
+    type: Payment
+    file: config/settings.env
+  - name: Acme Ledger
+absent_services: [Stripe]
+").unwrap();
+
+        let patterns: PatternConfig = serde_json::from_value(serde_json::json!({
+            "version": "1.0",
+            "patterns": {
+                "environment_variables": [
+                    { "pattern": "ACME_BILLING", "provider": "Unknown", "service_type": "Payment", "service_name": "Acme Billing", "confidence": 0.8 }
+                ]
+            }
+        })).unwrap();
+        let harness = RuleTestHarness::new(patterns, PluginRegistry::default());
+
+        assert_eq!(find_fixtures(&[root.path().to_path_buf()]), vec![fixture.clone()]);
+        let result = harness.run(&fixture).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.failures, vec!["expected service 'Acme Ledger' was not detected".to_string()]);
+    }
+}
@@ -0,0 +1,362 @@
+//! Salesforce customization repositories: Apex classes and triggers, Aura components and
+//! Visualforce pages with the Apex controllers they bind to, and the object, field and flow
+//! metadata XML of an sfdx/metadata API source tree. Lightning web components are JavaScript and
+//! go through the JS analyzer, whose `@salesforce/apex/Class.method` imports already become modules.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::analysis::code_structure::{CodeElement, CodeElementType};
+
+/// Apex is case-insensitive, so every pattern here is too
+static APEX_ANNOTATIONS: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:@\w+(?:\([^)]*\))?\s*)+").unwrap());
+static APEX_TYPE: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"(?i)^((?:(?:global|public|private|protected|virtual|abstract|static|with\s+sharing|without\s+sharing|inherited\s+sharing)\s+)*)(class|interface|enum)\s+(\w+)"
+).unwrap());
+static APEX_TRIGGER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^trigger\s+(\w+)\s+on\s+(\w+)\s*\(([^)]*)").unwrap());
+static APEX_METHOD: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r"(?i)^(?:(global|public|private|protected)\s+)?(?:(?:static|virtual|abstract|override|testmethod|webservice|final)\s+)*([\w.]+(?:<[\w.,\s<>]*>)?(?:\[\])?)\s+(\w+)\s*\("
+).unwrap());
+static APEX_CONSTRUCTOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^(global|public|private|protected)\s+(\w+)\s*\(").unwrap());
+static MARKUP_ROOT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<(aura:component|aura:application|aura:event|apex:page|apex:component)\b[^>]*>").unwrap());
+static AURA_ATTRIBUTE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?i)<aura:attribute\b[^>]*\bname\s*=\s*"([^"]+)""#).unwrap());
+static ACTION_CALL: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<actionCalls>(.*?)</actionCalls>").unwrap());
+static SUBFLOW: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<subflows>(.*?)</subflows>").unwrap());
+
+/// Words that can stand where a method's return type does without declaring one
+const APEX_STATEMENTS: &[&str] = &[
+    "return", "new", "throw", "else", "insert", "update", "delete", "upsert", "undelete", "merge", "when",
+];
+const APEX_CONTROL: &[&str] = &["if", "for", "while", "catch", "switch", "return"];
+const APEX_MODIFIERS: &[&str] = &[
+    "global", "public", "private", "protected", "static", "virtual", "abstract", "override", "testmethod",
+    "webservice", "final", "transient",
+];
+
+/// The language the metadata analyzer reads `file_name` as, for the Salesforce files whose
+/// extension alone does not say so; both arguments are lowercase
+pub fn language_of(file_name: &str, path_str: &str) -> Option<String> {
+    let aura = path_str.contains("/aura/")
+        && (file_name.ends_with(".cmp") || file_name.ends_with(".app") || file_name.ends_with(".evt"));
+    let visualforce = file_name.ends_with(".page") || file_name.ends_with(".component");
+    let metadata = [".object-meta.xml", ".field-meta.xml", ".flow-meta.xml"].iter().any(|s| file_name.ends_with(s));
+    (aura || visualforce || metadata).then(|| "salesforce".to_string())
+}
+
+fn element(name: &str, element_type: CodeElementType, path: &str, line_number: usize, language: &str) -> CodeElement {
+    CodeElement {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        element_type,
+        file_path: path.to_string(),
+        line_number,
+        language: language.to_string(),
+        signature: None,
+        doc_comment: None,
+        visibility: None,
+        parameters: Vec::new(),
+        return_type: None,
+        parent_id: None,
+        qualified_name: String::new(),
+        decorators: Vec::new(),
+        origin: Default::default(),
+    }
+}
+
+/// Classes, interfaces, enums, methods and constructors of an Apex class file, or the trigger of a
+/// `.trigger` file with its sObject and DML events
+pub fn analyze_apex(content: &str, path: &str) -> Vec<CodeElement> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut elements = Vec::new();
+    let mut types: Vec<String> = Vec::new();
+    for (line_num, raw) in lines.iter().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            continue;
+        }
+        let line = APEX_ANNOTATIONS.replace(trimmed, "");
+        let line = line.trim();
+        let doc_comment = super::doc_comments::extract_doc_comment(&lines, line_num);
+
+        if let Some(caps) = APEX_TRIGGER.captures(line) {
+            let mut trigger = element(&caps[1], CodeElementType::Function, path, line_num + 1, "apex");
+            trigger.signature = Some(line.to_string());
+            trigger.doc_comment = doc_comment;
+            trigger.parameters = caps[3].split(',').map(|e| e.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|e| !e.is_empty()).collect();
+            trigger.return_type = Some(caps[2].to_string());
+            elements.push(trigger);
+        } else if let Some(caps) = APEX_TYPE.captures(line) {
+            let element_type = match caps[2].to_lowercase().as_str() {
+                "interface" => CodeElementType::Interface,
+                "enum" => CodeElementType::Enum,
+                _ => CodeElementType::Class,
+            };
+            let mut declared = element(&caps[3], element_type, path, line_num + 1, "apex");
+            declared.signature = Some(line.to_string());
+            declared.doc_comment = doc_comment;
+            declared.visibility = Some(visibility(&caps[1]));
+            types.push(caps[3].to_lowercase());
+            elements.push(declared);
+        } else if let Some(caps) = APEX_METHOD.captures(line) {
+            let (return_type, name) = (caps[2].to_lowercase(), caps[3].to_lowercase());
+            let assignment = line.find('=').is_some_and(|eq| line.find('(').is_some_and(|open| eq < open));
+            if APEX_STATEMENTS.contains(&return_type.as_str()) || APEX_CONTROL.contains(&name.as_str()) || assignment {
+                continue;
+            }
+            // `public Account(...)`: the visibility word was read as the return type
+            let constructor = APEX_CONSTRUCTOR.captures(line).filter(|c| types.contains(&c[2].to_lowercase()));
+            let name = constructor.as_ref().map(|c| c[2].to_string()).unwrap_or_else(|| caps[3].to_string());
+            let mut method = element(&name, CodeElementType::Method, path, line_num + 1, "apex");
+            method.visibility = Some(visibility(line));
+            method.doc_comment = doc_comment;
+            complete_apex(&mut method, line);
+            method.signature = Some(line.to_string());
+            elements.push(method);
+        }
+    }
+    elements
+}
+
+/// `global`, `public`, `protected` or Apex's default `private`, from a declaration's modifiers
+fn visibility(modifiers: &str) -> String {
+    let lowered = modifiers.to_lowercase();
+    lowered.split_whitespace()
+        .find(|w| matches!(*w, "global" | "public" | "private" | "protected"))
+        .unwrap_or("private")
+        .to_string()
+}
+
+/// Re-derives the parameter names and the return type of an Apex method from its whole
+/// `signature`; triggers keep the events they were read with
+pub(crate) fn complete_apex(element: &mut CodeElement, signature: &str) {
+    let signature = APEX_ANNOTATIONS.replace(signature.trim(), "");
+    let Some(open) = signature.find('(') else { return };
+    if signature.to_lowercase().starts_with("trigger ") {
+        return;
+    }
+    let close = signature[open..].find(')').map(|c| open + c).unwrap_or(signature.len());
+    element.parameters = split_top_level(&signature[open + 1..close]).iter()
+        .filter_map(|p| p.split_whitespace().last().map(str::to_string))
+        .collect();
+
+    let before = signature[..open].trim_end();
+    let before = before[..before.len() - before.rsplit(char::is_whitespace).next().unwrap_or("").len()].trim();
+    let mut words = before;
+    while let Some((first, rest)) = words.split_once(char::is_whitespace) {
+        if !APEX_MODIFIERS.contains(&first.to_lowercase().as_str()) {
+            break;
+        }
+        words = rest.trim_start();
+    }
+    element.return_type = Some(words.to_string())
+        .filter(|w| !w.is_empty() && !APEX_MODIFIERS.contains(&w.to_lowercase().as_str()));
+}
+
+/// Splits a parameter list at the commas outside generic brackets (`Map<String, Object> m`)
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in list.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect()
+}
+
+/// The text of the first `<tag>` in `xml`
+fn tag_value(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(xml[start..end].trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// The value of `attribute="..."` in a markup tag
+fn attribute(tag: &str, attribute: &str) -> Option<String> {
+    Regex::new(&format!(r#"(?i)\b{}\s*=\s*"([^"]*)""#, attribute)).ok()?
+        .captures(tag)
+        .map(|c| c[1].trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// 1-based line of the first occurrence of `needle`, or the first line
+fn line_of(content: &str, needle: &str) -> usize {
+    content.find(needle).map(|pos| content[..pos].matches('\n').count() + 1).unwrap_or(1)
+}
+
+/// The text of `<tag>` as a direct child of the root element, which the metadata API writes one
+/// indent in; nested elements (a flow action's `<description>`) are skipped
+fn root_value(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let mut elements = xml.lines().filter(|l| l.trim_start().starts_with('<') && !l.trim_start().starts_with("<?"));
+    elements.next()?;
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let child_indent = indent(elements.clone().next()?);
+    elements.find(|l| indent(l) == child_indent && l.trim_start().starts_with(&open))
+        .and_then(|l| tag_value(l, tag))
+}
+
+/// Elements of a file [`language_of`] recognised: Aura components and Visualforce pages become
+/// classes that import their Apex controllers, custom objects structs, custom fields variables
+/// typed by the field type, and flows functions that import the Apex actions and subflows they run
+pub fn analyze_metadata(content: &str, path: &str) -> Vec<CodeElement> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let name = file_name.split('.').next().unwrap_or(file_name);
+    let lowered = file_name.to_lowercase();
+    let mut elements = Vec::new();
+
+    if lowered.ends_with(".object-meta.xml") {
+        let mut object = element(name, CodeElementType::Struct, path, line_of(content, "<CustomObject"), "salesforce");
+        object.signature = root_value(content, "label").map(|label| format!("{} ({})", name, label));
+        object.doc_comment = root_value(content, "description");
+        object.visibility = Some(root_value(content, "visibility").unwrap_or_else(|| "Public".to_string()).to_lowercase());
+        elements.push(object);
+    } else if lowered.ends_with(".field-meta.xml") {
+        let mut field = element(name, CodeElementType::Variable, path, line_of(content, "<CustomField"), "salesforce");
+        field.doc_comment = root_value(content, "description");
+        field.return_type = match (root_value(content, "type"), root_value(content, "referenceTo")) {
+            (Some(field_type), Some(target)) => Some(format!("{}({})", field_type, target)),
+            (field_type, _) => field_type,
+        };
+        field.signature = root_value(content, "formula").map(|formula| format!("{} = {}", name, formula));
+        elements.push(field);
+    } else if lowered.ends_with(".flow-meta.xml") {
+        let mut flow = element(name, CodeElementType::Function, path, line_of(content, "<Flow"), "salesforce");
+        flow.doc_comment = root_value(content, "description");
+        let start = tag_value(content, "start").unwrap_or_default();
+        flow.signature = Some([
+            root_value(content, "processType"),
+            tag_value(&start, "triggerType"),
+            tag_value(&start, "object").map(|object| format!("on {}", object)),
+        ].into_iter().flatten().collect::<Vec<_>>().join(" ")).filter(|s| !s.is_empty());
+        elements.push(flow);
+        for call in ACTION_CALL.captures_iter(content) {
+            if tag_value(&call[1], "actionType").is_some_and(|t| t.eq_ignore_ascii_case("apex")) {
+                if let Some(action) = tag_value(&call[1], "actionName") {
+                    elements.push(element(&action, CodeElementType::Module, path, line_of(content, &call[0]), "salesforce"));
+                }
+            }
+        }
+        for subflow in SUBFLOW.captures_iter(content) {
+            if let Some(flow_name) = tag_value(&subflow[1], "flowName") {
+                elements.push(element(&flow_name, CodeElementType::Module, path, line_of(content, &subflow[0]), "salesforce"));
+            }
+        }
+    } else if let Some(root) = MARKUP_ROOT.find(content) {
+        let tag = root.as_str();
+        let language = if tag.to_lowercase().starts_with("<aura") { "aura" } else { "visualforce" };
+        let line_number = line_of(content, tag);
+        let mut markup = element(name, CodeElementType::Class, path, line_number, language);
+        markup.signature = Some(tag.split_whitespace().collect::<Vec<_>>().join(" "));
+        markup.visibility = Some(attribute(tag, "access").unwrap_or_else(|| "public".to_string()).to_lowercase());
+        markup.parameters = AURA_ATTRIBUTE.captures_iter(content).map(|c| c[1].to_string()).collect();
+        markup.return_type = attribute(tag, "standardController");
+        elements.push(markup);
+        let controllers = attribute(tag, "controller").into_iter()
+            .chain(attribute(tag, "extensions").into_iter().flat_map(|e| {
+                e.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect::<Vec<_>>()
+            }));
+        for controller in controllers {
+            elements.push(element(&controller, CodeElementType::Module, path, line_number, language));
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_apex_triggers_markup_and_flows() {
+        let apex = r#"
+/**
+ * Account operations for the Lightning pages
+ */
+public with sharing class AccountService {
+    public AccountService(Id ownerId) {
+    }
+
+    @AuraEnabled(cacheable=true)
+    public static List<Account> findAccounts(String term, Map<String, Object> filters) {
+        List<Account> accounts = [SELECT Id FROM Account];
+        if (accounts.isEmpty()) {
+            return new List<Account>();
+        }
+        return accounts;
+    }
+
+    global interface Scorer {
+        Decimal score(Account account);
+    }
+}
+"#;
+        let elements = analyze_apex(apex, "force-app/main/default/classes/AccountService.cls");
+        let names: Vec<&str> = elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["AccountService", "AccountService", "findAccounts", "Scorer", "score"]);
+        assert_eq!(elements[0].visibility.as_deref(), Some("public"));
+        assert_eq!(elements[0].doc_comment.as_deref(), Some("Account operations for the Lightning pages"));
+        assert_eq!(elements[1].return_type, None);
+        assert_eq!(elements[2].parameters, vec!["term", "filters"]);
+        assert_eq!(elements[2].return_type.as_deref(), Some("List<Account>"));
+        assert_eq!(elements[3].element_type, CodeElementType::Interface);
+        assert_eq!(elements[4].return_type.as_deref(), Some("Decimal"));
+
+        let trigger = analyze_apex("trigger OpportunityTrigger on Opportunity (before insert, after  update) {\n}\n", "triggers/OpportunityTrigger.trigger");
+        assert_eq!(trigger[0].element_type, CodeElementType::Function);
+        assert_eq!(trigger[0].return_type.as_deref(), Some("Opportunity"));
+        assert_eq!(trigger[0].parameters, vec!["before insert", "after update"]);
+
+        assert_eq!(language_of("accountcard.cmp", "force-app/main/default/aura/accountcard/accountcard.cmp").as_deref(), Some("salesforce"));
+        assert_eq!(language_of("layout.cmp", "src/layout.cmp"), None);
+        let component = analyze_metadata(
+            "<aura:component controller=\"AccountService\" access=\"global\">\n  <aura:attribute name=\"recordId\" type=\"Id\"/>\n</aura:component>\n",
+            "force-app/main/default/aura/AccountCard/AccountCard.cmp",
+        );
+        assert_eq!(component[0].name, "AccountCard");
+        assert_eq!(component[0].language, "aura");
+        assert_eq!(component[0].parameters, vec!["recordId"]);
+        assert_eq!((component[1].name.as_str(), &component[1].element_type), ("AccountService", &CodeElementType::Module));
+        let page = analyze_metadata("<apex:page standardController=\"Case\" extensions=\"CaseExt, CaseAudit\">\n</apex:page>\n", "pages/CaseView.page");
+        assert_eq!(page.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(), vec!["CaseView", "CaseExt", "CaseAudit"]);
+        assert_eq!(page[0].return_type.as_deref(), Some("Case"));
+
+        let field = analyze_metadata(
+            "<CustomField>\n  <fullName>Account__c</fullName>\n  <type>Lookup</type>\n  <referenceTo>Account</referenceTo>\n</CustomField>\n",
+            "objects/Invoice__c/fields/Account__c.field-meta.xml",
+        );
+        assert_eq!(field[0].return_type.as_deref(), Some("Lookup(Account)"));
+        let flow = analyze_metadata(r#"<Flow>
+    <actionCalls>
+        <name>Score</name>
+        <actionName>LeadScorer</actionName>
+        <actionType>apex</actionType>
+    </actionCalls>
+    <actionCalls>
+        <actionName>emailSimple</actionName>
+        <actionType>emailSimple</actionType>
+    </actionCalls>
+    <processType>AutoLaunchedFlow</processType>
+    <start>
+        <object>Lead</object>
+        <triggerType>RecordAfterSave</triggerType>
+    </start>
+    <subflows>
+        <flowName>Notify_Owner</flowName>
+    </subflows>
+</Flow>
+"#, "flows/Lead_Router.flow-meta.xml");
+        assert_eq!(flow.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(), vec!["Lead_Router", "LeadScorer", "Notify_Owner"]);
+        assert_eq!(flow[0].signature.as_deref(), Some("AutoLaunchedFlow RecordAfterSave on Lead"));
+        assert_eq!(flow[1].line_number, 2);
+    }
+}
@@ -0,0 +1,420 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use crate::analysis::{CodeCall, CodeElement};
+use crate::analysis::utils::resolve_local_imports;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BoundaryKind {
+    Service, // Backed by its own dependency manifest, i.e. a separately built sub-project
+    Domain,  // A cohesive group of source directories inside a single build
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceBoundary {
+    pub name: String,
+    pub kind: BoundaryKind,
+    pub paths: Vec<String>,
+    pub file_count: usize,
+    pub element_count: usize,
+    pub internal_references: usize,
+    pub outbound_references: usize,
+    pub cohesion: f64,
+    pub data_stores: Vec<String>,
+    pub shared_data_stores: Vec<String>,
+    pub depends_on: Vec<String>,
+    pub confidence: f64,
+    pub evidence: Vec<String>,
+}
+
+/// A data store (database, cache, queue) referenced from a file
+#[derive(Debug, Clone)]
+pub struct DataStoreUse {
+    pub name: String,
+    pub file_path: String,
+}
+
+/// Directories that usually hold one folder per module rather than being a module themselves
+const CONTAINER_DIRS: &[&str] = &["src", "app", "apps", "lib", "libs", "packages", "services", "modules", "internal", "pkg", "cmd", "main", "java", "kotlin"];
+
+/// Suggests service/domain boundaries from sub-projects, references between them and the data stores they touch
+pub struct ServiceBoundaryInferrer {
+    projects: Vec<String>,
+}
+
+impl ServiceBoundaryInferrer {
+    pub fn new() -> Self {
+        ServiceBoundaryInferrer { projects: Vec::new() }
+    }
+
+    /// Use these repository-relative directories as the sub-projects instead of manifest locations
+    pub fn with_projects(mut self, projects: &[String]) -> Self {
+        self.projects = projects.iter()
+            .map(|p| p.trim_start_matches("./").trim_matches('/').to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        self
+    }
+
+    /// `manifest_paths` are repository-relative dependency manifests; every directory holding one
+    /// other than the root is treated as a sub-project. Without sub-projects the top-level source
+    /// directories are used as candidate domains instead. Configured projects replace both.
+    pub fn infer(
+        &self,
+        repo_path: &Path,
+        manifest_paths: &[String],
+        elements: &[CodeElement],
+        calls: &[CodeCall],
+        data_stores: &[DataStoreUse],
+    ) -> Vec<ServiceBoundary> {
+        let files: BTreeSet<&str> = elements.iter().map(|e| e.file_path.as_str()).collect();
+        if files.is_empty() {
+            return Vec::new();
+        }
+
+        let mut sub_projects: BTreeSet<String> = manifest_paths.iter()
+            .filter_map(|p| Path::new(p).parent().map(|d| d.to_string_lossy().replace('\\', "/")))
+            .filter(|d| !d.is_empty())
+            .collect();
+        // Nested manifests (e.g. a package inside a service) belong to the outer sub-project
+        let nested: Vec<String> = sub_projects.iter()
+            .filter(|d| sub_projects.iter().any(|outer| outer != *d && d.starts_with(&format!("{}/", outer))))
+            .cloned()
+            .collect();
+        for dir in nested {
+            sub_projects.remove(&dir);
+        }
+
+        if !self.projects.is_empty() {
+            sub_projects = self.projects.iter().cloned().collect();
+        }
+
+        let use_sub_projects = !self.projects.is_empty() || sub_projects.len() >= 2;
+        let unit_of = |file: &str| -> String {
+            if use_sub_projects {
+                sub_projects.iter()
+                    .find(|d| file.starts_with(&format!("{}/", d)))
+                    .cloned()
+                    .unwrap_or_default()
+            } else {
+                directory_unit(file)
+            }
+        };
+
+        let file_units: HashMap<&str, String> = files.iter().map(|f| (*f, unit_of(f))).collect();
+        let mut units: BTreeMap<String, Unit> = BTreeMap::new();
+        for unit in file_units.values() {
+            let entry = units.entry(unit.clone()).or_default();
+            entry.files += 1;
+            entry.manifest = use_sub_projects && !unit.is_empty();
+        }
+        for element in elements {
+            if let Some(unit) = file_units.get(element.file_path.as_str()) {
+                units.entry(unit.clone()).or_default().elements += 1;
+            }
+        }
+        if units.len() < 2 {
+            return Vec::new();
+        }
+
+        // References between units: resolved calls plus file-level imports
+        let mut references: HashMap<(String, String), usize> = HashMap::new();
+        let element_files: HashMap<&str, &str> = elements.iter().map(|e| (e.id.as_str(), e.file_path.as_str())).collect();
+        for call in calls {
+            if let (Some(from), Some(to)) = (element_files.get(call.caller_id.as_str()), element_files.get(call.callee_id.as_str())) {
+                *references.entry((file_units[from].clone(), file_units[to].clone())).or_default() += 1;
+            }
+        }
+        let file_list: Vec<&str> = files.iter().copied().collect();
+        for file in &file_list {
+            let content = match std::fs::read_to_string(repo_path.join(file)) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            for (_, target) in resolve_local_imports(&content, file, &file_list) {
+                *references.entry((file_units[file].clone(), file_units[target].clone())).or_default() += 1;
+            }
+        }
+        for ((from, to), count) in &references {
+            if from == to {
+                units.get_mut(from).unwrap().internal += count;
+            } else {
+                let unit = units.get_mut(from).unwrap();
+                unit.outbound += count;
+                *unit.depends_on.entry(to.clone()).or_default() += count;
+            }
+        }
+
+        // Data stores per unit
+        let mut store_units: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for store in data_stores {
+            let unit = file_units.get(store.file_path.as_str()).cloned().unwrap_or_else(|| unit_of(&store.file_path));
+            if units.contains_key(&unit) {
+                store_units.entry(store.name.clone()).or_default().insert(unit);
+            }
+        }
+        for (store, owners) in &store_units {
+            for owner in owners {
+                units.get_mut(owner).unwrap().data_stores.insert(store.clone());
+            }
+        }
+
+        // Directory units that share a data store and reference each other both ways are one domain
+        let mut groups: BTreeMap<String, String> = units.keys().map(|u| (u.clone(), u.clone())).collect();
+        if !use_sub_projects {
+            let names: Vec<String> = units.keys().cloned().collect();
+            for (i, a) in names.iter().enumerate() {
+                for b in names.iter().skip(i + 1) {
+                    let shares_store = units[a].data_stores.intersection(&units[b].data_stores).next().is_some();
+                    let mutual = units[a].depends_on.contains_key(b) && units[b].depends_on.contains_key(a);
+                    if shares_store && mutual {
+                        let (root_a, root_b) = (find(&groups, a), find(&groups, b));
+                        if root_a != root_b {
+                            groups.insert(root_b, root_a);
+                        }
+                    }
+                }
+            }
+        }
+        let mut merged: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for unit in units.keys() {
+            merged.entry(find(&groups, unit)).or_default().push(unit.clone());
+        }
+
+        let mut boundaries = Vec::new();
+        for (root, members) in &merged {
+            let mut files = 0;
+            let mut element_count = 0;
+            let mut internal = 0;
+            let mut outbound = 0;
+            let mut stores: BTreeSet<String> = BTreeSet::new();
+            let mut depends_on: BTreeMap<String, usize> = BTreeMap::new();
+            for member in members {
+                let unit = &units[member];
+                files += unit.files;
+                element_count += unit.elements;
+                internal += unit.internal;
+                stores.extend(unit.data_stores.iter().cloned());
+                for (target, count) in &unit.depends_on {
+                    if members.contains(target) {
+                        internal += count;
+                    } else {
+                        outbound += count;
+                        *depends_on.entry(display_name(&find(&groups, target))).or_default() += count;
+                    }
+                }
+            }
+
+            let shared: Vec<String> = stores.iter()
+                .filter(|s| store_units[*s].iter().any(|u| !members.contains(u)))
+                .cloned()
+                .collect();
+            let cohesion = if internal + outbound == 0 {
+                0.0
+            } else {
+                internal as f64 / (internal + outbound) as f64
+            };
+            let manifest = units[root].manifest;
+
+            let mut evidence = Vec::new();
+            let mut confidence: f64 = 0.4;
+            if manifest {
+                confidence += 0.2;
+                evidence.push(format!("Has its own dependency manifest in {}/", root));
+            }
+            if members.len() > 1 {
+                evidence.push(format!("Merged {} directories that share data stores and reference each other", members.len()));
+            }
+            if internal + outbound > 0 {
+                confidence += 0.3 * cohesion;
+                evidence.push(format!("{} of {} references stay inside the boundary", internal, internal + outbound));
+            } else {
+                evidence.push("No references to or from other boundaries were found".to_string());
+            }
+            let owned = stores.len() - shared.len();
+            if owned > 0 {
+                confidence += 0.1;
+                evidence.push(format!("Owns {} data store(s) exclusively", owned));
+            }
+            if !shared.is_empty() {
+                confidence -= 0.15 * shared.len() as f64;
+                evidence.push(format!("Shares data store(s) with other boundaries: {}", shared.join(", ")));
+            }
+            if root.is_empty() {
+                confidence -= 0.2;
+                evidence.push("Files outside any sub-project or module directory".to_string());
+            }
+
+            let mut depends: Vec<(String, usize)> = depends_on.into_iter().collect();
+            depends.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+            boundaries.push(ServiceBoundary {
+                name: display_name(root),
+                kind: if manifest { BoundaryKind::Service } else { BoundaryKind::Domain },
+                paths: members.iter().map(|m| if m.is_empty() { ".".to_string() } else { m.clone() }).collect(),
+                file_count: files,
+                element_count,
+                internal_references: internal,
+                outbound_references: outbound,
+                cohesion: (cohesion * 100.0).round() / 100.0,
+                data_stores: stores.into_iter().collect(),
+                shared_data_stores: shared,
+                depends_on: depends.into_iter().map(|(name, _)| name).collect(),
+                confidence: (confidence.clamp(0.05, 1.0) * 100.0).round() / 100.0,
+                evidence,
+            });
+        }
+
+        boundaries.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name)));
+        boundaries
+    }
+}
+
+#[derive(Default)]
+struct Unit {
+    files: usize,
+    elements: usize,
+    internal: usize,
+    outbound: usize,
+    manifest: bool,
+    depends_on: BTreeMap<String, usize>,
+    data_stores: BTreeSet<String>,
+}
+
+fn find(groups: &BTreeMap<String, String>, unit: &str) -> String {
+    let mut current = unit.to_string();
+    while let Some(parent) = groups.get(&current) {
+        if *parent == current {
+            break;
+        }
+        current = parent.clone();
+    }
+    current
+}
+
+fn display_name(unit: &str) -> String {
+    if unit.is_empty() {
+        "(root)".to_string()
+    } else {
+        unit.to_string()
+    }
+}
+
+/// First directory below any leading container directories: `src/billing/api.ts` -> `src/billing`.
+/// Files directly inside a container directory belong to the root unit.
+pub(crate) fn directory_unit(file: &str) -> String {
+    let parts: Vec<&str> = file.split('/').collect();
+    let dirs = &parts[..parts.len().saturating_sub(1)];
+    let mut idx = 0;
+    while idx < dirs.len() && CONTAINER_DIRS.contains(&dirs[idx]) {
+        idx += 1;
+    }
+    // Java/Kotlin packages nest under a reverse-DNS prefix: src/main/java/com/acme/billing
+    if idx < dirs.len() && matches!(dirs[idx], "com" | "org" | "net" | "io") {
+        idx += 2;
+    }
+    if idx >= dirs.len() {
+        return String::new();
+    }
+    dirs[..=idx].join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::CodeElementType;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    fn element(id: &str, file_path: &str) -> CodeElement {
+        CodeElement {
+            id: id.to_string(),
+            name: id.to_string(),
+            element_type: CodeElementType::Function,
+            file_path: file_path.to_string(),
+            line_number: 1,
+            language: "typescript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_infers_boundaries_from_directories_and_data_stores() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("src/orders/service.ts", "import { charge } from \"../billing/charge\";\nimport { load } from \"./repo\";\n"),
+            ("src/orders/repo.ts", ""),
+            ("src/billing/charge.ts", "import { save } from \"./ledger\";\n"),
+            ("src/billing/ledger.ts", ""),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let elements: Vec<CodeElement> = files.iter().enumerate().map(|(i, (p, _))| element(&format!("e{}", i), p)).collect();
+        let stores = vec![
+            DataStoreUse { name: "PostgreSQL".to_string(), file_path: "src/orders/repo.ts".to_string() },
+            DataStoreUse { name: "Redis".to_string(), file_path: "src/billing/ledger.ts".to_string() },
+        ];
+
+        let boundaries = ServiceBoundaryInferrer::new().infer(temp_dir.path(), &["package.json".to_string()], &elements, &[], &stores);
+        assert_eq!(boundaries.len(), 2);
+        let orders = boundaries.iter().find(|b| b.name == "src/orders").unwrap();
+        assert_eq!(orders.kind, BoundaryKind::Domain);
+        assert_eq!(orders.internal_references, 1);
+        assert_eq!(orders.outbound_references, 1);
+        assert_eq!(orders.depends_on, vec!["src/billing".to_string()]);
+        assert_eq!(orders.data_stores, vec!["PostgreSQL".to_string()]);
+        let billing = boundaries.iter().find(|b| b.name == "src/billing").unwrap();
+        assert_eq!(billing.cohesion, 1.0);
+        assert!(billing.confidence > orders.confidence);
+    }
+
+    #[test]
+    fn test_nested_manifests_shared_stores_and_single_directory_repositories() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            ("services/api/index.ts", "import { slug } from \"./lib/util\";\n"),
+            ("services/api/lib/util.ts", ""),
+            ("services/worker/job.ts", ""),
+            ("scripts/seed.ts", ""),
+        ];
+        for (path, content) in files {
+            write(temp_dir.path(), path, content);
+        }
+        let elements: Vec<CodeElement> = files.iter().enumerate().map(|(i, (p, _))| element(&format!("e{}", i), p)).collect();
+        let manifests = ["services/api/package.json", "services/api/lib/package.json", "services/worker/package.json"]
+            .map(String::from);
+        let stores = vec![
+            DataStoreUse { name: "PostgreSQL".to_string(), file_path: "services/api/index.ts".to_string() },
+            DataStoreUse { name: "PostgreSQL".to_string(), file_path: "services/worker/job.ts".to_string() },
+        ];
+
+        let boundaries = ServiceBoundaryInferrer::new().infer(temp_dir.path(), &manifests, &elements, &[], &stores);
+        let names: Vec<&str> = boundaries.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["services/api", "services/worker", "(root)"]);
+        // The nested package belongs to the outer service
+        let api = &boundaries[0];
+        assert_eq!(api.kind, BoundaryKind::Service);
+        assert_eq!(api.file_count, 2);
+        assert_eq!(api.internal_references, 1);
+        assert_eq!(api.shared_data_stores, vec!["PostgreSQL".to_string()]);
+        let root = &boundaries[2];
+        assert_eq!(root.kind, BoundaryKind::Domain);
+        assert_eq!(root.paths, vec![".".to_string()]);
+        assert!(root.evidence.iter().any(|e| e.contains("outside any sub-project")));
+
+        // A single source directory has nothing to split
+        let single = [element("a", "src/orders/a.ts"), element("b", "src/orders/b.ts")];
+        assert!(ServiceBoundaryInferrer::new().infer(temp_dir.path(), &[], &single, &[], &[]).is_empty());
+        assert!(ServiceBoundaryInferrer::new().infer(temp_dir.path(), &[], &[], &[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Declarations that span more than their first line: signatures whose parameter list, return type
+//! or `where`/`extends` clause continues on the following lines, and the decorators, annotations
+//! and attributes stacked above a declaration (`@app.route(...)`, `@Injectable()`, `#[derive(...)]`).
+
+/// Lines a signature may span before it is cut off
+const MAX_SIGNATURE_LINES: usize = 12;
+
+/// Lines a single decorator may span (`@Component({\n  selector: ...\n})`)
+const MAX_DECORATOR_LINES: usize = 12;
+
+/// A line ending in one of these continues the signature on the next line
+const CONTINUED_BY_ENDING: &[&str] = &[",", "(", "[", "->", "where", "|", "&"];
+
+/// A line starting with one of these continues the signature of the line before it
+const CONTINUES_BEFORE: &[&str] = &["->", ")", "]", "where", "extends", "implements", "throws", ":", "{", "|", "&"];
+
+/// Net change in `(`/`[` nesting over a line, ignoring brackets inside string literals
+fn bracket_delta(line: &str) -> i32 {
+    let mut delta = 0;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for c in line.chars() {
+        match quote {
+            Some(q) => {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '"' | '`' => quote = Some(c),
+                '(' | '[' => delta += 1,
+                ')' | ']' => delta -= 1,
+                _ => {}
+            },
+        }
+        prev = c;
+    }
+    delta
+}
+
+/// The whole signature of the declaration on `line_number` (1-based), its lines joined with single
+/// spaces, or `None` when it fits on that one line
+pub fn full_signature(lines: &[&str], line_number: usize, language: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    let mut depth = 0;
+    for (idx, line) in lines.iter().enumerate().skip(line_number.checked_sub(1)?).take(MAX_SIGNATURE_LINES) {
+        let line = line.trim();
+        parts.push(line);
+        depth += bracket_delta(line);
+        // A brace opens the body even inside an open call (`useCallback(async () => {`)
+        let opens_body = language != "python" && line.contains('{');
+        if depth > 0 && !opens_body {
+            continue;
+        }
+        let finished = if language == "python" {
+            line.ends_with(':')
+        } else {
+            opens_body || line.ends_with(';') || line.ends_with("=>") || line.ends_with('=')
+        };
+        if finished {
+            break;
+        }
+        let continued = CONTINUED_BY_ENDING.iter().any(|e| line.ends_with(e))
+            || lines.get(idx + 1)
+                .map(|next| CONTINUES_BEFORE.iter().any(|s| next.trim().starts_with(s)))
+                .unwrap_or(false);
+        if !continued {
+            break;
+        }
+    }
+    if parts.len() < 2 {
+        return None;
+    }
+    Some(join_lines(&parts))
+}
+
+/// Joins the lines of one declaration with single spaces, without the padding and trailing commas
+/// the line breaks leave inside brackets
+fn join_lines(parts: &[&str]) -> String {
+    let joined = parts.iter().filter(|p| !p.is_empty()).copied().collect::<Vec<_>>().join(" ");
+    joined.replace("( ", "(").replace("[ ", "[")
+        .replace(", )", ")").replace(",)", ")").replace(" )", ")")
+        .replace(", ]", "]").replace(" ]", "]")
+}
+
+/// Whether `text` is exactly one decorator, annotation or attribute with nothing declared after it
+fn is_decorator(text: &str) -> bool {
+    if let Some(attribute) = text.strip_prefix("#[") {
+        return text.ends_with(']') && !attribute.starts_with("doc");
+    }
+    let Some(rest) = text.strip_prefix('@') else {
+        return false;
+    };
+    let name_len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.')).unwrap_or(rest.len());
+    if name_len == 0 {
+        return false;
+    }
+    let rest = rest[name_len..].trim_start();
+    if !rest.starts_with('(') {
+        return rest.is_empty();
+    }
+    let mut depth = 0;
+    for (pos, c) in rest.char_indices() {
+        depth += bracket_delta(&c.to_string());
+        if depth == 0 {
+            return rest[pos + 1..].trim().is_empty();
+        }
+    }
+    false
+}
+
+/// The decorators directly above the declaration on `line_number` (1-based), in source order.
+/// Objective-C is skipped since its `@property`/`@interface` lines are declarations, not decorators.
+pub fn decorators(lines: &[&str], line_number: usize, language: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    if language == "objective-c" {
+        return found;
+    }
+    let mut pending: Vec<&str> = Vec::new();
+    for line in lines[..line_number.saturating_sub(1).min(lines.len())].iter().rev() {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+        pending.insert(0, line);
+        let joined = join_lines(&pending);
+        if is_decorator(&joined) {
+            found.insert(0, joined);
+            pending.clear();
+        } else if pending.len() >= MAX_DECORATOR_LINES || bracket_delta(&joined) >= 0 {
+            // Not the tail of a multi-line decorator still waiting for its opening line
+            break;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multi_line_signatures_and_decorators() {
+        let python = "@app.route(\n    \"/orders\",\n    methods=[\"POST\"],\n)\n@login_required\ndef create_order(\n    customer_id: int,\n    items: List[Item],\n) -> Order:\n    pass\n";
+        let lines: Vec<&str> = python.lines().collect();
+        assert_eq!(full_signature(&lines, 6, "python").as_deref(), Some("def create_order(customer_id: int, items: List[Item]) -> Order:"));
+        assert_eq!(decorators(&lines, 6, "python"), vec!["@app.route(\"/orders\", methods=[\"POST\"])", "@login_required"]);
+
+        let rust = "/// Loads an order\n#[inline]\npub fn load<T>(\n    id: u64,\n) -> Result<T>\nwhere\n    T: Clone,\n{\n}\nfn short() {}\n";
+        let lines: Vec<&str> = rust.lines().collect();
+        assert_eq!(full_signature(&lines, 3, "rust").as_deref(), Some("pub fn load<T>(id: u64) -> Result<T> where T: Clone, {"));
+        assert_eq!(decorators(&lines, 3, "rust"), vec!["#[inline]"]);
+        assert_eq!(full_signature(&lines, 10, "rust"), None);
+
+        // A Swift property wrapper above a function declares the property, not the function
+        let swift = "@Published var count = 0\nfunc increment() {\n}\n";
+        let lines: Vec<&str> = swift.lines().collect();
+        assert!(decorators(&lines, 2, "swift").is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+//! Fully-qualified names for code elements and the per-repository symbol table built from them.
+//! A qualified name is the file's module path followed by the element's containers, joined with
+//! `::` whatever the language (`src::orders::service::OrderService::create`), so it stays the same
+//! across re-analysis and tells apart elements that share a bare name.
+
+use std::collections::HashMap;
+use crate::analysis::{CodeElement, CodeElementType};
+
+/// File stems that name their directory rather than a module of their own
+const INDEX_STEMS: &[&str] = &["mod", "index", "__init__", "lib", "main"];
+
+/// `src/orders/service.ts` -> `src::orders::service`, `src/orders/mod.rs` -> `src::orders`
+pub fn module_path(file_path: &str) -> String {
+    let without_ext = match file_path.rsplit_once('.') {
+        Some((stem, _)) if !stem.ends_with('/') && !stem.is_empty() => stem,
+        _ => file_path,
+    };
+    let mut segments: Vec<&str> = without_ext.split(['/', '\\'])
+        .filter(|s| !s.is_empty() && *s != ".")
+        .collect();
+    if segments.len() > 1 && segments.last().map(|s| INDEX_STEMS.contains(s)).unwrap_or(false) {
+        segments.pop();
+    }
+    segments.join("::")
+}
+
+/// Sets `qualified_name` from the file and chain of containers on every element that has none
+/// yet (plugins may supply their own). Imported modules are external, so their qualified name is
+/// the module name itself.
+pub fn assign_qualified_names(elements: &mut [CodeElement]) {
+    let parents: HashMap<String, (String, Option<String>)> = elements.iter()
+        .map(|e| (e.id.clone(), (e.name.clone(), e.parent_id.clone())))
+        .collect();
+    for element in elements.iter_mut().filter(|e| e.qualified_name.is_empty()) {
+        if element.element_type == CodeElementType::Module {
+            element.qualified_name = element.name.clone();
+            continue;
+        }
+        let mut segments = vec![element.name.clone()];
+        let mut parent = element.parent_id.clone();
+        // Bounded in case a malformed plugin element points back at itself
+        while let Some((name, next)) = parent.as_ref().and_then(|id| parents.get(id)).filter(|_| segments.len() < 32) {
+            segments.push(name.clone());
+            parent = next.clone();
+        }
+        segments.push(module_path(&element.file_path));
+        segments.retain(|s| !s.is_empty());
+        segments.reverse();
+        element.qualified_name = segments.join("::");
+    }
+}
+
+/// Drops elements detected twice on the same line under the same qualified name, pointing any
+/// `parent_id` at the copy that is kept
+pub fn dedupe_elements(elements: Vec<CodeElement>) -> Vec<CodeElement> {
+    let mut kept_ids: HashMap<(String, usize), String> = HashMap::new();
+    let mut replaced: HashMap<String, String> = HashMap::new();
+    let mut kept = Vec::with_capacity(elements.len());
+    for element in elements {
+        if element.element_type == CodeElementType::Module {
+            kept.push(element);
+            continue;
+        }
+        let key = (element.qualified_name.clone(), element.line_number);
+        match kept_ids.get(&key) {
+            Some(id) => {
+                replaced.insert(element.id, id.clone());
+            }
+            None => {
+                kept_ids.insert(key, element.id.clone());
+                kept.push(element);
+            }
+        }
+    }
+    for element in kept.iter_mut() {
+        if let Some(id) = element.parent_id.as_ref().and_then(|p| replaced.get(p)) {
+            element.parent_id = Some(id.clone());
+        }
+    }
+    kept
+}
+
+/// Element ids by qualified name, plus the bare-name index used to resolve partial references
+pub struct SymbolTable {
+    by_qualified: HashMap<String, String>,
+    by_name: HashMap<String, Vec<(String, String)>>, // name -> [(qualified name, id)]
+    files: HashMap<String, String>,                  // id -> file_path
+}
+
+impl SymbolTable {
+    pub fn new(elements: &[CodeElement]) -> Self {
+        let mut by_qualified = HashMap::new();
+        let mut by_name: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        let mut files = HashMap::new();
+        for element in elements.iter().filter(|e| !e.qualified_name.is_empty()) {
+            // Overloads share a qualified name; the first declaration stands for all of them
+            by_qualified.entry(element.qualified_name.clone()).or_insert_with(|| element.id.clone());
+            by_name.entry(element.name.clone()).or_default().push((element.qualified_name.clone(), element.id.clone()));
+            files.insert(element.id.clone(), element.file_path.clone());
+        }
+        SymbolTable { by_qualified, by_name, files }
+    }
+
+    /// The element with exactly this qualified name
+    pub fn get(&self, qualified_name: &str) -> Option<&str> {
+        self.by_qualified.get(qualified_name).map(|id| id.as_str())
+    }
+
+    /// Resolves a reference as written in `from_file`: `OrderService.create`, `orders::OrderService`
+    /// or a bare `create`. A qualified name ending in the reference matches; ties go to the element
+    /// in `from_file`, and references that stay ambiguous resolve to nothing.
+    pub fn resolve(&self, reference: &str, from_file: &str) -> Option<&str> {
+        let reference = reference.replace(['.', '#'], "::").replace("->", "::");
+        let reference = reference.trim_matches(':');
+        if let Some(id) = self.get(reference) {
+            return Some(id);
+        }
+        let name = reference.rsplit("::").next()?;
+        let suffix = format!("::{}", reference);
+        let candidates: Vec<&str> = self.by_name.get(name)?
+            .iter()
+            .filter(|(qualified, _)| qualified == reference || qualified.ends_with(&suffix))
+            .map(|(_, id)| id.as_str())
+            .collect();
+        match candidates.as_slice() {
+            [id] => Some(id),
+            _ => {
+                let local: Vec<&str> = candidates.into_iter()
+                    .filter(|id| self.files.get(*id).map(|f| f == from_file).unwrap_or(false))
+                    .collect();
+                if local.len() == 1 { Some(local[0]) } else { None }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(id: &str, name: &str, element_type: CodeElementType, file_path: &str, parent_id: Option<&str>) -> CodeElement {
+        CodeElement {
+            id: id.to_string(),
+            name: name.to_string(),
+            element_type,
+            file_path: file_path.to_string(),
+            line_number: 1,
+            language: "typescript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: parent_id.map(|p| p.to_string()),
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_qualified_names_resolve_across_files() {
+        assert_eq!(module_path("src/orders/service.ts"), "src::orders::service");
+        assert_eq!(module_path("src/orders/mod.rs"), "src::orders");
+        assert_eq!(module_path("app.py"), "app");
+
+        let mut elements = vec![
+            element("c1", "OrderService", CodeElementType::Class, "src/orders/service.ts", None),
+            element("m1", "create", CodeElementType::Method, "src/orders/service.ts", Some("c1")),
+            element("f1", "create", CodeElementType::Function, "src/billing/index.ts", None),
+            element("i1", "lodash", CodeElementType::Module, "src/billing/index.ts", None),
+        ];
+        assign_qualified_names(&mut elements);
+        assert_eq!(elements[1].qualified_name, "src::orders::service::OrderService::create");
+        assert_eq!(elements[2].qualified_name, "src::billing::create");
+        assert_eq!(elements[3].qualified_name, "lodash");
+
+        let symbols = SymbolTable::new(&elements);
+        assert_eq!(symbols.get("src::billing::create"), Some("f1"));
+        assert_eq!(symbols.resolve("OrderService.create", "src/api/routes.ts"), Some("m1"));
+        // A bare name shared by two files only resolves from one of them
+        assert_eq!(symbols.resolve("create", "src/api/routes.ts"), None);
+        assert_eq!(symbols.resolve("create", "src/billing/index.ts"), Some("f1"));
+    }
+}
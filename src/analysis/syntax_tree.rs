@@ -0,0 +1,474 @@
+//! The tree-sitter backend of [`CodeAnalyzer`](super::CodeAnalyzer), built with the `tree-sitter`
+//! feature. It reads JS/TS, Python, Rust, Go and Java into the same elements as the line
+//! heuristics, but from the syntax tree: signatures that span lines, classes and functions nested
+//! in each other, Rust `impl` and Go receiver methods attached to their type, and the calls each
+//! function makes, which [`call_graph`](super::call_graph) resolves.
+
+use crate::analysis::call_graph::CallReference;
+use crate::analysis::code_structure::CodeElement;
+
+/// What the backend read from one file
+pub struct ParsedFile {
+    pub elements: Vec<CodeElement>,
+    pub calls: Vec<CallReference>,
+}
+
+/// Without the feature every file goes to the line heuristics
+#[cfg(not(feature = "tree-sitter"))]
+pub fn parse(_language: &str, _path: &str, _content: &str) -> Option<ParsedFile> {
+    None
+}
+
+/// Parses `content` as `language` (as named by `utils::detect_language`), or `None` when the
+/// language has no grammar here and the line heuristics should read it
+#[cfg(feature = "tree-sitter")]
+pub fn parse(language: &str, path: &str, content: &str) -> Option<ParsedFile> {
+    let grammar: tree_sitter::Language = match language {
+        "javascript" => tree_sitter_javascript::LANGUAGE.into(),
+        "typescript" if path.ends_with(".tsx") => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        "python" => tree_sitter_python::LANGUAGE.into(),
+        "rust" => tree_sitter_rust::LANGUAGE.into(),
+        "go" => tree_sitter_go::LANGUAGE.into(),
+        "java" => tree_sitter_java::LANGUAGE.into(),
+        _ => return None,
+    };
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&grammar).ok()?;
+    let tree = parser.parse(content, None)?;
+
+    let mut walker = backend::Walker::new(language, path, content);
+    walker.walk(tree.root_node(), &backend::Scope::default());
+    Some(walker.finish())
+}
+
+#[cfg(feature = "tree-sitter")]
+mod backend {
+    use tree_sitter::Node;
+    use super::ParsedFile;
+    use crate::analysis::call_graph::{call_reference, CallReference};
+    use crate::analysis::code_structure::{CodeElement, CodeElementType};
+
+    /// Where a node sits: the container a declaration belongs to, or the function a call is made from
+    #[derive(Clone, Default)]
+    pub struct Scope {
+        container: Option<String>,
+        function: Option<String>,
+        /// The type of the enclosing Rust `impl` block
+        impl_type: Option<String>,
+    }
+
+    enum Declaration {
+        Container(CodeElementType),
+        Function,
+    }
+
+    pub struct Walker<'a> {
+        language: &'a str,
+        path: &'a str,
+        source: &'a [u8],
+        lines: Vec<&'a str>,
+        elements: Vec<CodeElement>,
+        calls: Vec<CallReference>,
+        /// Methods of Rust `impl` blocks and Go receivers, by the type they are attached to
+        receivers: Vec<(usize, String)>,
+    }
+
+    /// `*Order`, `&mut Order<T>`, `Order[T]` -> `Order`
+    fn type_name(text: &str) -> String {
+        let text = text.trim_start_matches(['*', '&']).trim_start_matches("mut ").trim();
+        text.split(['<', '[', ' ']).next().unwrap_or(text).to_string()
+    }
+
+    impl<'a> Walker<'a> {
+        pub fn new(language: &'a str, path: &'a str, content: &'a str) -> Self {
+            Walker {
+                language,
+                path,
+                source: content.as_bytes(),
+                lines: content.lines().collect(),
+                elements: Vec::new(),
+                calls: Vec::new(),
+                receivers: Vec::new(),
+            }
+        }
+
+        fn text(&self, node: Node) -> &'a str {
+            node.utf8_text(self.source).unwrap_or("")
+        }
+
+        fn field_text(&self, node: Node, field: &str) -> Option<&'a str> {
+            node.child_by_field_name(field).map(|n| self.text(n))
+        }
+
+        /// Attaches receiver methods to the type of that name declared in the same file
+        pub fn finish(mut self) -> ParsedFile {
+            for (index, receiver) in std::mem::take(&mut self.receivers) {
+                let parent = self.elements.iter()
+                    .find(|e| e.name == receiver && !matches!(e.element_type, CodeElementType::Function | CodeElementType::Method | CodeElementType::Module))
+                    .map(|e| e.id.clone());
+                self.elements[index].parent_id = parent;
+            }
+            ParsedFile { elements: self.elements, calls: self.calls }
+        }
+
+        pub fn walk(&mut self, node: Node, scope: &Scope) {
+            let mut inner = scope.clone();
+            match node.kind() {
+                "impl_item" => inner.impl_type = self.field_text(node, "type").map(type_name),
+                "import_statement" | "import_from_statement" | "use_declaration" | "import_spec" | "import_declaration" => {
+                    for module in self.imports(node) {
+                        let line = node.start_position().row + 1;
+                        self.elements.push(self.element(module, CodeElementType::Module, line));
+                    }
+                    return;
+                }
+                "call_expression" | "call" | "method_invocation" => self.call(node, scope),
+                _ => {}
+            }
+            if let Some(declaration) = self.declaration(node) {
+                if let Some(id) = self.declare(node, declaration, scope, &mut inner) {
+                    inner.function = Some(id);
+                }
+            }
+
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.named_children(&mut cursor).collect();
+            for child in children {
+                self.walk(child, &inner);
+            }
+        }
+
+        fn declaration(&self, node: Node) -> Option<Declaration> {
+            Some(match node.kind() {
+                "class_declaration" | "abstract_class_declaration" | "class_definition" | "record_declaration" => {
+                    Declaration::Container(CodeElementType::Class)
+                }
+                "class" if node.child_by_field_name("name").is_some() => Declaration::Container(CodeElementType::Class),
+                "interface_declaration" | "trait_item" => Declaration::Container(CodeElementType::Interface),
+                "enum_declaration" | "enum_item" => Declaration::Container(CodeElementType::Enum),
+                "struct_item" => Declaration::Container(CodeElementType::Struct),
+                "type_spec" => match node.child_by_field_name("type")?.kind() {
+                    "struct_type" => Declaration::Container(CodeElementType::Struct),
+                    "interface_type" => Declaration::Container(CodeElementType::Interface),
+                    _ => return None,
+                },
+                "function_declaration" | "generator_function_declaration" | "function_definition" | "function_item"
+                | "function_signature_item" | "method_declaration" | "constructor_declaration" | "method_definition"
+                | "method_signature" | "abstract_method_signature" | "function_signature" | "method_elem" => Declaration::Function,
+                "variable_declarator" => match node.child_by_field_name("value")?.kind() {
+                    "arrow_function" | "function_expression" | "function" => Declaration::Function,
+                    _ => return None,
+                },
+                _ => return None,
+            })
+        }
+
+        /// Records the element `node` declares and narrows `inner` to it; returns the id of a function
+        fn declare(&mut self, node: Node, declaration: Declaration, scope: &Scope, inner: &mut Scope) -> Option<String> {
+            let name_node = node.child_by_field_name("name")?;
+            let line_number = name_node.start_position().row + 1;
+            let in_type = scope.container.is_some() || scope.impl_type.is_some() && scope.function.is_none();
+            let element_type = match declaration {
+                Declaration::Container(element_type) => element_type,
+                Declaration::Function if in_type || node.kind() == "method_declaration" && self.language == "go" => CodeElementType::Method,
+                Declaration::Function => CodeElementType::Function,
+            };
+            let mut element = self.element(self.text(name_node).to_string(), element_type.clone(), line_number);
+            element.parent_id = scope.container.clone();
+            element.signature = Some(self.signature(node, name_node));
+            element.doc_comment = self.doc_comment(node, line_number - 1);
+            element.visibility = self.visibility(node, &element.name, in_type);
+            element.decorators = crate::analysis::signatures::decorators(&self.lines, line_number, self.language);
+
+            let id = element.id.clone();
+            let is_function = matches!(element_type, CodeElementType::Function | CodeElementType::Method);
+            if is_function {
+                let function = if node.kind() == "variable_declarator" { node.child_by_field_name("value")? } else { node };
+                element.parameters = match function.child_by_field_name("parameters") {
+                    Some(parameters) => self.parameter_names(parameters),
+                    None => self.field_text(function, "parameter").map(|p| vec![p.to_string()]).unwrap_or_default(),
+                };
+                let return_type = match (self.language, node.kind()) {
+                    ("java", _) => node.child_by_field_name("type"),
+                    ("go", _) => node.child_by_field_name("result"),
+                    _ => function.child_by_field_name("return_type"),
+                };
+                element.return_type = return_type
+                    .map(|r| self.text(r).trim_start_matches([':', '-', '>', ' ']).trim().to_string())
+                    .filter(|r| !r.is_empty());
+            }
+
+            let receiver = match (self.language, node.kind()) {
+                ("go", "method_declaration") => node.child_by_field_name("receiver")
+                    .and_then(|r| r.named_child(0))
+                    .and_then(|p| self.field_text(p, "type"))
+                    .map(type_name),
+                ("rust", _) if scope.container.is_none() && scope.function.is_none() => scope.impl_type.clone(),
+                _ => None,
+            };
+            if let Some(receiver) = receiver {
+                self.receivers.push((self.elements.len(), receiver));
+            }
+            self.elements.push(element);
+
+            // `Self::` in a method body still means the impl's type
+            *inner = Scope {
+                container: (!is_function).then(|| id.clone()),
+                function: None,
+                impl_type: if is_function { scope.impl_type.clone() } else { None },
+            };
+            is_function.then_some(id)
+        }
+
+        fn element(&self, name: String, element_type: CodeElementType, line_number: usize) -> CodeElement {
+            CodeElement {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                element_type,
+                file_path: self.path.to_string(),
+                line_number,
+                language: self.language.to_string(),
+                signature: None,
+                doc_comment: None,
+                visibility: None,
+                parameters: Vec::new(),
+                return_type: None,
+                parent_id: None,
+                qualified_name: String::new(),
+                decorators: Vec::new(),
+                origin: Default::default(),
+            }
+        }
+
+        /// The declaration up to its body, from the line its name is on (annotations above it are
+        /// decorators) and including `export`/`const` before it, with whitespace collapsed
+        fn signature(&self, node: Node, name_node: Node) -> String {
+            let mut outer = node;
+            while let Some(parent) = outer.parent() {
+                let wraps = matches!(parent.kind(), "export_statement" | "lexical_declaration" | "variable_declaration")
+                    && parent.start_position().row == name_node.start_position().row;
+                if !wraps {
+                    break;
+                }
+                outer = parent;
+            }
+            let row = name_node.start_position().row;
+            let start = if outer.start_position().row == row {
+                outer.start_byte()
+            } else {
+                name_node.start_byte() - name_node.start_position().column
+            };
+            let body = if node.kind() == "variable_declarator" {
+                node.child_by_field_name("value").and_then(|v| v.child_by_field_name("body"))
+            } else {
+                node.child_by_field_name("body")
+            };
+            let end = body.map(|b| b.start_byte()).unwrap_or(node.end_byte()).max(start);
+            let text = std::str::from_utf8(&self.source[start..end]).unwrap_or("");
+            text.split_whitespace().collect::<Vec<_>>().join(" ").trim_end_matches(['{', '=']).trim_end().to_string()
+        }
+
+        /// Python docstrings follow the whole signature; everything else is documented above
+        fn doc_comment(&self, node: Node, row: usize) -> Option<String> {
+            let docstring_row = (self.language == "python").then(|| node.child_by_field_name("body")).flatten()
+                .and_then(|body| body.named_child(0))
+                .filter(|first| first.kind() == "expression_statement" && first.named_child(0).is_some_and(|s| s.kind() == "string"))
+                .map(|first| first.start_position().row);
+            let row = docstring_row.map(|r| r.saturating_sub(1)).unwrap_or(row);
+            crate::analysis::doc_comments::extract_doc_comment(&self.lines, row)
+        }
+
+        fn visibility(&self, node: Node, name: &str, in_type: bool) -> Option<String> {
+            let mut cursor = node.walk();
+            let modifiers: Vec<Node> = node.children(&mut cursor).collect();
+            let visibility = match self.language {
+                "rust" => if modifiers.iter().any(|m| m.kind() == "visibility_modifier") { "public" } else { "private" },
+                "go" => if name.starts_with(|c: char| c.is_uppercase()) { "public" } else { "private" },
+                "java" => {
+                    let words = modifiers.iter().find(|m| m.kind() == "modifiers").map(|m| self.text(*m)).unwrap_or("");
+                    ["public", "private", "protected"].into_iter()
+                        .find(|v| words.split_whitespace().any(|w| w == *v))
+                        .unwrap_or("package-private")
+                }
+                "python" => if name.starts_with('_') && !name.ends_with("__") { "private" } else { "public" },
+                _ => {
+                    if let Some(modifier) = modifiers.iter().find(|m| m.kind() == "accessibility_modifier") {
+                        return Some(self.text(*modifier).to_string());
+                    }
+                    let exported = node.parent().is_some_and(|p| p.kind() == "export_statement");
+                    if name.starts_with('#') {
+                        "private"
+                    } else if exported || in_type {
+                        "public"
+                    } else {
+                        return None;
+                    }
+                }
+            };
+            Some(visibility.to_string())
+        }
+
+        fn parameter_names(&self, parameters: Node) -> Vec<String> {
+            let mut cursor = parameters.walk();
+            let children: Vec<Node> = parameters.named_children(&mut cursor).collect();
+            let mut names = Vec::new();
+            for parameter in children {
+                match parameter.kind() {
+                    "comment" | "line_comment" | "block_comment" | "attribute_item" | "decorator" => {}
+                    "identifier" | "self_parameter" | "list_splat_pattern" | "dictionary_splat_pattern" | "rest_pattern" => {
+                        names.push(self.text(parameter).to_string())
+                    }
+                    "typed_parameter" => names.extend(parameter.named_child(0).map(|n| self.text(n).to_string())),
+                    _ => {
+                        let mut cursor = parameter.walk();
+                        let declared: Vec<String> = parameter.children_by_field_name("name", &mut cursor)
+                            .map(|n| self.text(n).to_string())
+                            .collect();
+                        if let Some(pattern) = self.field_text(parameter, "pattern").or_else(|| self.field_text(parameter, "left")) {
+                            names.push(pattern.to_string());
+                        } else if !declared.is_empty() {
+                            names.extend(declared);
+                        } else {
+                            names.push(self.text(parameter).to_string());
+                        }
+                    }
+                }
+            }
+            names
+        }
+
+        /// Module names as the line heuristics record them: the import source, Python's dotted
+        /// module, the crate a Rust `use` starts from, the Go package path and the Java import
+        fn imports(&self, node: Node) -> Vec<String> {
+            let unquote = |s: &str| s.trim_matches(['"', '\'', '`']).to_string();
+            match node.kind() {
+                "import_statement" if self.language == "python" => {
+                    let mut cursor = node.walk();
+                    node.children_by_field_name("name", &mut cursor)
+                        .map(|n| if n.kind() == "aliased_import" { self.field_text(n, "name").unwrap_or("") } else { self.text(n) })
+                        .map(str::to_string)
+                        .collect()
+                }
+                "import_statement" => self.field_text(node, "source").map(unquote).into_iter().collect(),
+                "import_from_statement" => self.field_text(node, "module_name").map(str::to_string).into_iter().collect(),
+                "use_declaration" => self.field_text(node, "argument")
+                    .map(|a| a.trim_start_matches("::").split(['{', ':']).next().unwrap_or("").trim().to_string())
+                    .filter(|a| !a.is_empty())
+                    .into_iter()
+                    .collect(),
+                "import_spec" => self.field_text(node, "path").map(unquote).into_iter().collect(),
+                _ => {
+                    let text = self.text(node).trim_start_matches("import").trim();
+                    vec![text.trim_start_matches("static ").trim_end_matches(';').trim().to_string()]
+                }
+            }
+        }
+
+        /// Records the call `node` makes from the enclosing function; `require("x")` is an import
+        fn call(&mut self, node: Node, scope: &Scope) {
+            let field = |node: Node, name: &str| self.field_text(node, name);
+            let callee = match node.kind() {
+                "method_invocation" => call_reference(field(node, "object"), field(node, "name").unwrap_or("")),
+                _ => {
+                    let Some(function) = node.child_by_field_name("function") else { return };
+                    match function.kind() {
+                        "identifier" if self.text(function) == "require" => {
+                            let module = node.child_by_field_name("arguments")
+                                .and_then(|a| a.named_child(0))
+                                .filter(|a| a.kind() == "string")
+                                .map(|a| self.text(a).trim_matches(['"', '\'', '`']).to_string());
+                            if let Some(module) = module {
+                                let line = node.start_position().row + 1;
+                                self.elements.push(self.element(module, CodeElementType::Module, line));
+                            }
+                            return;
+                        }
+                        "identifier" => call_reference(None, self.text(function)),
+                        "member_expression" => call_reference(field(function, "object"), field(function, "property").unwrap_or("")),
+                        "attribute" => call_reference(field(function, "object"), field(function, "attribute").unwrap_or("")),
+                        "selector_expression" => call_reference(field(function, "operand"), field(function, "field").unwrap_or("")),
+                        "field_expression" => call_reference(field(function, "value"), field(function, "field").unwrap_or("")),
+                        "scoped_identifier" => {
+                            let receiver = match (field(function, "path"), &scope.impl_type) {
+                                (Some("Self"), Some(impl_type)) => Some(impl_type.as_str()),
+                                (path, _) => path,
+                            };
+                            call_reference(receiver, field(function, "name").unwrap_or(""))
+                        }
+                        _ => return,
+                    }
+                }
+            };
+            let Some((reference, call_type)) = callee else { return };
+            let Some(caller_id) = scope.function.clone() else { return };
+            self.calls.push(CallReference {
+                caller_id,
+                reference,
+                call_type: call_type.to_string(),
+                line_number: node.start_position().row + 1,
+            });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tree-sitter"))]
+mod tests {
+    use super::*;
+    use crate::analysis::CodeElementType;
+
+    #[test]
+    fn test_nested_declarations_and_calls() {
+        let python = r#"import os
+from orders.models import Order
+
+class OrderService:
+    """Creates and ships orders"""
+
+    class Config:
+        def load(self):
+            return os.environ
+
+    def create(
+        self,
+        customer_id: int,
+        items=None,
+    ) -> Order:
+        self.validate(items)
+        return Order(customer_id)
+
+    def validate(self, items):
+        pass
+"#;
+        let parsed = parse("python", "orders/service.py", python).unwrap();
+        let by_name = |name: &str| parsed.elements.iter().find(|e| e.name == name).unwrap();
+        let service = by_name("OrderService");
+        assert_eq!(service.doc_comment.as_deref(), Some("Creates and ships orders"));
+        assert_eq!(by_name("Config").parent_id.as_ref(), Some(&service.id));
+        assert_eq!(by_name("load").parent_id.as_ref(), Some(&by_name("Config").id));
+        let create = by_name("create");
+        assert_eq!(create.element_type, CodeElementType::Method);
+        assert_eq!(create.parameters, vec!["self", "customer_id", "items"]);
+        assert_eq!(create.return_type.as_deref(), Some("Order"));
+        assert_eq!(create.signature.as_deref(), Some("def create( self, customer_id: int, items=None, ) -> Order:"));
+        assert_eq!(by_name("orders.models").element_type, CodeElementType::Module);
+
+        let rust = "pub struct Cart { items: Vec<u32> }\n\nimpl Cart {\n    pub fn new() -> Self {\n        Self::empty()\n    }\n\n    fn empty() -> Self {\n        Cart { items: Vec::new() }\n    }\n}\n";
+        let parsed_rust = parse("rust", "src/cart.rs", rust).unwrap();
+        let cart = parsed_rust.elements.iter().find(|e| e.name == "Cart").unwrap();
+        let new = parsed_rust.elements.iter().find(|e| e.name == "new").unwrap();
+        assert_eq!(new.parent_id.as_ref(), Some(&cart.id));
+        assert_eq!(new.visibility.as_deref(), Some("public"));
+        assert_eq!(parsed_rust.calls[0].reference, "Cart.empty");
+
+        let mut elements = parsed.elements;
+        elements.extend(parsed_rust.elements);
+        crate::analysis::symbols::assign_qualified_names(&mut elements);
+        let mut references = parsed.calls;
+        references.extend(parsed_rust.calls);
+        let calls = crate::analysis::call_graph::resolve_calls(&elements, references);
+        let name_of = |id: &str| elements.iter().find(|e| e.id == id).unwrap().name.as_str();
+        let resolved: Vec<(&str, &str)> = calls.iter().map(|c| (name_of(&c.caller_id), name_of(&c.callee_id))).collect();
+        assert_eq!(resolved, vec![("create", "validate"), ("new", "empty")]);
+    }
+}
@@ -0,0 +1,384 @@
+//! Links between the repositories of a system. Every repository is analyzed on its own, so
+//! nothing in one repository's graph points at another; this joins them by what they have in
+//! common: one repository depending on the package another publishes, the third-party packages
+//! they share, HTTP calls from one to the endpoints another serves, and cloud resources declared
+//! in both.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use walkdir::WalkDir;
+use crate::analysis::utils;
+use crate::security::types::{SecurityEntity, SecurityEntityType};
+use crate::storage::{Repository, StoredDependency, StoredEndpoint};
+
+static VERSION_SEGMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^v\d+$").unwrap());
+static STRING_LITERAL: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"\n]*)"|'([^'\n]*)'|`([^`\n]*)`"#).unwrap());
+
+/// Path segments too common to tell one service's endpoint from another's
+const COMMON_SEGMENTS: &[&str] = &[
+    "api", "rest", "graphql", "public", "internal", "health", "healthz", "ready", "readyz", "live", "livez",
+    "status", "metrics", "ping", "version", "login", "logout", "index",
+];
+
+/// Resource names so generic that two repositories declaring them says nothing
+const GENERIC_RESOURCE_NAMES: &[&str] = &["main", "this", "default", "primary", "example", "test"];
+
+/// A repository of the system with what was stored by its last analysis
+pub struct SystemMember {
+    pub repository: Repository,
+    pub dependencies: Vec<StoredDependency>,
+    pub endpoints: Vec<StoredEndpoint>,
+    pub resources: Vec<SecurityEntity>,
+    /// A local checkout, searched for calls to the other members' endpoints
+    pub checkout: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossRepoRelation {
+    DependsOnRepository,
+    SharesDependency,
+    CallsEndpoint,
+    SharesResource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossRepoLink {
+    pub source_repository_id: String,
+    pub target_repository_id: String,
+    pub relation: CrossRepoRelation,
+    /// The package, endpoint (`GET /orders/:id`) or resource the repositories are linked through
+    pub target: String,
+    pub evidence: String,
+}
+
+/// Finds the links between every ordered pair of a system's repositories; links that are
+/// symmetric (shared dependencies and resources) are reported once per pair
+#[derive(Default)]
+pub struct SystemLinker;
+
+impl SystemLinker {
+    pub fn new() -> Self {
+        SystemLinker
+    }
+
+    pub fn link(&self, members: &[SystemMember]) -> Vec<CrossRepoLink> {
+        let mut links = Vec::new();
+        for (i, source) in members.iter().enumerate() {
+            for (j, target) in members.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                links.extend(repository_dependencies(source, target));
+                links.extend(endpoint_calls(source, target));
+                if i < j {
+                    links.extend(shared_dependencies(source, target));
+                    links.extend(shared_resources(source, target));
+                }
+            }
+        }
+        links
+    }
+}
+
+fn link(source: &SystemMember, target: &SystemMember, relation: CrossRepoRelation, through: String, evidence: String) -> CrossRepoLink {
+    CrossRepoLink {
+        source_repository_id: source.repository.id.clone(),
+        target_repository_id: target.repository.id.clone(),
+        relation,
+        target: through,
+        evidence,
+    }
+}
+
+/// The repository's location without scheme and `.git` (`github.com/acme/orders`)
+fn url_path(url: &str) -> String {
+    let url = url.split("://").last().unwrap_or(url);
+    let url = url.split('@').next_back().unwrap_or(url).replace(':', "/");
+    url.trim_end_matches('/').trim_end_matches(".git").to_lowercase()
+}
+
+/// Whether `dependency` is the package built from `repository`: a package of the same name, with
+/// or without a scope (`@acme/orders`), or a Go module named after the repository's URL
+fn publishes(repository: &Repository, dependency: &StoredDependency) -> bool {
+    let name = dependency.name.to_lowercase();
+    let repo_name = repository.name.to_lowercase();
+    if name == repo_name || (name.starts_with('@') && name.rsplit('/').next() == Some(repo_name.as_str())) {
+        return true;
+    }
+    let location = url_path(&repository.url);
+    location.contains('/') && (name == location || name.starts_with(&format!("{}/", location)))
+}
+
+fn repository_dependencies(source: &SystemMember, target: &SystemMember) -> Option<CrossRepoLink> {
+    let dependency = source.dependencies.iter().find(|d| publishes(&target.repository, d))?;
+    Some(link(
+        source,
+        target,
+        CrossRepoRelation::DependsOnRepository,
+        dependency.name.clone(),
+        format!("{} {} {} in {}", dependency.package_manager, dependency.name, dependency.version, dependency.file_path),
+    ))
+}
+
+/// One link per pair listing every package both depend on, with the versions that differ
+fn shared_dependencies(a: &SystemMember, b: &SystemMember) -> Option<CrossRepoLink> {
+    let internal = |d: &StoredDependency| publishes(&a.repository, d) || publishes(&b.repository, d);
+    let versions = |member: &SystemMember| -> BTreeMap<(String, String), String> {
+        member.dependencies.iter()
+            .filter(|d| !internal(d))
+            .map(|d| ((d.package_manager.clone(), d.name.clone()), d.version.clone()))
+            .collect()
+    };
+    let (a_versions, b_versions) = (versions(a), versions(b));
+    let shared: Vec<(&(String, String), &String, &String)> = a_versions.iter()
+        .filter_map(|(key, a_version)| b_versions.get(key).map(|b_version| (key, a_version, b_version)))
+        .collect();
+    if shared.is_empty() {
+        return None;
+    }
+    let names: Vec<&str> = shared.iter().map(|((_, name), _, _)| name.as_str()).collect();
+    let differing: Vec<String> = shared.iter()
+        .filter(|(_, a_version, b_version)| a_version != b_version)
+        .map(|((_, name), a_version, b_version)| format!("{} ({} / {})", name, a_version, b_version))
+        .collect();
+    let mut evidence = format!("{} shared dependencies", shared.len());
+    if !differing.is_empty() {
+        evidence.push_str(&format!("; versions differ: {}", differing.join(", ")));
+    }
+    Some(link(a, b, CrossRepoRelation::SharesDependency, names.join(", "), evidence))
+}
+
+fn is_parameter(segment: &str) -> bool {
+    segment.starts_with([':', '{', '<', '[', '*']) || segment.starts_with("${")
+}
+
+/// A pattern matching string literals that address `path`: the path alone, after a host or an
+/// interpolated base URL, with any value (or `${...}`) for its parameters; trailing parameters may
+/// be left off, as in `"/orders/" + id`. Paths without a segment specific to the service match
+/// too much to count as a call.
+fn call_pattern(path: &str) -> Option<Regex> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+    let distinctive = segments.iter().any(|s| {
+        !is_parameter(s) && s.len() >= 3 && !VERSION_SEGMENT.is_match(s) && !COMMON_SEGMENTS.contains(&s.to_lowercase().as_str())
+    });
+    if !distinctive {
+        return None;
+    }
+    let last_static = segments.iter().rposition(|s| !is_parameter(s))?;
+    let mut pattern = String::from(r#"(?:^|\}|//[\w.\-]+(?::\d+)?)"#);
+    for (i, segment) in segments.iter().enumerate() {
+        let value = if is_parameter(segment) { r"(?:\$\{[^}]*\}|[^/?#\s]+)".to_string() } else { regex::escape(segment) };
+        if i > last_static {
+            pattern.push_str(&format!("(?:/(?:{})?)?", value));
+        } else {
+            pattern.push('/');
+            pattern.push_str(&value);
+        }
+    }
+    pattern.push_str(r"/?(?:$|[?#])");
+    Regex::new(&pattern).ok()
+}
+
+fn endpoint_label(endpoint: &StoredEndpoint) -> String {
+    format!("{} {}", endpoint.method.to_uppercase(), endpoint.path)
+}
+
+/// Calls from `source`'s code to `target`'s HTTP endpoints, found as string literals naming the
+/// endpoint's path. Paths `source` serves itself are skipped, since those literals are as likely
+/// its own routes.
+fn endpoint_calls(source: &SystemMember, target: &SystemMember) -> Vec<CrossRepoLink> {
+    let Some(checkout) = &source.checkout else { return Vec::new() };
+    let served: HashSet<&str> = source.endpoints.iter().map(|e| e.path.as_str()).collect();
+    let mut seen = HashSet::new();
+    let patterns: Vec<(&StoredEndpoint, Regex)> = target.endpoints.iter()
+        .filter(|e| e.protocol == "http" && !served.contains(e.path.as_str()))
+        .filter(|e| seen.insert(endpoint_label(e)))
+        .filter_map(|e| call_pattern(&e.path).map(|pattern| (e, pattern)))
+        .collect();
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+
+    let mut found: BTreeMap<String, String> = BTreeMap::new();
+    for entry in WalkDir::new(checkout).into_iter().filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        let path = entry.path();
+        let relative = path.strip_prefix(checkout).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if utils::should_skip_file(file_name, &relative) || utils::detect_language(path).is_none() {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        if utils::is_minified_or_compiled(&content, &relative) {
+            continue;
+        }
+        for (idx, line) in content.lines().enumerate() {
+            for caps in STRING_LITERAL.captures_iter(line) {
+                let text = caps.get(1).or(caps.get(2)).or(caps.get(3)).map(|m| m.as_str()).unwrap_or("");
+                for (endpoint, pattern) in &patterns {
+                    if pattern.is_match(text) {
+                        found.entry(endpoint_label(endpoint)).or_insert_with(|| format!("{}:{}", relative, idx + 1));
+                    }
+                }
+            }
+        }
+    }
+    found.into_iter()
+        .map(|(endpoint, location)| link(source, target, CrossRepoRelation::CallsEndpoint, endpoint, location))
+        .collect()
+}
+
+fn is_cloud_resource(entity_type: &SecurityEntityType) -> bool {
+    matches!(
+        entity_type,
+        SecurityEntityType::S3Bucket | SecurityEntityType::RdsInstance | SecurityEntityType::LambdaFunction
+            | SecurityEntityType::ApiGateway | SecurityEntityType::Vpc | SecurityEntityType::Subnet
+            | SecurityEntityType::Ec2Instance | SecurityEntityType::SecurityGroup | SecurityEntityType::IamRole
+    )
+}
+
+/// The identity two declarations of a resource share: its ARN, or else its type and name
+fn resource_key(resource: &SecurityEntity) -> Option<String> {
+    if !is_cloud_resource(&resource.entity_type) {
+        return None;
+    }
+    if let Some(arn) = resource.arn.as_ref().filter(|a| !a.is_empty()) {
+        return Some(arn.clone());
+    }
+    let name = resource.name.to_lowercase();
+    if name.is_empty() || GENERIC_RESOURCE_NAMES.contains(&name.as_str()) {
+        return None;
+    }
+    Some(format!("{:?} {}", resource.entity_type, resource.name))
+}
+
+fn shared_resources(a: &SystemMember, b: &SystemMember) -> Vec<CrossRepoLink> {
+    let b_resources: BTreeMap<String, &SecurityEntity> = b.resources.iter()
+        .filter_map(|r| resource_key(r).map(|key| (key, r)))
+        .collect();
+    let mut seen = HashSet::new();
+    a.resources.iter()
+        .filter_map(|r| {
+            let key = resource_key(r)?;
+            let other = b_resources.get(&key)?;
+            seen.insert(key.clone()).then(|| {
+                link(a, b, CrossRepoRelation::SharesResource, key, format!("{} / {}", r.file_path, other.file_path))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn repository(id: &str, url: &str) -> Repository {
+        Repository {
+            id: id.to_string(),
+            name: id.to_string(),
+            url: url.to_string(),
+            branch: "main".to_string(),
+            auth_type: None,
+            auth_value: None,
+            last_analyzed_at: None,
+            analysis_profile: None,
+            workspace_id: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn dependency(name: &str, version: &str, package_manager: &str) -> StoredDependency {
+        StoredDependency {
+            id: name.to_string(),
+            repository_id: String::new(),
+            name: name.to_string(),
+            version: version.to_string(),
+            package_manager: package_manager.to_string(),
+            is_dev: false,
+            is_optional: false,
+            file_path: "package.json".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn endpoint(method: &str, path: &str) -> StoredEndpoint {
+        StoredEndpoint {
+            id: path.to_string(),
+            repository_id: "orders".to_string(),
+            path: path.to_string(),
+            method: method.to_string(),
+            handler: None,
+            file_path: "src/routes.js".to_string(),
+            line_number: Some(1),
+            framework: Some("express".to_string()),
+            middleware: Vec::new(),
+            parameters: Vec::new(),
+            created_at: String::new(),
+            protocol: "http".to_string(),
+        }
+    }
+
+    fn bucket(name: &str, file_path: &str) -> SecurityEntity {
+        SecurityEntity {
+            id: format!("{}:{}", file_path, name),
+            entity_type: SecurityEntityType::S3Bucket,
+            name: name.to_string(),
+            provider: "aws".to_string(),
+            configuration: HashMap::new(),
+            file_path: file_path.to_string(),
+            line_number: Some(1),
+            arn: None,
+            region: None,
+        }
+    }
+
+    #[test]
+    fn test_links_repositories_of_a_system() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/client.js"), "\
+const base = process.env.ORDERS_URL;
+export const getOrder = (id) => fetch(`${base}/api/orders/${id}`);
+export const health = () => fetch('/health');
+").unwrap();
+
+        let orders = SystemMember {
+            repository: repository("orders", "https://github.com/acme/orders.git"),
+            dependencies: vec![dependency("@acme/models", "1.0.0", "npm"), dependency("express", "4.18.2", "npm")],
+            endpoints: vec![endpoint("GET", "/api/orders/:id"), endpoint("GET", "/health"), endpoint("POST", "/api/refunds")],
+            resources: vec![bucket("acme-invoices", "infra/s3.tf"), bucket("main", "infra/s3.tf")],
+            checkout: None,
+        };
+        let web = SystemMember {
+            repository: repository("web", "https://github.com/acme/web.git"),
+            dependencies: vec![dependency("@acme/orders", "2.1.0", "npm"), dependency("express", "4.19.0", "npm")],
+            endpoints: vec![endpoint("GET", "/health")],
+            resources: vec![bucket("acme-invoices", "terraform/main.tf"), bucket("main", "terraform/main.tf")],
+            checkout: Some(dir.path().to_path_buf()),
+        };
+
+        let links = SystemLinker::new().link(&[orders, web]);
+        let mut found: Vec<(&str, &str, CrossRepoRelation, &str, &str)> = links.iter()
+            .map(|l| (
+                l.source_repository_id.as_str(),
+                l.target_repository_id.as_str(),
+                l.relation,
+                l.target.as_str(),
+                l.evidence.as_str(),
+            ))
+            .collect();
+        found.sort_by_key(|l| format!("{:?}", l));
+        assert_eq!(found, vec![
+            ("orders", "web", CrossRepoRelation::SharesDependency, "express", "1 shared dependencies; versions differ: express (4.18.2 / 4.19.0)"),
+            ("orders", "web", CrossRepoRelation::SharesResource, "S3Bucket acme-invoices", "infra/s3.tf / terraform/main.tf"),
+            ("web", "orders", CrossRepoRelation::CallsEndpoint, "GET /api/orders/:id", "src/client.js:2"),
+            ("web", "orders", CrossRepoRelation::DependsOnRepository, "@acme/orders", "npm @acme/orders 2.1.0 in package.json"),
+        ]);
+    }
+}
@@ -0,0 +1,365 @@
+//! Conformance to a declared target ("golden") architecture. The spec names the components the
+//! code should be split into and which of them may depend on which, the external services the
+//! repository may use and the controls it must have; each analysis compares what was detected
+//! against it and reports the deltas.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use crate::analysis::architecture_rules::{ArchitectureRule, ArchitectureRuleEngine, ArchitectureRuleSet, ModulePatterns};
+use crate::analysis::utils::glob_to_regex;
+use crate::analysis::FindingSeverity;
+use crate::security::types::SecurityVulnerability;
+use crate::storage::observability_repo::StoredObservabilityCoverage;
+use crate::storage::StoredService;
+
+/// Controls a spec may require, with the vulnerability types that show one is missing
+const SECURITY_CONTROLS: &[(&str, &[&str])] = &[
+    ("authentication", &["UnauthenticatedEndpoint", "MissingAuthenticationCheck"]),
+    ("encryption", &["UnencryptedS3Bucket", "CleartextTrafficAllowed"]),
+    ("least_privilege", &["WildcardAction", "WildcardResource", "OverlyPermissiveAssumeRolePolicy", "OverlyPermissiveFirebaseRules"]),
+    ("network_isolation", &["OpenSecurityGroup", "PublicS3Bucket"]),
+    ("secrets_management", &["HardcodedApiKey"]),
+];
+
+/// Controls checked against the observability coverage of each service, with the gap that breaks them
+const OBSERVABILITY_CONTROLS: &[(&str, &str)] = &[
+    ("structured_logging", "NoStructuredLogging"),
+    ("metrics", "NoMetrics"),
+    ("tracing", "NoTracing"),
+];
+
+/// Controls checked by the presence of something: tests, CI pipelines
+const PRESENCE_CONTROLS: &[&str] = &["tests", "ci"];
+
+/// The architecture a repository is meant to have, e.g.
+///
+/// ```yaml
+/// name: checkout
+/// components:
+///   - name: api
+///     paths: "src/api/**"
+///     depends_on: [domain]
+///   - name: domain
+///     paths: ["src/domain/**", "src/model/**"]
+/// allowed_externals: [postgresql, stripe, "aws*"]
+/// required_controls: [authentication, tracing, tests]
+/// ```
+///
+/// A component may depend on itself and the components it lists; leaving `allowed_externals` out
+/// allows any external service.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TargetArchitecture {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub components: Vec<TargetComponent>,
+    #[serde(default)]
+    pub allowed_externals: Option<Vec<String>>,
+    #[serde(default)]
+    pub required_controls: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetComponent {
+    pub name: String,
+    pub paths: ModulePatterns,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaKind {
+    MissingComponent,
+    UndeclaredDependency,
+    UnexpectedExternal,
+    MissingControl,
+}
+
+impl DeltaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeltaKind::MissingComponent => "missing_component",
+            DeltaKind::UndeclaredDependency => "undeclared_dependency",
+            DeltaKind::UnexpectedExternal => "unexpected_external",
+            DeltaKind::MissingControl => "missing_control",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<DeltaKind> {
+        match value {
+            "missing_component" => Some(DeltaKind::MissingComponent),
+            "undeclared_dependency" => Some(DeltaKind::UndeclaredDependency),
+            "unexpected_external" => Some(DeltaKind::UnexpectedExternal),
+            "missing_control" => Some(DeltaKind::MissingControl),
+            _ => None,
+        }
+    }
+}
+
+/// One way the repository differs from its target architecture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceDelta {
+    pub kind: DeltaKind,
+    /// The component, external service or control the delta is about
+    pub subject: String,
+    pub expected: String,
+    pub actual: String,
+    pub severity: FindingSeverity,
+    pub file_path: Option<String>,
+    pub line_number: Option<usize>,
+}
+
+/// What an analysis detected, as far as the spec can check it
+pub struct ConformanceInput<'a> {
+    pub repo_path: &'a Path,
+    pub source_files: &'a [&'a str],
+    pub services: &'a [StoredService],
+    pub vulnerabilities: &'a [SecurityVulnerability],
+    pub observability: &'a [StoredObservabilityCoverage],
+    pub test_count: usize,
+    pub pipeline_count: usize,
+}
+
+impl TargetArchitecture {
+    /// Parse a spec written in YAML or JSON
+    pub fn parse(content: &str) -> Result<TargetArchitecture> {
+        let spec: TargetArchitecture = serde_yaml::from_str(content).context("Invalid target architecture")?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let names: BTreeSet<&str> = self.components.iter().map(|c| c.name.as_str()).collect();
+        if names.len() != self.components.len() {
+            anyhow::bail!("Component names must be unique");
+        }
+        for component in &self.components {
+            if component.paths.globs().is_empty() {
+                anyhow::bail!("Component '{}' needs at least one path", component.name);
+            }
+            if let Some(unknown) = component.depends_on.iter().find(|d| !names.contains(d.as_str())) {
+                anyhow::bail!("Component '{}' depends on unknown component '{}'", component.name, unknown);
+            }
+        }
+        let known = |control: &str| {
+            SECURITY_CONTROLS.iter().any(|(name, _)| *name == control)
+                || OBSERVABILITY_CONTROLS.iter().any(|(name, _)| *name == control)
+                || PRESENCE_CONTROLS.contains(&control)
+        };
+        if let Some(unknown) = self.required_controls.iter().find(|c| !known(c)) {
+            anyhow::bail!("Unknown control '{}'", unknown);
+        }
+        Ok(())
+    }
+
+    pub fn check(&self, input: &ConformanceInput) -> Vec<ConformanceDelta> {
+        let mut deltas = Vec::new();
+        self.check_components(input, &mut deltas);
+        self.check_externals(input, &mut deltas);
+        self.check_controls(input, &mut deltas);
+        deltas
+    }
+
+    fn check_components(&self, input: &ConformanceInput, deltas: &mut Vec<ConformanceDelta>) {
+        for component in &self.components {
+            let patterns: Vec<Regex> = component.paths.globs().iter().map(|g| glob_to_regex(g)).collect();
+            if !input.source_files.iter().any(|f| patterns.iter().any(|p| p.is_match(f))) {
+                deltas.push(ConformanceDelta {
+                    kind: DeltaKind::MissingComponent,
+                    subject: component.name.clone(),
+                    expected: format!("source files in {}", component.paths.globs().join(", ")),
+                    actual: "no source files".to_string(),
+                    severity: FindingSeverity::Medium,
+                    file_path: None,
+                    line_number: None,
+                });
+            }
+        }
+
+        // Dependencies between components are checked as rules forbidding the undeclared ones
+        let rules: Vec<ArchitectureRule> = self.components.iter()
+            .filter_map(|component| {
+                let forbid: Vec<String> = self.components.iter()
+                    .filter(|other| other.name != component.name && !component.depends_on.contains(&other.name))
+                    .map(|other| other.name.clone())
+                    .collect();
+                (!forbid.is_empty()).then(|| ArchitectureRule {
+                    name: component.name.clone(),
+                    from: component.name.clone(),
+                    allow: Vec::new(),
+                    forbid,
+                    severity: None,
+                    description: None,
+                })
+            })
+            .collect();
+        let rule_set = ArchitectureRuleSet {
+            modules: self.components.iter().map(|c| (c.name.clone(), c.paths.clone())).collect::<BTreeMap<_, _>>(),
+            rules,
+            hexagonal: None,
+        };
+        for finding in ArchitectureRuleEngine::new(rule_set).evaluate(input.repo_path, input.source_files, &[]) {
+            let target = finding.target_layer.unwrap_or_default();
+            let allowed = self.components.iter()
+                .find(|c| c.name == finding.rule)
+                .map(|c| c.depends_on.join(", "))
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| "nothing".to_string());
+            deltas.push(ConformanceDelta {
+                kind: DeltaKind::UndeclaredDependency,
+                subject: finding.rule.clone(),
+                expected: format!("{} depends on {}", finding.rule, allowed),
+                actual: format!("{} imports {} ({})", finding.source_file, finding.target_file, target),
+                severity: FindingSeverity::High,
+                file_path: Some(finding.source_file),
+                line_number: Some(finding.line_number),
+            });
+        }
+    }
+
+    fn check_externals(&self, input: &ConformanceInput, deltas: &mut Vec<ConformanceDelta>) {
+        let Some(allowed) = &self.allowed_externals else { return };
+        let allows = |value: &str| allowed.iter().any(|pattern| {
+            let (pattern, value) = (pattern.to_lowercase(), value.to_lowercase());
+            match pattern.strip_suffix('*') {
+                Some(prefix) => value.starts_with(prefix),
+                None => value == pattern,
+            }
+        });
+        let mut seen = BTreeSet::new();
+        for service in input.services {
+            if allows(&service.name) || allows(&service.provider) || !seen.insert(service.name.to_lowercase()) {
+                continue;
+            }
+            deltas.push(ConformanceDelta {
+                kind: DeltaKind::UnexpectedExternal,
+                subject: service.name.clone(),
+                expected: if allowed.is_empty() { "no external services".to_string() } else { allowed.join(", ") },
+                actual: format!("{} {} ({})", service.provider, service.name, service.service_type),
+                severity: FindingSeverity::Medium,
+                file_path: Some(service.file_path.clone()),
+                line_number: service.line_number,
+            });
+        }
+    }
+
+    fn check_controls(&self, input: &ConformanceInput, deltas: &mut Vec<ConformanceDelta>) {
+        for control in &self.required_controls {
+            let missing = |actual: String, file_path: Option<String>, line_number: Option<usize>| ConformanceDelta {
+                kind: DeltaKind::MissingControl,
+                subject: control.clone(),
+                expected: format!("{} in place", control.replace('_', " ")),
+                actual,
+                severity: FindingSeverity::High,
+                file_path,
+                line_number,
+            };
+            if let Some((_, types)) = SECURITY_CONTROLS.iter().find(|(name, _)| name == control) {
+                for vulnerability in input.vulnerabilities.iter().filter(|v| types.contains(&v.vulnerability_type.as_str())) {
+                    deltas.push(missing(
+                        format!("{}: {}", vulnerability.vulnerability_type, vulnerability.description),
+                        Some(vulnerability.file_path.clone()),
+                        vulnerability.line_number,
+                    ));
+                }
+            } else if let Some((_, gap)) = OBSERVABILITY_CONTROLS.iter().find(|(name, _)| name == control) {
+                let services: Vec<&str> = input.observability.iter()
+                    .filter(|c| c.gaps.iter().any(|g| g == gap))
+                    .map(|c| c.service.as_str())
+                    .collect();
+                if !services.is_empty() {
+                    deltas.push(missing(format!("missing in {}", services.join(", ")), None, None));
+                }
+            } else if control == "tests" && input.test_count == 0 {
+                deltas.push(missing("no tests".to_string(), None, None));
+            } else if control == "ci" && input.pipeline_count == 0 {
+                deltas.push(missing("no CI pipelines".to_string(), None, None));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::types::VulnerabilitySeverity;
+
+    fn service(name: &str, provider: &str) -> StoredService {
+        StoredService {
+            id: name.to_string(),
+            repository_id: "repo".to_string(),
+            provider: provider.to_string(),
+            service_type: "Database".to_string(),
+            name: name.to_string(),
+            configuration: String::new(),
+            file_path: "src/api/db.ts".to_string(),
+            line_number: Some(3),
+            confidence: 0.9,
+            evidence: Vec::new(),
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_reports_deltas_from_target_architecture() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/api")).unwrap();
+        std::fs::create_dir_all(dir.path().join("src/domain")).unwrap();
+        std::fs::write(dir.path().join("src/api/orders.ts"), "import { Order } from \"../domain/order\";\n").unwrap();
+        std::fs::write(dir.path().join("src/domain/order.ts"), "import { render } from \"../api/orders\";\n").unwrap();
+
+        let spec = TargetArchitecture::parse(r#"
+name: checkout
+components:
+  - name: api
+    paths: "src/api/**"
+    depends_on: [domain]
+  - name: domain
+    paths: "src/domain/**"
+  - name: billing
+    paths: ["src/billing/**"]
+allowed_externals: [postgresql, "aws*"]
+required_controls: [authentication, tests]
+"#).unwrap();
+        let files = ["src/api/orders.ts", "src/domain/order.ts"];
+        let services = [service("PostgreSQL", "postgresql"), service("S3", "aws"), service("MongoDB", "mongodb")];
+        let vulnerabilities = [SecurityVulnerability {
+            id: "v1".to_string(),
+            entity_id: "e1".to_string(),
+            vulnerability_type: "UnauthenticatedEndpoint".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "POST /orders has no authentication".to_string(),
+            recommendation: String::new(),
+            file_path: "src/api/orders.ts".to_string(),
+            line_number: Some(1),
+            affected_range: None,
+            fixed_version: None,
+        }];
+        let deltas = spec.check(&ConformanceInput {
+            repo_path: dir.path(),
+            source_files: &files,
+            services: &services,
+            vulnerabilities: &vulnerabilities,
+            observability: &[],
+            test_count: 4,
+            pipeline_count: 0,
+        });
+
+        let summary: Vec<(DeltaKind, &str)> = deltas.iter().map(|d| (d.kind, d.subject.as_str())).collect();
+        assert_eq!(summary, vec![
+            (DeltaKind::MissingComponent, "billing"),
+            (DeltaKind::UndeclaredDependency, "domain"),
+            (DeltaKind::UnexpectedExternal, "MongoDB"),
+            (DeltaKind::MissingControl, "authentication"),
+        ]);
+        assert_eq!(deltas[1].actual, "src/domain/order.ts imports src/api/orders.ts (api)");
+        assert_eq!(deltas[1].line_number, Some(1));
+
+        assert!(TargetArchitecture::parse("components:\n  - name: api\n    paths: src/api\n    depends_on: [web]\n").is_err());
+        assert!(TargetArchitecture::parse("required_controls: [firewall]\n").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use crate::analysis::git_blame::GitBlame;
+use crate::analysis::todo_scanner::find_markers;
+use crate::analysis::{CodeElement, CodeElementType};
+use crate::analysis::dependencies::{DependencyManifest, PackageDependency};
+use crate::analysis::update_bots::UpdateBotDetector;
+
+/// Functions with more decision points than this count as complex
+const COMPLEXITY_THRESHOLD: usize = 10;
+/// Consecutive normalized lines that must match for a block to count as duplicated
+const DUPLICATION_WINDOW: usize = 6;
+/// Manifests untouched for longer than this are treated as outdated
+const STALE_MANIFEST_DAYS: f64 = 365.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebtSubscore {
+    pub score: f64,  // 0 (no debt) to 100
+    pub weight: f64, // Share of the composite score
+    pub summary: String,
+    pub metrics: BTreeMap<String, f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechDebtScore {
+    pub score: f64,
+    pub grade: String,
+    pub subscores: BTreeMap<String, DebtSubscore>,
+}
+
+/// Aggregates complexity, duplication, dependency staleness, missing tests and TODO density
+/// into a composite 0-100 debt score (higher means more debt)
+pub struct TechDebtCalculator {
+    history_depth: Option<usize>,
+}
+
+impl TechDebtCalculator {
+    pub fn new() -> Self {
+        TechDebtCalculator { history_depth: None }
+    }
+
+    /// Limit the manifest age lookups to the last `history_depth` commits; 0 skips them
+    pub fn with_history_depth(mut self, history_depth: usize) -> Self {
+        self.history_depth = Some(history_depth);
+        self
+    }
+
+    pub fn calculate(
+        &self,
+        repo_path: &Path,
+        elements: &[CodeElement],
+        tested_element_ids: &HashSet<String>,
+        manifests: &[DependencyManifest],
+    ) -> TechDebtScore {
+        let mut files: Vec<&str> = elements.iter().map(|e| e.file_path.as_str()).collect();
+        files.sort();
+        files.dedup();
+        let contents: HashMap<&str, String> = files.iter()
+            .filter_map(|f| std::fs::read_to_string(repo_path.join(f)).ok().map(|c| (*f, c)))
+            .collect();
+
+        let mut subscores = BTreeMap::new();
+        subscores.insert("complexity".to_string(), complexity(elements, &contents));
+        subscores.insert("duplication".to_string(), duplication(&contents));
+        subscores.insert("dependency_staleness".to_string(), dependency_staleness(repo_path, manifests, self.history_depth));
+        subscores.insert("missing_tests".to_string(), missing_tests(elements, tested_element_ids));
+        subscores.insert("todo_density".to_string(), todo_density(&contents));
+
+        let score = subscores.values().map(|s| s.score * s.weight).sum::<f64>();
+        let score = (score * 10.0).round() / 10.0;
+        let grade = match score {
+            s if s < 20.0 => "A",
+            s if s < 35.0 => "B",
+            s if s < 50.0 => "C",
+            s if s < 70.0 => "D",
+            _ => "E",
+        };
+
+        TechDebtScore {
+            score,
+            grade: grade.to_string(),
+            subscores,
+        }
+    }
+}
+
+fn subscore(score: f64, weight: f64, summary: String, metrics: &[(&str, f64)]) -> DebtSubscore {
+    DebtSubscore {
+        score: (score.clamp(0.0, 100.0) * 10.0).round() / 10.0,
+        weight,
+        summary,
+        metrics: metrics.iter().map(|(k, v)| (k.to_string(), (v * 100.0).round() / 100.0)).collect(),
+        notes: Vec::new(),
+    }
+}
+
+/// Decision points per function body, where a body runs until the next element in the same file
+fn complexity(elements: &[CodeElement], contents: &HashMap<&str, String>) -> DebtSubscore {
+    let decision = Regex::new(r"\b(?:if|elif|for|foreach|while|case|catch|except|when)\b|&&|\|\||\?\?").unwrap();
+    let mut by_file: HashMap<&str, Vec<&CodeElement>> = HashMap::new();
+    for element in elements {
+        by_file.entry(element.file_path.as_str()).or_default().push(element);
+    }
+
+    let mut functions = 0usize;
+    let mut complex = 0usize;
+    let mut total = 0usize;
+    for (file, mut file_elements) in by_file {
+        let lines: Vec<&str> = match contents.get(file) {
+            Some(c) => c.lines().collect(),
+            None => continue,
+        };
+        file_elements.sort_by_key(|e| e.line_number);
+        for (idx, element) in file_elements.iter().enumerate() {
+            if !matches!(element.element_type, CodeElementType::Function | CodeElementType::Method) {
+                continue;
+            }
+            let start = element.line_number.saturating_sub(1).min(lines.len());
+            let end = file_elements.get(idx + 1)
+                .map(|next| next.line_number.saturating_sub(1))
+                .unwrap_or(lines.len())
+                .clamp(start, lines.len());
+            let points: usize = lines[start..end].iter().map(|l| decision.find_iter(l).count()).sum();
+            functions += 1;
+            total += points + 1;
+            if points > COMPLEXITY_THRESHOLD {
+                complex += 1;
+            }
+        }
+    }
+
+    let ratio = if functions == 0 { 0.0 } else { complex as f64 / functions as f64 };
+    let average = if functions == 0 { 0.0 } else { total as f64 / functions as f64 };
+    subscore(
+        ratio * 250.0,
+        0.25,
+        format!("{} of {} functions exceed {} decision points", complex, functions, COMPLEXITY_THRESHOLD),
+        &[("functions", functions as f64), ("complex_functions", complex as f64), ("average_complexity", average)],
+    )
+}
+
+/// Share of meaningful lines that belong to a block repeated elsewhere
+fn duplication(contents: &HashMap<&str, String>) -> DebtSubscore {
+    let mut windows: HashMap<u64, Vec<(&str, usize)>> = HashMap::new();
+    let mut normalized: HashMap<&str, Vec<String>> = HashMap::new();
+    for (file, content) in contents {
+        let lines: Vec<String> = content.lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|l| l.len() > 3 && !l.starts_with("import ") && !l.starts_with("use ") && !l.starts_with("from ") && !l.starts_with("//") && !l.starts_with('#'))
+            .collect();
+        for start in 0..lines.len().saturating_sub(DUPLICATION_WINDOW - 1) {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            lines[start..start + DUPLICATION_WINDOW].hash(&mut hasher);
+            windows.entry(hasher.finish()).or_default().push((file, start));
+        }
+        normalized.insert(file, lines);
+    }
+
+    let mut duplicated: HashSet<(&str, usize)> = HashSet::new();
+    for locations in windows.values().filter(|l| l.len() > 1) {
+        for (file, start) in locations {
+            for offset in 0..DUPLICATION_WINDOW {
+                duplicated.insert((file, start + offset));
+            }
+        }
+    }
+    let total: usize = normalized.values().map(|l| l.len()).sum();
+    let ratio = if total == 0 { 0.0 } else { duplicated.len() as f64 / total as f64 };
+    subscore(
+        ratio * 400.0,
+        0.2,
+        format!("{:.1}% of {} code lines are in duplicated blocks", ratio * 100.0, total),
+        &[("code_lines", total as f64), ("duplicated_lines", duplicated.len() as f64), ("duplication_ratio", ratio)],
+    )
+}
+
+/// Offline proxy for staleness: how long ago the manifests were last touched in git,
+/// plus the share of dependencies that are unpinned or pre-1.0. Dependabot/Renovate config
+/// is read so dependencies the bots are told to skip are called out, and having no update
+/// automation at all counts against the score.
+fn dependency_staleness(repo_path: &Path, manifests: &[DependencyManifest], history_depth: Option<usize>) -> DebtSubscore {
+    let dependencies: Vec<(&DependencyManifest, &PackageDependency)> = manifests.iter()
+        .flat_map(|m| m.dependencies.iter().filter(|d| !d.is_dev).map(move |d| (m, d)))
+        .collect();
+    if dependencies.is_empty() {
+        return subscore(0.0, 0.15, "No runtime dependencies".to_string(), &[("dependencies", 0.0)]);
+    }
+
+    let is_loose = |d: &PackageDependency| {
+        let version = d.version.trim().trim_start_matches(['^', '~', '=', 'v']);
+        version.is_empty() || version == "*" || version == "latest" || version.starts_with("0.")
+    };
+    let loose = dependencies.iter().filter(|(_, d)| is_loose(d)).count();
+    let loose_ratio = loose as f64 / dependencies.len() as f64;
+
+    let mut blame = GitBlame::open_with_depth(repo_path, history_depth);
+    let now = chrono::Utc::now().timestamp();
+    let ages: HashMap<&str, f64> = manifests.iter()
+        .take(20)
+        .filter_map(|m| blame.last_modified(&m.file_path).map(|ts| (m.file_path.as_str(), (now - ts).max(0) as f64 / 86_400.0)))
+        .collect();
+    // The most recently touched manifest is the best sign of maintenance
+    let days = ages.values().cloned().fold(None, |min: Option<f64>, d| Some(min.map_or(d, |m| m.min(d))));
+
+    let automation = UpdateBotDetector::new().detect_automation(repo_path);
+    let with_runtime_deps: Vec<&DependencyManifest> = manifests.iter().filter(|m| m.dependencies.iter().any(|d| !d.is_dev)).collect();
+    let uncovered = with_runtime_deps.iter().filter(|m| !automation.covers(m)).count();
+
+    let mut notes = Vec::new();
+    let mut ignored = 0usize;
+    let mut outdated_but_ignored = 0usize;
+    for (manifest, dependency) in &dependencies {
+        let Some((config, rule)) = automation.ignored_by(&dependency.name, &dependency.package_manager) else { continue };
+        ignored += 1;
+        let outdated = is_loose(dependency) || ages.get(manifest.file_path.as_str()).is_some_and(|d| *d > STALE_MANIFEST_DAYS);
+        if outdated {
+            outdated_but_ignored += 1;
+        }
+        let scope = if rule.versions.is_empty() { String::new() } else { format!(" for {}", rule.versions.join(", ")) };
+        notes.push(format!(
+            "{} {} ({}) is {}ignored by {} config in {}{}",
+            dependency.name, dependency.version, manifest.file_path,
+            if outdated { "outdated but " } else { "" },
+            config.bot.as_str(), config.file_path, scope,
+        ));
+    }
+    if !automation.is_configured() {
+        notes.push("No Dependabot or Renovate config; dependency updates are entirely manual".to_string());
+    }
+
+    let age_score = days.map(|d| d / 730.0 * 100.0).unwrap_or(0.0).min(100.0);
+    let mut score = if days.is_some() { age_score * 0.7 + loose_ratio * 100.0 * 0.3 } else { loose_ratio * 100.0 };
+    score += if automation.is_configured() { uncovered as f64 / with_runtime_deps.len() as f64 * 10.0 } else { 20.0 };
+    let mut summary = match days {
+        Some(d) => format!("Manifests last updated {:.0} days ago; {} of {} dependencies unpinned or pre-1.0", d, loose, dependencies.len()),
+        None => format!("{} of {} dependencies unpinned or pre-1.0 (no git history)", loose, dependencies.len()),
+    };
+    if !automation.is_configured() {
+        summary.push_str("; no update automation");
+    } else if outdated_but_ignored > 0 {
+        summary.push_str(&format!("; {} outdated but ignored by update bot config", outdated_but_ignored));
+    }
+
+    let mut result = subscore(
+        score,
+        0.15,
+        summary,
+        &[
+            ("dependencies", dependencies.len() as f64),
+            ("loose_versions", loose as f64),
+            ("days_since_manifest_update", days.unwrap_or(-1.0)),
+            ("update_automation", if automation.is_configured() { 1.0 } else { 0.0 }),
+            ("uncovered_manifests", uncovered as f64),
+            ("ignored_dependencies", ignored as f64),
+            ("outdated_but_ignored", outdated_but_ignored as f64),
+        ],
+    );
+    result.notes = notes;
+    result
+}
+
+fn missing_tests(elements: &[CodeElement], tested_element_ids: &HashSet<String>) -> DebtSubscore {
+    let candidates: Vec<&CodeElement> = elements.iter()
+        .filter(|e| matches!(e.element_type, CodeElementType::Function | CodeElementType::Method | CodeElementType::Class))
+        .filter(|e| e.visibility.as_deref() != Some("private"))
+        .collect();
+    let tested = candidates.iter().filter(|e| tested_element_ids.contains(&e.id)).count();
+    let untested_ratio = if candidates.is_empty() { 0.0 } else { 1.0 - tested as f64 / candidates.len() as f64 };
+    subscore(
+        untested_ratio * 100.0,
+        0.25,
+        format!("{} of {} public functions/classes are linked to a test", tested, candidates.len()),
+        &[("candidates", candidates.len() as f64), ("tested", tested as f64), ("untested_ratio", untested_ratio)],
+    )
+}
+
+/// TODO/FIXME/HACK/XXX markers per thousand lines
+fn todo_density(contents: &HashMap<&str, String>) -> DebtSubscore {
+    let lines: usize = contents.values().map(|c| c.lines().count()).sum();
+    let markers: usize = contents.values().map(|c| find_markers(c).len()).sum();
+    let per_kloc = if lines == 0 { 0.0 } else { markers as f64 * 1000.0 / lines as f64 };
+    subscore(
+        per_kloc * 10.0,
+        0.15,
+        format!("{} TODO/FIXME markers ({:.1} per 1000 lines)", markers, per_kloc),
+        &[("markers", markers as f64), ("lines", lines as f64), ("per_kloc", per_kloc)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scores_duplication_todos_and_missing_tests() {
+        let temp_dir = TempDir::new().unwrap();
+        let block = "let total = items.length;\nlet first = items[0];\nlet last = items[total - 1];\nconsole.log(first);\nconsole.log(last);\nreturn total * 2;\n";
+        fs::write(temp_dir.path().join("a.js"), format!("function a(items) {{\n{}}}\n// TODO: remove\n", block)).unwrap();
+        fs::write(temp_dir.path().join("b.js"), format!("function b(items) {{\n{}}}\n", block)).unwrap();
+
+        let element = |id: &str, file: &str| CodeElement {
+            id: id.to_string(),
+            name: id.to_string(),
+            element_type: CodeElementType::Function,
+            file_path: file.to_string(),
+            line_number: 1,
+            language: "javascript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        };
+        let elements = vec![element("a", "a.js"), element("b", "b.js")];
+        let tested: HashSet<String> = ["a".to_string()].into_iter().collect();
+
+        let debt = TechDebtCalculator::new().calculate(temp_dir.path(), &elements, &tested, &[]);
+        assert!(debt.subscores["duplication"].metrics["duplicated_lines"] >= 12.0);
+        assert_eq!(debt.subscores["todo_density"].metrics["markers"], 1.0);
+        assert_eq!(debt.subscores["missing_tests"].metrics["untested_ratio"], 0.5);
+        assert_eq!(debt.subscores["dependency_staleness"].score, 0.0);
+        assert!(debt.score > 0.0);
+    }
+}
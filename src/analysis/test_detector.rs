@@ -4,6 +4,7 @@ use std::path::Path;
 use walkdir::WalkDir;
 use uuid::Uuid;
 use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum TestFramework {
@@ -93,9 +94,7 @@ impl TestDetector {
             test_files_found += 1;
 
             // Normalize path
-            let normalized_path = path.strip_prefix(repo_path)
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_else(|_| path.to_string_lossy().to_string());
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
 
             // Determine language
             let language = utils::detect_language(path);
@@ -755,4 +754,3 @@ impl TestDetector {
         Vec::new()
     }
 }
-
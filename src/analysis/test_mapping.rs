@@ -0,0 +1,324 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use crate::analysis::coverage::element_coverage;
+use crate::analysis::{CodeElement, CodeElementType, DetectedTest, FileCoverage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TestLinkStrategy {
+    Call,   // The test body calls the element, which lives in a file the test imports or targets
+    Naming, // The test name mentions the element, which lives in the file the test targets
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCodeLink {
+    pub test_id: String,
+    pub code_element_id: String,
+    pub strategy: TestLinkStrategy,
+    pub confidence: f64,
+}
+
+/// Links detected tests to the code elements they exercise
+pub struct TestMapper;
+
+impl TestMapper {
+    pub fn new() -> Self {
+        TestMapper
+    }
+
+    /// Coverage can't tell which test ran which line, but it corroborates links:
+    /// elements that were executed keep their confidence, never-executed ones are demoted
+    pub fn map_tests(
+        &self,
+        repo_path: &Path,
+        tests: &[DetectedTest],
+        elements: &[CodeElement],
+        coverage: &[FileCoverage],
+    ) -> Vec<TestCodeLink> {
+        let test_files: HashSet<&str> = tests.iter().map(|t| t.file_path.as_str()).collect();
+        let test_names: HashSet<(&str, &str)> = tests.iter()
+            .map(|t| (t.file_path.as_str(), t.name.as_str()))
+            .collect();
+
+        // Candidate subjects: functions, methods and classes outside test files (inline test modules
+        // are the exception, so elements sharing a file with tests are kept unless they are tests)
+        let mut elements_by_file: HashMap<&str, Vec<&CodeElement>> = HashMap::new();
+        for element in elements {
+            if !matches!(element.element_type, CodeElementType::Function | CodeElementType::Method | CodeElementType::Class) {
+                continue;
+            }
+            if element.name.len() < 3 || test_names.contains(&(element.file_path.as_str(), element.name.as_str())) {
+                continue;
+            }
+            elements_by_file.entry(element.file_path.as_str()).or_default().push(element);
+        }
+        let source_files: Vec<&str> = elements_by_file.keys().copied().collect();
+
+        let executed: HashMap<String, bool> = element_coverage(elements, coverage)
+            .into_iter()
+            .map(|c| (c.element_id, c.lines_hit > 0))
+            .collect();
+
+        let mut tests_by_file: HashMap<&str, Vec<&DetectedTest>> = HashMap::new();
+        for test in tests {
+            tests_by_file.entry(test.file_path.as_str()).or_default().push(test);
+        }
+
+        let mut links = Vec::new();
+        for (test_file, mut file_tests) in tests_by_file {
+            let content = match std::fs::read_to_string(repo_path.join(test_file)) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let lines: Vec<&str> = content.lines().collect();
+            file_tests.sort_by_key(|t| t.line_number);
+
+            let imported = imported_files(&content, test_file, &source_files);
+            let named = subject_files(test_file, &source_files, &test_files);
+            let mut candidate_files: Vec<(&str, f64)> = Vec::new();
+            for file in &imported {
+                candidate_files.push((file, 0.9));
+            }
+            for file in &named {
+                if !imported.contains(file) {
+                    candidate_files.push((file, 0.8));
+                }
+            }
+            if elements_by_file.contains_key(test_file) && !candidate_files.iter().any(|(f, _)| *f == test_file) {
+                candidate_files.push((test_file, 0.9));
+            }
+            if candidate_files.is_empty() {
+                continue;
+            }
+
+            for (idx, test) in file_tests.iter().enumerate() {
+                let start = test.line_number.saturating_sub(1);
+                let end = file_tests.get(idx + 1)
+                    .map(|next| next.line_number.saturating_sub(1))
+                    .unwrap_or(lines.len())
+                    .max(start + 1)
+                    .min(lines.len());
+                let body = lines.get(start..end).map(|l| l.join("\n")).unwrap_or_default();
+                let test_name = normalize_name(&test.name);
+
+                let mut linked: HashSet<&str> = HashSet::new();
+                for (file, base_confidence) in &candidate_files {
+                    for element in elements_by_file.get(file).into_iter().flatten() {
+                        if linked.contains(element.id.as_str()) {
+                            continue;
+                        }
+                        let strategy = if calls_element(&body, &element.name) {
+                            TestLinkStrategy::Call
+                        } else if *file != test_file && named.contains(file) && test_name.contains(&normalize_name(&element.name)) {
+                            TestLinkStrategy::Naming
+                        } else {
+                            continue;
+                        };
+
+                        let mut confidence = match strategy {
+                            TestLinkStrategy::Call => *base_confidence,
+                            TestLinkStrategy::Naming => 0.6,
+                        };
+                        if executed.get(&element.id) == Some(&false) {
+                            confidence -= 0.2;
+                        }
+
+                        linked.insert(element.id.as_str());
+                        links.push(TestCodeLink {
+                            test_id: test.id.clone(),
+                            code_element_id: element.id.clone(),
+                            strategy,
+                            confidence: (confidence * 100.0).round() / 100.0,
+                        });
+                    }
+                }
+            }
+        }
+
+        links
+    }
+}
+
+/// "test_parse_config" / "testParseConfig" / "parses config" all compare as "parseconfig"-like strings
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn calls_element(body: &str, name: &str) -> bool {
+    let escaped = regex::escape(name);
+    // Calls, constructor use and associated-function paths
+    let pattern = format!(r"(?:^|[^\w]){}\s*(?:\(|::|\.)|new\s+{}\b", escaped, escaped);
+    Regex::new(&pattern).map(|re| re.is_match(body)).unwrap_or(false)
+}
+
+/// Files a test file imports, resolved against known source files by path suffix
+fn imported_files<'a>(content: &str, test_file: &str, source_files: &[&'a str]) -> Vec<&'a str> {
+    let patterns = [
+        // JS/TS relative imports and requires
+        Regex::new(r#"(?:from\s+|require\(\s*|import\s+)["'](\.[^"']+)["']"#).unwrap(),
+        // Python absolute and relative imports
+        Regex::new(r"^\s*from\s+([\w.]+)\s+import\b").unwrap(),
+        // Java / Kotlin imports
+        Regex::new(r"^\s*import\s+(?:static\s+)?([\w.]+)\s*;?\s*$").unwrap(),
+    ];
+    let test_dir = Path::new(test_file).parent().unwrap_or_else(|| Path::new(""));
+
+    let mut suffixes: Vec<String> = Vec::new();
+    for line in content.lines() {
+        for (idx, pattern) in patterns.iter().enumerate() {
+            if let Some(cap) = pattern.captures(line) {
+                let target = &cap[1];
+                let suffix = if idx == 0 {
+                    normalize_relative(test_dir, target)
+                } else {
+                    target.trim_start_matches('.').replace('.', "/")
+                };
+                if !suffix.is_empty() {
+                    suffixes.push(suffix);
+                }
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for suffix in &suffixes {
+        for file in source_files {
+            let without_ext = file.rsplit_once('.').map(|(p, _)| p).unwrap_or(file);
+            let matches = without_ext == suffix
+                || without_ext.ends_with(&format!("/{}", suffix))
+                || without_ext == format!("{}/index", suffix)
+                || without_ext == format!("{}/__init__", suffix);
+            if matches && !files.contains(file) {
+                files.push(*file);
+            }
+        }
+    }
+    files
+}
+
+fn normalize_relative(base: &Path, target: &str) -> String {
+    let mut parts: Vec<String> = base.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    for segment in target.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => { parts.pop(); }
+            other => parts.push(other.to_string()),
+        }
+    }
+    let joined = parts.join("/");
+    // Drop an explicit extension so it matches the suffix comparison
+    match joined.rsplit_once('.') {
+        Some((stem, ext)) if !ext.contains('/') && ext.len() <= 4 => stem.to_string(),
+        _ => joined,
+    }
+}
+
+/// Source files a test targets by naming convention: foo.test.ts, foo_test.go, test_foo.py, FooTest.java
+fn subject_files<'a>(test_file: &str, source_files: &[&'a str], test_files: &HashSet<&str>) -> Vec<&'a str> {
+    let file_name = Path::new(test_file).file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let stem = file_name.split('.').next().unwrap_or("");
+    let subject = [".test", ".spec"].iter()
+        .find_map(|marker| file_name.find(marker).map(|idx| &file_name[..idx]))
+        .or_else(|| stem.strip_suffix("_test"))
+        .or_else(|| stem.strip_prefix("test_"))
+        .or_else(|| stem.strip_suffix("Tests"))
+        .or_else(|| stem.strip_suffix("Test"))
+        .unwrap_or("");
+    if subject.is_empty() {
+        return Vec::new();
+    }
+
+    let test_dir = Path::new(test_file).parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+    let mut matches: Vec<&str> = source_files.iter()
+        .copied()
+        .filter(|file| !test_files.contains(file))
+        .filter(|file| {
+            let name = Path::new(file).file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.split('.').next() == Some(subject)
+        })
+        .collect();
+    // Prefer the file next to the test when several share the name
+    if let Some(same_dir) = matches.iter().find(|f| Path::new(f).parent().map(|p| p.to_string_lossy() == test_dir).unwrap_or(false)) {
+        matches = vec![*same_dir];
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::TestFramework;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn element(id: &str, name: &str, file_path: &str) -> CodeElement {
+        CodeElement {
+            id: id.to_string(),
+            name: name.to_string(),
+            element_type: CodeElementType::Function,
+            file_path: file_path.to_string(),
+            line_number: 1,
+            language: "javascript".to_string(),
+            signature: None,
+            doc_comment: None,
+            visibility: None,
+            parameters: Vec::new(),
+            return_type: None,
+            parent_id: None,
+            qualified_name: String::new(),
+            decorators: Vec::new(),
+            origin: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_links_tests_to_called_and_named_functions() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/cart.test.js"), r#"import { addItem } from "./cart";
+
+test("addItem increases the total", () => {
+  expect(addItem([], 2)).toHaveLength(1);
+});
+
+test("removeItem drops the item", () => {
+  expect(true).toBe(true);
+});
+"#).unwrap();
+
+        let make_test = |id: &str, name: &str, line: usize| DetectedTest {
+            id: id.to_string(),
+            name: name.to_string(),
+            test_framework: TestFramework::Jest,
+            file_path: "src/cart.test.js".to_string(),
+            line_number: line,
+            language: "javascript".to_string(),
+            test_type: "unit".to_string(),
+            suite_name: None,
+            assertions: Vec::new(),
+            setup_methods: Vec::new(),
+            teardown_methods: Vec::new(),
+            signature: None,
+            doc_comment: None,
+            parameters: Vec::new(),
+            return_type: None,
+        };
+        let tests = vec![make_test("t1", "addItem increases the total", 3), make_test("t2", "removeItem drops the item", 7)];
+        let elements = vec![element("e1", "addItem", "src/cart.js"), element("e2", "removeItem", "src/cart.js")];
+
+        let links = TestMapper::new().map_tests(temp_dir.path(), &tests, &elements, &[]);
+        assert_eq!(links.len(), 2);
+        let call = links.iter().find(|l| l.test_id == "t1").unwrap();
+        assert_eq!(call.code_element_id, "e1");
+        assert_eq!(call.strategy, TestLinkStrategy::Call);
+        let named = links.iter().find(|l| l.test_id == "t2").unwrap();
+        assert_eq!(named.code_element_id, "e2");
+        assert_eq!(named.strategy, TestLinkStrategy::Naming);
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analysis::git_blame::GitBlame;
+use crate::analysis::utils;
+use crate::analysis::repo_path::RepoPath;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum TodoKind {
+    Todo,
+    Fixme,
+    Hack,
+    Xxx,
+}
+
+impl TodoKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoKind::Todo => "todo",
+            TodoKind::Fixme => "fixme",
+            TodoKind::Hack => "hack",
+            TodoKind::Xxx => "xxx",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoMarker {
+    pub kind: TodoKind,
+    pub text: String,
+    pub file_path: String,
+    pub line_number: usize,
+    pub language: Option<String>,
+    pub author: Option<String>,       // `TODO(alice):` owner, else the blamed commit author
+    pub committed_at: Option<String>, // RFC 3339 time of the blamed commit
+    pub age_days: Option<i64>,
+}
+
+/// A marker must follow a comment opener so identifiers such as `todoList` or string contents are ignored
+static MARKER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?://+|#|/\*+|^\s*\*|--|<!--|;)\s*(TODO|FIXME|HACK|XXX)\b(?:\(([^)]*)\))?[:\s-]*(.*)").unwrap()
+});
+
+/// Extensions scanned besides the languages `utils::detect_language` knows
+const EXTRA_EXTENSIONS: &[&str] = &["c", "h", "cc", "cpp", "hpp", "cs", "rb", "php", "kt", "scala", "sh", "yml", "yaml", "sql", "vue", "svelte"];
+
+/// Finds TODO/FIXME/HACK/XXX comments in source files
+pub struct TodoScanner {
+    history_depth: Option<usize>,
+}
+
+impl TodoScanner {
+    pub fn new() -> Self {
+        TodoScanner { history_depth: None }
+    }
+
+    /// Limit blame to the last `history_depth` commits; 0 leaves markers without author and age
+    pub fn with_history_depth(mut self, history_depth: usize) -> Self {
+        self.history_depth = Some(history_depth);
+        self
+    }
+
+    /// Walk the repository; when it is a git checkout each marker gets its author and age from blame
+    pub fn scan(&self, repo_path: &Path) -> Result<Vec<TodoMarker>> {
+        let mut markers = Vec::new();
+        let mut blame = GitBlame::open_with_depth(repo_path, self.history_depth);
+        let now = chrono::Utc::now().timestamp();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name_lower = path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let path_str = path.to_string_lossy().to_lowercase();
+            if utils::should_skip_file(&file_name_lower, &path_str) {
+                continue;
+            }
+
+            let language = utils::detect_language(path);
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            if language.is_none() && !EXTRA_EXTENSIONS.contains(&extension.as_str()) {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            if utils::is_minified_or_compiled(&content, &path_str) {
+                continue;
+            }
+
+            let normalized_path = RepoPath::new(repo_path, path).into_string();
+
+            for (line_number, kind, owner, text) in find_markers(&content) {
+                let blamed = blame.line(&normalized_path, line_number).cloned();
+                markers.push(TodoMarker {
+                    kind,
+                    text,
+                    file_path: normalized_path.clone(),
+                    line_number,
+                    language: language.clone(),
+                    author: owner.or_else(|| blamed.as_ref().and_then(|b| b.author.clone())),
+                    committed_at: blamed.as_ref()
+                        .and_then(|b| chrono::DateTime::from_timestamp(b.timestamp, 0))
+                        .map(|t| t.to_rfc3339()),
+                    age_days: blamed.map(|b| (now - b.timestamp).max(0) / 86_400),
+                });
+            }
+        }
+
+        Ok(markers)
+    }
+}
+
+/// `(line_number, kind, owner, text)` for every marker comment in a file
+pub fn find_markers(content: &str) -> Vec<(usize, TodoKind, Option<String>, String)> {
+    content.lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let cap = MARKER.captures(line)?;
+            let kind = match &cap[1] {
+                "TODO" => TodoKind::Todo,
+                "FIXME" => TodoKind::Fixme,
+                "HACK" => TodoKind::Hack,
+                _ => TodoKind::Xxx,
+            };
+            let owner = cap.get(2).map(|m| m.as_str().trim().to_string()).filter(|o| !o.is_empty());
+            let text = cap[3].trim().trim_end_matches("*/").trim_end_matches("-->").trim().to_string();
+            Some((idx + 1, kind, owner, text))
+        })
+        .collect()
+}
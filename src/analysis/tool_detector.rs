@@ -2,6 +2,9 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::analysis::repo_path::RepoPath;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ToolType {
@@ -16,6 +19,8 @@ pub enum ToolType {
     ShellScript,
     DevEnvironment,
     Sdk,
+    BuildCache,
+    Toolchain,
     Other,
 }
 
@@ -31,6 +36,8 @@ pub enum ToolCategory {
     GoBuild,
     Maven,
     Gradle,
+    Bazel,
+    Buck,
     
     // Testing
     Jest,
@@ -129,6 +136,9 @@ impl ToolDetector {
         
         // Detect development environments
         tools.extend(self.detect_dev_environments(repo_path)?);
+
+        // Detect Bazel/Buck remote caches, executors and toolchains
+        tools.extend(self.detect_remote_builds(repo_path)?);
         
         // Deduplicate tools
         Ok(self.deduplicate_tools(tools))
@@ -160,7 +170,7 @@ impl ToolDetector {
                 });
                 
                 // Detect tools from command
-                if let Some(tool) = self.detect_tool_from_command(command, &package_json) {
+                if let Some(tool) = self.detect_tool_from_command(command, Path::new("package.json")) {
                     tools.push(tool);
                 }
             }
@@ -186,7 +196,7 @@ impl ToolDetector {
     }
 
     /// Detect tool from a command string
-    fn detect_tool_from_command(&self, command: &str, file_path: &Path) -> Option<DetectedTool> {
+    pub(crate) fn detect_tool_from_command(&self, command: &str, file_path: &Path) -> Option<DetectedTool> {
         let command_lower = command.to_lowercase();
         
         // Build tools
@@ -369,9 +379,7 @@ impl ToolDetector {
                     tool_type: ToolType::Linter,
                     category: ToolCategory::Eslint,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -389,9 +397,7 @@ impl ToolDetector {
                     tool_type: ToolType::Formatter,
                     category: ToolCategory::Prettier,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -407,9 +413,7 @@ impl ToolDetector {
                     tool_type: ToolType::BuildTool,
                     category: ToolCategory::Webpack,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -425,9 +429,7 @@ impl ToolDetector {
                     tool_type: ToolType::BuildTool,
                     category: ToolCategory::Vite,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -444,9 +446,7 @@ impl ToolDetector {
                     tool_type: ToolType::TestFramework,
                     category: ToolCategory::Jest,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -462,9 +462,29 @@ impl ToolDetector {
                     tool_type: ToolType::TaskRunner,
                     category: ToolCategory::Make,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
+                    line_number: None,
+                    detection_method: "config_file".to_string(),
+                    configuration: HashMap::new(),
+                    scripts: Vec::new(),
+                    confidence: 0.9,
+                });
+            }
+
+            // Justfile, Taskfile and Gradle build scripts
+            let task_runner = match file_name.as_str() {
+                "justfile" => Some(("just", ToolType::TaskRunner, ToolCategory::Just)),
+                "taskfile.yml" | "taskfile.yaml" => Some(("task", ToolType::TaskRunner, ToolCategory::Task)),
+                "build.gradle" | "build.gradle.kts" => Some(("gradle", ToolType::BuildTool, ToolCategory::Gradle)),
+                _ => None,
+            };
+            if let Some((name, tool_type, category)) = task_runner {
+                tools.push(DetectedTool {
+                    name: name.to_string(),
+                    tool_type,
+                    category,
+                    version: None,
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "config_file".to_string(),
                     configuration: HashMap::new(),
@@ -609,9 +629,7 @@ impl ToolDetector {
                             tool_type: ToolType::ShellScript,
                             category: ToolCategory::Bash,
                             version: None,
-                            file_path: path.strip_prefix(repo_path)
-                                .map(|p| p.to_string_lossy().to_string())
-                                .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                            file_path: RepoPath::new(repo_path, path).into_string(),
                             line_number: None,
                             detection_method: "script_file".to_string(),
                             configuration: HashMap::new(),
@@ -624,9 +642,7 @@ impl ToolDetector {
                             tool_type: ToolType::ShellScript,
                             category: ToolCategory::Bash,
                             version: None,
-                            file_path: path.strip_prefix(repo_path)
-                                .map(|p| p.to_string_lossy().to_string())
-                                .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                            file_path: RepoPath::new(repo_path, path).into_string(),
                             line_number: None,
                             detection_method: "script_file".to_string(),
                             configuration: HashMap::new(),
@@ -666,9 +682,7 @@ impl ToolDetector {
                     tool_type: ToolType::DevEnvironment,
                     category: ToolCategory::Venv,
                     version: None,
-                    file_path: path.strip_prefix(repo_path)
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|_| path.to_string_lossy().to_string()),
+                    file_path: RepoPath::new(repo_path, path).into_string(),
                     line_number: None,
                     detection_method: "directory_pattern".to_string(),
                     configuration: HashMap::new(),
@@ -715,6 +729,88 @@ impl ToolDetector {
         Ok(tools)
     }
 
+    /// Detect Bazel and Buck: remote cache, remote execution and build event endpoints from
+    /// `.bazelrc` files and `.buckconfig`, with the credentials configured for them, and the
+    /// toolchains BUILD/BUCK files define and WORKSPACE/MODULE.bazel files register
+    fn detect_remote_builds(&self, repo_path: &Path) -> Result<Vec<DetectedTool>> {
+        let mut tools = Vec::new();
+        let mut bazel_endpoints = Vec::new();
+        let mut bazel_credentials = Vec::new();
+        let bazel_version = std::fs::read_to_string(repo_path.join(".bazelversion")).ok()
+            .and_then(|v| v.lines().next().map(|l| l.trim().to_string()))
+            .filter(|v| !v.is_empty());
+
+        use walkdir::WalkDir;
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                // bazel-out and the bazel-<workspace> convenience symlinks hold build outputs
+                !(name == ".git" || name == "node_modules" || name == "buck-out" || name.starts_with("bazel-"))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let build_system = match file_name {
+                ".buckconfig" | "BUCK" | "TARGETS" => ToolCategory::Buck,
+                ".bazelrc" | "WORKSPACE" | "WORKSPACE.bazel" | "MODULE.bazel" | "BUILD" | "BUILD.bazel" => ToolCategory::Bazel,
+                name if name.ends_with(".bazelrc") => ToolCategory::Bazel,
+                _ => continue,
+            };
+            let Ok(content) = std::fs::read_to_string(path) else { continue };
+            let file_path = RepoPath::new(repo_path, path).into_string();
+
+            match file_name {
+                "BUILD" | "BUILD.bazel" | "BUCK" | "TARGETS" => {
+                    let package = file_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+                    tools.extend(toolchain_definitions(&content, package, &file_path, &build_system));
+                    continue;
+                }
+                ".buckconfig" => {
+                    tools.extend(buckconfig_endpoints(&content).into_iter().map(|e| e.into_tool(&file_path, ToolCategory::Buck)));
+                }
+                "WORKSPACE" | "WORKSPACE.bazel" | "MODULE.bazel" => {
+                    tools.extend(toolchain_registrations(&content, &file_path));
+                }
+                _ => {
+                    let rc = parse_bazelrc(&content);
+                    bazel_endpoints.extend(rc.endpoints.into_iter().map(|e| (file_path.clone(), e)));
+                    bazel_credentials.extend(rc.credentials);
+                    tools.extend(rc.toolchains.into_iter().map(|(label, line)| {
+                        toolchain_tool(&label, ToolCategory::Bazel, &file_path, line, "toolchain_registration", HashMap::new())
+                    }));
+                }
+            }
+
+            let is_bazel = build_system == ToolCategory::Bazel;
+            tools.push(DetectedTool {
+                name: if is_bazel { "bazel" } else { "buck" }.to_string(),
+                tool_type: ToolType::BuildTool,
+                category: build_system,
+                version: if is_bazel { bazel_version.clone() } else { None },
+                file_path,
+                line_number: None,
+                detection_method: "config_file".to_string(),
+                configuration: HashMap::new(),
+                scripts: Vec::new(),
+                confidence: 0.95,
+            });
+        }
+
+        // Credentials under a named config apply to that config, others to every build
+        for (file_path, mut endpoint) in bazel_endpoints {
+            endpoint.credentials = bazel_credentials.iter()
+                .filter(|(config, _)| config.is_none() || *config == endpoint.config)
+                .map(|(_, flag)| flag.clone())
+                .collect();
+            tools.push(endpoint.into_tool(&file_path, ToolCategory::Bazel));
+        }
+
+        Ok(tools)
+    }
+
     /// Deduplicate tools (same name, category, type)
     fn deduplicate_tools(&self, tools: Vec<DetectedTool>) -> Vec<DetectedTool> {
         let mut seen: std::collections::HashMap<(String, ToolCategory, ToolType), usize> = 
@@ -753,3 +849,221 @@ impl ToolDetector {
     }
 }
 
+/// Bazel flags naming a remote endpoint, with the kind of endpoint they name
+const BAZEL_ENDPOINT_FLAGS: &[(&str, &str)] = &[
+    ("remote_cache", "remote_cache"),
+    ("remote_executor", "remote_executor"),
+    ("bes_backend", "build_event_service"),
+];
+/// Bazel flags that send credentials to remote endpoints
+const BAZEL_CREDENTIAL_FLAGS: &[&str] = &[
+    "google_credentials", "google_default_credentials", "credential_helper", "tls_client_certificate",
+    "remote_header", "remote_cache_header", "remote_exec_header", "bes_header",
+];
+/// `.buckconfig` keys naming a remote endpoint, by section
+const BUCK_ENDPOINT_KEYS: &[(&str, &str, &str)] = &[
+    ("cache", "http_url", "remote_cache"),
+    ("buck2_re_client", "action_cache_address", "remote_cache"),
+    ("buck2_re_client", "cas_address", "remote_cache"),
+    ("buck2_re_client", "engine_address", "remote_executor"),
+];
+/// `.buckconfig` keys that send credentials to remote endpoints, by section
+const BUCK_CREDENTIAL_KEYS: &[(&str, &str)] = &[
+    ("cache", "http_read_headers"),
+    ("cache", "http_write_headers"),
+    ("cache", "http_client_tls_cert"),
+    ("buck2_re_client", "http_headers"),
+    ("buck2_re_client", "tls_client_cert"),
+];
+
+static REGISTER_TOOLCHAINS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bregister_toolchains\s*\(([^)]*)\)").unwrap());
+static TOOLCHAIN_RULE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^[ \t]*(toolchain|\w+_toolchain)\s*\(").unwrap());
+static QUOTED: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).unwrap());
+
+/// A remote cache, executor or build event service a build is configured to use
+struct RemoteEndpoint {
+    kind: &'static str,
+    url: String,
+    /// The `--config` the flag is under (`build:ci`), if any
+    config: Option<String>,
+    credentials: Vec<String>,
+    line_number: usize,
+}
+
+impl RemoteEndpoint {
+    fn into_tool(self, file_path: &str, category: ToolCategory) -> DetectedTool {
+        let mut configuration = HashMap::new();
+        configuration.insert("kind".to_string(), self.kind.to_string());
+        configuration.insert("url".to_string(), self.url.clone());
+        if let Some(config) = self.config {
+            configuration.insert("config".to_string(), config);
+        }
+        let authentication = if self.credentials.is_empty() { "none".to_string() } else { self.credentials.join(",") };
+        configuration.insert("authentication".to_string(), authentication);
+        DetectedTool {
+            name: self.url,
+            tool_type: ToolType::BuildCache,
+            category,
+            version: None,
+            file_path: file_path.to_string(),
+            line_number: Some(self.line_number),
+            detection_method: "config_file".to_string(),
+            configuration,
+            scripts: Vec::new(),
+            confidence: 0.9,
+        }
+    }
+}
+
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+/// What a Bazel rc file says about remote builds
+#[derive(Default)]
+struct BazelRc {
+    endpoints: Vec<RemoteEndpoint>,
+    /// Credential flags, with the config they apply to
+    credentials: Vec<(Option<String>, String)>,
+    /// `--extra_toolchains` labels with their line
+    toolchains: Vec<(String, usize)>,
+}
+
+/// Reads a Bazel rc file, whose flags are written `--flag=value` or `--flag value`
+fn parse_bazelrc(content: &str) -> BazelRc {
+    let mut rc = BazelRc::default();
+    for (idx, line) in content.lines().enumerate() {
+        let mut words = line.split('#').next().unwrap_or("").split_whitespace();
+        let Some(command) = words.next() else { continue };
+        if command == "import" || command == "try-import" {
+            continue;
+        }
+        let config = command.split_once(':').map(|(_, c)| c.to_string());
+        let words: Vec<&str> = words.collect();
+        for (i, word) in words.iter().enumerate() {
+            let Some(flag) = word.strip_prefix("--") else { continue };
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name, value),
+                None => (flag, words.get(i + 1).copied().filter(|v| !v.starts_with("--")).unwrap_or("")),
+            };
+            let value = value.trim_matches(['"', '\'']);
+            if let Some((_, kind)) = BAZEL_ENDPOINT_FLAGS.iter().find(|(f, _)| *f == name) {
+                if !value.is_empty() {
+                    rc.endpoints.push(RemoteEndpoint {
+                        kind,
+                        url: value.to_string(),
+                        config: config.clone(),
+                        credentials: Vec::new(),
+                        line_number: idx + 1,
+                    });
+                }
+            } else if BAZEL_CREDENTIAL_FLAGS.contains(&name) && value != "false" {
+                rc.credentials.push((config.clone(), name.to_string()));
+            } else if name == "extra_toolchains" {
+                rc.toolchains.extend(value.split(',').filter(|l| !l.is_empty()).map(|l| (l.to_string(), idx + 1)));
+            }
+        }
+    }
+    rc
+}
+
+/// Remote endpoints of a `.buckconfig`, each authenticated by the credential keys of its section
+fn buckconfig_endpoints(content: &str) -> Vec<RemoteEndpoint> {
+    let mut endpoints = Vec::new();
+    let mut credentials: HashMap<String, Vec<String>> = HashMap::new();
+    let mut section = String::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let (key, value) = (key.trim(), value.trim());
+        if value.is_empty() {
+            continue;
+        }
+        if let Some((_, _, kind)) = BUCK_ENDPOINT_KEYS.iter().find(|(s, k, _)| *s == section && *k == key) {
+            endpoints.push((section.clone(), RemoteEndpoint {
+                kind,
+                url: value.to_string(),
+                config: None,
+                credentials: Vec::new(),
+                line_number: idx + 1,
+            }));
+        } else if BUCK_CREDENTIAL_KEYS.contains(&(section.as_str(), key)) {
+            credentials.entry(section.clone()).or_default().push(key.to_string());
+        }
+    }
+    endpoints.into_iter()
+        .map(|(section, mut endpoint)| {
+            endpoint.credentials = credentials.get(&section).cloned().unwrap_or_default();
+            endpoint
+        })
+        .collect()
+}
+
+fn toolchain_tool(label: &str, category: ToolCategory, file_path: &str, line_number: usize, method: &str, configuration: HashMap<String, String>) -> DetectedTool {
+    DetectedTool {
+        name: label.to_string(),
+        tool_type: ToolType::Toolchain,
+        category,
+        version: None,
+        file_path: file_path.to_string(),
+        line_number: Some(line_number),
+        detection_method: method.to_string(),
+        configuration,
+        scripts: Vec::new(),
+        confidence: 0.9,
+    }
+}
+
+/// Toolchains a WORKSPACE or MODULE.bazel file registers, by label
+fn toolchain_registrations(content: &str, file_path: &str) -> Vec<DetectedTool> {
+    REGISTER_TOOLCHAINS.captures_iter(content)
+        .flat_map(|call| {
+            let args = call.get(1).unwrap();
+            QUOTED.captures_iter(args.as_str())
+                .filter_map(|q| q.get(1).or(q.get(2)).map(|m| (m.as_str().to_string(), line_at(content, args.start() + m.start()))))
+                .collect::<Vec<_>>()
+        })
+        .map(|(label, line)| toolchain_tool(&label, ToolCategory::Bazel, file_path, line, "toolchain_registration", HashMap::new()))
+        .collect()
+}
+
+/// `toolchain()` and `*_toolchain()` rules of a BUILD or BUCK file, labelled by their package
+fn toolchain_definitions(content: &str, package: &str, file_path: &str, category: &ToolCategory) -> Vec<DetectedTool> {
+    let mut tools = Vec::new();
+    for rule in TOOLCHAIN_RULE.captures_iter(content) {
+        let call = rule.get(0).unwrap();
+        let mut depth = 1;
+        let args_end = content[call.end()..].char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| call.end() + i)
+            .unwrap_or(content.len());
+        let args = &content[call.end()..args_end];
+        let attribute = |attr: &str| {
+            Regex::new(&format!(r#"\b{}\s*=\s*["']([^"']+)["']"#, attr)).ok()
+                .and_then(|re| re.captures(args).map(|c| c[1].to_string()))
+        };
+        let Some(name) = attribute("name") else { continue };
+        let mut configuration = HashMap::new();
+        configuration.insert("rule".to_string(), rule[1].to_string());
+        if let Some(toolchain_type) = attribute("toolchain_type") {
+            configuration.insert("toolchain_type".to_string(), toolchain_type);
+        }
+        let label = format!("//{}:{}", package, name);
+        tools.push(toolchain_tool(&label, category.clone(), file_path, line_at(content, call.start()), "toolchain_definition", configuration));
+    }
+    tools
+}
@@ -0,0 +1,303 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::analysis::dependencies::DependencyManifest;
+use crate::analysis::PackageManager;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UpdateBot {
+    Dependabot,
+    Renovate,
+}
+
+impl UpdateBot {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateBot::Dependabot => "dependabot",
+            UpdateBot::Renovate => "renovate",
+        }
+    }
+}
+
+/// A dependency the bot is told to skip or hold back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoreRule {
+    pub pattern: String,          // Exact name, `*` glob or `/regex/`
+    pub ecosystem: Option<String>, // None applies to every ecosystem the bot covers
+    pub versions: Vec<String>,    // Empty ignores every update
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBotConfig {
+    pub bot: UpdateBot,
+    pub file_path: String,
+    pub enabled: bool,
+    /// Dependabot `package-ecosystem` values; Renovate `enabledManagers` or `*` for all
+    pub ecosystems: Vec<String>,
+    /// Dependabot update directories; empty means the whole repository
+    pub directories: Vec<String>,
+    pub schedule: Option<String>,
+    pub ignored: Vec<IgnoreRule>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateAutomation {
+    pub configs: Vec<UpdateBotConfig>,
+}
+
+/// Renovate reads the first of these it finds
+const RENOVATE_FILES: &[&str] = &[
+    "renovate.json", "renovate.json5", ".github/renovate.json", ".github/renovate.json5",
+    ".gitlab/renovate.json", ".gitlab/renovate.json5", ".renovaterc", ".renovaterc.json", ".renovaterc.json5",
+];
+
+const DEPENDABOT_FILES: &[&str] = &[".github/dependabot.yml", ".github/dependabot.yaml"];
+
+static LINE_COMMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\s*//.*$").unwrap());
+static TRAILING_COMMA: Lazy<Regex> = Lazy::new(|| Regex::new(r",(\s*[}\]])").unwrap());
+
+impl UpdateAutomation {
+    /// At least one enabled bot config
+    pub fn is_configured(&self) -> bool {
+        self.configs.iter().any(|c| c.enabled)
+    }
+
+    /// Whether an enabled bot watches this manifest's ecosystem and directory
+    pub fn covers(&self, manifest: &DependencyManifest) -> bool {
+        let ecosystem = ecosystem_of(&manifest.package_manager);
+        let directory = manifest.file_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        self.configs.iter()
+            .filter(|c| c.enabled)
+            .filter(|c| c.ecosystems.iter().any(|e| e == "*" || Some(e.as_str()) == ecosystem))
+            .any(|c| c.directories.is_empty() || c.directories.iter().any(|d| directory_matches(d, directory)))
+    }
+
+    /// The config and rule that keep the bot from updating a dependency
+    pub fn ignored_by(&self, name: &str, package_manager: &PackageManager) -> Option<(&UpdateBotConfig, &IgnoreRule)> {
+        let ecosystem = ecosystem_of(package_manager);
+        self.configs.iter().filter(|c| c.enabled).find_map(|config| {
+            config.ignored.iter()
+                .find(|rule| rule.ecosystem.as_deref().is_none_or(|e| Some(e) == ecosystem) && name_matches(&rule.pattern, name))
+                .map(|rule| (config, rule))
+        })
+    }
+}
+
+/// Finds Dependabot and Renovate configuration and the dependencies each one ignores
+pub struct UpdateBotDetector;
+
+impl UpdateBotDetector {
+    pub fn new() -> Self {
+        UpdateBotDetector
+    }
+
+    pub fn detect_automation(&self, repo_path: &Path) -> UpdateAutomation {
+        let mut configs = Vec::new();
+        for file in DEPENDABOT_FILES {
+            if let Ok(content) = std::fs::read_to_string(repo_path.join(file)) {
+                configs.extend(parse_dependabot(file, &content));
+            }
+        }
+
+        let renovate = RENOVATE_FILES.iter()
+            .find_map(|file| {
+                let content = std::fs::read_to_string(repo_path.join(file)).ok()?;
+                let value = parse_json5(&content)?;
+                Some((file.to_string(), value))
+            })
+            .or_else(|| {
+                let content = std::fs::read_to_string(repo_path.join("package.json")).ok()?;
+                let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+                value.get("renovate").cloned().map(|v| ("package.json".to_string(), v))
+            });
+        if let Some((file_path, value)) = renovate {
+            configs.push(parse_renovate(file_path, &value));
+        }
+
+        UpdateAutomation { configs }
+    }
+}
+
+/// One config per `updates` entry so ecosystems stay paired with their directories
+fn parse_dependabot(file_path: &str, content: &str) -> Vec<UpdateBotConfig> {
+    let yaml: serde_yaml::Value = match serde_yaml::from_str(content) {
+        Ok(y) => y,
+        Err(_) => return Vec::new(),
+    };
+    let strings = |v: Option<&serde_yaml::Value>| -> Vec<String> {
+        v.and_then(|v| v.as_sequence())
+            .map(|s| s.iter().filter_map(|i| i.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    };
+
+    let mut configs = Vec::new();
+    for update in yaml.get("updates").and_then(|u| u.as_sequence()).into_iter().flatten() {
+        let Some(ecosystem) = update.get("package-ecosystem").and_then(|e| e.as_str()) else { continue };
+        let ignored = update.get("ignore").and_then(|i| i.as_sequence()).into_iter().flatten()
+            .filter_map(|ignore| {
+                let name = ignore.get("dependency-name")?.as_str()?;
+                let mut versions = strings(ignore.get("versions"));
+                versions.extend(strings(ignore.get("update-types")));
+                Some(IgnoreRule { pattern: name.to_string(), ecosystem: Some(ecosystem.to_string()), versions })
+            })
+            .collect();
+        let mut directories = strings(update.get("directories"));
+        directories.extend(update.get("directory").and_then(|d| d.as_str()).map(String::from));
+        configs.push(UpdateBotConfig {
+            bot: UpdateBot::Dependabot,
+            file_path: file_path.to_string(),
+            // A zero PR limit turns version updates off for the entry
+            enabled: update.get("open-pull-requests-limit").and_then(|l| l.as_u64()) != Some(0),
+            ecosystems: vec![ecosystem.to_string()],
+            directories,
+            schedule: update.get("schedule").and_then(|s| s.get("interval")).and_then(|i| i.as_str()).map(String::from),
+            ignored,
+        });
+    }
+
+    configs
+}
+
+fn parse_renovate(file_path: String, value: &serde_json::Value) -> UpdateBotConfig {
+    let strings = |v: Option<&serde_json::Value>| -> Vec<String> {
+        v.and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    };
+
+    let ecosystems: Vec<String> = strings(value.get("enabledManagers")).iter().map(|m| renovate_ecosystem(m)).collect();
+    let mut ignored: Vec<IgnoreRule> = strings(value.get("ignoreDeps")).into_iter()
+        .map(|pattern| IgnoreRule { pattern, ecosystem: None, versions: Vec::new() })
+        .collect();
+
+    for rule in value.get("packageRules").and_then(|r| r.as_array()).into_iter().flatten() {
+        let disabled = rule.get("enabled").and_then(|e| e.as_bool()) == Some(false);
+        let allowed = rule.get("allowedVersions").and_then(|a| a.as_str());
+        if !disabled && allowed.is_none() {
+            continue;
+        }
+        let mut patterns = strings(rule.get("matchPackageNames"));
+        patterns.extend(strings(rule.get("matchDepNames")));
+        patterns.extend(strings(rule.get("packageNames")));
+        patterns.extend(strings(rule.get("matchPackagePatterns")).into_iter().map(|p| format!("/{}/", p)));
+        let managers = strings(rule.get("matchManagers"));
+        let versions: Vec<String> = allowed.filter(|_| !disabled).map(|a| vec![format!("outside {}", a)]).unwrap_or_default();
+        for pattern in patterns {
+            if managers.is_empty() {
+                ignored.push(IgnoreRule { pattern, ecosystem: None, versions: versions.clone() });
+            } else {
+                for manager in &managers {
+                    ignored.push(IgnoreRule { pattern: pattern.clone(), ecosystem: Some(renovate_ecosystem(manager)), versions: versions.clone() });
+                }
+            }
+        }
+    }
+
+    let schedule = value.get("schedule")
+        .and_then(|s| s.as_array().and_then(|a| a.first()).or(Some(s)))
+        .and_then(|s| s.as_str())
+        .map(String::from);
+
+    UpdateBotConfig {
+        bot: UpdateBot::Renovate,
+        file_path,
+        enabled: value.get("enabled").and_then(|e| e.as_bool()) != Some(false),
+        ecosystems: if ecosystems.is_empty() { vec!["*".to_string()] } else { ecosystems },
+        directories: Vec::new(),
+        schedule,
+        ignored,
+    }
+}
+
+/// Good enough for Renovate configs: drop full-line comments and trailing commas
+fn parse_json5(content: &str) -> Option<serde_json::Value> {
+    serde_json::from_str(content).ok().or_else(|| {
+        let stripped = LINE_COMMENT.replace_all(content, "");
+        serde_json::from_str(&TRAILING_COMMA.replace_all(&stripped, "$1")).ok()
+    })
+}
+
+/// Dependabot's `package-ecosystem` name for a manifest's package manager
+fn ecosystem_of(package_manager: &PackageManager) -> Option<&'static str> {
+    match package_manager {
+        PackageManager::Npm => Some("npm"),
+        PackageManager::Pip => Some("pip"),
+        PackageManager::Cargo => Some("cargo"),
+        PackageManager::Maven => Some("maven"),
+        PackageManager::Gradle => Some("gradle"),
+        PackageManager::Go => Some("gomod"),
+        PackageManager::Composer => Some("composer"),
+        PackageManager::NuGet => Some("nuget"),
+        PackageManager::SwiftPackageManager => Some("swift"),
+        PackageManager::CocoaPods => Some("cocoapods"),
+        PackageManager::WebMethodsIS | PackageManager::Unity | PackageManager::Unreal => None,
+    }
+}
+
+/// Renovate manager names mapped onto Dependabot's ecosystem names
+fn renovate_ecosystem(manager: &str) -> String {
+    match manager {
+        m if m.starts_with("pip") || m == "poetry" || m == "pep621" || m == "setup-cfg" => "pip".to_string(),
+        m if m.starts_with("gradle") => "gradle".to_string(),
+        "bun" => "npm".to_string(),
+        m => m.to_string(),
+    }
+}
+
+fn directory_matches(pattern: &str, directory: &str) -> bool {
+    let pattern = pattern.trim_matches('/');
+    match pattern.find('*') {
+        Some(star) => directory.starts_with(pattern[..star].trim_end_matches('/')),
+        None => pattern == directory,
+    }
+}
+
+fn name_matches(pattern: &str, name: &str) -> bool {
+    if pattern.len() > 2 && pattern.starts_with('/') && pattern.ends_with('/') {
+        return Regex::new(&pattern[1..pattern.len() - 1]).map(|re| re.is_match(name)).unwrap_or(false);
+    }
+    if pattern.contains('*') {
+        let glob = format!("^{}$", regex::escape(pattern).replace(r"\*", ".*"));
+        return Regex::new(&glob).map(|re| re.is_match(name)).unwrap_or(false);
+    }
+    pattern == name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::dependencies::PackageDependency;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_dependabot_and_renovate_ignores() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".github")).unwrap();
+        fs::write(
+            temp_dir.path().join(".github/dependabot.yml"),
+            "version: 2\nupdates:\n  - package-ecosystem: npm\n    directory: \"/web\"\n    schedule:\n      interval: weekly\n    ignore:\n      - dependency-name: \"@types/*\"\n",
+        ).unwrap();
+        fs::write(
+            temp_dir.path().join("renovate.json5"),
+            "{\n  // Keep the ORM where it is\n  \"enabledManagers\": [\"pip_requirements\"],\n  \"packageRules\": [{\"matchPackageNames\": [\"sqlalchemy\"], \"enabled\": false},],\n}\n",
+        ).unwrap();
+
+        let automation = UpdateBotDetector::new().detect_automation(temp_dir.path());
+        assert_eq!(automation.configs.len(), 2);
+        assert_eq!(automation.configs[0].schedule.as_deref(), Some("weekly"));
+        assert!(automation.is_configured());
+
+        let manifest = |pm: PackageManager, path: &str| DependencyManifest { package_manager: pm, dependencies: Vec::<PackageDependency>::new(), file_path: path.to_string() };
+        assert!(automation.covers(&manifest(PackageManager::Npm, "web/package.json")));
+        assert!(!automation.covers(&manifest(PackageManager::Npm, "package.json")));
+        assert!(automation.covers(&manifest(PackageManager::Pip, "requirements.txt")));
+        assert!(!automation.covers(&manifest(PackageManager::Cargo, "Cargo.toml")));
+
+        let (config, _) = automation.ignored_by("@types/node", &PackageManager::Npm).unwrap();
+        assert_eq!(config.bot, UpdateBot::Dependabot);
+        assert_eq!(automation.ignored_by("sqlalchemy", &PackageManager::Pip).unwrap().0.bot, UpdateBot::Renovate);
+        assert!(automation.ignored_by("react", &PackageManager::Npm).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use regex::Regex;
 use std::path::Path;
 
 /// Detect programming language from file extension
@@ -16,6 +17,7 @@ pub fn detect_language(path: &Path) -> Option<String> {
                 "swift" => Some("swift".to_string()),
                 "m" | "mm" => Some("objective-c".to_string()),
                 "java" => Some("java".to_string()),
+                "cls" | "trigger" => Some("apex".to_string()),
                 "h" => {
                     // Header files could be C, C++, or Objective-C
                     // Check if it's likely Objective-C by looking at the directory structure
@@ -127,3 +129,103 @@ pub fn should_skip_file(file_name: &str, path_str: &str) -> bool {
     file_name.ends_with(".rlib")
 }
 
+
+/// Resolve the local imports of a source file against a list of known repository files
+///
+/// Understands JS/TS relative imports and requires, Python `from x import` (absolute and relative)
+/// and Java/Kotlin imports. Returns `(line_number, target_file)` pairs; unresolvable imports such as
+/// third-party packages are dropped.
+pub fn resolve_local_imports<'a>(content: &str, file_path: &str, files: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let relative = regex::Regex::new(r#"(?:from\s+|require\(\s*|import\s+)["'](\.{1,2}/[^"']+)["']"#).unwrap();
+    let python = regex::Regex::new(r"^\s*from\s+(\.*)([\w.]*)\s+import\b").unwrap();
+    let java = regex::Regex::new(r"^\s*import\s+(?:static\s+)?([\w.]+)\s*;?\s*$").unwrap();
+    let base_dir: Vec<&str> = Path::new(file_path).parent()
+        .and_then(|p| p.to_str())
+        .map(|p| p.split('/').filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let mut targets = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let mut suffixes: Vec<String> = Vec::new();
+        for cap in relative.captures_iter(line) {
+            let mut parts = base_dir.clone();
+            for segment in cap[1].split('/') {
+                match segment {
+                    "." | "" => {}
+                    ".." => { parts.pop(); }
+                    other => parts.push(other),
+                }
+            }
+            suffixes.push(parts.join("/"));
+        }
+        if let Some(cap) = python.captures(line) {
+            let module = cap[2].replace('.', "/");
+            if cap[1].is_empty() {
+                if module.contains('/') {
+                    suffixes.push(module);
+                }
+            } else {
+                // One leading dot is the current package, each extra dot goes up a level
+                let mut parts = base_dir.clone();
+                for _ in 1..cap[1].len() {
+                    parts.pop();
+                }
+                let mut prefix = parts.join("/");
+                if !module.is_empty() {
+                    if !prefix.is_empty() {
+                        prefix.push('/');
+                    }
+                    prefix.push_str(&module);
+                }
+                suffixes.push(prefix);
+            }
+        } else if let Some(cap) = java.captures(line) {
+            if cap[1].contains('.') {
+                suffixes.push(cap[1].replace('.', "/"));
+            }
+        }
+
+        for suffix in suffixes.iter().filter(|s| !s.is_empty()) {
+            let found = files.iter().find(|candidate| {
+                let stem = candidate.rsplit_once('.').map(|(s, _)| s).unwrap_or(candidate);
+                **candidate != file_path
+                    && (stem == suffix
+                        || *candidate == suffix
+                        || stem.ends_with(&format!("/{}", suffix))
+                        || stem == format!("{}/index", suffix)
+                        || stem == format!("{}/__init__", suffix))
+            });
+            if let Some(target) = found {
+                targets.push((idx + 1, *target));
+            }
+        }
+    }
+    targets
+}
+
+/// `**` spans directories, `*` and `?` stay within one path segment; a bare directory matches everything below it
+pub fn glob_to_regex(glob: &str) -> Regex {
+    let mut pattern = String::from("^");
+    let mut chars = glob.trim_start_matches("./").chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            other => pattern.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    if !glob.contains('*') && !glob.contains('?') {
+        pattern.push_str("(?:/.*)?");
+    }
+    pattern.push('$');
+    Regex::new(&pattern).unwrap_or_else(|_| Regex::new("$^").unwrap())
+}
@@ -0,0 +1,113 @@
+//! The embedding entry point: run the full analysis pipeline from another Rust program, without
+//! starting the server or an actix runtime.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::analysis::{AnalysisProfile, CodeRelationship};
+use crate::config::Config;
+use crate::ingestion::RepositoryCrawler;
+use crate::security::{SecurityEntity, SecurityVulnerability};
+use crate::storage::{
+    Repository, StoredDependency, StoredEndpoint, StoredService,
+    StoredServiceBoundary, StoredTechDebtScore, DEFAULT_WORKSPACE,
+};
+
+pub mod pipeline;
+pub mod progress;
+mod state;
+
+pub use pipeline::AnalysisCounts;
+pub use state::AppState;
+use pipeline::perform_analysis;
+
+/// Runs analyses with one configuration. Results are stored in `config.database` like the
+/// server's, so a later `serve` shows them; point it at a scratch file to keep them apart.
+pub struct Analyzer {
+    config: Config,
+    profile: Option<AnalysisProfile>,
+}
+
+/// Everything one analysis found, as plain serializable data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub repository: Repository,
+    pub profile: AnalysisProfile,
+    pub summary: AnalysisCounts,
+    pub dependencies: Vec<StoredDependency>,
+    pub services: Vec<StoredService>,
+    pub endpoints: Vec<StoredEndpoint>,
+    pub security_entities: Vec<SecurityEntity>,
+    pub vulnerabilities: Vec<SecurityVulnerability>,
+    pub service_boundaries: Vec<StoredServiceBoundary>,
+    pub code_relationships: Vec<CodeRelationship>,
+    pub tech_debt: Option<StoredTechDebtScore>,
+}
+
+impl Analyzer {
+    pub fn new(config: Config) -> Self {
+        Analyzer { config, profile: None }
+    }
+
+    /// Overrides the configured default profile
+    pub fn with_profile(mut self, profile: AnalysisProfile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Analyzes a local checkout; re-analyzing the same path updates its stored results
+    pub fn analyze(&self, path: impl AsRef<Path>) -> Result<AnalysisReport> {
+        let path = path.as_ref().canonicalize()
+            .map_err(|e| anyhow!("{}: {}", path.as_ref().display(), e))?;
+        crate::network::configure(&self.config.network)?;
+        let state = AppState::open(&self.config)?;
+        let repository = find_or_create(&state, &path.to_string_lossy(), "main", true)?;
+
+        state.progress_tracker.start_analysis(&repository.id, 13);
+        let result = perform_analysis(&state, &repository.id, self.profile)?;
+        state.progress_tracker.clear_progress(&repository.id);
+        let repository = state.repo_repo.find_by_id(&repository.id)?.unwrap_or(repository);
+        AnalysisReport::collect(&state, repository, result.profile, result.results)
+    }
+}
+
+impl AnalysisReport {
+    /// Reads a stored analysis back
+    pub(crate) fn collect(state: &AppState, repository: Repository, profile: AnalysisProfile, summary: AnalysisCounts) -> Result<Self> {
+        let id = repository.id.clone();
+        Ok(AnalysisReport {
+            dependencies: state.dep_repo.get_by_repository(&id)?,
+            services: state.service_repo.get_by_repository(&id)?,
+            endpoints: state.endpoint_repo.get_by_repository(&id)?,
+            security_entities: state.security_repo.get_entities(&id)?,
+            vulnerabilities: state.security_repo.get_vulnerabilities(&id)?,
+            service_boundaries: state.boundary_repo.get_by_repository(&id)?,
+            code_relationships: state.code_relationship_repo.get_by_repository(&id)?,
+            tech_debt: state.tech_debt_repo.get_latest(&id)?,
+            repository,
+            profile,
+            summary,
+        })
+    }
+}
+
+/// The repository stored for `target` on `branch`, registered in the default workspace when
+/// `create` is set
+pub(crate) fn find_or_create(state: &AppState, target: &str, branch: &str, create: bool) -> Result<Repository> {
+    // Local checkouts are stored by absolute path so later runs from elsewhere find them
+    let url = match Path::new(target).canonicalize() {
+        Ok(path) if RepositoryCrawler::is_local_path(target) => path.to_string_lossy().to_string(),
+        _ => target.to_string(),
+    };
+    let existing = state.repo_repo.list_all()?
+        .into_iter()
+        .find(|r| r.url == url && r.branch == branch);
+    match existing {
+        Some(repository) => Ok(repository),
+        None if create => {
+            let name = url.trim_end_matches('/').trim_end_matches(".git").rsplit(['/', '\\']).next().unwrap_or("repository").to_string();
+            Ok(state.repo_repo.create(&name, &url, Some(branch), None, None, DEFAULT_WORKSPACE)?)
+        }
+        None => Err(anyhow!("{} ({} branch) has not been analyzed", url, branch)),
+    }
+}
@@ -0,0 +1,1478 @@
+//! The analysis pipeline: clone or open a repository, run every detector over it and store the
+//! results. The server, the CLI and [`Analyzer`](crate::Analyzer) all run analyses through
+//! [`perform_analysis`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use crate::analyzer::AppState;
+use crate::ingestion::{RepositoryCrawler, RepositoryCredentials, RepositoryFingerprint, RepositoryLink};
+use crate::analysis::{DependencyExtractor, ToolDetector, BuildGraphExtractor, DataPipelineDetector, TestDetector, KubernetesDetector, GatewayDetector, FrameworkDetector, EntrypointDetector, BackgroundJobDetector, NotificationDetector, PaymentDetector, PiiDetector, ObservabilityDetector, CostEstimator, EnvironmentMatrixBuilder, ArtifactInventoryScanner, PinningAuditor, LanguageStatsCalculator, FrontendRouteDetector, CodeOrigin, CoverageParser, TestMapper, GraphQLClientDetector, ServiceBoundaryInferrer, DataStoreUse, DecompositionAdvisor, EndpointUse, LayeringAnalyzer, LayeringAnalysis, RepoConfig, AnalysisProfile, ArchitectureRuleSet, ArchitectureRuleEngine, TechDebtCalculator, TodoScanner, AdrExtractor, DecisionComponent, DocQualityScorer, DocLinkExtractor, DocDriftDetector, ChangelogParser, ArchitectureSnapshot, ReleaseSource, Detection, FalsePositiveFilter};
+use crate::security::ServiceDetector;
+use crate::security::analyzer::SecurityAnalyzer;
+use crate::graph::GraphBuilder;
+use crate::analysis::CodeAnalyzer;
+use crate::config::StorageConfig;
+use crate::analysis::{ConformanceDelta, ConformanceInput, TargetArchitecture};
+use crate::storage::Repository;
+
+#[derive(Serialize)]
+pub(crate) struct AnalysisResult {
+    pub(crate) message: String,
+    pub(crate) profile: AnalysisProfile,
+    pub(crate) repository: serde_json::Value,
+    pub(crate) results: AnalysisCounts,
+}
+
+/// How much one analysis found
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalysisCounts {
+    pub manifests_found: usize,
+    pub total_dependencies: usize,
+    pub services_found: usize,
+    pub graph_built: bool,
+    pub code_elements_found: usize,
+    /// Generated, vendored or minified code elements
+    pub non_authored_code_elements: usize,
+    pub code_calls_found: usize,
+    pub security_entities_found: usize,
+    pub security_relationships_found: usize,
+    pub security_vulnerabilities_found: usize,
+    pub tests_found: usize,
+    pub documentation_indexed: bool,
+}
+
+/// The checked-out commit plus profile, or `None` when the working tree has uncommitted changes
+/// (or is not a git repository) and saved step outputs can't be trusted to match it
+fn checkpoint_revision(repo_path: &std::path::Path, profile: AnalysisProfile) -> Option<String> {
+    let repo = git2::Repository::open(repo_path).ok()?;
+    let head = repo.head().ok()?.peel_to_commit().ok()?.id();
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+    if !repo.statuses(Some(&mut options)).ok()?.is_empty() {
+        return None;
+    }
+    Some(format!("{}:{}", head, profile.as_str()))
+}
+
+/// Perform the actual analysis work (runs in blocking thread pool)
+pub(crate) fn perform_analysis(
+    state: &AppState,
+    repository_id: &str,
+    requested_profile: Option<AnalysisProfile>,
+) -> Result<AnalysisResult, anyhow::Error> {
+    
+    // API key validation removed for local tool simplicity
+    // Get repository
+    state.progress_tracker.update_progress(&repository_id, 1, "Fetching repository information", "Loading repository details...", None);
+    log::info!("Step 1/11: Fetching repository information...");
+    let repo = match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(repo)) => {
+            log::info!("Found repository: {} ({})", repo.name, repo.url);
+            repo
+        },
+        Ok(None) => {
+            log::error!("Repository not found: {}", repository_id);
+            state.progress_tracker.fail_analysis(&repository_id, "Repository not found");
+            return Err(anyhow::anyhow!("Repository not found"));
+        }
+        Err(e) => {
+            log::error!("Database error fetching repository: {}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Database error: {}", e));
+            return Err(anyhow::anyhow!("Database error: {}", e));
+        }
+    };
+
+    // Clone/update repository
+    state.progress_tracker.update_progress(&repository_id, 2, "Initializing crawler", "Setting up repository crawler...", None);
+    log::info!("Step 2/11: Initializing repository crawler...");
+    let storage_config = StorageConfig {
+        repository_cache_path: "./cache/repos".to_string(),
+        max_cache_size: "10GB".to_string(),
+    };
+    
+    let crawler = match RepositoryCrawler::new(&storage_config) {
+        Ok(c) => {
+            log::info!("Crawler initialized successfully");
+            c
+        },
+        Err(e) => {
+            log::error!("Failed to initialize crawler: {}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Failed to initialize crawler: {}", e));
+            return Err(anyhow::anyhow!("Failed to initialize crawler: {}", e));
+        }
+    };
+
+    state.progress_tracker.update_progress(&repository_id, 3, "Preparing repository", 
+        if crate::ingestion::crawler::RepositoryCrawler::is_local_path(&repo.url) {
+            format!("Using local repository at {}...", repo.url)
+        } else {
+            format!("Fetching repository from {}...", repo.url)
+        }.as_str(), 
+        Some(serde_json::json!({"url": repo.url, "branch": repo.branch, "is_local": crate::ingestion::crawler::RepositoryCrawler::is_local_path(&repo.url)})));
+    log::info!("Step 3/11: Preparing repository from {} (branch: {})...", repo.url, repo.branch);
+    // Credentials stored as secret references (env:, file:, vault:, aws-sm:) are looked up now
+    let auth_value = repo.auth_value.as_deref()
+        .map(|value| crate::ingestion::secrets::resolve_allowed(value, &state.config.secrets, &repo.workspace_id))
+        .transpose();
+    let auth_value = match auth_value {
+        Ok(auth_value) => auth_value,
+        Err(e) => {
+            log::error!("✗ Failed to resolve repository credentials: {:#}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Failed to resolve repository credentials: {:#}", e));
+            return Err(anyhow::anyhow!("Failed to resolve repository credentials: {:#}", e));
+        }
+    };
+    let credentials = repo.auth_type.as_deref().zip(auth_value.as_deref())
+        .map(|(auth_type, auth_value)| RepositoryCredentials::from_stored(auth_type, auth_value));
+    
+    let repo_path = match crawler.clone_or_update(
+        &repo.url,
+        Some(&repo.branch),
+        credentials.as_ref(),
+    ) {
+        Ok(path) => {
+            log::info!("✓ Repository cloned/updated successfully to: {}", path.display());
+            path
+        },
+        Err(e) => {
+            log::error!("✗ Failed to clone repository: {}", e);
+            return Err(anyhow::anyhow!("Failed to clone repository: {}", e));
+        }
+    };
+
+    // Commit identity is known now, so forks and mirrors registered by URL alone get linked
+    match link_related_repositories(state, &repo, Some(&repo_path)) {
+        Ok(links) if !links.is_empty() => log::info!("✓ Linked {} fork(s)/mirror(s)", links.len()),
+        Ok(_) => {}
+        Err(e) => log::warn!("⚠ Failed to look for forks and mirrors: {}", e),
+    }
+
+    // The repository's own .wavelength.yml narrows what is analyzed and reported, on top of the
+    // server-wide [analysis] settings
+    let mut repo_config = match RepoConfig::load(&repo_path) {
+        Ok(Some((config_file, config))) => {
+            log::info!("✓ Using repository configuration from {}", config_file);
+            config
+        }
+        Ok(None) => RepoConfig::default_for(&repo_path),
+        Err(e) => {
+            log::warn!("⚠ Ignoring repository configuration: {:#}", e);
+            RepoConfig::default_for(&repo_path)
+        }
+    };
+    repo_config.inherit(&state.analysis_config);
+    let profile = AnalysisProfile::resolve(requested_profile, repo.analysis_profile, state.analysis_config.profile);
+    let profile_settings = profile.settings();
+    log::info!("✓ Using the {} analysis profile", profile.as_str());
+    repo_config.apply_profile(&profile_settings);
+    // Outputs of slow steps are checkpointed under this revision, so an analysis a crash or
+    // restart cut short resumes from them as long as the checkout and profile are unchanged
+    let revision = checkpoint_revision(&repo_path, profile);
+
+    // Extract dependencies
+    state.progress_tracker.update_progress(&repository_id, 4, "Extracting dependencies", "Scanning package.json, requirements.txt, Cargo.toml, and other manifest files...", None);
+    log::info!("Step 4/11: Extracting dependencies from repository...");
+    let extractor = DependencyExtractor::new();
+    let manifests = match state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "dependencies", || extractor.extract_from_repository(&repo_path)) {
+        Ok(m) => {
+            let total_deps: usize = m.iter().map(|manifest| manifest.dependencies.len()).sum();
+            log::info!("✓ Found {} manifest files with {} total dependencies", m.len(), total_deps);
+            m
+        },
+        Err(e) => {
+            log::error!("✗ Failed to extract dependencies: {}", e);
+            return Err(anyhow::anyhow!("Failed to extract dependencies: {}", e));
+        }
+    };
+
+    // Store dependencies
+    let total_deps_to_store: usize = manifests.iter().map(|m| m.dependencies.len()).sum();
+    log::info!("Storing {} dependencies from {} manifest file(s) in database...", total_deps_to_store, manifests.len());
+    let mut stored_deps = 0;
+    let mut manifest_count = 0;
+    for manifest in &manifests {
+        manifest_count += 1;
+        log::info!("  Processing manifest {}/{}: {} ({} dependencies)", manifest_count, manifests.len(), manifest.file_path, manifest.dependencies.len());
+        if let Err(e) = state.dep_repo.store_dependencies(
+            &repo.id,
+            &manifest.dependencies,
+            &manifest.file_path,
+        ) {
+            log::error!("✗ Failed to store dependencies from {}: {}", manifest.file_path, e);
+            return Err(anyhow::anyhow!("Failed to store dependencies from {}: {}", manifest.file_path, e));
+        }
+        stored_deps += manifest.dependencies.len();
+    }
+    log::info!("✓ Successfully stored {} dependencies from {} manifest file(s)", stored_deps, manifests.len());
+
+    // Compute language statistics (part of step 4)
+    match LanguageStatsCalculator::new().calculate(&repo_path) {
+        Ok(stats) => {
+            if let Some(primary) = stats.first() {
+                log::info!("✓ Language breakdown: {} languages, primary {} ({:.1}%)", stats.len(), primary.language, primary.percentage);
+            }
+            if let Err(e) = state.language_stats_repo.store_stats(&repo.id, &stats) {
+                log::warn!("⚠ Failed to store language statistics: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to compute language statistics: {}", e);
+        }
+    }
+
+    // Detect frameworks and runtimes (part of step 4, used to prioritize endpoint detection)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting web frameworks and runtimes...");
+    let framework_detector = FrameworkDetector::new();
+    let frameworks = match framework_detector.detect_frameworks(&repo_path, &manifests) {
+        Ok(f) => {
+            if !f.is_empty() {
+                let names: Vec<String> = f.iter()
+                    .map(|fw| match &fw.version {
+                        Some(v) => format!("{} {}", fw.name, v),
+                        None => fw.name.clone(),
+                    })
+                    .collect();
+                log::info!("✓ Detected {} framework(s)/runtime(s): {}", f.len(), names.join(", "));
+            } else {
+                log::info!("✓ No frameworks detected");
+            }
+            f
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect frameworks: {}", e);
+            Vec::new()
+        }
+    };
+    if let Err(e) = state.framework_repo.store_frameworks(&repo.id, &frameworks) {
+        log::warn!("⚠ Failed to store frameworks: {}", e);
+    }
+    let framework_names: Vec<String> = frameworks.iter().map(|f| f.name.clone()).collect();
+
+    // Detect services
+    state.progress_tracker.update_progress(&repository_id, 5, "Detecting external services", "Scanning for AWS, Firebase, Clerk, AI services, and other integrations...", None);
+    log::info!("Step 5/11: Detecting external services...");
+    // Plugins the repository has opted into (or not out of), from the hot-reloaded registry
+    let plugin_selection = state.plugin_selection_repo.get_selection(&repo.id).unwrap_or_else(|e| {
+        log::warn!("⚠ Failed to load plugin selection, using plugin defaults: {}", e);
+        crate::plugins::PluginSelection::default()
+    });
+    let plugin_registry = state.plugin_registry.read().unwrap().for_repository(&plugin_selection);
+    let detector = Arc::new(match ServiceDetector::with_registry(&plugin_registry) {
+        Ok(d) => {
+            log::info!("✓ Loaded service detection patterns with plugins");
+            d
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to load plugins, using default patterns: {}", e);
+            ServiceDetector::new()
+        }
+    });
+    // Services and endpoints come from the built-in detectors, run through the analyzer interface
+    let builtin = crate::plugins::builtin_analyzers(detector.clone());
+    let mut services = match state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "services", || {
+        builtin.run("services", &repo_path).map(|output| output.detected_services)
+    }) {
+        Ok(s) => {
+            if !s.is_empty() {
+                let service_names: Vec<String> = s.iter().map(|svc| format!("{} ({:?})", svc.name, svc.provider)).collect();
+                log::info!("✓ Detected {} service(s): {}", s.len(), service_names.join(", "));
+            } else {
+                log::info!("✓ No external services detected");
+            }
+            s
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect services: {}", e);
+            return Err(anyhow::anyhow!("Failed to detect services: {}", e));
+        }
+    };
+
+    // Analyzer plugins run once here: their services are stored with the detected ones, while
+    // their elements, relationships and findings are merged in at the code and rules steps
+    let mut plugin_output = crate::plugins::run_native_analyzers(&plugin_registry, &repo_path);
+    plugin_output.merge(crate::plugins::run_wasm_analyzers(&plugin_registry, &repo_path));
+    plugin_output.merge(crate::plugins::run_subprocess_plugins(&plugin_registry, &repo_path, &mut |name, progress| {
+        let message = match progress.percent {
+            Some(percent) => format!("Plugin {}: {} ({:.0}%)", name, progress.message, percent),
+            None => format!("Plugin {}: {}", name, progress.message),
+        };
+        state.progress_tracker.update_status_message(&repository_id, &message);
+    }));
+    services.extend(plugin_output.services.iter().map(|s| s.to_detected(&detector, &repo_path)));
+    services.extend(plugin_output.detected_services.iter().cloned());
+    services = ServiceDetector::aggregate(&repo_path, services);
+    repo_config.retain_files(&mut services, |s| &s.file_path);
+    // Detections marked as false positives after earlier analyses of the repository
+    let false_positives = match state.false_positive_repo.get_by_repository(&repo.id) {
+        Ok(verdicts) => FalsePositiveFilter::new(&verdicts),
+        Err(e) => {
+            log::warn!("⚠ Failed to load false-positive verdicts: {}", e);
+            FalsePositiveFilter::default()
+        }
+    };
+    if !false_positives.is_empty() {
+        services.retain(|s| !false_positives.excludes(&Detection::service(
+            &state.service_repo.provider_to_string(&s.provider), &s.name, &s.file_path, s.confidence,
+        )));
+    }
+    let low_confidence = repo_config.apply_service_threshold(&mut services);
+    if low_confidence > 0 {
+        log::info!("  {} service(s) below the confidence threshold ({:?})", low_confidence, repo_config.thresholds.below.unwrap_or_default());
+    }
+
+    // Store services
+    log::info!("Storing {} service(s) in database...", services.len());
+    if let Err(e) = state.service_repo.store_services(&repo.id, &services) {
+        log::error!("✗ Failed to store services: {}", e);
+        return Err(anyhow::anyhow!("Failed to store services: {}", e));
+    }
+    log::info!("✓ Successfully stored {} service(s)", services.len());
+
+    // Detect developer tools and scripts
+    state.progress_tracker.update_progress(&repository_id, 6, "Detecting developer tools", "Scanning for build tools, test frameworks, linters, and scripts...", None);
+    log::info!("Step 6/11: Detecting developer tools...");
+    let tool_detector = ToolDetector::new();
+    let tools = match tool_detector.detect_tools(&repo_path) {
+        Ok(t) => {
+            if !t.is_empty() {
+                let tool_names: Vec<String> = t.iter().map(|tool| format!("{} ({:?})", tool.name, tool.category)).collect();
+                log::info!("✓ Detected {} tool(s): {}", t.len(), tool_names.join(", "));
+            } else {
+                log::info!("✓ No developer tools detected");
+            }
+            t
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect tools: {}", e);
+            return Err(anyhow::anyhow!("Failed to detect tools: {}", e));
+        }
+    };
+
+    // Store tools
+    log::info!("Storing {} tool(s) in database...", tools.len());
+    if let Err(e) = state.tool_repo.store_tools(&repo.id, &tools) {
+        log::error!("✗ Failed to store tools: {}", e);
+        return Err(anyhow::anyhow!("Failed to store tools: {}", e));
+    }
+    log::info!("✓ Successfully stored {} tool(s)", tools.len());
+
+    // Detect Kubernetes workloads (part of step 6, must run before the graph is built)
+    state.progress_tracker.update_status_message(&repository_id, "Extracting Kubernetes workload topology...");
+    log::info!("Detecting Kubernetes resources...");
+    let kubernetes_detector = KubernetesDetector::new();
+    match repo_config.run("kubernetes", || kubernetes_detector.detect_resources(&repo_path)) {
+        Ok(resources) => {
+            if !resources.is_empty() {
+                let workloads = resources.iter().filter(|r| r.is_workload()).count();
+                log::info!("✓ Detected {} Kubernetes resource(s) ({} workload(s))", resources.len(), workloads);
+            } else {
+                log::info!("✓ No Kubernetes resources detected");
+            }
+            if let Err(e) = state.kubernetes_repo.store_resources(&repo.id, &resources) {
+                log::warn!("⚠ Failed to store Kubernetes resources: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect Kubernetes resources: {}", e);
+        }
+    }
+
+    // Detect API gateway routes (part of step 6, must run before the graph is built)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting API gateway routes...");
+    log::info!("Detecting API gateway routes...");
+    let gateway_detector = GatewayDetector::new();
+    match repo_config.run("gateways", || gateway_detector.detect_routes(&repo_path)) {
+        Ok(routes) => {
+            log::info!("✓ Detected {} API gateway route(s)", routes.len());
+            if let Err(e) = state.gateway_repo.store_routes(&repo.id, &routes) {
+                log::warn!("⚠ Failed to store API gateway routes: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect API gateway routes: {}", e);
+        }
+    }
+
+    // Model CI/CD pipelines (part of step 6, jobs link to the tools detected above)
+    state.progress_tracker.update_status_message(&repository_id, "Modeling CI/CD pipelines...");
+    log::info!("Parsing CI/CD pipelines...");
+    let pipelines = match repo_config.run("pipelines", || crate::parsers::FileParser.detect_pipelines(&repo_path)) {
+        Ok(pipelines) => {
+            let job_count: usize = pipelines.iter().map(|p| p.jobs.len()).sum();
+            log::info!("✓ Parsed {} pipeline(s) with {} job(s)", pipelines.len(), job_count);
+            if let Err(e) = state.pipeline_repo.store_pipelines(&repo.id, &pipelines) {
+                log::warn!("⚠ Failed to store CI/CD pipelines: {}", e);
+            }
+            pipelines
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to parse CI/CD pipelines: {}", e);
+            Vec::new()
+        }
+    };
+
+    // Correlate .env files, Helm values and CI variables per environment (part of step 6, uses the pipelines above)
+    state.progress_tracker.update_status_message(&repository_id, "Building environment matrix...");
+    let mut environment_matrix = match repo_config.run("environments", || EnvironmentMatrixBuilder::new().build(&repo_path, &pipelines)) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("⚠ Failed to build environment matrix: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut environment_matrix.values, |v| &v.file_path);
+    log::info!("✓ Built environment matrix for {} environment(s): {} setting(s) differ",
+        environment_matrix.environments.len(), environment_matrix.differing_keys().len());
+    if let Err(e) = state.environment_repo.store_values(&repo.id, &environment_matrix.values) {
+        log::warn!("⚠ Failed to store environment matrix: {}", e);
+    }
+
+    // Link task runner targets to each other, their tools and the CI jobs that run them (part of step 6)
+    state.progress_tracker.update_status_message(&repository_id, "Extracting build target graph...");
+    match repo_config.run("build_graph", || BuildGraphExtractor::new().extract(&repo_path, &tools, &pipelines)) {
+        Ok(mut targets) => {
+            repo_config.retain_files(&mut targets, |t| &t.file_path);
+            let unused = targets.iter().filter(|t| t.unused).count();
+            log::info!("✓ Extracted {} build target(s), {} not run by CI or other targets", targets.len(), unused);
+            if let Err(e) = state.build_target_repo.store_targets(&repo.id, &targets) {
+                log::warn!("⚠ Failed to store build targets: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to extract build target graph: {}", e);
+        }
+    }
+
+    // Model Airflow/dbt/Dagster/Prefect pipelines, their task order and datasets (part of step 6)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting data pipelines...");
+    match repo_config.run("data_pipelines", || DataPipelineDetector::new().detect(&repo_path)) {
+        Ok(mut data_pipelines) => {
+            repo_config.retain_files(&mut data_pipelines, |p| &p.file_path);
+            let task_count: usize = data_pipelines.iter().map(|p| p.tasks.len()).sum();
+            log::info!("✓ Found {} data pipeline(s) with {} task(s)", data_pipelines.len(), task_count);
+            if let Err(e) = state.data_pipeline_repo.store_pipelines(&repo.id, &data_pipelines) {
+                log::warn!("⚠ Failed to store data pipelines: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect data pipelines: {}", e);
+        }
+    }
+
+    // Detect application entrypoints (part of step 6, they become graph roots)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting application entrypoints...");
+    let entrypoint_detector = EntrypointDetector::new();
+    match repo_config.run("entrypoints", || entrypoint_detector.detect_entrypoints(&repo_path)) {
+        Ok(entrypoints) => {
+            log::info!("✓ Detected {} entrypoint(s)", entrypoints.len());
+            if let Err(e) = state.entrypoint_repo.store_entrypoints(&repo.id, &entrypoints) {
+                log::warn!("⚠ Failed to store entrypoints: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect entrypoints: {}", e);
+        }
+    }
+
+    // Detect background jobs and scheduled tasks (part of step 6, they link to their handlers in the graph)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting background jobs...");
+    match repo_config.run("background_jobs", || BackgroundJobDetector::new().detect_jobs(&repo_path)) {
+        Ok(jobs) => {
+            log::info!("✓ Detected {} background job(s)", jobs.len());
+            if let Err(e) = state.background_job_repo.store_jobs(&repo.id, &jobs) {
+                log::warn!("⚠ Failed to store background jobs: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect background jobs: {}", e);
+        }
+    }
+
+    // Extract email/SMS provider senders, templates and callbacks (part of step 6, they join services and endpoints in the graph)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting notification providers...");
+    match repo_config.run("notifications", || NotificationDetector::new().detect_configs(&repo_path)) {
+        Ok(configs) => {
+            log::info!("✓ Detected {} notification provider setting(s)", configs.len());
+            if let Err(e) = state.notification_repo.store_configs(&repo.id, &configs) {
+                log::warn!("⚠ Failed to store notification provider settings: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect notification providers: {}", e);
+        }
+    }
+
+    // Inventory client-side routes (part of step 6, routes link to their components in the graph)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting frontend routes...");
+    match repo_config.run("frontend_routes", || FrontendRouteDetector::new().detect_routes(&repo_path, &framework_names)) {
+        Ok(routes) => {
+            log::info!("✓ Detected {} frontend route(s)", routes.len());
+            if let Err(e) = state.frontend_route_repo.store_routes(&repo.id, &routes) {
+                log::warn!("⚠ Failed to store frontend routes: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect frontend routes: {}", e);
+        }
+    }
+
+    // Detect GraphQL operations in client code (part of step 6, they link to schema types in the graph)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting GraphQL client operations...");
+    match repo_config.run("graphql", || GraphQLClientDetector::new().detect(&repo_path)) {
+        Ok(usage) => {
+            log::info!("✓ Detected {} GraphQL operation(s) and {} schema type(s)", usage.operations.len(), usage.schema_types.len());
+            if let Err(e) = state.graphql_usage_repo.store_usage(&repo.id, &usage) {
+                log::warn!("⚠ Failed to store GraphQL usage: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to detect GraphQL operations: {}", e);
+        }
+    }
+
+    // Build and store knowledge graph
+    state.progress_tracker.update_progress(&repository_id, 7, "Building knowledge graph", "Creating relationships between repositories, dependencies, services, and code elements...", None);
+    log::info!("Step 7/11: Building knowledge graph...");
+    let graph_builder = GraphBuilder::new(
+        state.repo_repo.db.clone(),
+        state.repo_repo.clone(),
+        state.dep_repo.clone(),
+        state.service_repo.clone(),
+        state.tool_repo.clone(),
+        state.code_relationship_repo.clone(),
+        state.test_repo.clone(),
+        state.port_repo.clone(),
+        state.endpoint_repo.clone(),
+    );
+    
+    log::info!("Building knowledge graph from stored data (dependencies, services, code elements)...");
+    match graph_builder.build_for_repository(&repo.id) {
+        Ok(graph) => {
+            // Count node types for better diagnostics
+            use std::collections::HashMap;
+            let mut node_type_counts: HashMap<String, usize> = HashMap::new();
+            for node in &graph.nodes {
+                let node_type_str = format!("{:?}", node.node_type);
+                *node_type_counts.entry(node_type_str).or_insert(0) += 1;
+            }
+            let node_type_summary: Vec<String> = node_type_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            log::info!("✓ Knowledge graph built: {} nodes ({}), {} edges", 
+                graph.nodes.len(), node_type_summary.join(", "), graph.edges.len());
+            
+            log::info!("Storing knowledge graph in database...");
+            {
+                let progress_tracker_nodes = state.progress_tracker.clone();
+                let progress_tracker_edges = state.progress_tracker.clone();
+                let repository_id_clone = repository_id.clone();
+                let repository_id_clone2 = repository_id.clone();
+                if let Err(e) = graph_builder.store_graph(
+                    &repo.id, 
+                    &graph,
+                    Some(move |stored, total| {
+                        let percent = (stored as f64 / total as f64 * 100.0) as u32;
+                        progress_tracker_nodes.update_status_message(
+                            &repository_id_clone,
+                            &format!("Storing graph nodes: {}/{} ({}%)...", stored, total, percent)
+                        );
+                    }),
+                    Some(move |stored, total| {
+                        let percent = (stored as f64 / total as f64 * 100.0) as u32;
+                        progress_tracker_edges.update_status_message(
+                            &repository_id_clone2,
+                            &format!("Storing graph edges: {}/{} ({}%)...", stored, total, percent)
+                        );
+                    })
+                ) {
+                    log::error!("✗ Failed to store graph: {}", e);
+                    return Err(anyhow::anyhow!("Failed to store graph: {}", e));
+                }
+            }
+            log::info!("✓ Successfully stored knowledge graph");
+        }
+        Err(e) => {
+            log::error!("✗ Failed to build graph: {}", e);
+            return Err(anyhow::anyhow!("Failed to build graph: {}", e));
+        }
+    }
+
+    // Analyze code structure
+    state.progress_tracker.update_progress(&repository_id, 8, "Analyzing code structure", "Scanning source files and extracting functions, classes, modules, and their relationships...", None);
+    log::info!("Step 8/11: Analyzing code structure...");
+    log::info!("Scanning repository for source code files (this may take a while for large repositories)...");
+    let code_analyzer = CodeAnalyzer::with_settings(state.config.analyzers.settings("code_structure"));
+    let mut code_structure = match state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "code structure", || code_analyzer.analyze_repository(&repo_path)) {
+        Ok(structure) => {
+            // Count element types for better diagnostics
+            use std::collections::HashMap;
+            let mut element_type_counts: HashMap<String, usize> = HashMap::new();
+            let mut language_counts: HashMap<String, usize> = HashMap::new();
+            for element in &structure.elements {
+                *element_type_counts.entry(format!("{:?}", element.element_type)).or_insert(0) += 1;
+                *language_counts.entry(element.language.clone()).or_insert(0) += 1;
+            }
+            let element_summary: Vec<String> = element_type_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            let language_summary: Vec<String> = language_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            log::info!("✓ Code analysis complete: {} elements ({}), {} calls", 
+                structure.elements.len(), element_summary.join(", "), structure.calls.len());
+            if !language_summary.is_empty() {
+                log::info!("  Languages detected: {}", language_summary.join(", "));
+            }
+            structure
+        },
+        Err(e) => {
+            log::error!("✗ Failed to analyze code structure: {}", e);
+            return Err(anyhow::anyhow!("Failed to analyze code structure: {}", e));
+        }
+    };
+
+    // Vendored and generated elements are kept and stored tagged with their origin; only ignored files are dropped
+    let element_count = code_structure.elements.len();
+    code_structure.elements.retain(|e| !repo_config.is_ignored(&e.file_path));
+    if code_structure.elements.len() < element_count {
+        let kept: std::collections::HashSet<String> = code_structure.elements.iter().map(|e| e.id.clone()).collect();
+        code_structure.calls.retain(|c| kept.contains(&c.caller_id) && kept.contains(&c.callee_id));
+    }
+
+    // Merge plugin assets with code elements
+    let mut all_code_elements = code_structure.elements.clone();
+    all_code_elements.extend(plugin_output.elements.iter().cloned());
+    crate::analysis::symbols::assign_qualified_names(&mut all_code_elements);
+    let plugin_relationships = plugin_output.relationships.clone();
+    // Metrics only measure the code the configuration includes, authored code by default
+    let measured_elements: Vec<_> = all_code_elements.iter().filter(|e| repo_config.includes_origin(e.origin)).cloned().collect();
+    let non_authored_elements = all_code_elements.iter().filter(|e| e.origin != CodeOrigin::Authored).count();
+    if non_authored_elements > 0 {
+        log::info!("✓ Tagged {} vendored/generated code element(s)", non_authored_elements);
+    }
+    if !plugin_output.elements.is_empty() {
+        log::info!("✓ Merged {} plugin assets and {} plugin relationships", plugin_output.elements.len(), plugin_relationships.len());
+    }
+
+    // Store code elements and calls
+    log::info!("Storing {} code elements in database...", all_code_elements.len());
+    {
+        let progress_tracker = state.progress_tracker.clone();
+        let repository_id_clone = repository_id.clone();
+        if let Err(e) = state.code_repo.store_elements(
+            &repo.id, 
+            &all_code_elements,
+            Some(move |stored, total| {
+                let percent = (stored as f64 / total as f64 * 100.0) as u32;
+                progress_tracker.update_status_message(
+                    &repository_id_clone,
+                    &format!("Storing code elements: {}/{} ({}%)...", stored, total, percent)
+                );
+            })
+        ) {
+            log::error!("✗ Failed to store code elements: {}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Failed to store code elements: {}", e));
+            return Err(anyhow::anyhow!("Failed to store code elements: {}", e));
+        }
+    }
+    log::info!("✓ Stored {} code elements", all_code_elements.len());
+    
+    log::info!("Storing {} code calls in database...", code_structure.calls.len());
+
+    // Detect ports
+    state.progress_tracker.update_progress(&repository_id, 9, "Detecting ports", "Scanning for server ports, database ports, and network configuration...", None);
+    log::info!("Step 9/13: Detecting ports...");
+    let port_detector = crate::analysis::PortDetector::new();
+    let mut ports = match repo_config.run("ports", || port_detector.detect_ports(&repo_path)) {
+        Ok(p) => {
+            if !p.is_empty() {
+                let port_list: Vec<String> = p.iter().map(|port| format!("{} ({:?})", port.port, port.port_type)).collect();
+                log::info!("✓ Detected {} port(s): {}", p.len(), port_list.join(", "));
+            } else {
+                log::info!("✓ No ports detected");
+            }
+            p
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect ports: {}", e);
+            return Err(anyhow::anyhow!("Failed to detect ports: {}", e));
+        }
+    };
+
+    repo_config.retain_files(&mut ports, |p| &p.file_path);
+
+    // Store ports
+    log::info!("Storing {} port(s) in database...", ports.len());
+    if let Err(e) = state.port_repo.store_ports(&repo.id, &ports) {
+        log::error!("✗ Failed to store ports: {}", e);
+        return Err(anyhow::anyhow!("Failed to store ports: {}", e));
+    }
+    log::info!("✓ Successfully stored {} port(s)", ports.len());
+
+    // Detect endpoints
+    state.progress_tracker.update_progress(&repository_id, 10, "Detecting API endpoints", "Scanning for API routes, REST endpoints, and HTTP handlers...", None);
+    log::info!("Step 10/13: Detecting API endpoints...");
+    let mut endpoints = match repo_config.run("endpoints", || {
+        state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "endpoints", || {
+            builtin.run("endpoints", &repo_path).map(|mut output| {
+                output.endpoints.extend(plugin_output.endpoints.iter().cloned());
+                crate::analysis::EndpointDetector::prefer_frameworks(output.endpoints, &framework_names)
+            })
+        })
+    }) {
+        Ok(e) => {
+            if !e.is_empty() {
+                let endpoint_summary: Vec<String> = e.iter()
+                    .take(10)
+                    .map(|ep| format!("{:?} {}", ep.method, ep.path))
+                    .collect();
+                let more = if e.len() > 10 { format!(" and {} more", e.len() - 10) } else { String::new() };
+                log::info!("✓ Detected {} endpoint(s): {}{}", e.len(), endpoint_summary.join(", "), more);
+            } else {
+                log::info!("✓ No endpoints detected");
+            }
+            e
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect endpoints: {}", e);
+            return Err(anyhow::anyhow!("Failed to detect endpoints: {}", e));
+        }
+    };
+
+    repo_config.retain_files(&mut endpoints, |e| &e.file_path);
+
+    // Store endpoints
+    log::info!("Storing {} endpoint(s) in database...", endpoints.len());
+    if let Err(e) = state.endpoint_repo.store_endpoints(&repo.id, &endpoints) {
+        log::error!("✗ Failed to store endpoints: {}", e);
+        return Err(anyhow::anyhow!("Failed to store endpoints: {}", e));
+    }
+    log::info!("✓ Successfully stored {} endpoint(s)", endpoints.len());
+
+    // Payment webhooks, checkouts and price IDs, linked to the endpoints above (part of step 10, PCI findings join step 12)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting payment flows...");
+    let mut payments = match repo_config.run("payments", || PaymentDetector::new().detect_flows(&repo_path, &endpoints)) {
+        Ok(p) => p,
+        Err(e) => {
+            log::warn!("⚠ Failed to detect payment flows: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut payments.flows, |f| &f.file_path);
+    repo_config.retain_files(&mut payments.pci_findings, |f| &f.file_path);
+    log::info!("✓ Detected {} payment flow(s), {} PCI finding(s)", payments.flows.len(), payments.pci_findings.len());
+    if let Err(e) = state.payment_repo.store_flows(&repo.id, &payments.flows) {
+        log::warn!("⚠ Failed to store payment flows: {}", e);
+    }
+
+    // PII fields in models and personal data reaching logs, analytics or external hosts (part of step 10, findings join step 12)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting personal data flows...");
+    let mut privacy = match repo_config.run("privacy", || PiiDetector::new().detect_pii(&repo_path)) {
+        Ok(p) => p,
+        Err(e) => {
+            log::warn!("⚠ Failed to detect personal data: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut privacy.fields, |f| &f.file_path);
+    repo_config.retain_files(&mut privacy.flows, |f| &f.file_path);
+    log::info!("✓ Detected {} PII field(s), {} personal data flow(s)", privacy.fields.len(), privacy.flows.len());
+    if let Err(e) = state.privacy_repo.store_analysis(&repo.id, &privacy.fields, &privacy.flows) {
+        log::warn!("⚠ Failed to store personal data: {}", e);
+    }
+    
+    log::info!("Storing {} code calls in database...", code_structure.calls.len());
+    {
+        let progress_tracker = state.progress_tracker.clone();
+        let repository_id_clone = repository_id.clone();
+        if let Err(e) = state.code_repo.store_calls(
+            &repo.id, 
+            &code_structure.calls,
+            Some(move |stored, total| {
+                let percent = (stored as f64 / total as f64 * 100.0) as u32;
+                progress_tracker.update_status_message(
+                    &repository_id_clone,
+                    &format!("Storing code calls: {}/{} ({}%)...", stored, total, percent)
+                );
+            })
+        ) {
+            log::error!("✗ Failed to store code calls: {}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Failed to store code calls: {}", e));
+            return Err(anyhow::anyhow!("Failed to store code calls: {}", e));
+        }
+    }
+    log::info!("✓ Stored {} code calls", code_structure.calls.len());
+
+    // Detect relationships between code elements and services/dependencies (part of step 8)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting relationships between code elements and services/dependencies...");
+    log::info!("Detecting relationships between code elements and services/dependencies...");
+    use crate::analysis::CodeRelationshipDetector;
+    let relationship_detector = CodeRelationshipDetector::new(&repo_path);
+    
+    // Get stored services and dependencies for relationship detection
+    log::info!("  Loading {} service(s) and dependencies for relationship detection...", services.len());
+    let stored_services = match state.service_repo.get_by_repository(&repo.id) {
+        Ok(s) => {
+            log::info!("  Loaded {} service(s) from database", s.len());
+            s
+        },
+        Err(e) => {
+            log::warn!("⚠ Failed to get services for relationship detection: {}", e);
+            Vec::new()
+        }
+    };
+    
+    let stored_deps_vec = match state.dep_repo.get_by_repository(&repo.id) {
+        Ok(d) => {
+            log::info!("  Loaded {} dependencies from database", d.len());
+            d
+        },
+        Err(e) => {
+            log::warn!("⚠ Failed to get dependencies for relationship detection: {}", e);
+            Vec::new()
+        }
+    };
+    
+    log::info!("  Analyzing {} code element(s) for relationships to {} service(s) and {} dependencies...", 
+        all_code_elements.len(), stored_services.len(), stored_deps_vec.len());
+    let code_relationships = match relationship_detector.detect_relationships(&code_structure, &stored_services, &stored_deps_vec) {
+        Ok(rels) => {
+            if !rels.is_empty() {
+                log::info!("✓ Detected {} code-to-service/dependency relationship(s)", rels.len());
+            } else {
+                log::info!("✓ No code relationships detected");
+            }
+            rels
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect code relationships: {}", e);
+            Vec::new() // Continue even if relationship detection fails
+        }
+    };
+    
+    // Combine regular code relationships with plugin relationships
+    let mut all_code_relationships = code_relationships;
+    all_code_relationships.extend(plugin_relationships);
+    if !false_positives.is_empty() {
+        let elements: std::collections::HashMap<&str, _> = all_code_elements.iter().map(|e| (e.id.as_str(), e)).collect();
+        all_code_relationships.retain(|r| {
+            let element = elements.get(r.code_element_id.as_str()).copied();
+            Detection::relationship(r, element, &stored_services, &stored_deps_vec)
+                .is_none_or(|d| !false_positives.excludes(&d))
+        });
+    }
+    let low_confidence = repo_config.apply_relationship_threshold(&mut all_code_relationships);
+    if low_confidence > 0 {
+        log::info!("  {} code relationship(s) below the confidence threshold ({:?})", low_confidence, repo_config.thresholds.below.unwrap_or_default());
+    }
+    
+    // Store code relationships (still part of step 8)
+    if !all_code_relationships.is_empty() {
+        log::info!("Storing {} code relationship(s) in database...", all_code_relationships.len());
+        {
+            let progress_tracker = state.progress_tracker.clone();
+            let repository_id_clone = repository_id.clone();
+            if let Err(e) = state.code_relationship_repo.store_relationships(
+                &repo.id, 
+                &all_code_relationships,
+                Some(move |stored, total| {
+                    let percent = (stored as f64 / total as f64 * 100.0) as u32;
+                    progress_tracker.update_status_message(
+                        &repository_id_clone,
+                        &format!("Storing code relationships: {}/{} ({}%)...", stored, total, percent)
+                    );
+                })
+            ) {
+                log::error!("✗ Failed to store code relationships: {}", e);
+            } else {
+                log::info!("✓ Successfully stored {} code relationship(s)", all_code_relationships.len());
+            }
+        }
+    }
+
+    // Suggest service/domain boundaries (part of step 8, uses the relationships above to place data stores)
+    state.progress_tracker.update_status_message(&repository_id, "Inferring service boundaries...");
+    let database_services: std::collections::HashMap<&str, &str> = stored_services.iter()
+        .filter(|s| s.service_type == "database")
+        .map(|s| (s.id.as_str(), s.name.as_str()))
+        .collect();
+    let element_files: std::collections::HashMap<&str, &str> = all_code_elements.iter()
+        .map(|e| (e.id.as_str(), e.file_path.as_str()))
+        .collect();
+    let mut data_store_uses: Vec<DataStoreUse> = stored_services.iter()
+        .filter(|s| database_services.contains_key(s.id.as_str()))
+        .map(|s| DataStoreUse { name: s.name.clone(), file_path: s.file_path.clone() })
+        .collect();
+    for relationship in &all_code_relationships {
+        if let (Some(name), Some(file_path)) = (database_services.get(relationship.target_id.as_str()), element_files.get(relationship.code_element_id.as_str())) {
+            data_store_uses.push(DataStoreUse { name: name.to_string(), file_path: file_path.to_string() });
+        }
+    }
+    let manifest_paths: Vec<String> = manifests.iter().map(|m| m.file_path.clone()).collect();
+    let boundaries = if repo_config.analyzer_enabled("boundaries") {
+        ServiceBoundaryInferrer::new()
+            .with_projects(&repo_config.projects)
+            .infer(&repo_path, &manifest_paths, &measured_elements, &code_structure.calls, &data_store_uses)
+    } else {
+        Vec::new()
+    };
+    log::info!("✓ Suggested {} service/domain boundary(ies)", boundaries.len());
+    if let Err(e) = state.boundary_repo.store_boundaries(&repo.id, &boundaries) {
+        log::warn!("⚠ Failed to store service boundaries: {}", e);
+    }
+
+    // Propose service extractions from communities of files, their tables and endpoints (part of step 8)
+    state.progress_tracker.update_status_message(&repository_id, "Proposing service extractions...");
+    let endpoint_uses: Vec<EndpointUse> = state.endpoint_repo.get_by_repository(&repo.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| EndpointUse { name: format!("{} {}", e.method, e.path), file_path: e.file_path })
+        .collect();
+    let decomposition = if repo_config.analyzer_enabled("decomposition") {
+        DecompositionAdvisor::new().analyze(&repo_path, &measured_elements, &code_structure.calls, &endpoint_uses, &data_store_uses)
+    } else {
+        Default::default()
+    };
+    log::info!("✓ Proposed {} service extraction candidate(s) (modularity {:.2})", decomposition.candidates.len(), decomposition.modularity);
+    if let Err(e) = state.boundary_repo.store_decomposition(&repo.id, &decomposition) {
+        log::warn!("⚠ Failed to store decomposition report: {}", e);
+    }
+
+    // Logging, metrics and tracing per service, with their exporters (part of step 8, services come from the boundaries above)
+    state.progress_tracker.update_status_message(&repository_id, "Detecting observability stack...");
+    let mut observability = match repo_config.run("observability", || ObservabilityDetector::new().detect_stack(&repo_path, &boundaries)) {
+        Ok(o) => o,
+        Err(e) => {
+            log::warn!("⚠ Failed to detect observability stack: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut observability.libraries, |l| &l.file_path);
+    repo_config.retain_files(&mut observability.exporters, |e| &e.file_path);
+    let gaps: usize = observability.coverage.iter().map(|c| c.gaps.len()).sum();
+    log::info!("✓ Detected {} observability library(ies), {} exporter(s), {} coverage gap(s)", observability.libraries.len(), observability.exporters.len(), gaps);
+    if let Err(e) = state.observability_repo.store_analysis(&repo.id, &observability) {
+        log::warn!("⚠ Failed to store observability stack: {}", e);
+    }
+
+    // Detect layering conventions and the imports that break them (part of step 8)
+    state.progress_tracker.update_status_message(&repository_id, "Checking architecture layering...");
+    let source_files: Vec<&str> = element_files.values().copied().collect::<std::collections::BTreeSet<_>>().into_iter().collect();
+    let mut layering = if repo_config.analyzer_enabled("layering") {
+        LayeringAnalyzer::new().analyze(&repo_path, &source_files)
+    } else {
+        LayeringAnalysis::default()
+    };
+    layering.findings.retain(|f| !repo_config.suppresses(&f.rule, &f.source_file, &f.message));
+    log::info!("✓ Layering pattern {:?}: {} file(s) assigned to layers, {} finding(s)", layering.pattern, layering.assignments.len(), layering.findings.len());
+    if let Err(e) = state.architecture_repo.store_layers(&repo.id, &layering.assignments) {
+        log::warn!("⚠ Failed to store architecture layers: {}", e);
+    }
+    if let Err(e) = state.architecture_repo.store_findings(&repo.id, "layering", &layering.findings) {
+        log::warn!("⚠ Failed to store architecture findings: {}", e);
+    }
+
+    // Evaluate the repository's declared dependency rules, if it has a rule file (part of step 8)
+    let mut rule_findings = match repo_config.run("rules", || ArchitectureRuleSet::load(&repo_path)) {
+        Ok(Some((rule_file, rule_set))) => {
+            state.progress_tracker.update_status_message(&repository_id, &format!("Checking architecture rules from {}...", rule_file));
+            let findings = ArchitectureRuleEngine::new(rule_set).evaluate(&repo_path, &source_files, &layering.assignments);
+            log::info!("✓ Architecture rules in {}: {} violation(s)", rule_file, findings.len());
+            findings
+        }
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            log::warn!("⚠ Failed to load architecture rules: {:#}", e);
+            Vec::new()
+        }
+    };
+    rule_findings.retain(|f| !repo_config.suppresses(&f.rule, &f.source_file, &f.message));
+    if let Err(e) = state.architecture_repo.store_findings(&repo.id, "rules", &rule_findings) {
+        log::warn!("⚠ Failed to store architecture rule violations: {}", e);
+    }
+    let plugin_findings: Vec<_> = plugin_output.findings.iter()
+        .map(|f| f.to_finding())
+        .filter(|f| !repo_config.is_excluded(&f.source_file) && !repo_config.suppresses(&f.rule, &f.source_file, &f.message))
+        .collect();
+    if let Err(e) = state.architecture_repo.store_findings(&repo.id, "plugins", &plugin_findings) {
+        log::warn!("⚠ Failed to store plugin findings: {}", e);
+    }
+
+    // Detect tests
+    state.progress_tracker.update_progress(&repository_id, 11, "Detecting tests", "Scanning for test files and test functions...", None);
+    log::info!("Step 11/13: Detecting tests...");
+    log::info!("Scanning repository for test files (this may take a while for large repositories)...");
+    let test_detector = TestDetector::new();
+    let mut tests = match repo_config.run("tests", || {
+        state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "tests", || test_detector.detect_tests(&repo_path))
+    }) {
+        Ok(t) => {
+            // Count test frameworks for better diagnostics
+            use std::collections::HashMap;
+            let mut framework_counts: HashMap<String, usize> = HashMap::new();
+            let mut language_counts: HashMap<String, usize> = HashMap::new();
+            for test in &t {
+                *framework_counts.entry(format!("{:?}", test.test_framework)).or_insert(0) += 1;
+                *language_counts.entry(test.language.clone()).or_insert(0) += 1;
+            }
+            let framework_summary: Vec<String> = framework_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            let language_summary: Vec<String> = language_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            log::info!("✓ Test detection complete: {} test(s) ({}), languages: {}", 
+                t.len(), framework_summary.join(", "), language_summary.join(", "));
+            state.progress_tracker.update_progress(&repository_id, 11, "Detecting tests", 
+                format!("Found {} test(s) using {}", t.len(), framework_summary.join(", ")).as_str(),
+                Some(serde_json::json!({
+                    "tests": t.len(),
+                    "frameworks": framework_counts.len()
+                })));
+            t
+        },
+        Err(e) => {
+            log::error!("✗ Failed to detect tests: {}", e);
+            // Don't fail the entire analysis if test detection fails
+            log::warn!("⚠ Continuing analysis without test detection");
+            Vec::new()
+        }
+    };
+
+    repo_config.retain_files(&mut tests, |t| &t.file_path);
+
+    // Store tests
+    if !tests.is_empty() {
+        log::info!("Storing {} test(s) in database...", tests.len());
+        if let Err(e) = state.test_repo.store_tests(&repo.id, &tests) {
+            log::warn!("⚠ Failed to store tests: {}", e);
+            // Don't fail the entire analysis if test storage fails
+        } else {
+            log::info!("✓ Stored {} test(s)", tests.len());
+        }
+    } else {
+        log::info!("✓ No tests detected");
+    }
+
+    // Ingest committed coverage reports (part of step 11, uploaded reports are kept separately)
+    state.progress_tracker.update_status_message(&repository_id, "Reading test coverage reports...");
+    match repo_config.run("coverage", || CoverageParser::new().detect_reports(&repo_path)) {
+        Ok(coverage) => {
+            if !coverage.is_empty() {
+                log::info!("✓ Read coverage for {} file(s)", coverage.len());
+            }
+            if let Err(e) = state.coverage_repo.store_coverage(&repo.id, "repository", &coverage) {
+                log::warn!("⚠ Failed to store coverage: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to read coverage reports: {}", e);
+        }
+    }
+
+    // Link tests to the code they exercise (part of step 11, uploaded coverage corroborates links too)
+    state.progress_tracker.update_status_message(&repository_id, "Mapping tests to code...");
+    let coverage: Vec<_> = state.coverage_repo.get_effective_coverage(&repo.id)
+        .unwrap_or_default()
+        .iter()
+        .map(|f| f.to_file_coverage())
+        .collect();
+    let links = TestMapper::new().map_tests(&repo_path, &tests, &measured_elements, &coverage);
+    log::info!("✓ Linked tests to code elements ({} link(s))", links.len());
+    if let Err(e) = state.test_repo.store_code_links(&repo.id, &links) {
+        log::warn!("⚠ Failed to store test-to-code links: {}", e);
+    }
+
+    // Inventory TODO/FIXME/HACK comments with blame author and age (part of step 11)
+    state.progress_tracker.update_status_message(&repository_id, "Collecting TODO/FIXME markers...");
+    match repo_config.run("todos", || TodoScanner::new().with_history_depth(profile_settings.history_depth).scan(&repo_path)) {
+        Ok(mut todos) => {
+            repo_config.retain_files(&mut todos, |t| &t.file_path);
+            log::info!("✓ Found {} TODO/FIXME/HACK/XXX marker(s)", todos.len());
+            if let Err(e) = state.todo_repo.store_todos(&repo.id, &todos) {
+                log::warn!("⚠ Failed to store TODO markers: {}", e);
+            }
+        }
+        Err(e) => {
+            log::warn!("⚠ Failed to scan TODO markers: {}", e);
+        }
+    }
+
+    // Score technical debt (part of step 11, missing tests come from the links above)
+    state.progress_tracker.update_status_message(&repository_id, "Scoring technical debt...");
+    let tested_element_ids: std::collections::HashSet<String> = links.iter().map(|l| l.code_element_id.clone()).collect();
+    let debt = TechDebtCalculator::new().with_history_depth(profile_settings.history_depth).calculate(&repo_path, &measured_elements, &tested_element_ids, &manifests);
+    log::info!("✓ Technical debt score {:.1} (grade {})", debt.score, debt.grade);
+    if let Err(e) = state.tech_debt_repo.store_score(&repo.id, &debt) {
+        log::warn!("⚠ Failed to store technical debt score: {}", e);
+    }
+
+    // Estimate the monthly cost of the resources declared in IaC (part of step 11)
+    state.progress_tracker.update_status_message(&repository_id, "Estimating cloud cost...");
+    let mut cost = match repo_config.run("cost", || CostEstimator::new().estimate(&repo_path)) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("⚠ Failed to estimate cloud cost: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut cost.resources, |r| &r.file_path);
+    log::info!("✓ Estimated ${:.2}/month across {} IaC resource(s), {} oversized",
+        cost.monthly_total, cost.resources.len(),
+        cost.resources.iter().filter(|r| r.oversized.is_some()).count());
+    if let Err(e) = state.cost_repo.store_resources(&repo.id, &cost.resources) {
+        log::warn!("⚠ Failed to store cost estimate: {}", e);
+    }
+
+    // Catalog committed binaries, packages and vendored code (part of step 11)
+    state.progress_tracker.update_status_message(&repository_id, "Inventorying committed artifacts...");
+    let mut artifacts = match repo_config.run("artifacts", || ArtifactInventoryScanner::new().scan(&repo_path)) {
+        Ok(a) => a,
+        Err(e) => {
+            log::warn!("⚠ Failed to inventory artifacts: {}", e);
+            Default::default()
+        }
+    };
+    // The inventory reports on vendored directories themselves, so only ignored paths are dropped
+    artifacts.artifacts.retain(|a| !repo_config.is_ignored(&a.file_path));
+    artifacts.findings.retain(|f| !repo_config.is_ignored(&f.file_path));
+    artifacts.findings.retain(|f| !repo_config.suppresses(&f.rule, &f.file_path, &f.message));
+    log::info!("✓ Inventoried {} artifact(s), {} hygiene finding(s)", artifacts.artifacts.len(), artifacts.findings.len());
+    if let Err(e) = state.artifact_repo.store_inventory(&repo.id, &artifacts) {
+        log::warn!("⚠ Failed to store artifact inventory: {}", e);
+    }
+
+    // Audit floating versions across manifests, Dockerfiles, CI configs and scripts (part of step 11)
+    state.progress_tracker.update_status_message(&repository_id, "Auditing version pinning...");
+    let mut pinning = match repo_config.run("pinning", || PinningAuditor::new().audit(&repo_path, &manifests)) {
+        Ok(p) => p,
+        Err(e) => {
+            log::warn!("⚠ Failed to audit version pinning: {}", e);
+            Default::default()
+        }
+    };
+    repo_config.retain_files(&mut pinning.findings, |f| &f.file_path);
+    pinning.findings.retain(|f| !repo_config.suppresses(&f.rule, &f.file_path, &f.message));
+    log::info!("✓ Reproducibility score {:.1}: {} of {} version reference(s) pinned, {} finding(s)",
+        pinning.score, pinning.pinned, pinning.checked, pinning.findings.len());
+    if let Err(e) = state.pinning_repo.store_audit(&repo.id, &pinning) {
+        log::warn!("⚠ Failed to store version pinning audit: {}", e);
+    }
+
+    // Analyze security configuration
+    state.progress_tracker.update_progress(&repository_id, 12, "Analyzing security configuration", "Scanning configuration files and source code for security entities, API keys, and vulnerabilities...", None);
+    log::info!("Step 12/13: Analyzing security configuration...");
+    log::info!("Scanning repository for security entities (API keys, secrets, IAM roles, etc.)...");
+    let security_analyzer = SecurityAnalyzer::new();
+    let mut security_analysis = match repo_config.run("security", || state.progress_tracker.checkpoint(&repository_id, revision.as_deref(), "security analysis", || {
+        security_analyzer.analyze_repository(&repo_path, Some(&code_structure), Some(&services)).map(|mut analysis| {
+            // Endpoints missing the auth guard the rest of their framework's routes have
+            let (endpoint_entities, endpoint_vulns) = crate::security::endpoint_auth::analyze_endpoint_auth(&endpoints);
+            analysis.entities.extend(endpoint_entities);
+            analysis.vulnerabilities.extend(endpoint_vulns);
+            // Bazel/Buck remote caches and executors configured without credentials
+            let (cache_entities, cache_vulns) = crate::security::build_cache::analyze_remote_caches(&tools);
+            analysis.entities.extend(cache_entities);
+            analysis.vulnerabilities.extend(cache_vulns);
+            // Card data in logs or request bodies, and payment webhooks without a signature check
+            let (payment_entities, payment_vulns) = crate::security::payment_pci::analyze_pci_findings(&payments.pci_findings);
+            analysis.entities.extend(payment_entities);
+            analysis.vulnerabilities.extend(payment_vulns);
+            // Personal data written to logs or sent to analytics and external hosts
+            let (pii_entities, pii_vulns) = crate::security::pii_flow::analyze_pii_flows(&privacy.flows);
+            analysis.entities.extend(pii_entities);
+            analysis.vulnerabilities.extend(pii_vulns);
+            analysis
+        })
+    })) {
+        Ok(analysis) => {
+            // Count entity types for better diagnostics
+            use std::collections::HashMap;
+            let mut entity_type_counts: HashMap<String, usize> = HashMap::new();
+            for entity in &analysis.entities {
+                *entity_type_counts.entry(format!("{:?}", entity.entity_type)).or_insert(0) += 1;
+            }
+            let entity_summary: Vec<String> = entity_type_counts.iter()
+                .map(|(k, v)| format!("{}: {}", k, v))
+                .collect();
+            log::info!("✓ Security analysis complete: {} entities ({}), {} relationships, {} vulnerabilities", 
+                analysis.entities.len(), entity_summary.join(", "), analysis.relationships.len(), analysis.vulnerabilities.len());
+            state.progress_tracker.update_progress(&repository_id, 12, "Analyzing security configuration", 
+                format!("Found {} security entities, {} relationships, {} vulnerabilities", 
+                    analysis.entities.len(), analysis.relationships.len(), analysis.vulnerabilities.len()).as_str(),
+                Some(serde_json::json!({
+                    "entities": analysis.entities.len(),
+                    "relationships": analysis.relationships.len(),
+                    "vulnerabilities": analysis.vulnerabilities.len()
+                })));
+            analysis
+        },
+        Err(e) => {
+            log::error!("✗ Failed to analyze security: {}", e);
+            state.progress_tracker.fail_analysis(&repository_id, &format!("Failed to analyze security: {}", e));
+            return Err(anyhow::anyhow!("Failed to analyze security: {}", e));
+        }
+    };
+
+    if repo_config.retain_files(&mut security_analysis.entities, |e| &e.file_path) > 0 {
+        let kept: std::collections::HashSet<String> = security_analysis.entities.iter().map(|e| e.id.clone()).collect();
+        security_analysis.relationships.retain(|r| kept.contains(&r.source_entity_id) && kept.contains(&r.target_entity_id));
+        security_analysis.vulnerabilities.retain(|v| kept.contains(&v.entity_id));
+    }
+    security_analysis.vulnerabilities.retain(|v| {
+        !repo_config.suppresses(&v.vulnerability_type, &v.file_path, &v.description)
+            && !false_positives.excludes(&Detection::vulnerability(v))
+    });
+
+    // Store security entities, relationships, and vulnerabilities
+    // IMPORTANT: Delete in reverse dependency order to avoid foreign key constraint issues
+    // Delete vulnerabilities and relationships first (they reference entities), then entities
+    log::info!("Storing security data: {} entities, {} relationships, {} vulnerabilities...", 
+        security_analysis.entities.len(), security_analysis.relationships.len(), security_analysis.vulnerabilities.len());
+    
+    // First, delete old vulnerabilities and relationships (they reference entities)
+    log::info!("Clearing existing security data...");
+    if let Err(e) = state.security_repo.store_vulnerabilities(&repo.id, &[]) {
+        log::warn!("Failed to clear old vulnerabilities: {}", e);
+    }
+    if let Err(e) = state.security_repo.store_relationships(&repo.id, &[]) {
+        log::warn!("Failed to clear old relationships: {}", e);
+    }
+    
+    // Now store entities (they can be deleted safely)
+    log::info!("Storing {} security entities...", security_analysis.entities.len());
+    if let Err(e) = state.security_repo.store_entities(&repo.id, &security_analysis.entities) {
+        log::error!("✗ Failed to store security entities: {}", e);
+        return Err(anyhow::anyhow!("Failed to store security entities: {}", e));
+    }
+    log::info!("✓ Stored {} security entities", security_analysis.entities.len());
+
+    // Now store relationships (entities exist now)
+    log::info!("Storing {} security relationships...", security_analysis.relationships.len());
+    if let Err(e) = state.security_repo.store_relationships(&repo.id, &security_analysis.relationships) {
+        log::error!("✗ Failed to store security relationships: {}", e);
+        return Err(anyhow::anyhow!("Failed to store security relationships: {}", e));
+    }
+    log::info!("✓ Stored {} security relationships", security_analysis.relationships.len());
+
+    // Finally store vulnerabilities (entities exist now)
+    log::info!("Storing {} security vulnerabilities...", security_analysis.vulnerabilities.len());
+    if let Err(e) = state.security_repo.store_vulnerabilities(&repo.id, &security_analysis.vulnerabilities) {
+        log::error!("✗ Failed to store security vulnerabilities: {}", e);
+        return Err(anyhow::anyhow!("Failed to store security vulnerabilities: {}", e));
+    }
+    log::info!("✓ Stored {} security vulnerabilities", security_analysis.vulnerabilities.len());
+
+    // Index documentation files (experimental - may be removed)
+    state.progress_tracker.update_progress(&repository_id, 13, "Indexing developer documentation", "Scanning for README, API docs, and other documentation files...", None);
+    log::info!("Step 13/13: Indexing developer documentation...");
+    use crate::analysis::DocumentationIndexer;
+    let doc_indexer = DocumentationIndexer::new();
+    match repo_config.run("documentation", || doc_indexer.index_repository(&repo_path, &repo.id)) {
+        Ok(docs) => {
+            log::info!("✓ Indexed {} documentation files", docs.len());
+            state.progress_tracker.update_progress(&repository_id, 13, "Indexing developer documentation", 
+                format!("Indexed {} documentation files", docs.len()).as_str(),
+                Some(serde_json::json!({
+                    "documentation_files": docs.len()
+                })));
+            
+            // Store documentation
+            if let Err(e) = state.documentation_repo.store_documentation(&docs) {
+                log::warn!("⚠ Failed to store documentation: {}", e);
+                // Don't fail the entire analysis if documentation storage fails
+            } else {
+                log::info!("✓ Stored {} documentation files", docs.len());
+            }
+
+            // Link decision records to the modules, classes and services they mention
+            let components: Vec<DecisionComponent> = all_code_elements.iter()
+                .filter(|e| matches!(e.element_type, crate::analysis::CodeElementType::Module | crate::analysis::CodeElementType::Class))
+                .map(|e| DecisionComponent { component_type: "code_element".to_string(), id: e.id.clone(), name: e.name.clone() })
+                .chain(stored_services.iter().map(|s| DecisionComponent { component_type: "service".to_string(), id: s.id.clone(), name: s.name.clone() }))
+                .collect();
+            let decisions = AdrExtractor::new().extract(&repo_path, &docs, &components);
+            if let Err(e) = state.adr_repo.store_decisions(&repo.id, &decisions) {
+                log::warn!("⚠ Failed to store architecture decision records: {}", e);
+            } else if !decisions.is_empty() {
+                log::info!("✓ Stored {} architecture decision records", decisions.len());
+            }
+
+            let mut code_files: Vec<&str> = all_code_elements.iter().map(|e| e.file_path.as_str()).collect();
+            code_files.sort();
+            code_files.dedup();
+            let service_identifiers: Vec<&str> = stored_services.iter()
+                .flat_map(|s| [s.name.as_str(), s.provider.as_str()])
+                .collect();
+            let drift = DocDriftDetector::new().detect(&repo_path, &docs, &endpoints, &service_identifiers, &code_files);
+            let doc_quality = DocQualityScorer::new().with_history_depth(profile_settings.history_depth).score(&repo_path, &docs, &code_files).with_drift(drift);
+            if let Err(e) = state.doc_quality_repo.store_report(&repo.id, &doc_quality) {
+                log::warn!("⚠ Failed to store documentation quality report: {}", e);
+            } else {
+                log::info!("✓ Documentation quality score: {} ({}), {} drift findings", doc_quality.score, doc_quality.grade, doc_quality.drift.len());
+            }
+
+            let mut service_names: Vec<&str> = stored_services.iter().map(|s| s.name.as_str()).collect();
+            service_names.sort();
+            service_names.dedup();
+            let doc_links = DocLinkExtractor::new().extract(&repo_path, &docs, &all_code_elements, &service_names);
+            if let Err(e) = state.doc_link_repo.store_links(&repo.id, &doc_links) {
+                log::warn!("⚠ Failed to store documentation links: {}", e);
+            } else {
+                log::info!("✓ Stored {} documentation links and references", doc_links.len());
+            }
+        },
+        Err(e) => {
+            log::warn!("⚠ Failed to index documentation: {}", e);
+            // Don't fail the entire analysis if documentation indexing fails
+        }
+    }
+
+    // Release history from the changelog, plus a snapshot of this analysis to line changes up with releases
+    match ChangelogParser::new().parse_repository(&repo_path) {
+        Ok(releases) => {
+            if let Err(e) = state.release_repo.store_releases(&repo.id, ReleaseSource::Changelog, &releases) {
+                log::warn!("⚠ Failed to store changelog releases: {}", e);
+            } else if !releases.is_empty() {
+                log::info!("✓ Parsed {} releases from the changelog", releases.len());
+            }
+        }
+        Err(e) => log::warn!("⚠ Failed to read changelog: {}", e),
+    }
+    let snapshot = ArchitectureSnapshot {
+        taken_at: chrono::Utc::now().to_rfc3339(),
+        dependencies: stored_deps_vec.iter().map(|d| (d.name.clone(), d.version.clone())).collect(),
+        services: stored_services.iter().map(|s| s.name.clone()).collect(),
+        endpoint_count: endpoints.len(),
+        code_element_count: all_code_elements.len(),
+    };
+    if let Err(e) = state.release_repo.store_snapshot(&repo.id, &snapshot) {
+        log::warn!("⚠ Failed to store architecture snapshot: {}", e);
+    }
+
+    // Post-analysis plugins see the stored result and may add derived nodes, edges and annotations;
+    // report section plugins then see the enriched result
+    let mut plugin_sections = Vec::new();
+    let has_post_analysis = plugin_registry.with_hook(crate::plugins::PluginHook::PostAnalysis).next().is_some();
+    let has_report_sections = plugin_registry.with_hook(crate::plugins::PluginHook::ReportSection).next().is_some();
+    if has_post_analysis || has_report_sections {
+        state.progress_tracker.update_status_message(repository_id, "Running post-analysis plugins...");
+        match graph_builder.get_graph(&repo.id) {
+            Ok(graph) => {
+                let mut summary = crate::plugins::AnalysisSummary {
+                    repository_id: repo.id.clone(),
+                    repository_name: repo.name.clone(),
+                    repo_path: repo_path.to_string_lossy().to_string(),
+                    graph,
+                    services: stored_services.clone(),
+                    findings: state.architecture_repo.get_findings(&repo.id).unwrap_or_default(),
+                };
+                let mut on_progress = |name: &str, progress: &crate::plugins::Progress| {
+                    state.progress_tracker.update_status_message(repository_id, &format!("Plugin {}: {}", name, progress.message));
+                };
+                if has_post_analysis {
+                    let mut graph = summary.graph.clone();
+                    if crate::plugins::run_post_analysis(&plugin_registry, &summary, &mut graph, &mut on_progress) {
+                        if let Err(e) = graph_builder.store_graph(&repo.id, &graph, None::<fn(u32, u32)>, None::<fn(u32, u32)>) {
+                            log::warn!("⚠ Failed to store plugin enrichments: {}", e);
+                        }
+                        summary.graph = graph;
+                    }
+                }
+                plugin_sections = crate::plugins::run_report_section_plugins(&plugin_registry, &summary, &mut on_progress);
+            }
+            Err(e) => log::warn!("⚠ Skipping post-analysis plugins, graph unavailable: {}", e),
+        }
+    }
+    if let Err(e) = state.plugin_report_repo.store_sections(&repo.id, &plugin_sections) {
+        log::warn!("⚠ Failed to store plugin report sections: {}", e);
+    }
+
+    // Compare against the uploaded target architecture, once everything it checks is stored
+    state.progress_tracker.update_status_message(repository_id, "Checking conformance to the target architecture...");
+    match check_conformance(state, &repo.id, &repo_path) {
+        Ok(Some(deltas)) => log::info!("✓ Target architecture: {} conformance delta(s)", deltas.len()),
+        Ok(None) => {}
+        Err(e) => log::warn!("⚠ Failed to check conformance to the target architecture: {:#}", e),
+    }
+
+    // Update last analyzed timestamp
+    log::info!("Updating repository timestamp...");
+    if let Err(e) = state.repo_repo.update_last_analyzed(&repo.id) {
+        log::error!("✗ Failed to update repository timestamp: {}", e);
+        return Err(anyhow::anyhow!("Failed to update repository: {}", e));
+    }
+
+    // Mark analysis as complete
+    state.progress_tracker.complete_analysis(&repository_id);
+    
+    log::info!("✓ Analysis complete for repository: {}", repo.name);
+    Ok(AnalysisResult {
+        message: "Repository analyzed successfully".to_string(),
+        profile,
+        repository: serde_json::json!({
+            "id": repo.id,
+            "name": repo.name,
+            "url": repo.url,
+            "branch": repo.branch
+        }),
+        results: AnalysisCounts {
+            manifests_found: manifests.len(),
+            total_dependencies: stored_deps,
+            services_found: services.len(),
+            graph_built: true,
+            code_elements_found: code_structure.elements.len(),
+            non_authored_code_elements: non_authored_elements,
+            code_calls_found: code_structure.calls.len(),
+            security_entities_found: security_analysis.entities.len(),
+            security_relationships_found: security_analysis.relationships.len(),
+            security_vulnerabilities_found: security_analysis.vulnerabilities.len(),
+            tests_found: tests.len(),
+            documentation_indexed: repo_config.analyzer_enabled("documentation"),
+        },
+    })
+}
+
+/// Fingerprint the repository and link it to forks and mirrors of it in the same workspace.
+/// `checkout` adds commit identity; without one only the remote URL is compared.
+pub(crate) fn link_related_repositories(state: &AppState, repo: &Repository, checkout: Option<&Path>) -> anyhow::Result<Vec<RepositoryLink>> {
+    let mut fingerprint = RepositoryFingerprint::from_url(&repo.id, &repo.url, &repo.branch);
+    if let Some(checkout) = checkout {
+        fingerprint = fingerprint.with_checkout(checkout);
+    }
+    state.repository_link_repo.store_fingerprint(&fingerprint)?;
+
+    let links: Vec<RepositoryLink> = state.repository_link_repo.get_fingerprints_in_workspace(&repo.workspace_id)?
+        .iter()
+        .filter_map(|other| fingerprint.relation_to(other))
+        .collect();
+    state.repository_link_repo.store_links(&repo.id, &links)?;
+    Ok(links)
+}
+
+/// Compare the repository's stored analysis against its target architecture, if one was
+/// uploaded, and store the deltas. `repo_path` is read for the imports between components.
+pub(crate) fn check_conformance(state: &AppState, repository_id: &str, repo_path: &Path) -> anyhow::Result<Option<Vec<ConformanceDelta>>> {
+    let Some(stored) = state.architecture_repo.get_spec(repository_id)? else {
+        return Ok(None);
+    };
+    let spec = TargetArchitecture::parse(&stored.content)?;
+    let source_files: BTreeSet<String> = state.code_repo.get_by_repository(repository_id)?
+        .into_iter()
+        .map(|e| e.file_path)
+        .collect();
+    let source_files: Vec<&str> = source_files.iter().map(|f| f.as_str()).collect();
+    let deltas = spec.check(&ConformanceInput {
+        repo_path,
+        source_files: &source_files,
+        services: &state.service_repo.get_by_repository(repository_id)?,
+        vulnerabilities: &state.security_repo.get_vulnerabilities(repository_id)?,
+        observability: &state.observability_repo.get_coverage(repository_id)?,
+        test_count: state.test_repo.get_by_repository(repository_id)?.len(),
+        pipeline_count: state.pipeline_repo.get_by_repository(repository_id)?.len(),
+    });
+    state.architecture_repo.store_conformance(repository_id, &deltas)?;
+    Ok(Some(deltas))
+}
@@ -0,0 +1,424 @@
+use serde::{Deserialize, Serialize, Serializer, Deserializer};
+use serde::de::DeserializeOwned;
+use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::storage::AnalysisRunRepository;
+
+#[derive(Debug, Clone)]
+pub struct AnalysisProgress {
+    pub repository_id: String,
+    pub current_step: u32,
+    pub total_steps: u32,
+    pub step_name: String,
+    pub progress_percent: f64,
+    pub status_message: String,
+    pub details: Option<serde_json::Value>,
+    pub started_at: DateTime<Utc>,
+    pub last_updated: DateTime<Utc>,
+}
+
+// Custom serialization to handle DateTime properly
+impl Serialize for AnalysisProgress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("AnalysisProgress", 9)?;
+        state.serialize_field("repository_id", &self.repository_id)?;
+        state.serialize_field("current_step", &self.current_step)?;
+        state.serialize_field("total_steps", &self.total_steps)?;
+        state.serialize_field("step_name", &self.step_name)?;
+        state.serialize_field("progress_percent", &self.progress_percent)?;
+        state.serialize_field("status_message", &self.status_message)?;
+        state.serialize_field("details", &self.details)?;
+        state.serialize_field("started_at", &self.started_at.to_rfc3339())?;
+        state.serialize_field("last_updated", &self.last_updated.to_rfc3339())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for AnalysisProgress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{self, MapAccess, Visitor};
+        use std::fmt;
+
+        struct AnalysisProgressVisitor;
+
+        impl<'de> Visitor<'de> for AnalysisProgressVisitor {
+            type Value = AnalysisProgress;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct AnalysisProgress")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<AnalysisProgress, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut repository_id = None;
+                let mut current_step = None;
+                let mut total_steps = None;
+                let mut step_name = None;
+                let mut progress_percent = None;
+                let mut status_message = None;
+                let mut details = None;
+                let mut started_at = None;
+                let mut last_updated = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        "repository_id" => {
+                            if repository_id.is_some() {
+                                return Err(de::Error::duplicate_field("repository_id"));
+                            }
+                            repository_id = Some(map.next_value()?);
+                        }
+                        "current_step" => {
+                            if current_step.is_some() {
+                                return Err(de::Error::duplicate_field("current_step"));
+                            }
+                            current_step = Some(map.next_value()?);
+                        }
+                        "total_steps" => {
+                            if total_steps.is_some() {
+                                return Err(de::Error::duplicate_field("total_steps"));
+                            }
+                            total_steps = Some(map.next_value()?);
+                        }
+                        "step_name" => {
+                            if step_name.is_some() {
+                                return Err(de::Error::duplicate_field("step_name"));
+                            }
+                            step_name = Some(map.next_value()?);
+                        }
+                        "progress_percent" => {
+                            if progress_percent.is_some() {
+                                return Err(de::Error::duplicate_field("progress_percent"));
+                            }
+                            progress_percent = Some(map.next_value()?);
+                        }
+                        "status_message" => {
+                            if status_message.is_some() {
+                                return Err(de::Error::duplicate_field("status_message"));
+                            }
+                            status_message = Some(map.next_value()?);
+                        }
+                        "details" => {
+                            if details.is_some() {
+                                return Err(de::Error::duplicate_field("details"));
+                            }
+                            details = Some(map.next_value()?);
+                        }
+                        "started_at" => {
+                            if started_at.is_some() {
+                                return Err(de::Error::duplicate_field("started_at"));
+                            }
+                            let s: String = map.next_value()?;
+                            started_at = Some(
+                                DateTime::parse_from_rfc3339(&s)
+                                    .map(|dt| dt.with_timezone(&Utc))
+                                    .map_err(de::Error::custom)?
+                            );
+                        }
+                        "last_updated" => {
+                            if last_updated.is_some() {
+                                return Err(de::Error::duplicate_field("last_updated"));
+                            }
+                            let s: String = map.next_value()?;
+                            last_updated = Some(
+                                DateTime::parse_from_rfc3339(&s)
+                                    .map(|dt| dt.with_timezone(&Utc))
+                                    .map_err(de::Error::custom)?
+                            );
+                        }
+                        _ => {
+                            let _ = map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(AnalysisProgress {
+                    repository_id: repository_id.ok_or_else(|| de::Error::missing_field("repository_id"))?,
+                    current_step: current_step.ok_or_else(|| de::Error::missing_field("current_step"))?,
+                    total_steps: total_steps.ok_or_else(|| de::Error::missing_field("total_steps"))?,
+                    step_name: step_name.ok_or_else(|| de::Error::missing_field("step_name"))?,
+                    progress_percent: progress_percent.ok_or_else(|| de::Error::missing_field("progress_percent"))?,
+                    status_message: status_message.ok_or_else(|| de::Error::missing_field("status_message"))?,
+                    details,
+                    started_at: started_at.ok_or_else(|| de::Error::missing_field("started_at"))?,
+                    last_updated: last_updated.ok_or_else(|| de::Error::missing_field("last_updated"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(AnalysisProgressVisitor)
+    }
+}
+
+pub struct ProgressTracker {
+    progress: Arc<Mutex<HashMap<String, AnalysisProgress>>>,
+    store: Option<AnalysisRunRepository>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        ProgressTracker {
+            progress: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
+        }
+    }
+
+    /// Write progress through to the database and keep step checkpoints there
+    pub fn with_store(store: AnalysisRunRepository) -> Self {
+        ProgressTracker {
+            progress: Arc::new(Mutex::new(HashMap::new())),
+            store: Some(store),
+        }
+    }
+
+    fn persist(&self, progress: &AnalysisProgress) {
+        let Some(store) = &self.store else { return };
+        let saved = serde_json::to_string(progress)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| store.save_progress(&progress.repository_id, &progress.step_name, &json));
+        if let Err(e) = saved {
+            log::warn!("⚠ Failed to save analysis progress for {}: {}", progress.repository_id, e);
+        }
+    }
+
+    fn modify(&self, repository_id: &str, change: impl FnOnce(&mut AnalysisProgress)) {
+        let updated = {
+            let mut progress_map = self.progress.lock().unwrap();
+            progress_map.get_mut(repository_id).map(|progress| {
+                change(progress);
+                progress.last_updated = Utc::now();
+                progress.clone()
+            })
+        };
+        if let Some(progress) = updated {
+            self.persist(&progress);
+        }
+    }
+
+    /// Analyses the database shows as running when the server starts were cut short by a crash
+    /// or restart; mark them interrupted so clients stop waiting, keeping their checkpoints
+    pub fn recover_interrupted(&self) -> Vec<AnalysisProgress> {
+        let Some(store) = &self.store else { return Vec::new() };
+        let unfinished = match store.get_unfinished() {
+            Ok(runs) => runs,
+            Err(e) => {
+                log::warn!("⚠ Failed to load unfinished analyses: {}", e);
+                return Vec::new();
+            }
+        };
+        let mut interrupted = Vec::new();
+        for json in unfinished {
+            let Ok(mut progress) = serde_json::from_str::<AnalysisProgress>(&json) else { continue };
+            progress.status_message = format!(
+                "Interrupted at step {}/{} ({}); analyze again to resume",
+                progress.current_step, progress.total_steps, progress.step_name
+            );
+            progress.step_name = "Interrupted".to_string();
+            progress.last_updated = Utc::now();
+            self.persist(&progress);
+            interrupted.push(progress);
+        }
+        interrupted
+    }
+
+    pub fn start_analysis(&self, repository_id: &str, total_steps: u32) {
+        let progress = AnalysisProgress {
+            repository_id: repository_id.to_string(),
+            current_step: 0,
+            total_steps,
+            step_name: "Starting...".to_string(),
+            progress_percent: 0.0,
+            status_message: "Initializing analysis".to_string(),
+            details: None,
+            started_at: Utc::now(),
+            last_updated: Utc::now(),
+        };
+        self.persist(&progress);
+        let mut progress_map = self.progress.lock().unwrap();
+        progress_map.insert(repository_id.to_string(), progress);
+    }
+
+    pub fn update_progress(
+        &self,
+        repository_id: &str,
+        current_step: u32,
+        step_name: &str,
+        status_message: &str,
+        details: Option<serde_json::Value>,
+    ) {
+        self.modify(repository_id, |progress| {
+            progress.current_step = current_step;
+            progress.step_name = step_name.to_string();
+            progress.status_message = status_message.to_string();
+            progress.details = details;
+            progress.progress_percent = (current_step as f64 / progress.total_steps as f64) * 100.0;
+        });
+    }
+
+    /// Update only the status message without changing the step number
+    /// Useful for batch operations that want to show progress within a step
+    pub fn update_status_message(
+        &self,
+        repository_id: &str,
+        status_message: &str,
+    ) {
+        // Not saved: these change too often, and a resumed analysis only needs the step
+        let mut progress_map = self.progress.lock().unwrap();
+        if let Some(progress) = progress_map.get_mut(repository_id) {
+            progress.status_message = status_message.to_string();
+            progress.last_updated = Utc::now();
+        }
+    }
+
+    /// Falls back to the saved progress, e.g. of an analysis interrupted by a restart
+    pub fn get_progress(&self, repository_id: &str) -> Option<AnalysisProgress> {
+        if let Some(progress) = self.progress.lock().unwrap().get(repository_id).cloned() {
+            return Some(progress);
+        }
+        let json = self.store.as_ref()?.get_progress(repository_id).ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// A finished analysis has nothing to resume, so its checkpoints are dropped
+    pub fn complete_analysis(&self, repository_id: &str) {
+        self.modify(repository_id, |progress| {
+            progress.current_step = progress.total_steps;
+            progress.step_name = "Complete".to_string();
+            progress.status_message = "Analysis completed successfully".to_string();
+            progress.progress_percent = 100.0;
+        });
+        self.discard_checkpoints(repository_id);
+    }
+
+    /// Checkpoints are kept so analyzing again resumes at the failed step
+    pub fn fail_analysis(&self, repository_id: &str, error: &str) {
+        self.modify(repository_id, |progress| {
+            progress.step_name = "Failed".to_string();
+            progress.status_message = format!("Analysis failed: {}", error);
+        });
+    }
+
+    /// Reuse the output a step saved during an earlier, unfinished analysis of the same `revision`,
+    /// or run the step and save its output. Without a revision or a database the step just runs.
+    pub fn checkpoint<T, E>(
+        &self,
+        repository_id: &str,
+        revision: Option<&str>,
+        name: &str,
+        run: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let (Some(store), Some(revision)) = (&self.store, revision) else { return run() };
+        if let Ok(Some(payload)) = store.get_checkpoint(repository_id, revision, name) {
+            if let Ok(output) = serde_json::from_str(&payload) {
+                log::info!("↺ Resuming with saved {} from an earlier unfinished analysis", name);
+                self.update_status_message(repository_id, &format!("Reusing saved {} from the interrupted analysis...", name));
+                return Ok(output);
+            }
+        }
+
+        let output = run()?;
+        let saved = serde_json::to_string(&output)
+            .map_err(anyhow::Error::from)
+            .and_then(|payload| store.store_checkpoint(repository_id, revision, name, &payload));
+        if let Err(e) = saved {
+            log::warn!("⚠ Failed to save {} checkpoint: {}", name, e);
+        }
+        Ok(output)
+    }
+
+    /// Make the next analysis start over
+    pub fn discard_checkpoints(&self, repository_id: &str) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.clear_checkpoints(repository_id) {
+                log::warn!("⚠ Failed to clear analysis checkpoints for {}: {}", repository_id, e);
+            }
+        }
+    }
+
+    pub fn clear_progress(&self, repository_id: &str) {
+        let mut progress_map = self.progress.lock().unwrap();
+        progress_map.remove(repository_id);
+    }
+    
+    /// Keep completed analyses for a period of time (5 minutes) before clearing
+    pub fn cleanup_old_progress(&self, max_age_seconds: i64) {
+        let mut progress_map = self.progress.lock().unwrap();
+        let now = Utc::now();
+        let mut to_remove = Vec::new();
+        
+        for (repo_id, progress) in progress_map.iter() {
+            let age = now.signed_duration_since(progress.last_updated);
+            // Only remove completed or failed analyses that are old
+            if (progress.step_name == "Complete" || progress.step_name == "Failed") 
+                && age.num_seconds() > max_age_seconds {
+                to_remove.push(repo_id.clone());
+            }
+        }
+        
+        for repo_id in to_remove {
+            progress_map.remove(&repo_id);
+        }
+    }
+}
+
+impl Default for ProgressTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DatabaseConfig;
+    use crate::storage::{Database, RepositoryRepository, DEFAULT_WORKSPACE};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_restart_reports_interrupted_analysis_and_resumes_from_checkpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new(&DatabaseConfig {
+            database_path: temp_dir.path().join("test.db").to_str().unwrap().to_string(),
+            graph_db_path: temp_dir.path().join("graph.db").to_str().unwrap().to_string(),
+        }).unwrap();
+        let repo = RepositoryRepository::new(db.clone())
+            .create("app", "https://example.com/app.git", None, None, None, DEFAULT_WORKSPACE).unwrap();
+
+        let tracker = ProgressTracker::with_store(AnalysisRunRepository::new(db.clone()));
+        tracker.start_analysis(&repo.id, 13);
+        tracker.update_progress(&repo.id, 8, "Analyzing code structure", "Scanning...", None);
+        let first: Result<Vec<u32>, String> = tracker.checkpoint(&repo.id, Some("abc:standard"), "code structure", || Ok(vec![1, 2]));
+        assert_eq!(first.unwrap(), vec![1, 2]);
+
+        // A new tracker over the same database stands in for the restarted server
+        let restarted = ProgressTracker::with_store(AnalysisRunRepository::new(db));
+        let interrupted = restarted.recover_interrupted();
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].current_step, 8);
+        assert_eq!(restarted.get_progress(&repo.id).unwrap().step_name, "Interrupted");
+
+        restarted.start_analysis(&repo.id, 13);
+        let resumed: Result<Vec<u32>, String> = restarted.checkpoint(&repo.id, Some("abc:standard"), "code structure", || Err("re-ran".to_string()));
+        assert_eq!(resumed.unwrap(), vec![1, 2]);
+        let changed: Result<Vec<u32>, String> = restarted.checkpoint(&repo.id, Some("def:standard"), "code structure", || Ok(vec![3]));
+        assert_eq!(changed.unwrap(), vec![3]);
+
+        restarted.complete_analysis(&repo.id);
+        let fresh: Result<Vec<u32>, String> = restarted.checkpoint(&repo.id, Some("def:standard"), "code structure", || Ok(vec![4]));
+        assert_eq!(fresh.unwrap(), vec![4]);
+        assert!(restarted.recover_interrupted().is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use crate::analyzer::progress::ProgressTracker;
+use crate::config::Config;
+use crate::search::EmbeddingProvider;
+use crate::storage::{Database, AnalysisRunRepository, RepositoryRepository, DependencyRepository, ServiceRepository, CodeElementRepository, CodeRelationshipRepository, SecurityRepository, ToolRepository, DocumentationRepository, TestRepository, PortRepository, EndpointRepository, KubernetesRepository, GatewayRepository, PipelineRepository, BuildTargetRepository, DataPipelineRepository, FrameworkRepository, EntrypointRepository, BackgroundJobRepository, NotificationRepository, PaymentRepository, PrivacyRepository, ObservabilityRepository, CostRepository, EnvironmentRepository, ArtifactRepository, PinningRepository, ImageRepository, LanguageStatsRepository, FrontendRouteRepository, CoverageRepository, GraphQLUsageRepository, ServiceBoundaryRepository, ArchitectureRepository, TechDebtRepository, TodoRepository, AdrRepository, EmbeddingRepository, DocQualityRepository, DocLinkRepository, ReleaseRepository, PluginSelectionRepository, PluginReportRepository, WorkspaceRepository, JiraIssueRepository, RepositoryLinkRepository, SystemRepository, FalsePositiveRepository, ScheduleRepository, WebhookRepository};
+
+/// The database, its repositories and the services an analysis runs with. The server shares one
+/// across request handlers; [`Analyzer`](crate::Analyzer) opens its own.
+pub struct AppState {
+    pub repo_repo: RepositoryRepository,
+    pub dep_repo: DependencyRepository,
+    pub service_repo: ServiceRepository,
+    pub code_repo: CodeElementRepository,
+    pub code_relationship_repo: CodeRelationshipRepository,
+    pub security_repo: SecurityRepository,
+    pub tool_repo: ToolRepository,
+    pub documentation_repo: DocumentationRepository,
+    pub test_repo: TestRepository,
+    pub port_repo: PortRepository,
+    pub endpoint_repo: EndpointRepository,
+    pub kubernetes_repo: KubernetesRepository,
+    pub gateway_repo: GatewayRepository,
+    pub build_target_repo: BuildTargetRepository,
+    pub data_pipeline_repo: DataPipelineRepository,
+    pub pipeline_repo: PipelineRepository,
+    pub framework_repo: FrameworkRepository,
+    pub entrypoint_repo: EntrypointRepository,
+    pub background_job_repo: BackgroundJobRepository,
+    pub notification_repo: NotificationRepository,
+    pub payment_repo: PaymentRepository,
+    pub privacy_repo: PrivacyRepository,
+    pub observability_repo: ObservabilityRepository,
+    pub cost_repo: CostRepository,
+    pub environment_repo: EnvironmentRepository,
+    pub artifact_repo: ArtifactRepository,
+    pub pinning_repo: PinningRepository,
+    pub image_repo: ImageRepository,
+    pub language_stats_repo: LanguageStatsRepository,
+    pub frontend_route_repo: FrontendRouteRepository,
+    pub coverage_repo: CoverageRepository,
+    pub graphql_usage_repo: GraphQLUsageRepository,
+    pub boundary_repo: ServiceBoundaryRepository,
+    pub architecture_repo: ArchitectureRepository,
+    pub tech_debt_repo: TechDebtRepository,
+    pub todo_repo: TodoRepository,
+    pub adr_repo: AdrRepository,
+    pub embedding_repo: EmbeddingRepository,
+    pub doc_quality_repo: DocQualityRepository,
+    pub doc_link_repo: DocLinkRepository,
+    pub release_repo: ReleaseRepository,
+    pub plugin_selection_repo: PluginSelectionRepository,
+    pub plugin_report_repo: PluginReportRepository,
+    pub workspace_repo: WorkspaceRepository,
+    pub jira_issue_repo: JiraIssueRepository,
+    pub repository_link_repo: RepositoryLinkRepository,
+    pub system_repo: SystemRepository,
+    pub false_positive_repo: FalsePositiveRepository,
+    pub schedule_repo: ScheduleRepository,
+    pub webhook_repo: WebhookRepository,
+    pub embedding_provider: Option<crate::search::EmbeddingProvider>,
+    pub plugins_config: crate::config::PluginsConfig,
+    pub analysis_config: crate::config::AnalysisConfig,
+    /// The effective configuration the server started with
+    pub config: crate::config::Config,
+    /// Loaded from `config/plugins` at startup and reloaded when it changes
+    pub plugin_registry: crate::plugins::SharedRegistry,
+    pub progress_tracker: Arc<ProgressTracker>,
+}
+
+impl AppState {
+    pub fn open(config: &Config) -> Result<Self> {
+        // Initialize database
+        let db = Database::new(&config.database)
+            .map_err(|e| anyhow!("Failed to initialize database at {}: {:#}", config.database.database_path, e))?;
+    
+        // Initialize repositories
+        let repo_repo = RepositoryRepository::new(db.clone());
+        let dep_repo = DependencyRepository::new(db.clone());
+        let service_repo = ServiceRepository::new(db.clone());
+        let code_repo = CodeElementRepository::new(db.clone());
+        let code_relationship_repo = CodeRelationshipRepository::new(db.clone());
+        let security_repo = SecurityRepository::new(db.clone());
+        let tool_repo = ToolRepository::new(db.clone());
+        let documentation_repo = DocumentationRepository::new(db.clone());
+        let test_repo = TestRepository::new(db.clone());
+        let port_repo = PortRepository::new(db.clone());
+        let endpoint_repo = EndpointRepository::new(db.clone());
+        let kubernetes_repo = KubernetesRepository::new(db.clone());
+        let gateway_repo = GatewayRepository::new(db.clone());
+        let build_target_repo = BuildTargetRepository::new(db.clone());
+        let data_pipeline_repo = DataPipelineRepository::new(db.clone());
+        let pipeline_repo = PipelineRepository::new(db.clone());
+        let framework_repo = FrameworkRepository::new(db.clone());
+        let entrypoint_repo = EntrypointRepository::new(db.clone());
+        let background_job_repo = BackgroundJobRepository::new(db.clone());
+        let notification_repo = NotificationRepository::new(db.clone());
+        let payment_repo = PaymentRepository::new(db.clone());
+        let privacy_repo = PrivacyRepository::new(db.clone());
+        let observability_repo = ObservabilityRepository::new(db.clone());
+        let cost_repo = CostRepository::new(db.clone());
+        let environment_repo = EnvironmentRepository::new(db.clone());
+        let artifact_repo = ArtifactRepository::new(db.clone());
+        let pinning_repo = PinningRepository::new(db.clone());
+        let image_repo = ImageRepository::new(db.clone());
+        let language_stats_repo = LanguageStatsRepository::new(db.clone());
+        let frontend_route_repo = FrontendRouteRepository::new(db.clone());
+        let coverage_repo = CoverageRepository::new(db.clone());
+        let graphql_usage_repo = GraphQLUsageRepository::new(db.clone());
+        let boundary_repo = ServiceBoundaryRepository::new(db.clone());
+        let architecture_repo = ArchitectureRepository::new(db.clone());
+        let tech_debt_repo = TechDebtRepository::new(db.clone());
+        let todo_repo = TodoRepository::new(db.clone());
+        let adr_repo = AdrRepository::new(db.clone());
+        let embedding_repo = EmbeddingRepository::new(db.clone());
+        let doc_quality_repo = DocQualityRepository::new(db.clone());
+        let doc_link_repo = DocLinkRepository::new(db.clone());
+        let release_repo = ReleaseRepository::new(db.clone());
+        let plugin_selection_repo = PluginSelectionRepository::new(db.clone());
+        let plugin_report_repo = PluginReportRepository::new(db.clone());
+        let workspace_repo = WorkspaceRepository::new(db.clone());
+        let jira_issue_repo = JiraIssueRepository::new(db.clone());
+        let repository_link_repo = RepositoryLinkRepository::new(db.clone());
+        let system_repo = SystemRepository::new(db.clone());
+        let false_positive_repo = FalsePositiveRepository::new(db.clone());
+        let schedule_repo = ScheduleRepository::new(db.clone());
+        let webhook_repo = WebhookRepository::new(db.clone());
+        let embedding_provider = match EmbeddingProvider::from_config(&config.embeddings) {
+            Ok(provider) => {
+                if let Some(p) = &provider {
+                    log::info!("Semantic search enabled ({})", p.model_id());
+                }
+                provider
+            }
+            Err(e) => {
+                log::warn!("⚠ Semantic search disabled: {}", e);
+                None
+            }
+        };
+    
+        let plugin_registry = crate::plugins::watch::shared(std::path::Path::new("config/plugins"));
+
+        // Initialize progress tracker; analyses a crash or restart cut short are reported as interrupted
+        let progress_tracker = Arc::new(ProgressTracker::with_store(AnalysisRunRepository::new(db.clone())));
+        for progress in progress_tracker.recover_interrupted() {
+            log::warn!("⚠ Analysis of {} was interrupted at step {}/{}; analyze it again to resume", progress.repository_id, progress.current_step, progress.total_steps);
+        }
+    
+        Ok(AppState {
+            repo_repo,
+            dep_repo,
+            service_repo,
+            code_repo,
+            code_relationship_repo,
+            security_repo,
+            tool_repo,
+            documentation_repo,
+            test_repo,
+            port_repo,
+            endpoint_repo,
+            kubernetes_repo,
+            gateway_repo,
+            build_target_repo,
+            data_pipeline_repo,
+            pipeline_repo,
+            framework_repo,
+            entrypoint_repo,
+            background_job_repo,
+            notification_repo,
+            payment_repo,
+            privacy_repo,
+            observability_repo,
+            cost_repo,
+            environment_repo,
+            artifact_repo,
+            pinning_repo,
+            image_repo,
+            language_stats_repo,
+            frontend_route_repo,
+            coverage_repo,
+            graphql_usage_repo,
+            boundary_repo,
+            architecture_repo,
+            tech_debt_repo,
+            todo_repo,
+            adr_repo,
+            embedding_repo,
+            doc_quality_repo,
+            doc_link_repo,
+            release_repo,
+            plugin_selection_repo,
+            plugin_report_repo,
+            workspace_repo,
+            jira_issue_repo,
+            repository_link_repo,
+            system_repo,
+            false_positive_repo,
+            schedule_repo,
+            webhook_repo,
+            embedding_provider,
+            plugins_config: config.plugins.clone(),
+            analysis_config: config.analysis.clone(),
+            config: config.clone(),
+            plugin_registry,
+            progress_tracker,
+        })
+    }
+}
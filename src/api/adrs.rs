@@ -0,0 +1,24 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+
+/// Architecture decision records with the components they mention, filtered by `?status=` and `?component=` (name)
+pub async fn get_adrs(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.adr_repo.get_by_repository(&path.into_inner()) {
+        Ok(decisions) => {
+            let filtered: Vec<_> = decisions.into_iter()
+                .filter(|d| query.get("status").map(|s| d.status.as_deref().map(|ds| ds.eq_ignore_ascii_case(s)).unwrap_or(false)).unwrap_or(true))
+                .filter(|d| query.get("component").map(|c| d.mentions.iter().any(|m| m.name.eq_ignore_ascii_case(c))).unwrap_or(true))
+                .collect();
+            HttpResponse::Ok().json(filtered)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,241 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::api::{ApiState, ErrorResponse};
+use crate::analyzer::pipeline::check_conformance;
+use crate::api::related::existing_checkout;
+use crate::analysis::{ArchitecturePattern, ConformanceDelta, Layer, TargetArchitecture};
+use crate::storage::architecture_repo::StoredArchitectureFinding;
+
+#[derive(Serialize)]
+pub struct ArchitectureCheckResponse {
+    pub passed: bool,
+    pub fail_on: String,
+    pub violation_count: usize,
+    pub violations: Vec<StoredArchitectureFinding>,
+}
+
+#[derive(Serialize)]
+pub struct ArchitectureResponse {
+    pub pattern: ArchitecturePattern,
+    pub layers: BTreeMap<String, usize>,
+    pub finding_count: usize,
+    pub findings: Vec<StoredArchitectureFinding>,
+}
+
+#[derive(Serialize)]
+pub struct ConformanceReport {
+    pub name: Option<String>,
+    pub spec_updated_at: String,
+    /// Unset until an analysis has compared the repository against the current spec
+    pub checked_at: Option<String>,
+    pub conforms: bool,
+    pub delta_count: usize,
+    pub deltas: Vec<ConformanceDelta>,
+}
+
+fn conformance_report(state: &ApiState, repository_id: &str) -> Result<ConformanceReport, HttpResponse> {
+    let internal_error = |e: anyhow::Error| HttpResponse::InternalServerError().json(ErrorResponse {
+        error: e.to_string(),
+    });
+    let spec = match state.architecture_repo.get_spec(repository_id).map_err(internal_error)? {
+        Some(spec) => spec,
+        None => return Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "No target architecture uploaded".to_string(),
+        })),
+    };
+    let deltas = state.architecture_repo.get_conformance(repository_id).map_err(internal_error)?;
+    Ok(ConformanceReport {
+        name: TargetArchitecture::parse(&spec.content).ok().and_then(|s| s.name),
+        spec_updated_at: spec.updated_at,
+        conforms: spec.checked_at.is_some() && deltas.is_empty(),
+        checked_at: spec.checked_at,
+        delta_count: deltas.len(),
+        deltas,
+    })
+}
+
+/// Upload the target architecture (YAML or JSON, see [`TargetArchitecture`]). The repository is
+/// checked against it right away when a checkout is on disk, and after every analysis.
+pub async fn put_architecture_spec(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    body: String,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let repo = match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(repo)) => repo,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    if let Err(e) = TargetArchitecture::parse(&body) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("{:#}", e),
+        });
+    }
+    if let Err(e) = state.architecture_repo.store_spec(&repository_id, &body) {
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        });
+    }
+
+    if let Some(checkout) = existing_checkout(&repo.url) {
+        let (state, repository_id) = (state.clone(), repository_id.clone());
+        let checked = web::block(move || check_conformance(&state, &repository_id, &checkout)).await;
+        if let Err(e) = checked.map_err(anyhow::Error::from).and_then(|result| result) {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e.to_string(),
+            });
+        }
+    }
+    match conformance_report(&state, &repository_id) {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(response) => response,
+    }
+}
+
+/// The uploaded target architecture, as written
+pub async fn get_architecture_spec(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.architecture_repo.get_spec(&path.into_inner()) {
+        Ok(Some(spec)) => HttpResponse::Ok().json(spec),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "No target architecture uploaded".to_string(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+pub async fn delete_architecture_spec(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.architecture_repo.delete_spec(&path.into_inner()) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Deltas between the last analysis and the target architecture: missing components, imports
+/// between components the spec does not allow, external services it does not list and required
+/// controls that are missing
+pub async fn get_architecture_conformance(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match conformance_report(&state, &path.into_inner()) {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(response) => response,
+    }
+}
+
+/// Detected architecture pattern, files per layer and architecture findings
+pub async fn get_architecture(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let layers = match state.architecture_repo.get_layers(&repository_id) {
+        Ok(l) => l,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let findings = match state.architecture_repo.get_findings(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let mut layer_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for assignment in &layers {
+        *layer_counts.entry(assignment.layer.clone()).or_default() += 1;
+    }
+    let present: HashSet<Layer> = layers.iter().filter_map(|l| Layer::parse(&l.layer)).collect();
+
+    HttpResponse::Ok().json(ArchitectureResponse {
+        pattern: ArchitecturePattern::from_layers(&present),
+        layers: layer_counts,
+        finding_count: findings.len(),
+        findings,
+    })
+}
+
+/// Architecture findings, optionally filtered by `?severity=` and `?rule=`
+pub async fn get_architecture_findings(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.architecture_repo.get_findings(&path.into_inner()) {
+        Ok(findings) => {
+            let filtered: Vec<StoredArchitectureFinding> = findings.into_iter()
+                .filter(|f| query.get("severity").map(|s| f.severity.eq_ignore_ascii_case(s)).unwrap_or(true))
+                .filter(|f| query.get("rule").map(|r| &f.rule == r).unwrap_or(true))
+                .collect();
+            HttpResponse::Ok().json(filtered)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// CI gate over declared rule violations: `passed` is false when any violation is at or above
+/// `?fail_on=` (low, medium or high; defaults to low). `?include_layering=true` also gates on
+/// the built-in layering findings. Responds 409 when the check fails so scripts can use the status.
+pub async fn check_architecture(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let rank = |severity: &str| match severity.to_lowercase().as_str() {
+        "high" => 2,
+        "medium" => 1,
+        _ => 0,
+    };
+    let fail_on = query.get("fail_on").cloned().unwrap_or_else(|| "low".to_string());
+    let include_layering = query.get("include_layering").map(|v| v == "true").unwrap_or(false);
+
+    match state.architecture_repo.get_findings(&path.into_inner()) {
+        Ok(findings) => {
+            let violations: Vec<StoredArchitectureFinding> = findings.into_iter()
+                .filter(|f| f.source == "rules" || include_layering)
+                .filter(|f| rank(&f.severity) >= rank(&fail_on))
+                .collect();
+            let response = ArchitectureCheckResponse {
+                passed: violations.is_empty(),
+                fail_on,
+                violation_count: violations.len(),
+                violations,
+            };
+            if response.passed {
+                HttpResponse::Ok().json(response)
+            } else {
+                HttpResponse::Conflict().json(response)
+            }
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
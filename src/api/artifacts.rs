@@ -0,0 +1,45 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::{StoredArtifact, StoredHygieneFinding};
+
+#[derive(Serialize)]
+pub struct ArtifactsResponse {
+    pub total_bytes: u64,
+    pub artifacts: Vec<StoredArtifact>,
+    pub findings: Vec<StoredHygieneFinding>,
+}
+
+/// Committed binaries, packages, archives, vendored code and image references, with hygiene findings.
+/// `?kind=binary|java_archive|vendored|container_image|...` narrows the artifacts.
+pub async fn get_artifacts(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let artifacts = match state.artifact_repo.get_artifacts(&repository_id) {
+        Ok(a) => a,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let findings = match state.artifact_repo.get_findings(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let artifacts: Vec<StoredArtifact> = artifacts.into_iter()
+        .filter(|a| query.get("kind").map(|k| a.kind.eq_ignore_ascii_case(k)).unwrap_or(true))
+        .collect();
+    HttpResponse::Ok().json(ArtifactsResponse {
+        total_bytes: artifacts.iter().filter_map(|a| a.size_bytes).sum(),
+        artifacts,
+        findings,
+    })
+}
@@ -0,0 +1,16 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// Background job endpoints
+pub async fn get_background_jobs(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.background_job_repo.get_by_repository(&path.into_inner()) {
+        Ok(jobs) => HttpResponse::Ok().json(jobs),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,39 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+
+/// Suggested service/domain boundaries, highest confidence first
+pub async fn get_service_boundaries(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.boundary_repo.get_by_repository(&path.into_inner()) {
+        Ok(boundaries) => HttpResponse::Ok().json(boundaries),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Candidate service extractions, best first, with their coupling metrics and the steps to take.
+/// `?min_score=0.6` drops weaker candidates.
+pub async fn get_decomposition(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.boundary_repo.get_decomposition(&path.into_inner()) {
+        Ok(report) => {
+            let mut report = report.unwrap_or_default();
+            if let Some(min_score) = query.get("min_score").and_then(|s| s.parse::<f64>().ok()) {
+                report.candidates.retain(|c| c.score >= min_score);
+            }
+            HttpResponse::Ok().json(report)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
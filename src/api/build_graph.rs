@@ -0,0 +1,61 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::StoredBuildTarget;
+
+#[derive(Serialize)]
+pub struct BuildGraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: String, // "depends_on", "uses_tool" or "runs_in_ci" (CI job -> target)
+}
+
+#[derive(Serialize)]
+pub struct BuildGraphResponse {
+    /// Targets at least two CI jobs run, directly or through other targets; a slow one delays all of them
+    pub bottlenecks: Vec<String>,
+    /// Targets neither CI nor another target runs
+    pub unused: Vec<String>,
+    pub edges: Vec<BuildGraphEdge>,
+    pub targets: Vec<StoredBuildTarget>,
+}
+
+/// Make/Just/Task targets, npm scripts and Gradle tasks with what they run, the tools they invoke
+/// and the CI jobs that call them. `?runner=make|just|task|npm|gradle` narrows the targets.
+pub async fn get_build_graph(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let mut targets = match state.build_target_repo.get_by_repository(&path.into_inner()) {
+        Ok(t) => t,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    if let Some(runner) = query.get("runner") {
+        targets.retain(|t| t.runner.eq_ignore_ascii_case(runner));
+    }
+
+    let mut edges = Vec::new();
+    for target in &targets {
+        for dependency in &target.depends_on {
+            edges.push(BuildGraphEdge { source: target.key.clone(), target: dependency.clone(), kind: "depends_on".to_string() });
+        }
+        for tool in &target.tools {
+            edges.push(BuildGraphEdge { source: target.key.clone(), target: tool.clone(), kind: "uses_tool".to_string() });
+        }
+        for job in &target.ci_jobs {
+            edges.push(BuildGraphEdge { source: job.clone(), target: target.key.clone(), kind: "runs_in_ci".to_string() });
+        }
+    }
+
+    HttpResponse::Ok().json(BuildGraphResponse {
+        bottlenecks: targets.iter().filter(|t| t.ci_reach >= 2).map(|t| t.key.clone()).collect(),
+        unused: targets.iter().filter(|t| t.unused).map(|t| t.key.clone()).collect(),
+        edges,
+        targets,
+    })
+}
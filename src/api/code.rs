@@ -11,8 +11,17 @@ pub async fn get_code_elements(
     // API key validation removed for local tool simplicity
     let repository_id = path.into_inner();
     
+    // Look up a single element by its fully-qualified name
+    if let Some(qualified_name) = query.get("qualified_name") {
+        match state.code_repo.find_by_qualified_name(&repository_id, qualified_name) {
+            Ok(Some(element)) => HttpResponse::Ok().json(vec![element]),
+            Ok(None) => HttpResponse::Ok().json(Vec::<crate::analysis::CodeElement>::new()),
+            Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        }
     // Check if filtering by type
-    if let Some(element_type) = query.get("type") {
+    } else if let Some(element_type) = query.get("type") {
         match state.code_repo.get_by_type(&repository_id, element_type) {
             Ok(elements) => HttpResponse::Ok().json(elements),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
@@ -34,12 +43,22 @@ pub async fn get_code_calls(
     state: web::Data<ApiState>,
     _req: HttpRequest,
     path: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
 ) -> impl Responder {
     // API key validation removed for local tool simplicity
     let repository_id = path.into_inner();
     
     match state.code_repo.get_calls(&repository_id) {
-        Ok(calls) => HttpResponse::Ok().json(calls),
+        Ok(mut calls) => {
+            // Calls made by or to one element, and one kind of call
+            if let Some(element_id) = query.get("code_element_id") {
+                calls.retain(|c| &c.caller_id == element_id || &c.callee_id == element_id);
+            }
+            if let Some(call_type) = query.get("call_type") {
+                calls.retain(|c| &c.call_type == call_type);
+            }
+            HttpResponse::Ok().json(calls)
+        }
         Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
             error: e.to_string(),
         }),
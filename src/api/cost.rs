@@ -0,0 +1,47 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::analysis::cost_estimator::COST_ASSUMPTIONS;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::StoredResourceCost;
+
+#[derive(Serialize)]
+pub struct CostResponse {
+    pub currency: &'static str,
+    pub monthly_total: f64,
+    pub by_category: BTreeMap<String, f64>,
+    pub oversized: usize,
+    pub unpriced: usize,
+    pub assumptions: &'static [&'static str],
+    pub resources: Vec<StoredResourceCost>,
+}
+
+/// Rough monthly cost of the repository's IaC resources, with a per-resource breakdown
+pub async fn get_cost_estimate(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let resources = match state.cost_repo.get_by_repository(&path.into_inner()) {
+        Ok(r) => r,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let mut by_category: BTreeMap<String, f64> = BTreeMap::new();
+    for resource in &resources {
+        *by_category.entry(resource.category.clone()).or_default() += resource.monthly_cost.unwrap_or(0.0);
+    }
+    let monthly_total = by_category.values().sum::<f64>();
+
+    HttpResponse::Ok().json(CostResponse {
+        currency: "USD",
+        monthly_total: (monthly_total * 100.0).round() / 100.0,
+        by_category: by_category.into_iter().map(|(k, v)| (k, (v * 100.0).round() / 100.0)).collect(),
+        oversized: resources.iter().filter(|r| r.oversized.is_some()).count(),
+        unpriced: resources.iter().filter(|r| r.monthly_cost.is_none()).count(),
+        assumptions: COST_ASSUMPTIONS,
+        resources,
+    })
+}
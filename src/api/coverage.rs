@@ -0,0 +1,131 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Deserialize;
+use std::collections::HashSet;
+use crate::api::{ApiState, ErrorResponse};
+use crate::analysis::coverage::{detect_format, element_coverage, resolve_paths};
+use crate::analysis::{CoverageFormat, CoverageParser};
+
+/// Functions below this line coverage with at least this complexity are reported as untested complex code
+const UNTESTED_COVERAGE_THRESHOLD: f64 = 50.0;
+const COMPLEX_THRESHOLD: usize = 5;
+
+#[derive(Debug, Deserialize)]
+pub struct CoverageUploadQuery {
+    pub format: Option<String>, // lcov, cobertura or jacoco; detected from the content when omitted
+}
+
+/// Get file and function coverage for a repository, including untested complex functions
+pub async fn get_coverage(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let files = match state.coverage_repo.get_effective_coverage(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let elements = match state.code_repo.get_by_repository(&repository_id) {
+        Ok(e) => e,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let coverage: Vec<_> = files.iter().map(|f| f.to_file_coverage()).collect();
+    let element_coverage = element_coverage(&elements, &coverage);
+    let mut untested_complex: Vec<_> = element_coverage.iter()
+        .filter(|e| e.line_percentage < UNTESTED_COVERAGE_THRESHOLD && e.complexity >= COMPLEX_THRESHOLD)
+        .cloned()
+        .collect();
+    untested_complex.sort_by(|a, b| {
+        b.complexity.cmp(&a.complexity)
+            .then_with(|| a.line_percentage.partial_cmp(&b.line_percentage).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    let lines_found: usize = files.iter().map(|f| f.lines_found).sum();
+    let lines_hit: usize = files.iter().map(|f| f.lines_hit).sum();
+    let branches_found: usize = files.iter().map(|f| f.branches_found).sum();
+    let branches_hit: usize = files.iter().map(|f| f.branches_hit).sum();
+    let line_percentage = if lines_found > 0 {
+        (lines_hit as f64 / lines_found as f64 * 10000.0).round() / 100.0
+    } else {
+        0.0
+    };
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "repository_id": repository_id,
+        "summary": {
+            "files": files.len(),
+            "lines_found": lines_found,
+            "lines_hit": lines_hit,
+            "line_percentage": line_percentage,
+            "branches_found": branches_found,
+            "branches_hit": branches_hit,
+        },
+        "files": files,
+        "elements": element_coverage,
+        "untested_complex": untested_complex,
+    }))
+}
+
+/// Upload a coverage report produced outside the repository (e.g. by CI)
+pub async fn upload_coverage(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<CoverageUploadQuery>,
+    body: String,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+
+    let format = match query.format.as_deref() {
+        Some("lcov") => CoverageFormat::Lcov,
+        Some("cobertura") => CoverageFormat::Cobertura,
+        Some("jacoco") => CoverageFormat::Jacoco,
+        Some(other) => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Unsupported coverage format '{}', expected lcov, cobertura or jacoco", other),
+        }),
+        None => match detect_format(&body) {
+            Some(f) => f,
+            None => return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Could not detect coverage format, pass ?format=lcov|cobertura|jacoco".to_string(),
+            }),
+        },
+    };
+
+    // Resolve reported paths against the files seen during the last analysis
+    let known_files: Vec<String> = state.code_repo.get_by_repository(&repository_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| e.file_path)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let parsed = CoverageParser::new().parse(&body, format, "upload");
+    let files = resolve_paths(parsed, &known_files);
+
+    match state.coverage_repo.store_coverage(&repository_id, "upload", &files) {
+        Ok(_) => HttpResponse::Created().json(serde_json::json!({
+            "repository_id": repository_id,
+            "files": files.len(),
+            "resolved_files": files.iter().filter(|f| known_files.contains(&f.file_path)).count(),
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,77 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::data_pipeline_repo::StoredDataPipeline;
+
+/// A dataset with the tasks that write and read it, as `pipeline / task`
+#[derive(Serialize, Default)]
+pub struct DatasetLineage {
+    pub name: String,
+    /// Warehouse table of a dbt source
+    pub relation: Option<String>,
+    pub producers: Vec<String>,
+    pub consumers: Vec<String>,
+    /// Pipelines the dataset's updates schedule
+    pub triggers: Vec<String>,
+    /// dbt exposures (dashboards, notebooks, applications) built on the dataset
+    pub exposures: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct DataPipelinesResponse {
+    pub pipelines: Vec<StoredDataPipeline>,
+    pub datasets: Vec<DatasetLineage>,
+}
+
+/// Airflow DAGs, dbt projects, Dagster jobs/assets and Prefect flows with their tasks, and the
+/// datasets that connect them, down to the dbt exposures that consume them. `?orchestrator=airflow|dbt|dagster|prefect` narrows the pipelines.
+pub async fn get_data_pipelines(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let mut pipelines = match state.data_pipeline_repo.get_by_repository(&path.into_inner()) {
+        Ok(p) => p,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    if let Some(orchestrator) = query.get("orchestrator") {
+        pipelines.retain(|p| p.orchestrator.eq_ignore_ascii_case(orchestrator));
+    }
+
+    let mut datasets: BTreeMap<String, DatasetLineage> = BTreeMap::new();
+    for pipeline in &pipelines {
+        for task in &pipeline.tasks {
+            let step = format!("{} / {}", pipeline.name, task.name);
+            for output in &task.outputs {
+                lineage(&mut datasets, output).producers.push(step.clone());
+            }
+            for input in &task.inputs {
+                lineage(&mut datasets, input).consumers.push(step.clone());
+            }
+        }
+        for dataset in &pipeline.triggered_by {
+            lineage(&mut datasets, dataset).triggers.push(pipeline.name.clone());
+        }
+        for source in &pipeline.sources {
+            lineage(&mut datasets, &source.name).relation = source.relation.clone();
+        }
+        for exposure in &pipeline.exposures {
+            for dataset in &exposure.depends_on {
+                lineage(&mut datasets, dataset).exposures.push(format!("{} / {}", pipeline.name, exposure.name));
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(DataPipelinesResponse {
+        pipelines,
+        datasets: datasets.into_values().collect(),
+    })
+}
+
+fn lineage<'a>(datasets: &'a mut BTreeMap<String, DatasetLineage>, name: &str) -> &'a mut DatasetLineage {
+    datasets.entry(name.to_string()).or_insert_with(|| DatasetLineage { name: name.to_string(), ..Default::default() })
+}
@@ -1,4 +1,5 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use crate::api::{ApiState, ErrorResponse};
 
@@ -151,3 +152,106 @@ pub async fn search_documentation(
     }
 }
 
+
+/// Onboarding score for the repository's docs: section checks, broken links, staleness and suggested fixes
+pub async fn get_documentation_quality(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.doc_quality_repo.get_latest(&path.into_inner()) {
+        Ok(Some(report)) => HttpResponse::Ok().json(report),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "No documentation quality report yet; analyze the repository first".to_string(),
+        }),
+        Err(e) => {
+            log::error!("Failed to get documentation quality: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Failed to get documentation quality: {}", e),
+            })
+        }
+    }
+}
+
+/// Drift between the docs and detected endpoints, services and env vars, filtered by
+/// `?kind=endpoint|service|env_var` and `?direction=undetected|undocumented`
+pub async fn get_documentation_drift(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.doc_quality_repo.get_latest(&path.into_inner()) {
+        Ok(Some(quality)) => {
+            let drift: Vec<_> = quality.report.drift.into_iter()
+                .filter(|d| query.get("kind").map(|k| d.kind.as_str() == k).unwrap_or(true))
+                .filter(|d| query.get("direction").map(|dir| d.direction.as_str() == dir).unwrap_or(true))
+                .collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "total": drift.len(),
+                "drift": drift,
+                "analyzed_at": quality.created_at,
+            }))
+        }
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "No documentation report yet; analyze the repository first".to_string(),
+        }),
+        Err(e) => {
+            log::error!("Failed to get documentation drift: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Failed to get documentation drift: {}", e),
+            })
+        }
+    }
+}
+
+/// Links and references extracted from docs, filtered by `?kind=` and `?doc=` (documentation path)
+pub async fn get_documentation_links(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.doc_link_repo.get_by_repository(&path.into_inner()) {
+        Ok(links) => {
+            let filtered: Vec<_> = links.into_iter()
+                .filter(|l| query.get("kind").map(|k| l.kind.eq_ignore_ascii_case(k)).unwrap_or(true))
+                .filter(|l| query.get("doc").map(|d| &l.doc_path == d).unwrap_or(true))
+                .collect();
+            HttpResponse::Ok().json(filtered)
+        }
+        Err(e) => {
+            log::error!("Failed to get documentation links: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Failed to get documentation links: {}", e),
+            })
+        }
+    }
+}
+
+/// Docs referencing `?file=` (a file or directory) and/or `?component=` (element or service name),
+/// i.e. the docs that may need updating when that code changes
+pub async fn get_documentation_references(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let file = query.get("file").map(|f| f.trim_start_matches("./").trim_end_matches('/'));
+    let component = query.get("component").map(|c| c.as_str());
+    if file.is_none() && component.is_none() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Provide a 'file' or 'component' query parameter".to_string(),
+        });
+    }
+
+    match state.doc_link_repo.get_referencing(&path.into_inner(), file, component) {
+        Ok(links) => HttpResponse::Ok().json(links),
+        Err(e) => {
+            log::error!("Failed to get documentation references: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Failed to get documentation references: {}", e),
+            })
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Lookups for editor extensions, keyed by the file and line the cursor is on. Paths in
+//! responses are relative to the repository root so they resolve in any checkout.
+
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::analysis::{find_env_var, CodeElement, EnvVarReference, RelationshipTargetType};
+use crate::api::{ApiState, ErrorResponse};
+use crate::ingestion::RepositoryCrawler;
+use crate::report::editor_links::repository_root;
+use crate::storage::{Repository, StoredEndpoint, StoredService};
+
+/// A service or package a code element uses
+#[derive(Debug, Serialize)]
+pub struct UsedTarget {
+    pub target_type: String,
+    pub target_id: String,
+    pub name: String,
+    pub relationship_type: String,
+    pub confidence: f64,
+    pub evidence: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HoverInfo {
+    pub file_path: String,
+    pub line: usize,
+    /// The innermost element starting at or above the line
+    pub element: Option<CodeElement>,
+    pub uses: Vec<UsedTarget>,
+    pub endpoints: Vec<StoredEndpoint>,
+    pub services: Vec<StoredService>,
+    /// Ready to show as a hover
+    pub markdown: String,
+}
+
+/// One line worth a code lens or gutter marker
+#[derive(Debug, Serialize)]
+pub struct FileAnnotation {
+    pub line_number: usize,
+    /// "code_element", "endpoint" or "service"
+    pub kind: String,
+    pub label: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceUsage {
+    pub file_path: String,
+    pub line_number: usize,
+    pub element: String,
+    pub relationship_type: String,
+    pub confidence: f64,
+    pub evidence: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceUsages {
+    pub services: Vec<StoredService>,
+    /// Code that uses the service, from code relationships
+    pub usages: Vec<ServiceUsage>,
+}
+
+/// Stored paths, repository-relative; endpoints and services keep the checkout prefix
+struct PathResolver {
+    roots: Vec<String>,
+}
+
+impl PathResolver {
+    fn new(state: &ApiState, repository: &Repository) -> Self {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        if let Ok(crawler) = RepositoryCrawler::new(&state.config.storage) {
+            roots.push(crawler.get_repo_path(&repository.url));
+        }
+        roots.extend(repository_root(&state.config.storage, &repository.url));
+        PathResolver {
+            roots: roots.iter().map(|r| normalize(&r.to_string_lossy()).trim_end_matches('/').to_string()).collect(),
+        }
+    }
+
+    fn relative(&self, path: &str) -> String {
+        let path = normalize(path);
+        self.roots.iter()
+            .find_map(|root| path.strip_prefix(root.as_str()).and_then(|p| p.strip_prefix('/')))
+            .unwrap_or(&path)
+            .to_string()
+    }
+
+    /// The editor may send an absolute path in its own checkout, so match on the relative suffix
+    fn matches(&self, requested: &str, stored: &str) -> bool {
+        let requested = normalize(requested);
+        let stored = self.relative(stored);
+        !stored.is_empty() && (requested == stored || requested.ends_with(&format!("/{}", stored)))
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+fn find_repository(state: &ApiState, repository_id: &str) -> Result<Repository, HttpResponse> {
+    match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(repository)) => Ok(repository),
+        Ok(None) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        })),
+        Err(e) => Err(HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        })),
+    }
+}
+
+fn required<'a>(query: &'a HashMap<String, String>, name: &str) -> Result<&'a str, HttpResponse> {
+    query.get(name).map(|v| v.as_str()).filter(|v| !v.is_empty()).ok_or_else(|| HttpResponse::BadRequest().json(ErrorResponse {
+        error: format!("'{}' query parameter is required", name),
+    }))
+}
+
+fn element_kind(element: &CodeElement) -> String {
+    format!("{:?}", element.element_type).to_lowercase()
+}
+
+/// What a file's elements use, with relationship targets resolved to names
+fn targets_by_element(state: &ApiState, repository_id: &str) -> anyhow::Result<HashMap<String, Vec<UsedTarget>>> {
+    let services: HashMap<String, String> = state.service_repo.get_by_repository(repository_id)?
+        .into_iter().map(|s| (s.id, s.name)).collect();
+    let dependencies: HashMap<String, String> = state.dep_repo.get_by_repository(repository_id)?
+        .into_iter().map(|d| (d.id, format!("{} {}", d.name, d.version))).collect();
+    let mut targets: HashMap<String, Vec<UsedTarget>> = HashMap::new();
+    for relationship in state.code_relationship_repo.get_by_repository(repository_id)? {
+        let (target_type, name) = match relationship.target_type {
+            RelationshipTargetType::Service => ("service", services.get(&relationship.target_id)),
+            RelationshipTargetType::Dependency => ("dependency", dependencies.get(&relationship.target_id)),
+        };
+        targets.entry(relationship.code_element_id).or_default().push(UsedTarget {
+            target_type: target_type.to_string(),
+            name: name.cloned().unwrap_or_else(|| relationship.target_id.clone()),
+            target_id: relationship.target_id,
+            relationship_type: relationship.relationship_type,
+            confidence: relationship.confidence,
+            evidence: relationship.evidence,
+        });
+    }
+    Ok(targets)
+}
+
+fn hover_markdown(element: Option<&CodeElement>, uses: &[UsedTarget], endpoints: &[StoredEndpoint], services: &[StoredService]) -> String {
+    let mut lines = Vec::new();
+    if let Some(element) = element {
+        let signature = element.signature.as_deref().unwrap_or(&element.name);
+        lines.push(format!("**{}** `{}`", element_kind(element), signature));
+    }
+    if !uses.is_empty() {
+        let names: Vec<String> = uses.iter()
+            .map(|u| format!("{} ({}, {:.0}%)", u.name, u.target_type, u.confidence * 100.0))
+            .collect();
+        lines.push(format!("Uses: {}", names.join(", ")));
+    }
+    if !endpoints.is_empty() {
+        let routes: Vec<String> = endpoints.iter().map(|e| format!("`{} {}`", e.method, e.path)).collect();
+        lines.push(format!("Endpoints: {}", routes.join(", ")));
+    }
+    if !services.is_empty() {
+        let names: Vec<String> = services.iter().map(|s| format!("{} ({})", s.name, s.service_type)).collect();
+        lines.push(format!("Services: {}", names.join(", ")));
+    }
+    lines.join("\n\n")
+}
+
+/// `?file=<path>&line=<n>`: the element under the cursor, what it uses, and the endpoints and
+/// services declared between it and the next element
+pub async fn get_hover(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let repository = match find_repository(&state, &repository_id) {
+        Ok(repository) => repository,
+        Err(response) => return response,
+    };
+    let file = match required(&query, "file") {
+        Ok(file) => file,
+        Err(response) => return response,
+    };
+    let line = match query.get("line").map(|l| l.parse::<usize>()) {
+        Some(Ok(line)) => line,
+        _ => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "'line' query parameter must be a line number".to_string(),
+        }),
+    };
+    let resolver = PathResolver::new(&state, &repository);
+
+    let result = (|| -> anyhow::Result<HoverInfo> {
+        let elements: Vec<CodeElement> = state.code_repo.get_by_repository(&repository_id)?
+            .into_iter()
+            .filter(|e| resolver.matches(file, &e.file_path))
+            .collect();
+        let element = elements.iter().filter(|e| e.line_number <= line).max_by_key(|e| e.line_number).cloned();
+        let start = element.as_ref().map(|e| e.line_number).unwrap_or(1);
+        let end = elements.iter().map(|e| e.line_number).filter(|l| *l > line).min().unwrap_or(usize::MAX);
+        let in_span = |l: Option<usize>| l.is_some_and(|l| l >= start && l < end);
+
+        let uses = match &element {
+            Some(element) => targets_by_element(&state, &repository_id)?.remove(&element.id).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let endpoints: Vec<StoredEndpoint> = state.endpoint_repo.get_by_repository(&repository_id)?
+            .into_iter()
+            .filter(|e| resolver.matches(file, &e.file_path) && in_span(e.line_number))
+            .collect();
+        let services: Vec<StoredService> = state.service_repo.get_by_repository(&repository_id)?
+            .into_iter()
+            .filter(|s| resolver.matches(file, &s.file_path) && in_span(s.line_number))
+            .collect();
+        Ok(HoverInfo {
+            file_path: elements.first().map(|e| resolver.relative(&e.file_path)).unwrap_or_else(|| normalize(file)),
+            line,
+            markdown: hover_markdown(element.as_ref(), &uses, &endpoints, &services),
+            element,
+            uses,
+            endpoints,
+            services,
+        })
+    })();
+    match result {
+        Ok(hover) => HttpResponse::Ok().json(hover),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// `?file=<path>`: everything known about a file, by line, for code lenses
+pub async fn get_file_annotations(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let repository = match find_repository(&state, &repository_id) {
+        Ok(repository) => repository,
+        Err(response) => return response,
+    };
+    let file = match required(&query, "file") {
+        Ok(file) => file,
+        Err(response) => return response,
+    };
+    let resolver = PathResolver::new(&state, &repository);
+
+    let result = (|| -> anyhow::Result<Vec<FileAnnotation>> {
+        let mut targets = targets_by_element(&state, &repository_id)?;
+        let mut annotations = Vec::new();
+        for element in state.code_repo.get_by_repository(&repository_id)? {
+            if !resolver.matches(file, &element.file_path) {
+                continue;
+            }
+            let uses = targets.remove(&element.id).unwrap_or_default();
+            annotations.push(FileAnnotation {
+                line_number: element.line_number,
+                kind: "code_element".to_string(),
+                label: format!("{} {}", element_kind(&element), element.name),
+                detail: (!uses.is_empty()).then(|| {
+                    format!("uses {}", uses.iter().map(|u| u.name.as_str()).collect::<Vec<_>>().join(", "))
+                }),
+            });
+        }
+        for endpoint in state.endpoint_repo.get_by_repository(&repository_id)? {
+            if let (true, Some(line_number)) = (resolver.matches(file, &endpoint.file_path), endpoint.line_number) {
+                annotations.push(FileAnnotation {
+                    line_number,
+                    kind: "endpoint".to_string(),
+                    label: format!("{} {}", endpoint.method, endpoint.path),
+                    detail: endpoint.handler,
+                });
+            }
+        }
+        for service in state.service_repo.get_by_repository(&repository_id)? {
+            if let (true, Some(line_number)) = (resolver.matches(file, &service.file_path), service.line_number) {
+                annotations.push(FileAnnotation {
+                    line_number,
+                    kind: "service".to_string(),
+                    label: service.name,
+                    detail: Some(format!("{} ({})", service.provider, service.service_type)),
+                });
+            }
+        }
+        annotations.sort_by_key(|a| a.line_number);
+        Ok(annotations)
+    })();
+    match result {
+        Ok(annotations) => HttpResponse::Ok().json(annotations),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Who uses this service: `{service}` is a service id or name (case-insensitive)
+pub async fn get_service_usages(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (repository_id, service) = path.into_inner();
+    let repository = match find_repository(&state, &repository_id) {
+        Ok(repository) => repository,
+        Err(response) => return response,
+    };
+    let resolver = PathResolver::new(&state, &repository);
+
+    let result = (|| -> anyhow::Result<ServiceUsages> {
+        let services: Vec<StoredService> = state.service_repo.get_by_repository(&repository_id)?
+            .into_iter()
+            .filter(|s| s.id == service || s.name.eq_ignore_ascii_case(&service))
+            .collect();
+        let elements: HashMap<String, CodeElement> = state.code_repo.get_by_repository(&repository_id)?
+            .into_iter().map(|e| (e.id.clone(), e)).collect();
+        let mut usages = Vec::new();
+        for stored in &services {
+            for relationship in state.code_relationship_repo.get_by_target(&repository_id, "service", &stored.id)? {
+                if let Some(element) = elements.get(&relationship.code_element_id) {
+                    usages.push(ServiceUsage {
+                        file_path: resolver.relative(&element.file_path),
+                        line_number: element.line_number,
+                        element: element.name.clone(),
+                        relationship_type: relationship.relationship_type,
+                        confidence: relationship.confidence,
+                        evidence: relationship.evidence,
+                    });
+                }
+            }
+        }
+        usages.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+        usages.dedup_by(|a, b| a.file_path == b.file_path && a.line_number == b.line_number);
+        let services = services.into_iter()
+            .map(|s| StoredService { file_path: resolver.relative(&s.file_path), ..s })
+            .collect();
+        Ok(ServiceUsages { services, usages })
+    })();
+    match result {
+        Ok(usages) if usages.services.is_empty() => HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No service '{}' in this repository", service),
+        }),
+        Ok(usages) => HttpResponse::Ok().json(usages),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Where an environment variable is defined (env files, Dockerfiles, compose files) and read
+pub async fn get_env_var_references(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (repository_id, name) = path.into_inner();
+    let repository = match find_repository(&state, &repository_id) {
+        Ok(repository) => repository,
+        Err(response) => return response,
+    };
+    let repo_path = match RepositoryCrawler::new(&state.config.storage) {
+        Ok(crawler) => crawler.get_repo_path(&repository.url),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let mut code_files: Vec<String> = match state.code_repo.get_by_repository(&repository_id) {
+        Ok(elements) => elements.into_iter().map(|e| e.file_path).collect(),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    code_files.dedup();
+
+    let scanned = web::block(move || -> Vec<EnvVarReference> {
+        let files: Vec<&str> = code_files.iter().map(|f| f.as_str()).collect();
+        find_env_var(Path::new(&repo_path), &name, &files)
+    }).await;
+    match scanned {
+        Ok(references) => HttpResponse::Ok().json(references),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,133 @@
+use actix_web::{web, HttpResponse, Responder};
+use chrono::{Duration, Utc};
+use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
+use crate::integrations::email::EmailChannel;
+use crate::report::digest::{self, DigestEntry, EmailBody};
+use crate::storage::Repository;
+
+async fn channel(state: &ApiState) -> anyhow::Result<EmailChannel> {
+    let password = match state.config.email.password.clone() {
+        Some(password) => Some(web::block(move || crate::ingestion::secrets::resolve(&password)).await??),
+        None => None,
+    };
+    EmailChannel::new(&state.config.email, password.as_deref())
+}
+
+/// The digest of every analyzed repository, or of those in `workspace`, marking those analyzed in
+/// the last `hours`
+fn build_digest(state: &ApiState, workspace: Option<&str>, hours: u64) -> anyhow::Result<EmailBody> {
+    let repositories = match workspace {
+        Some(workspace) => state.repo_repo.list_by_workspace(workspace)?,
+        None => state.repo_repo.list_all()?,
+    };
+    let mut entries = Vec::new();
+    for repository in repositories.into_iter().filter(|r| r.last_analyzed_at.is_some()) {
+        let mut entry = DigestEntry {
+            dependencies: state.dep_repo.get_by_repository(&repository.id)?.len(),
+            services: state.service_repo.get_by_repository(&repository.id)?.len(),
+            endpoints: state.endpoint_repo.get_by_repository(&repository.id)?.len(),
+            critical: 0,
+            high: 0,
+            other_vulnerabilities: 0,
+            repository,
+        };
+        let vulnerabilities = state.security_repo.get_vulnerabilities(&entry.repository.id)?;
+        entry.count_vulnerabilities(vulnerabilities.iter().map(|v| &v.severity));
+        entries.push(entry);
+    }
+    let since = Utc::now() - Duration::hours(hours.max(1) as i64);
+    Ok(digest::digest(&entries, since, &state.config.server.base_url()))
+}
+
+/// Sends a digest every `hours`, starting one interval after startup
+pub(crate) async fn send_digest_on_schedule(state: web::Data<ApiState>, hours: u64) {
+    let period = std::time::Duration::from_secs(hours * 3600);
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    loop {
+        interval.tick().await;
+        let sent = match build_digest(&state, None, hours) {
+            Ok(body) => match channel(&state).await {
+                Ok(channel) => channel.send(&body).await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(()) => log::info!("✓ Sent email digest to {}", state.config.email.to.join(", ")),
+            Err(e) => log::warn!("⚠ Email digest failed: {:#}", e),
+        }
+    }
+}
+
+pub(crate) fn failure_alerts_enabled(state: &ApiState) -> bool {
+    state.config.email.is_configured() && state.config.email.alert_on_failure && !crate::network::is_offline()
+}
+
+/// Emails the failure of an analysis in the background, when failure alerts are on
+pub(crate) fn spawn_failure_alert(state: &web::Data<ApiState>, repository_id: &str, error: String) {
+    if !failure_alerts_enabled(state) {
+        return;
+    }
+    let repository = match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(repository)) => repository,
+        _ => return,
+    };
+    let state = state.clone();
+    actix_web::rt::spawn(async move { send_failure_alert(&state, &repository, &error).await });
+}
+
+pub(crate) async fn send_failure_alert(state: &ApiState, repository: &Repository, error: &str) {
+    let body = digest::failure_alert(repository, error, &state.config.server.base_url());
+    let sent = match channel(state).await {
+        Ok(channel) => channel.send(&body).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = sent {
+        log::warn!("⚠ Failure alert for {} was not sent: {:#}", repository.name, e);
+    }
+}
+
+/// Preview the digest of the caller's workspace as HTML without sending it
+pub async fn preview_digest(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+) -> impl Responder {
+    match build_digest(&state, Some(&workspace.0), state.config.email.digest_interval_hours) {
+        Ok(body) => HttpResponse::Ok().content_type("text/html; charset=utf-8").body(body.html),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Send the digest of the caller's workspace now
+pub async fn send_digest(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+) -> impl Responder {
+    if !state.config.email.is_configured() {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "Email is not configured; set SMTP_HOST, EMAIL_FROM and EMAIL_TO".to_string(),
+        });
+    }
+    let body = match build_digest(&state, Some(&workspace.0), state.config.email.digest_interval_hours) {
+        Ok(body) => body,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let sent = match channel(&state).await {
+        Ok(channel) => channel.send(&body).await,
+        Err(e) => Err(e),
+    };
+    match sent {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "subject": body.subject,
+            "recipients": state.config.email.to,
+        })),
+        Err(e) => HttpResponse::BadGateway().json(ErrorResponse {
+            error: format!("{:#}", e),
+        }),
+    }
+}
@@ -1,5 +1,6 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
 
 // Endpoint endpoints
 pub async fn get_endpoints(
@@ -18,11 +19,12 @@ pub async fn get_endpoints(
 pub async fn search_endpoints(
     state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> impl Responder {
     if let Some(path_pattern) = query.get("path") {
         let repository_id = query.get("repository_id");
-        match state.endpoint_repo.get_by_path(path_pattern, repository_id.map(|s| s.as_str())) {
+        match state.endpoint_repo.get_by_path(path_pattern, &workspace.0, repository_id.map(|s| s.as_str())) {
             Ok(endpoints) => HttpResponse::Ok().json(endpoints),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
@@ -30,7 +32,7 @@ pub async fn search_endpoints(
         }
     } else if let Some(method) = query.get("method") {
         let repository_id = query.get("repository_id");
-        match state.endpoint_repo.get_by_method(method, repository_id.map(|s| s.as_str())) {
+        match state.endpoint_repo.get_by_method(method, &workspace.0, repository_id.map(|s| s.as_str())) {
             Ok(endpoints) => HttpResponse::Ok().json(endpoints),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
@@ -38,7 +40,7 @@ pub async fn search_endpoints(
         }
     } else if let Some(framework) = query.get("framework") {
         let repository_id = query.get("repository_id");
-        match state.endpoint_repo.get_by_framework(framework, repository_id.map(|s| s.as_str())) {
+        match state.endpoint_repo.get_by_framework(framework, &workspace.0, repository_id.map(|s| s.as_str())) {
             Ok(endpoints) => HttpResponse::Ok().json(endpoints),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
@@ -1,13 +1,39 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
+use crate::report::editor_links::{repository_root, EditorLinks};
 use std::collections::HashMap;
+use std::path::Path;
+
+/// Adds an `editor_link` next to every `file_path` in the response
+fn add_editor_links(value: &mut serde_json::Value, links: &EditorLinks, root: &Path) {
+    match value {
+        serde_json::Value::Object(object) => {
+            if let Some(file_path) = object.get("file_path").and_then(|p| p.as_str()).filter(|p| !p.is_empty()) {
+                let line = object.get("line_number").and_then(|l| l.as_u64()).map(|l| l as usize);
+                let link = links.link(root, file_path, line);
+                object.insert("editor_link".to_string(), serde_json::Value::String(link));
+            }
+            for nested in object.values_mut() {
+                add_editor_links(nested, links, root);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                add_editor_links(item, links, root);
+            }
+        }
+        _ => {}
+    }
+}
 
 /// Get entity details and relationships
-/// Supports: dependency, service, code_element, security_entity
+/// Supports: dependency, service, code_element, security_entity.
+/// File references carry an `editor_link` for `EDITOR_PROTOCOL`, or for `?editor=` when given.
 pub async fn get_entity_details(
     state: web::Data<ApiState>,
     _req: HttpRequest,
     path: web::Path<(String, String, String)>, // (repo_id, entity_type, entity_id)
+    query: web::Query<HashMap<String, String>>,
 ) -> impl Responder {
     let (repo_id, entity_type, entity_id) = path.into_inner();
     
@@ -27,6 +53,12 @@ pub async fn get_entity_details(
                         .map(|d| serde_json::to_value(d).unwrap())
                         .collect();
                     details.insert("related_dependencies".to_string(), serde_json::json!(related));
+
+                    // Known advisories from the last OSV lookup
+                    if let Ok(vulnerabilities) = state.security_repo.get_vulnerabilities(&repo_id) {
+                        let advisories: Vec<_> = vulnerabilities.into_iter().filter(|v| v.entity_id == entity_id).collect();
+                        details.insert("vulnerabilities".to_string(), serde_json::json!(advisories));
+                    }
                 }
             }
         },
@@ -276,6 +308,15 @@ pub async fn get_entity_details(
         }
     }
     
+    let root = state.repo_repo.find_by_id(&repo_id).ok().flatten()
+        .and_then(|repo| repository_root(&state.config.storage, &repo.url));
+    if let Some(root) = root {
+        let links = EditorLinks::for_request(&state.config.server.editor_protocol, query.get("editor"));
+        for value in details.values_mut() {
+            add_editor_links(value, &links, &root);
+        }
+    }
+
     if details.is_empty() {
         HttpResponse::NotFound().json(ErrorResponse {
             error: format!("Entity not found: ID '{}' (type: '{}', repo: '{}'). This may be a short ID - ensure you're using the full UUID.", entity_id, entity_type, repo_id),
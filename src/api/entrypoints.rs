@@ -0,0 +1,16 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// Entrypoint endpoints
+pub async fn get_entrypoints(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.entrypoint_repo.get_by_repository(&path.into_inner()) {
+        Ok(entrypoints) => HttpResponse::Ok().json(entrypoints),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,102 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use crate::analysis::environment_matrix::DEFAULT_ENVIRONMENT;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::StoredEnvironmentValue;
+
+#[derive(Serialize)]
+pub struct EnvironmentCell {
+    pub value: String,
+    pub inherited: bool, // Taken from `.env`/`values.yaml` because the environment does not set it
+    pub source: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct EnvironmentSetting {
+    pub key: String,
+    pub kind: String,
+    pub differs: bool,
+    pub values: BTreeMap<String, Option<EnvironmentCell>>, // Environment -> value, None when unset
+}
+
+#[derive(Serialize)]
+pub struct EnvironmentMatrixResponse {
+    pub environments: Vec<String>,
+    pub differing: usize,
+    pub settings: Vec<EnvironmentSetting>,
+}
+
+/// Settings per environment (dev/staging/prod, ...) from `.env` files, Helm values and CI variables.
+/// `?kind=endpoint|service|config` and `?differs=true` narrow the matrix.
+pub async fn get_environment_matrix(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let values = match state.environment_repo.get_by_repository(&path.into_inner()) {
+        Ok(v) => v,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let environments: Vec<String> = values.iter()
+        .map(|v| v.environment.clone())
+        .filter(|env| env != DEFAULT_ENVIRONMENT)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    // Later rows win, matching how the detector resolves repeated keys
+    let mut by_key: BTreeMap<&str, HashMap<&str, &StoredEnvironmentValue>> = BTreeMap::new();
+    for value in &values {
+        by_key.entry(value.key.as_str()).or_default().insert(value.environment.as_str(), value);
+    }
+
+    let mut settings = Vec::new();
+    for (key, per_environment) in by_key {
+        let mut cells = BTreeMap::new();
+        for environment in &environments {
+            let (stored, inherited) = match per_environment.get(environment.as_str()) {
+                Some(v) => (Some(*v), false),
+                None => (per_environment.get(DEFAULT_ENVIRONMENT).copied(), true),
+            };
+            cells.insert(environment.clone(), stored.map(|v| EnvironmentCell {
+                value: v.value.clone(),
+                inherited,
+                source: v.source.clone(),
+                file_path: v.file_path.clone(),
+                line_number: v.line_number,
+            }));
+        }
+        let distinct: BTreeSet<Option<&str>> = cells.values()
+            .map(|c| c.as_ref().map(|c| c.value.as_str()))
+            .collect();
+        let kind = ["service", "endpoint"].into_iter()
+            .find(|k| per_environment.values().any(|v| v.kind == *k))
+            .unwrap_or("config");
+        settings.push(EnvironmentSetting {
+            key: key.to_string(),
+            kind: kind.to_string(),
+            differs: distinct.len() > 1,
+            values: cells,
+        });
+    }
+
+    let differing = settings.iter().filter(|s| s.differs).count();
+    let differs_only = query.get("differs").map(|d| d == "true").unwrap_or(false);
+    let settings = settings.into_iter()
+        .filter(|s| query.get("kind").map(|k| s.kind.eq_ignore_ascii_case(k)).unwrap_or(true))
+        .filter(|s| !differs_only || s.differs)
+        .collect();
+
+    HttpResponse::Ok().json(EnvironmentMatrixResponse {
+        environments,
+        differing,
+        settings,
+    })
+}
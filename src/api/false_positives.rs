@@ -0,0 +1,139 @@
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::analysis::false_positives::suggest_fixes;
+use crate::analysis::{Detection, VerdictKind};
+use crate::api::{ApiState, ErrorResponse};
+
+#[derive(Debug, Deserialize)]
+pub struct MarkFalsePositiveRequest {
+    pub kind: VerdictKind,
+    /// Id of the stored service, vulnerability or code relationship
+    pub id: String,
+    pub reason: Option<String>,
+}
+
+fn internal_error(e: impl ToString) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: e.to_string(),
+    })
+}
+
+fn repository_exists(state: &ApiState, repository_id: &str) -> Result<(), HttpResponse> {
+    match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        })),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+/// What the last analysis stored of the kind, by stored id
+fn detections(state: &ApiState, repository_id: &str, kind: VerdictKind) -> anyhow::Result<Vec<(String, Detection)>> {
+    Ok(match kind {
+        VerdictKind::Service => state.service_repo.get_by_repository(repository_id)?
+            .into_iter()
+            .map(|s| {
+                let detection = Detection::service(&s.provider, &s.name, &s.file_path, s.confidence);
+                (s.id, detection)
+            })
+            .collect(),
+        VerdictKind::Vulnerability => state.security_repo.get_vulnerabilities(repository_id)?
+            .into_iter()
+            .map(|v| (v.id.clone(), Detection::vulnerability(&v)))
+            .collect(),
+        VerdictKind::Relationship => {
+            let elements = state.code_repo.get_by_repository(repository_id)?;
+            let elements: HashMap<&str, _> = elements.iter().map(|e| (e.id.as_str(), e)).collect();
+            let services = state.service_repo.get_by_repository(repository_id)?;
+            let dependencies = state.dep_repo.get_by_repository(repository_id)?;
+            state.code_relationship_repo.get_by_repository(repository_id)?
+                .into_iter()
+                .filter_map(|r| {
+                    let element = elements.get(r.code_element_id.as_str()).copied();
+                    let detection = Detection::relationship(&r, element, &services, &dependencies)?;
+                    Some((r.id, detection))
+                })
+                .collect()
+        }
+    })
+}
+
+/// Mark a detection of the repository's last analysis as a false positive; later analyses leave it out
+pub async fn mark_false_positive(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+    body: web::Json<MarkFalsePositiveRequest>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = repository_exists(&state, &repository_id) {
+        return response;
+    }
+    let detection = match detections(&state, &repository_id, body.kind) {
+        Ok(detections) => detections.into_iter().find(|(id, _)| *id == body.id).map(|(_, d)| d),
+        Err(e) => return internal_error(e),
+    };
+    let Some(detection) = detection else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No {} with id {} in the repository's last analysis", body.kind.as_str(), body.id),
+        });
+    };
+
+    match state.false_positive_repo.mark(&repository_id, &detection, body.reason.as_deref()) {
+        Ok(verdict) => HttpResponse::Created().json(verdict),
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn list_false_positives(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.false_positive_repo.get_by_repository(&path.into_inner()) {
+        Ok(verdicts) => HttpResponse::Ok().json(verdicts),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Withdraw a verdict; the next analysis reports the detection again
+pub async fn delete_false_positive(
+    state: web::Data<ApiState>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (repository_id, verdict_id) = path.into_inner();
+    match state.false_positive_repo.delete(&repository_id, &verdict_id) {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "False positive not found".to_string(),
+        }),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// `.wavelength.yml` changes that generalize the repository's verdicts
+pub async fn suggest_false_positive_fixes(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = repository_exists(&state, &repository_id) {
+        return response;
+    }
+    let verdicts = match state.false_positive_repo.get_by_repository(&repository_id) {
+        Ok(verdicts) => verdicts,
+        Err(e) => return internal_error(e),
+    };
+    let marked: std::collections::HashSet<&str> = verdicts.iter().map(|v| v.fingerprint.as_str()).collect();
+    let mut unmarked = Vec::new();
+    for kind in [VerdictKind::Service, VerdictKind::Relationship] {
+        match detections(&state, &repository_id, kind) {
+            Ok(detections) => unmarked.extend(
+                detections.into_iter().map(|(_, d)| d).filter(|d| !marked.contains(d.fingerprint().as_str()))
+            ),
+            Err(e) => return internal_error(e),
+        }
+    }
+
+    HttpResponse::Ok().json(suggest_fixes(&verdicts, &unmarked))
+}
@@ -0,0 +1,16 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// Frontend route endpoints
+pub async fn get_frontend_routes(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.frontend_route_repo.get_by_repository(&path.into_inner()) {
+        Ok(routes) => HttpResponse::Ok().json(routes),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use crate::analysis::{evaluate_gate, GateInput, GateResult};
+use crate::api::{ApiState, ErrorResponse};
+use crate::api::schemas::SchemaVersionQuery;
+use crate::api::workspaces::CurrentWorkspace;
+use crate::config::GateConfig;
+use crate::report::schema::{versioned, SchemaKind};
+
+/// Thresholds a single gate run sets instead of the configured ones
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GateOverrides {
+    pub max_critical: Option<usize>,
+    pub max_high: Option<usize>,
+    pub allow_new_services: Option<bool>,
+    pub max_dependency_staleness_days: Option<u64>,
+}
+
+impl GateOverrides {
+    pub fn apply(&self, config: &GateConfig) -> GateConfig {
+        GateConfig {
+            max_critical: self.max_critical.unwrap_or(config.max_critical),
+            max_high: self.max_high.or(config.max_high),
+            allow_new_services: self.allow_new_services.unwrap_or(config.allow_new_services),
+            max_dependency_staleness_days: self.max_dependency_staleness_days.or(config.max_dependency_staleness_days),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GateRequest {
+    pub repository_id: String,
+    /// Compare services with this repository's latest analysis, e.g. the main branch when gating a
+    /// feature branch, instead of with the previous analysis of `repository_id`
+    pub baseline_repository_id: Option<String>,
+    #[serde(flatten)]
+    pub overrides: GateOverrides,
+}
+
+/// Evaluates the latest stored analysis of a repository
+pub(crate) fn run_gate(state: &ApiState, repository_id: &str, baseline_repository_id: Option<&str>, policy: &GateConfig) -> anyhow::Result<GateResult> {
+    let mut snapshots = state.release_repo.get_snapshots(repository_id, 2)?;
+    let current = snapshots.pop();
+    let baseline = match baseline_repository_id {
+        Some(baseline_id) => state.release_repo.get_snapshots(baseline_id, 1)?.pop(),
+        None => snapshots.pop(),
+    };
+    let vulnerabilities = state.security_repo.get_vulnerabilities(repository_id)?;
+    let dependency_staleness_days = state.tech_debt_repo.get_latest(repository_id)?
+        .and_then(|debt| debt.subscores.get("dependency_staleness")?.metrics.get("days_since_manifest_update").copied())
+        .filter(|days| *days >= 0.0);
+
+    Ok(evaluate_gate(policy, &GateInput {
+        vulnerabilities: &vulnerabilities,
+        current: current.as_ref(),
+        baseline: baseline.as_ref(),
+        dependency_staleness_days,
+    }))
+}
+
+/// Gate a pipeline on the repository's latest analysis: 200 when every check passes, 422 otherwise
+pub async fn check_gate(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    query: web::Query<SchemaVersionQuery>,
+    body: web::Json<GateRequest>,
+) -> impl Responder {
+    let schema_version = match query.resolve(&state) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+    for id in std::iter::once(&body.repository_id).chain(body.baseline_repository_id.as_ref()) {
+        match state.repo_repo.find_by_id(id) {
+            // The ids come from the body, which the workspace middleware does not see
+            Ok(Some(repository)) if repository.workspace_id != workspace.0 => return HttpResponse::NotFound().json(ErrorResponse {
+                error: format!("Repository {} not found", id),
+            }),
+            Ok(Some(repository)) if repository.last_analyzed_at.is_some() => {}
+            Ok(Some(repository)) => return HttpResponse::NotFound().json(ErrorResponse {
+                error: format!("{} has not been analyzed yet", repository.name),
+            }),
+            Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+                error: format!("Repository {} not found", id),
+            }),
+            Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let policy = body.overrides.apply(&state.config.gate);
+    let result = run_gate(&state, &body.repository_id, body.baseline_repository_id.as_deref(), &policy)
+        .and_then(|result| Ok((result.passed, versioned(SchemaKind::Gate, &result, schema_version)?)));
+    match result {
+        Ok((true, document)) => HttpResponse::Ok().json(document),
+        Ok((false, document)) => HttpResponse::UnprocessableEntity().json(document),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
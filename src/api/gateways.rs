@@ -0,0 +1,55 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::StoredGatewayRoute;
+
+#[derive(Serialize)]
+pub struct Gateway {
+    pub name: String,
+    pub kind: String,
+    pub file_path: String,
+    pub upstreams: usize,
+    pub routes: Vec<StoredGatewayRoute>,
+}
+
+/// API gateways declared in the repository (Kong, Apollo Federation, AWS API Gateway) with the
+/// routes they send to each upstream. `?kind=kong|apollo_federation|aws_api_gateway` narrows them.
+pub async fn get_gateways(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let routes = match state.gateway_repo.get_by_repository(&path.into_inner()) {
+        Ok(r) => r,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let mut gateways: Vec<Gateway> = Vec::new();
+    for route in routes {
+        if !query.get("kind").map(|k| route.kind.eq_ignore_ascii_case(k)).unwrap_or(true) {
+            continue;
+        }
+        match gateways.iter_mut().find(|g| g.name == route.gateway && g.kind == route.kind) {
+            Some(gateway) => gateway.routes.push(route),
+            None => gateways.push(Gateway {
+                name: route.gateway.clone(),
+                kind: route.kind.clone(),
+                file_path: route.file_path.clone(),
+                upstreams: 0,
+                routes: vec![route],
+            }),
+        }
+    }
+    for gateway in &mut gateways {
+        let mut upstreams: Vec<&str> = gateway.routes.iter().map(|r| r.upstream.as_str()).collect();
+        upstreams.sort();
+        upstreams.dedup();
+        gateway.upstreams = upstreams.len();
+    }
+
+    HttpResponse::Ok().json(gateways)
+}
@@ -1,13 +1,21 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
+use crate::api::schemas::SchemaVersionQuery;
 use crate::graph::GraphBuilder;
+use crate::graph::neo4j::{cypher_script, Neo4jExporter, Neo4jPushSummary};
+use crate::report::schema::{versioned, SchemaKind};
 
-/// Get knowledge graph for a repository
+/// Get knowledge graph for a repository, as a versioned document
 pub async fn get_graph(
     state: web::Data<ApiState>,
     _req: HttpRequest,
     path: web::Path<String>,
+    query: web::Query<SchemaVersionQuery>,
 ) -> impl Responder {
+    let schema_version = match query.resolve(&state) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
     // API key validation removed for local tool simplicity
     let repository_id = path.into_inner();
     let graph_builder = GraphBuilder::new(
@@ -22,7 +30,7 @@ pub async fn get_graph(
         state.endpoint_repo.clone(),
     );
 
-    match graph_builder.get_graph(&repository_id) {
+    match graph_builder.get_graph(&repository_id).and_then(|graph| versioned(SchemaKind::Graph, &graph, schema_version)) {
         Ok(graph) => HttpResponse::Ok().json(graph),
         Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
             error: e.to_string(),
@@ -89,3 +97,95 @@ pub async fn get_node_neighbors(
     }
 }
 
+
+fn graph_builder(state: &ApiState) -> GraphBuilder {
+    GraphBuilder::new(
+        state.repo_repo.db.clone(),
+        state.repo_repo.clone(),
+        state.dep_repo.clone(),
+        state.service_repo.clone(),
+        state.tool_repo.clone(),
+        state.code_relationship_repo.clone(),
+        state.test_repo.clone(),
+        state.port_repo.clone(),
+        state.endpoint_repo.clone(),
+    )
+}
+
+async fn push_to_neo4j(state: &ApiState, repository_id: &str) -> anyhow::Result<Neo4jPushSummary> {
+    let graph = graph_builder(state).get_graph(repository_id)?;
+    let password = state.config.neo4j.password.clone().unwrap_or_default();
+    let password = web::block(move || crate::ingestion::secrets::resolve(&password)).await??;
+    Neo4jExporter::connect(&state.config.neo4j, &password).await?.push(repository_id, &graph).await
+}
+
+/// After an analysis: writes `<cypher_dir>/<repository id>.cypher` when a directory is set, and
+/// pushes to Neo4j when it is configured for that and the network is available
+pub(crate) async fn export_after_analysis(state: web::Data<ApiState>, repository_id: String) {
+    let config = &state.config.neo4j;
+    if let Some(dir) = &config.cypher_dir {
+        let written = graph_builder(&state).get_graph(&repository_id).and_then(|graph| {
+            std::fs::create_dir_all(dir)?;
+            let path = std::path::Path::new(dir).join(format!("{}.cypher", repository_id));
+            std::fs::write(&path, cypher_script(&repository_id, &graph))?;
+            Ok(path)
+        });
+        match written {
+            Ok(path) => log::info!("✓ Wrote Cypher export to {}", path.display()),
+            Err(e) => log::warn!("⚠ Writing Cypher export failed: {:#}", e),
+        }
+    }
+    if config.is_configured() && config.on_analysis && !crate::network::is_offline() {
+        match push_to_neo4j(&state, &repository_id).await {
+            Ok(summary) => log::info!("✓ Neo4j: pushed {} nodes, {} relationships", summary.nodes, summary.relationships),
+            Err(e) => log::warn!("⚠ Neo4j export failed: {:#}", e),
+        }
+    }
+}
+
+/// The knowledge graph as a Cypher script that replaces the repository's nodes in Neo4j
+pub async fn get_graph_cypher(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    match graph_builder(&state).get_graph(&repository_id) {
+        Ok(graph) => HttpResponse::Ok()
+            .content_type("application/x-cypher-query; charset=utf-8")
+            .insert_header(("Content-Disposition", format!("attachment; filename=\"{}.cypher\"", repository_id)))
+            .body(cypher_script(&repository_id, &graph)),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Push the repository's knowledge graph to the configured Neo4j database now
+pub async fn push_graph_to_neo4j(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if !state.config.neo4j.is_configured() {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "Neo4j is not configured; set NEO4J_URI and NEO4J_PASSWORD".to_string(),
+        });
+    }
+    let repository_id = path.into_inner();
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+    match push_to_neo4j(&state, &repository_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => HttpResponse::BadGateway().json(ErrorResponse {
+            error: format!("{:#}", e),
+        }),
+    }
+}
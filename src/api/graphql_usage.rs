@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// GraphQL client usage endpoints
+pub async fn get_graphql_operations(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.graphql_usage_repo.get_operations(&path.into_inner()) {
+        Ok(operations) => HttpResponse::Ok().json(operations),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+pub async fn get_graphql_schema_types(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.graphql_usage_repo.get_schema_types(&path.into_inner()) {
+        Ok(types) => HttpResponse::Ok().json(types),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Client operations affected by changing a schema type (`?type=User`) or field (`?type=User&field=email`)
+pub async fn get_graphql_impact(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> impl Responder {
+    let type_name = match query.get("type") {
+        Some(t) => t,
+        None => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Missing 'type' query parameter".to_string(),
+        }),
+    };
+
+    match state.graphql_usage_repo.get_operations_using(&path.into_inner(), type_name, query.get("field").map(|s| s.as_str())) {
+        Ok(operations) => HttpResponse::Ok().json(operations),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,115 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use crate::api::{ApiState, ErrorResponse};
+use crate::ingestion::image_registry::RegistryClient;
+use crate::storage::StoredImageMetadata;
+
+#[derive(Serialize)]
+pub struct ImagesResponse {
+    pub total_bytes: u64,
+    pub vulnerabilities: usize,
+    pub images: Vec<StoredImageMetadata>,
+}
+
+/// Looks up every image the repository references (Dockerfiles, compose files, Kubernetes
+/// workloads) in its registry and the configured scanner, then refreshes the image nodes of the
+/// stored graph. Returns the number of images looked up.
+pub(crate) async fn refresh_repository(state: web::Data<ApiState>, repository_id: &str) -> anyhow::Result<usize> {
+    let config = &state.config.images;
+    let mut images = BTreeSet::new();
+    for artifact in state.artifact_repo.get_artifacts(repository_id)? {
+        if artifact.kind == "container_image" {
+            images.insert(artifact.name);
+        }
+    }
+    for resource in state.kubernetes_repo.get_by_repository(repository_id)? {
+        images.extend(resource.images);
+    }
+    // Templated references (`${TAG}`, `{{ .Values.image }}`) cannot be resolved without a deploy
+    let unresolved = images.iter().filter(|i| i.contains(['$', '{'])).count();
+    let mut images: Vec<String> = images.into_iter().filter(|i| !i.contains(['$', '{'])).collect();
+    if images.len() > config.max_lookups {
+        log::info!("Looking up {} of {} container images (images.max_lookups)", config.max_lookups, images.len());
+        images.truncate(config.max_lookups);
+    }
+    if unresolved > 0 {
+        log::debug!("Skipping {} templated container image references", unresolved);
+    }
+
+    let scanner_token = match config.scanner_token.clone() {
+        Some(token) => Some(web::block(move || crate::ingestion::secrets::resolve(&token)).await??),
+        None => None,
+    };
+    let client = RegistryClient::new(&config.platform, config.scanner_url.clone(), scanner_token)?;
+    let mut metadata = Vec::new();
+    for image in &images {
+        let found = client.lookup(image).await;
+        if let Some(error) = &found.error {
+            log::debug!("Registry lookup for {} failed: {}", image, error);
+        }
+        metadata.push(found);
+    }
+    state.image_repo.store_metadata(repository_id, &metadata)?;
+
+    // The graph was stored before the lookup finished, so its image nodes are updated in place
+    let stored = state.image_repo.get_by_repository(repository_id)?;
+    let graph_builder = crate::api::reports::graph_builder(&state);
+    let mut graph = graph_builder.get_graph(repository_id)?;
+    if !graph.nodes.is_empty() {
+        graph.attach_image_metadata(repository_id, &stored);
+        graph_builder.store_graph(repository_id, &graph, None::<fn(u32, u32)>, None::<fn(u32, u32)>)?;
+    }
+    Ok(metadata.len())
+}
+
+fn images_response(state: &ApiState, repository_id: &str) -> HttpResponse {
+    match state.image_repo.get_by_repository(repository_id) {
+        Ok(images) => HttpResponse::Ok().json(ImagesResponse {
+            total_bytes: images.iter().filter_map(|i| i.size_bytes).sum(),
+            vulnerabilities: images.iter().map(|i| i.vulnerabilities.len()).sum(),
+            images,
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Registry digest, size, layers and known CVEs of the images the repository references
+pub async fn get_images(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    images_response(&state, &path.into_inner())
+}
+
+/// Look the repository's images up now rather than waiting for its next analysis
+pub async fn refresh_images(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(e) = crate::network::ensure_online("Container image lookups") {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: e.to_string(),
+        });
+    }
+    let repository_id = path.into_inner();
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+    match refresh_repository(state.clone(), &repository_id).await {
+        Ok(_) => images_response(&state, &repository_id),
+        Err(e) => HttpResponse::BadGateway().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
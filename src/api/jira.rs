@@ -0,0 +1,98 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashSet;
+use crate::api::{ApiState, ErrorResponse};
+use crate::integrations::jira::{fingerprint, is_ticketed, JiraClient, JiraSyncSummary};
+
+/// Opens a ticket for each high or critical vulnerability without one and refreshes the rest.
+/// Tickets are matched by fingerprint: first in the local mapping, then by label in Jira.
+pub(crate) async fn sync_repository(state: web::Data<ApiState>, repository_id: &str) -> anyhow::Result<JiraSyncSummary> {
+    let repository = state.repo_repo.find_by_id(repository_id)?
+        .ok_or_else(|| anyhow::anyhow!("Repository not found"))?;
+    let token = state.config.jira.token.clone().unwrap_or_default();
+    let token = web::block(move || crate::ingestion::secrets::resolve(&token)).await??;
+    let client = JiraClient::new(&state.config.jira, &token)?;
+    let base_url = state.config.server.base_url();
+
+    let mut seen = HashSet::new();
+    let vulnerabilities: Vec<_> = state.security_repo.get_vulnerabilities(repository_id)?
+        .into_iter()
+        .filter(|v| is_ticketed(&v.severity))
+        .map(|v| (fingerprint(&repository.url, &v), v))
+        .filter(|(fp, _)| seen.insert(fp.clone()))
+        .collect();
+
+    let mut summary = JiraSyncSummary::default();
+    for (fp, vulnerability) in vulnerabilities {
+        let existing = match state.jira_issue_repo.find_issue_key(&fp)? {
+            Some(key) => Some(key),
+            None => client.find_by_fingerprint(&fp).await.unwrap_or_else(|e| {
+                log::debug!("Jira label search failed: {}", e);
+                None
+            }),
+        };
+        let result = match existing {
+            Some(key) => client.update_issue(&key, &repository, &vulnerability, &base_url).await
+                .map(|()| (key, false)),
+            None => client.create_issue(&repository, &vulnerability, &fp, &base_url).await
+                .map(|key| (key, true)),
+        };
+        match result {
+            Ok((key, created)) => {
+                state.jira_issue_repo.record(&fp, repository_id, &key)?;
+                if created {
+                    summary.created.push(key);
+                } else {
+                    summary.updated.push(key);
+                }
+            }
+            Err(e) => {
+                log::warn!("⚠ Jira ticket for {} in {} failed: {}", vulnerability.vulnerability_type, vulnerability.file_path, e);
+                summary.failed += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Run the Jira sync for one repository now rather than waiting for its next analysis
+pub async fn sync_jira_issues(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if !state.config.jira.is_configured() {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "Jira is not configured; set JIRA_URL, JIRA_PROJECT and JIRA_TOKEN".to_string(),
+        });
+    }
+    let repository_id = path.into_inner();
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+    match sync_repository(state, &repository_id).await {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => HttpResponse::BadGateway().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Tickets opened for the repository's findings, by fingerprint
+pub async fn get_jira_issues(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.jira_issue_repo.get_by_repository(&path.into_inner()) {
+        Ok(links) => HttpResponse::Ok().json(links),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -1,7 +1,23 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use serde::Deserialize;
 use crate::api::{ApiState, ErrorResponse};
-use crate::crawler::{AnalysisJob, JobType, ScheduledJob};
+use crate::api::workspaces::CurrentWorkspace;
+use crate::crawler::{AnalysisJob, CronSchedule, JobType, ScheduledJob};
+
+/// Jobs may only name repositories in the caller's workspace
+pub(crate) fn foreign_repository(state: &ApiState, workspace: &CurrentWorkspace, repository_ids: &[String]) -> Option<HttpResponse> {
+    let owned = match state.repo_repo.ids_in_workspace(&workspace.0) {
+        Ok(ids) => ids,
+        Err(e) => return Some(HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        })),
+    };
+    repository_ids.iter().find(|id| !owned.contains(*id)).map(|id| {
+        HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("Repository {} not found", id),
+        })
+    })
+}
 
 #[derive(Debug, Deserialize)]
 pub struct CreateJobRequest {
@@ -25,10 +41,14 @@ pub struct BatchAnalyzeRequest {
 
 /// Create a new analysis job
 pub async fn create_job(
-    _state: web::Data<ApiState>,
+    state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     body: web::Json<CreateJobRequest>,
 ) -> impl Responder {
+    if let Some(response) = foreign_repository(&state, &workspace, body.repository_id.as_slice()) {
+        return response;
+    }
 
     let job_type = match body.job_type.as_str() {
         "analyze_repository" => JobType::AnalyzeRepository,
@@ -88,10 +108,14 @@ pub async fn list_jobs(
 
 /// Create a scheduled job
 pub async fn create_scheduled_job(
-    _state: web::Data<ApiState>,
+    state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     body: web::Json<CreateScheduledJobRequest>,
 ) -> impl Responder {
+    if let Some(response) = foreign_repository(&state, &workspace, body.repository_id.as_slice()) {
+        return response;
+    }
 
     let job_type = match body.job_type.as_str() {
         "analyze_repository" => JobType::AnalyzeRepository,
@@ -104,6 +128,12 @@ pub async fn create_scheduled_job(
         }
     };
 
+    if let Err(e) = CronSchedule::parse(&body.schedule) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("{:#}", e),
+        });
+    }
+
     let scheduled_job = ScheduledJob::new(
         body.name.clone(),
         body.schedule.clone(),
@@ -121,10 +151,14 @@ pub async fn create_scheduled_job(
 
 /// Batch analyze repositories
 pub async fn batch_analyze(
-    _state: web::Data<ApiState>,
+    state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     body: web::Json<BatchAnalyzeRequest>,
 ) -> impl Responder {
+    if let Some(response) = foreign_repository(&state, &workspace, &body.repository_ids) {
+        return response;
+    }
 
     let job_ids: Vec<String> = body.repository_ids.iter().map(|repo_id| {
         let job = AnalysisJob::new(
@@ -0,0 +1,34 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+use crate::analysis::KubernetesTopology;
+
+// Kubernetes endpoints
+pub async fn get_kubernetes_resources(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.kubernetes_repo.get_by_repository(&path.into_inner()) {
+        Ok(resources) => HttpResponse::Ok().json(resources),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Runtime topology: ingress host -> service -> workload -> image
+pub async fn get_kubernetes_topology(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.kubernetes_repo.get_by_repository(&path.into_inner()) {
+        Ok(stored) => {
+            let resources = stored.iter().map(|r| r.to_resource()).collect();
+            HttpResponse::Ok().json(KubernetesTopology::from_resources(resources))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
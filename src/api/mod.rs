@@ -1,7 +1,5 @@
 use actix_web::{web, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
-use crate::storage::{RepositoryRepository, DependencyRepository, ServiceRepository, CodeElementRepository, CodeRelationshipRepository, SecurityRepository, ToolRepository, DocumentationRepository, TestRepository, PortRepository, EndpointRepository};
-use std::sync::Arc;
 
 pub mod server;
 pub mod repositories;
@@ -20,21 +18,49 @@ pub mod version_check;
 pub mod plugins;
 pub mod ports;
 pub mod endpoints;
+pub mod kubernetes;
+pub mod gateways;
+pub mod build_graph;
+pub mod data_pipelines;
+pub mod pipelines;
+pub mod entrypoints;
+pub mod background_jobs;
+pub mod notifications;
+pub mod payments;
+pub mod privacy;
+pub mod observability;
+pub mod cost;
+pub mod environments;
+pub mod artifacts;
+pub mod pinning;
+pub mod images;
+pub mod frontend_routes;
+pub mod coverage;
+pub mod graphql_usage;
+pub mod stats;
+pub mod boundaries;
+pub mod architecture;
+pub mod tech_debt;
+pub mod todos;
+pub mod adrs;
+pub mod search;
+pub mod releases;
+pub mod workspaces;
+pub mod jira;
+pub mod servicenow;
+pub mod editor;
+pub mod email;
+pub mod gate;
+pub mod one_shot;
+pub mod schemas;
+pub mod related;
+pub mod systems;
+pub mod false_positives;
+pub mod schedules;
+pub mod webhooks;
 
-pub struct ApiState {
-    pub repo_repo: RepositoryRepository,
-    pub dep_repo: DependencyRepository,
-    pub service_repo: ServiceRepository,
-    pub code_repo: CodeElementRepository,
-    pub code_relationship_repo: CodeRelationshipRepository,
-    pub security_repo: SecurityRepository,
-    pub tool_repo: ToolRepository,
-    pub documentation_repo: DocumentationRepository,
-    pub test_repo: TestRepository,
-    pub port_repo: PortRepository,
-    pub endpoint_repo: EndpointRepository,
-    pub progress_tracker: Arc<progress::ProgressTracker>,
-}
+/// Request handlers share the analysis pipeline's state
+pub use crate::analyzer::AppState as ApiState;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -49,16 +75,27 @@ pub async fn health() -> impl Responder {
     }))
 }
 
+// Effective configuration, with secrets redacted
+pub async fn get_config(state: web::Data<ApiState>) -> impl Responder {
+    HttpResponse::Ok().json(state.config.redacted())
+}
+
 // Version endpoint
-pub async fn version(query: web::Query<std::collections::HashMap<String, String>>) -> impl Responder {
+pub async fn version(state: web::Data<ApiState>, query: web::Query<std::collections::HashMap<String, String>>) -> impl Responder {
     use crate::api::version_check;
+    use crate::report::editor_links::{EditorLinks, EDITOR_PRESETS};
     
     // Get current version
     let current_version = version_check::get_current_version();
     
-    // Get editor protocol from environment (default: vscode)
-    let editor_protocol = std::env::var("EDITOR_PROTOCOL")
-        .unwrap_or_else(|_| "vscode".to_string());
+    // Editor links: EDITOR_PROTOCOL, unless this request names another editor with ?editor=
+    let editor_protocol = query.get("editor").cloned()
+        .filter(|e| EditorLinks::parse(e).is_some())
+        .unwrap_or_else(|| state.config.server.editor_protocol.clone());
+    let editor_links = EditorLinks::new(&editor_protocol);
+    let editor_presets: serde_json::Map<String, serde_json::Value> = EDITOR_PRESETS.iter()
+        .map(|(name, template)| (name.to_string(), serde_json::Value::String(template.to_string())))
+        .collect();
     
     // Check if force refresh is requested
     let force = query.get("force").and_then(|v| v.parse::<bool>().ok()).unwrap_or(false);
@@ -68,31 +105,19 @@ pub async fn version(query: web::Query<std::collections::HashMap<String, String>
     
     // Get loaded plugins
     let plugin_dir = std::path::Path::new("config/plugins");
-    let mut plugin_names = Vec::new();
-    if plugin_dir.exists() && plugin_dir.is_dir() {
-        if let Ok(entries) = std::fs::read_dir(plugin_dir) {
-            plugin_names = entries.filter_map(|e| e.ok())
-                .filter(|e| {
-                    e.path().is_file() && 
-                    e.path().extension().and_then(|s| s.to_str()) == Some("json")
-                })
-                .filter_map(|e| {
-                    e.path().file_stem()
-                        .and_then(|s| s.to_str())
-                        .map(|s| s.to_string())
-                })
-                .collect();
-            plugin_names.sort();
-        }
-    }
+    let mut plugin_names = crate::plugins::PluginRegistry::load(plugin_dir).names();
+    plugin_names.sort();
     
     log::debug!("Version endpoint returning: {}, editor_protocol: {}, force: {}, plugins: {:?}", current_version, editor_protocol, force, plugin_names);
     HttpResponse::Ok().json(serde_json::json!({
         "version": current_version,
         "editor_protocol": editor_protocol,
+        "editor_link_template": editor_links.template(),
+        "editor_presets": editor_presets,
         "latest_version": version_info.latest,
         "update_available": version_info.update_available,
         "last_checked": version_info.last_checked,
+        "offline": crate::network::is_offline(),
         "plugins": plugin_names
     }))
 }
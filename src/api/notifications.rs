@@ -0,0 +1,16 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// Notification provider endpoints
+pub async fn get_notifications(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.notification_repo.get_by_repository(&path.into_inner()) {
+        Ok(configs) => HttpResponse::Ok().json(configs),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
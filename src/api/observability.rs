@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::{StoredObservabilityComponent, StoredObservabilityCoverage};
+
+#[derive(Serialize)]
+pub struct ObservabilityResponse {
+    pub libraries: Vec<StoredObservabilityComponent>,
+    pub exporters: Vec<StoredObservabilityComponent>,
+    pub coverage: Vec<StoredObservabilityCoverage>,
+}
+
+/// Logging/metrics/tracing libraries, where telemetry is exported, and each service's coverage gaps
+pub async fn get_observability(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let components = match state.observability_repo.get_components(&repository_id) {
+        Ok(c) => c,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let coverage = match state.observability_repo.get_coverage(&repository_id) {
+        Ok(c) => c,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let (libraries, exporters) = components.into_iter().partition(|c| c.component == "library");
+    HttpResponse::Ok().json(ObservabilityResponse {
+        libraries,
+        exporters,
+        coverage,
+    })
+}
@@ -0,0 +1,71 @@
+use actix_web::web;
+use crate::api::email::{failure_alerts_enabled, send_failure_alert};
+use crate::analyzer::pipeline::perform_analysis;
+use crate::api::server::build_state;
+use crate::config::Config;
+
+/// `ONE_SHOT=1`: analyze every registered repository in turn, publish their artifacts and return
+/// instead of serving, so containers and CI jobs need not start, poll and stop a server.
+/// `Ok(false)` means at least one analysis failed.
+pub async fn run(config: &Config) -> anyhow::Result<bool> {
+    let state = build_state(config)?;
+    let repositories = state.repo_repo.list_all()?;
+    if repositories.is_empty() {
+        log::warn!("⚠ One-shot run: no repositories are registered, nothing to do");
+        return Ok(true);
+    }
+
+    let total = repositories.len();
+    let mut failed = Vec::new();
+    for (i, repository) in repositories.into_iter().enumerate() {
+        log::info!("[{}/{}] Analyzing {} ({})", i + 1, total, repository.name, repository.url);
+        state.progress_tracker.start_analysis(&repository.id, 13);
+        let analysis = {
+            let state = state.clone();
+            let repository_id = repository.id.clone();
+            web::block(move || perform_analysis(&state, &repository_id, None)).await
+        };
+        let error = match analysis {
+            Ok(Ok(result)) => {
+                log::info!("✓ {} ({} profile)", result.message, result.profile.as_str());
+                None
+            }
+            Ok(Err(e)) => Some(format!("{:#}", e)),
+            Err(e) => Some(format!("analysis did not finish: {}", e)),
+        };
+        state.progress_tracker.clear_progress(&repository.id);
+
+        if let Some(error) = error {
+            log::error!("✗ {} failed: {}", repository.name, error);
+            if failure_alerts_enabled(&state) {
+                send_failure_alert(&state, &repository, &error).await;
+            }
+            crate::api::webhooks::notify(&state, &repository.id, Some(&error)).await;
+            failed.push(repository.name);
+            continue;
+        }
+
+        // Awaited rather than spawned, since the process exits when the loop ends
+        if let Some(provider) = state.embedding_provider.clone() {
+            match crate::search::index_repository(&provider, &state.documentation_repo, &state.code_repo, &state.embedding_repo, &repository.id).await {
+                Ok(count) => log::info!("✓ Indexed {} items for semantic search", count),
+                Err(e) => log::warn!("⚠ Failed to build semantic search index: {}", e),
+            }
+        }
+        if config.artifacts.bucket.is_some() {
+            match crate::api::reports::publish_artifacts(state.clone(), repository.id.clone()).await {
+                Ok(Some(run)) => log::info!("✓ Published {} artifacts to {}", run.artifacts.len(), run.location),
+                Ok(None) => {}
+                Err(e) => log::warn!("⚠ Publishing artifacts failed: {:#}", e),
+            }
+        }
+        crate::api::webhooks::notify(&state, &repository.id, None).await;
+    }
+
+    if failed.is_empty() {
+        log::info!("✓ One-shot run finished: {} repositories analyzed", total);
+    } else {
+        log::error!("✗ One-shot run finished: {} of {} analyses failed ({})", failed.len(), total, failed.join(", "));
+    }
+    Ok(failed.is_empty())
+}
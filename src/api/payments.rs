@@ -0,0 +1,62 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use crate::api::{ApiState, ErrorResponse};
+use crate::security::payment_pci::PCI_VULNERABILITY_TYPES;
+use crate::security::SecurityVulnerability;
+use crate::storage::{StoredEndpoint, StoredPaymentFlow};
+
+#[derive(Serialize)]
+pub struct PaymentsResponse {
+    pub providers: BTreeMap<String, usize>,
+    pub flows: Vec<StoredPaymentFlow>,
+    pub endpoints: Vec<StoredEndpoint>,
+    pub findings: Vec<SecurityVulnerability>,
+}
+
+/// Payment flows per provider, the endpoints that run them and PCI-relevant findings
+pub async fn get_payments(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let flows = match state.payment_repo.get_by_repository(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let endpoints = match state.endpoint_repo.get_by_repository(&repository_id) {
+        Ok(e) => e,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let vulnerabilities = match state.security_repo.get_vulnerabilities(&repository_id) {
+        Ok(v) => v,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let mut providers: BTreeMap<String, usize> = BTreeMap::new();
+    for flow in &flows {
+        *providers.entry(flow.provider.clone()).or_default() += 1;
+    }
+    let linked: HashSet<&str> = flows.iter().filter_map(|f| f.endpoint.as_deref()).collect();
+    let endpoints = endpoints.into_iter()
+        .filter(|e| e.protocol == "http" && linked.contains(format!("{} {}", e.method, e.path).as_str()))
+        .collect();
+    let findings = vulnerabilities.into_iter()
+        .filter(|v| PCI_VULNERABILITY_TYPES.contains(&v.vulnerability_type.as_str()))
+        .collect();
+
+    HttpResponse::Ok().json(PaymentsResponse {
+        providers,
+        flows,
+        endpoints,
+        findings,
+    })
+}
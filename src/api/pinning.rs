@@ -0,0 +1,28 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+
+/// Reproducibility score and floating-version findings for manifests, images, GitHub Actions and
+/// install commands. `?kind=dependency|image|action|install` and `?severity=high|medium|low` narrow the findings.
+pub async fn get_pinning_audit(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    match state.pinning_repo.get_audit(&path.into_inner()) {
+        Ok(audit) => {
+            let mut audit = audit.unwrap_or_default();
+            if let Some(kind) = query.get("kind") {
+                audit.findings.retain(|f| format!("{:?}", f.kind).eq_ignore_ascii_case(kind));
+            }
+            if let Some(severity) = query.get("severity") {
+                audit.findings.retain(|f| format!("{:?}", f.severity).eq_ignore_ascii_case(severity));
+            }
+            HttpResponse::Ok().json(audit)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
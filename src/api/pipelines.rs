@@ -0,0 +1,16 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+
+// CI/CD pipeline endpoints
+pub async fn get_pipelines(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.pipeline_repo.get_by_repository(&path.into_inner()) {
+        Ok(pipelines) => HttpResponse::Ok().json(pipelines),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
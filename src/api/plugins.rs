@@ -1,13 +1,23 @@
-use actix_web::{HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::fs;
+use crate::api::{ApiState, ErrorResponse};
+use crate::plugins::install::{self, InstallRequest};
+use crate::plugins::{LoadedPlugin, PluginHook, PluginSelection};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PluginInfo {
     pub name: String,
     pub path: String,
     pub version: Option<String>,
+    pub description: Option<String>,
+    pub manifest_version: u32,
+    pub runtime: String, // "patterns" | "wasm" | "process" | "native"
+    pub hooks: Vec<PluginHook>,
+    /// Whether the plugin runs for repositories that have not opted in or out
+    pub default_enabled: bool,
+    pub valid: bool,
+    pub errors: Vec<String>,
     pub patterns_count: PluginPatternCounts,
 }
 
@@ -27,48 +37,36 @@ pub struct PluginsResponse {
     pub total: usize,
 }
 
-/// Get list of loaded plugins
-pub async fn get_plugins() -> impl Responder {
-    let plugin_dir = Path::new("config/plugins");
-    let mut plugins = Vec::new();
-
-    if plugin_dir.exists() && plugin_dir.is_dir() {
-        if let Ok(entries) = fs::read_dir(plugin_dir) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
-                        let plugin_name = path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-                        
-                        // Try to load plugin config to get details
-                        if let Ok(content) = fs::read_to_string(&path) {
-                            if let Ok(plugin_config) = serde_json::from_str::<crate::security::pattern_config::PatternConfig>(&content) {
-                                let patterns_count = PluginPatternCounts {
-                                    environment_variables: plugin_config.patterns.environment_variables.len(),
-                                    sdk_patterns: plugin_config.patterns.sdk_patterns.len(),
-                                    api_endpoints: plugin_config.patterns.api_endpoints.len(),
-                                    database_patterns: plugin_config.patterns.database_patterns.len(),
-                                    aws_infrastructure: plugin_config.patterns.aws_infrastructure.len(),
-                                    aws_sdk_v2_services: plugin_config.patterns.aws_sdk_v2_services.len(),
-                                };
-
-                                plugins.push(PluginInfo {
-                                    name: plugin_name,
-                                    path: path.to_string_lossy().to_string(),
-                                    version: Some(plugin_config.version),
-                                    patterns_count,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+impl From<&LoadedPlugin> for PluginInfo {
+    fn from(plugin: &LoadedPlugin) -> Self {
+        let patterns = plugin.manifest.patterns.clone().unwrap_or_default();
+        PluginInfo {
+            name: plugin.manifest.name.clone(),
+            path: plugin.source.to_string_lossy().to_string(),
+            version: Some(plugin.manifest.version.clone()).filter(|v| !v.is_empty()),
+            description: plugin.manifest.description.clone(),
+            manifest_version: plugin.manifest.manifest_version,
+            runtime: plugin.runtime.kind().to_string(),
+            hooks: plugin.manifest.hooks.clone(),
+            default_enabled: plugin.manifest.default_enabled,
+            valid: plugin.is_valid(),
+            errors: plugin.errors.clone(),
+            patterns_count: PluginPatternCounts {
+                environment_variables: patterns.environment_variables.len(),
+                sdk_patterns: patterns.sdk_patterns.len(),
+                api_endpoints: patterns.api_endpoints.len(),
+                database_patterns: patterns.database_patterns.len(),
+                aws_infrastructure: patterns.aws_infrastructure.len(),
+                aws_sdk_v2_services: patterns.aws_sdk_v2_services.len(),
+            },
         }
     }
+}
 
+/// Get list of loaded plugins with the hooks they implement; invalid plugins are listed with their errors
+pub async fn get_plugins(state: web::Data<ApiState>) -> impl Responder {
+    let registry = state.plugin_registry.read().unwrap().clone();
+    let mut plugins: Vec<PluginInfo> = registry.plugins.iter().map(PluginInfo::from).collect();
     plugins.sort_by(|a, b| a.name.cmp(&b.name));
 
     HttpResponse::Ok().json(PluginsResponse {
@@ -77,3 +75,153 @@ pub async fn get_plugins() -> impl Responder {
     })
 }
 
+/// Install or update a pattern/WASM plugin from a pinned URL or git commit; it is used from the next analysis on
+pub async fn install_plugin(
+    state: web::Data<ApiState>,
+    body: web::Json<InstallRequest>,
+) -> impl Responder {
+    let request = body.into_inner();
+    let config = state.plugins_config.clone();
+
+    let bundle = match (&request.url, &request.git) {
+        (Some(url), None) => {
+            let sha256 = match &request.sha256 {
+                Some(sha256) => sha256.clone(),
+                None => return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "sha256 is required for URL installs".to_string(),
+                }),
+            };
+            let bytes = match install::download(url).await {
+                Ok(bytes) => bytes,
+                Err(e) => return HttpResponse::BadGateway().json(ErrorResponse {
+                    error: format!("Failed to download plugin: {}", e),
+                }),
+            };
+            if let Err(e) = install::verify(&bytes, Some(&sha256), request.signature.as_deref(), &config) {
+                return HttpResponse::BadRequest().json(ErrorResponse { error: e.to_string() });
+            }
+            let name = request.name.clone().unwrap_or_else(|| install::name_from_url(url));
+            install::bundle_from_bytes(bytes, &name)
+        }
+        (None, Some(git)) => {
+            let rev = match &request.rev {
+                Some(rev) => rev.clone(),
+                None => return HttpResponse::BadRequest().json(ErrorResponse {
+                    error: "rev (a full commit id) is required for git installs".to_string(),
+                }),
+            };
+            // The commit id pins the content; a signature, when used, is over the commit id
+            if let Err(e) = install::verify(rev.as_bytes(), None, request.signature.as_deref(), &config) {
+                return HttpResponse::BadRequest().json(ErrorResponse { error: e.to_string() });
+            }
+            let (git, path) = (git.clone(), request.path.clone());
+            match web::block(move || install::bundle_from_git(&git, &rev, path.as_deref())).await {
+                Ok(result) => result,
+                Err(e) => Err(anyhow::anyhow!("{}", e)),
+            }
+        }
+        _ => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Provide either url or git".to_string(),
+        }),
+    };
+    let bundle = match bundle {
+        Ok(bundle) => bundle,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: format!("{:#}", e) }),
+    };
+
+    match web::block(move || install::install_bundle(&bundle, Path::new("config/plugins"))).await {
+        Ok(Ok(plugin)) => {
+            // The watcher would pick this up too, but the caller may analyze straight away
+            crate::plugins::watch::reload(Path::new("config/plugins"), &state.plugin_registry);
+            HttpResponse::Created().json(PluginInfo::from(&plugin))
+        }
+        Ok(Err(e)) => HttpResponse::UnprocessableEntity().json(ErrorResponse { error: format!("{:#}", e) }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse { error: e.to_string() }),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepositoryPluginInfo {
+    pub name: String,
+    pub runtime: String,
+    pub default_enabled: bool,
+    /// Whether the repository's next analysis runs this plugin
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepositoryPluginsResponse {
+    pub repository_id: String,
+    pub selection: PluginSelection,
+    pub plugins: Vec<RepositoryPluginInfo>,
+}
+
+fn repository_plugins(state: &ApiState, repository_id: String, selection: PluginSelection) -> RepositoryPluginsResponse {
+    let registry = state.plugin_registry.read().unwrap();
+    let mut plugins: Vec<RepositoryPluginInfo> = registry.plugins.iter()
+        .filter(|p| p.is_valid())
+        .map(|p| RepositoryPluginInfo {
+            name: p.manifest.name.clone(),
+            runtime: p.runtime.kind().to_string(),
+            default_enabled: p.manifest.default_enabled,
+            enabled: selection.allows(&p.manifest),
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    RepositoryPluginsResponse { repository_id, selection, plugins }
+}
+
+/// The repository's plugin opt-ins/opt-outs and which plugins its next analysis will run
+pub async fn get_repository_plugins(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    match state.plugin_selection_repo.get_selection(&repository_id) {
+        Ok(selection) => HttpResponse::Ok().json(repository_plugins(&state, repository_id, selection)),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Replace the repository's plugin selection (`{"enabled": [...], "disabled": [...]}`)
+pub async fn set_repository_plugins(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<PluginSelection>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let selection = body.into_inner();
+
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+
+    if let Some(name) = selection.enabled.iter().find(|name| selection.disabled.contains(name)) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Plugin '{}' is both enabled and disabled", name),
+        });
+    }
+    let known = state.plugin_registry.read().unwrap().names();
+    if let Some(name) = selection.enabled.iter().chain(&selection.disabled).find(|name| !known.contains(name)) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Unknown plugin '{}'", name),
+        });
+    }
+
+    match state.plugin_selection_repo.set_selection(&repository_id, &selection) {
+        Ok(()) => HttpResponse::Ok().json(repository_plugins(&state, repository_id, selection)),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -1,5 +1,6 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
 
 // Port endpoints
 pub async fn get_ports(
@@ -18,12 +19,13 @@ pub async fn get_ports(
 pub async fn search_ports_by_port(
     state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> impl Responder {
     if let Some(port_str) = query.get("port") {
         if let Ok(port) = port_str.parse::<u16>() {
             let repository_id = query.get("repository_id");
-            match state.port_repo.get_by_port(port, repository_id.map(|s| s.as_str())) {
+            match state.port_repo.get_by_port(port, &workspace.0, repository_id.map(|s| s.as_str())) {
                 Ok(ports) => HttpResponse::Ok().json(ports),
                 Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                     error: e.to_string(),
@@ -36,7 +38,7 @@ pub async fn search_ports_by_port(
         }
     } else if let Some(port_type) = query.get("type") {
         let repository_id = query.get("repository_id");
-        match state.port_repo.get_by_type(port_type, repository_id.map(|s| s.as_str())) {
+        match state.port_repo.get_by_type(port_type, &workspace.0, repository_id.map(|s| s.as_str())) {
             Ok(ports) => HttpResponse::Ok().json(ports),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
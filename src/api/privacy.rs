@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::security::pii_flow::PII_VULNERABILITY_TYPES;
+use crate::security::SecurityVulnerability;
+use crate::storage::{StoredPiiField, StoredPiiFlow};
+
+#[derive(Serialize)]
+pub struct PrivacyResponse {
+    pub categories: BTreeMap<String, usize>,
+    pub fields: Vec<StoredPiiField>,
+    pub flows: Vec<StoredPiiFlow>,
+    pub findings: Vec<SecurityVulnerability>,
+}
+
+/// PII fields per category, where personal data flows to logs, analytics and external hosts, and the resulting findings
+pub async fn get_privacy(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let fields = match state.privacy_repo.get_fields(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let flows = match state.privacy_repo.get_flows(&repository_id) {
+        Ok(f) => f,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let vulnerabilities = match state.security_repo.get_vulnerabilities(&repository_id) {
+        Ok(v) => v,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    let mut categories: BTreeMap<String, usize> = BTreeMap::new();
+    for field in &fields {
+        *categories.entry(field.category.clone()).or_default() += 1;
+    }
+    let findings = vulnerabilities.into_iter()
+        .filter(|v| PII_VULNERABILITY_TYPES.contains(&v.vulnerability_type.as_str()))
+        .collect();
+
+    HttpResponse::Ok().json(PrivacyResponse {
+        categories,
+        fields,
+        flows,
+        findings,
+    })
+}
@@ -0,0 +1,43 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::api::{ApiState, ErrorResponse};
+use crate::ingestion::RepositoryCrawler;
+
+/// Checkout available without fetching: the local path itself, or an existing cached clone
+pub(crate) fn existing_checkout(url: &str) -> Option<std::path::PathBuf> {
+    if RepositoryCrawler::is_local_path(url) {
+        return Some(std::path::PathBuf::from(url.strip_prefix("file://").unwrap_or(url)));
+    }
+    let config = crate::config::Config::load().unwrap_or_default();
+    let path = RepositoryCrawler::new(&config.storage).ok()?.get_repo_path(url);
+    path.join(".git").exists().then_some(path)
+}
+
+/// Forks and mirrors of the repository, with whether a mirror's analysis can stand in for its own
+pub async fn get_related_repositories(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+
+    match state.repository_link_repo.get_links(&repository_id) {
+        Ok(links) => HttpResponse::Ok().json(serde_json::json!({
+            "repository_id": repository_id,
+            "mirrors": links.iter().filter(|l| l.relation == "mirror").count(),
+            "forks": links.iter().filter(|l| l.relation == "fork").count(),
+            "related": links,
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,26 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::analysis::{build_timeline, ReleaseSource};
+
+/// Releases from the changelog and GitHub, newest first, with the dependency and architecture
+/// changes seen between analyses (`?limit=` snapshots, default 20)
+pub async fn get_release_timeline(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(20);
+
+    let loaded = state.release_repo.get_releases(&repository_id, ReleaseSource::Changelog)
+        .and_then(|changelog| Ok((changelog, state.release_repo.get_releases(&repository_id, ReleaseSource::GitHub)?)))
+        .and_then(|(changelog, hosted)| Ok((changelog, hosted, state.release_repo.get_snapshots(&repository_id, limit)?)));
+    match loaded {
+        Ok((changelog, hosted, snapshots)) => HttpResponse::Ok().json(build_timeline(changelog, hosted, &snapshots)),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -1,18 +1,13 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
-use crate::report::ReportGenerator;
+use crate::integrations::object_storage::{Artifact, ObjectStore, PublishedRun};
+use crate::report::{ReportGenerator, OpenApiGenerator, BackstageGenerator, EditorLinks, SbomGenerator};
+use std::collections::HashMap;
 use crate::graph::GraphBuilder;
+use crate::report::schema::{versioned, SchemaKind};
 
-/// Generate HTML report for a repository
-pub async fn generate_report(
-    state: web::Data<ApiState>,
-    _req: HttpRequest,
-    path: web::Path<String>,
-) -> impl Responder {
-    let repository_id = path.into_inner();
-    
-    // Create graph builder
-    let graph_builder = GraphBuilder::new(
+pub(crate) fn graph_builder(state: &ApiState) -> GraphBuilder {
+    GraphBuilder::new(
         state.repo_repo.db.clone(),
         state.repo_repo.clone(),
         state.dep_repo.clone(),
@@ -22,10 +17,11 @@ pub async fn generate_report(
         state.test_repo.clone(),
         state.port_repo.clone(),
         state.endpoint_repo.clone(),
-    );
-    
-    // Create report generator
-    let report_generator = ReportGenerator::new(
+    )
+}
+
+fn report_generator(state: &ApiState) -> ReportGenerator {
+    ReportGenerator::new(
         state.repo_repo.clone(),
         state.dep_repo.clone(),
         state.service_repo.clone(),
@@ -35,10 +31,25 @@ pub async fn generate_report(
         state.tool_repo.clone(),
         state.port_repo.clone(),
         state.endpoint_repo.clone(),
-        graph_builder,
-    );
-    
-    match report_generator.generate_html_report(&repository_id) {
+        state.boundary_repo.clone(),
+        state.todo_repo.clone(),
+        state.doc_quality_repo.clone(),
+        state.plugin_report_repo.clone(),
+        graph_builder(state),
+    )
+}
+
+/// Generate HTML report for a repository; file references open in `EDITOR_PROTOCOL`, or `?editor=`
+pub async fn generate_report(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let links = EditorLinks::for_request(&state.config.server.editor_protocol, query.get("editor"));
+
+    match report_generator(&state).with_editor_links(links, state.config.storage.clone()).generate_html_report(&repository_id) {
         Ok(html) => HttpResponse::Ok()
             .content_type("text/html")
             .body(html),
@@ -48,3 +59,249 @@ pub async fn generate_report(
     }
 }
 
+/// Generate an OpenAPI 3 document from the endpoints detected in a repository
+pub async fn generate_openapi_spec(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let repository = match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(repo)) => repo,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+
+    match state.endpoint_repo.get_by_repository(&repository_id) {
+        Ok(endpoints) => HttpResponse::Ok().json(OpenApiGenerator::new().generate(&repository, &endpoints)),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to generate OpenAPI spec: {}", e),
+        }),
+    }
+}
+
+/// Software bill of materials of a repository's dependencies, as CycloneDX 1.5 JSON or, with
+/// `?format=spdx`, SPDX 2.3 JSON
+pub async fn generate_sbom(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    let format = query.get("format").map(|f| f.to_lowercase()).unwrap_or_else(|| "cyclonedx".to_string());
+    if !matches!(format.as_str(), "cyclonedx" | "spdx") {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Unknown SBOM format '{}', expected cyclonedx or spdx", format),
+        });
+    }
+
+    let repository = match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(repo)) => repo,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let dependencies = match state.dep_repo.get_by_repository(&repository_id) {
+        Ok(dependencies) => dependencies,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to generate SBOM: {}", e),
+        }),
+    };
+
+    let (document, content_type, extension) = match format.as_str() {
+        "spdx" => (SbomGenerator::new().spdx(&repository, &dependencies), "application/spdx+json", "spdx.json"),
+        _ => (SbomGenerator::new().cyclonedx(&repository, &dependencies), "application/vnd.cyclonedx+json", "cdx.json"),
+    };
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}.{}\"", repository.name, extension)))
+        .json(document)
+}
+
+/// Backstage entities for a repository as one multi-document YAML
+fn backstage_catalog(state: &ApiState, repository_id: &str) -> Result<Option<(String, String)>, anyhow::Error> {
+    let repository = match state.repo_repo.find_by_id(repository_id)? {
+        Some(repo) => repo,
+        None => return Ok(None),
+    };
+    let boundaries = state.boundary_repo.get_by_repository(repository_id)?;
+    let services = state.service_repo.get_by_repository(repository_id)?;
+    let endpoints = state.endpoint_repo.get_by_repository(repository_id)?;
+
+    let entities = BackstageGenerator::new(&state.config.backstage).generate(&repository, &boundaries, &services, &endpoints);
+    let component = entities[0]["metadata"]["name"].as_str().unwrap_or(&repository.name).to_string();
+    Ok(Some((component, BackstageGenerator::to_yaml(&entities)?)))
+}
+
+/// Download a repository's Backstage `catalog-info.yaml`
+pub async fn generate_backstage_catalog(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match backstage_catalog(&state, &path.into_inner()) {
+        Ok(Some((_, yaml))) => HttpResponse::Ok()
+            .content_type("application/yaml")
+            .insert_header(("Content-Disposition", "attachment; filename=\"catalog-info.yaml\""))
+            .body(yaml),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to generate Backstage catalog: {}", e),
+        }),
+    }
+}
+
+/// Write a repository's `catalog-info.yaml` to the configured catalog directory
+pub async fn publish_backstage_catalog(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let catalog_dir = match &state.config.backstage.catalog_dir {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "No catalog location configured; set BACKSTAGE_CATALOG_DIR".to_string(),
+        }),
+    };
+    let (component, yaml) = match backstage_catalog(&state, &path.into_inner()) {
+        Ok(Some(catalog)) => catalog,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to generate Backstage catalog: {}", e),
+        }),
+    };
+
+    let file = catalog_dir.join(&component).join("catalog-info.yaml");
+    let written = std::fs::create_dir_all(catalog_dir.join(&component)).and_then(|_| std::fs::write(&file, yaml));
+    match written {
+        Ok(()) => HttpResponse::Ok().json(serde_json::json!({
+            "component": component,
+            "path": file.to_string_lossy(),
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to write {}: {}", file.display(), e),
+        }),
+    }
+}
+
+/// The configured artifacts for a repository, plus a `manifest.json` describing the run
+fn collect_artifacts(state: &ApiState, repository_id: &str, run_at: chrono::DateTime<chrono::Utc>) -> Result<Option<Vec<Artifact>>, anyhow::Error> {
+    let repository = match state.repo_repo.find_by_id(repository_id)? {
+        Some(repo) => repo,
+        None => return Ok(None),
+    };
+
+    let schema_version = state.config.output.schema_version;
+    let mut artifacts = Vec::new();
+    for kind in &state.config.artifacts.include {
+        let artifact = match kind.as_str() {
+            "report" => Artifact {
+                name: "report.html".to_string(),
+                content_type: "text/html",
+                body: report_generator(state).generate_html_report(repository_id)?.into_bytes(),
+            },
+            "openapi" => Artifact {
+                name: "openapi.json".to_string(),
+                content_type: "application/json",
+                body: serde_json::to_vec_pretty(&OpenApiGenerator::new().generate(&repository, &state.endpoint_repo.get_by_repository(repository_id)?))?,
+            },
+            "graph" => Artifact {
+                name: "graph.json".to_string(),
+                content_type: "application/json",
+                body: serde_json::to_vec_pretty(&versioned(SchemaKind::Graph, &graph_builder(state).get_graph(repository_id)?, schema_version)?)?,
+            },
+            "dependencies" => Artifact {
+                name: "dependencies.json".to_string(),
+                content_type: "application/json",
+                body: serde_json::to_vec_pretty(&versioned(SchemaKind::Dependencies, &state.dep_repo.get_by_repository(repository_id)?, schema_version)?)?,
+            },
+            "sbom" => Artifact {
+                name: "sbom.cdx.json".to_string(),
+                content_type: "application/vnd.cyclonedx+json",
+                body: serde_json::to_vec_pretty(&SbomGenerator::new().cyclonedx(&repository, &state.dep_repo.get_by_repository(repository_id)?))?,
+            },
+            "backstage" => match backstage_catalog(state, repository_id)? {
+                Some((_, yaml)) => Artifact {
+                    name: "catalog-info.yaml".to_string(),
+                    content_type: "application/yaml",
+                    body: yaml.into_bytes(),
+                },
+                None => continue,
+            },
+            other => {
+                log::warn!("⚠ Skipping unknown artifact '{}'", other);
+                continue;
+            }
+        };
+        artifacts.push(artifact);
+    }
+
+    let manifest = serde_json::json!({
+        "repository": {
+            "id": repository.id,
+            "name": repository.name,
+            "url": repository.url,
+            "branch": repository.branch,
+            "last_analyzed_at": repository.last_analyzed_at,
+        },
+        "run_at": run_at,
+        "artifacts": artifacts.iter().map(|a| &a.name).collect::<Vec<_>>(),
+    });
+    artifacts.push(Artifact {
+        name: "manifest.json".to_string(),
+        content_type: "application/json",
+        body: serde_json::to_vec_pretty(&versioned(SchemaKind::Manifest, &manifest, schema_version)?)?,
+    });
+    Ok(Some(artifacts))
+}
+
+/// Generates the repository's artifacts and uploads them under a new run prefix
+pub(crate) async fn publish_artifacts(state: web::Data<ApiState>, repository_id: String) -> Result<Option<PublishedRun>, anyhow::Error> {
+    let config = &state.config.artifacts;
+    let bucket = config.bucket.as_deref()
+        .ok_or_else(|| anyhow::anyhow!("No artifact bucket configured; set ARTIFACTS_BUCKET"))?;
+    let store = ObjectStore::parse(bucket, config.endpoint_url.as_deref())?;
+
+    web::block(move || {
+        let run_at = chrono::Utc::now();
+        match collect_artifacts(&state, &repository_id, run_at)? {
+            Some(artifacts) => Ok(Some(store.publish(&store.run_prefix(&repository_id, run_at), &artifacts)?)),
+            None => Ok(None),
+        }
+    }).await?
+}
+
+/// Upload a repository's report, OpenAPI spec, graph and dependency inventory to the artifact bucket
+pub async fn publish_repository_artifacts(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if state.config.artifacts.bucket.is_none() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "No artifact bucket configured; set ARTIFACTS_BUCKET".to_string(),
+        });
+    }
+    match publish_artifacts(state, path.into_inner()).await {
+        Ok(Some(run)) => HttpResponse::Ok().json(run),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to publish artifacts: {:#}", e),
+        }),
+    }
+}
@@ -0,0 +1,211 @@
+use actix_web::{web, HttpResponse, Responder};
+use chrono::{DateTime, Timelike, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::analysis::AnalysisProfile;
+use crate::api::{ApiState, ErrorResponse};
+use crate::analyzer::pipeline::perform_analysis;
+use crate::api::repositories::spawn_follow_ups;
+use crate::api::workspaces::CurrentWorkspace;
+use crate::crawler::CronSchedule;
+use crate::storage::{AnalysisSchedule, RunStatus};
+
+#[derive(Debug, Deserialize)]
+pub struct SetScheduleRequest {
+    /// Five-field cron expression, in UTC
+    pub cron: String,
+    pub profile: Option<AnalysisProfile>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn internal_error(e: impl ToString) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: e.to_string(),
+    })
+}
+
+/// Schedules only reach repositories of the caller's workspace
+fn check_repository(state: &ApiState, workspace: &CurrentWorkspace, repository_id: &str) -> Result<(), HttpResponse> {
+    match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(repository)) if repository.workspace_id == workspace.0 => Ok(()),
+        Ok(_) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        })),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+/// Re-analyze the repository on a cron schedule, replacing its current schedule if it has one
+pub async fn set_schedule(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+    body: web::Json<SetScheduleRequest>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    let cron = body.cron.trim();
+    let next_run_at = match CronSchedule::parse(cron) {
+        Ok(schedule) => schedule.next_after(Utc::now()),
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("{:#}", e),
+        }),
+    };
+    if next_run_at.is_none() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Cron expression '{}' never matches", cron),
+        });
+    }
+
+    match state.schedule_repo.upsert(&repository_id, cron, body.profile, body.enabled, next_run_at) {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn get_schedule(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    match state.schedule_repo.find(&repository_id) {
+        Ok(Some(schedule)) => HttpResponse::Ok().json(schedule),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository has no schedule".to_string(),
+        }),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Stop re-analyzing the repository; its run history is kept
+pub async fn delete_schedule(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    match state.schedule_repo.delete(&repository_id) {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository has no schedule".to_string(),
+        }),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// The repository's scheduled analyses, newest first (`?limit=`, 50 by default)
+pub async fn list_scheduled_runs(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    let limit = query.get("limit").and_then(|l| l.parse::<usize>().ok()).unwrap_or(50).clamp(1, 500);
+    match state.schedule_repo.runs(&repository_id, limit) {
+        Ok(runs) => HttpResponse::Ok().json(runs),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Starts the analyses whose schedules are due, checking at the start of every minute
+pub(crate) async fn run_due_analyses(state: web::Data<ApiState>) {
+    match state.schedule_repo.fail_interrupted() {
+        Ok(0) => {}
+        Ok(count) => log::warn!("⚠ {} scheduled analyses were interrupted by a restart", count),
+        Err(e) => log::warn!("⚠ Failed to close interrupted scheduled analyses: {}", e),
+    }
+    loop {
+        let now = Utc::now();
+        let into_minute = now.second() as u64 * 1000 + now.timestamp_subsec_millis() as u64;
+        tokio::time::sleep(std::time::Duration::from_millis(60_000 - into_minute.min(59_999))).await;
+
+        let now = Utc::now();
+        match state.schedule_repo.due(now) {
+            Ok(schedules) => {
+                for schedule in schedules {
+                    start_scheduled_analysis(&state, schedule, now);
+                }
+            }
+            Err(e) => log::warn!("⚠ Failed to load analysis schedules: {}", e),
+        }
+    }
+}
+
+fn start_scheduled_analysis(state: &web::Data<ApiState>, schedule: AnalysisSchedule, now: DateTime<Utc>) {
+    let repository_id = schedule.repository_id.clone();
+    let scheduled_for = schedule.next_run_at.unwrap_or(now);
+    // Advance first, so a failure below can't start the same run every minute
+    let next_run_at = CronSchedule::parse(&schedule.cron).ok().and_then(|c| c.next_after(now));
+    if let Err(e) = state.schedule_repo.advance(&repository_id, now, next_run_at) {
+        log::warn!("⚠ Failed to advance the schedule of {}: {}", repository_id, e);
+        return;
+    }
+
+    if state.schedule_repo.is_running(&repository_id).unwrap_or(false) {
+        log::info!("Skipping scheduled analysis of {}: the previous one is still running", repository_id);
+        if let Err(e) = state.schedule_repo.record_run(&schedule, scheduled_for, RunStatus::Skipped) {
+            log::warn!("⚠ Failed to record skipped analysis of {}: {}", repository_id, e);
+        }
+        return;
+    }
+    let run = match state.schedule_repo.record_run(&schedule, scheduled_for, RunStatus::Running) {
+        Ok(run) => run,
+        Err(e) => {
+            log::warn!("⚠ Failed to record scheduled analysis of {}: {}", repository_id, e);
+            return;
+        }
+    };
+
+    log::info!("Starting scheduled analysis of {} ({})", repository_id, schedule.cron);
+    let state = state.clone();
+    actix_web::rt::spawn(async move {
+        state.progress_tracker.start_analysis(&repository_id, 13);
+        let analysis = {
+            let state = state.clone();
+            let repository_id = repository_id.clone();
+            web::block(move || perform_analysis(&state, &repository_id, schedule.profile)).await
+        };
+        let outcome = match analysis {
+            Ok(Ok(result)) => {
+                spawn_follow_ups(&state, &repository_id, &result);
+                Ok(())
+            }
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(e) => Err(format!("Failed to execute analysis: {}", e)),
+        };
+        let finished = match outcome {
+            Ok(()) => {
+                log::info!("✓ Scheduled analysis of {} completed", repository_id);
+                state.schedule_repo.finish_run(&run.id, RunStatus::Completed, None)
+            }
+            Err(error) => {
+                log::warn!("⚠ Scheduled analysis of {} failed: {}", repository_id, error);
+                state.progress_tracker.fail_analysis(&repository_id, &error);
+                crate::api::email::spawn_failure_alert(&state, &repository_id, error.clone());
+                crate::api::webhooks::spawn_notifications(&state, &repository_id, Some(error.clone()));
+                state.schedule_repo.finish_run(&run.id, RunStatus::Failed, Some(&error))
+            }
+        };
+        if let Err(e) = finished {
+            log::warn!("⚠ Failed to record the end of the scheduled analysis of {}: {}", repository_id, e);
+        }
+    });
+}
@@ -0,0 +1,69 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Deserialize;
+use crate::api::{ApiState, ErrorResponse};
+use crate::report::schema::{is_supported, SchemaKind, SCHEMA_KINDS, SCHEMA_VERSION};
+
+/// `?schema_version=` on endpoints that return versioned documents
+#[derive(Debug, Default, Deserialize)]
+pub struct SchemaVersionQuery {
+    pub schema_version: Option<u32>,
+}
+
+impl SchemaVersionQuery {
+    /// The version to answer in: the request's, else the configured pin. Unsupported versions
+    /// come back as a ready 400 response.
+    pub(crate) fn resolve(&self, state: &ApiState) -> Result<Option<u32>, HttpResponse> {
+        match self.schema_version.or(state.config.output.schema_version) {
+            Some(version) if !is_supported(version) => Err(HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!("Unsupported schema version {}; versions 1 to {} are available", version, SCHEMA_VERSION),
+            })),
+            version => Ok(version),
+        }
+    }
+}
+
+/// List the JSON Schemas of exported documents, with the current and pinned versions
+pub async fn list_schemas(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+) -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "current_version": SCHEMA_VERSION,
+        "pinned_version": state.config.output.schema_version,
+        "versions": (1..=SCHEMA_VERSION).collect::<Vec<_>>(),
+        "schemas": SCHEMA_KINDS.iter().map(|kind| serde_json::json!({
+            "name": kind.name(),
+            "path": format!("/api/v1/schemas/{}", kind.name()),
+        })).collect::<Vec<_>>(),
+    }))
+}
+
+/// One JSON Schema; `?version=` picks an older version than the current one
+pub async fn get_schema(
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> impl Responder {
+    let name = path.into_inner();
+    let kind = match SchemaKind::parse(&name) {
+        Some(kind) => kind,
+        None => return HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No schema named '{}'", name),
+        }),
+    };
+    let version = match query.get("version").map(|v| v.parse::<u32>()) {
+        None => SCHEMA_VERSION,
+        Some(Ok(version)) => version,
+        Some(Err(_)) => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "version must be a number".to_string(),
+        }),
+    };
+    match kind.schema(version) {
+        Some(schema) => HttpResponse::Ok()
+            .content_type("application/schema+json")
+            .json(schema),
+        None => HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No version {} of the {} schema; versions 1 to {} are available", version, name, SCHEMA_VERSION),
+        }),
+    }
+}
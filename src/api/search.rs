@@ -0,0 +1,85 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
+use crate::search;
+
+fn disabled() -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(ErrorResponse {
+        error: "Semantic search is disabled; set EMBEDDINGS_PROVIDER to local, openai or ollama".to_string(),
+    })
+}
+
+/// Natural-language search over documentation and code signatures: `?q=` (required), `?repository_id=`,
+/// `?type=documentation|code_element` and `?limit=` (default 10, max 50)
+pub async fn semantic_search(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    workspace: CurrentWorkspace,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let provider = match &state.embedding_provider {
+        Some(p) => p,
+        None => return disabled(),
+    };
+    let question = match query.get("q").map(|q| q.trim()).filter(|q| !q.is_empty()) {
+        Some(q) => q.to_string(),
+        None => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Query parameter 'q' is required".to_string(),
+        }),
+    };
+    let limit = query.get("limit").and_then(|l| l.parse::<usize>().ok()).unwrap_or(10).clamp(1, 50);
+
+    let vector = match provider.embed(std::slice::from_ref(&question)).await {
+        Ok(mut vectors) if !vectors.is_empty() => vectors.remove(0),
+        Ok(_) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Embedding provider returned no vector".to_string(),
+        }),
+        Err(e) => return HttpResponse::BadGateway().json(ErrorResponse {
+            error: format!("Failed to embed query: {}", e),
+        }),
+    };
+
+    match state.embedding_repo.search(
+        &provider.model_id(),
+        &vector,
+        &workspace.0,
+        query.get("repository_id").map(|s| s.as_str()),
+        query.get("type").map(|s| s.as_str()),
+        limit,
+    ) {
+        Ok(matches) => {
+            HttpResponse::Ok().json(serde_json::json!({
+                "query": question,
+                "model": provider.model_id(),
+                "results": matches,
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Rebuild a repository's vectors from its stored documentation and code elements
+pub async fn reindex_embeddings(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let provider = match &state.embedding_provider {
+        Some(p) => p,
+        None => return disabled(),
+    };
+    let repository_id = path.into_inner();
+    match search::index_repository(provider, &state.documentation_repo, &state.code_repo, &state.embedding_repo, &repository_id).await {
+        Ok(count) => HttpResponse::Ok().json(serde_json::json!({
+            "repository_id": repository_id,
+            "model": provider.model_id(),
+            "indexed": count,
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
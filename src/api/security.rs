@@ -1,7 +1,17 @@
 
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use crate::analysis::dependencies::{OsvClient, VULNERABLE_DEPENDENCY};
 use crate::api::{ApiState, ErrorResponse};
 
+/// Looks every stored dependency of the repository up in the configured OSV database and
+/// replaces its dependency advisories with what was found. Returns the number of findings.
+pub(crate) async fn refresh_advisories(state: web::Data<ApiState>, repository_id: &str) -> anyhow::Result<usize> {
+    let dependencies = state.dep_repo.get_by_repository(repository_id)?;
+    let vulnerabilities = OsvClient::new(&state.config.advisories.osv_url)?.scan(&dependencies).await?;
+    state.security_repo.replace_vulnerabilities(repository_id, VULNERABLE_DEPENDENCY, &vulnerabilities)?;
+    Ok(vulnerabilities.len())
+}
+
 /// Get security entities for a repository
 pub async fn get_security_entities(
     state: web::Data<ApiState>,
@@ -75,3 +85,40 @@ pub async fn get_security_vulnerabilities(
     }
 }
 
+
+/// Look the repository's dependencies up in the OSV database now rather than waiting for its
+/// next analysis; answers with the dependency advisories found
+pub async fn refresh_dependency_advisories(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(e) = crate::network::ensure_online("Dependency advisory lookups") {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: e.to_string(),
+        });
+    }
+    let repository_id = path.into_inner();
+    match state.repo_repo.find_by_id(&repository_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+    if let Err(e) = refresh_advisories(state.clone(), &repository_id).await {
+        return HttpResponse::BadGateway().json(ErrorResponse {
+            error: e.to_string(),
+        });
+    }
+    match state.security_repo.get_vulnerabilities(&repository_id) {
+        Ok(vulnerabilities) => HttpResponse::Ok().json(
+            vulnerabilities.into_iter().filter(|v| v.vulnerability_type == VULNERABLE_DEPENDENCY).collect::<Vec<_>>()
+        ),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -1,32 +1,68 @@
 use actix_web::{web, App, HttpServer};
 use actix_files::Files;
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
-use crate::api::{ApiState, health, version};
+use crate::api::{ApiState, health, version, get_config};
 use crate::graphql::GraphQLSchema;
 use crate::api::repositories::{
     create_repository, list_repositories, get_repository,
     analyze_repository, get_dependencies, search_dependencies,
-    delete_repository,
+    delete_repository, set_analysis_profile,
 };
 use crate::api::services::{get_services, search_services_by_provider};
 use crate::api::ports::{get_ports, search_ports_by_port};
 use crate::api::endpoints::{get_endpoints, search_endpoints};
+use crate::api::kubernetes::{get_kubernetes_resources, get_kubernetes_topology};
+use crate::api::gateways::get_gateways;
+use crate::api::build_graph::get_build_graph;
+use crate::api::data_pipelines::get_data_pipelines;
+use crate::api::pipelines::get_pipelines;
+use crate::api::entrypoints::get_entrypoints;
+use crate::api::background_jobs::get_background_jobs;
+use crate::api::notifications::get_notifications;
+use crate::api::payments::get_payments;
+use crate::api::privacy::get_privacy;
+use crate::api::observability::get_observability;
+use crate::api::cost::get_cost_estimate;
+use crate::api::environments::get_environment_matrix;
+use crate::api::artifacts::get_artifacts;
+use crate::api::pinning::get_pinning_audit;
+use crate::api::images::{get_images, refresh_images};
+use crate::api::frontend_routes::get_frontend_routes;
+use crate::api::coverage::{get_coverage, upload_coverage};
+use crate::api::graphql_usage::{get_graphql_operations, get_graphql_schema_types, get_graphql_impact};
+use crate::api::boundaries::{get_service_boundaries, get_decomposition};
+use crate::api::architecture::{get_architecture, get_architecture_findings, check_architecture, put_architecture_spec, get_architecture_spec, delete_architecture_spec, get_architecture_conformance};
+use crate::api::tech_debt::{get_tech_debt, get_tech_debt_history};
+use crate::api::related::get_related_repositories;
+use crate::api::false_positives::{mark_false_positive, list_false_positives, delete_false_positive, suggest_false_positive_fixes};
+use crate::api::schedules::{set_schedule, get_schedule, delete_schedule, list_scheduled_runs};
+use crate::api::webhooks::{list_webhooks, create_webhook, delete_webhook};
+use crate::api::systems::{create_system, list_systems, get_system, delete_system, add_system_repository, remove_system_repository, get_system_links, get_system_graph};
+use crate::api::todos::{get_todos, get_todo_summary};
+use crate::api::adrs::get_adrs;
+use crate::api::search::{semantic_search, reindex_embeddings};
+use crate::api::releases::get_release_timeline;
+use crate::api::stats::get_repository_stats;
 use crate::api::tools::{get_tools, get_tool_scripts, search_tools};
-use crate::api::graph::{get_graph, get_graph_statistics, get_node_neighbors};
+use crate::api::graph::{get_graph, get_graph_statistics, get_node_neighbors, get_graph_cypher, push_graph_to_neo4j};
 use crate::api::code::{get_code_elements, get_code_calls, get_code_relationships};
-use crate::api::security::{get_security_entities, get_security_relationships, get_security_vulnerabilities};
+use crate::api::security::{get_security_entities, get_security_relationships, get_security_vulnerabilities, refresh_dependency_advisories};
 use crate::api::entity_details::get_entity_details;
 use crate::api::jobs::{create_job, get_job_status, list_jobs, create_scheduled_job, batch_analyze};
 use crate::api::progress::get_analysis_progress;
-use crate::api::reports::generate_report;
-use crate::api::documentation::{get_documentation, get_documentation_by_type, search_documentation};
-use crate::api::tests::{get_tests, get_tests_by_framework};
-use crate::api::plugins::get_plugins;
+use crate::api::reports::{generate_report, generate_openapi_spec, generate_sbom, generate_backstage_catalog, publish_backstage_catalog, publish_repository_artifacts};
+use crate::api::documentation::{get_documentation, get_documentation_by_type, search_documentation, get_documentation_quality, get_documentation_drift, get_documentation_links, get_documentation_references};
+use crate::api::tests::{get_tests, get_tests_by_framework, get_tests_for_code};
+use crate::api::workspaces::{CurrentWorkspace, scope_to_workspace, list_workspaces, get_current_workspace, create_workspace};
+use crate::api::jira::{sync_jira_issues, get_jira_issues};
+use crate::api::servicenow::{get_cmdb_payload, push_cmdb_payload};
+use crate::api::email::{preview_digest, send_digest};
+use crate::api::gate::check_gate;
+use crate::api::schemas::{list_schemas, get_schema};
+use crate::api::editor::{get_hover, get_file_annotations, get_service_usages, get_env_var_references};
+use crate::api::plugins::{get_plugins, install_plugin, get_repository_plugins, set_repository_plugins};
 use crate::crawler::webhooks::{handle_github_webhook, handle_gitlab_webhook};
 use crate::config::Config;
-use crate::storage::{Database, RepositoryRepository, DependencyRepository, ServiceRepository, CodeElementRepository, CodeRelationshipRepository, SecurityRepository, ToolRepository, DocumentationRepository, TestRepository, PortRepository, EndpointRepository};
-use crate::api::progress::ProgressTracker;
-use std::sync::Arc;
 use actix_web::HttpResponse;
 use std::fs;
 
@@ -38,45 +74,31 @@ async fn index_handler() -> actix_web::Result<HttpResponse> {
         .body(content))
 }
 
+/// The database, repositories and shared services every request handler and the CLI work against
+pub fn build_state(config: &Config) -> std::io::Result<web::Data<ApiState>> {
+    Ok(web::Data::new(ApiState::open(config).map_err(std::io::Error::other)?))
+}
+
 pub async fn start_server(config: Config) -> std::io::Result<()> {
-    // Initialize database
-    let db = Database::new(&config.database)
-        .expect("Failed to initialize database");
-    
-    // Initialize repositories
-    let repo_repo = RepositoryRepository::new(db.clone());
-    let dep_repo = DependencyRepository::new(db.clone());
-    let service_repo = ServiceRepository::new(db.clone());
-    let code_repo = CodeElementRepository::new(db.clone());
-    let code_relationship_repo = CodeRelationshipRepository::new(db.clone());
-    let security_repo = SecurityRepository::new(db.clone());
-    let tool_repo = ToolRepository::new(db.clone());
-    let documentation_repo = DocumentationRepository::new(db.clone());
-    let test_repo = TestRepository::new(db.clone());
-    let port_repo = PortRepository::new(db.clone());
-    let endpoint_repo = EndpointRepository::new(db.clone());
-    
-    // Initialize progress tracker
-    let progress_tracker = Arc::new(ProgressTracker::new());
-    
-    // Create API state
-    let api_state = web::Data::new(ApiState {
-        repo_repo: repo_repo.clone(),
-        dep_repo: dep_repo.clone(),
-        service_repo: service_repo.clone(),
-        code_repo: code_repo.clone(),
-        code_relationship_repo: code_relationship_repo.clone(),
-        security_repo: security_repo.clone(),
-        tool_repo: tool_repo.clone(),
-        documentation_repo: documentation_repo.clone(),
-        test_repo: test_repo.clone(),
-        port_repo: port_repo.clone(),
-        endpoint_repo: endpoint_repo.clone(),
-        progress_tracker: progress_tracker.clone(),
-    });
+    let api_state = build_state(&config)?;
+
+    // Plugins are shared across analyses and reloaded when config/plugins changes
+    if let Err(e) = crate::plugins::watch::spawn(std::path::PathBuf::from("config/plugins"), api_state.plugin_registry.clone()) {
+        log::warn!("⚠ Plugin hot-reload disabled: {:#}", e);
+    }
+
+    // Scheduled pushes would only fail offline
+    let online = !config.network.offline;
+    if online && config.servicenow.is_configured() && config.servicenow.sync_interval_hours > 0 {
+        actix_web::rt::spawn(crate::api::servicenow::push_on_schedule(api_state.clone(), config.servicenow.sync_interval_hours));
+    }
+    if online && config.email.is_configured() && config.email.digest_interval_hours > 0 {
+        actix_web::rt::spawn(crate::api::email::send_digest_on_schedule(api_state.clone(), config.email.digest_interval_hours));
+    }
+    actix_web::rt::spawn(crate::api::schedules::run_due_analyses(api_state.clone()));
     
     // Create progress tracker state for the progress endpoint
-    let progress_state = web::Data::new(progress_tracker.clone());
+    let progress_state = web::Data::new(api_state.progress_tracker.clone());
 
     // Create GraphQL schema
     let schema = GraphQLSchema::build(
@@ -88,12 +110,13 @@ pub async fn start_server(config: Config) -> std::io::Result<()> {
     .finish();
     let schema = web::Data::new(schema);
 
-    // GraphQL handler
+    // GraphQL handler; resolvers only see the caller's workspace
     async fn graphql_handler(
         schema: web::Data<GraphQLSchema>,
+        workspace: CurrentWorkspace,
         req: GraphQLRequest,
     ) -> GraphQLResponse {
-        schema.execute(req.into_inner()).await.into()
+        schema.execute(req.into_inner().data(workspace)).await.into()
     }
     
     // GraphiQL handler
@@ -125,18 +148,46 @@ pub async fn start_server(config: Config) -> std::io::Result<()> {
             .service(
                 web::scope("/api/v1")
                     .app_data(api_state.clone())
+                    .wrap(actix_web::middleware::from_fn(scope_to_workspace))
                     // Health and version endpoints
                     .route("/version", web::get().to(version))
+                    .route("/config", web::get().to(get_config))
+                    // Email digest
+                    .route("/email/digest", web::get().to(preview_digest))
+                    .route("/email/digest", web::post().to(send_digest))
+                    // CI gate
+                    .route("/gate", web::post().to(check_gate))
+                    // JSON Schemas of exported documents
+                    .route("/schemas", web::get().to(list_schemas))
+                    .route("/schemas/{name}", web::get().to(get_schema))
+                    // Workspace endpoints
+                    .route("/workspaces", web::get().to(list_workspaces))
+                    .route("/workspaces", web::post().to(create_workspace))
+                    .route("/workspaces/current", web::get().to(get_current_workspace))
+                    // System endpoints (repositories analyzed together)
+                    .route("/systems", web::get().to(list_systems))
+                    .route("/systems", web::post().to(create_system))
+                    .route("/systems/{id}", web::get().to(get_system))
+                    .route("/systems/{id}", web::delete().to(delete_system))
+                    .route("/systems/{id}/repositories/{repository_id}", web::put().to(add_system_repository))
+                    .route("/systems/{id}/repositories/{repository_id}", web::delete().to(remove_system_repository))
+                    .route("/systems/{id}/links", web::get().to(get_system_links))
+                    .route("/systems/{id}/graph", web::get().to(get_system_graph))
                     // Plugin endpoints
                     .route("/plugins", web::get().to(get_plugins))
+                    .route("/plugins/install", web::post().to(install_plugin))
                     // Repository endpoints
                     .route("/repositories", web::post().to(create_repository))
                     .route("/repositories", web::get().to(list_repositories))
                     .route("/repositories/{id}", web::get().to(get_repository))
                     .route("/repositories/{id}", web::delete().to(delete_repository))
                     .route("/repositories/{id}/analyze", web::post().to(analyze_repository))
+                    .route("/repositories/{id}/profile", web::put().to(set_analysis_profile))
                     .route("/repositories/{id}/progress", web::get().to(get_analysis_progress))
                     .route("/repositories/{id}/dependencies", web::get().to(get_dependencies))
+                    .route("/repositories/{id}/stats", web::get().to(get_repository_stats))
+                    // Forks and mirrors of a repository
+                    .route("/repositories/{id}/related", web::get().to(get_related_repositories))
                     // Dependency search
                     .route("/dependencies/search", web::get().to(search_dependencies))
                     // Service endpoints
@@ -148,6 +199,73 @@ pub async fn start_server(config: Config) -> std::io::Result<()> {
                     // Endpoint endpoints
                     .route("/repositories/{id}/endpoints", web::get().to(get_endpoints))
                     .route("/endpoints/search", web::get().to(search_endpoints))
+                    // Kubernetes endpoints
+                    .route("/repositories/{id}/kubernetes", web::get().to(get_kubernetes_resources))
+                    .route("/repositories/{id}/kubernetes/topology", web::get().to(get_kubernetes_topology))
+                    // API gateway route endpoints
+                    .route("/repositories/{id}/gateways", web::get().to(get_gateways))
+                    // CI/CD pipeline endpoints
+                    .route("/repositories/{id}/pipelines", web::get().to(get_pipelines))
+                    // Build target graph endpoints
+                    .route("/repositories/{id}/build-graph", web::get().to(get_build_graph))
+                    // Data pipeline lineage endpoints
+                    .route("/repositories/{id}/data-pipelines", web::get().to(get_data_pipelines))
+                    // Entrypoint endpoints
+                    .route("/repositories/{id}/entrypoints", web::get().to(get_entrypoints))
+                    // Background job endpoints
+                    .route("/repositories/{id}/background-jobs", web::get().to(get_background_jobs))
+                    // Notification provider endpoints
+                    .route("/repositories/{id}/notifications", web::get().to(get_notifications))
+                    // Payment flow endpoints
+                    .route("/repositories/{id}/payments", web::get().to(get_payments))
+                    // Personal data endpoints
+                    .route("/repositories/{id}/privacy", web::get().to(get_privacy))
+                    // Observability stack endpoints
+                    .route("/repositories/{id}/observability", web::get().to(get_observability))
+                    // Cloud cost estimate endpoints
+                    .route("/repositories/{id}/cost", web::get().to(get_cost_estimate))
+                    // Committed artifact inventory endpoints
+                    .route("/repositories/{id}/artifacts", web::get().to(get_artifacts))
+                    // Version pinning audit endpoints
+                    .route("/repositories/{id}/pinning", web::get().to(get_pinning_audit))
+                    // Container image registry metadata endpoints
+                    .route("/repositories/{id}/images", web::get().to(get_images))
+                    .route("/repositories/{id}/images/refresh", web::post().to(refresh_images))
+                    // Environment matrix endpoints
+                    .route("/repositories/{id}/environments", web::get().to(get_environment_matrix))
+                    // GraphQL client usage endpoints
+                    .route("/repositories/{id}/graphql/operations", web::get().to(get_graphql_operations))
+                    .route("/repositories/{id}/graphql/schema", web::get().to(get_graphql_schema_types))
+                    .route("/repositories/{id}/graphql/impact", web::get().to(get_graphql_impact))
+                    // Service boundary endpoints
+                    .route("/repositories/{id}/boundaries", web::get().to(get_service_boundaries))
+                    .route("/repositories/{id}/decomposition", web::get().to(get_decomposition))
+                    // Architecture endpoints
+                    .route("/repositories/{id}/architecture", web::get().to(get_architecture))
+                    .route("/repositories/{id}/architecture/findings", web::get().to(get_architecture_findings))
+                    .route("/repositories/{id}/architecture/check", web::get().to(check_architecture))
+                    .route("/repositories/{id}/architecture/spec", web::put().to(put_architecture_spec))
+                    .route("/repositories/{id}/architecture/spec", web::get().to(get_architecture_spec))
+                    .route("/repositories/{id}/architecture/spec", web::delete().to(delete_architecture_spec))
+                    .route("/repositories/{id}/architecture/conformance", web::get().to(get_architecture_conformance))
+                    // Technical debt endpoints
+                    .route("/repositories/{id}/tech-debt", web::get().to(get_tech_debt))
+                    .route("/repositories/{id}/tech-debt/history", web::get().to(get_tech_debt_history))
+                    // TODO inventory endpoints
+                    .route("/repositories/{id}/todos", web::get().to(get_todos))
+                    .route("/repositories/{id}/todos/summary", web::get().to(get_todo_summary))
+                    // Architecture decision records
+                    .route("/repositories/{id}/adrs", web::get().to(get_adrs))
+                    // Semantic search
+                    .route("/search/semantic", web::get().to(semantic_search))
+                    .route("/repositories/{id}/search/reindex", web::post().to(reindex_embeddings))
+                    // Release timeline
+                    .route("/repositories/{id}/releases", web::get().to(get_release_timeline))
+                    // Per-repository plugin selection
+                    .route("/repositories/{id}/plugins", web::get().to(get_repository_plugins))
+                    .route("/repositories/{id}/plugins", web::put().to(set_repository_plugins))
+                    // Frontend route endpoints
+                    .route("/repositories/{id}/frontend-routes", web::get().to(get_frontend_routes))
                     // Tool endpoints
                     .route("/repositories/{id}/tools", web::get().to(get_tools))
                     .route("/repositories/{repo_id}/tools/{tool_id}/scripts", web::get().to(get_tool_scripts))
@@ -156,6 +274,9 @@ pub async fn start_server(config: Config) -> std::io::Result<()> {
                     .route("/repositories/{id}/graph", web::get().to(get_graph))
                     .route("/repositories/{id}/graph/statistics", web::get().to(get_graph_statistics))
                     .route("/repositories/{id}/graph/nodes/{node_id}/neighbors", web::get().to(get_node_neighbors))
+                    // Neo4j export: a Cypher script, or a push over bolt
+                    .route("/repositories/{id}/graph/cypher", web::get().to(get_graph_cypher))
+                    .route("/repositories/{id}/graph/neo4j", web::post().to(push_graph_to_neo4j))
                     // Code structure endpoints
                     .route("/repositories/{id}/code/elements", web::get().to(get_code_elements))
                     .route("/repositories/{id}/code/calls", web::get().to(get_code_calls))
@@ -164,17 +285,57 @@ pub async fn start_server(config: Config) -> std::io::Result<()> {
                     .route("/repositories/{id}/security/entities", web::get().to(get_security_entities))
                     .route("/repositories/{id}/security/relationships", web::get().to(get_security_relationships))
                     .route("/repositories/{id}/security/vulnerabilities", web::get().to(get_security_vulnerabilities))
+                    .route("/repositories/{id}/security/advisories/refresh", web::post().to(refresh_dependency_advisories))
+                    .route("/repositories/{id}/false-positives", web::get().to(list_false_positives))
+                    .route("/repositories/{id}/false-positives", web::post().to(mark_false_positive))
+                    .route("/repositories/{id}/false-positives/suggestions", web::get().to(suggest_false_positive_fixes))
+                    .route("/repositories/{id}/false-positives/{verdict_id}", web::delete().to(delete_false_positive))
+                    .route("/repositories/{id}/schedule", web::get().to(get_schedule))
+                    .route("/repositories/{id}/schedule", web::post().to(set_schedule))
+                    .route("/repositories/{id}/schedule", web::delete().to(delete_schedule))
+                    .route("/repositories/{id}/schedule/runs", web::get().to(list_scheduled_runs))
+                    .route("/repositories/{id}/webhooks", web::get().to(list_webhooks))
+                    .route("/repositories/{id}/webhooks", web::post().to(create_webhook))
+                    .route("/repositories/{id}/webhooks/{webhook_id}", web::delete().to(delete_webhook))
                     // Entity details endpoints
                     .route("/repositories/{repo_id}/entities/{entity_type}/{entity_id}", web::get().to(get_entity_details))
+                    // Editor extension lookups
+                    .route("/repositories/{id}/editor/hover", web::get().to(get_hover))
+                    .route("/repositories/{id}/editor/file", web::get().to(get_file_annotations))
+                    .route("/repositories/{id}/editor/services/{service}/usages", web::get().to(get_service_usages))
+                    .route("/repositories/{id}/editor/env/{name}", web::get().to(get_env_var_references))
                     // Report endpoints
                     .route("/repositories/{id}/report", web::get().to(generate_report))
+                    .route("/repositories/{id}/sbom", web::get().to(generate_sbom))
+                    .route("/repositories/{id}/openapi", web::get().to(generate_openapi_spec))
+                    .route("/repositories/{id}/backstage", web::get().to(generate_backstage_catalog))
+                    .route("/repositories/{id}/backstage/publish", web::post().to(publish_backstage_catalog))
+                    .route("/repositories/{id}/artifacts/publish", web::post().to(publish_repository_artifacts))
+                    // Jira tickets for vulnerabilities
+                    .route("/repositories/{id}/jira/issues", web::get().to(get_jira_issues))
+                    .route("/repositories/{id}/jira/sync", web::post().to(sync_jira_issues))
+                    // ServiceNow CMDB export
+                    .route("/repositories/{id}/servicenow", web::get().to(get_cmdb_payload))
+                    .route("/repositories/{id}/servicenow/push", web::post().to(push_cmdb_payload))
                     // Documentation endpoints (experimental)
                     .route("/repositories/{id}/documentation", web::get().to(get_documentation))
                     .route("/repositories/{id}/documentation/type/{doc_type}", web::get().to(get_documentation_by_type))
                     .route("/repositories/{id}/documentation/search", web::get().to(search_documentation))
+                    .route("/repositories/{id}/documentation/quality", web::get().to(get_documentation_quality))
+                    .route("/repositories/{id}/documentation/drift", web::get().to(get_documentation_drift))
+                    .route("/repositories/{id}/documentation/links", web::get().to(get_documentation_links))
+                    .route("/repositories/{id}/documentation/references", web::get().to(get_documentation_references))
                     // Test endpoints
                     .route("/repositories/{id}/tests", web::get().to(get_tests))
                     .route("/repositories/{repo_id}/tests/framework/{framework}", web::get().to(get_tests_by_framework))
+                    .route("/repositories/{id}/tests/covering", web::get().to(get_tests_for_code))
+                    // Coverage endpoints (reports can be large, so uploads get a bigger payload limit)
+                    .service(
+                        web::resource("/repositories/{id}/coverage")
+                            .app_data(web::PayloadConfig::new(50 * 1024 * 1024))
+                            .route(web::get().to(get_coverage))
+                            .route(web::post().to(upload_coverage))
+                    )
                     // Job endpoints (Phase 8)
                     .route("/jobs", web::post().to(create_job))
                     .route("/jobs", web::get().to(list_jobs))
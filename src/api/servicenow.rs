@@ -0,0 +1,117 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::time::Duration;
+use crate::api::{ApiState, ErrorResponse};
+use crate::integrations::servicenow::{build_payload, CmdbPayload, CmdbPushSummary, ServiceNowClient};
+use crate::storage::Repository;
+
+fn cmdb_payload(state: &ApiState, repository: &Repository) -> anyhow::Result<CmdbPayload> {
+    Ok(build_payload(
+        repository,
+        &state.boundary_repo.get_by_repository(&repository.id)?,
+        &state.service_repo.get_by_repository(&repository.id)?,
+        &state.dep_repo.get_by_repository(&repository.id)?,
+        state.config.servicenow.include_packages,
+    ))
+}
+
+async fn client(state: &ApiState) -> anyhow::Result<ServiceNowClient> {
+    let password = state.config.servicenow.password.clone().unwrap_or_default();
+    let password = web::block(move || crate::ingestion::secrets::resolve(&password)).await??;
+    ServiceNowClient::new(&state.config.servicenow, &password)
+}
+
+/// Pushes every analyzed repository, then again every `sync_interval_hours`
+pub(crate) async fn push_on_schedule(state: web::Data<ApiState>, hours: u64) {
+    let mut interval = tokio::time::interval(Duration::from_secs(hours * 3600));
+    loop {
+        interval.tick().await;
+        let repositories = match state.repo_repo.list_all() {
+            Ok(repositories) => repositories,
+            Err(e) => {
+                log::warn!("⚠ ServiceNow export skipped: {}", e);
+                continue;
+            }
+        };
+        let client = match client(&state).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("⚠ ServiceNow export skipped: {:#}", e);
+                continue;
+            }
+        };
+        for repository in repositories.iter().filter(|r| r.last_analyzed_at.is_some()) {
+            let pushed = match cmdb_payload(&state, repository) {
+                Ok(payload) => client.push(&payload).await,
+                Err(e) => Err(e),
+            };
+            match pushed {
+                Ok(summary) => log::info!(
+                    "✓ ServiceNow: {} CIs inserted, {} updated for {}",
+                    summary.inserted, summary.updated, repository.name
+                ),
+                Err(e) => log::warn!("⚠ ServiceNow export of {} failed: {:#}", repository.name, e),
+            }
+        }
+    }
+}
+
+/// The CMDB items and relationships the repository maps to, without pushing them
+pub async fn get_cmdb_payload(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository = match state.repo_repo.find_by_id(&path.into_inner()) {
+        Ok(Some(repository)) => repository,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    match cmdb_payload(&state, &repository) {
+        Ok(payload) => HttpResponse::Ok().json(payload),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Push the repository's CIs and relationships to ServiceNow now
+pub async fn push_cmdb_payload(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if !state.config.servicenow.is_configured() {
+        return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+            error: "ServiceNow is not configured; set SERVICENOW_URL, SERVICENOW_USERNAME and SERVICENOW_PASSWORD".to_string(),
+        });
+    }
+    let repository = match state.repo_repo.find_by_id(&path.into_inner()) {
+        Ok(Some(repository)) => repository,
+        Ok(None) => return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        }),
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let payload = match cmdb_payload(&state, &repository) {
+        Ok(payload) => payload,
+        Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    };
+    let pushed: anyhow::Result<CmdbPushSummary> = match client(&state).await {
+        Ok(client) => client.push(&payload).await,
+        Err(e) => Err(e),
+    };
+    match pushed {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => HttpResponse::BadGateway().json(ErrorResponse {
+            error: format!("{:#}", e),
+        }),
+    }
+}
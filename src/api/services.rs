@@ -1,5 +1,6 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
 
 // Service endpoints
 pub async fn get_services(
@@ -19,20 +20,21 @@ pub async fn get_services(
 pub async fn search_services_by_provider(
     state: web::Data<ApiState>,
     _req: HttpRequest,
+    workspace: CurrentWorkspace,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> impl Responder {
     // API key validation removed for local tool simplicity
     if let Some(provider) = query.get("provider") {
-        // Global search - no repository_id filter (intentional cross-repo search)
-        match state.service_repo.get_by_provider(provider, None) {
+        // Cross-repo search, limited to the caller's workspace
+        match state.service_repo.get_by_provider(provider, &workspace.0, None) {
             Ok(services) => HttpResponse::Ok().json(services),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
             }),
         }
     } else if let Some(service_type) = query.get("type") {
-        // Global search - no repository_id filter (intentional cross-repo search)
-        match state.service_repo.get_by_service_type(service_type, None) {
+        // Cross-repo search, limited to the caller's workspace
+        match state.service_repo.get_by_service_type(service_type, &workspace.0, None) {
             Ok(services) => HttpResponse::Ok().json(services),
             Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: e.to_string(),
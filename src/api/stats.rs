@@ -0,0 +1,60 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::BTreeMap;
+use crate::api::{ApiState, ErrorResponse};
+
+/// Language breakdown plus framework/tool counts for dashboards
+pub async fn get_repository_stats(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let result = (|| -> anyhow::Result<serde_json::Value> {
+        let languages = state.language_stats_repo.get_by_repository(&repository_id)?;
+        let frameworks = state.framework_repo.get_by_repository(&repository_id)?;
+        let tools = state.tool_repo.get_tools_by_repository(&repository_id)?;
+
+        let total_bytes: u64 = languages.iter().map(|l| l.bytes).sum();
+        let total_files: usize = languages.iter().map(|l| l.files).sum();
+
+        let mut frameworks_by_category: BTreeMap<String, usize> = BTreeMap::new();
+        for framework in &frameworks {
+            *frameworks_by_category.entry(framework.category.clone()).or_insert(0) += 1;
+        }
+        let mut tools_by_category: BTreeMap<String, usize> = BTreeMap::new();
+        for tool in &tools {
+            *tools_by_category.entry(tool.tool_type.clone()).or_insert(0) += 1;
+        }
+
+        Ok(serde_json::json!({
+            "repository_id": repository_id,
+            "primary_language": languages.first().map(|l| l.language.clone()),
+            "total_bytes": total_bytes,
+            "total_files": total_files,
+            "languages": languages,
+            "frameworks": {
+                "total": frameworks.len(),
+                "by_category": frameworks_by_category,
+                "names": frameworks.iter().map(|f| f.name.clone()).collect::<Vec<_>>(),
+            },
+            "tools": {
+                "total": tools.len(),
+                "by_type": tools_by_category,
+            },
+            "counts": {
+                "dependencies": state.dep_repo.get_by_repository(&repository_id)?.len(),
+                "services": state.service_repo.get_by_repository(&repository_id)?.len(),
+                "endpoints": state.endpoint_repo.get_by_repository(&repository_id)?.len(),
+                "tests": state.test_repo.get_by_repository(&repository_id)?.len(),
+            },
+        }))
+    })();
+
+    match result {
+        Ok(stats) => HttpResponse::Ok().json(stats),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,210 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Deserialize;
+use crate::analysis::{CrossRepoLink, SystemLinker, SystemMember};
+use crate::api::{ApiState, ErrorResponse};
+use crate::api::jobs::foreign_repository;
+use crate::api::related::existing_checkout;
+use crate::api::schemas::SchemaVersionQuery;
+use crate::api::workspaces::CurrentWorkspace;
+use crate::graph::GraphBuilder;
+use crate::report::schema::{versioned, SchemaKind};
+use crate::storage::System;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSystemRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub repository_ids: Vec<String>,
+}
+
+fn internal_error(e: impl ToString) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: e.to_string(),
+    })
+}
+
+/// The system, when it belongs to the caller's workspace
+fn find_system(state: &ApiState, workspace: &CurrentWorkspace, id: &str) -> Result<System, HttpResponse> {
+    match state.system_repo.find_by_id(id) {
+        Ok(Some(system)) if system.workspace_id == workspace.0 => Ok(system),
+        Ok(_) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "System not found".to_string(),
+        })),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+/// What the last analysis of each member stored, with its checkout when one is on disk
+fn members(state: &ApiState, system: &System) -> anyhow::Result<Vec<SystemMember>> {
+    let mut members = Vec::new();
+    for repository_id in &system.repository_ids {
+        let Some(repository) = state.repo_repo.find_by_id(repository_id)? else { continue };
+        members.push(SystemMember {
+            dependencies: state.dep_repo.get_by_repository(repository_id)?,
+            endpoints: state.endpoint_repo.get_by_repository(repository_id)?,
+            resources: state.security_repo.get_entities(repository_id)?,
+            checkout: existing_checkout(&repository.url),
+            repository,
+        });
+    }
+    Ok(members)
+}
+
+/// Links between the system's repositories; searching the checkouts for endpoint calls reads
+/// every source file, so it runs off the request thread
+async fn system_links(state: &ApiState, system: &System) -> Result<Vec<CrossRepoLink>, HttpResponse> {
+    let members = members(state, system).map_err(internal_error)?;
+    web::block(move || SystemLinker::new().link(&members)).await.map_err(internal_error)
+}
+
+/// Systems of the caller's workspace
+pub async fn list_systems(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+) -> impl Responder {
+    match state.system_repo.list_by_workspace(&workspace.0) {
+        Ok(systems) => HttpResponse::Ok().json(systems),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Group repositories of the caller's workspace into a system
+pub async fn create_system(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    workspace: CurrentWorkspace,
+    body: web::Json<CreateSystemRequest>,
+) -> impl Responder {
+    let name = body.name.trim();
+    if name.is_empty() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "System name is required".to_string(),
+        });
+    }
+    if let Some(response) = foreign_repository(&state, &workspace, &body.repository_ids) {
+        return response;
+    }
+
+    match state.system_repo.create(&workspace.0, name, body.description.as_deref(), &body.repository_ids) {
+        Ok(system) => HttpResponse::Created().json(system),
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn get_system(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    match find_system(&state, &workspace, &path.into_inner()) {
+        Ok(system) => HttpResponse::Ok().json(system),
+        Err(response) => response,
+    }
+}
+
+pub async fn delete_system(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    let system = match find_system(&state, &workspace, &path.into_inner()) {
+        Ok(system) => system,
+        Err(response) => return response,
+    };
+    match state.system_repo.delete(&system.id) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Add a repository of the caller's workspace to the system
+pub async fn add_system_repository(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (id, repository_id) = path.into_inner();
+    let system = match find_system(&state, &workspace, &id) {
+        Ok(system) => system,
+        Err(response) => return response,
+    };
+    if let Some(response) = foreign_repository(&state, &workspace, std::slice::from_ref(&repository_id)) {
+        return response;
+    }
+    match state.system_repo.add_repository(&system.id, &repository_id).and_then(|_| state.system_repo.find_by_id(&system.id)) {
+        Ok(system) => HttpResponse::Ok().json(system),
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn remove_system_repository(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (id, repository_id) = path.into_inner();
+    let system = match find_system(&state, &workspace, &id) {
+        Ok(system) => system,
+        Err(response) => return response,
+    };
+    match state.system_repo.remove_repository(&system.id, &repository_id).and_then(|_| state.system_repo.find_by_id(&system.id)) {
+        Ok(system) => HttpResponse::Ok().json(system),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Cross-repository links of the system: package dependencies between its repositories, shared
+/// third-party packages, calls to each other's endpoints and shared cloud resources
+pub async fn get_system_links(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    let system = match find_system(&state, &workspace, &path.into_inner()) {
+        Ok(system) => system,
+        Err(response) => return response,
+    };
+    match system_links(&state, &system).await {
+        Ok(links) => HttpResponse::Ok().json(links),
+        Err(response) => response,
+    }
+}
+
+/// The graphs of the system's repositories joined by their cross-repository links, as a versioned
+/// graph document
+pub async fn get_system_graph(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+    query: web::Query<SchemaVersionQuery>,
+) -> impl Responder {
+    let schema_version = match query.resolve(&state) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+    let system = match find_system(&state, &workspace, &path.into_inner()) {
+        Ok(system) => system,
+        Err(response) => return response,
+    };
+    let links = match system_links(&state, &system).await {
+        Ok(links) => links,
+        Err(response) => return response,
+    };
+    let graph_builder = GraphBuilder::new(
+        state.repo_repo.db.clone(),
+        state.repo_repo.clone(),
+        state.dep_repo.clone(),
+        state.service_repo.clone(),
+        state.tool_repo.clone(),
+        state.code_relationship_repo.clone(),
+        state.test_repo.clone(),
+        state.port_repo.clone(),
+        state.endpoint_repo.clone(),
+    );
+
+    match graph_builder.build_for_system(&system.repository_ids, &links).and_then(|graph| versioned(SchemaKind::Graph, &graph, schema_version)) {
+        Ok(graph) => HttpResponse::Ok().json(graph),
+        Err(e) => internal_error(e),
+    }
+}
@@ -0,0 +1,36 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+
+/// Debt score and subscores from the latest analysis
+pub async fn get_tech_debt(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.tech_debt_repo.get_latest(&path.into_inner()) {
+        Ok(Some(score)) => HttpResponse::Ok().json(score),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "No technical debt score yet; analyze the repository first".to_string(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+/// Debt scores per analysis, oldest first, for trend charts (`?limit=`, default 50)
+pub async fn get_tech_debt_history(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(50);
+    match state.tech_debt_repo.get_history(&path.into_inner(), limit) {
+        Ok(history) => HttpResponse::Ok().json(history),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
@@ -47,3 +47,61 @@ pub async fn get_tests_by_framework(
     }
 }
 
+
+/// Get the tests that exercise a code element, looked up by `element_id`, `qualified_name` or `name`
+pub async fn get_tests_for_code(
+    state: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+
+    let elements = match state.code_repo.get_by_repository(&repository_id) {
+        Ok(elements) => elements,
+        Err(e) => {
+            log::error!("Failed to fetch code elements: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: format!("Failed to fetch code elements: {}", e),
+            });
+        }
+    };
+
+    let matching: Vec<_> = if let Some(element_id) = query.get("element_id") {
+        elements.into_iter().filter(|e| &e.id == element_id).collect()
+    } else if let Some(qualified_name) = query.get("qualified_name") {
+        elements.into_iter().filter(|e| &e.qualified_name == qualified_name).collect()
+    } else if let Some(name) = query.get("name") {
+        elements.into_iter().filter(|e| &e.name == name).collect()
+    } else {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Missing 'element_id', 'qualified_name' or 'name' query parameter".to_string(),
+        });
+    };
+
+    let mut results = Vec::new();
+    for element in matching {
+        match state.test_repo.get_tests_for_code_element(&repository_id, &element.id) {
+            Ok(tests) => {
+                let tests: Vec<serde_json::Value> = tests.into_iter()
+                    .map(|(test, link)| serde_json::json!({
+                        "test": test,
+                        "strategy": link.strategy,
+                        "confidence": link.confidence,
+                    }))
+                    .collect();
+                results.push(serde_json::json!({
+                    "element": element,
+                    "tests": tests,
+                }));
+            }
+            Err(e) => {
+                log::error!("Failed to fetch tests for code element: {}", e);
+                return HttpResponse::InternalServerError().json(ErrorResponse {
+                    error: format!("Failed to fetch tests: {}", e),
+                });
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(results)
+}
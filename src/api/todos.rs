@@ -0,0 +1,41 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use std::collections::HashMap;
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::StoredTodo;
+
+/// TODO/FIXME/HACK/XXX markers, filtered by `?kind=`, `?author=`, `?file=` (path prefix) and `?min_age_days=`
+pub async fn get_todos(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let min_age: Option<i64> = query.get("min_age_days").and_then(|d| d.parse().ok());
+    match state.todo_repo.get_by_repository(&path.into_inner()) {
+        Ok(todos) => {
+            let filtered: Vec<StoredTodo> = todos.into_iter()
+                .filter(|t| query.get("kind").map(|k| t.kind.eq_ignore_ascii_case(k)).unwrap_or(true))
+                .filter(|t| query.get("author").map(|a| t.author.as_deref() == Some(a.as_str())).unwrap_or(true))
+                .filter(|t| query.get("file").map(|f| t.file_path.starts_with(f.as_str())).unwrap_or(true))
+                .filter(|t| min_age.map(|min| t.age_days.map(|d| d >= min).unwrap_or(false)).unwrap_or(true))
+                .collect();
+            HttpResponse::Ok().json(filtered)
+        }
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+pub async fn get_todo_summary(
+    state: web::Data<ApiState>,
+    _req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    match state.todo_repo.get_summary(&path.into_inner()) {
+        Ok(summary) => HttpResponse::Ok().json(summary),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
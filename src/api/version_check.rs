@@ -67,10 +67,7 @@ fn is_newer_version(current: &str, latest: &str) -> bool {
 
 /// Check GitHub for latest release version
 async fn fetch_latest_version() -> Result<String, Box<dyn std::error::Error>> {
-    let client = reqwest::Client::builder()
-        .user_agent("wavelength-arch-decoder")
-        .timeout(Duration::from_secs(5))
-        .build()?;
+    let client = crate::network::http_client(Duration::from_secs(5))?;
     
     let response = client
         .get(GITHUB_API_URL)
@@ -109,7 +106,7 @@ pub async fn check_for_updates(force: bool) -> VersionInfo {
         .unwrap_or_else(|_| "true".to_string())
         .to_lowercase() == "true";
     
-    if !check_enabled {
+    if !check_enabled || crate::network::is_offline() {
         return VersionInfo {
             current: current.clone(),
             latest: None,
@@ -0,0 +1,201 @@
+use actix_web::{web, HttpResponse, Responder};
+use anyhow::anyhow;
+use serde::Deserialize;
+use crate::analysis::Detection;
+use crate::api::{ApiState, ErrorResponse};
+use crate::api::workspaces::CurrentWorkspace;
+use crate::config::SecretsConfig;
+use crate::ingestion::secrets::{self, SecretRef};
+use crate::integrations::webhooks::{deliver, AnalysisNotification};
+use crate::security::SecurityVulnerability;
+use crate::storage::{Repository, Webhook, WebhookKind};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub kind: WebhookKind,
+    /// The webhook URL or a secret reference to it (`env:SLACK_WEBHOOK_URL`)
+    pub url: String,
+    /// Signs generic payloads; may be a secret reference
+    pub secret: Option<String>,
+    #[serde(default = "enabled_by_default")]
+    pub on_completed: bool,
+    #[serde(default = "enabled_by_default")]
+    pub on_failed: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn internal_error(e: impl ToString) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: e.to_string(),
+    })
+}
+
+fn bad_request(error: String) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse { error })
+}
+
+/// Webhooks only reach repositories of the caller's workspace
+fn check_repository(state: &ApiState, workspace: &CurrentWorkspace, repository_id: &str) -> Result<(), HttpResponse> {
+    match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(repository)) if repository.workspace_id == workspace.0 => Ok(()),
+        Ok(_) => Err(HttpResponse::NotFound().json(ErrorResponse {
+            error: "Repository not found".to_string(),
+        })),
+        Err(e) => Err(internal_error(e)),
+    }
+}
+
+fn fingerprints(vulnerabilities: &[SecurityVulnerability]) -> Vec<String> {
+    vulnerabilities.iter().map(|v| Detection::vulnerability(v).fingerprint()).collect()
+}
+
+pub async fn list_webhooks(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    match state.webhook_repo.get_by_repository(&repository_id) {
+        Ok(webhooks) => HttpResponse::Ok().json(webhooks),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Post a summary to the URL when the repository's analyses complete or fail
+pub async fn create_webhook(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<String>,
+    body: web::Json<CreateWebhookRequest>,
+) -> impl Responder {
+    let repository_id = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    let url = body.url.trim();
+    match SecretRef::parse(url) {
+        SecretRef::Literal(_) if !url.starts_with("https://") && !url.starts_with("http://") => {
+            return bad_request("url must be an http(s) URL or a secret reference".to_string());
+        }
+        // The summary is posted to the looked-up URL, so a reference could send a server
+        // secret anywhere; only the operator's allowed prefixes are accepted
+        _ => if let Err(e) = SecretRef::check_allowed(url, &state.config.secrets, &workspace.0) {
+            return bad_request(format!("url: {}", e));
+        }
+    }
+    if let Some(Err(e)) = body.secret.as_deref().map(|s| SecretRef::check_allowed(s, &state.config.secrets, &workspace.0)) {
+        return bad_request(format!("secret: {}", e));
+    }
+    if !body.on_completed && !body.on_failed {
+        return bad_request("A webhook needs on_completed or on_failed".to_string());
+    }
+
+    // Vulnerabilities found before the first webhook are not news to it
+    let seeded = match state.webhook_repo.vulnerability_baseline(&repository_id) {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => state.security_repo.get_vulnerabilities(&repository_id)
+            .and_then(|v| state.webhook_repo.set_vulnerability_baseline(&repository_id, &fingerprints(&v))),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = seeded {
+        return internal_error(e);
+    }
+
+    match state.webhook_repo.create(&repository_id, body.kind, url, body.secret.as_deref(), body.on_completed, body.on_failed) {
+        Ok(webhook) => HttpResponse::Created().json(webhook),
+        Err(e) => internal_error(e),
+    }
+}
+
+pub async fn delete_webhook(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (repository_id, webhook_id) = path.into_inner();
+    if let Err(response) = check_repository(&state, &workspace, &repository_id) {
+        return response;
+    }
+    match state.webhook_repo.delete(&repository_id, &webhook_id) {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Webhook not found".to_string(),
+        }),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// Notify the repository's webhooks of a finished analysis; `error` is why it failed
+pub(crate) fn spawn_notifications(state: &web::Data<ApiState>, repository_id: &str, error: Option<String>) {
+    let state = state.clone();
+    let repository_id = repository_id.to_string();
+    actix_web::rt::spawn(async move { notify(&state, &repository_id, error.as_deref()).await });
+}
+
+pub(crate) async fn notify(state: &ApiState, repository_id: &str, error: Option<&str>) {
+    let webhooks: Vec<Webhook> = match state.webhook_repo.get_by_repository(repository_id) {
+        Ok(webhooks) => webhooks.into_iter()
+            .filter(|w| if error.is_some() { w.on_failed } else { w.on_completed })
+            .collect(),
+        Err(e) => {
+            log::warn!("⚠ Failed to load the webhooks of {}: {}", repository_id, e);
+            return;
+        }
+    };
+    if webhooks.is_empty() || crate::network::is_offline() {
+        return;
+    }
+    let repository = match state.repo_repo.find_by_id(repository_id) {
+        Ok(Some(repository)) => repository,
+        _ => return,
+    };
+
+    let base_url = state.config.server.base_url();
+    let notification = match error {
+        Some(error) => AnalysisNotification::failed(&repository, error, &base_url),
+        None => match completed(state, &repository, &base_url) {
+            Ok(notification) => notification,
+            Err(e) => {
+                log::warn!("⚠ Webhook notification for {} was not built: {:#}", repository.name, e);
+                return;
+            }
+        },
+    };
+    for webhook in webhooks {
+        let error = send(&webhook, &notification, &state.config.secrets, &repository.workspace_id).await.err().map(|e| format!("{:#}", e));
+        match &error {
+            Some(e) => log::warn!("⚠ {} webhook for {} failed: {}", webhook.kind.as_str(), repository.name, e),
+            None => log::info!("✓ Notified {} webhook of {}", webhook.kind.as_str(), repository.name),
+        }
+        if let Err(e) = state.webhook_repo.record_delivery(&webhook.id, error.as_deref()) {
+            log::warn!("⚠ Failed to record webhook delivery: {}", e);
+        }
+    }
+}
+
+/// Moves the vulnerability baseline on, so each vulnerability is reported as new once
+fn completed(state: &ApiState, repository: &Repository, base_url: &str) -> anyhow::Result<AnalysisNotification> {
+    let vulnerabilities = state.security_repo.get_vulnerabilities(&repository.id)?;
+    let baseline = state.webhook_repo.vulnerability_baseline(&repository.id)?;
+    let snapshots = state.release_repo.get_snapshots(&repository.id, 2)?;
+    let notification = AnalysisNotification::completed(repository, &vulnerabilities, baseline.as_deref(), &snapshots, base_url);
+    state.webhook_repo.set_vulnerability_baseline(&repository.id, &fingerprints(&vulnerabilities))?;
+    Ok(notification)
+}
+
+async fn send(webhook: &Webhook, notification: &AnalysisNotification, allowed: &SecretsConfig, workspace: &str) -> anyhow::Result<()> {
+    // References can block while they are looked up
+    let (url, secret) = (webhook.url.clone(), webhook.secret.clone());
+    let (allowed, workspace) = (allowed.clone(), workspace.to_string());
+    let (url, secret) = web::block(move || -> anyhow::Result<_> {
+        let resolve = |value: &str| secrets::resolve_allowed(value, &allowed, &workspace);
+        Ok((resolve(&url)?, secret.map(|s| resolve(&s)).transpose()?))
+    }).await.map_err(|e| anyhow!("resolving the webhook URL: {}", e))??;
+    deliver(webhook.kind, &url, secret.as_deref(), notification).await
+}
@@ -0,0 +1,172 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::future::{ready, Ready};
+use crate::api::{ApiState, ErrorResponse};
+use crate::storage::DEFAULT_WORKSPACE;
+
+/// Names the workspace for requests that do not carry a workspace token
+pub const WORKSPACE_HEADER: &str = "X-Workspace-Id";
+
+/// The workspace a request acts in. A `Bearer` token selects the workspace it was issued for;
+/// otherwise the `X-Workspace-Id` header names one, and without either the default workspace is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurrentWorkspace(pub String);
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+    /// When set, requests reach this workspace only with `Authorization: Bearer <token>`
+    pub token: Option<String>,
+}
+
+fn error(status: actix_web::http::StatusCode, message: String) -> HttpResponse {
+    HttpResponse::build(status).json(ErrorResponse { error: message })
+}
+
+fn resolve(state: &ApiState, req: &HttpRequest) -> Result<CurrentWorkspace, HttpResponse> {
+    use actix_web::http::StatusCode;
+
+    let bearer = req.headers().get(actix_web::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    if let Some(token) = bearer {
+        return match state.workspace_repo.find_by_token(token) {
+            Ok(Some(workspace)) => Ok(CurrentWorkspace(workspace.id)),
+            Ok(None) => Err(error(StatusCode::UNAUTHORIZED, "Unknown workspace token".to_string())),
+            Err(e) => Err(error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+        };
+    }
+
+    let id = req.headers().get(WORKSPACE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(DEFAULT_WORKSPACE);
+    match state.workspace_repo.find_by_id(id) {
+        Ok(Some(workspace)) if workspace.has_token => Err(error(
+            StatusCode::UNAUTHORIZED,
+            format!("Workspace {} requires its token", id),
+        )),
+        Ok(Some(workspace)) => Ok(CurrentWorkspace(workspace.id)),
+        Ok(None) => Err(error(StatusCode::NOT_FOUND, format!("Workspace {} not found", id))),
+        Err(e) => Err(error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+}
+
+impl FromRequest for CurrentWorkspace {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        if let Some(workspace) = req.extensions().get::<CurrentWorkspace>() {
+            return ready(Ok(workspace.clone()));
+        }
+        let result = match req.app_data::<web::Data<ApiState>>() {
+            Some(state) => resolve(state, req)
+                .map_err(|response| InternalError::from_response("workspace", response).into()),
+            None => Ok(CurrentWorkspace(DEFAULT_WORKSPACE.to_string())),
+        };
+        ready(result)
+    }
+}
+
+/// Repository ids a request touches: `/repositories/{id}/...` and `?repository_id=`
+fn requested_repositories(req: &ServiceRequest) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut segments = req.path().split('/');
+    while let Some(segment) = segments.next() {
+        if segment == "repositories" {
+            if let Some(id) = segments.next().filter(|id| !id.is_empty()) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    if let Ok(query) = web::Query::<HashMap<String, String>>::from_query(req.query_string()) {
+        ids.extend(query.get("repository_id").cloned());
+    }
+    ids
+}
+
+/// Resolves the request's workspace and answers 404 for repositories owned by another one, so
+/// per-repository handlers never see data outside the caller's workspace
+pub async fn scope_to_workspace(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let denied = match req.app_data::<web::Data<ApiState>>().cloned() {
+        Some(state) => match resolve(&state, req.request()) {
+            Ok(workspace) => {
+                let foreign = requested_repositories(&req).into_iter().any(|id| {
+                    matches!(state.repo_repo.find_by_id(&id), Ok(Some(repo)) if repo.workspace_id != workspace.0)
+                });
+                req.extensions_mut().insert(workspace);
+                foreign.then(|| error(actix_web::http::StatusCode::NOT_FOUND, "Repository not found".to_string()))
+            }
+            Err(response) => Some(response),
+        },
+        None => None,
+    };
+
+    match denied {
+        Some(response) => Ok(req.into_response(response)),
+        None => Ok(next.call(req).await?.map_into_boxed_body()),
+    }
+}
+
+/// The workspaces the caller can reach: its own, and those that need no token
+pub async fn list_workspaces(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+) -> impl Responder {
+    match state.workspace_repo.list_all() {
+        Ok(workspaces) => HttpResponse::Ok().json(
+            workspaces.into_iter().filter(|w| w.id == workspace.0 || !w.has_token).collect::<Vec<_>>(),
+        ),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+pub async fn get_current_workspace(
+    state: web::Data<ApiState>,
+    workspace: CurrentWorkspace,
+) -> impl Responder {
+    match state.workspace_repo.find_by_id(&workspace.0) {
+        Ok(Some(workspace)) => HttpResponse::Ok().json(workspace),
+        Ok(None) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Workspace not found".to_string(),
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
+
+pub async fn create_workspace(
+    state: web::Data<ApiState>,
+    body: web::Json<CreateWorkspaceRequest>,
+) -> impl Responder {
+    if body.name.trim().is_empty() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Workspace name is required".to_string(),
+        });
+    }
+    if body.token.as_deref().is_some_and(|t| t.len() < 16) {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Workspace tokens must be at least 16 characters".to_string(),
+        });
+    }
+    match state.workspace_repo.create(body.name.trim(), body.token.as_deref()) {
+        Ok(workspace) => HttpResponse::Created().json(workspace),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse {
+            error: e.to_string(),
+        }),
+    }
+}
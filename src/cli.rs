@@ -0,0 +1,375 @@
+use actix_web::web;
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use crate::analysis::AnalysisProfile;
+use crate::analysis::explain::Explainer;
+use crate::analysis::rule_tests::{find_fixtures, RuleTestHarness, EXPECTATIONS_FILE};
+use crate::api::gate::{run_gate, GateOverrides};
+use crate::analyzer::pipeline::{perform_analysis, AnalysisResult};
+use crate::api::server::build_state;
+use crate::api::ApiState;
+use crate::analyzer::{find_or_create, AnalysisReport};
+use crate::config::Config;
+use crate::ingestion::RepositoryCrawler;
+use crate::report::markdown::markdown_summary;
+use crate::report::sarif::sarif;
+use crate::report::schema::{versioned, SchemaKind};
+use crate::storage::Repository;
+
+#[derive(Parser)]
+#[command(name = "wavelength", version, about = "Understand repository architecture through knowledge graphs")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Make no outbound network calls (same as OFFLINE=1)
+    #[arg(long, global = true)]
+    pub offline: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start the HTTP server and web UI (the default)
+    Serve,
+    /// Analyze a repository without starting the server and write the results to disk
+    Analyze(AnalyzeArgs),
+    /// Analyze a repository and fail when it breaks the gate's thresholds, for CI pipelines
+    Gate(GateArgs),
+    /// Work with detection patterns and plugins
+    Rules(RulesArgs),
+    /// Report what an analysis of a local checkout would read, skip and match, without analyzing or storing anything
+    Explain(ExplainArgs),
+}
+
+impl Command {
+    /// Exit code when the configuration is invalid. The gate and rule tests use 2, "could not
+    /// run", so a CI pipeline never mistakes a broken threshold for a passing one.
+    pub fn config_error_code(command: Option<&Command>) -> i32 {
+        match command {
+            Some(Command::Gate(_)) | Some(Command::Rules(_)) => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// analysis.json: the stored results
+    Json,
+    /// summary.md
+    Markdown,
+    /// results.sarif: security findings for code scanning
+    Sarif,
+}
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    /// Local path or git URL
+    pub target: String,
+    #[arg(long)]
+    pub branch: Option<String>,
+    /// fast, standard or deep
+    #[arg(long, value_parser = parse_profile)]
+    pub profile: Option<AnalysisProfile>,
+    /// Directory the results are written to
+    #[arg(long, short, default_value = "wavelength-results")]
+    pub out: PathBuf,
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "json,markdown,sarif")]
+    pub format: Vec<OutputFormat>,
+    /// Write analysis.json in this older schema version (same as OUTPUT_SCHEMA_VERSION)
+    #[arg(long)]
+    pub schema_version: Option<u32>,
+    /// SQLite database to store the analysis in, instead of the configured one
+    #[arg(long)]
+    pub database: Option<PathBuf>,
+}
+
+/// Thresholds left unset come from the `[gate]` configuration
+#[derive(Args)]
+pub struct GateArgs {
+    /// Local path or git URL
+    pub target: String,
+    #[arg(long)]
+    pub branch: Option<String>,
+    /// fast, standard or deep
+    #[arg(long, value_parser = parse_profile)]
+    pub profile: Option<AnalysisProfile>,
+    /// Compare services with the latest analysis of this branch instead of the previous analysis
+    #[arg(long)]
+    pub baseline_branch: Option<String>,
+    /// Check the latest stored analysis instead of analyzing again
+    #[arg(long)]
+    pub skip_analysis: bool,
+    #[arg(long)]
+    pub max_critical: Option<usize>,
+    #[arg(long)]
+    pub max_high: Option<usize>,
+    #[arg(long)]
+    pub allow_new_services: bool,
+    #[arg(long)]
+    pub max_dependency_staleness_days: Option<u64>,
+    /// Print the result as JSON
+    #[arg(long)]
+    pub json: bool,
+    /// Schema version of the `--json` output
+    #[arg(long)]
+    pub schema_version: Option<u32>,
+    #[arg(long)]
+    pub database: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct RulesArgs {
+    #[command(subcommand)]
+    pub command: RulesCommand,
+}
+
+#[derive(Subcommand)]
+pub enum RulesCommand {
+    /// Run the patterns and plugins against fixture directories and check their `expected.yml`
+    Test(RulesTestArgs),
+}
+
+#[derive(Args)]
+pub struct RulesTestArgs {
+    /// Fixture directories, or directories to search for them
+    #[arg(default_value = "tests/rules")]
+    pub paths: Vec<PathBuf>,
+    /// Pattern file the plugin patterns are merged into
+    #[arg(long, default_value = "config/service_patterns.json")]
+    pub patterns: PathBuf,
+    #[arg(long, default_value = "config/plugins")]
+    pub plugins: PathBuf,
+    /// List what each fixture detected, including passing ones
+    #[arg(long, short)]
+    pub verbose: bool,
+    /// Print the results as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Local checkout
+    pub path: PathBuf,
+    /// fast, standard or deep
+    #[arg(long, value_parser = parse_profile)]
+    pub profile: Option<AnalysisProfile>,
+    /// Explain these files or directories, relative to the checkout (repeatable)
+    #[arg(long = "file")]
+    pub files: Vec<String>,
+    #[arg(long, default_value = "config/service_patterns.json")]
+    pub patterns: PathBuf,
+    #[arg(long, default_value = "config/plugins")]
+    pub plugins: PathBuf,
+    /// Explain every file, not only those with matches or near misses
+    #[arg(long, short)]
+    pub verbose: bool,
+    /// Print the report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+fn parse_profile(value: &str) -> Result<AnalysisProfile, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("unknown profile '{}', expected fast, standard or deep", value))
+}
+
+/// Runs the same pipeline as `POST /api/v1/repositories/{id}/analyze` and returns the updated repository
+async fn run_analysis(state: &web::Data<ApiState>, repository: Repository, profile: Option<AnalysisProfile>) -> Result<(Repository, AnalysisResult)> {
+    state.progress_tracker.start_analysis(&repository.id, 13);
+    let analysis_state = state.clone();
+    let repository_id = repository.id.clone();
+    let result = web::block(move || perform_analysis(&analysis_state, &repository_id, profile))
+        .await
+        .map_err(|e| anyhow!("analysis task failed: {}", e))??;
+    log::info!("✓ {} ({} profile)", result.message, result.profile.as_str());
+    let repository = state.repo_repo.find_by_id(&repository.id)?.unwrap_or(repository);
+    Ok((repository, result))
+}
+
+fn with_database(mut config: Config, database: &Option<PathBuf>) -> Config {
+    if let Some(database) = database {
+        config.database.database_path = database.to_string_lossy().to_string();
+    }
+    config
+}
+
+/// Analyzes the target, then exports the results
+pub async fn analyze(config: Config, args: AnalyzeArgs) -> Result<()> {
+    let config = with_database(config, &args.database);
+    let state = build_state(&config)?;
+    let repository = find_or_create(&state, &args.target, args.branch.as_deref().unwrap_or("main"), true)?;
+    let (repository, result) = run_analysis(&state, repository, args.profile).await?;
+
+    let report = AnalysisReport::collect(&state, repository, result.profile, result.results)?;
+    let root = RepositoryCrawler::new(&config.storage)?.get_repo_path(&report.repository.url);
+
+    std::fs::create_dir_all(&args.out).with_context(|| format!("creating {}", args.out.display()))?;
+    let mut written = Vec::new();
+    for format in &args.format {
+        let (file, contents) = match format {
+            OutputFormat::Json => ("analysis.json", serde_json::to_string_pretty(
+                &versioned(SchemaKind::Analysis, &report, args.schema_version.or(config.output.schema_version))?,
+            )?),
+            OutputFormat::Markdown => ("summary.md", markdown_summary(
+                &report.repository, &report.dependencies, &report.services, &report.endpoints, &report.vulnerabilities, &root,
+            )),
+            OutputFormat::Sarif => ("results.sarif", serde_json::to_string_pretty(&sarif(&report.vulnerabilities, &root))?),
+        };
+        let path = args.out.join(file);
+        std::fs::write(&path, contents).with_context(|| format!("writing {}", path.display()))?;
+        written.push(path);
+    }
+    for path in written {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+/// Analyzes the target (unless `--skip-analysis`) and checks it against the gate's thresholds.
+/// `Ok(false)` means a threshold was breached.
+pub async fn gate(config: Config, args: GateArgs) -> Result<bool> {
+    let config = with_database(config, &args.database);
+    let state = build_state(&config)?;
+    let branch = args.branch.as_deref().unwrap_or("main");
+    let mut repository = find_or_create(&state, &args.target, branch, !args.skip_analysis)?;
+    if !args.skip_analysis {
+        repository = run_analysis(&state, repository, args.profile).await?.0;
+    } else if repository.last_analyzed_at.is_none() {
+        return Err(anyhow!("{} has not been analyzed", repository.name));
+    }
+    let baseline = match &args.baseline_branch {
+        Some(baseline_branch) => Some(find_or_create(&state, &args.target, baseline_branch, false)?),
+        None => None,
+    };
+
+    let overrides = GateOverrides {
+        max_critical: args.max_critical,
+        max_high: args.max_high,
+        allow_new_services: args.allow_new_services.then_some(true),
+        max_dependency_staleness_days: args.max_dependency_staleness_days,
+    };
+    let result = run_gate(&state, &repository.id, baseline.as_ref().map(|b| b.id.as_str()), &overrides.apply(&config.gate))?;
+    if args.json {
+        let document = versioned(SchemaKind::Gate, &result, args.schema_version.or(config.output.schema_version))?;
+        println!("{}", serde_json::to_string_pretty(&document)?);
+    } else {
+        for check in &result.checks {
+            println!("{} {}: {}", if check.passed { "✓" } else { "✗" }, check.name, check.detail);
+        }
+        println!("Gate {}", if result.passed { "passed" } else { "failed" });
+    }
+    Ok(result.passed)
+}
+
+/// Whether every fixture passed
+pub fn test_rules(args: RulesTestArgs) -> Result<bool> {
+    let harness = RuleTestHarness::load(&args.patterns, &args.plugins)?;
+    let fixtures = find_fixtures(&args.paths);
+    if fixtures.is_empty() {
+        return Err(anyhow!("no fixtures (directories with {}) found", EXPECTATIONS_FILE));
+    }
+    let results = fixtures.iter()
+        .map(|fixture| harness.run(fixture))
+        .collect::<Result<Vec<_>>>()?;
+    let failed = results.iter().filter(|r| !r.passed).count();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        for result in &results {
+            println!("{} {}", if result.passed { "✓" } else { "✗" }, result.fixture);
+            for failure in &result.failures {
+                println!("    {}", failure);
+            }
+            if args.verbose || !result.passed {
+                for service in &result.services {
+                    println!("    service: {}", service);
+                }
+                for finding in &result.findings {
+                    println!("    finding: {}", finding);
+                }
+            }
+        }
+        println!("{} fixture(s), {} passed, {} failed", results.len(), results.len() - failed, failed);
+    }
+    Ok(failed == 0)
+}
+
+pub fn explain(config: Config, args: ExplainArgs) -> Result<()> {
+    if !args.path.is_dir() {
+        return Err(anyhow!("{} is not a local checkout; clone the repository first", args.path.display()));
+    }
+    let explainer = Explainer::load(&args.patterns, &args.plugins)?;
+    let mut report = explainer.explain(&args.path, args.profile, &config.analysis)?;
+    if !args.files.is_empty() {
+        report.files.retain(|file| args.files.iter().any(|wanted| {
+            let wanted = wanted.trim_start_matches("./").trim_end_matches('/');
+            file.path == wanted || file.path.starts_with(&format!("{}/", wanted))
+        }));
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{} ({} profile{})", report.repository, report.profile.as_str(),
+        report.config_file.as_deref().map(|f| format!(", {}", f)).unwrap_or_default());
+    for warning in &report.warnings {
+        println!("⚠ {}", warning);
+    }
+    if !report.disabled_analyzers.is_empty() {
+        println!("Disabled analyzers: {}", report.disabled_analyzers.join(", "));
+    }
+    if !report.skipped_directories.is_empty() {
+        println!("Skipped directories: {}", report.skipped_directories.join(", "));
+    }
+    println!("Files read:");
+    for (analyzer, count) in &report.analyzers {
+        println!("    {}: {}", analyzer, count);
+    }
+    println!("Files skipped:");
+    for (reason, count) in &report.skip_reasons {
+        println!("    {}: {}", reason, count);
+    }
+    println!("Services:");
+    for service in &report.services {
+        println!("    {} {} ({:?}, {:.2}) from {}{}", if service.kept { "✓" } else { "✗" }, service.name, service.provider,
+            service.confidence, service.file_path, service.reason.as_deref().map(|r| format!(": {}", r)).unwrap_or_default());
+    }
+
+    let show_all = args.verbose || !args.files.is_empty();
+    for file in report.files.iter().filter(|f| show_all || !f.matches.is_empty() || !f.near_misses.is_empty()) {
+        println!("{}", file.path);
+        if !file.analyzed_by.is_empty() {
+            println!("    read by: {}", file.analyzed_by.join(", "));
+        }
+        for reason in &file.skipped {
+            println!("    skipped by {}", reason);
+        }
+        if let Some(excluded) = &file.excluded {
+            println!("    results left out: {}", excluded);
+        }
+        for detection in &file.matches {
+            println!("    matched: {}", detection);
+        }
+        for miss in &file.near_misses {
+            println!("    near miss: {}", miss);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_config_problems_exit_as_could_not_run() {
+        let gate = Cli::try_parse_from(["wavelength-arch-decoder", "gate", "."]).unwrap();
+        assert_eq!(Command::config_error_code(gate.command.as_ref()), 2);
+        let serve = Cli::try_parse_from(["wavelength-arch-decoder"]).unwrap();
+        assert_eq!(Command::config_error_code(serve.command.as_ref()), 1);
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+
+const MONTHS: &[&str] = &["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// How far ahead to look for the next matching minute; `0 0 29 2 mon` can take years
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 8;
+
+/// A five-field cron expression (`minute hour day-of-month month day-of-week`), evaluated in UTC.
+/// Fields take `*`, values, `a-b` ranges, `/n` steps and comma lists; months and weekdays also
+/// take three-letter names, and Sunday is 0 or 7. As in Vixie cron, a day matches when either
+/// day field does if both are restricted. `@hourly`, `@daily`, `@weekly`, `@monthly` and
+/// `@yearly` stand for the usual expressions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<CronSchedule> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            bail!("Cron expression '{}' needs 5 fields (minute hour day-of-month month day-of-week), got {}", expression, fields.len());
+        }
+        let mut weekdays = parse_field(fields[4], 0, 7, WEEKDAYS).context("day-of-week")?;
+        // 7 is another name for Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(CronSchedule {
+            minutes: parse_field(fields[0], 0, 59, &[]).context("minute")?,
+            hours: parse_field(fields[1], 0, 23, &[]).context("hour")?,
+            days: parse_field(fields[2], 1, 31, &[]).context("day-of-month")?,
+            months: parse_field(fields[3], 1, 12, MONTHS).context("month")?,
+            weekdays,
+            days_restricted: !fields[2].starts_with('*'),
+            weekdays_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    /// The first matching minute strictly after `after`, if there is one within the next years
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after + Duration::days(MAX_LOOKAHEAD_DAYS);
+        while t <= limit {
+            if !matches(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = midnight(NaiveDate::from_ymd_opt(year, month, 1)?);
+            } else if !self.day_matches(t) {
+                t = midnight(t.date_naive().succ_opt()?);
+            } else if !matches(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if !matches(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let day = matches(self.days, t.day());
+        let weekday = matches(self.weekdays, t.weekday().num_days_from_sunday());
+        if self.days_restricted && self.weekdays_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+fn matches(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_time(chrono::NaiveTime::MIN))
+}
+
+/// The field's values as a bit set
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0).with_context(|| format!("invalid step in '{}'", part))?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start, min, names)?, value(end, min, names)?)
+        } else {
+            let start = value(range, min, names)?;
+            // `5/15` runs from 5 to the end of the range
+            (start, if step > 1 { max } else { start })
+        };
+        if start < min || end > max || start > end {
+            bail!("'{}' is outside {}-{}", part, min, max);
+        }
+        for v in (start..=end).step_by(step as usize) {
+            set |= 1 << v;
+        }
+    }
+    Ok(set)
+}
+
+fn value(text: &str, min: u32, names: &[&str]) -> Result<u32> {
+    let lower = text.to_lowercase();
+    if let Some(i) = names.iter().position(|n| *n == lower) {
+        return Ok(i as u32 + min);
+    }
+    text.parse().with_context(|| format!("invalid value '{}'", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        // Friday evening to Monday morning
+        let workday = CronSchedule::parse("*/15 9-17 * * mon-fri").unwrap();
+        assert_eq!(workday.next_after(at("2026-10-16T17:50:00Z")), Some(at("2026-10-19T09:00:00Z")));
+        assert_eq!(workday.next_after(at("2026-10-19T09:00:30Z")), Some(at("2026-10-19T09:15:00Z")));
+
+        let nightly = CronSchedule::parse("@daily").unwrap();
+        assert_eq!(nightly.next_after(at("2026-12-31T23:59:59Z")), Some(at("2027-01-01T00:00:00Z")));
+
+        // Either day field matches when both are given: the 1st, or any Sunday
+        let either = CronSchedule::parse("30 4 1 * 7").unwrap();
+        assert_eq!(either.next_after(at("2026-10-17T12:00:00Z")), Some(at("2026-10-18T04:30:00Z")));
+
+        let leap = CronSchedule::parse("0 0 29 feb *").unwrap();
+        assert_eq!(leap.next_after(at("2026-03-01T00:00:00Z")), Some(at("2028-02-29T00:00:00Z")));
+
+        assert!(CronSchedule::parse("0 3 * *").is_err());
+        assert!(CronSchedule::parse("61 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 pub mod queue;
 pub mod processor;
 pub mod webhooks;
+pub mod cron;
 
 pub use queue::{JobQueue, AnalysisJob, JobType, JobStatus, Scheduler, ScheduledJob};
+pub use cron::CronSchedule;
 
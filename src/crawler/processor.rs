@@ -1,7 +1,7 @@
 use anyhow::Result;
 use std::sync::{Arc, Mutex};
 use tokio::time::{sleep, Duration};
-use crate::crawler::{JobQueue, AnalysisJob, JobType, JobStatus, Scheduler, ScheduledJob};
+use crate::crawler::{JobQueue, AnalysisJob, JobType, JobStatus, Scheduler, ScheduledJob, CronSchedule};
 use crate::api::ApiState;
 use log::{info, warn};
 use chrono::Utc;
@@ -156,9 +156,11 @@ impl JobProcessor {
                     let mut sched = scheduler.lock().unwrap();
                     if let Some(job) = sched.get_job_mut(&scheduled_job.id) {
                         job.last_run = Some(Utc::now());
-                        // TODO: Calculate next_run based on cron expression
-                        // For now, just set it to 24 hours from now
-                        job.next_run = Some(Utc::now() + chrono::Duration::hours(24));
+                        job.next_run = CronSchedule::parse(&job.schedule).ok().and_then(|c| c.next_after(Utc::now()));
+                        if job.next_run.is_none() {
+                            warn!("Disabling scheduled job {}: '{}' never runs again", job.name, job.schedule);
+                            job.enabled = false;
+                        }
                     }
                 }
             }
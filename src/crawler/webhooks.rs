@@ -1,6 +1,9 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use serde::Deserialize;
-use crate::api::ApiState;
+use crate::api::{ApiState, ErrorResponse};
+use crate::integrations::github::{verify_signature, AnalysisDiff, GitHubClient};
+use crate::ingestion::{RepositoryCrawler, RepositoryCredentials};
+use crate::storage::Repository;
 use log::info;
 
 #[derive(Debug, Deserialize)]
@@ -9,6 +12,26 @@ pub struct GitHubWebhookPayload {
     pub repository: Option<GitHubRepository>,
     #[allow(dead_code)]
     pub pusher: Option<GitHubPusher>,
+    pub pull_request: Option<GitHubPullRequest>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubPullRequest {
+    pub number: u64,
+    pub head: GitHubBranchRef,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubBranchRef {
+    #[serde(rename = "ref")]
+    pub branch: String,
+    pub sha: String,
+    pub repo: Option<GitHubRepositoryName>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubRepositoryName {
+    pub full_name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -59,14 +82,55 @@ pub struct GitLabProject {
 
 /// Handle GitHub webhook
 pub async fn handle_github_webhook(
-    _state: web::Data<ApiState>,
-    _req: HttpRequest,
-    body: web::Json<GitHubWebhookPayload>,
+    state: web::Data<ApiState>,
+    req: HttpRequest,
+    body: web::Bytes,
 ) -> impl Responder {
-    // TODO: Verify webhook signature
-    
-    info!("Received GitHub webhook: {:?}", body.action);
-    
+    if let Some(secret) = state.config.github.webhook_secret.clone() {
+        // A secret that cannot be looked up rejects every delivery rather than accepting them unsigned
+        let secret = match web::block(move || crate::ingestion::secrets::resolve(&secret)).await {
+            Ok(Ok(secret)) => secret,
+            Ok(Err(e)) => return HttpResponse::ServiceUnavailable().json(ErrorResponse {
+                error: format!("Webhook secret is unavailable: {:#}", e),
+            }),
+            Err(e) => return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: e.to_string(),
+            }),
+        };
+        let signature = req.headers().get("X-Hub-Signature-256").and_then(|v| v.to_str().ok());
+        if !verify_signature(&secret, &body, signature) {
+            return HttpResponse::Unauthorized().json(ErrorResponse {
+                error: "Invalid webhook signature".to_string(),
+            });
+        }
+    }
+    let body: GitHubWebhookPayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Invalid webhook payload: {}", e),
+        }),
+    };
+    let event = req.headers().get("X-GitHub-Event").and_then(|v| v.to_str().ok()).unwrap_or_default();
+
+    info!("Received GitHub webhook: {} {:?}", event, body.action);
+
+    if let (Some(repo), Some(pull_request)) = (&body.repository, &body.pull_request) {
+        let reviewable = matches!(body.action.as_deref(), Some("opened" | "synchronize" | "reopened"));
+        if event == "pull_request" && reviewable {
+            return match start_pull_request_review(&state, repo, pull_request) {
+                Ok(message) => HttpResponse::Accepted().json(serde_json::json!({
+                    "message": message,
+                    "repository": repo.full_name,
+                    "pull_request": pull_request.number
+                })),
+                Err(message) => HttpResponse::Ok().json(serde_json::json!({
+                    "message": message,
+                    "repository": repo.full_name
+                })),
+            };
+        }
+    }
+
     if let Some(repo) = &body.repository {
         if let Some(action) = &body.action {
             if action == "push" {
@@ -85,6 +149,138 @@ pub async fn handle_github_webhook(
     }))
 }
 
+/// Queue a review of a pull request against a registered repository
+fn start_pull_request_review(state: &web::Data<ApiState>, repo: &GitHubRepository, pull_request: &GitHubPullRequest) -> Result<&'static str, &'static str> {
+    let config = &state.config.github;
+    if !config.check_runs && !config.pr_comments {
+        return Err("Pull request reviews are disabled");
+    }
+    if config.token.is_none() {
+        return Err("Pull request reviews need GITHUB_TOKEN");
+    }
+    // Reviews clone and analyze whatever branch a delivery names, so only signed deliveries start one
+    if config.webhook_secret.is_none() {
+        return Err("Pull request reviews need GITHUB_WEBHOOK_SECRET");
+    }
+    // The PR branch is fetched from the registered remote, so branches of forks cannot be reached
+    if pull_request.head.repo.as_ref().map(|r| &r.full_name) != Some(&repo.full_name) {
+        return Err("Pull requests from forks are not analyzed");
+    }
+    let base = state.repo_repo.list_all().ok()
+        .and_then(|repos| repos.into_iter()
+            .find(|r| crate::ingestion::releases::github_slug(&r.url).as_deref() == Some(repo.full_name.as_str())));
+    let base = match base {
+        Some(base) => base,
+        None => return Err("Repository is not registered"),
+    };
+
+    let state = state.clone();
+    let slug = repo.full_name.clone();
+    let pull_request = pull_request.clone();
+    actix_web::rt::spawn(async move {
+        if let Err(e) = review_pull_request(state, base, slug.clone(), pull_request.clone()).await {
+            log::warn!("⚠ Review of {}#{} failed: {:#}", slug, pull_request.number, e);
+        }
+    });
+    Ok("Pull request review started")
+}
+
+/// Analyze the PR branch as a temporary repository, compare it with the base repository's latest
+/// analysis and report the difference on the pull request
+async fn review_pull_request(state: web::Data<ApiState>, base: Repository, slug: String, pull_request: GitHubPullRequest) -> anyhow::Result<()> {
+    let config = state.config.github.clone();
+    let token = config.token.clone().unwrap_or_default();
+    let token = web::block(move || crate::ingestion::secrets::resolve(&token)).await??;
+
+    let before_snapshot = state.release_repo.get_snapshots(&base.id, 1)?.pop();
+    let before_vulnerabilities = state.security_repo.get_vulnerabilities(&base.id)?;
+    let before_findings = state.architecture_repo.get_findings(&base.id)?;
+
+    // The PR head gets a checkout of its own; analyzing it through the base repository's URL
+    // would switch the shared clone of the base repository to the PR branch
+    let checkout = {
+        let (base, storage, secrets) = (base.clone(), state.config.storage.clone(), state.config.secrets.clone());
+        let name = format!("{}-pr-{}-{}", slug.replace('/', "-"), pull_request.number, pull_request.head.sha);
+        let (branch, sha) = (pull_request.head.branch.clone(), pull_request.head.sha.clone());
+        web::block(move || -> anyhow::Result<_> {
+            let auth_value = base.auth_value.as_deref()
+                .map(|value| crate::ingestion::secrets::resolve_allowed(value, &secrets, &base.workspace_id))
+                .transpose()?;
+            let credentials = base.auth_type.as_deref().zip(auth_value.as_deref())
+                .map(|(auth_type, auth_value)| RepositoryCredentials::from_stored(auth_type, auth_value));
+            let path = RepositoryCrawler::new(&storage)?.clone_separately(&base.url, &name, &branch, credentials.as_ref())?;
+            // The branch may have moved on, or be missing so the default branch was cloned instead
+            let cloned = git2::Repository::open(&path)?.head()?.peel_to_commit()?.id().to_string();
+            if cloned != sha {
+                let _ = std::fs::remove_dir_all(&path);
+                anyhow::bail!("{} is at {} instead of {}", branch, cloned, sha);
+            }
+            Ok(path)
+        }).await??
+    };
+    let head = state.repo_repo.create(
+        &format!("{} #{}", base.name, pull_request.number),
+        &checkout.to_string_lossy(),
+        Some(&pull_request.head.branch),
+        None,
+        None,
+        &base.workspace_id,
+    );
+    let head = match head {
+        Ok(head) => head,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&checkout);
+            return Err(e);
+        }
+    };
+    info!("Analyzing {}#{} ({}) for review", slug, pull_request.number, pull_request.head.branch);
+    state.progress_tracker.start_analysis(&head.id, 13);
+    let analysis = {
+        let state = state.clone();
+        let head_id = head.id.clone();
+        let profile = base.analysis_profile;
+        web::block(move || crate::analyzer::pipeline::perform_analysis(&state, &head_id, profile)).await
+    };
+    let after = match analysis {
+        Ok(Ok(_)) => (|| -> anyhow::Result<_> {
+            Ok((
+                state.release_repo.get_snapshots(&head.id, 1)?.pop(),
+                state.security_repo.get_vulnerabilities(&head.id)?,
+                state.architecture_repo.get_findings(&head.id)?,
+            ))
+        })(),
+        Ok(Err(e)) => Err(e),
+        Err(e) => Err(anyhow::anyhow!("analysis did not finish: {}", e)),
+    };
+    // The PR's own results are only needed for the comparison
+    state.progress_tracker.clear_progress(&head.id);
+    if let Err(e) = state.repo_repo.delete(&head.id) {
+        log::warn!("⚠ Failed to remove temporary analysis of {}#{}: {}", slug, pull_request.number, e);
+    }
+    if let Err(e) = std::fs::remove_dir_all(&checkout) {
+        log::warn!("⚠ Failed to remove checkout of {}#{}: {}", slug, pull_request.number, e);
+    }
+    let (after_snapshot, after_vulnerabilities, after_findings) = after?;
+
+    let diff = AnalysisDiff::between(
+        (before_snapshot.as_ref(), &before_vulnerabilities, &before_findings),
+        (after_snapshot.as_ref(), after_vulnerabilities, after_findings),
+    );
+    let github = GitHubClient::new(&config.api_url, &token)?;
+    if config.check_runs {
+        let changed_files = github.changed_files(&slug, pull_request.number).await?;
+        github.create_check_run(&slug, &pull_request.head.sha, &diff, &changed_files).await?;
+    }
+    if config.pr_comments {
+        github.upsert_comment(&slug, pull_request.number, &diff).await?;
+    }
+    info!(
+        "✓ Reviewed {}#{}: {} new security and {} new architecture findings",
+        slug, pull_request.number, diff.new_vulnerabilities.len(), diff.new_findings.len()
+    );
+    Ok(())
+}
+
 /// Handle GitLab webhook
 pub async fn handle_gitlab_webhook(
     _state: web::Data<ApiState>,
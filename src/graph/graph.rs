@@ -2,8 +2,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
-use crate::storage::{Database, RepositoryRepository, DependencyRepository, ServiceRepository, ToolRepository, CodeRelationshipRepository, TestRepository, PortRepository, EndpointRepository};
-use crate::analysis::RelationshipTargetType;
+use crate::storage::{Database, RepositoryRepository, DependencyRepository, ServiceRepository, ToolRepository, CodeRelationshipRepository, TestRepository, PortRepository, EndpointRepository, KubernetesRepository, GatewayRepository, PipelineRepository, BuildTargetRepository, DataPipelineRepository, EntrypointRepository, BackgroundJobRepository, NotificationRepository, PaymentRepository, FrontendRouteRepository, CoverageRepository, GraphQLUsageRepository, AdrRepository, DocLinkRepository, ImageRepository, StoredImageMetadata};
+use crate::analysis::{CrossRepoLink, CrossRepoRelation, RelationshipTargetType};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum NodeType {
@@ -19,6 +19,28 @@ pub enum NodeType {
     TestFramework,
     Port,
     Endpoint,
+    KubernetesResource,
+    ContainerImage,
+    ApiGateway,
+    GatewayUpstream,    // Backend a gateway routes to that the graph has no other node for
+    Pipeline,
+    PipelineJob,
+    DeployTarget,
+    BuildTarget,        // Make/Just/Task target, npm script or Gradle task
+    DataPipeline,       // Airflow DAG, dbt project, Dagster job/asset group or Prefect flow
+    DataTask,           // Task, model or asset of a data pipeline
+    Dataset,            // Table, file or asset a data task reads or writes
+    DataExposure,       // Dashboard, notebook or application a dbt project declares as built on it
+    Entrypoint,
+    BackgroundJob,
+    NotificationChannel,
+    PaymentProvider,
+    FrontendRoute,
+    GraphQLOperation,
+    GraphQLType,
+    ArchitectureDecision,
+    Documentation,
+    PluginEntity,       // Added by a post-analysis plugin; `kind` and `plugin` properties say what and who
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +71,59 @@ pub enum EdgeType {
     HasPort,            // Repository -> Port
     HasEndpoint,        // Repository -> Endpoint
     EndpointUsesPort,   // Endpoint -> Port (if endpoint handler uses a port)
+    HasKubernetesResource, // Repository -> KubernetesResource
+    RoutesTo,           // KubernetesResource (Ingress) -> KubernetesResource (Service)
+    Selects,            // KubernetesResource (Service) -> KubernetesResource (workload)
+    UsesConfig,         // KubernetesResource (workload) -> KubernetesResource (ConfigMap/Secret)
+    RunsImage,          // KubernetesResource (workload) -> ContainerImage
+    BuildsImage,        // Repository -> ContainerImage
+    HasGateway,         // Repository -> ApiGateway
+    GatewayRoutesTo,    // ApiGateway -> KubernetesResource (Service) / Endpoint / GatewayUpstream
+    ReferencesImage,    // Repository -> ContainerImage (Dockerfile/compose references no workload runs)
+    HasPipeline,        // Repository -> Pipeline
+    PipelineHasJob,     // Pipeline -> PipelineJob
+    JobNeeds,           // PipelineJob -> PipelineJob
+    JobUsesTool,        // PipelineJob -> Tool
+    JobUsesProvider,    // PipelineJob -> ServiceProvider
+    DeploysTo,          // PipelineJob -> DeployTarget
+    JobRunsTarget,      // PipelineJob -> BuildTarget
+    TargetDependsOn,    // BuildTarget -> BuildTarget
+    TargetUsesTool,     // BuildTarget -> Tool
+    HasDataPipeline,    // Repository -> DataPipeline
+    HasDataTask,        // DataPipeline -> DataTask
+    DataTaskDependsOn,  // DataTask -> DataTask (upstream)
+    ReadsDataset,       // DataTask -> Dataset
+    WritesDataset,      // DataTask -> Dataset
+    TriggersPipeline,   // Dataset -> DataPipeline (data-aware scheduling)
+    HasExposure,        // DataPipeline -> DataExposure
+    ExposureUses,       // DataExposure -> Dataset
+    UsesWarehouse,      // DataPipeline -> Service (the warehouse a dbt project runs on)
+    HasEntrypoint,      // Repository -> Entrypoint
+    EntrypointInvokes,  // Entrypoint -> CodeElement
+    HasBackgroundJob,   // Repository -> BackgroundJob
+    JobInvokes,         // BackgroundJob -> CodeElement
+    HasNotificationChannel, // Repository -> NotificationChannel
+    SendsVia,           // NotificationChannel -> Service
+    CallsBack,          // NotificationChannel -> Endpoint (the provider posts status/events to it)
+    HasPaymentProvider, // Repository -> PaymentProvider
+    HandlesPayments,    // Endpoint -> PaymentProvider (creates checkouts or receives its webhooks)
+    HasFrontendRoute,   // Repository -> FrontendRoute
+    RendersComponent,   // FrontendRoute -> CodeElement
+    HasGraphQLOperation, // Repository -> GraphQLOperation
+    OperationUsesType,  // GraphQLOperation -> GraphQLType
+    HasDecision,        // Repository -> ArchitectureDecision
+    DecisionAffects,    // ArchitectureDecision -> CodeElement/Service
+    HasDocumentation,   // Repository -> Documentation
+    DocumentsCode,      // Documentation -> CodeElement
+    DocumentsService,   // Documentation -> Service
+    DocLinksTo,         // Documentation -> Documentation
+    Imports,            // CodeElement (importing file) -> CodeElement (Module)
+    Calls,              // CodeElement (Function/Method) -> CodeElement (Function/Method/Class it calls)
+    ContainedIn,        // CodeElement (Method) -> CodeElement (Class/Struct/Interface)
+    DependsOnRepository, // Repository -> Repository publishing a package it depends on (systems only)
+    SharesDependency,   // Repository -> Repository depending on the same packages (systems only)
+    CallsEndpoint,      // Repository -> Endpoint of another repository it calls (systems only)
+    SharesResource,     // Repository -> Repository declaring the same cloud resource (systems only)
     RelatedTo,          // Generic relationship
 }
 
@@ -338,8 +413,10 @@ impl GraphBuilder {
         }
         
         // Create test nodes
+        let mut test_node_ids: HashMap<String, String> = HashMap::new();
         for test in &tests {
             let test_node_id = Uuid::new_v4().to_string();
+            test_node_ids.insert(test.id.clone(), test_node_id.clone());
             let mut test_props = HashMap::new();
             test_props.insert("test_framework".to_string(), test.test_framework.clone());
             test_props.insert("test_type".to_string(), test.test_type.clone());
@@ -384,10 +461,12 @@ impl GraphBuilder {
 
         // Get tools
         let tools = self.tool_repo.get_tools_by_repository(repository_id)?;
+        let mut tool_node_ids: HashMap<String, String> = HashMap::new(); // lowercase name -> node_id
         
         // Create tool nodes and relationships
         for tool in &tools {
             let tool_node_id = Uuid::new_v4().to_string();
+            tool_node_ids.insert(tool.name.to_lowercase(), tool_node_id.clone());
             let mut tool_props = HashMap::new();
             tool_props.insert("tool_type".to_string(), tool.tool_type.clone());
             tool_props.insert("category".to_string(), tool.category.clone());
@@ -463,18 +542,440 @@ impl GraphBuilder {
             }
         }
 
+        // Entrypoints are roots for reachability analysis
+        let entrypoint_repo = EntrypointRepository::new(self.db.clone());
+        let entrypoints = match entrypoint_repo.get_by_repository(repository_id) {
+            Ok(e) => e,
+            Err(e) => {
+                log::warn!("Failed to load entrypoints for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut entrypoint_node_ids: Vec<String> = Vec::new();
+        for entrypoint in &entrypoints {
+            let entrypoint_node_id = Uuid::new_v4().to_string();
+            let mut entrypoint_props = HashMap::new();
+            entrypoint_props.insert("entrypoint_type".to_string(), entrypoint.entrypoint_type.clone());
+            entrypoint_props.insert("file_path".to_string(), entrypoint.file_path.clone());
+            if let Some(line) = entrypoint.line_number {
+                entrypoint_props.insert("line_number".to_string(), line.to_string());
+            }
+            if let Some(command) = &entrypoint.command {
+                entrypoint_props.insert("command".to_string(), command.clone());
+            }
+            if let Some(target_file) = &entrypoint.target_file {
+                entrypoint_props.insert("target_file".to_string(), target_file.clone());
+            }
+            if let Some(handler) = &entrypoint.handler {
+                entrypoint_props.insert("handler".to_string(), handler.clone());
+            }
+
+            nodes.push(GraphNode {
+                id: entrypoint_node_id.clone(),
+                node_type: NodeType::Entrypoint,
+                name: entrypoint.name.clone(),
+                properties: entrypoint_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: entrypoint_node_id.clone(),
+                edge_type: EdgeType::HasEntrypoint,
+                properties: HashMap::new(),
+            });
+            entrypoint_node_ids.push(entrypoint_node_id);
+        }
+
+        // Background jobs and scheduled tasks, with what triggers them
+        let background_job_repo = BackgroundJobRepository::new(self.db.clone());
+        let background_jobs = match background_job_repo.get_by_repository(repository_id) {
+            Ok(j) => j,
+            Err(e) => {
+                log::warn!("Failed to load background jobs for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut background_job_node_ids: Vec<String> = Vec::new();
+        for job in &background_jobs {
+            let job_node_id = Uuid::new_v4().to_string();
+            let mut job_props = HashMap::new();
+            job_props.insert("framework".to_string(), job.framework.clone());
+            job_props.insert("trigger".to_string(), job.trigger_type.clone());
+            job_props.insert("file_path".to_string(), job.file_path.clone());
+            if let Some(line) = job.line_number {
+                job_props.insert("line_number".to_string(), line.to_string());
+            }
+            if let Some(schedule) = &job.schedule {
+                job_props.insert("schedule".to_string(), schedule.clone());
+            }
+            if let Some(queue) = &job.queue {
+                job_props.insert("queue".to_string(), queue.clone());
+            }
+            if let Some(handler) = &job.handler {
+                job_props.insert("handler".to_string(), handler.clone());
+            }
+
+            nodes.push(GraphNode {
+                id: job_node_id.clone(),
+                node_type: NodeType::BackgroundJob,
+                name: job.name.clone(),
+                properties: job_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: job_node_id.clone(),
+                edge_type: EdgeType::HasBackgroundJob,
+                properties: HashMap::new(),
+            });
+            background_job_node_ids.push(job_node_id);
+        }
+
+        // Frontend routes link to the components that render them
+        let frontend_route_repo = FrontendRouteRepository::new(self.db.clone());
+        let frontend_routes = match frontend_route_repo.get_by_repository(repository_id) {
+            Ok(r) => r,
+            Err(e) => {
+                log::warn!("Failed to load frontend routes for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut frontend_route_node_ids: Vec<String> = Vec::new();
+        for route in &frontend_routes {
+            let route_node_id = Uuid::new_v4().to_string();
+            let mut route_props = HashMap::new();
+            route_props.insert("router".to_string(), route.router.clone());
+            route_props.insert("file_path".to_string(), route.file_path.clone());
+            if let Some(line) = route.line_number {
+                route_props.insert("line_number".to_string(), line.to_string());
+            }
+            if let Some(component) = &route.component {
+                route_props.insert("component".to_string(), component.clone());
+            }
+            if route.lazy {
+                route_props.insert("lazy".to_string(), "true".to_string());
+            }
+
+            nodes.push(GraphNode {
+                id: route_node_id.clone(),
+                node_type: NodeType::FrontendRoute,
+                name: route.path.clone(),
+                properties: route_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: route_node_id.clone(),
+                edge_type: EdgeType::HasFrontendRoute,
+                properties: HashMap::new(),
+            });
+            frontend_route_node_ids.push(route_node_id);
+        }
+
+        // GraphQL client operations and the schema types they select; only referenced types get nodes
+        let graphql_repo = GraphQLUsageRepository::new(self.db.clone());
+        let graphql_operations = graphql_repo.get_operations(repository_id).unwrap_or_else(|e| {
+            log::warn!("Failed to load GraphQL operations for graph: {}", e);
+            Vec::new()
+        });
+        if !graphql_operations.is_empty() {
+            let schema_kinds: HashMap<String, String> = graphql_repo.get_schema_types(repository_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| (t.name, t.kind))
+                .collect();
+            let mut graphql_type_node_ids: HashMap<String, String> = HashMap::new();
+
+            for operation in &graphql_operations {
+                let operation_node_id = Uuid::new_v4().to_string();
+                let mut operation_props = HashMap::new();
+                operation_props.insert("operation_type".to_string(), operation.operation_type.clone());
+                operation_props.insert("file_path".to_string(), operation.file_path.clone());
+                operation_props.insert("line_number".to_string(), operation.line_number.to_string());
+                if !operation.root_fields.is_empty() {
+                    operation_props.insert("root_fields".to_string(), operation.root_fields.join(", "));
+                }
+
+                nodes.push(GraphNode {
+                    id: operation_node_id.clone(),
+                    node_type: NodeType::GraphQLOperation,
+                    name: operation.name.clone().unwrap_or_else(|| format!("anonymous {}", operation.operation_type)),
+                    properties: operation_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: repo_node_id.clone(),
+                    target_node_id: operation_node_id.clone(),
+                    edge_type: EdgeType::HasGraphQLOperation,
+                    properties: HashMap::new(),
+                });
+
+                for type_name in &operation.types_used {
+                    let type_node_id = graphql_type_node_ids.entry(type_name.clone()).or_insert_with(|| {
+                        let type_node_id = format!("graphql_type:{}", type_name);
+                        let mut type_props = HashMap::new();
+                        if let Some(kind) = schema_kinds.get(type_name) {
+                            type_props.insert("kind".to_string(), kind.clone());
+                        }
+                        nodes.push(GraphNode {
+                            id: type_node_id.clone(),
+                            node_type: NodeType::GraphQLType,
+                            name: type_name.clone(),
+                            properties: type_props,
+                            repository_id: Some(repository_id.to_string()),
+                        });
+                        type_node_id
+                    }).clone();
+
+                    let fields: Vec<&str> = operation.fields_used.iter()
+                        .filter_map(|f| f.strip_prefix(&format!("{}.", type_name)))
+                        .collect();
+                    let mut edge_props = HashMap::new();
+                    if !fields.is_empty() {
+                        edge_props.insert("fields".to_string(), fields.join(", "));
+                    }
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: operation_node_id.clone(),
+                        target_node_id: type_node_id,
+                        edge_type: EdgeType::OperationUsesType,
+                        properties: edge_props,
+                    });
+                }
+            }
+        }
+
+        // Architecture decision records; linked services get an edge here, linked code elements in the first pass below.
+        // Mentions are matched by name because element and service ids change with every analysis.
+        let decisions = AdrRepository::new(self.db.clone()).get_by_repository(repository_id).unwrap_or_else(|e| {
+            log::warn!("Failed to load architecture decisions for graph: {}", e);
+            Vec::new()
+        });
+        let mut decisions_by_element_name: HashMap<String, Vec<String>> = HashMap::new();
+        for decision in &decisions {
+            let decision_node_id = format!("adr:{}", decision.id);
+            let mut decision_props = HashMap::new();
+            decision_props.insert("file_path".to_string(), decision.file_path.clone());
+            if let Some(number) = decision.number {
+                decision_props.insert("number".to_string(), number.to_string());
+            }
+            if let Some(status) = &decision.status {
+                decision_props.insert("status".to_string(), status.clone());
+            }
+            if let Some(date) = &decision.date {
+                decision_props.insert("date".to_string(), date.clone());
+            }
+            if let Some(text) = &decision.decision {
+                decision_props.insert("decision".to_string(), text.clone());
+            }
+
+            nodes.push(GraphNode {
+                id: decision_node_id.clone(),
+                node_type: NodeType::ArchitectureDecision,
+                name: decision.title.clone(),
+                properties: decision_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: decision_node_id.clone(),
+                edge_type: EdgeType::HasDecision,
+                properties: HashMap::new(),
+            });
+
+            for mention in &decision.mentions {
+                if mention.component_type == "service" {
+                    for service in services.iter().filter(|s| s.name == mention.name) {
+                        if let Some(service_node_id) = service_node_ids.get(&service.id) {
+                            edges.push(GraphEdge {
+                                id: Uuid::new_v4().to_string(),
+                                source_node_id: decision_node_id.clone(),
+                                target_node_id: service_node_id.clone(),
+                                edge_type: EdgeType::DecisionAffects,
+                                properties: HashMap::new(),
+                            });
+                        }
+                    }
+                } else {
+                    decisions_by_element_name.entry(mention.name.clone()).or_default().push(decision_node_id.clone());
+                }
+            }
+        }
+
+        // Documentation that links to other docs or references code/services; code references are
+        // resolved by file and name in the first pass below
+        let doc_links = DocLinkRepository::new(self.db.clone()).get_by_repository(repository_id).unwrap_or_else(|e| {
+            log::warn!("Failed to load documentation links for graph: {}", e);
+            Vec::new()
+        });
+        let linked_docs: HashSet<&str> = doc_links.iter().filter(|l| l.kind != "external").map(|l| l.doc_path.as_str()).collect();
+        let mut doc_node_ids: HashMap<String, String> = HashMap::new();
+        let mut docs_by_element: HashMap<(String, String), Vec<String>> = HashMap::new();
+        for link in doc_links.iter().filter(|l| l.kind != "external") {
+            let doc_node_id = doc_node_ids.entry(link.doc_path.clone()).or_insert_with(|| {
+                let doc_node_id = format!("doc:{}", link.doc_path);
+                let mut doc_props = HashMap::new();
+                doc_props.insert("file_path".to_string(), link.doc_path.clone());
+                nodes.push(GraphNode {
+                    id: doc_node_id.clone(),
+                    node_type: NodeType::Documentation,
+                    name: link.doc_path.rsplit('/').next().unwrap_or(&link.doc_path).to_string(),
+                    properties: doc_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: repo_node_id.clone(),
+                    target_node_id: doc_node_id.clone(),
+                    edge_type: EdgeType::HasDocumentation,
+                    properties: HashMap::new(),
+                });
+                doc_node_id
+            }).clone();
+
+            match (link.kind.as_str(), &link.target_file, &link.target_name) {
+                ("service", _, Some(name)) => {
+                    for service in services.iter().filter(|s| &s.name == name) {
+                        if let Some(service_node_id) = service_node_ids.get(&service.id) {
+                            let mut props = HashMap::new();
+                            props.insert("line_number".to_string(), link.line_number.to_string());
+                            edges.push(GraphEdge {
+                                id: Uuid::new_v4().to_string(),
+                                source_node_id: doc_node_id.clone(),
+                                target_node_id: service_node_id.clone(),
+                                edge_type: EdgeType::DocumentsService,
+                                properties: props,
+                            });
+                        }
+                    }
+                }
+                ("code", Some(file), Some(name)) => {
+                    docs_by_element.entry((file.clone(), name.clone())).or_default().push(doc_node_id.clone());
+                }
+                // Only docs with references of their own get a node, so links to other docs stop there
+                ("internal", Some(file), _) if file != &link.doc_path && linked_docs.contains(file.as_str()) => {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: doc_node_id.clone(),
+                        target_node_id: format!("doc:{}", file),
+                        edge_type: EdgeType::DocLinksTo,
+                        properties: HashMap::new(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
         // Add code relationships (code elements to services/dependencies)
         use crate::storage::CodeElementRepository;
         let code_repo = CodeElementRepository::new(self.db.clone());
         if let Ok(code_elements) = code_repo.get_by_repository(repository_id) {
             // Track code element nodes we create
             let mut code_element_nodes: HashMap<String, String> = HashMap::new();
+
+            // Test-to-code links; tested elements are shown so the edges have a target
+            let mut tests_by_element: HashMap<String, Vec<(String, String, f64)>> = HashMap::new();
+            for link in self.test_repo.get_code_links(repository_id).unwrap_or_default() {
+                if let Some(test_node_id) = test_node_ids.get(&link.test_id) {
+                    tests_by_element.entry(link.code_element_id.clone())
+                        .or_default()
+                        .push((test_node_id.clone(), link.strategy.clone(), link.confidence));
+                }
+            }
+
+            // Line coverage per function, when coverage reports were ingested
+            let coverage_files: Vec<_> = CoverageRepository::new(self.db.clone())
+                .get_effective_coverage(repository_id)
+                .unwrap_or_default()
+                .iter()
+                .map(|f| f.to_file_coverage())
+                .collect();
+            let element_coverage: HashMap<String, f64> = crate::analysis::coverage::element_coverage(&code_elements, &coverage_files)
+                .into_iter()
+                .map(|c| (c.element_id, c.line_percentage))
+                .collect();
             
+            // Job handlers named in another file (Celery beat, Quartz registrations) link only when unambiguous
+            let is_callable = |element: &crate::analysis::CodeElement| matches!(
+                element.element_type,
+                crate::analysis::CodeElementType::Function | crate::analysis::CodeElementType::Method | crate::analysis::CodeElementType::Class
+            );
+            let mut callable_name_counts: HashMap<&str, usize> = HashMap::new();
+            for element in code_elements.iter().filter(|e| is_callable(e)) {
+                *callable_name_counts.entry(element.name.as_str()).or_default() += 1;
+            }
+
             // First pass: Create nodes for Module-type code elements that should always be shown
             // Modules (packages, namespaces, etc.) are organizational units and should appear
-            // even if they don't have relationships, as they provide context for other elements
+            // even if they don't have relationships, as they provide context for other elements.
+            // Functions invoked by an entrypoint are always shown too, marked as entrypoints,
+            // as are components rendered by a frontend route, handlers run by a background job and
+            // elements an ADR or doc mentions.
             for code_element in &code_elements {
-                let should_always_show = matches!(code_element.element_type, crate::analysis::CodeElementType::Module);
+                // Serverless handlers reference modules without an extension ("src/handler.hello")
+                let path_without_ext = code_element.file_path.rsplit_once('.')
+                    .map(|(p, _)| p)
+                    .unwrap_or(&code_element.file_path);
+                let invoking_entrypoints: Vec<usize> = entrypoints.iter()
+                    .enumerate()
+                    .filter(|(_, ep)| {
+                        ep.handler.as_deref() == Some(code_element.name.as_str())
+                            && ep.target_file.as_deref()
+                                .map(|f| code_element.file_path.ends_with(f) || path_without_ext.ends_with(f))
+                                .unwrap_or(false)
+                    })
+                    .map(|(idx, _)| idx)
+                    .collect();
+                let is_entrypoint = !invoking_entrypoints.is_empty()
+                    && matches!(code_element.element_type, crate::analysis::CodeElementType::Function | crate::analysis::CodeElementType::Method);
+                let rendering_routes: Vec<usize> = if matches!(
+                    code_element.element_type,
+                    crate::analysis::CodeElementType::Function | crate::analysis::CodeElementType::Class
+                        | crate::analysis::CodeElementType::Constant | crate::analysis::CodeElementType::Variable
+                ) {
+                    frontend_routes.iter()
+                        .enumerate()
+                        .filter(|(_, route)| {
+                            route.component.as_deref() == Some(code_element.name.as_str())
+                                && route.component_file.as_deref() == Some(code_element.file_path.as_str())
+                        })
+                        .map(|(idx, _)| idx)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                let running_jobs: Vec<usize> = if is_callable(code_element) {
+                    background_jobs.iter()
+                        .enumerate()
+                        .filter(|(_, job)| {
+                            job.handler.as_deref().and_then(|h| h.rsplit("::").next()) == Some(code_element.name.as_str())
+                                && (job.file_path == code_element.file_path
+                                    || callable_name_counts.get(code_element.name.as_str()) == Some(&1))
+                        })
+                        .map(|(idx, _)| idx)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                let covering_tests = tests_by_element.get(&code_element.id);
+                let deciding_adrs = if matches!(code_element.element_type, crate::analysis::CodeElementType::Module | crate::analysis::CodeElementType::Class) {
+                    decisions_by_element_name.get(&code_element.name)
+                } else {
+                    None
+                };
+                let documenting_docs = docs_by_element.get(&(code_element.file_path.clone(), code_element.name.clone()));
+                let should_always_show = matches!(code_element.element_type, crate::analysis::CodeElementType::Module)
+                    || is_entrypoint
+                    || !rendering_routes.is_empty()
+                    || !running_jobs.is_empty()
+                    || covering_tests.is_some()
+                    || deciding_adrs.is_some()
+                    || documenting_docs.is_some();
                 
                 if should_always_show {
                     let code_node_id = format!("code:{}", code_element.id);
@@ -489,6 +990,15 @@ impl GraphBuilder {
                                 props.insert("line_number".to_string(), code_element.line_number.to_string());
                                 props.insert("element_type".to_string(), format!("{:?}", code_element.element_type));
                                 props.insert("language".to_string(), code_element.language.clone());
+                                if !code_element.qualified_name.is_empty() {
+                                    props.insert("qualified_name".to_string(), code_element.qualified_name.clone());
+                                }
+                                if let Some(coverage) = element_coverage.get(&code_element.id) {
+                                    props.insert("line_coverage".to_string(), coverage.to_string());
+                                }
+                                if is_entrypoint {
+                                    props.insert("is_entrypoint".to_string(), "true".to_string());
+                                }
                                 props
                             },
                             repository_id: Some(repository_id.to_string()),
@@ -496,6 +1006,71 @@ impl GraphBuilder {
                         code_element_nodes.insert(code_element.id.clone(), code_node_id.clone());
                     }
                 }
+
+                if is_entrypoint {
+                    for idx in invoking_entrypoints {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: entrypoint_node_ids[idx].clone(),
+                            target_node_id: format!("code:{}", code_element.id),
+                            edge_type: EdgeType::EntrypointInvokes,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+
+                for idx in rendering_routes {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: frontend_route_node_ids[idx].clone(),
+                        target_node_id: format!("code:{}", code_element.id),
+                        edge_type: EdgeType::RendersComponent,
+                        properties: HashMap::new(),
+                    });
+                }
+
+                for idx in running_jobs {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: background_job_node_ids[idx].clone(),
+                        target_node_id: format!("code:{}", code_element.id),
+                        edge_type: EdgeType::JobInvokes,
+                        properties: HashMap::new(),
+                    });
+                }
+
+                for (test_node_id, strategy, confidence) in covering_tests.into_iter().flatten() {
+                    let mut props = HashMap::new();
+                    props.insert("strategy".to_string(), strategy.clone());
+                    props.insert("confidence".to_string(), confidence.to_string());
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: test_node_id.clone(),
+                        target_node_id: format!("code:{}", code_element.id),
+                        edge_type: EdgeType::TestTestsCode,
+                        properties: props,
+                    });
+                }
+
+                for decision_node_id in deciding_adrs.into_iter().flatten() {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: decision_node_id.clone(),
+                        target_node_id: format!("code:{}", code_element.id),
+                        edge_type: EdgeType::DecisionAffects,
+                        properties: HashMap::new(),
+                    });
+                }
+
+                for doc_node_id in documenting_docs.into_iter().flatten() {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: doc_node_id.clone(),
+                        target_node_id: format!("code:{}", code_element.id),
+                        edge_type: EdgeType::DocumentsCode,
+                        properties: HashMap::new(),
+                    });
+                }
             }
             
             // Second pass: For each code element with relationships, create edges
@@ -518,6 +1093,12 @@ impl GraphBuilder {
                                 props.insert("line_number".to_string(), code_element.line_number.to_string());
                                 props.insert("element_type".to_string(), format!("{:?}", code_element.element_type));
                                 props.insert("language".to_string(), code_element.language.clone());
+                                if !code_element.qualified_name.is_empty() {
+                                    props.insert("qualified_name".to_string(), code_element.qualified_name.clone());
+                                }
+                                if let Some(coverage) = element_coverage.get(&code_element.id) {
+                                    props.insert("line_coverage".to_string(), coverage.to_string());
+                                }
                                 props
                             },
                             repository_id: Some(repository_id.to_string()),
@@ -568,6 +1149,81 @@ impl GraphBuilder {
                     }
                 }
             }
+
+            // Imports: the importing file's element -> the module node, which is always shown
+            let elements_by_id: HashMap<&str, &crate::analysis::CodeElement> = code_elements.iter()
+                .map(|e| (e.id.as_str(), e))
+                .collect();
+            let calls = code_repo.get_calls(repository_id).unwrap_or_default();
+            for call in &calls {
+                if call.call_type != "import" {
+                    continue;
+                }
+                let (module_node_id, importer) = match (code_element_nodes.get(&call.callee_id), elements_by_id.get(call.caller_id.as_str())) {
+                    (Some(module_node_id), Some(importer)) => (module_node_id.clone(), *importer),
+                    _ => continue,
+                };
+                if !code_element_nodes.contains_key(&importer.id) {
+                    nodes.push(code_element_node(importer, repository_id));
+                    code_element_nodes.insert(importer.id.clone(), format!("code:{}", importer.id));
+                }
+                let mut props = HashMap::new();
+                props.insert("file_path".to_string(), importer.file_path.clone());
+                props.insert("line_number".to_string(), call.line_number.to_string());
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: code_element_nodes[&importer.id].clone(),
+                    target_node_id: module_node_id,
+                    edge_type: EdgeType::Imports,
+                    properties: props,
+                });
+            }
+
+            // Calls made by the functions that are shown, one hop out so the graph stays readable
+            let callers: HashSet<String> = code_element_nodes.keys().cloned().collect();
+            for call in calls.iter().filter(|c| c.call_type != "import" && callers.contains(&c.caller_id)) {
+                let Some(callee) = elements_by_id.get(call.callee_id.as_str()) else { continue };
+                if !code_element_nodes.contains_key(&callee.id) {
+                    nodes.push(code_element_node(callee, repository_id));
+                    code_element_nodes.insert(callee.id.clone(), format!("code:{}", callee.id));
+                }
+                let mut props = HashMap::new();
+                props.insert("call_type".to_string(), call.call_type.clone());
+                props.insert("line_number".to_string(), call.line_number.to_string());
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: code_element_nodes[&call.caller_id].clone(),
+                    target_node_id: code_element_nodes[&callee.id].clone(),
+                    edge_type: EdgeType::Calls,
+                    properties: props,
+                });
+            }
+
+            // Methods that are shown link to their class/struct, which is shown with them for context
+            let mut shown: Vec<&str> = code_element_nodes.keys().map(|id| id.as_str()).collect();
+            shown.sort();
+            let mut contained = Vec::new();
+            for child_id in shown {
+                if let Some(parent) = elements_by_id.get(child_id)
+                    .and_then(|e| e.parent_id.as_deref())
+                    .and_then(|parent_id| elements_by_id.get(parent_id))
+                {
+                    contained.push((child_id.to_string(), *parent));
+                }
+            }
+            for (child_id, parent) in contained {
+                if !code_element_nodes.contains_key(&parent.id) {
+                    nodes.push(code_element_node(parent, repository_id));
+                    code_element_nodes.insert(parent.id.clone(), format!("code:{}", parent.id));
+                }
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: code_element_nodes[&child_id].clone(),
+                    target_node_id: code_element_nodes[&parent.id].clone(),
+                    edge_type: EdgeType::ContainedIn,
+                    properties: HashMap::new(),
+                });
+            }
         }
 
         // Get ports
@@ -619,7 +1275,13 @@ impl GraphBuilder {
         let endpoints = self.endpoint_repo.get_by_repository(repository_id)?;
         
         for endpoint in &endpoints {
-            let endpoint_key = format!("endpoint:{}:{}", endpoint.method, endpoint.path);
+            // WebSocket and SSE endpoints are named by protocol, so `GET /ws` and a socket on `/ws` stay apart
+            let endpoint_name = match endpoint.protocol.as_str() {
+                "websocket" => format!("WS {}", endpoint.path),
+                "sse" => format!("SSE {}", endpoint.path),
+                _ => format!("{} {}", endpoint.method, endpoint.path),
+            };
+            let endpoint_key = format!("endpoint:{}", endpoint_name);
             let endpoint_node_id = if let Some(id) = node_map.get(&endpoint_key) {
                 id.clone()
             } else {
@@ -627,6 +1289,7 @@ impl GraphBuilder {
                 let mut endpoint_props = HashMap::new();
                 endpoint_props.insert("path".to_string(), endpoint.path.clone());
                 endpoint_props.insert("method".to_string(), endpoint.method.clone());
+                endpoint_props.insert("protocol".to_string(), endpoint.protocol.clone());
                 endpoint_props.insert("file_path".to_string(), endpoint.file_path.clone());
                 if let Some(handler) = &endpoint.handler {
                     endpoint_props.insert("handler".to_string(), handler.clone());
@@ -641,7 +1304,7 @@ impl GraphBuilder {
                 nodes.push(GraphNode {
                     id: id.clone(),
                     node_type: NodeType::Endpoint,
-                    name: format!("{} {}", endpoint.method, endpoint.path),
+                    name: endpoint_name,
                     properties: endpoint_props,
                     repository_id: Some(repository_id.to_string()),
                 });
@@ -676,7 +1339,778 @@ impl GraphBuilder {
             }
         }
 
-        Ok(KnowledgeGraph { nodes, edges })
+        // Email/SMS channels, one per provider and channel, with their senders, templates and callbacks
+        let notification_repo = NotificationRepository::new(self.db.clone());
+        let notification_configs = match notification_repo.get_by_repository(repository_id) {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("Failed to load notification providers for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut channels: Vec<((&str, &str), Vec<&crate::storage::StoredNotificationConfig>)> = Vec::new();
+        for config in &notification_configs {
+            let key = (config.provider.as_str(), config.channel.as_str());
+            match channels.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, configs)) => configs.push(config),
+                None => channels.push((key, vec![config])),
+            }
+        }
+        for ((provider, channel), configs) in &channels {
+            let channel_node_id = Uuid::new_v4().to_string();
+            let mut channel_props = HashMap::new();
+            channel_props.insert("provider".to_string(), provider.to_string());
+            channel_props.insert("channel".to_string(), channel.to_string());
+            channel_props.insert("file_path".to_string(), configs[0].file_path.clone());
+            for (setting, prop) in [("sender", "senders"), ("template", "templates"), ("webhook", "webhooks")] {
+                let values: Vec<&str> = configs.iter()
+                    .filter(|c| c.setting == setting)
+                    .map(|c| c.value.as_str())
+                    .collect();
+                if !values.is_empty() {
+                    channel_props.insert(prop.to_string(), values.join(", "));
+                }
+            }
+
+            nodes.push(GraphNode {
+                id: channel_node_id.clone(),
+                node_type: NodeType::NotificationChannel,
+                name: format!("{} {}", provider, channel),
+                properties: channel_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: channel_node_id.clone(),
+                edge_type: EdgeType::HasNotificationChannel,
+                properties: HashMap::new(),
+            });
+
+            // SES is detected as an AWS service named after it
+            let provided_by = |service: &&crate::storage::StoredService| {
+                service.provider == *provider
+                    || service.name.to_lowercase().split(|c: char| !c.is_ascii_alphanumeric()).any(|w| w == *provider)
+            };
+            for service in services.iter().filter(provided_by) {
+                if let Some(service_node_id) = service_node_ids.get(&service.id) {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: channel_node_id.clone(),
+                        target_node_id: service_node_id.clone(),
+                        edge_type: EdgeType::SendsVia,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+
+            // Callback URLs end in the path of the route that receives them
+            let mut called_back = HashSet::new();
+            for webhook in configs.iter().filter(|c| c.setting == "webhook") {
+                for endpoint in endpoints.iter().filter(|e| e.protocol == "http" && e.path.len() > 1 && webhook.value.ends_with(&e.path)) {
+                    let Some(endpoint_node_id) = node_map.get(&format!("endpoint:{} {}", endpoint.method, endpoint.path)) else { continue };
+                    if called_back.insert(endpoint_node_id.clone()) {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: channel_node_id.clone(),
+                            target_node_id: endpoint_node_id.clone(),
+                            edge_type: EdgeType::CallsBack,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Payment providers with their webhook events, checkouts and prices, linked to the endpoints that run them
+        let payment_repo = PaymentRepository::new(self.db.clone());
+        let payment_flows = match payment_repo.get_by_repository(repository_id) {
+            Ok(f) => f,
+            Err(e) => {
+                log::warn!("Failed to load payment flows for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut payment_providers: Vec<(&str, Vec<&crate::storage::StoredPaymentFlow>)> = Vec::new();
+        for flow in &payment_flows {
+            match payment_providers.iter_mut().find(|(p, _)| *p == flow.provider) {
+                Some((_, flows)) => flows.push(flow),
+                None => payment_providers.push((flow.provider.as_str(), vec![flow])),
+            }
+        }
+        for (provider, flows) in &payment_providers {
+            let provider_node_id = Uuid::new_v4().to_string();
+            let mut provider_props = HashMap::new();
+            provider_props.insert("provider".to_string(), provider.to_string());
+            provider_props.insert("file_path".to_string(), flows[0].file_path.clone());
+            for (kind, prop) in [("event", "webhook_events"), ("checkout", "checkouts"), ("price", "prices"), ("product", "products")] {
+                let mut values: Vec<&str> = flows.iter()
+                    .filter(|f| f.kind == kind)
+                    .map(|f| f.value.as_str())
+                    .collect();
+                values.dedup();
+                if !values.is_empty() {
+                    provider_props.insert(prop.to_string(), values.join(", "));
+                }
+            }
+            provider_props.insert("verifies_webhooks".to_string(), flows.iter().any(|f| f.kind == "webhook").to_string());
+
+            nodes.push(GraphNode {
+                id: provider_node_id.clone(),
+                node_type: NodeType::PaymentProvider,
+                name: provider.to_string(),
+                properties: provider_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: provider_node_id.clone(),
+                edge_type: EdgeType::HasPaymentProvider,
+                properties: HashMap::new(),
+            });
+
+            // One edge per endpoint, listing what its handler does with the provider
+            let mut linked: Vec<(&str, Vec<&str>, Option<&str>)> = Vec::new();
+            for flow in flows {
+                let Some(endpoint) = flow.endpoint.as_deref() else { continue };
+                match linked.iter_mut().find(|(e, _, _)| *e == endpoint) {
+                    Some((_, kinds, _)) => if !kinds.contains(&flow.kind.as_str()) { kinds.push(flow.kind.as_str()) },
+                    None => linked.push((endpoint, vec![flow.kind.as_str()], flow.handler.as_deref())),
+                }
+            }
+            for (endpoint, kinds, handler) in linked {
+                let Some(endpoint_node_id) = node_map.get(&format!("endpoint:{}", endpoint)) else { continue };
+                let mut edge_props = HashMap::new();
+                edge_props.insert("kinds".to_string(), kinds.join(", "));
+                if let Some(handler) = handler {
+                    edge_props.insert("handler".to_string(), handler.to_string());
+                }
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: endpoint_node_id.clone(),
+                    target_node_id: provider_node_id.clone(),
+                    edge_type: EdgeType::HandlesPayments,
+                    properties: edge_props,
+                });
+            }
+        }
+
+        // Get Kubernetes resources and resolve their runtime topology
+        let kubernetes_repo = KubernetesRepository::new(self.db.clone());
+        let kubernetes_resources: Vec<_> = kubernetes_repo.get_by_repository(repository_id)?
+            .iter()
+            .map(|r| r.to_resource())
+            .collect();
+
+        if !kubernetes_resources.is_empty() {
+            let mut k8s_node_ids: HashMap<String, String> = HashMap::new();
+            for resource in &kubernetes_resources {
+                let resource_key = format!("{}/{}", resource.kind, resource.name);
+                if k8s_node_ids.contains_key(&resource_key) {
+                    continue;
+                }
+                let id = Uuid::new_v4().to_string();
+                let mut resource_props = HashMap::new();
+                resource_props.insert("kind".to_string(), resource.kind.clone());
+                resource_props.insert("file_path".to_string(), resource.file_path.clone());
+                if let Some(namespace) = &resource.namespace {
+                    resource_props.insert("namespace".to_string(), namespace.clone());
+                }
+                if !resource.hosts.is_empty() {
+                    resource_props.insert("hosts".to_string(), resource.hosts.join(", "));
+                }
+
+                nodes.push(GraphNode {
+                    id: id.clone(),
+                    node_type: NodeType::KubernetesResource,
+                    name: resource_key.clone(),
+                    properties: resource_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: repo_node_id.clone(),
+                    target_node_id: id.clone(),
+                    edge_type: EdgeType::HasKubernetesResource,
+                    properties: HashMap::new(),
+                });
+                if resource.kind == "Service" {
+                    node_map.insert(format!("k8s_service:{}", resource.name), id.clone());
+                }
+                k8s_node_ids.insert(resource_key, id);
+            }
+
+            // Images run by workloads; images built by this repo also get a BuildsImage edge
+            let built_images: HashSet<&String> = kubernetes_resources.iter()
+                .flat_map(|r| r.built_images.iter())
+                .collect();
+
+            let topology = crate::analysis::KubernetesTopology::from_resources(kubernetes_resources.clone());
+            for link in &topology.links {
+                let source_id = match k8s_node_ids.get(&link.source) {
+                    Some(id) => id.clone(),
+                    None => continue,
+                };
+
+                let (target_id, edge_type) = if link.link_type == "runs_image" {
+                    let image_key = format!("image:{}", link.target);
+                    let image_id = if let Some(id) = node_map.get(&image_key) {
+                        id.clone()
+                    } else {
+                        let id = Uuid::new_v4().to_string();
+                        let is_built = built_images.contains(&link.target);
+                        let mut image_props = HashMap::new();
+                        image_props.insert("image".to_string(), link.target.clone());
+                        image_props.insert("built_by_repo".to_string(), is_built.to_string());
+                        nodes.push(GraphNode {
+                            id: id.clone(),
+                            node_type: NodeType::ContainerImage,
+                            name: link.target.clone(),
+                            properties: image_props,
+                            repository_id: Some(repository_id.to_string()),
+                        });
+                        if is_built {
+                            edges.push(GraphEdge {
+                                id: Uuid::new_v4().to_string(),
+                                source_node_id: repo_node_id.clone(),
+                                target_node_id: id.clone(),
+                                edge_type: EdgeType::BuildsImage,
+                                properties: HashMap::new(),
+                            });
+                        }
+                        node_map.insert(image_key, id.clone());
+                        id
+                    };
+                    (image_id, EdgeType::RunsImage)
+                } else {
+                    // Targets that aren't declared in the repo (e.g. external secrets) are skipped
+                    let target_id = match k8s_node_ids.get(&link.target) {
+                        Some(id) => id.clone(),
+                        None => continue,
+                    };
+                    let edge_type = match link.link_type.as_str() {
+                        "routes_to" => EdgeType::RoutesTo,
+                        "selects" => EdgeType::Selects,
+                        "uses_config" => EdgeType::UsesConfig,
+                        _ => EdgeType::RelatedTo,
+                    };
+                    (target_id, edge_type)
+                };
+
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: source_id,
+                    target_node_id: target_id,
+                    edge_type,
+                    properties: HashMap::new(),
+                });
+            }
+        }
+
+        // API gateways and the upstreams their routes reach: a Kubernetes Service named after the
+        // upstream host, an endpoint of this repository on the same path, or else an upstream node
+        let gateway_routes = match GatewayRepository::new(self.db.clone()).get_by_repository(repository_id) {
+            Ok(r) => r,
+            Err(e) => {
+                log::warn!("Failed to load API gateway routes for graph: {}", e);
+                Vec::new()
+            }
+        };
+        for route in &gateway_routes {
+            let gateway_key = format!("gateway:{}:{}", route.kind, route.gateway);
+            let gateway_node_id = if let Some(id) = node_map.get(&gateway_key) {
+                id.clone()
+            } else {
+                let id = Uuid::new_v4().to_string();
+                let mut gateway_props = HashMap::new();
+                gateway_props.insert("kind".to_string(), route.kind.clone());
+                gateway_props.insert("file_path".to_string(), route.file_path.clone());
+                nodes.push(GraphNode {
+                    id: id.clone(),
+                    node_type: NodeType::ApiGateway,
+                    name: route.gateway.clone(),
+                    properties: gateway_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: repo_node_id.clone(),
+                    target_node_id: id.clone(),
+                    edge_type: EdgeType::HasGateway,
+                    properties: HashMap::new(),
+                });
+                node_map.insert(gateway_key, id.clone());
+                id
+            };
+
+            let host = route.upstream_url.as_deref()
+                .and_then(|u| u.split_once("://"))
+                .and_then(|(_, rest)| rest.split(['/', ':', '.']).next())
+                .unwrap_or(route.upstream.as_str());
+            let local_endpoints: Vec<String> = endpoints.iter()
+                .filter(|e| route.protocol == "http" && e.protocol == "http" && e.path == route.route)
+                .filter(|e| route.methods.is_empty() || route.methods.iter().any(|m| m.eq_ignore_ascii_case(&e.method)))
+                .filter_map(|e| node_map.get(&format!("endpoint:{} {}", e.method, e.path)).cloned())
+                .collect();
+            let targets = if let Some(id) = node_map.get(&format!("k8s_service:{}", host)) {
+                vec![id.clone()]
+            } else if !local_endpoints.is_empty() {
+                local_endpoints
+            } else {
+                let upstream_key = format!("upstream:{}", route.upstream);
+                let id = if let Some(id) = node_map.get(&upstream_key) {
+                    id.clone()
+                } else {
+                    let id = Uuid::new_v4().to_string();
+                    let mut upstream_props = HashMap::new();
+                    upstream_props.insert("protocol".to_string(), route.protocol.clone());
+                    if let Some(url) = &route.upstream_url {
+                        upstream_props.insert("url".to_string(), url.clone());
+                    }
+                    nodes.push(GraphNode {
+                        id: id.clone(),
+                        node_type: NodeType::GatewayUpstream,
+                        name: route.upstream.clone(),
+                        properties: upstream_props,
+                        repository_id: Some(repository_id.to_string()),
+                    });
+                    node_map.insert(upstream_key, id.clone());
+                    id
+                };
+                vec![id]
+            };
+
+            let mut edge_props = HashMap::new();
+            edge_props.insert("route".to_string(), route.route.clone());
+            edge_props.insert("protocol".to_string(), route.protocol.clone());
+            if !route.methods.is_empty() {
+                edge_props.insert("methods".to_string(), route.methods.join(", "));
+            }
+            for target_id in targets {
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: gateway_node_id.clone(),
+                    target_node_id: target_id,
+                    edge_type: EdgeType::GatewayRoutesTo,
+                    properties: edge_props.clone(),
+                });
+            }
+        }
+
+        // Get CI/CD pipelines
+        let mut ci_job_node_ids: HashMap<String, String> = HashMap::new(); // "pipeline / job" -> node_id
+        let pipeline_repo = PipelineRepository::new(self.db.clone());
+        let pipelines = match pipeline_repo.get_by_repository(repository_id) {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("Failed to load CI/CD pipelines for graph: {}", e);
+                Vec::new()
+            }
+        };
+
+        for pipeline in &pipelines {
+            let pipeline_node_id = Uuid::new_v4().to_string();
+            let mut pipeline_props = HashMap::new();
+            pipeline_props.insert("platform".to_string(), pipeline.platform.clone());
+            pipeline_props.insert("file_path".to_string(), pipeline.file_path.clone());
+            pipeline_props.insert("triggers".to_string(), pipeline.triggers.join(", "));
+            if !pipeline.stages.is_empty() {
+                pipeline_props.insert("stages".to_string(), pipeline.stages.join(", "));
+            }
+
+            nodes.push(GraphNode {
+                id: pipeline_node_id.clone(),
+                node_type: NodeType::Pipeline,
+                name: pipeline.name.clone(),
+                properties: pipeline_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: pipeline_node_id.clone(),
+                edge_type: EdgeType::HasPipeline,
+                properties: HashMap::new(),
+            });
+
+            // Job names are only unique within a pipeline
+            let mut job_node_ids: HashMap<String, String> = HashMap::new();
+            for job in &pipeline.jobs {
+                let job_node_id = Uuid::new_v4().to_string();
+                let mut job_props = HashMap::new();
+                job_props.insert("pipeline".to_string(), pipeline.name.clone());
+                job_props.insert("file_path".to_string(), pipeline.file_path.clone());
+                if let Some(stage) = &job.stage {
+                    job_props.insert("stage".to_string(), stage.clone());
+                }
+                if let Some(runs_on) = &job.runs_on {
+                    job_props.insert("runs_on".to_string(), runs_on.clone());
+                }
+                if let Some(environment) = &job.environment {
+                    job_props.insert("environment".to_string(), environment.clone());
+                }
+
+                nodes.push(GraphNode {
+                    id: job_node_id.clone(),
+                    node_type: NodeType::PipelineJob,
+                    name: job.name.clone(),
+                    properties: job_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: pipeline_node_id.clone(),
+                    target_node_id: job_node_id.clone(),
+                    edge_type: EdgeType::PipelineHasJob,
+                    properties: HashMap::new(),
+                });
+                job_node_ids.insert(job.name.clone(), job_node_id.clone());
+                ci_job_node_ids.insert(format!("{} / {}", pipeline.name, job.name), job_node_id.clone());
+
+                for tool in &job.tools {
+                    if let Some(tool_node_id) = tool_node_ids.get(&tool.to_lowercase()) {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: job_node_id.clone(),
+                            target_node_id: tool_node_id.clone(),
+                            edge_type: EdgeType::JobUsesTool,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+
+                for provider in &job.cloud_providers {
+                    let provider_node_id = if let Some(id) = node_map.get(provider) {
+                        id.clone()
+                    } else {
+                        let id = Uuid::new_v4().to_string();
+                        let mut provider_props = HashMap::new();
+                        provider_props.insert("type".to_string(), "service_provider".to_string());
+                        nodes.push(GraphNode {
+                            id: id.clone(),
+                            node_type: NodeType::ServiceProvider,
+                            name: provider.clone(),
+                            properties: provider_props,
+                            repository_id: Some(repository_id.to_string()),
+                        });
+                        node_map.insert(provider.clone(), id.clone());
+                        id
+                    };
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: job_node_id.clone(),
+                        target_node_id: provider_node_id,
+                        edge_type: EdgeType::JobUsesProvider,
+                        properties: HashMap::new(),
+                    });
+                }
+
+                for target in &job.deploy_targets {
+                    let target_key = format!("deploy_target:{}", target);
+                    let target_node_id = if let Some(id) = node_map.get(&target_key) {
+                        id.clone()
+                    } else {
+                        let id = Uuid::new_v4().to_string();
+                        let mut target_props = HashMap::new();
+                        let (kind, detail) = target.split_once(':').unwrap_or((target.as_str(), ""));
+                        target_props.insert("target_type".to_string(), kind.to_string());
+                        if !detail.is_empty() {
+                            target_props.insert("target".to_string(), detail.to_string());
+                        }
+                        nodes.push(GraphNode {
+                            id: id.clone(),
+                            node_type: NodeType::DeployTarget,
+                            name: target.clone(),
+                            properties: target_props,
+                            repository_id: Some(repository_id.to_string()),
+                        });
+                        node_map.insert(target_key, id.clone());
+                        id
+                    };
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: job_node_id.clone(),
+                        target_node_id: target_node_id,
+                        edge_type: EdgeType::DeploysTo,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+
+            // Job ordering within the pipeline
+            for job in &pipeline.jobs {
+                for needed in &job.needs {
+                    if let (Some(source), Some(target)) = (job_node_ids.get(&job.name), job_node_ids.get(needed)) {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: source.clone(),
+                            target_node_id: target.clone(),
+                            edge_type: EdgeType::JobNeeds,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Get build targets (task runner targets, the tools they invoke and the CI jobs that run them)
+        let build_targets = match BuildTargetRepository::new(self.db.clone()).get_by_repository(repository_id) {
+            Ok(t) => t,
+            Err(e) => {
+                log::warn!("Failed to load build targets for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut build_target_node_ids: HashMap<String, String> = HashMap::new(); // runner:name -> node_id
+        for target in &build_targets {
+            let target_node_id = Uuid::new_v4().to_string();
+            let mut target_props = HashMap::new();
+            target_props.insert("runner".to_string(), target.runner.clone());
+            target_props.insert("file_path".to_string(), target.file_path.clone());
+            if let Some(line) = target.line_number {
+                target_props.insert("line_number".to_string(), line.to_string());
+            }
+            target_props.insert("dependents".to_string(), target.dependents.to_string());
+            target_props.insert("ci_reach".to_string(), target.ci_reach.to_string());
+            target_props.insert("unused".to_string(), target.unused.to_string());
+
+            nodes.push(GraphNode {
+                id: target_node_id.clone(),
+                node_type: NodeType::BuildTarget,
+                name: target.key.clone(),
+                properties: target_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            build_target_node_ids.entry(target.key.clone()).or_insert(target_node_id.clone());
+
+            for tool in &target.tools {
+                if let Some(tool_node_id) = tool_node_ids.get(&tool.to_lowercase()) {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: target_node_id.clone(),
+                        target_node_id: tool_node_id.clone(),
+                        edge_type: EdgeType::TargetUsesTool,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+            for job in &target.ci_jobs {
+                if let Some(job_node_id) = ci_job_node_ids.get(job) {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: job_node_id.clone(),
+                        target_node_id: target_node_id.clone(),
+                        edge_type: EdgeType::JobRunsTarget,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+        }
+        for target in &build_targets {
+            for dependency in &target.depends_on {
+                if let (Some(source), Some(dependency_node_id)) = (build_target_node_ids.get(&target.key), build_target_node_ids.get(dependency)) {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: source.clone(),
+                        target_node_id: dependency_node_id.clone(),
+                        edge_type: EdgeType::TargetDependsOn,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+        }
+
+        // Get data pipelines (tasks in run order and the datasets that connect pipelines)
+        let data_pipelines = match DataPipelineRepository::new(self.db.clone()).get_by_repository(repository_id) {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("Failed to load data pipelines for graph: {}", e);
+                Vec::new()
+            }
+        };
+        let mut dataset_node_ids: HashMap<String, String> = HashMap::new(); // dataset name -> node_id
+        let mut dataset_node = |name: &str, nodes: &mut Vec<GraphNode>| -> String {
+            dataset_node_ids.entry(name.to_string()).or_insert_with(|| {
+                let node_id = Uuid::new_v4().to_string();
+                nodes.push(GraphNode {
+                    id: node_id.clone(),
+                    node_type: NodeType::Dataset,
+                    name: name.to_string(),
+                    properties: HashMap::new(),
+                    repository_id: Some(repository_id.to_string()),
+                });
+                node_id
+            }).clone()
+        };
+        for pipeline in &data_pipelines {
+            let pipeline_node_id = Uuid::new_v4().to_string();
+            let mut pipeline_props = HashMap::new();
+            pipeline_props.insert("orchestrator".to_string(), pipeline.orchestrator.clone());
+            pipeline_props.insert("file_path".to_string(), pipeline.file_path.clone());
+            if let Some(schedule) = &pipeline.schedule {
+                pipeline_props.insert("schedule".to_string(), schedule.clone());
+            }
+            if let Some(warehouse) = &pipeline.warehouse {
+                pipeline_props.insert("warehouse".to_string(), warehouse.clone());
+            }
+
+            nodes.push(GraphNode {
+                id: pipeline_node_id.clone(),
+                node_type: NodeType::DataPipeline,
+                name: pipeline.name.clone(),
+                properties: pipeline_props,
+                repository_id: Some(repository_id.to_string()),
+            });
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: repo_node_id.clone(),
+                target_node_id: pipeline_node_id.clone(),
+                edge_type: EdgeType::HasDataPipeline,
+                properties: HashMap::new(),
+            });
+            for dataset in &pipeline.triggered_by {
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: dataset_node(dataset, &mut nodes),
+                    target_node_id: pipeline_node_id.clone(),
+                    edge_type: EdgeType::TriggersPipeline,
+                    properties: HashMap::new(),
+                });
+            }
+
+            // The warehouse is linked to services detected under the adapter's name, e.g. a
+            // `snowflake` project to a Snowflake service or `postgres` to PostgreSQL
+            if let Some(warehouse) = &pipeline.warehouse {
+                let adapter = warehouse.to_lowercase();
+                for service in services.iter().filter(|s| s.provider == adapter || s.name.to_lowercase().contains(&adapter)) {
+                    if let Some(service_node_id) = service_node_ids.get(&service.id) {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: pipeline_node_id.clone(),
+                            target_node_id: service_node_id.clone(),
+                            edge_type: EdgeType::UsesWarehouse,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+            }
+
+            // dbt sources are the datasets of the same name, with where they live
+            for source in &pipeline.sources {
+                let source_node_id = dataset_node(&source.name, &mut nodes);
+                if let Some(node) = nodes.iter_mut().find(|n| n.id == source_node_id) {
+                    node.properties.insert("kind".to_string(), "source".to_string());
+                    node.properties.insert("file_path".to_string(), source.file_path.clone());
+                    if let Some(relation) = &source.relation {
+                        node.properties.insert("relation".to_string(), relation.clone());
+                    }
+                    if let Some(loader) = &source.loader {
+                        node.properties.insert("loader".to_string(), loader.clone());
+                    }
+                }
+            }
+            for exposure in &pipeline.exposures {
+                let exposure_node_id = Uuid::new_v4().to_string();
+                let mut exposure_props = HashMap::new();
+                exposure_props.insert("pipeline".to_string(), pipeline.name.clone());
+                exposure_props.insert("kind".to_string(), exposure.kind.clone());
+                exposure_props.insert("file_path".to_string(), exposure.file_path.clone());
+                if let Some(owner) = &exposure.owner {
+                    exposure_props.insert("owner".to_string(), owner.clone());
+                }
+                if let Some(url) = &exposure.url {
+                    exposure_props.insert("url".to_string(), url.clone());
+                }
+
+                nodes.push(GraphNode {
+                    id: exposure_node_id.clone(),
+                    node_type: NodeType::DataExposure,
+                    name: exposure.name.clone(),
+                    properties: exposure_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: pipeline_node_id.clone(),
+                    target_node_id: exposure_node_id.clone(),
+                    edge_type: EdgeType::HasExposure,
+                    properties: HashMap::new(),
+                });
+                for dataset in &exposure.depends_on {
+                    edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: exposure_node_id.clone(),
+                        target_node_id: dataset_node(dataset, &mut nodes),
+                        edge_type: EdgeType::ExposureUses,
+                        properties: HashMap::new(),
+                    });
+                }
+            }
+
+            // Task names are only unique within a pipeline
+            let mut task_node_ids: HashMap<&str, String> = HashMap::new();
+            for task in &pipeline.tasks {
+                let task_node_id = Uuid::new_v4().to_string();
+                let mut task_props = HashMap::new();
+                task_props.insert("pipeline".to_string(), pipeline.name.clone());
+                task_props.insert("kind".to_string(), task.kind.clone());
+                task_props.insert("file_path".to_string(), task.file_path.clone());
+                if let Some(line) = task.line_number {
+                    task_props.insert("line_number".to_string(), line.to_string());
+                }
+                if let Some(notebook) = &task.notebook {
+                    task_props.insert("notebook".to_string(), notebook.clone());
+                }
+
+                nodes.push(GraphNode {
+                    id: task_node_id.clone(),
+                    node_type: NodeType::DataTask,
+                    name: task.name.clone(),
+                    properties: task_props,
+                    repository_id: Some(repository_id.to_string()),
+                });
+                edges.push(GraphEdge {
+                    id: Uuid::new_v4().to_string(),
+                    source_node_id: pipeline_node_id.clone(),
+                    target_node_id: task_node_id.clone(),
+                    edge_type: EdgeType::HasDataTask,
+                    properties: HashMap::new(),
+                });
+                for (datasets, edge_type) in [(&task.inputs, EdgeType::ReadsDataset), (&task.outputs, EdgeType::WritesDataset)] {
+                    for dataset in datasets {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: task_node_id.clone(),
+                            target_node_id: dataset_node(dataset, &mut nodes),
+                            edge_type: edge_type.clone(),
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+                task_node_ids.insert(task.name.as_str(), task_node_id);
+            }
+            for task in &pipeline.tasks {
+                for upstream in &task.upstream {
+                    if let (Some(source), Some(target)) = (task_node_ids.get(task.name.as_str()), task_node_ids.get(upstream.as_str())) {
+                        edges.push(GraphEdge {
+                            id: Uuid::new_v4().to_string(),
+                            source_node_id: source.clone(),
+                            target_node_id: target.clone(),
+                            edge_type: EdgeType::DataTaskDependsOn,
+                            properties: HashMap::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut graph = KnowledgeGraph { nodes, edges };
+        match ImageRepository::new(self.db.clone()).get_by_repository(repository_id) {
+            Ok(images) => graph.attach_image_metadata(repository_id, &images),
+            Err(e) => log::warn!("⚠ Failed to load container image metadata: {}", e),
+        }
+        Ok(graph)
     }
 
     /// Store graph in database
@@ -778,6 +2212,79 @@ impl GraphBuilder {
         Ok(())
     }
 
+    /// The stored graphs of a system's repositories joined by the links between them. Links are
+    /// drawn between the repository nodes, except calls, which point at the endpoint called when
+    /// its node is in the callee's graph.
+    pub fn build_for_system(&self, repository_ids: &[String], links: &[CrossRepoLink]) -> Result<KnowledgeGraph> {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut repo_nodes: HashMap<String, String> = HashMap::new();
+        let mut endpoint_nodes: HashMap<(String, String), String> = HashMap::new();
+
+        for repository_id in repository_ids {
+            let graph = self.get_graph(repository_id)?;
+            for node in &graph.nodes {
+                match node.node_type {
+                    NodeType::Repository => {
+                        repo_nodes.entry(repository_id.clone()).or_insert_with(|| node.id.clone());
+                    }
+                    NodeType::Endpoint => {
+                        let method = node.properties.get("method").map(|m| m.to_uppercase()).unwrap_or_default();
+                        let path = node.properties.get("path").cloned().unwrap_or_else(|| node.name.clone());
+                        endpoint_nodes.insert((repository_id.clone(), format!("{} {}", method, path)), node.id.clone());
+                    }
+                    _ => {}
+                }
+            }
+            nodes.extend(graph.nodes);
+            edges.extend(graph.edges);
+
+            // Repositories not analyzed yet still take part in the system
+            if !repo_nodes.contains_key(repository_id) {
+                let Some(repo) = self.repo_repo.find_by_id(repository_id)? else { continue };
+                let node_id = Uuid::new_v4().to_string();
+                let mut props = HashMap::new();
+                props.insert("url".to_string(), repo.url.clone());
+                props.insert("branch".to_string(), repo.branch.clone());
+                nodes.push(GraphNode {
+                    id: node_id.clone(),
+                    node_type: NodeType::Repository,
+                    name: repo.name,
+                    properties: props,
+                    repository_id: Some(repository_id.clone()),
+                });
+                repo_nodes.insert(repository_id.clone(), node_id);
+            }
+        }
+
+        for link in links {
+            let (Some(source), Some(target_repo)) = (repo_nodes.get(&link.source_repository_id), repo_nodes.get(&link.target_repository_id)) else {
+                continue;
+            };
+            let (edge_type, target) = match link.relation {
+                CrossRepoRelation::DependsOnRepository => (EdgeType::DependsOnRepository, target_repo),
+                CrossRepoRelation::SharesDependency => (EdgeType::SharesDependency, target_repo),
+                CrossRepoRelation::SharesResource => (EdgeType::SharesResource, target_repo),
+                CrossRepoRelation::CallsEndpoint => (
+                    EdgeType::CallsEndpoint,
+                    endpoint_nodes.get(&(link.target_repository_id.clone(), link.target.clone())).unwrap_or(target_repo),
+                ),
+            };
+            let mut props = HashMap::new();
+            props.insert("target".to_string(), link.target.clone());
+            props.insert("evidence".to_string(), link.evidence.clone());
+            edges.push(GraphEdge {
+                id: Uuid::new_v4().to_string(),
+                source_node_id: source.clone(),
+                target_node_id: target.clone(),
+                edge_type,
+                properties: props,
+            });
+        }
+
+        Ok(KnowledgeGraph { nodes, edges })
+    }
+
     /// Get graph from database
     pub fn get_graph(&self, repository_id: &str) -> Result<KnowledgeGraph> {
         let conn = self.db.get_connection();
@@ -849,6 +2356,28 @@ impl GraphBuilder {
             NodeType::TestFramework => "test_framework",
             NodeType::Port => "port",
             NodeType::Endpoint => "endpoint",
+            NodeType::KubernetesResource => "kubernetes_resource",
+            NodeType::ContainerImage => "container_image",
+            NodeType::ApiGateway => "api_gateway",
+            NodeType::GatewayUpstream => "gateway_upstream",
+            NodeType::Pipeline => "pipeline",
+            NodeType::PipelineJob => "pipeline_job",
+            NodeType::BuildTarget => "build_target",
+            NodeType::DataPipeline => "data_pipeline",
+            NodeType::DataTask => "data_task",
+            NodeType::Dataset => "dataset",
+            NodeType::DataExposure => "data_exposure",
+            NodeType::DeployTarget => "deploy_target",
+            NodeType::Entrypoint => "entrypoint",
+            NodeType::BackgroundJob => "background_job",
+            NodeType::NotificationChannel => "notification_channel",
+            NodeType::PaymentProvider => "payment_provider",
+            NodeType::FrontendRoute => "frontend_route",
+            NodeType::GraphQLOperation => "graphql_operation",
+            NodeType::GraphQLType => "graphql_type",
+            NodeType::ArchitectureDecision => "architecture_decision",
+            NodeType::Documentation => "documentation",
+            NodeType::PluginEntity => "plugin_entity",
         }.to_string()
     }
 
@@ -866,6 +2395,28 @@ impl GraphBuilder {
             "test_framework" => NodeType::TestFramework,
             "port" => NodeType::Port,
             "endpoint" => NodeType::Endpoint,
+            "kubernetes_resource" => NodeType::KubernetesResource,
+            "container_image" => NodeType::ContainerImage,
+            "api_gateway" => NodeType::ApiGateway,
+            "gateway_upstream" => NodeType::GatewayUpstream,
+            "pipeline" => NodeType::Pipeline,
+            "pipeline_job" => NodeType::PipelineJob,
+            "build_target" => NodeType::BuildTarget,
+            "data_pipeline" => NodeType::DataPipeline,
+            "data_task" => NodeType::DataTask,
+            "dataset" => NodeType::Dataset,
+            "data_exposure" => NodeType::DataExposure,
+            "deploy_target" => NodeType::DeployTarget,
+            "entrypoint" => NodeType::Entrypoint,
+            "background_job" => NodeType::BackgroundJob,
+            "notification_channel" => NodeType::NotificationChannel,
+            "payment_provider" => NodeType::PaymentProvider,
+            "frontend_route" => NodeType::FrontendRoute,
+            "graphql_operation" => NodeType::GraphQLOperation,
+            "graphql_type" => NodeType::GraphQLType,
+            "architecture_decision" => NodeType::ArchitectureDecision,
+            "documentation" => NodeType::Documentation,
+            "plugin_entity" => NodeType::PluginEntity,
             _ => NodeType::Repository,
         }
     }
@@ -889,6 +2440,59 @@ impl GraphBuilder {
             EdgeType::HasPort => "has_port",
             EdgeType::HasEndpoint => "has_endpoint",
             EdgeType::EndpointUsesPort => "endpoint_uses_port",
+            EdgeType::HasKubernetesResource => "has_kubernetes_resource",
+            EdgeType::RoutesTo => "routes_to",
+            EdgeType::Selects => "selects",
+            EdgeType::UsesConfig => "uses_config",
+            EdgeType::RunsImage => "runs_image",
+            EdgeType::BuildsImage => "builds_image",
+            EdgeType::HasGateway => "has_gateway",
+            EdgeType::GatewayRoutesTo => "gateway_routes_to",
+            EdgeType::ReferencesImage => "references_image",
+            EdgeType::HasPipeline => "has_pipeline",
+            EdgeType::PipelineHasJob => "pipeline_has_job",
+            EdgeType::JobNeeds => "job_needs",
+            EdgeType::JobUsesTool => "job_uses_tool",
+            EdgeType::JobUsesProvider => "job_uses_provider",
+            EdgeType::DeploysTo => "deploys_to",
+            EdgeType::JobRunsTarget => "job_runs_target",
+            EdgeType::TargetDependsOn => "target_depends_on",
+            EdgeType::TargetUsesTool => "target_uses_tool",
+            EdgeType::HasDataPipeline => "has_data_pipeline",
+            EdgeType::HasDataTask => "has_data_task",
+            EdgeType::DataTaskDependsOn => "data_task_depends_on",
+            EdgeType::ReadsDataset => "reads_dataset",
+            EdgeType::WritesDataset => "writes_dataset",
+            EdgeType::TriggersPipeline => "triggers_pipeline",
+            EdgeType::HasExposure => "has_exposure",
+            EdgeType::ExposureUses => "exposure_uses",
+            EdgeType::UsesWarehouse => "uses_warehouse",
+            EdgeType::HasEntrypoint => "has_entrypoint",
+            EdgeType::EntrypointInvokes => "entrypoint_invokes",
+            EdgeType::HasBackgroundJob => "has_background_job",
+            EdgeType::JobInvokes => "job_invokes",
+            EdgeType::HasNotificationChannel => "has_notification_channel",
+            EdgeType::SendsVia => "sends_via",
+            EdgeType::CallsBack => "calls_back",
+            EdgeType::HasPaymentProvider => "has_payment_provider",
+            EdgeType::HandlesPayments => "handles_payments",
+            EdgeType::HasFrontendRoute => "has_frontend_route",
+            EdgeType::RendersComponent => "renders_component",
+            EdgeType::HasGraphQLOperation => "has_graphql_operation",
+            EdgeType::OperationUsesType => "operation_uses_type",
+            EdgeType::HasDecision => "has_decision",
+            EdgeType::DecisionAffects => "decision_affects",
+            EdgeType::HasDocumentation => "has_documentation",
+            EdgeType::DocumentsCode => "documents_code",
+            EdgeType::DocumentsService => "documents_service",
+            EdgeType::DocLinksTo => "doc_links_to",
+            EdgeType::Imports => "imports",
+            EdgeType::Calls => "calls",
+            EdgeType::ContainedIn => "contained_in",
+            EdgeType::DependsOnRepository => "depends_on_repository",
+            EdgeType::SharesDependency => "shares_dependency",
+            EdgeType::CallsEndpoint => "calls_endpoint",
+            EdgeType::SharesResource => "shares_resource",
             EdgeType::RelatedTo => "related_to",
         }.to_string()
     }
@@ -912,12 +2516,84 @@ impl GraphBuilder {
             "has_port" => EdgeType::HasPort,
             "has_endpoint" => EdgeType::HasEndpoint,
             "endpoint_uses_port" => EdgeType::EndpointUsesPort,
+            "has_kubernetes_resource" => EdgeType::HasKubernetesResource,
+            "routes_to" => EdgeType::RoutesTo,
+            "selects" => EdgeType::Selects,
+            "uses_config" => EdgeType::UsesConfig,
+            "runs_image" => EdgeType::RunsImage,
+            "builds_image" => EdgeType::BuildsImage,
+            "has_gateway" => EdgeType::HasGateway,
+            "gateway_routes_to" => EdgeType::GatewayRoutesTo,
+            "references_image" => EdgeType::ReferencesImage,
+            "has_pipeline" => EdgeType::HasPipeline,
+            "pipeline_has_job" => EdgeType::PipelineHasJob,
+            "job_needs" => EdgeType::JobNeeds,
+            "job_uses_tool" => EdgeType::JobUsesTool,
+            "job_uses_provider" => EdgeType::JobUsesProvider,
+            "deploys_to" => EdgeType::DeploysTo,
+            "job_runs_target" => EdgeType::JobRunsTarget,
+            "target_depends_on" => EdgeType::TargetDependsOn,
+            "target_uses_tool" => EdgeType::TargetUsesTool,
+            "has_data_pipeline" => EdgeType::HasDataPipeline,
+            "has_data_task" => EdgeType::HasDataTask,
+            "data_task_depends_on" => EdgeType::DataTaskDependsOn,
+            "reads_dataset" => EdgeType::ReadsDataset,
+            "writes_dataset" => EdgeType::WritesDataset,
+            "triggers_pipeline" => EdgeType::TriggersPipeline,
+            "has_exposure" => EdgeType::HasExposure,
+            "exposure_uses" => EdgeType::ExposureUses,
+            "uses_warehouse" => EdgeType::UsesWarehouse,
+            "has_entrypoint" => EdgeType::HasEntrypoint,
+            "entrypoint_invokes" => EdgeType::EntrypointInvokes,
+            "has_background_job" => EdgeType::HasBackgroundJob,
+            "job_invokes" => EdgeType::JobInvokes,
+            "has_notification_channel" => EdgeType::HasNotificationChannel,
+            "sends_via" => EdgeType::SendsVia,
+            "calls_back" => EdgeType::CallsBack,
+            "has_payment_provider" => EdgeType::HasPaymentProvider,
+            "handles_payments" => EdgeType::HandlesPayments,
+            "has_frontend_route" => EdgeType::HasFrontendRoute,
+            "renders_component" => EdgeType::RendersComponent,
+            "has_graphql_operation" => EdgeType::HasGraphQLOperation,
+            "operation_uses_type" => EdgeType::OperationUsesType,
+            "has_decision" => EdgeType::HasDecision,
+            "decision_affects" => EdgeType::DecisionAffects,
+            "has_documentation" => EdgeType::HasDocumentation,
+            "documents_code" => EdgeType::DocumentsCode,
+            "documents_service" => EdgeType::DocumentsService,
+            "doc_links_to" => EdgeType::DocLinksTo,
+            "imports" => EdgeType::Imports,
+            "calls" => EdgeType::Calls,
+            "contained_in" => EdgeType::ContainedIn,
+            "depends_on_repository" => EdgeType::DependsOnRepository,
+            "shares_dependency" => EdgeType::SharesDependency,
+            "calls_endpoint" => EdgeType::CallsEndpoint,
+            "shares_resource" => EdgeType::SharesResource,
             "related_to" => EdgeType::RelatedTo,
             _ => EdgeType::RelatedTo,
         }
     }
 }
 
+/// A node for a code element pulled into the graph by an edge rather than by its own relationships
+fn code_element_node(element: &crate::analysis::CodeElement, repository_id: &str) -> GraphNode {
+    let mut props = HashMap::new();
+    props.insert("file_path".to_string(), element.file_path.clone());
+    props.insert("line_number".to_string(), element.line_number.to_string());
+    props.insert("element_type".to_string(), format!("{:?}", element.element_type));
+    props.insert("language".to_string(), element.language.clone());
+    if !element.qualified_name.is_empty() {
+        props.insert("qualified_name".to_string(), element.qualified_name.clone());
+    }
+    GraphNode {
+        id: format!("code:{}", element.id),
+        node_type: NodeType::CodeElement,
+        name: element.name.clone(),
+        properties: props,
+        repository_id: Some(repository_id.to_string()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphStatistics {
     pub total_nodes: usize,
@@ -963,6 +2639,65 @@ impl KnowledgeGraph {
         }
     }
 
+    /// Registry digest, size, layers and vulnerability counts on the image nodes. Images without a node
+    /// (referenced from a Dockerfile or compose file rather than run by a workload) get one, linked
+    /// from the repository.
+    pub fn attach_image_metadata(&mut self, repository_id: &str, images: &[StoredImageMetadata]) {
+        let repo_node_id = self.nodes.iter()
+            .find(|n| n.node_type == NodeType::Repository)
+            .map(|n| n.id.clone());
+        for image in images {
+            let index = match self.nodes.iter().position(|n| n.node_type == NodeType::ContainerImage && n.name == image.image) {
+                Some(index) => index,
+                None => {
+                    let Some(repo_node_id) = &repo_node_id else { continue };
+                    let id = Uuid::new_v4().to_string();
+                    let mut image_props = HashMap::new();
+                    image_props.insert("image".to_string(), image.image.clone());
+                    image_props.insert("built_by_repo".to_string(), "false".to_string());
+                    self.nodes.push(GraphNode {
+                        id: id.clone(),
+                        node_type: NodeType::ContainerImage,
+                        name: image.image.clone(),
+                        properties: image_props,
+                        repository_id: Some(repository_id.to_string()),
+                    });
+                    self.edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id: repo_node_id.clone(),
+                        target_node_id: id,
+                        edge_type: EdgeType::ReferencesImage,
+                        properties: HashMap::new(),
+                    });
+                    self.nodes.len() - 1
+                }
+            };
+
+            let props = &mut self.nodes[index].properties;
+            props.insert("registry".to_string(), image.registry.clone());
+            let fields = [
+                ("digest", image.digest.clone()),
+                ("platform", image.platform.clone()),
+                ("size_bytes", image.size_bytes.map(|s| s.to_string())),
+                ("base_image", image.base_image.clone()),
+                ("image_created", image.image_created.clone()),
+                ("lookup_error", image.error.clone()),
+            ];
+            for (key, value) in fields {
+                match value {
+                    Some(value) => props.insert(key.to_string(), value),
+                    None => props.remove(key),
+                };
+            }
+            props.insert("layer_count".to_string(), image.layers.len().to_string());
+            props.insert("vulnerabilities".to_string(), image.vulnerabilities.len().to_string());
+            for severity in ["critical", "high", "medium", "low"] {
+                let count = image.vulnerabilities.iter().filter(|v| v.severity == severity).count();
+                props.insert(format!("vulnerabilities_{}", severity), count.to_string());
+            }
+        }
+    }
+
     /// Find nodes by type
     pub fn find_nodes_by_type(&self, node_type: &NodeType) -> Vec<&GraphNode> {
         self.nodes.iter()
@@ -1,3 +1,4 @@
 pub mod graph;
+pub mod neo4j;
 
 pub use graph::GraphBuilder;
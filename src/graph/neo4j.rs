@@ -0,0 +1,229 @@
+use anyhow::{anyhow, Result};
+use neo4rs::{query, BoltType, ConfigBuilder, Graph};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use crate::config::Neo4jConfig;
+use crate::graph::graph::{EdgeType, KnowledgeGraph, NodeType};
+
+/// Shared by every exported node, alongside the node type label, so a repository's nodes can be
+/// replaced without touching anything else in the database
+pub const NODE_LABEL: &str = "Wavelength";
+
+/// Rows sent per `UNWIND` when pushing
+const BATCH_SIZE: usize = 1000;
+
+/// What a push wrote
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Neo4jPushSummary {
+    pub nodes: usize,
+    pub relationships: usize,
+}
+
+/// `Service`, `CodeElement`, ... as Neo4j labels
+fn node_label(node_type: &NodeType) -> String {
+    format!("{:?}", node_type)
+}
+
+/// `DEPENDS_ON`, `USES_SERVICE`, ... as Neo4j relationship types
+fn relationship_type(edge_type: &EdgeType) -> String {
+    let chars: Vec<char> = format!("{:?}", edge_type).chars().collect();
+    let mut name = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        // A word starts after a lowercase letter, or ends an acronym (`GraphQLOperation`)
+        let starts_word = i > 0 && c.is_ascii_uppercase()
+            && (chars[i - 1].is_ascii_lowercase() || chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase()));
+        if starts_word {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+/// A single-quoted Cypher string literal
+fn cypher_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '\'' => quoted.push_str("\\'"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// A property key, backtick-quoted when it is not a plain identifier
+fn cypher_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') && !key.starts_with(|c: char| c.is_ascii_digit()) {
+        key.to_string()
+    } else {
+        format!("`{}`", key.replace('`', "``"))
+    }
+}
+
+/// A `{key: 'value', ...}` map literal, with keys in a stable order
+fn cypher_map<'a>(entries: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+    let entries: BTreeMap<&str, &str> = entries.into_iter().collect();
+    let pairs: Vec<String> = entries.iter().map(|(k, v)| format!("{}: {}", cypher_key(k), cypher_string(v))).collect();
+    format!("{{{}}}", pairs.join(", "))
+}
+
+/// A script for `cypher-shell` or Neo4j Browser that replaces the repository's nodes with `graph`.
+/// Nodes carry the [`NODE_LABEL`] and their type as labels, `id`, `name` and `repository_id` plus
+/// their properties; relationships are typed by edge type and keep their `id` and properties.
+pub fn cypher_script(repository_id: &str, graph: &KnowledgeGraph) -> String {
+    let mut script = format!(
+        "// Wavelength knowledge graph for repository {}: {} nodes, {} relationships\n",
+        repository_id, graph.nodes.len(), graph.edges.len()
+    );
+    script.push_str(&format!(
+        "CREATE CONSTRAINT wavelength_node_id IF NOT EXISTS FOR (n:{}) REQUIRE n.id IS UNIQUE;\n",
+        NODE_LABEL
+    ));
+    script.push_str(&format!(
+        "MATCH (n:{} {{repository_id: {}}}) DETACH DELETE n;\n",
+        NODE_LABEL, cypher_string(repository_id)
+    ));
+
+    for node in &graph.nodes {
+        let properties = cypher_map(
+            node.properties.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+                .chain([("name", node.name.as_str()), ("repository_id", repository_id)])
+        );
+        script.push_str(&format!(
+            "MERGE (n:{} {{id: {}}}) SET n:{}, n += {};\n",
+            NODE_LABEL, cypher_string(&node.id), node_label(&node.node_type), properties
+        ));
+    }
+    for edge in &graph.edges {
+        script.push_str(&format!(
+            "MATCH (a:{label} {{id: {}}}), (b:{label} {{id: {}}}) MERGE (a)-[r:{} {{id: {}}}]->(b) SET r += {};\n",
+            cypher_string(&edge.source_node_id),
+            cypher_string(&edge.target_node_id),
+            relationship_type(&edge.edge_type),
+            cypher_string(&edge.id),
+            cypher_map(edge.properties.iter().map(|(k, v)| (k.as_str(), v.as_str()))),
+            label = NODE_LABEL,
+        ));
+    }
+    script
+}
+
+fn properties_param(properties: &HashMap<String, String>) -> BoltType {
+    BoltType::from(properties.clone())
+}
+
+/// Writes knowledge graphs to a Neo4j database over bolt
+pub struct Neo4jExporter {
+    graph: Graph,
+}
+
+impl Neo4jExporter {
+    /// `password` is the resolved secret, not the configured reference
+    pub async fn connect(config: &Neo4jConfig, password: &str) -> Result<Self> {
+        let uri = config.uri.as_ref()
+            .ok_or_else(|| anyhow!("Neo4j is not configured; set NEO4J_URI and NEO4J_PASSWORD"))?;
+        crate::network::ensure_online("Neo4j")?;
+        let mut builder = ConfigBuilder::default().uri(uri.as_str()).user(config.user.as_str()).password(password);
+        if let Some(database) = &config.database {
+            builder = builder.db(database.as_str());
+        }
+        Ok(Neo4jExporter { graph: Graph::connect(builder.build()?).await? })
+    }
+
+    /// Replaces the repository's nodes and relationships with `graph`, in batches per label and type
+    pub async fn push(&self, repository_id: &str, graph: &KnowledgeGraph) -> Result<Neo4jPushSummary> {
+        self.graph.run(query(&format!(
+            "CREATE CONSTRAINT wavelength_node_id IF NOT EXISTS FOR (n:{}) REQUIRE n.id IS UNIQUE",
+            NODE_LABEL
+        ))).await?;
+        self.graph.run(query(&format!("MATCH (n:{} {{repository_id: $repository_id}}) DETACH DELETE n", NODE_LABEL))
+            .param("repository_id", repository_id)).await?;
+
+        // Labels and relationship types cannot be parameters, so each gets its own statement
+        let mut nodes_by_label: BTreeMap<String, Vec<BoltType>> = BTreeMap::new();
+        for node in &graph.nodes {
+            let row: HashMap<String, BoltType> = HashMap::from([
+                ("id".to_string(), BoltType::from(node.id.as_str())),
+                ("name".to_string(), BoltType::from(node.name.as_str())),
+                ("properties".to_string(), properties_param(&node.properties)),
+            ]);
+            nodes_by_label.entry(node_label(&node.node_type)).or_default().push(BoltType::from(row));
+        }
+        for (label, rows) in &nodes_by_label {
+            let statement = format!(
+                "UNWIND $rows AS row MERGE (n:{} {{id: row.id}}) SET n:{}, n += row.properties, n.name = row.name, n.repository_id = $repository_id",
+                NODE_LABEL, label
+            );
+            for batch in rows.chunks(BATCH_SIZE) {
+                self.graph.run(query(&statement).param("rows", batch.to_vec()).param("repository_id", repository_id)).await?;
+            }
+        }
+
+        let mut edges_by_type: BTreeMap<String, Vec<BoltType>> = BTreeMap::new();
+        for edge in &graph.edges {
+            let row: HashMap<String, BoltType> = HashMap::from([
+                ("id".to_string(), BoltType::from(edge.id.as_str())),
+                ("source".to_string(), BoltType::from(edge.source_node_id.as_str())),
+                ("target".to_string(), BoltType::from(edge.target_node_id.as_str())),
+                ("properties".to_string(), properties_param(&edge.properties)),
+            ]);
+            edges_by_type.entry(relationship_type(&edge.edge_type)).or_default().push(BoltType::from(row));
+        }
+        for (relationship, rows) in &edges_by_type {
+            let statement = format!(
+                "UNWIND $rows AS row MATCH (a:{label} {{id: row.source}}), (b:{label} {{id: row.target}}) \
+                 MERGE (a)-[r:{} {{id: row.id}}]->(b) SET r += row.properties",
+                relationship, label = NODE_LABEL
+            );
+            for batch in rows.chunks(BATCH_SIZE) {
+                self.graph.run(query(&statement).param("rows", batch.to_vec())).await?;
+            }
+        }
+
+        Ok(Neo4jPushSummary { nodes: graph.nodes.len(), relationships: graph.edges.len() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph::{GraphEdge, GraphNode};
+
+    #[test]
+    fn test_cypher_script_labels_types_and_escapes() {
+        let node = |id: &str, node_type: NodeType, name: &str| GraphNode {
+            id: id.to_string(),
+            node_type,
+            name: name.to_string(),
+            properties: HashMap::from([("file path".to_string(), "src/o'brien.rs".to_string())]),
+            repository_id: Some("repo-1".to_string()),
+        };
+        let graph = KnowledgeGraph {
+            nodes: vec![node("a", NodeType::CodeElement, "main"), node("b", NodeType::Service, "Postgres\nprimary")],
+            edges: vec![GraphEdge {
+                id: "e1".to_string(),
+                source_node_id: "a".to_string(),
+                target_node_id: "b".to_string(),
+                edge_type: EdgeType::CodeUsesService,
+                properties: HashMap::new(),
+            }],
+        };
+
+        let script = cypher_script("repo-1", &graph);
+        assert!(script.contains("MATCH (n:Wavelength {repository_id: 'repo-1'}) DETACH DELETE n;"));
+        assert!(script.contains(
+            "MERGE (n:Wavelength {id: 'a'}) SET n:CodeElement, n += {`file path`: 'src/o\\'brien.rs', name: 'main', repository_id: 'repo-1'};"
+        ));
+        assert!(script.contains("name: 'Postgres\\nprimary'"));
+        assert!(script.contains("MERGE (a)-[r:CODE_USES_SERVICE {id: 'e1'}]->(b) SET r += {};"));
+        assert_eq!(relationship_type(&EdgeType::HasGraphQLOperation), "HAS_GRAPH_QL_OPERATION");
+        assert_eq!(script.lines().filter(|l| !l.starts_with("//")).count(), 5);
+    }
+}
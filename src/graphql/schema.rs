@@ -1,10 +1,26 @@
 use async_graphql::{Context, Object, Result as GraphQLResult, Schema, EmptySubscription};
 use crate::api::ApiState;
+use crate::api::workspaces::CurrentWorkspace;
+use crate::storage::DEFAULT_WORKSPACE;
 use crate::graphql::types::*;
 use crate::graph::GraphBuilder;
 
 pub type GraphQLSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
 
+/// The caller's workspace, attached to each request by the GraphQL handler
+fn workspace(ctx: &Context<'_>) -> CurrentWorkspace {
+    ctx.data_opt::<CurrentWorkspace>().cloned()
+        .unwrap_or_else(|| CurrentWorkspace(DEFAULT_WORKSPACE.to_string()))
+}
+
+/// Repositories of other workspaces are reported as missing
+fn ensure_in_workspace(ctx: &Context<'_>, state: &ApiState, repository_id: &str) -> GraphQLResult<()> {
+    match state.repo_repo.find_by_id(repository_id)? {
+        Some(repo) if repo.workspace_id != workspace(ctx).0 => Err("Repository not found".into()),
+        _ => Ok(()),
+    }
+}
+
 pub struct QueryRoot;
 
 #[Object]
@@ -13,7 +29,8 @@ impl QueryRoot {
     async fn repository(&self, ctx: &Context<'_>, id: String) -> GraphQLResult<Option<RepositoryType>> {
         let state = ctx.data::<ApiState>()?;
         match state.repo_repo.find_by_id(&id)? {
-            Some(repo) => Ok(Some(RepositoryType::from(repo))),
+            Some(repo) if repo.workspace_id == workspace(ctx).0 => Ok(Some(RepositoryType::from(repo))),
+            Some(_) => Ok(None),
             None => Ok(None),
         }
     }
@@ -21,7 +38,7 @@ impl QueryRoot {
     /// List all repositories
     async fn repositories(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<RepositoryType>> {
         let state = ctx.data::<ApiState>()?;
-        let repos = state.repo_repo.list_all()?;
+        let repos = state.repo_repo.list_by_workspace(&workspace(ctx).0)?;
         Ok(repos.into_iter().map(RepositoryType::from).collect())
     }
 
@@ -33,6 +50,7 @@ impl QueryRoot {
         filter: Option<DependencyFilter>,
     ) -> GraphQLResult<Vec<DependencyType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let mut deps = state.dep_repo.get_by_repository(&repository_id)?;
         
         if let Some(f) = filter {
@@ -58,6 +76,7 @@ impl QueryRoot {
         filter: Option<ServiceFilter>,
     ) -> GraphQLResult<Vec<ServiceType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let mut services = state.service_repo.get_by_repository(&repository_id)?;
         
         if let Some(f) = filter {
@@ -80,6 +99,7 @@ impl QueryRoot {
         element_type: Option<String>,
     ) -> GraphQLResult<Vec<CodeElementType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let mut elements = state.code_repo.get_by_repository(&repository_id)?;
         
         if let Some(et) = element_type {
@@ -96,6 +116,7 @@ impl QueryRoot {
         repository_id: String,
     ) -> GraphQLResult<Vec<CodeCallType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let calls = state.code_repo.get_calls(&repository_id)?;
         Ok(calls.into_iter().map(CodeCallType::from).collect())
     }
@@ -108,6 +129,7 @@ impl QueryRoot {
         filter: Option<SecurityEntityFilter>,
     ) -> GraphQLResult<Vec<SecurityEntityType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let entities = if let Some(f) = &filter {
             if let Some(entity_type) = &f.entity_type {
                 state.security_repo.get_by_type(&repository_id, entity_type)?
@@ -129,6 +151,7 @@ impl QueryRoot {
         filter: Option<VulnerabilityFilter>,
     ) -> GraphQLResult<Vec<SecurityVulnerabilityType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let vulnerabilities = if let Some(f) = &filter {
             if let Some(severity) = &f.severity {
                 state.security_repo.get_vulnerabilities_by_severity(&repository_id, severity)?
@@ -149,6 +172,7 @@ impl QueryRoot {
         repository_id: String,
     ) -> GraphQLResult<Vec<SecurityRelationshipType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let relationships = state.security_repo.get_relationships(&repository_id)?;
         Ok(relationships.into_iter().map(SecurityRelationshipType::from).collect())
     }
@@ -160,6 +184,7 @@ impl QueryRoot {
         repository_id: String,
     ) -> GraphQLResult<GraphType> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let graph_builder = GraphBuilder::new(
             state.repo_repo.db.clone(),
             state.repo_repo.clone(),
@@ -199,6 +224,7 @@ impl QueryRoot {
         repository_id: String,
     ) -> GraphQLResult<GraphStatisticsType> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let graph_builder = GraphBuilder::new(
             state.repo_repo.db.clone(),
             state.repo_repo.clone(),
@@ -244,8 +270,8 @@ impl QueryRoot {
         name: String,
     ) -> GraphQLResult<Vec<DependencyType>> {
         let state = ctx.data::<ApiState>()?;
-        // GraphQL search - global search across all repositories
-        let deps = state.dep_repo.get_by_package_name(&name, None)?;
+        // GraphQL search - across the caller's workspace
+        let deps = state.dep_repo.get_by_package_name(&name, &workspace(ctx).0, None)?;
         Ok(deps.into_iter().map(DependencyType::from).collect())
     }
 
@@ -256,8 +282,8 @@ impl QueryRoot {
         provider: String,
     ) -> GraphQLResult<Vec<ServiceType>> {
         let state = ctx.data::<ApiState>()?;
-        // GraphQL search - global search across all repositories
-        let services = state.service_repo.get_by_provider(&provider, None)?;
+        // GraphQL search - across the caller's workspace
+        let services = state.service_repo.get_by_provider(&provider, &workspace(ctx).0, None)?;
         Ok(services.into_iter().map(ServiceType::from).collect())
     }
 
@@ -269,6 +295,7 @@ impl QueryRoot {
         filter: Option<PortFilter>,
     ) -> GraphQLResult<Vec<PortType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let mut ports = state.port_repo.get_by_repository(&repository_id)?;
         
         if let Some(f) = filter {
@@ -294,6 +321,7 @@ impl QueryRoot {
         filter: Option<EndpointFilter>,
     ) -> GraphQLResult<Vec<EndpointType>> {
         let state = ctx.data::<ApiState>()?;
+        ensure_in_workspace(ctx, state, &repository_id)?;
         let mut endpoints = state.endpoint_repo.get_by_repository(&repository_id)?;
         
         if let Some(f) = filter {
@@ -325,7 +353,7 @@ impl MutationRoot {
         branch: Option<String>,
     ) -> GraphQLResult<RepositoryType> {
         let state = ctx.data::<ApiState>()?;
-                let repo = state.repo_repo.create(&name, &url, branch.as_deref(), None, None)?;
+                let repo = state.repo_repo.create(&name, &url, branch.as_deref(), None, None, &workspace(ctx).0)?;
         Ok(RepositoryType::from(repo))
     }
 
@@ -1,11 +1,12 @@
-use async_graphql::{SimpleObject, InputObject};
-use crate::storage::{Repository, StoredDependency, StoredService, StoredPort, StoredEndpoint};
+use async_graphql::{SimpleObject, InputObject, ComplexObject, Context, Result as GraphQLResult};
+use crate::storage::{Repository, StoredDependency, StoredService, StoredPort, StoredEndpoint, StoredFramework};
 use crate::analysis::{CodeElement, CodeCall};
 use crate::security::{SecurityEntity, SecurityVulnerability, SecurityRelationship};
 
 // GraphQL Types
 
 #[derive(SimpleObject, Clone)]
+#[graphql(complex)]
 pub struct RepositoryType {
     pub id: String,
     pub name: String,
@@ -28,6 +29,39 @@ impl From<Repository> for RepositoryType {
     }
 }
 
+#[ComplexObject]
+impl RepositoryType {
+    /// Web frameworks, ORMs and runtimes detected during analysis
+    async fn frameworks(&self, ctx: &Context<'_>) -> GraphQLResult<Vec<FrameworkType>> {
+        let state = ctx.data::<crate::api::ApiState>()?;
+        let frameworks = state.framework_repo.get_by_repository(&self.id)?;
+        Ok(frameworks.into_iter().map(FrameworkType::from).collect())
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct FrameworkType {
+    pub name: String,
+    pub category: String,
+    pub language: String,
+    pub version: Option<String>,
+    pub file_path: String,
+    pub confidence: f64,
+}
+
+impl From<StoredFramework> for FrameworkType {
+    fn from(framework: StoredFramework) -> Self {
+        FrameworkType {
+            name: framework.name,
+            category: framework.category,
+            language: framework.language,
+            version: framework.version,
+            file_path: framework.file_path,
+            confidence: framework.confidence,
+        }
+    }
+}
+
 #[derive(SimpleObject, Clone)]
 pub struct DependencyType {
     pub id: String,
@@ -94,6 +128,10 @@ pub struct CodeElementType {
     pub visibility: Option<String>,
     pub parameters: Vec<String>,
     pub return_type: Option<String>,
+    pub parent_id: Option<String>,
+    pub qualified_name: String,
+    pub decorators: Vec<String>,
+    pub origin: String,
 }
 
 impl From<CodeElement> for CodeElementType {
@@ -110,6 +148,10 @@ impl From<CodeElement> for CodeElementType {
             visibility: elem.visibility,
             parameters: elem.parameters,
             return_type: elem.return_type,
+            parent_id: elem.parent_id,
+            qualified_name: elem.qualified_name,
+            decorators: elem.decorators,
+            origin: elem.origin.as_str().to_string(),
         }
     }
 }
@@ -303,6 +345,7 @@ pub struct EndpointType {
     pub framework: Option<String>,
     pub middleware: Vec<String>,
     pub parameters: Vec<String>,
+    pub protocol: String,
 }
 
 impl From<StoredEndpoint> for EndpointType {
@@ -317,6 +360,7 @@ impl From<StoredEndpoint> for EndpointType {
             framework: endpoint.framework,
             middleware: endpoint.middleware,
             parameters: endpoint.parameters,
+            protocol: endpoint.protocol,
         }
     }
 }
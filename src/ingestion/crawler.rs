@@ -1,4 +1,5 @@
 use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
 use git2::{Repository, FetchOptions, RemoteCallbacks, Cred};
 use std::path::{Path, PathBuf};
 use std::fs;
@@ -20,6 +21,35 @@ pub enum AuthType {
     UsernamePassword(String, String), // Username and password
 }
 
+impl RepositoryCredentials {
+    /// Credentials from a repository's stored `auth_type` and its (resolved) `auth_value`
+    pub fn from_stored(auth_type: &str, auth_value: &str) -> Self {
+        let auth_type = match auth_type {
+            "ssh_key" => {
+                log::info!("Using SSH key authentication");
+                AuthType::SshKey(auth_value.to_string())
+            }
+            "username_password" => {
+                log::info!("Using username/password authentication");
+                // Decode base64(username:password)
+                let decoded = general_purpose::STANDARD.decode(auth_value).unwrap_or_default();
+                let creds_str = String::from_utf8(decoded).unwrap_or_default();
+                let (username, password) = creds_str.split_once(':').unwrap_or((&creds_str, ""));
+                AuthType::UsernamePassword(username.to_string(), password.to_string())
+            }
+            "token" => {
+                log::info!("Using token authentication");
+                AuthType::Token(auth_value.to_string())
+            }
+            _ => {
+                log::info!("Using default token authentication");
+                AuthType::Token(auth_value.to_string())
+            }
+        };
+        RepositoryCredentials { auth_type }
+    }
+}
+
 impl RepositoryCrawler {
     pub fn new(config: &StorageConfig) -> Result<Self> {
         let cache_path = PathBuf::from(&config.repository_cache_path);
@@ -97,7 +127,14 @@ impl RepositoryCrawler {
         let repo_path = self.cache_path.join(&repo_name);
         let branch = branch.unwrap_or("main");
 
-        if repo_path.exists() {
+        if crate::network::is_offline() {
+            if !repo_path.exists() {
+                return Err(anyhow::anyhow!(
+                    "Cannot clone {} in offline mode; analyze a local checkout of it instead", url
+                ));
+            }
+            log::warn!("⚠ Offline mode: analyzing the cached clone of {} without fetching", url);
+        } else if repo_path.exists() {
             // Update existing repository
             self.update_repository(&repo_path, branch, credentials)?;
         } else {
@@ -107,6 +144,20 @@ impl RepositoryCrawler {
 
         Ok(repo_path)
     }
+
+    /// Clone `branch` into a directory of its own under the cache, named `name`, instead of the
+    /// checkout shared by everything registered with `url`, which stays on its branch. An earlier
+    /// clone with the same name is replaced.
+    pub fn clone_separately(&self, url: &str, name: &str, branch: &str, credentials: Option<&RepositoryCredentials>) -> Result<PathBuf> {
+        crate::network::ensure_online("Cloning a branch")?;
+        let path = self.cache_path.join(".checkouts").join(name);
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir_all(self.cache_path.join(".checkouts"))?;
+        self.clone_repository(url, &path, branch, credentials)?;
+        Ok(path)
+    }
     
 
     /// Clone a repository
@@ -115,6 +166,7 @@ impl RepositoryCrawler {
         
         let mut fetch_options = FetchOptions::new();
         fetch_options.download_tags(git2::AutotagOption::All);
+        fetch_options.proxy_options(crate::network::git_proxy_options(url));
         
         // Set up callbacks for authentication
         let mut callbacks = RemoteCallbacks::new();
@@ -149,6 +201,7 @@ impl RepositoryCrawler {
                 // Recreate fetch_options for the fallback clone
                 let mut fallback_fetch_options = FetchOptions::new();
                 fallback_fetch_options.download_tags(git2::AutotagOption::All);
+                fallback_fetch_options.proxy_options(crate::network::git_proxy_options(url));
                 let mut fallback_callbacks = RemoteCallbacks::new();
                 let fallback_creds = credentials.cloned();
                 fallback_callbacks.credentials(move |url_str, username_from_url, allowed_types| {
@@ -167,6 +220,7 @@ impl RepositoryCrawler {
                 
                 // Fetch all branches to ensure we have remote refs
                 let mut fetch_all_options = FetchOptions::new();
+                fetch_all_options.proxy_options(crate::network::git_proxy_options(url));
                 let mut fetch_all_callbacks = RemoteCallbacks::new();
                 let fetch_all_creds = credentials.cloned();
                 fetch_all_callbacks.credentials(move |url_str, username_from_url, allowed_types| {
@@ -236,6 +290,7 @@ impl RepositoryCrawler {
         
         let mut fetch_options = FetchOptions::new();
         fetch_options.download_tags(git2::AutotagOption::None); // Don't download tags to speed up
+        fetch_options.proxy_options(crate::network::git_proxy_options(remote_url));
         let mut callbacks = RemoteCallbacks::new();
         let creds = credentials.cloned();
         
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What identifies a repository's remote and history, for spotting forks and mirrors of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryFingerprint {
+    pub repository_id: String,
+    pub normalized_url: String,
+    pub branch: String,
+    /// Commit the branch pointed at when the checkout was last seen
+    pub head_commit: Option<String>,
+    /// Parentless commits reachable from the head; shared roots mean shared history
+    pub root_commits: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RepositoryRelation {
+    Mirror, // Same remote and branch, or the same head commit
+    Fork,   // Shared history that has since diverged
+}
+
+impl RepositoryRelation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryRelation::Mirror => "mirror",
+            RepositoryRelation::Fork => "fork",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "mirror" => Some(RepositoryRelation::Mirror),
+            "fork" => Some(RepositoryRelation::Fork),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryLink {
+    pub related_repository_id: String,
+    pub relation: RepositoryRelation,
+    pub evidence: String,
+    /// Both sides were last seen at the same commit, so one analysis stands for both
+    pub same_head: bool,
+}
+
+/// `host/owner/repo` for any of the https, ssh and scp-like spellings of a remote; local paths are
+/// canonicalized so two spellings of one checkout compare equal
+pub fn normalize_remote_url(url: &str) -> String {
+    let url = url.trim();
+    if crate::ingestion::RepositoryCrawler::is_local_path(url) {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        return std::fs::canonicalize(path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.trim_end_matches('/').to_string());
+    }
+
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let without_user = without_scheme.rsplit_once('@').map(|(_, rest)| rest).unwrap_or(without_scheme);
+    // scp-like `host:owner/repo`; a port (`host:22/owner/repo`) is dropped the same way
+    let (host, path) = without_user.split_once([':', '/']).unwrap_or((without_user, ""));
+    let path = match path.split_once('/') {
+        Some((port, rest)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => path,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    format!("{}/{}", host.to_lowercase(), path)
+}
+
+impl RepositoryFingerprint {
+    pub fn from_url(repository_id: &str, url: &str, branch: &str) -> Self {
+        RepositoryFingerprint {
+            repository_id: repository_id.to_string(),
+            normalized_url: normalize_remote_url(url),
+            branch: branch.to_string(),
+            head_commit: None,
+            root_commits: Vec::new(),
+        }
+    }
+
+    /// Adds the head and root commits of a checkout; left as is when it is not a git repository
+    pub fn with_checkout(mut self, path: &Path) -> Self {
+        let Ok(repo) = git2::Repository::open(path) else { return self };
+        let Some(head) = repo.head().ok().and_then(|h| h.peel_to_commit().ok()) else { return self };
+        self.head_commit = Some(head.id().to_string());
+
+        let roots = repo.revwalk().and_then(|mut walk| {
+            walk.push(head.id())?;
+            Ok(walk.filter_map(|oid| oid.ok())
+                .filter(|oid| repo.find_commit(*oid).map(|c| c.parent_count() == 0).unwrap_or(false))
+                .map(|oid| oid.to_string())
+                .collect::<Vec<_>>())
+        });
+        if let Ok(mut roots) = roots {
+            roots.sort();
+            self.root_commits = roots;
+        }
+        self
+    }
+
+    /// How `other` relates to this repository, if it is a fork or mirror of it
+    pub fn relation_to(&self, other: &RepositoryFingerprint) -> Option<RepositoryLink> {
+        if self.repository_id == other.repository_id {
+            return None;
+        }
+        let same_head = self.head_commit.is_some() && self.head_commit == other.head_commit;
+        let link = |relation, evidence: String| Some(RepositoryLink {
+            related_repository_id: other.repository_id.clone(),
+            relation,
+            evidence,
+            same_head,
+        });
+
+        if self.normalized_url == other.normalized_url {
+            // Another branch of the same remote is neither a fork nor a mirror
+            return if self.branch == other.branch {
+                link(RepositoryRelation::Mirror, format!("Same remote {} and branch {}", self.normalized_url, self.branch))
+            } else {
+                None
+            };
+        }
+        if same_head {
+            return link(RepositoryRelation::Mirror, format!("Same head commit {}", short(self.head_commit.as_deref().unwrap_or_default())));
+        }
+        let shared_root = self.root_commits.iter().find(|root| other.root_commits.contains(root))?;
+        link(RepositoryRelation::Fork, format!("Shared root commit {} with diverged heads", short(shared_root)))
+    }
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_remotes_and_classifies_forks_and_mirrors() {
+        assert_eq!(normalize_remote_url("https://GitHub.com/acme/shop.git"), "github.com/acme/shop");
+        assert_eq!(normalize_remote_url("git@github.com:acme/shop.git"), "github.com/acme/shop");
+        assert_eq!(normalize_remote_url("ssh://git@github.com:22/acme/shop/"), "github.com/acme/shop");
+
+        let fingerprint = |id: &str, url: &str, branch: &str, head: Option<&str>, roots: &[&str]| RepositoryFingerprint {
+            head_commit: head.map(String::from),
+            root_commits: roots.iter().map(|r| r.to_string()).collect(),
+            ..RepositoryFingerprint::from_url(id, url, branch)
+        };
+        let upstream = fingerprint("a", "https://github.com/acme/shop", "main", Some("c3"), &["c1"]);
+
+        let same_remote = fingerprint("b", "git@github.com:acme/shop.git", "main", None, &[]);
+        assert_eq!(upstream.relation_to(&same_remote).unwrap().relation, RepositoryRelation::Mirror);
+        let other_branch = fingerprint("c", "git@github.com:acme/shop.git", "develop", Some("c4"), &["c1"]);
+        assert!(upstream.relation_to(&other_branch).is_none());
+
+        let mirror = fingerprint("d", "https://gitlab.internal/mirrors/shop", "main", Some("c3"), &["c1"]);
+        let link = upstream.relation_to(&mirror).unwrap();
+        assert_eq!(link.relation, RepositoryRelation::Mirror);
+        assert!(link.same_head);
+
+        let fork = fingerprint("e", "https://github.com/someone/shop", "main", Some("c9"), &["c1"]);
+        let link = upstream.relation_to(&fork).unwrap();
+        assert_eq!(link.relation, RepositoryRelation::Fork);
+        assert!(!link.same_head);
+
+        let unrelated = fingerprint("f", "https://github.com/acme/billing", "main", Some("d2"), &["d1"]);
+        assert!(upstream.relation_to(&unrelated).is_none());
+    }
+}
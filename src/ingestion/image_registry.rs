@@ -0,0 +1,296 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{ACCEPT, WWW_AUTHENTICATE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+/// Registry, repository and tag or digest of an image reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReference {
+    pub registry: String,
+    pub repository: String,
+    pub reference: String, // Tag, or `sha256:...` when pinned by digest
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageVulnerability {
+    pub id: String,
+    pub severity: String,
+    pub package: Option<String>,
+    pub installed_version: Option<String>,
+    pub fixed_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    pub image: String,
+    pub registry: String,
+    pub digest: Option<String>,
+    pub platform: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub layers: Vec<String>,
+    pub base_image: Option<String>, // From the `org.opencontainers.image.base.name` label
+    pub created: Option<String>,
+    pub vulnerabilities: Vec<ImageVulnerability>,
+    pub error: Option<String>,
+}
+
+/// Docker Hub shorthand (`nginx`, `bitnami/redis`) expands to `registry-1.docker.io/library/...`
+pub fn parse_reference(image: &str) -> Option<ImageReference> {
+    let image = image.trim();
+    if image.is_empty() || image.contains(['$', '{', ' ']) {
+        return None;
+    }
+    let (name, reference) = match image.split_once('@') {
+        Some((name, digest)) => (name, digest.to_string()),
+        None => match image.rsplit_once(':') {
+            // A colon after the last slash is a tag; before it, a registry port
+            Some((name, tag)) if !tag.contains('/') => (name, tag.to_string()),
+            _ => (image, "latest".to_string()),
+        },
+    };
+    // A digest reference may still carry a tag (`nginx:1.25@sha256:...`)
+    let name = match name.rsplit_once(':') {
+        Some((n, tag)) if !tag.contains('/') => n,
+        _ => name,
+    };
+
+    let (registry, repository) = match name.split_once('/') {
+        Some((first, rest)) if first.contains(['.', ':']) || first == "localhost" => (first.to_string(), rest.to_string()),
+        _ => ("docker.io".to_string(), name.to_string()),
+    };
+    let registry = match registry.as_str() {
+        "docker.io" | "index.docker.io" => "registry-1.docker.io".to_string(),
+        _ => registry,
+    };
+    let repository = if registry == "registry-1.docker.io" && !repository.contains('/') {
+        format!("library/{}", repository)
+    } else {
+        repository
+    };
+    Some(ImageReference { registry, repository, reference })
+}
+
+/// `realm`, `service` and `scope` of a `Bearer` challenge
+fn parse_challenge(header: &str) -> Option<(String, Vec<(String, String)>)> {
+    let params = header.strip_prefix("Bearer ").or_else(|| header.strip_prefix("bearer "))?;
+    let mut realm = None;
+    let mut query = Vec::new();
+    for part in params.split(',') {
+        let Some((key, value)) = part.trim().split_once('=') else { continue };
+        let value = value.trim_matches('"').to_string();
+        match key {
+            "realm" => realm = Some(value),
+            "service" | "scope" => query.push((key.to_string(), value)),
+            _ => {}
+        }
+    }
+    Some((realm?, query))
+}
+
+/// Manifest of the requested platform in an image index or manifest list
+fn platform_digest(index: &Value, platform: &str) -> Option<String> {
+    let (os, arch) = platform.split_once('/').unwrap_or(("linux", platform));
+    let (arch, variant) = match arch.split_once('/') {
+        Some((arch, variant)) => (arch, Some(variant)),
+        None => (arch, None),
+    };
+    index.get("manifests")?.as_array()?.iter()
+        .find(|m| {
+            let p = &m["platform"];
+            p["os"] == os && p["architecture"] == arch
+                && variant.map(|v| p["variant"] == v).unwrap_or(true)
+        })
+        .and_then(|m| m["digest"].as_str())
+        .map(|d| d.to_string())
+}
+
+/// Known CVEs from a scanner response: either `{"vulnerabilities": [...]}` or a Trivy report
+pub fn parse_scan(body: &Value) -> Vec<ImageVulnerability> {
+    let text = |v: &Value, keys: &[&str]| keys.iter()
+        .find_map(|k| v.get(*k).and_then(|s| s.as_str()))
+        .map(|s| s.to_string());
+    let entries: Vec<&Value> = match body.get("vulnerabilities").and_then(|v| v.as_array()) {
+        Some(list) => list.iter().collect(),
+        None => body.get("Results").and_then(|r| r.as_array()).into_iter().flatten()
+            .filter_map(|r| r.get("Vulnerabilities").and_then(|v| v.as_array()))
+            .flatten()
+            .collect(),
+    };
+    entries.into_iter()
+        .filter_map(|v| Some(ImageVulnerability {
+            id: text(v, &["id", "VulnerabilityID"])?,
+            severity: text(v, &["severity", "Severity"]).unwrap_or_else(|| "unknown".to_string()).to_lowercase(),
+            package: text(v, &["package", "PkgName"]),
+            installed_version: text(v, &["installed_version", "InstalledVersion"]),
+            fixed_version: text(v, &["fixed_version", "FixedVersion"]),
+        }))
+        .collect()
+}
+
+pub struct RegistryClient {
+    client: reqwest::Client,
+    platform: String,
+    scanner_url: Option<String>,
+    scanner_token: Option<String>,
+}
+
+impl RegistryClient {
+    pub fn new(platform: &str, scanner_url: Option<String>, scanner_token: Option<String>) -> Result<Self> {
+        Ok(RegistryClient {
+            client: crate::network::http_client(Duration::from_secs(30))?,
+            platform: platform.to_string(),
+            scanner_url,
+            scanner_token,
+        })
+    }
+
+    /// Never fails: an image that cannot be looked up is returned with `error` set
+    pub async fn lookup(&self, image: &str) -> ImageMetadata {
+        let mut metadata = ImageMetadata {
+            image: image.to_string(),
+            ..Default::default()
+        };
+        let Some(reference) = parse_reference(image) else {
+            metadata.error = Some("unresolved image reference".to_string());
+            return metadata;
+        };
+        metadata.registry = reference.registry.clone();
+        if let Err(e) = self.fetch_manifest(&reference, &mut metadata).await {
+            metadata.error = Some(e.to_string());
+        }
+        if let Some(url) = &self.scanner_url {
+            match self.scan(url, image).await {
+                Ok(vulnerabilities) => metadata.vulnerabilities = vulnerabilities,
+                Err(e) => log::debug!("Scanner lookup for {} failed: {}", image, e),
+            }
+        }
+        metadata
+    }
+
+    async fn fetch_manifest(&self, reference: &ImageReference, metadata: &mut ImageMetadata) -> Result<()> {
+        let mut token = None;
+        let (manifest, digest) = self.get_manifest(reference, &reference.reference, &mut token).await?;
+        metadata.digest = digest;
+
+        let manifest = if manifest.get("manifests").is_some() {
+            let platform_digest = platform_digest(&manifest, &self.platform)
+                .ok_or_else(|| anyhow!("no {} manifest", self.platform))?;
+            let (manifest, _) = self.get_manifest(reference, &platform_digest, &mut token).await?;
+            metadata.platform = Some(self.platform.clone());
+            manifest
+        } else {
+            manifest
+        };
+
+        let layers = manifest["layers"].as_array().cloned().unwrap_or_default();
+        metadata.size_bytes = Some(layers.iter().filter_map(|l| l["size"].as_u64()).sum());
+        metadata.layers = layers.iter().filter_map(|l| l["digest"].as_str()).map(|d| d.to_string()).collect();
+
+        if let Some(config_digest) = manifest["config"]["digest"].as_str() {
+            let url = format!("https://{}/v2/{}/blobs/{}", reference.registry, reference.repository, config_digest);
+            let config: Value = self.authorized_get(&url, "*/*", &mut token).await?.json().await?;
+            metadata.created = config["created"].as_str().map(|c| c.to_string());
+            metadata.base_image = config["config"]["Labels"]["org.opencontainers.image.base.name"].as_str().map(|b| b.to_string());
+            if metadata.platform.is_none() {
+                if let (Some(os), Some(arch)) = (config["os"].as_str(), config["architecture"].as_str()) {
+                    metadata.platform = Some(format!("{}/{}", os, arch));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_manifest(&self, reference: &ImageReference, tag_or_digest: &str, token: &mut Option<String>) -> Result<(Value, Option<String>)> {
+        let url = format!("https://{}/v2/{}/manifests/{}", reference.registry, reference.repository, tag_or_digest);
+        let response = self.authorized_get(&url, MANIFEST_TYPES, token).await?;
+        let digest = response.headers().get("Docker-Content-Digest")
+            .and_then(|d| d.to_str().ok())
+            .map(|d| d.to_string());
+        Ok((response.json().await?, digest))
+    }
+
+    /// Retries once with an anonymous token when the registry answers with a `Bearer` challenge
+    async fn authorized_get(&self, url: &str, accept: &str, token: &mut Option<String>) -> Result<reqwest::Response> {
+        let send = |token: &Option<String>| {
+            let mut request = self.client.get(url).header(ACCEPT, accept);
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            request.send()
+        };
+        let mut response = send(token).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            let challenge = response.headers().get(WWW_AUTHENTICATE)
+                .and_then(|h| h.to_str().ok())
+                .and_then(parse_challenge)
+                .ok_or_else(|| anyhow!("registry requires credentials"))?;
+            let (realm, query) = challenge;
+            let body: Value = self.client.get(&realm).query(&query).send().await?.error_for_status()?.json().await?;
+            *token = body["token"].as_str().or_else(|| body["access_token"].as_str()).map(|t| t.to_string());
+            response = send(token).await?;
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("registry returned status: {}", response.status()));
+        }
+        Ok(response)
+    }
+
+    async fn scan(&self, url: &str, image: &str) -> Result<Vec<ImageVulnerability>> {
+        let mut request = self.client.get(url).query(&[("image", image)]);
+        if let Some(token) = &self.scanner_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("scanner returned status: {}", response.status()));
+        }
+        Ok(parse_scan(&response.json().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_references_and_scanner_reports() {
+        let hub = parse_reference("nginx:1.25").unwrap();
+        assert_eq!(hub.registry, "registry-1.docker.io");
+        assert_eq!(hub.repository, "library/nginx");
+        assert_eq!(hub.reference, "1.25");
+
+        let private = parse_reference("registry.acme.io:5000/team/api@sha256:abc").unwrap();
+        assert_eq!(private.registry, "registry.acme.io:5000");
+        assert_eq!(private.repository, "team/api");
+        assert_eq!(private.reference, "sha256:abc");
+
+        assert_eq!(parse_reference("bitnami/redis").unwrap().reference, "latest");
+        assert!(parse_reference("${IMAGE}:latest").is_none());
+
+        let (realm, query) = parse_challenge(r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/nginx:pull""#).unwrap();
+        assert_eq!(realm, "https://auth.docker.io/token");
+        assert_eq!(query.len(), 2);
+
+        let index = serde_json::json!({"manifests": [
+            {"digest": "sha256:arm", "platform": {"os": "linux", "architecture": "arm64"}},
+            {"digest": "sha256:amd", "platform": {"os": "linux", "architecture": "amd64"}}
+        ]});
+        assert_eq!(platform_digest(&index, "linux/amd64").as_deref(), Some("sha256:amd"));
+
+        let trivy = serde_json::json!({"Results": [{"Vulnerabilities": [
+            {"VulnerabilityID": "CVE-2024-1", "Severity": "HIGH", "PkgName": "openssl", "FixedVersion": "3.0.14"}
+        ]}]});
+        let found = parse_scan(&trivy);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].severity, "high");
+        assert_eq!(found[0].package.as_deref(), Some("openssl"));
+        assert_eq!(parse_scan(&serde_json::json!({"vulnerabilities": [{"id": "CVE-2024-2"}]}))[0].severity, "unknown");
+    }
+}
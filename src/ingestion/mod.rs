@@ -1,6 +1,12 @@
 pub mod crawler;
+pub mod fingerprint;
+pub mod image_registry;
 pub mod indexer;
+pub mod releases;
+pub mod secrets;
 
-pub use crawler::{RepositoryCrawler, RepositoryCredentials, AuthType};
+pub use crawler::{RepositoryCrawler, RepositoryCredentials};
+pub use fingerprint::{RepositoryFingerprint, RepositoryLink, RepositoryRelation};
 pub use indexer::FileType;
+pub use secrets::SecretRef;
 
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::time::Duration;
+use crate::analysis::{ChangelogParser, Release, ReleaseSource};
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    name: Option<String>,
+    published_at: Option<String>,
+    html_url: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// `owner/repo` for github.com HTTPS and SSH remotes
+pub fn github_slug(url: &str) -> Option<String> {
+    let rest = url.strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("http://github.com/"))
+        .or_else(|| url.strip_prefix("git@github.com:"))?;
+    let mut parts = rest.trim_end_matches('/').trim_end_matches(".git").splitn(3, '/');
+    match (parts.next(), parts.next()) {
+        (Some(owner), Some(repo)) if !owner.is_empty() && !repo.is_empty() => Some(format!("{}/{}", owner, repo)),
+        _ => None,
+    }
+}
+
+/// Published releases of a GitHub repository (most recent 100), with notes parsed into sections
+pub async fn fetch_github_releases(slug: &str, token: Option<&str>) -> Result<Vec<Release>> {
+    let client = crate::network::http_client(Duration::from_secs(15))?;
+
+    let mut request = client.get(format!("https://api.github.com/repos/{}/releases?per_page=100", slug));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("GitHub API returned status: {}", response.status()));
+    }
+
+    let parser = ChangelogParser::new();
+    let releases: Vec<GitHubRelease> = response.json().await?;
+    Ok(releases.into_iter()
+        .filter(|r| !r.draft)
+        .map(|r| Release {
+            version: r.tag_name.trim_start_matches(['v', 'V']).to_string(),
+            date: r.published_at.as_deref().and_then(|d| d.get(..10)).map(|d| d.to_string()),
+            source: ReleaseSource::GitHub,
+            title: r.name.filter(|n| !n.trim().is_empty()),
+            url: r.html_url,
+            sections: parser.parse_notes(r.body.as_deref().unwrap_or("")),
+        })
+        .collect())
+}
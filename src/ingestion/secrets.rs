@@ -0,0 +1,224 @@
+use anyhow::{anyhow, Context, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::config::SecretsConfig;
+
+/// Where a repository's `auth_value` comes from. Values with one of these prefixes are looked up
+/// when the repository is cloned, so only the reference is stored:
+///
+/// - `env:GITHUB_TOKEN` - an environment variable of the server
+/// - `file:/run/secrets/git-token` - a file's contents, trailing newline trimmed
+/// - `vault:secret/data/git#token` - a field of a HashiCorp Vault secret (KV v1 or v2), using
+///   `VAULT_ADDR` and `VAULT_TOKEN`
+/// - `aws-sm:prod/git#token` - an AWS Secrets Manager secret, optionally one key of its JSON,
+///   fetched with the `aws` CLI and its usual credentials
+///
+/// Anything else is the secret itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    Literal(String),
+    Env(String),
+    File(PathBuf),
+    Vault { path: String, field: String },
+    AwsSecretsManager { secret_id: String, key: Option<String> },
+}
+
+impl SecretRef {
+    pub fn parse(value: &str) -> SecretRef {
+        if let Some(name) = value.strip_prefix("env:") {
+            SecretRef::Env(name.to_string())
+        } else if let Some(path) = value.strip_prefix("file:") {
+            SecretRef::File(PathBuf::from(path))
+        } else if let Some(reference) = value.strip_prefix("vault:") {
+            let (path, field) = reference.split_once('#').unwrap_or((reference, "value"));
+            SecretRef::Vault { path: path.trim_matches('/').to_string(), field: field.to_string() }
+        } else if let Some(reference) = value.strip_prefix("aws-sm:") {
+            let (secret_id, key) = match reference.split_once('#') {
+                Some((id, key)) => (id, Some(key.to_string())),
+                None => (reference, None),
+            };
+            SecretRef::AwsSecretsManager { secret_id: secret_id.to_string(), key }
+        } else {
+            SecretRef::Literal(value.to_string())
+        }
+    }
+
+    /// Checked when a repository is created, before anything is looked up
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            SecretRef::Env(name) if name.is_empty() => Err(anyhow!("env: needs a variable name")),
+            SecretRef::File(path) if path.as_os_str().is_empty() => Err(anyhow!("file: needs a path")),
+            SecretRef::Vault { path, field } if path.is_empty() || field.is_empty() => {
+                Err(anyhow!("vault: needs a secret path, e.g. vault:secret/data/git#token"))
+            }
+            SecretRef::AwsSecretsManager { secret_id, .. } if secret_id.is_empty() => {
+                Err(anyhow!("aws-sm: needs a secret id, e.g. aws-sm:prod/git#token"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether an API caller in `workspace` may store `value`. Literal secrets always can; a
+    /// reference must start with one of the operator's prefixes in [`SecretsConfig`], since the
+    /// server would otherwise hand its own or another team's secrets to whoever asks.
+    pub fn check_allowed(value: &str, config: &SecretsConfig, workspace: &str) -> Result<()> {
+        let reference = SecretRef::parse(value);
+        if let SecretRef::Literal(_) = reference {
+            return Ok(());
+        }
+        reference.validate()?;
+        // `file:/run/secrets/../../etc/shadow` must not escape an allowed directory
+        if value.split(['/', '#', ':']).any(|part| part == "..") {
+            return Err(anyhow!("secret references may not contain '..'"));
+        }
+        let mut allowed = config.allowed_references.iter().chain(config.workspaces.get(workspace).into_iter().flatten());
+        if allowed.any(|prefix| !prefix.is_empty() && value.starts_with(prefix.as_str())) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "secret reference '{}' is not allowed in this workspace; the operator can allow it in secrets.allowed_references (SECRET_REFERENCES_ALLOWED) or secrets.workspaces",
+                value
+            ))
+        }
+    }
+
+    /// Look the secret up; blocks on network lookups, so call it off the async workers
+    pub fn resolve(&self) -> Result<String> {
+        match self {
+            SecretRef::Literal(value) => Ok(value.clone()),
+            SecretRef::Env(name) => std::env::var(name).with_context(|| format!("environment variable {} is not set", name)),
+            SecretRef::File(path) => std::fs::read_to_string(path)
+                .map(|content| content.trim_end_matches(['\r', '\n']).to_string())
+                .with_context(|| format!("reading secret file {}", path.display())),
+            SecretRef::Vault { path, field } => resolve_vault(path, field),
+            SecretRef::AwsSecretsManager { secret_id, key } => resolve_aws_secrets_manager(secret_id, key.as_deref()),
+        }
+    }
+}
+
+/// Resolve a stored `auth_value`, literal or reference
+pub fn resolve(value: &str) -> Result<String> {
+    SecretRef::parse(value).resolve()
+}
+
+/// Resolve a value an API caller stored, checking it against the allow-list again in case it
+/// was stored before the list was narrowed
+pub fn resolve_allowed(value: &str, config: &SecretsConfig, workspace: &str) -> Result<String> {
+    SecretRef::check_allowed(value, config, workspace)?;
+    resolve(value)
+}
+
+fn resolve_vault(path: &str, field: &str) -> Result<String> {
+    crate::network::ensure_online("vault: secret references")?;
+    let address = std::env::var("VAULT_ADDR").context("vault: references need VAULT_ADDR")?;
+    let token = match std::env::var("VAULT_TOKEN") {
+        Ok(token) => token,
+        Err(_) => vault_token_file().context("vault: references need VAULT_TOKEN or ~/.vault-token")?,
+    };
+    let url = format!("{}/v1/{}", address.trim_end_matches('/'), path);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let body: serde_json::Value = runtime.block_on(async {
+        let client = crate::network::http_client(Duration::from_secs(15))?;
+        let response = client.get(&url).header("X-Vault-Token", token).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("Vault returned {} for {}", response.status(), path));
+        }
+        Ok::<_, anyhow::Error>(response.json().await?)
+    })?;
+
+    // KV v2 nests the secret under data.data, KV v1 under data
+    let data = &body["data"];
+    let value = data["data"].get(field).or_else(|| data.get(field));
+    match value {
+        Some(serde_json::Value::String(value)) => Ok(value.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => Err(anyhow!("Vault secret {} has no field '{}'", path, field)),
+    }
+}
+
+fn vault_token_file() -> Option<String> {
+    let home = std::env::var_os("HOME")?;
+    std::fs::read_to_string(PathBuf::from(home).join(".vault-token")).ok().map(|t| t.trim().to_string())
+}
+
+fn resolve_aws_secrets_manager(secret_id: &str, key: Option<&str>) -> Result<String> {
+    crate::network::ensure_online("aws-sm: secret references")?;
+    let output = std::process::Command::new("aws")
+        .args(["secretsmanager", "get-secret-value", "--secret-id", secret_id, "--query", "SecretString", "--output", "text"])
+        .output()
+        .context("aws-sm: references need the aws CLI")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "aws secretsmanager get-secret-value failed for {}: {}",
+            secret_id,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let secret = String::from_utf8(output.stdout)?.trim_end().to_string();
+    match key {
+        None => Ok(secret),
+        Some(key) => {
+            let json: serde_json::Value = serde_json::from_str(&secret)
+                .with_context(|| format!("secret {} is not JSON, so '#{}' cannot be selected", secret_id, key))?;
+            match json.get(key) {
+                Some(serde_json::Value::String(value)) => Ok(value.clone()),
+                Some(other) => Ok(other.to_string()),
+                None => Err(anyhow!("secret {} has no key '{}'", secret_id, key)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_resolve_secret_references() {
+        assert_eq!(SecretRef::parse("ghp_abc"), SecretRef::Literal("ghp_abc".to_string()));
+        assert_eq!(
+            SecretRef::parse("vault:/secret/data/git#token"),
+            SecretRef::Vault { path: "secret/data/git".to_string(), field: "token".to_string() }
+        );
+        assert_eq!(
+            SecretRef::parse("aws-sm:prod/git"),
+            SecretRef::AwsSecretsManager { secret_id: "prod/git".to_string(), key: None }
+        );
+        assert!(SecretRef::parse("vault:#token").validate().is_err());
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("token");
+        std::fs::write(&path, "s3cret\n").unwrap();
+        assert_eq!(resolve(&format!("file:{}", path.display())).unwrap(), "s3cret");
+        assert_eq!(resolve("ghp_abc").unwrap(), "ghp_abc");
+        assert!(resolve("env:WAVELENGTH_TEST_UNSET_SECRET").is_err());
+    }
+
+    #[test]
+    fn test_references_outside_the_allow_list_are_refused() {
+        let mut config = SecretsConfig {
+            allowed_references: vec!["file:/run/secrets/git/".to_string()],
+            ..Default::default()
+        };
+        config.workspaces.insert("team-a".to_string(), vec!["vault:secret/data/team-a/".to_string()]);
+
+        assert!(SecretRef::check_allowed("ghp_literal", &config, "default").is_ok());
+        assert!(SecretRef::check_allowed("file:/run/secrets/git/token", &config, "default").is_ok());
+        assert!(SecretRef::check_allowed("vault:secret/data/team-a/git#token", &config, "team-a").is_ok());
+
+        for refused in [
+            "env:GITHUB_TOKEN",
+            "file:/etc/shadow",
+            "file:/run/secrets/git/../../../etc/shadow",
+            "aws-sm:prod/db#password",
+        ] {
+            let error = SecretRef::check_allowed(refused, &config, "team-a").unwrap_err().to_string();
+            assert!(error.contains("not allowed") || error.contains(".."), "{}: {}", refused, error);
+        }
+        // Another team's prefix is not shared
+        assert!(SecretRef::check_allowed("vault:secret/data/team-a/git#token", &config, "team-b").is_err());
+        assert!(resolve_allowed("env:PATH", &config, "default").is_err());
+        assert!(SecretRef::check_allowed("env:PATH", &SecretsConfig::default(), "default").is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Result};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+use crate::config::EmailConfig;
+use crate::report::digest::EmailBody;
+
+/// Connection security for `SMTP_SECURITY`
+pub const SMTP_SECURITY: &[&str] = &["starttls", "tls", "none"];
+
+/// Sends digests and alerts over SMTP to every configured recipient
+pub struct EmailChannel {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailChannel {
+    /// `password` is the resolved secret, not the configured reference
+    pub fn new(config: &EmailConfig, password: Option<&str>) -> Result<Self> {
+        let (host, from) = match (&config.smtp_host, &config.from) {
+            (Some(host), Some(from)) if !config.to.is_empty() => (host, from),
+            _ => return Err(anyhow!("Email is not configured; set SMTP_HOST, EMAIL_FROM and EMAIL_TO")),
+        };
+        crate::network::ensure_online("Email")?;
+        let mut builder = match config.security.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(host)?,
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host),
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?,
+        };
+        builder = builder.port(config.smtp_port).timeout(Some(Duration::from_secs(30)));
+        if let (Some(username), Some(password)) = (&config.username, password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.to_string()));
+        }
+        Ok(EmailChannel {
+            transport: builder.build(),
+            from: from.parse().map_err(|e| anyhow!("EMAIL_FROM '{}': {}", from, e))?,
+            to: config.to.iter()
+                .map(|to| to.parse().map_err(|e| anyhow!("EMAIL_TO '{}': {}", to, e)))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub async fn send(&self, body: &EmailBody) -> Result<()> {
+        let mut message = Message::builder().from(self.from.clone()).subject(&body.subject);
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = message.multipart(MultiPart::alternative_plain_html(body.text.clone(), body.html.clone()))?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
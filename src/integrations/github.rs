@@ -0,0 +1,259 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use crate::analysis::{build_timeline, ArchitectureSnapshot, SnapshotChange};
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+use crate::storage::StoredArchitectureFinding;
+
+/// Check run name, and the marker that identifies our PR comment so it is updated in place
+const CHECK_NAME: &str = "Wavelength architecture";
+const COMMENT_MARKER: &str = "<!-- wavelength-arch-decoder -->";
+
+/// GitHub accepts at most 50 annotations per request
+const MAX_ANNOTATIONS: usize = 50;
+
+/// What a pull request changes compared with the last analysis of its base branch
+#[derive(Debug, Clone)]
+pub struct AnalysisDiff {
+    pub change: Option<SnapshotChange>,
+    pub new_vulnerabilities: Vec<SecurityVulnerability>,
+    pub new_findings: Vec<StoredArchitectureFinding>,
+}
+
+impl AnalysisDiff {
+    /// Findings are matched on what they say and where, not on line numbers, which shift with unrelated edits
+    pub fn between(
+        before: (Option<&ArchitectureSnapshot>, &[SecurityVulnerability], &[StoredArchitectureFinding]),
+        after: (Option<&ArchitectureSnapshot>, Vec<SecurityVulnerability>, Vec<StoredArchitectureFinding>),
+    ) -> AnalysisDiff {
+        let (before_snapshot, before_vulnerabilities, before_findings) = before;
+        let (after_snapshot, after_vulnerabilities, after_findings) = after;
+
+        let change = match (before_snapshot, after_snapshot) {
+            (Some(before), Some(after)) => build_timeline(Vec::new(), Vec::new(), &[before.clone(), after.clone()]).changes.pop(),
+            _ => None,
+        };
+        let new_vulnerabilities = after_vulnerabilities.into_iter()
+            .filter(|v| !before_vulnerabilities.iter().any(|b| {
+                b.vulnerability_type == v.vulnerability_type && b.file_path == v.file_path && b.description == v.description
+            }))
+            .collect();
+        let new_findings = after_findings.into_iter()
+            .filter(|f| !before_findings.iter().any(|b| b.rule == f.rule && b.source_file == f.source_file && b.message == f.message))
+            .collect();
+
+        AnalysisDiff { change, new_vulnerabilities, new_findings }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.new_vulnerabilities.is_empty() && self.new_findings.is_empty()
+    }
+
+    /// Markdown for the check run summary and the PR comment
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(change) = &self.change {
+            let mut push_list = |label: &str, items: &[String]| {
+                if !items.is_empty() {
+                    lines.push(format!("- **{}**: {}", label, items.iter().map(|i| format!("`{}`", i)).collect::<Vec<_>>().join(", ")));
+                }
+            };
+            push_list("Dependencies added", &change.dependencies_added);
+            push_list("Dependencies removed", &change.dependencies_removed);
+            push_list("Dependencies changed", &change.dependencies_changed);
+            push_list("Services added", &change.services_added);
+            push_list("Services removed", &change.services_removed);
+            if change.endpoint_delta != 0 {
+                lines.push(format!("- **Endpoints**: {:+}", change.endpoint_delta));
+            }
+            if change.code_element_delta != 0 {
+                lines.push(format!("- **Code elements**: {:+}", change.code_element_delta));
+            }
+        }
+        if lines.is_empty() {
+            lines.push("No dependency, service or endpoint changes.".to_string());
+        }
+
+        let mut summary = format!("### Architecture changes\n\n{}\n", lines.join("\n"));
+        if !self.new_vulnerabilities.is_empty() {
+            summary.push_str(&format!("\n### New security findings ({})\n\n", self.new_vulnerabilities.len()));
+            for v in &self.new_vulnerabilities {
+                summary.push_str(&format!("- **{:?}** {}: {} (`{}`)\n", v.severity, v.vulnerability_type, v.description, v.file_path));
+            }
+        }
+        if !self.new_findings.is_empty() {
+            summary.push_str(&format!("\n### New architecture findings ({})\n\n", self.new_findings.len()));
+            for f in &self.new_findings {
+                summary.push_str(&format!("- **{}** {}: {} (`{}`)\n", f.severity, f.rule, f.message, f.source_file));
+            }
+        }
+        summary
+    }
+
+    /// Check run annotations for the new findings in files the pull request touches
+    pub fn annotations(&self, changed_files: &[String]) -> Vec<Value> {
+        let changed = |path: &str| changed_files.iter().find(|f| path == f.as_str() || path.ends_with(&format!("/{}", f))).cloned();
+
+        let vulnerabilities = self.new_vulnerabilities.iter().filter_map(|v| {
+            let path = changed(&v.file_path)?;
+            let line = v.line_number.unwrap_or(1).max(1);
+            let level = match v.severity {
+                VulnerabilitySeverity::Critical | VulnerabilitySeverity::High => "failure",
+                VulnerabilitySeverity::Medium => "warning",
+                _ => "notice",
+            };
+            Some(json!({
+                "path": path,
+                "start_line": line,
+                "end_line": line,
+                "annotation_level": level,
+                "title": v.vulnerability_type,
+                "message": format!("{}\n\n{}", v.description, v.recommendation),
+            }))
+        });
+        let findings = self.new_findings.iter().filter_map(|f| {
+            let path = changed(&f.source_file)?;
+            let line = f.line_number.max(1);
+            Some(json!({
+                "path": path,
+                "start_line": line,
+                "end_line": line,
+                "annotation_level": if f.severity == "error" { "failure" } else { "warning" },
+                "title": f.rule,
+                "message": f.message,
+            }))
+        });
+        vulnerabilities.chain(findings).take(MAX_ANNOTATIONS).collect()
+    }
+}
+
+/// Checks `X-Hub-Signature-256` (`sha256=<hex HMAC of the body>`)
+pub fn verify_signature(secret: &str, body: &[u8], signature: Option<&str>) -> bool {
+    let expected = match signature.and_then(|s| s.strip_prefix("sha256=")) {
+        Some(hex) => hex.to_ascii_lowercase(),
+        None => return false,
+    };
+    let actual: String = hmac_sha256(secret.as_bytes(), body).iter().map(|b| format!("{:02x}", b)).collect();
+    // Compare without short-circuiting so timing does not reveal how much matched
+    expected.len() == actual.len() && expected.bytes().zip(actual.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK: usize = 64;
+    let mut block = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let inner = Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x36))
+        .chain_update(message)
+        .finalize();
+    Sha256::new()
+        .chain_update(block.map(|b| b ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// The few GitHub REST calls used to report on pull requests
+pub struct GitHubClient {
+    api_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: &str, token: &str) -> Result<Self> {
+        crate::network::ensure_online("Reporting to GitHub")?;
+        Ok(GitHubClient {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            client: crate::network::http_client(Duration::from_secs(30))?,
+        })
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("GitHub API returned {}: {}", status, body.chars().take(300).collect::<String>()));
+        }
+        Ok(response.json().await.unwrap_or(Value::Null))
+    }
+
+    /// Paths the pull request adds or modifies (first 300 files)
+    pub async fn changed_files(&self, slug: &str, number: u64) -> Result<Vec<String>> {
+        let mut files = Vec::new();
+        for page in 1..=3 {
+            let url = format!("{}/repos/{}/pulls/{}/files?per_page=100&page={}", self.api_url, slug, number, page);
+            let batch = self.send(self.client.get(url)).await?;
+            let batch = batch.as_array().cloned().unwrap_or_default();
+            files.extend(batch.iter()
+                .filter(|f| f["status"] != "removed")
+                .filter_map(|f| f["filename"].as_str().map(|s| s.to_string())));
+            if batch.len() < 100 {
+                break;
+            }
+        }
+        Ok(files)
+    }
+
+    /// Needs a GitHub App token; personal access tokens cannot create check runs
+    pub async fn create_check_run(&self, slug: &str, head_sha: &str, diff: &AnalysisDiff, changed_files: &[String]) -> Result<()> {
+        let title = if diff.is_clean() {
+            "No new findings".to_string()
+        } else {
+            format!("{} new security and {} new architecture findings", diff.new_vulnerabilities.len(), diff.new_findings.len())
+        };
+        let body = json!({
+            "name": CHECK_NAME,
+            "head_sha": head_sha,
+            "status": "completed",
+            "conclusion": if diff.is_clean() { "success" } else { "neutral" },
+            "output": {
+                "title": title,
+                "summary": diff.summary(),
+                "annotations": diff.annotations(changed_files),
+            },
+        });
+        self.send(self.client.post(format!("{}/repos/{}/check-runs", self.api_url, slug)).json(&body)).await?;
+        Ok(())
+    }
+
+    /// Posts the summary as a PR comment, editing our earlier comment instead of adding another
+    pub async fn upsert_comment(&self, slug: &str, number: u64, diff: &AnalysisDiff) -> Result<()> {
+        let body = json!({ "body": format!("{}\n## {}\n\n{}", COMMENT_MARKER, CHECK_NAME, diff.summary()) });
+        let comments = self.send(self.client.get(format!("{}/repos/{}/issues/{}/comments?per_page=100", self.api_url, slug, number))).await?;
+        let existing = comments.as_array()
+            .and_then(|comments| comments.iter().find(|c| c["body"].as_str().is_some_and(|b| b.starts_with(COMMENT_MARKER))))
+            .and_then(|c| c["id"].as_u64());
+        let request = match existing {
+            Some(id) => self.client.patch(format!("{}/repos/{}/issues/comments/{}", self.api_url, slug, id)),
+            None => self.client.post(format!("{}/repos/{}/issues/{}/comments", self.api_url, slug, number)),
+        };
+        self.send(request.json(&body)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        // Example from GitHub's webhook documentation
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+        assert!(verify_signature("It's a Secret to Everybody", b"Hello, World!", Some(signature)));
+        assert!(!verify_signature("wrong secret", b"Hello, World!", Some(signature)));
+        assert!(!verify_signature("It's a Secret to Everybody", b"Hello, World!", None));
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use crate::config::JiraConfig;
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+use crate::storage::Repository;
+
+/// Jira rejects summaries longer than this
+const MAX_SUMMARY: usize = 255;
+
+/// What a sync did, by issue key
+#[derive(Debug, Default, Serialize)]
+pub struct JiraSyncSummary {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub failed: usize,
+}
+
+/// Only these severities get a ticket
+pub fn is_ticketed(severity: &VulnerabilitySeverity) -> bool {
+    matches!(severity, VulnerabilitySeverity::Critical | VulnerabilitySeverity::High)
+}
+
+/// Identifies a finding across analyses: the repository, what was found and where, but not the line
+/// number or the vulnerability id, which change from one analysis to the next
+pub fn fingerprint(repository_url: &str, vulnerability: &SecurityVulnerability) -> String {
+    let digest = Sha256::new()
+        .chain_update(repository_url.trim_end_matches(".git").as_bytes())
+        .chain_update([0])
+        .chain_update(vulnerability.vulnerability_type.as_bytes())
+        .chain_update([0])
+        .chain_update(vulnerability.file_path.as_bytes())
+        .chain_update([0])
+        .chain_update(vulnerability.description.as_bytes())
+        .finalize();
+    digest.iter().take(10).map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The label that finds a finding's ticket again when the local mapping is lost
+pub fn fingerprint_label(fingerprint: &str) -> String {
+    format!("wavelength-{}", fingerprint)
+}
+
+/// The vulnerability's security entity in the UI's repository view
+fn back_link(base_url: &str, repository: &Repository, vulnerability: &SecurityVulnerability) -> String {
+    format!(
+        "{}/#repository-detail?repo={}&tab=security&entity={}&entityType=security_entity",
+        base_url, repository.id, vulnerability.entity_id
+    )
+}
+
+/// Jira REST API v2 client for the configured project
+pub struct JiraClient {
+    base_url: String,
+    email: Option<String>,
+    token: String,
+    project: String,
+    issue_type: String,
+    labels: Vec<String>,
+    client: reqwest::Client,
+}
+
+impl JiraClient {
+    /// `token` is the resolved secret, not the configured reference
+    pub fn new(config: &JiraConfig, token: &str) -> Result<Self> {
+        let (url, project) = match (&config.url, &config.project) {
+            (Some(url), Some(project)) => (url, project),
+            _ => return Err(anyhow!("Jira is not configured; set JIRA_URL, JIRA_PROJECT and JIRA_TOKEN")),
+        };
+        crate::network::ensure_online("Jira")?;
+        Ok(JiraClient {
+            base_url: url.trim_end_matches('/').to_string(),
+            email: config.email.clone(),
+            token: token.to_string(),
+            project: project.clone(),
+            issue_type: config.issue_type.clone(),
+            labels: config.labels.clone(),
+            client: crate::network::http_client(Duration::from_secs(30))?,
+        })
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let request = match &self.email {
+            Some(email) => request.basic_auth(email, Some(&self.token)),
+            None => request.bearer_auth(&self.token),
+        };
+        let response = request.header("Accept", "application/json").send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Jira returned {}: {}", status, body.chars().take(300).collect::<String>()));
+        }
+        Ok(response.json().await.unwrap_or(Value::Null))
+    }
+
+    /// The project's issue carrying the fingerprint label, if someone already opened one
+    pub async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<String>> {
+        let jql = format!("project = \"{}\" AND labels = \"{}\"", self.project, fingerprint_label(fingerprint));
+        let request = self.client.get(format!("{}/rest/api/2/search", self.base_url))
+            .query(&[("jql", jql.as_str()), ("fields", "key"), ("maxResults", "1")]);
+        let result = self.send(request).await?;
+        Ok(result["issues"][0]["key"].as_str().map(|k| k.to_string()))
+    }
+
+    /// Opens a ticket and returns its key
+    pub async fn create_issue(&self, repository: &Repository, vulnerability: &SecurityVulnerability, fingerprint: &str, base_url: &str) -> Result<String> {
+        let mut fields = self.fields(repository, vulnerability, base_url);
+        fields["project"] = json!({ "key": self.project });
+        fields["issuetype"] = json!({ "name": self.issue_type });
+        let mut labels = vec!["wavelength".to_string(), fingerprint_label(fingerprint)];
+        labels.extend(self.labels.iter().cloned());
+        fields["labels"] = json!(labels);
+
+        let created = self.send(self.client.post(format!("{}/rest/api/2/issue", self.base_url)).json(&json!({ "fields": fields }))).await?;
+        created["key"].as_str()
+            .map(|k| k.to_string())
+            .ok_or_else(|| anyhow!("Jira did not return an issue key"))
+    }
+
+    /// Refreshes the summary and description; status, assignee and labels are left to the team
+    pub async fn update_issue(&self, key: &str, repository: &Repository, vulnerability: &SecurityVulnerability, base_url: &str) -> Result<()> {
+        let fields = self.fields(repository, vulnerability, base_url);
+        self.send(self.client.put(format!("{}/rest/api/2/issue/{}", self.base_url, key)).json(&json!({ "fields": fields }))).await?;
+        Ok(())
+    }
+
+    fn fields(&self, repository: &Repository, vulnerability: &SecurityVulnerability, base_url: &str) -> Value {
+        let summary = format!("[Wavelength] {} in {}: {}", vulnerability.vulnerability_type, repository.name, vulnerability.file_path);
+        let location = match vulnerability.line_number {
+            Some(line) => format!("{}:{}", vulnerability.file_path, line),
+            None => vulnerability.file_path.clone(),
+        };
+        // Jira wiki markup, which is what API v2 descriptions use
+        let description = format!(
+            "*Severity:* {:?}\n*Repository:* {} ({})\n*Location:* {{{{{}}}}}\n\n{}\n\nh3. Recommendation\n{}\n\n[View in Wavelength|{}]",
+            vulnerability.severity,
+            repository.name,
+            repository.url,
+            location,
+            vulnerability.description,
+            vulnerability.recommendation,
+            back_link(base_url, repository, vulnerability),
+        );
+        json!({
+            "summary": summary.chars().take(MAX_SUMMARY).collect::<String>(),
+            "description": description,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_line_and_id() {
+        let vulnerability = SecurityVulnerability {
+            id: "a".to_string(),
+            entity_id: "e".to_string(),
+            vulnerability_type: "HardcodedSecret".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: "AWS key in source".to_string(),
+            recommendation: "Rotate it".to_string(),
+            file_path: "src/config.rs".to_string(),
+            line_number: Some(10),
+            affected_range: None,
+            fixed_version: None,
+        };
+        let moved = SecurityVulnerability { id: "b".to_string(), line_number: Some(42), ..vulnerability.clone() };
+
+        let url = "https://github.com/acme/app";
+        assert_eq!(fingerprint(url, &vulnerability), fingerprint(&format!("{}.git", url), &moved));
+        assert_ne!(fingerprint(url, &vulnerability), fingerprint("https://github.com/acme/other", &vulnerability));
+        assert_eq!(fingerprint(url, &vulnerability).len(), 20);
+    }
+}
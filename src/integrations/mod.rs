@@ -0,0 +1,7 @@
+//! Pushes analysis results into other tools
+pub mod email;
+pub mod github;
+pub mod jira;
+pub mod object_storage;
+pub mod servicenow;
+pub mod webhooks;
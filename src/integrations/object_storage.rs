@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// What can be published for a repository, by the name `include` uses
+pub const ARTIFACT_KINDS: &[&str] = &["report", "openapi", "graph", "dependencies", "sbom", "backstage"];
+
+/// A generated file to publish, named relative to its run prefix
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub name: String,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+/// Where a run's artifacts went
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishedRun {
+    pub location: String,
+    pub artifacts: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    S3,
+    Gcs,
+    /// A local directory, e.g. a volume mounted into a container
+    File,
+}
+
+/// An `s3://bucket/prefix`, `gs://bucket/prefix` or `file:///directory` destination. Uploads go
+/// through the `aws` and `gcloud` CLIs so their usual credentials (profiles, instance roles,
+/// workload identity) apply. For `file://` the whole path is the bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectStore {
+    pub provider: Provider,
+    pub bucket: String,
+    pub prefix: String,
+    /// S3-compatible endpoint such as MinIO
+    pub endpoint_url: Option<String>,
+}
+
+impl ObjectStore {
+    pub fn parse(url: &str, endpoint_url: Option<&str>) -> Result<ObjectStore> {
+        let (provider, rest) = if let Some(rest) = url.strip_prefix("s3://") {
+            (Provider::S3, rest)
+        } else if let Some(rest) = url.strip_prefix("gs://") {
+            (Provider::Gcs, rest)
+        } else if let Some(path) = url.strip_prefix("file://") {
+            if path.is_empty() {
+                return Err(anyhow!("'{}' has no directory", url));
+            }
+            return Ok(ObjectStore {
+                provider: Provider::File,
+                bucket: path.trim_end_matches('/').to_string(),
+                prefix: String::new(),
+                endpoint_url: None,
+            });
+        } else {
+            return Err(anyhow!("'{}' is not an s3://, gs:// or file:// URL", url));
+        };
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            return Err(anyhow!("'{}' has no bucket name", url));
+        }
+        Ok(ObjectStore {
+            provider,
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            endpoint_url: endpoint_url.map(|e| e.to_string()),
+        })
+    }
+
+    /// `<prefix>/<repository id>/<UTC timestamp>`, so every run keeps its own copy
+    pub fn run_prefix(&self, repository_id: &str, run_at: DateTime<Utc>) -> String {
+        let run = format!("{}/{}", repository_id, run_at.format("%Y%m%dT%H%M%SZ"));
+        if self.prefix.is_empty() { run } else { format!("{}/{}", self.prefix, run) }
+    }
+
+    fn url(&self, key: &str) -> String {
+        let scheme = match self.provider {
+            Provider::S3 => "s3",
+            Provider::Gcs => "gs",
+            Provider::File => "file",
+        };
+        format!("{}://{}/{}", scheme, self.bucket, key)
+    }
+
+    /// Uploads each artifact under the run prefix; blocks on the CLI, so call it off the async workers
+    pub fn publish(&self, run_prefix: &str, artifacts: &[Artifact]) -> Result<PublishedRun> {
+        if self.provider != Provider::File {
+            crate::network::ensure_online("Publishing artifacts")?;
+        }
+        for artifact in artifacts {
+            self.upload(&format!("{}/{}", run_prefix, artifact.name), artifact)?;
+        }
+        Ok(PublishedRun {
+            location: self.url(run_prefix),
+            artifacts: artifacts.iter().map(|a| a.name.clone()).collect(),
+        })
+    }
+
+    fn upload(&self, key: &str, artifact: &Artifact) -> Result<()> {
+        let destination = self.url(key);
+        let (cli, mut command) = match self.provider {
+            Provider::S3 => {
+                let mut command = Command::new("aws");
+                command.args(["s3", "cp", "-", &destination, "--content-type", artifact.content_type, "--only-show-errors"]);
+                if let Some(endpoint) = &self.endpoint_url {
+                    command.args(["--endpoint-url", endpoint]);
+                }
+                ("aws", command)
+            }
+            Provider::Gcs => {
+                let mut command = Command::new("gcloud");
+                command.args(["storage", "cp", "-", &destination, "--content-type", artifact.content_type]);
+                ("gcloud", command)
+            }
+            Provider::File => {
+                let path = std::path::Path::new(&self.bucket).join(key);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+                }
+                return std::fs::write(&path, &artifact.body).with_context(|| format!("writing {}", path.display()));
+            }
+        };
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("publishing to {} needs the {} CLI", destination, cli))?;
+        child.stdin.take().context("no stdin for upload")?.write_all(&artifact.body)?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(anyhow!("uploading {} failed: {}", destination, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_destination_and_run_prefix() {
+        let store = ObjectStore::parse("s3://archive/wavelength/", None).unwrap();
+        assert_eq!(store.provider, Provider::S3);
+        assert_eq!(store.bucket, "archive");
+        let run_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        assert_eq!(store.run_prefix("repo-1", run_at), "wavelength/repo-1/20240501T123000Z");
+
+        let store = ObjectStore::parse("gs://archive", None).unwrap();
+        assert_eq!(store.run_prefix("repo-1", run_at), "repo-1/20240501T123000Z");
+        assert!(ObjectStore::parse("https://archive", None).is_err());
+        assert!(ObjectStore::parse("s3:///prefix", None).is_err());
+
+        let store = ObjectStore::parse("file:///srv/artifacts/", None).unwrap();
+        assert_eq!(store.provider, Provider::File);
+        assert_eq!(store.url(&store.run_prefix("repo-1", run_at)), "file:///srv/artifacts/repo-1/20240501T123000Z");
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use crate::config::ServiceNowConfig;
+use crate::storage::{Repository, StoredDependency, StoredService, StoredServiceBoundary};
+
+const DEPENDS_ON: &str = "Depends on::Used by";
+const CONTAINS: &str = "Contains::Contained by";
+
+/// One configuration item for the Identification and Reconciliation Engine, which decides
+/// whether it is new or matches an existing CI
+#[derive(Debug, Clone, Serialize)]
+pub struct CmdbItem {
+    #[serde(rename = "className")]
+    pub class_name: String,
+    pub values: Map<String, Value>,
+}
+
+/// A relationship between two items, by their position in the payload
+#[derive(Debug, Clone, Serialize)]
+pub struct CmdbRelation {
+    #[serde(rename = "type")]
+    pub relation_type: String,
+    pub parent: usize,
+    pub child: usize,
+}
+
+/// The body of `POST /api/now/identifyreconcile`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CmdbPayload {
+    pub items: Vec<CmdbItem>,
+    pub relations: Vec<CmdbRelation>,
+}
+
+impl CmdbPayload {
+    fn add(&mut self, class_name: &str, values: Value) -> usize {
+        let values = match values {
+            Value::Object(values) => values,
+            _ => Map::new(),
+        };
+        self.items.push(CmdbItem { class_name: class_name.to_string(), values });
+        self.items.len() - 1
+    }
+
+    fn relate(&mut self, relation_type: &str, parent: usize, child: usize) {
+        self.relations.push(CmdbRelation { relation_type: relation_type.to_string(), parent, child });
+    }
+}
+
+/// What the CMDB did with a pushed payload
+#[derive(Debug, Default, Serialize)]
+pub struct CmdbPushSummary {
+    pub inserted: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub errors: Vec<String>,
+}
+
+/// Data stores are database instances; every other detected service is a generic service CI
+fn service_class(service_type: &str) -> &'static str {
+    match service_type {
+        "database" => "cmdb_ci_db_instance",
+        _ => "cmdb_ci_service",
+    }
+}
+
+/// Maps a repository to an application CI, with a contained application for each separately
+/// built sub-project and `Depends on` relationships to its services, data stores and, when
+/// `include_packages` is set, its runtime packages
+pub fn build_payload(
+    repository: &Repository,
+    boundaries: &[StoredServiceBoundary],
+    services: &[StoredService],
+    dependencies: &[StoredDependency],
+    include_packages: bool,
+) -> CmdbPayload {
+    let mut payload = CmdbPayload::default();
+    let application = payload.add("cmdb_ci_appl", json!({
+        "name": repository.name,
+        "short_description": format!("{} ({} branch), discovered by Wavelength", repository.url, repository.branch),
+    }));
+
+    // Keyed by name so a store found by several detectors becomes one CI
+    let mut targets: BTreeMap<String, &'static str> = BTreeMap::new();
+    for service in services {
+        targets.entry(service.name.clone()).or_insert_with(|| service_class(&service.service_type));
+    }
+    for boundary in boundaries {
+        for store in &boundary.data_stores {
+            targets.entry(store.clone()).or_insert("cmdb_ci_db_instance");
+        }
+    }
+    let targets: BTreeMap<String, usize> = targets.into_iter()
+        .map(|(name, class_name)| {
+            let index = payload.add(class_name, json!({ "name": name }));
+            (name, index)
+        })
+        .collect();
+
+    let sub_projects: Vec<&StoredServiceBoundary> = boundaries.iter().filter(|b| b.kind == "service").collect();
+    let sub_project_index: BTreeMap<&str, usize> = sub_projects.iter()
+        .map(|b| (b.name.as_str(), payload.add("cmdb_ci_appl", json!({
+            "name": format!("{} / {}", repository.name, b.name),
+            "short_description": format!("{} in {}", b.paths.join(", "), repository.url),
+        }))))
+        .collect();
+
+    for index in targets.values() {
+        payload.relate(DEPENDS_ON, application, *index);
+    }
+    for boundary in &sub_projects {
+        let index = sub_project_index[boundary.name.as_str()];
+        payload.relate(CONTAINS, application, index);
+        for dependency in &boundary.depends_on {
+            if let Some(other) = sub_project_index.get(dependency.as_str()) {
+                payload.relate(DEPENDS_ON, index, *other);
+            }
+        }
+        for store in &boundary.data_stores {
+            payload.relate(DEPENDS_ON, index, targets[store]);
+        }
+    }
+
+    if include_packages {
+        let mut seen = std::collections::HashSet::new();
+        for dependency in dependencies.iter().filter(|d| !d.is_dev) {
+            if seen.insert((&dependency.name, &dependency.version)) {
+                let index = payload.add("cmdb_ci_spkg", json!({
+                    "name": dependency.name,
+                    "version": dependency.version,
+                }));
+                payload.relate(DEPENDS_ON, application, index);
+            }
+        }
+    }
+
+    payload
+}
+
+/// Pushes payloads through the IRE REST API with basic auth
+pub struct ServiceNowClient {
+    instance_url: String,
+    username: String,
+    password: String,
+    data_source: String,
+    client: reqwest::Client,
+}
+
+impl ServiceNowClient {
+    /// `password` is the resolved secret, not the configured reference
+    pub fn new(config: &ServiceNowConfig, password: &str) -> Result<Self> {
+        let (url, username) = match (&config.instance_url, &config.username) {
+            (Some(url), Some(username)) => (url, username),
+            _ => return Err(anyhow!("ServiceNow is not configured; set SERVICENOW_URL, SERVICENOW_USERNAME and SERVICENOW_PASSWORD")),
+        };
+        crate::network::ensure_online("ServiceNow")?;
+        Ok(ServiceNowClient {
+            instance_url: url.trim_end_matches('/').to_string(),
+            username: username.clone(),
+            password: password.to_string(),
+            data_source: config.data_source.clone(),
+            client: crate::network::http_client(Duration::from_secs(60))?,
+        })
+    }
+
+    pub async fn push(&self, payload: &CmdbPayload) -> Result<CmdbPushSummary> {
+        let response = self.client
+            .post(format!("{}/api/now/identifyreconcile", self.instance_url))
+            .query(&[("sysparm_data_source", self.data_source.as_str())])
+            .basic_auth(&self.username, Some(&self.password))
+            .header("Accept", "application/json")
+            .json(payload)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("ServiceNow returned {}: {}", status, body.chars().take(300).collect::<String>()));
+        }
+
+        let body: Value = response.json().await?;
+        let result = if body["result"].is_object() { &body["result"] } else { &body };
+        let mut summary = CmdbPushSummary::default();
+        for item in result["items"].as_array().into_iter().flatten() {
+            match item["operation"].as_str() {
+                Some("INSERT") => summary.inserted += 1,
+                Some("UPDATE") => summary.updated += 1,
+                Some("NO_CHANGE") => summary.unchanged += 1,
+                _ => {}
+            }
+            for error in item["errors"].as_array().into_iter().flatten() {
+                summary.errors.push(format!(
+                    "{}: {}",
+                    item["className"].as_str().unwrap_or("item"),
+                    error["message"].as_str().unwrap_or("unknown error")
+                ));
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_build_payload_relates_services_and_sub_projects() {
+        let repository = Repository {
+            id: "r".to_string(),
+            name: "shop".to_string(),
+            url: "https://github.com/acme/shop".to_string(),
+            branch: "main".to_string(),
+            auth_type: None,
+            auth_value: None,
+            last_analyzed_at: None,
+            analysis_profile: None,
+            workspace_id: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let service = |name: &str, service_type: &str| StoredService {
+            id: name.to_string(),
+            repository_id: "r".to_string(),
+            provider: name.to_string(),
+            service_type: service_type.to_string(),
+            name: name.to_string(),
+            configuration: String::new(),
+            file_path: String::new(),
+            line_number: None,
+            confidence: 1.0,
+            evidence: Vec::new(),
+            created_at: String::new(),
+        };
+        let boundary: StoredServiceBoundary = serde_json::from_value(json!({
+            "id": "b", "repository_id": "r", "name": "api", "kind": "service", "paths": ["api"],
+            "file_count": 1, "element_count": 1, "internal_references": 0, "outbound_references": 0,
+            "cohesion": 1.0, "data_stores": ["PostgreSQL"], "shared_data_stores": [], "depends_on": [],
+            "confidence": 1.0, "evidence": [], "created_at": ""
+        })).unwrap();
+
+        let payload = build_payload(&repository, &[boundary], &[service("PostgreSQL", "database"), service("Stripe", "payment")], &[], false);
+        let classes: Vec<&str> = payload.items.iter().map(|i| i.class_name.as_str()).collect();
+        assert_eq!(classes, ["cmdb_ci_appl", "cmdb_ci_db_instance", "cmdb_ci_service", "cmdb_ci_appl"]);
+        // The repository depends on both services, contains the sub-project, which uses the database
+        assert_eq!(payload.relations.len(), 4);
+        assert!(payload.relations.iter().any(|r| r.relation_type == CONTAINS && r.parent == 0 && r.child == 3));
+        assert!(payload.relations.iter().any(|r| r.relation_type == DEPENDS_ON && r.parent == 3 && r.child == 1));
+    }
+}
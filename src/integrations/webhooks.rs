@@ -0,0 +1,284 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::time::Duration;
+use crate::analysis::changelog::{ArchitectureSnapshot, SnapshotChange};
+use crate::analysis::Detection;
+use crate::integrations::github::hmac_sha256;
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+use crate::storage::{Repository, WebhookKind};
+
+/// Lines of each list shown in chat messages; the generic payload carries them all
+const MAX_CHAT_ITEMS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    AnalysisCompleted,
+    AnalysisFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotifiedRepository {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub branch: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewVulnerability {
+    pub severity: VulnerabilitySeverity,
+    pub vulnerability_type: String,
+    pub description: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+}
+
+/// What changed with an analysis; also the body of generic webhooks
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisNotification {
+    pub event: NotificationEvent,
+    pub repository: NotifiedRepository,
+    pub link: String,
+    pub sent_at: DateTime<Utc>,
+    /// Why the analysis failed
+    pub error: Option<String>,
+    /// Vulnerabilities the previous notification did not see
+    pub new_vulnerabilities: Vec<NewVulnerability>,
+    pub vulnerability_count: usize,
+    /// Changes since the previous analysis
+    pub changes: Option<SnapshotChange>,
+}
+
+impl AnalysisNotification {
+    pub fn failed(repository: &Repository, error: &str, base_url: &str) -> Self {
+        AnalysisNotification {
+            event: NotificationEvent::AnalysisFailed,
+            error: Some(error.to_string()),
+            ..Self::base(repository, base_url)
+        }
+    }
+
+    /// `baseline` holds the vulnerability fingerprints of the previous notification; without
+    /// one every vulnerability is new. `snapshots` are the last two analyses, oldest first
+    pub fn completed(
+        repository: &Repository,
+        vulnerabilities: &[SecurityVulnerability],
+        baseline: Option<&[String]>,
+        snapshots: &[ArchitectureSnapshot],
+        base_url: &str,
+    ) -> Self {
+        let seen: HashSet<&str> = baseline.unwrap_or_default().iter().map(|f| f.as_str()).collect();
+        let mut new_vulnerabilities: Vec<NewVulnerability> = vulnerabilities.iter()
+            .filter(|v| !seen.contains(Detection::vulnerability(v).fingerprint().as_str()))
+            .map(|v| NewVulnerability {
+                severity: v.severity.clone(),
+                vulnerability_type: v.vulnerability_type.clone(),
+                description: v.description.clone(),
+                file_path: v.file_path.clone(),
+                line_number: v.line_number,
+            })
+            .collect();
+        new_vulnerabilities.sort_by_key(|v| severity_rank(&v.severity));
+        let changes = match snapshots {
+            [.., before, after] => Some(SnapshotChange::between(before, after, Vec::new())),
+            _ => None,
+        };
+        AnalysisNotification {
+            event: NotificationEvent::AnalysisCompleted,
+            new_vulnerabilities,
+            vulnerability_count: vulnerabilities.len(),
+            changes,
+            ..Self::base(repository, base_url)
+        }
+    }
+
+    fn base(repository: &Repository, base_url: &str) -> Self {
+        AnalysisNotification {
+            event: NotificationEvent::AnalysisCompleted,
+            repository: NotifiedRepository {
+                id: repository.id.clone(),
+                name: repository.name.clone(),
+                url: repository.url.clone(),
+                branch: repository.branch.clone(),
+            },
+            link: format!("{}/#repository-detail?repo={}", base_url, repository.id),
+            sent_at: Utc::now(),
+            error: None,
+            new_vulnerabilities: Vec::new(),
+            vulnerability_count: 0,
+            changes: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self.event {
+            NotificationEvent::AnalysisCompleted => format!("Analysis of {} completed", self.repository.name),
+            NotificationEvent::AnalysisFailed => format!("Analysis of {} failed", self.repository.name),
+        }
+    }
+
+    /// The summary as lines of plain text, lists cut to a few items
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} ({} branch)", self.repository.url, self.repository.branch)];
+        if let Some(error) = &self.error {
+            lines.push(format!("Error: {}", error));
+            return lines;
+        }
+        lines.push(format!("{} new vulnerabilities ({} in total)", self.new_vulnerabilities.len(), self.vulnerability_count));
+        lines.extend(capped(self.new_vulnerabilities.iter().map(|v| {
+            let line = v.line_number.map(|l| format!(":{}", l)).unwrap_or_default();
+            format!("• {:?} {} in {}{}", v.severity, v.vulnerability_type, v.file_path, line)
+        })));
+        if let Some(changes) = &self.changes {
+            let lists = [
+                ("Services added", &changes.services_added),
+                ("Services removed", &changes.services_removed),
+                ("Dependencies added", &changes.dependencies_added),
+                ("Dependencies removed", &changes.dependencies_removed),
+                ("Dependencies changed", &changes.dependencies_changed),
+            ];
+            for (label, items) in lists.into_iter().filter(|(_, items)| !items.is_empty()) {
+                lines.push(format!("{}: {}", label, items.len()));
+                lines.extend(capped(items.iter().map(|item| format!("• {}", item))));
+            }
+            if lists.iter().all(|(_, items)| items.is_empty()) {
+                lines.push("No service or dependency changes".to_string());
+            }
+        }
+        lines
+    }
+
+    /// The request body for a webhook of `kind`
+    pub fn message(&self, kind: WebhookKind) -> Value {
+        match kind {
+            WebhookKind::Slack => json!({
+                "text": format!("*{}*\n{}\n<{}|Open in Wavelength>", self.title(), self.lines().join("\n"), self.link),
+            }),
+            WebhookKind::Teams => json!({
+                "@type": "MessageCard",
+                "@context": "https://schema.org/extensions",
+                "summary": self.title(),
+                "themeColor": if self.event == NotificationEvent::AnalysisFailed { "C0392B" } else { "27AE60" },
+                "title": self.title(),
+                // Teams cards render markdown, where a line break needs two trailing spaces
+                "text": self.lines().join("  \n"),
+                "potentialAction": [{
+                    "@type": "OpenUri",
+                    "name": "Open in Wavelength",
+                    "targets": [{ "os": "default", "uri": self.link }],
+                }],
+            }),
+            WebhookKind::Generic => serde_json::to_value(self).unwrap_or(Value::Null),
+        }
+    }
+}
+
+fn severity_rank(severity: &VulnerabilitySeverity) -> u8 {
+    match severity {
+        VulnerabilitySeverity::Critical => 0,
+        VulnerabilitySeverity::High => 1,
+        VulnerabilitySeverity::Medium => 2,
+        VulnerabilitySeverity::Low => 3,
+        VulnerabilitySeverity::Info => 4,
+    }
+}
+
+fn capped(items: impl ExactSizeIterator<Item = String>) -> Vec<String> {
+    let total = items.len();
+    let mut lines: Vec<String> = items.take(MAX_CHAT_ITEMS).collect();
+    if total > MAX_CHAT_ITEMS {
+        lines.push(format!("• … and {} more", total - MAX_CHAT_ITEMS));
+    }
+    lines
+}
+
+/// Posts the notification; `url` and `secret` are resolved secrets, not references. Generic
+/// payloads carry `X-Wavelength-Signature: sha256=<hex HMAC of the body>` when there is a secret
+pub async fn deliver(kind: WebhookKind, url: &str, secret: Option<&str>, notification: &AnalysisNotification) -> Result<()> {
+    crate::network::ensure_online("Webhook notifications")?;
+    let body = serde_json::to_vec(&notification.message(kind))?;
+    let mut request = crate::network::http_client(Duration::from_secs(30))?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "wavelength-arch-decoder");
+    if let (WebhookKind::Generic, Some(secret)) = (kind, secret) {
+        let signature: String = hmac_sha256(secret.as_bytes(), &body).iter().map(|b| format!("{:02x}", b)).collect();
+        request = request.header("X-Wavelength-Signature", format!("sha256={}", signature));
+    }
+    let response = request.body(body).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("{} returned {}: {}", kind.as_str(), status, body.chars().take(300).collect::<String>()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn test_completed_notification_lists_new_findings_and_changes() {
+        let repository = Repository {
+            id: "r".to_string(),
+            name: "shop".to_string(),
+            url: "https://github.com/acme/shop".to_string(),
+            branch: "main".to_string(),
+            auth_type: None,
+            auth_value: None,
+            last_analyzed_at: None,
+            analysis_profile: None,
+            workspace_id: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let vulnerability = |severity: VulnerabilitySeverity, file: &str| SecurityVulnerability {
+            id: file.to_string(),
+            entity_id: String::new(),
+            vulnerability_type: "hardcoded_secret".to_string(),
+            severity,
+            description: "Hardcoded API key".to_string(),
+            recommendation: String::new(),
+            file_path: file.to_string(),
+            line_number: Some(3),
+            affected_range: None,
+            fixed_version: None,
+        };
+        let known = vulnerability(VulnerabilitySeverity::Low, "src/old.ts");
+        let vulnerabilities = vec![known.clone(), vulnerability(VulnerabilitySeverity::Critical, "src/new.ts")];
+        let baseline = vec![Detection::vulnerability(&known).fingerprint()];
+        let snapshot = |services: &[&str], react: &str| ArchitectureSnapshot {
+            taken_at: Utc::now().to_rfc3339(),
+            dependencies: BTreeMap::from([("react".to_string(), react.to_string())]),
+            services: services.iter().map(|s| s.to_string()).collect::<BTreeSet<_>>(),
+            endpoint_count: 0,
+            code_element_count: 0,
+        };
+        let snapshots = [snapshot(&["Stripe"], "18.2.0"), snapshot(&["Stripe", "Twilio"], "18.3.1")];
+
+        let notification = AnalysisNotification::completed(&repository, &vulnerabilities, Some(&baseline), &snapshots, "http://localhost:8080");
+        assert_eq!(notification.new_vulnerabilities.len(), 1);
+        assert_eq!(notification.new_vulnerabilities[0].file_path, "src/new.ts");
+        assert_eq!(notification.lines(), vec![
+            "https://github.com/acme/shop (main branch)",
+            "1 new vulnerabilities (2 in total)",
+            "• Critical hardcoded_secret in src/new.ts:3",
+            "Services added: 1",
+            "• Twilio",
+            "Dependencies changed: 1",
+            "• react 18.2.0 -> 18.3.1",
+        ]);
+
+        let slack = notification.message(WebhookKind::Slack);
+        assert!(slack["text"].as_str().unwrap().starts_with("*Analysis of shop completed*\n"));
+        let generic = notification.message(WebhookKind::Generic);
+        assert_eq!(generic["event"], "analysis_completed");
+        assert_eq!(generic["changes"]["services_added"], json!(["Twilio"]));
+    }
+}
@@ -1,3 +1,27 @@
+//! Wavelength Architecture Decoder as a library.
+//!
+//! [`Analyzer`] runs the same analysis as the server and returns an [`AnalysisReport`]. It is
+//! synchronous and needs no actix runtime, so other Rust tools can embed it directly. Turn off
+//! the default `server` feature to leave out actix-web, GraphQL and the crawler:
+//!
+//! ```no_run
+//! use wavelength_arch_decoder::{AnalysisProfile, Analyzer, Config};
+//!
+//! let mut config = Config::default();
+//! config.database.database_path = "/tmp/wavelength.db".to_string();
+//! let report = Analyzer::new(config)
+//!     .with_profile(AnalysisProfile::Fast)
+//!     .analyze("./my-service")?;
+//! for service in &report.services {
+//!     println!("{} ({})", service.name, service.provider);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The re-exports below are the stable surface. The modules are public for the binary, the
+//! integration tests and individual detectors, and change with the server.
+
+#[cfg(feature = "server")]
 pub mod api;
 pub mod config;
 pub mod storage;
@@ -6,7 +30,19 @@ pub mod parsers;
 pub mod analysis;
 pub mod security;
 pub mod graph;
+#[cfg(feature = "server")]
 pub mod graphql;
+#[cfg(feature = "server")]
 pub mod crawler;
 pub mod report;
+pub mod search;
+pub mod plugins;
+pub mod network;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod integrations;
+pub mod analyzer;
 
+pub use analysis::AnalysisProfile;
+pub use analyzer::{AnalysisCounts, AnalysisReport, Analyzer};
+pub use config::Config;
@@ -9,23 +9,97 @@ mod graph;
 mod graphql;
 mod crawler;
 mod report;
+mod search;
+mod plugins;
+mod network;
+mod integrations;
+mod analyzer;
+mod cli;
+#[cfg(test)]
+mod test_support;
 
 use api::server::start_server;
+use clap::Parser;
+use cli::{Cli, Command, RulesCommand};
 use config::Config;
-use log::info;
+use log::{error, info, warn};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // Initialize logger
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
+    let cli = Cli::parse();
 
     info!("Starting Wavelength Architecture Decoder...");
 
-    // Load configuration
-    let config = Config::from_env()
-        .expect("Failed to load configuration. Please check your environment variables.");
+    // Load and validate configuration, reporting every problem before giving up
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load configuration: {:#}", e);
+            error!("Please check wavelength.toml/wavelength.yaml and your environment variables.");
+            std::process::exit(1);
+        }
+    };
+    let problems = config.validate();
+    if !problems.is_empty() {
+        error!("Found {} configuration problem(s):", problems.len());
+        for problem in &problems {
+            error!("  ✗ {}", problem);
+        }
+        error!("Please check wavelength.toml/wavelength.yaml and your environment variables.");
+        std::process::exit(Command::config_error_code(cli.command.as_ref()));
+    }
 
     info!("Configuration loaded successfully");
+    config.network.offline |= cli.offline;
+    if let Err(e) = network::configure(&config.network) {
+        error!("Failed to apply network settings: {:#}", e);
+        std::process::exit(1);
+    }
+    if config.network.offline {
+        warn!("Offline mode: no outbound network calls will be made");
+        for note in network::offline_degradations(&config) {
+            warn!("  • {}", note);
+        }
+    }
+
+    match cli.command {
+        Some(Command::Analyze(args)) => {
+            if let Err(e) = cli::analyze(config, args).await {
+                error!("Analysis failed: {:#}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        // 1 fails the pipeline on a breached threshold, 2 when the gate could not be checked at all
+        Some(Command::Gate(args)) => match cli::gate(config, args).await {
+            Ok(true) => return Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                error!("Gate check failed: {:#}", e);
+                std::process::exit(2);
+            }
+        },
+        Some(Command::Rules(args)) => match args.command {
+            RulesCommand::Test(args) => match cli::test_rules(args) {
+                Ok(true) => return Ok(()),
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    error!("Rule tests could not run: {:#}", e);
+                    std::process::exit(2);
+                }
+            },
+        },
+        Some(Command::Explain(args)) => {
+            if let Err(e) = cli::explain(config, args) {
+                error!("Explain failed: {:#}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Serve) | None => {}
+    }
     
     // Check and log plugins at startup
     let plugin_dir = std::path::Path::new("config/plugins");
@@ -49,6 +123,17 @@ async fn main() -> std::io::Result<()> {
         }
     }
     
+    if config.server.one_shot {
+        return match api::one_shot::run(&config).await {
+            Ok(true) => Ok(()),
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                error!("One-shot run failed: {:#}", e);
+                std::process::exit(2);
+            }
+        };
+    }
+
     info!("Server will start on {}:{}", config.server.host, config.server.port);
 
     // Start server
@@ -0,0 +1,525 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// A CI/CD pipeline definition (one workflow file, .gitlab-ci.yml, or Jenkinsfile)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiPipeline {
+    pub platform: String,
+    pub name: String,
+    pub file_path: String,
+    pub triggers: Vec<String>, // e.g. "push", "pull_request", "schedule", "merge_request"
+    pub stages: Vec<String>,
+    pub jobs: Vec<CiJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiJob {
+    pub name: String,
+    pub stage: Option<String>,
+    pub needs: Vec<String>, // Jobs this job depends on
+    pub runs_on: Option<String>,
+    pub environment: Option<String>,
+    pub actions: Vec<String>, // Reusable actions/images referenced by the job
+    pub commands: Vec<String>,
+    pub tools: Vec<String>, // Tool names inferred from actions and commands
+    pub cloud_providers: Vec<String>, // Provider names (aws, gcp, azure, ...) matching ServiceProvider strings
+    pub deploy_targets: Vec<String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>, // Environment variables the job sees, pipeline-level ones included
+}
+
+impl CiJob {
+    fn new(name: &str) -> Self {
+        CiJob {
+            name: name.to_string(),
+            stage: None,
+            needs: Vec::new(),
+            runs_on: None,
+            environment: None,
+            actions: Vec::new(),
+            commands: Vec::new(),
+            tools: Vec::new(),
+            cloud_providers: Vec::new(),
+            deploy_targets: Vec::new(),
+            variables: BTreeMap::new(),
+        }
+    }
+
+    /// Infer tools, cloud providers, and deploy targets from the job's actions and commands
+    fn classify(&mut self) {
+        for action in &self.actions {
+            let action_lower = action.to_lowercase();
+            for (pattern, tool) in ACTION_TOOLS {
+                if action_lower.contains(pattern) {
+                    push_unique(&mut self.tools, tool);
+                }
+            }
+            for (pattern, provider) in ACTION_PROVIDERS {
+                if action_lower.contains(pattern) {
+                    push_unique(&mut self.cloud_providers, provider);
+                }
+            }
+        }
+
+        for command in &self.commands {
+            let words: Vec<&str> = command.split(|c: char| c.is_whitespace() || c == ';' || c == '&' || c == '|')
+                .filter(|w| !w.is_empty())
+                .collect();
+            for (binary, tool) in COMMAND_TOOLS {
+                if words.iter().any(|w| w == binary) {
+                    push_unique(&mut self.tools, tool);
+                }
+            }
+            for (binary, provider) in COMMAND_PROVIDERS {
+                if words.iter().any(|w| w == binary) {
+                    push_unique(&mut self.cloud_providers, provider);
+                }
+            }
+            if let Some(target) = deploy_target_from_command(command) {
+                push_unique(&mut self.deploy_targets, &target);
+            }
+        }
+
+        if let Some(env) = &self.environment {
+            let target = format!("environment:{}", env);
+            push_unique(&mut self.deploy_targets, &target);
+        }
+    }
+}
+
+/// Action/image substrings mapped to the tool they set up
+const ACTION_TOOLS: &[(&str, &str)] = &[
+    ("actions/setup-node", "node"),
+    ("actions/setup-python", "python"),
+    ("actions/setup-go", "go"),
+    ("actions/setup-java", "java"),
+    ("dtolnay/rust-toolchain", "cargo"),
+    ("actions-rs/", "cargo"),
+    ("docker/build-push-action", "docker"),
+    ("docker/login-action", "docker"),
+    ("docker/setup-buildx-action", "docker"),
+    ("hashicorp/setup-terraform", "terraform"),
+    ("azure/setup-helm", "helm"),
+    ("azure/setup-kubectl", "kubectl"),
+    ("codecov/codecov-action", "codecov"),
+    ("github/codeql-action", "codeql"),
+    ("sonarsource/", "sonarqube"),
+];
+
+/// Action/image substrings mapped to the cloud provider they authenticate against
+const ACTION_PROVIDERS: &[(&str, &str)] = &[
+    ("aws-actions/", "aws"),
+    ("google-github-actions/", "gcp"),
+    ("azure/login", "azure"),
+    ("azure/webapps-deploy", "azure"),
+    ("amondnet/vercel-action", "vercel"),
+    ("netlify/actions", "netlify"),
+    ("akhileshns/heroku-deploy", "heroku"),
+    ("cloudflare/wrangler-action", "cloudflare"),
+    ("digitalocean/action-doctl", "digitalocean"),
+];
+
+/// Command binaries mapped to tool names
+const COMMAND_TOOLS: &[(&str, &str)] = &[
+    ("npm", "npm"),
+    ("npx", "npm"),
+    ("yarn", "yarn"),
+    ("pnpm", "pnpm"),
+    ("cargo", "cargo"),
+    ("go", "go"),
+    ("pip", "pip"),
+    ("poetry", "poetry"),
+    ("pytest", "pytest"),
+    ("mvn", "maven"),
+    ("gradle", "gradle"),
+    ("./gradlew", "gradle"),
+    ("make", "make"),
+    ("docker", "docker"),
+    ("docker-compose", "docker-compose"),
+    ("terraform", "terraform"),
+    ("kubectl", "kubectl"),
+    ("helm", "helm"),
+    ("ansible-playbook", "ansible"),
+    ("serverless", "serverless"),
+    ("sls", "serverless"),
+    ("jest", "jest"),
+    ("eslint", "eslint"),
+];
+
+/// Command binaries mapped to cloud providers
+const COMMAND_PROVIDERS: &[(&str, &str)] = &[
+    ("aws", "aws"),
+    ("sam", "aws"),
+    ("cdk", "aws"),
+    ("gcloud", "gcp"),
+    ("gsutil", "gcp"),
+    ("az", "azure"),
+    ("vercel", "vercel"),
+    ("netlify", "netlify"),
+    ("heroku", "heroku"),
+    ("wrangler", "cloudflare"),
+    ("doctl", "digitalocean"),
+    ("firebase", "firebase"),
+    ("flyctl", "fly"),
+    ("fly", "fly"),
+];
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+/// Recognize common deploy commands and describe their target
+fn deploy_target_from_command(command: &str) -> Option<String> {
+    let patterns: &[(&str, &str)] = &[
+        (r"kubectl\s+(?:apply|rollout|set\s+image)\b", "kubernetes"),
+        (r"helm\s+(?:upgrade|install)\s+(?:--\S+\s+)*([A-Za-z0-9_-]+)", "helm"),
+        (r"terraform\s+apply\b", "terraform"),
+        (r"aws\s+s3\s+(?:sync|cp)\s+\S+\s+(s3://[A-Za-z0-9._/-]+)", "s3"),
+        (r"aws\s+ecs\s+update-service\b", "ecs"),
+        (r"aws\s+lambda\s+update-function-code\b", "lambda"),
+        (r"gcloud\s+run\s+deploy\s+([A-Za-z0-9_-]+)", "cloud-run"),
+        (r"gcloud\s+app\s+deploy\b", "app-engine"),
+        (r"az\s+webapp\s+(?:deploy|up)\b", "azure-webapp"),
+        (r"(?:serverless|sls)\s+deploy\b", "serverless"),
+        (r"vercel\b.*--prod\b", "vercel"),
+        (r"netlify\s+deploy\b", "netlify"),
+        (r"firebase\s+deploy\b", "firebase"),
+        (r"(?:flyctl|fly)\s+deploy\b", "fly"),
+        (r"git\s+push\s+heroku\b", "heroku"),
+        (r"docker\s+push\s+(\S+)", "registry"),
+    ];
+
+    for (pattern, target) in patterns {
+        if let Ok(re) = Regex::new(pattern) {
+            if let Some(cap) = re.captures(command) {
+                return Some(match cap.get(1) {
+                    Some(detail) => format!("{}:{}", target, detail.as_str()),
+                    None => target.to_string(),
+                });
+            }
+        }
+    }
+    None
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(items)) => items.iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `KEY: value` pairs of an `env:`/`variables:` block; GitLab's `{ value, description }` form included
+fn variable_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+    if let Some(map) = value.and_then(|v| v.as_object()) {
+        for (key, value) in map {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                Value::Object(_) => match value.get("value").and_then(|v| v.as_str()) {
+                    Some(s) => s.to_string(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            variables.insert(key.clone(), value);
+        }
+    }
+    variables
+}
+
+/// Split a multi-line `run`/`script` block into individual commands
+fn split_commands(script: &str) -> Vec<String> {
+    script.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.to_string())
+        .collect()
+}
+
+/// Parse a GitHub Actions workflow
+pub fn parse_github_actions(file_path: &str, config: &Value) -> CiPipeline {
+    let name = config.get("name")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| file_path.rsplit('/').next().unwrap_or(file_path).to_string());
+
+    // `on` is parsed as boolean `true` by YAML 1.1 parsers, so check both keys
+    let triggers = match config.get("on").or_else(|| config.get("true")) {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect(),
+        Some(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+
+    let workflow_env = variable_map(config.get("env"));
+    let mut jobs = Vec::new();
+    if let Some(job_map) = config.get("jobs").and_then(|v| v.as_object()) {
+        for (job_id, job_def) in job_map {
+            let mut job = CiJob::new(job_id);
+            job.needs = string_list(job_def.get("needs"));
+            job.variables = workflow_env.clone();
+            job.variables.extend(variable_map(job_def.get("env")));
+            job.runs_on = match job_def.get("runs-on") {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(Value::Array(items)) => Some(items.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(",")),
+                _ => None,
+            };
+            job.environment = match job_def.get("environment") {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(env) => env.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                None => None,
+            };
+            // Reusable workflow call
+            if let Some(uses) = job_def.get("uses").and_then(|v| v.as_str()) {
+                job.actions.push(uses.to_string());
+            }
+            if let Some(steps) = job_def.get("steps").and_then(|v| v.as_array()) {
+                for step in steps {
+                    if let Some(uses) = step.get("uses").and_then(|v| v.as_str()) {
+                        job.actions.push(uses.to_string());
+                    }
+                    if let Some(run) = step.get("run").and_then(|v| v.as_str()) {
+                        job.commands.extend(split_commands(run));
+                    }
+                }
+            }
+            job.classify();
+            jobs.push(job);
+        }
+    }
+
+    CiPipeline {
+        platform: "github".to_string(),
+        name,
+        file_path: file_path.to_string(),
+        triggers,
+        stages: Vec::new(),
+        jobs,
+    }
+}
+
+/// Parse a GitLab CI configuration
+pub fn parse_gitlab_ci(file_path: &str, config: &Value) -> CiPipeline {
+    const RESERVED: &[&str] = &["stages", "variables", "default", "include", "workflow", "image", "services", "cache", "before_script", "after_script"];
+
+    let stages = string_list(config.get("stages"));
+    let global_variables = variable_map(config.get("variables"));
+    let mut triggers = Vec::new();
+    let mut jobs = Vec::new();
+
+    if let Some(map) = config.as_object() {
+        for (key, job_def) in map {
+            // Hidden jobs (templates) start with a dot
+            if RESERVED.contains(&key.as_str()) || key.starts_with('.') || !job_def.is_object() {
+                continue;
+            }
+            if job_def.get("script").is_none() && job_def.get("trigger").is_none() && job_def.get("extends").is_none() {
+                continue;
+            }
+
+            let mut job = CiJob::new(key);
+            job.stage = Some(job_def.get("stage")
+                .and_then(|v| v.as_str())
+                .unwrap_or("test")
+                .to_string());
+            job.needs = match job_def.get("needs") {
+                Some(Value::Array(items)) => items.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()).or_else(|| v.get("job").and_then(|j| j.as_str()).map(|s| s.to_string())))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            job.runs_on = string_list(job_def.get("tags")).first().cloned();
+            job.variables = global_variables.clone();
+            job.variables.extend(variable_map(job_def.get("variables")));
+            job.environment = match job_def.get("environment") {
+                Some(Value::String(s)) => Some(s.clone()),
+                Some(env) => env.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                None => None,
+            };
+            match job_def.get("image") {
+                Some(Value::String(image)) => job.actions.push(image.clone()),
+                Some(image) => {
+                    if let Some(name) = image.get("name").and_then(|v| v.as_str()) {
+                        job.actions.push(name.to_string());
+                    }
+                }
+                None => {}
+            }
+            for script_key in ["before_script", "script", "after_script"] {
+                for line in string_list(job_def.get(script_key)) {
+                    job.commands.extend(split_commands(&line));
+                }
+            }
+            if let Some(rules) = job_def.get("rules").and_then(|v| v.as_array()) {
+                for rule in rules {
+                    if let Some(condition) = rule.get("if").and_then(|v| v.as_str()) {
+                        if condition.contains("merge_request") {
+                            push_unique(&mut triggers, "merge_request");
+                        }
+                        if condition.contains("schedule") {
+                            push_unique(&mut triggers, "schedule");
+                        }
+                        if condition.contains("CI_COMMIT_TAG") {
+                            push_unique(&mut triggers, "tag");
+                        }
+                    }
+                }
+            }
+            for only in string_list(job_def.get("only")) {
+                let trigger = match only.as_str() {
+                    "merge_requests" => "merge_request",
+                    "schedules" => "schedule",
+                    "tags" => "tag",
+                    _ => "push",
+                };
+                push_unique(&mut triggers, trigger);
+            }
+            job.classify();
+            jobs.push(job);
+        }
+    }
+
+    if triggers.is_empty() {
+        triggers.push("push".to_string());
+    }
+
+    CiPipeline {
+        platform: "gitlab".to_string(),
+        name: ".gitlab-ci.yml".to_string(),
+        file_path: file_path.to_string(),
+        triggers,
+        stages,
+        jobs,
+    }
+}
+
+/// Parse a declarative Jenkinsfile. Each stage becomes a job; `sh` steps become commands.
+pub fn parse_jenkinsfile(file_path: &str, content: &str) -> CiPipeline {
+    let stage_re = Regex::new(r#"stage\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap();
+    let sh_re = Regex::new(r#"sh\s*\(?\s*(?:script:\s*)?(?:'''|"""|'|")([^'"]+)"#).unwrap();
+
+    let mut triggers = Vec::new();
+    if content.contains("cron(") {
+        triggers.push("schedule".to_string());
+    }
+    if content.contains("pollSCM(") || content.contains("githubPush()") {
+        triggers.push("push".to_string());
+    }
+
+    let mut stages = Vec::new();
+    let mut jobs: Vec<CiJob> = Vec::new();
+    for line in content.lines() {
+        if let Some(cap) = stage_re.captures(line) {
+            let stage = cap[1].to_string();
+            let mut job = CiJob::new(&stage);
+            job.stage = Some(stage.clone());
+            // Declarative stages run sequentially
+            if let Some(previous) = jobs.last() {
+                job.needs.push(previous.name.clone());
+            }
+            stages.push(stage);
+            jobs.push(job);
+            continue;
+        }
+        if let Some(job) = jobs.last_mut() {
+            if let Some(cap) = sh_re.captures(line) {
+                job.commands.extend(split_commands(&cap[1]));
+            }
+        }
+    }
+    for job in &mut jobs {
+        job.classify();
+    }
+
+    CiPipeline {
+        platform: "jenkins".to_string(),
+        name: "Jenkinsfile".to_string(),
+        file_path: file_path.to_string(),
+        triggers,
+        stages,
+        jobs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_actions_jobs() {
+        let yaml = r#"
+name: CI
+on:
+  push:
+    branches: [main]
+  pull_request:
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+      - run: |
+          npm ci
+          npm test
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    environment: production
+    steps:
+      - uses: aws-actions/configure-aws-credentials@v4
+      - run: aws s3 sync ./dist s3://my-bucket
+"#;
+        let config: Value = serde_yaml::from_str(yaml).unwrap();
+        let pipeline = parse_github_actions(".github/workflows/ci.yml", &config);
+
+        assert_eq!(pipeline.name, "CI");
+        assert!(pipeline.triggers.contains(&"push".to_string()));
+        assert!(pipeline.triggers.contains(&"pull_request".to_string()));
+
+        let build = pipeline.jobs.iter().find(|j| j.name == "build").unwrap();
+        assert!(build.tools.contains(&"node".to_string()));
+        assert!(build.tools.contains(&"npm".to_string()));
+
+        let deploy = pipeline.jobs.iter().find(|j| j.name == "deploy").unwrap();
+        assert_eq!(deploy.needs, vec!["build".to_string()]);
+        assert_eq!(deploy.cloud_providers, vec!["aws".to_string()]);
+        assert!(deploy.deploy_targets.contains(&"s3:s3://my-bucket".to_string()));
+        assert!(deploy.deploy_targets.contains(&"environment:production".to_string()));
+    }
+
+    #[test]
+    fn test_parse_gitlab_ci_skips_templates() {
+        let yaml = r#"
+stages: [build, deploy]
+.template:
+  script: echo hidden
+build:
+  stage: build
+  script:
+    - cargo build --release
+deploy:
+  stage: deploy
+  needs: [build]
+  script:
+    - helm upgrade --install api ./chart
+"#;
+        let config: Value = serde_yaml::from_str(yaml).unwrap();
+        let pipeline = parse_gitlab_ci(".gitlab-ci.yml", &config);
+
+        assert_eq!(pipeline.stages, vec!["build".to_string(), "deploy".to_string()]);
+        assert_eq!(pipeline.jobs.len(), 2);
+        let deploy = pipeline.jobs.iter().find(|j| j.name == "deploy").unwrap();
+        assert_eq!(deploy.deploy_targets, vec!["helm:api".to_string()]);
+    }
+}
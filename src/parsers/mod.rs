@@ -2,8 +2,13 @@ use anyhow::Result;
 use serde_json::Value;
 use std::fs;
 use std::path::Path;
+use walkdir::WalkDir;
 use crate::ingestion::FileType;
 
+pub mod cicd;
+
+pub use cicd::{CiPipeline, CiJob};
+
 pub struct FileParser;
 
 impl FileParser {
@@ -85,7 +90,7 @@ impl FileParser {
         }
     }
 
-    /// Parse CI/CD configuration files
+    /// Parse CI/CD configuration files, modeling jobs, stages, triggers and deploy targets
     fn parse_cicd_file(&self, path: &Path, content: &str) -> Result<ParsedFile> {
         let path_str = path.to_string_lossy().to_lowercase();
         
@@ -106,13 +111,64 @@ impl FileParser {
         };
 
         let yaml: Value = serde_yaml::from_str(content).unwrap_or(Value::Null);
+
+        let file_path = path.to_string_lossy().to_string();
+        let pipeline = match platform {
+            "github" if yaml.is_object() => Some(cicd::parse_github_actions(&file_path, &yaml)),
+            "gitlab" if yaml.is_object() => Some(cicd::parse_gitlab_ci(&file_path, &yaml)),
+            "jenkins" => Some(cicd::parse_jenkinsfile(&file_path, content)),
+            _ => None,
+        };
         
         Ok(ParsedFile::CiCd {
             platform: platform.to_string(),
             config: yaml,
+            pipeline,
         })
     }
 
+    /// Find and parse all supported CI/CD pipelines in a repository.
+    /// File paths on the returned pipelines are relative to `repo_path`.
+    pub fn detect_pipelines(&self, repo_path: &Path) -> Result<Vec<CiPipeline>> {
+        let mut pipelines = Vec::new();
+
+        for entry in WalkDir::new(repo_path)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                name != "node_modules" && name != "target" && name != ".git" && name != "vendor"
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let relative = match path.strip_prefix(repo_path) {
+                Ok(p) => p,
+                Err(_) => continue,
+            };
+            let relative_str = relative.to_string_lossy().replace('\\', "/");
+            let file_name = relative.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+            let is_pipeline = (relative_str.starts_with(".github/workflows/")
+                    && (file_name.ends_with(".yml") || file_name.ends_with(".yaml")))
+                || file_name == ".gitlab-ci.yml"
+                || file_name == "Jenkinsfile";
+            if !is_pipeline {
+                continue;
+            }
+
+            let content = match fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            if let Ok(ParsedFile::CiCd { pipeline: Some(pipeline), .. }) = self.parse_cicd_file(relative, &content) {
+                pipelines.push(pipeline);
+            }
+        }
+
+        Ok(pipelines)
+    }
+
     /// Detect cloud provider from content
     fn detect_provider(&self, content: &str) -> String {
         let content_lower = content.to_lowercase();
@@ -197,6 +253,7 @@ pub enum ParsedFile {
     CiCd {
         platform: String,
         config: Value,
+        pipeline: Option<CiPipeline>,
     },
     Documentation {
         content: String,
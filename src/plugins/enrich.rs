@@ -0,0 +1,204 @@
+//! Hooks that run on a stored analysis. Plugins receive an [`AnalysisSummary`] and answer with
+//!
+//! - `post_analysis`: an [`Enrichment`], i.e. derived graph nodes and edges, and annotations on
+//!   existing nodes (an internal CMDB id on a service, an owning team on a module)
+//! - `report_section`: [`ReportSection`]s that the HTML report renders after the built-in sections
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::graph::graph::{EdgeType, GraphEdge, GraphNode, KnowledgeGraph, NodeType};
+use crate::storage::{StoredArchitectureFinding, StoredService};
+
+/// What plugins see of a completed analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    pub repository_id: String,
+    pub repository_name: String,
+    pub repo_path: String,
+    pub graph: KnowledgeGraph,
+    pub services: Vec<StoredService>,
+    pub findings: Vec<StoredArchitectureFinding>,
+}
+
+/// A node to add; `key` is the plugin's own handle for it, usable as an edge endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentNode {
+    pub key: String,
+    /// Free-form category, e.g. `"cmdb_ci"` or `"team"`
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+/// An edge between existing graph node ids and/or keys of added nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentEdge {
+    pub source: String,
+    pub target: String,
+    /// Free-form label, e.g. `"registered_as"`
+    pub relation: String,
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+/// A property to set on an existing node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub node_id: String,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Enrichment {
+    #[serde(default)]
+    pub nodes: Vec<EnrichmentNode>,
+    #[serde(default)]
+    pub edges: Vec<EnrichmentEdge>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// A named report section: JSON data, optionally laid out by a Markdown or HTML template whose
+/// `{{path.to.value}}` placeholders are filled from `data`. Without a template the data is shown as
+/// a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSection {
+    pub title: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub format: SectionFormat,
+}
+
+/// What applying an enrichment changed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnrichmentStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub annotations: usize,
+    pub skipped: usize,
+}
+
+impl Enrichment {
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty() && self.annotations.is_empty()
+    }
+
+    /// Merge into `graph`. Edges to unknown nodes and annotations that would overwrite a property
+    /// the decoder set are skipped, so a plugin can add to an analysis but not rewrite it.
+    pub fn apply(self, plugin: &str, repository_id: &str, graph: &mut KnowledgeGraph) -> EnrichmentStats {
+        let mut stats = EnrichmentStats::default();
+        let mut keys: HashMap<String, String> = HashMap::new();
+
+        for node in self.nodes {
+            if keys.contains_key(&node.key) {
+                stats.skipped += 1;
+                continue;
+            }
+            let id = Uuid::new_v4().to_string();
+            let mut properties = node.properties;
+            properties.insert("kind".to_string(), node.kind);
+            properties.insert("plugin".to_string(), plugin.to_string());
+            graph.nodes.push(GraphNode {
+                id: id.clone(),
+                node_type: NodeType::PluginEntity,
+                name: node.name,
+                properties,
+                repository_id: Some(repository_id.to_string()),
+            });
+            keys.insert(node.key, id);
+            stats.nodes += 1;
+        }
+
+        let resolve = |handle: &str, graph: &KnowledgeGraph| -> Option<String> {
+            keys.get(handle).cloned()
+                .or_else(|| graph.nodes.iter().any(|n| n.id == handle).then(|| handle.to_string()))
+        };
+        for edge in self.edges {
+            match (resolve(&edge.source, graph), resolve(&edge.target, graph)) {
+                (Some(source_node_id), Some(target_node_id)) => {
+                    let mut properties = edge.properties;
+                    properties.insert("relation".to_string(), edge.relation);
+                    properties.insert("plugin".to_string(), plugin.to_string());
+                    graph.edges.push(GraphEdge {
+                        id: Uuid::new_v4().to_string(),
+                        source_node_id,
+                        target_node_id,
+                        edge_type: EdgeType::RelatedTo,
+                        properties,
+                    });
+                    stats.edges += 1;
+                }
+                _ => stats.skipped += 1,
+            }
+        }
+
+        for annotation in self.annotations {
+            let target = keys.get(&annotation.node_id).unwrap_or(&annotation.node_id);
+            match graph.nodes.iter_mut().find(|n| &n.id == target) {
+                Some(node) if !node.properties.contains_key(&annotation.key) => {
+                    node.properties.insert(annotation.key, annotation.value);
+                    stats.annotations += 1;
+                }
+                _ => stats.skipped += 1,
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, node_type: NodeType, name: &str) -> GraphNode {
+        GraphNode {
+            id: id.to_string(),
+            node_type,
+            name: name.to_string(),
+            properties: HashMap::from([("type".to_string(), "service".to_string())]),
+            repository_id: Some("repo".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_apply_adds_nodes_edges_and_annotations() {
+        let mut graph = KnowledgeGraph {
+            nodes: vec![node("svc-1", NodeType::Service, "Stripe")],
+            edges: Vec::new(),
+        };
+        let enrichment: Enrichment = serde_json::from_str(r#"{
+            "nodes": [{"key": "ci", "kind": "cmdb_ci", "name": "CI0042 Payments"}],
+            "edges": [
+                {"source": "svc-1", "target": "ci", "relation": "registered_as"},
+                {"source": "svc-1", "target": "missing", "relation": "registered_as"}
+            ],
+            "annotations": [
+                {"node_id": "svc-1", "key": "cmdb_id", "value": "CI0042"},
+                {"node_id": "svc-1", "key": "type", "value": "overwritten"}
+            ]
+        }"#).unwrap();
+
+        let stats = enrichment.apply("cmdb", "repo", &mut graph);
+        assert_eq!((stats.nodes, stats.edges, stats.annotations, stats.skipped), (1, 1, 1, 2));
+        assert_eq!(graph.nodes[1].node_type, NodeType::PluginEntity);
+        assert_eq!(graph.nodes[1].properties["plugin"], "cmdb");
+        assert_eq!(graph.edges[0].target_node_id, graph.nodes[1].id);
+        assert_eq!(graph.nodes[0].properties["cmdb_id"], "CI0042");
+        assert_eq!(graph.nodes[0].properties["type"], "service");
+    }
+}
@@ -0,0 +1,321 @@
+//! Installing and updating plugins from a URL or a git repository.
+//!
+//! Only plugins that run sandboxed can be installed remotely: pattern files, manifests with a
+//! `patterns` section and WASM analyzers. Every install is pinned — a SHA-256 for URL downloads,
+//! a full commit id for git — and may additionally be signed with a trusted Ed25519 key. The bundle
+//! is validated in a staging directory and only then moved into `config/plugins`, which every
+//! analysis reads afresh, so an install takes effect without a restart.
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+use crate::config::PluginsConfig;
+use crate::plugins::{LoadedPlugin, PluginManifest, PluginRegistry, PluginRuntime, WasmAnalyzer};
+
+const MAX_DOWNLOAD_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallRequest {
+    /// HTTP(S) URL of a pattern file, a patterns-only manifest or a `.wasm` module
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Git repository holding a `plugin.json`
+    #[serde(default)]
+    pub git: Option<String>,
+    /// Full commit id to install from `git`
+    #[serde(default)]
+    pub rev: Option<String>,
+    /// Directory of the plugin inside the git repository
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Plugin name for URL installs; defaults to the manifest name or the file name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Hex SHA-256 of the downloaded file; required for `url`
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// Base64 Ed25519 signature of the downloaded file, or of the commit id for git installs
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// A plugin ready to be placed under the plugin directory
+#[derive(Debug, Clone)]
+pub enum Bundle {
+    /// `config/plugins/<file_name>`
+    File { file_name: String, bytes: Vec<u8> },
+    /// `config/plugins/<name>/` with `plugin.json` and the files it references
+    Directory { name: String, files: Vec<(PathBuf, Vec<u8>)> },
+}
+
+impl Bundle {
+    fn target(&self, plugin_dir: &Path) -> PathBuf {
+        match self {
+            Bundle::File { file_name, .. } => plugin_dir.join(file_name),
+            Bundle::Directory { name, .. } => plugin_dir.join(name),
+        }
+    }
+
+    fn write_to(&self, dir: &Path) -> Result<PathBuf> {
+        let target = self.target(dir);
+        match self {
+            Bundle::File { bytes, .. } => std::fs::write(&target, bytes)?,
+            Bundle::Directory { files, .. } => {
+                for (relative, bytes) in files {
+                    let path = target.join(relative);
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(path, bytes)?;
+                }
+            }
+        }
+        Ok(target)
+    }
+}
+
+pub async fn download(url: &str) -> Result<Vec<u8>> {
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(anyhow!("only http(s) URLs can be installed"));
+    }
+    crate::network::ensure_online("Downloading plugins")?;
+    let client = crate::network::http_client(Duration::from_secs(60))?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("download failed with status: {}", response.status()));
+    }
+    let bytes = response.bytes().await?;
+    if bytes.len() > MAX_DOWNLOAD_BYTES {
+        return Err(anyhow!("plugin is larger than {} bytes", MAX_DOWNLOAD_BYTES));
+    }
+    Ok(bytes.to_vec())
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Check the pinned checksum (when given) and the signature against the trusted keys
+pub fn verify(payload: &[u8], sha256: Option<&str>, signature: Option<&str>, config: &PluginsConfig) -> Result<()> {
+    if let Some(expected) = sha256 {
+        let actual = sha256_hex(payload);
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(anyhow!("checksum mismatch: expected {}, got {}", expected.trim(), actual));
+        }
+    }
+
+    let signature = match signature {
+        Some(signature) => signature,
+        None if config.require_signature => return Err(anyhow!("a signature is required to install plugins")),
+        None => return Ok(()),
+    };
+    let engine = base64::engine::general_purpose::STANDARD;
+    let signature_bytes: [u8; 64] = engine.decode(signature.trim())?
+        .try_into()
+        .map_err(|_| anyhow!("signature must be 64 bytes"))?;
+    let signature = Signature::from_bytes(&signature_bytes);
+    for key in &config.trusted_keys {
+        let key_bytes: [u8; 32] = match engine.decode(key.trim()).ok().and_then(|k| k.try_into().ok()) {
+            Some(bytes) => bytes,
+            None => {
+                log::warn!("⚠ Ignoring malformed trusted plugin key");
+                continue;
+            }
+        };
+        if let Ok(key) = VerifyingKey::from_bytes(&key_bytes) {
+            if key.verify(payload, &signature).is_ok() {
+                return Ok(());
+            }
+        }
+    }
+    Err(anyhow!("signature does not match any trusted key"))
+}
+
+/// Work out what a downloaded file is and where it goes
+pub fn bundle_from_bytes(bytes: Vec<u8>, name_hint: &str) -> Result<Bundle> {
+    let name = sanitize_name(name_hint)?;
+    if bytes.starts_with(b"\0asm") {
+        return Ok(Bundle::File { file_name: format!("{}.wasm", name), bytes });
+    }
+
+    let value: serde_json::Value = serde_json::from_slice(&bytes).context("expected a .wasm module or a JSON plugin")?;
+    if value.get("manifest_version").is_some() {
+        let manifest: PluginManifest = serde_json::from_value(value)?;
+        if manifest.wasm.is_some() || manifest.process.is_some() {
+            return Err(anyhow!("manifests that reference other files must be installed from git"));
+        }
+        return Ok(Bundle::File { file_name: format!("{}.json", sanitize_name(&manifest.name)?), bytes });
+    }
+    Ok(Bundle::File { file_name: format!("{}.json", name), bytes })
+}
+
+/// Check out `rev` of a git repository and collect its manifest plus the WASM module it names
+pub fn bundle_from_git(url: &str, rev: &str, path: Option<&str>) -> Result<Bundle> {
+    if !["https://", "http://", "ssh://", "git@"].iter().any(|scheme| url.starts_with(scheme)) {
+        return Err(anyhow!("git installs need a remote https or ssh URL"));
+    }
+    if rev.len() != 40 || !rev.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow!("rev must be a full 40-character commit id"));
+    }
+    crate::network::ensure_online("Installing plugins from git")?;
+    let checkout = std::env::temp_dir().join(format!("wavelength-plugin-{}", Uuid::new_v4()));
+    let result = (|| {
+        let repo = git2::Repository::clone(url, &checkout).with_context(|| format!("cloning {}", url))?;
+        let commit = repo.find_commit(git2::Oid::from_str(rev)?).context("commit not found in repository")?;
+        repo.checkout_tree(commit.as_object(), Some(git2::build::CheckoutBuilder::new().force()))?;
+
+        let root = match path {
+            Some(path) if path.split('/').any(|part| part == "..") => return Err(anyhow!("path may not leave the repository")),
+            Some(path) => checkout.join(path.trim_matches('/')),
+            None => checkout.clone(),
+        };
+        let manifest_bytes = std::fs::read(root.join("plugin.json")).context("no plugin.json in the repository")?;
+        let manifest: PluginManifest = serde_json::from_slice(&manifest_bytes).context("invalid plugin.json")?;
+        if manifest.process.is_some() {
+            return Err(anyhow!("subprocess plugins cannot be installed remotely"));
+        }
+
+        let mut files = vec![(PathBuf::from("plugin.json"), manifest_bytes)];
+        if let Some(wasm) = &manifest.wasm {
+            if wasm.is_absolute() || wasm.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+                return Err(anyhow!("wasm module must live inside the plugin directory"));
+            }
+            let bytes = std::fs::read(root.join(wasm)).with_context(|| format!("reading {}", wasm.display()))?;
+            files.push((wasm.clone(), bytes));
+        }
+        Ok(Bundle::Directory { name: sanitize_name(&manifest.name)?, files })
+    })();
+    let _ = std::fs::remove_dir_all(&checkout);
+    result
+}
+
+/// Validate the bundle in a staging directory, then move it into place, replacing any plugin of
+/// the same name. Returns the plugin as the registry now sees it.
+pub fn install_bundle(bundle: &Bundle, plugin_dir: &Path) -> Result<LoadedPlugin> {
+    std::fs::create_dir_all(plugin_dir)?;
+    let staging = plugin_dir.join(format!(".staging-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&staging)?;
+    let result = stage_and_install(bundle, plugin_dir, &staging);
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+fn stage_and_install(bundle: &Bundle, plugin_dir: &Path, staging: &Path) -> Result<LoadedPlugin> {
+    let staged_path = bundle.write_to(staging)?;
+    let staged = PluginRegistry::load(staging).plugins.into_iter()
+        .find(|p| p.source.starts_with(staging))
+        .ok_or_else(|| anyhow!("bundle does not contain a plugin"))?;
+    if !staged.is_valid() {
+        return Err(anyhow!("invalid plugin: {}", staged.errors.join("; ")));
+    }
+    match &staged.runtime {
+        PluginRuntime::Wasm { module } => {
+            WasmAnalyzer::load(module).context("WASM module failed to load")?;
+        }
+        PluginRuntime::None => {}
+        _ => return Err(anyhow!("only pattern and WASM plugins can be installed remotely")),
+    }
+
+    let name = staged.manifest.name.clone();
+    let existing = PluginRegistry::load(plugin_dir).plugins.into_iter().find(|p| p.manifest.name == name);
+    if let Some(existing) = &existing {
+        if !existing.source.starts_with(plugin_dir) {
+            return Err(anyhow!("'{}' is the name of a built-in plugin", name));
+        }
+    }
+
+    // Move the old version aside so a failed swap can be undone
+    let target = bundle.target(plugin_dir);
+    let old_paths: Vec<PathBuf> = existing.iter()
+        .map(|p| if p.source.file_name().map(|f| f == "plugin.json").unwrap_or(false) {
+            p.source.parent().unwrap_or(plugin_dir).to_path_buf()
+        } else {
+            p.source.clone()
+        })
+        .chain(std::iter::once(target.clone()).filter(|t| t.exists()))
+        .collect();
+    let mut backups = Vec::new();
+    for old in old_paths {
+        if backups.iter().any(|(o, _): &(PathBuf, PathBuf)| *o == old) {
+            continue;
+        }
+        let backup = staging.join(format!("previous-{}", backups.len()));
+        std::fs::rename(&old, &backup)?;
+        backups.push((old, backup));
+    }
+
+    if let Err(e) = std::fs::rename(&staged_path, &target) {
+        for (old, backup) in &backups {
+            let _ = std::fs::rename(backup, old);
+        }
+        return Err(e.into());
+    }
+
+    let installed = PluginRegistry::load(plugin_dir).plugins.into_iter()
+        .find(|p| p.manifest.name == name && p.source.starts_with(&target))
+        .ok_or_else(|| anyhow!("installed plugin was not picked up"))?;
+    log::info!("✓ Installed plugin '{}' {} at {}{}", name, installed.manifest.version, target.display(),
+        if backups.is_empty() { "" } else { " (replacing the previous version)" });
+    Ok(installed)
+}
+
+fn sanitize_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() || name.starts_with('.') || !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+        return Err(anyhow!("invalid plugin name '{}'", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Name for a URL install: the request's, else the last path segment without its extension
+pub fn name_from_url(url: &str) -> String {
+    let segment = url.split(['?', '#']).next().unwrap_or(url).trim_end_matches('/').rsplit('/').next().unwrap_or("plugin");
+    Path::new(segment).file_stem().and_then(|s| s.to_str()).unwrap_or("plugin").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::fs;
+    use tempfile::TempDir;
+
+    const PATTERNS: &str = r#"{"version": "1.0", "patterns": {"environment_variables": [{"pattern": "ACME_QUEUE_URL", "provider": "Unknown", "service_type": "Other"}]}}"#;
+
+    #[test]
+    fn test_verifies_and_replaces_pattern_plugin() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let engine = base64::engine::general_purpose::STANDARD;
+        let config = PluginsConfig {
+            trusted_keys: vec![engine.encode(signing_key.verifying_key().to_bytes())],
+            require_signature: true,
+        };
+        let payload = PATTERNS.as_bytes();
+        let signature = engine.encode(signing_key.sign(payload).to_bytes());
+
+        verify(payload, Some(&sha256_hex(payload)), Some(&signature), &config).unwrap();
+        assert!(verify(payload, Some(&sha256_hex(b"other")), Some(&signature), &config).is_err());
+        assert!(verify(payload, Some(&sha256_hex(payload)), None, &config).is_err());
+        assert!(verify(b"tampered", None, Some(&signature), &config).is_err());
+
+        let plugin_dir = TempDir::new().unwrap();
+        fs::write(plugin_dir.path().join("acme.json"), r#"{"version": "0.9", "patterns": {}}"#).unwrap();
+        let bundle = bundle_from_bytes(payload.to_vec(), &name_from_url("https://plugins.example.com/acme.json?dl=1")).unwrap();
+        let installed = install_bundle(&bundle, plugin_dir.path()).unwrap();
+        assert_eq!(installed.manifest.name, "acme");
+        assert_eq!(installed.manifest.version, "1.0");
+
+        let names: Vec<_> = fs::read_dir(plugin_dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(names, vec!["acme.json"]);
+
+        // An invalid update is rejected and leaves the installed version alone
+        let broken = br#"{"manifest_version": 1, "name": "acme", "version": "2.0.0", "hooks": []}"#;
+        assert!(install_bundle(&bundle_from_bytes(broken.to_vec(), "acme").unwrap(), plugin_dir.path()).is_err());
+        assert_eq!(fs::read_to_string(plugin_dir.path().join("acme.json")).unwrap(), PATTERNS);
+    }
+}
@@ -0,0 +1,52 @@
+//! Python detector packages written for the original bridge, which imported the package and printed
+//! its `decoder_format` JSON. They are now launched through `sdk/python/wavelength_plugin.py
+//! --legacy`, which speaks the [`crate::plugins::runner`] protocol on their behalf.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::plugins::runner::PluginCommand;
+
+/// Python adapter for legacy detectors, relative to the server's working directory like `config/`
+const PYTHON_SDK: &str = "sdk/python/wavelength_plugin.py";
+
+/// Known legacy packages: (Python module, detector class)
+const LEGACY_PLUGINS: &[(&str, &str)] = &[("webm_asset_plugin", "WebMethodsAssetDetector")];
+
+/// Runner commands for the legacy plugins checked out next to the decoder
+pub fn discover() -> Vec<PluginCommand> {
+    let sdk = Path::new(PYTHON_SDK);
+    let sdk = match sdk.canonicalize() {
+        Ok(path) => path,
+        Err(_) => {
+            log::debug!("Python plugin SDK not found at {}", PYTHON_SDK);
+            return Vec::new();
+        }
+    };
+
+    LEGACY_PLUGINS.iter().filter_map(|(module, detector_class)| {
+        let plugin_dir = find_checkout(module)?;
+        log::info!("Using legacy plugin '{}' at: {}", module, plugin_dir.display());
+        Some(PluginCommand {
+            name: module.to_string(),
+            command: vec![
+                "python3".to_string(),
+                sdk.to_string_lossy().to_string(),
+                "--legacy".to_string(),
+                format!("{}.detector:{}", module, detector_class),
+            ],
+            env: HashMap::from([("PYTHONPATH".to_string(), plugin_dir.to_string_lossy().to_string())]),
+            working_dir: Some(plugin_dir),
+            timeout_secs: 600,
+            idle_timeout_secs: 600, // The wrapped detector reports no progress of its own
+        })
+    }).collect()
+}
+
+/// `wavelength-arch-decoder-<name>-plugin` checkouts in the current directory or one or two levels up
+fn find_checkout(module: &str) -> Option<PathBuf> {
+    let repo_name = format!("wavelength-arch-decoder-{}-plugin", module.replace('_', "-"));
+    ["", "../", "../../"].iter()
+        .map(|prefix| PathBuf::from(format!("{}{}", prefix, repo_name)))
+        .find(|path| path.join(module).exists())
+        .map(|path| path.canonicalize().unwrap_or(path))
+}
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::plugins::runner::PluginCommand;
+use crate::security::pattern_config::{PatternConfig, PatternSet};
+
+/// Newest manifest format this build understands
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Points in an analysis where a plugin can take part
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHook {
+    /// Extra service detection patterns, merged into `config/service_patterns.json`
+    Patterns,
+    /// Sees repository files and reports elements, relationships, services and findings
+    FileAnalyzer,
+    /// Runs after the analysis is stored and may add to it
+    PostAnalysis,
+    /// Contributes a section to the HTML report
+    ReportSection,
+}
+
+impl PluginHook {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluginHook::Patterns => "patterns",
+            PluginHook::FileAnalyzer => "file_analyzer",
+            PluginHook::PostAnalysis => "post_analysis",
+            PluginHook::ReportSection => "report_section",
+        }
+    }
+}
+
+/// How to start a subprocess plugin; see [`crate::plugins::runner`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessSpec {
+    /// Program and arguments, e.g. `["python3", "main.py"]`
+    pub command: Vec<String>,
+    /// Relative paths resolve against the manifest's directory, which is also the default
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    600
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    120
+}
+
+/// `plugin.json`: what a plugin is, which hooks it implements and how to run it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub manifest_version: u32,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Oldest decoder release the plugin works with
+    #[serde(default)]
+    pub min_decoder_version: Option<String>,
+    pub hooks: Vec<PluginHook>,
+    #[serde(default)]
+    pub patterns: Option<PatternSet>,
+    /// WASM module implementing `file_analyzer`, relative to the manifest
+    #[serde(default)]
+    pub wasm: Option<PathBuf>,
+    #[serde(default)]
+    pub process: Option<ProcessSpec>,
+    /// Niche plugins set this to false and only run for repositories that opt in
+    #[serde(default = "default_enabled")]
+    pub default_enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl PluginManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Manifest for a plugin found without one
+    pub fn bare(name: &str, version: &str, hooks: Vec<PluginHook>) -> Self {
+        PluginManifest {
+            manifest_version: MANIFEST_VERSION,
+            name: name.to_string(),
+            version: version.to_string(),
+            description: None,
+            min_decoder_version: None,
+            hooks,
+            patterns: None,
+            wasm: None,
+            process: None,
+            default_enabled: true,
+        }
+    }
+
+    /// A pre-manifest pattern file (`{"version", "patterns"}`) is a plugin with only the patterns hook
+    pub fn from_pattern_config(name: &str, config: PatternConfig) -> Self {
+        let mut manifest = Self::bare(name, &config.version, vec![PluginHook::Patterns]);
+        manifest.patterns = Some(config.patterns);
+        manifest
+    }
+
+    /// A `.wasm` file on its own is a file analyzer named after the file
+    pub fn for_wasm_module(path: &Path) -> Self {
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("wasm-plugin");
+        let mut manifest = Self::bare(name, "0.0.0", vec![PluginHook::FileAnalyzer]);
+        manifest.wasm = path.file_name().map(PathBuf::from);
+        manifest
+    }
+
+    pub fn implements(&self, hook: PluginHook) -> bool {
+        self.hooks.contains(&hook)
+    }
+
+    /// Problems that keep the plugin from loading; `base_dir` is the manifest's directory
+    pub fn validate(&self, base_dir: &Path) -> Vec<String> {
+        let mut errors = self.validate_metadata();
+        if self.wasm.is_some() && self.process.is_some() {
+            errors.push("declare either `wasm` or `process`, not both".to_string());
+        }
+        if let Some(wasm) = &self.wasm {
+            if !base_dir.join(wasm).is_file() {
+                errors.push(format!("wasm module {} not found", wasm.display()));
+            }
+        }
+        if let Some(process) = &self.process {
+            if process.command.is_empty() {
+                errors.push("process.command is empty".to_string());
+            }
+        }
+        for hook in &self.hooks {
+            let backed = match hook {
+                PluginHook::Patterns => self.patterns.is_some(),
+                PluginHook::FileAnalyzer => self.wasm.is_some() || self.process.is_some(),
+                // Only subprocess plugins can take part after analysis
+                PluginHook::PostAnalysis | PluginHook::ReportSection => self.process.is_some(),
+            };
+            if !backed {
+                let needs = match hook {
+                    PluginHook::Patterns => "a `patterns` section",
+                    PluginHook::FileAnalyzer => "`wasm` or `process`",
+                    PluginHook::PostAnalysis | PluginHook::ReportSection => "`process`",
+                };
+                errors.push(format!("hook {} needs {}", hook.as_str(), needs));
+            }
+        }
+        if self.patterns.is_some() && !self.implements(PluginHook::Patterns) {
+            errors.push("has a `patterns` section but does not declare the patterns hook".to_string());
+        }
+
+        errors
+    }
+
+    /// Name, version and compatibility checks that apply however the plugin runs
+    pub fn validate_metadata(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.manifest_version == 0 || self.manifest_version > MANIFEST_VERSION {
+            errors.push(format!("unsupported manifest_version {} (this decoder reads up to {})", self.manifest_version, MANIFEST_VERSION));
+        }
+        if self.name.is_empty() || !self.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            errors.push(format!("invalid name '{}': use letters, digits, '-', '_' or '.'", self.name));
+        }
+        if parse_version(&self.version).is_none() {
+            errors.push(format!("invalid version '{}': expected MAJOR.MINOR[.PATCH]", self.version));
+        }
+        if let Some(required) = &self.min_decoder_version {
+            match (parse_version(required), parse_version(env!("CARGO_PKG_VERSION"))) {
+                (Some(required_version), Some(current)) if required_version > current => {
+                    errors.push(format!("requires decoder {} or newer (running {})", required, env!("CARGO_PKG_VERSION")));
+                }
+                (None, _) => errors.push(format!("invalid min_decoder_version '{}'", required)),
+                _ => {}
+            }
+        }
+        if self.hooks.is_empty() {
+            errors.push("declares no hooks".to_string());
+        }
+        errors
+    }
+
+    pub fn process_command(&self, base_dir: &Path) -> Option<PluginCommand> {
+        let process = self.process.as_ref()?;
+        Some(PluginCommand {
+            name: self.name.clone(),
+            command: process.command.clone(),
+            working_dir: Some(match &process.working_dir {
+                Some(dir) if dir.is_relative() => base_dir.join(dir),
+                Some(dir) => dir.clone(),
+                None => base_dir.to_path_buf(),
+            }),
+            env: process.env.clone(),
+            timeout_secs: process.timeout_secs,
+            idle_timeout_secs: process.idle_timeout_secs,
+        })
+    }
+}
+
+/// `1.2.3` or `1.2` (optionally `v`-prefixed, pre-release suffix ignored) as comparable numbers;
+/// pattern files have always been versioned `1.0`
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim_start_matches('v').split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.ok()?,
+        None => 0,
+    };
+    parts.next().is_none().then_some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validates_hooks_against_runtimes() {
+        let manifest: PluginManifest = serde_json::from_str(r#"{
+            "manifest_version": 1,
+            "name": "terraform-modules",
+            "version": "0.3.0",
+            "hooks": ["file_analyzer", "report_section"],
+            "process": {"command": ["python3", "main.py"]}
+        }"#).unwrap();
+        assert!(manifest.validate(Path::new(".")).is_empty());
+        let command = manifest.process_command(Path::new("config/plugins/terraform")).unwrap();
+        assert_eq!(command.working_dir, Some(PathBuf::from("config/plugins/terraform")));
+        assert_eq!(command.timeout_secs, 600);
+
+        let broken: PluginManifest = serde_json::from_str(r#"{
+            "manifest_version": 2,
+            "name": "bad name",
+            "version": "1.x",
+            "min_decoder_version": "99.0.0",
+            "hooks": ["patterns", "post_analysis"]
+        }"#).unwrap();
+        let errors = broken.validate(Path::new("."));
+        assert_eq!(errors.len(), 6, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.contains("hook post_analysis needs `process`")));
+    }
+}
@@ -0,0 +1,316 @@
+pub mod manifest;
+pub mod registry;
+pub mod native;
+pub mod install;
+pub mod enrich;
+pub mod watch;
+pub mod wasm;
+pub mod runner;
+pub mod legacy_python;
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use walkdir::WalkDir;
+use std::collections::HashMap;
+use crate::analysis::{ArchitectureFinding, CodeElement, CodeRelationship, DetectedEndpoint, FindingSeverity, RepoPath};
+use crate::analysis::utils::should_skip_file;
+use crate::security::{DetectedService, EvidenceKind, ServiceDetector, ServiceEvidence, ServiceProvider};
+
+pub use manifest::{PluginHook, PluginManifest};
+pub use registry::{LoadedPlugin, PluginRegistry, PluginRuntime, PluginSelection};
+pub use watch::SharedRegistry;
+pub use native::{builtin_analyzers, register_analyzer, registered_analyzers, unregister_analyzer, AnalyzerPlugin, AnalyzerRegistry};
+pub use wasm::WasmAnalyzer;
+pub use runner::{PluginCommand, Progress, RpcPlugin};
+pub use enrich::{AnalysisSummary, Annotation, Enrichment, EnrichmentEdge, EnrichmentNode, ReportSection, SectionFormat};
+
+/// Files larger than this are not sent to plugins
+pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// What an analyzer plugin declares about itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzerInfo {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Extensions (without the dot) the plugin wants to see; empty means every text file
+    #[serde(default)]
+    pub file_extensions: Vec<String>,
+}
+
+/// One repository file handed to a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEvent {
+    pub path: String, // Repository-relative
+    pub language: Option<String>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginService {
+    pub name: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub service_type: Option<String>,
+    pub file_path: String,
+    #[serde(default)]
+    pub line_number: Option<usize>,
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginFinding {
+    pub rule: String,
+    #[serde(default)]
+    pub severity: Option<String>, // "low" | "medium" | "high"
+    pub message: String,
+    pub file_path: String,
+    #[serde(default)]
+    pub line_number: Option<usize>,
+}
+
+fn default_confidence() -> f64 {
+    0.7
+}
+
+impl PluginService {
+    /// Provider and type strings use the same names as the pattern config (`"Stripe"`, `"Database"`)
+    pub fn to_detected(&self, detector: &ServiceDetector, repo_path: &Path) -> DetectedService {
+        let mut configuration = HashMap::new();
+        configuration.insert("detection_method".to_string(), "plugin".to_string());
+        let file_path = RepoPath::new(repo_path, Path::new(&self.file_path)).into_string();
+        let confidence = self.confidence.clamp(0.0, 1.0);
+        DetectedService {
+            provider: detector.parse_provider(self.provider.as_deref().unwrap_or(&self.name)).unwrap_or(ServiceProvider::Unknown),
+            service_type: detector.parse_service_type(self.service_type.as_deref().unwrap_or("Other")),
+            name: self.name.clone(),
+            configuration,
+            evidence: vec![ServiceEvidence {
+                kind: EvidenceKind::Plugin,
+                file_path: file_path.clone(),
+                line_number: self.line_number,
+                confidence,
+            }],
+            file_path,
+            line_number: self.line_number,
+            confidence,
+        }
+    }
+}
+
+impl PluginFinding {
+    pub fn to_finding(&self) -> ArchitectureFinding {
+        ArchitectureFinding {
+            rule: self.rule.clone(),
+            severity: match self.severity.as_deref().map(|s| s.to_lowercase()).as_deref() {
+                Some("high") => FindingSeverity::High,
+                Some("low") => FindingSeverity::Low,
+                _ => FindingSeverity::Medium,
+            },
+            source_file: self.file_path.clone(),
+            source_layer: None,
+            target_file: self.file_path.clone(),
+            target_layer: None,
+            line_number: self.line_number.unwrap_or(0),
+            message: self.message.clone(),
+        }
+    }
+}
+
+/// Everything a plugin reports; every field is optional in the plugin's JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnalyzerOutput {
+    #[serde(default)]
+    pub elements: Vec<CodeElement>,
+    #[serde(default)]
+    pub relationships: Vec<CodeRelationship>,
+    #[serde(default)]
+    pub services: Vec<PluginService>,
+    #[serde(default)]
+    pub findings: Vec<PluginFinding>,
+    /// Services with their evidence, as the built-in service detector reports them
+    #[serde(default)]
+    pub detected_services: Vec<DetectedService>,
+    #[serde(default)]
+    pub endpoints: Vec<DetectedEndpoint>,
+}
+
+impl AnalyzerOutput {
+    pub fn merge(&mut self, other: AnalyzerOutput) {
+        self.elements.extend(other.elements);
+        self.relationships.extend(other.relationships);
+        self.services.extend(other.services);
+        self.findings.extend(other.findings);
+        self.detected_services.extend(other.detected_services);
+        self.endpoints.extend(other.endpoints);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.relationships.is_empty() && self.services.is_empty() && self.findings.is_empty()
+            && self.detected_services.is_empty() && self.endpoints.is_empty()
+    }
+}
+
+/// Repository files a plugin asked for, read lazily so only one file is in memory at a time
+pub fn file_events<'a>(repo_path: &'a Path, extensions: &'a [String]) -> impl Iterator<Item = FileEvent> + 'a {
+    WalkDir::new(repo_path)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(move |e| {
+            let path_str = e.path().to_string_lossy();
+            let file_name = e.file_name().to_string_lossy();
+            let wanted = extensions.is_empty() || e.path().extension()
+                .and_then(|x| x.to_str())
+                .map(|x| extensions.iter().any(|w| w.eq_ignore_ascii_case(x)))
+                .unwrap_or(false);
+            wanted && !should_skip_file(&file_name, &path_str)
+                && e.metadata().map(|m| m.len() <= MAX_FILE_BYTES).unwrap_or(false)
+        })
+        .filter_map(move |e| {
+            // Binary files fail UTF-8 decoding and are skipped
+            let content = std::fs::read_to_string(e.path()).ok()?;
+            let path = e.path().strip_prefix(repo_path).ok()?;
+            let path = RepoPath::from_relative(&path.to_string_lossy()).into_string();
+            Some(FileEvent {
+                language: crate::analysis::utils::detect_language(e.path()),
+                path,
+                content,
+            })
+        })
+}
+
+/// Run every WASM file analyzer over the repository; a failing plugin is logged and skipped
+pub fn run_wasm_analyzers(registry: &PluginRegistry, repo_path: &Path) -> AnalyzerOutput {
+    let mut output = AnalyzerOutput::default();
+    for plugin in registry.with_hook(PluginHook::FileAnalyzer) {
+        let path = match &plugin.runtime {
+            PluginRuntime::Wasm { module } => module,
+            _ => continue,
+        };
+        let result = WasmAnalyzer::load(path).and_then(|analyzer| {
+            let info = analyzer.info().clone();
+            analyzer.analyze(repo_path).map(|out| (info, out))
+        });
+        match result {
+            Ok((info, out)) => {
+                log::info!("✓ WASM plugin '{}' reported {} elements, {} relationships, {} services, {} findings",
+                    info.name, out.elements.len(), out.relationships.len(), out.services.len(), out.findings.len());
+                output.merge(out);
+            }
+            Err(e) => log::warn!("⚠ WASM plugin {} failed: {}", path.display(), e),
+        }
+    }
+    output
+}
+
+/// Run every registered native analyzer; a failing analyzer is logged and skipped
+pub fn run_native_analyzers(registry: &PluginRegistry, repo_path: &Path) -> AnalyzerOutput {
+    let mut output = AnalyzerOutput::default();
+    for plugin in registry.with_hook(PluginHook::FileAnalyzer) {
+        if !matches!(plugin.runtime, PluginRuntime::Native) {
+            continue;
+        }
+        let analyzer = match registry.native.find(&plugin.manifest.name) {
+            Some(analyzer) => analyzer,
+            None => continue,
+        };
+        match analyzer.analyze(repo_path) {
+            Ok(out) => {
+                log::info!("✓ Analyzer '{}' reported {} elements, {} relationships, {} services, {} findings",
+                    analyzer.name(), out.elements.len(), out.relationships.len(), out.services.len(), out.findings.len());
+                output.merge(out);
+            }
+            Err(e) => log::warn!("⚠ Analyzer '{}' failed: {:#}", analyzer.name(), e),
+        }
+    }
+    output
+}
+
+/// Run every subprocess file analyzer over the repository; `on_progress` receives the plugin name
+/// with each progress notification
+pub fn run_subprocess_plugins(registry: &PluginRegistry, repo_path: &Path, on_progress: &mut dyn FnMut(&str, &Progress)) -> AnalyzerOutput {
+    let mut output = AnalyzerOutput::default();
+    for plugin in registry.with_hook(PluginHook::FileAnalyzer) {
+        let command = match &plugin.runtime {
+            PluginRuntime::Process { command } => command,
+            _ => continue,
+        };
+        match runner::run(command, repo_path, &mut |p| on_progress(&command.name, p)) {
+            Ok(out) => {
+                log::info!("✓ Plugin '{}' reported {} elements, {} relationships, {} services, {} findings",
+                    command.name, out.elements.len(), out.relationships.len(), out.services.len(), out.findings.len());
+                output.merge(out);
+            }
+            Err(e) => log::warn!("⚠ Plugin '{}' failed: {:#}", command.name, e),
+        }
+    }
+    output
+}
+
+/// Offer a stored analysis to every `post_analysis` plugin, native and subprocess, and merge what
+/// they add into `graph`; returns whether anything changed
+pub fn run_post_analysis(
+    registry: &PluginRegistry,
+    summary: &AnalysisSummary,
+    graph: &mut crate::graph::graph::KnowledgeGraph,
+    on_progress: &mut dyn FnMut(&str, &Progress),
+) -> bool {
+    let mut changed = false;
+    for plugin in registry.with_hook(PluginHook::PostAnalysis) {
+        let name = plugin.manifest.name.as_str();
+        let result = match &plugin.runtime {
+            PluginRuntime::Native => match registry.native.find(name) {
+                Some(analyzer) => analyzer.enrich(summary),
+                None => continue,
+            },
+            PluginRuntime::Process { command } => runner::run_enrich(command, summary, &mut |p| on_progress(name, p)),
+            _ => continue,
+        };
+        match result {
+            Ok(enrichment) if enrichment.is_empty() => {}
+            Ok(enrichment) => {
+                let stats = enrichment.apply(name, &summary.repository_id, graph);
+                log::info!("✓ Plugin '{}' added {} nodes, {} edges, {} annotations ({} skipped)",
+                    name, stats.nodes, stats.edges, stats.annotations, stats.skipped);
+                changed |= stats.nodes + stats.edges + stats.annotations > 0;
+            }
+            Err(e) => log::warn!("⚠ Post-analysis plugin '{}' failed: {:#}", name, e),
+        }
+    }
+    changed
+}
+
+/// Collect report sections from every `report_section` plugin, native and subprocess, tagged with
+/// the plugin's name
+pub fn run_report_section_plugins(
+    registry: &PluginRegistry,
+    summary: &AnalysisSummary,
+    on_progress: &mut dyn FnMut(&str, &Progress),
+) -> Vec<(String, ReportSection)> {
+    let mut sections = Vec::new();
+    for plugin in registry.with_hook(PluginHook::ReportSection) {
+        let name = plugin.manifest.name.as_str();
+        let result = match &plugin.runtime {
+            PluginRuntime::Native => match registry.native.find(name) {
+                Some(analyzer) => analyzer.report_sections(summary),
+                None => continue,
+            },
+            PluginRuntime::Process { command } => runner::run_report_sections(command, summary, &mut |p| on_progress(name, p)),
+            _ => continue,
+        };
+        match result {
+            Ok(contributed) => {
+                if !contributed.is_empty() {
+                    log::info!("✓ Plugin '{}' contributed {} report section(s)", name, contributed.len());
+                }
+                sections.extend(contributed.into_iter().map(|s| (name.to_string(), s)));
+            }
+            Err(e) => log::warn!("⚠ Report section plugin '{}' failed: {:#}", name, e),
+        }
+    }
+    sections
+}
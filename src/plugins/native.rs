@@ -0,0 +1,242 @@
+//! Analyzers compiled into the binary. A downstream crate depends on this library, registers its
+//! analyzers before starting the server, and they run alongside WASM and subprocess plugins:
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use wavelength_arch_decoder::plugins::{register_analyzer, AnalyzerOutput, AnalyzerPlugin};
+//!
+//! struct LicenseAnalyzer;
+//!
+//! impl AnalyzerPlugin for LicenseAnalyzer {
+//!     fn name(&self) -> &str {
+//!         "license-check"
+//!     }
+//!
+//!     fn analyze(&self, repo_path: &Path) -> anyhow::Result<AnalyzerOutput> {
+//!         let _has_license = repo_path.join("LICENSE").exists();
+//!         Ok(AnalyzerOutput::default())
+//!     }
+//! }
+//!
+//! register_analyzer(LicenseAnalyzer);
+//! ```
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use crate::analysis::EndpointDetector;
+use crate::plugins::{AnalyzerOutput, PluginHook};
+use crate::security::ServiceDetector;
+use crate::plugins::enrich::{AnalysisSummary, Enrichment, ReportSection};
+
+/// Looks at a whole repository and reports in the same model as WASM and subprocess plugins
+pub trait AnalyzerPlugin: Send + Sync {
+    /// Unique among all plugins; shown in `GET /api/v1/plugins`
+    fn name(&self) -> &str;
+
+    fn version(&self) -> &str {
+        "0.0.0"
+    }
+
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    /// `file_analyzer` unless overridden; add `post_analysis` to have [`AnalyzerPlugin::enrich`] called
+    fn hooks(&self) -> Vec<PluginHook> {
+        vec![PluginHook::FileAnalyzer]
+    }
+
+    fn analyze(&self, repo_path: &Path) -> Result<AnalyzerOutput>;
+
+    fn enrich(&self, _summary: &AnalysisSummary) -> Result<Enrichment> {
+        Ok(Enrichment::default())
+    }
+
+    /// Called when `hooks` includes `report_section`
+    fn report_sections(&self, _summary: &AnalysisSummary) -> Result<Vec<ReportSection>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Native analyzers in registration order, one per name. The process-wide registry behind
+/// [`register_analyzer`] is what [`PluginRegistry::load`](crate::plugins::PluginRegistry::load)
+/// picks up; [`PluginRegistry::load_with`](crate::plugins::PluginRegistry::load_with) takes another.
+#[derive(Clone, Default)]
+pub struct AnalyzerRegistry {
+    analyzers: Vec<Arc<dyn AnalyzerPlugin>>,
+}
+
+impl AnalyzerRegistry {
+    /// One registered under the same name is replaced
+    pub fn register<A: AnalyzerPlugin + 'static>(&mut self, analyzer: A) {
+        self.register_shared(Arc::new(analyzer));
+    }
+
+    /// [`register`](Self::register) for an analyzer the caller keeps using
+    pub fn register_shared(&mut self, analyzer: Arc<dyn AnalyzerPlugin>) {
+        self.analyzers.retain(|a| a.name() != analyzer.name());
+        self.analyzers.push(analyzer);
+    }
+
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.analyzers.len();
+        self.analyzers.retain(|a| a.name() != name);
+        self.analyzers.len() != before
+    }
+
+    pub fn analyzers(&self) -> &[Arc<dyn AnalyzerPlugin>] {
+        &self.analyzers
+    }
+
+    pub fn find(&self, name: &str) -> Option<Arc<dyn AnalyzerPlugin>> {
+        self.analyzers.iter().find(|a| a.name() == name).cloned()
+    }
+
+    /// Runs the analyzer registered as `name`
+    pub fn run(&self, name: &str, repo_path: &Path) -> Result<AnalyzerOutput> {
+        self.find(name)
+            .ok_or_else(|| anyhow!("No analyzer named '{}' is registered", name))?
+            .analyze(repo_path)
+    }
+}
+
+impl std::fmt::Debug for AnalyzerRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.analyzers.iter().map(|a| a.name())).finish()
+    }
+}
+
+static ANALYZERS: Lazy<RwLock<AnalyzerRegistry>> = Lazy::new(|| RwLock::new(AnalyzerRegistry::default()));
+
+/// Add an analyzer to every subsequent analysis; one registered under the same name is replaced
+pub fn register_analyzer<A: AnalyzerPlugin + 'static>(analyzer: A) {
+    ANALYZERS.write().unwrap().register(analyzer);
+}
+
+pub fn unregister_analyzer(name: &str) -> bool {
+    ANALYZERS.write().unwrap().unregister(name)
+}
+
+/// Registered analyzers in registration order
+pub fn registered_analyzers() -> Vec<Arc<dyn AnalyzerPlugin>> {
+    ANALYZERS.read().unwrap().analyzers().to_vec()
+}
+
+/// A snapshot of the process-wide registry
+pub(crate) fn global_registry() -> AnalyzerRegistry {
+    ANALYZERS.read().unwrap().clone()
+}
+
+/// The detectors the analysis pipeline runs itself, under the names `services` and `endpoints`.
+/// They are kept apart from the plugin registry so a repository's plugin selection cannot turn
+/// them off; `services` is shared because plugin services are resolved against its patterns.
+pub fn builtin_analyzers(services: Arc<ServiceDetector>) -> AnalyzerRegistry {
+    let mut registry = AnalyzerRegistry::default();
+    registry.register_shared(services);
+    registry.register(EndpointDetector::new());
+    registry
+}
+
+impl AnalyzerPlugin for ServiceDetector {
+    fn name(&self) -> &str {
+        "services"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("External services from SDK imports, configuration and API calls")
+    }
+
+    fn analyze(&self, repo_path: &Path) -> Result<AnalyzerOutput> {
+        Ok(AnalyzerOutput {
+            detected_services: self.detect_services(repo_path)?,
+            ..AnalyzerOutput::default()
+        })
+    }
+}
+
+impl AnalyzerPlugin for EndpointDetector {
+    fn name(&self) -> &str {
+        "endpoints"
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("HTTP, WebSocket and SSE routes with their mount prefixes")
+    }
+
+    fn analyze(&self, repo_path: &Path) -> Result<AnalyzerOutput> {
+        Ok(AnalyzerOutput {
+            endpoints: self.detect_endpoints(repo_path)?,
+            ..AnalyzerOutput::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::{run_native_analyzers, PluginHook, PluginRegistry, PluginService};
+    use crate::security::ServiceProvider;
+    use std::fs;
+    use tempfile::TempDir;
+
+    struct DockerfileAnalyzer;
+
+    impl AnalyzerPlugin for DockerfileAnalyzer {
+        fn name(&self) -> &str {
+            "dockerfile-registry"
+        }
+
+        fn analyze(&self, repo_path: &Path) -> Result<AnalyzerOutput> {
+            let mut output = AnalyzerOutput::default();
+            if repo_path.join("Dockerfile").exists() {
+                output.services.push(PluginService {
+                    name: "Docker Hub".to_string(),
+                    provider: None,
+                    service_type: None,
+                    file_path: "Dockerfile".to_string(),
+                    line_number: Some(1),
+                    confidence: 0.9,
+                });
+            }
+            Ok(output)
+        }
+    }
+
+    #[test]
+    fn test_registered_analyzer_runs_through_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Dockerfile"), "FROM rust:1.75\n").unwrap();
+
+        let mut analyzers = AnalyzerRegistry::default();
+        analyzers.register(DockerfileAnalyzer);
+        analyzers.register(DockerfileAnalyzer);
+        let registry = PluginRegistry::load_with(&temp_dir.path().join("no-plugins"), analyzers.clone());
+        let native: Vec<_> = registry.with_hook(PluginHook::FileAnalyzer).filter(|p| p.runtime.kind() == "native").collect();
+        assert_eq!(native.len(), 1);
+
+        let output = run_native_analyzers(&registry, temp_dir.path());
+        assert_eq!(output.services[0].name, "Docker Hub");
+        assert!(analyzers.unregister("dockerfile-registry"));
+        assert!(analyzers.analyzers().is_empty());
+    }
+
+    #[test]
+    fn test_builtin_detectors_run_through_their_registry() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("main.tf"), "resource \"aws_s3_bucket\" \"uploads\" {\n  bucket = \"uploads\"\n}\n").unwrap();
+        fs::write(temp_dir.path().join("server.js"), "app.get('/api/orders', listOrders);\n").unwrap();
+
+        let builtin = builtin_analyzers(Arc::new(ServiceDetector::new()));
+        let names: Vec<&str> = builtin.analyzers().iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["services", "endpoints"]);
+
+        let services = builtin.run("services", temp_dir.path()).unwrap();
+        assert!(services.detected_services.iter().any(|s| s.provider == ServiceProvider::Aws));
+        let endpoints = builtin.run("endpoints", temp_dir.path()).unwrap();
+        assert_eq!(endpoints.endpoints[0].path, "/api/orders");
+        assert!(endpoints.services.is_empty() && endpoints.detected_services.is_empty());
+        assert!(builtin.run("tests", temp_dir.path()).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::plugins::legacy_python;
+use crate::plugins::manifest::{PluginHook, PluginManifest};
+use crate::plugins::native::AnalyzerRegistry;
+use crate::plugins::runner::PluginCommand;
+use crate::security::pattern_config::{PatternConfig, PatternLoader};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PluginRuntime {
+    /// Pattern-only plugins run no code
+    None,
+    Wasm { module: PathBuf },
+    Process { command: PluginCommand },
+    /// Compiled in and registered through [`crate::plugins::register_analyzer`]
+    Native,
+}
+
+impl PluginRuntime {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PluginRuntime::None => "patterns",
+            PluginRuntime::Wasm { .. } => "wasm",
+            PluginRuntime::Process { .. } => "process",
+            PluginRuntime::Native => "native",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadedPlugin {
+    pub manifest: PluginManifest,
+    /// File the plugin was loaded from
+    pub source: PathBuf,
+    pub runtime: PluginRuntime,
+    /// Validation problems; a plugin with any is listed but never run
+    pub errors: Vec<String>,
+}
+
+impl LoadedPlugin {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn from_manifest(manifest: PluginManifest, source: &Path) -> Self {
+        let base_dir = source.parent().unwrap_or(Path::new("."));
+        let errors = manifest.validate(base_dir);
+        let runtime = if let Some(wasm) = &manifest.wasm {
+            PluginRuntime::Wasm { module: base_dir.join(wasm) }
+        } else if let Some(command) = manifest.process_command(base_dir) {
+            PluginRuntime::Process { command }
+        } else {
+            PluginRuntime::None
+        };
+        LoadedPlugin { manifest, source: source.to_path_buf(), runtime, errors }
+    }
+
+    fn invalid(name: &str, source: &Path, error: String) -> Self {
+        LoadedPlugin {
+            manifest: PluginManifest::bare(name, "", Vec::new()),
+            source: source.to_path_buf(),
+            runtime: PluginRuntime::None,
+            errors: vec![error],
+        }
+    }
+}
+
+/// A repository's opt-ins and opt-outs by plugin name; plugins in neither list follow their
+/// manifest's `default_enabled`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginSelection {
+    #[serde(default)]
+    pub enabled: Vec<String>,
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+impl PluginSelection {
+    pub fn allows(&self, manifest: &PluginManifest) -> bool {
+        if self.disabled.contains(&manifest.name) {
+            false
+        } else if self.enabled.contains(&manifest.name) {
+            true
+        } else {
+            manifest.default_enabled
+        }
+    }
+}
+
+/// Everything found under `config/plugins`:
+///
+/// - `<name>/plugin.json`: a [`PluginManifest`]
+/// - `*.json`: a manifest if it has `manifest_version`, otherwise a legacy pattern file
+/// - `*.wasm`: a file analyzer without a manifest
+///
+/// Legacy Python detector checkouts next to the decoder are included as subprocess plugins, and
+/// registered native analyzers as `native` plugins.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PluginRegistry {
+    pub plugins: Vec<LoadedPlugin>,
+    /// The analyzers behind the `native` plugins
+    #[serde(skip)]
+    pub(crate) native: AnalyzerRegistry,
+}
+
+impl PluginRegistry {
+    /// Native analyzers come from [`crate::plugins::register_analyzer`]
+    pub fn load(plugin_dir: &Path) -> Self {
+        Self::load_with(plugin_dir, crate::plugins::native::global_registry())
+    }
+
+    pub fn load_with(plugin_dir: &Path, native: AnalyzerRegistry) -> Self {
+        let mut registry = PluginRegistry { native, ..PluginRegistry::default() };
+        let mut paths: Vec<PathBuf> = match std::fs::read_dir(plugin_dir) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(_) => Vec::new(),
+        };
+        paths.sort();
+
+        for path in paths {
+            // Hidden entries include install staging directories
+            if path.file_name().and_then(|f| f.to_str()).map(|f| f.starts_with('.')).unwrap_or(true) {
+                continue;
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown").to_string();
+            if path.is_dir() {
+                let manifest_path = path.join("plugin.json");
+                if manifest_path.is_file() {
+                    registry.plugins.push(match PluginManifest::load(&manifest_path) {
+                        Ok(manifest) => LoadedPlugin::from_manifest(manifest, &manifest_path),
+                        Err(e) => LoadedPlugin::invalid(&stem, &manifest_path, format!("{:#}", e)),
+                    });
+                }
+                continue;
+            }
+            match path.extension().and_then(|x| x.to_str()) {
+                Some("json") => registry.plugins.push(load_json(&stem, &path)),
+                Some("wasm") => registry.plugins.push(LoadedPlugin::from_manifest(PluginManifest::for_wasm_module(&path), &path)),
+                _ => {}
+            }
+        }
+
+        for command in legacy_python::discover() {
+            let mut manifest = PluginManifest::bare(&command.name, "0.0.0", vec![PluginHook::FileAnalyzer]);
+            manifest.description = Some("Legacy Python detector".to_string());
+            registry.plugins.push(LoadedPlugin {
+                manifest,
+                source: command.working_dir.clone().unwrap_or_default(),
+                runtime: PluginRuntime::Process { command },
+                errors: Vec::new(),
+            });
+        }
+
+        for analyzer in registry.native.analyzers().to_vec() {
+            let mut manifest = PluginManifest::bare(analyzer.name(), analyzer.version(), analyzer.hooks());
+            manifest.description = analyzer.description().map(|d| d.to_string());
+            registry.plugins.push(LoadedPlugin {
+                errors: manifest.validate_metadata(),
+                manifest,
+                source: PathBuf::new(),
+                runtime: PluginRuntime::Native,
+            });
+        }
+
+        // The first plugin with a name wins; later ones are reported rather than silently shadowed
+        let mut seen = HashSet::new();
+        for plugin in &mut registry.plugins {
+            if !plugin.manifest.name.is_empty() && !seen.insert(plugin.manifest.name.clone()) {
+                plugin.errors.push(format!("duplicate plugin name '{}'", plugin.manifest.name));
+            }
+        }
+        for plugin in registry.plugins.iter().filter(|p| !p.is_valid()) {
+            log::warn!("⚠ Plugin {} is disabled: {}", plugin.source.display(), plugin.errors.join("; "));
+        }
+
+        registry
+    }
+
+    /// Valid plugins implementing `hook`, in load order
+    pub fn with_hook(&self, hook: PluginHook) -> impl Iterator<Item = &LoadedPlugin> {
+        self.plugins.iter().filter(move |p| p.is_valid() && p.manifest.implements(hook))
+    }
+
+    /// The plugins a repository runs with
+    pub fn for_repository(&self, selection: &PluginSelection) -> PluginRegistry {
+        PluginRegistry {
+            plugins: self.plugins.iter().filter(|p| selection.allows(&p.manifest)).cloned().collect(),
+            native: self.native.clone(),
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.plugins.iter().map(|p| p.manifest.name.clone()).collect()
+    }
+
+    /// Patterns contributed by valid plugins, for merging into the base pattern config
+    pub fn pattern_contributions(&self) -> Vec<(String, PatternConfig)> {
+        self.with_hook(PluginHook::Patterns)
+            .filter_map(|p| p.manifest.patterns.clone().map(|patterns| (p.manifest.name.clone(), PatternConfig {
+                version: p.manifest.version.clone(),
+                patterns,
+            })))
+            .collect()
+    }
+}
+
+fn load_json(stem: &str, path: &Path) -> LoadedPlugin {
+    let value = match std::fs::read_to_string(path).map_err(anyhow::Error::from)
+        .and_then(|c| Ok(serde_json::from_str::<serde_json::Value>(&c)?)) {
+        Ok(value) => value,
+        Err(e) => return LoadedPlugin::invalid(stem, path, format!("{:#}", e)),
+    };
+    if value.get("manifest_version").is_some() {
+        return match PluginManifest::load(path) {
+            Ok(manifest) => LoadedPlugin::from_manifest(manifest, path),
+            Err(e) => LoadedPlugin::invalid(stem, path, format!("{:#}", e)),
+        };
+    }
+    match PatternLoader::load_from_file(path) {
+        Ok(config) => LoadedPlugin::from_manifest(PluginManifest::from_pattern_config(stem, config), path),
+        Err(e) => LoadedPlugin::invalid(stem, path, format!("not a plugin manifest or pattern file: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_repository_selection_overrides_plugin_default() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("stripe.json"), r#"{"version": "1.0", "patterns": {}}"#).unwrap();
+        fs::write(temp_dir.path().join("mainframe.json"), r#"{
+            "manifest_version": 1,
+            "name": "mainframe",
+            "version": "0.1.0",
+            "hooks": ["patterns"],
+            "patterns": {},
+            "default_enabled": false
+        }"#).unwrap();
+
+        let registry = PluginRegistry::load(temp_dir.path());
+        let names = |selection: &PluginSelection| {
+            let mut names: Vec<String> = registry.for_repository(selection).names().into_iter()
+                .filter(|n| n == "stripe" || n == "mainframe")
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(&PluginSelection::default()), vec!["stripe"]);
+        let selection = PluginSelection {
+            enabled: vec!["mainframe".to_string()],
+            disabled: vec!["stripe".to_string()],
+        };
+        assert_eq!(names(&selection), vec!["mainframe"]);
+    }
+}
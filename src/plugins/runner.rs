@@ -0,0 +1,387 @@
+//! Subprocess plugins speaking JSON-RPC 2.0 over stdin/stdout, one message per line.
+//!
+//! A session is `initialize` → `analyze` → `shutdown`, followed by an `exit` notification:
+//!
+//! - `initialize {protocol_version, host, capabilities}`: the host offers its capabilities and the
+//!   plugin answers with a [`Handshake`] listing the ones it supports. Protocol versions must share
+//!   a major version.
+//! - `analyze {repo_path}`: returns an [`AnalyzerOutput`]. Only sent if the plugin declared `analyze`.
+//! - `enrich {summary}`: returns an [`Enrichment`] for a stored analysis. Only sent if the plugin
+//!   declared `enrich`.
+//! - `report_sections {summary}`: returns `{sections: [ReportSection]}`. Only sent if the plugin
+//!   declared `report`.
+//! - While a request is running the plugin may send `progress {message, percent}` and
+//!   `log {level, message}` notifications. Any message resets the idle timeout.
+//!
+//! Lines on stdout that are not JSON are ignored and stderr goes to the debug log, so a chatty
+//! plugin cannot break the session. A plugin that misses a deadline is killed.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use crate::plugins::AnalyzerOutput;
+use crate::plugins::enrich::{AnalysisSummary, Enrichment, ReportSection};
+
+pub const PROTOCOL_VERSION: &str = "1.0";
+/// Everything the host understands; plugins pick from these during the handshake
+pub const HOST_CAPABILITIES: &[&str] = &["analyze", "enrich", "report", "progress", "log"];
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How to launch a subprocess plugin, resolved from a manifest's `process` section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCommand {
+    pub name: String,
+    pub command: Vec<String>,
+    pub working_dir: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+    /// Upper bound for one `analyze` call
+    pub timeout_secs: u64,
+    /// Upper bound for silence between messages; progress notifications keep a long analysis alive
+    pub idle_timeout_secs: u64,
+}
+
+/// The plugin's answer to `initialize`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Handshake {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    pub protocol_version: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progress {
+    pub message: String,
+    #[serde(default)]
+    pub percent: Option<f64>,
+}
+
+/// A running plugin process with a completed handshake
+pub struct RpcPlugin {
+    command: PluginCommand,
+    child: Child,
+    stdin: ChildStdin,
+    messages: Receiver<Value>,
+    next_id: u64,
+    handshake: Handshake,
+}
+
+impl RpcPlugin {
+    pub fn spawn(command: &PluginCommand) -> Result<Self> {
+        let (program, args) = command.command.split_first().ok_or_else(|| anyhow!("command is empty"))?;
+        let mut process = Command::new(program);
+        process.args(args)
+            .envs(&command.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(dir) = &command.working_dir {
+            process.current_dir(dir);
+        }
+        let mut child = process.spawn().with_context(|| format!("starting plugin '{}'", command.name))?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("plugin stdin unavailable"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("plugin stdout unavailable"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("plugin stderr unavailable"))?;
+
+        let (sender, messages) = mpsc::channel();
+        let name = command.name.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+                match serde_json::from_str::<Value>(&line) {
+                    Ok(message) => {
+                        if sender.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) if line.trim().is_empty() => {}
+                    Err(_) => log::debug!("[plugin {}] ignoring non-JSON output: {}", name, line),
+                }
+            }
+        });
+        let name = command.name.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+                log::debug!("[plugin {}] {}", name, line);
+            }
+        });
+
+        // A plugin configured with a short timeout should not get a longer one just to start up
+        let handshake_timeout = HANDSHAKE_TIMEOUT.min(Duration::from_secs(command.timeout_secs));
+        let mut plugin = RpcPlugin {
+            command: command.clone(),
+            child,
+            stdin,
+            messages,
+            next_id: 1,
+            handshake: Handshake { name: command.name.clone(), version: None, protocol_version: String::new(), capabilities: Vec::new() },
+        };
+        let result = plugin.request(
+            "initialize",
+            json!({
+                "protocol_version": PROTOCOL_VERSION,
+                "host": { "name": "wavelength-arch-decoder", "version": env!("CARGO_PKG_VERSION") },
+                "capabilities": HOST_CAPABILITIES,
+            }),
+            handshake_timeout,
+            handshake_timeout,
+            &mut |_| {},
+        ).context("handshake failed")?;
+        let handshake: Handshake = serde_json::from_value(result).context("invalid initialize result")?;
+        if major(&handshake.protocol_version) != major(PROTOCOL_VERSION) {
+            return Err(anyhow!("plugin speaks protocol {}, host speaks {}", handshake.protocol_version, PROTOCOL_VERSION));
+        }
+        plugin.handshake = handshake;
+        Ok(plugin)
+    }
+
+    pub fn handshake(&self) -> &Handshake {
+        &self.handshake
+    }
+
+    pub fn supports(&self, capability: &str) -> bool {
+        self.handshake.capabilities.iter().any(|c| c == capability)
+    }
+
+    /// Ask the plugin to analyze a repository; `on_progress` sees its progress notifications as they arrive
+    pub fn analyze(&mut self, repo_path: &Path, on_progress: &mut dyn FnMut(&Progress)) -> Result<AnalyzerOutput> {
+        if !self.supports("analyze") {
+            return Ok(AnalyzerOutput::default());
+        }
+        let result = self.request(
+            "analyze",
+            json!({ "repo_path": repo_path.to_string_lossy() }),
+            Duration::from_secs(self.command.timeout_secs),
+            Duration::from_secs(self.command.idle_timeout_secs),
+            on_progress,
+        )?;
+        if result.is_null() {
+            return Ok(AnalyzerOutput::default());
+        }
+        serde_json::from_value(result).context("invalid analyze result")
+    }
+
+    /// Hand the plugin a stored analysis to add to
+    pub fn enrich(&mut self, summary: &AnalysisSummary, on_progress: &mut dyn FnMut(&Progress)) -> Result<Enrichment> {
+        if !self.supports("enrich") {
+            return Ok(Enrichment::default());
+        }
+        let result = self.request(
+            "enrich",
+            json!({ "summary": summary }),
+            Duration::from_secs(self.command.timeout_secs),
+            Duration::from_secs(self.command.idle_timeout_secs),
+            on_progress,
+        )?;
+        if result.is_null() {
+            return Ok(Enrichment::default());
+        }
+        serde_json::from_value(result).context("invalid enrich result")
+    }
+
+    pub fn report_sections(&mut self, summary: &AnalysisSummary, on_progress: &mut dyn FnMut(&Progress)) -> Result<Vec<ReportSection>> {
+        if !self.supports("report") {
+            return Ok(Vec::new());
+        }
+        let result = self.request(
+            "report_sections",
+            json!({ "summary": summary }),
+            Duration::from_secs(self.command.timeout_secs),
+            Duration::from_secs(self.command.idle_timeout_secs),
+            on_progress,
+        )?;
+        #[derive(Deserialize)]
+        struct Sections {
+            #[serde(default)]
+            sections: Vec<ReportSection>,
+        }
+        if result.is_null() {
+            return Ok(Vec::new());
+        }
+        Ok(serde_json::from_value::<Sections>(result).context("invalid report_sections result")?.sections)
+    }
+
+    /// Polite shutdown; the process is killed if it does not exit in time
+    pub fn shutdown(mut self) {
+        if self.request("shutdown", Value::Null, SHUTDOWN_TIMEOUT, SHUTDOWN_TIMEOUT, &mut |_| {}).is_ok() {
+            let _ = self.notify("exit", Value::Null);
+            let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+            while Instant::now() < deadline {
+                if let Ok(Some(_)) = self.child.try_wait() {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        }
+        // Drop kills whatever is left
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        writeln!(self.stdin, "{}", message)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    fn request(
+        &mut self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+        idle_timeout: Duration,
+        on_progress: &mut dyn FnMut(&Progress),
+    ) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        writeln!(self.stdin, "{}", message).with_context(|| format!("plugin '{}' closed its input", self.command.name))?;
+        self.stdin.flush()?;
+
+        let deadline = Instant::now() + timeout;
+        let mut idle_deadline = Instant::now() + idle_timeout;
+        loop {
+            let wait = deadline.min(idle_deadline).saturating_duration_since(Instant::now());
+            let message = match self.messages.recv_timeout(wait) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = self.child.kill();
+                    let reason = if Instant::now() >= deadline { "timed out" } else { "went silent" };
+                    return Err(anyhow!("plugin '{}' {} during {}", self.command.name, reason, method));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    let status = self.child.wait().map(|s| s.to_string()).unwrap_or_default();
+                    return Err(anyhow!("plugin '{}' exited during {} ({})", self.command.name, method, status));
+                }
+            };
+            idle_deadline = Instant::now() + idle_timeout;
+
+            if message.get("id").and_then(|v| v.as_u64()) == Some(id) {
+                if let Some(error) = message.get("error") {
+                    let text = error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+                    return Err(anyhow!("plugin '{}' failed {}: {}", self.command.name, method, text));
+                }
+                return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+            }
+
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+            match message.get("method").and_then(|m| m.as_str()) {
+                Some("progress") => {
+                    if let Ok(progress) = serde_json::from_value::<Progress>(params) {
+                        on_progress(&progress);
+                    }
+                }
+                Some("log") => {
+                    let text = params.get("message").and_then(|m| m.as_str()).unwrap_or("");
+                    match params.get("level").and_then(|l| l.as_str()).unwrap_or("info") {
+                        "error" | "warn" | "warning" => log::warn!("[plugin {}] {}", self.command.name, text),
+                        "debug" | "trace" => log::debug!("[plugin {}] {}", self.command.name, text),
+                        _ => log::info!("[plugin {}] {}", self.command.name, text),
+                    }
+                }
+                _ => log::debug!("[plugin {}] ignoring message: {}", self.command.name, message),
+            }
+        }
+    }
+}
+
+impl Drop for RpcPlugin {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or("")
+}
+
+/// Run one subprocess plugin start to finish
+pub fn run(command: &PluginCommand, repo_path: &Path, on_progress: &mut dyn FnMut(&Progress)) -> Result<AnalyzerOutput> {
+    let mut plugin = RpcPlugin::spawn(command)?;
+    log::info!("Plugin '{}' {} ready (protocol {}, capabilities: {})",
+        plugin.handshake().name,
+        plugin.handshake().version.as_deref().unwrap_or(""),
+        plugin.handshake().protocol_version,
+        plugin.handshake().capabilities.join(", "));
+    let output = plugin.analyze(repo_path, on_progress)?;
+    plugin.shutdown();
+    Ok(output)
+}
+
+/// Start a subprocess plugin for the `post_analysis` hook only
+pub fn run_enrich(command: &PluginCommand, summary: &AnalysisSummary, on_progress: &mut dyn FnMut(&Progress)) -> Result<Enrichment> {
+    let mut plugin = RpcPlugin::spawn(command)?;
+    let enrichment = plugin.enrich(summary, on_progress)?;
+    plugin.shutdown();
+    Ok(enrichment)
+}
+
+/// Start a subprocess plugin for the `report_section` hook only; it runs after every enrichment,
+/// so the summary includes what other plugins added
+pub fn run_report_sections(command: &PluginCommand, summary: &AnalysisSummary, on_progress: &mut dyn FnMut(&Progress)) -> Result<Vec<ReportSection>> {
+    let mut plugin = RpcPlugin::spawn(command)?;
+    let sections = plugin.report_sections(summary, on_progress)?;
+    plugin.shutdown();
+    Ok(sections)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell_plugin(script: &str, timeout_secs: u64) -> PluginCommand {
+        PluginCommand {
+            name: "shell".to_string(),
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            working_dir: None,
+            env: HashMap::new(),
+            timeout_secs,
+            idle_timeout_secs: timeout_secs,
+        }
+    }
+
+    #[test]
+    fn test_handshake_progress_and_result() {
+        // Replies by line position: request ids are assigned 1, 2, 3 in order
+        let script = r#"
+            read line
+            echo 'starting up'
+            echo '{"jsonrpc":"2.0","id":1,"result":{"name":"sh-plugin","version":"0.1.0","protocol_version":"1.2","capabilities":["analyze","progress"]}}'
+            read line
+            echo '{"jsonrpc":"2.0","method":"progress","params":{"message":"half way","percent":50}}'
+            echo '{"jsonrpc":"2.0","id":2,"result":{"services":[{"name":"FakeMQ","file_path":"queue.cfg"}]}}'
+            read line
+            echo '{"jsonrpc":"2.0","id":3,"result":null}'
+            read line
+        "#;
+        let mut progress = Vec::new();
+        let mut plugin = RpcPlugin::spawn(&shell_plugin(script, 10)).unwrap();
+        assert_eq!(plugin.handshake().name, "sh-plugin");
+        assert!(plugin.supports("analyze"));
+
+        let output = plugin.analyze(Path::new("/tmp"), &mut |p| progress.push(p.message.clone())).unwrap();
+        assert_eq!(output.services[0].name, "FakeMQ");
+        assert_eq!(progress, vec!["half way"]);
+        plugin.shutdown();
+    }
+
+    #[test]
+    fn test_silent_plugin_times_out() {
+        let started = Instant::now();
+        let result = run(&shell_plugin("sleep 30", 1), Path::new("/tmp"), &mut |_| {});
+        assert!(result.unwrap_err().to_string().contains("handshake"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
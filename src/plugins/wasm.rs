@@ -0,0 +1,242 @@
+//! Sandboxed analyzer plugins compiled to WebAssembly.
+//!
+//! A plugin is a core WASM module without WASI: it cannot touch the filesystem, network or clock, and
+//! only sees the files the host hands it. Strings cross the boundary as UTF-8 JSON in guest memory:
+//!
+//! - `memory` (export): the guest's linear memory
+//! - `wavelength_alloc(len: i32) -> i32` (export): buffer for the host to write input into; the guest
+//!   owns it from then on
+//! - `wavelength_on_file(ptr: i32, len: i32) -> i64` (export): receives a [`FileEvent`], returns an
+//!   [`AnalyzerOutput`] as `(ptr << 32) | len`, or 0 for nothing
+//! - `wavelength_info() -> i64` (optional export): an [`AnalyzerInfo`], same encoding
+//! - `wavelength_finish() -> i64` (optional export): called once after the last file
+//! - `wavelength_free(ptr: i32, len: i32)` (optional export): releases an output buffer
+//! - `wavelength.log(ptr: i32, len: i32)` (optional import): writes a line to the server log
+//!
+//! Each call runs with a fuel budget and memory is capped, so a looping or greedy plugin fails
+//! instead of stalling the analysis.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use wasmtime::{Caller, Config, Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc};
+use crate::plugins::{file_events, AnalyzerInfo, AnalyzerOutput};
+
+/// Instructions (roughly) a plugin may spend on one call
+const FUEL_PER_CALL: u64 = 2_000_000_000;
+const MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+/// Plugins whose calls keep failing are abandoned for the rest of the repository
+const MAX_FAILED_CALLS: usize = 10;
+
+struct PluginState {
+    limits: StoreLimits,
+}
+
+pub struct WasmAnalyzer {
+    engine: Engine,
+    module: Module,
+    info: AnalyzerInfo,
+}
+
+/// One instantiation of a plugin; a fresh one is used per repository so no state leaks between them
+struct Session {
+    store: Store<PluginState>,
+    instance: Instance,
+    alloc: TypedFunc<i32, i32>,
+}
+
+impl WasmAnalyzer {
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        let fallback_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("wasm-plugin").to_string();
+        Self::from_bytes(&bytes, &fallback_name)
+    }
+
+    pub fn from_bytes(bytes: &[u8], fallback_name: &str) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::from_binary(&engine, bytes)?;
+
+        let mut analyzer = WasmAnalyzer {
+            engine,
+            module,
+            info: AnalyzerInfo { name: fallback_name.to_string(), version: None, file_extensions: Vec::new() },
+        };
+        let mut session = analyzer.instantiate()?;
+        if let Some(info) = session.call_optional("wavelength_info")? {
+            analyzer.info = serde_json::from_slice(&info).context("invalid wavelength_info output")?;
+        }
+        Ok(analyzer)
+    }
+
+    pub fn info(&self) -> &AnalyzerInfo {
+        &self.info
+    }
+
+    fn instantiate(&self) -> Result<Session> {
+        let mut store = Store::new(&self.engine, PluginState {
+            limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY_BYTES).instances(1).build(),
+        });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL_PER_CALL)?;
+
+        let mut linker: Linker<PluginState> = Linker::new(&self.engine);
+        let name = self.info.name.clone();
+        linker.func_wrap("wavelength", "log", move |mut caller: Caller<'_, PluginState>, ptr: i32, len: i32| {
+            if let Some(memory) = caller.get_export("memory").and_then(|e| e.into_memory()) {
+                let data = memory.data(&caller);
+                if let Some(bytes) = data.get(ptr as u32 as usize..(ptr as u32 as usize).saturating_add(len as u32 as usize)) {
+                    log::info!("[plugin {}] {}", name, String::from_utf8_lossy(bytes));
+                }
+            }
+        })?;
+
+        let instance = linker.instantiate(&mut store, &self.module)?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "wavelength_alloc")
+            .context("plugin does not export wavelength_alloc")?;
+        if instance.get_memory(&mut store, "memory").is_none() {
+            return Err(anyhow!("plugin does not export its memory"));
+        }
+        Ok(Session { store, instance, alloc })
+    }
+
+    /// Feed every matching repository file to the plugin and collect what it reports
+    pub fn analyze(&self, repo_path: &Path) -> Result<AnalyzerOutput> {
+        let mut session = self.instantiate()?;
+        let on_file = session.instance.get_typed_func::<(i32, i32), i64>(&mut session.store, "wavelength_on_file")
+            .context("plugin does not export wavelength_on_file")?;
+
+        let mut output = AnalyzerOutput::default();
+        let mut failures = 0;
+        for event in file_events(repo_path, &self.info.file_extensions) {
+            let input = serde_json::to_vec(&event)?;
+            let result = session.write_input(&input)
+                .and_then(|(ptr, len)| {
+                    session.store.set_fuel(FUEL_PER_CALL)?;
+                    on_file.call(&mut session.store, (ptr, len))
+                })
+                .and_then(|packed| session.take_output(packed))
+                .and_then(|bytes| match bytes {
+                    Some(bytes) => Ok(Some(serde_json::from_slice::<AnalyzerOutput>(&bytes)?)),
+                    None => Ok(None),
+                });
+            match result {
+                Ok(Some(out)) => output.merge(out),
+                Ok(None) => {}
+                Err(e) => {
+                    failures += 1;
+                    log::debug!("Plugin '{}' failed on {}: {}", self.info.name, event.path, e);
+                    if failures >= MAX_FAILED_CALLS {
+                        return Err(anyhow!("giving up after {} failed calls (last: {})", failures, e));
+                    }
+                }
+            }
+        }
+
+        if let Some(bytes) = session.call_optional("wavelength_finish")? {
+            output.merge(serde_json::from_slice(&bytes).context("invalid wavelength_finish output")?);
+        }
+        Ok(output)
+    }
+}
+
+impl Session {
+    fn write_input(&mut self, input: &[u8]) -> Result<(i32, i32)> {
+        let len = i32::try_from(input.len())?;
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        let memory = self.instance.get_memory(&mut self.store, "memory").ok_or_else(|| anyhow!("memory export missing"))?;
+        memory.write(&mut self.store, ptr as u32 as usize, input)?;
+        Ok((ptr, len))
+    }
+
+    /// Copy a `(ptr << 32) | len` result out of guest memory and let the guest free it
+    fn take_output(&mut self, packed: i64) -> Result<Option<Vec<u8>>> {
+        if packed == 0 {
+            return Ok(None);
+        }
+        let (ptr, len) = ((packed as u64 >> 32) as u32, packed as u64 as u32);
+        let memory = self.instance.get_memory(&mut self.store, "memory").ok_or_else(|| anyhow!("memory export missing"))?;
+        let bytes = memory.data(&self.store)
+            .get(ptr as usize..ptr as usize + len as usize)
+            .ok_or_else(|| anyhow!("output range {}+{} is outside guest memory", ptr, len))?
+            .to_vec();
+        if let Ok(free) = self.instance.get_typed_func::<(i32, i32), ()>(&mut self.store, "wavelength_free") {
+            free.call(&mut self.store, (ptr as i32, len as i32))?;
+        }
+        Ok(Some(bytes))
+    }
+
+    fn call_optional(&mut self, export: &str) -> Result<Option<Vec<u8>>> {
+        let func = match self.instance.get_typed_func::<(), i64>(&mut self.store, export) {
+            Ok(func) => func,
+            Err(_) => return Ok(None),
+        };
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let packed = func.call(&mut self.store, ())?;
+        self.take_output(packed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Reports one service per file and ignores the file content; output JSON lives in a data segment
+    const PLUGIN: &str = r#"
+        (module
+          (import "wavelength" "log" (func $log (param i32 i32)))
+          (memory (export "memory") 2)
+          (data (i32.const 0) "{\"name\":\"fake-mq\",\"version\":\"1.0.0\",\"file_extensions\":[\"cfg\"]}")
+          (data (i32.const 128) "{\"services\":[{\"name\":\"FakeMQ\",\"provider\":\"FakeMQ\",\"file_path\":\"queue.cfg\"}]}")
+          (data (i32.const 256) "scanning")
+          (global $next (mut i32) (i32.const 1024))
+          (func (export "wavelength_alloc") (param $len i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr (global.get $next))
+            (global.set $next (i32.add (global.get $next) (local.get $len)))
+            (local.get $ptr))
+          (func (export "wavelength_info") (result i64)
+            (i64.or (i64.shl (i64.const 0) (i64.const 32)) (i64.const 62)))
+          (func (export "wavelength_on_file") (param i32 i32) (result i64)
+            (call $log (i32.const 256) (i32.const 8))
+            (i64.or (i64.shl (i64.const 128) (i64.const 32)) (i64.const 76))))
+    "#;
+
+    #[test]
+    fn test_runs_plugin_over_matching_files() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("queue.cfg"), "broker = fakemq://localhost\n").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "# Not for this plugin\n").unwrap();
+
+        let analyzer = WasmAnalyzer::from_bytes(&wat::parse_str(PLUGIN).unwrap(), "fallback").unwrap();
+        assert_eq!(analyzer.info().name, "fake-mq");
+        assert_eq!(analyzer.info().file_extensions, vec!["cfg"]);
+
+        let output = analyzer.analyze(temp_dir.path()).unwrap();
+        assert_eq!(output.services.len(), 1);
+        assert_eq!(output.services[0].name, "FakeMQ");
+        assert_eq!(output.services[0].confidence, 0.7);
+    }
+
+    #[test]
+    fn test_runaway_plugin_is_stopped() {
+        let looping = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "wavelength_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "wavelength_on_file") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))
+        "#;
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+
+        let analyzer = WasmAnalyzer::from_bytes(&wat::parse_str(looping).unwrap(), "looping").unwrap();
+        assert_eq!(analyzer.info().name, "looping");
+        // A single file fails without aborting the plugin; output is simply empty
+        assert!(analyzer.analyze(temp_dir.path()).unwrap().is_empty());
+    }
+}
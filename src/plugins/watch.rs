@@ -0,0 +1,72 @@
+//! Reloads the plugin registry when `config/plugins` changes, so new or edited pattern files and
+//! plugins apply to the next analysis without restarting the server.
+
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use crate::plugins::PluginRegistry;
+
+/// The registry analyses read from; replaced wholesale on reload
+pub type SharedRegistry = Arc<RwLock<PluginRegistry>>;
+
+/// Editors and `cp -r` produce bursts of events; wait for this much quiet before reloading
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub fn shared(plugin_dir: &Path) -> SharedRegistry {
+    Arc::new(RwLock::new(PluginRegistry::load(plugin_dir)))
+}
+
+/// Load the registry from disk again and swap it in
+pub fn reload(plugin_dir: &Path, registry: &SharedRegistry) {
+    let loaded = PluginRegistry::load(plugin_dir);
+    let valid = loaded.plugins.iter().filter(|p| p.is_valid()).count();
+    let total = loaded.plugins.len();
+    *registry.write().unwrap() = loaded;
+    log::info!("Reloaded plugins from {}: {} of {} valid", plugin_dir.display(), valid, total);
+}
+
+/// Watch `plugin_dir` on a background thread for the life of the process
+pub fn spawn(plugin_dir: PathBuf, registry: SharedRegistry) -> Result<()> {
+    std::fs::create_dir_all(&plugin_dir)
+        .with_context(|| format!("creating {}", plugin_dir.display()))?;
+
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx).context("starting plugin directory watcher")?;
+    watcher.watch(&plugin_dir, RecursiveMode::Recursive)
+        .with_context(|| format!("watching {}", plugin_dir.display()))?;
+
+    std::thread::Builder::new()
+        .name("plugin-watcher".to_string())
+        .spawn(move || {
+            // The watcher stops when dropped, so the thread owns it
+            let _watcher = watcher;
+            while let Ok(event) = rx.recv() {
+                if !is_relevant(&event) {
+                    continue;
+                }
+                while rx.recv_timeout(DEBOUNCE).is_ok() {}
+                reload(&plugin_dir, &registry);
+            }
+        })
+        .context("spawning plugin watcher thread")?;
+    Ok(())
+}
+
+/// Ignore reads and install staging directories, which are renamed into place when complete
+fn is_relevant(event: &notify::Result<notify::Event>) -> bool {
+    match event {
+        Ok(event) => {
+            !event.kind.is_access()
+                && event.paths.iter().any(|path| {
+                    !path.components().any(|c| c.as_os_str().to_string_lossy().starts_with(".staging-"))
+                })
+        }
+        Err(e) => {
+            log::warn!("Plugin watcher error: {}", e);
+            false
+        }
+    }
+}
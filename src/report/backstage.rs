@@ -0,0 +1,158 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use crate::config::BackstageConfig;
+use crate::report::OpenApiGenerator;
+use crate::storage::{Repository, StoredEndpoint, StoredService, StoredServiceBoundary};
+
+/// Generates Backstage catalog entities from a repository's analysis: a Component for the
+/// repository and one per separately built sub-project, an API for its detected endpoints and a
+/// Resource for each external service or data store, connected with `dependsOn`
+pub struct BackstageGenerator {
+    owner: String,
+    lifecycle: String,
+    system: Option<String>,
+}
+
+impl BackstageGenerator {
+    pub fn new(config: &BackstageConfig) -> Self {
+        BackstageGenerator {
+            owner: config.owner.clone(),
+            lifecycle: config.lifecycle.clone(),
+            system: config.system.clone(),
+        }
+    }
+
+    pub fn generate(
+        &self,
+        repository: &Repository,
+        boundaries: &[StoredServiceBoundary],
+        services: &[StoredService],
+        endpoints: &[StoredEndpoint],
+    ) -> Vec<Value> {
+        let component = entity_name(&repository.name);
+
+        // Keyed by entity name so a store found by several detectors is listed once
+        let mut resources: BTreeMap<String, (String, String)> = BTreeMap::new();
+        for service in services {
+            resources.entry(entity_name(&service.name))
+                .or_insert_with(|| (service.name.clone(), service.service_type.clone()));
+        }
+        for boundary in boundaries {
+            for store in &boundary.data_stores {
+                resources.entry(entity_name(store)).or_insert_with(|| (store.clone(), "database".to_string()));
+            }
+        }
+
+        let mut entities = Vec::new();
+
+        let mut spec = self.spec("service");
+        spec.insert("dependsOn".to_string(), json!(resources.keys().map(|r| format!("resource:{}", r)).collect::<Vec<_>>()));
+        let api = format!("{}-api", component);
+        if !endpoints.is_empty() {
+            spec.insert("providesApis".to_string(), json!([api]));
+        }
+        entities.push(self.entity("Component", &component, &repository.name, repository, spec));
+
+        // Sub-projects with their own manifest are deployed on their own
+        let sub_projects: Vec<&StoredServiceBoundary> = boundaries.iter().filter(|b| b.kind == "service").collect();
+        for boundary in &sub_projects {
+            let mut spec = self.spec("service");
+            spec.insert("subcomponentOf".to_string(), json!(format!("component:{}", component)));
+            let mut depends_on: Vec<String> = boundary.depends_on.iter()
+                .filter(|d| sub_projects.iter().any(|b| &b.name == *d))
+                .map(|d| format!("component:{}-{}", component, entity_name(d)))
+                .collect();
+            depends_on.extend(boundary.data_stores.iter().map(|s| format!("resource:{}", entity_name(s))));
+            spec.insert("dependsOn".to_string(), json!(depends_on));
+            let name = format!("{}-{}", component, entity_name(&boundary.name));
+            let title = format!("{} / {}", repository.name, boundary.name);
+            entities.push(self.entity("Component", &name, &title, repository, spec));
+        }
+
+        if !endpoints.is_empty() {
+            let mut spec = self.spec("openapi");
+            let definition = OpenApiGenerator::new().generate(repository, endpoints);
+            spec.insert("definition".to_string(), json!(serde_yaml::to_string(&definition).unwrap_or_default()));
+            entities.push(self.entity("API", &api, &format!("{} API", repository.name), repository, spec));
+        }
+
+        for (name, (title, service_type)) in resources {
+            let mut spec = Map::new();
+            spec.insert("type".to_string(), json!(service_type));
+            spec.insert("owner".to_string(), json!(self.owner));
+            if let Some(system) = &self.system {
+                spec.insert("system".to_string(), json!(system));
+            }
+            entities.push(self.entity("Resource", &name, &title, repository, spec));
+        }
+
+        entities
+    }
+
+    /// All entities as one multi-document `catalog-info.yaml`
+    pub fn to_yaml(entities: &[Value]) -> Result<String> {
+        let documents = entities.iter()
+            .map(serde_yaml::to_string)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(documents.join("---\n"))
+    }
+
+    fn spec(&self, kind: &str) -> Map<String, Value> {
+        let mut spec = Map::new();
+        spec.insert("type".to_string(), json!(kind));
+        spec.insert("lifecycle".to_string(), json!(self.lifecycle));
+        spec.insert("owner".to_string(), json!(self.owner));
+        if let Some(system) = &self.system {
+            spec.insert("system".to_string(), json!(system));
+        }
+        spec
+    }
+
+    fn entity(&self, kind: &str, name: &str, title: &str, repository: &Repository, spec: Map<String, Value>) -> Value {
+        let mut annotations = Map::new();
+        annotations.insert("backstage.io/source-location".to_string(), json!(format!("url:{}", repository.url)));
+        if let Some(slug) = crate::ingestion::releases::github_slug(&repository.url) {
+            annotations.insert("github.com/project-slug".to_string(), json!(slug));
+        }
+        annotations.insert("wavelength/repository-id".to_string(), json!(repository.id));
+
+        json!({
+            "apiVersion": "backstage.io/v1alpha1",
+            "kind": kind,
+            "metadata": {
+                "name": name,
+                "title": title,
+                "annotations": annotations,
+            },
+            "spec": spec,
+        })
+    }
+}
+
+/// Backstage names are at most 63 characters of `[a-z0-9-_.]`, starting and ending alphanumerically
+fn entity_name(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.chars().take(63).collect();
+    let slug = slug.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    if slug.is_empty() { "unnamed".to_string() } else { slug.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_names() {
+        assert_eq!(entity_name("My Service (v2)"), "my-service-v2");
+        assert_eq!(entity_name("--PostgreSQL--"), "postgresql");
+        assert_eq!(entity_name("!!!"), "unnamed");
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use crate::report::plugin_sections::escape;
+use crate::security::VulnerabilitySeverity;
+use crate::storage::Repository;
+
+/// What the digest says about one repository
+#[derive(Debug, Clone)]
+pub struct DigestEntry {
+    pub repository: Repository,
+    pub dependencies: usize,
+    pub services: usize,
+    pub endpoints: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub other_vulnerabilities: usize,
+}
+
+impl DigestEntry {
+    pub fn count_vulnerabilities<'a>(&mut self, severities: impl IntoIterator<Item = &'a VulnerabilitySeverity>) {
+        for severity in severities {
+            match severity {
+                VulnerabilitySeverity::Critical => self.critical += 1,
+                VulnerabilitySeverity::High => self.high += 1,
+                _ => self.other_vulnerabilities += 1,
+            }
+        }
+    }
+}
+
+/// A rendered message: HTML for mail clients that show it, plain text for the rest
+#[derive(Debug, Clone)]
+pub struct EmailBody {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+// Mail clients drop <style> blocks, so everything is styled inline
+const CELL: &str = "padding:6px 10px;border-bottom:1px solid #ecf0f1;text-align:left";
+
+fn page(title: &str, content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="UTF-8"><title>{title}</title></head>
+<body style="margin:0;padding:20px;background:#f5f5f5;font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,Arial,sans-serif;color:#333">
+<div style="max-width:720px;margin:0 auto;background:#fff;padding:30px;border-radius:8px">
+<h1 style="color:#2c3e50;border-bottom:3px solid #3498db;padding-bottom:10px;font-size:22px">{title}</h1>
+{content}
+<p style="color:#7f8c8d;font-size:12px;margin-top:30px">Sent by Wavelength Architecture Decoder</p>
+</div>
+</body>
+</html>
+"#,
+        title = escape(title),
+        content = content
+    )
+}
+
+/// The scheduled summary of every analyzed repository; repositories analyzed since `since` are marked new
+pub fn digest(entries: &[DigestEntry], since: DateTime<Utc>, base_url: &str) -> EmailBody {
+    let analyzed = entries.iter()
+        .filter(|e| e.repository.last_analyzed_at.is_some_and(|at| at >= since))
+        .count();
+    let urgent: usize = entries.iter().map(|e| e.critical + e.high).sum();
+    let subject = format!(
+        "Wavelength digest: {} repositories, {} analyzed recently, {} critical/high findings",
+        entries.len(), analyzed, urgent
+    );
+
+    let mut rows = String::new();
+    let mut text = format!("{}\n\n", subject);
+    for entry in entries {
+        let repository = &entry.repository;
+        let link = format!("{}/#repository-detail?repo={}", base_url, repository.id);
+        let analyzed_at = repository.last_analyzed_at
+            .map(|at| at.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        let fresh = repository.last_analyzed_at.is_some_and(|at| at >= since);
+        let findings_style = if entry.critical + entry.high > 0 { "color:#c0392b;font-weight:bold" } else { "" };
+        rows.push_str(&format!(
+            "<tr><td style=\"{cell}\"><a href=\"{link}\" style=\"color:#3498db\">{name}</a>{new}</td><td style=\"{cell}\">{analyzed_at}</td><td style=\"{cell}\">{deps}</td><td style=\"{cell}\">{services}</td><td style=\"{cell}\">{endpoints}</td><td style=\"{cell};{findings_style}\">{critical} / {high}</td><td style=\"{cell}\">{other}</td></tr>\n",
+            cell = CELL,
+            link = escape(&link),
+            name = escape(&repository.name),
+            new = if fresh { " <span style=\"color:#27ae60;font-size:11px\">NEW</span>" } else { "" },
+            analyzed_at = analyzed_at,
+            deps = entry.dependencies,
+            services = entry.services,
+            endpoints = entry.endpoints,
+            findings_style = findings_style,
+            critical = entry.critical,
+            high = entry.high,
+            other = entry.other_vulnerabilities,
+        ));
+        text.push_str(&format!(
+            "- {} (analyzed {}): {} dependencies, {} services, {} endpoints, {} critical, {} high, {} other findings\n  {}\n",
+            repository.name, analyzed_at, entry.dependencies, entry.services, entry.endpoints,
+            entry.critical, entry.high, entry.other_vulnerabilities, link
+        ));
+    }
+
+    let content = if entries.is_empty() {
+        "<p>No repositories have been analyzed yet.</p>".to_string()
+    } else {
+        format!(
+            "<p>{analyzed} of {total} repositories were analyzed since {since}.</p>\n<table style=\"border-collapse:collapse;width:100%;font-size:14px\">\n<tr><th style=\"{cell}\">Repository</th><th style=\"{cell}\">Last analyzed</th><th style=\"{cell}\">Dependencies</th><th style=\"{cell}\">Services</th><th style=\"{cell}\">Endpoints</th><th style=\"{cell}\">Critical / High</th><th style=\"{cell}\">Other findings</th></tr>\n{rows}</table>",
+            analyzed = analyzed,
+            total = entries.len(),
+            since = since.format("%Y-%m-%d %H:%M UTC"),
+            cell = CELL,
+            rows = rows,
+        )
+    };
+    EmailBody { html: page("Architecture digest", &content), text, subject }
+}
+
+/// Sent when an analysis fails
+pub fn failure_alert(repository: &Repository, error: &str, base_url: &str) -> EmailBody {
+    let subject = format!("Wavelength analysis failed: {}", repository.name);
+    let link = format!("{}/#repository-detail?repo={}", base_url, repository.id);
+    let content = format!(
+        "<p>Analysis of <a href=\"{link}\" style=\"color:#3498db\">{name}</a> ({url}, {branch} branch) failed at {at}:</p>\n<pre style=\"background:#fdf2f2;border-left:4px solid #c0392b;padding:12px;white-space:pre-wrap;font-size:13px\">{error}</pre>",
+        link = escape(&link),
+        name = escape(&repository.name),
+        url = escape(&repository.url),
+        branch = escape(&repository.branch),
+        at = Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        error = escape(error),
+    );
+    let text = format!(
+        "{}\n\nAnalysis of {} ({}, {} branch) failed:\n\n{}\n\n{}\n",
+        subject, repository.name, repository.url, repository.branch, error, link
+    );
+    EmailBody { html: page(&subject, &content), text, subject }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn repository(name: &str, analyzed_hours_ago: i64) -> Repository {
+        Repository {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            url: format!("https://github.com/acme/{}", name),
+            branch: "main".to_string(),
+            auth_type: None,
+            auth_value: None,
+            last_analyzed_at: Some(Utc::now() - Duration::hours(analyzed_hours_ago)),
+            analysis_profile: None,
+            workspace_id: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_digest_marks_recent_analyses_and_escapes() {
+        let mut shop = DigestEntry {
+            repository: repository("shop<v2>", 2),
+            dependencies: 40,
+            services: 3,
+            endpoints: 12,
+            critical: 0,
+            high: 0,
+            other_vulnerabilities: 0,
+        };
+        shop.count_vulnerabilities(&[VulnerabilitySeverity::Critical, VulnerabilitySeverity::High, VulnerabilitySeverity::Low]);
+        let billing = DigestEntry { repository: repository("billing", 72), ..shop.clone() };
+
+        let body = digest(&[shop, billing], Utc::now() - Duration::hours(24), "http://localhost:8080");
+        assert_eq!(body.subject, "Wavelength digest: 2 repositories, 1 analyzed recently, 4 critical/high findings");
+        assert!(body.html.contains("shop&lt;v2&gt;</a> <span"));
+        assert_eq!(body.html.matches("NEW").count(), 1);
+        assert!(body.text.contains("- billing (analyzed "));
+
+        let alert = failure_alert(&repository("billing", 0), "clone failed: <timeout>", "http://localhost:8080");
+        assert!(alert.html.contains("clone failed: &lt;timeout&gt;"));
+    }
+}
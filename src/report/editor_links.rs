@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+use crate::config::StorageConfig;
+use crate::ingestion::RepositoryCrawler;
+
+/// Link templates for known editors. `{path}` is the file's absolute path, `{line}` its line
+/// (1 when unknown) and `{relative_path}` the path inside the repository.
+pub const EDITOR_PRESETS: &[(&str, &str)] = &[
+    ("vscode", "vscode://file{path}:{line}"),
+    ("vscode-insiders", "vscode-insiders://file{path}:{line}"),
+    ("cursor", "cursor://file{path}:{line}"),
+    ("windsurf", "windsurf://file{path}:{line}"),
+    ("idea", "idea://open?file={path}&line={line}"),
+    ("webstorm", "webstorm://open?file={path}&line={line}"),
+    ("pycharm", "pycharm://open?file={path}&line={line}"),
+    ("goland", "goland://open?file={path}&line={line}"),
+    ("phpstorm", "phpstorm://open?file={path}&line={line}"),
+    ("rubymine", "rubymine://open?file={path}&line={line}"),
+    ("clion", "clion://open?file={path}&line={line}"),
+    ("rider", "rider://open?file={path}&line={line}"),
+    ("sublime", "subl://open?url=file://{path}&line={line}"),
+    ("textmate", "txmt://open?url=file://{path}&line={line}"),
+    ("atom", "atom://core/open/file?filename={path}&line={line}"),
+    ("macvim", "mvim://open?url=file://{path}&line={line}"),
+    ("zed", "zed://file{path}:{line}"),
+];
+
+/// Schemes a custom template may use besides those of the presets. Everything else, such as
+/// `javascript:` or `data:`, is refused since the links end up in `href` attributes.
+const WEB_SCHEMES: &[&str] = &["http", "https"];
+
+/// Builds "open in editor" links from `EDITOR_PROTOCOL` or a per-request `?editor=` override
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorLinks {
+    template: String,
+}
+
+impl EditorLinks {
+    /// `setting` is a preset name, or a template containing `{path}` or `{relative_path}` whose
+    /// scheme is one of the presets' or http(s); `None` for anything else
+    pub fn parse(setting: &str) -> Option<Self> {
+        let setting = setting.trim();
+        let name = if setting.is_empty() { "vscode" } else { setting };
+        if let Some((_, template)) = EDITOR_PRESETS.iter().find(|(preset, _)| preset.eq_ignore_ascii_case(name)) {
+            return Some(EditorLinks { template: template.to_string() });
+        }
+        let placeholders = setting.contains("{path}") || setting.contains("{relative_path}");
+        let plain = !setting.chars().any(|c| c.is_whitespace() || c.is_control() || "\"'<>`\\".contains(c));
+        let scheme = setting.split_once(':').map(|(scheme, _)| scheme.to_ascii_lowercase())?;
+        let known = WEB_SCHEMES.contains(&scheme.as_str())
+            || EDITOR_PRESETS.iter().any(|(_, template)| template.split_once(':').map(|(s, _)| s) == Some(scheme.as_str()));
+        (placeholders && plain && known).then(|| EditorLinks { template: setting.to_string() })
+    }
+
+    /// Like `parse`, falling back to VS Code links for a setting it refuses
+    pub fn new(setting: &str) -> Self {
+        EditorLinks::parse(setting).unwrap_or_else(|| {
+            log::warn!("⚠ Editor setting {:?} is not a preset or an editor link template; using vscode", setting);
+            EditorLinks { template: EDITOR_PRESETS[0].1.to_string() }
+        })
+    }
+
+    /// A request's `?editor=` wins over the server's `EDITOR_PROTOCOL` when it is a valid setting
+    pub fn for_request(configured: &str, requested: Option<&String>) -> Self {
+        requested.filter(|e| !e.trim().is_empty())
+            .and_then(|e| EditorLinks::parse(e))
+            .unwrap_or_else(|| EditorLinks::new(configured))
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Link to `file_path` (relative to the repository checkout at `root`, or absolute)
+    pub fn link(&self, root: &Path, file_path: &str, line: Option<usize>) -> String {
+        let relative = file_path.trim_start_matches("./");
+        let absolute = if Path::new(file_path).is_absolute() { PathBuf::from(file_path) } else { root.join(relative) };
+        // Windows paths become /C:/..., which every template expects after file:// or file
+        let mut path = absolute.to_string_lossy().replace('\\', "/");
+        if !path.starts_with('/') {
+            path.insert(0, '/');
+        }
+        self.template
+            .replace("{path}", &encode_path(&path))
+            .replace("{relative_path}", &encode_path(relative))
+            .replace("{line}", &line.unwrap_or(1).max(1).to_string())
+    }
+}
+
+/// Escapes the characters that would end a path early inside a URL
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '%' => encoded.push_str("%25"),
+            ' ' => encoded.push_str("%20"),
+            '#' => encoded.push_str("%23"),
+            '?' => encoded.push_str("%3F"),
+            '&' => encoded.push_str("%26"),
+            _ => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+/// Absolute path of a repository's checkout, which editors need to open its files
+pub fn repository_root(storage: &StorageConfig, url: &str) -> Option<PathBuf> {
+    let root = RepositoryCrawler::new(storage).ok()?.get_repo_path(url);
+    if root.is_absolute() {
+        return Some(root);
+    }
+    Some(std::fs::canonicalize(&root).unwrap_or_else(|_| std::env::current_dir().unwrap_or_default().join(root)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_link_templates() {
+        let root = Path::new("/work/my repo");
+        assert_eq!(EditorLinks::new("vscode").link(root, "src/main.rs", Some(42)), "vscode://file/work/my%20repo/src/main.rs:42");
+        assert_eq!(EditorLinks::new("idea").link(root, "./src/main.rs", None), "idea://open?file=/work/my%20repo/src/main.rs&line=1");
+        assert_eq!(EditorLinks::new("zed").link(root, "/abs/x.rs", Some(3)), "zed://file/abs/x.rs:3");
+        assert_eq!(
+            EditorLinks::new("https://code.internal/edit?file={relative_path}#L{line}").link(root, "src/main.rs", Some(7)),
+            "https://code.internal/edit?file=src/main.rs#L7"
+        );
+
+        let requested = "sublime".to_string();
+        assert_eq!(EditorLinks::for_request("vscode", Some(&requested)).template(), "subl://open?url=file://{path}&line={line}");
+        assert_eq!(EditorLinks::for_request("vscode", None), EditorLinks::new("vscode"));
+    }
+
+    #[test]
+    fn test_editor_settings_that_could_inject_markup_are_refused() {
+        for setting in [
+            "javascript:alert(1)//{path}",
+            "JavaScript:alert(1)//{path}",
+            "data:text/html,<script>{path}</script>",
+            "vscode://file{path}\"onmouseover=\"alert(1)",
+            "https://x/{path}' onclick='alert(1)",
+            "notepad",
+        ] {
+            assert_eq!(EditorLinks::parse(setting), None, "{}", setting);
+        }
+        assert!(EditorLinks::parse("idea://open?file={path}&line={line}").is_some());
+
+        let requested = "javascript:alert(document.cookie)//{path}".to_string();
+        assert_eq!(EditorLinks::for_request("idea", Some(&requested)), EditorLinks::new("idea"));
+        assert_eq!(EditorLinks::new("javascript:{path}"), EditorLinks::new("vscode"));
+    }
+}
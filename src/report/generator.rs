@@ -3,10 +3,13 @@ use chrono::Utc;
 use crate::storage::{
     RepositoryRepository, DependencyRepository, ServiceRepository,
     CodeElementRepository, CodeRelationshipRepository, SecurityRepository,
-    ToolRepository, PortRepository, EndpointRepository,
-    Repository, StoredDependency, StoredService, StoredPort, StoredEndpoint,
+    ToolRepository, PortRepository, EndpointRepository, ServiceBoundaryRepository, TodoRepository, DocQualityRepository, PluginReportRepository,
+    Repository, StoredDependency, StoredService, StoredPort, StoredEndpoint, StoredServiceBoundary, TodoSummary, StoredDocQuality, StoredReportSection,
 };
 use crate::graph::GraphBuilder;
+use crate::config::StorageConfig;
+use crate::report::editor_links::{repository_root, EditorLinks};
+use crate::report::plugin_sections::escape;
 
 pub struct ReportGenerator {
     repo_repo: RepositoryRepository,
@@ -18,7 +21,13 @@ pub struct ReportGenerator {
     tool_repo: ToolRepository,
     port_repo: PortRepository,
     endpoint_repo: EndpointRepository,
+    boundary_repo: ServiceBoundaryRepository,
+    todo_repo: TodoRepository,
+    doc_quality_repo: DocQualityRepository,
+    plugin_report_repo: PluginReportRepository,
     graph_builder: GraphBuilder,
+    /// File references become "open in editor" links when set
+    editor: Option<(EditorLinks, StorageConfig)>,
 }
 
 impl ReportGenerator {
@@ -32,6 +41,10 @@ impl ReportGenerator {
         tool_repo: ToolRepository,
         port_repo: PortRepository,
         endpoint_repo: EndpointRepository,
+        boundary_repo: ServiceBoundaryRepository,
+        todo_repo: TodoRepository,
+        doc_quality_repo: DocQualityRepository,
+        plugin_report_repo: PluginReportRepository,
         graph_builder: GraphBuilder,
     ) -> Self {
         ReportGenerator {
@@ -44,10 +57,21 @@ impl ReportGenerator {
             tool_repo,
             port_repo,
             endpoint_repo,
+            boundary_repo,
+            todo_repo,
+            doc_quality_repo,
+            plugin_report_repo,
             graph_builder,
+            editor: None,
         }
     }
 
+    /// Link file references to the repository's checkout with `links`
+    pub fn with_editor_links(mut self, links: EditorLinks, storage: StorageConfig) -> Self {
+        self.editor = Some((links, storage));
+        self
+    }
+
     pub fn generate_html_report(&self, repository_id: &str) -> Result<String> {
         // Get repository
         let repo = self.repo_repo.find_by_id(repository_id)?
@@ -63,6 +87,10 @@ impl ReportGenerator {
         let tools = self.tool_repo.get_tools_by_repository(repository_id)?;
         let ports = self.port_repo.get_by_repository(repository_id)?;
         let endpoints = self.endpoint_repo.get_by_repository(repository_id)?;
+        let boundaries = self.boundary_repo.get_by_repository(repository_id)?;
+        let todo_summary = self.todo_repo.get_summary(repository_id)?;
+        let doc_quality = self.doc_quality_repo.get_latest(repository_id)?;
+        let plugin_sections = self.plugin_report_repo.get_sections(repository_id)?;
 
         // Get graph statistics
         let graph = self.graph_builder.build_for_repository(repository_id)?;
@@ -80,6 +108,10 @@ impl ReportGenerator {
             &tools,
             &ports,
             &endpoints,
+            &boundaries,
+            &todo_summary,
+            doc_quality.as_ref(),
+            &plugin_sections,
             &graph,
             &graph_stats,
         )?;
@@ -99,10 +131,26 @@ impl ReportGenerator {
         tools: &[crate::storage::tool_repo::StoredTool],
         ports: &[StoredPort],
         endpoints: &[StoredEndpoint],
+        boundaries: &[StoredServiceBoundary],
+        todo_summary: &TodoSummary,
+        doc_quality: Option<&StoredDocQuality>,
+        plugin_sections: &[StoredReportSection],
         graph: &crate::graph::graph::KnowledgeGraph,
         graph_stats: &crate::graph::graph::GraphStatistics,
     ) -> Result<String> {
         let now = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+        let editor = self.editor.as_ref()
+            .and_then(|(links, storage)| Some((links, repository_root(storage, &repo.url)?)));
+        let file_ref = |path: &str, line: Option<usize>| {
+            let label = match line {
+                Some(line) => format!("<code>{}:{}</code>", escape(path), line),
+                None => format!("<code>{}</code>", escape(path)),
+            };
+            match &editor {
+                Some((links, root)) if !path.is_empty() => format!(r#"<a href="{}">{}</a>"#, escape(&links.link(root, path, line)), label),
+                _ => label,
+            }
+        };
         
         // Group dependencies by package manager
         let mut deps_by_manager: std::collections::HashMap<String, Vec<&StoredDependency>> = std::collections::HashMap::new();
@@ -437,12 +485,12 @@ impl ReportGenerator {
                     r#"                    <tr>
                         <td>{}</td>
                         <td><span class="badge badge-success">{}</span></td>
-                        <td><code>{}</code></td>
+                        <td>{}</td>
                     </tr>
 "#,
                     service.name,
                     service.service_type,
-                    service.file_path
+                    file_ref(&service.file_path, service.line_number)
                 ));
             }
             if svcs.len() > 30 {
@@ -523,12 +571,12 @@ impl ReportGenerator {
                     r#"                    <tr>
                         <td>{}</td>
                         <td>{}</td>
-                        <td><code>{}</code></td>
+                        <td>{}</td>
                     </tr>
 "#,
                     entity.name,
                     &entity.provider,
-                    entity.file_path
+                    file_ref(&entity.file_path, entity.line_number)
                 ));
             }
             if entities.len() > 20 {
@@ -556,13 +604,13 @@ impl ReportGenerator {
             <div class="vulnerability">
                 <strong>{}</strong><br>
                 <em>Severity: {}</em><br>
-                <code>{}</code><br>
+                {}<br>
                 <p>{}</p>
             </div>
 "#,
                     vuln.vulnerability_type,
                     format!("{:?}", vuln.severity),
-                    vuln.file_path,
+                    file_ref(&vuln.file_path, vuln.line_number),
                     vuln.description
                 ));
             }
@@ -599,12 +647,12 @@ impl ReportGenerator {
                 r#"                    <tr>
                         <td>{}</td>
                         <td><span class="badge badge-warning">{}</span></td>
-                        <td><code>{}</code></td>
+                        <td>{}</td>
                     </tr>
 "#,
                 tool.name,
                 tool.tool_type,
-                tool.file_path
+                file_ref(&tool.file_path, tool.line_number)
             ));
         }
         if tools.len() > 50 {
@@ -659,14 +707,14 @@ impl ReportGenerator {
                         <td><strong>{}</strong></td>
                         <td>{}</td>
                         <td>{}</td>
-                        <td><code>{}</code></td>
+                        <td>{}</td>
                         <td><code>{}</code></td>
                     </tr>
 "#,
                         port.port,
                         port.framework.as_ref().map(|s| s.as_str()).unwrap_or("N/A"),
                         port.environment.as_ref().map(|s| s.as_str()).unwrap_or("N/A"),
-                        port.file_path,
+                        file_ref(&port.file_path, port.line_number),
                         if port.context.len() > 60 { format!("{}...", &port.context[..60]) } else { port.context.clone() }
                     ));
                 }
@@ -729,14 +777,14 @@ impl ReportGenerator {
                         <td><code>{}</code></td>
                         <td>{}</td>
                         <td>{}</td>
-                        <td><code>{}</code></td>
+                        <td>{}</td>
                     </tr>
 "#,
                         endpoint.method,
                         endpoint.path,
                         endpoint.handler.as_ref().map(|s| s.as_str()).unwrap_or("N/A"),
                         if endpoint.parameters.is_empty() { "None".to_string() } else { endpoint.parameters.join(", ") },
-                        endpoint.file_path
+                        file_ref(&endpoint.file_path, endpoint.line_number)
                     ));
                 }
                 if endpoint_list.len() > 100 {
@@ -752,6 +800,191 @@ impl ReportGenerator {
         }
         html.push_str("        </div>");
 
+        // Add service boundaries section
+        html.push_str(&format!(
+            r#"
+        <div class="section">
+            <h2>🧩 Service Boundaries</h2>
+            <p>Suggested service/domain boundaries: <strong>{}</strong></p>
+"#,
+            boundaries.len()
+        ));
+
+        if !boundaries.is_empty() {
+            html.push_str(
+                r#"
+            <table>
+                <thead>
+                    <tr>
+                        <th>Boundary</th>
+                        <th>Kind</th>
+                        <th>Confidence</th>
+                        <th>Cohesion</th>
+                        <th>Data Stores</th>
+                        <th>Depends On</th>
+                    </tr>
+                </thead>
+                <tbody>
+"#,
+            );
+            for boundary in boundaries {
+                html.push_str(&format!(
+                    r#"                    <tr>
+                        <td><code>{}</code><br><small>{}</small></td>
+                        <td><span class="badge badge-primary">{}</span></td>
+                        <td>{:.0}%</td>
+                        <td>{:.0}%</td>
+                        <td>{}</td>
+                        <td>{}</td>
+                    </tr>
+"#,
+                    boundary.name,
+                    boundary.evidence.join("; "),
+                    boundary.kind,
+                    boundary.confidence * 100.0,
+                    boundary.cohesion * 100.0,
+                    if boundary.data_stores.is_empty() { "None".to_string() } else { boundary.data_stores.join(", ") },
+                    if boundary.depends_on.is_empty() { "None".to_string() } else { boundary.depends_on.join(", ") }
+                ));
+            }
+            html.push_str("                </tbody>\n            </table>\n");
+        } else {
+            html.push_str("<p><em>No service boundaries inferred.</em></p>");
+        }
+        html.push_str("        </div>");
+
+        // Add TODO inventory section
+        html.push_str(&format!(
+            r#"
+        <div class="section">
+            <h2>📌 TODO Inventory</h2>
+            <p>TODO/FIXME/HACK/XXX markers found: <strong>{}</strong></p>
+"#,
+            todo_summary.total
+        ));
+
+        if todo_summary.total > 0 {
+            html.push_str("            <p>");
+            for (kind, count) in &todo_summary.by_kind {
+                html.push_str(&format!(r#"<span class="badge badge-primary">{}: {}</span> "#, kind.to_uppercase(), count));
+            }
+            html.push_str("</p>\n            <p><strong>By age:</strong> ");
+            let ages: Vec<String> = todo_summary.by_age.iter()
+                .map(|(bucket, count)| format!("{} {}", bucket.replace('_', " "), count))
+                .collect();
+            html.push_str(&ages.join(", "));
+            html.push_str("</p>\n");
+
+            if !todo_summary.oldest.is_empty() {
+                html.push_str(
+                    r#"
+            <div class="group-header">Oldest markers</div>
+            <table>
+                <thead>
+                    <tr>
+                        <th>Kind</th>
+                        <th>Text</th>
+                        <th>Author</th>
+                        <th>Age (days)</th>
+                        <th>Location</th>
+                    </tr>
+                </thead>
+                <tbody>
+"#,
+                );
+                for todo in &todo_summary.oldest {
+                    html.push_str(&format!(
+                        r#"                    <tr>
+                        <td><span class="badge badge-primary">{}</span></td>
+                        <td>{}</td>
+                        <td>{}</td>
+                        <td>{}</td>
+                        <td>{}</td>
+                    </tr>
+"#,
+                        todo.kind.to_uppercase(),
+                        todo.text,
+                        todo.author.as_deref().unwrap_or("unknown"),
+                        todo.age_days.map(|d| d.to_string()).unwrap_or_default(),
+                        file_ref(&todo.file_path, Some(todo.line_number))
+                    ));
+                }
+                html.push_str("                </tbody>\n            </table>\n");
+            }
+        } else {
+            html.push_str("<p><em>No TODO markers found.</em></p>");
+        }
+        html.push_str("        </div>");
+
+        // Add documentation quality section
+        if let Some(quality) = doc_quality {
+            let report = &quality.report;
+            html.push_str(&format!(
+                r#"
+        <div class="section">
+            <h2>📚 Documentation Quality</h2>
+            <p>Onboarding score: <strong>{}</strong>/100 (grade <strong>{}</strong>)</p>
+            <p>"#,
+                report.score, report.grade
+            ));
+            for check in &report.checks {
+                let badge = if check.passed { "badge-success" } else { "badge-danger" };
+                html.push_str(&format!(r#"<span class="badge {}" title="{}">{}</span> "#, badge, check.detail, check.name));
+            }
+            html.push_str("</p>\n");
+
+            if report.gaps.is_empty() {
+                html.push_str("            <p><em>No documentation gaps found.</em></p>\n");
+            } else {
+                html.push_str("            <div class=\"group-header\">Suggested improvements</div>\n            <ul>\n");
+                for gap in &report.gaps {
+                    html.push_str(&format!("                <li>{}</li>\n", gap));
+                }
+                html.push_str("            </ul>\n");
+            }
+
+            if !report.drift.is_empty() {
+                html.push_str(
+                    r#"            <div class="group-header">Documentation drift</div>
+            <table>
+                <thead>
+                    <tr>
+                        <th>Kind</th>
+                        <th>Finding</th>
+                        <th>Location</th>
+                    </tr>
+                </thead>
+                <tbody>
+"#,
+                );
+                for drift in &report.drift {
+                    let kind = match drift.kind {
+                        crate::analysis::DriftKind::Endpoint => "Endpoint",
+                        crate::analysis::DriftKind::Service => "Service",
+                        crate::analysis::DriftKind::EnvVar => "Env var",
+                    };
+                    let location = match (&drift.file_path, drift.line_number) {
+                        (Some(file), line) => file_ref(file, line),
+                        _ => String::new(),
+                    };
+                    html.push_str(&format!(
+                        r#"                    <tr>
+                        <td><span class="badge badge-warning">{}</span></td>
+                        <td>{}</td>
+                        <td>{}</td>
+                    </tr>
+"#,
+                        kind, drift.message, location
+                    ));
+                }
+                html.push_str("                </tbody>\n            </table>\n");
+            }
+            html.push_str("        </div>");
+        }
+
+        // Sections contributed by report_section plugins
+        html.push_str(&super::plugin_sections::render_sections(plugin_sections));
+
         // Add graph statistics
         html.push_str(&format!(
             r#"
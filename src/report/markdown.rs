@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::report::sarif::relative_uri;
+use crate::security::SecurityVulnerability;
+use crate::storage::{Repository, StoredDependency, StoredEndpoint, StoredService};
+
+/// Table cells can't hold pipes or newlines
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn location(root: &Path, file_path: &str, line: Option<usize>) -> String {
+    let file = relative_uri(root, file_path);
+    match line {
+        Some(line) => format!("`{}:{}`", file, line),
+        None => format!("`{}`", file),
+    }
+}
+
+/// A Markdown summary for pull request comments, wikis and CI job summaries
+pub fn markdown_summary(
+    repository: &Repository,
+    dependencies: &[StoredDependency],
+    services: &[StoredService],
+    endpoints: &[StoredEndpoint],
+    vulnerabilities: &[SecurityVulnerability],
+    root: &Path,
+) -> String {
+    let mut md = format!("# Architecture Summary: {}\n\n", repository.name);
+    md.push_str(&format!("- **Source:** {} ({} branch)\n", repository.url, repository.branch));
+    if let Some(at) = repository.last_analyzed_at {
+        md.push_str(&format!("- **Analyzed:** {}\n", at.format("%Y-%m-%d %H:%M UTC")));
+    }
+    md.push_str(&format!(
+        "- **Found:** {} dependencies, {} services, {} endpoints, {} security findings\n",
+        dependencies.len(), services.len(), endpoints.len(), vulnerabilities.len()
+    ));
+
+    let mut managers: BTreeMap<&str, usize> = BTreeMap::new();
+    for dependency in dependencies {
+        *managers.entry(dependency.package_manager.as_str()).or_insert(0) += 1;
+    }
+    if !managers.is_empty() {
+        md.push_str("\n## Dependencies\n\n| Package manager | Packages |\n|---|---|\n");
+        for (manager, count) in managers {
+            md.push_str(&format!("| {} | {} |\n", cell(manager), count));
+        }
+    }
+
+    if !services.is_empty() {
+        md.push_str("\n## Services\n\n| Service | Provider | Type | Where |\n|---|---|---|---|\n");
+        for service in services {
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(&service.name), cell(&service.provider), cell(&service.service_type),
+                location(root, &service.file_path, service.line_number)
+            ));
+        }
+    }
+
+    if !endpoints.is_empty() {
+        md.push_str("\n## Endpoints\n\n| Method | Path | Handler | Where |\n|---|---|---|---|\n");
+        for endpoint in endpoints {
+            md.push_str(&format!(
+                "| {} | `{}` | {} | {} |\n",
+                endpoint.method, cell(&endpoint.path), cell(endpoint.handler.as_deref().unwrap_or("-")),
+                location(root, &endpoint.file_path, endpoint.line_number)
+            ));
+        }
+    }
+
+    md.push_str("\n## Security Findings\n\n");
+    if vulnerabilities.is_empty() {
+        md.push_str("No security findings.\n");
+    } else {
+        let mut sorted: Vec<&SecurityVulnerability> = vulnerabilities.iter().collect();
+        sorted.sort_by_key(|v| v.severity.clone() as u8);
+        md.push_str("| Severity | Finding | Where | Recommendation |\n|---|---|---|---|\n");
+        for vulnerability in sorted {
+            md.push_str(&format!(
+                "| {:?} | {} | {} | {} |\n",
+                vulnerability.severity, cell(&vulnerability.description),
+                location(root, &vulnerability.file_path, vulnerability.line_number),
+                cell(&vulnerability.recommendation)
+            ));
+        }
+    }
+    md
+}
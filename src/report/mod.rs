@@ -1,4 +1,16 @@
 pub mod generator;
+pub mod openapi;
+pub mod plugin_sections;
+pub mod backstage;
+pub mod editor_links;
+pub mod digest;
+pub mod markdown;
+pub mod sarif;
+pub mod sbom;
+pub mod schema;
 
 pub use generator::ReportGenerator;
-
+pub use openapi::OpenApiGenerator;
+pub use backstage::BackstageGenerator;
+pub use editor_links::EditorLinks;
+pub use sbom::SbomGenerator;
@@ -0,0 +1,201 @@
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use crate::storage::{Repository, StoredEndpoint};
+
+/// Generates an OpenAPI 3 document from detected endpoints.
+/// The result is a starting point for teams without a spec, not an authoritative contract.
+pub struct OpenApiGenerator;
+
+impl OpenApiGenerator {
+    pub fn new() -> Self {
+        OpenApiGenerator
+    }
+
+    pub fn generate(&self, repository: &Repository, endpoints: &[StoredEndpoint]) -> Value {
+        let mut paths: Map<String, Value> = Map::new();
+        let mut operation_ids: HashSet<String> = HashSet::new();
+        let mut tags: Vec<String> = Vec::new();
+
+        let mut sorted: Vec<&StoredEndpoint> = endpoints.iter()
+            // Endpoints read from existing specs are already documented
+            .filter(|e| e.framework.as_deref() != Some("openapi"))
+            // OpenAPI can't describe WebSocket channels
+            .filter(|e| e.protocol != "websocket")
+            .collect();
+        sorted.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.method.cmp(&b.method)));
+
+        for endpoint in sorted {
+            let (path, path_params) = normalize_path(&endpoint.path);
+            let methods: Vec<String> = if endpoint.method == "ANY" {
+                ["get", "post", "put", "patch", "delete"].iter().map(|m| m.to_string()).collect()
+            } else {
+                vec![endpoint.method.to_lowercase()]
+            };
+
+            let tag = tag_for_path(&path);
+            if let Some(tag) = &tag {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+
+            for method in methods {
+                let path_item = paths.entry(path.clone())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                let path_item = match path_item.as_object_mut() {
+                    Some(p) => p,
+                    None => continue,
+                };
+                // Keep the first detection when the same route is found twice
+                if path_item.contains_key(&method) {
+                    continue;
+                }
+
+                let base_id = endpoint.handler.clone()
+                    .filter(|h| !h.is_empty())
+                    .unwrap_or_else(|| fallback_operation_id(&method, &path));
+                let operation_id = unique_operation_id(&base_id, &method, &mut operation_ids);
+
+                let mut parameter_names: Vec<String> = path_params.clone();
+                for param in &endpoint.parameters {
+                    if !parameter_names.contains(param) {
+                        parameter_names.push(param.clone());
+                    }
+                }
+                let parameters: Vec<Value> = parameter_names.iter()
+                    .map(|name| json!({
+                        "name": name,
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string" }
+                    }))
+                    .collect();
+
+                let mut operation = json!({
+                    "operationId": operation_id,
+                    "summary": format!("{} {}", method.to_uppercase(), path),
+                    "responses": {
+                        "200": { "description": "Successful response" }
+                    },
+                    "x-source": {
+                        "file": endpoint.file_path,
+                        "line": endpoint.line_number,
+                        "framework": endpoint.framework,
+                    }
+                });
+                if let Some(op) = operation.as_object_mut() {
+                    if !parameters.is_empty() {
+                        op.insert("parameters".to_string(), Value::Array(parameters));
+                    }
+                    if let Some(tag) = &tag {
+                        op.insert("tags".to_string(), json!([tag]));
+                    }
+                    if matches!(method.as_str(), "post" | "put" | "patch") {
+                        op.insert("requestBody".to_string(), json!({
+                            "content": { "application/json": { "schema": { "type": "object" } } }
+                        }));
+                    }
+                }
+
+                path_item.insert(method, operation);
+            }
+        }
+
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": repository.name,
+                "description": format!("Generated by Wavelength Architecture Decoder from endpoints detected in {}", repository.url),
+                "version": "0.0.0"
+            },
+            "tags": tags.iter().map(|t| json!({ "name": t })).collect::<Vec<_>>(),
+            "paths": paths,
+        })
+    }
+}
+
+/// Convert framework-specific route syntax to OpenAPI templates:
+/// `:id`, `<int:id>`, `{id:[0-9]+}` and `[id]` all become `{id}`
+fn normalize_path(path: &str) -> (String, Vec<String>) {
+    // Typed forms go first so their inner colons aren't mistaken for `:param`
+    let patterns = [
+        (Regex::new(r"<(?:\w+:)?(\w+)>").unwrap(), "{$1}"),
+        (Regex::new(r"\{(\w+)(?::[^}]*)?\}").unwrap(), "{$1}"),
+        (Regex::new(r"\[(?:\.\.\.)?(\w+)\]").unwrap(), "{$1}"),
+        (Regex::new(r"/:(\w+)").unwrap(), "/{$1}"),
+    ];
+
+    let mut normalized = if path.starts_with('/') { path.to_string() } else { format!("/{}", path) };
+    let mut params = Vec::new();
+    for (pattern, replacement) in &patterns {
+        for cap in pattern.captures_iter(&normalized.clone()) {
+            let name = cap[1].to_string();
+            if !params.contains(&name) {
+                params.push(name);
+            }
+        }
+        normalized = pattern.replace_all(&normalized, *replacement).to_string();
+    }
+    (normalized, params)
+}
+
+/// First literal path segment, skipping common prefixes like /api and /v1
+fn tag_for_path(path: &str) -> Option<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty() && !s.starts_with('{'))
+        .find(|s| *s != "api" && !(s.starts_with('v') && s[1..].chars().all(|c| c.is_ascii_digit()) && s.len() > 1))
+        .map(|s| s.to_string())
+}
+
+fn fallback_operation_id(method: &str, path: &str) -> String {
+    let mut id = method.to_string();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        let cleaned: String = segment.chars().filter(|c| c.is_alphanumeric()).collect();
+        if cleaned.is_empty() {
+            continue;
+        }
+        let mut chars = cleaned.chars();
+        if segment.starts_with('{') {
+            id.push_str("By");
+        }
+        if let Some(first) = chars.next() {
+            id.push(first.to_ascii_uppercase());
+            id.push_str(chars.as_str());
+        }
+    }
+    id
+}
+
+fn unique_operation_id(base: &str, method: &str, used: &mut HashSet<String>) -> String {
+    let mut candidate = base.to_string();
+    if used.contains(&candidate) {
+        candidate = format!("{}_{}", base, method);
+    }
+    let mut counter = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{}_{}{}", base, method, counter);
+        counter += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path_params() {
+        assert_eq!(normalize_path("/users/:id").0, "/users/{id}");
+        assert_eq!(normalize_path("/users/<int:user_id>/posts").0, "/users/{user_id}/posts");
+        assert_eq!(normalize_path("/items/{id:[0-9]+}").0, "/items/{id}");
+        assert_eq!(normalize_path("/blog/[slug]").1, vec!["slug".to_string()]);
+    }
+
+    #[test]
+    fn test_fallback_operation_id() {
+        assert_eq!(fallback_operation_id("get", "/users/{id}"), "getUsersById");
+        assert_eq!(tag_for_path("/api/v1/orders/{id}"), Some("orders".to_string()));
+    }
+}
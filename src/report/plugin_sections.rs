@@ -0,0 +1,157 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use crate::plugins::{ReportSection, SectionFormat};
+use crate::storage::StoredReportSection;
+
+static PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{\s*([\w.]+)\s*\}\}").unwrap());
+
+/// HTML for the sections plugins contributed, in the order they were stored
+pub fn render_sections(sections: &[StoredReportSection]) -> String {
+    let mut html = String::new();
+    for stored in sections {
+        html.push_str(&format!(
+            r#"
+        <div class="section">
+            <h2>🧩 {}</h2>
+            <p><span class="badge badge-primary">plugin: {}</span></p>
+"#,
+            escape(&stored.section.title),
+            escape(&stored.plugin)
+        ));
+        html.push_str(&render_body(&stored.section));
+        html.push_str("        </div>");
+    }
+    html
+}
+
+fn render_body(section: &ReportSection) -> String {
+    match &section.template {
+        Some(template) => {
+            // Values are escaped before substitution; the template itself is the plugin's own markup
+            let filled = PLACEHOLDER.replace_all(template, |caps: &regex::Captures| {
+                lookup(&section.data, &caps[1]).map(|v| escape(&scalar(v))).unwrap_or_default()
+            });
+            match section.format {
+                SectionFormat::Html => filled.into_owned(),
+                SectionFormat::Markdown => markdown_to_html(&filled),
+            }
+        }
+        None => data_table(&section.data),
+    }
+}
+
+/// `a.b.0` walks objects by key and arrays by index
+fn lookup<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(data, |value, part| match value {
+        Value::Object(map) => map.get(part),
+        Value::Array(items) => part.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::ENABLE_TABLES);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+/// An array of objects becomes a table with a column per key, an object a two-column table
+fn data_table(data: &Value) -> String {
+    match data {
+        Value::Null => "<p><em>No data.</em></p>\n".to_string(),
+        Value::Array(rows) if !rows.is_empty() && rows.iter().all(|r| r.is_object()) => {
+            let mut columns: Vec<&String> = Vec::new();
+            for row in rows.iter().filter_map(|r| r.as_object()) {
+                for key in row.keys() {
+                    if !columns.contains(&key) {
+                        columns.push(key);
+                    }
+                }
+            }
+            let mut html = String::from("            <table>\n                <thead>\n                    <tr>\n");
+            for column in &columns {
+                html.push_str(&format!("                        <th>{}</th>\n", escape(column)));
+            }
+            html.push_str("                    </tr>\n                </thead>\n                <tbody>\n");
+            for row in rows.iter().filter_map(|r| r.as_object()) {
+                html.push_str("                    <tr>\n");
+                for column in &columns {
+                    html.push_str(&format!("                        <td>{}</td>\n", escape(&row.get(*column).map(scalar).unwrap_or_default())));
+                }
+                html.push_str("                    </tr>\n");
+            }
+            html.push_str("                </tbody>\n            </table>\n");
+            html
+        }
+        Value::Object(map) => {
+            let mut html = String::from("            <table>\n                <tbody>\n");
+            for (key, value) in map {
+                html.push_str(&format!(
+                    "                    <tr>\n                        <th>{}</th>\n                        <td>{}</td>\n                    </tr>\n",
+                    escape(key),
+                    escape(&scalar(value))
+                ));
+            }
+            html.push_str("                </tbody>\n            </table>\n");
+            html
+        }
+        Value::Array(items) => {
+            let mut html = String::from("            <ul>\n");
+            for item in items {
+                html.push_str(&format!("                <li>{}</li>\n", escape(&scalar(item))));
+            }
+            html.push_str("            </ul>\n");
+            html
+        }
+        other => format!("            <p>{}</p>\n", escape(&scalar(other))),
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stored(section: ReportSection) -> StoredReportSection {
+        StoredReportSection { plugin: "cmdb".to_string(), section }
+    }
+
+    #[test]
+    fn test_markdown_template_is_filled_and_escaped() {
+        let html = render_sections(&[stored(ReportSection {
+            title: "CMDB".to_string(),
+            data: json!({"owner": {"team": "Payments <core>"}, "ids": ["CI0042"]}),
+            template: Some("**Owner:** {{ owner.team }} ({{ids.0}}){{missing}}".to_string()),
+            format: SectionFormat::Markdown,
+        })]);
+        assert!(html.contains("<h2>🧩 CMDB</h2>"));
+        assert!(html.contains("<strong>Owner:</strong> Payments &lt;core&gt; (CI0042)</p>"), "{}", html);
+    }
+
+    #[test]
+    fn test_data_without_template_renders_as_table() {
+        let html = render_sections(&[stored(ReportSection {
+            title: "Queues".to_string(),
+            data: json!([{"name": "orders", "depth": 3}, {"name": "refunds", "dlq": true}]),
+            template: None,
+            format: SectionFormat::Markdown,
+        })]);
+        assert_eq!(html.matches("<th>").count(), 3);
+        assert!(html.contains("<td>refunds</td>"));
+        assert!(html.contains("<td>true</td>"));
+    }
+}
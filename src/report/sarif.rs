@@ -0,0 +1,113 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::security::{SecurityVulnerability, VulnerabilitySeverity};
+
+/// SARIF levels, which code scanning tools use to decide what fails a build
+fn level(severity: &VulnerabilitySeverity) -> &'static str {
+    match severity {
+        VulnerabilitySeverity::Critical | VulnerabilitySeverity::High => "error",
+        VulnerabilitySeverity::Medium => "warning",
+        VulnerabilitySeverity::Low | VulnerabilitySeverity::Info => "note",
+    }
+}
+
+/// `path` relative to the checkout at `root`, with forward slashes as SARIF URIs expect
+pub fn relative_uri(root: &Path, path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let root = root.to_string_lossy().replace('\\', "/");
+    let root = root.trim_start_matches("./").trim_end_matches('/');
+    let path = path.trim_start_matches("./");
+    path.strip_prefix(root)
+        .map(|p| p.trim_start_matches('/'))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// A SARIF 2.1.0 log with one rule per vulnerability type and one result per finding
+pub fn sarif(vulnerabilities: &[SecurityVulnerability], root: &Path) -> Value {
+    let mut rules: BTreeMap<&str, &SecurityVulnerability> = BTreeMap::new();
+    for vulnerability in vulnerabilities {
+        rules.entry(vulnerability.vulnerability_type.as_str()).or_insert(vulnerability);
+    }
+    let rule_ids: Vec<&str> = rules.keys().copied().collect();
+    let rules: Vec<Value> = rules.values()
+        .map(|v| json!({
+            "id": v.vulnerability_type,
+            "shortDescription": { "text": v.vulnerability_type },
+            "help": { "text": v.recommendation },
+            "defaultConfiguration": { "level": level(&v.severity) },
+        }))
+        .collect();
+
+    let results: Vec<Value> = vulnerabilities.iter()
+        .map(|v| {
+            let mut region = json!({});
+            if let Some(line) = v.line_number {
+                region = json!({ "startLine": line.max(1) });
+            }
+            json!({
+                "ruleId": v.vulnerability_type,
+                "ruleIndex": rule_ids.iter().position(|id| *id == v.vulnerability_type),
+                "level": level(&v.severity),
+                "message": { "text": v.description },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_uri(root, &v.file_path), "uriBaseId": "%SRCROOT%" },
+                        "region": region,
+                    }
+                }],
+                "properties": { "severity": format!("{:?}", v.severity).to_lowercase() },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Wavelength Architecture Decoder",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sarif_rules_and_locations() {
+        let vulnerability = |kind: &str, severity: VulnerabilitySeverity, file: &str| SecurityVulnerability {
+            id: kind.to_string(),
+            entity_id: "e".to_string(),
+            vulnerability_type: kind.to_string(),
+            severity,
+            description: format!("{} found", kind),
+            recommendation: "Fix it".to_string(),
+            file_path: file.to_string(),
+            line_number: Some(3),
+            affected_range: None,
+            fixed_version: None,
+        };
+        let log = sarif(&[
+            vulnerability("hardcoded_secret", VulnerabilitySeverity::Critical, "./cache/repos/shop/src/config.js"),
+            vulnerability("open_cors", VulnerabilitySeverity::Medium, "src/server.js"),
+        ], Path::new("./cache/repos/shop"));
+
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["results"][0]["level"], "error");
+        assert_eq!(run["results"][0]["ruleIndex"], 0);
+        assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "src/config.js");
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert_eq!(run["results"][1]["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+    }
+}
@@ -0,0 +1,299 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use crate::analysis::dependencies::exact_version;
+use crate::storage::{Repository, StoredDependency};
+
+const TOOL_NAME: &str = "Wavelength Architecture Decoder";
+
+/// One package of the bill of materials, with every manifest that declares it
+struct Package<'a> {
+    name: &'a str,
+    version: &'a str,
+    package_manager: &'a str,
+    is_dev: bool,
+    is_optional: bool,
+    files: Vec<&'a str>,
+}
+
+/// A package is listed once even when several manifests declare it; it only counts as a dev or
+/// optional dependency when every declaration says so
+fn packages(dependencies: &[StoredDependency]) -> Vec<Package<'_>> {
+    let mut packages: BTreeMap<(&str, &str, &str), Package> = BTreeMap::new();
+    for dependency in dependencies {
+        let key = (dependency.package_manager.as_str(), dependency.name.as_str(), dependency.version.as_str());
+        let package = packages.entry(key).or_insert_with(|| Package {
+            name: &dependency.name,
+            version: &dependency.version,
+            package_manager: &dependency.package_manager,
+            is_dev: true,
+            is_optional: true,
+            files: Vec::new(),
+        });
+        package.is_dev &= dependency.is_dev;
+        package.is_optional &= dependency.is_optional;
+        if !package.files.contains(&dependency.file_path.as_str()) {
+            package.files.push(&dependency.file_path);
+        }
+    }
+    packages.into_values().collect()
+}
+
+/// Percent-encodes the characters a purl segment may not contain
+fn purl_segment(segment: &str) -> String {
+    segment.chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '.' | '-' | '_' | '~' => c.to_string(),
+            _ => format!("%{:02X}", c as u32),
+        })
+        .collect()
+}
+
+/// The package URL (https://github.com/package-url/purl-spec) of a package from the ecosystems
+/// that have a purl type; packages of other managers (Unity, Unreal, webMethods) have none
+fn purl(package_manager: &str, name: &str, version: &str) -> Option<String> {
+    let (purl_type, namespace, name) = match package_manager {
+        "npm" => match name.rsplit_once('/') {
+            Some((scope, name)) if scope.starts_with('@') => ("npm", Some(scope.to_string()), name.to_string()),
+            _ => ("npm", None, name.to_string()),
+        },
+        "pip" => ("pypi", None, name.to_lowercase().replace('_', "-")),
+        "cargo" => ("cargo", None, name.to_string()),
+        "maven" | "gradle" => match name.split_once(':') {
+            Some((group, artifact)) => ("maven", Some(group.to_string()), artifact.to_string()),
+            None => return None,
+        },
+        "go" => match name.rsplit_once('/') {
+            Some((namespace, name)) => ("golang", Some(namespace.to_string()), name.to_string()),
+            None => ("golang", None, name.to_string()),
+        },
+        "composer" => match name.split_once('/') {
+            Some((vendor, name)) => ("composer", Some(vendor.to_string()), name.to_string()),
+            None => ("composer", None, name.to_string()),
+        },
+        "nuget" => ("nuget", None, name.to_string()),
+        "cocoapods" => ("cocoapods", None, name.to_string()),
+        _ => return None,
+    };
+    let mut purl = format!("pkg:{}/", purl_type);
+    if let Some(namespace) = namespace {
+        let segments: Vec<String> = namespace.split('/').map(purl_segment).collect();
+        purl.push_str(&segments.join("/"));
+        purl.push('/');
+    }
+    purl.push_str(&purl_segment(&name));
+    if let Some(version) = exact_version(version) {
+        purl.push('@');
+        purl.push_str(&purl_segment(version));
+    }
+    Some(purl)
+}
+
+/// Generates software bills of materials from a repository's stored dependencies, as CycloneDX 1.5
+/// or SPDX 2.3 JSON. Each package carries its package manager and the manifests that declare it.
+#[derive(Default)]
+pub struct SbomGenerator;
+
+impl SbomGenerator {
+    pub fn new() -> Self {
+        SbomGenerator
+    }
+
+    pub fn cyclonedx(&self, repository: &Repository, dependencies: &[StoredDependency]) -> Value {
+        let packages = packages(dependencies);
+        let components: Vec<Value> = packages.iter()
+            .map(|p| {
+                let purl = purl(p.package_manager, p.name, p.version);
+                // Purls drop range versions, so two declared versions can share one
+                let bom_ref = format!("{}:{}@{}", p.package_manager, p.name, p.version);
+                let scope = if p.is_dev {
+                    "excluded"
+                } else if p.is_optional {
+                    "optional"
+                } else {
+                    "required"
+                };
+                let mut component = json!({
+                    "type": "library",
+                    "bom-ref": bom_ref,
+                    "name": p.name,
+                    "version": p.version,
+                    "scope": scope,
+                    "properties": [
+                        { "name": "wavelength:package_manager", "value": p.package_manager },
+                    ],
+                    "evidence": {
+                        "occurrences": p.files.iter().map(|f| json!({ "location": f })).collect::<Vec<_>>(),
+                    },
+                });
+                if let Some(purl) = purl {
+                    component["purl"] = json!(purl);
+                }
+                component
+            })
+            .collect();
+        let refs: Vec<&Value> = components.iter().map(|c| &c["bom-ref"]).collect();
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+            "version": 1,
+            "metadata": {
+                "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "tools": {
+                    "components": [{ "type": "application", "name": TOOL_NAME, "version": env!("CARGO_PKG_VERSION") }],
+                },
+                "component": {
+                    "type": "application",
+                    "bom-ref": repository.id,
+                    "name": repository.name,
+                    "externalReferences": [{ "type": "vcs", "url": repository.url }],
+                    "properties": [{ "name": "wavelength:branch", "value": repository.branch }],
+                },
+            },
+            "components": components,
+            "dependencies": [{ "ref": repository.id, "dependsOn": refs }],
+        })
+    }
+
+    pub fn spdx(&self, repository: &Repository, dependencies: &[StoredDependency]) -> Value {
+        let root = "SPDXRef-Repository";
+        let download_location = if repository.url.contains("://") && !repository.url.starts_with("file://") {
+            repository.url.as_str()
+        } else {
+            "NOASSERTION"
+        };
+        let mut spdx_packages = vec![json!({
+            "SPDXID": root,
+            "name": repository.name,
+            "versionInfo": repository.branch,
+            "downloadLocation": download_location,
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": "NOASSERTION",
+            "copyrightText": "NOASSERTION",
+        })];
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": root,
+        })];
+
+        for (i, p) in packages(dependencies).iter().enumerate() {
+            let id = format!("SPDXRef-Package-{}", i + 1);
+            let mut package = json!({
+                "SPDXID": id,
+                "name": p.name,
+                "versionInfo": p.version,
+                "downloadLocation": "NOASSERTION",
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": "NOASSERTION",
+                "copyrightText": "NOASSERTION",
+                "sourceInfo": format!("{} dependency declared in {}", p.package_manager, p.files.join(", ")),
+            });
+            if let Some(purl) = purl(p.package_manager, p.name, p.version) {
+                package["externalRefs"] = json!([{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": purl,
+                }]);
+            }
+            spdx_packages.push(package);
+            // DEV_DEPENDENCY_OF and OPTIONAL_DEPENDENCY_OF point from the dependency to its dependent
+            relationships.push(if p.is_dev || p.is_optional {
+                json!({
+                    "spdxElementId": id,
+                    "relationshipType": if p.is_dev { "DEV_DEPENDENCY_OF" } else { "OPTIONAL_DEPENDENCY_OF" },
+                    "relatedSpdxElement": root,
+                })
+            } else {
+                json!({
+                    "spdxElementId": root,
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": id,
+                })
+            });
+        }
+
+        json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": format!("{} SBOM", repository.name),
+            "documentNamespace": format!("{}/spdx/{}-{}", env!("CARGO_PKG_REPOSITORY"), purl_segment(&repository.name), uuid::Uuid::new_v4()),
+            "creationInfo": {
+                "created": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "creators": [format!("Tool: {}-{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))],
+            },
+            "packages": spdx_packages,
+            "relationships": relationships,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_cyclonedx_and_spdx_documents() {
+        let repository = Repository {
+            id: "repo-1".to_string(),
+            name: "shop".to_string(),
+            url: "https://github.com/acme/shop".to_string(),
+            branch: "main".to_string(),
+            auth_type: None,
+            auth_value: None,
+            last_analyzed_at: None,
+            analysis_profile: None,
+            workspace_id: "default".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let dependency = |name: &str, version: &str, package_manager: &str, is_dev: bool, file_path: &str| StoredDependency {
+            id: format!("{}:{}", file_path, name),
+            repository_id: repository.id.clone(),
+            name: name.to_string(),
+            version: version.to_string(),
+            package_manager: package_manager.to_string(),
+            is_dev,
+            is_optional: false,
+            file_path: file_path.to_string(),
+            created_at: Utc::now(),
+        };
+        let dependencies = [
+            dependency("@angular/core", "17.0.1", "npm", false, "web/package.json"),
+            dependency("@angular/core", "17.0.1", "npm", false, "admin/package.json"),
+            dependency("jest", "^29.0.0", "npm", true, "web/package.json"),
+            dependency("org.slf4j:slf4j-api", "2.0.9", "maven", false, "pom.xml"),
+            dependency("Core", "unknown", "unreal", false, "Shooter.uproject"),
+        ];
+
+        let bom = SbomGenerator::new().cyclonedx(&repository, &dependencies);
+        assert_eq!(bom["specVersion"], "1.5");
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 4);
+        let angular = components.iter().find(|c| c["name"] == "@angular/core").unwrap();
+        assert_eq!(angular["purl"], "pkg:npm/%40angular/core@17.0.1");
+        assert_eq!(angular["evidence"]["occurrences"].as_array().unwrap().len(), 2);
+        let jest = components.iter().find(|c| c["name"] == "jest").unwrap();
+        assert_eq!(jest["purl"], "pkg:npm/jest");
+        assert_eq!(jest["scope"], "excluded");
+        let slf4j = components.iter().find(|c| c["name"] == "org.slf4j:slf4j-api").unwrap();
+        assert_eq!(slf4j["purl"], "pkg:maven/org.slf4j/slf4j-api@2.0.9");
+        let core = components.iter().find(|c| c["name"] == "Core").unwrap();
+        assert!(core.get("purl").is_none());
+        assert_eq!(core["bom-ref"], "unreal:Core@unknown");
+        assert_eq!(bom["dependencies"][0]["dependsOn"].as_array().unwrap().len(), 4);
+
+        let spdx = SbomGenerator::new().spdx(&repository, &dependencies);
+        assert_eq!(spdx["packages"].as_array().unwrap().len(), 5);
+        let relationships = spdx["relationships"].as_array().unwrap();
+        assert!(relationships.iter().any(|r| r["relationshipType"] == "DEV_DEPENDENCY_OF"));
+        assert_eq!(relationships.iter().filter(|r| r["relationshipType"] == "DEPENDS_ON").count(), 3);
+        let jest = spdx["packages"].as_array().unwrap().iter().find(|p| p["name"] == "jest").unwrap();
+        assert_eq!(jest["sourceInfo"], "npm dependency declared in web/package.json");
+    }
+}
@@ -0,0 +1,322 @@
+//! Versioned JSON output contract. Every exported document has a JSON Schema under
+//! `schemas/v<N>/`, and documents that are objects carry a top-level `schema_version`.
+//!
+//! A released version's schemas are frozen: any change to an exported structure adds
+//! `schemas/v<N+1>/` and bumps [`SCHEMA_VERSION`]. Integrators that pin an older version (the
+//! `output.schema_version` setting, or `?schema_version=` on the API) get documents cut down to
+//! what that version declares, so fields added later never reach them. A rename or removal also
+//! needs a step in [`versioned`] that maps the new shape back before the cut.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The version new documents are written in
+pub const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// `analysis.json` from the analyze command
+    Analysis,
+    /// The knowledge graph, from the API and `graph.json` artifacts
+    Graph,
+    /// `dependencies.json` artifacts
+    Dependencies,
+    /// `manifest.json` of an artifact run
+    Manifest,
+    /// CI gate results
+    Gate,
+}
+
+pub const SCHEMA_KINDS: &[SchemaKind] = &[
+    SchemaKind::Analysis,
+    SchemaKind::Graph,
+    SchemaKind::Dependencies,
+    SchemaKind::Manifest,
+    SchemaKind::Gate,
+];
+
+/// Schema documents by version, oldest first
+const SCHEMAS: &[[&str; 5]] = &[
+    [
+        include_str!("../../schemas/v1/analysis.schema.json"),
+        include_str!("../../schemas/v1/graph.schema.json"),
+        include_str!("../../schemas/v1/dependencies.schema.json"),
+        include_str!("../../schemas/v1/manifest.schema.json"),
+        include_str!("../../schemas/v1/gate.schema.json"),
+    ],
+    // v2: aggregated services list their evidence, endpoints name their protocol, tech debt
+    // subscores carry notes and dependency advisories their affected and fixed versions
+    [
+        include_str!("../../schemas/v2/analysis.schema.json"),
+        include_str!("../../schemas/v2/graph.schema.json"),
+        include_str!("../../schemas/v2/dependencies.schema.json"),
+        include_str!("../../schemas/v2/manifest.schema.json"),
+        include_str!("../../schemas/v2/gate.schema.json"),
+    ],
+];
+
+impl SchemaKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::Analysis => "analysis",
+            SchemaKind::Graph => "graph",
+            SchemaKind::Dependencies => "dependencies",
+            SchemaKind::Manifest => "manifest",
+            SchemaKind::Gate => "gate",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        SCHEMA_KINDS.iter().copied().find(|kind| kind.name() == name.trim_end_matches(".schema.json"))
+    }
+
+    /// The schema document for `version`, if that version exists
+    pub fn schema(&self, version: u32) -> Option<Value> {
+        let documents = SCHEMAS.get((version as usize).checked_sub(1)?)?;
+        let index = SCHEMA_KINDS.iter().position(|kind| kind == self)?;
+        // The schemas are checked by the tests, so a parse failure here is a build mistake
+        Some(serde_json::from_str(documents[index]).expect("bundled JSON Schema does not parse"))
+    }
+}
+
+pub fn is_supported(version: u32) -> bool {
+    (1..=SCHEMA_VERSION).contains(&version)
+}
+
+/// `value` as a document of `version` (the current one when unset): stamped with
+/// `schema_version` and, for older versions, cut down to the fields that version declares
+pub fn versioned<T: Serialize>(kind: SchemaKind, value: &T, version: Option<u32>) -> Result<Value> {
+    let version = version.unwrap_or(SCHEMA_VERSION);
+    let schema = kind.schema(version)
+        .ok_or_else(|| anyhow!("Unsupported schema version {}; this build writes versions 1 to {}", version, SCHEMA_VERSION))?;
+    let mut value = serde_json::to_value(value)?;
+    if let Value::Object(object) = &mut value {
+        object.insert("schema_version".to_string(), Value::from(version));
+    }
+    if version < SCHEMA_VERSION {
+        value = project(&value, &schema, &schema);
+    }
+    Ok(value)
+}
+
+/// Follows a local `#/$defs/...` reference
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix('#')) {
+        Some(pointer) => root.pointer(pointer).map(|target| resolve(target, root)).unwrap_or(schema),
+        None => schema,
+    }
+}
+
+/// Keeps the object properties `schema` declares, recursively; `additionalProperties` keeps the
+/// rest, checked against its own schema when it is one
+fn project(value: &Value, schema: &Value, root: &Value) -> Value {
+    let schema = resolve(schema, root);
+    if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+        return match value {
+            Value::Null => Value::Null,
+            _ => alternatives.iter()
+                .find(|alternative| resolve(alternative, root).get("type") != Some(&Value::from("null")))
+                .map(|alternative| project(value, alternative, root))
+                .unwrap_or_else(|| value.clone()),
+        };
+    }
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let additional = schema.get("additionalProperties");
+            let mut kept = Map::new();
+            for (key, field) in object {
+                match (properties.and_then(|p| p.get(key)), additional) {
+                    (Some(declared), _) => { kept.insert(key.clone(), project(field, declared, root)); }
+                    (None, Some(Value::Bool(true))) => { kept.insert(key.clone(), field.clone()); }
+                    (None, Some(additional @ Value::Object(_))) => { kept.insert(key.clone(), project(field, additional, root)); }
+                    (None, _) => {}
+                }
+            }
+            Value::Object(kept)
+        }
+        Value::Array(items) => match schema.get("items") {
+            Some(item_schema) => Value::Array(items.iter().map(|item| project(item, item_schema, root)).collect()),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_u64() || value.is_i64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Where `value` breaks `schema`, as `path: problem` lines. Covers the keywords the bundled
+/// schemas use (`type`, `enum`, `const`, `required`, `properties`, `additionalProperties`,
+/// `items`, `anyOf` and `$ref`); undeclared properties are reported too, since each one means
+/// an exported structure changed without a new schema version.
+pub fn check(value: &Value, schema: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    check_at("$", value, schema, schema, &mut problems);
+    problems
+}
+
+fn check_at(path: &str, value: &Value, schema: &Value, root: &Value, problems: &mut Vec<String>) {
+    let schema = resolve(schema, root);
+    if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+        let fits = alternatives.iter().any(|alternative| {
+            let mut nested = Vec::new();
+            check_at(path, value, alternative, root, &mut nested);
+            nested.is_empty()
+        });
+        if !fits {
+            problems.push(format!("{}: matches none of the allowed shapes", path));
+        }
+        return;
+    }
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|t| type_matches(value, t)) {
+        problems.push(format!("{}: expected {}, found {}", path, types.join(" or "), value));
+        return;
+    }
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            problems.push(format!("{}: expected {}, found {}", path, expected, value));
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            problems.push(format!("{}: {} is not one of {}", path, value, Value::Array(allowed.clone())));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            for required in schema.get("required").and_then(Value::as_array).into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(required) {
+                    problems.push(format!("{}: missing required '{}'", path, required));
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, field) in object {
+                let field_path = format!("{}.{}", path, key);
+                match (properties.and_then(|p| p.get(key)), schema.get("additionalProperties")) {
+                    (Some(declared), _) => check_at(&field_path, field, declared, root, problems),
+                    (None, Some(Value::Bool(true))) => {}
+                    (None, Some(additional @ Value::Object(_))) => check_at(&field_path, field, additional, root, problems),
+                    (None, _) if properties.is_some() => problems.push(format!("{}: not declared in the schema", field_path)),
+                    (None, _) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check_at(&format!("{}[{}]", path, i), item, item_schema, root, problems);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bundled_schemas_parse() {
+        for version in 1..=SCHEMA_VERSION {
+            for kind in SCHEMA_KINDS {
+                let schema = kind.schema(version).unwrap();
+                assert_eq!(schema["$id"], format!("urn:wavelength:schema:{}:{}", kind.name(), version));
+            }
+        }
+        assert!(SchemaKind::Graph.schema(SCHEMA_VERSION + 1).is_none());
+        assert_eq!(SchemaKind::parse("gate.schema.json"), Some(SchemaKind::Gate));
+    }
+
+    #[test]
+    fn test_versioned_documents_follow_their_schema() {
+        let gate = json!({
+            "passed": false,
+            "checks": [{"name": "critical_vulnerabilities", "passed": false, "detail": "1 critical vulnerabilities"}],
+        });
+        let document = versioned(SchemaKind::Gate, &gate, None).unwrap();
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+        assert!(check(&document, &SchemaKind::Gate.schema(SCHEMA_VERSION).unwrap()).is_empty());
+
+        let mut changed = document.clone();
+        changed["checks"][0]["severity"] = json!("critical");
+        changed.as_object_mut().unwrap().remove("passed");
+        let problems = check(&changed, &SchemaKind::Gate.schema(SCHEMA_VERSION).unwrap());
+        assert_eq!(problems, [
+            "$: missing required 'passed'",
+            "$.checks[0].severity: not declared in the schema",
+        ]);
+
+        assert!(versioned(SchemaKind::Gate, &gate, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_projection_drops_undeclared_fields() {
+        let schema = SchemaKind::Graph.schema(1).unwrap();
+        let graph = json!({
+            "schema_version": 1,
+            "nodes": [{"id": "n1", "node_type": "Service", "name": "Stripe", "properties": {"provider": "stripe"}, "repository_id": null, "weight": 3}],
+            "edges": [],
+            "statistics": {"nodes": 1},
+        });
+        let projected = project(&graph, &schema, &schema);
+        assert!(projected.get("statistics").is_none());
+        assert!(projected["nodes"][0].get("weight").is_none());
+        assert_eq!(projected["nodes"][0]["properties"]["provider"], "stripe");
+        assert!(check(&projected, &schema).is_empty());
+    }
+
+    #[test]
+    fn test_version_1_services_leave_out_evidence() {
+        let analysis = json!({
+            "services": [{
+                "id": "s1", "repository_id": "r1", "provider": "stripe", "service_type": "payment", "name": "Stripe",
+                "configuration": "{}", "file_path": "package.json", "line_number": null, "confidence": 0.9,
+                "evidence": [{"kind": "package", "file_path": "package.json", "line_number": 4, "confidence": 0.7}],
+                "created_at": "2024-01-01T00:00:00Z",
+            }],
+        });
+        let current = versioned(SchemaKind::Analysis, &analysis, None).unwrap();
+        assert_eq!(current["services"][0]["evidence"][0]["kind"], "package");
+        let pinned = versioned(SchemaKind::Analysis, &analysis, Some(1)).unwrap();
+        assert_eq!(pinned["schema_version"], 1);
+        assert!(pinned["services"][0].get("evidence").is_none());
+        assert_eq!(pinned["services"][0]["confidence"], 0.9);
+    }
+
+    #[test]
+    fn test_version_1_vulnerabilities_leave_out_advisory_versions() {
+        let analysis = json!({
+            "vulnerabilities": [{
+                "id": "v1", "entity_id": "lodash", "vulnerability_type": "GHSA-35jh-r3h4-6jhm", "severity": "High",
+                "description": "Command injection", "recommendation": "Upgrade to 4.17.21", "file_path": "package.json",
+                "line_number": null, "affected_range": "<4.17.21", "fixed_version": "4.17.21",
+            }],
+        });
+        let current = versioned(SchemaKind::Analysis, &analysis, None).unwrap();
+        assert_eq!(current["vulnerabilities"][0]["fixed_version"], "4.17.21");
+        let pinned = versioned(SchemaKind::Analysis, &analysis, Some(1)).unwrap();
+        let vulnerability = pinned["vulnerabilities"][0].as_object().unwrap();
+        assert!(!vulnerability.contains_key("affected_range") && !vulnerability.contains_key("fixed_version"));
+        assert!(check(&pinned["vulnerabilities"][0], &SchemaKind::Analysis.schema(1).unwrap()["$defs"]["Vulnerability"]).is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use crate::config::EmbeddingsConfig;
+
+/// Dimensions of the built-in local embedding
+const LOCAL_DIMENSIONS: usize = 384;
+
+/// Turns text into vectors. `Local` needs no network: it hashes words, identifier parts and
+/// word pairs into a fixed-size vector, which is enough to rank by shared vocabulary.
+#[derive(Debug, Clone)]
+pub enum EmbeddingProvider {
+    Local { dimensions: usize },
+    OpenAI { api_key: String, model: String, base_url: String },
+    Ollama { base_url: String, model: String },
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+impl EmbeddingProvider {
+    /// Provider selected by `EMBEDDINGS_PROVIDER`, or `None` when semantic search is disabled
+    pub fn from_config(config: &EmbeddingsConfig) -> Result<Option<Self>> {
+        match Self::configured(config)? {
+            Some(provider) if provider.is_remote() && crate::network::is_offline() => {
+                log::warn!("⚠ Offline mode: using the local embedding instead of {}", provider.model_id());
+                Ok(Some(EmbeddingProvider::Local { dimensions: LOCAL_DIMENSIONS }))
+            }
+            provider => Ok(provider),
+        }
+    }
+
+    /// The provider as configured, even if offline mode replaces it
+    pub fn configured(config: &EmbeddingsConfig) -> Result<Option<Self>> {
+        let provider = match config.provider.as_deref().map(|p| p.to_lowercase()) {
+            None => return Ok(None),
+            Some(p) => p,
+        };
+        let provider = match provider.as_str() {
+            "local" => EmbeddingProvider::Local { dimensions: LOCAL_DIMENSIONS },
+            "openai" => EmbeddingProvider::OpenAI {
+                api_key: config.api_key.clone()
+                    .context("EMBEDDINGS_PROVIDER=openai requires OPENAI_API_KEY")?,
+                model: config.model.clone().unwrap_or_else(|| "text-embedding-3-small".to_string()),
+                base_url: config.base_url.clone().unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
+            },
+            "ollama" => EmbeddingProvider::Ollama {
+                base_url: config.base_url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
+                model: config.model.clone().unwrap_or_else(|| "nomic-embed-text".to_string()),
+            },
+            other => anyhow::bail!("Unknown EMBEDDINGS_PROVIDER '{}' (expected local, openai or ollama)", other),
+        };
+        Ok(Some(provider))
+    }
+
+    /// Whether embedding goes off this machine; an Ollama on loopback does not
+    pub fn is_remote(&self) -> bool {
+        match self {
+            EmbeddingProvider::Local { .. } => false,
+            EmbeddingProvider::OpenAI { .. } => true,
+            EmbeddingProvider::Ollama { base_url, .. } => !matches!(
+                reqwest::Url::parse(base_url).ok().as_ref().and_then(|u| u.host_str()),
+                Some("localhost" | "127.0.0.1" | "[::1]")
+            ),
+        }
+    }
+
+    /// Identifies the vector space; vectors from different models are never compared
+    pub fn model_id(&self) -> String {
+        match self {
+            EmbeddingProvider::Local { dimensions } => format!("local:hash-{}", dimensions),
+            EmbeddingProvider::OpenAI { model, .. } => format!("openai:{}", model),
+            EmbeddingProvider::Ollama { model, .. } => format!("ollama:{}", model),
+        }
+    }
+
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            EmbeddingProvider::Local { dimensions } => {
+                Ok(texts.iter().map(|t| local_embedding(t, *dimensions)).collect())
+            }
+            EmbeddingProvider::OpenAI { api_key, model, base_url } => {
+                let response = http_client()?
+                    .post(format!("{}/embeddings", base_url.trim_end_matches('/')))
+                    .bearer_auth(api_key)
+                    .json(&serde_json::json!({ "model": model, "input": texts }))
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("OpenAI embeddings request failed with status {}", response.status());
+                }
+                let mut body: OpenAIEmbeddingResponse = response.json().await?;
+                body.data.sort_by_key(|d| d.index);
+                Ok(body.data.into_iter().map(|d| d.embedding).collect())
+            }
+            EmbeddingProvider::Ollama { base_url, model } => {
+                let response = http_client()?
+                    .post(format!("{}/api/embed", base_url.trim_end_matches('/')))
+                    .json(&serde_json::json!({ "model": model, "input": texts }))
+                    .send()
+                    .await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("Ollama embeddings request failed with status {}", response.status());
+                }
+                let body: OllamaEmbeddingResponse = response.json().await?;
+                Ok(body.embeddings)
+            }
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    crate::network::http_client(Duration::from_secs(60))
+}
+
+/// Signed feature hashing over lowercase words, camelCase/snake_case parts and adjacent word pairs, L2-normalized
+fn local_embedding(text: &str, dimensions: usize) -> Vec<f32> {
+    let mut vector = vec![0f32; dimensions];
+    let mut tokens: Vec<String> = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric() && c != '_').filter(|w| w.len() > 1) {
+        tokens.push(word.to_lowercase());
+        let parts = split_identifier(word);
+        if parts.len() > 1 {
+            tokens.extend(parts);
+        }
+    }
+
+    let mut add = |feature: &str, weight: f32| {
+        let mut hasher = DefaultHasher::new();
+        feature.hash(&mut hasher);
+        let hash = hasher.finish();
+        let sign = if hash & 1 == 0 { 1.0 } else { -1.0 };
+        vector[(hash >> 1) as usize % dimensions] += sign * weight;
+    };
+    for token in &tokens {
+        add(token, 1.0);
+    }
+    for pair in tokens.windows(2) {
+        add(&format!("{} {}", pair[0], pair[1]), 0.5);
+    }
+
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// `getUserById` / `get_user_by_id` -> ["get", "user", "by", "id"]
+fn split_identifier(word: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in word.chars() {
+        if c == '_' || (c.is_uppercase() && previous_lower) {
+            if current.len() > 1 {
+                parts.push(current.to_lowercase());
+            }
+            current.clear();
+        }
+        if c != '_' {
+            current.push(c);
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if current.len() > 1 {
+        parts.push(current.to_lowercase());
+    }
+    parts
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
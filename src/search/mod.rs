@@ -0,0 +1,87 @@
+pub mod embeddings;
+
+pub use embeddings::{EmbeddingProvider, cosine_similarity};
+
+use anyhow::Result;
+use crate::analysis::{CodeElement, CodeElementType};
+use crate::storage::{CodeElementRepository, DocumentationRepository, EmbeddingEntry, EmbeddingRepository};
+use crate::storage::documentation_repo::StoredDocumentation;
+
+/// Texts sent to the provider per request
+const BATCH_SIZE: usize = 64;
+/// Code elements embedded per repository, largest repositories are truncated
+const MAX_CODE_ELEMENTS: usize = 5000;
+/// Characters of each text sent for embedding
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// Embed a repository's stored documentation and code element signatures, replacing earlier vectors
+pub async fn index_repository(
+    provider: &EmbeddingProvider,
+    documentation_repo: &DocumentationRepository,
+    code_repo: &CodeElementRepository,
+    embedding_repo: &EmbeddingRepository,
+    repository_id: &str,
+) -> Result<usize> {
+    let mut entries: Vec<EmbeddingEntry> = documentation_repo.get_by_repository(repository_id)?
+        .iter()
+        .map(documentation_entry)
+        .collect();
+    entries.extend(code_repo.get_by_repository(repository_id)?
+        .iter()
+        .filter(|e| !matches!(e.element_type, CodeElementType::Variable | CodeElementType::Constant))
+        .take(MAX_CODE_ELEMENTS)
+        .map(code_entry));
+
+    for batch in entries.chunks_mut(BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|e| e.content.chars().take(MAX_TEXT_CHARS).collect()).collect();
+        let vectors = provider.embed(&texts).await?;
+        if vectors.len() != batch.len() {
+            anyhow::bail!("Embedding provider returned {} vectors for {} texts", vectors.len(), batch.len());
+        }
+        for (entry, vector) in batch.iter_mut().zip(vectors) {
+            entry.vector = vector;
+        }
+    }
+
+    embedding_repo.store_embeddings(repository_id, &provider.model_id(), &entries)?;
+    Ok(entries.len())
+}
+
+fn documentation_entry(doc: &StoredDocumentation) -> EmbeddingEntry {
+    let title = doc.title.clone().unwrap_or_else(|| doc.file_name.clone());
+    let content = [Some(title.clone()), doc.description.clone(), Some(doc.content_preview.clone())]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+    EmbeddingEntry {
+        source_type: "documentation".to_string(),
+        source_id: doc.id.clone(),
+        title,
+        file_path: doc.file_path.clone(),
+        line_number: None,
+        content,
+        vector: Vec::new(),
+    }
+}
+
+fn code_entry(element: &CodeElement) -> EmbeddingEntry {
+    let mut content = format!("{:?} {} in {}", element.element_type, element.name, element.file_path);
+    if let Some(signature) = &element.signature {
+        content.push('\n');
+        content.push_str(signature);
+    }
+    if let Some(doc_comment) = &element.doc_comment {
+        content.push('\n');
+        content.push_str(doc_comment);
+    }
+    EmbeddingEntry {
+        source_type: "code_element".to_string(),
+        source_id: element.id.clone(),
+        title: element.name.clone(),
+        file_path: element.file_path.clone(),
+        line_number: Some(element.line_number),
+        content,
+        vector: Vec::new(),
+    }
+}
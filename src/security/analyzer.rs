@@ -9,6 +9,8 @@ use crate::security::terraform::analyze_terraform;
 use crate::security::cloudformation::analyze_cloudformation;
 use crate::security::serverless::{analyze_serverless, analyze_sam};
 use crate::security::firebase::analyze_firebase_rules;
+use crate::security::android::analyze_android_manifest;
+use crate::security::ios::{analyze_entitlements, analyze_info_plist, is_ios_file};
 use crate::security::env_config::analyze_env_template;
 use crate::security::security_config::analyze_security_config;
 
@@ -165,6 +167,34 @@ impl SecurityAnalyzer {
                 }
             }
 
+            // Analyze Android manifests, with the Gradle module each belongs to
+            if file_name == "androidmanifest.xml" {
+                if let Ok(content) = std::fs::read_to_string(path) {
+                    let (android_entities, android_relationships, android_vulns) =
+                        analyze_android_manifest(&content, path, &normalized_path, &mut entity_map)?;
+                    entities.extend(android_entities);
+                    relationships.extend(android_relationships);
+                    vulnerabilities.extend(android_vulns);
+                }
+            }
+
+            // Analyze iOS/macOS Info.plist files with the entitlements beside them; plists may be binary
+            if is_ios_file(path, &file_name) {
+                if let Ok(bytes) = std::fs::read(path) {
+                    if file_name == "info.plist" {
+                        let (ios_entities, ios_relationships, ios_vulns) =
+                            analyze_info_plist(&bytes, path, &normalized_path, &mut entity_map)?;
+                        entities.extend(ios_entities);
+                        relationships.extend(ios_relationships);
+                        vulnerabilities.extend(ios_vulns);
+                    } else {
+                        let (ios_entities, ios_vulns) = analyze_entitlements(&bytes, path, &normalized_path, &mut entity_map)?;
+                        entities.extend(ios_entities);
+                        vulnerabilities.extend(ios_vulns);
+                    }
+                }
+            }
+
             // Analyze environment template files
             if file_name == ".env.example" || 
                file_name == ".env.template" || 
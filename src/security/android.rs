@@ -0,0 +1,495 @@
+//! Android apps: the `AndroidManifest.xml` of each source set becomes an app entity, with the
+//! permissions it requests and the product flavors and build types of its Gradle module, and an
+//! entity per activity, service, receiver and provider with its intent filters and deep links.
+//! Components other apps can start without a permission, cleartext traffic and debuggable
+//! release builds are raised as vulnerabilities.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityRelationship, SecurityVulnerability, VulnerabilitySeverity};
+
+static ATTRIBUTE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([\w:.-]+)\s*=\s*"([^"]*)""#).unwrap());
+static MANIFEST_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<manifest\b([^>]*)>").unwrap());
+static APPLICATION_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<application\b([^>]*)>").unwrap());
+static USES_PERMISSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<uses-permission\b([^>]*)>").unwrap());
+static COMPONENT_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<(activity-alias|activity|service|receiver|provider)\b([^>]*?)(/?)>").unwrap());
+static INTENT_FILTER: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<intent-filter\b([^>]*)>(.*?)</intent-filter>").unwrap());
+static FILTER_ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<(action|category|data)\b([^>]*)>").unwrap());
+static GRADLE_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(namespace|applicationId)\s*=?\s*["']([^"']+)["']"#).unwrap());
+static GRADLE_SETTING: Lazy<Regex> = Lazy::new(|| Regex::new(
+    r#"\b(dimension|applicationIdSuffix|applicationId|versionNameSuffix|isDebuggable|debuggable|isMinifyEnabled|minifyEnabled)\s*[=(]?\s*["']?([\w.-]+)["']?"#
+).unwrap());
+
+/// The Gradle build files a module may have, Kotlin DSL first
+const GRADLE_FILES: &[&str] = &["build.gradle.kts", "build.gradle"];
+
+/// `name="value"` pairs of one tag, keyed without the `android:` prefix
+fn attributes(tag: &str) -> HashMap<String, String> {
+    ATTRIBUTE.captures_iter(tag)
+        .map(|c| (c[1].trim_start_matches("android:").to_string(), c[2].to_string()))
+        .collect()
+}
+
+/// 1-based line of byte offset `pos`
+fn line_at(content: &str, pos: usize) -> usize {
+    content[..pos].matches('\n').count() + 1
+}
+
+/// The text between the braces opened by the first `{` at or after `start`
+fn braced_block(content: &str, start: usize) -> Option<&str> {
+    let open = start + content[start..].find('{')?;
+    let mut depth = 0;
+    for (i, c) in content[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&content[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The named entries of a `productFlavors { ... }` or `buildTypes { ... }` block (`free { ... }`,
+/// `create("free") { ... }`, `getByName("release") { ... }`) with the settings each one makes
+fn gradle_entries(block: &str) -> Vec<(String, HashMap<String, String>)> {
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut header_start = 0;
+    let mut body_start = 0;
+    for (i, c) in block.char_indices() {
+        match c {
+            '{' => {
+                if depth == 0 {
+                    let header = block[header_start..i].trim().lines().last().unwrap_or("").trim();
+                    let name = header.split(['"', '\'']).nth(1)
+                        .or_else(|| header.split(|c: char| !(c.is_alphanumeric() || c == '_')).rfind(|w| !w.is_empty()))
+                        .unwrap_or("")
+                        .to_string();
+                    entries.push((name, HashMap::new()));
+                    body_start = i + 1;
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some((_, settings)) = entries.last_mut() {
+                        for caps in GRADLE_SETTING.captures_iter(&block[body_start..i]) {
+                            let key = match &caps[1] {
+                                "isDebuggable" => "debuggable",
+                                "isMinifyEnabled" => "minifyEnabled",
+                                key => key,
+                            };
+                            settings.insert(key.to_string(), caps[2].to_string());
+                        }
+                    }
+                    header_start = i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    entries.into_iter().filter(|(name, _)| !name.is_empty()).collect()
+}
+
+/// The Gradle module a manifest at `<module>/src/<source set>/AndroidManifest.xml` belongs to
+struct GradleModule {
+    file_name: String,
+    content: String,
+}
+
+fn gradle_module(manifest: &Path) -> Option<GradleModule> {
+    let module_dir = manifest.parent()?.parent()?.parent()?;
+    GRADLE_FILES.iter().find_map(|file_name| {
+        std::fs::read_to_string(module_dir.join(file_name)).ok()
+            .map(|content| GradleModule { file_name: file_name.to_string(), content })
+    })
+}
+
+fn gradle_block<'a>(content: &'a str, name: &str) -> Option<&'a str> {
+    let start = Regex::new(&format!(r"\b{}\s*\{{", name)).ok()?.find(content)?.start();
+    braced_block(content, start)
+}
+
+/// Analyze an `AndroidManifest.xml` and the Gradle module it belongs to
+pub fn analyze_android_manifest(
+    content: &str,
+    path: &Path,
+    normalized_path: &str,
+    entity_map: &mut HashMap<String, String>,
+) -> Result<(Vec<SecurityEntity>, Vec<SecurityRelationship>, Vec<SecurityVulnerability>)> {
+    let mut entities = Vec::new();
+    let mut relationships = Vec::new();
+    let mut vulnerabilities = Vec::new();
+
+    let source_set = path.parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("main")
+        .to_string();
+    let module = gradle_module(path);
+    // The first `applicationId` is the default config's; flavors may set their own further down
+    let mut gradle_ids: HashMap<String, String> = HashMap::new();
+    for caps in module.iter().flat_map(|m| GRADLE_ID.captures_iter(&m.content)) {
+        gradle_ids.entry(caps[1].to_string()).or_insert_with(|| caps[2].to_string());
+    }
+    let manifest = MANIFEST_TAG.captures(content).map(|c| attributes(&c[1])).unwrap_or_default();
+    let package = manifest.get("package").or_else(|| gradle_ids.get("namespace")).cloned().unwrap_or_default();
+    let application_id = gradle_ids.get("applicationId").cloned().unwrap_or_else(|| package.clone());
+
+    let app_id = format!("{}:android:app:{}", normalized_path, Uuid::new_v4());
+    let app_name = if application_id.is_empty() { format!("AndroidManifest.xml ({})", source_set) } else { application_id.clone() };
+    entity_map.insert(format!("android:{}:{}", app_name, source_set), app_id.clone());
+
+    let application = APPLICATION_TAG.captures(content);
+    let application_attrs = application.as_ref().map(|c| attributes(&c[1])).unwrap_or_default();
+    let application_line = application.as_ref().and_then(|c| c.get(0)).map(|m| line_at(content, m.start()));
+    let permissions: Vec<Value> = USES_PERMISSION.captures_iter(content)
+        .filter_map(|c| attributes(&c[1]).remove("name"))
+        .map(Value::String)
+        .collect();
+
+    let mut config = HashMap::new();
+    config.insert("package".to_string(), Value::String(package.clone()));
+    config.insert("application_id".to_string(), Value::String(application_id));
+    config.insert("source_set".to_string(), Value::String(source_set.clone()));
+    config.insert("permissions".to_string(), Value::Array(permissions));
+    for attribute in ["usesCleartextTraffic", "networkSecurityConfig", "debuggable", "allowBackup"] {
+        if let Some(value) = application_attrs.get(attribute) {
+            config.insert(attribute.to_string(), Value::String(value.clone()));
+        }
+    }
+
+    let mut build_types = Vec::new();
+    if let Some(module) = &module {
+        config.insert("gradle_file".to_string(), Value::String(module.file_name.clone()));
+        let flavors = gradle_block(&module.content, "productFlavors").map(gradle_entries).unwrap_or_default();
+        config.insert("product_flavors".to_string(), Value::Array(
+            flavors.iter().map(|(name, settings)| json!({"name": name, "settings": settings})).collect()
+        ));
+        build_types = gradle_block(&module.content, "buildTypes").map(gradle_entries).unwrap_or_default();
+        config.insert("build_types".to_string(), Value::Array(
+            build_types.iter().map(|(name, settings)| json!({"name": name, "settings": settings})).collect()
+        ));
+    }
+
+    entities.push(SecurityEntity {
+        id: app_id.clone(),
+        entity_type: SecurityEntityType::AndroidApp,
+        name: app_name.clone(),
+        provider: "android".to_string(),
+        configuration: config,
+        file_path: normalized_path.to_string(),
+        line_number: application_line,
+        arn: None,
+        region: None,
+    });
+
+    // Cleartext traffic, from the manifest attribute or the network security config it points at
+    if application_attrs.get("usesCleartextTraffic").map(String::as_str) == Some("true") {
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:cleartext", app_id),
+            entity_id: app_id.clone(),
+            vulnerability_type: "CleartextTrafficAllowed".to_string(),
+            severity: VulnerabilitySeverity::Medium,
+            description: format!("{} {}", app_name, templates::DESC_ANDROID_CLEARTEXT),
+            recommendation: templates::REC_ANDROID_CLEARTEXT.to_string(),
+            file_path: normalized_path.to_string(),
+            line_number: application_line,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+    if let Some(config_name) = application_attrs.get("networkSecurityConfig").and_then(|c| c.strip_prefix("@xml/")) {
+        let config_path = path.with_file_name("res").join("xml").join(format!("{}.xml", config_name));
+        if let Ok(network_config) = std::fs::read_to_string(&config_path) {
+            if let Some(pos) = network_config.find("cleartextTrafficPermitted=\"true\"") {
+                let config_file = format!("{}/res/xml/{}.xml", normalized_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("."), config_name);
+                vulnerabilities.push(SecurityVulnerability {
+                    id: format!("{}:vuln:network-config", app_id),
+                    entity_id: app_id.clone(),
+                    vulnerability_type: "CleartextTrafficAllowed".to_string(),
+                    severity: VulnerabilitySeverity::Medium,
+                    description: format!("{} {} in {}", app_name, templates::DESC_ANDROID_CLEARTEXT, config_file),
+                    recommendation: templates::REC_ANDROID_CLEARTEXT.to_string(),
+                    file_path: config_file.trim_start_matches("./").to_string(),
+                    line_number: Some(line_at(&network_config, pos)),
+                    affected_range: None,
+                    fixed_version: None,
+                });
+            }
+        }
+    }
+    let debuggable_builds: Vec<&str> = build_types.iter()
+        .filter(|(name, settings)| name != "debug" && settings.get("debuggable").map(String::as_str) == Some("true"))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !debuggable_builds.is_empty() || application_attrs.get("debuggable").map(String::as_str) == Some("true") {
+        let builds = if debuggable_builds.is_empty() { "every".to_string() } else { format!("the {}", debuggable_builds.join(", ")) };
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:debuggable", app_id),
+            entity_id: app_id.clone(),
+            vulnerability_type: "DebuggableReleaseBuild".to_string(),
+            severity: VulnerabilitySeverity::High,
+            description: format!("{} {} ({} build)", app_name, templates::DESC_ANDROID_DEBUGGABLE, builds),
+            recommendation: templates::REC_ANDROID_DEBUGGABLE.to_string(),
+            file_path: normalized_path.to_string(),
+            line_number: application_line,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+
+    for caps in COMPONENT_TAG.captures_iter(content) {
+        let tag = caps.get(0).unwrap();
+        let kind = caps[1].to_string();
+        let attrs = attributes(&caps[2]);
+        let Some(raw_name) = attrs.get("name") else { continue };
+        let name = if raw_name.starts_with('.') { format!("{}{}", package, raw_name) } else { raw_name.clone() };
+        let line_number = line_at(content, tag.start());
+        let body = if &caps[3] == "/" {
+            ""
+        } else {
+            let close = format!("</{}>", kind);
+            content[tag.end()..].find(&close).map(|end| &content[tag.end()..tag.end() + end]).unwrap_or("")
+        };
+
+        let mut actions = Vec::new();
+        let mut deep_links = Vec::new();
+        let mut launcher = false;
+        let filters: Vec<_> = INTENT_FILTER.captures_iter(body).collect();
+        for filter in &filters {
+            let mut categories = Vec::new();
+            let mut data = Vec::new();
+            for entry in FILTER_ENTRY.captures_iter(&filter[2]) {
+                let entry_attrs = attributes(&entry[2]);
+                match &entry[1] {
+                    "action" => actions.extend(entry_attrs.get("name").cloned()),
+                    "category" => categories.extend(entry_attrs.get("name").cloned()),
+                    _ => data.push(entry_attrs),
+                }
+            }
+            launcher |= actions.iter().any(|a| a == "android.intent.action.MAIN")
+                && categories.iter().any(|c| c == "android.intent.category.LAUNCHER");
+            if categories.iter().any(|c| c == "android.intent.category.BROWSABLE") {
+                // `<data>` attributes of one filter combine, so schemes and hosts may sit on separate tags
+                let field = |key: &str| data.iter().filter_map(|d| d.get(key).cloned()).collect::<Vec<_>>();
+                let paths: Vec<String> = ["path", "pathPrefix", "pathPattern"].iter().flat_map(|k| field(k)).collect();
+                let hosts = field("host");
+                for scheme in field("scheme") {
+                    for host in if hosts.is_empty() { vec![String::new()] } else { hosts.clone() } {
+                        for path in if paths.is_empty() { vec![String::new()] } else { paths.clone() } {
+                            deep_links.push(format!("{}://{}{}", scheme, host, path));
+                        }
+                    }
+                }
+            }
+        }
+
+        let permission = attrs.get("permission").or_else(|| attrs.get("readPermission")).or_else(|| attrs.get("writePermission")).cloned();
+        // Before Android 12 a component without `android:exported` is exported once it has an intent filter
+        let exported = attrs.get("exported").map(|e| e == "true").unwrap_or(!filters.is_empty());
+
+        let id = format!("{}:android:{}:{}", normalized_path, kind, Uuid::new_v4());
+        entity_map.insert(name.clone(), id.clone());
+        let mut component_config = HashMap::new();
+        component_config.insert("kind".to_string(), Value::String(kind.clone()));
+        component_config.insert("exported".to_string(), Value::Bool(exported));
+        component_config.insert("exported_explicitly".to_string(), Value::Bool(attrs.contains_key("exported")));
+        component_config.insert("intent_actions".to_string(), json!(actions));
+        component_config.insert("deep_links".to_string(), json!(deep_links));
+        if let Some(permission) = &permission {
+            component_config.insert("permission".to_string(), Value::String(permission.clone()));
+        }
+        if let Some(authorities) = attrs.get("authorities") {
+            component_config.insert("authorities".to_string(), Value::String(authorities.clone()));
+        }
+        entities.push(SecurityEntity {
+            id: id.clone(),
+            entity_type: SecurityEntityType::AndroidComponent,
+            name: name.clone(),
+            provider: "android".to_string(),
+            configuration: component_config,
+            file_path: normalized_path.to_string(),
+            line_number: Some(line_number),
+            arn: None,
+            region: None,
+        });
+        relationships.push(SecurityRelationship {
+            source_entity_id: app_id.clone(),
+            target_entity_id: id.clone(),
+            relationship_type: "declares".to_string(),
+            permissions: permission.iter().cloned().collect(),
+            condition: exported.then(|| "exported".to_string()),
+        });
+
+        if exported && permission.is_none() && !launcher {
+            // Content providers hand out data; links into an activity are usually intended
+            let severity = match kind.as_str() {
+                "provider" => VulnerabilitySeverity::High,
+                "activity" | "activity-alias" if !deep_links.is_empty() => VulnerabilitySeverity::Low,
+                _ => VulnerabilitySeverity::Medium,
+            };
+            vulnerabilities.push(SecurityVulnerability {
+                id: format!("{}:vuln:1", id),
+                entity_id: id,
+                vulnerability_type: "ExportedComponentWithoutPermission".to_string(),
+                severity,
+                description: format!("{} {} {}", kind, name, templates::DESC_ANDROID_EXPORTED),
+                recommendation: templates::REC_ANDROID_EXPORTED.to_string(),
+                file_path: normalized_path.to_string(),
+                line_number: Some(line_number),
+                affected_range: None,
+                fixed_version: None,
+            });
+        }
+    }
+
+    Ok((entities, relationships, vulnerabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::test_support::write;
+
+    #[test]
+    fn test_android_manifest_components_flavors_and_findings() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "app/build.gradle.kts", r#"
+android {
+    namespace = "com.acme.shop"
+    defaultConfig { applicationId = "com.acme.shop" }
+    flavorDimensions += "tier"
+    productFlavors {
+        create("free") {
+            dimension = "tier"
+            applicationIdSuffix = ".free"
+        }
+        create("paid") { dimension = "tier" }
+    }
+    buildTypes {
+        getByName("release") {
+            isMinifyEnabled = true
+        }
+        create("staging") {
+            isDebuggable = true
+        }
+    }
+}
+"#);
+        write(root, "app/src/main/res/xml/network_security_config.xml", r#"<network-security-config>
+    <domain-config cleartextTrafficPermitted="true">
+        <domain>legacy.acme.com</domain>
+    </domain-config>
+</network-security-config>
+"#);
+        let manifest = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android">
+    <uses-permission android:name="android.permission.INTERNET" />
+    <application android:networkSecurityConfig="@xml/network_security_config">
+        <activity android:name=".MainActivity" android:exported="true">
+            <intent-filter>
+                <action android:name="android.intent.action.MAIN" />
+                <category android:name="android.intent.category.LAUNCHER" />
+            </intent-filter>
+        </activity>
+        <activity android:name=".ProductActivity">
+            <intent-filter android:autoVerify="true">
+                <action android:name="android.intent.action.VIEW" />
+                <category android:name="android.intent.category.BROWSABLE" />
+                <data android:scheme="https" android:host="shop.acme.com" android:pathPrefix="/p/" />
+            </intent-filter>
+        </activity>
+        <provider android:name="com.acme.shop.data.OrdersProvider" android:authorities="com.acme.shop.orders" android:exported="true" />
+        <service android:name=".SyncService" android:exported="true" android:permission="com.acme.shop.SYNC" />
+        <receiver android:name=".BootReceiver" android:exported="false" />
+    </application>
+</manifest>
+"#;
+        write(root, "app/src/main/AndroidManifest.xml", manifest);
+
+        let mut entity_map = HashMap::new();
+        let path = temp_dir.path().join("app/src/main/AndroidManifest.xml");
+        let (entities, relationships, vulnerabilities) =
+            analyze_android_manifest(manifest, &path, "app/src/main/AndroidManifest.xml", &mut entity_map).unwrap();
+
+        let app = &entities[0];
+        assert_eq!(app.name, "com.acme.shop");
+        assert_eq!(app.configuration["permissions"], json!(["android.permission.INTERNET"]));
+        assert_eq!(app.configuration["product_flavors"][0], json!({"name": "free", "settings": {"dimension": "tier", "applicationIdSuffix": ".free"}}));
+        assert_eq!(app.configuration["build_types"][1]["name"], "staging");
+        let product = entities.iter().find(|e| e.name == "com.acme.shop.ProductActivity").unwrap();
+        assert_eq!(product.configuration["exported"], json!(true));
+        assert_eq!(product.configuration["deep_links"], json!(["https://shop.acme.com/p/"]));
+        assert_eq!(relationships.len(), 5);
+
+        let findings: Vec<(&str, &str)> = vulnerabilities.iter()
+            .map(|v| (v.vulnerability_type.as_str(), v.file_path.as_str()))
+            .collect();
+        assert_eq!(findings, vec![
+            ("CleartextTrafficAllowed", "app/src/main/res/xml/network_security_config.xml"),
+            ("DebuggableReleaseBuild", "app/src/main/AndroidManifest.xml"),
+            ("ExportedComponentWithoutPermission", "app/src/main/AndroidManifest.xml"),
+            ("ExportedComponentWithoutPermission", "app/src/main/AndroidManifest.xml"),
+        ]);
+        assert!(matches!(vulnerabilities[2].severity, VulnerabilitySeverity::Low));
+        assert!(matches!(vulnerabilities[3].severity, VulnerabilitySeverity::High));
+        assert!(vulnerabilities[1].description.contains("the staging build"));
+    }
+
+    #[test]
+    fn test_android_manifest_without_package_implicit_exports_and_debug_builds() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(root, "lib/build.gradle", "android {\n    buildTypes {\n        debug {\n            debuggable true\n        }\n        release {\n            minifyEnabled true\n        }\n    }\n}\n");
+        let manifest = r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android">
+    <application android:usesCleartextTraffic="false">
+        <service android:name="com.acme.PushService">
+            <intent-filter>
+                <action android:name="com.google.firebase.MESSAGING_EVENT" />
+            </intent-filter>
+        </service>
+        <receiver android:name="com.acme.AlarmReceiver" android:exported="true" android:permission="com.acme.permission.ALARM" />
+        <activity android:exported="true" />
+    </application>
+</manifest>
+"#;
+        write(root, "lib/src/debug/AndroidManifest.xml", manifest);
+
+        let mut entity_map = HashMap::new();
+        let path = temp_dir.path().join("lib/src/debug/AndroidManifest.xml");
+        let (entities, relationships, vulnerabilities) =
+            analyze_android_manifest(manifest, &path, "lib/src/debug/AndroidManifest.xml", &mut entity_map).unwrap();
+
+        // Neither the manifest nor the Gradle module names the app, and the nameless activity is skipped
+        let names: Vec<&str> = entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["AndroidManifest.xml (debug)", "com.acme.PushService", "com.acme.AlarmReceiver"]);
+        assert_eq!(entities[0].configuration["gradle_file"], "build.gradle");
+        assert_eq!(entities[0].configuration["build_types"][0]["name"], "debug");
+        assert_eq!(entities[1].configuration["exported"], json!(true));
+        assert_eq!(entities[1].configuration["exported_explicitly"], json!(false));
+        assert_eq!(relationships[1].permissions, vec!["com.acme.permission.ALARM"]);
+
+        // A debuggable debug build is expected; only the implicitly exported service is reported
+        let findings: Vec<(&str, &str)> = vulnerabilities.iter()
+            .map(|v| (v.vulnerability_type.as_str(), v.description.as_str()))
+            .collect();
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        assert_eq!(findings[0].0, "ExportedComponentWithoutPermission");
+        assert!(findings[0].1.contains("com.acme.PushService"));
+        assert!(matches!(vulnerabilities[0].severity, VulnerabilitySeverity::Medium));
+    }
+}
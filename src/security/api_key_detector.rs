@@ -7,7 +7,7 @@ use walkdir::WalkDir;
 use serde_json::Value;
 use uuid::Uuid;
 use crate::security::{SecurityEntity, SecurityRelationship, SecurityVulnerability, SecurityEntityType, VulnerabilitySeverity};
-use crate::analysis::{CodeElement, CodeStructure};
+use crate::analysis::{CodeElement, CodeStructure, RepoPath};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedApiKey {
@@ -48,11 +48,7 @@ impl ApiKeyDetector {
             log::info!("Building code element map from {} elements", code_struct.elements.len());
             for element in &code_struct.elements {
                 // Normalize path: make it relative to repo_path if it's absolute
-                let normalized_path = if let Ok(rel_path) = std::path::Path::new(&element.file_path).strip_prefix(repo_path) {
-                    rel_path.to_string_lossy().to_string()
-                } else {
-                    element.file_path.clone()
-                };
+                let normalized_path = RepoPath::new(repo_path, Path::new(&element.file_path)).into_string();
                 code_elements_by_file
                     .entry(normalized_path)
                     .or_insert_with(Vec::new)
@@ -92,6 +88,7 @@ impl ApiKeyDetector {
                     crate::security::ServiceProvider::MistralAI => "mistral_ai",
                     crate::security::ServiceProvider::Perplexity => "perplexity",
                     crate::security::ServiceProvider::WebMethods => "webmethods",
+                    crate::security::ServiceProvider::Salesforce => "salesforce",
                     crate::security::ServiceProvider::Unknown => "generic",
                     _ => "generic",
                 };
@@ -174,27 +171,7 @@ impl ApiKeyDetector {
                 match std::fs::read_to_string(path) {
                     Ok(content) => {
                         // Normalize path relative to repo_path for matching and storage
-                        let file_path_str = if let Ok(rel_path) = path.strip_prefix(repo_path) {
-                            // Remove leading "./" if present
-                            let rel_str = rel_path.to_string_lossy().to_string();
-                            if rel_str.starts_with("./") {
-                                rel_str[2..].to_string()
-                            } else {
-                                rel_str
-                            }
-                        } else {
-                            // Try to strip common cache prefixes
-                            let path_str = path.to_string_lossy().to_string();
-                            if let Some(stripped) = path_str.strip_prefix("./cache/repos/") {
-                                if let Some(repo_rel) = stripped.find('/') {
-                                    stripped[repo_rel+1..].to_string()
-                                } else {
-                                    path_str
-                                }
-                            } else {
-                                path_str
-                            }
-                        };
+                        let file_path_str = RepoPath::new(repo_path, path).into_string();
                         let file_elements = code_elements_by_file.get(&file_path_str);
                         match self.scan_file_for_keys(&content, path, file_elements, &file_path_str) {
                             Ok(keys) => {
@@ -320,6 +297,8 @@ impl ApiKeyDetector {
                     recommendation: "Move API key to environment variables or secure secret management system".to_string(),
                     file_path: key.file_path,
                     line_number: key.line_number,
+                    affected_range: None,
+                    fixed_version: None,
                 });
             }
         }
@@ -0,0 +1,116 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::analysis::{DetectedTool, ToolCategory, ToolType};
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityVulnerability, VulnerabilitySeverity};
+
+/// Endpoints on the build machine itself, which only its own users can reach
+fn is_local(url: &str) -> bool {
+    let host = url.split("://").last().unwrap_or(url);
+    url.starts_with("unix:") || ["localhost", "127.0.0.1", "[::1]"].iter().any(|local| host.starts_with(local))
+}
+
+/// Flags the Bazel and Buck remote caches and executors the tool detector found configured
+/// without any credentials. An executor keeps its own action cache, so it is held to the same
+/// standard; build event services only receive logs and are recorded without a finding. Caches
+/// reached over plaintext HTTP or gRPC are worse, since anyone on the path can tamper with them too.
+pub fn analyze_remote_caches(tools: &[DetectedTool]) -> (Vec<SecurityEntity>, Vec<SecurityVulnerability>) {
+    let mut entities = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    for tool in tools.iter().filter(|t| t.tool_type == ToolType::BuildCache) {
+        let build_system = match tool.category {
+            ToolCategory::Buck => "buck",
+            _ => "bazel",
+        };
+        let id = format!("{}:remote_cache:{}", tool.file_path, tool.name);
+        let config: HashMap<String, Value> = tool.configuration.iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+        entities.push(SecurityEntity {
+            id: id.clone(),
+            entity_type: SecurityEntityType::RemoteBuildCache,
+            name: tool.name.clone(),
+            provider: build_system.to_string(),
+            configuration: config,
+            file_path: tool.file_path.clone(),
+            line_number: tool.line_number,
+            arn: None,
+            region: None,
+        });
+
+        let kind = tool.configuration.get("kind").map(|k| k.as_str()).unwrap_or("");
+        let authenticated = tool.configuration.get("authentication").is_some_and(|a| a != "none");
+        if authenticated || !matches!(kind, "remote_cache" | "remote_executor") || is_local(&tool.name) {
+            continue;
+        }
+        let url = tool.name.to_lowercase();
+        let severity = if url.starts_with("http://") || url.starts_with("grpc://") {
+            VulnerabilitySeverity::High
+        } else {
+            VulnerabilitySeverity::Medium
+        };
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:1", id),
+            entity_id: id,
+            vulnerability_type: "UnauthenticatedRemoteCache".to_string(),
+            severity,
+            description: format!("{} {} {} {}", build_system, kind.replace('_', " "), tool.name, templates::DESC_UNAUTHENTICATED_REMOTE_CACHE),
+            recommendation: templates::REC_UNAUTHENTICATED_REMOTE_CACHE.to_string(),
+            file_path: tool.file_path.clone(),
+            line_number: tool.line_number,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+    (entities, vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::ToolDetector;
+
+    #[test]
+    fn test_flags_remote_caches_without_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".bazelversion"), "7.1.0\n").unwrap();
+        std::fs::write(root.join(".bazelrc"), "\
+build --remote_cache=grpcs://cache.example.com
+build:rbe --remote_executor grpcs://rbe.example.com --extra_toolchains=//toolchains:rbe_cc
+build:rbe --google_default_credentials
+build --bes_backend=grpcs://bes.example.com
+build:local --remote_cache=http://localhost:9090
+").unwrap();
+        std::fs::write(root.join("MODULE.bazel"), "register_toolchains(\n    \"//toolchains:linux_cc\",\n)\n").unwrap();
+        std::fs::create_dir_all(root.join("toolchains")).unwrap();
+        std::fs::write(root.join("toolchains/BUILD.bazel"), "\
+toolchain(
+    name = \"linux_cc\",
+    toolchain_type = \"@bazel_tools//tools/cpp:toolchain_type\",
+    toolchain = \":cc\",
+)
+").unwrap();
+        std::fs::create_dir_all(root.join("mobile")).unwrap();
+        std::fs::write(root.join("mobile/.buckconfig"), "[cache]\n  mode = http\n  http_url = http://buck-cache.internal:8080\n").unwrap();
+
+        let tools = ToolDetector::new().detect_tools(root).unwrap();
+        let bazel = tools.iter().find(|t| t.name == "bazel").unwrap();
+        assert_eq!(bazel.version.as_deref(), Some("7.1.0"));
+        let toolchain = tools.iter().find(|t| t.name == "//toolchains:linux_cc").unwrap();
+        assert_eq!(toolchain.tool_type, ToolType::Toolchain);
+        assert_eq!(toolchain.configuration.get("toolchain_type").map(|t| t.as_str()), Some("@bazel_tools//tools/cpp:toolchain_type"));
+        assert!(tools.iter().any(|t| t.name == "//toolchains:rbe_cc" && t.tool_type == ToolType::Toolchain));
+
+        let (entities, vulnerabilities) = analyze_remote_caches(&tools);
+        assert_eq!(entities.len(), 5);
+        let mut flagged: Vec<(&str, Option<usize>, String)> = vulnerabilities.iter()
+            .map(|v| (v.file_path.as_str(), v.line_number, format!("{:?}", v.severity)))
+            .collect();
+        flagged.sort();
+        assert_eq!(flagged, vec![
+            (".bazelrc", Some(1), "Medium".to_string()),
+            ("mobile/.buckconfig", Some(3), "High".to_string()),
+        ]);
+    }
+}
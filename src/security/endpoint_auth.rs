@@ -0,0 +1,125 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::analysis::{DetectedEndpoint, HttpMethod};
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityVulnerability, VulnerabilitySeverity};
+
+/// Middleware names that authenticate or authorize the caller
+static AUTH_MIDDLEWARE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)auth|guard|jwt|bearer|token|session|passport|login|secured|roles|permission|apikey|api_key|protect|verify|require_?user|current_?user").unwrap()
+});
+/// Markers that an endpoint is public on purpose
+static PUBLIC_MARKER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:Public|PermitAll|AllowAnonymous)\b").unwrap());
+/// Paths that are expected to answer anonymous callers
+static PUBLIC_PATH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)health|ready|live|status|ping|metrics|version|login|logout|sign_?in|sign_?up|register|callback|webhook|oauth|token|public|docs|swagger|openapi|favicon|robots").unwrap()
+});
+
+fn is_authenticated(endpoint: &DetectedEndpoint) -> bool {
+    endpoint.middleware.iter().any(|m| AUTH_MIDDLEWARE.is_match(m))
+}
+
+fn is_public(endpoint: &DetectedEndpoint) -> bool {
+    endpoint.middleware.iter().any(|m| PUBLIC_MARKER.is_match(m)) || PUBLIC_PATH.is_match(&endpoint.path)
+}
+
+/// Flags endpoints that run no auth middleware or guard while other endpoints of the same
+/// framework do, which usually means a route was added without the protection its neighbours
+/// have. Frameworks that never guard a route in code (auth at a gateway or in a global filter)
+/// are left alone, as are health checks, login flows and other paths meant to be public.
+pub fn analyze_endpoint_auth(endpoints: &[DetectedEndpoint]) -> (Vec<SecurityEntity>, Vec<SecurityVulnerability>) {
+    let mut guarded_frameworks: HashMap<&str, usize> = HashMap::new();
+    for endpoint in endpoints.iter().filter(|e| is_authenticated(e)) {
+        *guarded_frameworks.entry(endpoint.framework.as_deref().unwrap_or("")).or_default() += 1;
+    }
+
+    let mut entities = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    for endpoint in endpoints {
+        let framework = endpoint.framework.as_deref().unwrap_or("");
+        if !guarded_frameworks.contains_key(framework) || is_authenticated(endpoint) || is_public(endpoint) {
+            continue;
+        }
+
+        let method = format!("{:?}", endpoint.method).to_uppercase();
+        let id = format!("{}:endpoint:{}", endpoint.file_path, Uuid::new_v4());
+        let mut config = HashMap::new();
+        config.insert("method".to_string(), Value::String(method.clone()));
+        config.insert("path".to_string(), Value::String(endpoint.path.clone()));
+        if let Some(handler) = &endpoint.handler {
+            config.insert("handler".to_string(), Value::String(handler.clone()));
+        }
+        config.insert("middleware".to_string(), serde_json::json!(endpoint.middleware));
+
+        entities.push(SecurityEntity {
+            id: id.clone(),
+            entity_type: SecurityEntityType::HttpEndpoint,
+            name: format!("{} {}", method, endpoint.path),
+            provider: framework.to_string(),
+            configuration: config,
+            file_path: endpoint.file_path.clone(),
+            line_number: endpoint.line_number,
+            arn: None,
+            region: None,
+        });
+
+        // Reads leak data; writes let anyone change it
+        let severity = match endpoint.method {
+            HttpMethod::Get | HttpMethod::Head | HttpMethod::Options => VulnerabilitySeverity::Medium,
+            _ => VulnerabilitySeverity::High,
+        };
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:1", id),
+            entity_id: id,
+            vulnerability_type: "UnauthenticatedEndpoint".to_string(),
+            severity,
+            description: format!("{} {} {}", method, endpoint.path, templates::DESC_UNAUTHENTICATED_ENDPOINT),
+            recommendation: templates::REC_UNAUTHENTICATED_ENDPOINT.to_string(),
+            file_path: endpoint.file_path.clone(),
+            line_number: endpoint.line_number,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+    (entities, vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::EndpointProtocol;
+
+    fn endpoint(framework: &str, method: HttpMethod, path: &str, middleware: &[&str]) -> DetectedEndpoint {
+        DetectedEndpoint {
+            path: path.to_string(),
+            method,
+            handler: None,
+            file_path: "src/routes.ts".to_string(),
+            line_number: Some(1),
+            framework: Some(framework.to_string()),
+            middleware: middleware.iter().map(|m| m.to_string()).collect(),
+            parameters: Vec::new(),
+            protocol: EndpointProtocol::Http,
+        }
+    }
+
+    #[test]
+    fn test_flags_endpoints_missing_the_guard_their_neighbours_have() {
+        let endpoints = vec![
+            endpoint("express", HttpMethod::Get, "/orders", &["requireAuth"]),
+            endpoint("express", HttpMethod::Delete, "/orders/:id", &["rateLimit"]),
+            endpoint("express", HttpMethod::Get, "/health", &[]),
+            endpoint("nestjs", HttpMethod::Get, "/reports", &["Public"]),
+            // No flask route is guarded in code, so auth must live elsewhere
+            endpoint("flask", HttpMethod::Post, "/items", &[]),
+        ];
+        let (entities, vulnerabilities) = analyze_endpoint_auth(&endpoints);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].name, "DELETE /orders/:id");
+        assert_eq!(vulnerabilities[0].vulnerability_type, "UnauthenticatedEndpoint");
+        assert!(matches!(vulnerabilities[0].severity, VulnerabilitySeverity::High));
+    }
+}
@@ -71,6 +71,8 @@ pub fn analyze_firebase_rules(
             recommendation: templates::REC_FIREBASE_RULES.to_string(),
             file_path: normalized_path.to_string(),
             line_number: None,
+            affected_range: None,
+            fixed_version: None,
         });
     }
 
@@ -91,6 +93,8 @@ pub fn analyze_firebase_rules(
                     recommendation: templates::REC_FIREBASE_AUTH.to_string(),
                     file_path: normalized_path.to_string(),
                     line_number: Some(line_num + 1),
+                    affected_range: None,
+                    fixed_version: None,
                 });
                 break; // Only report once per file
             }
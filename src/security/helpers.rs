@@ -1,4 +1,5 @@
 use std::path::Path;
+use crate::analysis::RepoPath;
 use crate::security::templates;
 
 /// Check if content is a CloudFormation template
@@ -13,28 +14,9 @@ pub fn is_sam_template(content: &str) -> bool {
     (content.contains("Transform: AWS::Serverless") || content.contains("Transform: AWS::Serverless-2016-10-31"))
 }
 
-/// Normalize file path to remove cache prefixes
+/// Normalize file path to be relative to the repository checkout
 pub fn normalize_path(path: &Path, repo_path: &Path) -> String {
-    if let Ok(rel_path) = path.strip_prefix(repo_path) {
-        let rel_str = rel_path.to_string_lossy().to_string();
-        if rel_str.starts_with("./") {
-            rel_str[2..].to_string()
-        } else {
-            rel_str
-        }
-    } else {
-        // Try to strip common cache prefixes
-        let path_str = path.to_string_lossy().to_string();
-        if let Some(stripped) = path_str.strip_prefix("./cache/repos/") {
-            if let Some(repo_rel) = stripped.find('/') {
-                stripped[repo_rel+1..].to_string()
-            } else {
-                path_str
-            }
-        } else {
-            path_str
-        }
-    }
+    RepoPath::new(repo_path, path).into_string()
 }
 
 /// Extract Terraform resource from content
@@ -0,0 +1,422 @@
+//! iOS and macOS apps: each `Info.plist` becomes an app entity with its bundle identifier, the URL
+//! schemes it claims, the background modes it runs in, the privacy usage descriptions it declares
+//! and its App Transport Security settings. The `.entitlements` files beside it (or on their own,
+//! for targets that generate their Info.plist) become an entitlements entity the app is signed
+//! with. ATS exceptions that allow cleartext or old TLS, custom URL schemes, `get-task-allow` and
+//! hardened runtime or sandbox opt-outs are raised as vulnerabilities.
+
+use anyhow::{Context, Result};
+use plist::{Dictionary, Value as Plist};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityRelationship, SecurityVulnerability, VulnerabilitySeverity};
+
+/// Directories holding dependencies' bundles rather than the repository's own apps
+const DEPENDENCY_DIRS: &[&str] = &["/pods/", "/carthage/", ".framework/", ".xcframework/", ".bundle/", "/sourcepackages/"];
+
+/// TLS versions ATS accepts below its TLS 1.2 default
+const WEAK_TLS: &[&str] = &["TLSv1.0", "TLSv1.1"];
+
+/// Hardened runtime entitlements that each turn off one of its protections
+const HARDENED_RUNTIME_EXCEPTIONS: &[&str] = &[
+    "com.apple.security.cs.allow-jit",
+    "com.apple.security.cs.allow-unsigned-executable-memory",
+    "com.apple.security.cs.allow-dyld-environment-variables",
+    "com.apple.security.cs.disable-library-validation",
+    "com.apple.security.cs.disable-executable-page-protection",
+    "com.apple.security.cs.debugger",
+];
+
+/// Whether the security analysis reads this file: `Info.plist` and `.entitlements` files of the
+/// repository's own targets; entitlements next to an Info.plist are read with it
+pub fn is_ios_file(path: &Path, file_name: &str) -> bool {
+    let path_str = format!("/{}", path.to_string_lossy().to_lowercase().replace('\\', "/"));
+    if DEPENDENCY_DIRS.iter().any(|dir| path_str.contains(dir)) {
+        return false;
+    }
+    file_name == "info.plist" || file_name.ends_with(".entitlements") && info_plist_beside(path).is_none()
+}
+
+fn info_plist_beside(path: &Path) -> Option<std::path::PathBuf> {
+    let dir = path.parent()?;
+    ["Info.plist", "info.plist"].iter().map(|name| dir.join(name)).find(|p| p.is_file())
+}
+
+/// 1-based line of `<key>name</key>` in an XML plist, `None` for binary plists
+fn key_line(text: &str, key: &str) -> Option<usize> {
+    let pos = text.find(&format!("<key>{}</key>", key))?;
+    Some(text[..pos].matches('\n').count() + 1)
+}
+
+fn strings(value: Option<&Plist>) -> Vec<String> {
+    value.and_then(Plist::as_array)
+        .map(|items| items.iter().filter_map(Plist::as_string).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn flag(dict: &Dictionary, key: &str) -> bool {
+    dict.get(key).and_then(Plist::as_boolean).unwrap_or(false)
+}
+
+/// A plist value as JSON; dates and data become strings
+fn to_json(value: &Plist) -> Value {
+    match value {
+        Plist::Dictionary(dict) => Value::Object(dict.iter().map(|(k, v)| (k.clone(), to_json(v))).collect()),
+        Plist::Array(items) => Value::Array(items.iter().map(to_json).collect()),
+        Plist::Boolean(b) => Value::Bool(*b),
+        Plist::String(s) => Value::String(s.clone()),
+        Plist::Integer(i) => i.as_signed().map(Value::from).unwrap_or_else(|| Value::String(i.to_string())),
+        Plist::Real(r) => json!(r),
+        Plist::Date(d) => Value::String(d.to_xml_format()),
+        Plist::Data(d) => Value::String(format!("<{} bytes>", d.len())),
+        _ => Value::Null,
+    }
+}
+
+fn parse(bytes: &[u8], normalized_path: &str) -> Result<Dictionary> {
+    Plist::from_reader(std::io::Cursor::new(bytes))
+        .with_context(|| format!("Failed to parse {}", normalized_path))?
+        .into_dictionary()
+        .with_context(|| format!("{} is not a plist dictionary", normalized_path))
+}
+
+/// Analyze an `Info.plist` and the `.entitlements` files in its directory
+pub fn analyze_info_plist(
+    bytes: &[u8],
+    path: &Path,
+    normalized_path: &str,
+    entity_map: &mut HashMap<String, String>,
+) -> Result<(Vec<SecurityEntity>, Vec<SecurityRelationship>, Vec<SecurityVulnerability>)> {
+    let mut entities = Vec::new();
+    let mut relationships = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    let Ok(info) = parse(bytes, normalized_path) else {
+        return Ok((entities, relationships, vulnerabilities));
+    };
+    // Info.plist is also the name of localized and resource plists that describe no bundle
+    if !info.contains_key("CFBundleIdentifier") && !info.contains_key("CFBundleExecutable") {
+        return Ok((entities, relationships, vulnerabilities));
+    }
+    let text = String::from_utf8_lossy(bytes);
+    let line = |key: &str| key_line(&text, key);
+
+    let bundle_id = info.get("CFBundleIdentifier").and_then(Plist::as_string).unwrap_or("").to_string();
+    let display_name = ["CFBundleDisplayName", "CFBundleName"].iter()
+        .find_map(|k| info.get(k).and_then(Plist::as_string))
+        .map(str::to_string);
+    let directory = normalized_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or(".");
+    let app_name = if bundle_id.is_empty() { format!("Info.plist ({})", directory) } else { bundle_id.clone() };
+    let app_id = format!("{}:ios:app:{}", normalized_path, Uuid::new_v4());
+    entity_map.insert(format!("ios:{}", directory), app_id.clone());
+
+    let url_types = info.get("CFBundleURLTypes").and_then(Plist::as_array).cloned().unwrap_or_default();
+    let url_schemes: Vec<String> = url_types.iter()
+        .filter_map(Plist::as_dictionary)
+        .flat_map(|t| strings(t.get("CFBundleURLSchemes")))
+        .collect();
+    let usage_descriptions: serde_json::Map<String, Value> = info.iter()
+        .filter(|(k, _)| k.starts_with("NS") && k.ends_with("UsageDescription"))
+        .map(|(k, v)| (k.clone(), to_json(v)))
+        .collect();
+
+    let mut config = HashMap::new();
+    config.insert("bundle_identifier".to_string(), Value::String(bundle_id));
+    if let Some(display_name) = display_name {
+        config.insert("display_name".to_string(), Value::String(display_name));
+    }
+    if let Some(version) = info.get("CFBundleShortVersionString").and_then(Plist::as_string) {
+        config.insert("version".to_string(), Value::String(version.to_string()));
+    }
+    config.insert("url_schemes".to_string(), json!(url_schemes));
+    config.insert("queried_schemes".to_string(), json!(strings(info.get("LSApplicationQueriesSchemes"))));
+    config.insert("background_modes".to_string(), json!(strings(info.get("UIBackgroundModes"))));
+    config.insert("usage_descriptions".to_string(), Value::Object(usage_descriptions));
+    let ats = info.get("NSAppTransportSecurity").and_then(Plist::as_dictionary);
+    if let Some(ats) = ats {
+        config.insert("app_transport_security".to_string(), to_json(&Plist::Dictionary(ats.clone())));
+    }
+
+    entities.push(SecurityEntity {
+        id: app_id.clone(),
+        entity_type: SecurityEntityType::IosApp,
+        name: app_name.clone(),
+        provider: "ios".to_string(),
+        configuration: config,
+        file_path: normalized_path.to_string(),
+        line_number: line("CFBundleIdentifier"),
+        arn: None,
+        region: None,
+    });
+
+    let mut vulnerability = |kind: &str, severity: VulnerabilitySeverity, description: String, recommendation: &str, line_number: Option<usize>| {
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:{}", app_id, vulnerabilities.len() + 1),
+            entity_id: app_id.clone(),
+            vulnerability_type: kind.to_string(),
+            severity,
+            description,
+            recommendation: recommendation.to_string(),
+            file_path: normalized_path.to_string(),
+            line_number,
+            affected_range: None,
+            fixed_version: None,
+        });
+    };
+
+    if let Some(ats) = ats {
+        if flag(ats, "NSAllowsArbitraryLoads") {
+            vulnerability(
+                "CleartextTrafficAllowed", VulnerabilitySeverity::Medium,
+                format!("{} {}", app_name, templates::DESC_IOS_ATS_ARBITRARY),
+                templates::REC_IOS_ATS, line("NSAllowsArbitraryLoads"),
+            );
+        }
+        for key in ["NSAllowsArbitraryLoadsInWebContent", "NSAllowsArbitraryLoadsForMedia"] {
+            if flag(ats, key) {
+                vulnerability(
+                    "CleartextTrafficAllowed", VulnerabilitySeverity::Low,
+                    format!("{} {} ({})", app_name, templates::DESC_IOS_ATS_ARBITRARY, key),
+                    templates::REC_IOS_ATS, line(key),
+                );
+            }
+        }
+        let domains = ats.get("NSExceptionDomains").and_then(Plist::as_dictionary);
+        for (domain, settings) in domains.into_iter().flat_map(|d| d.iter()) {
+            let Some(settings) = settings.as_dictionary() else { continue };
+            let subdomains = if flag(settings, "NSIncludesSubdomains") { " and its subdomains" } else { "" };
+            if flag(settings, "NSExceptionAllowsInsecureHTTPLoads") || flag(settings, "NSTemporaryExceptionAllowsInsecureHTTPLoads") {
+                vulnerability(
+                    "CleartextTrafficAllowed", VulnerabilitySeverity::Low,
+                    format!("{} {} {}{}", app_name, templates::DESC_IOS_ATS_EXCEPTION, domain, subdomains),
+                    templates::REC_IOS_ATS, line(domain),
+                );
+            }
+            let tls = ["NSExceptionMinimumTLSVersion", "NSTemporaryExceptionMinimumTLSVersion"].iter()
+                .find_map(|k| settings.get(k).and_then(Plist::as_string));
+            if let Some(tls) = tls.filter(|v| WEAK_TLS.contains(v)) {
+                vulnerability(
+                    "WeakTlsVersion", VulnerabilitySeverity::Medium,
+                    format!("{} {} {} with {}{}", app_name, templates::DESC_IOS_WEAK_TLS, tls, domain, subdomains),
+                    templates::REC_IOS_WEAK_TLS, line(domain),
+                );
+            }
+        }
+    }
+    if !url_schemes.is_empty() {
+        vulnerability(
+            "CustomUrlScheme", VulnerabilitySeverity::Low,
+            format!("{} {}: {}", app_name, templates::DESC_IOS_URL_SCHEME, url_schemes.join(", ")),
+            templates::REC_IOS_URL_SCHEME, line("CFBundleURLSchemes"),
+        );
+    }
+
+    // Entitlements the target is signed with
+    let mut siblings: Vec<_> = path.parent()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "entitlements"))
+        .collect();
+    siblings.sort();
+    for entitlements_path in siblings {
+        let Ok(entitlements) = std::fs::read(&entitlements_path) else { continue };
+        let file_name = entitlements_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let entitlements_file = if directory == "." { file_name.to_string() } else { format!("{}/{}", directory, file_name) };
+        let (mut found, found_vulns) = analyze_entitlements(&entitlements, &entitlements_path, &entitlements_file, entity_map)?;
+        for entity in &found {
+            relationships.push(SecurityRelationship {
+                source_entity_id: app_id.clone(),
+                target_entity_id: entity.id.clone(),
+                relationship_type: "signed_with".to_string(),
+                permissions: Vec::new(),
+                condition: None,
+            });
+        }
+        entities.append(&mut found);
+        vulnerabilities.extend(found_vulns);
+    }
+
+    Ok((entities, relationships, vulnerabilities))
+}
+
+/// Analyze a `.entitlements` file
+pub fn analyze_entitlements(
+    bytes: &[u8],
+    path: &Path,
+    normalized_path: &str,
+    entity_map: &mut HashMap<String, String>,
+) -> Result<(Vec<SecurityEntity>, Vec<SecurityVulnerability>)> {
+    let mut entities = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    let Ok(entitlements) = parse(bytes, normalized_path) else {
+        return Ok((entities, vulnerabilities));
+    };
+    let text = String::from_utf8_lossy(bytes);
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("entitlements").to_string();
+    let id = format!("{}:ios:entitlements:{}", normalized_path, Uuid::new_v4());
+    entity_map.insert(format!("ios:entitlements:{}", normalized_path), id.clone());
+
+    let mut config = HashMap::new();
+    for (key, field) in [
+        ("com.apple.developer.associated-domains", "associated_domains"),
+        ("com.apple.security.application-groups", "application_groups"),
+        ("keychain-access-groups", "keychain_access_groups"),
+        ("com.apple.developer.icloud-container-identifiers", "icloud_containers"),
+    ] {
+        let values = strings(entitlements.get(key));
+        if !values.is_empty() {
+            config.insert(field.to_string(), json!(values));
+        }
+    }
+    if let Some(aps) = entitlements.get("aps-environment").and_then(Plist::as_string) {
+        config.insert("aps_environment".to_string(), Value::String(aps.to_string()));
+    }
+    let enabled: Vec<&str> = entitlements.iter()
+        .filter(|(_, v)| v.as_boolean() == Some(true))
+        .map(|(k, _)| k.as_str())
+        .collect();
+    config.insert("enabled".to_string(), json!(enabled));
+
+    let mut vulnerability = |kind: &str, severity: VulnerabilitySeverity, description: String, recommendation: &str, key: &str| {
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:{}", id, vulnerabilities.len() + 1),
+            entity_id: id.clone(),
+            vulnerability_type: kind.to_string(),
+            severity,
+            description,
+            recommendation: recommendation.to_string(),
+            file_path: normalized_path.to_string(),
+            line_number: key_line(&text, key),
+            affected_range: None,
+            fixed_version: None,
+        });
+    };
+    if flag(&entitlements, "get-task-allow") {
+        vulnerability(
+            "DebuggableReleaseBuild", VulnerabilitySeverity::High,
+            format!("{} {}", name, templates::DESC_IOS_GET_TASK_ALLOW),
+            templates::REC_IOS_GET_TASK_ALLOW, "get-task-allow",
+        );
+    }
+    let exceptions: Vec<&str> = HARDENED_RUNTIME_EXCEPTIONS.iter().copied().filter(|k| flag(&entitlements, k)).collect();
+    if let Some(first) = exceptions.first() {
+        vulnerability(
+            "HardenedRuntimeException", VulnerabilitySeverity::Medium,
+            format!("{} {}: {}", name, templates::DESC_IOS_HARDENED_RUNTIME, exceptions.join(", ")),
+            templates::REC_IOS_HARDENED_RUNTIME, first,
+        );
+    }
+    if entitlements.get("com.apple.security.app-sandbox").and_then(Plist::as_boolean) == Some(false) {
+        vulnerability(
+            "AppSandboxDisabled", VulnerabilitySeverity::Medium,
+            format!("{} {}", name, templates::DESC_IOS_SANDBOX_DISABLED),
+            templates::REC_IOS_SANDBOX_DISABLED, "com.apple.security.app-sandbox",
+        );
+    }
+
+    entities.push(SecurityEntity {
+        id,
+        entity_type: SecurityEntityType::IosEntitlements,
+        name,
+        provider: "ios".to_string(),
+        configuration: config,
+        file_path: normalized_path.to_string(),
+        line_number: None,
+        arn: None,
+        region: None,
+    });
+    Ok((entities, vulnerabilities))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_info_plist_ats_schemes_and_entitlements() {
+        let temp_dir = TempDir::new().unwrap();
+        let app_dir = temp_dir.path().join("Shop");
+        fs::create_dir_all(&app_dir).unwrap();
+        let info = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key>
+    <string>com.acme.shop</string>
+    <key>CFBundleURLTypes</key>
+    <array>
+        <dict>
+            <key>CFBundleURLSchemes</key>
+            <array><string>acmeshop</string></array>
+        </dict>
+    </array>
+    <key>UIBackgroundModes</key>
+    <array><string>remote-notification</string><string>location</string></array>
+    <key>NSCameraUsageDescription</key>
+    <string>Scan receipts</string>
+    <key>NSAppTransportSecurity</key>
+    <dict>
+        <key>NSExceptionDomains</key>
+        <dict>
+            <key>legacy.acme.com</key>
+            <dict>
+                <key>NSIncludesSubdomains</key>
+                <true/>
+                <key>NSExceptionAllowsInsecureHTTPLoads</key>
+                <true/>
+                <key>NSExceptionMinimumTLSVersion</key>
+                <string>TLSv1.0</string>
+            </dict>
+        </dict>
+    </dict>
+</dict>
+</plist>
+"#;
+        fs::write(app_dir.join("Info.plist"), info).unwrap();
+        fs::write(app_dir.join("Shop.entitlements"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>aps-environment</key>
+    <string>production</string>
+    <key>com.apple.developer.associated-domains</key>
+    <array><string>applinks:shop.acme.com</string></array>
+    <key>get-task-allow</key>
+    <true/>
+</dict>
+</plist>
+"#).unwrap();
+        assert!(!is_ios_file(&app_dir.join("Shop.entitlements"), "shop.entitlements"));
+        assert!(!is_ios_file(Path::new("Pods/Alamofire/Info.plist"), "info.plist"));
+
+        let mut entity_map = HashMap::new();
+        let (entities, relationships, vulns) =
+            analyze_info_plist(info.as_bytes(), &app_dir.join("Info.plist"), "Shop/Info.plist", &mut entity_map).unwrap();
+
+        let app = &entities[0];
+        assert_eq!(app.name, "com.acme.shop");
+        assert_eq!(app.configuration["url_schemes"], json!(["acmeshop"]));
+        assert_eq!(app.configuration["background_modes"], json!(["remote-notification", "location"]));
+        assert_eq!(app.configuration["usage_descriptions"], json!({"NSCameraUsageDescription": "Scan receipts"}));
+        assert_eq!(app.line_number, Some(5));
+        let entitlements = &entities[1];
+        assert_eq!(entitlements.configuration["associated_domains"], json!(["applinks:shop.acme.com"]));
+        assert_eq!(entitlements.file_path, "Shop/Shop.entitlements");
+        assert_eq!(relationships[0].relationship_type, "signed_with");
+
+        let kinds: Vec<(&str, Option<usize>)> = vulns.iter().map(|v| (v.vulnerability_type.as_str(), v.line_number)).collect();
+        assert_eq!(kinds, vec![
+            ("CleartextTrafficAllowed", Some(22)),
+            ("WeakTlsVersion", Some(22)),
+            ("CustomUrlScheme", Some(10)),
+            ("DebuggableReleaseBuild", Some(8)),
+        ]);
+        assert!(vulns[0].description.contains("legacy.acme.com and its subdomains"));
+    }
+}
@@ -9,11 +9,17 @@ pub mod terraform;
 pub mod cloudformation;
 pub mod serverless;
 pub mod firebase;
+pub mod android;
+pub mod ios;
 pub mod env_config;
 pub mod security_config;
 pub mod pattern_config;
 pub mod generic_provider;
+pub mod endpoint_auth;
+pub mod build_cache;
+pub mod payment_pci;
+pub mod pii_flow;
 
-pub use service_detector::{ServiceDetector, DetectedService, ServiceProvider, ServiceType};
+pub use service_detector::{ServiceDetector, DetectedService, EvidenceKind, ServiceEvidence, ServiceProvider, ServiceType};
 pub use types::{SecurityEntity, SecurityEntityType, SecurityRelationship, SecurityVulnerability, VulnerabilitySeverity};
 
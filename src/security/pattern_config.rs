@@ -10,13 +10,20 @@ pub struct PatternConfig {
     pub patterns: PatternSet,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Every list may be omitted, so plugin manifests only spell out the patterns they add
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PatternSet {
+    #[serde(default)]
     pub environment_variables: Vec<PatternRule>,
+    #[serde(default)]
     pub sdk_patterns: Vec<PatternRule>,
+    #[serde(default)]
     pub api_endpoints: Vec<PatternRule>,
+    #[serde(default)]
     pub database_patterns: Vec<PatternRule>,
+    #[serde(default)]
     pub aws_infrastructure: Vec<AwsInfrastructureRule>,
+    #[serde(default)]
     pub aws_sdk_v2_services: Vec<AwsSdkV2Rule>,
     #[serde(default)]
     pub aws_sdk_v3_service_map: HashMap<String, String>,
@@ -74,59 +81,50 @@ impl PatternLoader {
     /// Load patterns from multiple files (for plugin system)
     /// Returns the config and a list of loaded plugin names
     pub fn load_with_plugins(base_path: &Path, plugin_dir: Option<&Path>) -> Result<(PatternConfig, Vec<String>)> {
+        match plugin_dir {
+            Some(plugin_path) if plugin_path.exists() && plugin_path.is_dir() => {
+                log::info!("Loading plugins from: {}", plugin_path.display());
+                Self::load_with_registry(base_path, &crate::plugins::PluginRegistry::load(plugin_path))
+            }
+            Some(plugin_path) => {
+                log::debug!("Plugin directory does not exist: {}", plugin_path.display());
+                Ok((Self::load_from_file(base_path)?, Vec::new()))
+            }
+            None => Ok((Self::load_from_file(base_path)?, Vec::new())),
+        }
+    }
+
+    /// Merge the patterns of plugins declaring the patterns hook (manifests and legacy pattern
+    /// files) from an already loaded registry
+    pub fn load_with_registry(base_path: &Path, registry: &crate::plugins::PluginRegistry) -> Result<(PatternConfig, Vec<String>)> {
         let mut config = Self::load_from_file(base_path)?;
         let mut loaded_plugins = Vec::new();
 
-        // Load custom pattern files from plugin directory
-        if let Some(plugin_path) = plugin_dir {
-            if plugin_path.exists() && plugin_path.is_dir() {
-                log::info!("Loading plugins from: {}", plugin_path.display());
-                for entry in fs::read_dir(plugin_path)? {
-                    let entry = entry?;
-                    let path = entry.path();
-                    if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("json") {
-                        let plugin_name = path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-                        
-                        match Self::load_from_file(&path) {
-                            Ok(plugin_config) => {
-                                // Merge plugin patterns into base config
-                                let env_vars_count = plugin_config.patterns.environment_variables.len();
-                                let sdk_patterns_count = plugin_config.patterns.sdk_patterns.len();
-                                let api_endpoints_count = plugin_config.patterns.api_endpoints.len();
-                                
-                                config.patterns.environment_variables.extend(plugin_config.patterns.environment_variables);
-                                config.patterns.sdk_patterns.extend(plugin_config.patterns.sdk_patterns);
-                                config.patterns.api_endpoints.extend(plugin_config.patterns.api_endpoints);
-                                config.patterns.database_patterns.extend(plugin_config.patterns.database_patterns);
-                                config.patterns.aws_infrastructure.extend(plugin_config.patterns.aws_infrastructure);
-                                config.patterns.aws_sdk_v2_services.extend(plugin_config.patterns.aws_sdk_v2_services);
-                                // Merge service maps
-                                for (k, v) in plugin_config.patterns.aws_sdk_v3_service_map {
-                                    config.patterns.aws_sdk_v3_service_map.insert(k, v);
-                                }
-                                
-                                loaded_plugins.push(plugin_name.clone());
-                                log::info!("  ✓ Loaded plugin: {} ({} env vars, {} SDK patterns, {} API endpoints)", 
-                                    plugin_name, env_vars_count, sdk_patterns_count, api_endpoints_count);
-                            }
-                            Err(e) => {
-                                log::warn!("  ⚠ Failed to load plugin {}: {}", path.display(), e);
-                            }
-                        }
-                    }
-                }
-                
-                if loaded_plugins.is_empty() {
-                    log::info!("  No plugins found in {}", plugin_path.display());
-                } else {
-                    log::info!("✓ Loaded {} plugin(s): {}", loaded_plugins.len(), loaded_plugins.join(", "));
-                }
-            } else {
-                log::debug!("Plugin directory does not exist: {}", plugin_path.display());
+        for (plugin_name, plugin_config) in registry.pattern_contributions() {
+            let env_vars_count = plugin_config.patterns.environment_variables.len();
+            let sdk_patterns_count = plugin_config.patterns.sdk_patterns.len();
+            let api_endpoints_count = plugin_config.patterns.api_endpoints.len();
+
+            config.patterns.environment_variables.extend(plugin_config.patterns.environment_variables);
+            config.patterns.sdk_patterns.extend(plugin_config.patterns.sdk_patterns);
+            config.patterns.api_endpoints.extend(plugin_config.patterns.api_endpoints);
+            config.patterns.database_patterns.extend(plugin_config.patterns.database_patterns);
+            config.patterns.aws_infrastructure.extend(plugin_config.patterns.aws_infrastructure);
+            config.patterns.aws_sdk_v2_services.extend(plugin_config.patterns.aws_sdk_v2_services);
+            // Merge service maps
+            for (k, v) in plugin_config.patterns.aws_sdk_v3_service_map {
+                config.patterns.aws_sdk_v3_service_map.insert(k, v);
             }
+
+            log::info!("  ✓ Loaded plugin: {} ({} env vars, {} SDK patterns, {} API endpoints)",
+                plugin_name, env_vars_count, sdk_patterns_count, api_endpoints_count);
+            loaded_plugins.push(plugin_name);
+        }
+
+        if loaded_plugins.is_empty() {
+            log::info!("  No pattern plugins enabled");
+        } else {
+            log::info!("✓ Loaded {} plugin(s): {}", loaded_plugins.len(), loaded_plugins.join(", "));
         }
 
         Ok((config, loaded_plugins))
@@ -0,0 +1,57 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::analysis::{PciFinding, PciFindingKind};
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityVulnerability, VulnerabilitySeverity};
+
+/// Vulnerability types raised here, which the payments view lists alongside the payment flows
+pub const PCI_VULNERABILITY_TYPES: &[&str] = &["CardDataLogged", "RawCardData", "UnverifiedPaymentWebhook"];
+
+/// Turns PCI findings from payment detection into a payment handler entity and a vulnerability each.
+/// Logged card data is critical since logs are rarely inside the cardholder data environment;
+/// raw card fields and unsigned webhooks are high.
+pub fn analyze_pci_findings(findings: &[PciFinding]) -> (Vec<SecurityEntity>, Vec<SecurityVulnerability>) {
+    let mut entities = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    for finding in findings {
+        let id = format!("{}:payment:{}", finding.file_path, Uuid::new_v4());
+        let provider = finding.provider.clone().unwrap_or_else(|| "pci".to_string());
+        let mut config = HashMap::new();
+        config.insert("evidence".to_string(), Value::String(finding.evidence.clone()));
+        if let Some(handler) = &finding.handler {
+            config.insert("handler".to_string(), Value::String(handler.clone()));
+        }
+
+        entities.push(SecurityEntity {
+            id: id.clone(),
+            entity_type: SecurityEntityType::PaymentHandler,
+            name: finding.handler.clone().unwrap_or_else(|| finding.file_path.clone()),
+            provider: provider.clone(),
+            configuration: config,
+            file_path: finding.file_path.clone(),
+            line_number: finding.line_number,
+            arn: None,
+            region: None,
+        });
+
+        let (vulnerability_type, severity, description, recommendation) = match finding.kind {
+            PciFindingKind::CardDataLogged => ("CardDataLogged", VulnerabilitySeverity::Critical, templates::DESC_CARD_DATA_LOGGED.to_string(), templates::REC_CARD_DATA_LOGGED),
+            PciFindingKind::RawCardData => ("RawCardData", VulnerabilitySeverity::High, templates::DESC_RAW_CARD_DATA.to_string(), templates::REC_RAW_CARD_DATA),
+            PciFindingKind::UnverifiedWebhook => ("UnverifiedPaymentWebhook", VulnerabilitySeverity::High, format!("{} {}", provider, templates::DESC_UNVERIFIED_PAYMENT_WEBHOOK), templates::REC_UNVERIFIED_PAYMENT_WEBHOOK),
+        };
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:1", id),
+            entity_id: id,
+            vulnerability_type: vulnerability_type.to_string(),
+            severity,
+            description: format!("{}: {}", description, finding.evidence),
+            recommendation: recommendation.to_string(),
+            file_path: finding.file_path.clone(),
+            line_number: finding.line_number,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+    (entities, vulnerabilities)
+}
@@ -0,0 +1,59 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::analysis::{DetectedPiiFlow, PiiCategory, PiiSink};
+use crate::security::templates;
+use crate::security::types::{SecurityEntity, SecurityEntityType, SecurityVulnerability, VulnerabilitySeverity};
+
+/// Vulnerability types raised here, which the privacy view lists alongside the PII fields and flows
+pub const PII_VULNERABILITY_TYPES: &[&str] = &["PiiLogged", "PiiSentToAnalytics", "PiiSentToThirdParty"];
+
+/// Turns personal data flows into a personal data entity and a vulnerability each. Analytics
+/// and error tracking SDKs are medium since they copy the data to a processor that needs a
+/// GDPR basis; logs and external calls are low as they are often intended. National IDs and
+/// dates of birth raise either one to high.
+pub fn analyze_pii_flows(flows: &[DetectedPiiFlow]) -> (Vec<SecurityEntity>, Vec<SecurityVulnerability>) {
+    let mut entities = Vec::new();
+    let mut vulnerabilities = Vec::new();
+    for flow in flows {
+        let id = format!("{}:pii:{}", flow.file_path, Uuid::new_v4());
+        let category = format!("{:?}", flow.category);
+        let mut config = HashMap::new();
+        config.insert("category".to_string(), Value::String(category.clone()));
+        config.insert("field".to_string(), Value::String(flow.field.clone()));
+        config.insert("destination".to_string(), Value::String(flow.destination.clone()));
+        config.insert("evidence".to_string(), Value::String(flow.evidence.clone()));
+
+        entities.push(SecurityEntity {
+            id: id.clone(),
+            entity_type: SecurityEntityType::PersonalData,
+            name: flow.field.clone(),
+            provider: flow.destination.clone(),
+            configuration: config,
+            file_path: flow.file_path.clone(),
+            line_number: flow.line_number,
+            arn: None,
+            region: None,
+        });
+
+        let sensitive = matches!(flow.category, PiiCategory::NationalId | PiiCategory::DateOfBirth);
+        let (vulnerability_type, severity, description, recommendation) = match flow.sink {
+            PiiSink::Logging => ("PiiLogged", VulnerabilitySeverity::Low, templates::DESC_PII_LOGGED, templates::REC_PII_LOGGED),
+            PiiSink::Analytics => ("PiiSentToAnalytics", VulnerabilitySeverity::Medium, templates::DESC_PII_ANALYTICS, templates::REC_PII_ANALYTICS),
+            PiiSink::ThirdParty => ("PiiSentToThirdParty", VulnerabilitySeverity::Low, templates::DESC_PII_THIRD_PARTY, templates::REC_PII_THIRD_PARTY),
+        };
+        vulnerabilities.push(SecurityVulnerability {
+            id: format!("{}:vuln:1", id),
+            entity_id: id,
+            vulnerability_type: vulnerability_type.to_string(),
+            severity: if sensitive { VulnerabilitySeverity::High } else { severity },
+            description: format!("{} ({}) {} {}: {}", flow.field, category, description, flow.destination, flow.evidence),
+            recommendation: recommendation.to_string(),
+            file_path: flow.file_path.clone(),
+            line_number: flow.line_number,
+            affected_range: None,
+            fixed_version: None,
+        });
+    }
+    (entities, vulnerabilities)
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use crate::analysis::RepoPath;
 use crate::ingestion::FileType;
 use crate::security::pattern_config::{PatternConfig, PatternLoader};
 use crate::security::generic_provider::GenericProviderDetector;
@@ -79,6 +80,7 @@ pub enum ServiceProvider {
     
     // Other
     WebMethods,
+    Salesforce,
     Unknown,
 }
 
@@ -91,8 +93,47 @@ pub struct DetectedService {
     pub file_path: String,
     pub line_number: Option<usize>,
     pub confidence: f64, // 0.0 to 1.0
+    /// Every detection the service was aggregated from; `confidence` is calibrated from these
+    #[serde(default)]
+    pub evidence: Vec<ServiceEvidence>,
 }
 
+/// Where a detection came from. Evidence of different kinds corroborates a service (an environment
+/// variable, an SDK import and an IaC resource are independent signals), while the same kind of
+/// evidence repeated across files does not
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    /// A package manifest dependency
+    Package,
+    EnvVar,
+    ConnectionString,
+    /// An SDK import or client construction in code
+    Sdk,
+    /// A call to the provider's API host in code
+    ApiEndpoint,
+    /// Terraform, CloudFormation or hosting configuration
+    Infrastructure,
+    /// Platform metadata such as Salesforce named credentials
+    Metadata,
+    Plugin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceEvidence {
+    pub kind: EvidenceKind,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub confidence: f64,
+}
+
+/// Package detections from the name heuristic alone (a dependency that merely looks like a client
+/// package) are dropped unless other evidence backs them
+const UNCORROBORATED_PACKAGE_CONFIDENCE: f64 = 0.5;
+
+/// Aggregated confidence never reaches certainty
+const MAX_AGGREGATED_CONFIDENCE: f64 = 0.99;
+
 pub struct ServiceDetector {
     pattern_config: PatternConfig,
     generic_detector: GenericProviderDetector,
@@ -132,6 +173,17 @@ impl ServiceDetector {
         }
     }
 
+    /// Create with the pattern plugins of an already loaded (and possibly per-repository) registry
+    pub fn with_registry(registry: &crate::plugins::PluginRegistry) -> Result<Self> {
+        let base_path = Path::new("config/service_patterns.json");
+        let (pattern_config, _plugins) = PatternLoader::load_with_registry(base_path, registry)?;
+
+        Ok(ServiceDetector {
+            pattern_config,
+            generic_detector: GenericProviderDetector::new(),
+        })
+    }
+
     /// Create with plugin directory support
     pub fn with_plugins(plugin_dir: Option<&Path>) -> Result<Self> {
         let base_path = Path::new("config/service_patterns.json");
@@ -149,13 +201,14 @@ impl ServiceDetector {
         
         // First, detect from generic package files
         if let Ok(generic_providers) = self.generic_detector.detect_from_packages(repo_path) {
+            let mut packages = Vec::new();
             for gp in generic_providers {
                 if let Some(provider) = self.parse_provider(&gp.provider_hint.as_deref().unwrap_or("Unknown")) {
                     let service_type = self.parse_service_type(
                         gp.service_type_hint.as_deref().unwrap_or("Other")
                     );
                     
-                    services.push(DetectedService {
+                    packages.push(DetectedService {
                         provider,
                         service_type,
                         name: gp.name.clone(),
@@ -165,12 +218,14 @@ impl ServiceDetector {
                             config.insert("detection_method".to_string(), "generic_package".to_string());
                             config
                         },
-                        file_path: format!("{}/{}", repo_path.display(), gp.source),
+                        file_path: RepoPath::from_relative(&gp.source).into_string(),
                         line_number: None,
                         confidence: gp.confidence,
+                        evidence: Vec::new(),
                     });
                 }
             }
+            services.extend(tagged(packages, EvidenceKind::Package));
         }
         
         // Use direct directory walking instead of FileIndexer
@@ -181,118 +236,156 @@ impl ServiceDetector {
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_file())
         {
-            let path = entry.path();
-            let file_name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("")
-                .to_lowercase();
-            
-            // Skip hidden files and common ignore patterns
-            if Self::should_skip_path(path, &file_name) {
-                continue;
-            }
-            
-            // Determine file type
-            let file_type = if file_name.contains("terraform") || 
-                             file_name.contains("cloudformation") ||
-                             file_name.ends_with(".tf") ||
-                             file_name.ends_with(".tfvars") {
-                FileType::Infrastructure
-            } else if file_name.ends_with(".json") ||
-                      file_name.ends_with(".yaml") ||
-                      file_name.ends_with(".yml") ||
-                      file_name.ends_with(".toml") ||
-                      file_name.ends_with(".env") ||
-                      file_name.contains("config") {
-                FileType::Config
-            } else if file_name.ends_with(".js") ||
-                      file_name.ends_with(".ts") ||
-                      file_name.ends_with(".jsx") ||
-                      file_name.ends_with(".tsx") ||
-                      file_name.ends_with(".py") ||
-                      file_name.ends_with(".rs") ||
-                      file_name.ends_with(".go") ||
-                      file_name.ends_with(".swift") ||
-                      file_name.ends_with(".m") ||
-                      file_name.ends_with(".mm") ||
-                      file_name.ends_with(".java") {
-                FileType::Code
-            } else {
-                continue;
-            };
-            
-            // Detect services based on file type
-            match file_type {
-                FileType::Config | FileType::Infrastructure => {
-                    if let Ok(detected) = self.detect_in_file(path, &file_type) {
-                        services.extend(detected);
-                    }
-                }
-                FileType::Code => {
-                    let language = path.extension()
-                        .and_then(|e| e.to_str())
-                        .map(|ext| {
-                            // Normalize extension to language name for comment detection
-                            match ext.to_lowercase().as_str() {
-                                "js" | "jsx" => "javascript",
-                                "ts" | "tsx" => "typescript",
-                                "py" => "python",
-                                "rs" => "rust",
-                                "go" => "go",
-                                "swift" => "swift",
-                                "m" | "mm" => "objective-c",
-                                "java" => "java",
-                                _ => ext, // Keep original if unknown
-                            }
-                        })
-                        .map(|s| s.to_string());
-                    if let Ok(detected) = self.detect_in_code(path, &language) {
-                        services.extend(detected);
-                    }
-                }
-                _ => {}
+            if let Ok(scan) = self.scan_file(entry.path()) {
+                services.extend(scan.services);
             }
         }
         
-        // Deduplicate services: combine identical services (same name, provider, type)
-        // Keep the one with highest confidence and merge file paths
-        let mut deduplicated: Vec<DetectedService> = Vec::new();
-        let mut seen: HashMap<(String, ServiceProvider, ServiceType), usize> = HashMap::new();
+        Ok(Self::aggregate(repo_path, services))
+    }
+
+    /// What the repository walk of `detect_services` does with one file: which detection it runs
+    /// on it and what that finds, or why it leaves the file alone
+    pub(crate) fn scan_file(&self, path: &Path) -> Result<FileScan, &'static str> {
+        let file_name = path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_lowercase();
         
-        for service in services {
-            let key = (service.name.clone(), service.provider.clone(), service.service_type.clone());
-            
-            if let Some(&existing_idx) = seen.get(&key) {
-                // Service already exists, merge if this one has higher confidence
-                let existing = &mut deduplicated[existing_idx];
-                if service.confidence > existing.confidence {
-                    existing.confidence = service.confidence;
-                    existing.line_number = service.line_number;
-                }
-                // Merge file paths in configuration
-                let file_paths = existing.configuration
-                    .get("file_paths")
-                    .map(|s| s.clone())
-                    .unwrap_or_else(|| existing.file_path.clone());
-                if !file_paths.contains(&service.file_path) {
-                    existing.configuration.insert(
-                        "file_paths".to_string(),
-                        format!("{}, {}", file_paths, service.file_path)
-                    );
+        // Skip hidden files and common ignore patterns
+        if let Some(reason) = Self::skip_reason(path, &file_name) {
+            return Err(reason);
+        }
+        
+        // Salesforce project and callout metadata, which the file types below do not cover
+        if file_name == "sfdx-project.json"
+            || file_name.ends_with(".namedcredential-meta.xml")
+            || file_name.ends_with(".remotesite-meta.xml") {
+            let services = self.detect_salesforce_metadata(path, &file_name).unwrap_or_default();
+            return Ok(FileScan { scanned_as: "salesforce metadata", services: tagged(services, EvidenceKind::Metadata) });
+        }
+
+        // Determine file type
+        let file_type = if file_name.contains("terraform") || 
+                         file_name.contains("cloudformation") ||
+                         file_name.ends_with(".tf") ||
+                         file_name.ends_with(".tfvars") {
+            FileType::Infrastructure
+        } else if file_name.ends_with(".json") ||
+                  file_name.ends_with(".yaml") ||
+                  file_name.ends_with(".yml") ||
+                  file_name.ends_with(".toml") ||
+                  file_name.ends_with(".env") ||
+                  file_name.contains("config") {
+            FileType::Config
+        } else if file_name.ends_with(".js") ||
+                  file_name.ends_with(".ts") ||
+                  file_name.ends_with(".jsx") ||
+                  file_name.ends_with(".tsx") ||
+                  file_name.ends_with(".py") ||
+                  file_name.ends_with(".rs") ||
+                  file_name.ends_with(".go") ||
+                  file_name.ends_with(".swift") ||
+                  file_name.ends_with(".m") ||
+                  file_name.ends_with(".mm") ||
+                  file_name.ends_with(".java") ||
+                  file_name.ends_with(".cls") ||
+                  file_name.ends_with(".trigger") {
+            FileType::Code
+        } else {
+            return Err("not a config, infrastructure or source file");
+        };
+        
+        // Detect services based on file type
+        match file_type {
+            FileType::Infrastructure => Ok(FileScan {
+                scanned_as: "infrastructure",
+                services: self.detect_in_file(path, &file_type).unwrap_or_default(),
+            }),
+            FileType::Config => Ok(FileScan {
+                scanned_as: "config",
+                services: self.detect_in_file(path, &file_type).unwrap_or_default(),
+            }),
+            _ => {
+                let language = path.extension()
+                    .and_then(|e| e.to_str())
+                    .map(|ext| {
+                        // Normalize extension to language name for comment detection
+                        match ext.to_lowercase().as_str() {
+                            "js" | "jsx" => "javascript",
+                            "ts" | "tsx" => "typescript",
+                            "py" => "python",
+                            "rs" => "rust",
+                            "go" => "go",
+                            "swift" => "swift",
+                            "m" | "mm" => "objective-c",
+                            "java" => "java",
+                            "cls" | "trigger" => "apex",
+                            _ => ext, // Keep original if unknown
+                        }
+                    })
+                    .map(|s| s.to_string());
+                Ok(FileScan {
+                    scanned_as: "code",
+                    services: self.detect_in_code(path, &language).unwrap_or_default(),
+                })
+            }
+        }
+    }
+
+    /// The patterns detection runs with, plugin patterns included
+    pub(crate) fn pattern_config(&self) -> &PatternConfig {
+        &self.pattern_config
+    }
+
+    /// Merge the detections of each service into one record that lists all of its evidence.
+    /// Detections are the same service when provider and type match and the names agree once
+    /// case, punctuation and suffixes like "SDK" or "API" are ignored; a detection backed only by
+    /// packages folds into the one other service of its provider. Running it again over already
+    /// aggregated services (with plugin services added, say) gives the same records
+    pub fn aggregate(repo_path: &Path, services: Vec<DetectedService>) -> Vec<DetectedService> {
+        let mut aggregated: Vec<DetectedService> = Vec::new();
+        let mut seen: HashMap<(String, ServiceProvider, ServiceType), usize> = HashMap::new();
+
+        for mut service in services {
+            RepoPath::normalize(repo_path, &mut service.file_path);
+            for evidence in &mut service.evidence {
+                RepoPath::normalize(repo_path, &mut evidence.file_path);
+            }
+            let key = (normalized_name(&service.name), service.provider.clone(), service.service_type.clone());
+            match seen.get(&key) {
+                Some(&idx) => merge(&mut aggregated[idx], service),
+                None => {
+                    seen.insert(key, aggregated.len());
+                    aggregated.push(service);
                 }
-            } else {
-                // New service, add it
-                let idx = deduplicated.len();
-                seen.insert(key, idx);
-                deduplicated.push(service);
             }
         }
-        
-        Ok(deduplicated)
+
+        // A client package is usually the same service its SDK import or environment variables
+        // point at, whose name rarely matches the package's
+        let (packages, mut aggregated): (Vec<_>, Vec<_>) = aggregated.into_iter()
+            .partition(|s| packages_only(s) && s.provider != ServiceProvider::Unknown);
+        for package in packages {
+            let mut candidates = aggregated.iter()
+                .enumerate()
+                .filter(|(_, s)| s.provider == package.provider && !packages_only(s))
+                .map(|(idx, _)| idx);
+            match (candidates.next(), candidates.next()) {
+                (Some(idx), None) => merge(&mut aggregated[idx], package),
+                _ => aggregated.push(package),
+            }
+        }
+
+        for service in &mut aggregated {
+            calibrate(service);
+        }
+        aggregated.retain(|s| !(packages_only(s) && s.confidence < UNCORROBORATED_PACKAGE_CONFIDENCE));
+        aggregated
     }
 
     /// Parse provider string to ServiceProvider enum
-    fn parse_provider(&self, provider_str: &str) -> Option<ServiceProvider> {
+    pub(crate) fn parse_provider(&self, provider_str: &str) -> Option<ServiceProvider> {
         match provider_str.to_uppercase().as_str() {
             "AWS" => Some(ServiceProvider::Aws),
             "AZURE" => Some(ServiceProvider::Azure),
@@ -336,12 +429,13 @@ impl ServiceDetector {
             "MISTRALAI" => Some(ServiceProvider::MistralAI),
             "PERPLEXITY" => Some(ServiceProvider::Perplexity),
             "WEBMETHODS" | "SOFTWAREAG" => Some(ServiceProvider::WebMethods),
+            "SALESFORCE" | "SFDC" => Some(ServiceProvider::Salesforce),
             _ => Some(ServiceProvider::Unknown),
         }
     }
 
     /// Parse service type string to ServiceType enum
-    fn parse_service_type(&self, type_str: &str) -> ServiceType {
+    pub(crate) fn parse_service_type(&self, type_str: &str) -> ServiceType {
         match type_str {
             "CloudProvider" => ServiceType::CloudProvider,
             "SaaS" => ServiceType::SaaS,
@@ -368,11 +462,11 @@ impl ServiceDetector {
         match file_type {
             FileType::Infrastructure => {
                 // Detect AWS services in Terraform/CloudFormation
-                services.extend(self.detect_aws_services(&content, file_path)?);
+                let mut detected = self.detect_aws_services(&content, file_path)?;
                 
                 // Detect Vercel/Netlify in config files
                 if file_name.contains("vercel") {
-                    services.push(DetectedService {
+                    detected.push(DetectedService {
                         provider: ServiceProvider::Vercel,
                         service_type: ServiceType::CloudProvider,
                         name: "Vercel".to_string(),
@@ -380,11 +474,12 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: None,
                         confidence: 0.9,
+                        evidence: Vec::new(),
                     });
                 }
                 
                 if file_name.contains("netlify") {
-                    services.push(DetectedService {
+                    detected.push(DetectedService {
                         provider: ServiceProvider::Netlify,
                         service_type: ServiceType::CloudProvider,
                         name: "Netlify".to_string(),
@@ -392,15 +487,17 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: None,
                         confidence: 0.9,
+                        evidence: Vec::new(),
                     });
                 }
+                services.extend(tagged(detected, EvidenceKind::Infrastructure));
             }
             FileType::Config => {
                 // Detect services in environment/config files
-                services.extend(self.detect_from_env_vars(&content, file_path)?);
+                services.extend(tagged(self.detect_from_env_vars(&content, file_path)?, EvidenceKind::EnvVar));
                 
                 // Detect database connections
-                services.extend(self.detect_databases(&content, file_path)?);
+                services.extend(tagged(self.detect_databases(&content, file_path)?, EvidenceKind::ConnectionString));
             }
             _ => {}
         }
@@ -414,10 +511,10 @@ impl ServiceDetector {
         let content = std::fs::read_to_string(file_path)?;
         
         // Detect service SDKs and API keys
-        services.extend(self.detect_service_sdks(&content, file_path, language.as_deref())?);
+        services.extend(tagged(self.detect_service_sdks(&content, file_path, language.as_deref())?, EvidenceKind::Sdk));
         
         // Detect API endpoints
-        services.extend(self.detect_api_endpoints(&content, file_path)?);
+        services.extend(tagged(self.detect_api_endpoints(&content, file_path)?, EvidenceKind::ApiEndpoint));
         
         Ok(services)
     }
@@ -444,6 +541,7 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: self.find_line_number(content, &rule.pattern),
                         confidence: rule.confidence,
+                        evidence: Vec::new(),
                     });
                 }
             }
@@ -459,6 +557,7 @@ impl ServiceDetector {
                 file_path: file_path.to_string_lossy().to_string(),
                 line_number: None,
                 confidence: 0.9,
+                evidence: Vec::new(),
             });
         }
         
@@ -525,6 +624,7 @@ impl ServiceDetector {
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: self.find_line_number(content, line),
                             confidence: rule.confidence,
+                            evidence: Vec::new(),
                         });
                         
                         // Only match one pattern per line (prioritize first match)
@@ -559,6 +659,7 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: self.find_line_number(content, &rule.pattern),
                         confidence: rule.confidence,
+                        evidence: Vec::new(),
                     });
                 }
             }
@@ -608,6 +709,7 @@ impl ServiceDetector {
                     file_path: file_path.to_string_lossy().to_string(),
                     line_number: self.find_line_number(content, &format!("@aws-sdk/client-{}", service)),
                     confidence: 0.9,
+                    evidence: Vec::new(),
                 });
             }
         }
@@ -640,6 +742,7 @@ impl ServiceDetector {
                             file_path: file_path.to_string_lossy().to_string(),
                             line_number: self.find_line_number(content, service_pattern),
                             confidence: rule.confidence,
+                            evidence: Vec::new(),
                         });
                         break; // Only add once per service per file
                     }
@@ -661,6 +764,7 @@ impl ServiceDetector {
                     file_path: file_path.to_string_lossy().to_string(),
                     line_number: self.find_line_number(content, "aws-sdk"),
                     confidence: 0.7,
+                    evidence: Vec::new(),
                 });
             }
         }
@@ -805,6 +909,7 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: self.find_line_number(content, &rule.pattern),
                         confidence: rule.confidence,
+                        evidence: Vec::new(),
                     });
                 }
             }
@@ -854,6 +959,7 @@ impl ServiceDetector {
                         file_path: file_path.to_string_lossy().to_string(),
                         line_number: self.find_line_number(content, &rule.pattern),
                         confidence: rule.confidence,
+                        evidence: Vec::new(),
                     });
                 }
             }
@@ -862,6 +968,64 @@ impl ServiceDetector {
         Ok(services)
     }
 
+    /// The org an sfdx project deploys to, and the endpoints its named credentials and remote site
+    /// settings let Apex call out to; an endpoint's provider comes from the API endpoint patterns
+    fn detect_salesforce_metadata(&self, file_path: &Path, file_name: &str) -> Result<Vec<DetectedService>> {
+        let content = std::fs::read_to_string(file_path)?;
+        if file_name == "sfdx-project.json" {
+            let mut configuration = HashMap::new();
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+                for (key, field) in [("api_version", "sourceApiVersion"), ("login_url", "sfdcLoginUrl")] {
+                    if let Some(value) = json.get(field).and_then(|v| v.as_str()) {
+                        configuration.insert(key.to_string(), value.to_string());
+                    }
+                }
+            }
+            return Ok(vec![DetectedService {
+                provider: ServiceProvider::Salesforce,
+                service_type: ServiceType::SaaS,
+                name: "Salesforce Platform".to_string(),
+                configuration,
+                file_path: file_path.to_string_lossy().to_string(),
+                line_number: None,
+                confidence: 0.95,
+                evidence: Vec::new(),
+            }]);
+        }
+
+        // Legacy named credentials and remote sites carry the URL in `<endpoint>`/`<url>`, newer
+        // named credentials as a `Url` parameter
+        let value = |tag: &str| -> Option<String> {
+            let start = content.find(&format!("<{}>", tag))? + tag.len() + 2;
+            let end = content[start..].find(&format!("</{}>", tag))? + start;
+            Some(content[start..end].trim().to_string())
+        };
+        let Some(url) = value("endpoint").or_else(|| value("url"))
+            .or_else(|| value("parameterValue").filter(|v| v.starts_with("http"))) else {
+            return Ok(Vec::new());
+        };
+        let name = file_path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.split('.').next())
+            .unwrap_or(file_name)
+            .to_string();
+        let rule = self.pattern_config.patterns.api_endpoints.iter().find(|r| url.contains(&r.pattern));
+        let mut configuration = HashMap::from([("endpoint".to_string(), url.clone())]);
+        let kind = if file_name.ends_with(".namedcredential-meta.xml") { "named_credential" } else { "remote_site" };
+        configuration.insert(kind.to_string(), name.clone());
+
+        Ok(vec![DetectedService {
+            provider: rule.and_then(|r| self.parse_provider(&r.provider)).unwrap_or(ServiceProvider::Unknown),
+            service_type: rule.map(|r| self.parse_service_type(&r.service_type)).unwrap_or(ServiceType::Api),
+            name,
+            configuration,
+            file_path: file_path.to_string_lossy().to_string(),
+            line_number: self.find_line_number(&content, &url),
+            confidence: 0.9,
+            evidence: Vec::new(),
+        }])
+    }
+
     /// Extract Vercel configuration
     fn extract_vercel_config(&self, content: &str) -> HashMap<String, String> {
         let mut config = HashMap::new();
@@ -1119,16 +1283,16 @@ impl ServiceDetector {
     /// Check if a file path should be skipped during analysis
     /// 
     /// Skips common build artifacts, dependencies, and virtual environments
-    fn should_skip_path(path: &Path, file_name: &str) -> bool {
+    fn skip_reason(path: &Path, file_name: &str) -> Option<&'static str> {
         // Skip hidden files
         if file_name.starts_with('.') {
-            return true;
+            return Some("hidden file");
         }
         
         let path_str = path.to_string_lossy().to_lowercase();
         
         // Skip common dependency and build directories
-        path_str.contains("node_modules") ||
+        let skipped = path_str.contains("node_modules") ||
         path_str.contains("target") ||
         path_str.contains(".git") ||
         // Skip Python virtual environments
@@ -1138,7 +1302,8 @@ impl ServiceDetector {
         path_str.contains("\\venv\\") ||
         path_str.contains("site-packages") ||
         path_str.contains(".venv/") ||
-        path_str.contains(".venv\\")
+        path_str.contains(".venv\\");
+        skipped.then_some("path contains a dependency, build or VCS directory name (node_modules, target, .git, venv, site-packages)")
     }
 
     /// Check if a pattern at a specific position is at a word boundary
@@ -1172,6 +1337,92 @@ impl ServiceDetector {
     }
 }
 
+/// A file `detect_services` looked at, with what it found there before aggregation
+pub(crate) struct FileScan {
+    pub scanned_as: &'static str,
+    pub services: Vec<DetectedService>,
+}
+
+/// Record each detection as one piece of evidence of `kind`
+fn tagged(mut services: Vec<DetectedService>, kind: EvidenceKind) -> Vec<DetectedService> {
+    for service in &mut services {
+        service.evidence = vec![ServiceEvidence {
+            kind,
+            file_path: service.file_path.clone(),
+            line_number: service.line_number,
+            confidence: service.confidence,
+        }];
+    }
+    services
+}
+
+/// The service name without case, punctuation or a trailing "service", "sdk", "api", "client" or
+/// "database", so "Stripe", "STRIPE Service" and "stripe-sdk" compare equal
+fn normalized_name(name: &str) -> String {
+    let mut normalized: String = name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    while let Some(stripped) = ["service", "sdk", "api", "client", "database"].iter()
+        .find_map(|suffix| normalized.strip_suffix(suffix).filter(|rest| !rest.is_empty()))
+    {
+        normalized = stripped.to_string();
+    }
+    normalized
+}
+
+fn packages_only(service: &DetectedService) -> bool {
+    !service.evidence.is_empty() && service.evidence.iter().all(|e| e.kind == EvidenceKind::Package)
+}
+
+/// Fold `other` into `existing`, keeping the existing name and configuration values
+fn merge(existing: &mut DetectedService, other: DetectedService) {
+    if existing.evidence.is_empty() && other.confidence > existing.confidence {
+        existing.confidence = other.confidence;
+        existing.line_number = other.line_number;
+    }
+    for (key, value) in other.configuration {
+        existing.configuration.entry(key).or_insert(value);
+    }
+    for evidence in other.evidence {
+        if !existing.evidence.iter().any(|e| e.kind == evidence.kind && e.file_path == evidence.file_path && e.line_number == evidence.line_number) {
+            existing.evidence.push(evidence);
+        }
+    }
+}
+
+/// Confidence that at least one kind of evidence is right: the strongest evidence of each kind
+/// counts as an independent signal, so corroboration raises it and repetition does not. The
+/// record points at its strongest evidence and lists every file it was seen in
+fn calibrate(service: &mut DetectedService) {
+    if service.evidence.is_empty() {
+        return;
+    }
+    let mut strongest: HashMap<EvidenceKind, f64> = HashMap::new();
+    for evidence in &service.evidence {
+        let confidence = strongest.entry(evidence.kind).or_insert(0.0);
+        *confidence = confidence.max(evidence.confidence);
+    }
+    let doubt: f64 = strongest.values().map(|c| 1.0 - c.clamp(0.0, 1.0)).product();
+    service.confidence = (((1.0 - doubt) * 1000.0).round() / 1000.0).min(MAX_AGGREGATED_CONFIDENCE);
+
+    let primary = service.evidence.iter()
+        .fold(&service.evidence[0], |best, e| if e.confidence > best.confidence { e } else { best });
+    service.file_path = primary.file_path.clone();
+    service.line_number = primary.line_number;
+
+    let mut files: Vec<&str> = Vec::new();
+    for evidence in &service.evidence {
+        if !files.contains(&evidence.file_path.as_str()) {
+            files.push(&evidence.file_path);
+        }
+    }
+    if files.len() > 1 {
+        let file_paths = files.join(", ");
+        service.configuration.insert("file_paths".to_string(), file_paths);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1486,5 +1737,56 @@ class CharsetMatch:
         
         assert!(real_detections > 0, "Should detect Cohere from real application code");
     }
-}
 
+    #[test]
+    fn test_salesforce_project_and_named_credentials() {
+        let temp_dir = TempDir::new().unwrap();
+        let metadata = temp_dir.path().join("force-app/main/default/namedCredentials");
+        fs::create_dir_all(&metadata).unwrap();
+        fs::write(temp_dir.path().join("sfdx-project.json"), r#"{"sourceApiVersion": "60.0"}"#).unwrap();
+        fs::write(metadata.join("OpenAI_API.namedCredential-meta.xml"), r#"<?xml version="1.0" encoding="UTF-8"?>
+<NamedCredential xmlns="http://soap.sforce.com/2006/04/metadata">
+    <endpoint>https://api.openai.com/v1</endpoint>
+    <label>OpenAI API</label>
+</NamedCredential>
+"#).unwrap();
+
+        let services = ServiceDetector::new().detect_services(temp_dir.path()).unwrap();
+        let platform = services.iter().find(|s| s.name == "Salesforce Platform").unwrap();
+        assert_eq!(platform.provider, ServiceProvider::Salesforce);
+        assert_eq!(platform.configuration.get("api_version").map(String::as_str), Some("60.0"));
+        let credential = services.iter().find(|s| s.name == "OpenAI_API").unwrap();
+        assert_eq!(credential.provider, ServiceProvider::OpenAI);
+        assert_eq!(credential.configuration.get("named_credential").map(String::as_str), Some("OpenAI_API"));
+        assert_eq!(credential.line_number, Some(3));
+    }
+
+    #[test]
+    fn test_evidence_aggregated_into_one_service() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{"dependencies": {"@stripe/stripe-js": "^2.0.0", "acme-api-client": "1.0.0"}}"#).unwrap();
+        fs::write(temp_dir.path().join("app.env"), "STRIPE_SECRET_KEY=sk_test_placeholder\n").unwrap();
+        fs::create_dir_all(temp_dir.path().join("deploy")).unwrap();
+        fs::write(temp_dir.path().join("deploy/production.env"), "STRIPE_SECRET_KEY=sk_live_placeholder\n").unwrap();
+        fs::write(temp_dir.path().join("billing.py"), "import stripe\n\nstripe.api_key = settings.STRIPE_KEY\n").unwrap();
+
+        let services = ServiceDetector::new().detect_services(temp_dir.path()).unwrap();
+        let stripe: Vec<_> = services.iter().filter(|s| s.provider == ServiceProvider::Stripe).collect();
+        assert_eq!(stripe.len(), 1, "{:?}", stripe);
+        let stripe = stripe[0];
+        let kinds: std::collections::BTreeSet<_> = stripe.evidence.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds.into_iter().collect::<Vec<_>>(), vec![EvidenceKind::Package, EvidenceKind::EnvVar, EvidenceKind::Sdk]);
+        assert_eq!(stripe.evidence.iter().filter(|e| e.kind == EvidenceKind::EnvVar).count(), 2);
+        // 1 - (1 - 0.6)(1 - 0.7)(1 - 0.8): the second environment file adds nothing
+        assert_eq!(stripe.confidence, 0.976);
+        assert_eq!(stripe.file_path, "billing.py");
+
+        // A package that only looks like a client library is not reported on its own
+        assert!(services.iter().all(|s| s.name != "acme-api-client"));
+
+        // Aggregating again leaves the records as they are
+        let again = ServiceDetector::aggregate(temp_dir.path(), services.clone());
+        assert_eq!(again.len(), services.len());
+        assert_eq!(again.iter().find(|s| s.provider == ServiceProvider::Stripe).unwrap().confidence, 0.976);
+    }
+}
@@ -30,6 +30,41 @@ pub const DESC_S3_UNENCRYPTED: &str = "S3 bucket does not have encryption enable
 pub const REC_S3_UNENCRYPTED: &str = "Enable server-side encryption for S3 bucket";
 pub const DESC_SECURITY_GROUP_OPEN: &str = "Security group allows access from anywhere (0.0.0.0/0)";
 pub const REC_SECURITY_GROUP_OPEN: &str = "Restrict security group rules to specific IP ranges";
+pub const DESC_UNAUTHENTICATED_ENDPOINT: &str = "runs no authentication middleware or guard, unlike other endpoints of the same framework";
+pub const REC_UNAUTHENTICATED_ENDPOINT: &str = "Add the authentication middleware or guard the other endpoints use, or mark the endpoint public explicitly";
+pub const DESC_CARD_DATA_LOGGED: &str = "Card number, CVV or track data is written to a log";
+pub const REC_CARD_DATA_LOGGED: &str = "Never log card data; log the provider token or the last four digits instead";
+pub const DESC_RAW_CARD_DATA: &str = "Raw card number or CVV is read from the request, which brings the server into PCI DSS scope";
+pub const REC_RAW_CARD_DATA: &str = "Collect card details with the provider's hosted fields or checkout and send only the resulting token";
+pub const DESC_UNVERIFIED_PAYMENT_WEBHOOK: &str = "webhook events are read from the request body without verifying the provider signature";
+pub const REC_UNVERIFIED_PAYMENT_WEBHOOK: &str = "Verify the webhook signature with the provider SDK before acting on the event";
+pub const DESC_PII_LOGGED: &str = "is written to";
+pub const REC_PII_LOGGED: &str = "Log a user ID instead, or mask the value before logging it";
+pub const DESC_PII_ANALYTICS: &str = "is sent to the analytics provider";
+pub const REC_PII_ANALYTICS: &str = "Send a pseudonymous user ID, or record the provider as a processor with a lawful basis for this data";
+pub const DESC_PII_THIRD_PARTY: &str = "is sent to the external host";
+pub const REC_PII_THIRD_PARTY: &str = "Confirm a data processing agreement covers this host and send only the fields it needs";
+pub const DESC_ANDROID_EXPORTED: &str = "can be started by any app on the device: it is exported and requires no permission";
+pub const REC_ANDROID_EXPORTED: &str = "Set android:exported=\"false\", or protect the component with a signature-level android:permission";
+pub const DESC_ANDROID_CLEARTEXT: &str = "allows cleartext HTTP traffic";
+pub const REC_ANDROID_CLEARTEXT: &str = "Use HTTPS and set cleartextTrafficPermitted=\"false\", limiting any exception to the domains that need it in the network security config";
+pub const DESC_ANDROID_DEBUGGABLE: &str = "is debuggable outside debug builds, so a debugger can attach to it and its data can be read with run-as";
+pub const REC_ANDROID_DEBUGGABLE: &str = "Remove android:debuggable from the manifest and only enable isDebuggable for the debug build type";
+pub const DESC_IOS_ATS_ARBITRARY: &str = "turns off App Transport Security, allowing cleartext HTTP and weak TLS to any host";
+pub const DESC_IOS_ATS_EXCEPTION: &str = "allows cleartext HTTP to";
+pub const REC_IOS_ATS: &str = "Serve the host over HTTPS and remove the App Transport Security exception, or limit it to the domains that still need it";
+pub const DESC_IOS_WEAK_TLS: &str = "accepts";
+pub const REC_IOS_WEAK_TLS: &str = "Upgrade the server to TLS 1.2 or later and remove NSExceptionMinimumTLSVersion";
+pub const DESC_IOS_URL_SCHEME: &str = "claims custom URL schemes, which any other app can also register and intercept";
+pub const REC_IOS_URL_SCHEME: &str = "Use universal links (associated domains) for links that carry tokens or user data, and validate every URL the app is opened with";
+pub const DESC_IOS_GET_TASK_ALLOW: &str = "sets get-task-allow, so a debugger can attach to the signed app";
+pub const REC_IOS_GET_TASK_ALLOW: &str = "Remove get-task-allow from the entitlements file; Xcode adds it to development builds when signing";
+pub const DESC_IOS_HARDENED_RUNTIME: &str = "turns off hardened runtime protections";
+pub const REC_IOS_HARDENED_RUNTIME: &str = "Remove the exceptions the app does not need; disable-library-validation in particular lets unsigned code be loaded";
+pub const DESC_IOS_SANDBOX_DISABLED: &str = "turns off the App Sandbox, giving the app the user's full file system and network access";
+pub const REC_IOS_SANDBOX_DISABLED: &str = "Enable com.apple.security.app-sandbox and request only the sandbox entitlements the app needs";
+pub const DESC_UNAUTHENTICATED_REMOTE_CACHE: &str = "is configured without credentials, so anyone who can reach it may read build outputs or write poisoned ones that every build then uses";
+pub const REC_UNAUTHENTICATED_REMOTE_CACHE: &str = "Require credentials on the cache (--google_credentials, --credential_helper, an auth --remote_header or mTLS) and serve it over TLS; keep write access to CI";
 pub const REC_FIREBASE_RULES: &str = "Restrict access rules to authenticated users and specific conditions";
 pub const REC_FIREBASE_AUTH: &str = "Add authentication checks to access rules";
 pub const REQUEST_AUTH: &str = "request.auth";
@@ -18,6 +18,14 @@ pub enum SecurityEntityType {
     EnvironmentConfig,
     SecurityConfig,
     ApiKey,
+    HttpEndpoint,
+    PaymentHandler,
+    PersonalData,
+    AndroidApp,
+    AndroidComponent,
+    IosApp,
+    IosEntitlements,
+    RemoteBuildCache,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,9 +69,15 @@ pub struct SecurityVulnerability {
     pub recommendation: String,
     pub file_path: String,
     pub line_number: Option<usize>,
+    /// Versions a dependency advisory applies to, e.g. `>=1.0.0, <1.4.2`
+    #[serde(default)]
+    pub affected_range: Option<String>,
+    /// The first release that fixes a dependency advisory
+    #[serde(default)]
+    pub fixed_version: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SecurityAnalysis {
     pub entities: Vec<SecurityEntity>,
     pub relationships: Vec<SecurityRelationship>,
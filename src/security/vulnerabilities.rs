@@ -21,6 +21,8 @@ pub fn check_iam_role_vulnerabilities(
             recommendation: templates::REC_ASSUME_ROLE_POLICY.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
     None
@@ -45,6 +47,8 @@ pub fn check_iam_policy_vulnerabilities(
             recommendation: templates::REC_WILDCARD_ACTION.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
 
@@ -59,6 +63,8 @@ pub fn check_iam_policy_vulnerabilities(
             recommendation: templates::REC_WILDCARD_RESOURCE.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
     None
@@ -87,6 +93,8 @@ pub fn check_s3_bucket_vulnerabilities(
             recommendation: templates::REC_S3_PUBLIC_ACCESS.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
 
@@ -101,6 +109,8 @@ pub fn check_s3_bucket_vulnerabilities(
             recommendation: templates::REC_S3_UNENCRYPTED.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
 
@@ -126,6 +136,8 @@ pub fn check_security_group_vulnerabilities(
             recommendation: templates::REC_SECURITY_GROUP_OPEN.to_string(),
             file_path: normalized_path.to_string(),
             line_number: Some(line_num + 1),
+            affected_range: None,
+            fixed_version: None,
         });
     }
 
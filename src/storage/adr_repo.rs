@@ -0,0 +1,99 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{ArchitectureDecision, DecisionLink};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredArchitectureDecision {
+    pub id: String,
+    pub repository_id: String,
+    pub number: Option<u32>,
+    pub title: String,
+    pub status: Option<String>,
+    pub superseded_by: Option<String>,
+    pub date: Option<String>,
+    pub decision: Option<String>,
+    pub file_path: String,
+    pub format: String,
+    pub mentions: Vec<DecisionLink>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct AdrRepository {
+    db: Database,
+}
+
+impl AdrRepository {
+    pub fn new(db: Database) -> Self {
+        AdrRepository { db }
+    }
+
+    pub fn store_decisions(&self, repository_id: &str, decisions: &[ArchitectureDecision]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing decisions for this repository
+        conn.execute(
+            "DELETE FROM architecture_decisions WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for decision in decisions {
+            conn.execute(
+                "INSERT INTO architecture_decisions (id, repository_id, number, title, status, superseded_by, date, decision, file_path, format, mentions, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    decision.number,
+                    decision.title,
+                    decision.status,
+                    decision.superseded_by,
+                    decision.date,
+                    decision.decision,
+                    decision.file_path,
+                    format!("{:?}", decision.format),
+                    serde_json::to_string(&decision.mentions)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredArchitectureDecision>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, number, title, status, superseded_by, date, decision, file_path, format, mentions, created_at
+             FROM architecture_decisions WHERE repository_id = ?1 ORDER BY number IS NULL, number, file_path"
+        )?;
+
+        let decisions = stmt.query_map(params![repository_id], |row| {
+            let mentions: String = row.get(10)?;
+            Ok(StoredArchitectureDecision {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                number: row.get(2)?,
+                title: row.get(3)?,
+                status: row.get(4)?,
+                superseded_by: row.get(5)?,
+                date: row.get(6)?,
+                decision: row.get(7)?,
+                file_path: row.get(8)?,
+                format: row.get(9)?,
+                mentions: serde_json::from_str(&mentions).unwrap_or_default(),
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(decisions)
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::{params, OptionalExtension};
+
+/// Step names of analyses that are no longer running
+const FINISHED_STEPS: &[&str] = &["Complete", "Failed", "Interrupted"];
+
+/// Progress of the latest analysis per repository and the step outputs it saved along the way,
+/// so an analysis cut short by a crash or restart can pick up where it stopped
+#[derive(Clone)]
+pub struct AnalysisRunRepository {
+    db: Database,
+}
+
+impl AnalysisRunRepository {
+    pub fn new(db: Database) -> Self {
+        AnalysisRunRepository { db }
+    }
+
+    /// `progress` is the serialized progress; one row per repository is overwritten as it advances
+    pub fn save_progress(&self, repository_id: &str, step_name: &str, progress: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO analysis_runs (repository_id, step_name, progress, updated_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![repository_id, step_name, progress, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_progress(&self, repository_id: &str) -> Result<Option<String>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let progress = conn.query_row(
+            "SELECT progress FROM analysis_runs WHERE repository_id = ?1",
+            params![repository_id],
+            |row| row.get(0),
+        ).optional()?;
+
+        Ok(progress)
+    }
+
+    /// Progress of analyses that never reached a final step
+    pub fn get_unfinished(&self) -> Result<Vec<String>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT step_name, progress FROM analysis_runs ORDER BY updated_at"
+        )?;
+        let runs = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|(step_name, _)| !FINISHED_STEPS.contains(&step_name.as_str()))
+            .map(|(_, progress)| progress)
+            .collect();
+
+        Ok(runs)
+    }
+
+    /// Replaces the step output saved under `name`; `revision` identifies the checkout it came from
+    pub fn store_checkpoint(&self, repository_id: &str, revision: &str, name: &str, payload: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM analysis_checkpoints WHERE repository_id = ?1 AND name = ?2",
+            params![repository_id, name],
+        )?;
+        conn.execute(
+            "INSERT INTO analysis_checkpoints (id, repository_id, revision, name, payload, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                revision,
+                name,
+                payload,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Saved output for `name`, only if it was saved for the same revision
+    pub fn get_checkpoint(&self, repository_id: &str, revision: &str, name: &str) -> Result<Option<String>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let payload = conn.query_row(
+            "SELECT payload FROM analysis_checkpoints WHERE repository_id = ?1 AND revision = ?2 AND name = ?3",
+            params![repository_id, revision, name],
+            |row| row.get(0),
+        ).optional()?;
+
+        Ok(payload)
+    }
+
+    pub fn clear_checkpoints(&self, repository_id: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM analysis_checkpoints WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        Ok(())
+    }
+}
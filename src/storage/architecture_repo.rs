@@ -0,0 +1,276 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::{params, OptionalExtension};
+use crate::analysis::{ArchitectureFinding, FindingSeverity};
+use crate::analysis::target_architecture::{ConformanceDelta, DeltaKind};
+use crate::analysis::layering::LayerAssignment;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredLayerAssignment {
+    pub file_path: String,
+    pub layer: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredArchitectureFinding {
+    pub id: String,
+    pub repository_id: String,
+    pub source: String, // "layering" for built-in conventions, "rules" for user-declared constraints
+    pub rule: String,
+    pub severity: String,
+    pub source_file: String,
+    pub source_layer: Option<String>,
+    pub target_file: String,
+    pub target_layer: Option<String>,
+    pub line_number: usize,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredArchitectureSpec {
+    pub repository_id: String,
+    pub content: String,
+    pub updated_at: String,
+    /// When an analysis last compared the repository against this spec
+    pub checked_at: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ArchitectureRepository {
+    db: Database,
+}
+
+impl ArchitectureRepository {
+    pub fn new(db: Database) -> Self {
+        ArchitectureRepository { db }
+    }
+
+    pub fn store_layers(&self, repository_id: &str, assignments: &[LayerAssignment]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM architecture_layers WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for assignment in assignments {
+            conn.execute(
+                "INSERT INTO architecture_layers (id, repository_id, file_path, layer, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    assignment.file_path,
+                    assignment.layer.as_str(),
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_layers(&self, repository_id: &str) -> Result<Vec<StoredLayerAssignment>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT file_path, layer FROM architecture_layers WHERE repository_id = ?1 ORDER BY layer, file_path"
+        )?;
+
+        let layers = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredLayerAssignment {
+                file_path: row.get(0)?,
+                layer: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(layers)
+    }
+
+    /// Replace the findings produced by one source, leaving the other sources' findings in place
+    pub fn store_findings(&self, repository_id: &str, source: &str, findings: &[ArchitectureFinding]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM architecture_findings WHERE repository_id = ?1 AND source = ?2",
+            params![repository_id, source],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for finding in findings {
+            conn.execute(
+                "INSERT INTO architecture_findings
+                 (id, repository_id, source, rule, severity, source_file, source_layer, target_file, target_layer, line_number, message, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    source,
+                    finding.rule,
+                    self.severity_to_string(&finding.severity),
+                    finding.source_file,
+                    finding.source_layer,
+                    finding.target_file,
+                    finding.target_layer,
+                    finding.line_number as i64,
+                    finding.message,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_findings(&self, repository_id: &str) -> Result<Vec<StoredArchitectureFinding>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, source, rule, severity, source_file, source_layer, target_file, target_layer, line_number, message, created_at
+             FROM architecture_findings WHERE repository_id = ?1
+             ORDER BY CASE severity WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, source_file, line_number"
+        )?;
+
+        let findings = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredArchitectureFinding {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                source: row.get(2)?,
+                rule: row.get(3)?,
+                severity: row.get(4)?,
+                source_file: row.get(5)?,
+                source_layer: row.get(6)?,
+                target_file: row.get(7)?,
+                target_layer: row.get(8)?,
+                line_number: row.get::<_, i64>(9)? as usize,
+                message: row.get(10)?,
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(findings)
+    }
+
+    /// Upload a target architecture, replacing the previous one and the deltas found against it
+    pub fn store_spec(&self, repository_id: &str, content: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO architecture_specs (repository_id, content, updated_at, checked_at) VALUES (?1, ?2, ?3, NULL)",
+            params![repository_id, content, Utc::now().to_rfc3339()],
+        )?;
+        conn.execute("DELETE FROM architecture_conformance WHERE repository_id = ?1", params![repository_id])?;
+        Ok(())
+    }
+
+    pub fn get_spec(&self, repository_id: &str) -> Result<Option<StoredArchitectureSpec>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let spec = conn.query_row(
+            "SELECT repository_id, content, updated_at, checked_at FROM architecture_specs WHERE repository_id = ?1",
+            params![repository_id],
+            |row| Ok(StoredArchitectureSpec {
+                repository_id: row.get(0)?,
+                content: row.get(1)?,
+                updated_at: row.get(2)?,
+                checked_at: row.get(3)?,
+            }),
+        ).optional()?;
+        Ok(spec)
+    }
+
+    pub fn delete_spec(&self, repository_id: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute("DELETE FROM architecture_conformance WHERE repository_id = ?1", params![repository_id])?;
+        conn.execute("DELETE FROM architecture_specs WHERE repository_id = ?1", params![repository_id])?;
+        Ok(())
+    }
+
+    /// Replace the deltas of the last conformance check and record when it ran
+    pub fn store_conformance(&self, repository_id: &str, deltas: &[ConformanceDelta]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute("DELETE FROM architecture_conformance WHERE repository_id = ?1", params![repository_id])?;
+        let now = Utc::now().to_rfc3339();
+        for delta in deltas {
+            conn.execute(
+                "INSERT INTO architecture_conformance
+                 (id, repository_id, kind, subject, expected, actual, severity, file_path, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    delta.kind.as_str(),
+                    delta.subject,
+                    delta.expected,
+                    delta.actual,
+                    self.severity_to_string(&delta.severity),
+                    delta.file_path,
+                    delta.line_number.map(|n| n as i64),
+                    now
+                ],
+            )?;
+        }
+        conn.execute(
+            "UPDATE architecture_specs SET checked_at = ?2 WHERE repository_id = ?1",
+            params![repository_id, now],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_conformance(&self, repository_id: &str) -> Result<Vec<ConformanceDelta>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT kind, subject, expected, actual, severity, file_path, line_number
+             FROM architecture_conformance WHERE repository_id = ?1
+             ORDER BY CASE severity WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, kind, subject"
+        )?;
+
+        let deltas = stmt.query_map(params![repository_id], |row| {
+            let kind: String = row.get(0)?;
+            let severity: String = row.get(4)?;
+            Ok(ConformanceDelta {
+                kind: DeltaKind::parse(&kind)
+                    .ok_or_else(|| rusqlite::Error::InvalidColumnType(0, "kind".to_string(), rusqlite::types::Type::Text))?,
+                subject: row.get(1)?,
+                expected: row.get(2)?,
+                actual: row.get(3)?,
+                severity: match severity.as_str() {
+                    "high" => FindingSeverity::High,
+                    "medium" => FindingSeverity::Medium,
+                    _ => FindingSeverity::Low,
+                },
+                file_path: row.get(5)?,
+                line_number: row.get::<_, Option<i64>>(6)?.map(|n| n as usize),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(deltas)
+    }
+
+    fn severity_to_string(&self, severity: &FindingSeverity) -> String {
+        match severity {
+            FindingSeverity::Low => "low",
+            FindingSeverity::Medium => "medium",
+            FindingSeverity::High => "high",
+        }.to_string()
+    }
+}
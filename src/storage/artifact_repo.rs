@@ -0,0 +1,173 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{ArtifactInventory, ArtifactKind, FindingSeverity};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredArtifact {
+    pub id: String,
+    pub repository_id: String,
+    pub kind: String,
+    pub name: String,
+    pub file_path: String,
+    pub size_bytes: Option<u64>,
+    pub sha256: Option<String>,
+    pub file_count: Option<usize>,
+    pub line_number: Option<usize>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredHygieneFinding {
+    pub id: String,
+    pub repository_id: String,
+    pub rule: String,
+    pub severity: String,
+    pub file_path: String,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct ArtifactRepository {
+    db: Database,
+}
+
+impl ArtifactRepository {
+    pub fn new(db: Database) -> Self {
+        ArtifactRepository { db }
+    }
+
+    pub fn store_inventory(&self, repository_id: &str, inventory: &ArtifactInventory) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing artifact inventory for this repository
+        conn.execute(
+            "DELETE FROM artifacts WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+        conn.execute(
+            "DELETE FROM hygiene_findings WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for artifact in &inventory.artifacts {
+            conn.execute(
+                "INSERT INTO artifacts
+                 (id, repository_id, kind, name, file_path, size_bytes, sha256, file_count, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    self.kind_to_string(&artifact.kind),
+                    artifact.name,
+                    artifact.file_path,
+                    artifact.size_bytes.map(|n| n as i64),
+                    artifact.sha256,
+                    artifact.file_count.map(|n| n as i64),
+                    artifact.line_number.map(|n| n as i32),
+                    now
+                ],
+            )?;
+        }
+
+        for finding in &inventory.findings {
+            conn.execute(
+                "INSERT INTO hygiene_findings (id, repository_id, rule, severity, file_path, message, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    finding.rule,
+                    self.severity_to_string(&finding.severity),
+                    finding.file_path,
+                    finding.message,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Largest first; container image references last
+    pub fn get_artifacts(&self, repository_id: &str) -> Result<Vec<StoredArtifact>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, name, file_path, size_bytes, sha256, file_count, line_number, created_at
+             FROM artifacts WHERE repository_id = ?1 ORDER BY size_bytes IS NULL, size_bytes DESC, file_path"
+        )?;
+
+        let artifacts = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredArtifact {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                kind: row.get(2)?,
+                name: row.get(3)?,
+                file_path: row.get(4)?,
+                size_bytes: row.get::<_, Option<i64>>(5)?.map(|n| n as u64),
+                sha256: row.get(6)?,
+                file_count: row.get::<_, Option<i64>>(7)?.map(|n| n as usize),
+                line_number: row.get::<_, Option<i32>>(8)?.map(|n| n as usize),
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(artifacts)
+    }
+
+    pub fn get_findings(&self, repository_id: &str) -> Result<Vec<StoredHygieneFinding>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, rule, severity, file_path, message, created_at
+             FROM hygiene_findings WHERE repository_id = ?1
+             ORDER BY CASE severity WHEN 'high' THEN 0 WHEN 'medium' THEN 1 ELSE 2 END, file_path"
+        )?;
+
+        let findings = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredHygieneFinding {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                rule: row.get(2)?,
+                severity: row.get(3)?,
+                file_path: row.get(4)?,
+                message: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(findings)
+    }
+
+    fn kind_to_string(&self, kind: &ArtifactKind) -> String {
+        match kind {
+            ArtifactKind::Binary => "binary",
+            ArtifactKind::Bytecode => "bytecode",
+            ArtifactKind::JavaArchive => "java_archive",
+            ArtifactKind::PythonPackage => "python_package",
+            ArtifactKind::Installer => "installer",
+            ArtifactKind::Archive => "archive",
+            ArtifactKind::ContainerImage => "container_image",
+            ArtifactKind::Vendored => "vendored",
+            ArtifactKind::LargeFile => "large_file",
+        }.to_string()
+    }
+
+    fn severity_to_string(&self, severity: &FindingSeverity) -> String {
+        match severity {
+            FindingSeverity::Low => "low",
+            FindingSeverity::Medium => "medium",
+            FindingSeverity::High => "high",
+        }.to_string()
+    }
+}
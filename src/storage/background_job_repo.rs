@@ -0,0 +1,109 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedBackgroundJob, JobTrigger};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredBackgroundJob {
+    pub id: String,
+    pub repository_id: String,
+    pub name: String,
+    pub framework: String,
+    pub trigger_type: String,
+    pub schedule: Option<String>,
+    pub queue: Option<String>,
+    pub handler: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct BackgroundJobRepository {
+    db: Database,
+}
+
+impl BackgroundJobRepository {
+    pub fn new(db: Database) -> Self {
+        BackgroundJobRepository { db }
+    }
+
+    pub fn store_jobs(&self, repository_id: &str, jobs: &[DetectedBackgroundJob]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing jobs for this repository
+        conn.execute(
+            "DELETE FROM background_jobs WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for job in jobs {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO background_jobs
+                 (id, repository_id, name, framework, trigger_type, schedule, queue, handler, file_path, line_number, language, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    id,
+                    repository_id,
+                    job.name,
+                    job.framework,
+                    self.trigger_to_string(&job.trigger),
+                    job.schedule,
+                    job.queue,
+                    job.handler,
+                    job.file_path,
+                    job.line_number.map(|n| n as i32),
+                    job.language,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredBackgroundJob>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, framework, trigger_type, schedule, queue, handler, file_path, line_number, language, created_at
+             FROM background_jobs WHERE repository_id = ?1 ORDER BY trigger_type, framework, name"
+        )?;
+
+        let jobs = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredBackgroundJob {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                framework: row.get(3)?,
+                trigger_type: row.get(4)?,
+                schedule: row.get(5)?,
+                queue: row.get(6)?,
+                handler: row.get(7)?,
+                file_path: row.get(8)?,
+                line_number: row.get::<_, Option<i32>>(9)?.map(|n| n as usize),
+                language: row.get(10)?,
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(jobs)
+    }
+
+    fn trigger_to_string(&self, trigger: &JobTrigger) -> String {
+        match trigger {
+            JobTrigger::Queue => "queue",
+            JobTrigger::Schedule => "schedule",
+            JobTrigger::Background => "background",
+        }.to_string()
+    }
+}
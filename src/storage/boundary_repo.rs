@@ -0,0 +1,163 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{ServiceBoundary, BoundaryKind, DecompositionReport};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredServiceBoundary {
+    pub id: String,
+    pub repository_id: String,
+    pub name: String,
+    pub kind: String,
+    pub paths: Vec<String>,
+    pub file_count: usize,
+    pub element_count: usize,
+    pub internal_references: usize,
+    pub outbound_references: usize,
+    pub cohesion: f64,
+    pub data_stores: Vec<String>,
+    pub shared_data_stores: Vec<String>,
+    pub depends_on: Vec<String>,
+    pub confidence: f64,
+    pub evidence: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct ServiceBoundaryRepository {
+    db: Database,
+}
+
+impl ServiceBoundaryRepository {
+    pub fn new(db: Database) -> Self {
+        ServiceBoundaryRepository { db }
+    }
+
+    pub fn store_boundaries(&self, repository_id: &str, boundaries: &[ServiceBoundary]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing boundaries for this repository
+        conn.execute(
+            "DELETE FROM service_boundaries WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for boundary in boundaries {
+            conn.execute(
+                "INSERT INTO service_boundaries
+                 (id, repository_id, name, kind, paths, file_count, element_count, internal_references, outbound_references,
+                  cohesion, data_stores, shared_data_stores, depends_on, confidence, evidence, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    boundary.name,
+                    self.kind_to_string(&boundary.kind),
+                    serde_json::to_string(&boundary.paths)?,
+                    boundary.file_count as i64,
+                    boundary.element_count as i64,
+                    boundary.internal_references as i64,
+                    boundary.outbound_references as i64,
+                    boundary.cohesion,
+                    serde_json::to_string(&boundary.data_stores)?,
+                    serde_json::to_string(&boundary.shared_data_stores)?,
+                    serde_json::to_string(&boundary.depends_on)?,
+                    boundary.confidence,
+                    serde_json::to_string(&boundary.evidence)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredServiceBoundary>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, kind, paths, file_count, element_count, internal_references, outbound_references,
+                    cohesion, data_stores, shared_data_stores, depends_on, confidence, evidence, created_at
+             FROM service_boundaries WHERE repository_id = ?1 ORDER BY confidence DESC, name"
+        )?;
+
+        let boundaries = stmt.query_map(params![repository_id], |row| {
+            let paths: String = row.get(4)?;
+            let data_stores: String = row.get(10)?;
+            let shared_data_stores: String = row.get(11)?;
+            let depends_on: String = row.get(12)?;
+            let evidence: String = row.get(14)?;
+            Ok(StoredServiceBoundary {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: row.get(3)?,
+                paths: serde_json::from_str(&paths).unwrap_or_default(),
+                file_count: row.get::<_, i64>(5)? as usize,
+                element_count: row.get::<_, i64>(6)? as usize,
+                internal_references: row.get::<_, i64>(7)? as usize,
+                outbound_references: row.get::<_, i64>(8)? as usize,
+                cohesion: row.get(9)?,
+                data_stores: serde_json::from_str(&data_stores).unwrap_or_default(),
+                shared_data_stores: serde_json::from_str(&shared_data_stores).unwrap_or_default(),
+                depends_on: serde_json::from_str(&depends_on).unwrap_or_default(),
+                confidence: row.get(13)?,
+                evidence: serde_json::from_str(&evidence).unwrap_or_default(),
+                created_at: row.get(15)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(boundaries)
+    }
+
+    /// Replaces the repository's previous decomposition report
+    pub fn store_decomposition(&self, repository_id: &str, report: &DecompositionReport) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM decomposition_reports WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+        conn.execute(
+            "INSERT INTO decomposition_reports (id, repository_id, report, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                serde_json::to_string(report)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_decomposition(&self, repository_id: &str) -> Result<Option<DecompositionReport>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT report FROM decomposition_reports WHERE repository_id = ?1"
+        )?;
+        let report = stmt.query_map(params![repository_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .find_map(|r| serde_json::from_str(&r).ok());
+
+        Ok(report)
+    }
+
+    fn kind_to_string(&self, kind: &BoundaryKind) -> String {
+        match kind {
+            BoundaryKind::Service => "service",
+            BoundaryKind::Domain => "domain",
+        }.to_string()
+    }
+}
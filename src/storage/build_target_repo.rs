@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::BuildTarget;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredBuildTarget {
+    pub id: String,
+    pub repository_id: String,
+    pub runner: String,
+    pub name: String,
+    pub key: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub commands: Vec<String>,
+    pub depends_on: Vec<String>,
+    pub tools: Vec<String>,
+    pub ci_jobs: Vec<String>,
+    pub dependents: usize,
+    pub ci_reach: usize,
+    pub unused: bool,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct BuildTargetRepository {
+    db: Database,
+}
+
+impl BuildTargetRepository {
+    pub fn new(db: Database) -> Self {
+        BuildTargetRepository { db }
+    }
+
+    pub fn store_targets(&self, repository_id: &str, targets: &[BuildTarget]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing build targets for this repository
+        conn.execute(
+            "DELETE FROM build_targets WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for target in targets {
+            conn.execute(
+                "INSERT INTO build_targets
+                 (id, repository_id, runner, name, file_path, line_number, commands, depends_on, tools, ci_jobs, dependents, ci_reach, unused, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    target.runner.as_str(),
+                    target.name,
+                    target.file_path,
+                    target.line_number.map(|n| n as i32),
+                    serde_json::to_string(&target.commands)?,
+                    serde_json::to_string(&target.depends_on)?,
+                    serde_json::to_string(&target.tools)?,
+                    serde_json::to_string(&target.ci_jobs)?,
+                    target.dependents as i64,
+                    target.ci_reach as i64,
+                    target.unused,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Targets most CI jobs depend on first
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredBuildTarget>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, runner, name, file_path, line_number, commands, depends_on, tools, ci_jobs, dependents, ci_reach, unused, created_at
+             FROM build_targets WHERE repository_id = ?1 ORDER BY ci_reach DESC, dependents DESC, runner, name"
+        )?;
+
+        let targets = stmt.query_map(params![repository_id], |row| {
+            let runner: String = row.get(2)?;
+            let name: String = row.get(3)?;
+            Ok(StoredBuildTarget {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                key: format!("{}:{}", runner, name),
+                runner,
+                name,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, Option<i32>>(5)?.map(|n| n as usize),
+                commands: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+                depends_on: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                tools: serde_json::from_str(&row.get::<_, String>(8)?).unwrap_or_default(),
+                ci_jobs: serde_json::from_str(&row.get::<_, String>(9)?).unwrap_or_default(),
+                dependents: row.get::<_, i64>(10)? as usize,
+                ci_reach: row.get::<_, i64>(11)? as usize,
+                unused: row.get(12)?,
+                created_at: row.get(13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(targets)
+    }
+}
@@ -3,7 +3,7 @@ use chrono::Utc;
 use uuid::Uuid;
 use crate::storage::Database;
 use rusqlite::params;
-use crate::analysis::{CodeElement, CodeCall, CodeElementType};
+use crate::analysis::{CodeElement, CodeCall, CodeElementType, CodeOrigin};
 
 #[derive(Clone)]
 pub struct CodeElementRepository {
@@ -41,11 +41,12 @@ impl CodeElementRepository {
         for element in elements.iter() {
             let element_type_str = self.element_type_to_string(&element.element_type);
             let parameters_json = serde_json::to_string(&element.parameters)?;
+            let decorators_json = serde_json::to_string(&element.decorators)?;
             
             conn.execute(
                 "INSERT INTO code_elements 
-                 (id, repository_id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                 (id, repository_id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type, parent_id, qualified_name, decorators, origin, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                 params![
                     element.id,
                     repository_id,
@@ -59,6 +60,10 @@ impl CodeElementRepository {
                     element.visibility,
                     parameters_json,
                     element.return_type,
+                    element.parent_id,
+                    element.qualified_name,
+                    decorators_json,
+                    element.origin.as_str(),
                     now.to_rfc3339()
                 ],
             )?;
@@ -140,7 +145,7 @@ impl CodeElementRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type
+            "SELECT id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type, parent_id, qualified_name, decorators, origin
              FROM code_elements WHERE repository_id = ?1 ORDER BY file_path, line_number"
         )?;
         
@@ -161,6 +166,12 @@ impl CodeElementRepository {
                 visibility: row.get(8)?,
                 parameters,
                 return_type: row.get(10)?,
+                parent_id: row.get(11)?,
+                qualified_name: row.get::<_, Option<String>>(12)?.unwrap_or_default(),
+                decorators: row.get::<_, Option<String>>(13)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                origin: CodeOrigin::parse(&row.get::<_, String>(14)?).unwrap_or_default(),
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -173,7 +184,7 @@ impl CodeElementRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type
+            "SELECT id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type, parent_id, qualified_name, decorators, origin
              FROM code_elements WHERE repository_id = ?1 AND element_type = ?2 ORDER BY file_path, line_number"
         )?;
         
@@ -194,6 +205,12 @@ impl CodeElementRepository {
                 visibility: row.get(8)?,
                 parameters,
                 return_type: row.get(10)?,
+                parent_id: row.get(11)?,
+                qualified_name: row.get::<_, Option<String>>(12)?.unwrap_or_default(),
+                decorators: row.get::<_, Option<String>>(13)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                origin: CodeOrigin::parse(&row.get::<_, String>(14)?).unwrap_or_default(),
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -201,6 +218,45 @@ impl CodeElementRepository {
         Ok(elements)
     }
 
+    /// The element stored under a fully-qualified name such as `src::orders::OrderService::create`
+    pub fn find_by_qualified_name(&self, repository_id: &str, qualified_name: &str) -> Result<Option<CodeElement>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, element_type, file_path, line_number, language, signature, doc_comment, visibility, parameters, return_type, parent_id, qualified_name, decorators, origin
+             FROM code_elements WHERE repository_id = ?1 AND qualified_name = ?2 ORDER BY file_path, line_number LIMIT 1"
+        )?;
+
+        let mut elements = stmt.query_map(params![repository_id, qualified_name], |row| {
+            let element_type_str: String = row.get(2)?;
+            let parameters_json: String = row.get(9)?;
+            let parameters: Vec<String> = serde_json::from_str(&parameters_json).unwrap_or_default();
+
+            Ok(CodeElement {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                element_type: self.string_to_element_type(&element_type_str),
+                file_path: row.get(3)?,
+                line_number: row.get::<_, i32>(4)? as usize,
+                language: row.get(5)?,
+                signature: row.get(6)?,
+                doc_comment: row.get(7)?,
+                visibility: row.get(8)?,
+                parameters,
+                return_type: row.get(10)?,
+                parent_id: row.get(11)?,
+                qualified_name: row.get::<_, Option<String>>(12)?.unwrap_or_default(),
+                decorators: row.get::<_, Option<String>>(13)?
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
+                origin: CodeOrigin::parse(&row.get::<_, String>(14)?).unwrap_or_default(),
+            })
+        })?;
+
+        Ok(elements.next().transpose()?)
+    }
+
     pub fn get_calls(&self, repository_id: &str) -> Result<Vec<CodeCall>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{CostCategory, ResourceCost};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredResourceCost {
+    pub id: String,
+    pub repository_id: String,
+    pub resource_type: String,
+    pub name: String,
+    pub category: String,
+    pub size: Option<String>,
+    pub count: u32,
+    pub monthly_cost: Option<f64>,
+    pub oversized: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct CostRepository {
+    db: Database,
+}
+
+impl CostRepository {
+    pub fn new(db: Database) -> Self {
+        CostRepository { db }
+    }
+
+    pub fn store_resources(&self, repository_id: &str, resources: &[ResourceCost]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing cost estimate for this repository
+        conn.execute(
+            "DELETE FROM resource_costs WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for resource in resources {
+            conn.execute(
+                "INSERT INTO resource_costs
+                 (id, repository_id, resource_type, name, category, size, count, monthly_cost, oversized, file_path, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    resource.resource_type,
+                    resource.name,
+                    self.category_to_string(&resource.category),
+                    resource.size,
+                    resource.count,
+                    resource.monthly_cost,
+                    resource.oversized,
+                    resource.file_path,
+                    resource.line_number.map(|n| n as i32),
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Most expensive first; unpriced resources last
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredResourceCost>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, resource_type, name, category, size, count, monthly_cost, oversized, file_path, line_number, created_at
+             FROM resource_costs WHERE repository_id = ?1 ORDER BY monthly_cost IS NULL, monthly_cost DESC, name"
+        )?;
+
+        let resources = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredResourceCost {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                resource_type: row.get(2)?,
+                name: row.get(3)?,
+                category: row.get(4)?,
+                size: row.get(5)?,
+                count: row.get(6)?,
+                monthly_cost: row.get(7)?,
+                oversized: row.get(8)?,
+                file_path: row.get(9)?,
+                line_number: row.get::<_, Option<i32>>(10)?.map(|n| n as usize),
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(resources)
+    }
+
+    fn category_to_string(&self, category: &CostCategory) -> String {
+        match category {
+            CostCategory::Compute => "compute",
+            CostCategory::Database => "database",
+            CostCategory::Cache => "cache",
+            CostCategory::Serverless => "serverless",
+            CostCategory::Network => "network",
+            CostCategory::Kubernetes => "kubernetes",
+        }.to_string()
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{CoverageFormat, FileCoverage};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredFileCoverage {
+    pub id: String,
+    pub repository_id: String,
+    pub file_path: String,
+    pub format: String,
+    pub report_path: String,
+    pub source: String, // "repository" for committed artifacts, "upload" for API uploads
+    pub lines_found: usize,
+    pub lines_hit: usize,
+    pub branches_found: usize,
+    pub branches_hit: usize,
+    pub line_percentage: f64,
+    #[serde(skip_serializing)]
+    pub lines: BTreeMap<usize, u64>,
+    #[serde(skip_serializing)]
+    pub branches: BTreeMap<usize, (usize, usize)>,
+    pub created_at: String,
+}
+
+impl StoredFileCoverage {
+    pub fn to_file_coverage(&self) -> FileCoverage {
+        FileCoverage {
+            file_path: self.file_path.clone(),
+            format: match self.format.as_str() {
+                "cobertura" => CoverageFormat::Cobertura,
+                "jacoco" => CoverageFormat::Jacoco,
+                _ => CoverageFormat::Lcov,
+            },
+            report_path: self.report_path.clone(),
+            lines: self.lines.clone(),
+            branches: self.branches.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CoverageRepository {
+    db: Database,
+}
+
+impl CoverageRepository {
+    pub fn new(db: Database) -> Self {
+        CoverageRepository { db }
+    }
+
+    /// Replace the coverage previously stored from the same source
+    pub fn store_coverage(&self, repository_id: &str, source: &str, files: &[FileCoverage]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM coverage_files WHERE repository_id = ?1 AND source = ?2",
+            params![repository_id, source],
+        )?;
+
+        let now = Utc::now();
+        for file in files {
+            let id = Uuid::new_v4().to_string();
+            let lines_json = serde_json::to_string(&file.lines)?;
+            let branches_json = serde_json::to_string(&file.branches)?;
+
+            conn.execute(
+                "INSERT INTO coverage_files
+                 (id, repository_id, file_path, format, report_path, source, lines_found, lines_hit, branches_found, branches_hit, line_percentage, lines, branches, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    id,
+                    repository_id,
+                    file.file_path,
+                    self.format_to_string(&file.format),
+                    file.report_path,
+                    source,
+                    file.lines_found() as i64,
+                    file.lines_hit() as i64,
+                    file.branches_found() as i64,
+                    file.branches_hit() as i64,
+                    file.line_percentage(),
+                    lines_json,
+                    branches_json,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredFileCoverage>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, file_path, format, report_path, source, lines_found, lines_hit, branches_found, branches_hit, line_percentage, lines, branches, created_at
+             FROM coverage_files WHERE repository_id = ?1 ORDER BY file_path"
+        )?;
+
+        let files = stmt.query_map(params![repository_id], |row| {
+            let lines_json: String = row.get(11)?;
+            let branches_json: String = row.get(12)?;
+
+            Ok(StoredFileCoverage {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                file_path: row.get(2)?,
+                format: row.get(3)?,
+                report_path: row.get(4)?,
+                source: row.get(5)?,
+                lines_found: row.get::<_, i64>(6)? as usize,
+                lines_hit: row.get::<_, i64>(7)? as usize,
+                branches_found: row.get::<_, i64>(8)? as usize,
+                branches_hit: row.get::<_, i64>(9)? as usize,
+                line_percentage: row.get(10)?,
+                lines: serde_json::from_str(&lines_json).unwrap_or_default(),
+                branches: serde_json::from_str(&branches_json).unwrap_or_default(),
+                created_at: row.get(13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(files)
+    }
+
+    /// Coverage per file, preferring uploaded reports over artifacts found in the repository
+    pub fn get_effective_coverage(&self, repository_id: &str) -> Result<Vec<StoredFileCoverage>> {
+        let mut by_file: HashMap<String, StoredFileCoverage> = HashMap::new();
+        for file in self.get_by_repository(repository_id)? {
+            let replace = match by_file.get(&file.file_path) {
+                Some(existing) => existing.source != "upload" && file.source == "upload",
+                None => true,
+            };
+            if replace {
+                by_file.insert(file.file_path.clone(), file);
+            }
+        }
+
+        let mut files: Vec<StoredFileCoverage> = by_file.into_values().collect();
+        files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        Ok(files)
+    }
+
+    fn format_to_string(&self, format: &CoverageFormat) -> String {
+        match format {
+            CoverageFormat::Lcov => "lcov",
+            CoverageFormat::Cobertura => "cobertura",
+            CoverageFormat::Jacoco => "jacoco",
+        }.to_string()
+    }
+}
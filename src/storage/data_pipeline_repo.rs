@@ -0,0 +1,102 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DataExposure, DataPipeline, DataSource, DataTask};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredDataPipeline {
+    pub id: String,
+    pub repository_id: String,
+    pub orchestrator: String,
+    pub name: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub schedule: Option<String>,
+    pub triggered_by: Vec<String>,
+    pub tasks: Vec<DataTask>,
+    pub warehouse: Option<String>,
+    pub sources: Vec<DataSource>,
+    pub exposures: Vec<DataExposure>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct DataPipelineRepository {
+    db: Database,
+}
+
+impl DataPipelineRepository {
+    pub fn new(db: Database) -> Self {
+        DataPipelineRepository { db }
+    }
+
+    pub fn store_pipelines(&self, repository_id: &str, pipelines: &[DataPipeline]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing data pipelines for this repository
+        conn.execute(
+            "DELETE FROM data_pipelines WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for pipeline in pipelines {
+            conn.execute(
+                "INSERT INTO data_pipelines
+                 (id, repository_id, orchestrator, name, file_path, line_number, schedule, triggered_by, tasks, warehouse, sources, exposures, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    pipeline.orchestrator.as_str(),
+                    pipeline.name,
+                    pipeline.file_path,
+                    pipeline.line_number.map(|n| n as i32),
+                    pipeline.schedule,
+                    serde_json::to_string(&pipeline.triggered_by)?,
+                    serde_json::to_string(&pipeline.tasks)?,
+                    pipeline.warehouse,
+                    serde_json::to_string(&pipeline.sources)?,
+                    serde_json::to_string(&pipeline.exposures)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredDataPipeline>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, orchestrator, name, file_path, line_number, schedule, triggered_by, tasks, warehouse, sources, exposures, created_at
+             FROM data_pipelines WHERE repository_id = ?1 ORDER BY orchestrator, file_path, name"
+        )?;
+
+        let pipelines = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredDataPipeline {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                orchestrator: row.get(2)?,
+                name: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, Option<i32>>(5)?.map(|n| n as usize),
+                schedule: row.get(6)?,
+                triggered_by: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                tasks: serde_json::from_str(&row.get::<_, String>(8)?).unwrap_or_default(),
+                warehouse: row.get(9)?,
+                sources: serde_json::from_str(&row.get::<_, String>(10)?).unwrap_or_default(),
+                exposures: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
+                created_at: row.get(12)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(pipelines)
+    }
+}
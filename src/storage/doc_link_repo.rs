@@ -0,0 +1,108 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::DocLink;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredDocLink {
+    pub id: String,
+    pub repository_id: String,
+    pub doc_path: String,
+    pub line_number: usize,
+    pub kind: String,
+    pub target: String,
+    pub target_file: Option<String>,
+    pub target_name: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct DocLinkRepository {
+    db: Database,
+}
+
+impl DocLinkRepository {
+    pub fn new(db: Database) -> Self {
+        DocLinkRepository { db }
+    }
+
+    pub fn store_links(&self, repository_id: &str, links: &[DocLink]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing links for this repository
+        conn.execute(
+            "DELETE FROM doc_links WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for link in links {
+            conn.execute(
+                "INSERT INTO doc_links (id, repository_id, doc_path, line_number, kind, target, target_file, target_name, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    link.doc_path,
+                    link.line_number as i64,
+                    link.kind.as_str(),
+                    link.target,
+                    link.target_file,
+                    link.target_name,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredDocLink>> {
+        self.query(
+            "SELECT id, repository_id, doc_path, line_number, kind, target, target_file, target_name, created_at
+             FROM doc_links WHERE repository_id = ?1 ORDER BY doc_path, line_number",
+            params![repository_id],
+        )
+    }
+
+    /// Documentation that references a file (or anything below a directory) or a component by name;
+    /// these are the docs to review when that code changes
+    pub fn get_referencing(&self, repository_id: &str, file: Option<&str>, name: Option<&str>) -> Result<Vec<StoredDocLink>> {
+        self.query(
+            "SELECT id, repository_id, doc_path, line_number, kind, target, target_file, target_name, created_at
+             FROM doc_links
+             WHERE repository_id = ?1
+               AND (?2 IS NULL OR target_file = ?2 OR target_file LIKE ?2 || '/%')
+               AND (?3 IS NULL OR target_name = ?3 COLLATE NOCASE)
+               AND (?2 IS NOT NULL OR ?3 IS NOT NULL)
+             ORDER BY doc_path, line_number",
+            params![repository_id, file, name],
+        )
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<StoredDocLink>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(sql)?;
+        let links = stmt.query_map(params, |row| {
+            Ok(StoredDocLink {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                doc_path: row.get(2)?,
+                line_number: row.get::<_, i64>(3)? as usize,
+                kind: row.get(4)?,
+                target: row.get(5)?,
+                target_file: row.get(6)?,
+                target_name: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links)
+    }
+}
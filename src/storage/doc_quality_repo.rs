@@ -0,0 +1,73 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::{params, OptionalExtension};
+use crate::analysis::DocQualityReport;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredDocQuality {
+    pub id: String,
+    pub repository_id: String,
+    #[serde(flatten)]
+    pub report: DocQualityReport,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct DocQualityRepository {
+    db: Database,
+}
+
+impl DocQualityRepository {
+    pub fn new(db: Database) -> Self {
+        DocQualityRepository { db }
+    }
+
+    pub fn store_report(&self, repository_id: &str, report: &DocQualityReport) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Only the latest report is kept
+        conn.execute(
+            "DELETE FROM doc_quality WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        conn.execute(
+            "INSERT INTO doc_quality (id, repository_id, score, grade, report, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                report.score,
+                report.grade,
+                serde_json::to_string(report)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_latest(&self, repository_id: &str) -> Result<Option<StoredDocQuality>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let row = conn.query_row(
+            "SELECT id, repository_id, report, created_at FROM doc_quality WHERE repository_id = ?1",
+            params![repository_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?)),
+        ).optional()?;
+
+        match row {
+            Some((id, repository_id, report, created_at)) => Ok(Some(StoredDocQuality {
+                id,
+                repository_id,
+                report: serde_json::from_str(&report)?,
+                created_at,
+            })),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::search::cosine_similarity;
+
+/// A piece of documentation or code and its vector
+#[derive(Debug, Clone)]
+pub struct EmbeddingEntry {
+    pub source_type: String, // "documentation" or "code_element"
+    pub source_id: String,
+    pub title: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub content: String,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SemanticMatch {
+    pub repository_id: String,
+    pub source_type: String,
+    pub source_id: String,
+    pub title: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub snippet: String,
+    pub score: f32,
+}
+
+#[derive(Clone)]
+pub struct EmbeddingRepository {
+    db: Database,
+}
+
+impl EmbeddingRepository {
+    pub fn new(db: Database) -> Self {
+        EmbeddingRepository { db }
+    }
+
+    pub fn store_embeddings(&self, repository_id: &str, model: &str, entries: &[EmbeddingEntry]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing vectors for this repository, whatever model produced them
+        conn.execute(
+            "DELETE FROM embeddings WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for entry in entries {
+            conn.execute(
+                "INSERT INTO embeddings (id, repository_id, model, source_type, source_id, title, file_path, line_number, content, vector, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    model,
+                    entry.source_type,
+                    entry.source_id,
+                    entry.title,
+                    entry.file_path,
+                    entry.line_number.map(|l| l as i64),
+                    entry.content,
+                    vector_to_bytes(&entry.vector),
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Nearest vectors by cosine similarity, across the workspace's repositories unless one is given
+    pub fn search(
+        &self,
+        model: &str,
+        query: &[f32],
+        workspace_id: &str,
+        repository_id: Option<&str>,
+        source_type: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SemanticMatch>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT repository_id, source_type, source_id, title, file_path, line_number, content, vector
+             FROM embeddings
+             WHERE model = ?1 AND (?2 IS NULL OR repository_id = ?2) AND (?3 IS NULL OR source_type = ?3) AND workspace_id = ?4"
+        )?;
+
+        let mut matches = stmt.query_map(params![model, repository_id, source_type, workspace_id], |row| {
+            let content: String = row.get(6)?;
+            let vector: Vec<u8> = row.get(7)?;
+            Ok(SemanticMatch {
+                repository_id: row.get(0)?,
+                source_type: row.get(1)?,
+                source_id: row.get(2)?,
+                title: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, Option<i64>>(5)?.map(|l| l as usize),
+                snippet: content.chars().take(300).collect(),
+                score: cosine_similarity(query, &bytes_to_vector(&vector)),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+}
+
+fn vector_to_bytes(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn bytes_to_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes.chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
@@ -3,7 +3,7 @@ use chrono::Utc;
 use uuid::Uuid;
 use crate::storage::Database;
 use rusqlite::params;
-use crate::analysis::{DetectedEndpoint, HttpMethod};
+use crate::analysis::{DetectedEndpoint, EndpointProtocol, HttpMethod};
 use serde_json;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -19,6 +19,7 @@ pub struct StoredEndpoint {
     pub middleware: Vec<String>,
     pub parameters: Vec<String>,
     pub created_at: String,
+    pub protocol: String, // "http", "websocket" or "sse"
 }
 
 #[derive(Clone)]
@@ -51,8 +52,8 @@ impl EndpointRepository {
             
             conn.execute(
                 "INSERT INTO endpoints 
-                 (id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                 (id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
                 params![
                     id,
                     repository_id,
@@ -64,7 +65,8 @@ impl EndpointRepository {
                     endpoint.framework,
                     middleware_json,
                     parameters_json,
-                    now.to_rfc3339()
+                    now.to_rfc3339(),
+                    self.protocol_to_string(&endpoint.protocol)
                 ],
             )?;
         }
@@ -77,7 +79,7 @@ impl EndpointRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
+            "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
              FROM endpoints WHERE repository_id = ?1 ORDER BY path, method"
         )?;
         
@@ -97,6 +99,7 @@ impl EndpointRepository {
                 middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                 parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                 created_at: row.get(10)?,
+                protocol: row.get(11)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -104,16 +107,17 @@ impl EndpointRepository {
         Ok(endpoints)
     }
 
-    pub fn get_by_path(&self, path: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_path(&self, path: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let endpoints: Vec<StoredEndpoint> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE path LIKE ?1 AND repository_id = ?2 ORDER BY method"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE path LIKE ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY method"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![format!("%{}%", path), repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![format!("%{}%", path), repo_id, workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -129,15 +133,16 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE path LIKE ?1 ORDER BY repository_id, method"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE path LIKE ?1 AND workspace_id = ?2 ORDER BY repository_id, method"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![format!("%{}%", path)], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![format!("%{}%", path), workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -153,6 +158,7 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
@@ -161,16 +167,17 @@ impl EndpointRepository {
         Ok(endpoints)
     }
 
-    pub fn get_by_method(&self, method: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_method(&self, method: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let endpoints: Vec<StoredEndpoint> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE method = ?1 AND repository_id = ?2 ORDER BY path"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE method = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY path"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![method, repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![method, repo_id, workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -186,15 +193,16 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE method = ?1 ORDER BY repository_id, path"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE method = ?1 AND workspace_id = ?2 ORDER BY repository_id, path"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![method], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![method, workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -210,6 +218,7 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
@@ -218,16 +227,17 @@ impl EndpointRepository {
         Ok(endpoints)
     }
 
-    pub fn get_by_framework(&self, framework: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_framework(&self, framework: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredEndpoint>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let endpoints: Vec<StoredEndpoint> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE framework = ?1 AND repository_id = ?2 ORDER BY path"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE framework = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY path"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![framework, repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![framework, repo_id, workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -243,15 +253,16 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at
-                 FROM endpoints WHERE framework = ?1 ORDER BY repository_id, path"
+                "SELECT id, repository_id, path, method, handler, file_path, line_number, framework, middleware, parameters, created_at, protocol
+                 FROM endpoints WHERE framework = ?1 AND workspace_id = ?2 ORDER BY repository_id, path"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![framework], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![framework, workspace_id], |row| {
                 let middleware_json: String = row.get(8)?;
                 let parameters_json: String = row.get(9)?;
                 
@@ -267,6 +278,7 @@ impl EndpointRepository {
                     middleware: serde_json::from_str(&middleware_json).unwrap_or_default(),
                     parameters: serde_json::from_str(&parameters_json).unwrap_or_default(),
                     created_at: row.get(10)?,
+                    protocol: row.get(11)?,
                 })
             })?.collect();
             result?
@@ -287,5 +299,13 @@ impl EndpointRepository {
             HttpMethod::Any => "ANY",
         }.to_string()
     }
+
+    fn protocol_to_string(&self, protocol: &EndpointProtocol) -> String {
+        match protocol {
+            EndpointProtocol::Http => "http",
+            EndpointProtocol::WebSocket => "websocket",
+            EndpointProtocol::Sse => "sse",
+        }.to_string()
+    }
 }
 
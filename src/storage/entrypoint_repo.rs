@@ -0,0 +1,108 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedEntrypoint, EntrypointType};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredEntrypoint {
+    pub id: String,
+    pub repository_id: String,
+    pub name: String,
+    pub entrypoint_type: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub command: Option<String>,
+    pub target_file: Option<String>,
+    pub handler: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct EntrypointRepository {
+    db: Database,
+}
+
+impl EntrypointRepository {
+    pub fn new(db: Database) -> Self {
+        EntrypointRepository { db }
+    }
+
+    pub fn store_entrypoints(&self, repository_id: &str, entrypoints: &[DetectedEntrypoint]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing entrypoints for this repository
+        conn.execute(
+            "DELETE FROM entrypoints WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for entrypoint in entrypoints {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO entrypoints
+                 (id, repository_id, name, entrypoint_type, file_path, line_number, language, command, target_file, handler, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    id,
+                    repository_id,
+                    entrypoint.name,
+                    self.entrypoint_type_to_string(&entrypoint.entrypoint_type),
+                    entrypoint.file_path,
+                    entrypoint.line_number.map(|n| n as i32),
+                    entrypoint.language,
+                    entrypoint.command,
+                    entrypoint.target_file,
+                    entrypoint.handler,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredEntrypoint>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, entrypoint_type, file_path, line_number, language, command, target_file, handler, created_at
+             FROM entrypoints WHERE repository_id = ?1 ORDER BY entrypoint_type, file_path"
+        )?;
+
+        let entrypoints = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredEntrypoint {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                entrypoint_type: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, Option<i32>>(5)?.map(|n| n as usize),
+                language: row.get(6)?,
+                command: row.get(7)?,
+                target_file: row.get(8)?,
+                handler: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entrypoints)
+    }
+
+    fn entrypoint_type_to_string(&self, entrypoint_type: &EntrypointType) -> String {
+        match entrypoint_type {
+            EntrypointType::MainFunction => "main_function",
+            EntrypointType::ContainerCommand => "container_command",
+            EntrypointType::PackageScript => "package_script",
+            EntrypointType::ServerlessHandler => "serverless_handler",
+            EntrypointType::CliScript => "cli_script",
+        }.to_string()
+    }
+}
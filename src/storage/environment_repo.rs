@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{EnvironmentSource, EnvironmentValue, SettingKind};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredEnvironmentValue {
+    pub id: String,
+    pub repository_id: String,
+    pub environment: String,
+    pub key: String,
+    pub value: String,
+    pub kind: String,
+    pub source: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct EnvironmentRepository {
+    db: Database,
+}
+
+impl EnvironmentRepository {
+    pub fn new(db: Database) -> Self {
+        EnvironmentRepository { db }
+    }
+
+    pub fn store_values(&self, repository_id: &str, values: &[EnvironmentValue]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing environment matrix for this repository
+        conn.execute(
+            "DELETE FROM environment_values WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for value in values {
+            conn.execute(
+                "INSERT INTO environment_values
+                 (id, repository_id, environment, key, value, kind, source, file_path, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    value.environment,
+                    value.key,
+                    value.value,
+                    self.kind_to_string(&value.kind),
+                    self.source_to_string(&value.source),
+                    value.file_path,
+                    value.line_number.map(|n| n as i32),
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// In detection order, so later values for the same environment and key win
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredEnvironmentValue>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, environment, key, value, kind, source, file_path, line_number, created_at
+             FROM environment_values WHERE repository_id = ?1 ORDER BY rowid"
+        )?;
+
+        let values = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredEnvironmentValue {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                environment: row.get(2)?,
+                key: row.get(3)?,
+                value: row.get(4)?,
+                kind: row.get(5)?,
+                source: row.get(6)?,
+                file_path: row.get(7)?,
+                line_number: row.get::<_, Option<i32>>(8)?.map(|n| n as usize),
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(values)
+    }
+
+    fn kind_to_string(&self, kind: &SettingKind) -> String {
+        match kind {
+            SettingKind::Endpoint => "endpoint",
+            SettingKind::Service => "service",
+            SettingKind::Config => "config",
+        }.to_string()
+    }
+
+    fn source_to_string(&self, source: &EnvironmentSource) -> String {
+        match source {
+            EnvironmentSource::DotEnv => "dotenv",
+            EnvironmentSource::HelmValues => "helm_values",
+            EnvironmentSource::CiVariables => "ci_variables",
+        }.to_string()
+    }
+}
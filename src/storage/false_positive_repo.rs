@@ -0,0 +1,127 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::analysis::false_positives::{Detection, VerdictKind};
+use crate::storage::Database;
+
+/// A user's verdict that a detection is wrong; analyses of the repository leave out detections
+/// with the same fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FalsePositive {
+    pub id: String,
+    pub repository_id: String,
+    pub fingerprint: String,
+    #[serde(flatten)]
+    pub detection: Detection,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct FalsePositiveRepository {
+    db: Database,
+}
+
+impl FalsePositiveRepository {
+    pub fn new(db: Database) -> Self {
+        FalsePositiveRepository { db }
+    }
+
+    /// Marking a detection twice keeps the first verdict
+    pub fn mark(&self, repository_id: &str, detection: &Detection, reason: Option<&str>) -> Result<FalsePositive> {
+        let fingerprint = detection.fingerprint();
+        if let Some(existing) = self.find_by_fingerprint(repository_id, &fingerprint)? {
+            return Ok(existing);
+        }
+
+        let verdict = FalsePositive {
+            id: Uuid::new_v4().to_string(),
+            repository_id: repository_id.to_string(),
+            fingerprint,
+            detection: detection.clone(),
+            reason: reason.map(str::to_string),
+            created_at: Utc::now().to_rfc3339(),
+        };
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO false_positives
+             (id, repository_id, kind, fingerprint, subject, detail, file_path, confidence, reason, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                verdict.id,
+                verdict.repository_id,
+                verdict.detection.kind.as_str(),
+                verdict.fingerprint,
+                verdict.detection.subject,
+                verdict.detection.detail,
+                verdict.detection.file_path,
+                verdict.detection.confidence,
+                verdict.reason,
+                verdict.created_at
+            ],
+        )?;
+
+        Ok(verdict)
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<FalsePositive>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, fingerprint, subject, detail, file_path, confidence, reason, created_at
+             FROM false_positives WHERE repository_id = ?1 ORDER BY created_at"
+        )?;
+        let verdicts = stmt.query_map(params![repository_id], row_to_false_positive)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(verdicts)
+    }
+
+    fn find_by_fingerprint(&self, repository_id: &str, fingerprint: &str) -> Result<Option<FalsePositive>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT id, repository_id, kind, fingerprint, subject, detail, file_path, confidence, reason, created_at
+             FROM false_positives WHERE repository_id = ?1 AND fingerprint = ?2",
+            params![repository_id, fingerprint],
+            row_to_false_positive,
+        ).optional()?)
+    }
+
+    /// Whether there was such a verdict
+    pub fn delete(&self, repository_id: &str, id: &str) -> Result<bool> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let deleted = conn.execute(
+            "DELETE FROM false_positives WHERE repository_id = ?1 AND id = ?2",
+            params![repository_id, id],
+        )?;
+        Ok(deleted > 0)
+    }
+}
+
+fn row_to_false_positive(row: &rusqlite::Row) -> rusqlite::Result<FalsePositive> {
+    let kind: String = row.get(2)?;
+    Ok(FalsePositive {
+        id: row.get(0)?,
+        repository_id: row.get(1)?,
+        fingerprint: row.get(3)?,
+        detection: Detection {
+            kind: VerdictKind::parse(&kind)
+                .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "kind".to_string(), rusqlite::types::Type::Text))?,
+            subject: row.get(4)?,
+            detail: row.get(5)?,
+            file_path: row.get(6)?,
+            confidence: row.get(7)?,
+        },
+        reason: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
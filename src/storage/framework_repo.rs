@@ -0,0 +1,102 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedFramework, FrameworkCategory};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredFramework {
+    pub id: String,
+    pub repository_id: String,
+    pub name: String,
+    pub category: String,
+    pub language: String,
+    pub version: Option<String>,
+    pub file_path: String,
+    pub confidence: f64,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct FrameworkRepository {
+    db: Database,
+}
+
+impl FrameworkRepository {
+    pub fn new(db: Database) -> Self {
+        FrameworkRepository { db }
+    }
+
+    pub fn store_frameworks(&self, repository_id: &str, frameworks: &[DetectedFramework]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing frameworks for this repository
+        conn.execute(
+            "DELETE FROM frameworks WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for framework in frameworks {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO frameworks
+                 (id, repository_id, name, category, language, version, file_path, confidence, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    repository_id,
+                    framework.name,
+                    self.category_to_string(&framework.category),
+                    framework.language,
+                    framework.version,
+                    framework.file_path,
+                    framework.confidence,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredFramework>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, category, language, version, file_path, confidence, created_at
+             FROM frameworks WHERE repository_id = ?1 ORDER BY category, name"
+        )?;
+
+        let frameworks = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredFramework {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                category: row.get(3)?,
+                language: row.get(4)?,
+                version: row.get(5)?,
+                file_path: row.get(6)?,
+                confidence: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(frameworks)
+    }
+
+    fn category_to_string(&self, category: &FrameworkCategory) -> String {
+        match category {
+            FrameworkCategory::Web => "web",
+            FrameworkCategory::Frontend => "frontend",
+            FrameworkCategory::Orm => "orm",
+            FrameworkCategory::Runtime => "runtime",
+            FrameworkCategory::GameEngine => "game_engine",
+        }.to_string()
+    }
+}
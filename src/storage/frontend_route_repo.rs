@@ -0,0 +1,105 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{FrontendRoute, FrontendRouter};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredFrontendRoute {
+    pub id: String,
+    pub repository_id: String,
+    pub path: String,
+    pub router: String,
+    pub component: Option<String>,
+    pub component_file: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub lazy: bool,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct FrontendRouteRepository {
+    db: Database,
+}
+
+impl FrontendRouteRepository {
+    pub fn new(db: Database) -> Self {
+        FrontendRouteRepository { db }
+    }
+
+    pub fn store_routes(&self, repository_id: &str, routes: &[FrontendRoute]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing frontend routes for this repository
+        conn.execute(
+            "DELETE FROM frontend_routes WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for route in routes {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO frontend_routes
+                 (id, repository_id, path, router, component, component_file, file_path, line_number, lazy, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    id,
+                    repository_id,
+                    route.path,
+                    self.router_to_string(&route.router),
+                    route.component,
+                    route.component_file,
+                    route.file_path,
+                    route.line_number.map(|n| n as i32),
+                    if route.lazy { 1 } else { 0 },
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredFrontendRoute>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, path, router, component, component_file, file_path, line_number, lazy, created_at
+             FROM frontend_routes WHERE repository_id = ?1 ORDER BY path, file_path"
+        )?;
+
+        let routes = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredFrontendRoute {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                path: row.get(2)?,
+                router: row.get(3)?,
+                component: row.get(4)?,
+                component_file: row.get(5)?,
+                file_path: row.get(6)?,
+                line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
+                lazy: row.get::<_, i32>(8)? != 0,
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(routes)
+    }
+
+    fn router_to_string(&self, router: &FrontendRouter) -> String {
+        match router {
+            FrontendRouter::NextPages => "nextjs_pages",
+            FrontendRouter::NextApp => "nextjs_app",
+            FrontendRouter::ReactRouter => "react_router",
+            FrontendRouter::VueRouter => "vue_router",
+            FrontendRouter::Angular => "angular",
+        }.to_string()
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{GatewayKind, GatewayRoute};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredGatewayRoute {
+    pub id: String,
+    pub repository_id: String,
+    pub kind: String,
+    pub gateway: String,
+    pub route: String,
+    pub methods: Vec<String>,
+    pub upstream: String,
+    pub upstream_url: Option<String>,
+    pub protocol: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct GatewayRepository {
+    db: Database,
+}
+
+impl GatewayRepository {
+    pub fn new(db: Database) -> Self {
+        GatewayRepository { db }
+    }
+
+    pub fn store_routes(&self, repository_id: &str, routes: &[GatewayRoute]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing gateway routes for this repository
+        conn.execute(
+            "DELETE FROM gateway_routes WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for route in routes {
+            conn.execute(
+                "INSERT INTO gateway_routes
+                 (id, repository_id, kind, gateway, route, methods, upstream, upstream_url, protocol, file_path, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    self.kind_to_string(&route.kind),
+                    route.gateway,
+                    route.route,
+                    serde_json::to_string(&route.methods)?,
+                    route.upstream,
+                    route.upstream_url,
+                    route.protocol,
+                    route.file_path,
+                    route.line_number.map(|n| n as i32),
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredGatewayRoute>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, gateway, route, methods, upstream, upstream_url, protocol, file_path, line_number, created_at
+             FROM gateway_routes WHERE repository_id = ?1 ORDER BY gateway, route"
+        )?;
+
+        let routes = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredGatewayRoute {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                kind: row.get(2)?,
+                gateway: row.get(3)?,
+                route: row.get(4)?,
+                methods: serde_json::from_str(&row.get::<_, String>(5)?).unwrap_or_default(),
+                upstream: row.get(6)?,
+                upstream_url: row.get(7)?,
+                protocol: row.get(8)?,
+                file_path: row.get(9)?,
+                line_number: row.get::<_, Option<i32>>(10)?.map(|n| n as usize),
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(routes)
+    }
+
+    fn kind_to_string(&self, kind: &GatewayKind) -> String {
+        match kind {
+            GatewayKind::Kong => "kong",
+            GatewayKind::ApolloFederation => "apollo_federation",
+            GatewayKind::AwsApiGateway => "aws_api_gateway",
+        }.to_string()
+    }
+}
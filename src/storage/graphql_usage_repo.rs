@@ -0,0 +1,180 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{GraphQLUsage, GraphQLOperationType};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredGraphQLOperation {
+    pub id: String,
+    pub repository_id: String,
+    pub name: Option<String>,
+    pub operation_type: String,
+    pub file_path: String,
+    pub line_number: usize,
+    pub root_fields: Vec<String>,
+    pub fields_used: Vec<String>,
+    pub types_used: Vec<String>,
+    pub fragment_spreads: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredGraphQLSchemaType {
+    pub id: String,
+    pub repository_id: String,
+    pub name: String,
+    pub kind: String,
+    pub file_path: String,
+    pub fields: HashMap<String, String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct GraphQLUsageRepository {
+    db: Database,
+}
+
+impl GraphQLUsageRepository {
+    pub fn new(db: Database) -> Self {
+        GraphQLUsageRepository { db }
+    }
+
+    pub fn store_usage(&self, repository_id: &str, usage: &GraphQLUsage) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing schema types and operations for this repository
+        conn.execute("DELETE FROM graphql_schema_types WHERE repository_id = ?1", params![repository_id])?;
+        conn.execute("DELETE FROM graphql_operations WHERE repository_id = ?1", params![repository_id])?;
+
+        let now = Utc::now().to_rfc3339();
+        for schema_type in &usage.schema_types {
+            conn.execute(
+                "INSERT INTO graphql_schema_types (id, repository_id, name, kind, file_path, fields, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    schema_type.name,
+                    schema_type.kind,
+                    schema_type.file_path,
+                    serde_json::to_string(&schema_type.fields)?,
+                    now
+                ],
+            )?;
+        }
+
+        for operation in &usage.operations {
+            conn.execute(
+                "INSERT INTO graphql_operations
+                 (id, repository_id, name, operation_type, file_path, line_number, root_fields, fields_used, types_used, fragment_spreads, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    operation.name,
+                    self.operation_type_to_string(&operation.operation_type),
+                    operation.file_path,
+                    operation.line_number as i32,
+                    serde_json::to_string(&operation.root_fields)?,
+                    serde_json::to_string(&operation.fields_used)?,
+                    serde_json::to_string(&operation.types_used)?,
+                    serde_json::to_string(&operation.fragment_spreads)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_operations(&self, repository_id: &str) -> Result<Vec<StoredGraphQLOperation>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, operation_type, file_path, line_number, root_fields, fields_used, types_used, fragment_spreads, created_at
+             FROM graphql_operations WHERE repository_id = ?1 ORDER BY file_path, line_number"
+        )?;
+
+        let operations = stmt.query_map(params![repository_id], |row| {
+            let root_fields: String = row.get(6)?;
+            let fields_used: String = row.get(7)?;
+            let types_used: String = row.get(8)?;
+            let fragment_spreads: String = row.get(9)?;
+            Ok(StoredGraphQLOperation {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                operation_type: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, i32>(5)? as usize,
+                root_fields: serde_json::from_str(&root_fields).unwrap_or_default(),
+                fields_used: serde_json::from_str(&fields_used).unwrap_or_default(),
+                types_used: serde_json::from_str(&types_used).unwrap_or_default(),
+                fragment_spreads: serde_json::from_str(&fragment_spreads).unwrap_or_default(),
+                created_at: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(operations)
+    }
+
+    pub fn get_schema_types(&self, repository_id: &str) -> Result<Vec<StoredGraphQLSchemaType>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, name, kind, file_path, fields, created_at
+             FROM graphql_schema_types WHERE repository_id = ?1 ORDER BY name"
+        )?;
+
+        let types = stmt.query_map(params![repository_id], |row| {
+            let fields: String = row.get(5)?;
+            Ok(StoredGraphQLSchemaType {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                kind: row.get(3)?,
+                file_path: row.get(4)?,
+                fields: serde_json::from_str(&fields).unwrap_or_default(),
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(types)
+    }
+
+    /// Operations affected by a change to a schema type, or to one of its fields.
+    /// Operations spreading an affected fragment are included too.
+    pub fn get_operations_using(&self, repository_id: &str, type_name: &str, field: Option<&str>) -> Result<Vec<StoredGraphQLOperation>> {
+        let operations = self.get_operations(repository_id)?;
+        let uses = |op: &StoredGraphQLOperation| match field {
+            Some(f) => op.fields_used.iter().any(|u| u == &format!("{}.{}", type_name, f)),
+            None => op.types_used.iter().any(|t| t == type_name),
+        };
+
+        let affected_fragments: Vec<String> = operations.iter()
+            .filter(|op| op.operation_type == "fragment" && uses(op))
+            .filter_map(|op| op.name.clone())
+            .collect();
+
+        Ok(operations.into_iter()
+            .filter(|op| uses(op) || op.fragment_spreads.iter().any(|s| affected_fragments.contains(s)))
+            .collect())
+    }
+
+    fn operation_type_to_string(&self, operation_type: &GraphQLOperationType) -> String {
+        match operation_type {
+            GraphQLOperationType::Query => "query",
+            GraphQLOperationType::Mutation => "mutation",
+            GraphQLOperationType::Subscription => "subscription",
+            GraphQLOperationType::Fragment => "fragment",
+        }.to_string()
+    }
+}
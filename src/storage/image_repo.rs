@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::ingestion::image_registry::ImageMetadata;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredImageVulnerability {
+    pub vulnerability_id: String,
+    pub severity: String,
+    pub package: Option<String>,
+    pub installed_version: Option<String>,
+    pub fixed_version: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredImageMetadata {
+    pub id: String,
+    pub repository_id: String,
+    pub image: String,
+    pub registry: String,
+    pub digest: Option<String>,
+    pub platform: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub layers: Vec<String>,
+    pub base_image: Option<String>,
+    pub image_created: Option<String>,
+    pub error: Option<String>,
+    pub fetched_at: String,
+    pub vulnerabilities: Vec<StoredImageVulnerability>,
+}
+
+#[derive(Clone)]
+pub struct ImageRepository {
+    db: Database,
+}
+
+impl ImageRepository {
+    pub fn new(db: Database) -> Self {
+        ImageRepository { db }
+    }
+
+    pub fn store_metadata(&self, repository_id: &str, images: &[ImageMetadata]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing image metadata for this repository
+        conn.execute(
+            "DELETE FROM image_metadata WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+        conn.execute(
+            "DELETE FROM image_vulnerabilities WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for image in images {
+            conn.execute(
+                "INSERT INTO image_metadata
+                 (id, repository_id, image, registry, digest, platform, size_bytes, layers, base_image, image_created, error, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    image.image,
+                    image.registry,
+                    image.digest,
+                    image.platform,
+                    image.size_bytes.map(|n| n as i64),
+                    serde_json::to_string(&image.layers)?,
+                    image.base_image,
+                    image.created,
+                    image.error,
+                    now
+                ],
+            )?;
+            for vulnerability in &image.vulnerabilities {
+                conn.execute(
+                    "INSERT INTO image_vulnerabilities
+                     (id, repository_id, image, vulnerability_id, severity, package, installed_version, fixed_version)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        repository_id,
+                        image.image,
+                        vulnerability.id,
+                        vulnerability.severity,
+                        vulnerability.package,
+                        vulnerability.installed_version,
+                        vulnerability.fixed_version
+                    ],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Largest images first, each with its known vulnerabilities
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredImageMetadata>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, image, registry, digest, platform, size_bytes, layers, base_image, image_created, error, fetched_at
+             FROM image_metadata WHERE repository_id = ?1 ORDER BY size_bytes IS NULL, size_bytes DESC, image"
+        )?;
+        let mut images = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredImageMetadata {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                image: row.get(2)?,
+                registry: row.get(3)?,
+                digest: row.get(4)?,
+                platform: row.get(5)?,
+                size_bytes: row.get::<_, Option<i64>>(6)?.map(|n| n as u64),
+                layers: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                base_image: row.get(8)?,
+                image_created: row.get(9)?,
+                error: row.get(10)?,
+                fetched_at: row.get(11)?,
+                vulnerabilities: Vec::new(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT image, vulnerability_id, severity, package, installed_version, fixed_version
+             FROM image_vulnerabilities WHERE repository_id = ?1
+             ORDER BY CASE severity WHEN 'critical' THEN 0 WHEN 'high' THEN 1 WHEN 'medium' THEN 2 ELSE 3 END, vulnerability_id"
+        )?;
+        let vulnerabilities = stmt.query_map(params![repository_id], |row| {
+            Ok((row.get::<_, String>(0)?, StoredImageVulnerability {
+                vulnerability_id: row.get(1)?,
+                severity: row.get(2)?,
+                package: row.get(3)?,
+                installed_version: row.get(4)?,
+                fixed_version: row.get(5)?,
+            }))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        for (image, vulnerability) in vulnerabilities {
+            if let Some(stored) = images.iter_mut().find(|i| i.image == image) {
+                stored.vulnerabilities.push(vulnerability);
+            }
+        }
+
+        Ok(images)
+    }
+}
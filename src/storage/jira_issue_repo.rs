@@ -0,0 +1,75 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use crate::storage::Database;
+
+/// A Jira issue opened for a vulnerability, keyed by the finding's fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueLink {
+    pub fingerprint: String,
+    pub repository_id: String,
+    pub issue_key: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Clone)]
+pub struct JiraIssueRepository {
+    db: Database,
+}
+
+impl JiraIssueRepository {
+    pub fn new(db: Database) -> Self {
+        JiraIssueRepository { db }
+    }
+
+    pub fn find_issue_key(&self, fingerprint: &str) -> Result<Option<String>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT issue_key FROM jira_issues WHERE fingerprint = ?1",
+            params![fingerprint],
+            |row| row.get(0),
+        ).optional()?)
+    }
+
+    /// Records a new link, or touches `updated_at` when the fingerprint is already linked
+    pub fn record(&self, fingerprint: &str, repository_id: &str, issue_key: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO jira_issues (fingerprint, repository_id, issue_key, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(fingerprint) DO UPDATE SET repository_id = ?2, issue_key = ?3, updated_at = ?4",
+            params![fingerprint, repository_id, issue_key, now],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<JiraIssueLink>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT fingerprint, repository_id, issue_key, created_at, updated_at
+             FROM jira_issues WHERE repository_id = ?1 ORDER BY created_at"
+        )?;
+        let links = stmt.query_map(params![repository_id], |row| {
+            Ok(JiraIssueLink {
+                fingerprint: row.get(0)?,
+                repository_id: row.get(1)?,
+                issue_key: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links)
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use std::collections::HashMap;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::KubernetesResource;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredKubernetesResource {
+    pub id: String,
+    pub repository_id: String,
+    pub kind: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub labels: HashMap<String, String>,
+    pub selector: HashMap<String, String>,
+    pub images: Vec<String>,
+    pub ports: Vec<u16>,
+    pub hosts: Vec<String>,
+    pub backend_services: Vec<String>,
+    pub config_refs: Vec<String>,
+    pub built_images: Vec<String>,
+    pub created_at: String,
+}
+
+impl StoredKubernetesResource {
+    /// Convert back into the analysis representation (used to compute topology)
+    pub fn to_resource(&self) -> KubernetesResource {
+        KubernetesResource {
+            kind: self.kind.clone(),
+            name: self.name.clone(),
+            namespace: self.namespace.clone(),
+            file_path: self.file_path.clone(),
+            line_number: self.line_number,
+            labels: self.labels.clone(),
+            selector: self.selector.clone(),
+            images: self.images.clone(),
+            ports: self.ports.clone(),
+            hosts: self.hosts.clone(),
+            backend_services: self.backend_services.clone(),
+            config_refs: self.config_refs.clone(),
+            built_images: self.built_images.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct KubernetesRepository {
+    db: Database,
+}
+
+impl KubernetesRepository {
+    pub fn new(db: Database) -> Self {
+        KubernetesRepository { db }
+    }
+
+    pub fn store_resources(&self, repository_id: &str, resources: &[KubernetesResource]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing resources for this repository
+        conn.execute(
+            "DELETE FROM kubernetes_resources WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for resource in resources {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO kubernetes_resources
+                 (id, repository_id, kind, name, namespace, file_path, line_number, labels, selector, images, ports, hosts, backend_services, config_refs, built_images, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    id,
+                    repository_id,
+                    resource.kind,
+                    resource.name,
+                    resource.namespace,
+                    resource.file_path,
+                    resource.line_number.map(|n| n as i32),
+                    serde_json::to_string(&resource.labels)?,
+                    serde_json::to_string(&resource.selector)?,
+                    serde_json::to_string(&resource.images)?,
+                    serde_json::to_string(&resource.ports)?,
+                    serde_json::to_string(&resource.hosts)?,
+                    serde_json::to_string(&resource.backend_services)?,
+                    serde_json::to_string(&resource.config_refs)?,
+                    serde_json::to_string(&resource.built_images)?,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredKubernetesResource>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, name, namespace, file_path, line_number, labels, selector, images, ports, hosts, backend_services, config_refs, built_images, created_at
+             FROM kubernetes_resources WHERE repository_id = ?1 ORDER BY kind, name"
+        )?;
+
+        let resources = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredKubernetesResource {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                kind: row.get(2)?,
+                name: row.get(3)?,
+                namespace: row.get(4)?,
+                file_path: row.get(5)?,
+                line_number: row.get::<_, Option<i32>>(6)?.map(|n| n as usize),
+                labels: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                selector: serde_json::from_str(&row.get::<_, String>(8)?).unwrap_or_default(),
+                images: serde_json::from_str(&row.get::<_, String>(9)?).unwrap_or_default(),
+                ports: serde_json::from_str(&row.get::<_, String>(10)?).unwrap_or_default(),
+                hosts: serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default(),
+                backend_services: serde_json::from_str(&row.get::<_, String>(12)?).unwrap_or_default(),
+                config_refs: serde_json::from_str(&row.get::<_, String>(13)?).unwrap_or_default(),
+                built_images: serde_json::from_str(&row.get::<_, String>(14)?).unwrap_or_default(),
+                created_at: row.get(15)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(resources)
+    }
+}
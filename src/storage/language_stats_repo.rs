@@ -0,0 +1,69 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::LanguageStat;
+
+#[derive(Clone)]
+pub struct LanguageStatsRepository {
+    db: Database,
+}
+
+impl LanguageStatsRepository {
+    pub fn new(db: Database) -> Self {
+        LanguageStatsRepository { db }
+    }
+
+    pub fn store_stats(&self, repository_id: &str, stats: &[LanguageStat]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing stats for this repository
+        conn.execute(
+            "DELETE FROM language_stats WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for stat in stats {
+            conn.execute(
+                "INSERT INTO language_stats (id, repository_id, language, bytes, files, percentage, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    stat.language,
+                    stat.bytes as i64,
+                    stat.files as i64,
+                    stat.percentage,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<LanguageStat>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT language, bytes, files, percentage
+             FROM language_stats WHERE repository_id = ?1 ORDER BY bytes DESC"
+        )?;
+
+        let stats = stmt.query_map(params![repository_id], |row| {
+            Ok(LanguageStat {
+                language: row.get(0)?,
+                bytes: row.get::<_, i64>(1)? as u64,
+                files: row.get::<_, i64>(2)? as usize,
+                percentage: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(stats)
+    }
+}
@@ -15,6 +15,46 @@ pub mod documentation_repo;
 pub mod test_repo;
 pub mod port_repo;
 pub mod endpoint_repo;
+pub mod kubernetes_repo;
+pub mod gateway_repo;
+pub mod build_target_repo;
+pub mod data_pipeline_repo;
+pub mod pinning_repo;
+pub mod pipeline_repo;
+pub mod framework_repo;
+pub mod entrypoint_repo;
+pub mod background_job_repo;
+pub mod notification_repo;
+pub mod payment_repo;
+pub mod privacy_repo;
+pub mod observability_repo;
+pub mod cost_repo;
+pub mod environment_repo;
+pub mod artifact_repo;
+pub mod image_repo;
+pub mod language_stats_repo;
+pub mod frontend_route_repo;
+pub mod coverage_repo;
+pub mod graphql_usage_repo;
+pub mod boundary_repo;
+pub mod architecture_repo;
+pub mod tech_debt_repo;
+pub mod todo_repo;
+pub mod adr_repo;
+pub mod embedding_repo;
+pub mod doc_quality_repo;
+pub mod doc_link_repo;
+pub mod release_repo;
+pub mod plugin_selection_repo;
+pub mod plugin_report_repo;
+pub mod workspace_repo;
+pub mod jira_issue_repo;
+pub mod analysis_run_repo;
+pub mod repository_link_repo;
+pub mod system_repo;
+pub mod false_positive_repo;
+pub mod schedule_repo;
+pub mod webhook_repo;
 // UserRepository and ApiKeyRepository kept for database schema but not exported (auth removed)
 pub use repository_repo::{RepositoryRepository, DependencyRepository, Repository, StoredDependency};
 pub use service_repo::{ServiceRepository, StoredService};
@@ -26,6 +66,46 @@ pub use documentation_repo::DocumentationRepository;
 pub use test_repo::TestRepository;
 pub use port_repo::{PortRepository, StoredPort};
 pub use endpoint_repo::{EndpointRepository, StoredEndpoint};
+pub use kubernetes_repo::{KubernetesRepository, StoredKubernetesResource};
+pub use gateway_repo::{GatewayRepository, StoredGatewayRoute};
+pub use build_target_repo::{BuildTargetRepository, StoredBuildTarget};
+pub use data_pipeline_repo::DataPipelineRepository;
+pub use pinning_repo::PinningRepository;
+pub use pipeline_repo::{PipelineRepository, StoredPipeline};
+pub use framework_repo::{FrameworkRepository, StoredFramework};
+pub use entrypoint_repo::{EntrypointRepository, StoredEntrypoint};
+pub use background_job_repo::{BackgroundJobRepository, StoredBackgroundJob};
+pub use notification_repo::{NotificationRepository, StoredNotificationConfig};
+pub use payment_repo::{PaymentRepository, StoredPaymentFlow};
+pub use privacy_repo::{PrivacyRepository, StoredPiiField, StoredPiiFlow};
+pub use observability_repo::{ObservabilityRepository, StoredObservabilityComponent, StoredObservabilityCoverage};
+pub use cost_repo::{CostRepository, StoredResourceCost};
+pub use environment_repo::{EnvironmentRepository, StoredEnvironmentValue};
+pub use artifact_repo::{ArtifactRepository, StoredArtifact, StoredHygieneFinding};
+pub use image_repo::{ImageRepository, StoredImageMetadata, StoredImageVulnerability};
+pub use language_stats_repo::LanguageStatsRepository;
+pub use frontend_route_repo::{FrontendRouteRepository, StoredFrontendRoute};
+pub use coverage_repo::{CoverageRepository, StoredFileCoverage};
+pub use graphql_usage_repo::{GraphQLUsageRepository, StoredGraphQLOperation};
+pub use boundary_repo::{ServiceBoundaryRepository, StoredServiceBoundary};
+pub use architecture_repo::{ArchitectureRepository, StoredArchitectureFinding, StoredArchitectureSpec};
+pub use tech_debt_repo::{TechDebtRepository, StoredTechDebtScore};
+pub use todo_repo::{TodoRepository, StoredTodo, TodoSummary};
+pub use adr_repo::{AdrRepository, StoredArchitectureDecision};
+pub use embedding_repo::{EmbeddingRepository, EmbeddingEntry, SemanticMatch};
+pub use doc_quality_repo::{DocQualityRepository, StoredDocQuality};
+pub use doc_link_repo::{DocLinkRepository, StoredDocLink};
+pub use release_repo::ReleaseRepository;
+pub use plugin_selection_repo::PluginSelectionRepository;
+pub use plugin_report_repo::{PluginReportRepository, StoredReportSection};
+pub use workspace_repo::{WorkspaceRepository, Workspace, DEFAULT_WORKSPACE};
+pub use jira_issue_repo::{JiraIssueRepository, JiraIssueLink};
+pub use analysis_run_repo::AnalysisRunRepository;
+pub use repository_link_repo::RepositoryLinkRepository;
+pub use system_repo::{SystemRepository, System};
+pub use false_positive_repo::{FalsePositiveRepository, FalsePositive};
+pub use schedule_repo::{ScheduleRepository, AnalysisSchedule, ScheduledRun, RunStatus};
+pub use webhook_repo::{WebhookRepository, Webhook, WebhookKind};
 
 #[derive(Clone)]
 pub struct Database {
@@ -76,7 +156,9 @@ impl Database {
                 auth_value TEXT,
                 last_analyzed_at TEXT,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                analysis_profile TEXT,
+                workspace_id TEXT NOT NULL DEFAULT 'default'
             )",
             [],
         )?;
@@ -96,8 +178,29 @@ impl Database {
             if !columns.iter().any(|c| c == "auth_value") {
                 conn.execute("ALTER TABLE repositories ADD COLUMN auth_value TEXT", [])?;
             }
+            if !columns.iter().any(|c| c == "analysis_profile") {
+                conn.execute("ALTER TABLE repositories ADD COLUMN analysis_profile TEXT", [])?;
+            }
+            if !columns.iter().any(|c| c == "workspace_id") {
+                conn.execute("ALTER TABLE repositories ADD COLUMN workspace_id TEXT NOT NULL DEFAULT 'default'", [])?;
+            }
         }
 
+        // Workspaces: each team's repositories, and everything derived from them, are kept apart
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS workspaces (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                token_hash TEXT UNIQUE,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO workspaces (id, name, token_hash, created_at) VALUES ('default', 'Default', NULL, ?1)",
+            [chrono::Utc::now().to_rfc3339()],
+        )?;
+
         // Graph nodes table (for knowledge graph)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS graph_nodes (
@@ -157,11 +260,20 @@ impl Database {
                 line_number INTEGER,
                 confidence REAL NOT NULL,
                 created_at TEXT NOT NULL,
+                evidence TEXT NOT NULL DEFAULT '[]',
                 FOREIGN KEY (repository_id) REFERENCES repositories(id)
             )",
             [],
         )?;
 
+        // Migration: services list the evidence they were aggregated from
+        let service_columns: Vec<String> = conn.prepare("PRAGMA table_info(services)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        if !service_columns.iter().any(|c| c == "evidence") {
+            conn.execute("ALTER TABLE services ADD COLUMN evidence TEXT NOT NULL DEFAULT '[]'", [])?;
+        }
+
         // Code elements table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS code_elements (
@@ -177,12 +289,33 @@ impl Database {
                 visibility TEXT,
                 parameters TEXT,
                 return_type TEXT,
+                parent_id TEXT,
+                qualified_name TEXT,
+                decorators TEXT,
+                origin TEXT NOT NULL DEFAULT 'authored',
                 created_at TEXT NOT NULL,
                 FOREIGN KEY (repository_id) REFERENCES repositories(id)
             )",
             [],
         )?;
 
+        // Migration: methods link to their containing class/struct, elements carry a qualified name, decorators and origin
+        let code_element_columns: Vec<String> = conn.prepare("PRAGMA table_info(code_elements)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        if !code_element_columns.iter().any(|c| c == "parent_id") {
+            conn.execute("ALTER TABLE code_elements ADD COLUMN parent_id TEXT", [])?;
+        }
+        if !code_element_columns.iter().any(|c| c == "qualified_name") {
+            conn.execute("ALTER TABLE code_elements ADD COLUMN qualified_name TEXT", [])?;
+        }
+        if !code_element_columns.iter().any(|c| c == "decorators") {
+            conn.execute("ALTER TABLE code_elements ADD COLUMN decorators TEXT", [])?;
+        }
+        if !code_element_columns.iter().any(|c| c == "origin") {
+            conn.execute("ALTER TABLE code_elements ADD COLUMN origin TEXT NOT NULL DEFAULT 'authored'", [])?;
+        }
+
         // Code calls table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS code_calls (
@@ -250,12 +383,24 @@ impl Database {
                 file_path TEXT NOT NULL,
                 line_number INTEGER,
                 created_at TEXT NOT NULL,
+                affected_range TEXT,
+                fixed_version TEXT,
                 FOREIGN KEY (repository_id) REFERENCES repositories(id),
                 FOREIGN KEY (entity_id) REFERENCES security_entities(id)
             )",
             [],
         )?;
 
+        // Migration: dependency advisories record the affected versions and the fixing release
+        let vulnerability_columns: Vec<String> = conn.prepare("PRAGMA table_info(security_vulnerabilities)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        for column in ["affected_range", "fixed_version"] {
+            if !vulnerability_columns.iter().any(|c| c == column) {
+                conn.execute(&format!("ALTER TABLE security_vulnerabilities ADD COLUMN {} TEXT", column), [])?;
+            }
+        }
+
         // Tools table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS tools (
@@ -406,124 +551,1071 @@ impl Database {
                 middleware TEXT NOT NULL,
                 parameters TEXT NOT NULL,
                 created_at TEXT NOT NULL,
+                protocol TEXT NOT NULL DEFAULT 'http',
                 FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
             )",
             [],
         )?;
 
-        // Create indexes
+        // Migration: endpoints record whether they speak plain HTTP, WebSocket or server-sent events
+        let endpoint_columns: Vec<String> = conn.prepare("PRAGMA table_info(endpoints)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        if !endpoint_columns.iter().any(|c| c == "protocol") {
+            conn.execute("ALTER TABLE endpoints ADD COLUMN protocol TEXT NOT NULL DEFAULT 'http'", [])?;
+        }
+
+        // Kubernetes resources table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_graph_nodes_repository ON graph_nodes(repository_id)",
+            "CREATE TABLE IF NOT EXISTS kubernetes_resources (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                namespace TEXT,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                labels TEXT NOT NULL,
+                selector TEXT NOT NULL,
+                images TEXT NOT NULL,
+                ports TEXT NOT NULL,
+                hosts TEXT NOT NULL,
+                backend_services TEXT NOT NULL,
+                config_refs TEXT NOT NULL,
+                built_images TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // CI/CD pipelines table (jobs stored as JSON)
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_graph_edges_source ON graph_edges(source_node_id)",
+            "CREATE TABLE IF NOT EXISTS ci_pipelines (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                platform TEXT NOT NULL,
+                name TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                triggers TEXT NOT NULL,
+                stages TEXT NOT NULL,
+                jobs TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Frameworks and runtimes table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_graph_edges_target ON graph_edges(target_node_id)",
+            "CREATE TABLE IF NOT EXISTS frameworks (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                category TEXT NOT NULL,
+                language TEXT NOT NULL,
+                version TEXT,
+                file_path TEXT NOT NULL,
+                confidence REAL NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Entrypoints table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_dependencies_repository ON dependencies(repository_id)",
+            "CREATE TABLE IF NOT EXISTS entrypoints (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                entrypoint_type TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                command TEXT,
+                target_file TEXT,
+                handler TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Background jobs table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_dependencies_name ON dependencies(name)",
+            "CREATE TABLE IF NOT EXISTS background_jobs (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                framework TEXT NOT NULL,
+                trigger_type TEXT NOT NULL,
+                schedule TEXT,
+                queue TEXT,
+                handler TEXT,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Notification provider configuration table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tools_repository ON tools(repository_id)",
+            "CREATE TABLE IF NOT EXISTS notification_configs (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                channel TEXT NOT NULL,
+                setting TEXT NOT NULL,
+                value TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Payment flow table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tools_category ON tools(category)",
+            "CREATE TABLE IF NOT EXISTS payment_flows (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                value TEXT NOT NULL,
+                endpoint TEXT,
+                handler TEXT,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // PII field and flow tables
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tool_scripts_tool ON tool_scripts(tool_id)",
+            "CREATE TABLE IF NOT EXISTS pii_fields (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                category TEXT NOT NULL,
+                model TEXT NOT NULL,
+                field TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tool_relationships_tool ON tool_relationships(tool_id)",
+            "CREATE TABLE IF NOT EXISTS pii_flows (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                category TEXT NOT NULL,
+                field TEXT NOT NULL,
+                sink TEXT NOT NULL,
+                destination TEXT NOT NULL,
+                evidence TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                language TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Observability library, exporter and coverage tables
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tool_relationships_target ON tool_relationships(target_type, target_id)",
+            "CREATE TABLE IF NOT EXISTS observability_components (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                component TEXT NOT NULL,
+                name TEXT NOT NULL,
+                signal TEXT NOT NULL,
+                structured INTEGER NOT NULL DEFAULT 0,
+                endpoint TEXT,
+                service TEXT,
+                file_count INTEGER NOT NULL DEFAULT 1,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_relationships_repository ON code_relationships(repository_id)",
+            "CREATE TABLE IF NOT EXISTS observability_coverage (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                service TEXT NOT NULL,
+                paths TEXT NOT NULL,
+                logging TEXT NOT NULL,
+                metrics TEXT NOT NULL,
+                tracing TEXT NOT NULL,
+                structured_logging INTEGER NOT NULL DEFAULT 0,
+                gaps TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // IaC resource cost estimate table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_relationships_element ON code_relationships(code_element_id)",
+            "CREATE TABLE IF NOT EXISTS resource_costs (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                resource_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                category TEXT NOT NULL,
+                size TEXT,
+                count INTEGER NOT NULL DEFAULT 1,
+                monthly_cost REAL,
+                oversized TEXT,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Per-environment settings table (environment matrix)
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_relationships_target ON code_relationships(target_type, target_id)",
+            "CREATE TABLE IF NOT EXISTS environment_values (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                environment TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                source TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Committed artifact inventory tables
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_documentation_repository ON documentation(repository_id)",
+            "CREATE TABLE IF NOT EXISTS artifacts (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                size_bytes INTEGER,
+                sha256 TEXT,
+                file_count INTEGER,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_documentation_type ON documentation(doc_type)",
+            "CREATE TABLE IF NOT EXISTS hygiene_findings (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                rule TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                message TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Container image registry metadata tables
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tests_repository ON tests(repository_id)",
+            "CREATE TABLE IF NOT EXISTS image_metadata (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                image TEXT NOT NULL,
+                registry TEXT NOT NULL,
+                digest TEXT,
+                platform TEXT,
+                size_bytes INTEGER,
+                layers TEXT NOT NULL,
+                base_image TEXT,
+                image_created TEXT,
+                error TEXT,
+                fetched_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tests_framework ON tests(test_framework)",
+            "CREATE TABLE IF NOT EXISTS image_vulnerabilities (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                image TEXT NOT NULL,
+                vulnerability_id TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                package TEXT,
+                installed_version TEXT,
+                fixed_version TEXT,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // API gateway route table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_tests_language ON tests(language)",
+            "CREATE TABLE IF NOT EXISTS gateway_routes (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                gateway TEXT NOT NULL,
+                route TEXT NOT NULL,
+                methods TEXT NOT NULL,
+                upstream TEXT NOT NULL,
+                upstream_url TEXT,
+                protocol TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Build target graph table
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_dependencies_package_manager ON dependencies(package_manager)",
+            "CREATE TABLE IF NOT EXISTS build_targets (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                runner TEXT NOT NULL,
+                name TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                commands TEXT NOT NULL,
+                depends_on TEXT NOT NULL,
+                tools TEXT NOT NULL,
+                ci_jobs TEXT NOT NULL,
+                dependents INTEGER NOT NULL,
+                ci_reach INTEGER NOT NULL,
+                unused INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Data pipelines table (Airflow DAGs, dbt projects, Dagster jobs/assets, Prefect flows)
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_services_repository ON services(repository_id)",
+            "CREATE TABLE IF NOT EXISTS data_pipelines (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                orchestrator TEXT NOT NULL,
+                name TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                schedule TEXT,
+                triggered_by TEXT NOT NULL,
+                tasks TEXT NOT NULL,
+                warehouse TEXT,
+                sources TEXT NOT NULL DEFAULT '[]',
+                exposures TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Migration: dbt projects record their warehouse adapter, sources and exposures
+        let data_pipeline_columns: Vec<String> = conn.prepare("PRAGMA table_info(data_pipelines)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        if !data_pipeline_columns.iter().any(|c| c == "warehouse") {
+            conn.execute("ALTER TABLE data_pipelines ADD COLUMN warehouse TEXT", [])?;
+            conn.execute("ALTER TABLE data_pipelines ADD COLUMN sources TEXT NOT NULL DEFAULT '[]'", [])?;
+            conn.execute("ALTER TABLE data_pipelines ADD COLUMN exposures TEXT NOT NULL DEFAULT '[]'", [])?;
+        }
+
+        // Version pinning audit table (one report per repository)
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_services_provider ON services(provider)",
+            "CREATE TABLE IF NOT EXISTS pinning_audits (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                score REAL NOT NULL,
+                report TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Progress of the latest analysis per repository, kept so a restart can report and resume it
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_services_type ON services(service_type)",
+            "CREATE TABLE IF NOT EXISTS analysis_runs (
+                repository_id TEXT PRIMARY KEY,
+                step_name TEXT NOT NULL,
+                progress TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Recurring re-analyses, one per repository, and the history of their runs
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_elements_repository ON code_elements(repository_id)",
+            "CREATE TABLE IF NOT EXISTS analysis_schedules (
+                repository_id TEXT PRIMARY KEY,
+                cron TEXT NOT NULL,
+                profile TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                last_run_at TEXT,
+                next_run_at TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_elements_type ON code_elements(element_type)",
+            "CREATE TABLE IF NOT EXISTS scheduled_runs (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                cron TEXT NOT NULL,
+                scheduled_for TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                completed_at TEXT,
+                status TEXT NOT NULL,
+                error TEXT,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_elements_language ON code_elements(language)",
+            "CREATE INDEX IF NOT EXISTS idx_scheduled_runs_repository ON scheduled_runs(repository_id, started_at)",
             [],
         )?;
+
+        // Step outputs of an unfinished analysis, reused when it is resumed at the same revision
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_calls_repository ON code_calls(repository_id)",
+            "CREATE TABLE IF NOT EXISTS analysis_checkpoints (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                revision TEXT NOT NULL,
+                name TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Remote and commit identity per repository, compared to find forks and mirrors
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_calls_caller ON code_calls(caller_id)",
+            "CREATE TABLE IF NOT EXISTS repository_fingerprints (
+                repository_id TEXT PRIMARY KEY,
+                normalized_url TEXT NOT NULL,
+                branch TEXT NOT NULL,
+                head_commit TEXT,
+                root_commits TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
             [],
         )?;
+
+        // Fork and mirror links between repositories, one row per direction
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_code_calls_callee ON code_calls(callee_id)",
-            [],
-        )?;
+            "CREATE TABLE IF NOT EXISTS repository_links (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                related_repository_id TEXT NOT NULL,
+                relation TEXT NOT NULL,
+                evidence TEXT NOT NULL,
+                same_head INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE,
+                FOREIGN KEY (related_repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Systems: named groups of a workspace's repositories whose graphs are joined
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS systems (
+                id TEXT PRIMARY KEY,
+                workspace_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS system_repositories (
+                system_id TEXT NOT NULL,
+                repository_id TEXT NOT NULL,
+                PRIMARY KEY (system_id, repository_id),
+                FOREIGN KEY (system_id) REFERENCES systems(id) ON DELETE CASCADE,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // False-positive verdicts users gave on detections, matched by fingerprint on later analyses
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS false_positives (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                detail TEXT NOT NULL,
+                file_path TEXT,
+                confidence REAL,
+                reason TEXT,
+                created_at TEXT NOT NULL,
+                UNIQUE (repository_id, fingerprint),
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Where analysis notifications are posted, and the vulnerabilities the last one saw
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhooks (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                url TEXT NOT NULL,
+                secret TEXT,
+                on_completed INTEGER NOT NULL DEFAULT 1,
+                on_failed INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL,
+                last_delivery_at TEXT,
+                last_error TEXT,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS webhook_baselines (
+                repository_id TEXT PRIMARY KEY,
+                fingerprints TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Language statistics table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS language_stats (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                language TEXT NOT NULL,
+                bytes INTEGER NOT NULL,
+                files INTEGER NOT NULL,
+                percentage REAL NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Frontend routes table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS frontend_routes (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                path TEXT NOT NULL,
+                router TEXT NOT NULL,
+                component TEXT,
+                component_file TEXT,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                lazy INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Test coverage table (per file, line hits stored as JSON)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS coverage_files (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                format TEXT NOT NULL,
+                report_path TEXT NOT NULL,
+                source TEXT NOT NULL,
+                lines_found INTEGER NOT NULL,
+                lines_hit INTEGER NOT NULL,
+                branches_found INTEGER NOT NULL,
+                branches_hit INTEGER NOT NULL,
+                line_percentage REAL NOT NULL,
+                lines TEXT NOT NULL,
+                branches TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Test-to-code links
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS test_code_links (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                test_id TEXT NOT NULL,
+                code_element_id TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                confidence REAL NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // GraphQL schema types declared in SDL files
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS graphql_schema_types (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                fields TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // GraphQL operations embedded in client code
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS graphql_operations (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT,
+                operation_type TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER NOT NULL,
+                root_fields TEXT NOT NULL,
+                fields_used TEXT NOT NULL,
+                types_used TEXT NOT NULL,
+                fragment_spreads TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Inferred service/domain boundaries
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS service_boundaries (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                paths TEXT NOT NULL,
+                file_count INTEGER NOT NULL,
+                element_count INTEGER NOT NULL,
+                internal_references INTEGER NOT NULL,
+                outbound_references INTEGER NOT NULL,
+                cohesion REAL NOT NULL,
+                data_stores TEXT NOT NULL,
+                shared_data_stores TEXT NOT NULL,
+                depends_on TEXT NOT NULL,
+                confidence REAL NOT NULL,
+                evidence TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Monolith decomposition report per repository, with its candidate service extractions
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS decomposition_reports (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                report TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Files assigned to architecture layers (controller, service, repository, domain, port, adapter)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_layers (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                layer TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Architecture findings (layering violations and user-declared rule violations)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_findings (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                rule TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                source_file TEXT NOT NULL,
+                source_layer TEXT,
+                target_file TEXT NOT NULL,
+                target_layer TEXT,
+                line_number INTEGER NOT NULL,
+                message TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Target architecture uploaded for a repository, as written (YAML or JSON)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_specs (
+                repository_id TEXT PRIMARY KEY,
+                content TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                checked_at TEXT,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Deltas between the last analysis and the target architecture
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_conformance (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                expected TEXT NOT NULL,
+                actual TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                file_path TEXT,
+                line_number INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Technical debt scores, one row per analysis for trend charts
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tech_debt_scores (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                score REAL NOT NULL,
+                grade TEXT NOT NULL,
+                subscores TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // TODO/FIXME/HACK/XXX comment markers
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS todos (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                text TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER NOT NULL,
+                language TEXT,
+                author TEXT,
+                committed_at TEXT,
+                age_days INTEGER,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Architecture decision records parsed from docs/adr and MADR files
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_decisions (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                number INTEGER,
+                title TEXT NOT NULL,
+                status TEXT,
+                superseded_by TEXT,
+                date TEXT,
+                decision TEXT,
+                file_path TEXT NOT NULL,
+                format TEXT NOT NULL,
+                mentions TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Vectors for semantic search over documentation and code element signatures
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS embeddings (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                source_type TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                line_number INTEGER,
+                content TEXT NOT NULL,
+                vector BLOB NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Documentation quality report (latest only)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS doc_quality (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                score REAL NOT NULL,
+                grade TEXT NOT NULL,
+                report TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Links and code/service references found in documentation
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS doc_links (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                doc_path TEXT NOT NULL,
+                line_number INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                target TEXT NOT NULL,
+                target_file TEXT,
+                target_name TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Releases parsed from the changelog or fetched from GitHub
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS releases (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                version TEXT NOT NULL,
+                release_date TEXT,
+                title TEXT,
+                url TEXT,
+                sections TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Architecture snapshot per analysis, for the release timeline
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS architecture_snapshots (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                snapshot TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Per-repository plugin opt-ins and opt-outs
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS repository_plugins (
+                repository_id TEXT PRIMARY KEY,
+                enabled TEXT NOT NULL,
+                disabled TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Report sections contributed by plugins
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS plugin_report_sections (
+                id TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                plugin TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                format TEXT NOT NULL,
+                data TEXT NOT NULL,
+                template TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (repository_id) REFERENCES repositories(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Jira issues opened for vulnerabilities. Not tied to the repository row, so re-adding a
+        // repository finds its existing tickets instead of opening duplicates.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS jira_issues (
+                fingerprint TEXT PRIMARY KEY,
+                repository_id TEXT NOT NULL,
+                issue_key TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create indexes
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_repositories_workspace ON repositories(workspace_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_graph_nodes_repository ON graph_nodes(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_graph_edges_source ON graph_edges(source_node_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_graph_edges_target ON graph_edges(target_node_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_dependencies_repository ON dependencies(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_dependencies_name ON dependencies(name)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tools_repository ON tools(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tools_category ON tools(category)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tool_scripts_tool ON tool_scripts(tool_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tool_relationships_tool ON tool_relationships(tool_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tool_relationships_target ON tool_relationships(target_type, target_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_relationships_repository ON code_relationships(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_relationships_element ON code_relationships(code_element_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_relationships_target ON code_relationships(target_type, target_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_documentation_repository ON documentation(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_documentation_type ON documentation(doc_type)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tests_repository ON tests(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tests_framework ON tests(test_framework)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tests_language ON tests(language)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_dependencies_package_manager ON dependencies(package_manager)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_services_repository ON services(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_services_provider ON services(provider)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_services_type ON services(service_type)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_elements_repository ON code_elements(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_elements_type ON code_elements(element_type)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_elements_language ON code_elements(language)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_elements_qualified_name ON code_elements(repository_id, qualified_name)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_calls_repository ON code_calls(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_calls_caller ON code_calls(caller_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_code_calls_callee ON code_calls(callee_id)",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_security_entities_repository ON security_entities(repository_id)",
             [],
@@ -576,6 +1668,192 @@ impl Database {
             "CREATE INDEX IF NOT EXISTS idx_endpoints_framework ON endpoints(framework)",
             [],
         )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_kubernetes_resources_repository ON kubernetes_resources(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_kubernetes_resources_kind ON kubernetes_resources(kind)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_ci_pipelines_repository ON ci_pipelines(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_frameworks_repository ON frameworks(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_entrypoints_repository ON entrypoints(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_background_jobs_repository ON background_jobs(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_notification_configs_repository ON notification_configs(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_payment_flows_repository ON payment_flows(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_pii_fields_repository ON pii_fields(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_pii_flows_repository ON pii_flows(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_observability_components_repository ON observability_components(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_observability_coverage_repository ON observability_coverage(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_resource_costs_repository ON resource_costs(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_environment_values_repository ON environment_values(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_artifacts_repository ON artifacts(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_hygiene_findings_repository ON hygiene_findings(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_image_metadata_repository ON image_metadata(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_image_vulnerabilities_repository ON image_vulnerabilities(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_gateway_routes_repository ON gateway_routes(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_decomposition_reports_repository ON decomposition_reports(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_build_targets_repository ON build_targets(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_data_pipelines_repository ON data_pipelines(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_pinning_audits_repository ON pinning_audits(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_analysis_checkpoints_repository ON analysis_checkpoints(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_repository_links_repository ON repository_links(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_language_stats_repository ON language_stats(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_frontend_routes_repository ON frontend_routes(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_coverage_files_repository ON coverage_files(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_test_code_links_element ON test_code_links(repository_id, code_element_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_graphql_schema_types_repository ON graphql_schema_types(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_graphql_operations_repository ON graphql_operations(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_service_boundaries_repository ON service_boundaries(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_architecture_layers_repository ON architecture_layers(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_architecture_findings_repository ON architecture_findings(repository_id, source)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_architecture_conformance_repository ON architecture_conformance(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tech_debt_scores_repository ON tech_debt_scores(repository_id, created_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_todos_repository ON todos(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_architecture_decisions_repository ON architecture_decisions(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_embeddings_repository ON embeddings(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_embeddings_model ON embeddings(model)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_doc_quality_repository ON doc_quality(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_doc_links_repository ON doc_links(repository_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_doc_links_target_file ON doc_links(repository_id, target_file)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_releases_repository ON releases(repository_id, source)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_architecture_snapshots_repository ON architecture_snapshots(repository_id, created_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_plugin_report_sections_repository ON plugin_report_sections(repository_id)",
+            [],
+        )?;
+
+        scope_derived_tables(&conn)?;
 
         Ok(())
     }
@@ -585,6 +1863,43 @@ impl Database {
     }
 }
 
+/// Gives every table holding a repository's data the repository's `workspace_id`, filled in on
+/// insert by a trigger, so queries that span repositories can filter on the caller's workspace
+fn scope_derived_tables(conn: &Connection) -> Result<()> {
+    let tables: Vec<String> = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT IN ('repositories', 'workspaces')")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for table in tables {
+        let columns: Vec<String> = conn
+            .prepare(&format!("PRAGMA table_info({})", table))?
+            .query_map([], |row| row.get(1))?
+            .collect::<Result<_, _>>()?;
+        if !columns.iter().any(|c| c == "repository_id") {
+            continue;
+        }
+        if !columns.iter().any(|c| c == "workspace_id") {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN workspace_id TEXT", table), [])?;
+            conn.execute(
+                &format!("UPDATE {t} SET workspace_id = (SELECT workspace_id FROM repositories WHERE id = {t}.repository_id)", t = table),
+                [],
+            )?;
+        }
+        conn.execute(
+            &format!(
+                "CREATE TRIGGER IF NOT EXISTS {t}_workspace AFTER INSERT ON {t} FOR EACH ROW WHEN NEW.workspace_id IS NULL
+                 BEGIN
+                     UPDATE {t} SET workspace_id = (SELECT workspace_id FROM repositories WHERE id = NEW.repository_id) WHERE rowid = NEW.rowid;
+                 END",
+                t = table
+            ),
+            [],
+        )?;
+        conn.execute(&format!("CREATE INDEX IF NOT EXISTS idx_{t}_workspace ON {t}(workspace_id)", t = table), [])?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -602,5 +1917,45 @@ mod tests {
         let db = Database::new(&config).unwrap();
         assert!(db_path.exists());
     }
+
+    #[test]
+    fn test_workspaces_scope_repositories() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = DatabaseConfig {
+            database_path: temp_dir.path().join("test.db").to_str().unwrap().to_string(),
+            graph_db_path: temp_dir.path().join("graph.db").to_str().unwrap().to_string(),
+        };
+        let db = Database::new(&config).unwrap();
+        let workspaces = WorkspaceRepository::new(db.clone());
+        let repos = RepositoryRepository::new(db.clone());
+        let deps = DependencyRepository::new(db);
+
+        assert!(workspaces.find_by_id(DEFAULT_WORKSPACE).unwrap().is_some());
+        let team = workspaces.create("Payments", Some("payments-team-token")).unwrap();
+        assert_eq!(workspaces.find_by_token("payments-team-token").unwrap().unwrap().id, team.id);
+        assert!(workspaces.find_by_token("wrong-token").unwrap().is_none());
+
+        repos.create("shared", "https://example.com/shared.git", None, None, None, DEFAULT_WORKSPACE).unwrap();
+        let owned = repos.create("billing", "https://example.com/billing.git", None, None, None, &team.id).unwrap();
+        let listed = repos.list_by_workspace(&team.id).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, owned.id);
+        assert!(!repos.ids_in_workspace(DEFAULT_WORKSPACE).unwrap().contains(&owned.id));
+
+        // Derived rows take their repository's workspace, and cross-repository searches stay in it
+        let react = crate::analysis::PackageDependency {
+            name: "react".to_string(),
+            version: "18.2.0".to_string(),
+            package_manager: crate::analysis::PackageManager::Npm,
+            is_dev: false,
+            is_optional: false,
+        };
+        deps.store_dependencies(&owned.id, std::slice::from_ref(&react), "package.json").unwrap();
+        let found = deps.get_by_package_name("react", &team.id, None).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].repository_id, owned.id);
+        assert!(deps.get_by_package_name("react", DEFAULT_WORKSPACE, None).unwrap().is_empty());
+        assert!(deps.get_by_package_name("react", DEFAULT_WORKSPACE, Some(&owned.id)).unwrap().is_empty());
+    }
 }
 
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedNotificationConfig, NotificationChannel, NotificationSetting};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredNotificationConfig {
+    pub id: String,
+    pub repository_id: String,
+    pub provider: String,
+    pub channel: String,
+    pub setting: String,
+    pub value: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct NotificationRepository {
+    db: Database,
+}
+
+impl NotificationRepository {
+    pub fn new(db: Database) -> Self {
+        NotificationRepository { db }
+    }
+
+    pub fn store_configs(&self, repository_id: &str, configs: &[DetectedNotificationConfig]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing notification configs for this repository
+        conn.execute(
+            "DELETE FROM notification_configs WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for config in configs {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO notification_configs
+                 (id, repository_id, provider, channel, setting, value, file_path, line_number, language, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    id,
+                    repository_id,
+                    config.provider,
+                    self.channel_to_string(&config.channel),
+                    self.setting_to_string(&config.setting),
+                    config.value,
+                    config.file_path,
+                    config.line_number.map(|n| n as i32),
+                    config.language,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredNotificationConfig>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, provider, channel, setting, value, file_path, line_number, language, created_at
+             FROM notification_configs WHERE repository_id = ?1 ORDER BY provider, setting, value"
+        )?;
+
+        let configs = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredNotificationConfig {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                provider: row.get(2)?,
+                channel: row.get(3)?,
+                setting: row.get(4)?,
+                value: row.get(5)?,
+                file_path: row.get(6)?,
+                line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
+                language: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(configs)
+    }
+
+    fn channel_to_string(&self, channel: &NotificationChannel) -> String {
+        match channel {
+            NotificationChannel::Email => "email",
+            NotificationChannel::Sms => "sms",
+        }.to_string()
+    }
+
+    fn setting_to_string(&self, setting: &NotificationSetting) -> String {
+        match setting {
+            NotificationSetting::Sender => "sender",
+            NotificationSetting::Template => "template",
+            NotificationSetting::Webhook => "webhook",
+        }.to_string()
+    }
+}
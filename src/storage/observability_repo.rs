@@ -0,0 +1,182 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{ObservabilityAnalysis, ObservabilitySignal};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredObservabilityComponent {
+    pub id: String,
+    pub repository_id: String,
+    pub component: String, // "library" or "exporter"
+    pub name: String,
+    pub signal: String,
+    pub structured: bool,
+    pub endpoint: Option<String>,
+    pub service: Option<String>,
+    pub file_count: usize,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredObservabilityCoverage {
+    pub id: String,
+    pub repository_id: String,
+    pub service: String,
+    pub paths: Vec<String>,
+    pub logging: Vec<String>,
+    pub metrics: Vec<String>,
+    pub tracing: Vec<String>,
+    pub structured_logging: bool,
+    pub gaps: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct ObservabilityRepository {
+    db: Database,
+}
+
+impl ObservabilityRepository {
+    pub fn new(db: Database) -> Self {
+        ObservabilityRepository { db }
+    }
+
+    pub fn store_analysis(&self, repository_id: &str, analysis: &ObservabilityAnalysis) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing libraries, exporters and coverage for this repository
+        conn.execute("DELETE FROM observability_components WHERE repository_id = ?1", params![repository_id])?;
+        conn.execute("DELETE FROM observability_coverage WHERE repository_id = ?1", params![repository_id])?;
+
+        let now = Utc::now().to_rfc3339();
+        let insert_component = |component: &str, name: &str, signal: &ObservabilitySignal, structured: bool, endpoint: &Option<String>,
+                                service: &Option<String>, file_count: usize, file_path: &str, line_number: Option<usize>| {
+            conn.execute(
+                "INSERT INTO observability_components
+                 (id, repository_id, component, name, signal, structured, endpoint, service, file_count, file_path, line_number, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    component,
+                    name,
+                    self.signal_to_string(signal),
+                    structured as i32,
+                    endpoint,
+                    service,
+                    file_count as i64,
+                    file_path,
+                    line_number.map(|n| n as i32),
+                    now
+                ],
+            )
+        };
+        for library in &analysis.libraries {
+            insert_component("library", &library.name, &library.signal, library.structured, &None,
+                &library.service, library.file_count, &library.file_path, library.line_number)?;
+        }
+        for exporter in &analysis.exporters {
+            insert_component("exporter", &exporter.backend, &exporter.signal, false, &exporter.endpoint,
+                &exporter.service, 1, &exporter.file_path, exporter.line_number)?;
+        }
+
+        for coverage in &analysis.coverage {
+            let gaps: Vec<String> = coverage.gaps.iter().map(|g| format!("{:?}", g)).collect();
+            conn.execute(
+                "INSERT INTO observability_coverage
+                 (id, repository_id, service, paths, logging, metrics, tracing, structured_logging, gaps, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    coverage.service,
+                    serde_json::to_string(&coverage.paths)?,
+                    serde_json::to_string(&coverage.logging)?,
+                    serde_json::to_string(&coverage.metrics)?,
+                    serde_json::to_string(&coverage.tracing)?,
+                    coverage.structured_logging as i32,
+                    serde_json::to_string(&gaps)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_components(&self, repository_id: &str) -> Result<Vec<StoredObservabilityComponent>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, component, name, signal, structured, endpoint, service, file_count, file_path, line_number, created_at
+             FROM observability_components WHERE repository_id = ?1 ORDER BY component DESC, signal, name"
+        )?;
+
+        let components = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredObservabilityComponent {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                component: row.get(2)?,
+                name: row.get(3)?,
+                signal: row.get(4)?,
+                structured: row.get::<_, i32>(5)? != 0,
+                endpoint: row.get(6)?,
+                service: row.get(7)?,
+                file_count: row.get::<_, i64>(8)? as usize,
+                file_path: row.get(9)?,
+                line_number: row.get::<_, Option<i32>>(10)?.map(|n| n as usize),
+                created_at: row.get(11)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(components)
+    }
+
+    pub fn get_coverage(&self, repository_id: &str) -> Result<Vec<StoredObservabilityCoverage>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, service, paths, logging, metrics, tracing, structured_logging, gaps, created_at
+             FROM observability_coverage WHERE repository_id = ?1 ORDER BY service"
+        )?;
+
+        let coverage = stmt.query_map(params![repository_id], |row| {
+            let paths: String = row.get(3)?;
+            let logging: String = row.get(4)?;
+            let metrics: String = row.get(5)?;
+            let tracing: String = row.get(6)?;
+            let gaps: String = row.get(8)?;
+            Ok(StoredObservabilityCoverage {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                service: row.get(2)?,
+                paths: serde_json::from_str(&paths).unwrap_or_default(),
+                logging: serde_json::from_str(&logging).unwrap_or_default(),
+                metrics: serde_json::from_str(&metrics).unwrap_or_default(),
+                tracing: serde_json::from_str(&tracing).unwrap_or_default(),
+                structured_logging: row.get::<_, i32>(7)? != 0,
+                gaps: serde_json::from_str(&gaps).unwrap_or_default(),
+                created_at: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(coverage)
+    }
+
+    fn signal_to_string(&self, signal: &ObservabilitySignal) -> String {
+        match signal {
+            ObservabilitySignal::Logging => "logging",
+            ObservabilitySignal::Metrics => "metrics",
+            ObservabilitySignal::Tracing => "tracing",
+        }.to_string()
+    }
+}
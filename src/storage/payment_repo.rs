@@ -0,0 +1,108 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedPaymentFlow, PaymentFlowKind};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredPaymentFlow {
+    pub id: String,
+    pub repository_id: String,
+    pub provider: String,
+    pub kind: String,
+    pub value: String,
+    pub endpoint: Option<String>,
+    pub handler: Option<String>,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct PaymentRepository {
+    db: Database,
+}
+
+impl PaymentRepository {
+    pub fn new(db: Database) -> Self {
+        PaymentRepository { db }
+    }
+
+    pub fn store_flows(&self, repository_id: &str, flows: &[DetectedPaymentFlow]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing payment flows for this repository
+        conn.execute(
+            "DELETE FROM payment_flows WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for flow in flows {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO payment_flows
+                 (id, repository_id, provider, kind, value, endpoint, handler, file_path, line_number, language, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    id,
+                    repository_id,
+                    flow.provider,
+                    self.kind_to_string(&flow.kind),
+                    flow.value,
+                    flow.endpoint,
+                    flow.handler,
+                    flow.file_path,
+                    flow.line_number.map(|n| n as i32),
+                    flow.language,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredPaymentFlow>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, provider, kind, value, endpoint, handler, file_path, line_number, language, created_at
+             FROM payment_flows WHERE repository_id = ?1 ORDER BY provider, kind, value"
+        )?;
+
+        let flows = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredPaymentFlow {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                provider: row.get(2)?,
+                kind: row.get(3)?,
+                value: row.get(4)?,
+                endpoint: row.get(5)?,
+                handler: row.get(6)?,
+                file_path: row.get(7)?,
+                line_number: row.get::<_, Option<i32>>(8)?.map(|n| n as usize),
+                language: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(flows)
+    }
+
+    fn kind_to_string(&self, kind: &PaymentFlowKind) -> String {
+        match kind {
+            PaymentFlowKind::Webhook => "webhook",
+            PaymentFlowKind::Event => "event",
+            PaymentFlowKind::Checkout => "checkout",
+            PaymentFlowKind::Price => "price",
+            PaymentFlowKind::Product => "product",
+        }.to_string()
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::PinningAudit;
+
+#[derive(Clone)]
+pub struct PinningRepository {
+    db: Database,
+}
+
+impl PinningRepository {
+    pub fn new(db: Database) -> Self {
+        PinningRepository { db }
+    }
+
+    pub fn store_audit(&self, repository_id: &str, audit: &PinningAudit) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM pinning_audits WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+        conn.execute(
+            "INSERT INTO pinning_audits (id, repository_id, score, report, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                audit.score,
+                serde_json::to_string(audit)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_audit(&self, repository_id: &str) -> Result<Option<PinningAudit>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT report FROM pinning_audits WHERE repository_id = ?1"
+        )?;
+        let audit = stmt.query_map(params![repository_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .find_map(|r| serde_json::from_str(&r).ok());
+
+        Ok(audit)
+    }
+}
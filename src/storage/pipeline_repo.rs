@@ -0,0 +1,92 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::parsers::{CiPipeline, CiJob};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredPipeline {
+    pub id: String,
+    pub repository_id: String,
+    pub platform: String,
+    pub name: String,
+    pub file_path: String,
+    pub triggers: Vec<String>,
+    pub stages: Vec<String>,
+    pub jobs: Vec<CiJob>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct PipelineRepository {
+    db: Database,
+}
+
+impl PipelineRepository {
+    pub fn new(db: Database) -> Self {
+        PipelineRepository { db }
+    }
+
+    pub fn store_pipelines(&self, repository_id: &str, pipelines: &[CiPipeline]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing pipelines for this repository
+        conn.execute(
+            "DELETE FROM ci_pipelines WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now();
+        for pipeline in pipelines {
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO ci_pipelines
+                 (id, repository_id, platform, name, file_path, triggers, stages, jobs, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    id,
+                    repository_id,
+                    pipeline.platform,
+                    pipeline.name,
+                    pipeline.file_path,
+                    serde_json::to_string(&pipeline.triggers)?,
+                    serde_json::to_string(&pipeline.stages)?,
+                    serde_json::to_string(&pipeline.jobs)?,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredPipeline>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, platform, name, file_path, triggers, stages, jobs, created_at
+             FROM ci_pipelines WHERE repository_id = ?1 ORDER BY file_path"
+        )?;
+
+        let pipelines = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredPipeline {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                platform: row.get(2)?,
+                name: row.get(3)?,
+                file_path: row.get(4)?,
+                triggers: serde_json::from_str(&row.get::<_, String>(5)?).unwrap_or_default(),
+                stages: serde_json::from_str(&row.get::<_, String>(6)?).unwrap_or_default(),
+                jobs: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default(),
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(pipelines)
+    }
+}
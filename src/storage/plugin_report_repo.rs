@@ -0,0 +1,85 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::plugins::{ReportSection, SectionFormat};
+
+/// A plugin's report section as stored with the analysis it was produced from
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredReportSection {
+    pub plugin: String,
+    pub section: ReportSection,
+}
+
+#[derive(Clone)]
+pub struct PluginReportRepository {
+    db: Database,
+}
+
+impl PluginReportRepository {
+    pub fn new(db: Database) -> Self {
+        PluginReportRepository { db }
+    }
+
+    /// Replace the repository's sections with those from the latest analysis
+    pub fn store_sections(&self, repository_id: &str, sections: &[(String, ReportSection)]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM plugin_report_sections WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for (position, (plugin, section)) in sections.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO plugin_report_sections (id, repository_id, plugin, position, title, format, data, template, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    plugin,
+                    position as i64,
+                    section.title,
+                    match section.format {
+                        SectionFormat::Markdown => "markdown",
+                        SectionFormat::Html => "html",
+                    },
+                    serde_json::to_string(&section.data)?,
+                    section.template,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_sections(&self, repository_id: &str) -> Result<Vec<StoredReportSection>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT plugin, title, format, data, template FROM plugin_report_sections
+             WHERE repository_id = ?1 ORDER BY position"
+        )?;
+        let sections = stmt.query_map(params![repository_id], |row| {
+            let format: String = row.get(2)?;
+            let data: String = row.get(3)?;
+            Ok(StoredReportSection {
+                plugin: row.get(0)?,
+                section: ReportSection {
+                    title: row.get(1)?,
+                    data: serde_json::from_str(&data).unwrap_or_default(),
+                    template: row.get(4)?,
+                    format: if format == "html" { SectionFormat::Html } else { SectionFormat::Markdown },
+                },
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(sections)
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use chrono::Utc;
+use crate::storage::Database;
+use rusqlite::{params, OptionalExtension};
+use crate::plugins::PluginSelection;
+
+#[derive(Clone)]
+pub struct PluginSelectionRepository {
+    db: Database,
+}
+
+impl PluginSelectionRepository {
+    pub fn new(db: Database) -> Self {
+        PluginSelectionRepository { db }
+    }
+
+    /// A repository without a stored selection runs every plugin enabled by default
+    pub fn get_selection(&self, repository_id: &str) -> Result<PluginSelection> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let row: Option<(String, String)> = conn.query_row(
+            "SELECT enabled, disabled FROM repository_plugins WHERE repository_id = ?1",
+            params![repository_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+
+        Ok(match row {
+            Some((enabled, disabled)) => PluginSelection {
+                enabled: serde_json::from_str(&enabled)?,
+                disabled: serde_json::from_str(&disabled)?,
+            },
+            None => PluginSelection::default(),
+        })
+    }
+
+    pub fn set_selection(&self, repository_id: &str, selection: &PluginSelection) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO repository_plugins (repository_id, enabled, disabled, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(repository_id) DO UPDATE SET
+                enabled = excluded.enabled, disabled = excluded.disabled, updated_at = excluded.updated_at",
+            params![
+                repository_id,
+                serde_json::to_string(&selection.enabled)?,
+                serde_json::to_string(&selection.disabled)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+}
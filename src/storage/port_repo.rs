@@ -98,16 +98,17 @@ impl PortRepository {
         Ok(ports)
     }
 
-    pub fn get_by_port(&self, port: u16, repository_id: Option<&str>) -> Result<Vec<StoredPort>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_port(&self, port: u16, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredPort>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let ports: Vec<StoredPort> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, port, port_type, context, file_path, line_number, framework, environment, is_config, created_at
-                 FROM ports WHERE port = ?1 AND repository_id = ?2 ORDER BY repository_id"
+                 FROM ports WHERE port = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![port as i32, repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![port as i32, repo_id, workspace_id], |row| {
                 Ok(StoredPort {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -126,9 +127,9 @@ impl PortRepository {
         } else {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, port, port_type, context, file_path, line_number, framework, environment, is_config, created_at
-                 FROM ports WHERE port = ?1 ORDER BY repository_id"
+                 FROM ports WHERE port = ?1 AND workspace_id = ?2 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![port as i32], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![port as i32, workspace_id], |row| {
                 Ok(StoredPort {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -149,16 +150,17 @@ impl PortRepository {
         Ok(ports)
     }
 
-    pub fn get_by_type(&self, port_type: &str, repository_id: Option<&str>) -> Result<Vec<StoredPort>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_type(&self, port_type: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredPort>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let ports: Vec<StoredPort> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, port, port_type, context, file_path, line_number, framework, environment, is_config, created_at
-                 FROM ports WHERE port_type = ?1 AND repository_id = ?2 ORDER BY port"
+                 FROM ports WHERE port_type = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY port"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![port_type, repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![port_type, repo_id, workspace_id], |row| {
                 Ok(StoredPort {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -177,9 +179,9 @@ impl PortRepository {
         } else {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, port, port_type, context, file_path, line_number, framework, environment, is_config, created_at
-                 FROM ports WHERE port_type = ?1 ORDER BY repository_id, port"
+                 FROM ports WHERE port_type = ?1 AND workspace_id = ?2 ORDER BY repository_id, port"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![port_type], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![port_type, workspace_id], |row| {
                 Ok(StoredPort {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -0,0 +1,172 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{DetectedPiiField, DetectedPiiFlow, PiiCategory, PiiSink};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredPiiField {
+    pub id: String,
+    pub repository_id: String,
+    pub category: String,
+    pub model: String,
+    pub field: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredPiiFlow {
+    pub id: String,
+    pub repository_id: String,
+    pub category: String,
+    pub field: String,
+    pub sink: String,
+    pub destination: String,
+    pub evidence: String,
+    pub file_path: String,
+    pub line_number: Option<usize>,
+    pub language: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct PrivacyRepository {
+    db: Database,
+}
+
+impl PrivacyRepository {
+    pub fn new(db: Database) -> Self {
+        PrivacyRepository { db }
+    }
+
+    pub fn store_analysis(&self, repository_id: &str, fields: &[DetectedPiiField], flows: &[DetectedPiiFlow]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing PII fields and flows for this repository
+        conn.execute("DELETE FROM pii_fields WHERE repository_id = ?1", params![repository_id])?;
+        conn.execute("DELETE FROM pii_flows WHERE repository_id = ?1", params![repository_id])?;
+
+        let now = Utc::now();
+        for field in fields {
+            conn.execute(
+                "INSERT INTO pii_fields
+                 (id, repository_id, category, model, field, file_path, line_number, language, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    self.category_to_string(&field.category),
+                    field.model,
+                    field.field,
+                    field.file_path,
+                    field.line_number.map(|n| n as i32),
+                    field.language,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+        for flow in flows {
+            conn.execute(
+                "INSERT INTO pii_flows
+                 (id, repository_id, category, field, sink, destination, evidence, file_path, line_number, language, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    self.category_to_string(&flow.category),
+                    flow.field,
+                    self.sink_to_string(&flow.sink),
+                    flow.destination,
+                    flow.evidence,
+                    flow.file_path,
+                    flow.line_number.map(|n| n as i32),
+                    flow.language,
+                    now.to_rfc3339()
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_fields(&self, repository_id: &str) -> Result<Vec<StoredPiiField>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, category, model, field, file_path, line_number, language, created_at
+             FROM pii_fields WHERE repository_id = ?1 ORDER BY category, model, field"
+        )?;
+
+        let fields = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredPiiField {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                category: row.get(2)?,
+                model: row.get(3)?,
+                field: row.get(4)?,
+                file_path: row.get(5)?,
+                line_number: row.get::<_, Option<i32>>(6)?.map(|n| n as usize),
+                language: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(fields)
+    }
+
+    pub fn get_flows(&self, repository_id: &str) -> Result<Vec<StoredPiiFlow>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, category, field, sink, destination, evidence, file_path, line_number, language, created_at
+             FROM pii_flows WHERE repository_id = ?1 ORDER BY sink, destination, file_path, line_number"
+        )?;
+
+        let flows = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredPiiFlow {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                category: row.get(2)?,
+                field: row.get(3)?,
+                sink: row.get(4)?,
+                destination: row.get(5)?,
+                evidence: row.get(6)?,
+                file_path: row.get(7)?,
+                line_number: row.get::<_, Option<i32>>(8)?.map(|n| n as usize),
+                language: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(flows)
+    }
+
+    fn category_to_string(&self, category: &PiiCategory) -> String {
+        match category {
+            PiiCategory::Email => "email",
+            PiiCategory::Phone => "phone",
+            PiiCategory::NationalId => "national_id",
+            PiiCategory::DateOfBirth => "date_of_birth",
+            PiiCategory::PostalAddress => "postal_address",
+            PiiCategory::IpAddress => "ip_address",
+            PiiCategory::Name => "name",
+        }.to_string()
+    }
+
+    fn sink_to_string(&self, sink: &PiiSink) -> String {
+        match sink {
+            PiiSink::Logging => "logging",
+            PiiSink::Analytics => "analytics",
+            PiiSink::ThirdParty => "third_party",
+        }.to_string()
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::{ArchitectureSnapshot, Release, ReleaseSource};
+
+#[derive(Clone)]
+pub struct ReleaseRepository {
+    db: Database,
+}
+
+impl ReleaseRepository {
+    pub fn new(db: Database) -> Self {
+        ReleaseRepository { db }
+    }
+
+    /// Replace the releases from one source; changelog and GitHub releases are refreshed independently
+    pub fn store_releases(&self, repository_id: &str, source: ReleaseSource, releases: &[Release]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM releases WHERE repository_id = ?1 AND source = ?2",
+            params![repository_id, source.as_str()],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for release in releases {
+            conn.execute(
+                "INSERT INTO releases (id, repository_id, source, version, release_date, title, url, sections, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    source.as_str(),
+                    release.version,
+                    release.date,
+                    release.title,
+                    release.url,
+                    serde_json::to_string(&release.sections)?,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_releases(&self, repository_id: &str, source: ReleaseSource) -> Result<Vec<Release>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT version, release_date, title, url, sections FROM releases
+             WHERE repository_id = ?1 AND source = ?2 ORDER BY rowid"
+        )?;
+        let releases = stmt.query_map(params![repository_id, source.as_str()], |row| {
+            let sections: String = row.get(4)?;
+            Ok(Release {
+                version: row.get(0)?,
+                date: row.get(1)?,
+                source,
+                title: row.get(2)?,
+                url: row.get(3)?,
+                sections: serde_json::from_str(&sections).unwrap_or_default(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(releases)
+    }
+
+    /// Snapshots are kept for every analysis so architecture changes can be placed between releases
+    pub fn store_snapshot(&self, repository_id: &str, snapshot: &ArchitectureSnapshot) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO architecture_snapshots (id, repository_id, snapshot, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                serde_json::to_string(snapshot)?,
+                snapshot.taken_at
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Oldest first, limited to the most recent `limit` analyses
+    pub fn get_snapshots(&self, repository_id: &str, limit: usize) -> Result<Vec<ArchitectureSnapshot>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT snapshot FROM architecture_snapshots WHERE repository_id = ?1 ORDER BY created_at DESC LIMIT ?2"
+        )?;
+        let mut snapshots = stmt.query_map(params![repository_id, limit as i64], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|s| serde_json::from_str(&s).ok())
+            .collect::<Vec<ArchitectureSnapshot>>();
+        snapshots.reverse();
+
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::ingestion::{RepositoryFingerprint, RepositoryLink, RepositoryRelation};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredRepositoryLink {
+    pub repository_id: String,
+    pub related_repository_id: String,
+    pub related_name: String,
+    pub related_url: String,
+    pub related_last_analyzed_at: Option<String>,
+    pub relation: String,
+    pub evidence: String,
+    pub same_head: bool,
+    /// A mirror at the same commit that has been analyzed, whose results stand for this repository
+    pub analysis_reusable: bool,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct RepositoryLinkRepository {
+    db: Database,
+}
+
+impl RepositoryLinkRepository {
+    pub fn new(db: Database) -> Self {
+        RepositoryLinkRepository { db }
+    }
+
+    pub fn store_fingerprint(&self, fingerprint: &RepositoryFingerprint) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO repository_fingerprints (repository_id, normalized_url, branch, head_commit, root_commits, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                fingerprint.repository_id,
+                fingerprint.normalized_url,
+                fingerprint.branch,
+                fingerprint.head_commit,
+                serde_json::to_string(&fingerprint.root_commits)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Fingerprints of the workspace's repositories; forks and mirrors are only linked within a workspace
+    pub fn get_fingerprints_in_workspace(&self, workspace_id: &str) -> Result<Vec<RepositoryFingerprint>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT f.repository_id, f.normalized_url, f.branch, f.head_commit, f.root_commits
+             FROM repository_fingerprints f JOIN repositories r ON r.id = f.repository_id
+             WHERE r.workspace_id = ?1"
+        )?;
+
+        let fingerprints = stmt.query_map(params![workspace_id], |row| {
+            Ok(RepositoryFingerprint {
+                repository_id: row.get(0)?,
+                normalized_url: row.get(1)?,
+                branch: row.get(2)?,
+                head_commit: row.get(3)?,
+                root_commits: serde_json::from_str(&row.get::<_, String>(4)?).unwrap_or_default(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(fingerprints)
+    }
+
+    /// Replace the repository's links in both directions, since a relation holds for both sides
+    pub fn store_links(&self, repository_id: &str, links: &[RepositoryLink]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM repository_links WHERE repository_id = ?1 OR related_repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for link in links {
+            for (from, to) in [(repository_id, link.related_repository_id.as_str()), (link.related_repository_id.as_str(), repository_id)] {
+                conn.execute(
+                    "INSERT INTO repository_links (id, repository_id, related_repository_id, relation, evidence, same_head, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        Uuid::new_v4().to_string(),
+                        from,
+                        to,
+                        link.relation.as_str(),
+                        link.evidence,
+                        link.same_head,
+                        now
+                    ],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Mirrors first, then forks
+    pub fn get_links(&self, repository_id: &str) -> Result<Vec<StoredRepositoryLink>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT l.repository_id, l.related_repository_id, r.name, r.url, r.last_analyzed_at, l.relation, l.evidence, l.same_head, l.created_at
+             FROM repository_links l JOIN repositories r ON r.id = l.related_repository_id
+             WHERE l.repository_id = ?1 ORDER BY l.relation DESC, r.name"
+        )?;
+
+        let links = stmt.query_map(params![repository_id], |row| {
+            let relation: String = row.get(5)?;
+            let same_head: bool = row.get(7)?;
+            let related_last_analyzed_at: Option<String> = row.get(4)?;
+            Ok(StoredRepositoryLink {
+                repository_id: row.get(0)?,
+                related_repository_id: row.get(1)?,
+                related_name: row.get(2)?,
+                related_url: row.get(3)?,
+                analysis_reusable: same_head
+                    && related_last_analyzed_at.is_some()
+                    && RepositoryRelation::parse(&relation) == Some(RepositoryRelation::Mirror),
+                related_last_analyzed_at,
+                relation,
+                evidence: row.get(6)?,
+                same_head,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links)
+    }
+}
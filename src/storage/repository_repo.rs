@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::storage::Database;
 use rusqlite::params;
-use crate::analysis::{PackageDependency, PackageManager};
+use crate::analysis::{AnalysisProfile, PackageDependency, PackageManager};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
@@ -15,6 +15,9 @@ pub struct Repository {
     pub auth_type: Option<String>,
     pub auth_value: Option<String>,
     pub last_analyzed_at: Option<DateTime<Utc>>,
+    /// Used when an analysis request does not name a profile
+    pub analysis_profile: Option<AnalysisProfile>,
+    pub workspace_id: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,7 +45,7 @@ impl RepositoryRepository {
         RepositoryRepository { db }
     }
 
-    pub fn create(&self, name: &str, url: &str, branch: Option<&str>, auth_type: Option<&str>, auth_value: Option<&str>) -> Result<Repository> {
+    pub fn create(&self, name: &str, url: &str, branch: Option<&str>, auth_type: Option<&str>, auth_value: Option<&str>, workspace_id: &str) -> Result<Repository> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now();
         let branch = branch.unwrap_or("main");
@@ -51,8 +54,8 @@ impl RepositoryRepository {
         let conn = conn.lock().unwrap();
         
         conn.execute(
-            "INSERT INTO repositories (id, name, url, branch, auth_type, auth_value, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO repositories (id, name, url, branch, auth_type, auth_value, created_at, updated_at, workspace_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 id,
                 name,
@@ -61,7 +64,8 @@ impl RepositoryRepository {
                 auth_type,
                 auth_value,
                 now.to_rfc3339(),
-                now.to_rfc3339()
+                now.to_rfc3339(),
+                workspace_id
             ],
         )?;
         
@@ -73,6 +77,8 @@ impl RepositoryRepository {
             auth_type: auth_type.map(|s| s.to_string()),
             auth_value: auth_value.map(|s| s.to_string()),
             last_analyzed_at: None,
+            analysis_profile: None,
+            workspace_id: workspace_id.to_string(),
             created_at: now,
             updated_at: now,
         })
@@ -83,7 +89,7 @@ impl RepositoryRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, name, url, branch, auth_type, auth_value, last_analyzed_at, created_at, updated_at
+            "SELECT id, name, url, branch, auth_type, auth_value, last_analyzed_at, created_at, updated_at, analysis_profile, workspace_id
              FROM repositories WHERE id = ?1"
         )?;
         
@@ -98,6 +104,9 @@ impl RepositoryRepository {
                 last_analyzed_at: row.get::<_, Option<String>>(6)?
                     .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                analysis_profile: row.get::<_, Option<String>>(9)?
+                    .and_then(|p| AnalysisProfile::parse(&p)),
+                workspace_id: row.get(10)?,
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
                     .map_err(|_| rusqlite::Error::InvalidColumnType(7, "created_at".to_string(), rusqlite::types::Type::Text))?
                     .with_timezone(&Utc),
@@ -119,7 +128,7 @@ impl RepositoryRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, name, url, branch, auth_type, auth_value, last_analyzed_at, created_at, updated_at
+            "SELECT id, name, url, branch, auth_type, auth_value, last_analyzed_at, created_at, updated_at, analysis_profile, workspace_id
              FROM repositories ORDER BY created_at DESC"
         )?;
         
@@ -134,6 +143,9 @@ impl RepositoryRepository {
                 last_analyzed_at: row.get::<_, Option<String>>(6)?
                     .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
                     .map(|dt| dt.with_timezone(&Utc)),
+                analysis_profile: row.get::<_, Option<String>>(9)?
+                    .and_then(|p| AnalysisProfile::parse(&p)),
+                workspace_id: row.get(10)?,
                 created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
                     .map_err(|_| rusqlite::Error::InvalidColumnType(7, "created_at".to_string(), rusqlite::types::Type::Text))?
                     .with_timezone(&Utc),
@@ -147,6 +159,34 @@ impl RepositoryRepository {
         Ok(repos)
     }
 
+    pub fn list_by_workspace(&self, workspace_id: &str) -> Result<Vec<Repository>> {
+        Ok(self.list_all()?.into_iter().filter(|r| r.workspace_id == workspace_id).collect())
+    }
+
+    /// Ids of the repositories a workspace owns, for filtering results that span repositories
+    pub fn ids_in_workspace(&self, workspace_id: &str) -> Result<std::collections::HashSet<String>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare("SELECT id FROM repositories WHERE workspace_id = ?1")?;
+        let ids = stmt.query_map(params![workspace_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<_, _>>()?;
+
+        Ok(ids)
+    }
+
+    pub fn set_analysis_profile(&self, id: &str, profile: Option<AnalysisProfile>) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE repositories SET analysis_profile = ?1, updated_at = ?2 WHERE id = ?3",
+            params![profile.map(|p| p.as_str()), Utc::now().to_rfc3339(), id],
+        )?;
+
+        Ok(())
+    }
+
     pub fn update_last_analyzed(&self, id: &str) -> Result<()> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
@@ -200,6 +240,128 @@ impl RepositoryRepository {
         // Dependencies
         conn.execute("DELETE FROM dependencies WHERE repository_id = ?1", params![id])?;
         
+        // Plugin report sections
+        conn.execute("DELETE FROM plugin_report_sections WHERE repository_id = ?1", params![id])?;
+        
+        // Plugin selection
+        conn.execute("DELETE FROM repository_plugins WHERE repository_id = ?1", params![id])?;
+        
+        // Releases and architecture snapshots
+        conn.execute("DELETE FROM releases WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM architecture_snapshots WHERE repository_id = ?1", params![id])?;
+        
+        // Documentation links
+        conn.execute("DELETE FROM doc_links WHERE repository_id = ?1", params![id])?;
+        
+        // Documentation quality
+        conn.execute("DELETE FROM doc_quality WHERE repository_id = ?1", params![id])?;
+        
+        // Semantic search vectors
+        conn.execute("DELETE FROM embeddings WHERE repository_id = ?1", params![id])?;
+        
+        // Architecture decision records
+        conn.execute("DELETE FROM architecture_decisions WHERE repository_id = ?1", params![id])?;
+        
+        // TODO markers
+        conn.execute("DELETE FROM todos WHERE repository_id = ?1", params![id])?;
+        
+        // Technical debt history
+        conn.execute("DELETE FROM tech_debt_scores WHERE repository_id = ?1", params![id])?;
+        
+        // Architecture layers and findings
+        conn.execute("DELETE FROM architecture_findings WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM architecture_conformance WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM architecture_specs WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM architecture_layers WHERE repository_id = ?1", params![id])?;
+        
+        // Service boundaries
+        conn.execute("DELETE FROM service_boundaries WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM decomposition_reports WHERE repository_id = ?1", params![id])?;
+        
+        // GraphQL client usage
+        conn.execute("DELETE FROM graphql_operations WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM graphql_schema_types WHERE repository_id = ?1", params![id])?;
+        
+        // Test-to-code links
+        conn.execute("DELETE FROM test_code_links WHERE repository_id = ?1", params![id])?;
+        
+        // Test coverage
+        conn.execute("DELETE FROM coverage_files WHERE repository_id = ?1", params![id])?;
+        
+        // Frontend routes
+        conn.execute("DELETE FROM frontend_routes WHERE repository_id = ?1", params![id])?;
+        
+        // Language statistics
+        conn.execute("DELETE FROM language_stats WHERE repository_id = ?1", params![id])?;
+        
+        // System membership
+        conn.execute("DELETE FROM system_repositories WHERE repository_id = ?1", params![id])?;
+        
+        // Entrypoints
+        conn.execute("DELETE FROM entrypoints WHERE repository_id = ?1", params![id])?;
+        
+        // Background jobs
+        conn.execute("DELETE FROM background_jobs WHERE repository_id = ?1", params![id])?;
+        
+        // Notification provider configuration
+        conn.execute("DELETE FROM notification_configs WHERE repository_id = ?1", params![id])?;
+        
+        // Payment flows
+        conn.execute("DELETE FROM payment_flows WHERE repository_id = ?1", params![id])?;
+        
+        // PII fields and flows
+        conn.execute("DELETE FROM pii_fields WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM pii_flows WHERE repository_id = ?1", params![id])?;
+        
+        // Observability libraries, exporters and coverage
+        conn.execute("DELETE FROM observability_components WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM observability_coverage WHERE repository_id = ?1", params![id])?;
+        
+        // IaC cost estimate
+        conn.execute("DELETE FROM resource_costs WHERE repository_id = ?1", params![id])?;
+        
+        // Environment matrix
+        conn.execute("DELETE FROM environment_values WHERE repository_id = ?1", params![id])?;
+        
+        // Artifact inventory
+        conn.execute("DELETE FROM artifacts WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM hygiene_findings WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM image_metadata WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM image_vulnerabilities WHERE repository_id = ?1", params![id])?;
+        
+        // Frameworks
+        conn.execute("DELETE FROM frameworks WHERE repository_id = ?1", params![id])?;
+        
+        // CI/CD pipelines
+        conn.execute("DELETE FROM ci_pipelines WHERE repository_id = ?1", params![id])?;
+        
+        // Kubernetes resources
+        conn.execute("DELETE FROM kubernetes_resources WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM gateway_routes WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM build_targets WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM data_pipelines WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM pinning_audits WHERE repository_id = ?1", params![id])?;
+        
+        // Saved analysis progress and checkpoints
+        conn.execute("DELETE FROM analysis_runs WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM analysis_checkpoints WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM analysis_schedules WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM scheduled_runs WHERE repository_id = ?1", params![id])?;
+        
+        // Fork/mirror fingerprint and links, from either side
+        conn.execute("DELETE FROM repository_fingerprints WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM repository_links WHERE repository_id = ?1 OR related_repository_id = ?1", params![id])?;
+        
+        // Jira tickets opened for findings
+        conn.execute("DELETE FROM jira_issues WHERE repository_id = ?1", params![id])?;
+
+        // False-positive verdicts
+        conn.execute("DELETE FROM false_positives WHERE repository_id = ?1", params![id])?;
+        
+        // Notification webhooks
+        conn.execute("DELETE FROM webhooks WHERE repository_id = ?1", params![id])?;
+        conn.execute("DELETE FROM webhook_baselines WHERE repository_id = ?1", params![id])?;
+        
         // Delete documentation (experimental - may be removed)
         conn.execute("DELETE FROM documentation WHERE repository_id = ?1", params![id])?;
         
@@ -249,6 +411,8 @@ impl DependencyRepository {
                 PackageManager::SwiftPackageManager => "swift-package-manager",
                 PackageManager::CocoaPods => "cocoapods",
                 PackageManager::WebMethodsIS => "webmethods-is",
+                PackageManager::Unity => "unity",
+                PackageManager::Unreal => "unreal",
             };
             
             conn.execute(
@@ -301,16 +465,17 @@ impl DependencyRepository {
         Ok(deps)
     }
 
-    pub fn get_by_package_name(&self, name: &str, repository_id: Option<&str>) -> Result<Vec<StoredDependency>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_package_name(&self, name: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredDependency>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let deps: Vec<StoredDependency> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, name, version, package_manager, is_dev, is_optional, file_path, created_at
-                 FROM dependencies WHERE name = ?1 AND repository_id = ?2 ORDER BY repository_id"
+                 FROM dependencies WHERE name = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![name, repo_id], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![name, repo_id, workspace_id], |row| {
                 Ok(StoredDependency {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -329,9 +494,9 @@ impl DependencyRepository {
         } else {
             let mut stmt = conn.prepare(
                 "SELECT id, repository_id, name, version, package_manager, is_dev, is_optional, file_path, created_at
-                 FROM dependencies WHERE name = ?1 ORDER BY repository_id"
+                 FROM dependencies WHERE name = ?1 AND workspace_id = ?2 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![name], |row| {
+            let result: Result<Vec<_>, _> = stmt.query_map(params![name, workspace_id], |row| {
                 Ok(StoredDependency {
                     id: row.get(0)?,
                     repository_id: row.get(1)?,
@@ -0,0 +1,262 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::analysis::AnalysisProfile;
+use crate::storage::Database;
+
+/// A repository's recurring re-analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisSchedule {
+    pub repository_id: String,
+    /// Five-field cron expression, in UTC
+    pub cron: String,
+    /// Falls back to the repository's default profile
+    pub profile: Option<AnalysisProfile>,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Running,
+    Completed,
+    Failed,
+    /// The previous scheduled analysis of the repository was still running
+    Skipped,
+}
+
+impl RunStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RunStatus::Running => "running",
+            RunStatus::Completed => "completed",
+            RunStatus::Failed => "failed",
+            RunStatus::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<RunStatus> {
+        match status {
+            "running" => Some(RunStatus::Running),
+            "completed" => Some(RunStatus::Completed),
+            "failed" => Some(RunStatus::Failed),
+            "skipped" => Some(RunStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// One scheduled analysis of a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledRun {
+    pub id: String,
+    pub repository_id: String,
+    pub cron: String,
+    /// When the schedule was due; the run starts within a minute of it
+    pub scheduled_for: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub status: RunStatus,
+    pub error: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct ScheduleRepository {
+    db: Database,
+}
+
+impl ScheduleRepository {
+    pub fn new(db: Database) -> Self {
+        ScheduleRepository { db }
+    }
+
+    /// Creates the repository's schedule or replaces its settings, keeping when it last ran
+    pub fn upsert(&self, repository_id: &str, cron: &str, profile: Option<AnalysisProfile>, enabled: bool, next_run_at: Option<DateTime<Utc>>) -> Result<AnalysisSchedule> {
+        let now = Utc::now().to_rfc3339();
+        {
+            let conn = self.db.get_connection();
+            let conn = conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO analysis_schedules (repository_id, cron, profile, enabled, last_run_at, next_run_at, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, NULL, ?5, ?6, ?6)
+                 ON CONFLICT(repository_id) DO UPDATE SET
+                    cron = excluded.cron, profile = excluded.profile, enabled = excluded.enabled,
+                    next_run_at = excluded.next_run_at, updated_at = excluded.updated_at",
+                params![repository_id, cron, profile.map(|p| p.as_str()), enabled, next_run_at.map(|t| t.to_rfc3339()), now],
+            )?;
+        }
+        self.find(repository_id)?.ok_or_else(|| anyhow::anyhow!("Schedule of {} was not stored", repository_id))
+    }
+
+    pub fn find(&self, repository_id: &str) -> Result<Option<AnalysisSchedule>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT repository_id, cron, profile, enabled, last_run_at, next_run_at, created_at, updated_at
+             FROM analysis_schedules WHERE repository_id = ?1",
+            params![repository_id],
+            row_to_schedule,
+        ).optional()?)
+    }
+
+    /// Enabled schedules whose next run is at or before `now`
+    pub fn due(&self, now: DateTime<Utc>) -> Result<Vec<AnalysisSchedule>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT repository_id, cron, profile, enabled, last_run_at, next_run_at, created_at, updated_at
+             FROM analysis_schedules WHERE enabled = 1 AND next_run_at IS NOT NULL"
+        )?;
+        let schedules = stmt.query_map([], row_to_schedule)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(schedules.into_iter().filter(|s| s.next_run_at.is_some_and(|t| t <= now)).collect())
+    }
+
+    pub fn advance(&self, repository_id: &str, last_run_at: DateTime<Utc>, next_run_at: Option<DateTime<Utc>>) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE analysis_schedules SET last_run_at = ?2, next_run_at = ?3 WHERE repository_id = ?1",
+            params![repository_id, last_run_at.to_rfc3339(), next_run_at.map(|t| t.to_rfc3339())],
+        )?;
+        Ok(())
+    }
+
+    /// Whether there was a schedule; its run history is kept
+    pub fn delete(&self, repository_id: &str) -> Result<bool> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let deleted = conn.execute("DELETE FROM analysis_schedules WHERE repository_id = ?1", params![repository_id])?;
+        Ok(deleted > 0)
+    }
+
+    pub fn record_run(&self, schedule: &AnalysisSchedule, scheduled_for: DateTime<Utc>, status: RunStatus) -> Result<ScheduledRun> {
+        let run = ScheduledRun {
+            id: Uuid::new_v4().to_string(),
+            repository_id: schedule.repository_id.clone(),
+            cron: schedule.cron.clone(),
+            scheduled_for,
+            started_at: Utc::now(),
+            completed_at: (status != RunStatus::Running).then(Utc::now),
+            status,
+            error: None,
+        };
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO scheduled_runs (id, repository_id, cron, scheduled_for, started_at, completed_at, status, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL)",
+            params![
+                run.id,
+                run.repository_id,
+                run.cron,
+                run.scheduled_for.to_rfc3339(),
+                run.started_at.to_rfc3339(),
+                run.completed_at.map(|t| t.to_rfc3339()),
+                run.status.as_str()
+            ],
+        )?;
+
+        Ok(run)
+    }
+
+    pub fn finish_run(&self, id: &str, status: RunStatus, error: Option<&str>) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE scheduled_runs SET status = ?2, error = ?3, completed_at = ?4 WHERE id = ?1",
+            params![id, status.as_str(), error, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn is_running(&self, repository_id: &str) -> Result<bool> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM scheduled_runs WHERE repository_id = ?1 AND status = 'running')",
+            params![repository_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Runs a restart cut short can't finish; returns how many there were
+    pub fn fail_interrupted(&self) -> Result<usize> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.execute(
+            "UPDATE scheduled_runs SET status = 'failed', error = 'Interrupted by a restart', completed_at = ?1 WHERE status = 'running'",
+            params![Utc::now().to_rfc3339()],
+        )?)
+    }
+
+    /// Newest first
+    pub fn runs(&self, repository_id: &str, limit: usize) -> Result<Vec<ScheduledRun>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, cron, scheduled_for, started_at, completed_at, status, error
+             FROM scheduled_runs WHERE repository_id = ?1 ORDER BY started_at DESC LIMIT ?2"
+        )?;
+        let runs = stmt.query_map(params![repository_id, limit as i64], |row| {
+            let status: String = row.get(6)?;
+            Ok(ScheduledRun {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                cron: row.get(2)?,
+                scheduled_for: timestamp(row, 3)?,
+                started_at: timestamp(row, 4)?,
+                completed_at: optional_timestamp(row, 5)?,
+                status: RunStatus::parse(&status)
+                    .ok_or_else(|| rusqlite::Error::InvalidColumnType(6, "status".to_string(), rusqlite::types::Type::Text))?,
+                error: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(runs)
+    }
+}
+
+fn row_to_schedule(row: &rusqlite::Row) -> rusqlite::Result<AnalysisSchedule> {
+    Ok(AnalysisSchedule {
+        repository_id: row.get(0)?,
+        cron: row.get(1)?,
+        profile: row.get::<_, Option<String>>(2)?.as_deref().and_then(AnalysisProfile::parse),
+        enabled: row.get(3)?,
+        last_run_at: optional_timestamp(row, 4)?,
+        next_run_at: optional_timestamp(row, 5)?,
+        created_at: timestamp(row, 6)?,
+        updated_at: timestamp(row, 7)?,
+    })
+}
+
+fn timestamp(row: &rusqlite::Row, column: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let text: String = row.get(column)?;
+    DateTime::parse_from_rfc3339(&text)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| rusqlite::Error::InvalidColumnType(column, "timestamp".to_string(), rusqlite::types::Type::Text))
+}
+
+fn optional_timestamp(row: &rusqlite::Row, column: usize) -> rusqlite::Result<Option<DateTime<Utc>>> {
+    match row.get::<_, Option<String>>(column)? {
+        Some(_) => timestamp(row, column).map(Some),
+        None => Ok(None),
+    }
+}
@@ -160,25 +160,7 @@ impl SecurityRepository {
             let mut stored = 0;
             
             for vuln in vulnerabilities {
-                let severity_str = self.severity_to_string(&vuln.severity);
-                
-                conn.execute(
-                    "INSERT INTO security_vulnerabilities 
-                     (id, repository_id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number, created_at)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-                    params![
-                        vuln.id,
-                        repository_id,
-                        vuln.entity_id,
-                        vuln.vulnerability_type,
-                        severity_str,
-                        vuln.description,
-                        vuln.recommendation,
-                        vuln.file_path,
-                        vuln.line_number.map(|n| n as i32),
-                        now.to_rfc3339()
-                    ],
-                )?;
+                self.insert_vulnerability(&conn, repository_id, vuln, &now.to_rfc3339())?;
                 
                 stored += 1;
                 if stored % batch_size == 0 || stored == total {
@@ -196,6 +178,49 @@ impl SecurityRepository {
         Ok(())
     }
 
+    /// Replace only the repository's vulnerabilities of one type, such as the dependency
+    /// advisories looked up after an analysis, leaving the analysis' own findings in place
+    pub fn replace_vulnerabilities(&self, repository_id: &str, vulnerability_type: &str, vulnerabilities: &[SecurityVulnerability]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute("PRAGMA foreign_keys = OFF", [])?;
+        conn.execute(
+            "DELETE FROM security_vulnerabilities WHERE repository_id = ?1 AND vulnerability_type = ?2",
+            params![repository_id, vulnerability_type],
+        )?;
+        let now = Utc::now().to_rfc3339();
+        for vuln in vulnerabilities {
+            self.insert_vulnerability(&conn, repository_id, vuln, &now)?;
+        }
+        conn.execute("PRAGMA foreign_keys = ON", [])?;
+
+        Ok(())
+    }
+
+    fn insert_vulnerability(&self, conn: &rusqlite::Connection, repository_id: &str, vuln: &SecurityVulnerability, created_at: &str) -> Result<()> {
+        conn.execute(
+            "INSERT INTO security_vulnerabilities 
+             (id, repository_id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number, created_at, affected_range, fixed_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                vuln.id,
+                repository_id,
+                vuln.entity_id,
+                vuln.vulnerability_type,
+                self.severity_to_string(&vuln.severity),
+                vuln.description,
+                vuln.recommendation,
+                vuln.file_path,
+                vuln.line_number.map(|n| n as i32),
+                created_at,
+                vuln.affected_range,
+                vuln.fixed_version
+            ],
+        )?;
+        Ok(())
+    }
+
     pub fn get_entities(&self, repository_id: &str) -> Result<Vec<SecurityEntity>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
@@ -295,7 +320,7 @@ impl SecurityRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number
+            "SELECT id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number, affected_range, fixed_version
              FROM security_vulnerabilities WHERE repository_id = ?1 ORDER BY 
              CASE severity 
                  WHEN 'Critical' THEN 1
@@ -321,6 +346,8 @@ impl SecurityRepository {
                     Some(n) => Some(n as usize),
                     None => None,
                 },
+                affected_range: row.get(8)?,
+                fixed_version: row.get(9)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -333,7 +360,7 @@ impl SecurityRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number
+            "SELECT id, entity_id, vulnerability_type, severity, description, recommendation, file_path, line_number, affected_range, fixed_version
              FROM security_vulnerabilities WHERE repository_id = ?1 AND severity = ?2 ORDER BY vulnerability_type"
         )?;
         
@@ -352,6 +379,8 @@ impl SecurityRepository {
                     Some(n) => Some(n as usize),
                     None => None,
                 },
+                affected_range: row.get(8)?,
+                fixed_version: row.get(9)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -375,6 +404,14 @@ impl SecurityRepository {
             SecurityEntityType::EnvironmentConfig => "environment_config",
             SecurityEntityType::SecurityConfig => "security_config",
             SecurityEntityType::ApiKey => "api_key",
+            SecurityEntityType::HttpEndpoint => "http_endpoint",
+            SecurityEntityType::PaymentHandler => "payment_handler",
+            SecurityEntityType::PersonalData => "personal_data",
+            SecurityEntityType::AndroidApp => "android_app",
+            SecurityEntityType::AndroidComponent => "android_component",
+            SecurityEntityType::IosApp => "ios_app",
+            SecurityEntityType::IosEntitlements => "ios_entitlements",
+            SecurityEntityType::RemoteBuildCache => "remote_build_cache",
         }.to_string()
     }
 
@@ -394,6 +431,14 @@ impl SecurityRepository {
             "environment_config" => SecurityEntityType::EnvironmentConfig,
             "security_config" => SecurityEntityType::SecurityConfig,
             "api_key" => SecurityEntityType::ApiKey,
+            "http_endpoint" => SecurityEntityType::HttpEndpoint,
+            "payment_handler" => SecurityEntityType::PaymentHandler,
+            "personal_data" => SecurityEntityType::PersonalData,
+            "android_app" => SecurityEntityType::AndroidApp,
+            "android_component" => SecurityEntityType::AndroidComponent,
+            "ios_app" => SecurityEntityType::IosApp,
+            "ios_entitlements" => SecurityEntityType::IosEntitlements,
+            "remote_build_cache" => SecurityEntityType::RemoteBuildCache,
             _ => SecurityEntityType::IamRole,
         }
     }
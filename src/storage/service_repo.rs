@@ -3,7 +3,7 @@ use chrono::Utc;
 use uuid::Uuid;
 use crate::storage::Database;
 use rusqlite::params;
-use crate::security::{DetectedService, ServiceProvider, ServiceType};
+use crate::security::{DetectedService, ServiceEvidence, ServiceProvider, ServiceType};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StoredService {
@@ -16,6 +16,9 @@ pub struct StoredService {
     pub file_path: String,
     pub line_number: Option<usize>,
     pub confidence: f64,
+    /// The detections the service was aggregated from
+    #[serde(default)]
+    pub evidence: Vec<ServiceEvidence>,
     pub created_at: String,
 }
 
@@ -46,11 +49,12 @@ impl ServiceRepository {
             let provider_str = self.provider_to_string(&service.provider);
             let service_type_str = self.service_type_to_string(&service.service_type);
             let config_json = serde_json::to_string(&service.configuration)?;
+            let evidence_json = serde_json::to_string(&service.evidence)?;
             
             conn.execute(
                 "INSERT INTO services 
-                 (id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                 (id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, evidence, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
                 params![
                     id,
                     repository_id,
@@ -61,6 +65,7 @@ impl ServiceRepository {
                     service.file_path,
                     service.line_number.map(|n| n as i32),
                     service.confidence,
+                    evidence_json,
                     now.to_rfc3339()
                 ],
             )?;
@@ -74,128 +79,65 @@ impl ServiceRepository {
         let conn = conn.lock().unwrap();
         
         let mut stmt = conn.prepare(
-            "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at
+            "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at, evidence
              FROM services WHERE repository_id = ?1 ORDER BY provider, name"
         )?;
         
-        let services = stmt.query_map(params![repository_id], |row| {
-            Ok(StoredService {
-                id: row.get(0)?,
-                repository_id: row.get(1)?,
-                provider: row.get(2)?,
-                service_type: row.get(3)?,
-                name: row.get(4)?,
-                configuration: row.get(5)?,
-                file_path: row.get(6)?,
-                line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
-                confidence: row.get(8)?,
-                created_at: row.get(9)?,
-            })
-        })?
+        let services = stmt.query_map(params![repository_id], row_to_service)?
         .collect::<Result<Vec<_>, _>>()?;
 
         Ok(services)
     }
 
-    pub fn get_by_provider(&self, provider: &str, repository_id: Option<&str>) -> Result<Vec<StoredService>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_provider(&self, provider: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredService>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let services: Vec<StoredService> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at
-                 FROM services WHERE provider = ?1 AND repository_id = ?2 ORDER BY repository_id"
+                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at, evidence
+                 FROM services WHERE provider = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![provider, repo_id], |row| {
-                Ok(StoredService {
-                    id: row.get(0)?,
-                    repository_id: row.get(1)?,
-                    provider: row.get(2)?,
-                    service_type: row.get(3)?,
-                    name: row.get(4)?,
-                    configuration: row.get(5)?,
-                    file_path: row.get(6)?,
-                    line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
-                    confidence: row.get(8)?,
-                    created_at: row.get(9)?,
-                })
-            })?.collect();
+            let result: Result<Vec<_>, _> = stmt.query_map(params![provider, repo_id, workspace_id], row_to_service)?.collect();
             result?
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at
-                 FROM services WHERE provider = ?1 ORDER BY repository_id"
+                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at, evidence
+                 FROM services WHERE provider = ?1 AND workspace_id = ?2 ORDER BY repository_id"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![provider], |row| {
-                Ok(StoredService {
-                    id: row.get(0)?,
-                    repository_id: row.get(1)?,
-                    provider: row.get(2)?,
-                    service_type: row.get(3)?,
-                    name: row.get(4)?,
-                    configuration: row.get(5)?,
-                    file_path: row.get(6)?,
-                    line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
-                    confidence: row.get(8)?,
-                    created_at: row.get(9)?,
-                })
-            })?.collect();
+            let result: Result<Vec<_>, _> = stmt.query_map(params![provider, workspace_id], row_to_service)?.collect();
             result?
         };
 
         Ok(services)
     }
 
-    pub fn get_by_service_type(&self, service_type: &str, repository_id: Option<&str>) -> Result<Vec<StoredService>> {
+    /// Limited to `workspace_id`, and to one of its repositories when one is given
+    pub fn get_by_service_type(&self, service_type: &str, workspace_id: &str, repository_id: Option<&str>) -> Result<Vec<StoredService>> {
         let conn = self.db.get_connection();
         let conn = conn.lock().unwrap();
         
         let services: Vec<StoredService> = if let Some(repo_id) = repository_id {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at
-                 FROM services WHERE service_type = ?1 AND repository_id = ?2 ORDER BY provider"
+                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at, evidence
+                 FROM services WHERE service_type = ?1 AND repository_id = ?2 AND workspace_id = ?3 ORDER BY provider"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![service_type, repo_id], |row| {
-                Ok(StoredService {
-                    id: row.get(0)?,
-                    repository_id: row.get(1)?,
-                    provider: row.get(2)?,
-                    service_type: row.get(3)?,
-                    name: row.get(4)?,
-                    configuration: row.get(5)?,
-                    file_path: row.get(6)?,
-                    line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
-                    confidence: row.get(8)?,
-                    created_at: row.get(9)?,
-                })
-            })?.collect();
+            let result: Result<Vec<_>, _> = stmt.query_map(params![service_type, repo_id, workspace_id], row_to_service)?.collect();
             result?
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at
-                 FROM services WHERE service_type = ?1 ORDER BY provider"
+                "SELECT id, repository_id, provider, service_type, name, configuration, file_path, line_number, confidence, created_at, evidence
+                 FROM services WHERE service_type = ?1 AND workspace_id = ?2 ORDER BY provider"
             )?;
-            let result: Result<Vec<_>, _> = stmt.query_map(params![service_type], |row| {
-                Ok(StoredService {
-                    id: row.get(0)?,
-                    repository_id: row.get(1)?,
-                    provider: row.get(2)?,
-                    service_type: row.get(3)?,
-                    name: row.get(4)?,
-                    configuration: row.get(5)?,
-                    file_path: row.get(6)?,
-                    line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
-                    confidence: row.get(8)?,
-                    created_at: row.get(9)?,
-                })
-            })?.collect();
+            let result: Result<Vec<_>, _> = stmt.query_map(params![service_type, workspace_id], row_to_service)?.collect();
             result?
         };
 
         Ok(services)
     }
 
-    fn provider_to_string(&self, provider: &ServiceProvider) -> String {
+    pub fn provider_to_string(&self, provider: &ServiceProvider) -> String {
         match provider {
             ServiceProvider::Aws => "aws",
             ServiceProvider::Azure => "azure",
@@ -240,6 +182,7 @@ impl ServiceRepository {
             ServiceProvider::MistralAI => "mistral_ai",
             ServiceProvider::Perplexity => "perplexity",
             ServiceProvider::WebMethods => "webmethods",
+            ServiceProvider::Salesforce => "salesforce",
             ServiceProvider::Unknown => "unknown",
         }.to_string()
     }
@@ -260,3 +203,18 @@ impl ServiceRepository {
     }
 }
 
+fn row_to_service(row: &rusqlite::Row) -> rusqlite::Result<StoredService> {
+    Ok(StoredService {
+        id: row.get(0)?,
+        repository_id: row.get(1)?,
+        provider: row.get(2)?,
+        service_type: row.get(3)?,
+        name: row.get(4)?,
+        configuration: row.get(5)?,
+        file_path: row.get(6)?,
+        line_number: row.get::<_, Option<i32>>(7)?.map(|n| n as usize),
+        confidence: row.get(8)?,
+        created_at: row.get(9)?,
+        evidence: serde_json::from_str(&row.get::<_, String>(10)?).unwrap_or_default(),
+    })
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::storage::Database;
+
+/// Repositories analyzed together as one system (the services, libraries and infrastructure
+/// repositories of a product), so their graphs can be joined by cross-repository edges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct System {
+    pub id: String,
+    pub workspace_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub repository_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct SystemRepository {
+    db: Database,
+}
+
+impl SystemRepository {
+    pub fn new(db: Database) -> Self {
+        SystemRepository { db }
+    }
+
+    pub fn create(&self, workspace_id: &str, name: &str, description: Option<&str>, repository_ids: &[String]) -> Result<System> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO systems (id, workspace_id, name, description, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, workspace_id, name, description, now.to_rfc3339()],
+        )?;
+        for repository_id in repository_ids {
+            conn.execute(
+                "INSERT OR IGNORE INTO system_repositories (system_id, repository_id) VALUES (?1, ?2)",
+                params![id, repository_id],
+            )?;
+        }
+
+        Ok(System {
+            id,
+            workspace_id: workspace_id.to_string(),
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            repository_ids: repository_ids.to_vec(),
+            created_at: now,
+        })
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Result<Option<System>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let system = conn.query_row(
+            "SELECT id, workspace_id, name, description, created_at FROM systems WHERE id = ?1",
+            params![id],
+            row_to_system,
+        ).optional()?;
+        match system {
+            Some(mut system) => {
+                system.repository_ids = member_ids(&conn, &system.id)?;
+                Ok(Some(system))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn list_by_workspace(&self, workspace_id: &str) -> Result<Vec<System>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, workspace_id, name, description, created_at FROM systems WHERE workspace_id = ?1 ORDER BY name"
+        )?;
+        let mut systems = stmt.query_map(params![workspace_id], row_to_system)?
+            .collect::<Result<Vec<_>, _>>()?;
+        for system in &mut systems {
+            system.repository_ids = member_ids(&conn, &system.id)?;
+        }
+        Ok(systems)
+    }
+
+    pub fn add_repository(&self, system_id: &str, repository_id: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR IGNORE INTO system_repositories (system_id, repository_id) VALUES (?1, ?2)",
+            params![system_id, repository_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_repository(&self, system_id: &str, repository_id: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "DELETE FROM system_repositories WHERE system_id = ?1 AND repository_id = ?2",
+            params![system_id, repository_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute("DELETE FROM system_repositories WHERE system_id = ?1", params![id])?;
+        conn.execute("DELETE FROM systems WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
+
+fn member_ids(conn: &rusqlite::Connection, system_id: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT sr.repository_id FROM system_repositories sr JOIN repositories r ON r.id = sr.repository_id
+         WHERE sr.system_id = ?1 ORDER BY r.name"
+    )?;
+    let ids = stmt.query_map(params![system_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(ids)
+}
+
+fn row_to_system(row: &rusqlite::Row) -> rusqlite::Result<System> {
+    Ok(System {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        name: row.get(2)?,
+        description: row.get(3)?,
+        repository_ids: Vec::new(),
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(4)?)
+            .map_err(|_| rusqlite::Error::InvalidColumnType(4, "created_at".to_string(), rusqlite::types::Type::Text))?
+            .with_timezone(&Utc),
+    })
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::{params, OptionalExtension};
+use crate::analysis::{TechDebtScore, DebtSubscore};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredTechDebtScore {
+    pub id: String,
+    pub repository_id: String,
+    pub score: f64,
+    pub grade: String,
+    pub subscores: BTreeMap<String, DebtSubscore>,
+    pub created_at: String,
+}
+
+#[derive(Clone)]
+pub struct TechDebtRepository {
+    db: Database,
+}
+
+impl TechDebtRepository {
+    pub fn new(db: Database) -> Self {
+        TechDebtRepository { db }
+    }
+
+    /// Scores are kept for every analysis so they can be charted over time
+    pub fn store_score(&self, repository_id: &str, score: &TechDebtScore) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO tech_debt_scores (id, repository_id, score, grade, subscores, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Uuid::new_v4().to_string(),
+                repository_id,
+                score.score,
+                score.grade,
+                serde_json::to_string(&score.subscores)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_latest(&self, repository_id: &str) -> Result<Option<StoredTechDebtScore>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let score = conn.query_row(
+            "SELECT id, repository_id, score, grade, subscores, created_at
+             FROM tech_debt_scores WHERE repository_id = ?1 ORDER BY created_at DESC LIMIT 1",
+            params![repository_id],
+            Self::row_to_score,
+        ).optional()?;
+
+        Ok(score)
+    }
+
+    /// Oldest first, limited to the most recent `limit` analyses
+    pub fn get_history(&self, repository_id: &str, limit: usize) -> Result<Vec<StoredTechDebtScore>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, score, grade, subscores, created_at
+             FROM tech_debt_scores WHERE repository_id = ?1 ORDER BY created_at DESC LIMIT ?2"
+        )?;
+
+        let mut scores = stmt.query_map(params![repository_id, limit as i64], Self::row_to_score)?
+            .collect::<Result<Vec<_>, _>>()?;
+        scores.reverse();
+
+        Ok(scores)
+    }
+
+    fn row_to_score(row: &rusqlite::Row) -> rusqlite::Result<StoredTechDebtScore> {
+        let subscores: String = row.get(4)?;
+        Ok(StoredTechDebtScore {
+            id: row.get(0)?,
+            repository_id: row.get(1)?,
+            score: row.get(2)?,
+            grade: row.get(3)?,
+            subscores: serde_json::from_str(&subscores).unwrap_or_default(),
+            created_at: row.get(5)?,
+        })
+    }
+}
@@ -2,7 +2,8 @@ use anyhow::Result;
 use chrono::Utc;
 use crate::storage::Database;
 use rusqlite::params;
-use crate::analysis::{DetectedTest, TestFramework};
+use uuid::Uuid;
+use crate::analysis::{DetectedTest, TestFramework, TestCodeLink, TestLinkStrategy};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StoredTest {
@@ -25,6 +26,17 @@ pub struct StoredTest {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredTestCodeLink {
+    pub id: String,
+    pub repository_id: String,
+    pub test_id: String,
+    pub code_element_id: String,
+    pub strategy: String, // "call" or "naming"
+    pub confidence: f64,
+    pub created_at: String,
+}
+
 #[derive(Clone)]
 pub struct TestRepository {
     db: Database,
@@ -178,6 +190,117 @@ impl TestRepository {
         Ok(tests)
     }
 
+    pub fn store_code_links(&self, repository_id: &str, links: &[TestCodeLink]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing links for this repository
+        conn.execute(
+            "DELETE FROM test_code_links WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for link in links {
+            let strategy = match link.strategy {
+                TestLinkStrategy::Call => "call",
+                TestLinkStrategy::Naming => "naming",
+            };
+            conn.execute(
+                "INSERT INTO test_code_links (id, repository_id, test_id, code_element_id, strategy, confidence, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    link.test_id,
+                    link.code_element_id,
+                    strategy,
+                    link.confidence,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_code_links(&self, repository_id: &str) -> Result<Vec<StoredTestCodeLink>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, test_id, code_element_id, strategy, confidence, created_at
+             FROM test_code_links WHERE repository_id = ?1 ORDER BY confidence DESC"
+        )?;
+
+        let links = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredTestCodeLink {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                test_id: row.get(2)?,
+                code_element_id: row.get(3)?,
+                strategy: row.get(4)?,
+                confidence: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links)
+    }
+
+    /// Tests linked to a code element, most confident first
+    pub fn get_tests_for_code_element(&self, repository_id: &str, code_element_id: &str) -> Result<Vec<(StoredTest, StoredTestCodeLink)>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.repository_id, t.name, t.test_framework, t.file_path, t.line_number,
+                    t.language, t.test_type, t.suite_name, t.assertions, t.setup_methods,
+                    t.teardown_methods, t.signature, t.doc_comment, t.parameters, t.return_type, t.created_at,
+                    l.id, l.strategy, l.confidence, l.created_at
+             FROM test_code_links l
+             JOIN tests t ON t.id = l.test_id
+             WHERE l.repository_id = ?1 AND l.code_element_id = ?2
+             ORDER BY l.confidence DESC, t.file_path, t.line_number"
+        )?;
+
+        let tests = stmt.query_map(params![repository_id, code_element_id], |row| {
+            let test = StoredTest {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                name: row.get(2)?,
+                test_framework: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, i32>(5)? as usize,
+                language: row.get(6)?,
+                test_type: row.get(7)?,
+                suite_name: row.get(8)?,
+                assertions: row.get(9)?,
+                setup_methods: row.get(10)?,
+                teardown_methods: row.get(11)?,
+                signature: row.get(12)?,
+                doc_comment: row.get(13)?,
+                parameters: row.get(14)?,
+                return_type: row.get(15)?,
+                created_at: row.get(16)?,
+            };
+            let link = StoredTestCodeLink {
+                id: row.get(17)?,
+                repository_id: test.repository_id.clone(),
+                test_id: test.id.clone(),
+                code_element_id: code_element_id.to_string(),
+                strategy: row.get(18)?,
+                confidence: row.get(19)?,
+                created_at: row.get(20)?,
+            };
+            Ok((test, link))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tests)
+    }
+
     fn framework_to_string(&self, framework: &TestFramework) -> String {
         match framework {
             TestFramework::Jest => "jest",
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use crate::storage::Database;
+use rusqlite::params;
+use crate::analysis::TodoMarker;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StoredTodo {
+    pub id: String,
+    pub repository_id: String,
+    pub kind: String,
+    pub text: String,
+    pub file_path: String,
+    pub line_number: usize,
+    pub language: Option<String>,
+    pub author: Option<String>,
+    pub committed_at: Option<String>,
+    pub age_days: Option<i64>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TodoSummary {
+    pub total: usize,
+    pub by_kind: BTreeMap<String, usize>,
+    pub by_author: BTreeMap<String, usize>,
+    pub by_age: BTreeMap<String, usize>,
+    pub oldest: Vec<StoredTodo>,
+}
+
+#[derive(Clone)]
+pub struct TodoRepository {
+    db: Database,
+}
+
+impl TodoRepository {
+    pub fn new(db: Database) -> Self {
+        TodoRepository { db }
+    }
+
+    pub fn store_todos(&self, repository_id: &str, todos: &[TodoMarker]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        // Delete existing markers for this repository
+        conn.execute(
+            "DELETE FROM todos WHERE repository_id = ?1",
+            params![repository_id],
+        )?;
+
+        let now = Utc::now().to_rfc3339();
+        for todo in todos {
+            conn.execute(
+                "INSERT INTO todos (id, repository_id, kind, text, file_path, line_number, language, author, committed_at, age_days, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    repository_id,
+                    todo.kind.as_str(),
+                    todo.text,
+                    todo.file_path,
+                    todo.line_number as i64,
+                    todo.language,
+                    todo.author,
+                    todo.committed_at,
+                    todo.age_days,
+                    now
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<StoredTodo>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, text, file_path, line_number, language, author, committed_at, age_days, created_at
+             FROM todos WHERE repository_id = ?1 ORDER BY file_path, line_number"
+        )?;
+
+        let todos = stmt.query_map(params![repository_id], |row| {
+            Ok(StoredTodo {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                kind: row.get(2)?,
+                text: row.get(3)?,
+                file_path: row.get(4)?,
+                line_number: row.get::<_, i64>(5)? as usize,
+                language: row.get(6)?,
+                author: row.get(7)?,
+                committed_at: row.get(8)?,
+                age_days: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(todos)
+    }
+
+    /// Counts by kind, author and age bucket plus the ten oldest markers
+    pub fn get_summary(&self, repository_id: &str) -> Result<TodoSummary> {
+        let todos = self.get_by_repository(repository_id)?;
+
+        let mut by_kind = BTreeMap::new();
+        let mut by_author = BTreeMap::new();
+        let mut by_age = BTreeMap::new();
+        for todo in &todos {
+            *by_kind.entry(todo.kind.clone()).or_default() += 1;
+            *by_author.entry(todo.author.clone().unwrap_or_else(|| "unknown".to_string())).or_default() += 1;
+            let bucket = match todo.age_days {
+                Some(d) if d < 30 => "under_30_days",
+                Some(d) if d < 180 => "30_to_180_days",
+                Some(d) if d < 365 => "180_to_365_days",
+                Some(_) => "over_1_year",
+                None => "unknown",
+            };
+            *by_age.entry(bucket.to_string()).or_default() += 1;
+        }
+
+        let mut oldest: Vec<StoredTodo> = todos.iter().filter(|t| t.age_days.is_some()).cloned().collect();
+        oldest.sort_by_key(|t| std::cmp::Reverse(t.age_days));
+        oldest.truncate(10);
+
+        Ok(TodoSummary {
+            total: todos.len(),
+            by_kind,
+            by_author,
+            by_age,
+            oldest,
+        })
+    }
+}
@@ -186,6 +186,8 @@ impl ToolRepository {
             ToolType::ShellScript => "ShellScript".to_string(),
             ToolType::DevEnvironment => "DevEnvironment".to_string(),
             ToolType::Sdk => "Sdk".to_string(),
+            ToolType::BuildCache => "BuildCache".to_string(),
+            ToolType::Toolchain => "Toolchain".to_string(),
             ToolType::Other => "Other".to_string(),
         }
     }
@@ -201,6 +203,8 @@ impl ToolRepository {
             ToolCategory::GoBuild => "GoBuild".to_string(),
             ToolCategory::Maven => "Maven".to_string(),
             ToolCategory::Gradle => "Gradle".to_string(),
+            ToolCategory::Bazel => "Bazel".to_string(),
+            ToolCategory::Buck => "Buck".to_string(),
             ToolCategory::Jest => "Jest".to_string(),
             ToolCategory::Mocha => "Mocha".to_string(),
             ToolCategory::Pytest => "Pytest".to_string(),
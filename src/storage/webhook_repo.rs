@@ -0,0 +1,198 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize, Serializer};
+use uuid::Uuid;
+use crate::storage::Database;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    /// Slack incoming webhook
+    Slack,
+    /// Microsoft Teams incoming webhook (connector card)
+    Teams,
+    /// Any URL, sent the summary as JSON
+    Generic,
+}
+
+impl WebhookKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookKind::Slack => "slack",
+            WebhookKind::Teams => "teams",
+            WebhookKind::Generic => "generic",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<WebhookKind> {
+        match kind {
+            "slack" => Some(WebhookKind::Slack),
+            "teams" => Some(WebhookKind::Teams),
+            "generic" => Some(WebhookKind::Generic),
+            _ => None,
+        }
+    }
+}
+
+/// Where a repository's analysis notifications are posted
+#[derive(Debug, Clone, Serialize)]
+pub struct Webhook {
+    pub id: String,
+    pub repository_id: String,
+    pub kind: WebhookKind,
+    /// A URL or a secret reference (`env:SLACK_WEBHOOK_URL`); webhook URLs are credentials, so
+    /// only the scheme and host are returned
+    #[serde(serialize_with = "masked_url")]
+    pub url: String,
+    /// Signs generic payloads (`X-Wavelength-Signature`); never returned
+    #[serde(skip_serializing)]
+    pub secret: Option<String>,
+    pub on_completed: bool,
+    pub on_failed: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_delivery_at: Option<DateTime<Utc>>,
+    /// Why the last delivery failed; cleared when one succeeds
+    pub last_error: Option<String>,
+}
+
+fn masked_url<S: Serializer>(url: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    let masked = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host = rest.split(['/', '?']).next().unwrap_or("");
+            let host = host.rsplit('@').next().unwrap_or(host);
+            format!("{}://{}/…", scheme, host)
+        }
+        // A secret reference names where the URL is, not the URL
+        None => url.to_string(),
+    };
+    serializer.serialize_str(&masked)
+}
+
+#[derive(Clone)]
+pub struct WebhookRepository {
+    db: Database,
+}
+
+impl WebhookRepository {
+    pub fn new(db: Database) -> Self {
+        WebhookRepository { db }
+    }
+
+    pub fn create(&self, repository_id: &str, kind: WebhookKind, url: &str, secret: Option<&str>, on_completed: bool, on_failed: bool) -> Result<Webhook> {
+        let webhook = Webhook {
+            id: Uuid::new_v4().to_string(),
+            repository_id: repository_id.to_string(),
+            kind,
+            url: url.to_string(),
+            secret: secret.map(|s| s.to_string()),
+            on_completed,
+            on_failed,
+            created_at: Utc::now(),
+            last_delivery_at: None,
+            last_error: None,
+        };
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO webhooks (id, repository_id, kind, url, secret, on_completed, on_failed, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                webhook.id,
+                webhook.repository_id,
+                webhook.kind.as_str(),
+                webhook.url,
+                webhook.secret,
+                webhook.on_completed,
+                webhook.on_failed,
+                webhook.created_at.to_rfc3339()
+            ],
+        )?;
+
+        Ok(webhook)
+    }
+
+    pub fn get_by_repository(&self, repository_id: &str) -> Result<Vec<Webhook>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, repository_id, kind, url, secret, on_completed, on_failed, created_at, last_delivery_at, last_error
+             FROM webhooks WHERE repository_id = ?1 ORDER BY created_at"
+        )?;
+        let webhooks = stmt.query_map(params![repository_id], |row| {
+            let kind: String = row.get(2)?;
+            let timestamp = |column: usize, text: Option<String>| -> rusqlite::Result<Option<DateTime<Utc>>> {
+                text.map(|t| DateTime::parse_from_rfc3339(&t)
+                    .map(|t| t.with_timezone(&Utc))
+                    .map_err(|_| rusqlite::Error::InvalidColumnType(column, "timestamp".to_string(), rusqlite::types::Type::Text)))
+                    .transpose()
+            };
+            Ok(Webhook {
+                id: row.get(0)?,
+                repository_id: row.get(1)?,
+                kind: WebhookKind::parse(&kind)
+                    .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "kind".to_string(), rusqlite::types::Type::Text))?,
+                url: row.get(3)?,
+                secret: row.get(4)?,
+                on_completed: row.get(5)?,
+                on_failed: row.get(6)?,
+                created_at: timestamp(7, row.get(7)?)?.unwrap_or_else(Utc::now),
+                last_delivery_at: timestamp(8, row.get(8)?)?,
+                last_error: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(webhooks)
+    }
+
+    /// Whether the repository had the webhook
+    pub fn delete(&self, repository_id: &str, id: &str) -> Result<bool> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let deleted = conn.execute(
+            "DELETE FROM webhooks WHERE repository_id = ?1 AND id = ?2",
+            params![repository_id, id],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    pub fn record_delivery(&self, id: &str, error: Option<&str>) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE webhooks SET last_delivery_at = ?2, last_error = ?3 WHERE id = ?1",
+            params![id, Utc::now().to_rfc3339(), error],
+        )?;
+        Ok(())
+    }
+
+    /// Fingerprints of the vulnerabilities the last notification saw, so the next one can tell
+    /// which are new; `None` before the first
+    pub fn vulnerability_baseline(&self, repository_id: &str) -> Result<Option<Vec<String>>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let baseline: Option<String> = conn.query_row(
+            "SELECT fingerprints FROM webhook_baselines WHERE repository_id = ?1",
+            params![repository_id],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(baseline.map(|b| serde_json::from_str(&b)).transpose()?)
+    }
+
+    pub fn set_vulnerability_baseline(&self, repository_id: &str, fingerprints: &[String]) -> Result<()> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO webhook_baselines (repository_id, fingerprints, updated_at) VALUES (?1, ?2, ?3)",
+            params![repository_id, serde_json::to_string(fingerprints)?, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::storage::Database;
+
+/// Repositories created without a workspace, and requests that name none, belong here
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// A team's isolated set of repositories. Everything derived from a repository is reached through
+/// it, so scoping repositories scopes their analysis results too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// Requests must present the workspace's token when it has one
+    pub has_token: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct WorkspaceRepository {
+    db: Database,
+}
+
+impl WorkspaceRepository {
+    pub fn new(db: Database) -> Self {
+        WorkspaceRepository { db }
+    }
+
+    /// Only the token's hash is stored
+    pub fn create(&self, name: &str, token: Option<&str>) -> Result<Workspace> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO workspaces (id, name, token_hash, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![id, name, token.map(hash_token), now.to_rfc3339()],
+        )?;
+
+        Ok(Workspace {
+            id,
+            name: name.to_string(),
+            has_token: token.is_some(),
+            created_at: now,
+        })
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Result<Option<Workspace>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT id, name, token_hash IS NOT NULL, created_at FROM workspaces WHERE id = ?1",
+            params![id],
+            row_to_workspace,
+        ).optional()?)
+    }
+
+    pub fn find_by_token(&self, token: &str) -> Result<Option<Workspace>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        Ok(conn.query_row(
+            "SELECT id, name, token_hash IS NOT NULL, created_at FROM workspaces WHERE token_hash = ?1",
+            params![hash_token(token)],
+            row_to_workspace,
+        ).optional()?)
+    }
+
+    pub fn list_all(&self) -> Result<Vec<Workspace>> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, token_hash IS NOT NULL, created_at FROM workspaces ORDER BY created_at"
+        )?;
+        let workspaces = stmt.query_map([], row_to_workspace)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(workspaces)
+    }
+}
+
+fn row_to_workspace(row: &rusqlite::Row) -> rusqlite::Result<Workspace> {
+    Ok(Workspace {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        has_token: row.get(2)?,
+        created_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(3)?)
+            .map_err(|_| rusqlite::Error::InvalidColumnType(3, "created_at".to_string(), rusqlite::types::Type::Text))?
+            .with_timezone(&Utc),
+    })
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
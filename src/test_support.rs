@@ -0,0 +1,10 @@
+//! Fixture helpers shared by the detector unit tests.
+
+use std::path::Path;
+
+/// Writes `content` to `path` under `root`, creating the parent directories
+pub fn write(root: &Path, path: &str, content: impl AsRef<[u8]>) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
@@ -1,4 +1,39 @@
 // API Client Library
+
+// Preset names, or link templates whose scheme is an editor's or http(s) and that hold nothing
+// that could leave an href attribute; the server applies the same rules
+const EDITOR_SCHEMES = ['vscode', 'vscode-insiders', 'cursor', 'windsurf', 'zed', 'idea', 'webstorm', 'pycharm',
+    'goland', 'phpstorm', 'rubymine', 'clion', 'rider', 'subl', 'txmt', 'atom', 'mvim', 'http', 'https'];
+
+function isEditorSetting(editor) {
+    if (/^[A-Za-z-]+$/.test(editor)) {
+        return true;
+    }
+    const scheme = editor.split(':')[0].toLowerCase();
+    return editor.includes(':') && EDITOR_SCHEMES.includes(scheme)
+        && (editor.includes('{path}') || editor.includes('{relative_path}'))
+        && !/[\s"'<>`\\]/.test(editor);
+}
+
+// The user's own editor for "open in editor" links: ?editor= in the page URL, remembered afterwards
+function preferredEditor() {
+    const requested = new URLSearchParams(window.location.search).get('editor');
+    if (requested && isEditorSetting(requested)) {
+        localStorage.setItem('editor', requested);
+    }
+    const editor = localStorage.getItem('editor');
+    if (editor && !isEditorSetting(editor)) {
+        localStorage.removeItem('editor');
+        return null;
+    }
+    return editor;
+}
+
+function editorQuery(prefix = '?') {
+    const editor = preferredEditor();
+    return editor ? `${prefix}editor=${encodeURIComponent(editor)}` : '';
+}
+
 class WavelengthAPI {
     constructor(baseURL = '/api/v1') {
         this.baseURL = baseURL;
@@ -55,7 +90,7 @@ class WavelengthAPI {
     async analyzeRepository(id) {
         return this.request(`/repositories/${id}/analyze`, {
             method: 'POST',
-            body: JSON.stringify({ repository_id: id }),
+            body: JSON.stringify({}),
         });
     }
 
@@ -127,11 +162,11 @@ class WavelengthAPI {
     }
 
     async getEntityDetails(repoId, entityType, entityId) {
-        return this.request(`/repositories/${repoId}/entities/${entityType}/${entityId}`);
+        return this.request(`/repositories/${repoId}/entities/${entityType}/${entityId}${editorQuery()}`);
     }
 
     async getVersion(force = false) {
-        const url = force ? '/version?force=true' : '/version';
+        const url = force ? `/version?force=true${editorQuery('&')}` : `/version${editorQuery()}`;
         return this.request(url);
     }
 
@@ -149,7 +184,7 @@ class WavelengthAPI {
 
     async getReport(repoId) {
         // Report endpoint returns HTML, not JSON
-        const url = `${this.baseURL}/repositories/${repoId}/report`;
+        const url = `${this.baseURL}/repositories/${repoId}/report${editorQuery()}`;
         return fetch(url).then(response => {
             if (!response.ok) {
                 return response.json().then(err => {
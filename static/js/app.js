@@ -1182,6 +1182,20 @@ function renderEnhancedGraph(graphData, container, repoId = null) {
         'test_uses_framework': 'uses',
         'TestTestsCode': 'tests',  // Show "tests" relationship
         'test_tests_code': 'tests',
+        'Imports': 'imports',
+        'imports': 'imports',
+        'Calls': 'calls',
+        'calls': 'calls',
+        'DependsOnRepository': 'depends on',
+        'depends_on_repository': 'depends on',
+        'SharesDependency': 'shares deps',
+        'shares_dependency': 'shares deps',
+        'CallsEndpoint': 'calls',
+        'calls_endpoint': 'calls',
+        'SharesResource': 'shares resource',
+        'shares_resource': 'shares resource',
+        'ContainedIn': '',  // Hide "contained in" - nesting is obvious
+        'contained_in': '',
         'RelatedTo': '',  // Hide generic relationships
         'related_to': '',
         'HasChild': '',  // Hide parent-child relationships
@@ -2219,6 +2233,12 @@ function escapeHtml(text) {
     return div.innerHTML;
 }
 
+// escapeHtml leaves quotes alone, which would end an attribute value early
+function escapeAttribute(text) {
+    return String(text).replace(/&/g, '&amp;').replace(/"/g, '&quot;').replace(/'/g, '&#39;')
+        .replace(/</g, '&lt;').replace(/>/g, '&gt;');
+}
+
 function showError(message) {
     // Simple error display - could be enhanced with a toast notification
     alert(message);
@@ -2824,29 +2844,16 @@ function createFileLink(filePath, lineNumber = null, repoData = null) {
         return '';
     }
     
-    // Create editor link using configurable protocol (default: vscode)
-    // Supported protocols: vscode, vscode-insiders, cursor, code, sublime, atom, etc.
-    const editorPath = normalizedPath.startsWith('/') || normalizedPath.match(/^[A-Za-z]:/)
-        ? normalizedPath
-        : `/${normalizedPath}`;
-    
-    // Map common editor protocols to their URL schemes
-    const editorProtocolMap = {
-        'vscode': 'vscode://file',
-        'vscode-insiders': 'vscode-insiders://file',
-        'cursor': 'cursor://file',
-        'code': 'code://file',
-        'sublime': 'subl://file',
-        'atom': 'atom://file',
-        'webstorm': 'webstorm://open?file=',
-        'idea': 'idea://open?file=',
-    };
-    
-    const editorScheme = editorProtocolMap[editorProtocol] || `${editorProtocol}://file`;
-    const editorLink = `${editorScheme}${editorPath}${lineNumber ? `:${lineNumber}` : ''}`;
-    const editorName = editorProtocol === 'vscode' ? 'VS Code' : 
-                      editorProtocol === 'cursor' ? 'Cursor' :
-                      editorProtocol === 'vscode-insiders' ? 'VS Code Insiders' :
+    // Render the link template the server resolved from EDITOR_PROTOCOL (or this user's ?editor=)
+    const editorPath = normalizedPath.startsWith('/') ? normalizedPath : `/${normalizedPath}`;
+    const encodePath = (path) => path.replace(/%/g, '%25').replace(/ /g, '%20').replace(/#/g, '%23')
+        .replace(/\?/g, '%3F').replace(/&/g, '%26');
+    const relativePath = filePath.replace(/\\/g, '/').replace(/^\.\//, '');
+    const editorLink = editorLinkTemplate
+        .split('{path}').join(encodePath(editorPath))
+        .split('{relative_path}').join(encodePath(relativePath))
+        .split('{line}').join(String(lineNumber || 1));
+    const editorName = editorNames[editorProtocol] ||
                       editorProtocol.charAt(0).toUpperCase() + editorProtocol.slice(1);
     
     // Create file:// link for macOS Finder (needs triple slash for absolute paths)
@@ -2857,15 +2864,15 @@ function createFileLink(filePath, lineNumber = null, repoData = null) {
         : `file://${normalizedPath}`; // Unix/macOS: file:///path (already has leading slash, becomes file:///path)
     
     return `
-        <a href="${editorLink}" 
+        <a href="${escapeAttribute(editorLink)}" 
            onclick="event.stopPropagation(); return true;"
-           title="Open in ${editorName}${lineNumber ? ` (line ${lineNumber})` : ''}"
+           title="Open in ${escapeAttribute(editorName)}${lineNumber ? ` (line ${escapeAttribute(lineNumber)})` : ''}"
            style="margin-left: 0.5rem; color: var(--primary-color); text-decoration: none; font-size: 0.875rem; white-space: nowrap;">
             🔗 Open in Editor
         </a>
         <a href="#" 
            onclick="event.stopPropagation(); 
-                    const path = '${normalizedPath}';
+                    const path = ${escapeAttribute(JSON.stringify(normalizedPath))};
                     if (navigator.clipboard && navigator.clipboard.writeText) {
                         navigator.clipboard.writeText(path).then(() => {
                             alert('Path copied to clipboard!\\n\\n' + path + '\\n\\nOn macOS: Press Cmd+Shift+G in Finder and paste the path.\\nOn Windows: Paste the path in File Explorer address bar.');
@@ -4498,6 +4505,14 @@ function renderServiceDetails(entity, details) {
         <strong>File:</strong> <code>${escapeHtml(entity.file_path)}</code>${entity.line_number ? `:${entity.line_number}` : ''}${createFileLink(entity.file_path, entity.line_number, currentRepoData)}
         <button class="btn-copy-inline" onclick="copyToClipboard('${escapeHtml(entity.file_path)}${entity.line_number ? `:${entity.line_number}` : ''}', 'File Path')" title="Copy file path">📋</button>
     </div>`;
+    if (entity.evidence && entity.evidence.length > 0) {
+        html += '<div class="detail-item"><strong>Evidence:</strong><ul>';
+        entity.evidence.forEach(ev => {
+            const location = `${ev.file_path}${ev.line_number ? `:${ev.line_number}` : ''}`;
+            html += `<li>${escapeHtml(ev.kind.replace(/_/g, ' '))} in <code>${escapeHtml(location)}</code>${createFileLink(ev.file_path, ev.line_number, currentRepoData)} (${(ev.confidence * 100).toFixed(0)}%)</li>`;
+        });
+        html += '</ul></div>';
+    }
     if (entity.configuration) {
         try {
             const config = typeof entity.configuration === 'string' ? JSON.parse(entity.configuration) : entity.configuration;
@@ -4587,7 +4602,8 @@ function renderRelationships(entityType, details) {
         html += '<div class="detail-section"><h3>Related Services</h3><div class="related-items">';
         details.related_services.forEach(svc => {
             const confidence = svc.confidence ? ` (${(svc.confidence * 100).toFixed(0)}% confidence)` : '';
-            const evidence = svc.evidence ? `<br><small class="text-muted">${escapeHtml(svc.evidence)}</small>` : '';
+            const kinds = [...new Set((svc.evidence || []).map(ev => ev.kind.replace(/_/g, ' ')))];
+            const evidence = kinds.length > 0 ? `<br><small class="text-muted">${escapeHtml(kinds.join(', '))}</small>` : '';
             html += `<div class="related-item clickable" onclick="showEntityDetail('${currentRepoId}', 'service', '${svc.id}')">
                 <div><strong>${escapeHtml(svc.name)}</strong></div>
                 <div>${escapeHtml(svc.provider)}${confidence}</div>
@@ -4681,6 +4697,7 @@ function renderRelationships(entityType, details) {
             html += `<div class="related-item vulnerability-item severity-${vuln.severity.toLowerCase()}">
                 <strong>${escapeHtml(vuln.vulnerability_type)}</strong>
                 <p>${escapeHtml(vuln.description)}</p>
+                ${vuln.affected_range ? `<p><strong>Affected:</strong> <code>${escapeHtml(vuln.affected_range)}</code>${vuln.fixed_version ? ` · <strong>Fixed in:</strong> <code>${escapeHtml(vuln.fixed_version)}</code>` : ''}</p>` : ''}
                 <p class="vulnerability-recommendation">💡 ${escapeHtml(vuln.recommendation)}</p>
             </div>`;
         });
@@ -4969,8 +4986,22 @@ async function showToolDetail(repoId, toolId) {
     }
 }
 
-// Global editor protocol (default: vscode)
+// Global editor protocol and its link template (default: vscode)
 let editorProtocol = 'vscode';
+let editorLinkTemplate = 'vscode://file{path}:{line}';
+const editorNames = {
+    'vscode': 'VS Code',
+    'vscode-insiders': 'VS Code Insiders',
+    'idea': 'IntelliJ IDEA',
+    'webstorm': 'WebStorm',
+    'pycharm': 'PyCharm',
+    'goland': 'GoLand',
+    'phpstorm': 'PhpStorm',
+    'rubymine': 'RubyMine',
+    'clion': 'CLion',
+    'textmate': 'TextMate',
+    'macvim': 'MacVim',
+};
 
 async function loadVersion(force = false) {
     try {
@@ -4985,6 +5016,13 @@ async function loadVersion(force = false) {
             editorProtocol = response.editor_protocol;
             console.log('Editor protocol:', editorProtocol);
         }
+        if (response.editor_link_template) {
+            editorLinkTemplate = response.editor_link_template;
+            // Custom templates have no name of their own; use their scheme
+            if (editorProtocol.includes('{')) {
+                editorProtocol = editorLinkTemplate.split(':')[0];
+            }
+        }
         
         // Load and display plugins
         const pluginsElement = document.getElementById('app-plugins');